@@ -0,0 +1,22 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Auto-compaction summarized older turns and waits for the user to accept, edit, or reject
+ * the summary before it replaces them (`confirm_auto_compaction`). Answered with
+ * `Op::CompactionReview` using `id`.
+ */
+export type CompactionReviewRequestEvent = { 
+/**
+ * Identifier to echo back in `Op::CompactionReview`.
+ */
+id: string, 
+/**
+ * Turn ID that triggered the compaction.
+ */
+turn_id: string, 
+/**
+ * The generated summary that would replace the compacted turns.
+ */
+summary: string, };
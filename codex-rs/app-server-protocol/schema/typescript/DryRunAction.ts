@@ -0,0 +1,9 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { FileChange } from "./FileChange";
+
+/**
+ * What a simulated tool call would have done.
+ */
+export type DryRunAction = { "type": "exec", command: Array<string>, cwd: string, } | { "type": "patch", changes: { [key in string]?: FileChange }, };
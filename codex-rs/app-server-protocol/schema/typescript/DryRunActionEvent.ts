@@ -0,0 +1,14 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { DryRunAction } from "./DryRunAction";
+
+export type DryRunActionEvent = { 
+/**
+ * Identifier of the tool call that was simulated.
+ */
+call_id: string, 
+/**
+ * Turn ID that this call belongs to.
+ */
+turn_id: string, action: DryRunAction, };
@@ -0,0 +1,8 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Whether a `[[patch_checks]]` entry rewrites files or only reports on them.
+ */
+export type PatchCheckKind = "format" | "lint";
@@ -0,0 +1,14 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { PatchCheckKind } from "./PatchCheckKind";
+
+export type PatchCheckResult = { name: string, kind: PatchCheckKind, exit_code: number, 
+/**
+ * Files a formatter rewrote. Always empty for linters.
+ */
+formatted_files: Array<string>, 
+/**
+ * Combined output, kept only when the check failed.
+ */
+output: string, };
@@ -0,0 +1,10 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { PatchCheckResult } from "./PatchCheckResult";
+
+export type PatchChecksEvent = { 
+/**
+ * Identifier of the patch the checks ran after.
+ */
+call_id: string, turn_id: string, results: Array<PatchCheckResult>, };
@@ -0,0 +1,22 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { PlanItemArg } from "./PlanItemArg";
+
+/**
+ * The model presented a plan and cannot run mutating tools until the user approves it
+ * (`require_plan_approval`). Answered with `Op::PatchApproval` using `call_id`.
+ */
+export type PlanApprovalRequestEvent = { 
+/**
+ * Responses API call id of the `update_plan` call that presented the plan.
+ */
+call_id: string, 
+/**
+ * Turn ID that this plan belongs to.
+ */
+turn_id: string, 
+/**
+ * Optional explanation the model gave alongside the plan.
+ */
+explanation: string | null, plan: Array<PlanItemArg>, };
@@ -9,4 +9,9 @@ export type StreamErrorEvent = { message: string, codex_error_info: CodexErrorIn
  * human-readable message that is surfaced as the terminal error if retries
  * are exhausted).
  */
-additional_details: string | null, };
+additional_details: string | null, 
+/**
+ * How long the agent waits before retrying, in milliseconds. Set when the provider
+ * throttled the request and asked for a specific delay.
+ */
+retry_delay_ms?: bigint, };
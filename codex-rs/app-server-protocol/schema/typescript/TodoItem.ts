@@ -0,0 +1,10 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { TodoStatus } from "./TodoStatus";
+
+export type TodoItem = { 
+/**
+ * Assigned when the item is added; stays the same while the item is on the list.
+ */
+id: number, text: string, status: TodoStatus, };
@@ -0,0 +1,9 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { TodoItem } from "./TodoItem";
+
+/**
+ * The whole todo list after a change. Recorded in the rollout so resumed sessions keep it.
+ */
+export type TodoListEvent = { items: Array<TodoItem>, };
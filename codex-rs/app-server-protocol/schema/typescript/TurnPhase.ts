@@ -0,0 +1,9 @@
+// GENERATED CODE! DO NOT MODIFY BY HAND!
+
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Where a running turn currently is. Phases are reported as they are entered; a turn may go
+ * through several sampling rounds, each repeating `Sending` through `RunningTool`.
+ */
+export type TurnPhase = { "type": "queued" } | { "type": "sending" } | { "type": "thinking" } | { "type": "streaming" } | { "type": "running_tool", index: number, total: number, };
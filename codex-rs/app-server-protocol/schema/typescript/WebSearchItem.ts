@@ -2,5 +2,10 @@
 
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 import type { WebSearchAction } from "./WebSearchAction";
+import type { WebSearchSource } from "./WebSearchSource";
 
-export type WebSearchItem = { id: string, query: string, action: WebSearchAction, };
+export type WebSearchItem = { id: string, query: string, action: WebSearchAction, 
+/**
+ * Results of a provider-backed search, best match first. Empty for hosted search.
+ */
+sources?: Array<WebSearchSource>, };
@@ -36,6 +36,7 @@ export type { CollabResumeBeginEvent } from "./CollabResumeBeginEvent";
 export type { CollabResumeEndEvent } from "./CollabResumeEndEvent";
 export type { CollabWaitingBeginEvent } from "./CollabWaitingBeginEvent";
 export type { CollabWaitingEndEvent } from "./CollabWaitingEndEvent";
+export type { CompactionReviewRequestEvent } from "./CompactionReviewRequestEvent";
 export type { CollaborationMode } from "./CollaborationMode";
 export type { ContentItem } from "./ContentItem";
 export type { ContextCompactedEvent } from "./ContextCompactedEvent";
@@ -45,6 +46,8 @@ export type { ConversationSummary } from "./ConversationSummary";
 export type { CreditsSnapshot } from "./CreditsSnapshot";
 export type { CustomPrompt } from "./CustomPrompt";
 export type { DeprecationNoticeEvent } from "./DeprecationNoticeEvent";
+export type { DryRunAction } from "./DryRunAction";
+export type { DryRunActionEvent } from "./DryRunActionEvent";
 export type { DynamicToolCallOutputContentItem } from "./DynamicToolCallOutputContentItem";
 export type { DynamicToolCallRequest } from "./DynamicToolCallRequest";
 export type { DynamicToolCallResponseEvent } from "./DynamicToolCallResponseEvent";
@@ -125,8 +128,12 @@ export type { ParsedCommand } from "./ParsedCommand";
 export type { PatchApplyBeginEvent } from "./PatchApplyBeginEvent";
 export type { PatchApplyEndEvent } from "./PatchApplyEndEvent";
 export type { PatchApplyStatus } from "./PatchApplyStatus";
+export type { PatchCheckKind } from "./PatchCheckKind";
+export type { PatchCheckResult } from "./PatchCheckResult";
+export type { PatchChecksEvent } from "./PatchChecksEvent";
 export type { PermissionProfile } from "./PermissionProfile";
 export type { Personality } from "./Personality";
+export type { PlanApprovalRequestEvent } from "./PlanApprovalRequestEvent";
 export type { PlanDeltaEvent } from "./PlanDeltaEvent";
 export type { PlanItem } from "./PlanItem";
 export type { PlanItemArg } from "./PlanItemArg";
@@ -189,6 +196,9 @@ export type { TextElement } from "./TextElement";
 export type { ThreadId } from "./ThreadId";
 export type { ThreadNameUpdatedEvent } from "./ThreadNameUpdatedEvent";
 export type { ThreadRolledBackEvent } from "./ThreadRolledBackEvent";
+export type { TodoItem } from "./TodoItem";
+export type { TodoListEvent } from "./TodoListEvent";
+export type { TodoStatus } from "./TodoStatus";
 export type { TokenCountEvent } from "./TokenCountEvent";
 export type { TokenUsage } from "./TokenUsage";
 export type { TokenUsageInfo } from "./TokenUsageInfo";
@@ -198,6 +208,8 @@ export type { TurnAbortedEvent } from "./TurnAbortedEvent";
 export type { TurnCompleteEvent } from "./TurnCompleteEvent";
 export type { TurnDiffEvent } from "./TurnDiffEvent";
 export type { TurnItem } from "./TurnItem";
+export type { TurnPhase } from "./TurnPhase";
+export type { TurnPhaseEvent } from "./TurnPhaseEvent";
 export type { TurnStartedEvent } from "./TurnStartedEvent";
 export type { UndoCompletedEvent } from "./UndoCompletedEvent";
 export type { UndoStartedEvent } from "./UndoStartedEvent";
@@ -213,4 +225,5 @@ export type { WebSearchBeginEvent } from "./WebSearchBeginEvent";
 export type { WebSearchEndEvent } from "./WebSearchEndEvent";
 export type { WebSearchItem } from "./WebSearchItem";
 export type { WebSearchMode } from "./WebSearchMode";
+export type { WebSearchSource } from "./WebSearchSource";
 export * as v2 from "./v2";
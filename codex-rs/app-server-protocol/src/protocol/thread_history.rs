@@ -41,6 +41,7 @@ use codex_protocol::protocol::PatchApplyEndEvent;
 use codex_protocol::protocol::ReviewOutputEvent;
 use codex_protocol::protocol::RolloutItem;
 use codex_protocol::protocol::ThreadRolledBackEvent;
+use codex_protocol::protocol::TurnAbortReason;
 use codex_protocol::protocol::TurnAbortedEvent;
 use codex_protocol::protocol::TurnCompleteEvent;
 use codex_protocol::protocol::TurnStartedEvent;
@@ -809,22 +810,30 @@ impl ThreadHistoryBuilder {
     }
 
     fn handle_turn_aborted(&mut self, payload: &TurnAbortedEvent) {
+        // Turns that stopped on an error or timeout were already marked failed by the `Error`
+        // event when it was persisted; otherwise this record is the only trace of the failure.
+        let status = match payload.reason {
+            TurnAbortReason::Timeout | TurnAbortReason::Error => TurnStatus::Failed,
+            TurnAbortReason::Interrupted
+            | TurnAbortReason::Replaced
+            | TurnAbortReason::ReviewEnded => TurnStatus::Interrupted,
+        };
         if let Some(turn_id) = payload.turn_id.as_deref() {
             // Prefer an exact ID match so we interrupt the turn explicitly targeted by the event.
             if let Some(turn) = self.current_turn.as_mut().filter(|turn| turn.id == turn_id) {
-                turn.status = TurnStatus::Interrupted;
+                turn.status = status;
                 return;
             }
 
             if let Some(turn) = self.turns.iter_mut().find(|turn| turn.id == turn_id) {
-                turn.status = TurnStatus::Interrupted;
+                turn.status = status;
                 return;
             }
         }
 
         // If the event has no ID (or refers to an unknown turn), fall back to the active turn.
         if let Some(turn) = self.current_turn.as_mut() {
-            turn.status = TurnStatus::Interrupted;
+            turn.status = status;
         }
     }
 
@@ -1366,6 +1375,35 @@ mod tests {
         );
     }
 
+    #[test]
+    fn marks_turn_as_failed_when_cancelled_by_timeout() {
+        let events = vec![
+            EventMsg::UserMessage(UserMessageEvent {
+                message: "Please do the thing".into(),
+                images: None,
+                text_elements: Vec::new(),
+                local_images: Vec::new(),
+            }),
+            EventMsg::AgentMessage(AgentMessageEvent {
+                message: "Working...".into(),
+                phase: None,
+            }),
+            EventMsg::TurnAborted(TurnAbortedEvent {
+                turn_id: Some("turn-1".into()),
+                reason: TurnAbortReason::Timeout,
+            }),
+        ];
+
+        let items = events
+            .into_iter()
+            .map(RolloutItem::EventMsg)
+            .collect::<Vec<_>>();
+        let turns = build_turns_from_rollout_items(&items);
+        assert_eq!(turns.len(), 1);
+        assert_eq!(turns[0].status, TurnStatus::Failed);
+        assert_eq!(turns[0].items.len(), 2);
+    }
+
     #[test]
     fn marks_turn_as_interrupted_when_aborted() {
         let events = vec![
@@ -1639,6 +1677,7 @@ mod tests {
                     query: Some("codex".into()),
                     queries: None,
                 },
+                sources: Vec::new(),
             }),
             EventMsg::ExecCommandEnd(ExecCommandEndEvent {
                 call_id: "exec-1".into(),
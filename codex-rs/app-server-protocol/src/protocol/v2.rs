@@ -4601,6 +4601,7 @@ mod tests {
                 query: Some("docs".to_string()),
                 queries: None,
             },
+            sources: Vec::new(),
         });
 
         assert_eq!(
@@ -48,13 +48,17 @@ use codex_core::config_loader::LoaderOverrides;
 use codex_core::default_client::SetOriginatorError;
 use codex_core::default_client::USER_AGENT_SUFFIX;
 use codex_core::default_client::get_codex_user_agent;
+use codex_core::default_client::set_default_client_api_log;
+use codex_core::default_client::set_default_client_proxy;
 use codex_core::default_client::set_default_client_residency_requirement;
+use codex_core::default_client::set_default_client_tls;
 use codex_core::default_client::set_default_originator;
 use codex_core::models_manager::collaboration_mode_presets::CollaborationModesConfig;
 use codex_feedback::CodexFeedback;
 use codex_protocol::ThreadId;
 use codex_protocol::protocol::SessionSource;
 use codex_state::log_db::LogDbLayer;
+use codex_utils_absolute_path::AbsolutePathBuf;
 use futures::FutureExt;
 use tokio::sync::broadcast;
 use tokio::sync::watch;
@@ -336,6 +340,25 @@ impl MessageProcessor {
                         }
                     }
                     set_default_client_residency_requirement(self.config.enforce_residency.value());
+                    if let Err(err) = set_default_client_proxy(self.config.outbound_proxy.as_ref())
+                    {
+                        let error = JSONRPCErrorError {
+                            code: INVALID_REQUEST_ERROR_CODE,
+                            message: format!("Invalid outbound_proxy config: {err}"),
+                            data: None,
+                        };
+                        self.outgoing.send_error(request_id.clone(), error).await;
+                        return;
+                    }
+                    set_default_client_api_log(
+                        self.config
+                            .api_log_path
+                            .as_ref()
+                            .map(AbsolutePathBuf::as_path),
+                    );
+                    if let Err(err) = set_default_client_tls(self.config.tls.as_ref()) {
+                        tracing::warn!("failed to load TLS certificates: {err}");
+                    }
                     let user_agent_suffix = format!("{name}; {version}");
                     if let Ok(mut suffix) = USER_AGENT_SUFFIX.lock() {
                         *suffix = Some(user_agent_suffix);
@@ -0,0 +1,538 @@
+//! Profile bundles for `codex config export` and `codex config import`.
+//!
+//! A bundle is a zip archive with a `profile.toml` config fragment — the `[profiles.<name>]`
+//! table, the `[mcp_servers]` definitions, and the `[tui] theme` — plus the custom prompts under
+//! `prompts/` and, when the theme is a custom one, its file under `themes/`. Importing merges the
+//! fragment into `config.toml` and copies the files into `$CODEX_HOME`. Anything that already
+//! exists is left alone unless the import is forced.
+//!
+//! MCP server `env` and `http_headers` values often hold tokens, so an export replaces them with
+//! a placeholder unless secrets are explicitly included.
+
+use std::fs::File;
+use std::io::Read;
+use std::io::Write;
+use std::path::Component;
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::anyhow;
+use anyhow::bail;
+use codex_config::CONFIG_TOML_FILE;
+use codex_core::config::edit::ConfigEdit;
+use codex_core::config::edit::ConfigEditsBuilder;
+use codex_core::config::edit::syntax_theme_edit;
+use toml_edit::DocumentMut;
+use toml_edit::Item as TomlItem;
+use toml_edit::Table as TomlTable;
+use zip::ZipArchive;
+use zip::ZipWriter;
+use zip::write::SimpleFileOptions;
+
+const PROFILE_ENTRY: &str = "profile.toml";
+const PROMPTS_DIR: &str = "prompts";
+const THEMES_DIR: &str = "themes";
+const REDACTED: &str = "<redacted>";
+/// MCP server settings whose values are redacted on export.
+const SECRET_TABLES: [&str; 2] = ["env", "http_headers"];
+
+/// What an export wrote or an import applied.
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct BundleSummary {
+    pub(crate) profile: String,
+    pub(crate) mcp_servers: Vec<String>,
+    pub(crate) theme: Option<String>,
+    /// Bundle entries for prompts and themes, e.g. `prompts/review.md`.
+    pub(crate) files: Vec<String>,
+    /// Config keys and files an import kept because they already exist.
+    pub(crate) skipped: Vec<String>,
+    /// MCP server settings an export replaced with a placeholder, e.g.
+    /// `mcp_servers.docs.env.API_KEY`.
+    pub(crate) redacted: Vec<String>,
+}
+
+/// An MCP server a bundle defines, with what importing it would run or connect to.
+#[derive(Debug, PartialEq)]
+pub(crate) struct BundledMcpServer {
+    pub(crate) name: String,
+    /// The command line of a stdio server or the URL of an HTTP one.
+    pub(crate) target: String,
+}
+
+/// Write `profile` and what it relies on from `codex_home` to a bundle at `output`. MCP server
+/// `env` and `http_headers` values are redacted unless `include_secrets` is set.
+pub(crate) fn export_bundle(
+    codex_home: &Path,
+    profile: &str,
+    output: &Path,
+    include_secrets: bool,
+) -> Result<BundleSummary> {
+    let config_path = codex_home.join(CONFIG_TOML_FILE);
+    let config = read_config(&config_path)?;
+    let profile_item = config
+        .get("profiles")
+        .and_then(|profiles| profiles.get(profile))
+        .filter(|item| item.is_table_like())
+        .ok_or_else(|| {
+            anyhow!(
+                "profile `{profile}` is not defined in {}",
+                config_path.display()
+            )
+        })?;
+
+    let mut summary = BundleSummary {
+        profile: profile.to_string(),
+        ..Default::default()
+    };
+    let mut fragment = DocumentMut::new();
+    let mut profiles = TomlTable::new();
+    profiles.set_implicit(true);
+    profiles.insert(profile, profile_item.clone());
+    fragment.insert("profiles", TomlItem::Table(profiles));
+    if let Some(servers) = config
+        .get("mcp_servers")
+        .filter(|item| item.is_table_like())
+    {
+        summary.mcp_servers = table_keys(servers);
+        let mut servers = servers.clone();
+        if !include_secrets {
+            summary.redacted = redact_secrets(&mut servers);
+        }
+        fragment.insert("mcp_servers", servers);
+    }
+    if let Some(theme) = configured_theme(&config) {
+        let mut tui = TomlTable::new();
+        tui.insert("theme", toml_edit::value(theme.as_str()));
+        fragment.insert("tui", TomlItem::Table(tui));
+        summary.theme = Some(theme);
+    }
+
+    let mut files = markdown_files(&codex_home.join(PROMPTS_DIR))?
+        .into_iter()
+        .map(|path| (PROMPTS_DIR, path))
+        .collect::<Vec<_>>();
+    if let Some(theme) = &summary.theme {
+        let path = codex_home.join(THEMES_DIR).join(format!("{theme}.tmTheme"));
+        if path.is_file() {
+            files.push((THEMES_DIR, path));
+        }
+    }
+
+    let file =
+        File::create(output).with_context(|| format!("failed to create {}", output.display()))?;
+    let mut zip = ZipWriter::new(file);
+    let options = SimpleFileOptions::default();
+    zip.start_file(PROFILE_ENTRY, options)?;
+    zip.write_all(fragment.to_string().as_bytes())?;
+    for (dir, path) in files {
+        let Some(file_name) = path.file_name().and_then(|name| name.to_str()) else {
+            continue;
+        };
+        let contents =
+            std::fs::read(&path).with_context(|| format!("failed to read {}", path.display()))?;
+        let entry = format!("{dir}/{file_name}");
+        zip.start_file(entry.as_str(), options)?;
+        zip.write_all(&contents)?;
+        summary.files.push(entry);
+    }
+    zip.finish()?;
+    Ok(summary)
+}
+
+/// Add the profile, MCP servers, theme, and files in `bundle` to `codex_home`, naming the
+/// profile `rename` when given. Without `force`, an existing profile is an error and existing
+/// MCP servers, theme, and files are kept.
+pub(crate) fn import_bundle(
+    codex_home: &Path,
+    bundle: &Path,
+    rename: Option<&str>,
+    force: bool,
+) -> Result<BundleSummary> {
+    let (mut archive, fragment) = open_bundle(bundle)?;
+    let (bundled_name, profile_item) = fragment
+        .get("profiles")
+        .and_then(TomlItem::as_table_like)
+        .and_then(|profiles| profiles.iter().next())
+        .map(|(name, item)| (name.to_string(), item.clone()))
+        .ok_or_else(|| anyhow!("{} does not contain a profile", bundle.display()))?;
+    let profile = rename.map_or(bundled_name, str::to_string);
+
+    let config = read_config(&codex_home.join(CONFIG_TOML_FILE))?;
+    if !force
+        && config
+            .get("profiles")
+            .and_then(|profiles| profiles.get(&profile))
+            .is_some()
+    {
+        bail!(
+            "profile `{profile}` already exists; pass --force to replace it or --as to import it under another name"
+        );
+    }
+
+    let mut summary = BundleSummary {
+        profile: profile.clone(),
+        ..Default::default()
+    };
+    let mut edits = vec![ConfigEdit::SetPath {
+        segments: vec!["profiles".to_string(), profile],
+        value: profile_item,
+    }];
+    if let Some(servers) = fragment
+        .get("mcp_servers")
+        .and_then(TomlItem::as_table_like)
+    {
+        for (name, server) in servers.iter() {
+            let exists = config
+                .get("mcp_servers")
+                .and_then(|existing| existing.get(name))
+                .is_some();
+            if exists && !force {
+                summary.skipped.push(format!("mcp_servers.{name}"));
+                continue;
+            }
+            edits.push(ConfigEdit::SetPath {
+                segments: vec!["mcp_servers".to_string(), name.to_string()],
+                value: server.clone(),
+            });
+            summary.mcp_servers.push(name.to_string());
+        }
+    }
+    if let Some(theme) = configured_theme(&fragment) {
+        if configured_theme(&config).is_some() && !force {
+            summary.skipped.push("tui.theme".to_string());
+        } else {
+            edits.push(syntax_theme_edit(&theme));
+            summary.theme = Some(theme);
+        }
+    }
+    ConfigEditsBuilder::new(codex_home)
+        .with_edits(edits)
+        .apply_blocking()?;
+
+    for index in 0..archive.len() {
+        let mut entry = archive.by_index(index)?;
+        let Some(relative) = entry.enclosed_name().filter(|path| is_bundled_file(path)) else {
+            continue;
+        };
+        let name = entry.name().to_string();
+        let dest = codex_home.join(relative);
+        if dest.exists() && !force {
+            summary.skipped.push(name);
+            continue;
+        }
+        if let Some(parent) = dest.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("failed to create {}", parent.display()))?;
+        }
+        let mut contents = Vec::new();
+        entry.read_to_end(&mut contents)?;
+        std::fs::write(&dest, contents)
+            .with_context(|| format!("failed to write {}", dest.display()))?;
+        summary.files.push(name);
+    }
+    Ok(summary)
+}
+
+/// The MCP servers `bundle` defines, so they can be reviewed before an import adds them.
+pub(crate) fn bundled_mcp_servers(bundle: &Path) -> Result<Vec<BundledMcpServer>> {
+    let (_, fragment) = open_bundle(bundle)?;
+    let Some(servers) = fragment
+        .get("mcp_servers")
+        .and_then(TomlItem::as_table_like)
+    else {
+        return Ok(Vec::new());
+    };
+    Ok(servers
+        .iter()
+        .map(|(name, server)| BundledMcpServer {
+            name: name.to_string(),
+            target: server_target(server),
+        })
+        .collect())
+}
+
+fn open_bundle(bundle: &Path) -> Result<(ZipArchive<File>, DocumentMut)> {
+    let file =
+        File::open(bundle).with_context(|| format!("failed to open {}", bundle.display()))?;
+    let mut archive = ZipArchive::new(file)
+        .with_context(|| format!("{} is not a profile bundle", bundle.display()))?;
+    let fragment = {
+        let mut entry = archive
+            .by_name(PROFILE_ENTRY)
+            .with_context(|| format!("{} has no {PROFILE_ENTRY}", bundle.display()))?;
+        let mut contents = String::new();
+        entry.read_to_string(&mut contents)?;
+        contents
+            .parse::<DocumentMut>()
+            .with_context(|| format!("failed to parse {PROFILE_ENTRY} in {}", bundle.display()))?
+    };
+    Ok((archive, fragment))
+}
+
+/// Replace the values under each server's `env` and `http_headers` with a placeholder, returning
+/// the keys that were redacted.
+fn redact_secrets(servers: &mut TomlItem) -> Vec<String> {
+    let mut redacted = Vec::new();
+    let Some(servers) = servers.as_table_like_mut() else {
+        return redacted;
+    };
+    for (name, server) in servers.iter_mut() {
+        for table in SECRET_TABLES {
+            let Some(values) = server.get_mut(table).and_then(TomlItem::as_table_like_mut) else {
+                continue;
+            };
+            for (key, value) in values.iter_mut() {
+                *value = toml_edit::value(REDACTED);
+                redacted.push(format!("mcp_servers.{}.{table}.{}", name.get(), key.get()));
+            }
+        }
+    }
+    redacted
+}
+
+/// The URL of an HTTP server, or the command line of a stdio one.
+fn server_target(server: &TomlItem) -> String {
+    if let Some(url) = server.get("url").and_then(TomlItem::as_str) {
+        return url.to_string();
+    }
+    let command = server
+        .get("command")
+        .and_then(TomlItem::as_str)
+        .unwrap_or("<no command>");
+    let args = server
+        .get("args")
+        .and_then(TomlItem::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|arg| arg.as_str());
+    std::iter::once(command)
+        .chain(args)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn read_config(path: &Path) -> Result<DocumentMut> {
+    let contents = match std::fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(DocumentMut::new()),
+        Err(err) => return Err(err).with_context(|| format!("failed to read {}", path.display())),
+    };
+    contents
+        .parse()
+        .with_context(|| format!("failed to parse {}", path.display()))
+}
+
+fn configured_theme(config: &DocumentMut) -> Option<String> {
+    config
+        .get("tui")
+        .and_then(|tui| tui.get("theme"))
+        .and_then(TomlItem::as_str)
+        .map(str::to_string)
+}
+
+fn table_keys(item: &TomlItem) -> Vec<String> {
+    item.as_table_like()
+        .map(|table| table.iter().map(|(key, _)| key.to_string()).collect())
+        .unwrap_or_default()
+}
+
+/// The `.md` files directly inside `dir`, sorted by name; empty when `dir` does not exist.
+fn markdown_files(dir: &Path) -> Result<Vec<PathBuf>> {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err).with_context(|| format!("failed to read {}", dir.display())),
+    };
+    let mut files: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.is_file()
+                && path
+                    .extension()
+                    .and_then(|ext| ext.to_str())
+                    .is_some_and(|ext| ext.eq_ignore_ascii_case("md"))
+        })
+        .collect();
+    files.sort();
+    Ok(files)
+}
+
+/// Whether a bundle entry is a single file directly under `prompts/` or `themes/`.
+fn is_bundled_file(path: &Path) -> bool {
+    let components: Vec<Component> = path.components().collect();
+    matches!(
+        components.as_slice(),
+        [Component::Normal(dir), Component::Normal(_)]
+            if dir.to_str().is_some_and(|dir| dir == PROMPTS_DIR || dir == THEMES_DIR)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    const CONFIG: &str = r#"model = "gpt-5"
+
+[profiles.work]
+model = "o3"
+approval_policy = "on-request"
+
+[mcp_servers.docs]
+command = "docs-server"
+args = ["--stdio"]
+
+[mcp_servers.docs.env]
+API_KEY = "secret"
+
+[mcp_servers.search]
+url = "https://mcp.example.com/search"
+http_headers = { Authorization = "Bearer secret" }
+
+[tui]
+theme = "team"
+"#;
+
+    fn source_home() -> TempDir {
+        let home = TempDir::new().expect("tempdir");
+        std::fs::write(home.path().join(CONFIG_TOML_FILE), CONFIG).expect("write config");
+        std::fs::create_dir_all(home.path().join(PROMPTS_DIR)).expect("create prompts");
+        std::fs::write(home.path().join("prompts/review.md"), "Review $1").expect("write prompt");
+        std::fs::write(home.path().join("prompts/notes.txt"), "ignored").expect("write notes");
+        std::fs::create_dir_all(home.path().join(THEMES_DIR)).expect("create themes");
+        std::fs::write(home.path().join("themes/team.tmTheme"), "<plist/>").expect("write theme");
+        home
+    }
+
+    #[test]
+    fn export_then_import_recreates_the_profile() {
+        let source = source_home();
+        let bundle = source.path().join("work.zip");
+        let exported = export_bundle(source.path(), "work", &bundle, false).expect("export");
+        assert_eq!(
+            exported,
+            BundleSummary {
+                profile: "work".to_string(),
+                mcp_servers: vec!["docs".to_string(), "search".to_string()],
+                theme: Some("team".to_string()),
+                files: vec![
+                    "prompts/review.md".to_string(),
+                    "themes/team.tmTheme".to_string()
+                ],
+                skipped: Vec::new(),
+                redacted: vec![
+                    "mcp_servers.docs.env.API_KEY".to_string(),
+                    "mcp_servers.search.http_headers.Authorization".to_string(),
+                ],
+            }
+        );
+
+        let target = TempDir::new().expect("tempdir");
+        let imported =
+            import_bundle(target.path(), &bundle, Some("team-work"), false).expect("import");
+        assert_eq!(imported.profile, "team-work");
+        assert_eq!(imported.files, exported.files);
+
+        let config: toml::Value = toml::from_str(
+            &std::fs::read_to_string(target.path().join(CONFIG_TOML_FILE)).expect("read config"),
+        )
+        .expect("parse config");
+        assert_eq!(
+            config["profiles"]["team-work"]["model"].as_str(),
+            Some("o3")
+        );
+        assert_eq!(
+            config["mcp_servers"]["docs"]["command"].as_str(),
+            Some("docs-server")
+        );
+        assert_eq!(
+            config["mcp_servers"]["docs"]["env"]["API_KEY"].as_str(),
+            Some(REDACTED)
+        );
+        assert_eq!(
+            config["mcp_servers"]["search"]["http_headers"]["Authorization"].as_str(),
+            Some(REDACTED)
+        );
+        assert_eq!(config["tui"]["theme"].as_str(), Some("team"));
+        assert_eq!(config.get("model"), None);
+        assert_eq!(
+            std::fs::read_to_string(target.path().join("prompts/review.md")).expect("prompt"),
+            "Review $1"
+        );
+    }
+
+    #[test]
+    fn export_keeps_secrets_when_asked_and_lists_servers_to_review() {
+        let source = source_home();
+        let bundle = source.path().join("work.zip");
+        let exported = export_bundle(source.path(), "work", &bundle, true).expect("export");
+        assert_eq!(exported.redacted, Vec::<String>::new());
+
+        let target = TempDir::new().expect("tempdir");
+        import_bundle(target.path(), &bundle, None, false).expect("import");
+        let config: toml::Value = toml::from_str(
+            &std::fs::read_to_string(target.path().join(CONFIG_TOML_FILE)).expect("read config"),
+        )
+        .expect("parse config");
+        assert_eq!(
+            config["mcp_servers"]["docs"]["env"]["API_KEY"].as_str(),
+            Some("secret")
+        );
+
+        assert_eq!(
+            bundled_mcp_servers(&bundle).expect("list servers"),
+            vec![
+                BundledMcpServer {
+                    name: "docs".to_string(),
+                    target: "docs-server --stdio".to_string(),
+                },
+                BundledMcpServer {
+                    name: "search".to_string(),
+                    target: "https://mcp.example.com/search".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn import_keeps_existing_entries_unless_forced() {
+        let source = source_home();
+        let bundle = source.path().join("work.zip");
+        export_bundle(source.path(), "work", &bundle, false).expect("export");
+
+        let err = import_bundle(source.path(), &bundle, None, false)
+            .expect_err("existing profile is refused");
+        assert!(err.to_string().contains("already exists"));
+
+        let imported = import_bundle(source.path(), &bundle, Some("copy"), false).expect("import");
+        assert_eq!(
+            imported.skipped,
+            vec![
+                "mcp_servers.docs".to_string(),
+                "mcp_servers.search".to_string(),
+                "tui.theme".to_string(),
+                "prompts/review.md".to_string(),
+                "themes/team.tmTheme".to_string(),
+            ]
+        );
+
+        let forced = import_bundle(source.path(), &bundle, None, true).expect("forced import");
+        assert_eq!(forced.skipped, Vec::<String>::new());
+    }
+
+    #[test]
+    fn export_requires_a_defined_profile() {
+        let source = source_home();
+        let err = export_bundle(
+            source.path(),
+            "missing",
+            &source.path().join("out.zip"),
+            false,
+        )
+        .expect_err("unknown profile");
+        assert!(err.to_string().contains("profile `missing` is not defined"));
+    }
+}
@@ -0,0 +1,358 @@
+use std::io::IsTerminal;
+use std::io::Write;
+use std::path::PathBuf;
+
+use anyhow::Result;
+use anyhow::bail;
+use codex_core::config::Config;
+use codex_core::config::find_codex_home;
+use codex_core::config::validation::validate_config_layers;
+use codex_core::config_loader::CloudRequirementsLoader;
+use codex_core::config_loader::ConfigIssue;
+use codex_core::config_loader::ConfigIssueSeverity;
+use codex_core::config_loader::LoaderOverrides;
+use codex_core::config_loader::format_config_error;
+use codex_core::config_loader::format_config_error_with_source;
+use codex_core::config_loader::load_config_layers_state;
+use codex_utils_absolute_path::AbsolutePathBuf;
+use serde_json::json;
+use toml::Value as TomlValue;
+
+use crate::config_bundle::BundleSummary;
+use crate::config_bundle::bundled_mcp_servers;
+use crate::config_bundle::export_bundle;
+use crate::config_bundle::import_bundle;
+
+/// Subcommands:
+/// - `validate` — check the config files Codex would load for unknown keys, type
+///   mismatches, and invalid enum values
+/// - `export` — package a profile into a shareable bundle
+/// - `import` — add the profile from a bundle to this machine
+#[derive(Debug, clap::Parser)]
+pub struct ConfigCli {
+    #[command(subcommand)]
+    pub subcommand: ConfigSubcommand,
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum ConfigSubcommand {
+    /// Check the config files for the current directory and report every problem found.
+    Validate(ValidateArgs),
+
+    /// Package a profile with the MCP servers, theme, and custom prompts it uses into a zip
+    /// bundle.
+    Export(ExportArgs),
+
+    /// Add the profile, MCP servers, theme, and custom prompts from a bundle to this machine.
+    Import(ImportArgs),
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct ValidateArgs {
+    /// Output the issues as JSON.
+    #[arg(long)]
+    pub json: bool,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct ExportArgs {
+    /// Name of the `[profiles.<name>]` table to export.
+    #[arg(value_name = "PROFILE")]
+    pub profile: String,
+
+    /// Where to write the bundle. Defaults to `codex-profile-<PROFILE>.zip` in the current
+    /// directory.
+    #[arg(long, short = 'o', value_name = "FILE")]
+    pub output: Option<PathBuf>,
+
+    /// Keep the values of MCP server `env` and `http_headers` instead of redacting them.
+    #[arg(long)]
+    pub include_secrets: bool,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct ImportArgs {
+    /// Bundle written by `codex config export`.
+    #[arg(value_name = "FILE")]
+    pub bundle: PathBuf,
+
+    /// Import the profile under a different name.
+    #[arg(long = "as", value_name = "NAME")]
+    pub rename: Option<String>,
+
+    /// Replace the profile, MCP servers, theme, and prompts that already exist.
+    #[arg(long)]
+    pub force: bool,
+
+    /// Add the bundle's MCP servers without asking for confirmation.
+    #[arg(long, short = 'y')]
+    pub yes: bool,
+}
+
+impl ConfigCli {
+    pub async fn run(self, cli_kv_overrides: Vec<(String, TomlValue)>) -> Result<()> {
+        match self.subcommand {
+            ConfigSubcommand::Validate(args) => run_validate(args, cli_kv_overrides).await,
+            ConfigSubcommand::Export(args) => run_export(args),
+            ConfigSubcommand::Import(args) => run_import(args),
+        }
+    }
+}
+
+fn run_export(args: ExportArgs) -> Result<()> {
+    let codex_home = find_codex_home()?;
+    let output = args
+        .output
+        .unwrap_or_else(|| PathBuf::from(format!("codex-profile-{}.zip", args.profile)));
+    let summary = export_bundle(&codex_home, &args.profile, &output, args.include_secrets)?;
+    println!(
+        "Exported profile `{}` to {}.",
+        summary.profile,
+        output.display()
+    );
+    print_bundle_contents(&summary);
+    if !summary.redacted.is_empty() {
+        println!(
+            "Redacted (pass --include-secrets to keep): {}",
+            summary.redacted.join(", ")
+        );
+    } else if args.include_secrets && !summary.mcp_servers.is_empty() {
+        println!(
+            "Review it before sharing: MCP server definitions can include tokens in `env` or headers."
+        );
+    }
+    Ok(())
+}
+
+fn run_import(args: ImportArgs) -> Result<()> {
+    let codex_home = find_codex_home()?;
+    // Importing an MCP server means Codex will run its command or connect to its URL, so show
+    // them before writing anything.
+    let servers = bundled_mcp_servers(&args.bundle)?;
+    if !servers.is_empty() && !args.yes {
+        if !std::io::stdin().is_terminal() {
+            bail!("refusing to import MCP servers without confirmation; pass --yes");
+        }
+        println!("This bundle adds MCP servers that Codex will run or connect to:");
+        for server in &servers {
+            println!("  {}  {}", server.name, server.target);
+        }
+        print!("Import them? [y/N] ");
+        std::io::stdout().flush()?;
+        let mut answer = String::new();
+        std::io::stdin().read_line(&mut answer)?;
+        if !matches!(answer.trim(), "y" | "Y" | "yes") {
+            println!("Aborted.");
+            return Ok(());
+        }
+    }
+    let summary = import_bundle(
+        &codex_home,
+        &args.bundle,
+        args.rename.as_deref(),
+        args.force,
+    )?;
+    println!(
+        "Imported profile `{}`; start Codex with `--profile {}` to use it.",
+        summary.profile, summary.profile
+    );
+    print_bundle_contents(&summary);
+    if !summary.skipped.is_empty() {
+        println!(
+            "Kept existing (pass --force to replace): {}",
+            summary.skipped.join(", ")
+        );
+    }
+    Ok(())
+}
+
+fn print_bundle_contents(summary: &BundleSummary) {
+    if !summary.mcp_servers.is_empty() {
+        println!("MCP servers: {}", summary.mcp_servers.join(", "));
+    }
+    if let Some(theme) = &summary.theme {
+        println!("Theme: {theme}");
+    }
+    if !summary.files.is_empty() {
+        println!("Files: {}", summary.files.join(", "));
+    }
+}
+
+/// Config files that were checked, with the issues found in each.
+struct Report {
+    files: Vec<(PathBuf, Vec<ConfigIssue>)>,
+    /// Set when the config still fails to load for a reason no file-level check explains,
+    /// e.g. a bad `-c` override or a value disallowed by requirements.
+    load_error: Option<String>,
+}
+
+impl Report {
+    fn issues(&self) -> impl Iterator<Item = &ConfigIssue> {
+        self.files.iter().flat_map(|(_, issues)| issues)
+    }
+
+    fn count(&self, severity: ConfigIssueSeverity) -> usize {
+        self.issues()
+            .filter(|issue| issue.severity == severity)
+            .count()
+    }
+
+    fn has_errors(&self) -> bool {
+        self.load_error.is_some() || self.count(ConfigIssueSeverity::Error) > 0
+    }
+}
+
+async fn run_validate(
+    args: ValidateArgs,
+    cli_kv_overrides: Vec<(String, TomlValue)>,
+) -> Result<()> {
+    let report = build_report(cli_kv_overrides).await?;
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&report_json(&report))?);
+    } else {
+        print_report(&report);
+    }
+    if report.has_errors() {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+async fn build_report(cli_kv_overrides: Vec<(String, TomlValue)>) -> Result<Report> {
+    let codex_home = find_codex_home()?;
+    let cwd = AbsolutePathBuf::current_dir()?;
+    let layers = match load_config_layers_state(
+        &codex_home,
+        Some(cwd),
+        &cli_kv_overrides,
+        LoaderOverrides::default(),
+        CloudRequirementsLoader::default(),
+    )
+    .await
+    {
+        Ok(layers) => layers,
+        // The layers could not even be read, typically because of a TOML syntax error; the
+        // error already carries the file and location.
+        Err(err) => {
+            return Ok(Report {
+                files: Vec::new(),
+                load_error: Some(err.to_string()),
+            });
+        }
+    };
+    let files = validate_config_layers(&layers).await;
+
+    let load_error = match Config::load_with_cli_overrides(cli_kv_overrides).await {
+        Ok(_) => None,
+        Err(_) if files.iter().any(|(_, issues)| has_error(issues)) => None,
+        Err(err) => Some(err.to_string()),
+    };
+    Ok(Report { files, load_error })
+}
+
+fn has_error(issues: &[ConfigIssue]) -> bool {
+    issues
+        .iter()
+        .any(|issue| issue.severity == ConfigIssueSeverity::Error)
+}
+
+fn print_report(report: &Report) {
+    for (path, issues) in &report.files {
+        if issues.is_empty() {
+            println!("{}: ok", path.display());
+            continue;
+        }
+        let contents = std::fs::read_to_string(path).ok();
+        for issue in issues {
+            let rendered = match contents.as_deref() {
+                Some(contents) => format_config_error(&issue.error, contents),
+                None => format_config_error_with_source(&issue.error),
+            };
+            println!("{}: {rendered}\n", issue.severity);
+        }
+    }
+    if let Some(load_error) = &report.load_error {
+        println!("error: {load_error}\n");
+    }
+    if report.files.is_empty() && report.load_error.is_none() {
+        println!("No config files found.");
+        return;
+    }
+
+    let errors =
+        report.count(ConfigIssueSeverity::Error) + usize::from(report.load_error.is_some());
+    let warnings = report.count(ConfigIssueSeverity::Warning);
+    if errors + warnings > 0 {
+        println!(
+            "{errors} {}, {warnings} {}",
+            plural(errors, "error", "errors"),
+            plural(warnings, "warning", "warnings")
+        );
+    }
+}
+
+fn plural<'a>(count: usize, one: &'a str, many: &'a str) -> &'a str {
+    if count == 1 { one } else { many }
+}
+
+fn report_json(report: &Report) -> serde_json::Value {
+    let files: Vec<_> = report
+        .files
+        .iter()
+        .map(|(path, issues)| {
+            let issues: Vec<_> = issues
+                .iter()
+                .map(|issue| {
+                    json!({
+                        "severity": issue.severity.to_string(),
+                        "key": issue.key,
+                        "line": issue.error.range.start.line,
+                        "column": issue.error.range.start.column,
+                        "message": issue.error.message,
+                    })
+                })
+                .collect();
+            json!({ "path": path, "issues": issues })
+        })
+        .collect();
+    json!({
+        "valid": !report.has_errors(),
+        "files": files,
+        "load_error": report.load_error,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_core::config::validation::validate_config_toml;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn json_report_lists_issues_per_file() {
+        let path = PathBuf::from("/home/user/.codex/config.toml");
+        let issues = validate_config_toml(&path, "modle = \"gpt-5\"\n");
+        let report = Report {
+            files: vec![(path, issues)],
+            load_error: None,
+        };
+
+        assert_eq!(
+            report_json(&report),
+            json!({
+                "valid": true,
+                "files": [{
+                    "path": "/home/user/.codex/config.toml",
+                    "issues": [{
+                        "severity": "warning",
+                        "key": "modle",
+                        "line": 1,
+                        "column": 1,
+                        "message": "unknown config key `modle`",
+                    }],
+                }],
+                "load_error": null,
+            })
+        );
+    }
+}
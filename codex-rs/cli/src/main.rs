@@ -36,13 +36,20 @@ use supports_color::Stream;
 
 #[cfg(target_os = "macos")]
 mod app_cmd;
+mod config_bundle;
+mod config_cmd;
 #[cfg(target_os = "macos")]
 mod desktop_app;
 mod mcp_cmd;
+mod sessions_cmd;
+mod support_bundle;
 #[cfg(not(windows))]
 mod wsl_paths;
 
+use crate::config_cmd::ConfigCli;
 use crate::mcp_cmd::McpCli;
+use crate::sessions_cmd::SessionsCli;
+use crate::support_bundle::BundleCommand;
 
 use codex_core::config::Config;
 use codex_core::config::ConfigOverrides;
@@ -50,6 +57,7 @@ use codex_core::config::edit::ConfigEditsBuilder;
 use codex_core::config::find_codex_home;
 use codex_core::features::Stage;
 use codex_core::features::is_known_feature_key;
+use codex_core::preview_environment_context;
 use codex_core::terminal::TerminalName;
 
 /// Codex CLI
@@ -87,7 +95,8 @@ enum Subcommand {
     #[clap(visible_alias = "e")]
     Exec(ExecCli),
 
-    /// Run a code review non-interactively.
+    /// Run a code review non-interactively, or review an existing diff interactively with
+    /// `--diff <REF|PATCH>`.
     Review(ReviewArgs),
 
     /// Manage login.
@@ -132,6 +141,12 @@ enum Subcommand {
     /// Fork a previous interactive session (picker by default; use --last to fork the most recent).
     Fork(ForkCommand),
 
+    /// Browse a saved session's transcript read-only, without starting the model or tools.
+    View(ViewCommand),
+
+    /// List, show, export, and delete saved sessions.
+    Sessions(SessionsCli),
+
     /// [EXPERIMENTAL] Browse tasks from Codex Cloud and apply changes locally.
     #[clap(name = "cloud", alias = "cloud-tasks")]
     Cloud(CloudTasksCli),
@@ -146,6 +161,9 @@ enum Subcommand {
 
     /// Inspect feature flags.
     Features(FeaturesCli),
+
+    /// Check config files, or export and import profiles as shareable bundles.
+    Config(ConfigCli),
 }
 
 #[derive(Debug, Parser)]
@@ -166,6 +184,12 @@ enum DebugSubcommand {
     /// Tooling: helps debug the app server.
     AppServer(DebugAppServerCommand),
 
+    /// Zip the API wire log (`debug.api_log_path`) into a support archive.
+    Bundle(BundleCommand),
+
+    /// Print the `<environment_context>` block a new session in this directory would send.
+    EnvContext,
+
     /// Internal: reset local memory state for a fresh start.
     #[clap(hide = true)]
     ClearMemories,
@@ -208,6 +232,13 @@ struct ResumeCommand {
     config_overrides: TuiCli,
 }
 
+#[derive(Debug, Parser)]
+struct ViewCommand {
+    /// Rollout file path, session id (UUID), or thread name.
+    #[arg(value_name = "SESSION")]
+    session: String,
+}
+
 #[derive(Debug, Parser)]
 struct ForkCommand {
     /// Conversation/session id (UUID). When provided, forks this session.
@@ -585,6 +616,16 @@ async fn cli_main(arg0_paths: Arg0DispatchPaths) -> anyhow::Result<()> {
             );
             codex_exec::run_main(exec_cli, arg0_paths.clone()).await?;
         }
+        Some(Subcommand::Review(mut review_args)) if review_args.diff.is_some() => {
+            interactive.review_diff = review_args.diff.take();
+            interactive.review_instructions = review_args.prompt.take();
+            prepend_config_flags(
+                &mut interactive.config_overrides,
+                root_config_overrides.clone(),
+            );
+            let exit_info = run_interactive_tui(interactive, arg0_paths.clone()).await?;
+            handle_app_exit(exit_info)?;
+        }
         Some(Subcommand::Review(review_args)) => {
             let mut exec_cli = ExecCli::try_parse_from(["codex", "exec"])?;
             exec_cli.command = Some(ExecCommand::Review(review_args));
@@ -670,6 +711,21 @@ async fn cli_main(arg0_paths: Arg0DispatchPaths) -> anyhow::Result<()> {
             let exit_info = run_interactive_tui(interactive, arg0_paths.clone()).await?;
             handle_app_exit(exit_info)?;
         }
+        Some(Subcommand::View(ViewCommand { session })) => {
+            codex_tui::run_transcript_view(session).await?;
+        }
+        Some(Subcommand::Sessions(sessions_cli)) => {
+            let cli_kv_overrides = root_config_overrides
+                .parse_overrides()
+                .map_err(anyhow::Error::msg)?;
+            sessions_cli.run(cli_kv_overrides).await?;
+        }
+        Some(Subcommand::Config(config_cli)) => {
+            let cli_kv_overrides = root_config_overrides
+                .parse_overrides()
+                .map_err(anyhow::Error::msg)?;
+            config_cli.run(cli_kv_overrides).await?;
+        }
         Some(Subcommand::Login(mut login_cli)) => {
             prepend_config_flags(
                 &mut login_cli.config_overrides,
@@ -758,6 +814,13 @@ async fn cli_main(arg0_paths: Arg0DispatchPaths) -> anyhow::Result<()> {
             DebugSubcommand::AppServer(cmd) => {
                 run_debug_app_server_command(cmd).await?;
             }
+            DebugSubcommand::Bundle(mut cmd) => {
+                prepend_config_flags(&mut cmd.config_overrides, root_config_overrides.clone());
+                support_bundle::run_bundle_command(cmd, interactive.config_profile.clone()).await?;
+            }
+            DebugSubcommand::EnvContext => {
+                run_debug_env_context_command(&root_config_overrides, &interactive).await?;
+            }
             DebugSubcommand::ClearMemories => {
                 run_debug_clear_memories_command(&root_config_overrides, &interactive).await?;
             }
@@ -887,6 +950,23 @@ fn maybe_print_under_development_feature_warning(
     );
 }
 
+async fn run_debug_env_context_command(
+    root_config_overrides: &CliConfigOverrides,
+    interactive: &TuiCli,
+) -> anyhow::Result<()> {
+    let cli_kv_overrides = root_config_overrides
+        .parse_overrides()
+        .map_err(anyhow::Error::msg)?;
+    let overrides = ConfigOverrides {
+        config_profile: interactive.config_profile.clone(),
+        ..Default::default()
+    };
+    let config =
+        Config::load_with_cli_overrides_and_harness_overrides(cli_kv_overrides, overrides).await?;
+    println!("{}", preview_environment_context(&config).await);
+    Ok(())
+}
+
 async fn run_debug_clear_memories_command(
     root_config_overrides: &CliConfigOverrides,
     interactive: &TuiCli,
@@ -1068,6 +1148,12 @@ fn merge_interactive_cli_flags(interactive: &mut TuiCli, subcommand_cli: TuiCli)
     if subcommand_cli.dangerously_bypass_approvals_and_sandbox {
         interactive.dangerously_bypass_approvals_and_sandbox = true;
     }
+    if subcommand_cli.read_only {
+        interactive.read_only = true;
+    }
+    if subcommand_cli.dry_run {
+        interactive.dry_run = true;
+    }
     if let Some(cwd) = subcommand_cli.cwd {
         interactive.cwd = Some(cwd);
     }
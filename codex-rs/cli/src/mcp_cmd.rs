@@ -1,4 +1,6 @@
 use std::collections::HashMap;
+use std::io::IsTerminal;
+use std::io::Write;
 use std::sync::Arc;
 
 use anyhow::Context;
@@ -12,10 +14,13 @@ use codex_core::config::find_codex_home;
 use codex_core::config::load_global_mcp_servers;
 use codex_core::config::types::McpServerConfig;
 use codex_core::config::types::McpServerTransportConfig;
+use codex_core::git_info::get_git_repo_root;
 use codex_core::mcp::McpManager;
 use codex_core::mcp::auth::McpOAuthLoginSupport;
 use codex_core::mcp::auth::compute_auth_statuses;
 use codex_core::mcp::auth::oauth_login_support;
+use codex_core::mcp::discovery::DiscoveredMcpServer;
+use codex_core::mcp::discovery::discover_mcp_servers;
 use codex_core::plugins::PluginsManager;
 use codex_protocol::protocol::McpAuthStatus;
 use codex_rmcp_client::delete_oauth_tokens;
@@ -30,6 +35,7 @@ use codex_utils_cli::format_env_display::format_env_display;
 /// - `remove` — delete a server entry
 /// - `login`  — authenticate with MCP server using OAuth
 /// - `logout` — remove OAuth credentials for MCP server
+/// - `discover` — find local MCP servers and enable them for the current project
 #[derive(Debug, clap::Parser)]
 pub struct McpCli {
     #[clap(flatten)]
@@ -47,6 +53,7 @@ pub enum McpSubcommand {
     Remove(RemoveArgs),
     Login(LoginArgs),
     Logout(LogoutArgs),
+    Discover(DiscoverArgs),
 }
 
 #[derive(Debug, clap::Parser)]
@@ -151,6 +158,19 @@ pub struct LogoutArgs {
     pub name: String,
 }
 
+/// Scan project manifests (`.mcp.json`, `.vscode/mcp.json`), `~/.codex/mcp/`, and `mcp-*`
+/// executables on PATH for servers that are not configured yet.
+#[derive(Debug, clap::Parser)]
+pub struct DiscoverArgs {
+    /// Enable every discovered server for this project without asking.
+    #[arg(long, short = 'y', conflicts_with = "json")]
+    pub yes: bool,
+
+    /// Output the discovered servers as JSON instead of offering to enable them.
+    #[arg(long)]
+    pub json: bool,
+}
+
 impl McpCli {
     pub async fn run(self) -> Result<()> {
         let McpCli {
@@ -177,6 +197,9 @@ impl McpCli {
             McpSubcommand::Logout(args) => {
                 run_logout(&config_overrides, args).await?;
             }
+            McpSubcommand::Discover(args) => {
+                run_discover(&config_overrides, args).await?;
+            }
         }
 
         Ok(())
@@ -402,6 +425,110 @@ async fn run_logout(config_overrides: &CliConfigOverrides, logout_args: LogoutAr
     Ok(())
 }
 
+async fn run_discover(config_overrides: &CliConfigOverrides, args: DiscoverArgs) -> Result<()> {
+    let overrides = config_overrides
+        .parse_overrides()
+        .map_err(anyhow::Error::msg)?;
+    let config = Config::load_with_cli_overrides(overrides)
+        .await
+        .context("failed to load configuration")?;
+    let mcp_manager = McpManager::new(Arc::new(PluginsManager::new(config.codex_home.clone())));
+    let configured: Vec<String> = mcp_manager
+        .effective_servers(&config, None)
+        .into_keys()
+        .collect();
+    let project_root = get_git_repo_root(&config.cwd).unwrap_or_else(|| config.cwd.clone());
+    let path_env = std::env::var_os("PATH");
+
+    let servers = discover_mcp_servers(
+        &config.codex_home,
+        &project_root,
+        path_env.as_deref(),
+        &configured,
+    );
+
+    if args.json {
+        let json: Vec<serde_json::Value> = servers
+            .iter()
+            .map(|server| {
+                serde_json::json!({
+                    "name": server.name,
+                    "source": server.source,
+                    "origin": server.origin,
+                    "transport": server.config.transport,
+                })
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&json)?);
+        return Ok(());
+    }
+
+    if servers.is_empty() {
+        println!("No new MCP servers found.");
+        return Ok(());
+    }
+
+    let interactive = std::io::stdin().is_terminal();
+    let mut selected: Vec<&DiscoveredMcpServer> = Vec::new();
+    for server in &servers {
+        println!(
+            "{}  {}  (from {})",
+            server.name,
+            describe_transport(&server.config.transport),
+            server.origin.display()
+        );
+        if args.yes {
+            selected.push(server);
+        } else if interactive {
+            print!("Enable '{}' for this project? [y/N] ", server.name);
+            std::io::stdout().flush()?;
+            let mut answer = String::new();
+            std::io::stdin().read_line(&mut answer)?;
+            if matches!(answer.trim(), "y" | "Y" | "yes") {
+                selected.push(server);
+            }
+        }
+    }
+
+    if selected.is_empty() {
+        if !args.yes && !interactive {
+            println!("Run `codex mcp discover --yes` to enable them for this project.");
+        }
+        return Ok(());
+    }
+
+    let project_codex_dir = project_root.join(".codex");
+    let mut edits = ConfigEditsBuilder::new(&project_codex_dir);
+    for server in &selected {
+        edits = edits.set_mcp_server(&server.name, &server.config);
+    }
+    edits.apply().await.with_context(|| {
+        format!(
+            "failed to write MCP servers to {}",
+            project_codex_dir.display()
+        )
+    })?;
+
+    let names: Vec<&str> = selected.iter().map(|server| server.name.as_str()).collect();
+    println!(
+        "Enabled {} in {}.",
+        names.join(", "),
+        project_codex_dir.join("config.toml").display()
+    );
+    println!("Project config is only loaded once the project is trusted.");
+    Ok(())
+}
+
+fn describe_transport(transport: &McpServerTransportConfig) -> String {
+    match transport {
+        McpServerTransportConfig::Stdio { command, args, .. } => std::iter::once(command.as_str())
+            .chain(args.iter().map(String::as_str))
+            .collect::<Vec<_>>()
+            .join(" "),
+        McpServerTransportConfig::StreamableHttp { url, .. } => url.clone(),
+    }
+}
+
 async fn run_list(config_overrides: &CliConfigOverrides, list_args: ListArgs) -> Result<()> {
     let overrides = config_overrides
         .parse_overrides()
@@ -0,0 +1,999 @@
+use std::collections::HashSet;
+use std::io::IsTerminal;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::anyhow;
+use anyhow::bail;
+use codex_core::ARCHIVED_SESSIONS_SUBDIR;
+use codex_core::INTERACTIVE_SESSION_SOURCES;
+use codex_core::ImportFormat;
+use codex_core::RolloutRecorder;
+use codex_core::SESSIONS_SUBDIR;
+use codex_core::SessionStats;
+use codex_core::SessionTranscript;
+use codex_core::TaskOutcome;
+use codex_core::ThreadItem;
+use codex_core::ThreadSortKey;
+use codex_core::append_thread_name;
+use codex_core::config::Config;
+use codex_core::config::find_codex_home;
+use codex_core::describe_estimated_cost;
+use codex_core::find_thread_name_by_id;
+use codex_core::find_thread_names_by_ids;
+use codex_core::find_thread_path_by_reference;
+use codex_core::load_dataset_examples;
+use codex_core::load_session_stats;
+use codex_core::load_transcript;
+use codex_core::parse_transcript;
+use codex_core::read_session_meta_line;
+use codex_core::state_db::get_state_db;
+use codex_core::write_imported_rollout;
+use codex_protocol::num_format::format_with_separators;
+use codex_protocol::protocol::SessionMetaLine;
+use codex_utils_elapsed::format_duration;
+use serde_json::json;
+use toml::Value as TomlValue;
+
+/// Threads requested per page while listing.
+const LIST_PAGE_SIZE: usize = 50;
+/// Longest preview of the first user message shown by `list`.
+const PREVIEW_MAX_CHARS: usize = 60;
+
+/// Subcommands:
+/// - `list` — list saved sessions (with `--json`)
+/// - `show` — print a session's metadata and conversation (with `--json`)
+/// - `delete` — delete saved sessions
+/// - `export` — write one session, or many with `--out`, as Markdown, HTML, JSON, or the raw
+///   rollout
+/// - `import` — save a conversation exported from another agent tool as a session
+/// - `dataset` — write the tasks in saved sessions as JSONL examples for evals or fine-tuning
+/// - `stats` — summarize a saved session (with `--json`)
+#[derive(Debug, clap::Parser)]
+pub struct SessionsCli {
+    #[command(subcommand)]
+    pub subcommand: SessionsSubcommand,
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum SessionsSubcommand {
+    /// List saved sessions, most recently updated first.
+    List(ListArgs),
+
+    /// Show a saved session's metadata and conversation.
+    Show(ShowArgs),
+
+    /// Delete saved sessions.
+    Delete(DeleteArgs),
+
+    /// Export saved sessions as Markdown, HTML, JSON, or raw rollout files.
+    Export(ExportArgs),
+
+    /// Import a conversation exported from another agent tool as a saved session.
+    Import(ImportArgs),
+
+    /// Export the tasks in saved sessions as JSONL chat examples (messages with tool calls)
+    /// for evals or fine-tuning.
+    Dataset(DatasetArgs),
+
+    /// Show turns, token usage, tool calls, and estimated cost for a saved session.
+    Stats(StatsArgs),
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct ListArgs {
+    /// Maximum number of sessions to list.
+    #[arg(long, short = 'n', default_value_t = 20)]
+    pub limit: usize,
+
+    /// Include sessions started in any directory, not just the current one.
+    #[arg(long)]
+    pub all: bool,
+
+    /// Only list sessions whose first message contains this text.
+    #[arg(long, value_name = "TEXT")]
+    pub search: Option<String>,
+
+    /// Output the sessions as JSON.
+    #[arg(long)]
+    pub json: bool,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct ShowArgs {
+    /// Session id, thread name, or path to a rollout file.
+    #[arg(value_name = "SESSION")]
+    pub session: String,
+
+    /// Output the session as JSON.
+    #[arg(long)]
+    pub json: bool,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct DeleteArgs {
+    /// Session ids, thread names, or paths to rollout files.
+    #[arg(value_name = "SESSION", required = true)]
+    pub sessions: Vec<String>,
+
+    /// Delete without asking for confirmation.
+    #[arg(long, short = 'y')]
+    pub yes: bool,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct ExportArgs {
+    /// Session id, thread name, or path to a rollout file. Omit it and pass `--out` to export
+    /// many sessions at once.
+    #[arg(value_name = "SESSION", required_unless_present = "out")]
+    pub session: Option<String>,
+
+    /// Output format.
+    #[arg(long, value_enum, default_value_t = ExportFormat::Markdown)]
+    pub format: ExportFormat,
+
+    /// Write the session to this file instead of stdout.
+    #[arg(long, short = 'o', value_name = "FILE", conflicts_with = "out")]
+    pub output: Option<PathBuf>,
+
+    /// Export every saved session into this directory, one file per session.
+    #[arg(long, value_name = "DIR", conflicts_with = "session")]
+    pub out: Option<PathBuf>,
+
+    /// With `--out`, only export sessions updated within this long ago (`30m`, `12h`, `7d`, `2w`).
+    #[arg(long, value_name = "AGE", requires = "out", value_parser = parse_age)]
+    pub since: Option<chrono::Duration>,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct ImportArgs {
+    /// Exported conversation: an OpenAI-style messages JSON file or a Claude Code session log.
+    #[arg(value_name = "FILE")]
+    pub file: PathBuf,
+
+    /// Format of the file.
+    #[arg(long, value_enum, default_value_t = ImportSource::Auto)]
+    pub format: ImportSource,
+
+    /// Thread name for the imported session.
+    #[arg(long)]
+    pub name: Option<String>,
+}
+
+#[derive(Debug, clap::Parser)]
+pub struct DatasetArgs {
+    /// Session ids, thread names, or paths to rollout files. Omit them to export every saved
+    /// session.
+    #[arg(value_name = "SESSION")]
+    pub sessions: Vec<String>,
+
+    /// Without sessions, only export sessions updated within this long ago (`30m`, `12h`, `7d`,
+    /// `2w`).
+    #[arg(long, value_name = "AGE", conflicts_with = "sessions", value_parser = parse_age)]
+    pub since: Option<chrono::Duration>,
+
+    /// Only export tasks that ran to completion, dropping interrupted and unfinished ones.
+    #[arg(long)]
+    pub successful_only: bool,
+
+    /// Write the examples to this file instead of stdout.
+    #[arg(long, short = 'o', value_name = "FILE")]
+    pub output: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ImportSource {
+    /// Detect the format from the file contents.
+    Auto,
+    /// A JSON array of `{ "role", "content" }` messages, or an object with `messages`.
+    Openai,
+    /// A Claude Code session log (`~/.claude/projects/<project>/<session>.jsonl`).
+    ClaudeCode,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ExportFormat {
+    /// The conversation as a Markdown document.
+    #[value(alias = "md")]
+    Markdown,
+    /// The conversation as a standalone HTML page.
+    Html,
+    /// Session metadata and turns as JSON.
+    Json,
+    /// A copy of the rollout file.
+    Jsonl,
+}
+
+impl ExportFormat {
+    fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Markdown => "md",
+            ExportFormat::Html => "html",
+            ExportFormat::Json => "json",
+            ExportFormat::Jsonl => "jsonl",
+        }
+    }
+}
+
+impl SessionsCli {
+    pub async fn run(self, cli_kv_overrides: Vec<(String, TomlValue)>) -> Result<()> {
+        match self.subcommand {
+            SessionsSubcommand::List(args) => run_list(args, cli_kv_overrides).await,
+            SessionsSubcommand::Show(args) => run_show(args).await,
+            SessionsSubcommand::Delete(args) => run_delete(args, cli_kv_overrides).await,
+            SessionsSubcommand::Export(args) => match args.out.clone() {
+                Some(out) => run_bulk_export(args, out, cli_kv_overrides).await,
+                None => run_export(args).await,
+            },
+            SessionsSubcommand::Import(args) => run_import(args).await,
+            SessionsSubcommand::Dataset(args) => run_dataset(args, cli_kv_overrides).await,
+            SessionsSubcommand::Stats(args) => run_stats(args).await,
+        }
+    }
+}
+
+/// A session resolved from a user-supplied reference, with everything `show` and `export` need.
+struct LoadedSession {
+    meta: SessionMetaLine,
+    name: Option<String>,
+    transcript: SessionTranscript,
+}
+
+async fn resolve_session(codex_home: &Path, reference: &str) -> Result<PathBuf> {
+    let cwd = std::env::current_dir().context("failed to resolve current directory")?;
+    find_thread_path_by_reference(codex_home, &cwd, reference)
+        .await
+        .with_context(|| format!("failed to look up session `{reference}`"))?
+        .ok_or_else(|| anyhow!("No saved session matches `{reference}`."))
+}
+
+async fn load_session(codex_home: &Path, path: &Path) -> Result<LoadedSession> {
+    let meta = read_session_meta_line(path)
+        .await
+        .with_context(|| format!("failed to read {}", path.display()))?;
+    let name = find_thread_name_by_id(codex_home, &meta.meta.id)
+        .await
+        .unwrap_or_default();
+    let transcript = load_transcript(path)
+        .await
+        .with_context(|| format!("failed to read {}", path.display()))?;
+    Ok(LoadedSession {
+        meta,
+        name,
+        transcript,
+    })
+}
+
+async fn run_list(args: ListArgs, cli_kv_overrides: Vec<(String, TomlValue)>) -> Result<()> {
+    let config = Config::load_with_cli_overrides(cli_kv_overrides)
+        .await
+        .context("failed to load config")?;
+    let cwd = std::env::current_dir().context("failed to resolve current directory")?;
+
+    let mut items: Vec<ThreadItem> = Vec::new();
+    let mut cursor = None;
+    while items.len() < args.limit {
+        let page = RolloutRecorder::list_threads(
+            &config,
+            LIST_PAGE_SIZE,
+            cursor.as_ref(),
+            ThreadSortKey::UpdatedAt,
+            INTERACTIVE_SESSION_SOURCES,
+            None,
+            &config.model_provider_id,
+            args.search.as_deref(),
+        )
+        .await
+        .context("failed to list sessions")?;
+        items.extend(
+            page.items
+                .into_iter()
+                .filter(|item| args.all || item.cwd.as_deref() == Some(cwd.as_path())),
+        );
+        match page.next_cursor {
+            Some(next) => cursor = Some(next),
+            None => break,
+        }
+    }
+    items.truncate(args.limit);
+
+    let ids: HashSet<_> = items.iter().filter_map(|item| item.thread_id).collect();
+    let names = find_thread_names_by_ids(&config.codex_home, &ids)
+        .await
+        .unwrap_or_default();
+    let name_of = |item: &ThreadItem| item.thread_id.and_then(|id| names.get(&id).cloned());
+
+    if args.json {
+        let sessions: Vec<_> = items
+            .iter()
+            .map(|item| {
+                json!({
+                    "id": item.thread_id.map(|id| id.to_string()),
+                    "name": name_of(item),
+                    "path": item.path,
+                    "cwd": item.cwd,
+                    "git_branch": item.git_branch,
+                    "created_at": item.created_at,
+                    "updated_at": item.updated_at,
+                    "first_user_message": item.first_user_message,
+                })
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&sessions)?);
+        return Ok(());
+    }
+
+    if items.is_empty() {
+        if args.all {
+            println!("No saved sessions.");
+        } else {
+            println!("No saved sessions in this directory. Use --all to list every session.");
+        }
+        return Ok(());
+    }
+    for item in &items {
+        let id = item
+            .thread_id
+            .map(|id| id.to_string())
+            .unwrap_or_else(|| "-".to_string());
+        let updated = item.updated_at.as_deref().unwrap_or("-");
+        let title = name_of(item)
+            .or_else(|| item.first_user_message.as_deref().map(preview))
+            .unwrap_or_default();
+        match item.cwd.as_deref().filter(|_| args.all) {
+            Some(dir) => println!("{updated}  {id}  {title}  ({})", dir.display()),
+            None => println!("{updated}  {id}  {title}"),
+        }
+    }
+    Ok(())
+}
+
+async fn run_show(args: ShowArgs) -> Result<()> {
+    let codex_home = find_codex_home().context("failed to resolve CODEX_HOME")?;
+    let path = resolve_session(&codex_home, &args.session).await?;
+    let session = load_session(&codex_home, &path).await?;
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&session_json(&session))?);
+    } else {
+        print!("{}", session_markdown(&session));
+    }
+    Ok(())
+}
+
+async fn run_export(args: ExportArgs) -> Result<()> {
+    let codex_home = find_codex_home().context("failed to resolve CODEX_HOME")?;
+    let reference = args
+        .session
+        .ok_or_else(|| anyhow!("pass a session to export, or --out DIR to export many"))?;
+    let path = resolve_session(&codex_home, &reference).await?;
+    let contents = render_export(&codex_home, &path, args.format).await?;
+    match args.output {
+        Some(output) => {
+            tokio::fs::write(&output, contents)
+                .await
+                .with_context(|| format!("failed to write {}", output.display()))?;
+            eprintln!("Exported {} to {}", path.display(), output.display());
+        }
+        None => print!("{contents}"),
+    }
+    Ok(())
+}
+
+async fn run_bulk_export(
+    args: ExportArgs,
+    out: PathBuf,
+    cli_kv_overrides: Vec<(String, TomlValue)>,
+) -> Result<()> {
+    let config = Config::load_with_cli_overrides(cli_kv_overrides)
+        .await
+        .context("failed to load config")?;
+    let cutoff = args
+        .since
+        .and_then(|age| chrono::Utc::now().checked_sub_signed(age));
+    tokio::fs::create_dir_all(&out)
+        .await
+        .with_context(|| format!("failed to create {}", out.display()))?;
+
+    let mut exported = 0usize;
+    let mut failed = 0usize;
+    for path in saved_session_paths(&config, cutoff).await? {
+        let Some(stem) = path.file_stem() else {
+            continue;
+        };
+        let target = out.join(stem).with_extension(args.format.extension());
+        let result = match render_export(&config.codex_home, &path, args.format).await {
+            Ok(contents) => tokio::fs::write(&target, contents)
+                .await
+                .with_context(|| format!("failed to write {}", target.display())),
+            Err(err) => Err(err),
+        };
+        match result {
+            Ok(()) => exported += 1,
+            Err(err) => {
+                eprintln!("warning: skipped {}: {err:#}", path.display());
+                failed += 1;
+            }
+        }
+    }
+
+    println!("Exported {exported} session(s) to {}", out.display());
+    if failed > 0 {
+        bail!("{failed} session(s) could not be exported");
+    }
+    Ok(())
+}
+
+/// Rollout files of every saved session, most recently updated first, stopping at the first
+/// one last updated before `cutoff`.
+async fn saved_session_paths(
+    config: &Config,
+    cutoff: Option<chrono::DateTime<chrono::Utc>>,
+) -> Result<Vec<PathBuf>> {
+    let mut paths = Vec::new();
+    let mut cursor = None;
+    loop {
+        let page = RolloutRecorder::list_threads(
+            config,
+            LIST_PAGE_SIZE,
+            cursor.as_ref(),
+            ThreadSortKey::UpdatedAt,
+            INTERACTIVE_SESSION_SOURCES,
+            None,
+            &config.model_provider_id,
+            None,
+        )
+        .await
+        .context("failed to list sessions")?;
+        for item in page.items {
+            // Pages are ordered by update time, so the first session past the cutoff ends the scan.
+            if let Some(cutoff) = cutoff
+                && updated_before(&item, cutoff)
+            {
+                return Ok(paths);
+            }
+            paths.push(item.path);
+        }
+        match page.next_cursor {
+            Some(next) => cursor = Some(next),
+            None => return Ok(paths),
+        }
+    }
+}
+
+async fn run_dataset(args: DatasetArgs, cli_kv_overrides: Vec<(String, TomlValue)>) -> Result<()> {
+    let config = Config::load_with_cli_overrides(cli_kv_overrides)
+        .await
+        .context("failed to load config")?;
+    let paths = if args.sessions.is_empty() {
+        let cutoff = args
+            .since
+            .and_then(|age| chrono::Utc::now().checked_sub_signed(age));
+        saved_session_paths(&config, cutoff).await?
+    } else {
+        let mut paths = Vec::new();
+        for reference in &args.sessions {
+            paths.push(resolve_session(&config.codex_home, reference).await?);
+        }
+        paths
+    };
+
+    let mut out = String::new();
+    let mut examples = 0usize;
+    let mut sessions = 0usize;
+    for path in &paths {
+        let tasks = match load_dataset_examples(path).await {
+            Ok(tasks) => tasks,
+            Err(err) => {
+                eprintln!("warning: skipped {}: {err}", path.display());
+                continue;
+            }
+        };
+        let before = examples;
+        for example in tasks.into_iter().filter(|example| {
+            !args.successful_only || example.metadata.outcome == TaskOutcome::Completed
+        }) {
+            out.push_str(&serde_json::to_string(&example)?);
+            out.push('\n');
+            examples += 1;
+        }
+        if examples > before {
+            sessions += 1;
+        }
+    }
+
+    match args.output {
+        Some(output) => {
+            tokio::fs::write(&output, out)
+                .await
+                .with_context(|| format!("failed to write {}", output.display()))?;
+            eprintln!(
+                "Wrote {examples} example(s) from {sessions} session(s) to {}",
+                output.display()
+            );
+        }
+        None => print!("{out}"),
+    }
+    Ok(())
+}
+
+/// Render one session in `format`; the single-session and bulk exports share this.
+async fn render_export(codex_home: &Path, path: &Path, format: ExportFormat) -> Result<String> {
+    if format == ExportFormat::Jsonl {
+        return tokio::fs::read_to_string(path)
+            .await
+            .with_context(|| format!("failed to read {}", path.display()));
+    }
+    let session = load_session(codex_home, path).await?;
+    Ok(match format {
+        ExportFormat::Markdown => session_markdown(&session),
+        ExportFormat::Html => session_html(&session),
+        ExportFormat::Json | ExportFormat::Jsonl => format!(
+            "{}\n",
+            serde_json::to_string_pretty(&session_json(&session))?
+        ),
+    })
+}
+
+fn updated_before(item: &ThreadItem, cutoff: chrono::DateTime<chrono::Utc>) -> bool {
+    item.updated_at
+        .as_deref()
+        .or(item.created_at.as_deref())
+        .and_then(|timestamp| chrono::DateTime::parse_from_rfc3339(timestamp).ok())
+        .is_some_and(|timestamp| timestamp < cutoff)
+}
+
+/// Parse an age such as `30m`, `12h`, `7d`, or `2w`.
+fn parse_age(value: &str) -> Result<chrono::Duration, String> {
+    let value = value.trim();
+    let split = value
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(value.len());
+    let (amount, unit) = value.split_at(split);
+    let amount: i64 = amount
+        .parse()
+        .map_err(|_| format!("invalid age `{value}`; expected e.g. 12h, 7d, or 2w"))?;
+    let age = match unit {
+        "m" => chrono::Duration::try_minutes(amount),
+        "h" => chrono::Duration::try_hours(amount),
+        "d" => chrono::Duration::try_days(amount),
+        "w" => chrono::Duration::try_weeks(amount),
+        _ => {
+            return Err(format!(
+                "invalid age `{value}`; use m, h, d, or w as the unit"
+            ));
+        }
+    };
+    age.ok_or_else(|| format!("age `{value}` is too large"))
+}
+
+async fn run_import(args: ImportArgs) -> Result<()> {
+    let codex_home = find_codex_home().context("failed to resolve CODEX_HOME")?;
+    let cwd = std::env::current_dir().context("failed to resolve current directory")?;
+    let contents = tokio::fs::read_to_string(&args.file)
+        .await
+        .with_context(|| format!("failed to read {}", args.file.display()))?;
+    let format = match args.format {
+        ImportSource::Auto => ImportFormat::Auto,
+        ImportSource::Openai => ImportFormat::OpenAiMessages,
+        ImportSource::ClaudeCode => ImportFormat::ClaudeCode,
+    };
+    let conversation = parse_transcript(&contents, format)
+        .with_context(|| format!("failed to parse {}", args.file.display()))?;
+    let (thread_id, path) = write_imported_rollout(&codex_home, &conversation, &cwd)
+        .await
+        .context("failed to write the imported session")?;
+    if let Some(name) = args.name.as_deref() {
+        append_thread_name(&codex_home, thread_id, name)
+            .await
+            .context("failed to name the imported session")?;
+    }
+    println!(
+        "Imported {} messages as {thread_id} ({}).",
+        conversation.messages.len(),
+        path.display()
+    );
+    println!("Resume it with `codex resume {thread_id}`.");
+    Ok(())
+}
+
+async fn run_delete(args: DeleteArgs, cli_kv_overrides: Vec<(String, TomlValue)>) -> Result<()> {
+    let config = Config::load_with_cli_overrides(cli_kv_overrides)
+        .await
+        .context("failed to load config")?;
+    let mut targets = Vec::new();
+    for reference in &args.sessions {
+        let path = resolve_session(&config.codex_home, reference).await?;
+        ensure_in_sessions_dir(&config.codex_home, &path).await?;
+        let meta = read_session_meta_line(&path)
+            .await
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        targets.push((meta.meta.id, path));
+    }
+
+    if !args.yes {
+        if !std::io::stdin().is_terminal() {
+            bail!("refusing to delete sessions without confirmation; pass --yes");
+        }
+        for (id, path) in &targets {
+            eprintln!("{id}  {}", path.display());
+        }
+        eprint!("Delete {} session(s)? [y/N] ", targets.len());
+        std::io::stderr().flush()?;
+        let mut answer = String::new();
+        std::io::stdin().read_line(&mut answer)?;
+        if !matches!(answer.trim(), "y" | "Y" | "yes") {
+            eprintln!("Aborted.");
+            return Ok(());
+        }
+    }
+
+    let state_db = get_state_db(&config, None).await;
+    for (id, path) in targets {
+        tokio::fs::remove_file(&path)
+            .await
+            .with_context(|| format!("failed to delete {}", path.display()))?;
+        if let Some(state_db) = state_db.as_ref()
+            && let Err(err) = state_db.delete_thread(id).await
+        {
+            eprintln!("warning: failed to remove {id} from the state database: {err}");
+        }
+        println!("Deleted {id}");
+    }
+    Ok(())
+}
+
+/// Only rollout files Codex manages may be deleted, never an arbitrary path given on the command line.
+async fn ensure_in_sessions_dir(codex_home: &Path, path: &Path) -> Result<()> {
+    let path = tokio::fs::canonicalize(path)
+        .await
+        .with_context(|| format!("failed to resolve {}", path.display()))?;
+    for subdir in [SESSIONS_SUBDIR, ARCHIVED_SESSIONS_SUBDIR] {
+        if let Ok(root) = tokio::fs::canonicalize(codex_home.join(subdir)).await
+            && path.starts_with(&root)
+        {
+            return Ok(());
+        }
+    }
+    bail!(
+        "{} is not in {}; only saved sessions can be deleted",
+        path.display(),
+        codex_home.join(SESSIONS_SUBDIR).display()
+    )
+}
+
+fn preview(message: &str) -> String {
+    let line = message.lines().next().unwrap_or_default().trim();
+    if line.chars().count() > PREVIEW_MAX_CHARS {
+        let truncated: String = line.chars().take(PREVIEW_MAX_CHARS - 1).collect();
+        format!("{truncated}…")
+    } else {
+        line.to_string()
+    }
+}
+
+fn session_json(session: &LoadedSession) -> serde_json::Value {
+    let meta = &session.meta.meta;
+    json!({
+        "id": meta.id.to_string(),
+        "name": session.name,
+        "path": session.transcript.path,
+        "cwd": meta.cwd,
+        "created_at": meta.timestamp,
+        "git_branch": session.meta.git.as_ref().and_then(|git| git.branch.clone()),
+        "model": session.transcript.model,
+        "turns": session.transcript.turns.iter().map(|turn| json!({
+            "prompt": turn.prompt,
+            "responses": turn.responses,
+            "patches": turn.patches,
+        })).collect::<Vec<_>>(),
+    })
+}
+
+fn session_html(session: &LoadedSession) -> String {
+    let meta = &session.meta.meta;
+    let title = html_escape(session.name.as_deref().unwrap_or("Codex session"));
+    let mut out = format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{title}</title>\n\
+         <style>body{{font-family:sans-serif;max-width:60rem;margin:2rem auto}}\
+         pre{{white-space:pre-wrap;background:#f6f8fa;padding:.75rem}}</style>\n\
+         </head>\n<body>\n<h1>{title}</h1>\n<ul>\n"
+    );
+    out.push_str(&format!("<li>Session: <code>{}</code></li>\n", meta.id));
+    out.push_str(&format!(
+        "<li>Started: {}</li>\n",
+        html_escape(&meta.timestamp)
+    ));
+    out.push_str(&format!(
+        "<li>Directory: <code>{}</code></li>\n",
+        html_escape(&meta.cwd.display().to_string())
+    ));
+    if let Some(branch) = session
+        .meta
+        .git
+        .as_ref()
+        .and_then(|git| git.branch.as_deref())
+    {
+        out.push_str(&format!(
+            "<li>Branch: <code>{}</code></li>\n",
+            html_escape(branch)
+        ));
+    }
+    if let Some(model) = &session.transcript.model {
+        out.push_str(&format!("<li>Model: {}</li>\n", html_escape(model)));
+    }
+    out.push_str("</ul>\n");
+    for (index, turn) in session.transcript.turns.iter().enumerate() {
+        out.push_str(&format!(
+            "<h2>Turn {}</h2>\n<h3>User</h3>\n<pre>{}</pre>\n",
+            index + 1,
+            html_escape(turn.prompt.trim_end())
+        ));
+        for response in &turn.responses {
+            out.push_str(&format!(
+                "<h3>Codex</h3>\n<pre>{}</pre>\n",
+                html_escape(response.trim_end())
+            ));
+        }
+        for patch in &turn.patches {
+            out.push_str(&format!(
+                "<pre class=\"patch\">{}</pre>\n",
+                html_escape(patch.trim_end())
+            ));
+        }
+    }
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+fn html_escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+fn session_markdown(session: &LoadedSession) -> String {
+    let meta = &session.meta.meta;
+    let mut out = format!(
+        "# {}\n\n",
+        session.name.as_deref().unwrap_or("Codex session")
+    );
+    out.push_str(&format!("- Session: `{}`\n", meta.id));
+    out.push_str(&format!("- Started: {}\n", meta.timestamp));
+    out.push_str(&format!("- Directory: `{}`\n", meta.cwd.display()));
+    if let Some(branch) = session
+        .meta
+        .git
+        .as_ref()
+        .and_then(|git| git.branch.as_deref())
+    {
+        out.push_str(&format!("- Branch: `{branch}`\n"));
+    }
+    if let Some(model) = &session.transcript.model {
+        out.push_str(&format!("- Model: {model}\n"));
+    }
+    for (index, turn) in session.transcript.turns.iter().enumerate() {
+        out.push_str(&format!(
+            "\n## Turn {}\n\n**User**\n\n{}\n",
+            index + 1,
+            turn.prompt.trim_end()
+        ));
+        for response in &turn.responses {
+            out.push_str(&format!("\n**Codex**\n\n{}\n", response.trim_end()));
+        }
+        for patch in &turn.patches {
+            out.push_str(&format!("\n```diff\n{}\n```\n", patch.trim_end()));
+        }
+    }
+    out
+}
+
+async fn run_stats(args: StatsArgs) -> Result<()> {
+    let codex_home = find_codex_home().context("failed to resolve CODEX_HOME")?;
+    let path = resolve_session(&codex_home, &args.session).await?;
+    let stats = load_session_stats(&path)
+        .await
+        .with_context(|| format!("failed to read {}", path.display()))?;
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&stats)?);
+        return Ok(());
+    }
+
+    for line in format_stats(&stats) {
+        println!("{line}");
+    }
+    Ok(())
+}
+
+fn format_stats(stats: &SessionStats) -> Vec<String> {
+    let usage = &stats.token_usage;
+    let mut lines = vec![
+        format!("Session:     {}", stats.path.display()),
+        format!(
+            "Model:       {}",
+            stats.model.as_deref().unwrap_or("unknown")
+        ),
+        format!("Turns:       {}", stats.turns),
+        format!(
+            "Wall time:   {}",
+            stats
+                .wall_time
+                .map(format_duration)
+                .unwrap_or_else(|| "unknown".to_string())
+        ),
+        format!(
+            "Tokens:      {} total ({} input, {} cached, {} output, {} reasoning)",
+            format_with_separators(usage.total_tokens),
+            format_with_separators(usage.non_cached_input()),
+            format_with_separators(usage.cached_input()),
+            format_with_separators(usage.output_tokens),
+            format_with_separators(usage.reasoning_output_tokens),
+        ),
+        format!(
+            "Exec:        {} succeeded, {} failed",
+            stats.exec_succeeded, stats.exec_failed
+        ),
+        format!(
+            "Est. cost:   {}",
+            describe_estimated_cost(stats.estimated_cost_usd)
+        ),
+    ];
+    if stats.tool_calls.is_empty() {
+        lines.push("Tool calls:  none".to_string());
+    } else {
+        let total: usize = stats.tool_calls.values().sum();
+        lines.push(format!("Tool calls:  {total}"));
+        let name_width = stats.tool_calls.keys().map(String::len).max().unwrap_or(0);
+        for (name, count) in &stats.tool_calls {
+            lines.push(format!("  {name:<name_width$}  {count}"));
+        }
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_core::TranscriptTurn;
+    use codex_protocol::ThreadId;
+    use codex_protocol::protocol::GitInfo;
+    use codex_protocol::protocol::SessionMeta;
+    use codex_protocol::protocol::TokenUsage;
+    use pretty_assertions::assert_eq;
+    use std::collections::BTreeMap;
+    use std::time::Duration;
+
+    #[test]
+    fn parses_ages() {
+        assert_eq!(parse_age("30m"), Ok(chrono::Duration::minutes(30)));
+        assert_eq!(parse_age("7d"), Ok(chrono::Duration::days(7)));
+        assert_eq!(parse_age("2w"), Ok(chrono::Duration::weeks(2)));
+        assert!(parse_age("7").is_err());
+        assert!(parse_age("d").is_err());
+        assert!(parse_age("3y").is_err());
+    }
+
+    #[test]
+    fn html_escape_neutralizes_markup() {
+        assert_eq!(
+            html_escape("<script>alert(\"a & b\")</script>"),
+            "&lt;script&gt;alert(&quot;a &amp; b&quot;)&lt;/script&gt;"
+        );
+    }
+
+    #[test]
+    fn preview_uses_first_line_and_truncates() {
+        assert_eq!(preview("fix the build\nplease"), "fix the build");
+        let long = "x".repeat(PREVIEW_MAX_CHARS + 10);
+        assert_eq!(preview(&long).chars().count(), PREVIEW_MAX_CHARS);
+        assert!(preview(&long).ends_with('…'));
+    }
+
+    #[test]
+    fn session_markdown_includes_metadata_and_turns() {
+        let session = LoadedSession {
+            meta: SessionMetaLine {
+                meta: SessionMeta {
+                    id: ThreadId::from_string("67e55044-10b1-426f-9247-bb680e5fe0c8")
+                        .expect("thread id"),
+                    timestamp: "2025-06-01T10:00:00Z".to_string(),
+                    cwd: PathBuf::from("/repo"),
+                    ..Default::default()
+                },
+                git: Some(GitInfo {
+                    commit_hash: None,
+                    branch: Some("main".to_string()),
+                    repository_url: None,
+                }),
+            },
+            name: Some("Fix the build".to_string()),
+            transcript: SessionTranscript {
+                path: PathBuf::from("rollout.jsonl"),
+                model: Some("gpt-5".to_string()),
+                turns: vec![TranscriptTurn {
+                    prompt: "why does cargo fail?".to_string(),
+                    responses: vec!["A missing import.".to_string()],
+                    patches: vec!["*** Begin Patch\n*** End Patch".to_string()],
+                }],
+            },
+        };
+
+        assert_eq!(
+            session_markdown(&session),
+            "# Fix the build
+
+- Session: `67e55044-10b1-426f-9247-bb680e5fe0c8`
+- Started: 2025-06-01T10:00:00Z
+- Directory: `/repo`
+- Branch: `main`
+- Model: gpt-5
+
+## Turn 1
+
+**User**
+
+why does cargo fail?
+
+**Codex**
+
+A missing import.
+
+```diff
+*** Begin Patch
+*** End Patch
+```
+"
+        );
+    }
+
+    #[test]
+    fn format_stats_lists_every_field() {
+        let stats = SessionStats {
+            path: PathBuf::from("rollout.jsonl"),
+            model: Some("gpt-5".to_string()),
+            turns: 3,
+            token_usage: TokenUsage {
+                input_tokens: 12_000,
+                cached_input_tokens: 2_000,
+                output_tokens: 1_500,
+                reasoning_output_tokens: 500,
+                total_tokens: 13_500,
+            },
+            tool_calls: BTreeMap::from([
+                ("apply_patch".to_string(), 1),
+                ("shell_command".to_string(), 4),
+            ]),
+            exec_succeeded: 3,
+            exec_failed: 1,
+            wall_time: Some(Duration::from_secs(95)),
+            estimated_cost_usd: Some(0.0278),
+        };
+
+        assert_eq!(
+            format_stats(&stats),
+            vec![
+                "Session:     rollout.jsonl",
+                "Model:       gpt-5",
+                "Turns:       3",
+                "Wall time:   1m 35s",
+                "Tokens:      13,500 total (10,000 input, 2,000 cached, 1,500 output, 500 reasoning)",
+                "Exec:        3 succeeded, 1 failed",
+                "Est. cost:   ~$0.03 (list prices as of 2025-12)",
+                "Tool calls:  5",
+                "  apply_patch    1",
+                "  shell_command  4",
+            ]
+        );
+    }
+}
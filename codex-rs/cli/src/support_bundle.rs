@@ -0,0 +1,126 @@
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::anyhow;
+use codex_core::config::Config;
+use codex_core::config::ConfigOverrides;
+use codex_utils_cli::CliConfigOverrides;
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+use zip::ZipWriter;
+use zip::write::SimpleFileOptions;
+
+#[derive(Debug, clap::Parser)]
+pub struct BundleCommand {
+    /// Where to write the archive. Defaults to `codex-support-<timestamp>.zip` in the current
+    /// directory.
+    #[arg(long, short = 'o', value_name = "FILE")]
+    pub output: Option<PathBuf>,
+
+    #[clap(skip)]
+    pub config_overrides: CliConfigOverrides,
+}
+
+/// Zip the API wire log (`debug.api_log_path`) together with version details so it can be
+/// attached to a support request.
+pub async fn run_bundle_command(cmd: BundleCommand, config_profile: Option<String>) -> Result<()> {
+    let cli_kv_overrides = cmd
+        .config_overrides
+        .parse_overrides()
+        .map_err(anyhow::Error::msg)?;
+    let overrides = ConfigOverrides {
+        config_profile,
+        ..Default::default()
+    };
+    let config =
+        Config::load_with_cli_overrides_and_harness_overrides(cli_kv_overrides, overrides).await?;
+
+    let api_log_path = config.api_log_path.ok_or_else(|| {
+        anyhow!("No API wire log is configured. Set `debug.api_log_path` and reproduce the issue.")
+    })?;
+    if !api_log_path.as_path().exists() {
+        return Err(anyhow!(
+            "The API wire log {} does not exist yet. Reproduce the issue first.",
+            api_log_path.as_path().display()
+        ));
+    }
+
+    let created_at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default();
+    let output = cmd
+        .output
+        .unwrap_or_else(|| PathBuf::from(format!("codex-support-{created_at}.zip")));
+    write_support_bundle(&output, api_log_path.as_path(), created_at)?;
+    println!("Wrote support bundle to {}.", output.display());
+    println!("Review it before sharing: request and response bodies are included (size-capped).");
+    Ok(())
+}
+
+fn write_support_bundle(output: &Path, api_log_path: &Path, created_at: u64) -> Result<()> {
+    let file =
+        File::create(output).with_context(|| format!("failed to create {}", output.display()))?;
+    let mut zip = ZipWriter::new(file);
+    let options = SimpleFileOptions::default();
+
+    let manifest = serde_json::json!({
+        "codex_version": env!("CARGO_PKG_VERSION"),
+        "os": std::env::consts::OS,
+        "arch": std::env::consts::ARCH,
+        "created_at": created_at,
+    });
+    zip.start_file("manifest.json", options)?;
+    zip.write_all(serde_json::to_string_pretty(&manifest)?.as_bytes())?;
+
+    let api_log = std::fs::read(api_log_path)
+        .with_context(|| format!("failed to read {}", api_log_path.display()))?;
+    zip.start_file("api-log.jsonl", options)?;
+    zip.write_all(&api_log)?;
+
+    zip.finish()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::io::Read;
+
+    #[test]
+    fn support_bundle_contains_manifest_and_api_log() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let api_log_path = dir.path().join("api.jsonl");
+        std::fs::write(&api_log_path, "{\"kind\":\"request\"}\n").expect("write api log");
+        let output = dir.path().join("bundle.zip");
+
+        write_support_bundle(&output, &api_log_path, 42).expect("write bundle");
+
+        let mut archive =
+            zip::ZipArchive::new(File::open(&output).expect("open bundle")).expect("read zip");
+        let mut names: Vec<String> = archive.file_names().map(str::to_string).collect();
+        names.sort();
+        assert_eq!(names, vec!["api-log.jsonl", "manifest.json"]);
+
+        let mut api_log = String::new();
+        archive
+            .by_name("api-log.jsonl")
+            .expect("api log entry")
+            .read_to_string(&mut api_log)
+            .expect("read api log entry");
+        assert_eq!(api_log, "{\"kind\":\"request\"}\n");
+
+        let mut manifest = String::new();
+        archive
+            .by_name("manifest.json")
+            .expect("manifest entry")
+            .read_to_string(&mut manifest)
+            .expect("read manifest entry");
+        let manifest: serde_json::Value = serde_json::from_str(&manifest).expect("manifest json");
+        assert_eq!(manifest["created_at"], 42);
+    }
+}
@@ -6,6 +6,12 @@ use http::HeaderMap;
 use serde::Deserialize;
 use std::collections::BTreeSet;
 use std::fmt::Display;
+use std::time::Duration;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+/// Limit id used for snapshots built from the generic `x-ratelimit-*` provider headers.
+pub const PROVIDER_LIMIT_ID: &str = "provider";
 
 #[derive(Debug)]
 pub struct RateLimitError {
@@ -46,9 +52,100 @@ pub fn parse_all_rate_limits(headers: &HeaderMap) -> Vec<RateLimitSnapshot> {
         has_rate_limit_data(&snapshot).then_some(snapshot)
     }));
 
+    if let Some(snapshot) = parse_provider_rate_limit(headers) {
+        snapshots.push(snapshot);
+    }
+
     snapshots
 }
 
+/// Parses the `x-ratelimit-{limit,remaining,reset}-{requests,tokens}` headers sent by
+/// OpenAI-compatible providers. Requests map to the primary window and tokens to the secondary.
+pub fn parse_provider_rate_limit(headers: &HeaderMap) -> Option<RateLimitSnapshot> {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default();
+    let now = i64::try_from(now).unwrap_or(i64::MAX);
+    let primary = parse_provider_rate_limit_window(headers, "requests", now);
+    let secondary = parse_provider_rate_limit_window(headers, "tokens", now);
+    if primary.is_none() && secondary.is_none() {
+        return None;
+    }
+    Some(RateLimitSnapshot {
+        limit_id: Some(PROVIDER_LIMIT_ID.to_string()),
+        limit_name: Some("API".to_string()),
+        primary,
+        secondary,
+        credits: None,
+        plan_type: None,
+    })
+}
+
+/// Reads the delay requested by a throttled response from `retry-after-ms` or `retry-after`
+/// (delta-seconds form).
+pub fn parse_retry_after(headers: &HeaderMap) -> Option<Duration> {
+    if let Some(millis) = parse_header_f64(headers, "retry-after-ms").filter(|ms| *ms >= 0.0) {
+        return Some(Duration::from_secs_f64(millis / 1000.0));
+    }
+    parse_header_f64(headers, "retry-after")
+        .filter(|seconds| *seconds >= 0.0)
+        .map(Duration::from_secs_f64)
+}
+
+fn parse_provider_rate_limit_window(
+    headers: &HeaderMap,
+    kind: &str,
+    now: i64,
+) -> Option<RateLimitWindow> {
+    let limit = parse_header_f64(headers, &format!("x-ratelimit-limit-{kind}"))?;
+    let remaining = parse_header_f64(headers, &format!("x-ratelimit-remaining-{kind}"))?;
+    if limit <= 0.0 {
+        return None;
+    }
+    let used_percent = ((limit - remaining) / limit * 100.0).clamp(0.0, 100.0);
+    let resets_at = parse_header_str(headers, &format!("x-ratelimit-reset-{kind}"))
+        .and_then(parse_reset_duration)
+        .map(|reset| now.saturating_add(i64::try_from(reset.as_secs()).unwrap_or(i64::MAX)));
+    Some(RateLimitWindow {
+        used_percent,
+        window_minutes: None,
+        resets_at,
+    })
+}
+
+/// Parses reset durations such as `20ms`, `1s`, `6m0s`, or `1h2m3.5s`. A bare number is
+/// treated as seconds.
+fn parse_reset_duration(raw: &str) -> Option<Duration> {
+    let raw = raw.trim();
+    if let Ok(seconds) = raw.parse::<f64>() {
+        return (seconds >= 0.0).then(|| Duration::from_secs_f64(seconds));
+    }
+
+    let mut total = Duration::ZERO;
+    let mut rest = raw;
+    while !rest.is_empty() {
+        let number_len = rest
+            .find(|ch: char| !(ch.is_ascii_digit() || ch == '.'))
+            .unwrap_or(rest.len());
+        let value = rest[..number_len].parse::<f64>().ok()?;
+        rest = &rest[number_len..];
+        let unit_len = rest
+            .find(|ch: char| ch.is_ascii_digit() || ch == '.')
+            .unwrap_or(rest.len());
+        let seconds = match &rest[..unit_len] {
+            "ms" => value / 1000.0,
+            "s" => value,
+            "m" => value * 60.0,
+            "h" => value * 3600.0,
+            _ => return None,
+        };
+        total += Duration::from_secs_f64(seconds);
+        rest = &rest[unit_len..];
+    }
+    Some(total)
+}
+
 /// Parses rate-limit headers for the provided limit id.
 ///
 /// `limit_id` should match the server-provided metered limit id (e.g. `codex`,
@@ -351,6 +448,65 @@ mod tests {
         assert_eq!(updates[1].limit_name, None);
     }
 
+    #[test]
+    fn parse_provider_rate_limit_reads_requests_and_tokens() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "x-ratelimit-limit-requests",
+            HeaderValue::from_static("100"),
+        );
+        headers.insert(
+            "x-ratelimit-remaining-requests",
+            HeaderValue::from_static("25"),
+        );
+        headers.insert(
+            "x-ratelimit-reset-requests",
+            HeaderValue::from_static("1m30s"),
+        );
+        headers.insert("x-ratelimit-limit-tokens", HeaderValue::from_static("1000"));
+        headers.insert(
+            "x-ratelimit-remaining-tokens",
+            HeaderValue::from_static("1000"),
+        );
+
+        let snapshot = parse_provider_rate_limit(&headers).expect("snapshot");
+        assert_eq!(snapshot.limit_id.as_deref(), Some(PROVIDER_LIMIT_ID));
+        let primary = snapshot.primary.expect("primary");
+        assert_eq!(primary.used_percent, 75.0);
+        assert!(primary.resets_at.is_some());
+        let secondary = snapshot.secondary.expect("secondary");
+        assert_eq!(secondary.used_percent, 0.0);
+        assert_eq!(secondary.resets_at, None);
+    }
+
+    #[test]
+    fn parse_reset_duration_accepts_provider_formats() {
+        assert_eq!(
+            parse_reset_duration("20ms"),
+            Some(Duration::from_millis(20))
+        );
+        assert_eq!(parse_reset_duration("6m0s"), Some(Duration::from_secs(360)));
+        assert_eq!(
+            parse_reset_duration("1h2m3.5s"),
+            Some(Duration::from_secs_f64(3723.5))
+        );
+        assert_eq!(parse_reset_duration("12"), Some(Duration::from_secs(12)));
+        assert_eq!(parse_reset_duration("soon"), None);
+    }
+
+    #[test]
+    fn parse_retry_after_prefers_millisecond_header() {
+        let mut headers = HeaderMap::new();
+        headers.insert("retry-after", HeaderValue::from_static("7"));
+        assert_eq!(parse_retry_after(&headers), Some(Duration::from_secs(7)));
+
+        headers.insert("retry-after-ms", HeaderValue::from_static("1500"));
+        assert_eq!(
+            parse_retry_after(&headers),
+            Some(Duration::from_millis(1500))
+        );
+    }
+
     #[test]
     fn parse_all_rate_limits_includes_default_codex_snapshot() {
         let headers = HeaderMap::new();
@@ -266,6 +266,8 @@ async fn streaming_client_retries_on_transport_error() -> Result<()> {
         stream: true,
         include: Vec::new(),
         service_tier: None,
+        temperature: None,
+        top_p: None,
         prompt_cache_key: None,
         text: None,
     };
@@ -308,6 +310,8 @@ async fn azure_default_store_attaches_ids_and_headers() -> Result<()> {
         stream: true,
         include: Vec::new(),
         service_tier: None,
+        temperature: None,
+        top_p: None,
         prompt_cache_key: None,
         text: None,
     };
@@ -5,6 +5,7 @@ mod retry;
 mod sse;
 mod telemetry;
 mod transport;
+mod wire_log;
 
 pub use crate::default_client::CodexHttpClient;
 pub use crate::default_client::CodexRequestBuilder;
@@ -23,3 +24,5 @@ pub use crate::transport::ByteStream;
 pub use crate::transport::HttpTransport;
 pub use crate::transport::ReqwestTransport;
 pub use crate::transport::StreamResponse;
+pub use crate::wire_log::WIRE_LOG_MAX_BODY_BYTES;
+pub use crate::wire_log::WireLog;
@@ -4,6 +4,7 @@ use crate::error::TransportError;
 use crate::request::Request;
 use crate::request::RequestCompression;
 use crate::request::Response;
+use crate::wire_log::WireLog;
 use async_trait::async_trait;
 use bytes::Bytes;
 use futures::StreamExt;
@@ -11,6 +12,7 @@ use futures::stream::BoxStream;
 use http::HeaderMap;
 use http::Method;
 use http::StatusCode;
+use std::sync::Arc;
 use tracing::Level;
 use tracing::enabled;
 use tracing::trace;
@@ -32,12 +34,45 @@ pub trait HttpTransport: Send + Sync {
 #[derive(Clone, Debug)]
 pub struct ReqwestTransport {
     client: CodexHttpClient,
+    wire_log: Option<Arc<WireLog>>,
 }
 
 impl ReqwestTransport {
     pub fn new(client: reqwest::Client) -> Self {
         Self {
             client: CodexHttpClient::new(client),
+            wire_log: None,
+        }
+    }
+
+    /// Records every request and response made through this transport to `wire_log`.
+    pub fn with_wire_log(mut self, wire_log: Option<Arc<WireLog>>) -> Self {
+        self.wire_log = wire_log;
+        self
+    }
+
+    fn log_request(&self, req: &Request) -> Option<u64> {
+        self.wire_log.as_ref().map(|wire_log| {
+            wire_log.record_request(&req.method, &req.url, &req.headers, req.body.as_ref())
+        })
+    }
+
+    fn log_response(
+        &self,
+        log_id: Option<u64>,
+        url: &str,
+        status: StatusCode,
+        headers: &HeaderMap,
+        body: Option<&[u8]>,
+    ) {
+        if let (Some(wire_log), Some(id)) = (self.wire_log.as_ref(), log_id) {
+            wire_log.record_response(id, url, status, headers, body);
+        }
+    }
+
+    fn log_error(&self, log_id: Option<u64>, url: &str, err: &TransportError) {
+        if let (Some(wire_log), Some(id)) = (self.wire_log.as_ref(), log_id) {
+            wire_log.record_error(id, url, &err.to_string());
         }
     }
 
@@ -132,11 +167,21 @@ impl HttpTransport for ReqwestTransport {
         }
 
         let url = req.url.clone();
+        let log_id = self.log_request(&req);
         let builder = self.build(req)?;
-        let resp = builder.send().await.map_err(Self::map_error)?;
+        let resp = builder
+            .send()
+            .await
+            .map_err(Self::map_error)
+            .inspect_err(|err| self.log_error(log_id, &url, err))?;
         let status = resp.status();
         let headers = resp.headers().clone();
-        let bytes = resp.bytes().await.map_err(Self::map_error)?;
+        let bytes = resp
+            .bytes()
+            .await
+            .map_err(Self::map_error)
+            .inspect_err(|err| self.log_error(log_id, &url, err))?;
+        self.log_response(log_id, &url, status, &headers, Some(&bytes));
         if !status.is_success() {
             let body = String::from_utf8(bytes.to_vec()).ok();
             return Err(TransportError::Http {
@@ -164,12 +209,24 @@ impl HttpTransport for ReqwestTransport {
         }
 
         let url = req.url.clone();
+        let log_id = self.log_request(&req);
         let builder = self.build(req)?;
-        let resp = builder.send().await.map_err(Self::map_error)?;
+        let resp = builder
+            .send()
+            .await
+            .map_err(Self::map_error)
+            .inspect_err(|err| self.log_error(log_id, &url, err))?;
         let status = resp.status();
         let headers = resp.headers().clone();
         if !status.is_success() {
             let body = resp.text().await.ok();
+            self.log_response(
+                log_id,
+                &url,
+                status,
+                &headers,
+                body.as_deref().map(str::as_bytes),
+            );
             return Err(TransportError::Http {
                 status,
                 url: Some(url),
@@ -177,6 +234,7 @@ impl HttpTransport for ReqwestTransport {
                 body,
             });
         }
+        self.log_response(log_id, &url, status, &headers, None);
         let stream = resp
             .bytes_stream()
             .map(|result| result.map_err(Self::map_error));
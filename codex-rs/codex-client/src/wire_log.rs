@@ -0,0 +1,295 @@
+use http::HeaderMap;
+use http::Method;
+use http::StatusCode;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::fs::OpenOptions;
+use std::io;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+/// Bodies larger than this are cut off in the wire log.
+pub const WIRE_LOG_MAX_BODY_BYTES: usize = 64 * 1024;
+
+const REDACTED: &str = "[redacted]";
+
+/// Headers whose values carry credentials and are never written to the wire log.
+const SENSITIVE_HEADERS: &[&str] = &[
+    "authorization",
+    "proxy-authorization",
+    "cookie",
+    "set-cookie",
+    "api-key",
+    "x-api-key",
+    "chatgpt-account-id",
+];
+
+/// Query parameters that some providers use to pass API keys.
+const SENSITIVE_QUERY_PARAMS: &[&str] = &["key", "api_key", "api-key"];
+
+/// Appends sanitized HTTP exchanges to a JSONL file so provider errors can be diagnosed after
+/// the fact. Credentials are stripped from headers and URLs, and bodies are size-capped.
+///
+/// Only traffic through [`crate::ReqwestTransport`] is recorded; WebSocket sessions are not.
+#[derive(Debug)]
+pub struct WireLog {
+    path: PathBuf,
+    next_id: AtomicU64,
+    file: Mutex<File>,
+}
+
+#[derive(Serialize)]
+struct WireLogEntry<'a> {
+    timestamp_ms: u128,
+    id: u64,
+    kind: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    method: Option<&'a str>,
+    url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    status: Option<u16>,
+    headers: BTreeMap<String, String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    body: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<&'a str>,
+}
+
+impl WireLog {
+    /// Opens `path` for appending, creating it readable only by the owner since it holds
+    /// prompts and model output.
+    pub fn new(path: PathBuf) -> io::Result<Self> {
+        let mut options = OpenOptions::new();
+        options.create(true).append(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        let file = options.open(&path)?;
+        Ok(Self {
+            path,
+            next_id: AtomicU64::new(1),
+            file: Mutex::new(file),
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Records an outgoing request and returns the id that ties it to its response.
+    pub fn record_request(
+        &self,
+        method: &Method,
+        url: &str,
+        headers: &HeaderMap,
+        body: Option<&serde_json::Value>,
+    ) -> u64 {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        self.append(WireLogEntry {
+            timestamp_ms: now_ms(),
+            id,
+            kind: "request",
+            method: Some(method.as_str()),
+            url: redact_url(url),
+            status: None,
+            headers: redact_headers(headers),
+            body: body.map(|body| truncate_body(body.to_string().as_bytes())),
+            error: None,
+        });
+        id
+    }
+
+    /// Records the response to request `id`. `body` is `None` for streamed responses, whose
+    /// payload is not buffered.
+    pub fn record_response(
+        &self,
+        id: u64,
+        url: &str,
+        status: StatusCode,
+        headers: &HeaderMap,
+        body: Option<&[u8]>,
+    ) {
+        self.append(WireLogEntry {
+            timestamp_ms: now_ms(),
+            id,
+            kind: "response",
+            method: None,
+            url: redact_url(url),
+            status: Some(status.as_u16()),
+            headers: redact_headers(headers),
+            body: body.map(truncate_body),
+            error: None,
+        });
+    }
+
+    /// Records a request `id` that failed before a response arrived.
+    pub fn record_error(&self, id: u64, url: &str, error: &str) {
+        self.append(WireLogEntry {
+            timestamp_ms: now_ms(),
+            id,
+            kind: "error",
+            method: None,
+            url: redact_url(url),
+            status: None,
+            headers: BTreeMap::new(),
+            body: None,
+            error: Some(error),
+        });
+    }
+
+    fn append(&self, entry: WireLogEntry<'_>) {
+        let mut line = match serde_json::to_string(&entry) {
+            Ok(line) => line,
+            Err(err) => {
+                tracing::warn!("failed to serialize wire log entry: {err}");
+                return;
+            }
+        };
+        line.push('\n');
+
+        let mut file = self
+            .file
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        if let Err(err) = file.write_all(line.as_bytes()) {
+            tracing::warn!("failed to write wire log {}: {err}", self.path.display());
+        }
+    }
+}
+
+fn now_ms() -> u128 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis())
+        .unwrap_or_default()
+}
+
+fn redact_headers(headers: &HeaderMap) -> BTreeMap<String, String> {
+    headers
+        .iter()
+        .map(|(name, value)| {
+            let value = if SENSITIVE_HEADERS.contains(&name.as_str()) {
+                REDACTED.to_string()
+            } else {
+                String::from_utf8_lossy(value.as_bytes()).into_owned()
+            };
+            (name.as_str().to_string(), value)
+        })
+        .collect()
+}
+
+fn redact_url(url: &str) -> String {
+    let Ok(mut parsed) = reqwest::Url::parse(url) else {
+        return url.to_string();
+    };
+    if !parsed.username().is_empty() || parsed.password().is_some() {
+        let _ = parsed.set_username("");
+        let _ = parsed.set_password(None);
+    }
+    if parsed.query().is_some() {
+        let pairs: Vec<(String, String)> = parsed
+            .query_pairs()
+            .map(|(key, value)| {
+                let value = if SENSITIVE_QUERY_PARAMS.contains(&key.as_ref()) {
+                    REDACTED.to_string()
+                } else {
+                    value.into_owned()
+                };
+                (key.into_owned(), value)
+            })
+            .collect();
+        parsed.query_pairs_mut().clear().extend_pairs(pairs);
+    }
+    parsed.to_string()
+}
+
+fn truncate_body(body: &[u8]) -> String {
+    let text = String::from_utf8_lossy(body);
+    if text.len() <= WIRE_LOG_MAX_BODY_BYTES {
+        return text.into_owned();
+    }
+    let mut end = WIRE_LOG_MAX_BODY_BYTES;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    format!("{}…[truncated {} bytes]", &text[..end], text.len() - end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use http::HeaderValue;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn wire_log_strips_credentials_and_caps_bodies() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let log = WireLog::new(dir.path().join("api.jsonl")).expect("open wire log");
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "authorization",
+            HeaderValue::from_static("Bearer sk-secret"),
+        );
+        headers.insert("x-request-id", HeaderValue::from_static("req-1"));
+        let body = serde_json::json!({ "input": "x".repeat(WIRE_LOG_MAX_BODY_BYTES) });
+        let id = log.record_request(
+            &Method::POST,
+            "https://user:pw@example.com/v1/responses?key=abc&mode=fast",
+            &headers,
+            Some(&body),
+        );
+        log.record_response(
+            id,
+            "https://example.com/v1/responses",
+            StatusCode::BAD_REQUEST,
+            &HeaderMap::new(),
+            Some(b"{\"error\":\"bad\"}"),
+        );
+
+        let contents = std::fs::read_to_string(log.path()).expect("read wire log");
+        assert!(!contents.contains("sk-secret"));
+        assert!(!contents.contains("abc"));
+        assert!(!contents.contains("pw@"));
+
+        let entries: Vec<serde_json::Value> = contents
+            .lines()
+            .map(|line| serde_json::from_str(line).expect("valid json line"))
+            .collect();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0]["headers"]["authorization"], REDACTED);
+        assert_eq!(entries[0]["headers"]["x-request-id"], "req-1");
+        assert_eq!(
+            entries[0]["url"],
+            "https://example.com/v1/responses?key=%5Bredacted%5D&mode=fast"
+        );
+        let request_body = entries[0]["body"].as_str().expect("request body");
+        assert!(request_body.ends_with("[truncated 12 bytes]"));
+        assert_eq!(entries[1]["id"], entries[0]["id"]);
+        assert_eq!(entries[1]["status"], 400);
+        assert_eq!(entries[1]["body"], "{\"error\":\"bad\"}");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn wire_log_is_created_private() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let log = WireLog::new(dir.path().join("api.jsonl")).expect("open wire log");
+        let mode = std::fs::metadata(log.path())
+            .expect("wire log exists")
+            .permissions()
+            .mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+}
@@ -19,6 +19,9 @@ pub enum ConstraintError {
     #[error("field `{field_name}` cannot be empty")]
     EmptyField { field_name: String },
 
+    #[error("`{field_name}` cannot be changed in a session started with --read-only or --dry-run")]
+    ReadOnlySession { field_name: &'static str },
+
     #[error("invalid rules in requirements (set by {requirement_source}): {reason}")]
     ExecPolicyParse {
         requirement_source: RequirementSource,
@@ -7,6 +7,7 @@ mod merge;
 mod overrides;
 mod requirements_exec_policy;
 mod state;
+mod validation;
 
 pub const CONFIG_TOML_FILE: &str = "config.toml";
 
@@ -35,6 +36,7 @@ pub use diagnostics::TextPosition;
 pub use diagnostics::TextRange;
 pub use diagnostics::config_error_from_toml;
 pub use diagnostics::config_error_from_typed_toml;
+pub use diagnostics::config_path_for_layer;
 pub use diagnostics::first_layer_config_error;
 pub use diagnostics::first_layer_config_error_from_entries;
 pub use diagnostics::format_config_error;
@@ -53,3 +55,6 @@ pub use state::ConfigLayerEntry;
 pub use state::ConfigLayerStack;
 pub use state::ConfigLayerStackOrdering;
 pub use state::LoaderOverrides;
+pub use validation::ConfigIssue;
+pub use validation::ConfigIssueSeverity;
+pub use validation::validate_toml_against_schema;
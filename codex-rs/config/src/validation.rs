@@ -0,0 +1,696 @@
+//! Validate a `config.toml` file against the JSON schema for the config.
+//!
+//! Deserializing the config stops at the first problem and ignores unknown keys. This pass
+//! walks the whole document instead and reports every unknown key, type mismatch, and invalid
+//! enum value together with its location in the file.
+
+use crate::ConfigError;
+use crate::diagnostics::text_range_from_span;
+use serde_json::Value as JsonValue;
+use std::fmt;
+use std::ops::Range;
+use std::path::Path;
+use toml_edit::Document;
+use toml_edit::Item;
+use toml_edit::Table;
+use toml_edit::Value;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ConfigIssueSeverity {
+    /// The value is rejected when the config is loaded.
+    Error,
+    /// The config still loads, but the setting is ignored.
+    Warning,
+}
+
+impl fmt::Display for ConfigIssueSeverity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigIssueSeverity::Error => f.write_str("error"),
+            ConfigIssueSeverity::Warning => f.write_str("warning"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigIssue {
+    pub severity: ConfigIssueSeverity,
+    /// Dotted path of the offending key, e.g. `tui.notifications`. Empty for syntax errors.
+    pub key: String,
+    pub error: ConfigError,
+}
+
+impl fmt::Display for ConfigIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}:{}: {}: {}",
+            self.error.path.display(),
+            self.error.range.start.line,
+            self.error.range.start.column,
+            self.severity,
+            self.error.message
+        )
+    }
+}
+
+/// Check `contents` against `schema` (a draft-07 JSON schema whose `$ref`s point into its own
+/// `definitions`). Keys listed in `legacy_keys` are accepted anywhere even though the schema
+/// does not mention them, which covers serde aliases kept for backwards compatibility.
+pub fn validate_toml_against_schema(
+    path: impl AsRef<Path>,
+    contents: &str,
+    schema: &JsonValue,
+    legacy_keys: &[&str],
+) -> Vec<ConfigIssue> {
+    let path = path.as_ref();
+    let doc = match contents.parse::<Document<String>>() {
+        Ok(doc) => doc,
+        Err(err) => {
+            let range = text_range_from_span(contents, err.span().unwrap_or(0..0));
+            return vec![ConfigIssue {
+                severity: ConfigIssueSeverity::Error,
+                key: String::new(),
+                error: ConfigError::new(path.to_path_buf(), range, err.message()),
+            }];
+        }
+    };
+    let validator = Validator {
+        root: schema,
+        path,
+        contents,
+        legacy_keys,
+    };
+    let mut issues = validator.check(Node::Item(doc.as_item()), schema, "", None);
+    issues.sort_by_key(|issue| (issue.error.range.start.line, issue.error.range.start.column));
+    issues
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    String,
+    Integer,
+    Float,
+    Boolean,
+    Datetime,
+    Array,
+    Table,
+}
+
+impl Kind {
+    fn matches(self, schema_type: &str) -> bool {
+        matches!(
+            (self, schema_type),
+            (Kind::String, "string")
+                | (Kind::Integer, "integer" | "number")
+                | (Kind::Float, "number")
+                | (Kind::Boolean, "boolean")
+                | (Kind::Array, "array")
+                | (Kind::Table, "object")
+        )
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Kind::String => "string",
+            Kind::Integer => "integer",
+            Kind::Float => "float",
+            Kind::Boolean => "boolean",
+            Kind::Datetime => "datetime",
+            Kind::Array => "array",
+            Kind::Table => "table",
+        }
+    }
+}
+
+fn schema_type_name(schema_type: &str) -> &str {
+    match schema_type {
+        "object" => "table",
+        other => other,
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Node<'a> {
+    Item(&'a Item),
+    Table(&'a Table),
+    Value(&'a Value),
+}
+
+impl<'a> Node<'a> {
+    fn kind(self) -> Option<Kind> {
+        match self {
+            Node::Item(Item::None) => None,
+            Node::Item(Item::Value(value)) | Node::Value(value) => Some(match value {
+                Value::String(_) => Kind::String,
+                Value::Integer(_) => Kind::Integer,
+                Value::Float(_) => Kind::Float,
+                Value::Boolean(_) => Kind::Boolean,
+                Value::Datetime(_) => Kind::Datetime,
+                Value::Array(_) => Kind::Array,
+                Value::InlineTable(_) => Kind::Table,
+            }),
+            Node::Item(Item::Table(_)) | Node::Table(_) => Some(Kind::Table),
+            Node::Item(Item::ArrayOfTables(_)) => Some(Kind::Array),
+        }
+    }
+
+    fn span(self) -> Option<Range<usize>> {
+        match self {
+            Node::Item(Item::None) => None,
+            Node::Item(Item::Value(value)) | Node::Value(value) => value.span(),
+            Node::Item(Item::Table(table)) | Node::Table(table) => table.span(),
+            Node::Item(Item::ArrayOfTables(array)) => array.span(),
+        }
+    }
+
+    /// The scalar value as JSON, for comparing against `enum`, `const`, and `minimum`.
+    fn scalar(self) -> Option<JsonValue> {
+        let value = match self {
+            Node::Item(Item::Value(value)) | Node::Value(value) => value,
+            _ => return None,
+        };
+        match value {
+            Value::String(s) => Some(JsonValue::from(s.value().as_str())),
+            Value::Integer(i) => Some(JsonValue::from(*i.value())),
+            Value::Float(f) => Some(JsonValue::from(*f.value())),
+            Value::Boolean(b) => Some(JsonValue::from(*b.value())),
+            _ => None,
+        }
+    }
+
+    /// Entries of a table: key, key span, and value.
+    fn entries(self) -> Vec<(&'a str, Option<Range<usize>>, Node<'a>)> {
+        match self {
+            Node::Item(Item::Table(table)) | Node::Table(table) => table
+                .iter()
+                .map(|(key, item)| {
+                    let key_span = table.get_key_value(key).and_then(|(key, _)| key.span());
+                    (key, key_span, Node::Item(item))
+                })
+                .collect(),
+            Node::Item(Item::Value(Value::InlineTable(table)))
+            | Node::Value(Value::InlineTable(table)) => table
+                .iter()
+                .map(|(key, value)| {
+                    let key_span = table.get_key_value(key).and_then(|(key, _)| key.span());
+                    (key, key_span, Node::Value(value))
+                })
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    fn elements(self) -> Vec<Node<'a>> {
+        match self {
+            Node::Item(Item::Value(Value::Array(array))) | Node::Value(Value::Array(array)) => {
+                array.iter().map(Node::Value).collect()
+            }
+            Node::Item(Item::ArrayOfTables(array)) => array.iter().map(Node::Table).collect(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+struct Validator<'a> {
+    root: &'a JsonValue,
+    path: &'a Path,
+    contents: &'a str,
+    legacy_keys: &'a [&'a str],
+}
+
+impl<'a> Validator<'a> {
+    fn resolve(&self, schema: &'a JsonValue) -> &'a JsonValue {
+        let mut schema = schema;
+        // Bound the walk so a self-referential `$ref` cannot loop forever.
+        for _ in 0..16 {
+            let Some(reference) = schema.get("$ref").and_then(JsonValue::as_str) else {
+                break;
+            };
+            match reference
+                .strip_prefix('#')
+                .and_then(|pointer| self.root.pointer(pointer))
+            {
+                Some(target) => schema = target,
+                None => break,
+            }
+        }
+        schema
+    }
+
+    fn issue(
+        &self,
+        severity: ConfigIssueSeverity,
+        key: &str,
+        span: Option<Range<usize>>,
+        message: String,
+    ) -> ConfigIssue {
+        let range = text_range_from_span(self.contents, span.unwrap_or(0..0));
+        ConfigIssue {
+            severity,
+            key: key.to_string(),
+            error: ConfigError::new(self.path.to_path_buf(), range, message),
+        }
+    }
+
+    fn check(
+        &self,
+        node: Node<'_>,
+        schema: &'a JsonValue,
+        key: &str,
+        key_span: Option<Range<usize>>,
+    ) -> Vec<ConfigIssue> {
+        let schema = self.resolve(schema);
+        let Some(kind) = node.kind() else {
+            return Vec::new();
+        };
+        let span = node.span().or(key_span.clone());
+        let object = match schema {
+            JsonValue::Object(object) => object,
+            JsonValue::Bool(false) => {
+                return vec![self.issue(
+                    ConfigIssueSeverity::Error,
+                    key,
+                    span,
+                    format!("`{key}` is not allowed here"),
+                )];
+            }
+            _ => return Vec::new(),
+        };
+
+        let mut issues = Vec::new();
+        if let Some(parts) = object.get("allOf").and_then(JsonValue::as_array) {
+            for part in parts {
+                issues.extend(self.check(node, part, key, key_span.clone()));
+            }
+        }
+        for keyword in ["anyOf", "oneOf"] {
+            if let Some(branches) = object.get(keyword).and_then(JsonValue::as_array) {
+                issues.extend(self.check_any(node, kind, branches, key, key_span.clone()));
+            }
+        }
+
+        if let Some(types) = object.get("type") {
+            let types = schema_types(types);
+            if !types.is_empty() && !types.iter().any(|t| kind.matches(t)) {
+                issues.push(self.type_mismatch(kind, &types, key, span));
+                return issues;
+            }
+        }
+        if let Some(scalar) = node.scalar() {
+            let allowed = object
+                .get("enum")
+                .and_then(JsonValue::as_array)
+                .cloned()
+                .or_else(|| object.get("const").map(|value| vec![value.clone()]));
+            if let Some(allowed) = allowed
+                && !allowed.contains(&scalar)
+            {
+                issues.push(self.invalid_value(&scalar, &allowed, key, span.clone()));
+            }
+            if let (Some(minimum), Some(number)) = (
+                object.get("minimum").and_then(JsonValue::as_f64),
+                scalar.as_f64(),
+            ) && number < minimum
+            {
+                issues.push(self.issue(
+                    ConfigIssueSeverity::Error,
+                    key,
+                    span.clone(),
+                    format!("`{key}` must be at least {minimum}, found {scalar}"),
+                ));
+            }
+        }
+
+        match kind {
+            Kind::Table => issues.extend(self.check_table(node, object, key)),
+            Kind::Array => {
+                if let Some(items) = object.get("items") {
+                    for (index, element) in node.elements().into_iter().enumerate() {
+                        let element_schema = match items {
+                            JsonValue::Array(schemas) => match schemas.get(index) {
+                                Some(schema) => schema,
+                                None => continue,
+                            },
+                            schema => schema,
+                        };
+                        let element_key = format!("{key}[{index}]");
+                        issues.extend(self.check(
+                            element,
+                            element_schema,
+                            &element_key,
+                            span.clone(),
+                        ));
+                    }
+                }
+            }
+            _ => {}
+        }
+        issues
+    }
+
+    fn check_table(
+        &self,
+        node: Node<'_>,
+        object: &'a serde_json::Map<String, JsonValue>,
+        key: &str,
+    ) -> Vec<ConfigIssue> {
+        let properties = object.get("properties").and_then(JsonValue::as_object);
+        let additional = object.get("additionalProperties");
+        let mut issues = Vec::new();
+        for (child_key, child_key_span, child) in node.entries() {
+            let child_path = if key.is_empty() {
+                child_key.to_string()
+            } else {
+                format!("{key}.{child_key}")
+            };
+            if let Some(child_schema) = properties.and_then(|properties| properties.get(child_key))
+            {
+                issues.extend(self.check(child, child_schema, &child_path, child_key_span));
+                continue;
+            }
+            match additional {
+                Some(JsonValue::Bool(false)) => {
+                    if !self.legacy_keys.contains(&child_key) {
+                        issues.push(self.issue(
+                            ConfigIssueSeverity::Warning,
+                            &child_path,
+                            child_key_span.or_else(|| child.span()),
+                            format!("unknown config key `{child_path}`"),
+                        ));
+                    }
+                }
+                Some(child_schema @ JsonValue::Object(_)) => {
+                    issues.extend(self.check(child, child_schema, &child_path, child_key_span));
+                }
+                _ => {}
+            }
+        }
+        issues
+    }
+
+    /// A value must match at least one branch of an `anyOf`/`oneOf`.
+    fn check_any(
+        &self,
+        node: Node<'_>,
+        kind: Kind,
+        branches: &'a [JsonValue],
+        key: &str,
+        key_span: Option<Range<usize>>,
+    ) -> Vec<ConfigIssue> {
+        let results: Vec<Vec<ConfigIssue>> = branches
+            .iter()
+            .map(|branch| self.check(node, branch, key, key_span.clone()))
+            .collect();
+        let has_errors = |issues: &Vec<ConfigIssue>| {
+            issues
+                .iter()
+                .any(|issue| issue.severity == ConfigIssueSeverity::Error)
+        };
+        if let Some(best) = results
+            .iter()
+            .filter(|issues| !has_errors(issues))
+            .min_by_key(|issues| issues.len())
+        {
+            return best.clone();
+        }
+
+        let span = node.span().or(key_span);
+        // Unit enum variants are one branch per value; report them as a single list.
+        if let (Some(scalar), Some(allowed)) = (node.scalar(), self.enum_values(branches)) {
+            return vec![self.invalid_value(&scalar, &allowed, key, span)];
+        }
+        // Otherwise explain the closest branch: the one accepting this kind of value.
+        let matching_kind = branches
+            .iter()
+            .zip(results)
+            .filter(|(branch, _)| {
+                let types = self.branch_types(branch);
+                types.is_empty() || types.iter().any(|t| kind.matches(t))
+            })
+            .map(|(_, issues)| issues)
+            .min_by_key(Vec::len);
+        match matching_kind {
+            Some(issues) => issues,
+            None => {
+                let mut types = Vec::new();
+                for branch in branches {
+                    for t in self.branch_types(branch) {
+                        if !types.contains(&t) {
+                            types.push(t);
+                        }
+                    }
+                }
+                vec![self.type_mismatch(kind, &types, key, span)]
+            }
+        }
+    }
+
+    /// All allowed values when every branch is an `enum` or `const`.
+    fn enum_values(&self, branches: &'a [JsonValue]) -> Option<Vec<JsonValue>> {
+        let mut values = Vec::new();
+        for branch in branches {
+            let branch = self.resolve(branch);
+            if let Some(options) = branch.get("enum").and_then(JsonValue::as_array) {
+                values.extend(options.iter().cloned());
+            } else if let Some(value) = branch.get("const") {
+                values.push(value.clone());
+            } else if let Some(nested) = branch
+                .get("anyOf")
+                .or_else(|| branch.get("oneOf"))
+                .and_then(JsonValue::as_array)
+            {
+                values.extend(self.enum_values(nested)?);
+            } else {
+                return None;
+            }
+        }
+        Some(values)
+    }
+
+    fn branch_types(&self, branch: &'a JsonValue) -> Vec<&'a str> {
+        let branch = self.resolve(branch);
+        if let Some(types) = branch.get("type") {
+            return schema_types(types);
+        }
+        ["allOf", "anyOf", "oneOf"]
+            .iter()
+            .filter_map(|keyword| branch.get(*keyword).and_then(JsonValue::as_array))
+            .flatten()
+            .flat_map(|nested| self.branch_types(nested))
+            .collect()
+    }
+
+    fn type_mismatch(
+        &self,
+        kind: Kind,
+        expected: &[&str],
+        key: &str,
+        span: Option<Range<usize>>,
+    ) -> ConfigIssue {
+        let expected = expected
+            .iter()
+            .map(|t| schema_type_name(t))
+            .collect::<Vec<_>>()
+            .join(" or ");
+        self.issue(
+            ConfigIssueSeverity::Error,
+            key,
+            span,
+            format!(
+                "invalid type for `{key}`: expected {expected}, found {}",
+                kind.name()
+            ),
+        )
+    }
+
+    fn invalid_value(
+        &self,
+        value: &JsonValue,
+        allowed: &[JsonValue],
+        key: &str,
+        span: Option<Range<usize>>,
+    ) -> ConfigIssue {
+        let allowed = allowed
+            .iter()
+            .map(JsonValue::to_string)
+            .collect::<Vec<_>>()
+            .join(", ");
+        self.issue(
+            ConfigIssueSeverity::Error,
+            key,
+            span,
+            format!("invalid value {value} for `{key}`; expected one of {allowed}"),
+        )
+    }
+}
+
+fn schema_types(types: &JsonValue) -> Vec<&str> {
+    match types {
+        JsonValue::String(t) => vec![t.as_str()],
+        JsonValue::Array(types) => types.iter().filter_map(JsonValue::as_str).collect(),
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    fn schema() -> JsonValue {
+        json!({
+            "type": "object",
+            "additionalProperties": false,
+            "properties": {
+                "model": { "type": "string" },
+                "approval_policy": { "allOf": [{ "$ref": "#/definitions/AskForApproval" }] },
+                "tui": { "$ref": "#/definitions/Tui" },
+                "mcp_servers": {
+                    "type": "object",
+                    "additionalProperties": { "$ref": "#/definitions/McpServer" }
+                }
+            },
+            "definitions": {
+                "AskForApproval": {
+                    "oneOf": [
+                        { "type": "string", "enum": ["untrusted"] },
+                        { "type": "string", "enum": ["never"] }
+                    ]
+                },
+                "Tui": {
+                    "type": "object",
+                    "additionalProperties": false,
+                    "properties": {
+                        "notifications": {
+                            "anyOf": [
+                                { "type": "boolean" },
+                                { "type": "array", "items": { "type": "string" } }
+                            ]
+                        },
+                        "alternate_screen": { "type": "boolean" }
+                    }
+                },
+                "McpServer": {
+                    "type": "object",
+                    "additionalProperties": false,
+                    "properties": {
+                        "command": { "type": "string" },
+                        "startup_timeout_sec": { "type": "number", "minimum": 0.0 }
+                    }
+                }
+            }
+        })
+    }
+
+    fn summarize(contents: &str) -> Vec<(ConfigIssueSeverity, String, usize, usize, String)> {
+        validate_toml_against_schema("config.toml", contents, &schema(), &["legacy_key"])
+            .into_iter()
+            .map(|issue| {
+                (
+                    issue.severity,
+                    issue.key,
+                    issue.error.range.start.line,
+                    issue.error.range.start.column,
+                    issue.error.message,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn valid_config_has_no_issues() {
+        let contents = r#"
+model = "gpt-5"
+approval_policy = "never"
+legacy_key = 1
+
+[tui]
+notifications = ["agent-turn-complete"]
+
+[mcp_servers.docs]
+command = "docs-server"
+startup_timeout_sec = 10
+"#;
+        assert_eq!(summarize(contents), Vec::new());
+    }
+
+    #[test]
+    fn reports_every_issue_with_its_location() {
+        let contents = r#"model = 5
+approval_policy = "sometimes"
+modle = "gpt-5"
+
+[tui]
+notifications = "yes"
+alternate_screeen = false
+
+[mcp_servers.docs]
+comand = "docs-server"
+startup_timeout_sec = -1
+"#;
+        assert_eq!(
+            summarize(contents),
+            vec![
+                (
+                    ConfigIssueSeverity::Error,
+                    "model".to_string(),
+                    1,
+                    9,
+                    "invalid type for `model`: expected string, found integer".to_string(),
+                ),
+                (
+                    ConfigIssueSeverity::Error,
+                    "approval_policy".to_string(),
+                    2,
+                    19,
+                    "invalid value \"sometimes\" for `approval_policy`; expected one of \"untrusted\", \"never\"".to_string(),
+                ),
+                (
+                    ConfigIssueSeverity::Warning,
+                    "modle".to_string(),
+                    3,
+                    1,
+                    "unknown config key `modle`".to_string(),
+                ),
+                (
+                    ConfigIssueSeverity::Error,
+                    "tui.notifications".to_string(),
+                    6,
+                    17,
+                    "invalid type for `tui.notifications`: expected boolean or array, found string".to_string(),
+                ),
+                (
+                    ConfigIssueSeverity::Warning,
+                    "tui.alternate_screeen".to_string(),
+                    7,
+                    1,
+                    "unknown config key `tui.alternate_screeen`".to_string(),
+                ),
+                (
+                    ConfigIssueSeverity::Warning,
+                    "mcp_servers.docs.comand".to_string(),
+                    10,
+                    1,
+                    "unknown config key `mcp_servers.docs.comand`".to_string(),
+                ),
+                (
+                    ConfigIssueSeverity::Error,
+                    "mcp_servers.docs.startup_timeout_sec".to_string(),
+                    11,
+                    23,
+                    "`mcp_servers.docs.startup_timeout_sec` must be at least 0, found -1".to_string(),
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn syntax_errors_are_reported_as_a_single_issue() {
+        let issues = summarize("model = \n");
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].0, ConfigIssueSeverity::Error);
+        assert_eq!(issues[0].2, 1);
+    }
+}
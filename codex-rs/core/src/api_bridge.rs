@@ -5,6 +5,7 @@ use codex_api::TransportError;
 use codex_api::error::ApiError;
 use codex_api::rate_limits::parse_promo_message;
 use codex_api::rate_limits::parse_rate_limit_for_limit;
+use codex_api::rate_limits::parse_retry_after;
 use http::HeaderMap;
 use serde::Deserialize;
 
@@ -87,6 +88,15 @@ pub(crate) fn map_api_error(err: ApiError) -> CodexErr {
                         }
                     }
 
+                    // Plain throttling: honor the provider's requested delay so the turn retries
+                    // after a visible countdown instead of failing outright.
+                    if let Some(delay) = headers.as_ref().and_then(parse_retry_after) {
+                        return CodexErr::Stream(
+                            format!("rate limited by provider ({status})"),
+                            Some(delay),
+                        );
+                    }
+
                     CodexErr::RetryLimit(RetryLimitReachedError {
                         status,
                         request_id: extract_request_tracking_id(headers.as_ref()),
@@ -148,6 +158,23 @@ mod tests {
         assert!(matches!(err, CodexErr::ServerOverloaded));
     }
 
+    #[test]
+    fn map_api_error_retries_throttled_request_after_requested_delay() {
+        let mut headers = HeaderMap::new();
+        headers.insert("retry-after", http::HeaderValue::from_static("12"));
+        let err = map_api_error(ApiError::Transport(TransportError::Http {
+            status: http::StatusCode::TOO_MANY_REQUESTS,
+            url: Some("http://example.com/v1/responses".to_string()),
+            headers: Some(headers),
+            body: Some("{}".to_string()),
+        }));
+
+        let CodexErr::Stream(_, delay) = err else {
+            panic!("expected CodexErr::Stream, got {err:?}");
+        };
+        assert_eq!(delay, Some(std::time::Duration::from_secs(12)));
+    }
+
     #[test]
     fn map_api_error_maps_usage_limit_limit_name_header() {
         let mut headers = HeaderMap::new();
@@ -0,0 +1,266 @@
+//! Fit the text of an attachment or mentioned document under `[attachments] max_bytes`.
+//!
+//! Oversized text is cut down to its beginning and end, replaced with a summary written by the
+//! turn's model, or left out, depending on `[attachments] oversized`. Whatever happens is
+//! described in a note that is shown to the user as a warning and sent to the model next to
+//! the (remaining) text, so neither mistakes a partial attachment for the whole file.
+//!
+//! Attached images are downscaled to `[image_upload]` when they are converted for the prompt;
+//! [`downscaled_image_notes`] describes what was sent in their place.
+
+use codex_protocol::models::BaseInstructions;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::user_input::UserInput;
+use codex_utils_image::EncodedImage;
+use codex_utils_image::ImageLimits;
+use codex_utils_image::PromptImageMode;
+use codex_utils_image::cached_for_prompt;
+use codex_utils_string::format_bytes;
+use codex_utils_string::take_bytes_at_char_boundary;
+use codex_utils_string::take_last_bytes_at_char_boundary;
+use futures::prelude::*;
+
+use crate::Prompt;
+use crate::client_common::ResponseEvent;
+use crate::codex::Session;
+use crate::codex::TurnContext;
+use crate::compact::content_items_to_text;
+use crate::config::types::OversizedAttachmentStrategy;
+
+/// At most this much of an oversized attachment is sent to the summarizer.
+const MAX_SUMMARY_INPUT_BYTES: usize = 1024 * 1024;
+
+const SUMMARY_INSTRUCTIONS: &str = "You summarize documents that are too long to send in full. \
+Keep the facts, names, numbers, and section structure a reader would need to answer questions \
+about the document, and say which parts you compressed the most. Reply with the summary only.";
+
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct FittedAttachment {
+    /// The text to send, or `None` when the attachment was refused.
+    pub(crate) text: Option<String>,
+    /// What was done to an oversized attachment; `None` when the text fit.
+    pub(crate) note: Option<String>,
+}
+
+/// Apply the session's `[attachments]` policy to `text`, which belongs to the attachment
+/// called `name`.
+pub(crate) async fn fit_attachment(
+    sess: &Session,
+    turn_context: &TurnContext,
+    name: &str,
+    text: String,
+) -> FittedAttachment {
+    let limits = turn_context.config.attachments;
+    if text.len() <= limits.max_bytes {
+        return FittedAttachment {
+            text: Some(text),
+            note: None,
+        };
+    }
+    match limits.oversized {
+        OversizedAttachmentStrategy::Truncate => truncate_attachment(name, &text, limits.max_bytes),
+        OversizedAttachmentStrategy::Refuse => refuse_attachment(name, &text, limits.max_bytes),
+        OversizedAttachmentStrategy::Summarize => {
+            match summarize_attachment(sess, turn_context, name, &text, limits.max_bytes).await {
+                Ok(summary) => FittedAttachment {
+                    text: Some(summary),
+                    note: Some(format!(
+                        "{name} has {} bytes of text, over the {}-byte attachment limit; sent a \
+summary instead",
+                        text.len(),
+                        limits.max_bytes
+                    )),
+                },
+                Err(err) => {
+                    let mut fitted = truncate_attachment(name, &text, limits.max_bytes);
+                    fitted.note = fitted
+                        .note
+                        .map(|note| format!("{note} (summarizing failed: {err})"));
+                    fitted
+                }
+            }
+        }
+    }
+}
+
+fn truncate_attachment(name: &str, text: &str, max_bytes: usize) -> FittedAttachment {
+    FittedAttachment {
+        text: Some(head_and_tail(text, max_bytes)),
+        note: Some(format!(
+            "{name} has {} bytes of text, over the {max_bytes}-byte attachment limit; sent the \
+beginning and the end",
+            text.len()
+        )),
+    }
+}
+
+fn refuse_attachment(name: &str, text: &str, max_bytes: usize) -> FittedAttachment {
+    FittedAttachment {
+        text: None,
+        note: Some(format!(
+            "{name} was not attached: it has {} bytes of text, over the {max_bytes}-byte \
+attachment limit",
+            text.len()
+        )),
+    }
+}
+
+/// The first and last `max_bytes / 2` bytes of `text`, with a marker where the middle was.
+pub(crate) fn head_and_tail(text: &str, max_bytes: usize) -> String {
+    if text.len() <= max_bytes {
+        return text.to_string();
+    }
+    let head = take_bytes_at_char_boundary(text, max_bytes / 2);
+    let tail = take_last_bytes_at_char_boundary(text, max_bytes - max_bytes / 2);
+    let omitted = text.len() - head.len() - tail.len();
+    format!("{head}\n[… {omitted} bytes omitted …]\n{tail}")
+}
+
+/// One line for each local image in `input` that was resized or re-encoded under `limits`,
+/// giving its original and sent dimensions. Must run after the input was converted for the
+/// prompt, which leaves the encoded images in the cache.
+pub(crate) fn downscaled_image_notes(input: &[UserInput], limits: ImageLimits) -> Vec<String> {
+    input
+        .iter()
+        .filter_map(|item| match item {
+            UserInput::LocalImage { path } => {
+                let image = cached_for_prompt(path, PromptImageMode::Downscale(limits))?;
+                let name = path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().into_owned())
+                    .unwrap_or_else(|| path.display().to_string());
+                image
+                    .was_downscaled()
+                    .then(|| downscaled_image_note(&name, &image))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+fn downscaled_image_note(name: &str, image: &EncodedImage) -> String {
+    format!(
+        "Downscaled {name} from {}×{} ({}) to {}×{} {} ({}) before upload",
+        image.original_width,
+        image.original_height,
+        format_bytes(image.original_bytes as u64),
+        image.width,
+        image.height,
+        image.mime,
+        format_bytes(image.bytes.len() as u64)
+    )
+}
+
+async fn summarize_attachment(
+    sess: &Session,
+    turn_context: &TurnContext,
+    name: &str,
+    text: &str,
+    max_bytes: usize,
+) -> Result<String, String> {
+    let prompt = Prompt {
+        input: vec![ResponseItem::Message {
+            id: None,
+            role: "user".to_string(),
+            content: vec![ContentItem::InputText {
+                text: format!(
+                    "Summarize {name} in at most {max_bytes} bytes.\n\n{}",
+                    head_and_tail(text, MAX_SUMMARY_INPUT_BYTES)
+                ),
+            }],
+            end_turn: None,
+            phase: None,
+        }],
+        base_instructions: BaseInstructions {
+            text: SUMMARY_INSTRUCTIONS.to_string(),
+        },
+        ..Default::default()
+    };
+    let mut client_session = sess.services.model_client.new_session();
+    let mut stream = client_session
+        .stream(
+            &prompt,
+            &turn_context.model_info,
+            &turn_context.otel_manager,
+            turn_context.reasoning_effort,
+            turn_context.reasoning_summary,
+            turn_context.config.service_tier,
+            None,
+        )
+        .await
+        .map_err(|err| err.to_string())?;
+    let mut summary = String::new();
+    while let Some(event) = stream.next().await {
+        match event.map_err(|err| err.to_string())? {
+            ResponseEvent::OutputItemDone(ResponseItem::Message { role, content, .. })
+                if role == "assistant" =>
+            {
+                if let Some(text) = content_items_to_text(&content) {
+                    summary.push_str(&text);
+                }
+            }
+            ResponseEvent::Completed { .. } => break,
+            _ => {}
+        }
+    }
+    let summary = summary.trim();
+    if summary.is_empty() {
+        return Err("the model returned an empty summary".to_string());
+    }
+    Ok(take_bytes_at_char_boundary(summary, max_bytes).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn head_and_tail_keeps_both_ends() {
+        let text = format!("{}{}{}", "a".repeat(10), "b".repeat(80), "c".repeat(10));
+
+        assert_eq!(
+            head_and_tail(&text, 20),
+            format!(
+                "{}\n[… 80 bytes omitted …]\n{}",
+                "a".repeat(10),
+                "c".repeat(10)
+            )
+        );
+        assert_eq!(head_and_tail("short", 20), "short");
+    }
+
+    #[test]
+    fn downscaled_image_note_gives_both_sizes() {
+        let image = EncodedImage {
+            bytes: vec![0; 300 * 1024],
+            mime: "image/jpeg".to_string(),
+            width: 2048,
+            height: 1536,
+            original_width: 4032,
+            original_height: 3024,
+            original_bytes: 5 * 1024 * 1024,
+        };
+
+        assert_eq!(
+            downscaled_image_note("photo.png", &image),
+            "Downscaled photo.png from 4032×3024 (5 MiB) to 2048×1536 image/jpeg (300 KiB) before upload"
+        );
+    }
+
+    #[test]
+    fn refused_attachments_explain_the_limit() {
+        assert_eq!(
+            refuse_attachment("/tmp/big.pdf", &"x".repeat(30), 20),
+            FittedAttachment {
+                text: None,
+                note: Some(
+                    "/tmp/big.pdf was not attached: it has 30 bytes of text, over the 20-byte \
+attachment limit"
+                        .to_string()
+                ),
+            }
+        );
+    }
+}
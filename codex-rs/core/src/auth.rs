@@ -1,6 +1,8 @@
+mod profiles;
 mod storage;
 
 use async_trait::async_trait;
+use chrono::DateTime;
 use chrono::Utc;
 use reqwest::StatusCode;
 use serde::Deserialize;
@@ -19,6 +21,7 @@ use codex_app_server_protocol::AuthMode as ApiAuthMode;
 use codex_otel::TelemetryAuthMode;
 use codex_protocol::config_types::ForcedLoginMethod;
 
+pub use crate::auth::profiles::AuthProfile;
 pub use crate::auth::storage::AuthCredentialsStoreMode;
 pub use crate::auth::storage::AuthDotJson;
 use crate::auth::storage::AuthStorageBackend;
@@ -30,6 +33,7 @@ use crate::token_data::KnownPlan as InternalKnownPlan;
 use crate::token_data::PlanType as InternalPlanType;
 use crate::token_data::TokenData;
 use crate::token_data::parse_chatgpt_jwt_claims;
+use crate::token_data::parse_jwt_expiration;
 use crate::util::try_parse_error_message;
 use codex_client::CodexHttpClient;
 use codex_protocol::account::PlanType as AccountPlanType;
@@ -262,6 +266,13 @@ impl CodexAuth {
         self.get_current_token_data().and_then(|t| t.id_token.email)
     }
 
+    /// When the current access token expires, read from its `exp` claim.
+    /// Returns `None` if `is_chatgpt_auth()` is false or the token has no expiry.
+    pub fn access_token_expires_at(&self) -> Option<DateTime<Utc>> {
+        let access_token = self.get_current_token_data()?.access_token;
+        parse_jwt_expiration(&access_token).ok().flatten()
+    }
+
     /// Account-facing plan classification derived from the current token.
     /// Returns a high-level `AccountPlanType` (e.g., Free/Plus/Pro/Team/…)
     /// mapped from the ID token's internal plan value. Prefer this when you
@@ -377,14 +388,19 @@ pub fn read_codex_api_key_from_env() -> Option<String> {
         .filter(|value| !value.is_empty())
 }
 
-/// Delete the auth.json file inside `codex_home` if it exists. Returns `Ok(true)`
-/// if a file was removed, `Ok(false)` if no auth file was present.
+/// Delete the auth.json file inside `codex_home` if it exists and forget the
+/// active auth profile. Returns `Ok(true)` if a file was removed, `Ok(false)`
+/// if no auth file was present.
 pub fn logout(
     codex_home: &Path,
     auth_credentials_store_mode: AuthCredentialsStoreMode,
 ) -> std::io::Result<bool> {
     let storage = create_auth_storage(codex_home.to_path_buf(), auth_credentials_store_mode);
-    storage.delete()
+    let removed = storage.delete()?;
+    if auth_credentials_store_mode != AuthCredentialsStoreMode::Ephemeral {
+        profiles::clear_active_profile(codex_home)?;
+    }
+    Ok(removed)
 }
 
 /// Writes an `auth.json` that contains only the API key.
@@ -421,14 +437,19 @@ pub fn login_with_chatgpt_auth_tokens(
     )
 }
 
-/// Persist the provided auth payload using the specified backend.
+/// Persist the provided auth payload using the specified backend and mark the
+/// saved auth profile for the same account, if any, as active.
 pub fn save_auth(
     codex_home: &Path,
     auth: &AuthDotJson,
     auth_credentials_store_mode: AuthCredentialsStoreMode,
 ) -> std::io::Result<()> {
     let storage = create_auth_storage(codex_home.to_path_buf(), auth_credentials_store_mode);
-    storage.save(auth)
+    storage.save(auth)?;
+    if auth_credentials_store_mode != AuthCredentialsStoreMode::Ephemeral {
+        profiles::rekey_active_profile(codex_home, auth, auth_credentials_store_mode)?;
+    }
+    Ok(())
 }
 
 /// Load CLI auth data using the configured credential store backend.
@@ -1247,6 +1268,29 @@ impl AuthManager {
         Ok(removed)
     }
 
+    /// Saved auth profiles, sorted by name.
+    pub fn auth_profiles(&self) -> std::io::Result<Vec<AuthProfile>> {
+        profiles::list_profiles(&self.codex_home, self.auth_credentials_store_mode)
+    }
+
+    /// Name of the profile whose credentials are currently active, if any.
+    pub fn active_auth_profile(&self) -> Option<String> {
+        profiles::read_active_profile(&self.codex_home)
+    }
+
+    /// Save the current credentials as profile `name` so they can be switched
+    /// back to later without logging in again.
+    pub fn save_auth_profile(&self, name: &str) -> std::io::Result<()> {
+        profiles::save_profile(&self.codex_home, name, self.auth_credentials_store_mode)
+    }
+
+    /// Replace the active credentials with profile `name` and reload. Returns
+    /// whether the cached auth changed.
+    pub fn switch_auth_profile(&self, name: &str) -> std::io::Result<bool> {
+        profiles::switch_profile(&self.codex_home, name, self.auth_credentials_store_mode)?;
+        Ok(self.reload())
+    }
+
     pub fn get_api_auth_mode(&self) -> Option<ApiAuthMode> {
         self.auth_cached().as_ref().map(CodexAuth::api_auth_mode)
     }
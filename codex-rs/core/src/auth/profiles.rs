@@ -0,0 +1,308 @@
+//! Named auth profiles.
+//!
+//! A profile is a saved copy of the active credentials stored under
+//! `$CODEX_HOME/auth-profiles/<name>/` with the same backend (file, keyring,
+//! or auto) as the active `auth.json`. Switching profiles copies the chosen
+//! profile over the active credentials. Before doing so it writes the
+//! outgoing credentials back to their own profile, so refresh tokens that
+//! rotated while the profile was active are not lost. Logging out forgets the
+//! active profile, and logging in marks the profile for the same account, if
+//! any, so one account's credentials are never written into another's profile.
+
+use std::io::ErrorKind;
+use std::path::Path;
+use std::path::PathBuf;
+
+use codex_app_server_protocol::AuthMode as ApiAuthMode;
+
+use super::AuthCredentialsStoreMode;
+use super::AuthDotJson;
+use super::create_auth_storage;
+
+const AUTH_PROFILES_DIR: &str = "auth-profiles";
+const ACTIVE_PROFILE_FILE: &str = "active";
+
+/// Summary of a saved auth profile, suitable for showing in a picker.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuthProfile {
+    pub name: String,
+    pub auth_mode: ApiAuthMode,
+    /// Account email from the ChatGPT ID token. `None` for API keys.
+    pub email: Option<String>,
+    /// Whether these credentials are the ones currently in `auth.json`.
+    pub is_active: bool,
+}
+
+fn profiles_dir(codex_home: &Path) -> PathBuf {
+    codex_home.join(AUTH_PROFILES_DIR)
+}
+
+fn profile_home(codex_home: &Path, name: &str) -> PathBuf {
+    profiles_dir(codex_home).join(name)
+}
+
+fn validate_profile_name(name: &str) -> std::io::Result<()> {
+    let valid = !name.is_empty()
+        && name != ACTIVE_PROFILE_FILE
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if valid {
+        Ok(())
+    } else {
+        Err(std::io::Error::new(
+            ErrorKind::InvalidInput,
+            format!(
+                "invalid profile name `{name}`: use letters, digits, `-`, or `_` (and not `{ACTIVE_PROFILE_FILE}`)"
+            ),
+        ))
+    }
+}
+
+pub(super) fn read_active_profile(codex_home: &Path) -> Option<String> {
+    let name = std::fs::read_to_string(profiles_dir(codex_home).join(ACTIVE_PROFILE_FILE)).ok()?;
+    let name = name.trim();
+    validate_profile_name(name).ok()?;
+    Some(name.to_string())
+}
+
+fn write_active_profile(codex_home: &Path, name: &str) -> std::io::Result<()> {
+    let dir = profiles_dir(codex_home);
+    std::fs::create_dir_all(&dir)?;
+    std::fs::write(dir.join(ACTIVE_PROFILE_FILE), name)
+}
+
+/// Forget which profile is active, e.g. after logging out.
+pub(super) fn clear_active_profile(codex_home: &Path) -> std::io::Result<()> {
+    match std::fs::remove_file(profiles_dir(codex_home).join(ACTIVE_PROFILE_FILE)) {
+        Err(err) if err.kind() == ErrorKind::NotFound => Ok(()),
+        result => result,
+    }
+}
+
+/// Point the active marker at the saved profile holding the same account as freshly logged-in
+/// `auth`, or clear it, so a later switch never writes the new login over an unrelated profile.
+pub(super) fn rekey_active_profile(
+    codex_home: &Path,
+    auth: &AuthDotJson,
+    mode: AuthCredentialsStoreMode,
+) -> std::io::Result<()> {
+    let identity = credential_identity(auth);
+    let matching = saved_profiles(codex_home, mode)?
+        .into_iter()
+        .find(|(_, saved)| identity.is_some() && credential_identity(saved) == identity);
+    match matching {
+        Some((name, _)) => write_active_profile(codex_home, &name),
+        None => clear_active_profile(codex_home),
+    }
+}
+
+/// The account behind a set of credentials: the ChatGPT user and workspace, or the API key.
+fn credential_identity(auth: &AuthDotJson) -> Option<(Option<String>, Option<String>)> {
+    match &auth.tokens {
+        Some(tokens) => Some((
+            tokens.id_token.chatgpt_user_id.clone(),
+            tokens.account_id.clone(),
+        )),
+        None => auth.openai_api_key.clone().map(|key| (None, Some(key))),
+    }
+}
+
+fn saved_profiles(
+    codex_home: &Path,
+    mode: AuthCredentialsStoreMode,
+) -> std::io::Result<Vec<(String, AuthDotJson)>> {
+    let entries = match std::fs::read_dir(profiles_dir(codex_home)) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err),
+    };
+    let mut profiles = Vec::new();
+    for entry in entries {
+        let entry = entry?;
+        if !entry.file_type()?.is_dir() {
+            continue;
+        }
+        let Some(name) = entry.file_name().to_str().map(str::to_string) else {
+            continue;
+        };
+        if validate_profile_name(&name).is_err() {
+            continue;
+        }
+        let Some(auth) = create_auth_storage(entry.path(), mode).load()? else {
+            continue;
+        };
+        profiles.push((name, auth));
+    }
+    profiles.sort_by(|a, b| a.0.cmp(&b.0));
+    Ok(profiles)
+}
+
+pub(super) fn list_profiles(
+    codex_home: &Path,
+    mode: AuthCredentialsStoreMode,
+) -> std::io::Result<Vec<AuthProfile>> {
+    let active = read_active_profile(codex_home);
+    Ok(saved_profiles(codex_home, mode)?
+        .into_iter()
+        .map(|(name, auth)| AuthProfile {
+            auth_mode: auth.resolved_mode(),
+            email: auth.tokens.and_then(|tokens| tokens.id_token.email),
+            is_active: active.as_deref() == Some(name.as_str()),
+            name,
+        })
+        .collect())
+}
+
+/// Save the active credentials as profile `name`, replacing any profile with
+/// that name, and mark it active.
+pub(super) fn save_profile(
+    codex_home: &Path,
+    name: &str,
+    mode: AuthCredentialsStoreMode,
+) -> std::io::Result<()> {
+    validate_profile_name(name)?;
+    let Some(auth) = create_auth_storage(codex_home.to_path_buf(), mode).load()? else {
+        return Err(std::io::Error::new(
+            ErrorKind::NotFound,
+            "not logged in; log in before saving a profile",
+        ));
+    };
+    if auth.resolved_mode() == ApiAuthMode::ChatgptAuthTokens {
+        return Err(std::io::Error::new(
+            ErrorKind::InvalidInput,
+            "externally managed ChatGPT tokens cannot be saved as a profile",
+        ));
+    }
+    let home = profile_home(codex_home, name);
+    std::fs::create_dir_all(&home)?;
+    create_auth_storage(home, mode).save(&auth)?;
+    write_active_profile(codex_home, name)
+}
+
+/// Make profile `name` the active credentials.
+pub(super) fn switch_profile(
+    codex_home: &Path,
+    name: &str,
+    mode: AuthCredentialsStoreMode,
+) -> std::io::Result<()> {
+    validate_profile_name(name)?;
+    let Some(target) = create_auth_storage(profile_home(codex_home, name), mode).load()? else {
+        return Err(std::io::Error::new(
+            ErrorKind::NotFound,
+            format!("no auth profile named `{name}`"),
+        ));
+    };
+    let active_storage = create_auth_storage(codex_home.to_path_buf(), mode);
+    if let Some(active) = read_active_profile(codex_home)
+        && active != name
+        && let Some(current) = active_storage.load()?
+    {
+        create_auth_storage(profile_home(codex_home, &active), mode).save(&current)?;
+    }
+    active_storage.save(&target)?;
+    write_active_profile(codex_home, name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::load_auth_dot_json;
+    use crate::auth::login_with_api_key;
+    use crate::auth::logout;
+    use pretty_assertions::assert_eq;
+    use tempfile::tempdir;
+
+    const MODE: AuthCredentialsStoreMode = AuthCredentialsStoreMode::File;
+
+    fn api_key_auth(api_key: &str) -> AuthDotJson {
+        AuthDotJson {
+            auth_mode: Some(ApiAuthMode::ApiKey),
+            openai_api_key: Some(api_key.to_string()),
+            tokens: None,
+            last_refresh: None,
+        }
+    }
+
+    fn active_api_key(codex_home: &Path) -> Option<String> {
+        load_auth_dot_json(codex_home, MODE)
+            .expect("load auth")
+            .and_then(|auth| auth.openai_api_key)
+    }
+
+    #[test]
+    fn switch_restores_profile_and_keeps_outgoing_changes() -> std::io::Result<()> {
+        let dir = tempdir()?;
+        let codex_home = dir.path();
+        login_with_api_key(codex_home, "sk-work", MODE)?;
+        save_profile(codex_home, "work", MODE)?;
+        login_with_api_key(codex_home, "sk-personal", MODE)?;
+        save_profile(codex_home, "personal", MODE)?;
+
+        switch_profile(codex_home, "work", MODE)?;
+        assert_eq!(active_api_key(codex_home), Some("sk-work".to_string()));
+
+        // Credentials that change while a profile is active are written back on switch.
+        create_auth_storage(codex_home.to_path_buf(), MODE)
+            .save(&api_key_auth("sk-work-rotated"))?;
+        switch_profile(codex_home, "personal", MODE)?;
+        assert_eq!(active_api_key(codex_home), Some("sk-personal".to_string()));
+        switch_profile(codex_home, "work", MODE)?;
+        assert_eq!(
+            active_api_key(codex_home),
+            Some("sk-work-rotated".to_string())
+        );
+
+        assert_eq!(
+            list_profiles(codex_home, MODE)?,
+            vec![
+                AuthProfile {
+                    name: "personal".to_string(),
+                    auth_mode: ApiAuthMode::ApiKey,
+                    email: None,
+                    is_active: false,
+                },
+                AuthProfile {
+                    name: "work".to_string(),
+                    auth_mode: ApiAuthMode::ApiKey,
+                    email: None,
+                    is_active: true,
+                },
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn logout_and_login_move_the_active_marker() -> std::io::Result<()> {
+        let dir = tempdir()?;
+        let codex_home = dir.path();
+        login_with_api_key(codex_home, "sk-work", MODE)?;
+        save_profile(codex_home, "work", MODE)?;
+
+        logout(codex_home, MODE)?;
+        assert_eq!(read_active_profile(codex_home), None);
+
+        // A different account must not be written back into `work` on the next switch.
+        login_with_api_key(codex_home, "sk-other", MODE)?;
+        assert_eq!(read_active_profile(codex_home), None);
+        switch_profile(codex_home, "work", MODE)?;
+        assert_eq!(active_api_key(codex_home), Some("sk-work".to_string()));
+
+        logout(codex_home, MODE)?;
+        login_with_api_key(codex_home, "sk-work", MODE)?;
+        assert_eq!(read_active_profile(codex_home), Some("work".to_string()));
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_unknown_and_invalid_profile_names() {
+        let dir = tempdir().expect("tempdir");
+        let codex_home = dir.path();
+
+        let unknown = switch_profile(codex_home, "missing", MODE).expect_err("unknown profile");
+        assert_eq!(unknown.kind(), ErrorKind::NotFound);
+        let invalid = save_profile(codex_home, "../escape", MODE).expect_err("invalid name");
+        assert_eq!(invalid.kind(), ErrorKind::InvalidInput);
+    }
+}
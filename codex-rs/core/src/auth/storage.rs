@@ -17,6 +17,7 @@ use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::Mutex;
+use tracing::info;
 use tracing::warn;
 
 use crate::token_data::TokenData;
@@ -195,7 +196,17 @@ impl KeyringAuthStorage {
 impl AuthStorageBackend for KeyringAuthStorage {
     fn load(&self) -> std::io::Result<Option<AuthDotJson>> {
         let key = compute_store_key(&self.codex_home)?;
-        self.load_from_keyring(&key)
+        if let Some(auth) = self.load_from_keyring(&key)? {
+            return Ok(Some(auth));
+        }
+        // Credentials written to auth.json before the keyring was enabled are
+        // moved into the keyring on first load; `save` removes the file.
+        let Some(auth) = FileAuthStorage::new(self.codex_home.clone()).load()? else {
+            return Ok(None);
+        };
+        self.save(&auth)?;
+        info!("migrated CLI auth from auth.json to the keyring");
+        Ok(Some(auth))
     }
 
     fn save(&self, auth: &AuthDotJson) -> std::io::Result<()> {
@@ -544,6 +555,30 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn keyring_auth_storage_load_migrates_auth_file_into_keyring() -> anyhow::Result<()> {
+        let codex_home = tempdir()?;
+        let mock_keyring = MockKeyringStore::default();
+        let storage = KeyringAuthStorage::new(
+            codex_home.path().to_path_buf(),
+            Arc::new(mock_keyring.clone()),
+        );
+        let expected = auth_with_prefix("legacy-file");
+        FileAuthStorage::new(codex_home.path().to_path_buf()).save(&expected)?;
+
+        let loaded = storage.load()?;
+
+        assert_eq!(loaded, Some(expected.clone()));
+        let key = compute_store_key(codex_home.path())?;
+        assert_keyring_saved_auth_and_removed_fallback(
+            &mock_keyring,
+            &key,
+            codex_home.path(),
+            &expected,
+        );
+        Ok(())
+    }
+
     #[test]
     fn keyring_auth_storage_compute_store_key_for_home_directory() -> anyhow::Result<()> {
         let codex_home = PathBuf::from("~/.codex");
@@ -41,7 +41,6 @@ use codex_api::MemorySummarizeInput as ApiMemorySummarizeInput;
 use codex_api::MemorySummarizeOutput as ApiMemorySummarizeOutput;
 use codex_api::RawMemory as ApiRawMemory;
 use codex_api::RequestTelemetry;
-use codex_api::ReqwestTransport;
 use codex_api::ResponseCreateWsRequest;
 use codex_api::ResponsesApiRequest;
 use codex_api::ResponsesClient as ApiResponsesClient;
@@ -61,6 +60,7 @@ use codex_otel::OtelManager;
 
 use codex_protocol::ThreadId;
 use codex_protocol::config_types::ReasoningSummary as ReasoningSummaryConfig;
+use codex_protocol::config_types::SamplingParams;
 use codex_protocol::config_types::ServiceTier;
 use codex_protocol::config_types::Verbosity as VerbosityConfig;
 use codex_protocol::models::ResponseItem;
@@ -90,7 +90,7 @@ use crate::client_common::Prompt;
 use crate::client_common::ResponseEvent;
 use crate::client_common::ResponseStream;
 use crate::config::Config;
-use crate::default_client::build_reqwest_client;
+use crate::default_client::build_api_transport;
 use crate::error::CodexErr;
 use crate::error::Result;
 use crate::flags::CODEX_RS_SSE_FIXTURE;
@@ -287,7 +287,7 @@ impl ModelClient {
             return Ok(Vec::new());
         }
         let client_setup = self.current_client_setup().await?;
-        let transport = ReqwestTransport::new(build_reqwest_client());
+        let transport = build_api_transport();
         let request_telemetry = Self::build_request_telemetry(otel_manager);
         let client =
             ApiCompactClient::new(transport, client_setup.api_provider, client_setup.api_auth)
@@ -328,7 +328,7 @@ impl ModelClient {
         }
 
         let client_setup = self.current_client_setup().await?;
-        let transport = ReqwestTransport::new(build_reqwest_client());
+        let transport = build_api_transport();
         let request_telemetry = Self::build_request_telemetry(otel_manager);
         let client =
             ApiMemoriesClient::new(transport, client_setup.api_provider, client_setup.api_auth)
@@ -532,6 +532,18 @@ impl ModelClientSession {
             None
         };
         let text = create_text_param_for_request(verbosity, &prompt.output_schema);
+        // Reasoning models reject sampling overrides, so drop them rather than failing the turn.
+        let sampling = if model_info.supports_reasoning_summaries {
+            if !prompt.sampling.is_default() {
+                warn!(
+                    "temperature/top_p are set but ignored as the model does not support them: {}",
+                    model_info.slug
+                );
+            }
+            SamplingParams::default()
+        } else {
+            prompt.sampling
+        };
         let prompt_cache_key = Some(self.client.state.conversation_id.to_string());
         let request = ResponsesApiRequest {
             model: model_info.slug.clone(),
@@ -549,6 +561,8 @@ impl ModelClientSession {
                 Some(service_tier) => Some(service_tier.to_string()),
                 None => None,
             },
+            temperature: sampling.temperature,
+            top_p: sampling.top_p,
             prompt_cache_key,
             text,
         };
@@ -774,7 +788,7 @@ impl ModelClientSession {
             .map(super::auth::AuthManager::unauthorized_recovery);
         loop {
             let client_setup = self.client.current_client_setup().await?;
-            let transport = ReqwestTransport::new(build_reqwest_client());
+            let transport = build_api_transport();
             let (request_telemetry, sse_telemetry) = Self::build_streaming_telemetry(otel_manager);
             let compression = self.responses_request_compression(client_setup.auth.as_ref());
             let options = self.build_responses_options(turn_metadata_header, compression);
@@ -2,6 +2,7 @@ use crate::client_common::tools::ToolSpec;
 use crate::config::types::Personality;
 use crate::error::Result;
 pub use codex_api::common::ResponseEvent;
+use codex_protocol::config_types::SamplingParams;
 use codex_protocol::models::BaseInstructions;
 use codex_protocol::models::FunctionCallOutputBody;
 use codex_protocol::models::ResponseItem;
@@ -42,6 +43,9 @@ pub struct Prompt {
 
     /// Optional the output schema for the model's response.
     pub output_schema: Option<Value>,
+
+    /// Temperature / top_p overrides, applied only when the model accepts them.
+    pub sampling: SamplingParams,
 }
 
 impl Prompt {
@@ -262,6 +266,8 @@ mod tests {
             include: vec![],
             prompt_cache_key: None,
             service_tier: None,
+            temperature: None,
+            top_p: None,
             text: Some(TextControls {
                 verbosity: Some(OpenAiVerbosity::Low),
                 format: None,
@@ -304,6 +310,8 @@ mod tests {
             include: vec![],
             prompt_cache_key: None,
             service_tier: None,
+            temperature: None,
+            top_p: None,
             text: Some(text_controls),
         };
 
@@ -341,6 +349,8 @@ mod tests {
             include: vec![],
             prompt_cache_key: None,
             service_tier: None,
+            temperature: None,
+            top_p: None,
             text: None,
         };
 
@@ -363,6 +373,8 @@ mod tests {
             include: vec![],
             prompt_cache_key: None,
             service_tier: Some(ServiceTier::Flex.to_string()),
+            temperature: None,
+            top_p: None,
             text: None,
         };
 
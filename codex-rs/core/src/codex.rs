@@ -5,6 +5,7 @@ use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::atomic::AtomicU64;
+use std::time::Duration;
 
 use crate::AuthManager;
 use crate::CodexAuth;
@@ -25,6 +26,7 @@ use crate::compact::should_use_remote_compact_task;
 use crate::compact_remote::run_inline_remote_auto_compact_task;
 use crate::config::ManagedFeatures;
 use crate::connectors;
+use crate::cwd_tracking::cwd_after_command;
 use crate::exec_policy::ExecPolicyManager;
 use crate::features::FEATURES;
 use crate::features::Feature;
@@ -34,6 +36,7 @@ use crate::models_manager::collaboration_mode_presets::CollaborationModesConfig;
 use crate::models_manager::manager::ModelsManager;
 use crate::parse_command::parse_command;
 use crate::parse_turn_item;
+use crate::pinned_files;
 use crate::realtime_conversation::RealtimeConversationManager;
 use crate::realtime_conversation::handle_audio as handle_realtime_conversation_audio;
 use crate::realtime_conversation::handle_close as handle_realtime_conversation_close;
@@ -83,6 +86,7 @@ use codex_protocol::models::BaseInstructions;
 use codex_protocol::models::PermissionProfile;
 use codex_protocol::models::format_allow_prefixes;
 use codex_protocol::openai_models::ModelInfo;
+use codex_protocol::plan_tool::UpdatePlanArgs;
 use codex_protocol::protocol::FileChange;
 use codex_protocol::protocol::HasLegacyEvent;
 use codex_protocol::protocol::ItemCompletedEvent;
@@ -93,13 +97,17 @@ use codex_protocol::protocol::RolloutItem;
 use codex_protocol::protocol::SessionSource;
 use codex_protocol::protocol::SubAgentSource;
 use codex_protocol::protocol::TurnAbortReason;
+use codex_protocol::protocol::TurnAbortedEvent;
 use codex_protocol::protocol::TurnContextItem;
 use codex_protocol::protocol::TurnContextNetworkItem;
+use codex_protocol::protocol::TurnPhase;
+use codex_protocol::protocol::TurnPhaseEvent;
 use codex_protocol::protocol::TurnStartedEvent;
 use codex_protocol::request_user_input::RequestUserInputArgs;
 use codex_protocol::request_user_input::RequestUserInputResponse;
 use codex_rmcp_client::ElicitationResponse;
 use codex_rmcp_client::OAuthCredentialsStoreMode;
+use codex_utils_image::PromptImageMode;
 use codex_utils_stream_parser::AssistantTextChunk;
 use codex_utils_stream_parser::AssistantTextStreamParser;
 use codex_utils_stream_parser::ProposedPlanSegment;
@@ -144,6 +152,7 @@ use crate::codex_thread::ThreadConfigSnapshot;
 use crate::compact::collect_user_messages;
 use crate::config::Config;
 use crate::config::Constrained;
+use crate::config::ConstraintError;
 use crate::config::ConstraintResult;
 use crate::config::GhostSnapshotConfig;
 use crate::config::StartedNetworkProxy;
@@ -153,6 +162,7 @@ use crate::config::types::ShellEnvironmentPolicy;
 use crate::context_manager::ContextManager;
 use crate::context_manager::TotalTokenUsageBreakdown;
 use crate::environment_context::EnvironmentContext;
+use crate::environment_context::EnvironmentDetails;
 use crate::error::CodexErr;
 use crate::error::Result as CodexResult;
 #[cfg(test)]
@@ -182,6 +192,12 @@ pub(crate) struct PreviousTurnSettings {
     pub(crate) realtime_active: Option<bool>,
 }
 
+use crate::attachment_policy::downscaled_image_notes;
+use crate::contextual_user_message::CARRY_OVER_BRIEFING_FRAGMENT;
+use crate::document_text::DocumentInjections;
+use crate::document_text::attached_documents_as_mentions;
+use crate::document_text::build_document_injections;
+use crate::document_text::document_references;
 use crate::exec_policy::ExecPolicyUpdateError;
 use crate::feedback_tags;
 use crate::file_watcher::FileWatcher;
@@ -212,6 +228,8 @@ use crate::protocol::ApplyPatchApprovalRequestEvent;
 use crate::protocol::AskForApproval;
 use crate::protocol::BackgroundEventEvent;
 use crate::protocol::CompactedItem;
+use crate::protocol::CompactionReviewDecision;
+use crate::protocol::CompactionReviewRequestEvent;
 use crate::protocol::DeprecationNoticeEvent;
 use crate::protocol::ErrorEvent;
 use crate::protocol::Event;
@@ -222,6 +240,7 @@ use crate::protocol::ModelRerouteEvent;
 use crate::protocol::ModelRerouteReason;
 use crate::protocol::NetworkApprovalContext;
 use crate::protocol::Op;
+use crate::protocol::PlanApprovalRequestEvent;
 use crate::protocol::PlanDeltaEvent;
 use crate::protocol::RateLimitSnapshot;
 use crate::protocol::ReasoningContentDeltaEvent;
@@ -263,6 +282,7 @@ use crate::skills::injection::app_id_from_path;
 use crate::skills::injection::tool_kind_for_path;
 use crate::skills::resolve_skill_dependencies_for_turn;
 use crate::state::ActiveTurn;
+use crate::state::PlanApproval;
 use crate::state::SessionServices;
 use crate::state::SessionState;
 use crate::state_db;
@@ -271,6 +291,9 @@ use crate::tasks::RegularTask;
 use crate::tasks::ReviewTask;
 use crate::tasks::SessionTask;
 use crate::tasks::SessionTaskContext;
+use crate::todo_list::TodoList;
+use crate::todo_list::TodoListUpdate;
+use crate::tool_plugins::ToolPlugins;
 use crate::tools::ToolRouter;
 use crate::tools::context::SharedTurnDiffTracker;
 use crate::tools::handlers::SEARCH_TOOL_BM25_TOOL_NAME;
@@ -286,6 +309,7 @@ use crate::tools::spec::ToolsConfigParams;
 use crate::turn_diff_tracker::TurnDiffTracker;
 use crate::unified_exec::UnifiedExecProcessManager;
 use crate::util::backoff;
+use crate::webhooks::Webhooks;
 use crate::windows_sandbox::WindowsSandboxLevelExt;
 use codex_async_utils::OrCancelExt;
 use codex_otel::OtelManager;
@@ -293,6 +317,7 @@ use codex_otel::TelemetryAuthMode;
 use codex_protocol::config_types::CollaborationMode;
 use codex_protocol::config_types::Personality;
 use codex_protocol::config_types::ReasoningSummary as ReasoningSummaryConfig;
+use codex_protocol::config_types::SamplingParams;
 use codex_protocol::config_types::ServiceTier;
 use codex_protocol::config_types::WindowsSandboxLevel;
 use codex_protocol::models::ContentItem;
@@ -302,6 +327,7 @@ use codex_protocol::models::ResponseItem;
 use codex_protocol::openai_models::ReasoningEffort as ReasoningEffortConfig;
 use codex_protocol::protocol::CodexErrorInfo;
 use codex_protocol::protocol::InitialHistory;
+use codex_protocol::todo_tool::TodoListEvent;
 use codex_protocol::user_input::UserInput;
 use codex_utils_absolute_path::AbsolutePathBuf;
 use codex_utils_readiness::Readiness;
@@ -474,6 +500,7 @@ impl Codex {
             collaboration_mode,
             model_reasoning_summary: config.model_reasoning_summary,
             service_tier: config.service_tier,
+            sampling: SamplingParams::default(),
             developer_instructions: config.developer_instructions.clone(),
             user_instructions,
             personality: config.personality,
@@ -673,6 +700,7 @@ pub(crate) struct TurnContext {
     pub(crate) user_instructions: Option<String>,
     pub(crate) collaboration_mode: CollaborationMode,
     pub(crate) personality: Option<Personality>,
+    pub(crate) sampling: SamplingParams,
     pub(crate) approval_policy: Constrained<AskForApproval>,
     pub(crate) sandbox_policy: Constrained<SandboxPolicy>,
     pub(crate) network: Option<NetworkProxy>,
@@ -811,6 +839,7 @@ impl TurnContext {
             developer_instructions: self.developer_instructions.clone(),
             final_output_json_schema: self.final_output_json_schema.clone(),
             truncation_policy: Some(self.truncation_policy.into()),
+            sampling: (!self.sampling.is_default()).then_some(self.sampling),
         }
     }
 
@@ -828,7 +857,7 @@ impl TurnContext {
     }
 }
 
-fn local_time_context() -> (String, String) {
+pub(crate) fn local_time_context() -> (String, String) {
     match iana_time_zone::get_timezone() {
         Ok(timezone) => (Local::now().format("%Y-%m-%d").to_string(), timezone),
         Err(_) => (
@@ -846,6 +875,8 @@ pub(crate) struct SessionConfiguration {
     collaboration_mode: CollaborationMode,
     model_reasoning_summary: Option<ReasoningSummaryConfig>,
     service_tier: Option<ServiceTier>,
+    /// Temperature / top_p overrides set with `Op::SetSamplingParams`.
+    sampling: SamplingParams,
 
     /// Developer instructions that supplement the base instructions.
     developer_instructions: Option<String>,
@@ -924,13 +955,29 @@ impl SessionConfiguration {
         if let Some(service_tier) = updates.service_tier {
             next_configuration.service_tier = service_tier;
         }
+        if let Some(sampling) = updates.sampling {
+            next_configuration.sampling = sampling;
+        }
         if let Some(personality) = updates.personality {
             next_configuration.personality = Some(personality);
         }
+        // `--read-only` and `--dry-run` fix both policies for the whole session.
+        let policies_locked =
+            self.original_config_do_not_use.read_only || self.original_config_do_not_use.dry_run;
         if let Some(approval_policy) = updates.approval_policy {
+            if policies_locked && approval_policy != self.approval_policy.value() {
+                return Err(ConstraintError::ReadOnlySession {
+                    field_name: "approval_policy",
+                });
+            }
             next_configuration.approval_policy.set(approval_policy)?;
         }
         if let Some(sandbox_policy) = updates.sandbox_policy.clone() {
+            if policies_locked && &sandbox_policy != self.sandbox_policy.get() {
+                return Err(ConstraintError::ReadOnlySession {
+                    field_name: "sandbox_policy",
+                });
+            }
             next_configuration.sandbox_policy.set(sandbox_policy)?;
         }
         if let Some(windows_sandbox_level) = updates.windows_sandbox_level {
@@ -955,6 +1002,7 @@ pub(crate) struct SessionSettingsUpdate {
     pub(crate) collaboration_mode: Option<CollaborationMode>,
     pub(crate) reasoning_summary: Option<ReasoningSummaryConfig>,
     pub(crate) service_tier: Option<Option<ServiceTier>>,
+    pub(crate) sampling: Option<SamplingParams>,
     pub(crate) final_output_json_schema: Option<Option<Value>>,
     pub(crate) personality: Option<Personality>,
     pub(crate) app_server_client_name: Option<String>,
@@ -1142,6 +1190,7 @@ impl Session {
             user_instructions: session_configuration.user_instructions.clone(),
             collaboration_mode: session_configuration.collaboration_mode.clone(),
             personality: session_configuration.personality,
+            sampling: session_configuration.sampling,
             approval_policy: session_configuration.approval_policy.clone(),
             sandbox_policy: session_configuration.sandbox_policy.clone(),
             network,
@@ -1490,6 +1539,15 @@ impl Session {
                 (None, None)
             };
 
+        let (tool_plugins, tool_plugin_errors) =
+            ToolPlugins::load(&config.tool_plugins, &session_configuration.dynamic_tools).await;
+        for message in tool_plugin_errors {
+            post_session_configured_events.push(Event {
+                id: "".to_owned(),
+                msg: EventMsg::Warning(WarningEvent { message }),
+            });
+        }
+
         let services = SessionServices {
             // Initialize the MCP connection manager with an uninitialized
             // instance. It will be replaced with one created via
@@ -1514,6 +1572,8 @@ impl Session {
             hooks: Hooks::new(HooksConfig {
                 legacy_notify_argv: config.notify.clone(),
             }),
+            webhooks: Webhooks::new(&config.webhooks, conversation_id),
+            tool_plugins,
             rollout: Mutex::new(rollout_recorder),
             user_shell: Arc::new(default_shell),
             shell_snapshot_tx,
@@ -1667,6 +1727,10 @@ impl Session {
             Arc::clone(&config),
             &session_configuration.session_source,
         );
+        crate::semantic_search::start_semantic_index_task(
+            Arc::clone(&config),
+            &session_configuration.session_source,
+        );
 
         Ok(sess)
     }
@@ -1836,6 +1900,8 @@ impl Session {
                 let previous_turn_settings = reconstructed_rollout.previous_turn_settings.clone();
                 self.set_previous_turn_settings(previous_turn_settings.clone())
                     .await;
+                self.restore_sampling_params(reconstructed_rollout.reference_context_item.as_ref())
+                    .await;
                 {
                     let mut state = self.state.lock().await;
                     state.set_reference_context_item(reconstructed_rollout.reference_context_item);
@@ -1874,6 +1940,7 @@ impl Session {
                     let mut state = self.state.lock().await;
                     state.set_token_info(Some(info));
                 }
+                self.state.lock().await.todo_list = TodoList::from_rollout(&rollout_items);
                 if let Some(selected_tools) = restored_tool_selection {
                     self.set_mcp_tool_selection(selected_tools).await;
                 }
@@ -1895,6 +1962,8 @@ impl Session {
                     reconstructed_rollout.previous_turn_settings.clone(),
                 )
                 .await;
+                self.restore_sampling_params(reconstructed_rollout.reference_context_item.as_ref())
+                    .await;
                 {
                     let mut state = self.state.lock().await;
                     state.set_reference_context_item(
@@ -1915,6 +1984,7 @@ impl Session {
                     let mut state = self.state.lock().await;
                     state.set_token_info(Some(info));
                 }
+                self.state.lock().await.todo_list = TodoList::from_rollout(&rollout_items);
                 if let Some(selected_tools) = restored_tool_selection {
                     self.set_mcp_tool_selection(selected_tools).await;
                 }
@@ -2044,6 +2114,19 @@ impl Session {
         );
     }
 
+    /// Carry the sampling overrides recorded in the rollout into a resumed or forked session.
+    async fn restore_sampling_params(&self, reference_context_item: Option<&TurnContextItem>) {
+        let Some(sampling) = reference_context_item.and_then(|item| item.sampling) else {
+            return;
+        };
+        let _ = self
+            .update_settings(SessionSettingsUpdate {
+                sampling: Some(sampling),
+                ..Default::default()
+            })
+            .await;
+    }
+
     pub(crate) async fn update_settings(
         &self,
         updates: SessionSettingsUpdate,
@@ -2439,6 +2522,7 @@ impl Session {
         // Persist the event into rollout (recorder filters as needed)
         let rollout_items = vec![RolloutItem::EventMsg(event.msg.clone())];
         self.persist_rollout_items(&rollout_items).await;
+        self.services.webhooks.deliver(&event).await;
         if let Err(e) = self.tx_event.send(event).await {
             debug!("dropping event because channel is closed: {e}");
         }
@@ -2457,6 +2541,7 @@ impl Session {
         self.persist_rollout_items(&[RolloutItem::EventMsg(event.msg.clone())])
             .await;
         self.flush_rollout().await;
+        self.services.webhooks.deliver(&event).await;
         if let Err(e) = self.tx_event.send(event).await {
             debug!("dropping event because channel is closed: {e}");
         }
@@ -3127,9 +3212,15 @@ impl Session {
             .agent_control
             .format_environment_context_subagents(self.conversation_id)
             .await;
+        let details = EnvironmentDetails::collect(
+            &turn_context.config.environment_context,
+            &turn_context.cwd,
+        )
+        .await;
         contextual_user_sections.push(
             EnvironmentContext::from_turn_context(turn_context, shell.as_ref())
                 .with_subagents(subagents)
+                .with_details(details)
                 .serialize_to_xml(),
         );
 
@@ -3164,6 +3255,23 @@ impl Session {
         state.clone_history()
     }
 
+    /// The input for the next sampling request: the history prepared for the prompt, with
+    /// the current contents of any pinned files.
+    pub(crate) async fn sampling_request_input(
+        &self,
+        turn_context: &TurnContext,
+    ) -> Vec<ResponseItem> {
+        let mut input = self
+            .clone_history()
+            .await
+            .for_prompt(&turn_context.model_info.input_modalities);
+        let pinned_files = self.state.lock().await.pinned_files.clone();
+        if let Some(message) = pinned_files::pinned_files_message(&pinned_files).await {
+            pinned_files::insert_before_last_user_message(&mut input, message);
+        }
+        input
+    }
+
     pub(crate) async fn reference_context_item(&self) -> Option<TurnContextItem> {
         let state = self.state.lock().await;
         state.reference_context_item()
@@ -3294,6 +3402,185 @@ impl Session {
         state.set_dependency_env(values);
     }
 
+    /// Default working directory for the agent's next shell call: where the previous call
+    /// left off when `persistent_cwd` is enabled, otherwise the turn cwd.
+    pub(crate) async fn agent_cwd(&self, turn_context: &TurnContext) -> PathBuf {
+        if !turn_context.features.enabled(Feature::PersistentCwd) {
+            return turn_context.cwd.clone();
+        }
+        let state = self.state.lock().await;
+        state
+            .agent_cwd(&turn_context.cwd)
+            .unwrap_or_else(|| turn_context.cwd.clone())
+    }
+
+    /// Remember the directory a successful shell call left the agent in. Returns the new
+    /// directory when it changed.
+    pub(crate) async fn track_agent_cwd(
+        &self,
+        turn_context: &TurnContext,
+        command: &[String],
+        cwd: &Path,
+    ) -> Option<PathBuf> {
+        if !turn_context.features.enabled(Feature::PersistentCwd) {
+            return None;
+        }
+        let next = cwd_after_command(command, cwd).filter(|next| next.is_dir())?;
+        let mut state = self.state.lock().await;
+        state.set_agent_cwd(turn_context.cwd.clone(), next.clone());
+        Some(next)
+    }
+
+    /// Apply an `update_todos` change, announce the new list if it changed, and return the list
+    /// as shown to the model.
+    pub(crate) async fn update_todo_list(
+        &self,
+        turn_context: &TurnContext,
+        update: TodoListUpdate,
+    ) -> Result<String, String> {
+        let (changed, list) = {
+            let mut state = self.state.lock().await;
+            let changed = state.todo_list.apply(update)?;
+            (changed, state.todo_list.clone())
+        };
+        if changed {
+            self.send_event(
+                turn_context,
+                EventMsg::TodoListUpdate(TodoListEvent {
+                    items: list.items().to_vec(),
+                }),
+            )
+            .await;
+        }
+        Ok(list.describe())
+    }
+
+    /// Whether mutating tools are held back until the user approves a plan. Sessions that can
+    /// never ask (`approval_policy = "never"`) and sub-agents are not gated.
+    pub(crate) async fn awaiting_plan_approval(&self, turn_context: &TurnContext) -> bool {
+        if !turn_context.config.require_plan_approval
+            || turn_context.approval_policy.value() == AskForApproval::Never
+            || matches!(turn_context.session_source, SessionSource::SubAgent(_))
+        {
+            return false;
+        }
+        self.state.lock().await.plan_approval.is_none()
+    }
+
+    /// Ask the user to approve `plan` and lift the plan approval gate when they do.
+    pub(crate) async fn request_plan_approval(
+        &self,
+        turn_context: &TurnContext,
+        call_id: String,
+        plan: UpdatePlanArgs,
+    ) -> ReviewDecision {
+        let (tx_approve, rx_approve) = oneshot::channel();
+        let prev_entry = {
+            let mut active = self.active_turn.lock().await;
+            match active.as_mut() {
+                Some(at) => {
+                    let mut ts = at.turn_state.lock().await;
+                    ts.insert_pending_approval(call_id.clone(), tx_approve)
+                }
+                None => None,
+            }
+        };
+        if prev_entry.is_some() {
+            warn!("Overwriting existing pending approval for call_id: {call_id}");
+        }
+
+        let event = EventMsg::PlanApprovalRequest(PlanApprovalRequestEvent {
+            call_id,
+            turn_id: turn_context.sub_id.clone(),
+            explanation: plan.explanation,
+            plan: plan.plan,
+        });
+        self.send_event(turn_context, event).await;
+        let decision = rx_approve.await.unwrap_or_default();
+        let approval = match decision {
+            ReviewDecision::Approved => Some(PlanApproval::Turn),
+            ReviewDecision::ApprovedForSession => Some(PlanApproval::Session),
+            _ => None,
+        };
+        if approval.is_some() {
+            self.state.lock().await.plan_approval = approval;
+        }
+        decision
+    }
+
+    /// Re-arm the plan approval gate for a new user turn unless the plan was approved for the
+    /// whole session.
+    pub(crate) async fn rearm_plan_approval(&self) {
+        let mut state = self.state.lock().await;
+        if state.plan_approval == Some(PlanApproval::Turn) {
+            state.plan_approval = None;
+        }
+    }
+
+    /// Whether auto-compaction waits for the user to review its summary
+    /// (`confirm_auto_compaction`). As with plan approval, sessions that can never ask and
+    /// sub-agents keep the summary as generated.
+    pub(crate) fn confirms_auto_compaction(&self, turn_context: &TurnContext) -> bool {
+        turn_context.config.confirm_auto_compaction
+            && turn_context.approval_policy.value() != AskForApproval::Never
+            && !matches!(turn_context.session_source, SessionSource::SubAgent(_))
+    }
+
+    /// Show the user the summary auto-compaction produced and wait for their decision.
+    pub(crate) async fn request_compaction_review(
+        &self,
+        turn_context: &TurnContext,
+        summary: String,
+    ) -> CompactionReviewDecision {
+        // A turn compacts at most once at a time, so the turn id identifies the review.
+        let id = turn_context.sub_id.clone();
+        let (tx_decision, rx_decision) = oneshot::channel();
+        let prev_entry = {
+            let mut active = self.active_turn.lock().await;
+            match active.as_mut() {
+                Some(at) => {
+                    let mut ts = at.turn_state.lock().await;
+                    ts.insert_pending_compaction_review(id.clone(), tx_decision)
+                }
+                None => None,
+            }
+        };
+        if prev_entry.is_some() {
+            warn!("Overwriting existing pending compaction review for id: {id}");
+        }
+
+        let event = EventMsg::CompactionReviewRequest(CompactionReviewRequestEvent {
+            id,
+            turn_id: turn_context.sub_id.clone(),
+            summary,
+        });
+        self.send_event(turn_context, event).await;
+        rx_decision
+            .await
+            .unwrap_or(CompactionReviewDecision::Accept)
+    }
+
+    pub async fn notify_compaction_review(&self, id: &str, decision: CompactionReviewDecision) {
+        let entry = {
+            let mut active = self.active_turn.lock().await;
+            match active.as_mut() {
+                Some(at) => {
+                    let mut ts = at.turn_state.lock().await;
+                    ts.remove_pending_compaction_review(id)
+                }
+                None => None,
+            }
+        };
+        match entry {
+            Some(tx_decision) => {
+                tx_decision.send(decision).ok();
+            }
+            None => {
+                warn!("No pending compaction review found for id: {id}");
+            }
+        }
+    }
+
     pub(crate) async fn set_server_reasoning_included(&self, included: bool) {
         let mut state = self.state.lock().await;
         state.set_server_reasoning_included(included);
@@ -3365,6 +3652,7 @@ impl Session {
         turn_context: &TurnContext,
         message: impl Into<String>,
         codex_error: CodexErr,
+        retry_delay: Option<Duration>,
     ) {
         let additional_details = codex_error.to_string();
         let codex_error_info = CodexErrorInfo::ResponseStreamDisconnected {
@@ -3374,6 +3662,8 @@ impl Session {
             message: message.into(),
             codex_error_info: Some(codex_error_info),
             additional_details: Some(additional_details),
+            retry_delay_ms: retry_delay
+                .map(|delay| u64::try_from(delay.as_millis()).unwrap_or(u64::MAX)),
         });
         self.send_event(turn_context, event).await;
     }
@@ -3436,8 +3726,9 @@ impl Session {
             });
         }
 
+        let image_mode = PromptImageMode::Downscale(self.get_config().await.image_upload);
         let mut turn_state = active_turn.turn_state.lock().await;
-        turn_state.push_pending_input(input.into());
+        turn_state.push_pending_input(ResponseInputItem::from_user_input(input, image_mode));
         Ok(active_turn_id.clone())
     }
 
@@ -3781,6 +4072,10 @@ async fn submission_loop(sess: Arc<Session>, config: Arc<Config>, rx_sub: Receiv
                     handlers::dynamic_tool_response(&sess, id, response).await;
                     false
                 }
+                Op::CompactionReview { id, decision } => {
+                    handlers::compaction_review(&sess, id, decision).await;
+                    false
+                }
                 Op::AddToHistory { text } => {
                     handlers::add_to_history(&sess, &config, text).await;
                     false
@@ -3857,12 +4152,37 @@ async fn submission_loop(sess: Arc<Session>, config: Arc<Config>, rx_sub: Receiv
                     handlers::thread_rollback(&sess, sub.id.clone(), num_turns).await;
                     false
                 }
+                Op::TrimToolOutput { call_id } => {
+                    handlers::trim_tool_output(&sess, sub.id.clone(), &call_id).await;
+                    false
+                }
                 Op::SetThreadName { name } => {
                     handlers::set_thread_name(&sess, sub.id.clone(), name).await;
                     false
                 }
-                Op::RunUserShellCommand { command } => {
-                    handlers::run_user_shell_command(&sess, sub.id.clone(), command).await;
+                Op::SetSamplingParams { sampling } => {
+                    handlers::set_sampling_params(&sess, sub.id.clone(), sampling).await;
+                    false
+                }
+                Op::SetPinnedFiles { paths } => {
+                    sess.state.lock().await.pinned_files = paths;
+                    false
+                }
+                Op::SetCarryOverBriefing { briefing } => {
+                    sess.state.lock().await.pending_briefing = Some(briefing);
+                    false
+                }
+                Op::RunUserShellCommand {
+                    command,
+                    exclude_from_context,
+                } => {
+                    handlers::run_user_shell_command(
+                        &sess,
+                        sub.id.clone(),
+                        command,
+                        exclude_from_context,
+                    )
+                    .await;
                     false
                 }
                 Op::ResolveElicitation {
@@ -3916,6 +4236,7 @@ mod handlers {
 
     use crate::codex::spawn_review_thread;
     use crate::config::Config;
+    use crate::context_inspector;
 
     use crate::mcp::auth::compute_auth_statuses;
     use crate::mcp::collect_mcp_snapshot_from_manager;
@@ -3928,6 +4249,8 @@ mod handlers {
     use crate::tasks::execute_user_shell_command;
     use codex_protocol::custom_prompts::CustomPrompt;
     use codex_protocol::protocol::CodexErrorInfo;
+    use codex_protocol::protocol::CompactedItem;
+    use codex_protocol::protocol::CompactionReviewDecision;
     use codex_protocol::protocol::ErrorEvent;
     use codex_protocol::protocol::Event;
     use codex_protocol::protocol::EventMsg;
@@ -3952,6 +4275,7 @@ mod handlers {
     use crate::context_manager::is_user_turn_boundary;
     use codex_protocol::config_types::CollaborationMode;
     use codex_protocol::config_types::ModeKind;
+    use codex_protocol::config_types::SamplingParams;
     use codex_protocol::config_types::Settings;
     use codex_protocol::dynamic_tools::DynamicToolResponse;
     use codex_protocol::mcp::RequestId as ProtocolRequestId;
@@ -3988,6 +4312,29 @@ mod handlers {
         }
     }
 
+    pub async fn set_sampling_params(sess: &Session, sub_id: String, sampling: SamplingParams) {
+        if let Err(message) = sampling.validate() {
+            sess.send_event_raw(Event {
+                id: sub_id,
+                msg: EventMsg::Error(ErrorEvent {
+                    message,
+                    codex_error_info: Some(CodexErrorInfo::BadRequest),
+                }),
+            })
+            .await;
+            return;
+        }
+        override_turn_context(
+            sess,
+            sub_id,
+            SessionSettingsUpdate {
+                sampling: Some(sampling),
+                ..Default::default()
+            },
+        )
+        .await;
+    }
+
     pub async fn user_input_or_turn(sess: &Arc<Session>, sub_id: String, op: Op) {
         let (items, updates) = match op {
             Op::UserTurn {
@@ -4060,7 +4407,12 @@ mod handlers {
         }
     }
 
-    pub async fn run_user_shell_command(sess: &Arc<Session>, sub_id: String, command: String) {
+    pub async fn run_user_shell_command(
+        sess: &Arc<Session>,
+        sub_id: String,
+        command: String,
+        exclude_from_context: bool,
+    ) {
         if let Some((turn_context, cancellation_token)) =
             sess.active_turn_context_and_cancellation_token().await
         {
@@ -4070,6 +4422,7 @@ mod handlers {
                     session,
                     turn_context,
                     command,
+                    exclude_from_context,
                     cancellation_token,
                     UserShellCommandMode::ActiveTurnAuxiliary,
                 )
@@ -4082,7 +4435,7 @@ mod handlers {
         sess.spawn_task(
             Arc::clone(&turn_context),
             Vec::new(),
-            UserShellCommandTask::new(command),
+            UserShellCommandTask::new(command, exclude_from_context),
         )
         .await;
     }
@@ -4191,6 +4544,14 @@ mod handlers {
         sess.notify_dynamic_tool_response(&id, response).await;
     }
 
+    pub async fn compaction_review(
+        sess: &Arc<Session>,
+        id: String,
+        decision: CompactionReviewDecision,
+    ) {
+        sess.notify_compaction_review(&id, decision).await;
+    }
+
     pub async fn add_to_history(sess: &Arc<Session>, config: &Arc<Config>, text: String) {
         let id = sess.conversation_id;
         let config = Arc::clone(config);
@@ -4538,6 +4899,44 @@ mod handlers {
         .await;
     }
 
+    pub async fn trim_tool_output(sess: &Arc<Session>, sub_id: String, call_id: &str) {
+        let error = |message: String| Event {
+            id: sub_id.clone(),
+            msg: EventMsg::Error(ErrorEvent {
+                message,
+                codex_error_info: None,
+            }),
+        };
+        let has_active_turn = { sess.active_turn.lock().await.is_some() };
+        if has_active_turn {
+            sess.send_event_raw(error(
+                "Cannot trim tool output while a turn is in progress.".to_string(),
+            ))
+            .await;
+            return;
+        }
+
+        let mut items = sess.clone_history().await.raw_items().to_vec();
+        if !context_inspector::trim_tool_output(&mut items, call_id) {
+            sess.send_event_raw(error(format!(
+                "No tool output with call id {call_id} is in the context."
+            )))
+            .await;
+            return;
+        }
+
+        // Persist the trimmed history as a replacement so a resumed session matches.
+        let turn_context = sess.new_default_turn_with_sub_id(sub_id.clone()).await;
+        let reference_context_item = sess.reference_context_item().await;
+        let compacted_item = CompactedItem {
+            message: String::new(),
+            replacement_history: Some(items.clone()),
+        };
+        sess.replace_compacted_history(items, reference_context_item, compacted_item)
+            .await;
+        sess.recompute_token_usage(turn_context.as_ref()).await;
+    }
+
     /// Persists the thread name in the session index, updates in-memory state, and emits
     /// a `ThreadNameUpdated` event on success.
     ///
@@ -4784,6 +5183,7 @@ async fn spawn_review_thread(
         compact_prompt: parent_turn_context.compact_prompt.clone(),
         collaboration_mode: parent_turn_context.collaboration_mode.clone(),
         personality: parent_turn_context.personality,
+        sampling: SamplingParams::default(),
         approval_policy: parent_turn_context.approval_policy.clone(),
         sandbox_policy: parent_turn_context.sandbox_policy.clone(),
         network: parent_turn_context.network.clone(),
@@ -4910,6 +5310,7 @@ pub(crate) async fn run_turn(
         collaboration_mode_kind: turn_context.collaboration_mode.mode,
     });
     sess.send_event(&turn_context, event).await;
+    send_turn_phase(&sess, &turn_context, TurnPhase::Queued).await;
     // TODO(ccunningham): Pre-turn compaction runs before context updates and the
     // new user message are recorded. Estimate pending incoming items (context
     // diffs/full reinjection + user input) and trigger compaction preemptively
@@ -5006,6 +5407,21 @@ pub(crate) async fn run_turn(
             .await;
     }
 
+    let input = attached_documents_as_mentions(input);
+    let DocumentInjections {
+        items: document_items,
+        warnings: document_warnings,
+    } = build_document_injections(
+        &sess,
+        &turn_context,
+        document_references(&input, &turn_context.cwd),
+    )
+    .await;
+    for message in document_warnings {
+        sess.send_event(&turn_context, EventMsg::Warning(WarningEvent { message }))
+            .await;
+    }
+
     let mut explicitly_enabled_connectors = collect_explicit_app_ids(&input);
     explicitly_enabled_connectors.extend(collect_explicit_app_ids_from_skill_items(
         &skill_items,
@@ -5032,10 +5448,16 @@ pub(crate) async fn run_turn(
     sess.merge_connector_selection(explicitly_enabled_connectors.clone())
         .await;
 
-    let initial_input_for_turn: ResponseInputItem = ResponseInputItem::from(input.clone());
+    let image_limits = turn_context.config.image_upload;
+    let initial_input_for_turn =
+        ResponseInputItem::from_user_input(input.clone(), PromptImageMode::Downscale(image_limits));
     let response_item: ResponseItem = initial_input_for_turn.clone().into();
     sess.record_user_prompt_and_emit_turn_item(turn_context.as_ref(), &input, response_item)
         .await;
+    for message in downscaled_image_notes(&input, image_limits) {
+        sess.send_event(&turn_context, EventMsg::Warning(WarningEvent { message }))
+            .await;
+    }
     // Track the previous-turn baseline from the regular user-turn path only so
     // standalone tasks (compact/shell/review/undo) cannot suppress future
     // model/realtime injections.
@@ -5044,11 +5466,23 @@ pub(crate) async fn run_turn(
         realtime_active: Some(turn_context.realtime_active),
     }))
     .await;
+    sess.rearm_plan_approval().await;
 
     if !skill_items.is_empty() {
         sess.record_conversation_items(&turn_context, &skill_items)
             .await;
     }
+    if !document_items.is_empty() {
+        sess.record_conversation_items(&turn_context, &document_items)
+            .await;
+    }
+    let pending_briefing = sess.state.lock().await.pending_briefing.take();
+    if let Some(briefing) = pending_briefing {
+        let message =
+            CARRY_OVER_BRIEFING_FRAGMENT.into_message(CARRY_OVER_BRIEFING_FRAGMENT.wrap(briefing));
+        sess.record_conversation_items(&turn_context, &[message])
+            .await;
+    }
 
     sess.maybe_start_ghost_snapshot(Arc::clone(&turn_context), cancellation_token.child_token())
         .await;
@@ -5095,11 +5529,7 @@ pub(crate) async fn run_turn(
         }
 
         // Construct the input that we will send to the model.
-        let sampling_request_input: Vec<ResponseItem> = {
-            sess.clone_history()
-                .await
-                .for_prompt(&turn_context.model_info.input_modalities)
-        };
+        let sampling_request_input = sess.sampling_request_input(&turn_context).await;
 
         let sampling_request_input_messages = sampling_request_input
             .iter()
@@ -5242,12 +5672,22 @@ pub(crate) async fn run_turn(
                     codex_error_info: Some(CodexErrorInfo::BadRequest),
                 });
                 sess.send_event(&turn_context, event).await;
+                record_turn_cancellation(&sess, &turn_context, TurnAbortReason::Error).await;
                 break;
             }
             Err(e) => {
                 info!("Turn error: {e:#}");
+                turn_context
+                    .otel_manager
+                    .counter("codex.turn.error", 1, &[]);
                 let event = EventMsg::Error(e.to_error_event(None));
                 sess.send_event(&turn_context, event).await;
+                let reason = if e.is_timeout() {
+                    TurnAbortReason::Timeout
+                } else {
+                    TurnAbortReason::Error
+                };
+                record_turn_cancellation(&sess, &turn_context, reason).await;
                 // let the user continue the conversation
                 break;
             }
@@ -5257,6 +5697,22 @@ pub(crate) async fn run_turn(
     last_agent_message
 }
 
+/// Record in the rollout why a turn ended before the model finished, so a resumed session can
+/// explain the half-finished exchange. Live clients already saw the `Error` event and still get
+/// `TurnComplete`, so the record is only persisted, not sent.
+async fn record_turn_cancellation(
+    sess: &Session,
+    turn_context: &TurnContext,
+    reason: TurnAbortReason,
+) {
+    let event = EventMsg::TurnAborted(TurnAbortedEvent {
+        turn_id: Some(turn_context.sub_id.clone()),
+        reason,
+    });
+    sess.persist_rollout_items(&[RolloutItem::EventMsg(event)])
+        .await;
+}
+
 async fn run_pre_sampling_compact(
     sess: &Arc<Session>,
     turn_context: &Arc<TurnContext>,
@@ -5517,6 +5973,7 @@ fn build_prompt(
         base_instructions,
         personality: turn_context.personality,
         output_schema: turn_context.final_output_json_schema.clone(),
+        sampling: turn_context.sampling,
     }
 }
 #[allow(clippy::too_many_arguments)]
@@ -5612,19 +6069,21 @@ async fn run_sampling_request(
         }
         if retries < max_retries {
             retries += 1;
-            let delay = match &err {
-                CodexErr::Stream(_, requested_delay) => {
-                    requested_delay.unwrap_or_else(|| backoff(retries))
-                }
-                _ => backoff(retries),
+            // Streams only carry a requested delay when the provider throttled the request.
+            let requested_delay = match &err {
+                CodexErr::Stream(_, requested_delay) => *requested_delay,
+                _ => None,
             };
+            let delay = requested_delay.unwrap_or_else(|| backoff(retries));
             warn!(
                 "stream disconnected - retrying sampling request ({retries}/{max_retries} in {delay:?})...",
             );
 
             // In release builds, hide the first websocket retry notification to reduce noisy
             // transient reconnect messages. In debug builds, keep full visibility for diagnosis.
+            // Throttling is always reported so the UI can count down to the retry.
             let report_error = retries > 1
+                || requested_delay.is_some()
                 || cfg!(debug_assertions)
                 || !sess
                     .services
@@ -5634,12 +6093,13 @@ async fn run_sampling_request(
                 // Surface retry information to any UI/front‑end so the
                 // user understands what is happening instead of staring
                 // at a seemingly frozen screen.
-                sess.notify_stream_error(
-                    &turn_context,
-                    format!("Reconnecting... {retries}/{max_retries}"),
-                    err,
-                )
-                .await;
+                let message = if requested_delay.is_some() {
+                    format!("Rate limited, retrying... {retries}/{max_retries}")
+                } else {
+                    format!("Reconnecting... {retries}/{max_retries}")
+                };
+                sess.notify_stream_error(&turn_context, message, err, requested_delay)
+                    .await;
             }
             tokio::time::sleep(delay).await;
         } else {
@@ -5715,8 +6175,20 @@ async fn built_tools(
             connectors::filter_codex_apps_tools_by_policy(selected_mcp_tools, &turn_context.config);
     }
 
+    let mut disabled_tools =
+        crate::tools::policy::disabled_tools(&turn_context.config.tool_policies);
+    if turn_context.config.read_only {
+        // Mutating calls are refused at dispatch anyway; hiding the patch tool keeps the model
+        // from planning around it.
+        disabled_tools.push("apply_patch".to_string());
+    }
+    let tools_config = turn_context
+        .tools_config
+        .clone()
+        .with_tool_plugins(sess.services.tool_plugins.specs())
+        .with_disabled_tools(disabled_tools);
     Ok(Arc::new(ToolRouter::from_config(
-        &turn_context.tools_config,
+        &tools_config,
         has_mcp_servers.then(|| {
             mcp_tools
                 .into_iter()
@@ -5944,6 +6416,9 @@ fn realtime_text_for_event(msg: &EventMsg) -> Option<String> {
         | EventMsg::ExecCommandEnd(_)
         | EventMsg::PatchApplyBegin(_)
         | EventMsg::PatchApplyEnd(_)
+        | EventMsg::PatchChecks(_)
+        | EventMsg::DryRunAction(_)
+        | EventMsg::TurnPhase(_)
         | EventMsg::ViewImageToolCall(_)
         | EventMsg::ImageGenerationBegin(_)
         | EventMsg::ImageGenerationEnd(_)
@@ -5953,6 +6428,8 @@ fn realtime_text_for_event(msg: &EventMsg) -> Option<String> {
         | EventMsg::DynamicToolCallResponse(_)
         | EventMsg::ElicitationRequest(_)
         | EventMsg::ApplyPatchApprovalRequest(_)
+        | EventMsg::PlanApprovalRequest(_)
+        | EventMsg::CompactionReviewRequest(_)
         | EventMsg::DeprecationNotice(_)
         | EventMsg::BackgroundEvent(_)
         | EventMsg::UndoStarted(_)
@@ -5967,6 +6444,7 @@ fn realtime_text_for_event(msg: &EventMsg) -> Option<String> {
         | EventMsg::RemoteSkillDownloaded(_)
         | EventMsg::SkillsUpdateAvailable
         | EventMsg::PlanUpdate(_)
+        | EventMsg::TodoListUpdate(_)
         | EventMsg::TurnAborted(_)
         | EventMsg::ShutdownComplete
         | EventMsg::EnteredReviewMode(_)
@@ -6102,6 +6580,26 @@ async fn flush_assistant_text_segments_for_item(
     emit_streamed_assistant_text_delta(sess, turn_context, plan_mode_state, item_id, parsed).await;
 }
 
+/// Persist the assistant text streamed before an interrupt so the next turn can build on it.
+///
+/// The `<turn_aborted>` marker recorded by the abort path follows this message, which tells the
+/// model the response was cut off rather than finished.
+async fn record_interrupted_agent_message(sess: &Session, turn_context: &TurnContext, text: &str) {
+    if text.trim().is_empty() {
+        return;
+    }
+    let item = ResponseItem::Message {
+        id: None,
+        role: "assistant".to_string(),
+        content: vec![ContentItem::OutputText {
+            text: text.to_string(),
+        }],
+        end_turn: None,
+        phase: None,
+    };
+    sess.record_conversation_items(turn_context, &[item]).await;
+}
+
 /// Flush any remaining buffered assistant text parser state at response completion.
 async fn flush_assistant_text_segments_all(
     sess: &Session,
@@ -6247,12 +6745,33 @@ async fn handle_assistant_item_done_in_plan_mode(
     false
 }
 
+async fn send_turn_phase(sess: &Session, turn_context: &TurnContext, phase: TurnPhase) {
+    sess.send_event(
+        turn_context,
+        EventMsg::TurnPhase(TurnPhaseEvent {
+            turn_id: turn_context.sub_id.clone(),
+            phase,
+        }),
+    )
+    .await;
+}
+
 async fn drain_in_flight(
     in_flight: &mut FuturesOrdered<BoxFuture<'static, CodexResult<ResponseInputItem>>>,
     sess: Arc<Session>,
     turn_context: Arc<TurnContext>,
 ) -> CodexResult<()> {
-    while let Some(res) = in_flight.next().await {
+    let total = in_flight.len();
+    for index in 1..=total {
+        send_turn_phase(
+            &sess,
+            &turn_context,
+            TurnPhase::RunningTool { index, total },
+        )
+        .await;
+        let Some(res) = in_flight.next().await else {
+            break;
+        };
         match res {
             Ok(response_input) => {
                 sess.record_conversation_items(&turn_context, &[response_input.into()])
@@ -6293,6 +6812,7 @@ async fn try_run_sampling_request(
         auth_mode = sess.services.auth_manager.auth_mode(),
         features = sess.features.enabled_features(),
     );
+    send_turn_phase(&sess, &turn_context, TurnPhase::Sending).await;
     let mut stream = client_session
         .stream(
             prompt,
@@ -6322,6 +6842,10 @@ async fn try_run_sampling_request(
     let plan_mode = turn_context.collaboration_mode.mode == ModeKind::Plan;
     let mut assistant_message_stream_parsers = AssistantMessageStreamParsers::new(plan_mode);
     let mut plan_mode_state = plan_mode.then(|| PlanModeStreamState::new(&turn_context.sub_id));
+    // Raw text streamed for the active assistant message, kept so an interrupt can persist what
+    // the user already saw instead of dropping it from the conversation.
+    let mut partial_agent_text = String::new();
+    let mut streaming_reported = false;
     let receiving_span = trace_span!("receiving_stream");
     let outcome: CodexResult<SamplingRequestResult> = loop {
         let handle_responses = trace_span!(
@@ -6339,7 +6863,13 @@ async fn try_run_sampling_request(
             .await
         {
             Ok(event) => event,
-            Err(codex_async_utils::CancelErr::Cancelled) => break Err(CodexErr::TurnAborted),
+            Err(codex_async_utils::CancelErr::Cancelled) => {
+                if !plan_mode {
+                    record_interrupted_agent_message(&sess, &turn_context, &partial_agent_text)
+                        .await;
+                }
+                break Err(CodexErr::TurnAborted);
+            }
         };
 
         let event = match event {
@@ -6357,8 +6887,11 @@ async fn try_run_sampling_request(
             .record_responses(&handle_responses, &event);
 
         match event {
-            ResponseEvent::Created => {}
+            ResponseEvent::Created => {
+                send_turn_phase(&sess, &turn_context, TurnPhase::Thinking).await;
+            }
             ResponseEvent::OutputItemDone(item) => {
+                partial_agent_text.clear();
                 let previously_active_item = active_item.take();
                 if let Some(previous) = previously_active_item.as_ref()
                     && matches!(previous, TurnItem::AgentMessage(_))
@@ -6406,6 +6939,7 @@ async fn try_run_sampling_request(
                 needs_follow_up |= output_result.needs_follow_up;
             }
             ResponseEvent::OutputItemAdded(item) => {
+                partial_agent_text.clear();
                 if let Some(turn_item) = handle_non_tool_response_item(&item, plan_mode) {
                     let mut turn_item = turn_item;
                     let mut seeded_parsed: Option<ParsedAssistantTextDelta> = None;
@@ -6414,6 +6948,7 @@ async fn try_run_sampling_request(
                         && let Some(raw_text) = raw_assistant_output_text_from_item(&item)
                     {
                         let item_id = turn_item.id();
+                        partial_agent_text.push_str(&raw_text);
                         let mut seeded =
                             assistant_message_stream_parsers.seed_item_text(&item_id, &raw_text);
                         if let TurnItem::AgentMessage(agent_message) = &mut turn_item {
@@ -6505,6 +7040,11 @@ async fn try_run_sampling_request(
                 if let Some(active) = active_item.as_ref() {
                     let item_id = active.id();
                     if matches!(active, TurnItem::AgentMessage(_)) {
+                        if !streaming_reported {
+                            streaming_reported = true;
+                            send_turn_phase(&sess, &turn_context, TurnPhase::Streaming).await;
+                        }
+                        partial_agent_text.push_str(&delta);
                         let parsed = assistant_message_stream_parsers.parse_delta(&item_id, &delta);
                         emit_streamed_assistant_text_delta(
                             &sess,
@@ -7596,6 +8136,7 @@ mod tests {
             developer_instructions: None,
             final_output_json_schema: None,
             truncation_policy: Some(turn_context.truncation_policy.into()),
+            sampling: None,
         };
         let turn_id = previous_context_item
             .turn_id
@@ -7810,6 +8351,7 @@ mod tests {
             developer_instructions: config.developer_instructions.clone(),
             user_instructions: config.user_instructions.clone(),
             service_tier: None,
+            sampling: SamplingParams::default(),
             personality: config.personality,
             base_instructions: config
                 .base_instructions
@@ -7905,6 +8447,7 @@ mod tests {
             developer_instructions: config.developer_instructions.clone(),
             user_instructions: config.user_instructions.clone(),
             service_tier: None,
+            sampling: SamplingParams::default(),
             personality: config.personality,
             base_instructions: config
                 .base_instructions
@@ -8231,6 +8774,7 @@ mod tests {
             developer_instructions: config.developer_instructions.clone(),
             user_instructions: config.user_instructions.clone(),
             service_tier: None,
+            sampling: SamplingParams::default(),
             personality: config.personality,
             base_instructions: config
                 .base_instructions
@@ -8253,6 +8797,47 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn read_only_sessions_reject_policy_overrides() {
+        let mut session_configuration = make_session_configuration_for_tests().await;
+        let mut config = (*session_configuration.original_config_do_not_use).clone();
+        config.read_only = true;
+        session_configuration.original_config_do_not_use = Arc::new(config);
+
+        let unchanged = SessionSettingsUpdate {
+            approval_policy: Some(session_configuration.approval_policy.value()),
+            sandbox_policy: Some(session_configuration.sandbox_policy.get().clone()),
+            ..Default::default()
+        };
+        assert!(session_configuration.apply(&unchanged).is_ok());
+
+        let sandbox = SessionSettingsUpdate {
+            sandbox_policy: Some(SandboxPolicy::DangerFullAccess),
+            ..Default::default()
+        };
+        assert_eq!(
+            session_configuration.apply(&sandbox).err(),
+            Some(ConstraintError::ReadOnlySession {
+                field_name: "sandbox_policy"
+            })
+        );
+
+        let approval_policy = match session_configuration.approval_policy.value() {
+            AskForApproval::Never => AskForApproval::OnRequest,
+            _ => AskForApproval::Never,
+        };
+        let approval = SessionSettingsUpdate {
+            approval_policy: Some(approval_policy),
+            ..Default::default()
+        };
+        assert_eq!(
+            session_configuration.apply(&approval).err(),
+            Some(ConstraintError::ReadOnlySession {
+                field_name: "approval_policy"
+            })
+        );
+    }
+
     #[tokio::test]
     async fn session_new_fails_when_zsh_fork_enabled_without_zsh_path() {
         let codex_home = tempfile::tempdir().expect("create temp dir");
@@ -8290,6 +8875,7 @@ mod tests {
             developer_instructions: config.developer_instructions.clone(),
             user_instructions: config.user_instructions.clone(),
             service_tier: None,
+            sampling: SamplingParams::default(),
             personality: config.personality,
             base_instructions: config
                 .base_instructions
@@ -8382,6 +8968,7 @@ mod tests {
             developer_instructions: config.developer_instructions.clone(),
             user_instructions: config.user_instructions.clone(),
             service_tier: None,
+            sampling: SamplingParams::default(),
             personality: config.personality,
             base_instructions: config
                 .base_instructions
@@ -8443,6 +9030,8 @@ mod tests {
             hooks: Hooks::new(HooksConfig {
                 legacy_notify_argv: config.notify.clone(),
             }),
+            webhooks: Webhooks::new(&config.webhooks, conversation_id),
+            tool_plugins: ToolPlugins::default(),
             rollout: Mutex::new(None),
             user_shell: Arc::new(default_user_shell()),
             shell_snapshot_tx: watch::channel(None).0,
@@ -8754,6 +9343,7 @@ mod tests {
             developer_instructions: config.developer_instructions.clone(),
             user_instructions: config.user_instructions.clone(),
             service_tier: None,
+            sampling: SamplingParams::default(),
             personality: config.personality,
             base_instructions: config
                 .base_instructions
@@ -8815,6 +9405,8 @@ mod tests {
             hooks: Hooks::new(HooksConfig {
                 legacy_notify_argv: config.notify.clone(),
             }),
+            webhooks: Webhooks::new(&config.webhooks, conversation_id),
+            tool_plugins: ToolPlugins::default(),
             rollout: Mutex::new(None),
             user_shell: Arc::new(default_user_shell()),
             shell_snapshot_tx: watch::channel(None).0,
@@ -10242,6 +10834,66 @@ mod tests {
         pretty_assertions::assert_eq!(exec_output.metadata, ResponseExecMetadata { exit_code: 0 });
         assert!(exec_output.output.contains("hi"));
     }
+
+    #[tokio::test]
+    async fn plan_approval_gate_holds_until_a_plan_is_approved() {
+        use crate::protocol::AskForApproval;
+
+        let (session, mut turn_context) = make_session_and_context().await;
+        assert!(!session.awaiting_plan_approval(&turn_context).await);
+
+        let mut config = (*turn_context.config).clone();
+        config.require_plan_approval = true;
+        turn_context.config = Arc::new(config);
+        turn_context
+            .approval_policy
+            .set(AskForApproval::OnRequest)
+            .expect("test setup should allow updating approval policy");
+        assert!(session.awaiting_plan_approval(&turn_context).await);
+
+        turn_context
+            .approval_policy
+            .set(AskForApproval::Never)
+            .expect("test setup should allow updating approval policy");
+        assert!(!session.awaiting_plan_approval(&turn_context).await);
+
+        turn_context
+            .approval_policy
+            .set(AskForApproval::OnRequest)
+            .expect("test setup should allow updating approval policy");
+        session.state.lock().await.plan_approval = Some(PlanApproval::Turn);
+        assert!(!session.awaiting_plan_approval(&turn_context).await);
+        session.rearm_plan_approval().await;
+        assert!(session.awaiting_plan_approval(&turn_context).await);
+
+        session.state.lock().await.plan_approval = Some(PlanApproval::Session);
+        session.rearm_plan_approval().await;
+        assert!(!session.awaiting_plan_approval(&turn_context).await);
+    }
+
+    #[tokio::test]
+    async fn compaction_review_requires_config_and_an_askable_session() {
+        use crate::protocol::AskForApproval;
+
+        let (session, mut turn_context) = make_session_and_context().await;
+        assert!(!session.confirms_auto_compaction(&turn_context));
+
+        let mut config = (*turn_context.config).clone();
+        config.confirm_auto_compaction = true;
+        turn_context.config = Arc::new(config);
+        turn_context
+            .approval_policy
+            .set(AskForApproval::OnRequest)
+            .expect("test setup should allow updating approval policy");
+        assert!(session.confirms_auto_compaction(&turn_context));
+
+        turn_context
+            .approval_policy
+            .set(AskForApproval::Never)
+            .expect("test setup should allow updating approval policy");
+        assert!(!session.confirms_auto_compaction(&turn_context));
+    }
+
     #[tokio::test]
     async fn unified_exec_rejects_escalated_permissions_when_policy_not_on_request() {
         use crate::protocol::AskForApproval;
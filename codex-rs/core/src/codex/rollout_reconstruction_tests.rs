@@ -56,6 +56,7 @@ async fn record_initial_history_resumed_bare_turn_context_does_not_hydrate_previ
         developer_instructions: None,
         final_output_json_schema: None,
         truncation_policy: Some(turn_context.truncation_policy.into()),
+        sampling: None,
     };
     let rollout_items = vec![RolloutItem::TurnContext(previous_context_item)];
 
@@ -94,6 +95,7 @@ async fn record_initial_history_resumed_hydrates_previous_turn_settings_from_lif
         developer_instructions: None,
         final_output_json_schema: None,
         truncation_policy: Some(turn_context.truncation_policy.into()),
+        sampling: None,
     };
     let turn_id = previous_context_item
         .turn_id
@@ -755,6 +757,7 @@ async fn record_initial_history_resumed_turn_context_after_compaction_reestablis
         developer_instructions: None,
         final_output_json_schema: None,
         truncation_policy: Some(turn_context.truncation_policy.into()),
+        sampling: None,
     };
     let previous_turn_id = previous_context_item
         .turn_id
@@ -826,6 +829,7 @@ async fn record_initial_history_resumed_turn_context_after_compaction_reestablis
             developer_instructions: None,
             final_output_json_schema: None,
             truncation_policy: Some(turn_context.truncation_policy.into()),
+            sampling: None,
         }))
         .expect("serialize expected reference context item")
     );
@@ -854,6 +858,7 @@ async fn record_initial_history_resumed_aborted_turn_without_id_clears_active_tu
         developer_instructions: None,
         final_output_json_schema: None,
         truncation_policy: Some(turn_context.truncation_policy.into()),
+        sampling: None,
     };
     let previous_turn_id = previous_context_item
         .turn_id
@@ -959,6 +964,7 @@ async fn record_initial_history_resumed_unmatched_abort_preserves_active_turn_fo
         developer_instructions: None,
         final_output_json_schema: None,
         truncation_policy: Some(turn_context.truncation_policy.into()),
+        sampling: None,
     };
 
     let rollout_items = vec![
@@ -1060,6 +1066,7 @@ async fn record_initial_history_resumed_trailing_incomplete_turn_compaction_clea
         developer_instructions: None,
         final_output_json_schema: None,
         truncation_policy: Some(turn_context.truncation_policy.into()),
+        sampling: None,
     };
     let previous_turn_id = previous_context_item
         .turn_id
@@ -1203,6 +1210,7 @@ async fn record_initial_history_resumed_replaced_incomplete_compacted_turn_clear
         developer_instructions: None,
         final_output_json_schema: None,
         truncation_policy: Some(turn_context.truncation_policy.into()),
+        sampling: None,
     };
     let previous_turn_id = previous_context_item
         .turn_id
@@ -2,6 +2,8 @@ use crate::agent::AgentStatus;
 use crate::codex::Codex;
 use crate::codex::SteerInputError;
 use crate::config::ConstraintResult;
+use crate::context_inspector::NextTurnContext;
+use crate::context_inspector::inspect_next_turn;
 use crate::error::Result as CodexResult;
 use crate::features::Feature;
 use crate::file_watcher::WatchRegistration;
@@ -132,6 +134,11 @@ impl CodexThread {
         self.rollout_path.clone()
     }
 
+    /// Wait until every rollout item recorded so far has been written to disk.
+    pub async fn flush_rollout(&self) {
+        self.codex.session.flush_rollout().await;
+    }
+
     pub fn state_db(&self) -> Option<StateDbHandle> {
         self.codex.state_db()
     }
@@ -140,6 +147,11 @@ impl CodexThread {
         self.codex.thread_config_snapshot().await
     }
 
+    /// What the next model request would contain, split into sections with token estimates.
+    pub async fn next_turn_context(&self) -> NextTurnContext {
+        inspect_next_turn(&self.codex.session).await
+    }
+
     pub fn enabled(&self, feature: Feature) -> bool {
         self.codex.enabled(feature)
     }
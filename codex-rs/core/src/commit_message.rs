@@ -0,0 +1,167 @@
+//! Draft a commit message for the staged changes (`/commit-msg`) and commit with it.
+//!
+//! The draft comes from a read-only fork of the conversation, so the message can say why a
+//! change was made and not just what the diff shows. Recent commit subjects are included so
+//! the draft follows the repository's existing scopes and wording.
+
+use std::path::Path;
+use std::time::Duration;
+
+use codex_utils_string::take_bytes_at_char_boundary;
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::ThreadManager;
+use crate::config::Config;
+use crate::forked_prompt::ask_forked_conversation;
+use crate::pull_request::git_output;
+use crate::pull_request::git_stdout;
+
+/// The staged diff shown to the drafting model is cut off after this many bytes.
+const MAX_DIFF_BYTES: usize = 64 * 1024;
+const RECENT_SUBJECTS: usize = 10;
+/// Commit hooks can run formatters and tests, so allow them more time than plain git reads.
+const COMMIT_TIMEOUT: Duration = Duration::from_secs(300);
+
+#[derive(Debug, Deserialize)]
+struct CommitMessageDraft {
+    message: String,
+}
+
+/// Draft a conventional-commit message for what is staged in `config.cwd`, informed by the
+/// conversation in `rollout_path` when there is one.
+pub async fn draft_commit_message(
+    thread_manager: &ThreadManager,
+    config: &Config,
+    rollout_path: Option<&Path>,
+) -> Result<String, String> {
+    let cwd = config.cwd.as_path();
+    let diff = git_stdout(cwd, &["diff", "--cached"]).await?;
+    if diff.trim().is_empty() {
+        return Err("nothing is staged; stage changes with `git add` first".to_string());
+    }
+    // A repository without commits has no history to imitate.
+    let recent = git_stdout(
+        cwd,
+        &["log", "--format=%s", "-n", &RECENT_SUBJECTS.to_string()],
+    )
+    .await
+    .unwrap_or_default();
+    let draft: CommitMessageDraft = ask_forked_conversation(
+        thread_manager,
+        config,
+        rollout_path,
+        &commit_prompt(&diff, &recent),
+        json!({
+            "type": "object",
+            "properties": { "message": { "type": "string" } },
+            "required": ["message"],
+            "additionalProperties": false,
+        }),
+        "the commit message",
+    )
+    .await?;
+    let message = draft.message.trim();
+    if message.is_empty() {
+        return Err("the model wrote an empty commit message".to_string());
+    }
+    Ok(message.to_string())
+}
+
+/// Commit the staged changes in `cwd` with `message`, returning the new commit's short hash and
+/// subject.
+pub async fn commit_staged(cwd: &Path, message: &str) -> Result<String, String> {
+    let output = git_output(cwd, &["commit", "--quiet", "-m", message], COMMIT_TIMEOUT).await?;
+    if !output.status.success() {
+        // Hooks often report on stdout, git itself on stderr.
+        let details = [output.stderr.as_slice(), output.stdout.as_slice()]
+            .iter()
+            .map(|bytes| String::from_utf8_lossy(bytes).trim().to_string())
+            .filter(|text| !text.is_empty())
+            .collect::<Vec<_>>()
+            .join("\n");
+        return Err(format!("git commit failed: {details}"));
+    }
+    let summary = git_stdout(cwd, &["log", "-1", "--format=%h %s"]).await?;
+    Ok(summary.trim().to_string())
+}
+
+fn commit_prompt(diff: &str, recent_subjects: &str) -> String {
+    let mut prompt = "Write the commit message for the staged changes below in the Conventional \
+Commits format: `type(scope): summary`, where type is feat, fix, docs, style, refactor, perf, \
+test, build, ci, chore, or revert, the scope is optional, and the summary is imperative, without \
+a trailing period, and under 72 characters. When the change needs explaining, add a blank line \
+and a body wrapped at 72 columns that says what changed and why; take the why from our \
+conversation. Do not run commands or edit files. Reply with JSON: `message` is the full commit \
+message.\n"
+        .to_string();
+    let recent_subjects = recent_subjects.trim();
+    if !recent_subjects.is_empty() {
+        prompt.push_str(&format!(
+            "\nRecent commit subjects, for the scopes and wording this repository uses:\n{recent_subjects}\n"
+        ));
+    }
+    let shown = take_bytes_at_char_boundary(diff, MAX_DIFF_BYTES);
+    prompt.push_str(&format!(
+        "\nStaged diff:\n```diff\n{}\n```\n",
+        shown.trim_end()
+    ));
+    if shown.len() < diff.len() {
+        prompt.push_str(&format!(
+            "(The diff was cut off after {MAX_DIFF_BYTES} bytes of {}.)\n",
+            diff.len()
+        ));
+    }
+    prompt
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::process::Command;
+    use tempfile::TempDir;
+
+    fn git(dir: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .env("GIT_CONFIG_GLOBAL", "/dev/null")
+            .env("GIT_CONFIG_NOSYSTEM", "1")
+            .status()
+            .expect("run git");
+        assert!(status.success(), "git {args:?} failed");
+    }
+
+    #[test]
+    fn prompt_lists_recent_subjects_before_the_diff() {
+        let prompt = commit_prompt("+fn added() {}\n", "fix(tui): wrap long lines\n");
+
+        assert!(
+            prompt.contains(
+                "uses:\nfix(tui): wrap long lines\n\nStaged diff:\n```diff\n+fn added() {}\n```\n"
+            ),
+            "{prompt}"
+        );
+    }
+
+    #[tokio::test]
+    async fn commits_the_staged_changes() {
+        let dir = TempDir::new().expect("tempdir");
+        let repo = dir.path();
+        git(repo, &["init", "--quiet"]);
+        git(repo, &["config", "user.name", "Test User"]);
+        git(repo, &["config", "user.email", "test@example.com"]);
+        git(repo, &["config", "commit.gpgsign", "false"]);
+        std::fs::write(repo.join("a.txt"), "a\n").expect("write file");
+        git(repo, &["add", "a.txt"]);
+
+        let summary = commit_staged(repo, "feat: add a\n\nThe first file.")
+            .await
+            .expect("commit");
+
+        let (hash, subject) = summary.split_once(' ').expect("hash and subject");
+        assert!(!hash.is_empty());
+        assert_eq!(subject, "feat: add a");
+    }
+}
@@ -12,6 +12,7 @@ use crate::codex::get_last_assistant_message_from_turn;
 use crate::error::CodexErr;
 use crate::error::Result as CodexResult;
 use crate::protocol::CompactedItem;
+use crate::protocol::CompactionReviewDecision;
 use crate::protocol::EventMsg;
 use crate::protocol::TurnStartedEvent;
 use crate::protocol::WarningEvent;
@@ -63,7 +64,15 @@ pub(crate) async fn run_inline_auto_compact_task(
         text_elements: Vec::new(),
     }];
 
-    run_compact_task_inner(sess, turn_context, input, initial_context_injection).await?;
+    let review_summary = sess.confirms_auto_compaction(&turn_context);
+    run_compact_task_inner(
+        sess,
+        turn_context,
+        input,
+        initial_context_injection,
+        review_summary,
+    )
+    .await?;
     Ok(())
 }
 
@@ -83,6 +92,7 @@ pub(crate) async fn run_compact_task(
         turn_context,
         input,
         InitialContextInjection::DoNotInject,
+        false,
     )
     .await
 }
@@ -92,6 +102,7 @@ async fn run_compact_task_inner(
     turn_context: Arc<TurnContext>,
     input: Vec<UserInput>,
     initial_context_injection: InitialContextInjection,
+    review_summary: bool,
 ) -> CodexResult<()> {
     let compaction_item = TurnItem::ContextCompaction(ContextCompactionItem::new());
     sess.emit_turn_item_started(&turn_context, &compaction_item)
@@ -99,6 +110,8 @@ async fn run_compact_task_inner(
     let initial_input_for_turn: ResponseInputItem = ResponseInputItem::from(input);
 
     let mut history = sess.clone_history().await;
+    // Kept so a rejected summary can put the thread back as it was.
+    let history_before_compaction = review_summary.then(|| history.clone());
     history.record_items(
         &[initial_input_for_turn.into()],
         turn_context.truncation_policy,
@@ -175,6 +188,7 @@ async fn run_compact_task_inner(
                         turn_context.as_ref(),
                         format!("Reconnecting... {retries}/{max_retries}"),
                         e,
+                        None,
                     )
                     .await;
                     tokio::time::sleep(delay).await;
@@ -190,7 +204,31 @@ async fn run_compact_task_inner(
 
     let history_snapshot = sess.clone_history().await;
     let history_items = history_snapshot.raw_items();
-    let summary_suffix = get_last_assistant_message_from_turn(history_items).unwrap_or_default();
+    let mut summary_suffix =
+        get_last_assistant_message_from_turn(history_items).unwrap_or_default();
+    if let Some(history_before_compaction) = history_before_compaction {
+        match sess
+            .request_compaction_review(turn_context.as_ref(), summary_suffix.clone())
+            .await
+        {
+            CompactionReviewDecision::Accept => {}
+            CompactionReviewDecision::Edit { summary } => summary_suffix = summary,
+            CompactionReviewDecision::Reject => {
+                let reference_context_item = sess.reference_context_item().await;
+                sess.replace_history(
+                    history_before_compaction.raw_items().to_vec(),
+                    reference_context_item,
+                )
+                .await;
+                sess.recompute_token_usage(&turn_context).await;
+                let warning = EventMsg::Warning(WarningEvent {
+                    message: "Compaction rejected; the thread history was kept. Run /compact when you are ready, or start a new thread.".to_string(),
+                });
+                sess.send_event(&turn_context, warning).await;
+                return Err(CodexErr::Interrupted);
+            }
+        }
+    }
     let summary_text = format!("{SUMMARY_PREFIX}\n{summary_suffix}");
     let user_messages = collect_user_messages(history_items);
 
@@ -45,6 +45,11 @@ pub enum ConfigEdit {
     RecordModelMigrationSeen { from: String, to: String },
     /// Replace the entire `[mcp_servers]` table.
     ReplaceMcpServers(BTreeMap<String, McpServerConfig>),
+    /// Add or overwrite a single `[mcp_servers.<name>]` entry.
+    SetMcpServer {
+        name: String,
+        config: McpServerConfig,
+    },
     /// Set or clear a skill config entry under `[[skills.config]]`.
     SetSkillConfig { path: PathBuf, enabled: bool },
     /// Set trust_level under `[projects."<path>"]`,
@@ -371,6 +376,10 @@ impl ConfigDocument {
                 value(*acknowledged),
             )),
             ConfigEdit::ReplaceMcpServers(servers) => Ok(self.replace_mcp_servers(servers)),
+            ConfigEdit::SetMcpServer { name, config } => Ok(self.insert(
+                &["mcp_servers".to_string(), name.clone()],
+                document_helpers::serialize_mcp_server(config),
+            )),
             ConfigEdit::SetSkillConfig { path, enabled } => {
                 Ok(self.set_skill_config(path.as_path(), *enabled))
             }
@@ -845,6 +854,14 @@ impl ConfigEditsBuilder {
         self
     }
 
+    pub fn set_mcp_server(mut self, name: &str, config: &McpServerConfig) -> Self {
+        self.edits.push(ConfigEdit::SetMcpServer {
+            name: name.to_string(),
+            config: config.clone(),
+        });
+        self
+    }
+
     pub fn set_project_trust_level<P: Into<PathBuf>>(
         mut self,
         project_path: P,
@@ -2,8 +2,24 @@ use crate::auth::AuthCredentialsStoreMode;
 use crate::config::edit::ConfigEdit;
 use crate::config::edit::ConfigEditsBuilder;
 use crate::config::types::AppsConfigToml;
+use crate::config::types::AttachmentsConfig;
+use crate::config::types::AttachmentsToml;
+use crate::config::types::AudioTranscriptionConfig;
+use crate::config::types::AudioTranscriptionToml;
+use crate::config::types::ClientTlsConfig;
+use crate::config::types::ContextWarningsConfig;
 use crate::config::types::DEFAULT_OTEL_ENVIRONMENT;
+use crate::config::types::DEFAULT_TUI_TRANSCRIPT_MEMORY_LINES;
+use crate::config::types::DebugToml;
+use crate::config::types::EnvironmentContextConfig;
+use crate::config::types::EnvironmentContextToml;
+use crate::config::types::FetchUrlConfig;
+use crate::config::types::FetchUrlToml;
+use crate::config::types::FooterHint;
+use crate::config::types::ForgeConfig;
 use crate::config::types::History;
+use crate::config::types::HistoryTimestamps;
+use crate::config::types::ImageUploadToml;
 use crate::config::types::McpServerConfig;
 use crate::config::types::McpServerDisabledReason;
 use crate::config::types::McpServerTransportConfig;
@@ -16,16 +32,30 @@ use crate::config::types::Notifications;
 use crate::config::types::OtelConfig;
 use crate::config::types::OtelConfigToml;
 use crate::config::types::OtelExporterKind;
+use crate::config::types::OutboundProxyConfig;
+use crate::config::types::PatchCheckConfig;
 use crate::config::types::PluginConfig;
+use crate::config::types::RolloutFsyncPolicy;
 use crate::config::types::SandboxWorkspaceWrite;
+use crate::config::types::SemanticSearchConfig;
+use crate::config::types::SemanticSearchToml;
 use crate::config::types::ShellEnvironmentPolicy;
 use crate::config::types::ShellEnvironmentPolicyToml;
 use crate::config::types::SkillsConfig;
+use crate::config::types::SpellcheckConfig;
+use crate::config::types::StatusLineCommandConfig;
+use crate::config::types::StatusLineRowConfig;
+use crate::config::types::ToolPluginConfig;
+use crate::config::types::ToolPolicyConfig;
 use crate::config::types::Tui;
 use crate::config::types::UriBasedFileOpener;
+use crate::config::types::WebSearchProviderConfig;
+use crate::config::types::WebSearchProviderToml;
+use crate::config::types::WebhookConfig;
 use crate::config::types::WindowsSandboxModeToml;
 use crate::config::types::WindowsToml;
 use crate::config_loader::CloudRequirementsLoader;
+use crate::config_loader::ConfigIssueSeverity;
 use crate::config_loader::ConfigLayerStack;
 use crate::config_loader::ConfigRequirements;
 use crate::config_loader::ConstrainedWithSource;
@@ -74,6 +104,7 @@ use codex_protocol::openai_models::ReasoningEffort;
 use codex_rmcp_client::OAuthCredentialsStoreMode;
 use codex_utils_absolute_path::AbsolutePathBuf;
 use codex_utils_absolute_path::AbsolutePathBufGuard;
+use codex_utils_image::ImageLimits;
 use schemars::JsonSchema;
 use serde::Deserialize;
 use serde::Serialize;
@@ -100,6 +131,7 @@ pub mod profile;
 pub mod schema;
 pub mod service;
 pub mod types;
+pub mod validation;
 pub use codex_config::Constrained;
 pub use codex_config::ConstraintError;
 pub use codex_config::ConstraintResult;
@@ -244,6 +276,10 @@ pub struct Config {
     /// Compact prompt override.
     pub compact_prompt: Option<String>,
 
+    /// Ask the user to accept, edit, or reject the summary auto-compaction produces before it
+    /// replaces older turns.
+    pub confirm_auto_compaction: bool,
+
     /// Optional commit attribution text for commit message co-author trailers.
     ///
     /// - `None`: use default attribution (`Codex <noreply@openai.com>`)
@@ -273,6 +309,16 @@ pub struct Config {
     /// If unset the feature is disabled.
     pub notify: Option<Vec<String>>,
 
+    /// Endpoints that receive a JSON POST when a turn completes, an approval is requested, a
+    /// patch is applied, or the session ends.
+    pub webhooks: Vec<WebhookConfig>,
+
+    /// Custom tools backed by sandboxed WASI modules, keyed by tool name.
+    pub tool_plugins: BTreeMap<String, ToolPluginConfig>,
+
+    /// Per-tool enablement and argument restrictions, keyed by tool name.
+    pub tool_policies: BTreeMap<String, ToolPolicyConfig>,
+
     /// TUI notifications preference. When set, the TUI will send terminal notifications on
     /// approvals and turn completions when not focused.
     pub tui_notifications: Notifications,
@@ -305,9 +351,47 @@ pub struct Config {
     /// `current-dir`.
     pub tui_status_line: Option<Vec<String>>,
 
+    /// Status line rows with aligned segments (`tui.status_line_rows`). Takes precedence over
+    /// `tui_status_line` when set.
+    pub tui_status_line_rows: Option<Vec<StatusLineRowConfig>>,
+
+    /// Commands that provide status line segments (`tui.status_line_commands`).
+    pub tui_status_line_commands: BTreeMap<String, StatusLineCommandConfig>,
+
+    /// Hints shown in the TUI footer (`tui.footer_hints`); `None` shows the default set.
+    pub tui_footer_hints: Option<Vec<FooterHint>>,
+
+    /// Context window usage warnings (`tui.context_warnings`).
+    pub tui_context_warnings: ContextWarningsConfig,
+
     /// Syntax highlighting theme override (kebab-case name).
     pub tui_theme: Option<String>,
 
+    /// Key binding that interrupts the running turn (`tui.interrupt_key`).
+    pub tui_interrupt_key: Option<String>,
+
+    /// Whether the TUI sets the terminal window title (`tui.terminal_title`).
+    pub tui_terminal_title: bool,
+
+    /// Whether the TUI integrates with the tmux pane it runs in (`tui.tmux`).
+    pub tui_tmux: bool,
+
+    /// Whether the TUI shows the session as an iTerm2 badge (`tui.terminal_badge`).
+    pub tui_terminal_badge: bool,
+
+    /// Rendered transcript lines the TUI keeps in memory before spilling older history cells
+    /// to disk (`tui.transcript_memory_lines`); `0` disables spilling.
+    pub tui_transcript_memory_lines: usize,
+
+    /// How the TUI timestamps history cells (`tui.timestamps`).
+    pub tui_timestamps: HistoryTimestamps,
+
+    /// Whether the TUI writes a session summary on exit (`tui.summarize_on_exit`).
+    pub tui_summarize_on_exit: bool,
+
+    /// Composer spellcheck settings (`tui.spellcheck`).
+    pub tui_spellcheck: SpellcheckConfig,
+
     /// The directory that should be treated as the current working directory
     /// for the session. All relative paths inside the business-logic layer are
     /// resolved against this path.
@@ -369,6 +453,54 @@ pub struct Config {
     /// Memories subsystem settings.
     pub memories: MemoriesConfig,
 
+    /// Semantic code search index settings.
+    pub semantic_search: SemanticSearchConfig,
+
+    /// How attached audio files are transcribed.
+    pub audio_transcription: AudioTranscriptionConfig,
+
+    /// Domain allowlist and limits for the `fetch_url` tool.
+    pub fetch_url: FetchUrlConfig,
+
+    /// Optional details added to the `<environment_context>` block sent at session start.
+    pub environment_context: EnvironmentContextConfig,
+
+    /// Models `/compare-models` sends the same prompt to.
+    pub compare_models: Vec<String>,
+
+    /// When true, the model must present a plan with `update_plan` and have the user approve
+    /// it before any mutating tool (shell commands, patches) runs.
+    pub require_plan_approval: bool,
+
+    /// Shell command run after every successful `apply_patch`; its result is appended to the
+    /// patch output so the model sees failing tests or builds.
+    pub post_patch_command: Option<String>,
+
+    /// Formatters and linters run on the files each applied patch changed.
+    pub patch_checks: Vec<PatchCheckConfig>,
+
+    /// Code host `/pr` pushes to and opens pull requests on.
+    pub forge: ForgeConfig,
+
+    /// Size limit and strategy for oversized attachments and mentioned documents.
+    pub attachments: AttachmentsConfig,
+
+    /// Resizing and re-encoding applied to attached images before upload.
+    pub image_upload: ImageLimits,
+
+    /// Search API backing the `search_web` tool.
+    pub web_search_provider: WebSearchProviderConfig,
+
+    /// Explicit proxy for outbound HTTP clients. `None` falls back to the proxy environment
+    /// variables.
+    pub outbound_proxy: Option<OutboundProxyConfig>,
+
+    /// Extra CA certificates and client certificate for outbound HTTP clients.
+    pub tls: Option<ClientTlsConfig>,
+
+    /// Wire log for model API traffic (`debug.api_log_path`). `None` disables it.
+    pub api_log_path: Option<AbsolutePathBuf>,
+
     /// Directory containing all Codex state (defaults to `~/.codex` but can be
     /// overridden by the `CODEX_HOME` environment variable).
     pub codex_home: PathBuf,
@@ -382,9 +514,22 @@ pub struct Config {
     /// Settings that govern if and what will be written to `~/.codex/history.jsonl`.
     pub history: History,
 
+    /// When the session rollout file is fsynced.
+    pub rollout_fsync: RolloutFsyncPolicy,
+
     /// When true, session is not persisted on disk. Default to `false`
     pub ephemeral: bool,
 
+    /// When true, the session was started with `--read-only`: the sandbox is forced to
+    /// read-only and core refuses every mutating tool call (file writes, patches, and shell
+    /// commands outside the known-safe allowlist).
+    pub read_only: bool,
+
+    /// When true, the session was started with `--dry-run`: mutating commands and patches are
+    /// shown as previews and the model receives simulated results instead of running them. The
+    /// sandbox is forced to read-only.
+    pub dry_run: bool,
+
     /// Optional URI-based file opener. If set, citations to files in the model
     /// output will be hyperlinked using the specified URI scheme.
     pub file_opener: UriBasedFileOpener,
@@ -605,15 +750,32 @@ impl ConfigBuilder {
                         Some(err),
                     ));
                 }
-                return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, err));
+                // No config file is at fault, so the bad value came from a `-c` override.
+                // Validate the merged config to name the offending key.
+                let merged_toml = config_layer_stack.effective_config();
+                let merged_issue = toml::to_string(&merged_toml).ok().and_then(|contents| {
+                    validation::validate_config_toml("", &contents)
+                        .into_iter()
+                        .find(|issue| issue.severity == ConfigIssueSeverity::Error)
+                });
+                return Err(match merged_issue {
+                    Some(issue) => std::io::Error::new(
+                        std::io::ErrorKind::InvalidData,
+                        format!("invalid config override: {}", issue.error.message),
+                    ),
+                    None => std::io::Error::new(std::io::ErrorKind::InvalidData, err),
+                });
             }
         };
-        Config::load_config_with_layer_stack(
+        let unknown_key_warnings = validation::unknown_key_warnings(&config_layer_stack).await;
+        let mut config = Config::load_config_with_layer_stack(
             config_toml,
             harness_overrides,
             codex_home,
             config_layer_stack,
-        )
+        )?;
+        config.startup_warnings.extend(unknown_key_warnings);
+        Ok(config)
     }
 }
 
@@ -1056,6 +1218,18 @@ pub struct ConfigToml {
     #[serde(default)]
     pub notify: Option<Vec<String>>,
 
+    /// Endpoints that receive a JSON POST on key session events.
+    #[serde(default)]
+    pub webhooks: Option<Vec<WebhookConfig>>,
+
+    /// Custom tools backed by sandboxed WASI modules, keyed by tool name.
+    #[serde(default)]
+    pub tool_plugins: Option<BTreeMap<String, ToolPluginConfig>>,
+
+    /// Per-tool enablement and argument restrictions, keyed by tool name.
+    #[serde(default)]
+    pub tool_policies: Option<BTreeMap<String, ToolPolicyConfig>>,
+
     /// System instructions.
     pub instructions: Option<String>,
 
@@ -1072,6 +1246,9 @@ pub struct ConfigToml {
     /// Compact prompt used for history compaction.
     pub compact_prompt: Option<String>,
 
+    /// Show the summary auto-compaction generates and ask before it replaces older turns.
+    pub confirm_auto_compaction: Option<bool>,
+
     /// Optional commit attribution text for commit message co-author trailers.
     ///
     /// Set to an empty string to disable automatic commit attribution.
@@ -1089,6 +1266,7 @@ pub struct ConfigToml {
     /// file (default): Use a file in the Codex home directory.
     /// keyring: Use an OS-specific keyring service.
     /// auto: Use the keyring if available, otherwise use a file.
+    /// With keyring or auto, an existing auth.json is moved into the keyring on next load.
     #[serde(default)]
     pub cli_auth_credentials_store: Option<AuthCredentialsStoreMode>,
 
@@ -1161,6 +1339,9 @@ pub struct ConfigToml {
     /// Defaults to `$CODEX_HOME/log`.
     pub log_dir: Option<AbsolutePathBuf>,
 
+    /// When the session rollout file is fsynced: `always`, `turn` (the default), or `never`.
+    pub rollout_fsync: Option<RolloutFsyncPolicy>,
+
     /// Optional URI-based file opener. If set, citations to files in the model
     /// output will be hyperlinked using the specified URI scheme.
     pub file_opener: Option<UriBasedFileOpener>,
@@ -1202,6 +1383,40 @@ pub struct ConfigToml {
     #[serde(default)]
     pub audio: Option<RealtimeAudioToml>,
 
+    /// Transcription backend for audio files attached to a message.
+    pub audio_transcription: Option<AudioTranscriptionToml>,
+
+    /// Domain allowlist and limits for the `fetch_url` tool.
+    pub fetch_url: Option<FetchUrlToml>,
+
+    /// Optional details added to the `<environment_context>` block sent at session start.
+    pub environment_context: Option<EnvironmentContextToml>,
+
+    /// Models `/compare-models` sends the same prompt to, e.g. `["gpt-5", "gpt-5-mini"]`.
+    pub compare_models: Option<Vec<String>>,
+
+    /// Require the user to approve the model's plan before it may run commands or edit files.
+    pub require_plan_approval: Option<bool>,
+
+    /// Command to run after each applied patch, e.g. `"cargo test --quiet"`.
+    pub post_patch_command: Option<String>,
+
+    /// Formatters and linters run on the files each applied patch changed, from
+    /// `[[patch_checks]]` entries.
+    pub patch_checks: Option<Vec<PatchCheckConfig>>,
+
+    /// Code host settings for `/pr`.
+    pub forge: Option<ForgeConfig>,
+
+    /// Size limit and strategy for oversized attachments and mentioned documents.
+    pub attachments: Option<AttachmentsToml>,
+
+    /// Resizing and re-encoding applied to attached images before upload.
+    pub image_upload: Option<ImageUploadToml>,
+
+    /// Search API backing the `search_web` tool.
+    pub web_search_provider: Option<WebSearchProviderToml>,
+
     /// Experimental / do not use. Overrides only the realtime conversation
     /// websocket transport base URL (the `Op::RealtimeConversation`
     /// `/v1/realtime`
@@ -1228,6 +1443,18 @@ pub struct ConfigToml {
     /// Memories subsystem settings.
     pub memories: Option<MemoriesToml>,
 
+    /// Semantic code search index settings.
+    pub semantic_search: Option<SemanticSearchToml>,
+
+    /// Proxy for outbound HTTP clients (model API, MCP HTTP transport, login).
+    pub outbound_proxy: Option<OutboundProxyConfig>,
+
+    /// Custom CA bundle and client certificate for the model API and login clients.
+    pub tls: Option<ClientTlsConfig>,
+
+    /// Diagnostics such as the API wire log.
+    pub debug: Option<DebugToml>,
+
     /// User-level skill config entries keyed by SKILL.md path.
     pub skills: Option<SkillsConfig>,
 
@@ -1329,6 +1556,10 @@ impl From<ConfigToml> for UserSavedConfig {
 #[schemars(deny_unknown_fields)]
 pub struct ProjectConfig {
     pub trust_level: Option<TrustLevel>,
+
+    /// Extra environment variables for agent commands run in this project, applied on top
+    /// of `shell_environment_policy.set`.
+    pub shell_environment_set: Option<HashMap<String, String>>,
 }
 
 impl ProjectConfig {
@@ -1590,6 +1821,8 @@ pub struct ConfigOverrides {
     pub show_raw_agent_reasoning: Option<bool>,
     pub tools_web_search_request: Option<bool>,
     pub ephemeral: Option<bool>,
+    pub read_only: Option<bool>,
+    pub dry_run: Option<bool>,
     /// Additional directories that should be treated as writable roots for this session.
     pub additional_writable_roots: Vec<PathBuf>,
 }
@@ -1733,8 +1966,18 @@ impl Config {
             show_raw_agent_reasoning,
             tools_web_search_request: override_tools_web_search_request,
             ephemeral,
+            read_only,
+            dry_run,
             additional_writable_roots,
         } = overrides;
+        let read_only = read_only.unwrap_or_default();
+        // `--read-only` and `--dry-run` win over any sandbox mode from flags, profiles, or
+        // config, so a command that slips past the dry-run preview still cannot write.
+        let sandbox_mode = if read_only || dry_run.unwrap_or_default() {
+            Some(SandboxMode::ReadOnly)
+        } else {
+            sandbox_mode
+        };
 
         let active_profile_name = config_profile_key
             .as_ref()
@@ -1788,7 +2031,10 @@ impl Config {
             .collect::<Result<Vec<_>, _>>()?;
         let active_project = cfg
             .get_active_project(&resolved_cwd)
-            .unwrap_or(ProjectConfig { trust_level: None });
+            .unwrap_or(ProjectConfig {
+                trust_level: None,
+                shell_environment_set: None,
+            });
         let sandbox_mode_was_explicit = sandbox_mode.is_some()
             || config_profile.sandbox_mode.is_some()
             || cfg.sandbox_mode.is_some();
@@ -1874,7 +2120,11 @@ impl Config {
             })?
             .clone();
 
-        let shell_environment_policy = cfg.shell_environment_policy.into();
+        let mut shell_environment_policy: ShellEnvironmentPolicy =
+            cfg.shell_environment_policy.into();
+        if let Some(project_env) = &active_project.shell_environment_set {
+            shell_environment_policy.r#set.extend(project_env.clone());
+        }
         let allow_login_shell = cfg.allow_login_shell.unwrap_or(true);
 
         let history = cfg.history.unwrap_or_default();
@@ -2157,11 +2407,15 @@ impl Config {
             enforce_residency: enforce_residency.value,
             did_user_set_custom_approval_policy_or_sandbox_mode,
             notify: cfg.notify,
+            webhooks: cfg.webhooks.unwrap_or_default(),
+            tool_plugins: cfg.tool_plugins.unwrap_or_default(),
+            tool_policies: cfg.tool_policies.unwrap_or_default(),
             user_instructions,
             base_instructions,
             personality,
             developer_instructions,
             compact_prompt,
+            confirm_auto_compaction: cfg.confirm_auto_compaction.unwrap_or(false),
             commit_attribution,
             // The config.toml omits "_mode" because it's a config file. However, "_mode"
             // is important in code to differentiate the mode from the store implementation.
@@ -2192,13 +2446,31 @@ impl Config {
             agent_max_depth,
             agent_roles,
             memories: cfg.memories.unwrap_or_default().into(),
+            semantic_search: cfg.semantic_search.unwrap_or_default().into(),
+            audio_transcription: cfg.audio_transcription.unwrap_or_default().into(),
+            fetch_url: cfg.fetch_url.unwrap_or_default().into(),
+            environment_context: cfg.environment_context.unwrap_or_default().into(),
+            compare_models: cfg.compare_models.unwrap_or_default(),
+            require_plan_approval: cfg.require_plan_approval.unwrap_or(false),
+            post_patch_command: cfg.post_patch_command,
+            patch_checks: cfg.patch_checks.unwrap_or_default(),
+            forge: cfg.forge.unwrap_or_default(),
+            attachments: cfg.attachments.unwrap_or_default().into(),
+            image_upload: cfg.image_upload.unwrap_or_default().into(),
+            web_search_provider: cfg.web_search_provider.unwrap_or_default().into(),
+            outbound_proxy: cfg.outbound_proxy,
+            tls: cfg.tls,
+            api_log_path: cfg.debug.and_then(|debug| debug.api_log_path),
             agent_job_max_runtime_seconds,
             codex_home,
             sqlite_home,
             log_dir,
             config_layer_stack,
             history,
+            rollout_fsync: cfg.rollout_fsync.unwrap_or_default(),
             ephemeral: ephemeral.unwrap_or_default(),
+            read_only,
+            dry_run: dry_run.unwrap_or_default(),
             file_opener: cfg.file_opener.unwrap_or(UriBasedFileOpener::VsCode),
             codex_linux_sandbox_exe,
             main_execve_wrapper_exe,
@@ -2286,7 +2558,35 @@ impl Config {
                 .map(|t| t.alternate_screen)
                 .unwrap_or_default(),
             tui_status_line: cfg.tui.as_ref().and_then(|t| t.status_line.clone()),
+            tui_status_line_rows: cfg.tui.as_ref().and_then(|t| t.status_line_rows.clone()),
+            tui_status_line_commands: cfg
+                .tui
+                .as_ref()
+                .map(|t| t.status_line_commands.clone())
+                .unwrap_or_default(),
+            tui_footer_hints: cfg.tui.as_ref().and_then(|t| t.footer_hints.clone()),
+            tui_context_warnings: cfg
+                .tui
+                .as_ref()
+                .map(|t| t.context_warnings.clone())
+                .unwrap_or_default(),
             tui_theme: cfg.tui.as_ref().and_then(|t| t.theme.clone()),
+            tui_interrupt_key: cfg.tui.as_ref().and_then(|t| t.interrupt_key.clone()),
+            tui_terminal_title: cfg.tui.as_ref().is_none_or(|t| t.terminal_title),
+            tui_tmux: cfg.tui.as_ref().is_some_and(|t| t.tmux),
+            tui_terminal_badge: cfg.tui.as_ref().is_some_and(|t| t.terminal_badge),
+            tui_transcript_memory_lines: cfg
+                .tui
+                .as_ref()
+                .and_then(|t| t.transcript_memory_lines)
+                .unwrap_or(DEFAULT_TUI_TRANSCRIPT_MEMORY_LINES),
+            tui_timestamps: cfg.tui.as_ref().map(|t| t.timestamps).unwrap_or_default(),
+            tui_summarize_on_exit: cfg.tui.as_ref().is_some_and(|t| t.summarize_on_exit),
+            tui_spellcheck: cfg
+                .tui
+                .as_ref()
+                .map(|t| t.spellcheck.clone())
+                .unwrap_or_default(),
             otel: {
                 let t: OtelConfigToml = cfg.otel.unwrap_or_default();
                 let log_user_prompt = t.log_user_prompt.unwrap_or(false);
@@ -2681,7 +2981,19 @@ consolidation_model = "gpt-5"
                 show_tooltips: true,
                 alternate_screen: AltScreenMode::default(),
                 status_line: None,
+                status_line_rows: None,
+                status_line_commands: BTreeMap::new(),
+                footer_hints: None,
+                context_warnings: ContextWarningsConfig::default(),
                 theme: None,
+                interrupt_key: None,
+                terminal_title: true,
+                tmux: false,
+                terminal_badge: false,
+                transcript_memory_lines: None,
+                timestamps: HistoryTimestamps::Off,
+                summarize_on_exit: false,
+                spellcheck: SpellcheckConfig::default(),
                 model_availability_nux: ModelAvailabilityNuxConfig {
                     shown_count: HashMap::from([
                         ("gpt-bar".to_string(), 4),
@@ -2840,7 +3152,19 @@ theme = "dracula"
                 show_tooltips: true,
                 alternate_screen: AltScreenMode::Auto,
                 status_line: None,
+                status_line_rows: None,
+                status_line_commands: BTreeMap::new(),
+                footer_hints: None,
+                context_warnings: ContextWarningsConfig::default(),
                 theme: None,
+                interrupt_key: None,
+                terminal_title: true,
+                tmux: false,
+                terminal_badge: false,
+                transcript_memory_lines: None,
+                timestamps: HistoryTimestamps::Off,
+                summarize_on_exit: false,
+                spellcheck: SpellcheckConfig::default(),
                 model_availability_nux: ModelAvailabilityNuxConfig::default(),
             }
         );
@@ -3537,6 +3861,61 @@ profile = "project"
         Ok(())
     }
 
+    #[test]
+    fn read_only_override_forces_read_only_sandbox() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        let cfg = ConfigToml {
+            sandbox_mode: Some(SandboxMode::DangerFullAccess),
+            ..Default::default()
+        };
+
+        let overrides = ConfigOverrides {
+            sandbox_mode: Some(SandboxMode::WorkspaceWrite),
+            read_only: Some(true),
+            ..Default::default()
+        };
+
+        let config = Config::load_from_base_config_with_overrides(
+            cfg,
+            overrides,
+            codex_home.path().to_path_buf(),
+        )?;
+
+        assert!(config.read_only);
+        assert!(matches!(
+            config.permissions.sandbox_policy.get(),
+            SandboxPolicy::ReadOnly { .. }
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn dry_run_override_forces_read_only_sandbox() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        let cfg = ConfigToml {
+            sandbox_mode: Some(SandboxMode::DangerFullAccess),
+            ..Default::default()
+        };
+
+        let config = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides {
+                dry_run: Some(true),
+                ..Default::default()
+            },
+            codex_home.path().to_path_buf(),
+        )?;
+
+        assert!(config.dry_run);
+        assert!(matches!(
+            config.permissions.sandbox_policy.get(),
+            SandboxPolicy::ReadOnly { .. }
+        ));
+
+        Ok(())
+    }
+
     #[test]
     fn feature_table_overrides_legacy_flags() -> std::io::Result<()> {
         let codex_home = TempDir::new()?;
@@ -5212,6 +5591,25 @@ model_verbosity = "high"
                 agent_max_depth: DEFAULT_AGENT_MAX_DEPTH,
                 agent_roles: BTreeMap::new(),
                 memories: MemoriesConfig::default(),
+                semantic_search: SemanticSearchConfig::default(),
+                audio_transcription: AudioTranscriptionConfig::default(),
+                fetch_url: FetchUrlConfig::default(),
+                environment_context: EnvironmentContextConfig::default(),
+                compare_models: Vec::new(),
+                require_plan_approval: false,
+                confirm_auto_compaction: false,
+                webhooks: Vec::new(),
+                tool_plugins: BTreeMap::new(),
+                tool_policies: BTreeMap::new(),
+                post_patch_command: None,
+                patch_checks: Vec::new(),
+                forge: ForgeConfig::default(),
+                attachments: AttachmentsConfig::default(),
+                image_upload: ImageLimits::default(),
+                web_search_provider: WebSearchProviderConfig::default(),
+                outbound_proxy: None,
+                tls: None,
+                api_log_path: None,
                 agent_job_max_runtime_seconds: DEFAULT_AGENT_JOB_MAX_RUNTIME_SECONDS,
                 codex_home: fixture.codex_home(),
                 sqlite_home: fixture.codex_home(),
@@ -5219,7 +5617,10 @@ model_verbosity = "high"
                 config_layer_stack: Default::default(),
                 startup_warnings: Vec::new(),
                 history: History::default(),
+                rollout_fsync: RolloutFsyncPolicy::default(),
                 ephemeral: false,
+                read_only: false,
+                dry_run: false,
                 file_opener: UriBasedFileOpener::VsCode,
                 codex_linux_sandbox_exe: None,
                 main_execve_wrapper_exe: None,
@@ -5254,7 +5655,10 @@ model_verbosity = "high"
                 features: Features::with_defaults().into(),
                 suppress_unstable_features_warning: false,
                 active_profile: Some("o3".to_string()),
-                active_project: ProjectConfig { trust_level: None },
+                active_project: ProjectConfig {
+                    trust_level: None,
+                    shell_environment_set: None,
+                },
                 windows_wsl_setup_acknowledged: false,
                 notices: Default::default(),
                 check_for_update_on_startup: true,
@@ -5268,7 +5672,19 @@ model_verbosity = "high"
                 feedback_enabled: true,
                 tui_alternate_screen: AltScreenMode::Auto,
                 tui_status_line: None,
+                tui_status_line_rows: None,
+                tui_status_line_commands: BTreeMap::new(),
+                tui_footer_hints: None,
+                tui_context_warnings: ContextWarningsConfig::default(),
                 tui_theme: None,
+                tui_interrupt_key: None,
+                tui_terminal_title: true,
+                tui_tmux: false,
+                tui_terminal_badge: false,
+                tui_transcript_memory_lines: DEFAULT_TUI_TRANSCRIPT_MEMORY_LINES,
+                tui_timestamps: HistoryTimestamps::Off,
+                tui_summarize_on_exit: false,
+                tui_spellcheck: SpellcheckConfig::default(),
                 otel: OtelConfig::default(),
             },
             o3_profile_config
@@ -5342,6 +5758,25 @@ model_verbosity = "high"
             agent_max_depth: DEFAULT_AGENT_MAX_DEPTH,
             agent_roles: BTreeMap::new(),
             memories: MemoriesConfig::default(),
+            semantic_search: SemanticSearchConfig::default(),
+            audio_transcription: AudioTranscriptionConfig::default(),
+            fetch_url: FetchUrlConfig::default(),
+            environment_context: EnvironmentContextConfig::default(),
+            compare_models: Vec::new(),
+            require_plan_approval: false,
+            confirm_auto_compaction: false,
+            webhooks: Vec::new(),
+            tool_plugins: BTreeMap::new(),
+            tool_policies: BTreeMap::new(),
+            post_patch_command: None,
+            patch_checks: Vec::new(),
+            forge: ForgeConfig::default(),
+            attachments: AttachmentsConfig::default(),
+            image_upload: ImageLimits::default(),
+            web_search_provider: WebSearchProviderConfig::default(),
+            outbound_proxy: None,
+            tls: None,
+            api_log_path: None,
             agent_job_max_runtime_seconds: DEFAULT_AGENT_JOB_MAX_RUNTIME_SECONDS,
             codex_home: fixture.codex_home(),
             sqlite_home: fixture.codex_home(),
@@ -5349,7 +5784,10 @@ model_verbosity = "high"
             config_layer_stack: Default::default(),
             startup_warnings: Vec::new(),
             history: History::default(),
+            rollout_fsync: RolloutFsyncPolicy::default(),
             ephemeral: false,
+            read_only: false,
+            dry_run: false,
             file_opener: UriBasedFileOpener::VsCode,
             codex_linux_sandbox_exe: None,
             main_execve_wrapper_exe: None,
@@ -5384,7 +5822,10 @@ model_verbosity = "high"
             features: Features::with_defaults().into(),
             suppress_unstable_features_warning: false,
             active_profile: Some("gpt3".to_string()),
-            active_project: ProjectConfig { trust_level: None },
+            active_project: ProjectConfig {
+                trust_level: None,
+                shell_environment_set: None,
+            },
             windows_wsl_setup_acknowledged: false,
             notices: Default::default(),
             check_for_update_on_startup: true,
@@ -5398,7 +5839,19 @@ model_verbosity = "high"
             feedback_enabled: true,
             tui_alternate_screen: AltScreenMode::Auto,
             tui_status_line: None,
+            tui_status_line_rows: None,
+            tui_status_line_commands: BTreeMap::new(),
+            tui_footer_hints: None,
+            tui_context_warnings: ContextWarningsConfig::default(),
             tui_theme: None,
+            tui_interrupt_key: None,
+            tui_terminal_title: true,
+            tui_tmux: false,
+            tui_terminal_badge: false,
+            tui_transcript_memory_lines: DEFAULT_TUI_TRANSCRIPT_MEMORY_LINES,
+            tui_timestamps: HistoryTimestamps::Off,
+            tui_summarize_on_exit: false,
+            tui_spellcheck: SpellcheckConfig::default(),
             otel: OtelConfig::default(),
         };
 
@@ -5470,6 +5923,25 @@ model_verbosity = "high"
             agent_max_depth: DEFAULT_AGENT_MAX_DEPTH,
             agent_roles: BTreeMap::new(),
             memories: MemoriesConfig::default(),
+            semantic_search: SemanticSearchConfig::default(),
+            audio_transcription: AudioTranscriptionConfig::default(),
+            fetch_url: FetchUrlConfig::default(),
+            environment_context: EnvironmentContextConfig::default(),
+            compare_models: Vec::new(),
+            require_plan_approval: false,
+            confirm_auto_compaction: false,
+            webhooks: Vec::new(),
+            tool_plugins: BTreeMap::new(),
+            tool_policies: BTreeMap::new(),
+            post_patch_command: None,
+            patch_checks: Vec::new(),
+            forge: ForgeConfig::default(),
+            attachments: AttachmentsConfig::default(),
+            image_upload: ImageLimits::default(),
+            web_search_provider: WebSearchProviderConfig::default(),
+            outbound_proxy: None,
+            tls: None,
+            api_log_path: None,
             agent_job_max_runtime_seconds: DEFAULT_AGENT_JOB_MAX_RUNTIME_SECONDS,
             codex_home: fixture.codex_home(),
             sqlite_home: fixture.codex_home(),
@@ -5477,7 +5949,10 @@ model_verbosity = "high"
             config_layer_stack: Default::default(),
             startup_warnings: Vec::new(),
             history: History::default(),
+            rollout_fsync: RolloutFsyncPolicy::default(),
             ephemeral: false,
+            read_only: false,
+            dry_run: false,
             file_opener: UriBasedFileOpener::VsCode,
             codex_linux_sandbox_exe: None,
             main_execve_wrapper_exe: None,
@@ -5512,7 +5987,10 @@ model_verbosity = "high"
             features: Features::with_defaults().into(),
             suppress_unstable_features_warning: false,
             active_profile: Some("zdr".to_string()),
-            active_project: ProjectConfig { trust_level: None },
+            active_project: ProjectConfig {
+                trust_level: None,
+                shell_environment_set: None,
+            },
             windows_wsl_setup_acknowledged: false,
             notices: Default::default(),
             check_for_update_on_startup: true,
@@ -5526,7 +6004,19 @@ model_verbosity = "high"
             feedback_enabled: true,
             tui_alternate_screen: AltScreenMode::Auto,
             tui_status_line: None,
+            tui_status_line_rows: None,
+            tui_status_line_commands: BTreeMap::new(),
+            tui_footer_hints: None,
+            tui_context_warnings: ContextWarningsConfig::default(),
             tui_theme: None,
+            tui_interrupt_key: None,
+            tui_terminal_title: true,
+            tui_tmux: false,
+            tui_terminal_badge: false,
+            tui_transcript_memory_lines: DEFAULT_TUI_TRANSCRIPT_MEMORY_LINES,
+            tui_timestamps: HistoryTimestamps::Off,
+            tui_summarize_on_exit: false,
+            tui_spellcheck: SpellcheckConfig::default(),
             otel: OtelConfig::default(),
         };
 
@@ -5584,6 +6074,25 @@ model_verbosity = "high"
             agent_max_depth: DEFAULT_AGENT_MAX_DEPTH,
             agent_roles: BTreeMap::new(),
             memories: MemoriesConfig::default(),
+            semantic_search: SemanticSearchConfig::default(),
+            audio_transcription: AudioTranscriptionConfig::default(),
+            fetch_url: FetchUrlConfig::default(),
+            environment_context: EnvironmentContextConfig::default(),
+            compare_models: Vec::new(),
+            require_plan_approval: false,
+            confirm_auto_compaction: false,
+            webhooks: Vec::new(),
+            tool_plugins: BTreeMap::new(),
+            tool_policies: BTreeMap::new(),
+            post_patch_command: None,
+            patch_checks: Vec::new(),
+            forge: ForgeConfig::default(),
+            attachments: AttachmentsConfig::default(),
+            image_upload: ImageLimits::default(),
+            web_search_provider: WebSearchProviderConfig::default(),
+            outbound_proxy: None,
+            tls: None,
+            api_log_path: None,
             agent_job_max_runtime_seconds: DEFAULT_AGENT_JOB_MAX_RUNTIME_SECONDS,
             codex_home: fixture.codex_home(),
             sqlite_home: fixture.codex_home(),
@@ -5591,7 +6100,10 @@ model_verbosity = "high"
             config_layer_stack: Default::default(),
             startup_warnings: Vec::new(),
             history: History::default(),
+            rollout_fsync: RolloutFsyncPolicy::default(),
             ephemeral: false,
+            read_only: false,
+            dry_run: false,
             file_opener: UriBasedFileOpener::VsCode,
             codex_linux_sandbox_exe: None,
             main_execve_wrapper_exe: None,
@@ -5626,7 +6138,10 @@ model_verbosity = "high"
             features: Features::with_defaults().into(),
             suppress_unstable_features_warning: false,
             active_profile: Some("gpt5".to_string()),
-            active_project: ProjectConfig { trust_level: None },
+            active_project: ProjectConfig {
+                trust_level: None,
+                shell_environment_set: None,
+            },
             windows_wsl_setup_acknowledged: false,
             notices: Default::default(),
             check_for_update_on_startup: true,
@@ -5640,7 +6155,19 @@ model_verbosity = "high"
             feedback_enabled: true,
             tui_alternate_screen: AltScreenMode::Auto,
             tui_status_line: None,
+            tui_status_line_rows: None,
+            tui_status_line_commands: BTreeMap::new(),
+            tui_footer_hints: None,
+            tui_context_warnings: ContextWarningsConfig::default(),
             tui_theme: None,
+            tui_interrupt_key: None,
+            tui_terminal_title: true,
+            tui_tmux: false,
+            tui_terminal_badge: false,
+            tui_transcript_memory_lines: DEFAULT_TUI_TRANSCRIPT_MEMORY_LINES,
+            tui_timestamps: HistoryTimestamps::Off,
+            tui_summarize_on_exit: false,
+            tui_spellcheck: SpellcheckConfig::default(),
             otel: OtelConfig::default(),
         };
 
@@ -5955,6 +6482,7 @@ trust_level = "untrusted"
                 project_key,
                 ProjectConfig {
                     trust_level: Some(TrustLevel::Trusted),
+                    shell_environment_set: None,
                 },
             )])),
             ..Default::default()
@@ -5995,6 +6523,7 @@ trust_level = "untrusted"
                 project_key,
                 ProjectConfig {
                     trust_level: Some(TrustLevel::Trusted),
+                    shell_environment_set: None,
                 },
             )])),
             ..Default::default()
@@ -6203,6 +6732,7 @@ mcp_oauth_callback_url = "https://example.com/callback"
                     test_path.to_string_lossy().to_string(),
                     ProjectConfig {
                         trust_level: Some(TrustLevel::Untrusted),
+                        shell_environment_set: None,
                     },
                 )])),
                 ..Default::default()
@@ -0,0 +1,149 @@
+//! Validation of `config.toml` files against the config schema.
+//!
+//! Loading a config only reports the first value serde rejects, and silently ignores keys it
+//! does not know. [`validate_config_toml`] reports everything at once, with locations, and is
+//! used by `codex config validate` and for the unknown-key warnings shown at startup.
+
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::LazyLock;
+
+use serde_json::Value as JsonValue;
+
+use crate::config::CONFIG_TOML_FILE;
+use crate::config::schema::config_schema;
+use crate::config_loader::ConfigIssue;
+use crate::config_loader::ConfigIssueSeverity;
+use crate::config_loader::ConfigLayerStack;
+use crate::config_loader::ConfigLayerStackOrdering;
+
+/// Keys serde still accepts as aliases of renamed settings; the schema only lists the new
+/// names.
+const LEGACY_KEYS: &[&str] = &[
+    "experimental_instructions_file",
+    "web_search_request",
+    "ignore_untracked_files_over_bytes",
+    "large_untracked_dir_warning_threshold",
+];
+
+static CONFIG_SCHEMA_JSON: LazyLock<JsonValue> =
+    LazyLock::new(|| serde_json::to_value(config_schema()).unwrap_or(JsonValue::Bool(true)));
+
+/// Check the contents of a `config.toml` file, returning issues sorted by location.
+pub fn validate_config_toml(path: impl AsRef<Path>, contents: &str) -> Vec<ConfigIssue> {
+    codex_config::validate_toml_against_schema(path, contents, &CONFIG_SCHEMA_JSON, LEGACY_KEYS)
+}
+
+/// The `config.toml` files backing `layers`, lowest precedence first.
+pub fn config_files_for_layers(layers: &ConfigLayerStack) -> Vec<PathBuf> {
+    layers
+        .get_layers(ConfigLayerStackOrdering::LowestPrecedenceFirst, false)
+        .into_iter()
+        .filter_map(|layer| codex_config::config_path_for_layer(layer, CONFIG_TOML_FILE))
+        .collect()
+}
+
+/// Validate every config file in `layers` that exists on disk.
+pub async fn validate_config_layers(layers: &ConfigLayerStack) -> Vec<(PathBuf, Vec<ConfigIssue>)> {
+    let mut results = Vec::new();
+    for path in config_files_for_layers(layers) {
+        let contents = match tokio::fs::read_to_string(&path).await {
+            Ok(contents) => contents,
+            Err(err) => {
+                if err.kind() != std::io::ErrorKind::NotFound {
+                    tracing::debug!("Failed to read config file {}: {err}", path.display());
+                }
+                continue;
+            }
+        };
+        let issues = validate_config_toml(&path, &contents);
+        results.push((path, issues));
+    }
+    results
+}
+
+/// Startup warnings for keys in the loaded config files that Codex ignores.
+pub(crate) async fn unknown_key_warnings(layers: &ConfigLayerStack) -> Vec<String> {
+    validate_config_layers(layers)
+        .await
+        .into_iter()
+        .flat_map(|(_, issues)| issues)
+        .filter(|issue| issue.severity == ConfigIssueSeverity::Warning)
+        .map(|issue| {
+            format!(
+                "Ignoring unknown config key `{}` at {}:{}:{}. Run `codex config validate` for details.",
+                issue.key,
+                issue.error.path.display(),
+                issue.error.range.start.line,
+                issue.error.range.start.column,
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn summarize(contents: &str) -> Vec<(ConfigIssueSeverity, String, usize)> {
+        validate_config_toml("config.toml", contents)
+            .into_iter()
+            .map(|issue| (issue.severity, issue.key, issue.error.range.start.line))
+            .collect()
+    }
+
+    #[test]
+    fn accepts_a_typical_config() {
+        let contents = r#"
+model = "gpt-5.1-codex"
+model_reasoning_effort = "high"
+approval_policy = "on-request"
+sandbox_mode = "workspace-write"
+
+[sandbox_workspace_write]
+network_access = true
+
+[tools]
+web_search_request = true
+
+[tui]
+notifications = ["agent-turn-complete"]
+
+[mcp_servers.docs]
+command = "docs-server"
+args = ["--stdio"]
+
+[profiles.fast]
+model = "gpt-5.1-codex-mini"
+"#;
+        assert_eq!(summarize(contents), Vec::new());
+    }
+
+    #[test]
+    fn reports_unknown_keys_types_and_enum_values() {
+        let contents = r#"model_reasoning_effort = "extreme"
+sandbox_mode = "workspace-write"
+modle = "gpt-5"
+
+[tui]
+notifications = "yes"
+"#;
+        assert_eq!(
+            summarize(contents),
+            vec![
+                (
+                    ConfigIssueSeverity::Error,
+                    "model_reasoning_effort".to_string(),
+                    1
+                ),
+                (ConfigIssueSeverity::Warning, "modle".to_string(), 3),
+                (
+                    ConfigIssueSeverity::Error,
+                    "tui.notifications".to_string(),
+                    6
+                ),
+            ]
+        );
+    }
+}
@@ -27,6 +27,8 @@ use toml::Value as TomlValue;
 pub use codex_config::CloudRequirementsLoadError;
 pub use codex_config::CloudRequirementsLoader;
 pub use codex_config::ConfigError;
+pub use codex_config::ConfigIssue;
+pub use codex_config::ConfigIssueSeverity;
 pub use codex_config::ConfigLayerEntry;
 pub use codex_config::ConfigLayerStack;
 pub use codex_config::ConfigLayerStackOrdering;
@@ -49,6 +49,7 @@ async fn make_config_for_test(
                 project_path.to_string_lossy().to_string(),
                 ProjectConfig {
                     trust_level: Some(trust_level),
+                    shell_environment_set: None,
                 },
             )])),
             project_root_markers,
@@ -0,0 +1,421 @@
+//! Break down what the next model request would contain, for the TUI's context inspector.
+//!
+//! The input is assembled the same way `run_turn` does it, and each item is filed under a
+//! section with an estimated token count, so it is easy to see whether something the model
+//! "forgot" was compacted away or never sent, or which tool output is taking up the context
+//! window. Tool definitions are not included.
+
+use std::collections::HashMap;
+
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::FunctionCallOutputBody;
+use codex_protocol::models::LocalShellAction;
+use codex_protocol::models::ReasoningItemReasoningSummary;
+use codex_protocol::models::ResponseItem;
+
+use crate::codex::Session;
+use crate::compact::content_items_to_text;
+use crate::compact::is_summary_message;
+use crate::context_manager::estimate_item_token_count;
+use crate::contextual_user_message::AGENTS_MD_FRAGMENT;
+use crate::contextual_user_message::ENVIRONMENT_CONTEXT_FRAGMENT;
+use crate::contextual_user_message::PINNED_FILES_FRAGMENT;
+use crate::truncate::approx_token_count;
+
+/// Text that replaces a tool output removed with `Op::TrimToolOutput`.
+pub(crate) const TRIMMED_TOOL_OUTPUT: &str =
+    "[output removed from the context by the user to save space]";
+
+/// Sections of a request, in the order they are reported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ContextSectionKind {
+    /// The model's base instructions.
+    Instructions,
+    /// Developer messages: permissions, collaboration mode, and other settings.
+    DeveloperMessages,
+    /// AGENTS.md and user instructions.
+    ProjectInstructions,
+    EnvironmentContext,
+    PinnedFiles,
+    /// The summary left behind by compaction.
+    CompactedSummary,
+    /// User and assistant messages, tool calls, and their outputs.
+    RecentTurns,
+}
+
+impl ContextSectionKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            ContextSectionKind::Instructions => "Instructions",
+            ContextSectionKind::DeveloperMessages => "Developer messages",
+            ContextSectionKind::ProjectInstructions => "Project instructions",
+            ContextSectionKind::EnvironmentContext => "Environment context",
+            ContextSectionKind::PinnedFiles => "Pinned files",
+            ContextSectionKind::CompactedSummary => "Compacted summary",
+            ContextSectionKind::RecentTurns => "Recent turns",
+        }
+    }
+}
+
+/// One input item: a message, a tool call, or a tool output.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContextEntry {
+    /// What the item is, e.g. `user` or `tool output: shell`.
+    pub heading: String,
+    /// Readable rendering of the item's content.
+    pub text: String,
+    /// Estimated tokens, using the same byte heuristic as the context-window estimate.
+    pub tokens: i64,
+    /// Set for tool outputs, which can be removed with `Op::TrimToolOutput`.
+    pub trimmable_call_id: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContextSection {
+    pub kind: ContextSectionKind,
+    pub entries: Vec<ContextEntry>,
+}
+
+impl ContextSection {
+    pub fn tokens(&self) -> i64 {
+        self.entries
+            .iter()
+            .map(|entry| entry.tokens)
+            .fold(0, i64::saturating_add)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NextTurnContext {
+    pub sections: Vec<ContextSection>,
+    pub context_window: Option<i64>,
+}
+
+impl NextTurnContext {
+    pub fn total_tokens(&self) -> i64 {
+        self.sections
+            .iter()
+            .map(ContextSection::tokens)
+            .fold(0, i64::saturating_add)
+    }
+
+    /// Tool outputs that can be trimmed, largest first.
+    pub fn trimmable_entries(&self) -> Vec<&ContextEntry> {
+        let mut entries = self
+            .sections
+            .iter()
+            .flat_map(|section| &section.entries)
+            .filter(|entry| entry.trimmable_call_id.is_some())
+            .collect::<Vec<_>>();
+        entries.sort_by_key(|entry| std::cmp::Reverse(entry.tokens));
+        entries
+    }
+}
+
+pub(crate) async fn inspect_next_turn(sess: &Session) -> NextTurnContext {
+    let turn_context = sess.new_default_turn().await;
+    let input = sess.sampling_request_input(&turn_context).await;
+    let base_instructions = sess.get_base_instructions().await;
+    build_next_turn_context(
+        &base_instructions.text,
+        &input,
+        turn_context.model_context_window(),
+    )
+}
+
+pub(crate) fn build_next_turn_context(
+    base_instructions: &str,
+    input: &[ResponseItem],
+    context_window: Option<i64>,
+) -> NextTurnContext {
+    let mut sections = vec![ContextSection {
+        kind: ContextSectionKind::Instructions,
+        entries: vec![ContextEntry {
+            heading: "instructions".to_string(),
+            text: base_instructions.trim_end().to_string(),
+            tokens: i64::try_from(approx_token_count(base_instructions)).unwrap_or(i64::MAX),
+            trimmable_call_id: None,
+        }],
+    }];
+    let tool_names = tool_names_by_call_id(input);
+    for item in input {
+        let Some(entry) = entry_for_item(item, &tool_names) else {
+            continue;
+        };
+        let kind = classify(item);
+        match sections.iter_mut().find(|section| section.kind == kind) {
+            Some(section) => section.entries.push(entry),
+            None => sections.push(ContextSection {
+                kind,
+                entries: vec![entry],
+            }),
+        }
+    }
+    sections.sort_by_key(|section| section.kind);
+    NextTurnContext {
+        sections,
+        context_window,
+    }
+}
+
+/// Replace the output of tool call `call_id` in `items` with [`TRIMMED_TOOL_OUTPUT`], returning
+/// whether it was found.
+pub(crate) fn trim_tool_output(items: &mut [ResponseItem], call_id: &str) -> bool {
+    let Some(output) = items.iter_mut().find_map(|item| match item {
+        ResponseItem::FunctionCallOutput {
+            call_id: id,
+            output,
+        }
+        | ResponseItem::CustomToolCallOutput {
+            call_id: id,
+            output,
+        } if id == call_id => Some(output),
+        _ => None,
+    }) else {
+        return false;
+    };
+    output.body = FunctionCallOutputBody::Text(TRIMMED_TOOL_OUTPUT.to_string());
+    true
+}
+
+fn tool_names_by_call_id(input: &[ResponseItem]) -> HashMap<&str, &str> {
+    input
+        .iter()
+        .filter_map(|item| match item {
+            ResponseItem::FunctionCall { name, call_id, .. }
+            | ResponseItem::CustomToolCall { name, call_id, .. } => {
+                Some((call_id.as_str(), name.as_str()))
+            }
+            ResponseItem::LocalShellCall {
+                call_id: Some(call_id),
+                ..
+            } => Some((call_id.as_str(), "shell")),
+            _ => None,
+        })
+        .collect()
+}
+
+fn classify(item: &ResponseItem) -> ContextSectionKind {
+    match item {
+        ResponseItem::Message { role, .. } if role == "developer" => {
+            ContextSectionKind::DeveloperMessages
+        }
+        ResponseItem::Message { role, content, .. } if role == "user" => {
+            let Some(text) = content.iter().find_map(|item| match item {
+                ContentItem::InputText { text } => Some(text.as_str()),
+                _ => None,
+            }) else {
+                return ContextSectionKind::RecentTurns;
+            };
+            if AGENTS_MD_FRAGMENT.matches_text(text) {
+                ContextSectionKind::ProjectInstructions
+            } else if ENVIRONMENT_CONTEXT_FRAGMENT.matches_text(text) {
+                ContextSectionKind::EnvironmentContext
+            } else if PINNED_FILES_FRAGMENT.matches_text(text) {
+                ContextSectionKind::PinnedFiles
+            } else if is_summary_message(text) {
+                ContextSectionKind::CompactedSummary
+            } else {
+                ContextSectionKind::RecentTurns
+            }
+        }
+        ResponseItem::Compaction { .. } => ContextSectionKind::CompactedSummary,
+        _ => ContextSectionKind::RecentTurns,
+    }
+}
+
+fn entry_for_item(item: &ResponseItem, tool_names: &HashMap<&str, &str>) -> Option<ContextEntry> {
+    let mut trimmable_call_id = None;
+    let (heading, text) = match item {
+        ResponseItem::Message { role, content, .. } => {
+            let images = content
+                .iter()
+                .filter(|item| matches!(item, ContentItem::InputImage { .. }))
+                .count();
+            let mut text = content_items_to_text(content).unwrap_or_default();
+            if images > 0 {
+                if !text.is_empty() {
+                    text.push('\n');
+                }
+                text.push_str(&format!("[{images} image(s)]"));
+            }
+            (role.clone(), text)
+        }
+        ResponseItem::Reasoning { summary, .. } => {
+            let summary = summary
+                .iter()
+                .map(|ReasoningItemReasoningSummary::SummaryText { text }| text.as_str())
+                .collect::<Vec<_>>()
+                .join("\n");
+            ("reasoning".to_string(), summary)
+        }
+        ResponseItem::LocalShellCall {
+            action: LocalShellAction::Exec(action),
+            ..
+        } => ("tool call: shell".to_string(), action.command.join(" ")),
+        ResponseItem::FunctionCall {
+            name, arguments, ..
+        } => (format!("tool call: {name}"), arguments.clone()),
+        ResponseItem::CustomToolCall { name, input, .. } => {
+            (format!("tool call: {name}"), input.clone())
+        }
+        ResponseItem::FunctionCallOutput { call_id, output }
+        | ResponseItem::CustomToolCallOutput { call_id, output } => {
+            let text = output.body.to_text().unwrap_or_default();
+            if text != TRIMMED_TOOL_OUTPUT {
+                trimmable_call_id = Some(call_id.clone());
+            }
+            let heading = match tool_names.get(call_id.as_str()) {
+                Some(name) => format!("tool output: {name}"),
+                None => "tool output".to_string(),
+            };
+            (heading, text)
+        }
+        ResponseItem::WebSearchCall { .. } => ("web search".to_string(), String::new()),
+        ResponseItem::ImageGenerationCall { revised_prompt, .. } => (
+            "image generation".to_string(),
+            revised_prompt.clone().unwrap_or_default(),
+        ),
+        ResponseItem::Compaction { .. } => {
+            ("compacted history".to_string(), "(encrypted)".to_string())
+        }
+        ResponseItem::GhostSnapshot { .. } | ResponseItem::Other => return None,
+    };
+    Some(ContextEntry {
+        heading,
+        text: text.trim_end().to_string(),
+        tokens: estimate_item_token_count(item),
+        trimmable_call_id,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compact::SUMMARY_PREFIX;
+    use codex_protocol::models::FunctionCallOutputPayload;
+    use pretty_assertions::assert_eq;
+
+    fn message(role: &str, text: &str) -> ResponseItem {
+        ResponseItem::Message {
+            id: None,
+            role: role.to_string(),
+            content: vec![ContentItem::InputText {
+                text: text.to_string(),
+            }],
+            end_turn: None,
+            phase: None,
+        }
+    }
+
+    fn shell_call(call_id: &str, output: &str) -> [ResponseItem; 2] {
+        [
+            ResponseItem::FunctionCall {
+                id: None,
+                name: "shell".to_string(),
+                arguments: "{\"command\":[\"cargo\",\"test\"]}".to_string(),
+                call_id: call_id.to_string(),
+            },
+            ResponseItem::FunctionCallOutput {
+                call_id: call_id.to_string(),
+                output: FunctionCallOutputPayload::from_text(output.to_string()),
+            },
+        ]
+    }
+
+    #[test]
+    fn groups_input_items_into_sections() {
+        let mut input = vec![
+            message("developer", "<permissions instructions>"),
+            message(
+                "user",
+                "# AGENTS.md instructions for /repo\n\n<INSTRUCTIONS>\nuse tabs\n</INSTRUCTIONS>",
+            ),
+            message(
+                "user",
+                "<environment_context>\n  <cwd>/repo</cwd>\n</environment_context>",
+            ),
+            message("user", &format!("{SUMMARY_PREFIX}\nwe renamed the crate")),
+            message("user", "run the tests"),
+        ];
+        input.extend(shell_call("call-1", "ok"));
+        input.push(message(
+            "user",
+            "<pinned_files>\n<file path=\"a\">\nx\n</file>\n</pinned_files>",
+        ));
+
+        let context = build_next_turn_context("be helpful", &input, Some(1000));
+        let kinds = context
+            .sections
+            .iter()
+            .map(|section| (section.kind, section.entries.len()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            kinds,
+            vec![
+                (ContextSectionKind::Instructions, 1),
+                (ContextSectionKind::DeveloperMessages, 1),
+                (ContextSectionKind::ProjectInstructions, 1),
+                (ContextSectionKind::EnvironmentContext, 1),
+                (ContextSectionKind::PinnedFiles, 1),
+                (ContextSectionKind::CompactedSummary, 1),
+                (ContextSectionKind::RecentTurns, 3),
+            ]
+        );
+        let recent = &context.sections[6];
+        assert_eq!(
+            recent
+                .entries
+                .iter()
+                .map(|entry| (entry.heading.as_str(), entry.text.as_str()))
+                .collect::<Vec<_>>(),
+            vec![
+                ("user", "run the tests"),
+                ("tool call: shell", "{\"command\":[\"cargo\",\"test\"]}"),
+                ("tool output: shell", "ok"),
+            ]
+        );
+        assert_eq!(
+            recent.entries[2].trimmable_call_id.as_deref(),
+            Some("call-1")
+        );
+        assert_eq!(
+            context.total_tokens(),
+            context
+                .sections
+                .iter()
+                .map(ContextSection::tokens)
+                .sum::<i64>()
+        );
+        assert!(recent.tokens() > 0);
+    }
+
+    #[test]
+    fn trimming_replaces_a_tool_output_and_lists_the_largest_first() {
+        let mut input = vec![message("user", "run the tests")];
+        input.extend(shell_call("small", "ok"));
+        input.extend(shell_call("large", &"test result line\n".repeat(500)));
+
+        let context = build_next_turn_context("", &input, None);
+        let trimmable = context
+            .trimmable_entries()
+            .into_iter()
+            .filter_map(|entry| entry.trimmable_call_id.as_deref())
+            .collect::<Vec<_>>();
+        assert_eq!(trimmable, vec!["large", "small"]);
+
+        assert!(trim_tool_output(&mut input, "large"));
+        assert!(!trim_tool_output(&mut input, "missing"));
+        let context = build_next_turn_context("", &input, None);
+        let trimmable = context
+            .trimmable_entries()
+            .into_iter()
+            .filter_map(|entry| entry.trimmable_call_id.as_deref())
+            .collect::<Vec<_>>();
+        assert_eq!(trimmable, vec!["small"]);
+        assert_eq!(
+            context.sections[1].entries[4].text,
+            TRIMMED_TOOL_OUTPUT.to_string()
+        );
+    }
+}
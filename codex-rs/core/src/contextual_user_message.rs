@@ -13,6 +13,12 @@ pub(crate) const TURN_ABORTED_OPEN_TAG: &str = "<turn_aborted>";
 pub(crate) const TURN_ABORTED_CLOSE_TAG: &str = "</turn_aborted>";
 pub(crate) const SUBAGENT_NOTIFICATION_OPEN_TAG: &str = "<subagent_notification>";
 pub(crate) const SUBAGENT_NOTIFICATION_CLOSE_TAG: &str = "</subagent_notification>";
+pub(crate) const ATTACHED_DOCUMENT_OPEN_TAG: &str = "<attached_document>";
+pub(crate) const ATTACHED_DOCUMENT_CLOSE_TAG: &str = "</attached_document>";
+pub(crate) const PINNED_FILES_OPEN_TAG: &str = "<pinned_files>";
+pub(crate) const PINNED_FILES_CLOSE_TAG: &str = "</pinned_files>";
+pub(crate) const CARRY_OVER_BRIEFING_OPEN_TAG: &str = "<carry_over_briefing>";
+pub(crate) const CARRY_OVER_BRIEFING_CLOSE_TAG: &str = "</carry_over_briefing>";
 
 #[derive(Clone, Copy)]
 pub(crate) struct ContextualUserFragmentDefinition {
@@ -84,6 +90,15 @@ pub(crate) const SUBAGENT_NOTIFICATION_FRAGMENT: ContextualUserFragmentDefinitio
         SUBAGENT_NOTIFICATION_OPEN_TAG,
         SUBAGENT_NOTIFICATION_CLOSE_TAG,
     );
+pub(crate) const ATTACHED_DOCUMENT_FRAGMENT: ContextualUserFragmentDefinition =
+    ContextualUserFragmentDefinition::new(ATTACHED_DOCUMENT_OPEN_TAG, ATTACHED_DOCUMENT_CLOSE_TAG);
+pub(crate) const PINNED_FILES_FRAGMENT: ContextualUserFragmentDefinition =
+    ContextualUserFragmentDefinition::new(PINNED_FILES_OPEN_TAG, PINNED_FILES_CLOSE_TAG);
+pub(crate) const CARRY_OVER_BRIEFING_FRAGMENT: ContextualUserFragmentDefinition =
+    ContextualUserFragmentDefinition::new(
+        CARRY_OVER_BRIEFING_OPEN_TAG,
+        CARRY_OVER_BRIEFING_CLOSE_TAG,
+    );
 
 const CONTEXTUAL_USER_FRAGMENTS: &[ContextualUserFragmentDefinition] = &[
     AGENTS_MD_FRAGMENT,
@@ -92,6 +107,9 @@ const CONTEXTUAL_USER_FRAGMENTS: &[ContextualUserFragmentDefinition] = &[
     USER_SHELL_COMMAND_FRAGMENT,
     TURN_ABORTED_FRAGMENT,
     SUBAGENT_NOTIFICATION_FRAGMENT,
+    ATTACHED_DOCUMENT_FRAGMENT,
+    PINNED_FILES_FRAGMENT,
+    CARRY_OVER_BRIEFING_FRAGMENT,
 ];
 
 pub(crate) fn is_contextual_user_fragment(content_item: &ContentItem) -> bool {
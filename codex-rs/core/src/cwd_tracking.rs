@@ -0,0 +1,101 @@
+//! Carry `cd` effects from one agent shell call to the next (`persistent_cwd`).
+//!
+//! Every shell call runs in a fresh process, so `cd crates/foo && cargo test` followed by
+//! `cargo fmt` would run the second command back in the session cwd. With the feature on,
+//! the session remembers where the last successful command left off and uses that as the
+//! default working directory for the next one, the way an interactive shell would.
+
+use std::path::Component;
+use std::path::Path;
+use std::path::PathBuf;
+
+use codex_shell_command::bash::parse_shell_lc_sequential_commands;
+
+/// The directory a successful run of `command` from `cwd` would leave an interactive shell in.
+///
+/// Only scripts made of plain commands joined by `&&` or `;` are understood, since every
+/// `cd` in such a script runs when the script succeeds. Returns `None` when the script does
+/// not change directory or its effect cannot be determined statically (`cd -`, `cd ~`,
+/// variables, `||`, pipelines, subshells, ...).
+pub(crate) fn cwd_after_command(command: &[String], cwd: &Path) -> Option<PathBuf> {
+    let commands = parse_shell_lc_sequential_commands(command)?;
+
+    let mut current = cwd.to_path_buf();
+    let mut changed = false;
+    for words in commands {
+        if words.first().map(String::as_str) != Some("cd") {
+            continue;
+        }
+        let [_, target] = words.as_slice() else {
+            return None;
+        };
+        if target == "-" || target.starts_with('~') || target.starts_with('-') {
+            return None;
+        }
+        current = normalize(&current.join(target));
+        changed = true;
+    }
+    (changed && current != cwd).then_some(current)
+}
+
+/// Resolve `.` and `..` lexically, without following symlinks.
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn bash(script: &str) -> Vec<String> {
+        vec!["bash".to_string(), "-lc".to_string(), script.to_string()]
+    }
+
+    #[test]
+    fn follows_cd_through_and_chains() {
+        let cwd = Path::new("/repo");
+
+        assert_eq!(
+            cwd_after_command(&bash("cd crates/core && cargo test"), cwd),
+            Some(PathBuf::from("/repo/crates/core"))
+        );
+        assert_eq!(
+            cwd_after_command(&bash("cd crates; cd ../docs"), cwd),
+            Some(PathBuf::from("/repo/docs"))
+        );
+        assert_eq!(
+            cwd_after_command(&bash("cd /tmp"), cwd),
+            Some(PathBuf::from("/tmp"))
+        );
+    }
+
+    #[test]
+    fn ignores_scripts_with_unknown_effects() {
+        let cwd = Path::new("/repo");
+
+        for script in [
+            "cargo test",
+            "cd",
+            "cd -",
+            "cd ~/src",
+            "cd $HOME",
+            "cd foo || exit 1",
+            "cd foo | cat",
+            "(cd foo && make)",
+            "cd .",
+        ] {
+            assert_eq!(cwd_after_command(&bash(script), cwd), None, "{script}");
+        }
+    }
+}
@@ -1,9 +1,17 @@
+use crate::config::types::ClientTlsConfig;
+use crate::config::types::OutboundProxyConfig;
 use crate::config_loader::ResidencyRequirement;
 use crate::spawn::CODEX_SANDBOX_ENV_VAR;
 use codex_client::CodexHttpClient;
 pub use codex_client::CodexRequestBuilder;
+use codex_client::ReqwestTransport;
+use codex_client::WireLog;
+use codex_utils_absolute_path::AbsolutePathBuf;
 use reqwest::header::HeaderMap;
 use reqwest::header::HeaderValue;
+use std::io;
+use std::path::Path;
+use std::sync::Arc;
 use std::sync::LazyLock;
 use std::sync::Mutex;
 use std::sync::RwLock;
@@ -36,6 +44,18 @@ pub struct Originator {
 static ORIGINATOR: LazyLock<RwLock<Option<Originator>>> = LazyLock::new(|| RwLock::new(None));
 static REQUIREMENTS_RESIDENCY: LazyLock<RwLock<Option<ResidencyRequirement>>> =
     LazyLock::new(|| RwLock::new(None));
+static OUTBOUND_PROXY: LazyLock<RwLock<Option<reqwest::Proxy>>> =
+    LazyLock::new(|| RwLock::new(None));
+static CLIENT_TLS: LazyLock<RwLock<Option<ClientTls>>> = LazyLock::new(|| RwLock::new(None));
+static API_WIRE_LOG: LazyLock<RwLock<Option<Arc<WireLog>>>> = LazyLock::new(|| RwLock::new(None));
+
+/// Certificates loaded from [`ClientTlsConfig`], kept parsed so every client build does not
+/// re-read the files.
+#[derive(Clone)]
+struct ClientTls {
+    root_certificates: Vec<reqwest::Certificate>,
+    identity: Option<reqwest::Identity>,
+}
 
 #[derive(Debug)]
 pub enum SetOriginatorError {
@@ -87,6 +107,166 @@ pub fn set_default_client_residency_requirement(enforce_residency: Option<Reside
     *guard = enforce_residency;
 }
 
+/// Route outbound HTTP clients (including MCP streamable HTTP clients) through `config`. With
+/// `None`, clients keep honoring `HTTPS_PROXY`/`HTTP_PROXY`/`ALL_PROXY` and `NO_PROXY`.
+///
+/// Fails when the proxy URL is invalid or `password_env` names an unset variable, so callers
+/// stop instead of silently connecting without the configured proxy or credentials.
+pub fn set_default_client_proxy(config: Option<&OutboundProxyConfig>) -> io::Result<()> {
+    let proxy = config.map(build_outbound_proxy).transpose()?;
+    codex_rmcp_client::set_outbound_proxy(proxy.clone());
+    let Ok(mut guard) = OUTBOUND_PROXY.write() else {
+        tracing::warn!("Failed to acquire outbound proxy lock");
+        return Ok(());
+    };
+    *guard = proxy;
+    Ok(())
+}
+
+fn build_outbound_proxy(config: &OutboundProxyConfig) -> io::Result<reqwest::Proxy> {
+    let mut proxy = reqwest::Proxy::all(config.url.as_str()).map_err(|err| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("invalid outbound_proxy url {}: {err}", config.url),
+        )
+    })?;
+    if let Some(username) = config.username.as_deref() {
+        let password = match config.password_env.as_deref() {
+            Some(var) => std::env::var(var).map_err(|_| {
+                io::Error::new(
+                    io::ErrorKind::NotFound,
+                    format!(
+                        "environment variable {var} named by outbound_proxy.password_env is not set"
+                    ),
+                )
+            })?,
+            None => String::new(),
+        };
+        proxy = proxy.basic_auth(username, &password);
+    }
+    let no_proxy = match config.no_proxy.as_ref() {
+        Some(hosts) => reqwest::NoProxy::from_string(&hosts.join(",")),
+        None => reqwest::NoProxy::from_env(),
+    };
+    Ok(proxy.no_proxy(no_proxy))
+}
+
+/// Apply the proxy set by [`set_default_client_proxy`], if any. Clients that do not go through
+/// [`build_reqwest_client`] (such as the login flows) use this directly.
+pub fn apply_outbound_proxy(builder: reqwest::ClientBuilder) -> reqwest::ClientBuilder {
+    match OUTBOUND_PROXY.read().ok().and_then(|guard| guard.clone()) {
+        Some(proxy) => builder.proxy(proxy),
+        None => builder,
+    }
+}
+
+/// Trust the extra CA bundle and present the client certificate from `config` on clients built by
+/// [`build_reqwest_client`]. Fails if a configured file cannot be read or parsed.
+pub fn set_default_client_tls(config: Option<&ClientTlsConfig>) -> io::Result<()> {
+    let tls = config.map(load_client_tls).transpose()?;
+    let Ok(mut guard) = CLIENT_TLS.write() else {
+        tracing::warn!("Failed to acquire client TLS lock");
+        return Ok(());
+    };
+    *guard = tls;
+    Ok(())
+}
+
+fn load_client_tls(config: &ClientTlsConfig) -> io::Result<ClientTls> {
+    let read = |path: &AbsolutePathBuf| {
+        std::fs::read(path).map_err(|err| {
+            io::Error::new(
+                err.kind(),
+                format!("failed to read {}: {err}", path.display()),
+            )
+        })
+    };
+    let root_certificates = match config.ca_certificate.as_ref() {
+        Some(path) => {
+            let certificates =
+                reqwest::Certificate::from_pem_bundle(&read(path)?).map_err(|err| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("failed to parse CA bundle {}: {err}", path.display()),
+                    )
+                })?;
+            if certificates.is_empty() {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("no certificates found in CA bundle {}", path.display()),
+                ));
+            }
+            certificates
+        }
+        None => Vec::new(),
+    };
+    let identity = match (&config.client_certificate, &config.client_private_key) {
+        (Some(cert_path), Some(key_path)) => {
+            let mut pem = read(cert_path)?;
+            pem.extend_from_slice(&read(key_path)?);
+            let identity = reqwest::Identity::from_pem(&pem).map_err(|err| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!(
+                        "failed to parse client identity using {} and {}: {err}",
+                        cert_path.display(),
+                        key_path.display()
+                    ),
+                )
+            })?;
+            Some(identity)
+        }
+        (Some(_), None) | (None, Some(_)) => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "tls.client_certificate and tls.client_private_key must both be set for mutual TLS",
+            ));
+        }
+        (None, None) => None,
+    };
+    Ok(ClientTls {
+        root_certificates,
+        identity,
+    })
+}
+
+/// Apply the certificates set by [`set_default_client_tls`], if any.
+pub fn apply_client_tls(mut builder: reqwest::ClientBuilder) -> reqwest::ClientBuilder {
+    let Some(tls) = CLIENT_TLS.read().ok().and_then(|guard| guard.clone()) else {
+        return builder;
+    };
+    for certificate in tls.root_certificates {
+        builder = builder.add_root_certificate(certificate);
+    }
+    if let Some(identity) = tls.identity {
+        // PEM identities are only understood by the rustls backend.
+        builder = builder.use_rustls_tls().identity(identity);
+    }
+    builder
+}
+
+/// Record sanitized model API requests and responses to `path` (`debug.api_log_path`). `None`
+/// turns the wire log off.
+pub fn set_default_client_api_log(path: Option<&Path>) {
+    let Ok(mut guard) = API_WIRE_LOG.write() else {
+        tracing::warn!("Failed to acquire API wire log lock");
+        return;
+    };
+    *guard = path.and_then(|path| match WireLog::new(path.to_path_buf()) {
+        Ok(wire_log) => Some(Arc::new(wire_log)),
+        Err(err) => {
+            tracing::warn!("failed to open API wire log {}: {err}", path.display());
+            None
+        }
+    });
+}
+
+/// Transport for model API clients, wired to the wire log set by [`set_default_client_api_log`].
+pub fn build_api_transport() -> ReqwestTransport {
+    let wire_log = API_WIRE_LOG.read().ok().and_then(|guard| guard.clone());
+    ReqwestTransport::new(build_reqwest_client()).with_wire_log(wire_log)
+}
+
 pub fn originator() -> Originator {
     if let Ok(guard) = ORIGINATOR.read()
         && let Some(originator) = guard.as_ref()
@@ -191,7 +371,10 @@ pub fn build_reqwest_client() -> reqwest::Client {
         .default_headers(default_headers());
     if is_sandboxed() {
         builder = builder.no_proxy();
+    } else {
+        builder = apply_outbound_proxy(builder);
     }
+    builder = apply_client_tls(builder);
 
     builder.build().unwrap_or_else(|_| reqwest::Client::new())
 }
@@ -249,6 +432,95 @@ mod tests {
         assert_eq!(is_first_party_chat_originator("codex_vscode"), false);
     }
 
+    #[tokio::test]
+    async fn outbound_proxy_forwards_requests_with_basic_auth() {
+        skip_if_no_network!();
+
+        use wiremock::Mock;
+        use wiremock::MockServer;
+        use wiremock::ResponseTemplate;
+        use wiremock::matchers::header;
+        use wiremock::matchers::method;
+        use wiremock::matchers::path;
+
+        let proxy_server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/probe"))
+            .and(header("proxy-authorization", "Basic dXNlcjo="))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&proxy_server)
+            .await;
+
+        let proxy = build_outbound_proxy(&OutboundProxyConfig {
+            url: proxy_server.uri(),
+            username: Some("user".to_string()),
+            password_env: None,
+            no_proxy: Some(Vec::new()),
+        })
+        .expect("valid proxy config");
+        let client = reqwest::Client::builder()
+            .proxy(proxy)
+            .build()
+            .expect("client");
+        let resp = client
+            .get("http://upstream.invalid/probe")
+            .send()
+            .await
+            .expect("request through proxy");
+        assert!(resp.status().is_success());
+    }
+
+    #[test]
+    fn outbound_proxy_accepts_socks_and_rejects_invalid_urls() {
+        let config = |url: &str| OutboundProxyConfig {
+            url: url.to_string(),
+            ..Default::default()
+        };
+        assert!(build_outbound_proxy(&config("socks5h://127.0.0.1:1080")).is_ok());
+        assert!(build_outbound_proxy(&config("not a proxy url")).is_err());
+    }
+
+    #[test]
+    fn outbound_proxy_rejects_unset_password_env() {
+        let var = "CODEX_TEST_OUTBOUND_PROXY_PASSWORD_UNSET";
+        let Err(err) = build_outbound_proxy(&OutboundProxyConfig {
+            url: "http://127.0.0.1:3128".to_string(),
+            username: Some("user".to_string()),
+            password_env: Some(var.to_string()),
+            no_proxy: None,
+        }) else {
+            panic!("unset password_env should be rejected");
+        };
+        assert_eq!(err.kind(), io::ErrorKind::NotFound);
+        assert!(err.to_string().contains(var), "{err}");
+    }
+
+    #[test]
+    fn client_tls_rejects_unusable_certificate_config() -> io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let empty_bundle = dir.path().join("empty.pem");
+        std::fs::write(&empty_bundle, "")?;
+        let empty_bundle = AbsolutePathBuf::from_absolute_path(empty_bundle)?;
+
+        let Err(err) = load_client_tls(&ClientTlsConfig {
+            ca_certificate: Some(empty_bundle.clone()),
+            ..Default::default()
+        }) else {
+            panic!("empty CA bundle should be rejected");
+        };
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+
+        let Err(err) = load_client_tls(&ClientTlsConfig {
+            client_certificate: Some(empty_bundle),
+            ..Default::default()
+        }) else {
+            panic!("client certificate without a key should be rejected");
+        };
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_create_client_sets_default_headers() {
         skip_if_no_network!();
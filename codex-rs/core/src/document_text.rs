@@ -0,0 +1,298 @@
+//! Text extraction for PDF and Word documents referenced in a user message.
+//!
+//! When a turn's input names an existing `.pdf` or `.docx` file (an `@`-mention, a pasted
+//! path, or an attachment), the extracted text is injected as a contextual user message so the
+//! model sees the document's contents instead of a binary path it cannot read. Text over the
+//! `[attachments]` limit is handled by [`crate::attachment_policy`].
+
+mod docx;
+mod pdf;
+
+use std::path::Path;
+use std::path::PathBuf;
+
+use codex_protocol::models::ResponseItem;
+use codex_protocol::user_input::UserInput;
+
+use crate::attachment_policy::FittedAttachment;
+use crate::attachment_policy::fit_attachment;
+use crate::codex::Session;
+use crate::codex::TurnContext;
+use crate::contextual_user_message::ATTACHED_DOCUMENT_FRAGMENT;
+
+/// Files larger than this are not opened.
+const MAX_DOCUMENT_FILE_BYTES: u64 = 50 * 1024 * 1024;
+/// Only the first few documents referenced in a single message are extracted.
+const MAX_DOCUMENTS_PER_TURN: usize = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DocumentKind {
+    Pdf,
+    Docx,
+}
+
+impl DocumentKind {
+    fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?;
+        if extension.eq_ignore_ascii_case("pdf") {
+            Some(Self::Pdf)
+        } else if extension.eq_ignore_ascii_case("docx") {
+            Some(Self::Docx)
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct DocumentInjections {
+    pub(crate) items: Vec<ResponseItem>,
+    pub(crate) warnings: Vec<String>,
+}
+
+/// Documents attached through the image channel cannot be sent as images; turn them back into
+/// path mentions so [`document_references`] picks them up.
+pub(crate) fn attached_documents_as_mentions(input: Vec<UserInput>) -> Vec<UserInput> {
+    input
+        .into_iter()
+        .map(|item| match item {
+            UserInput::LocalImage { path } if DocumentKind::from_path(&path).is_some() => {
+                UserInput::Text {
+                    text: format!("@{}", path.display()),
+                    text_elements: Vec::new(),
+                }
+            }
+            other => other,
+        })
+        .collect()
+}
+
+/// Existing PDF/docx files named in the text of `input`, resolved against `cwd`.
+pub(crate) fn document_references(input: &[UserInput], cwd: &Path) -> Vec<PathBuf> {
+    let mut paths: Vec<PathBuf> = Vec::new();
+    for item in input {
+        let UserInput::Text { text, .. } = item else {
+            continue;
+        };
+        for token in candidate_tokens(text) {
+            let token = token
+                .trim_start_matches('@')
+                .trim_matches(|c| matches!(c, '`' | '\'' | '(' | ')' | '<' | '>'))
+                .trim_end_matches(|c| matches!(c, '.' | ',' | ';' | ':' | '!' | '?'));
+            let candidate = Path::new(token);
+            if DocumentKind::from_path(candidate).is_none() {
+                continue;
+            }
+            let path = cwd.join(candidate);
+            if path.is_file() && !paths.contains(&path) {
+                paths.push(path);
+            }
+        }
+    }
+    paths.truncate(MAX_DOCUMENTS_PER_TURN);
+    paths
+}
+
+/// Whitespace-separated tokens, keeping double-quoted paths (as inserted for paths with
+/// spaces) together.
+fn candidate_tokens(text: &str) -> Vec<&str> {
+    let mut tokens = Vec::new();
+    let mut rest = text;
+    loop {
+        rest = rest.trim_start();
+        if rest.is_empty() {
+            return tokens;
+        }
+        let (token, next) = match rest.strip_prefix('"').or_else(|| rest.strip_prefix("@\"")) {
+            Some(quoted) => match quoted.find('"') {
+                Some(end) => (&quoted[..end], &quoted[end + 1..]),
+                None => (quoted, ""),
+            },
+            None => {
+                let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+                (&rest[..end], &rest[end..])
+            }
+        };
+        tokens.push(token);
+        rest = next;
+    }
+}
+
+/// Extract every referenced document, producing one contextual message per readable file and
+/// a warning for each file that yielded no text or was over the attachment limit.
+pub(crate) async fn build_document_injections(
+    sess: &Session,
+    turn_context: &TurnContext,
+    paths: Vec<PathBuf>,
+) -> DocumentInjections {
+    let mut injections = DocumentInjections::default();
+    for path in paths {
+        let extraction = {
+            let path = path.clone();
+            tokio::task::spawn_blocking(move || extract_document_pages(&path))
+                .await
+                .unwrap_or_else(|err| Err(format!("extraction task failed: {err}")))
+        };
+        match extraction {
+            Ok(pages) => {
+                let name = path.display().to_string();
+                let fitted = fit_attachment(sess, turn_context, &name, join_pages(&pages)).await;
+                if let Some(note) = &fitted.note {
+                    injections.warnings.push(note.clone());
+                }
+                injections
+                    .items
+                    .push(ATTACHED_DOCUMENT_FRAGMENT.into_message(format_document(&path, &fitted)));
+            }
+            Err(err) => injections.warnings.push(format!(
+                "Could not extract text from {}: {err}",
+                path.display()
+            )),
+        }
+    }
+    injections
+}
+
+fn extract_document_pages(path: &Path) -> Result<Vec<String>, String> {
+    let Some(kind) = DocumentKind::from_path(path) else {
+        return Err("unsupported document type".to_string());
+    };
+    let size = std::fs::metadata(path)
+        .map_err(|err| err.to_string())?
+        .len();
+    if size > MAX_DOCUMENT_FILE_BYTES {
+        return Err(format!(
+            "file is {size} bytes; the limit is {MAX_DOCUMENT_FILE_BYTES}"
+        ));
+    }
+    let data = std::fs::read(path).map_err(|err| err.to_string())?;
+    let pages = match kind {
+        DocumentKind::Pdf => pdf::extract_pages(&data)?,
+        DocumentKind::Docx => docx::extract_pages(&data)?,
+    };
+    if pages.iter().all(|page| page.trim().is_empty()) {
+        return Err("no extractable text (the document may be scanned images)".to_string());
+    }
+    Ok(pages)
+}
+
+fn join_pages(pages: &[String]) -> String {
+    let mut body = String::new();
+    if pages.len() == 1 {
+        body.push_str(&pages[0]);
+    } else {
+        for (index, page) in pages.iter().enumerate() {
+            if !body.is_empty() {
+                body.push('\n');
+            }
+            body.push_str(&format!(
+                "--- page {} of {} ---\n{page}\n",
+                index + 1,
+                pages.len()
+            ));
+        }
+    }
+    body.trim_end().to_string()
+}
+
+fn format_document(path: &Path, fitted: &FittedAttachment) -> String {
+    let mut document = format!("<path>{}</path>\n", path.display());
+    if let Some(note) = &fitted.note {
+        document.push_str(&format!("[{note}]\n"));
+    }
+    if let Some(text) = &fitted.text {
+        document.push_str(text);
+    }
+    ATTACHED_DOCUMENT_FRAGMENT.wrap(document.trim_end().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn text_input(text: &str) -> Vec<UserInput> {
+        vec![UserInput::Text {
+            text: text.to_string(),
+            text_elements: Vec::new(),
+        }]
+    }
+
+    #[test]
+    fn finds_mentioned_documents_relative_to_cwd() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        std::fs::write(dir.path().join("spec.pdf"), b"%PDF-1.4").expect("write pdf");
+        std::fs::write(dir.path().join("my notes.docx"), b"PK").expect("write docx");
+        std::fs::write(dir.path().join("readme.md"), b"# hi").expect("write md");
+
+        let paths = document_references(
+            &text_input("Compare @spec.pdf, \"my notes.docx\" and readme.md; ignore missing.pdf."),
+            dir.path(),
+        );
+
+        assert_eq!(
+            paths,
+            vec![
+                dir.path().join("spec.pdf"),
+                dir.path().join("my notes.docx")
+            ]
+        );
+    }
+
+    #[test]
+    fn attached_documents_become_mentions() {
+        let input = attached_documents_as_mentions(vec![
+            UserInput::LocalImage {
+                path: PathBuf::from("/tmp/report.pdf"),
+            },
+            UserInput::LocalImage {
+                path: PathBuf::from("/tmp/chart.png"),
+            },
+        ]);
+
+        assert_eq!(
+            input,
+            vec![
+                UserInput::Text {
+                    text: "@/tmp/report.pdf".to_string(),
+                    text_elements: Vec::new(),
+                },
+                UserInput::LocalImage {
+                    path: PathBuf::from("/tmp/chart.png"),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn formats_pages_with_markers() {
+        let text = format_document(
+            Path::new("/tmp/report.pdf"),
+            &FittedAttachment {
+                text: Some(join_pages(&["Intro".to_string(), "Results".to_string()])),
+                note: None,
+            },
+        );
+
+        assert_eq!(
+            text,
+            "<attached_document>\n<path>/tmp/report.pdf</path>\n--- page 1 of 2 ---\nIntro\n\n--- page 2 of 2 ---\nResults\n</attached_document>"
+        );
+    }
+
+    #[test]
+    fn notes_oversized_documents_before_their_text() {
+        let text = format_document(
+            Path::new("/tmp/big.docx"),
+            &FittedAttachment {
+                text: None,
+                note: Some("/tmp/big.docx was not attached".to_string()),
+            },
+        );
+
+        assert_eq!(
+            text,
+            "<attached_document>\n<path>/tmp/big.docx</path>\n[/tmp/big.docx was not attached]\n</attached_document>"
+        );
+    }
+}
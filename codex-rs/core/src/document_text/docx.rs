@@ -0,0 +1,152 @@
+//! Text extraction for Word `.docx` files.
+//!
+//! A `.docx` is a zip archive whose body lives in `word/document.xml`. We keep the text runs
+//! (`<w:t>`), tabs, and line breaks, end paragraphs with newlines, and split pages on explicit
+//! or last-rendered page breaks so callers can add page markers.
+
+use std::io::Cursor;
+use std::io::Read;
+
+/// Upper bound on the inflated `document.xml`.
+const MAX_DOCUMENT_XML_BYTES: u64 = 32 * 1024 * 1024;
+
+/// Extract the text of each page. Documents without page breaks come back as one page.
+pub(super) fn extract_pages(data: &[u8]) -> Result<Vec<String>, String> {
+    let mut archive = zip::ZipArchive::new(Cursor::new(data))
+        .map_err(|err| format!("not a docx archive: {err}"))?;
+    let entry = archive
+        .by_name("word/document.xml")
+        .map_err(|_| "missing word/document.xml".to_string())?;
+    let mut xml = String::new();
+    entry
+        .take(MAX_DOCUMENT_XML_BYTES)
+        .read_to_string(&mut xml)
+        .map_err(|err| format!("failed to read word/document.xml: {err}"))?;
+    Ok(pages_from_document_xml(&xml))
+}
+
+fn pages_from_document_xml(xml: &str) -> Vec<String> {
+    let mut pages = Vec::new();
+    let mut page = String::new();
+    let mut in_text = false;
+    let mut rest = xml;
+    while let Some(open) = rest.find('<') {
+        if in_text {
+            page.push_str(&unescape_xml(&rest[..open]));
+        }
+        let Some(close) = rest[open..].find('>').map(|rel| open + rel) else {
+            break;
+        };
+        let tag = &rest[open + 1..close];
+        let self_closing = tag.ends_with('/');
+        let name = tag
+            .trim_start_matches('/')
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or_default();
+        match name {
+            "w:t" => in_text = !tag.starts_with('/') && !self_closing,
+            "w:tab" => page.push('\t'),
+            "w:br" if tag.contains("w:type=\"page\"") => {
+                pages.push(std::mem::take(&mut page));
+            }
+            "w:br" | "w:cr" => page.push('\n'),
+            "w:lastRenderedPageBreak" => pages.push(std::mem::take(&mut page)),
+            "w:p" if tag.starts_with('/') => page.push('\n'),
+            _ => {}
+        }
+        rest = &rest[close + 1..];
+    }
+    pages.push(page);
+    pages
+        .into_iter()
+        .map(|page| page.trim().to_string())
+        .filter(|page| !page.is_empty())
+        .collect()
+}
+
+fn unescape_xml(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(amp) = rest.find('&') {
+        out.push_str(&rest[..amp]);
+        let after = &rest[amp + 1..];
+        let Some(semi) = after.find(';').filter(|semi| *semi <= 8) else {
+            out.push('&');
+            rest = after;
+            continue;
+        };
+        let entity = &after[..semi];
+        let decoded = match entity {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            _ => entity
+                .strip_prefix("#x")
+                .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                .or_else(|| entity.strip_prefix('#').and_then(|dec| dec.parse().ok()))
+                .and_then(char::from_u32),
+        };
+        match decoded {
+            Some(c) => {
+                out.push(c);
+                rest = &after[semi + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = after;
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::io::Write;
+
+    #[test]
+    fn extracts_paragraphs_and_splits_on_page_breaks() {
+        let xml = r#"<w:document><w:body>
+            <w:p><w:r><w:t>Design &amp; scope</w:t></w:r></w:p>
+            <w:p><w:r><w:t xml:space="preserve">Owner:</w:t><w:tab/><w:t>Ada</w:t></w:r></w:p>
+            <w:p><w:r><w:br w:type="page"/><w:t>Appendix</w:t></w:r></w:p>
+        </w:body></w:document>"#;
+
+        assert_eq!(
+            pages_from_document_xml(xml),
+            vec![
+                "Design & scope\nOwner:\tAda".to_string(),
+                "Appendix".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn reads_document_xml_from_archive() {
+        let mut buffer = Cursor::new(Vec::new());
+        {
+            let mut writer = zip::ZipWriter::new(&mut buffer);
+            writer
+                .start_file(
+                    "word/document.xml",
+                    zip::write::SimpleFileOptions::default(),
+                )
+                .expect("start file");
+            writer
+                .write_all(b"<w:document><w:p><w:t>Hello docx</w:t></w:p></w:document>")
+                .expect("write xml");
+            writer.finish().expect("finish zip");
+        }
+
+        assert_eq!(
+            extract_pages(buffer.get_ref()).expect("pages"),
+            vec!["Hello docx".to_string()]
+        );
+    }
+}
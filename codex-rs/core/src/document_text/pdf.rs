@@ -0,0 +1,895 @@
+//! Minimal PDF text extraction.
+//!
+//! This is not a PDF renderer: it walks the page tree, inflates `FlateDecode` content streams
+//! (including objects packed into object streams), and collects the strings shown by the text
+//! operators. Fonts with a `ToUnicode` CMap are decoded through it; everything else is read as
+//! single-byte text. Scanned PDFs and exotic encodings simply yield no text, which the caller
+//! reports instead of sending binary data to the model.
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::io::Read;
+use std::sync::LazyLock;
+
+use flate2::read::ZlibDecoder;
+use regex_lite::Regex;
+
+/// Upper bound on a single inflated stream so a hostile file cannot balloon memory.
+const MAX_INFLATED_STREAM_BYTES: u64 = 16 * 1024 * 1024;
+/// Page tree nesting beyond this is treated as malformed.
+const MAX_PAGE_TREE_DEPTH: usize = 32;
+/// `bfrange` entries expanding to more codes than this are truncated.
+const MAX_CMAP_RANGE: u32 = 0x1_0000;
+
+static NAMED_REF_RE: LazyLock<Regex> =
+    LazyLock::new(
+        || match Regex::new(r"/([^\s/<>\[\]()]+)\s+(\d+)\s+\d+\s+R") {
+            Ok(regex) => regex,
+            Err(error) => panic!("invalid named reference regex: {error}"),
+        },
+    );
+static REF_RE: LazyLock<Regex> = LazyLock::new(|| match Regex::new(r"(\d+)\s+\d+\s+R") {
+    Ok(regex) => regex,
+    Err(error) => panic!("invalid reference regex: {error}"),
+});
+
+struct PdfObject {
+    dict: String,
+    stream: Option<Vec<u8>>,
+}
+
+/// Extract the text of each page, in page order.
+pub(super) fn extract_pages(data: &[u8]) -> Result<Vec<String>, String> {
+    if !data.starts_with(b"%PDF") {
+        return Err("not a PDF file".to_string());
+    }
+    let objects = parse_objects(data);
+    if objects.is_empty() {
+        return Err("no PDF objects found".to_string());
+    }
+    if contains(data, b"/Encrypt") {
+        return Err("the PDF is encrypted".to_string());
+    }
+
+    let mut pages = Vec::new();
+    if let Some(root) = objects
+        .values()
+        .find(|object| dict_name(&object.dict, "/Type") == Some("Catalog"))
+        .and_then(|catalog| dict_ref(&catalog.dict, "/Pages"))
+    {
+        let mut visited = HashSet::new();
+        collect_pages(&objects, root, None, 0, &mut visited, &mut pages);
+    }
+    if pages.is_empty() {
+        // Broken page tree: fall back to every content-looking stream in object order.
+        let mut ids: Vec<&u32> = objects.keys().collect();
+        ids.sort();
+        pages = ids
+            .into_iter()
+            .filter_map(|id| {
+                let object = &objects[id];
+                let stream = object.stream.as_ref()?;
+                (dict_name(&object.dict, "/Type").is_none() && contains(stream, b"BT")).then(|| {
+                    PageContent {
+                        streams: vec![*id],
+                        resources: None,
+                    }
+                })
+            })
+            .collect();
+    }
+
+    Ok(pages
+        .iter()
+        .map(|page| {
+            let fonts = page_fonts(&objects, page.resources.as_deref());
+            let mut text = String::new();
+            for id in &page.streams {
+                if let Some(stream) = objects.get(id).and_then(|object| object.stream.as_ref()) {
+                    extract_text_from_content(stream, &fonts, &mut text);
+                }
+            }
+            text.trim().to_string()
+        })
+        .collect())
+}
+
+struct PageContent {
+    streams: Vec<u32>,
+    /// Raw `/Resources` value (inline dict or reference), inherited from ancestors if absent.
+    resources: Option<String>,
+}
+
+fn collect_pages(
+    objects: &HashMap<u32, PdfObject>,
+    id: u32,
+    inherited_resources: Option<&str>,
+    depth: usize,
+    visited: &mut HashSet<u32>,
+    pages: &mut Vec<PageContent>,
+) {
+    if depth > MAX_PAGE_TREE_DEPTH || !visited.insert(id) {
+        return;
+    }
+    let Some(object) = objects.get(&id) else {
+        return;
+    };
+    let dict = &object.dict;
+    let resources = dict_value(dict, "/Resources").or(inherited_resources);
+    match dict_name(dict, "/Type") {
+        Some("Pages") => {
+            for kid in dict_value(dict, "/Kids").map(refs_in).unwrap_or_default() {
+                collect_pages(objects, kid, resources, depth + 1, visited, pages);
+            }
+        }
+        Some("Page") => {
+            let streams = dict_value(dict, "/Contents")
+                .map(|contents| {
+                    let refs = refs_in(contents);
+                    // `/Contents` may point at an array object rather than a stream.
+                    match refs.as_slice() {
+                        [single] if objects.get(single).is_some_and(|o| o.stream.is_none()) => {
+                            refs_in(&objects[single].dict)
+                        }
+                        _ => refs,
+                    }
+                })
+                .unwrap_or_default();
+            pages.push(PageContent {
+                streams,
+                resources: resources.map(str::to_string),
+            });
+        }
+        _ => {}
+    }
+}
+
+/// Resolve the `ToUnicode` CMaps of the fonts available to a page, keyed by resource name.
+fn page_fonts(objects: &HashMap<u32, PdfObject>, resources: Option<&str>) -> HashMap<String, CMap> {
+    let mut fonts = HashMap::new();
+    let Some(resources) = resources.map(|value| resolve(objects, value)) else {
+        return fonts;
+    };
+    let Some(font_dict) = dict_value(resources, "/Font").map(|value| resolve(objects, value))
+    else {
+        return fonts;
+    };
+    for captures in NAMED_REF_RE.captures_iter(font_dict) {
+        let (Some(name), Some(id)) = (captures.get(1), captures.get(2)) else {
+            continue;
+        };
+        let Ok(id) = id.as_str().parse::<u32>() else {
+            continue;
+        };
+        let cmap = objects
+            .get(&id)
+            .and_then(|font| dict_ref(&font.dict, "/ToUnicode"))
+            .and_then(|cmap_id| objects.get(&cmap_id))
+            .and_then(|cmap| cmap.stream.as_deref())
+            .map(|stream| CMap::parse(&String::from_utf8_lossy(stream)));
+        if let Some(cmap) = cmap {
+            fonts.insert(name.as_str().to_string(), cmap);
+        }
+    }
+    fonts
+}
+
+/// Follow a single indirect reference, returning the referenced dict text or `value` itself.
+fn resolve<'a>(objects: &'a HashMap<u32, PdfObject>, value: &'a str) -> &'a str {
+    match parse_ref(value) {
+        Some(id) => objects
+            .get(&id)
+            .map_or(value, |object| object.dict.as_str()),
+        None => value,
+    }
+}
+
+fn parse_objects(data: &[u8]) -> HashMap<u32, PdfObject> {
+    let mut objects = HashMap::new();
+    let mut object_streams = Vec::new();
+    let mut pos = 0;
+    while let Some(offset) = find(&data[pos..], b"obj") {
+        let keyword = pos + offset;
+        pos = keyword + 3;
+        if data.get(keyword.wrapping_sub(3)..keyword) == Some(&b"end"[..]) {
+            continue;
+        }
+        let Some(id) = object_id_before(data, keyword) else {
+            continue;
+        };
+        let body_start = keyword + 3;
+        let Some(endobj) = find(&data[body_start..], b"endobj").map(|rel| body_start + rel) else {
+            break;
+        };
+        let stream_keyword = find(&data[body_start..endobj], b"stream").map(|rel| body_start + rel);
+        let object = match stream_keyword {
+            Some(stream_keyword) => {
+                let dict = String::from_utf8_lossy(&data[body_start..stream_keyword]).into_owned();
+                let mut start = stream_keyword + b"stream".len();
+                if data.get(start) == Some(&b'\r') {
+                    start += 1;
+                }
+                if data.get(start) == Some(&b'\n') {
+                    start += 1;
+                }
+                let Some(end) = find(&data[start..], b"endstream").map(|rel| start + rel) else {
+                    break;
+                };
+                pos = end;
+                let stream = decode_stream(&dict, &data[start..end]);
+                PdfObject { dict, stream }
+            }
+            None => {
+                pos = endobj;
+                PdfObject {
+                    dict: String::from_utf8_lossy(&data[body_start..endobj]).into_owned(),
+                    stream: None,
+                }
+            }
+        };
+        if dict_name(&object.dict, "/Type") == Some("ObjStm") {
+            object_streams.push(object);
+        } else {
+            objects.insert(id, object);
+        }
+    }
+
+    for object_stream in object_streams {
+        unpack_object_stream(&object_stream, &mut objects);
+    }
+    objects
+}
+
+/// Parse `<id> <generation>` immediately preceding an `obj` keyword.
+fn object_id_before(data: &[u8], keyword: usize) -> Option<u32> {
+    let skip_space = |mut idx: usize| {
+        while idx > 0 && data[idx - 1].is_ascii_whitespace() {
+            idx -= 1;
+        }
+        idx
+    };
+    let skip_digits = |mut idx: usize| {
+        while idx > 0 && data[idx - 1].is_ascii_digit() {
+            idx -= 1;
+        }
+        idx
+    };
+    let end = skip_space(keyword);
+    let generation_start = skip_digits(end);
+    if generation_start == end {
+        return None;
+    }
+    let id_end = skip_space(generation_start);
+    if id_end == generation_start {
+        return None;
+    }
+    let id_start = skip_digits(id_end);
+    if id_start == id_end {
+        return None;
+    }
+    std::str::from_utf8(&data[id_start..id_end])
+        .ok()?
+        .parse()
+        .ok()
+}
+
+fn unpack_object_stream(object_stream: &PdfObject, objects: &mut HashMap<u32, PdfObject>) {
+    let Some(stream) = object_stream.stream.as_ref() else {
+        return;
+    };
+    let (Some(count), Some(first)) = (
+        dict_int(&object_stream.dict, "/N"),
+        dict_int(&object_stream.dict, "/First"),
+    ) else {
+        return;
+    };
+    let Some(header) = stream.get(..first) else {
+        return;
+    };
+    let numbers: Vec<usize> = String::from_utf8_lossy(header)
+        .split_ascii_whitespace()
+        .filter_map(|token| token.parse().ok())
+        .collect();
+    let entries: Vec<(usize, usize)> = numbers
+        .chunks_exact(2)
+        .take(count)
+        .map(|pair| (pair[0], first + pair[1]))
+        .collect();
+    for (index, (id, start)) in entries.iter().enumerate() {
+        let end = entries
+            .get(index + 1)
+            .map_or(stream.len(), |(_, next)| *next);
+        let (Some(body), Ok(id)) = (stream.get(*start..end), u32::try_from(*id)) else {
+            continue;
+        };
+        objects.entry(id).or_insert_with(|| PdfObject {
+            dict: String::from_utf8_lossy(body).into_owned(),
+            stream: None,
+        });
+    }
+}
+
+fn decode_stream(dict: &str, raw: &[u8]) -> Option<Vec<u8>> {
+    match dict_value(dict, "/Filter") {
+        None => Some(raw.to_vec()),
+        Some(filter) if filter.contains("FlateDecode") && !filter.contains("DCTDecode") => {
+            let mut inflated = Vec::new();
+            let result = ZlibDecoder::new(raw)
+                .take(MAX_INFLATED_STREAM_BYTES)
+                .read_to_end(&mut inflated);
+            // Truncated streams still carry useful text; keep whatever inflated cleanly.
+            if result.is_err() && inflated.is_empty() {
+                return None;
+            }
+            Some(inflated)
+        }
+        Some(_) => None,
+    }
+}
+
+/// Return the raw text of the value stored under `key` in a dictionary: a nested `<<...>>`,
+/// an `[...]` array, an `N G R` reference, or a single token.
+fn dict_value<'a>(dict: &'a str, key: &str) -> Option<&'a str> {
+    let mut search = 0;
+    while let Some(rel) = dict[search..].find(key) {
+        let start = search + rel + key.len();
+        search = start;
+        let rest = &dict[start..];
+        // Require a delimiter so `/Type` does not match `/TypeX`.
+        if rest
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_ascii_alphanumeric())
+        {
+            continue;
+        }
+        let trimmed = rest.trim_start();
+        if trimmed.is_empty() {
+            return None;
+        }
+        let offset = rest.len() - trimmed.len();
+        let value = if trimmed.starts_with("<<") {
+            balanced(trimmed, "<<", ">>")
+        } else if trimmed.starts_with('[') {
+            balanced(trimmed, "[", "]")
+        } else if let Some(captures) = REF_RE.captures(trimmed)
+            && captures.get(0).is_some_and(|m| m.start() == 0)
+        {
+            captures.get(0).map(|m| m.as_str())
+        } else {
+            let end = trimmed
+                .char_indices()
+                .skip(1)
+                .find(|(_, c)| c.is_whitespace() || "/<>[]()".contains(*c))
+                .map_or(trimmed.len(), |(idx, _)| idx);
+            Some(&trimmed[..end])
+        };
+        return value.map(|value| &dict[start + offset..start + offset + value.len()]);
+    }
+    None
+}
+
+fn balanced<'a>(text: &'a str, open: &str, close: &str) -> Option<&'a str> {
+    let mut depth = 0usize;
+    let mut idx = 0;
+    while idx < text.len() {
+        let rest = &text[idx..];
+        if rest.starts_with(open) {
+            depth += 1;
+            idx += open.len();
+        } else if rest.starts_with(close) {
+            depth = depth.saturating_sub(1);
+            idx += close.len();
+            if depth == 0 {
+                return Some(&text[..idx]);
+            }
+        } else {
+            idx += rest.chars().next().map_or(1, char::len_utf8);
+        }
+    }
+    None
+}
+
+fn dict_name<'a>(dict: &'a str, key: &str) -> Option<&'a str> {
+    dict_value(dict, key)?.strip_prefix('/')
+}
+
+fn dict_ref(dict: &str, key: &str) -> Option<u32> {
+    parse_ref(dict_value(dict, key)?)
+}
+
+fn dict_int(dict: &str, key: &str) -> Option<usize> {
+    dict_value(dict, key)?.parse().ok()
+}
+
+fn parse_ref(value: &str) -> Option<u32> {
+    let captures = REF_RE.captures(value.trim())?;
+    captures.get(1)?.as_str().parse().ok()
+}
+
+fn refs_in(value: &str) -> Vec<u32> {
+    REF_RE
+        .captures_iter(value)
+        .filter_map(|captures| captures.get(1)?.as_str().parse().ok())
+        .collect()
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
+
+fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+    find(haystack, needle).is_some()
+}
+
+/// Code-to-text mapping parsed from a font's `ToUnicode` stream.
+struct CMap {
+    code_len: usize,
+    map: HashMap<u32, String>,
+}
+
+impl CMap {
+    fn parse(text: &str) -> Self {
+        let mut cmap = Self {
+            code_len: 1,
+            map: HashMap::new(),
+        };
+        if let Some(section) = sections(text, "begincodespacerange", "endcodespacerange").next()
+            && let Some(Token::Hex(low)) = tokens(section).into_iter().next()
+        {
+            cmap.code_len = low.len().clamp(1, 4);
+        }
+        for section in sections(text, "beginbfchar", "endbfchar") {
+            let tokens = tokens(section);
+            for pair in tokens.chunks_exact(2) {
+                if let [Token::Hex(src), Token::Hex(dst)] = pair {
+                    cmap.map.insert(code_value(src), utf16_be(dst));
+                }
+            }
+        }
+        for section in sections(text, "beginbfrange", "endbfrange") {
+            let tokens = tokens(section);
+            let mut idx = 0;
+            while idx + 2 < tokens.len() {
+                let (Token::Hex(low), Token::Hex(high)) = (&tokens[idx], &tokens[idx + 1]) else {
+                    idx += 1;
+                    continue;
+                };
+                let (low, high) = (code_value(low), code_value(high));
+                let high = high.min(low.saturating_add(MAX_CMAP_RANGE));
+                match &tokens[idx + 2] {
+                    Token::Hex(dst) => {
+                        for (offset, code) in (low..=high).enumerate() {
+                            let mut dst = dst.clone();
+                            increment_last_code_unit(&mut dst, offset);
+                            cmap.map.insert(code, utf16_be(&dst));
+                        }
+                        idx += 3;
+                    }
+                    Token::ArrayStart => {
+                        let mut code = low;
+                        idx += 3;
+                        while let Some(Token::Hex(dst)) = tokens.get(idx) {
+                            cmap.map.insert(code, utf16_be(dst));
+                            code += 1;
+                            idx += 1;
+                        }
+                        idx += 1;
+                    }
+                    Token::ArrayEnd => idx += 3,
+                }
+            }
+        }
+        cmap
+    }
+
+    fn decode(&self, bytes: &[u8], out: &mut String) {
+        for chunk in bytes.chunks(self.code_len) {
+            let code = code_value(chunk);
+            if let Some(text) = self.map.get(&code) {
+                out.push_str(text);
+            } else if self.code_len == 1 {
+                push_latin1(chunk[0], out);
+            }
+        }
+    }
+}
+
+enum Token {
+    Hex(Vec<u8>),
+    ArrayStart,
+    ArrayEnd,
+}
+
+fn sections<'a>(text: &'a str, begin: &'a str, end: &'a str) -> impl Iterator<Item = &'a str> {
+    text.split(begin)
+        .skip(1)
+        .filter_map(move |chunk| chunk.split(end).next())
+}
+
+fn tokens(section: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut chars = section.char_indices().peekable();
+    while let Some((idx, c)) = chars.next() {
+        match c {
+            '<' => {
+                let rest = &section[idx + 1..];
+                let end = rest.find('>').unwrap_or(rest.len());
+                tokens.push(Token::Hex(hex_bytes(rest[..end].as_bytes())));
+                while chars.peek().is_some_and(|(next, _)| *next <= idx + 1 + end) {
+                    chars.next();
+                }
+            }
+            '[' => tokens.push(Token::ArrayStart),
+            ']' => tokens.push(Token::ArrayEnd),
+            _ => {}
+        }
+    }
+    tokens
+}
+
+fn code_value(bytes: &[u8]) -> u32 {
+    bytes
+        .iter()
+        .take(4)
+        .fold(0u32, |acc, byte| (acc << 8) | u32::from(*byte))
+}
+
+fn increment_last_code_unit(bytes: &mut [u8], offset: usize) {
+    let len = bytes.len();
+    if len >= 2 {
+        let unit = u16::from_be_bytes([bytes[len - 2], bytes[len - 1]]);
+        let unit = unit.wrapping_add(offset as u16).to_be_bytes();
+        bytes[len - 2] = unit[0];
+        bytes[len - 1] = unit[1];
+    } else if len == 1 {
+        bytes[0] = bytes[0].wrapping_add(offset as u8);
+    }
+}
+
+fn utf16_be(bytes: &[u8]) -> String {
+    let units: Vec<u16> = bytes
+        .chunks(2)
+        .map(|pair| u16::from_be_bytes([pair[0], pair.get(1).copied().unwrap_or(0)]))
+        .collect();
+    String::from_utf16_lossy(&units)
+}
+
+fn hex_bytes(hex: &[u8]) -> Vec<u8> {
+    let digits: Vec<u8> = hex
+        .iter()
+        .filter_map(|c| (*c as char).to_digit(16).map(|d| d as u8))
+        .collect();
+    digits
+        .chunks(2)
+        .map(|pair| (pair[0] << 4) | pair.get(1).copied().unwrap_or(0))
+        .collect()
+}
+
+fn push_latin1(byte: u8, out: &mut String) {
+    let c = char::from(byte);
+    if !c.is_control() || c == '\n' || c == '\t' {
+        out.push(c);
+    }
+}
+
+enum Operand {
+    Str(Vec<u8>),
+    Num(f32),
+    Name(String),
+    Array(Vec<Operand>),
+}
+
+/// Interpret a content stream just far enough to recover the shown text.
+fn extract_text_from_content(content: &[u8], fonts: &HashMap<String, CMap>, out: &mut String) {
+    let mut operands: Vec<Operand> = Vec::new();
+    let mut arrays: Vec<Vec<Operand>> = Vec::new();
+    let mut font: Option<&CMap> = None;
+    let mut last_line_y: Option<f32> = None;
+    let mut idx = 0;
+
+    let push = |operand: Operand, operands: &mut Vec<Operand>, arrays: &mut Vec<Vec<Operand>>| {
+        match arrays.last_mut() {
+            Some(array) => array.push(operand),
+            None => operands.push(operand),
+        }
+    };
+
+    while idx < content.len() {
+        let byte = content[idx];
+        match byte {
+            b if b.is_ascii_whitespace() => idx += 1,
+            b'%' => {
+                while idx < content.len() && content[idx] != b'\n' && content[idx] != b'\r' {
+                    idx += 1;
+                }
+            }
+            b'(' => {
+                let (string, next) = literal_string(content, idx + 1);
+                push(Operand::Str(string), &mut operands, &mut arrays);
+                idx = next;
+            }
+            b'<' if content.get(idx + 1) == Some(&b'<') => idx += 2,
+            b'>' if content.get(idx + 1) == Some(&b'>') => idx += 2,
+            b'<' => {
+                let end = find(&content[idx..], b">").map_or(content.len(), |rel| idx + rel);
+                push(
+                    Operand::Str(hex_bytes(&content[idx + 1..end])),
+                    &mut operands,
+                    &mut arrays,
+                );
+                idx = end + 1;
+            }
+            b'[' => {
+                arrays.push(Vec::new());
+                idx += 1;
+            }
+            b']' => {
+                if let Some(array) = arrays.pop() {
+                    push(Operand::Array(array), &mut operands, &mut arrays);
+                }
+                idx += 1;
+            }
+            b'/' => {
+                let start = idx + 1;
+                idx = start;
+                while idx < content.len() && is_regular(content[idx]) {
+                    idx += 1;
+                }
+                let name = String::from_utf8_lossy(&content[start..idx]).into_owned();
+                push(Operand::Name(name), &mut operands, &mut arrays);
+            }
+            _ => {
+                let start = idx;
+                while idx < content.len() && is_regular(content[idx]) {
+                    idx += 1;
+                }
+                if idx == start {
+                    idx += 1;
+                    continue;
+                }
+                let token = &content[start..idx];
+                if let Some(number) = std::str::from_utf8(token)
+                    .ok()
+                    .and_then(|token| token.parse::<f32>().ok())
+                {
+                    push(Operand::Num(number), &mut operands, &mut arrays);
+                    continue;
+                }
+                match token {
+                    b"Tf" => {
+                        font = operands.iter().find_map(|operand| match operand {
+                            Operand::Name(name) => fonts.get(name),
+                            _ => None,
+                        });
+                    }
+                    b"Tj" | b"'" | b"\"" => {
+                        if token != b"Tj" {
+                            newline(out);
+                        }
+                        if let Some(Operand::Str(string)) = operands.last() {
+                            show_string(string, font, out);
+                        }
+                    }
+                    b"TJ" => {
+                        if let Some(Operand::Array(items)) = operands.last() {
+                            for item in items {
+                                match item {
+                                    Operand::Str(string) => show_string(string, font, out),
+                                    // Large negative kerning is how many producers encode spaces.
+                                    Operand::Num(adjust) if *adjust < -200.0 => space(out),
+                                    _ => {}
+                                }
+                            }
+                        }
+                    }
+                    b"Td" | b"TD" => {
+                        if let [.., Operand::Num(tx), Operand::Num(ty)] = operands.as_slice() {
+                            if *ty != 0.0 {
+                                newline(out);
+                            } else if *tx > 0.0 {
+                                space(out);
+                            }
+                        }
+                    }
+                    b"Tm" => {
+                        if let [.., Operand::Num(y)] = operands.as_slice() {
+                            if last_line_y.is_some_and(|last| (last - y).abs() > 0.5) {
+                                newline(out);
+                            } else {
+                                space(out);
+                            }
+                            last_line_y = Some(*y);
+                        }
+                    }
+                    b"T*" => newline(out),
+                    b"ET" => space(out),
+                    b"BI" => {
+                        // Inline image data is binary; skip to the `EI` terminator.
+                        idx =
+                            find(&content[idx..], b"EI").map_or(content.len(), |rel| idx + rel + 2);
+                    }
+                    _ => {}
+                }
+                operands.clear();
+                arrays.clear();
+            }
+        }
+    }
+}
+
+fn is_regular(byte: u8) -> bool {
+    !byte.is_ascii_whitespace() && !b"()<>[]{}/%".contains(&byte)
+}
+
+fn literal_string(content: &[u8], mut idx: usize) -> (Vec<u8>, usize) {
+    let mut out = Vec::new();
+    let mut depth = 1;
+    while idx < content.len() {
+        let byte = content[idx];
+        idx += 1;
+        match byte {
+            b'\\' => {
+                let Some(&escaped) = content.get(idx) else {
+                    break;
+                };
+                idx += 1;
+                match escaped {
+                    b'n' => out.push(b'\n'),
+                    b'r' => out.push(b'\r'),
+                    b't' => out.push(b'\t'),
+                    b'b' | b'f' => {}
+                    b'\r' | b'\n' => {
+                        if escaped == b'\r' && content.get(idx) == Some(&b'\n') {
+                            idx += 1;
+                        }
+                    }
+                    b'0'..=b'7' => {
+                        let mut value = u32::from(escaped - b'0');
+                        for _ in 0..2 {
+                            match content.get(idx) {
+                                Some(digit @ b'0'..=b'7') => {
+                                    value = value * 8 + u32::from(digit - b'0');
+                                    idx += 1;
+                                }
+                                _ => break,
+                            }
+                        }
+                        out.push((value & 0xff) as u8);
+                    }
+                    other => out.push(other),
+                }
+            }
+            b'(' => {
+                depth += 1;
+                out.push(byte);
+            }
+            b')' => {
+                depth -= 1;
+                if depth == 0 {
+                    break;
+                }
+                out.push(byte);
+            }
+            _ => out.push(byte),
+        }
+    }
+    (out, idx)
+}
+
+fn show_string(bytes: &[u8], font: Option<&CMap>, out: &mut String) {
+    if let Some(cmap) = font {
+        cmap.decode(bytes, out);
+    } else if let Some(utf16) = bytes.strip_prefix(&[0xfe, 0xff]) {
+        out.push_str(&utf16_be(utf16));
+    } else {
+        for byte in bytes {
+            push_latin1(*byte, out);
+        }
+    }
+}
+
+fn space(out: &mut String) {
+    if !out.is_empty() && !out.ends_with(char::is_whitespace) {
+        out.push(' ');
+    }
+}
+
+fn newline(out: &mut String) {
+    let trimmed_len = out.trim_end_matches(' ').len();
+    out.truncate(trimmed_len);
+    if !out.is_empty() && !out.ends_with('\n') {
+        out.push('\n');
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::Compression;
+    use flate2::write::ZlibEncoder;
+    use pretty_assertions::assert_eq;
+    use std::io::Write;
+
+    fn deflate(data: &[u8]) -> Vec<u8> {
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(data).expect("deflate");
+        encoder.finish().expect("deflate")
+    }
+
+    fn pdf_with_pages(pages: &[&[u8]]) -> Vec<u8> {
+        let mut pdf = b"%PDF-1.4\n1 0 obj << /Type /Catalog /Pages 2 0 R >> endobj\n".to_vec();
+        let kids: Vec<String> = (0..pages.len())
+            .map(|idx| format!("{} 0 R", 10 + idx * 2))
+            .collect();
+        pdf.extend(
+            format!(
+                "2 0 obj << /Type /Pages /Kids [{}] /Count {} >> endobj\n",
+                kids.join(" "),
+                pages.len()
+            )
+            .as_bytes(),
+        );
+        for (idx, content) in pages.iter().enumerate() {
+            let page_id = 10 + idx * 2;
+            let content_id = page_id + 1;
+            let stream = deflate(content);
+            pdf.extend(
+                format!(
+                    "{page_id} 0 obj << /Type /Page /Parent 2 0 R /Contents {content_id} 0 R >> endobj\n{content_id} 0 obj << /Length {} /Filter /FlateDecode >>\nstream\n",
+                    stream.len()
+                )
+                .as_bytes(),
+            );
+            pdf.extend(stream);
+            pdf.extend(b"\nendstream\nendobj\n");
+        }
+        pdf.extend(b"trailer << /Root 1 0 R >>\n%%EOF\n");
+        pdf
+    }
+
+    #[test]
+    fn extracts_text_per_page_in_page_tree_order() {
+        let pdf = pdf_with_pages(&[
+            b"BT /F1 12 Tf 72 720 Td (Quarterly report) Tj 0 -14 Td (Revenue grew) Tj ET",
+            b"BT /F1 12 Tf [(Second) -250 (page)] TJ ET",
+        ]);
+
+        let pages = extract_pages(&pdf).expect("pages");
+
+        assert_eq!(
+            pages,
+            vec![
+                "Quarterly report\nRevenue grew".to_string(),
+                "Second page".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn decodes_strings_through_to_unicode_cmap() {
+        let cmap = CMap::parse(
+            "begincodespacerange <0000> <FFFF> endcodespacerange\n\
+             2 beginbfchar <0001> <0048> <0002> <0069> endbfchar\n\
+             1 beginbfrange <0010> <0012> <0061> endbfrange",
+        );
+        let mut fonts = HashMap::new();
+        fonts.insert("F1".to_string(), cmap);
+        let mut out = String::new();
+
+        extract_text_from_content(
+            b"BT /F1 10 Tf [<00010002> -300 <001000110012>] TJ ET",
+            &fonts,
+            &mut out,
+        );
+
+        assert_eq!(out.trim(), "Hi abc");
+    }
+
+    #[test]
+    fn rejects_non_pdf_input() {
+        assert!(extract_pages(b"PK\x03\x04").is_err());
+    }
+}
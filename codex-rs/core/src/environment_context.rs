@@ -1,13 +1,26 @@
 use crate::codex::TurnContext;
+use crate::codex::local_time_context;
+use crate::config::Config;
+use crate::config::types::EnvironmentContextConfig;
 use crate::contextual_user_message::ENVIRONMENT_CONTEXT_FRAGMENT;
+use crate::features::Feature;
+use crate::git_info::git_status_short;
+use crate::shell;
 use crate::shell::Shell;
 use codex_protocol::models::ResponseItem;
 use codex_protocol::protocol::TurnContextItem;
 use codex_protocol::protocol::TurnContextNetworkItem;
 use serde::Deserialize;
 use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::Path;
 use std::path::PathBuf;
 
+/// Longest `git status` (in lines) included in the environment context.
+const MAX_GIT_STATUS_LINES: usize = 50;
+/// Most entries included in the directory listing.
+const MAX_DIRECTORY_LISTING_ENTRIES: usize = 200;
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename = "environment_context", rename_all = "snake_case")]
 pub(crate) struct EnvironmentContext {
@@ -17,6 +30,7 @@ pub(crate) struct EnvironmentContext {
     pub timezone: Option<String>,
     pub network: Option<NetworkContext>,
     pub subagents: Option<String>,
+    pub details: Option<EnvironmentDetails>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
@@ -25,6 +39,129 @@ pub(crate) struct NetworkContext {
     denied_domains: Vec<String>,
 }
 
+/// The optional parts of the context selected by `[environment_context]`. They are gathered
+/// when the initial context is built and are not repeated in per-turn updates.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub(crate) struct EnvironmentDetails {
+    os: Option<String>,
+    git_status: Option<String>,
+    directory_listing: Option<(usize, String)>,
+    custom: BTreeMap<String, String>,
+}
+
+impl EnvironmentDetails {
+    pub(crate) async fn collect(config: &EnvironmentContextConfig, cwd: &Path) -> Self {
+        let os = config.os.then(|| {
+            let info = os_info::get();
+            format!(
+                "{} {} ({})",
+                info.os_type(),
+                info.version(),
+                info.architecture().unwrap_or("unknown")
+            )
+        });
+        let git_status = if config.git_status {
+            git_status_short(cwd)
+                .await
+                .map(|status| truncate_lines(status.trim_end(), MAX_GIT_STATUS_LINES))
+        } else {
+            None
+        };
+        let depth = config.directory_listing_depth;
+        let directory_listing = if depth > 0 {
+            let cwd = cwd.to_path_buf();
+            tokio::task::spawn_blocking(move || directory_listing(&cwd, depth))
+                .await
+                .ok()
+                .map(|listing| (depth, listing))
+        } else {
+            None
+        };
+        Self {
+            os,
+            git_status,
+            directory_listing,
+            custom: config.custom.clone(),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.os.is_none()
+            && self.git_status.is_none()
+            && self.directory_listing.is_none()
+            && self.custom.is_empty()
+    }
+}
+
+fn truncate_lines(text: &str, max_lines: usize) -> String {
+    let total = text.lines().count();
+    if total <= max_lines {
+        return text.to_string();
+    }
+    let mut kept: Vec<&str> = text.lines().take(max_lines).collect();
+    let omitted = format!("... ({} more)", total - max_lines);
+    kept.push(&omitted);
+    kept.join("\n")
+}
+
+/// Files and directories under `cwd` up to `depth` levels deep, indented by level. Hidden and
+/// git-ignored entries are skipped.
+fn directory_listing(cwd: &Path, depth: usize) -> String {
+    let mut lines = Vec::new();
+    let mut omitted = 0usize;
+    let walker = ignore::WalkBuilder::new(cwd)
+        .max_depth(Some(depth))
+        .sort_by_file_name(|a, b| a.cmp(b))
+        .build();
+    for entry in walker.flatten() {
+        if entry.depth() == 0 {
+            continue;
+        }
+        if lines.len() == MAX_DIRECTORY_LISTING_ENTRIES {
+            omitted += 1;
+            continue;
+        }
+        let name = entry.file_name().to_string_lossy();
+        let suffix = if entry
+            .file_type()
+            .is_some_and(|file_type| file_type.is_dir())
+        {
+            "/"
+        } else {
+            ""
+        };
+        let indent = "  ".repeat(entry.depth() - 1);
+        lines.push(format!("{indent}{name}{suffix}"));
+    }
+    if omitted > 0 {
+        lines.push(format!("... ({omitted} more)"));
+    }
+    lines.join("\n")
+}
+
+/// The `<environment_context>` block a new session started from `config` would send, for
+/// `codex debug env-context`.
+pub async fn preview_environment_context(config: &Config) -> String {
+    let zsh_fork_shell = config
+        .zsh_path
+        .as_ref()
+        .filter(|_| config.features.enabled(Feature::ShellZshFork))
+        .and_then(|zsh_path| shell::get_shell(shell::ShellType::Zsh, Some(zsh_path)));
+    let shell = zsh_fork_shell.unwrap_or_else(shell::default_user_shell);
+    let (current_date, timezone) = local_time_context();
+    let details = EnvironmentDetails::collect(&config.environment_context, &config.cwd).await;
+    EnvironmentContext::new(
+        Some(config.cwd.clone()),
+        shell,
+        Some(current_date),
+        Some(timezone),
+        EnvironmentContext::network_from_config(config),
+        None,
+    )
+    .with_details(details)
+    .serialize_to_xml()
+}
+
 impl EnvironmentContext {
     pub fn new(
         cwd: Option<PathBuf>,
@@ -41,12 +178,15 @@ impl EnvironmentContext {
             timezone,
             network,
             subagents,
+            details: None,
         }
     }
 
     /// Compares two environment contexts, ignoring the shell. Useful when
     /// comparing turn to turn, since the initial environment_context will
     /// include the shell, and then it is not configurable from turn to turn.
+    /// The optional details are only sent with the initial context, so they
+    /// are ignored as well.
     pub fn equals_except_shell(&self, other: &EnvironmentContext) -> bool {
         let EnvironmentContext {
             cwd,
@@ -55,6 +195,7 @@ impl EnvironmentContext {
             network,
             subagents,
             shell: _,
+            details: _,
         } = other;
         self.cwd == *cwd
             && self.current_date == *current_date
@@ -114,13 +255,19 @@ impl EnvironmentContext {
         self
     }
 
+    pub fn with_details(mut self, details: EnvironmentDetails) -> Self {
+        if !details.is_empty() {
+            self.details = Some(details);
+        }
+        self
+    }
+
     fn network_from_turn_context(turn_context: &TurnContext) -> Option<NetworkContext> {
-        let network = turn_context
-            .config
-            .config_layer_stack
-            .requirements()
-            .network
-            .as_ref()?;
+        Self::network_from_config(&turn_context.config)
+    }
+
+    fn network_from_config(config: &Config) -> Option<NetworkContext> {
+        let network = config.config_layer_stack.requirements().network.as_ref()?;
 
         Some(NetworkContext {
             allowed_domains: network.allowed_domains.clone().unwrap_or_default(),
@@ -188,6 +335,24 @@ impl EnvironmentContext {
             lines.extend(subagents.lines().map(|line| format!("    {line}")));
             lines.push("  </subagents>".to_string());
         }
+        if let Some(details) = self.details {
+            if let Some(os) = details.os {
+                lines.push(format!("  <os>{os}</os>"));
+            }
+            if let Some(git_status) = details.git_status {
+                lines.push("  <git_status>".to_string());
+                lines.extend(git_status.lines().map(|line| format!("    {line}")));
+                lines.push("  </git_status>".to_string());
+            }
+            if let Some((depth, listing)) = details.directory_listing {
+                lines.push(format!("  <directory_listing depth=\"{depth}\">"));
+                lines.extend(listing.lines().map(|line| format!("    {line}")));
+                lines.push("  </directory_listing>".to_string());
+            }
+            for (key, value) in details.custom {
+                lines.push(format!("  <custom key=\"{key}\">{value}</custom>"));
+            }
+        }
         ENVIRONMENT_CONTEXT_FRAGMENT.wrap(lines.join("\n"))
     }
 }
@@ -474,4 +639,49 @@ mod tests {
 
         assert_eq!(context.serialize_to_xml(), expected);
     }
+
+    #[tokio::test]
+    async fn serialize_environment_context_with_details() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        std::fs::create_dir_all(dir.path().join("src/bin")).expect("create dirs");
+        std::fs::write(dir.path().join("Cargo.toml"), "").expect("write file");
+        std::fs::write(dir.path().join("src/lib.rs"), "").expect("write file");
+        std::fs::write(dir.path().join("src/bin/tool.rs"), "").expect("write file");
+        std::fs::write(dir.path().join(".env"), "").expect("write file");
+
+        let config = EnvironmentContextConfig {
+            directory_listing_depth: 2,
+            custom: BTreeMap::from([("team".to_string(), "payments".to_string())]),
+            ..Default::default()
+        };
+        let details = EnvironmentDetails::collect(&config, dir.path()).await;
+        let context = EnvironmentContext::new(None, fake_shell(), None, None, None, None)
+            .with_details(details);
+
+        let expected = r#"<environment_context>
+  <shell>bash</shell>
+  <directory_listing depth="2">
+    Cargo.toml
+    src/
+      bin/
+      lib.rs
+  </directory_listing>
+  <custom key="team">payments</custom>
+</environment_context>"#;
+        assert_eq!(context.serialize_to_xml(), expected);
+    }
+
+    #[tokio::test]
+    async fn details_are_omitted_by_default() {
+        let details =
+            EnvironmentDetails::collect(&EnvironmentContextConfig::default(), Path::new("/")).await;
+        let context = EnvironmentContext::new(None, fake_shell(), None, None, None, None)
+            .with_details(details);
+        assert_eq!(context.details, None);
+    }
+
+    #[test]
+    fn truncate_lines_reports_omitted_lines() {
+        assert_eq!(truncate_lines("a\nb\nc", 2), "a\nb\n... (1 more)");
+    }
 }
@@ -228,6 +228,16 @@ impl CodexErr {
             CodexErr::LandlockRuleset(_) | CodexErr::LandlockPathFd(_) => false,
         }
     }
+
+    /// Whether the error means the model (or the connection to it) went quiet rather than
+    /// failing outright.
+    pub fn is_timeout(&self) -> bool {
+        match self {
+            CodexErr::Timeout => true,
+            CodexErr::Stream(message, _) => message.contains("idle timeout"),
+            _ => false,
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -582,6 +592,12 @@ impl CodexErr {
             CodexErr::ResponseStreamFailed(_) => CodexErrorInfo::ResponseStreamConnectionFailed {
                 http_status_code: self.http_status_code_value(),
             },
+            // Streams that dropped without an HTTP status usually mean the network went away.
+            CodexErr::Stream(..) | CodexErr::Timeout => {
+                CodexErrorInfo::ResponseStreamDisconnected {
+                    http_status_code: None,
+                }
+            }
             CodexErr::RefreshTokenFailed(_) => CodexErrorInfo::Unauthorized,
             CodexErr::SessionConfiguredNotFirstEvent
             | CodexErr::InternalServerError
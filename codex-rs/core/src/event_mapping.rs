@@ -140,6 +140,7 @@ pub fn parse_turn_item(item: &ResponseItem) -> Option<TurnItem> {
                 id: id.clone().unwrap_or_default(),
                 query,
                 action,
+                sources: Vec::new(),
             }))
         }
         ResponseItem::ImageGenerationCall {
@@ -483,6 +484,7 @@ mod tests {
                         query: Some("weather".to_string()),
                         queries: None,
                     },
+                    sources: Vec::new(),
                 }
             ),
             other => panic!("expected TurnItem::WebSearch, got {other:?}"),
@@ -510,6 +512,7 @@ mod tests {
                     action: WebSearchAction::OpenPage {
                         url: Some("https://example.com".to_string()),
                     },
+                    sources: Vec::new(),
                 }
             ),
             other => panic!("expected TurnItem::WebSearch, got {other:?}"),
@@ -539,6 +542,7 @@ mod tests {
                         url: Some("https://example.com".to_string()),
                         pattern: Some("needle".to_string()),
                     },
+                    sources: Vec::new(),
                 }
             ),
             other => panic!("expected TurnItem::WebSearch, got {other:?}"),
@@ -561,6 +565,7 @@ mod tests {
                     id: "ws_partial".to_string(),
                     query: String::new(),
                     action: WebSearchAction::Other,
+                    sources: Vec::new(),
                 }
             ),
             other => panic!("expected TurnItem::WebSearch, got {other:?}"),
@@ -2,11 +2,20 @@ use crate::config::types::EnvironmentVariablePattern;
 use crate::config::types::ShellEnvironmentPolicy;
 use crate::config::types::ShellEnvironmentPolicyInherit;
 use codex_protocol::ThreadId;
+use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::collections::HashSet;
 
 pub const CODEX_THREAD_ID_ENV_VAR: &str = "CODEX_THREAD_ID";
 
+/// Variables that convince common tools to emit color even though their output is captured.
+const FORCE_COLOR_ENV: [(&str, &str); 4] = [
+    ("FORCE_COLOR", "1"),
+    ("CLICOLOR_FORCE", "1"),
+    ("CARGO_TERM_COLOR", "always"),
+    ("TERM", "xterm-256color"),
+];
+
 /// Construct an environment map based on the rules in the specified policy. The
 /// resulting map can be passed directly to `Command::envs()` after calling
 /// `env_clear()` to ensure no unintended variables are leaked to the spawned
@@ -85,7 +94,12 @@ where
         env_map.retain(|k, _| matches_any(k, &policy.include_only));
     }
 
-    // Step 6 – Populate the thread ID environment variable when provided.
+    // Step 6 – Add secrets last so `include_only` never filters them out.
+    for (key, val) in load_secrets(policy) {
+        env_map.insert(key, val);
+    }
+
+    // Step 7 – Populate the thread ID environment variable when provided.
     if let Some(thread_id) = thread_id {
         env_map.insert(CODEX_THREAD_ID_ENV_VAR.to_string(), thread_id.to_string());
     }
@@ -93,6 +107,93 @@ where
     env_map
 }
 
+fn load_secrets(policy: &ShellEnvironmentPolicy) -> Vec<(String, String)> {
+    let Some(path) = policy.secrets_file.as_deref() else {
+        return Vec::new();
+    };
+    match std::fs::read_to_string(path) {
+        Ok(contents) => parse_secrets_file(&contents),
+        Err(err) => {
+            tracing::warn!("failed to read secrets file {}: {err}", path.display());
+            Vec::new()
+        }
+    }
+}
+
+/// Parse dotenv-style `KEY=VALUE` lines. Blank lines, `#` comments, and an `export `
+/// prefix are allowed, and values may be wrapped in single or double quotes.
+fn parse_secrets_file(contents: &str) -> Vec<(String, String)> {
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| {
+            let line = line.strip_prefix("export ").unwrap_or(line);
+            let (key, value) = line.split_once('=')?;
+            let key = key.trim();
+            if key.is_empty() {
+                return None;
+            }
+            let value = value.trim();
+            let value = ['"', '\'']
+                .iter()
+                .find_map(|quote| {
+                    value
+                        .strip_prefix(*quote)
+                        .and_then(|value| value.strip_suffix(*quote))
+                })
+                .unwrap_or(value);
+            Some((key.to_string(), value.to_string()))
+        })
+        .collect()
+}
+
+/// Placeholder shown instead of a value in [`describe_env`].
+pub const REDACTED_ENV_VALUE: &str = "<redacted>";
+
+/// The environment agent commands receive under `policy`, sorted by name, for display.
+///
+/// Values from the secrets file, and of variables whose names look like credentials
+/// (`*KEY*`, `*SECRET*`, `*TOKEN*`, `*PASSWORD*`, `*PASSWD*`, `*CREDENTIAL*`), are replaced with
+/// [`REDACTED_ENV_VALUE`].
+pub fn describe_env(policy: &ShellEnvironmentPolicy) -> BTreeMap<String, String> {
+    let secret_names: HashSet<String> = load_secrets(policy)
+        .into_iter()
+        .map(|(key, _)| key)
+        .collect();
+    let sensitive = [
+        EnvironmentVariablePattern::new_case_insensitive("*KEY*"),
+        EnvironmentVariablePattern::new_case_insensitive("*SECRET*"),
+        EnvironmentVariablePattern::new_case_insensitive("*TOKEN*"),
+        EnvironmentVariablePattern::new_case_insensitive("*PASSWORD*"),
+        EnvironmentVariablePattern::new_case_insensitive("*PASSWD*"),
+        EnvironmentVariablePattern::new_case_insensitive("*CREDENTIAL*"),
+    ];
+    create_env(policy, None)
+        .into_iter()
+        .map(|(key, value)| {
+            let redact = secret_names.contains(&key)
+                || sensitive.iter().any(|pattern| pattern.matches(&key));
+            let value = if redact {
+                REDACTED_ENV_VALUE.to_string()
+            } else {
+                value
+            };
+            (key, value)
+        })
+        .collect()
+}
+
+/// Ask commands to emit ANSI color (`colored_exec_output`).
+///
+/// The TUI renders the colors; model-facing output is stripped of escapes regardless.
+pub(crate) fn apply_force_color_env(env: &mut HashMap<String, String>) {
+    env.remove("NO_COLOR");
+    for (key, value) in FORCE_COLOR_ENV {
+        env.insert(key.to_string(), value.to_string());
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -310,4 +411,91 @@ mod tests {
         expected.insert(CODEX_THREAD_ID_ENV_VAR.to_string(), thread_id.to_string());
         assert_eq!(result, expected);
     }
+
+    #[test]
+    fn force_color_env_drops_no_color() {
+        let mut env = hashmap! {
+            "NO_COLOR".to_string() => "1".to_string(),
+            "TERM".to_string() => "dumb".to_string(),
+            "PATH".to_string() => "/usr/bin".to_string(),
+        };
+
+        apply_force_color_env(&mut env);
+
+        let expected: HashMap<String, String> = hashmap! {
+            "FORCE_COLOR".to_string() => "1".to_string(),
+            "CLICOLOR_FORCE".to_string() => "1".to_string(),
+            "CARGO_TERM_COLOR".to_string() => "always".to_string(),
+            "TERM".to_string() => "xterm-256color".to_string(),
+            "PATH".to_string() => "/usr/bin".to_string(),
+        };
+        assert_eq!(env, expected);
+    }
+
+    #[test]
+    fn parses_dotenv_style_secrets() {
+        let contents =
+            "# deploy keys\nexport API_KEY=\"abc 123\"\n\nDB_PASSWORD='p=w'\nBROKEN\n=nokey\n";
+
+        assert_eq!(
+            parse_secrets_file(contents),
+            vec![
+                ("API_KEY".to_string(), "abc 123".to_string()),
+                ("DB_PASSWORD".to_string(), "p=w".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn secrets_survive_include_only_and_are_redacted_for_display() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let secrets_file = dir.path().join("secrets.env");
+        std::fs::write(&secrets_file, "DB_PASSWORD=hunter2\n").expect("write secrets");
+        let policy = ShellEnvironmentPolicy {
+            inherit: ShellEnvironmentPolicyInherit::None,
+            include_only: vec![EnvironmentVariablePattern::new_case_insensitive("PATH")],
+            secrets_file: Some(secrets_file),
+            ..Default::default()
+        };
+
+        let env = populate_env(make_vars(&[]), &policy, None);
+        assert_eq!(
+            env,
+            hashmap! { "DB_PASSWORD".to_string() => "hunter2".to_string() }
+        );
+
+        let described = describe_env(&policy);
+        assert_eq!(
+            described.get("DB_PASSWORD").map(String::as_str),
+            Some(REDACTED_ENV_VALUE)
+        );
+    }
+
+    #[test]
+    fn describe_env_redacts_credential_like_names() {
+        let policy = ShellEnvironmentPolicy {
+            inherit: ShellEnvironmentPolicyInherit::None,
+            r#set: hashmap! {
+                "PGPASSWORD".to_string() => "hunter2".to_string(),
+                "SMB_PASSWD".to_string() => "hunter2".to_string(),
+                "GOOGLE_APPLICATION_CREDENTIALS".to_string() => "/keys/sa.json".to_string(),
+                "EDITOR".to_string() => "vi".to_string(),
+            },
+            ..Default::default()
+        };
+
+        let described = describe_env(&policy);
+        assert_eq!(
+            described,
+            BTreeMap::from([
+                ("EDITOR".to_string(), "vi".to_string()),
+                (
+                    "GOOGLE_APPLICATION_CREDENTIALS".to_string(),
+                    REDACTED_ENV_VALUE.to_string()
+                ),
+                ("PGPASSWORD".to_string(), REDACTED_ENV_VALUE.to_string()),
+                ("SMB_PASSWD".to_string(), REDACTED_ENV_VALUE.to_string()),
+            ])
+        );
+    }
 }
@@ -117,6 +117,18 @@ pub enum Feature {
     Sqlite,
     /// Enable startup memory extraction and file-backed memory consolidation.
     MemoryTool,
+    /// Build a local embedding index of the workspace and expose `semantic_search`.
+    SemanticSearch,
+    /// Expose `fetch_url`, which downloads pages from `fetch_url.allowed_domains` as markdown.
+    FetchUrl,
+    /// Expose `search_web`, backed by the API configured in `[web_search_provider]`.
+    WebSearchProvider,
+    /// Expose `update_todos`, a session todo list shown as a checklist and kept across resume.
+    TodoList,
+    /// Ask agent commands to emit color so the TUI can render it; the model sees plain text.
+    ColoredExecOutput,
+    /// Carry `cd` effects from one agent shell call to the next.
+    PersistentCwd,
     /// Append additional AGENTS.md guidance to user instructions.
     ChildAgentsMd,
     /// Allow `detail: "original"` image outputs on supported models.
@@ -546,6 +558,42 @@ pub const FEATURES: &[FeatureSpec] = &[
         stage: Stage::UnderDevelopment,
         default_enabled: false,
     },
+    FeatureSpec {
+        id: Feature::SemanticSearch,
+        key: "semantic_search",
+        stage: Stage::UnderDevelopment,
+        default_enabled: false,
+    },
+    FeatureSpec {
+        id: Feature::FetchUrl,
+        key: "fetch_url",
+        stage: Stage::UnderDevelopment,
+        default_enabled: false,
+    },
+    FeatureSpec {
+        id: Feature::WebSearchProvider,
+        key: "web_search_provider",
+        stage: Stage::UnderDevelopment,
+        default_enabled: false,
+    },
+    FeatureSpec {
+        id: Feature::TodoList,
+        key: "todo_list",
+        stage: Stage::UnderDevelopment,
+        default_enabled: false,
+    },
+    FeatureSpec {
+        id: Feature::ColoredExecOutput,
+        key: "colored_exec_output",
+        stage: Stage::UnderDevelopment,
+        default_enabled: false,
+    },
+    FeatureSpec {
+        id: Feature::PersistentCwd,
+        key: "persistent_cwd",
+        stage: Stage::UnderDevelopment,
+        default_enabled: false,
+    },
     FeatureSpec {
         id: Feature::ChildAgentsMd,
         key: "child_agents_md",
@@ -0,0 +1,616 @@
+//! Download a web page and reduce it to readable markdown for the `fetch_url` tool.
+//!
+//! Only hosts listed in `[fetch_url].allowed_domains` (or their subdomains) are fetched, and
+//! redirects are held to the same rule. HTML responses go through a small readability pass:
+//! the main `<article>`/`<main>` region is preferred, navigation chrome and scripts are
+//! dropped, and the remaining structure is rendered as markdown.
+
+use std::time::Duration;
+
+use codex_utils_string::take_bytes_at_char_boundary;
+use reqwest::redirect;
+use url::Url;
+
+use crate::config::types::FetchUrlConfig;
+use crate::default_client::apply_client_tls;
+use crate::default_client::apply_outbound_proxy;
+use crate::default_client::get_codex_user_agent;
+
+const MAX_REDIRECTS: usize = 5;
+
+/// Elements whose contents never carry article text.
+const SKIPPED_ELEMENTS: &[&str] = &[
+    "aside", "button", "footer", "form", "head", "header", "iframe", "nav", "noscript", "script",
+    "select", "style", "svg", "template",
+];
+
+/// Elements that never have a closing tag.
+const VOID_ELEMENTS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "track",
+    "wbr",
+];
+
+/// Parse `raw` and check that it is an http(s) URL whose host is on the allowlist.
+pub(crate) fn parse_allowed_url(raw: &str, allowed_domains: &[String]) -> Result<Url, String> {
+    let url = Url::parse(raw.trim()).map_err(|err| format!("invalid URL `{raw}`: {err}"))?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(format!(
+            "unsupported URL scheme `{}`; only http and https are fetched",
+            url.scheme()
+        ));
+    }
+    let Some(host) = url.host_str() else {
+        return Err(format!("URL `{raw}` has no host"));
+    };
+    if !host_is_allowed(host, allowed_domains) {
+        return Err(format!(
+            "{host} is not in fetch_url.allowed_domains; ask the user to add it to config.toml"
+        ));
+    }
+    Ok(url)
+}
+
+/// Whether `host` equals an allowed domain or is a subdomain of one.
+pub(crate) fn host_is_allowed(host: &str, allowed_domains: &[String]) -> bool {
+    let host = host.trim_end_matches('.').to_ascii_lowercase();
+    allowed_domains.iter().any(|domain| {
+        let domain = domain.trim().trim_start_matches("*.").trim_end_matches('.');
+        if domain.is_empty() {
+            return false;
+        }
+        let domain = domain.to_ascii_lowercase();
+        host == domain
+            || host
+                .strip_suffix(domain.as_str())
+                .is_some_and(|prefix| prefix.ends_with('.'))
+    })
+}
+
+/// Fetch `url` and return its readable content as markdown, capped at
+/// `config.max_output_bytes`.
+pub(crate) async fn fetch_markdown(config: &FetchUrlConfig, url: &Url) -> Result<String, String> {
+    let allowed_domains = config.allowed_domains.clone();
+    let redirect_policy = redirect::Policy::custom(move |attempt| {
+        if attempt.previous().len() >= MAX_REDIRECTS {
+            return attempt.error(format!("more than {MAX_REDIRECTS} redirects"));
+        }
+        let allowed = attempt
+            .url()
+            .host_str()
+            .is_some_and(|host| host_is_allowed(host, &allowed_domains));
+        if allowed {
+            attempt.follow()
+        } else {
+            let target = attempt.url().to_string();
+            attempt.error(format!(
+                "redirect to {target} is outside fetch_url.allowed_domains"
+            ))
+        }
+    });
+    let builder = reqwest::Client::builder()
+        .user_agent(get_codex_user_agent())
+        .timeout(Duration::from_secs(config.timeout_secs))
+        .redirect(redirect_policy);
+    let client = apply_client_tls(apply_outbound_proxy(builder))
+        .build()
+        .map_err(|err| format!("failed to build HTTP client: {err}"))?;
+
+    let mut response = client
+        .get(url.clone())
+        .header(
+            reqwest::header::ACCEPT,
+            "text/html,application/xhtml+xml,text/plain;q=0.9,*/*;q=0.5",
+        )
+        .send()
+        .await
+        .map_err(|err| format!("request failed: {err}"))?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(format!("{url} returned {status}"));
+    }
+    let final_url = response.url().clone();
+    let content_type = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+
+    let mut body = Vec::new();
+    let mut truncated = false;
+    while let Some(chunk) = response
+        .chunk()
+        .await
+        .map_err(|err| format!("failed to read response: {err}"))?
+    {
+        let remaining = config.max_download_bytes.saturating_sub(body.len());
+        if chunk.len() > remaining {
+            body.extend_from_slice(&chunk[..remaining]);
+            truncated = true;
+            break;
+        }
+        body.extend_from_slice(&chunk);
+    }
+    let text = String::from_utf8_lossy(&body);
+
+    let is_html = content_type.contains("html")
+        || (content_type.is_empty() && text.trim_start().starts_with('<'));
+    let markdown = if is_html {
+        html_to_markdown(&text, &final_url)
+    } else if content_type.starts_with("text/")
+        || content_type.contains("json")
+        || content_type.contains("xml")
+    {
+        text.trim().to_string()
+    } else {
+        return Err(format!(
+            "unsupported content type `{content_type}`; only text and HTML pages can be fetched"
+        ));
+    };
+    if markdown.is_empty() {
+        return Err(format!("{final_url} has no readable text"));
+    }
+
+    let mut output = format!("Source: {final_url}\n\n");
+    output.push_str(take_bytes_at_char_boundary(
+        &markdown,
+        config.max_output_bytes,
+    ));
+    if truncated || markdown.len() > config.max_output_bytes {
+        output.push_str("\n\n[truncated: the page is longer than the fetch_url limits]");
+    }
+    Ok(output)
+}
+
+#[derive(Debug, PartialEq)]
+enum Token<'a> {
+    Text(&'a str),
+    Open { name: String, attrs: &'a str },
+    Close { name: String },
+}
+
+fn tokenize(html: &str) -> Vec<Token<'_>> {
+    let mut tokens = Vec::new();
+    let mut rest = html;
+    while !rest.is_empty() {
+        let Some(open) = rest.find('<') else {
+            tokens.push(Token::Text(rest));
+            break;
+        };
+        if open > 0 {
+            tokens.push(Token::Text(&rest[..open]));
+        }
+        rest = &rest[open..];
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            rest = comment
+                .find("-->")
+                .map_or("", |end| &comment[end + "-->".len()..]);
+            continue;
+        }
+        let Some(close) = rest.find('>') else {
+            tokens.push(Token::Text(rest));
+            break;
+        };
+        let tag = &rest[1..close];
+        rest = &rest[close + 1..];
+        if tag.starts_with('!') || tag.starts_with('?') {
+            continue;
+        }
+        let (is_close, tag) = match tag.strip_prefix('/') {
+            Some(tag) => (true, tag),
+            None => (false, tag),
+        };
+        let name_end = tag
+            .find(|c: char| c.is_whitespace() || c == '/')
+            .unwrap_or(tag.len());
+        let name = tag[..name_end].to_ascii_lowercase();
+        if name.is_empty() {
+            continue;
+        }
+        if is_close {
+            tokens.push(Token::Close { name });
+            continue;
+        }
+        let attrs = &tag[name_end..];
+        // Script and style bodies may contain `<`; jump straight to the closing tag.
+        let raw_text_end = matches!(name.as_str(), "script" | "style").then(|| format!("</{name}"));
+        tokens.push(Token::Open { name, attrs });
+        if let Some(end_tag) = raw_text_end {
+            let end = rest
+                .to_ascii_lowercase()
+                .find(&end_tag)
+                .unwrap_or(rest.len());
+            rest = &rest[end..];
+        }
+    }
+    tokens
+}
+
+/// The token range of the first element named `name`, excluding its own tags.
+fn element_range(tokens: &[Token<'_>], name: &str) -> Option<(usize, usize)> {
+    let start = tokens
+        .iter()
+        .position(|token| matches!(token, Token::Open { name: n, .. } if n == name))?;
+    let mut depth = 0usize;
+    for (index, token) in tokens.iter().enumerate().skip(start) {
+        match token {
+            Token::Open { name: n, .. } if n == name => depth += 1,
+            Token::Close { name: n } if n == name => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some((start + 1, index));
+                }
+            }
+            _ => {}
+        }
+    }
+    Some((start + 1, tokens.len()))
+}
+
+fn attribute<'a>(attrs: &'a str, key: &str) -> Option<&'a str> {
+    let lower = attrs.to_ascii_lowercase();
+    let mut search_from = 0;
+    while let Some(found) = lower[search_from..].find(key) {
+        let start = search_from + found;
+        search_from = start + key.len();
+        let preceded_by_space = lower[..start]
+            .chars()
+            .next_back()
+            .is_none_or(char::is_whitespace);
+        let after = lower[search_from..].trim_start();
+        if !preceded_by_space || !after.starts_with('=') {
+            continue;
+        }
+        let value_start = attrs.len() - after.len() + 1;
+        let value = attrs[value_start..].trim_start();
+        return Some(match value.chars().next() {
+            Some(quote @ ('"' | '\'')) => {
+                let value = &value[1..];
+                &value[..value.find(quote).unwrap_or(value.len())]
+            }
+            _ => &value[..value.find(char::is_whitespace).unwrap_or(value.len())],
+        });
+    }
+    None
+}
+
+/// Decode the HTML entities that commonly appear in page text.
+pub(crate) fn decode_entities(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(amp) = rest.find('&') {
+        out.push_str(&rest[..amp]);
+        let after = &rest[amp + 1..];
+        let decoded = after.find(';').filter(|semi| *semi <= 10).and_then(|semi| {
+            let entity = &after[..semi];
+            let c = match entity {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some(' '),
+                "mdash" => Some('\u{2014}'),
+                "ndash" => Some('\u{2013}'),
+                "hellip" => Some('\u{2026}'),
+                "copy" => Some('\u{a9}'),
+                _ => entity
+                    .strip_prefix("#x")
+                    .or_else(|| entity.strip_prefix("#X"))
+                    .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                    .or_else(|| entity.strip_prefix('#').and_then(|dec| dec.parse().ok()))
+                    .and_then(char::from_u32),
+            };
+            c.map(|c| (c, semi))
+        });
+        match decoded {
+            Some((c, semi)) => {
+                out.push(c);
+                rest = &after[semi + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = after;
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+struct MarkdownWriter<'a> {
+    base: &'a Url,
+    out: String,
+    lists: Vec<Option<usize>>,
+    links: Vec<Option<String>>,
+    in_pre: bool,
+}
+
+impl MarkdownWriter<'_> {
+    fn blank_line(&mut self) {
+        let trimmed = self.out.trim_end_matches(' ').len();
+        self.out.truncate(trimmed);
+        if self.out.is_empty() || self.out.ends_with("\n\n") {
+            return;
+        }
+        if self.out.ends_with('\n') {
+            self.out.push('\n');
+        } else {
+            self.out.push_str("\n\n");
+        }
+    }
+
+    fn newline(&mut self) {
+        let trimmed = self.out.trim_end_matches(' ').len();
+        self.out.truncate(trimmed);
+        if !self.out.is_empty() && !self.out.ends_with('\n') {
+            self.out.push('\n');
+        }
+    }
+
+    fn text(&mut self, text: &str) {
+        let text = decode_entities(text);
+        if self.in_pre {
+            self.out.push_str(&text);
+            return;
+        }
+        let starts_with_space = text.starts_with(char::is_whitespace);
+        let ends_with_space = text.ends_with(char::is_whitespace);
+        let words = text.split_whitespace().collect::<Vec<_>>();
+        if words.is_empty() {
+            if starts_with_space && !self.at_line_start() {
+                self.out.push(' ');
+            }
+            return;
+        }
+        if starts_with_space && !self.at_line_start() {
+            self.out.push(' ');
+        }
+        self.out.push_str(&words.join(" "));
+        if ends_with_space {
+            self.out.push(' ');
+        }
+    }
+
+    fn at_line_start(&self) -> bool {
+        self.out.is_empty() || self.out.ends_with([' ', '\n', '[', '('])
+    }
+
+    fn open(&mut self, name: &str, attrs: &str) {
+        match name {
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                self.blank_line();
+                let level = name[1..].parse::<usize>().unwrap_or(1);
+                self.out.push_str(&"#".repeat(level));
+                self.out.push(' ');
+            }
+            "p" | "table" | "blockquote" | "figure" => self.blank_line(),
+            "div" | "section" | "article" | "main" | "tr" | "dt" | "dd" => self.newline(),
+            "br" => self.out.push('\n'),
+            "hr" => {
+                self.blank_line();
+                self.out.push_str("---\n\n");
+            }
+            "ul" => {
+                self.newline();
+                self.lists.push(None);
+            }
+            "ol" => {
+                self.newline();
+                self.lists.push(Some(0));
+            }
+            "li" => {
+                self.newline();
+                let depth = self.lists.len().saturating_sub(1);
+                self.out.push_str(&"  ".repeat(depth));
+                match self.lists.last_mut() {
+                    Some(Some(counter)) => {
+                        *counter += 1;
+                        self.out.push_str(&format!("{counter}. "));
+                    }
+                    _ => self.out.push_str("- "),
+                }
+            }
+            "pre" => {
+                self.blank_line();
+                self.out.push_str("```\n");
+                self.in_pre = true;
+            }
+            "code" if !self.in_pre => self.out.push('`'),
+            "strong" | "b" => self.out.push_str("**"),
+            "em" | "i" => self.out.push('*'),
+            "td" | "th" => self.out.push_str(" | "),
+            "a" => {
+                let href = attribute(attrs, "href")
+                    .map(decode_entities)
+                    .filter(|href| !href.starts_with('#') && !href.starts_with("javascript:"))
+                    .and_then(|href| self.base.join(&href).ok())
+                    .map(|url| url.to_string());
+                if href.is_some() {
+                    self.out.push('[');
+                }
+                self.links.push(href);
+            }
+            "img" => {
+                if let Some(alt) = attribute(attrs, "alt").filter(|alt| !alt.trim().is_empty()) {
+                    self.text(&format!("[image: {}]", alt.trim()));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn close(&mut self, name: &str) {
+        match name {
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" | "p" | "table" | "blockquote" | "figure" => {
+                self.blank_line()
+            }
+            "div" | "section" | "article" | "main" | "tr" | "dt" | "dd" | "li" => self.newline(),
+            "ul" | "ol" => {
+                self.lists.pop();
+                if self.lists.is_empty() {
+                    self.blank_line();
+                }
+            }
+            "pre" => {
+                self.in_pre = false;
+                self.newline();
+                self.out.push_str("```\n\n");
+            }
+            "code" if !self.in_pre => self.out.push('`'),
+            "strong" | "b" => self.out.push_str("**"),
+            "em" | "i" => self.out.push('*'),
+            "a" => {
+                if let Some(Some(href)) = self.links.pop() {
+                    let trimmed = self.out.trim_end().len();
+                    self.out.truncate(trimmed);
+                    self.out.push_str(&format!("]({href})"));
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Render the readable part of an HTML document as markdown. Relative links are resolved
+/// against `base`.
+pub(crate) fn html_to_markdown(html: &str, base: &Url) -> String {
+    let tokens = tokenize(html);
+    let title = element_range(&tokens, "title")
+        .map(|(start, end)| {
+            tokens[start..end]
+                .iter()
+                .filter_map(|token| match token {
+                    Token::Text(text) => Some(decode_entities(text)),
+                    _ => None,
+                })
+                .collect::<String>()
+        })
+        .map(|title| title.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|title| !title.is_empty());
+    let (start, end) = ["article", "main", "body"]
+        .iter()
+        .find_map(|name| element_range(&tokens, name))
+        .unwrap_or((0, tokens.len()));
+
+    let mut writer = MarkdownWriter {
+        base,
+        out: String::new(),
+        lists: Vec::new(),
+        links: Vec::new(),
+        in_pre: false,
+    };
+    let mut skipped: Vec<&str> = Vec::new();
+    for token in &tokens[start..end] {
+        match token {
+            Token::Open { name, attrs } => {
+                if !skipped.is_empty() || SKIPPED_ELEMENTS.contains(&name.as_str()) {
+                    let self_closing = attrs.trim_end().ends_with('/');
+                    if !self_closing && !VOID_ELEMENTS.contains(&name.as_str()) {
+                        skipped.push(name.as_str());
+                    }
+                    continue;
+                }
+                writer.open(name, attrs);
+            }
+            Token::Close { name } => {
+                if !skipped.is_empty() {
+                    if let Some(position) = skipped.iter().rposition(|open| *open == name.as_str())
+                    {
+                        skipped.truncate(position);
+                    }
+                    continue;
+                }
+                writer.close(name);
+            }
+            Token::Text(text) => {
+                if skipped.is_empty() {
+                    writer.text(text);
+                }
+            }
+        }
+    }
+
+    let mut markdown = String::new();
+    if let Some(title) = title
+        && !writer.out.trim_start().starts_with("# ")
+    {
+        markdown.push_str(&format!("# {title}\n\n"));
+    }
+    let mut blank_lines = 0;
+    for line in writer.out.lines() {
+        let line = line.trim_end();
+        if line.is_empty() {
+            blank_lines += 1;
+            if blank_lines > 1 {
+                continue;
+            }
+        } else {
+            blank_lines = 0;
+        }
+        markdown.push_str(line);
+        markdown.push('\n');
+    }
+    markdown.trim().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn domains(list: &[&str]) -> Vec<String> {
+        list.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn allowlist_matches_domains_and_subdomains() {
+        let allowed = domains(&["docs.rs", "*.example.com"]);
+
+        assert!(host_is_allowed("docs.rs", &allowed));
+        assert!(host_is_allowed("api.Example.com", &allowed));
+        assert!(host_is_allowed("example.com", &allowed));
+        assert!(!host_is_allowed("notdocs.rs", &allowed));
+        assert!(!host_is_allowed("example.com.evil.net", &allowed));
+    }
+
+    #[test]
+    fn rejects_urls_outside_allowlist_or_scheme() {
+        let allowed = domains(&["docs.rs"]);
+
+        assert!(parse_allowed_url("https://docs.rs/serde", &allowed).is_ok());
+        assert_eq!(
+            parse_allowed_url("https://crates.io/", &allowed),
+            Err(
+                "crates.io is not in fetch_url.allowed_domains; ask the user to add it to config.toml"
+                    .to_string()
+            )
+        );
+        assert!(parse_allowed_url("file:///etc/passwd", &allowed).is_err());
+    }
+
+    #[test]
+    fn converts_main_article_to_markdown() {
+        let html = r#"<!DOCTYPE html>
+<html><head><title>Release notes &amp; more</title><style>p { color: red; }</style></head>
+<body>
+  <nav><a href="/">Home</a> <a href="/blog">Blog</a></nav>
+  <article>
+    <h2>What&#39;s new</h2>
+    <p>Version <strong>2.0</strong> ships a
+       <a href="/docs/install">new installer</a>.</p>
+    <ul><li>Faster builds</li><li>Fewer <em>bugs</em></li></ul>
+    <pre><code>cargo install demo
+</code></pre>
+    <script>if (a < b) { track(); }</script>
+  </article>
+  <footer>Copyright</footer>
+</body></html>"#;
+        let base = Url::parse("https://example.com/blog/post").expect("url");
+
+        assert_eq!(
+            html_to_markdown(html, &base),
+            "# Release notes & more\n\n## What's new\n\nVersion **2.0** ships a [new installer](https://example.com/docs/install).\n\n- Faster builds\n- Fewer *bugs*\n\n```\ncargo install demo\n```"
+        );
+    }
+}
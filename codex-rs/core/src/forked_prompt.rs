@@ -0,0 +1,76 @@
+//! One-off questions to a read-only copy of the conversation.
+//!
+//! `/pr`, `/commit-msg`, and `/conflicts` need the model to write something informed by the
+//! conversation without adding the exchange to it. The prompt runs in an ephemeral fork of the
+//! session's rollout (or a fresh thread when the session has none yet) with a read-only
+//! sandbox, and the fork is shut down once its turn ends.
+
+use std::path::Path;
+
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::Op;
+use codex_protocol::user_input::UserInput;
+use serde::de::DeserializeOwned;
+
+use crate::ThreadManager;
+use crate::config::Config;
+use crate::model_comparison::ephemeral_read_only_config;
+
+/// Ask `prompt` in a read-only fork of the conversation at `rollout_path` and parse the reply,
+/// which `output_schema` constrains to JSON, as `T`. `what` names the output in error messages,
+/// e.g. "the pull request".
+pub(crate) async fn ask_forked_conversation<T: DeserializeOwned>(
+    thread_manager: &ThreadManager,
+    config: &Config,
+    rollout_path: Option<&Path>,
+    prompt: &str,
+    output_schema: serde_json::Value,
+    what: &str,
+) -> Result<T, String> {
+    let fork_config = ephemeral_read_only_config(config);
+    let new_thread = match rollout_path {
+        Some(path) => {
+            thread_manager
+                .fork_thread(usize::MAX, fork_config, path.to_path_buf(), false)
+                .await
+        }
+        None => thread_manager.start_thread(fork_config).await,
+    }
+    .map_err(|err| format!("failed to start a session to write {what}: {err}"))?;
+    let thread = new_thread.thread;
+
+    let result: Result<(Option<String>, Option<String>), String> = async {
+        thread
+            .submit(Op::UserInput {
+                items: vec![UserInput::Text {
+                    text: prompt.to_string(),
+                    text_elements: Vec::new(),
+                }],
+                final_output_json_schema: Some(output_schema),
+            })
+            .await
+            .map_err(|err| format!("failed to submit the prompt for {what}: {err}"))?;
+        let mut error = None;
+        loop {
+            let event = thread.next_event().await.map_err(|err| err.to_string())?;
+            match event.msg {
+                EventMsg::Error(event) => error = Some(event.message),
+                EventMsg::TurnComplete(event) => break Ok((event.last_agent_message, error)),
+                EventMsg::TurnAborted(event) => {
+                    break Err(format!("writing {what} was aborted: {:?}", event.reason));
+                }
+                _ => {}
+            }
+        }
+    }
+    .await;
+
+    let _ = thread.submit(Op::Shutdown).await;
+    thread_manager.remove_thread(&new_thread.thread_id).await;
+
+    let (message, error) = result?;
+    let message = message
+        .ok_or_else(|| error.unwrap_or_else(|| format!("the model did not write {what}")))?;
+    serde_json::from_str(message.trim())
+        .map_err(|err| format!("the model's reply for {what} was not valid JSON: {err}"))
+}
@@ -159,6 +159,15 @@ pub async fn get_has_changes(cwd: &Path) -> Option<bool> {
     Some(!output.stdout.is_empty())
 }
 
+/// `git status --short --branch` output for `cwd`, or `None` outside a repository.
+pub async fn git_status_short(cwd: &Path) -> Option<String> {
+    let output = run_git_command_with_timeout(&["status", "--short", "--branch"], cwd).await?;
+    if !output.status.success() {
+        return None;
+    }
+    String::from_utf8(output.stdout).ok()
+}
+
 fn parse_git_remote_urls(stdout: &str) -> Option<BTreeMap<String, String>> {
     let mut remotes = BTreeMap::new();
     for line in stdout.lines() {
@@ -9,6 +9,7 @@ mod analytics_client;
 pub mod api_bridge;
 mod apply_patch;
 mod apps;
+mod attachment_policy;
 pub mod auth;
 mod client;
 mod client_common;
@@ -23,22 +24,35 @@ mod agent;
 mod codex_delegate;
 mod command_canonicalization;
 mod commit_attribution;
+mod commit_message;
+pub use commit_message::commit_staged;
+pub use commit_message::draft_commit_message;
 pub mod config;
 pub mod config_loader;
 pub mod connectors;
+mod context_inspector;
+pub use context_inspector::ContextEntry;
+pub use context_inspector::ContextSection;
+pub use context_inspector::ContextSectionKind;
+pub use context_inspector::NextTurnContext;
 mod context_manager;
 mod contextual_user_message;
 pub mod custom_prompts;
+mod cwd_tracking;
+mod document_text;
 pub mod env;
 mod environment_context;
+pub use environment_context::preview_environment_context;
 pub mod error;
 pub mod exec;
 pub mod exec_env;
 mod exec_policy;
 pub mod external_agent_config;
 pub mod features;
+mod fetch_url;
 mod file_watcher;
 mod flags;
+mod forked_prompt;
 pub mod git_info;
 pub mod instructions;
 pub mod landlock;
@@ -53,14 +67,38 @@ pub use mcp_connection_manager::SandboxState;
 mod mcp_tool_call;
 mod memories;
 mod mentions;
+mod merge_conflicts;
+pub use merge_conflicts::ConflictHunk;
+pub use merge_conflicts::ConflictResolution;
+pub use merge_conflicts::apply_conflict_resolution;
+pub use merge_conflicts::find_conflicts;
+pub use merge_conflicts::parse_conflict_hunks;
+pub use merge_conflicts::propose_conflict_resolution;
 mod message_history;
+mod model_comparison;
+pub use model_comparison::ModelComparison;
+pub use model_comparison::ModelRun;
+pub use model_comparison::compare_models;
 mod model_provider_info;
 pub mod path_utils;
 pub mod personality_migration;
+mod pinned_files;
+pub use pinned_files::MAX_PINNED_FILE_BYTES;
 pub mod plugins;
+mod pull_request;
+pub use pull_request::PullRequest;
+pub use pull_request::create_pull_request;
+pub use pull_request::post_pull_request_comment;
 mod sandbox_tags;
 pub mod sandboxing;
 mod session_prefix;
+mod session_summary;
+pub use session_summary::CarryOverBriefing;
+pub use session_summary::SessionSummary;
+pub use session_summary::carry_over_briefing;
+pub use session_summary::read_session_summary;
+pub use session_summary::session_summary_path;
+pub use session_summary::summarize_session;
 mod shell_detect;
 mod stream_events_utils;
 pub mod test_support;
@@ -79,11 +117,14 @@ pub use model_provider_info::WireApi;
 pub use model_provider_info::built_in_model_providers;
 pub use model_provider_info::create_oss_provider_with_base_url;
 mod event_mapping;
+pub mod review_diff;
 pub mod review_format;
 pub mod review_prompts;
 mod seatbelt_permissions;
 mod thread_manager;
 pub mod web_search;
+mod web_search_provider;
+mod webhooks;
 pub mod windows_sandbox_read_grants;
 pub use thread_manager::NewThread;
 pub use thread_manager::ThreadManager;
@@ -101,12 +142,15 @@ pub mod project_doc;
 mod rollout;
 pub(crate) mod safety;
 pub mod seatbelt;
+pub mod semantic_search;
 pub mod shell;
 pub mod shell_snapshot;
 pub mod skills;
 pub mod spawn;
 pub mod state_db;
 pub mod terminal;
+mod todo_list;
+mod tool_plugins;
 mod tools;
 pub mod turn_diff_tracker;
 mod turn_metadata;
@@ -117,12 +161,26 @@ pub use rollout::RolloutRecorderParams;
 pub use rollout::SESSIONS_SUBDIR;
 pub use rollout::SessionMeta;
 pub use rollout::append_thread_name;
+pub use rollout::archive_rollout;
+pub use rollout::archive_rollout_with_state_db;
+pub use rollout::compare::SessionComparison;
+pub use rollout::compare::SessionTranscript;
+pub use rollout::compare::TranscriptTurn;
+pub use rollout::compare::compare_rollouts;
+pub use rollout::compare::load_transcript;
+pub use rollout::dataset::DatasetExample;
+pub use rollout::dataset::TaskOutcome;
+pub use rollout::dataset::load_dataset_examples;
 pub use rollout::find_archived_thread_path_by_id_str;
 #[deprecated(note = "use find_thread_path_by_id_str")]
 pub use rollout::find_conversation_path_by_id_str;
 pub use rollout::find_thread_name_by_id;
 pub use rollout::find_thread_path_by_id_str;
 pub use rollout::find_thread_path_by_name_str;
+pub use rollout::find_thread_path_by_reference;
+pub use rollout::import::ImportFormat;
+pub use rollout::import::parse_transcript;
+pub use rollout::import::write_imported_rollout;
 pub use rollout::list::Cursor;
 pub use rollout::list::ThreadItem;
 pub use rollout::list::ThreadSortKey;
@@ -130,9 +188,18 @@ pub use rollout::list::ThreadsPage;
 pub use rollout::list::parse_cursor;
 pub use rollout::list::read_head_for_summary;
 pub use rollout::list::read_session_meta_line;
+pub use rollout::lock::RolloutLock;
+pub use rollout::lock::SessionLockedError;
+pub use rollout::lock::check_rollout_lock;
 pub use rollout::policy::EventPersistenceMode;
 pub use rollout::rollout_date_parts;
 pub use rollout::session_index::find_thread_names_by_ids;
+pub use rollout::stats::SessionStats;
+pub use rollout::stats::describe_estimated_cost;
+pub use rollout::stats::estimate_cost_usd;
+pub use rollout::stats::load_session_stats;
+pub use rollout::unarchive_rollout;
+pub use rollout::unarchive_rollout_with_state_db;
 mod function_tool;
 mod state;
 mod tasks;
@@ -0,0 +1,395 @@
+//! Finding MCP servers that are installed locally but not configured yet.
+//!
+//! Three places are scanned, in order of precedence when names collide:
+//!
+//! 1. Project manifests at the project root: `.mcp.json` (`mcpServers`) and
+//!    `.vscode/mcp.json` (`servers`).
+//! 2. `$CODEX_HOME/mcp/`: `<name>.toml` files holding a single `[mcp_servers.<name>]` body, and
+//!    executables, which become stdio servers named after the file.
+//! 3. Executables on `PATH` named `mcp-<name>`.
+//!
+//! Discovery never changes configuration; callers decide which servers to enable and where.
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::ffi::OsStr;
+use std::path::Path;
+use std::path::PathBuf;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::config::types::McpServerConfig;
+use crate::config::types::McpServerTransportConfig;
+
+/// Directory under `CODEX_HOME` scanned for server files and executables.
+pub const MCP_DISCOVERY_DIR: &str = "mcp";
+/// Prefix that marks an executable on `PATH` as an MCP server.
+const PATH_PREFIX: &str = "mcp-";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum McpDiscoverySource {
+    ProjectManifest,
+    CodexHome,
+    Path,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiscoveredMcpServer {
+    pub name: String,
+    pub source: McpDiscoverySource,
+    /// The manifest, server file, or executable the server was found through.
+    pub origin: PathBuf,
+    pub config: McpServerConfig,
+}
+
+/// `.mcp.json` and `.vscode/mcp.json` entries. Both use the same per-server shape; only the
+/// top-level key differs.
+#[derive(Deserialize)]
+struct ManifestServer {
+    command: Option<String>,
+    #[serde(default)]
+    args: Vec<String>,
+    env: Option<HashMap<String, String>>,
+    url: Option<String>,
+    headers: Option<HashMap<String, String>>,
+}
+
+/// Scan every discovery location. Servers whose names appear in `configured` are skipped, as
+/// are names that `codex mcp add` would reject.
+pub fn discover_mcp_servers(
+    codex_home: &Path,
+    project_root: &Path,
+    path_env: Option<&OsStr>,
+    configured: &[String],
+) -> Vec<DiscoveredMcpServer> {
+    let mut found: BTreeMap<String, DiscoveredMcpServer> = BTreeMap::new();
+    let candidates = project_manifest_servers(project_root)
+        .into_iter()
+        .chain(codex_home_servers(&codex_home.join(MCP_DISCOVERY_DIR)))
+        .chain(path_servers(path_env));
+    for server in candidates {
+        if !is_valid_server_name(&server.name) || configured.contains(&server.name) {
+            continue;
+        }
+        found.entry(server.name.clone()).or_insert(server);
+    }
+    found.into_values().collect()
+}
+
+fn project_manifest_servers(project_root: &Path) -> Vec<DiscoveredMcpServer> {
+    let manifests = [
+        (project_root.join(".mcp.json"), "mcpServers"),
+        (project_root.join(".vscode").join("mcp.json"), "servers"),
+    ];
+    let mut servers = Vec::new();
+    for (path, key) in manifests {
+        let Ok(contents) = std::fs::read_to_string(&path) else {
+            continue;
+        };
+        let mut manifest: HashMap<String, serde_json::Value> = match serde_json::from_str(&contents)
+        {
+            Ok(manifest) => manifest,
+            Err(err) => {
+                tracing::warn!("ignoring malformed MCP manifest {}: {err}", path.display());
+                continue;
+            }
+        };
+        let Some(entries) = manifest.remove(key) else {
+            continue;
+        };
+        let entries: BTreeMap<String, ManifestServer> = match serde_json::from_value(entries) {
+            Ok(entries) => entries,
+            Err(err) => {
+                tracing::warn!("ignoring malformed MCP manifest {}: {err}", path.display());
+                continue;
+            }
+        };
+        for (name, entry) in entries {
+            let transport = match entry {
+                ManifestServer {
+                    command: Some(command),
+                    args,
+                    env,
+                    ..
+                } => McpServerTransportConfig::Stdio {
+                    command,
+                    args,
+                    env,
+                    env_vars: Vec::new(),
+                    cwd: None,
+                },
+                ManifestServer {
+                    url: Some(url),
+                    headers,
+                    ..
+                } => McpServerTransportConfig::StreamableHttp {
+                    url,
+                    bearer_token_env_var: None,
+                    http_headers: headers,
+                    env_http_headers: None,
+                },
+                ManifestServer { .. } => continue,
+            };
+            servers.push(DiscoveredMcpServer {
+                name,
+                source: McpDiscoverySource::ProjectManifest,
+                origin: path.clone(),
+                config: server_config(transport),
+            });
+        }
+    }
+    servers
+}
+
+fn codex_home_servers(dir: &Path) -> Vec<DiscoveredMcpServer> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut paths: Vec<PathBuf> = entries.flatten().map(|entry| entry.path()).collect();
+    paths.sort();
+    let mut servers = Vec::new();
+    for path in paths {
+        let Some(stem) = path.file_stem().and_then(OsStr::to_str) else {
+            continue;
+        };
+        let name = stem.to_string();
+        if path.extension().is_some_and(|ext| ext == "toml") {
+            let config = std::fs::read_to_string(&path)
+                .map_err(|err| err.to_string())
+                .and_then(|contents| {
+                    toml::from_str::<McpServerConfig>(&contents).map_err(|err| err.to_string())
+                });
+            match config {
+                Ok(config) => servers.push(DiscoveredMcpServer {
+                    name,
+                    source: McpDiscoverySource::CodexHome,
+                    origin: path,
+                    config,
+                }),
+                Err(err) => {
+                    tracing::warn!("ignoring MCP server file {}: {err}", path.display());
+                }
+            }
+        } else if is_executable(&path) {
+            servers.push(DiscoveredMcpServer {
+                name,
+                source: McpDiscoverySource::CodexHome,
+                config: stdio_server(path.to_string_lossy().into_owned()),
+                origin: path,
+            });
+        }
+    }
+    servers
+}
+
+fn path_servers(path_env: Option<&OsStr>) -> Vec<DiscoveredMcpServer> {
+    let Some(path_env) = path_env else {
+        return Vec::new();
+    };
+    let mut servers = Vec::new();
+    for dir in std::env::split_paths(path_env) {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        let mut paths: Vec<PathBuf> = entries.flatten().map(|entry| entry.path()).collect();
+        paths.sort();
+        for path in paths {
+            let Some(file_name) = path.file_name().and_then(OsStr::to_str) else {
+                continue;
+            };
+            let command = file_name.to_string();
+            let stem = if cfg!(windows) {
+                path.file_stem()
+                    .and_then(OsStr::to_str)
+                    .unwrap_or(file_name)
+            } else {
+                file_name
+            };
+            let Some(name) = stem.strip_prefix(PATH_PREFIX) else {
+                continue;
+            };
+            if name.is_empty() || !is_executable(&path) {
+                continue;
+            }
+            servers.push(DiscoveredMcpServer {
+                name: name.to_string(),
+                source: McpDiscoverySource::Path,
+                origin: path.clone(),
+                config: stdio_server(command),
+            });
+        }
+    }
+    servers
+}
+
+fn stdio_server(command: String) -> McpServerConfig {
+    server_config(McpServerTransportConfig::Stdio {
+        command,
+        args: Vec::new(),
+        env: None,
+        env_vars: Vec::new(),
+        cwd: None,
+    })
+}
+
+fn server_config(transport: McpServerTransportConfig) -> McpServerConfig {
+    McpServerConfig {
+        transport,
+        enabled: true,
+        required: false,
+        disabled_reason: None,
+        startup_timeout_sec: None,
+        tool_timeout_sec: None,
+        enabled_tools: None,
+        disabled_tools: None,
+        scopes: None,
+        oauth_resource: None,
+    }
+}
+
+fn is_valid_server_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+
+    std::fs::metadata(path)
+        .is_ok_and(|metadata| metadata.is_file() && metadata.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(windows)]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+        && path.extension().is_some_and(|ext| {
+            ["exe", "cmd", "bat"]
+                .iter()
+                .any(|candidate| ext.eq_ignore_ascii_case(candidate))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    #[test]
+    fn project_manifests_take_precedence_and_configured_servers_are_skipped() {
+        let project = TempDir::new().expect("project dir");
+        let codex_home = TempDir::new().expect("codex home");
+        std::fs::write(
+            project.path().join(".mcp.json"),
+            r#"{"mcpServers": {
+                "docs": {"command": "npx", "args": ["docs-mcp"], "env": {"TOKEN": "x"}},
+                "remote": {"url": "https://example.com/mcp"},
+                "github": {"command": "gh-mcp"}
+            }}"#,
+        )
+        .expect("write manifest");
+        let mcp_dir = codex_home.path().join(MCP_DISCOVERY_DIR);
+        std::fs::create_dir_all(&mcp_dir).expect("create mcp dir");
+        std::fs::write(mcp_dir.join("docs.toml"), "command = \"docs-server\"\n")
+            .expect("write server file");
+        std::fs::write(
+            mcp_dir.join("notes.toml"),
+            "url = \"http://localhost:9000/mcp\"\n",
+        )
+        .expect("write server file");
+
+        let servers = discover_mcp_servers(
+            codex_home.path(),
+            project.path(),
+            None,
+            &["github".to_string()],
+        );
+
+        let summary: Vec<(&str, McpDiscoverySource, &McpServerTransportConfig)> = servers
+            .iter()
+            .map(|server| {
+                (
+                    server.name.as_str(),
+                    server.source,
+                    &server.config.transport,
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    "docs",
+                    McpDiscoverySource::ProjectManifest,
+                    &McpServerTransportConfig::Stdio {
+                        command: "npx".to_string(),
+                        args: vec!["docs-mcp".to_string()],
+                        env: Some(HashMap::from([("TOKEN".to_string(), "x".to_string())])),
+                        env_vars: Vec::new(),
+                        cwd: None,
+                    },
+                ),
+                (
+                    "notes",
+                    McpDiscoverySource::CodexHome,
+                    &McpServerTransportConfig::StreamableHttp {
+                        url: "http://localhost:9000/mcp".to_string(),
+                        bearer_token_env_var: None,
+                        http_headers: None,
+                        env_http_headers: None,
+                    },
+                ),
+                (
+                    "remote",
+                    McpDiscoverySource::ProjectManifest,
+                    &McpServerTransportConfig::StreamableHttp {
+                        url: "https://example.com/mcp".to_string(),
+                        bearer_token_env_var: None,
+                        http_headers: None,
+                        env_http_headers: None,
+                    },
+                ),
+            ]
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn prefixed_executables_on_path_are_servers() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let bin = TempDir::new().expect("bin dir");
+        for (file, mode) in [
+            ("mcp-weather", 0o755),
+            ("mcp-notes", 0o644),
+            ("weather", 0o755),
+        ] {
+            let path = bin.path().join(file);
+            std::fs::write(&path, "#!/bin/sh\n").expect("write script");
+            std::fs::set_permissions(&path, std::fs::Permissions::from_mode(mode)).expect("chmod");
+        }
+        let empty = TempDir::new().expect("empty dir");
+
+        let servers = discover_mcp_servers(
+            empty.path(),
+            empty.path(),
+            Some(bin.path().as_os_str()),
+            &[],
+        );
+
+        assert_eq!(
+            servers,
+            vec![DiscoveredMcpServer {
+                name: "weather".to_string(),
+                source: McpDiscoverySource::Path,
+                origin: bin.path().join("mcp-weather"),
+                config: stdio_server("mcp-weather".to_string()),
+            }]
+        );
+    }
+}
@@ -1,4 +1,5 @@
 pub mod auth;
+pub mod discovery;
 mod skill_dependencies;
 pub(crate) use skill_dependencies::maybe_prompt_and_install_mcp_dependencies;
 
@@ -428,6 +428,16 @@ fn is_full_access_mode(turn_context: &TurnContext) -> bool {
         )
 }
 
+/// Whether `server` annotates `tool_name` as read-only (`readOnlyHint`). Tools without the
+/// annotation are assumed to change state.
+pub(crate) async fn mcp_tool_is_read_only(sess: &Session, server: &str, tool_name: &str) -> bool {
+    lookup_mcp_tool_metadata(sess, server, tool_name)
+        .await
+        .and_then(|metadata| metadata.annotations)
+        .and_then(|annotations| annotations.read_only_hint)
+        == Some(true)
+}
+
 async fn lookup_mcp_tool_metadata(
     sess: &Session,
     server: &str,
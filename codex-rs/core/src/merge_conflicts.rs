@@ -0,0 +1,416 @@
+//! Find merge-conflict hunks in the workspace and resolve them one at a time (`/conflicts`).
+//!
+//! Conflicts are found from git's unmerged paths plus any tracked file that still contains a
+//! `<<<<<<<` marker line. When the markers do not include the common ancestor (git's default
+//! `merge` conflict style), the base of each hunk is recovered by re-running the three-way
+//! merge on the index stages with `--diff3`.
+
+use std::path::Path;
+use std::path::PathBuf;
+
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::ThreadManager;
+use crate::config::Config;
+use crate::forked_prompt::ask_forked_conversation;
+use crate::pull_request::GIT_TIMEOUT;
+use crate::pull_request::git_output;
+use crate::pull_request::git_stdout;
+
+/// Lines of surrounding file content shown to the model with each hunk.
+const CONTEXT_LINES: usize = 20;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConflictHunk {
+    pub path: PathBuf,
+    /// `path` relative to the repository root, for display.
+    pub display_path: String,
+    /// 1-based line of the `<<<<<<<` marker.
+    pub line: usize,
+    pub ours_label: String,
+    pub theirs_label: String,
+    pub ours: String,
+    pub base: Option<String>,
+    pub theirs: String,
+    pub before: String,
+    pub after: String,
+    /// The hunk exactly as it appears in the file, markers included. Applying a resolution
+    /// replaces this text, so hunks stay addressable after earlier ones in the file change.
+    block: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct ConflictResolution {
+    pub resolution: String,
+    pub explanation: String,
+}
+
+/// Every conflicted hunk in the repository containing `cwd`, in path and line order.
+pub async fn find_conflicts(cwd: &Path) -> Result<Vec<ConflictHunk>, String> {
+    let root = PathBuf::from(
+        git_stdout(cwd, &["rev-parse", "--show-toplevel"])
+            .await?
+            .trim(),
+    );
+    let unmerged = git_stdout(&root, &["diff", "--name-only", "--diff-filter=U", "-z"]).await?;
+    let unmerged: Vec<&str> = unmerged.split('\0').filter(|p| !p.is_empty()).collect();
+    // `git grep` exits with 1 when nothing matches.
+    let grep = git_output(
+        &root,
+        &["grep", "-l", "-z", "-I", "-E", "^<{7}( |$)"],
+        GIT_TIMEOUT,
+    )
+    .await?;
+    let marked = String::from_utf8_lossy(&grep.stdout).into_owned();
+    let mut paths: Vec<&str> = unmerged
+        .iter()
+        .copied()
+        .chain(marked.split('\0').filter(|p| !p.is_empty()))
+        .collect();
+    paths.sort_unstable();
+    paths.dedup();
+
+    let mut hunks = Vec::new();
+    for display_path in paths {
+        let path = root.join(display_path);
+        // Unmerged paths include deletions, which leave nothing to walk through.
+        let Ok(bytes) = tokio::fs::read(&path).await else {
+            continue;
+        };
+        let contents = String::from_utf8_lossy(&bytes);
+        let mut file_hunks = parse_conflict_hunks(&path, display_path, &contents);
+        if file_hunks.iter().any(|hunk| hunk.base.is_none()) && unmerged.contains(&display_path) {
+            fill_bases_from_index(&root, display_path, &mut file_hunks).await;
+        }
+        hunks.extend(file_hunks);
+    }
+    Ok(hunks)
+}
+
+/// Split `contents` into its conflict hunks. Lines keep their endings.
+pub fn parse_conflict_hunks(path: &Path, display_path: &str, contents: &str) -> Vec<ConflictHunk> {
+    enum Section {
+        Outside,
+        Ours,
+        Base,
+        Theirs,
+    }
+
+    let lines: Vec<&str> = contents.split_inclusive('\n').collect();
+    let mut hunks = Vec::new();
+    let mut section = Section::Outside;
+    let mut start = 0;
+    let mut ours_label = String::new();
+    let (mut ours, mut base, mut theirs) = (String::new(), None::<String>, String::new());
+    for (index, line) in lines.iter().enumerate() {
+        match section {
+            Section::Outside => {
+                if let Some(label) = marker_label(line, '<') {
+                    section = Section::Ours;
+                    start = index;
+                    ours_label = label;
+                    ours.clear();
+                    base = None;
+                    theirs.clear();
+                }
+            }
+            Section::Ours | Section::Base if marker_label(line, '=').is_some() => {
+                section = Section::Theirs;
+            }
+            Section::Ours if marker_label(line, '|').is_some() => {
+                section = Section::Base;
+                base = Some(String::new());
+            }
+            Section::Ours => ours.push_str(line),
+            Section::Base => {
+                if let Some(base) = base.as_mut() {
+                    base.push_str(line);
+                }
+            }
+            Section::Theirs => {
+                if let Some(theirs_label) = marker_label(line, '>') {
+                    hunks.push(ConflictHunk {
+                        path: path.to_path_buf(),
+                        display_path: display_path.to_string(),
+                        line: start + 1,
+                        ours_label: std::mem::take(&mut ours_label),
+                        theirs_label,
+                        ours: std::mem::take(&mut ours),
+                        base: base.take(),
+                        theirs: std::mem::take(&mut theirs),
+                        before: lines[start.saturating_sub(CONTEXT_LINES)..start].concat(),
+                        after: lines[index + 1..(index + 1 + CONTEXT_LINES).min(lines.len())]
+                            .concat(),
+                        block: lines[start..=index].concat(),
+                    });
+                    section = Section::Outside;
+                } else {
+                    theirs.push_str(line);
+                }
+            }
+        }
+    }
+    hunks
+}
+
+/// The label after a seven-character conflict marker made of `marker`, or `None` when `line`
+/// is not such a marker.
+fn marker_label(line: &str, marker: char) -> Option<String> {
+    let line = line.trim_end_matches(['\n', '\r']);
+    let rest = line.strip_prefix(&marker.to_string().repeat(7))?;
+    if rest.is_empty() {
+        Some(String::new())
+    } else {
+        rest.strip_prefix(' ').map(str::to_string)
+    }
+}
+
+/// Recover each hunk's base by merging the index stages again with `--diff3`. Hunks are matched
+/// by their ours/theirs text, so a hunk the user has already edited is left without a base.
+async fn fill_bases_from_index(root: &Path, display_path: &str, hunks: &mut [ConflictHunk]) {
+    let Ok(dir) = tempfile::tempdir() else {
+        return;
+    };
+    let mut stage_files = Vec::new();
+    for (stage, name) in [(2, "ours"), (1, "base"), (3, "theirs")] {
+        // A file added on both sides has no base stage; an empty base merges the same way.
+        let contents = git_output(
+            root,
+            &["show", &format!(":{stage}:{display_path}")],
+            GIT_TIMEOUT,
+        )
+        .await
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| output.stdout)
+        .unwrap_or_default();
+        let file = dir.path().join(name);
+        if tokio::fs::write(&file, contents).await.is_err() {
+            return;
+        }
+        stage_files.push(file.to_string_lossy().into_owned());
+    }
+    let Ok(output) = git_output(
+        root,
+        &[
+            "merge-file",
+            "-p",
+            "--diff3",
+            &stage_files[0],
+            &stage_files[1],
+            &stage_files[2],
+        ],
+        GIT_TIMEOUT,
+    )
+    .await
+    else {
+        return;
+    };
+    let merged = String::from_utf8_lossy(&output.stdout);
+    let with_bases = parse_conflict_hunks(Path::new(display_path), display_path, &merged);
+    for hunk in hunks.iter_mut().filter(|hunk| hunk.base.is_none()) {
+        if let Some(found) = with_bases
+            .iter()
+            .find(|candidate| candidate.ours == hunk.ours && candidate.theirs == hunk.theirs)
+        {
+            hunk.base = found.base.clone();
+        }
+    }
+}
+
+/// Ask a read-only fork of the conversation at `rollout_path` how to resolve `hunk`.
+pub async fn propose_conflict_resolution(
+    thread_manager: &ThreadManager,
+    config: &Config,
+    rollout_path: Option<&Path>,
+    hunk: &ConflictHunk,
+) -> Result<ConflictResolution, String> {
+    ask_forked_conversation(
+        thread_manager,
+        config,
+        rollout_path,
+        &resolution_prompt(hunk),
+        json!({
+            "type": "object",
+            "properties": {
+                "resolution": { "type": "string" },
+                "explanation": { "type": "string" },
+            },
+            "required": ["resolution", "explanation"],
+            "additionalProperties": false,
+        }),
+        "a conflict resolution",
+    )
+    .await
+}
+
+fn resolution_prompt(hunk: &ConflictHunk) -> String {
+    let mut prompt = format!(
+        "Resolve this merge conflict in {} at line {}. Combine the intent of both sides where \
+they are compatible; when they are not, prefer the side that matches what we discussed and say \
+why. Do not run commands or edit files. Reply with JSON: `resolution` is the exact text that \
+replaces the whole conflict, markers included, with no markers left in it; `explanation` is one \
+or two sentences on what you kept from each side.\n",
+        hunk.display_path, hunk.line
+    );
+    prompt.push_str(&format!(
+        "\nLines before the conflict:\n```\n{}```\n",
+        hunk.before
+    ));
+    prompt.push_str(&format!(
+        "\nOurs ({}):\n```\n{}```\n",
+        hunk.ours_label, hunk.ours
+    ));
+    match &hunk.base {
+        Some(base) => prompt.push_str(&format!("\nBase (common ancestor):\n```\n{base}```\n")),
+        None => prompt.push_str("\nThe common ancestor is not available.\n"),
+    }
+    prompt.push_str(&format!(
+        "\nTheirs ({}):\n```\n{}```\n",
+        hunk.theirs_label, hunk.theirs
+    ));
+    prompt.push_str(&format!(
+        "\nLines after the conflict:\n```\n{}```\n",
+        hunk.after
+    ));
+    prompt
+}
+
+/// Replace `hunk` in its file with `resolution` and return how many conflict hunks are left in
+/// that file.
+pub async fn apply_conflict_resolution(
+    hunk: &ConflictHunk,
+    resolution: &str,
+) -> Result<usize, String> {
+    let path = &hunk.path;
+    let contents = tokio::fs::read_to_string(path)
+        .await
+        .map_err(|err| format!("failed to read {}: {err}", path.display()))?;
+    // Prefer the hunk's original line, so identical hunks in one file resolve in order.
+    let at_line: usize = contents
+        .split_inclusive('\n')
+        .take(hunk.line.saturating_sub(1))
+        .map(str::len)
+        .sum();
+    let offset = if contents[at_line..].starts_with(&hunk.block) {
+        Some(at_line)
+    } else {
+        contents.find(&hunk.block)
+    };
+    let Some(offset) = offset else {
+        return Err(format!(
+            "the conflict at {}:{} changed since it was shown; run /conflicts again",
+            hunk.display_path, hunk.line
+        ));
+    };
+    let mut replacement = resolution.to_string();
+    if !replacement.is_empty() && !replacement.ends_with('\n') {
+        replacement.push('\n');
+    }
+    if hunk.block.contains("\r\n") {
+        replacement = replacement.replace("\r\n", "\n").replace('\n', "\r\n");
+    }
+    let mut updated = String::with_capacity(contents.len());
+    updated.push_str(&contents[..offset]);
+    updated.push_str(&replacement);
+    updated.push_str(&contents[offset + hunk.block.len()..]);
+    tokio::fs::write(path, &updated)
+        .await
+        .map_err(|err| format!("failed to write {}: {err}", path.display()))?;
+    Ok(parse_conflict_hunks(path, &hunk.display_path, &updated).len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::process::Command;
+    use tempfile::TempDir;
+
+    const CONFLICTED: &str =
+        "fn main() {\n<<<<<<< HEAD\n    run(1);\n=======\n    run(2);\n>>>>>>> feature\n}\n";
+
+    fn git(dir: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .env("GIT_CONFIG_GLOBAL", "/dev/null")
+            .env("GIT_CONFIG_NOSYSTEM", "1")
+            .status()
+            .expect("run git");
+        assert!(status.success(), "git {args:?} failed");
+    }
+
+    #[test]
+    fn parses_ours_base_and_theirs() {
+        let contents = "a\n<<<<<<< HEAD\nours\n||||||| merged common ancestors\nbase\n=======\ntheirs\n>>>>>>> feature\nz\n";
+
+        let hunks = parse_conflict_hunks(Path::new("/repo/f.txt"), "f.txt", contents);
+
+        assert_eq!(hunks.len(), 1);
+        let hunk = &hunks[0];
+        assert_eq!(hunk.line, 2);
+        assert_eq!(hunk.ours_label, "HEAD");
+        assert_eq!(hunk.theirs_label, "feature");
+        assert_eq!(hunk.ours, "ours\n");
+        assert_eq!(hunk.base.as_deref(), Some("base\n"));
+        assert_eq!(hunk.theirs, "theirs\n");
+        assert_eq!(hunk.before, "a\n");
+        assert_eq!(hunk.after, "z\n");
+    }
+
+    #[tokio::test]
+    async fn applying_a_resolution_replaces_only_that_hunk() {
+        let dir = TempDir::new().expect("tempdir");
+        let path = dir.path().join("main.rs");
+        let contents = format!("{CONFLICTED}{CONFLICTED}");
+        std::fs::write(&path, &contents).expect("write file");
+        let hunks = parse_conflict_hunks(&path, "main.rs", &contents);
+
+        let remaining = apply_conflict_resolution(&hunks[1], "    run(3);")
+            .await
+            .expect("apply");
+
+        assert_eq!(remaining, 1);
+        assert_eq!(
+            std::fs::read_to_string(&path).expect("read file"),
+            format!("{CONFLICTED}fn main() {{\n    run(3);\n}}\n")
+        );
+    }
+
+    #[tokio::test]
+    async fn finds_unmerged_hunks_with_their_base() {
+        let dir = TempDir::new().expect("tempdir");
+        let repo = dir.path();
+        git(repo, &["init", "--quiet", "--initial-branch=main"]);
+        git(repo, &["config", "user.name", "Test User"]);
+        git(repo, &["config", "user.email", "test@example.com"]);
+        git(repo, &["config", "commit.gpgsign", "false"]);
+        std::fs::write(repo.join("a.txt"), "base\n").expect("write file");
+        git(repo, &["add", "a.txt"]);
+        git(repo, &["commit", "--quiet", "-m", "base"]);
+        git(repo, &["checkout", "--quiet", "-b", "feature"]);
+        std::fs::write(repo.join("a.txt"), "theirs\n").expect("write file");
+        git(repo, &["commit", "--quiet", "-am", "theirs"]);
+        git(repo, &["checkout", "--quiet", "main"]);
+        std::fs::write(repo.join("a.txt"), "ours\n").expect("write file");
+        git(repo, &["commit", "--quiet", "-am", "ours"]);
+        let merge = Command::new("git")
+            .args(["merge", "--quiet", "feature"])
+            .current_dir(repo)
+            .env("GIT_CONFIG_GLOBAL", "/dev/null")
+            .env("GIT_CONFIG_NOSYSTEM", "1")
+            .output()
+            .expect("run git merge");
+        assert!(!merge.status.success(), "the merge should conflict");
+
+        let hunks = find_conflicts(repo).await.expect("find conflicts");
+
+        assert_eq!(hunks.len(), 1);
+        assert_eq!(hunks[0].display_path, "a.txt");
+        assert_eq!(hunks[0].ours, "ours\n");
+        assert_eq!(hunks[0].base.as_deref(), Some("base\n"));
+        assert_eq!(hunks[0].theirs, "theirs\n");
+    }
+}
@@ -0,0 +1,178 @@
+//! Send one prompt to several models side by side.
+//!
+//! Each model gets its own ephemeral thread cloned from the caller's config, with a read-only
+//! sandbox and no approvals so the runs can inspect the workspace but never change it. The
+//! threads run concurrently and are shut down once their turn ends.
+
+use std::time::Duration;
+use std::time::Instant;
+
+use codex_protocol::protocol::AskForApproval;
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::Op;
+use codex_protocol::protocol::SandboxPolicy;
+use codex_protocol::protocol::TokenUsage;
+use codex_protocol::user_input::UserInput;
+use futures::future::join_all;
+
+use crate::ThreadManager;
+use crate::config::Config;
+use crate::config::Constrained;
+use crate::features::Feature;
+use crate::rollout::stats::estimate_cost_usd;
+
+/// Outcome of running the comparison prompt on one model.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModelRun {
+    pub model: String,
+    /// Final assistant message of the turn.
+    pub response: Option<String>,
+    /// Why the run did not produce a response, if it failed.
+    pub error: Option<String>,
+    pub token_usage: TokenUsage,
+    /// Time from submitting the prompt to the end of the turn.
+    pub latency: Duration,
+    /// `None` when the model's price is not known.
+    pub estimated_cost_usd: Option<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModelComparison {
+    pub prompt: String,
+    pub runs: Vec<ModelRun>,
+}
+
+/// Run `prompt` once on each of `models` in parallel and collect the results in order.
+pub async fn compare_models(
+    thread_manager: &ThreadManager,
+    config: &Config,
+    models: &[String],
+    prompt: &str,
+) -> ModelComparison {
+    let runs = join_all(
+        models
+            .iter()
+            .map(|model| run_model(thread_manager, config, model, prompt)),
+    )
+    .await;
+    ModelComparison {
+        prompt: prompt.to_string(),
+        runs,
+    }
+}
+
+fn read_only_config(config: &Config, model: &str) -> Config {
+    let mut config = ephemeral_read_only_config(config);
+    config.model = Some(model.to_string());
+    config
+}
+
+/// A copy of `config` for a throwaway thread that can read the workspace but not change it:
+/// nothing is saved, nothing asks for approval, and no sub-agents are spawned.
+pub(crate) fn ephemeral_read_only_config(config: &Config) -> Config {
+    let mut config = config.clone();
+    config.ephemeral = true;
+    config.permissions.approval_policy = Constrained::allow_only(AskForApproval::Never);
+    config.permissions.sandbox_policy =
+        Constrained::allow_only(SandboxPolicy::new_read_only_policy());
+    let _ = config.features.disable(Feature::Collab);
+    config
+}
+
+async fn run_model(
+    thread_manager: &ThreadManager,
+    config: &Config,
+    model: &str,
+    prompt: &str,
+) -> ModelRun {
+    let mut run = ModelRun {
+        model: model.to_string(),
+        response: None,
+        error: None,
+        token_usage: TokenUsage::default(),
+        latency: Duration::ZERO,
+        estimated_cost_usd: None,
+    };
+    let new_thread = match thread_manager
+        .start_thread(read_only_config(config, model))
+        .await
+    {
+        Ok(new_thread) => new_thread,
+        Err(err) => {
+            run.error = Some(format!("failed to start a session: {err}"));
+            return run;
+        }
+    };
+    let thread = new_thread.thread;
+
+    let started = Instant::now();
+    let submitted = thread
+        .submit(Op::UserInput {
+            items: vec![UserInput::Text {
+                text: prompt.to_string(),
+                text_elements: Vec::new(),
+            }],
+            final_output_json_schema: None,
+        })
+        .await;
+    if let Err(err) = submitted {
+        run.error = Some(format!("failed to submit the prompt: {err}"));
+    } else {
+        loop {
+            let event = match thread.next_event().await {
+                Ok(event) => event,
+                Err(err) => {
+                    run.error = Some(err.to_string());
+                    break;
+                }
+            };
+            match event.msg {
+                EventMsg::TokenCount(event) => {
+                    if let Some(info) = event.info {
+                        run.token_usage = info.total_token_usage;
+                    }
+                }
+                EventMsg::Error(event) => run.error = Some(event.message),
+                EventMsg::TurnComplete(event) => {
+                    run.response = event.last_agent_message;
+                    break;
+                }
+                EventMsg::TurnAborted(event) => {
+                    run.error = Some(format!("turn aborted: {:?}", event.reason));
+                    break;
+                }
+                _ => {}
+            }
+        }
+    }
+    run.latency = started.elapsed();
+    run.estimated_cost_usd = estimate_cost_usd(model, &run.token_usage);
+
+    let _ = thread.submit(Op::Shutdown).await;
+    thread_manager.remove_thread(&new_thread.thread_id).await;
+    run
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::test_config;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn comparison_runs_are_read_only() {
+        let config = read_only_config(&test_config(), "gpt-5-mini");
+
+        assert_eq!(config.model.as_deref(), Some("gpt-5-mini"));
+        assert!(config.ephemeral);
+        assert_eq!(
+            config.permissions.approval_policy.value(),
+            AskForApproval::Never
+        );
+        assert_eq!(
+            config.permissions.sandbox_policy.get(),
+            &SandboxPolicy::new_read_only_policy()
+        );
+        assert!(!config.features.enabled(Feature::Collab));
+    }
+}
@@ -0,0 +1,136 @@
+//! Files pinned into the model context with `Op::SetPinnedFiles` (`/pin` in the TUI).
+//!
+//! Pinned files are re-read for every sampling request and sent as a contextual user message
+//! just before the latest user message. They are never recorded in the history, so edits to a
+//! pinned file are picked up right away and the content does not pile up turn after turn.
+
+use std::path::Path;
+use std::path::PathBuf;
+
+use codex_protocol::models::ResponseItem;
+use codex_utils_string::take_bytes_at_char_boundary;
+
+use crate::contextual_user_message::PINNED_FILES_FRAGMENT;
+
+/// Content beyond this many bytes per pinned file is cut off.
+pub const MAX_PINNED_FILE_BYTES: usize = 64 * 1024;
+
+/// The message carrying the current contents of `paths`, or `None` when nothing is pinned.
+pub(crate) async fn pinned_files_message(paths: &[PathBuf]) -> Option<ResponseItem> {
+    if paths.is_empty() {
+        return None;
+    }
+    let mut sections = Vec::with_capacity(paths.len());
+    for path in paths {
+        let section = match tokio::fs::read(path).await {
+            Ok(bytes) => format_pinned_file(path, &String::from_utf8_lossy(&bytes)),
+            Err(err) => format!(
+                "<file path=\"{}\">\n[could not be read: {err}]\n</file>",
+                path.display()
+            ),
+        };
+        sections.push(section);
+    }
+    Some(PINNED_FILES_FRAGMENT.into_message(PINNED_FILES_FRAGMENT.wrap(sections.join("\n"))))
+}
+
+fn format_pinned_file(path: &Path, contents: &str) -> String {
+    let mut text = take_bytes_at_char_boundary(contents, MAX_PINNED_FILE_BYTES).to_string();
+    if text.len() < contents.len() {
+        text.push_str(&format!(
+            "\n[truncated: showing the first {} of {} bytes]",
+            text.len(),
+            contents.len()
+        ));
+    }
+    format!(
+        "<file path=\"{}\">\n{}\n</file>",
+        path.display(),
+        text.trim_end()
+    )
+}
+
+/// Insert `message` right before the last user message in `input`, or at the end if there is
+/// none, so the pinned content stays next to the request it applies to.
+pub(crate) fn insert_before_last_user_message(
+    input: &mut Vec<ResponseItem>,
+    message: ResponseItem,
+) {
+    let position = input
+        .iter()
+        .rposition(|item| matches!(item, ResponseItem::Message { role, .. } if role == "user"))
+        .unwrap_or(input.len());
+    input.insert(position, message);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::models::ContentItem;
+    use pretty_assertions::assert_eq;
+
+    fn message(role: &str, text: &str) -> ResponseItem {
+        ResponseItem::Message {
+            id: None,
+            role: role.to_string(),
+            content: vec![ContentItem::InputText {
+                text: text.to_string(),
+            }],
+            end_turn: None,
+            phase: None,
+        }
+    }
+
+    fn text_of(item: &ResponseItem) -> &str {
+        match item {
+            ResponseItem::Message { content, .. } => match content.as_slice() {
+                [ContentItem::InputText { text }] => text,
+                other => panic!("unexpected content {other:?}"),
+            },
+            other => panic!("unexpected item {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn pinned_files_are_read_fresh_and_placed_before_the_last_user_message() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let schema = dir.path().join("schema.sql");
+        std::fs::write(&schema, "create table users (id int);\n").expect("write schema");
+        let missing = dir.path().join("missing.md");
+
+        let pinned = pinned_files_message(&[schema.clone(), missing.clone()])
+            .await
+            .expect("pinned message");
+        let text = text_of(&pinned);
+        assert!(text.starts_with("<pinned_files>\n"));
+        assert!(text.contains(&format!(
+            "<file path=\"{}\">\ncreate table users (id int);\n</file>",
+            schema.display()
+        )));
+        assert!(text.contains(&format!(
+            "<file path=\"{}\">\n[could not be read:",
+            missing.display()
+        )));
+
+        let mut input = vec![
+            message("user", "first"),
+            message("assistant", "ok"),
+            message("user", "second"),
+        ];
+        insert_before_last_user_message(&mut input, pinned.clone());
+        assert_eq!(input[2], pinned);
+        assert_eq!(text_of(&input[3]), "second");
+
+        assert_eq!(pinned_files_message(&[]).await, None);
+    }
+
+    #[test]
+    fn large_files_are_truncated() {
+        let contents = "x".repeat(MAX_PINNED_FILE_BYTES + 10);
+        let text = format_pinned_file(Path::new("big.txt"), &contents);
+        assert!(text.ends_with(&format!(
+            "[truncated: showing the first {MAX_PINNED_FILE_BYTES} of {} bytes]\n</file>",
+            MAX_PINNED_FILE_BYTES + 10
+        )));
+    }
+}
@@ -0,0 +1,189 @@
+//! Load an existing diff for review: a patch file, a git range, or a ref such as a pull
+//! request branch.
+//!
+//! The diff is embedded in a custom review request so the reviewer treats it as the change
+//! under review even when the workspace is checked out elsewhere.
+
+use std::path::Path;
+
+use codex_protocol::protocol::ReviewRequest;
+use codex_protocol::protocol::ReviewTarget;
+use codex_utils_string::take_bytes_at_char_boundary;
+
+use crate::pull_request::GIT_TIMEOUT;
+use crate::pull_request::git_output;
+use crate::pull_request::git_stdout;
+
+/// The diff embedded in the review prompt is cut off after this many bytes.
+const MAX_REVIEW_DIFF_BYTES: usize = 256 * 1024;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReviewDiff {
+    /// What the user asked to review, e.g. `fix.patch` or `origin/feature`.
+    pub source: String,
+    pub diff: String,
+}
+
+/// Resolve `source` relative to `cwd`:
+///
+/// - an existing file is read as a patch;
+/// - a range (`a..b`, `a...b`, `sha^!`) is passed to `git diff` as written;
+/// - a ref that HEAD already contains is diffed up to HEAD (the changes made since it);
+/// - any other ref is diffed from where it forked off HEAD (the changes it would bring in).
+pub async fn load_review_diff(source: &str, cwd: &Path) -> Result<ReviewDiff, String> {
+    let source = source.trim();
+    if source.is_empty() {
+        return Err("name a git ref, range, or patch file to review".to_string());
+    }
+    let path = cwd.join(source);
+    let diff = if path.is_file() {
+        let bytes = tokio::fs::read(&path)
+            .await
+            .map_err(|err| format!("failed to read {}: {err}", path.display()))?;
+        String::from_utf8_lossy(&bytes).into_owned()
+    } else if source.starts_with('-') {
+        // Git would read this as an option rather than a revision.
+        return Err(format!("`{source}` is neither a file nor a git ref"));
+    } else if source.contains("..") || source.ends_with("^!") {
+        git_stdout(cwd, &["diff", source]).await?
+    } else {
+        let verified = git_output(
+            cwd,
+            &[
+                "rev-parse",
+                "--verify",
+                "--quiet",
+                &format!("{source}^{{commit}}"),
+            ],
+            GIT_TIMEOUT,
+        )
+        .await?;
+        if !verified.status.success() {
+            return Err(format!("`{source}` is neither a file nor a git ref"));
+        }
+        let contained = git_output(
+            cwd,
+            &["merge-base", "--is-ancestor", source, "HEAD"],
+            GIT_TIMEOUT,
+        )
+        .await?
+        .status
+        .success();
+        let range = if contained {
+            format!("{source}...HEAD")
+        } else {
+            format!("HEAD...{source}")
+        };
+        git_stdout(cwd, &["diff", &range]).await?
+    };
+    if diff.trim().is_empty() {
+        return Err(format!("{source} has no changes to review"));
+    }
+    Ok(ReviewDiff {
+        source: source.to_string(),
+        diff,
+    })
+}
+
+/// A review request for `diff`, with optional extra instructions from the user.
+pub fn diff_review_request(diff: &ReviewDiff, instructions: Option<&str>) -> ReviewRequest {
+    let mut prompt = format!(
+        "Review the code changes in the diff below ({}). The workspace may not be checked out \
+at these changes: treat the diff as the change under review and read workspace files only for \
+surrounding context. Report each problem the author would want fixed at the file and line of \
+the new version it applies to.\n",
+        diff.source
+    );
+    if let Some(instructions) = instructions.map(str::trim).filter(|text| !text.is_empty()) {
+        prompt.push_str(&format!("\n{instructions}\n"));
+    }
+    let shown = take_bytes_at_char_boundary(&diff.diff, MAX_REVIEW_DIFF_BYTES);
+    prompt.push_str(&format!("\n```diff\n{}\n```\n", shown.trim_end()));
+    if shown.len() < diff.diff.len() {
+        prompt.push_str(&format!(
+            "(The diff was cut off after {MAX_REVIEW_DIFF_BYTES} bytes of {}; review what is \
+shown.)\n",
+            diff.diff.len()
+        ));
+    }
+    ReviewRequest {
+        target: ReviewTarget::Custom {
+            instructions: prompt,
+        },
+        user_facing_hint: Some(format!("diff from {}", diff.source)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn patch_files_are_loaded_as_written() {
+        let dir = TempDir::new().expect("tempdir");
+        let patch = "diff --git a/a.rs b/a.rs\n-old\n+new\n";
+        std::fs::write(dir.path().join("fix.patch"), patch).expect("write patch");
+
+        let diff = load_review_diff("fix.patch", dir.path())
+            .await
+            .expect("load patch");
+
+        assert_eq!(
+            diff,
+            ReviewDiff {
+                source: "fix.patch".to_string(),
+                diff: patch.to_string(),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn empty_patch_files_are_rejected() {
+        let dir = TempDir::new().expect("tempdir");
+        std::fs::write(dir.path().join("empty.patch"), "\n").expect("write patch");
+
+        assert_eq!(
+            load_review_diff("empty.patch", dir.path()).await,
+            Err("empty.patch has no changes to review".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn option_like_sources_are_not_passed_to_git() {
+        let dir = TempDir::new().expect("tempdir");
+
+        assert_eq!(
+            load_review_diff("--output=/tmp/x..y", dir.path()).await,
+            Err("`--output=/tmp/x..y` is neither a file nor a git ref".to_string())
+        );
+    }
+
+    #[test]
+    fn request_embeds_the_diff_and_instructions() {
+        let diff = ReviewDiff {
+            source: "origin/feature".to_string(),
+            diff: "-old\n+new\n".to_string(),
+        };
+
+        let request = diff_review_request(&diff, Some(" focus on error handling "));
+
+        assert_eq!(
+            request.user_facing_hint.as_deref(),
+            Some("diff from origin/feature")
+        );
+        let ReviewTarget::Custom { instructions } = request.target else {
+            panic!("expected a custom review target");
+        };
+        assert!(instructions.contains("(origin/feature)"), "{instructions}");
+        assert!(
+            instructions.contains("\nfocus on error handling\n"),
+            "{instructions}"
+        );
+        assert!(
+            instructions.ends_with("```diff\n-old\n+new\n```\n"),
+            "{instructions}"
+        );
+    }
+}
@@ -1,3 +1,5 @@
+use std::path::Path;
+
 use crate::protocol::ReviewFinding;
 use crate::protocol::ReviewOutputEvent;
 
@@ -80,3 +82,70 @@ pub fn render_review_output_text(output: &ReviewOutputEvent) -> String {
         sections.join("\n\n")
     }
 }
+
+/// Render a review as markdown for use outside Codex, such as an exported file or a pull
+/// request comment. File paths inside `root` are shown relative to it.
+pub fn format_review_markdown(output: &ReviewOutputEvent, root: &Path) -> String {
+    let mut sections = vec!["## Code review".to_string()];
+    let explanation = output.overall_explanation.trim();
+    if !explanation.is_empty() {
+        sections.push(explanation.to_string());
+    }
+    for item in &output.findings {
+        let location = &item.code_location;
+        let path = location
+            .absolute_file_path
+            .strip_prefix(root)
+            .unwrap_or(&location.absolute_file_path);
+        let mut finding = format!(
+            "### {}\n`{}:{}-{}`",
+            item.title.trim(),
+            path.display(),
+            location.line_range.start,
+            location.line_range.end
+        );
+        let body = item.body.trim();
+        if !body.is_empty() {
+            finding.push_str("\n\n");
+            finding.push_str(body);
+        }
+        sections.push(finding);
+    }
+    if sections.len() == 1 {
+        sections.push(REVIEW_FALLBACK_MESSAGE.to_string());
+    }
+    format!("{}\n", sections.join("\n\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::ReviewCodeLocation;
+    use crate::protocol::ReviewLineRange;
+    use pretty_assertions::assert_eq;
+    use std::path::PathBuf;
+
+    #[test]
+    fn markdown_uses_paths_relative_to_the_root() {
+        let output = ReviewOutputEvent {
+            findings: vec![ReviewFinding {
+                title: "[P1] Unchecked index".to_string(),
+                body: "`items[0]` panics when the list is empty.".to_string(),
+                confidence_score: 0.8,
+                priority: 1,
+                code_location: ReviewCodeLocation {
+                    absolute_file_path: PathBuf::from("/repo/src/lib.rs"),
+                    line_range: ReviewLineRange { start: 10, end: 12 },
+                },
+            }],
+            overall_correctness: "patch is incorrect".to_string(),
+            overall_explanation: "One crash on empty input.".to_string(),
+            overall_confidence_score: 0.8,
+        };
+
+        assert_eq!(
+            format_review_markdown(&output, Path::new("/repo")),
+            "## Code review\n\nOne crash on empty input.\n\n### [P1] Unchecked index\n`src/lib.rs:10-12`\n\n`items[0]` panics when the list is empty.\n"
+        );
+    }
+}
@@ -0,0 +1,219 @@
+//! Moving rollouts between the sessions tree and the archive.
+//!
+//! Archived rollouts live flat under [`ARCHIVED_SESSIONS_SUBDIR`]. They drop out of the default
+//! session listings but stay readable, so an archived session can still be resumed or restored
+//! to its dated folder under [`SESSIONS_SUBDIR`].
+
+use std::ffi::OsStr;
+use std::fs::FileTimes;
+use std::fs::OpenOptions;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+use chrono::Utc;
+use codex_protocol::ThreadId;
+
+use super::ARCHIVED_SESSIONS_SUBDIR;
+use super::SESSIONS_SUBDIR;
+use super::list::parse_timestamp_uuid_from_filename;
+use super::list::rollout_date_parts;
+use super::lock::check_rollout_lock;
+use crate::session_summary::move_session_summary;
+use crate::state_db;
+use crate::state_db::StateDbHandle;
+
+/// Move a rollout from the sessions tree into the archive and return its new path.
+pub async fn archive_rollout(codex_home: &Path, rollout_path: &Path) -> io::Result<PathBuf> {
+    let state_db = state_db::open_if_present(codex_home, "").await;
+    archive_rollout_with_state_db(codex_home, rollout_path, state_db.as_ref()).await
+}
+
+/// [`archive_rollout`], recording the move in `state_db` instead of the one under `codex_home`.
+///
+/// Fails with a `WouldBlock` error wrapping [`super::lock::SessionLockedError`] while another
+/// process has the rollout open.
+pub async fn archive_rollout_with_state_db(
+    codex_home: &Path,
+    rollout_path: &Path,
+    state_db: Option<&StateDbHandle>,
+) -> io::Result<PathBuf> {
+    let (thread_id, file_name) =
+        checked_rollout(rollout_path, &codex_home.join(SESSIONS_SUBDIR)).await?;
+    let archive_dir = codex_home.join(ARCHIVED_SESSIONS_SUBDIR);
+    tokio::fs::create_dir_all(&archive_dir).await?;
+    let archived_path = archive_dir.join(&file_name);
+    tokio::fs::rename(rollout_path, &archived_path).await?;
+    move_session_summary(rollout_path, &archived_path).await;
+    if let Some(ctx) = state_db
+        && let Err(err) = ctx
+            .mark_archived(thread_id, archived_path.as_path(), Utc::now())
+            .await
+    {
+        tracing::warn!("failed to mark thread {thread_id} archived in the state db: {err}");
+    }
+    Ok(archived_path)
+}
+
+/// Move an archived rollout back to its dated folder and return its new path.
+pub async fn unarchive_rollout(codex_home: &Path, rollout_path: &Path) -> io::Result<PathBuf> {
+    let state_db = state_db::open_if_present(codex_home, "").await;
+    unarchive_rollout_with_state_db(codex_home, rollout_path, state_db.as_ref()).await
+}
+
+/// [`unarchive_rollout`], recording the move in `state_db` instead of the one under
+/// `codex_home`. The restored rollout is touched so it sorts as recently updated.
+pub async fn unarchive_rollout_with_state_db(
+    codex_home: &Path,
+    rollout_path: &Path,
+    state_db: Option<&StateDbHandle>,
+) -> io::Result<PathBuf> {
+    let (thread_id, file_name) =
+        checked_rollout(rollout_path, &codex_home.join(ARCHIVED_SESSIONS_SUBDIR)).await?;
+    let Some((year, month, day)) = rollout_date_parts(OsStr::new(&file_name)) else {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+                "rollout `{}` has no date in its name",
+                rollout_path.display()
+            ),
+        ));
+    };
+    let dest_dir = codex_home
+        .join(SESSIONS_SUBDIR)
+        .join(year)
+        .join(month)
+        .join(day);
+    tokio::fs::create_dir_all(&dest_dir).await?;
+    let restored_path = dest_dir.join(&file_name);
+    tokio::fs::rename(rollout_path, &restored_path).await?;
+    move_session_summary(rollout_path, &restored_path).await;
+    let touched = restored_path.clone();
+    tokio::task::spawn_blocking(move || {
+        OpenOptions::new()
+            .append(true)
+            .open(&touched)?
+            .set_times(FileTimes::new().set_modified(SystemTime::now()))
+    })
+    .await
+    .map_err(io::Error::other)??;
+    if let Some(ctx) = state_db
+        && let Err(err) = ctx
+            .mark_unarchived(thread_id, restored_path.as_path())
+            .await
+    {
+        tracing::warn!("failed to mark thread {thread_id} unarchived in the state db: {err}");
+    }
+    Ok(restored_path)
+}
+
+/// The thread id and file name of a rollout, once it is known to live under `root`.
+async fn checked_rollout(rollout_path: &Path, root: &Path) -> io::Result<(ThreadId, String)> {
+    let canonical_root = tokio::fs::canonicalize(root).await?;
+    let canonical_path = tokio::fs::canonicalize(rollout_path).await?;
+    if !canonical_path.starts_with(&canonical_root) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+                "rollout `{}` is not under `{}`",
+                rollout_path.display(),
+                root.display()
+            ),
+        ));
+    }
+    let file_name = canonical_path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let thread_id = parse_timestamp_uuid_from_filename(&file_name)
+        .and_then(|(_, uuid)| ThreadId::from_string(&uuid.to_string()).ok())
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("`{}` is not a rollout file", rollout_path.display()),
+            )
+        })?;
+    // Moving a rollout out from under another process would leave it appending to a file
+    // that is no longer listed.
+    if let Some(locked) = check_rollout_lock(rollout_path) {
+        return Err(io::Error::new(io::ErrorKind::WouldBlock, locked));
+    }
+    Ok((thread_id, file_name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    const FILE_NAME: &str =
+        "rollout-2025-01-03T11-00-00-00000000-0000-0000-0000-0000000000d4.jsonl";
+
+    #[tokio::test]
+    async fn archive_and_restore_round_trip() {
+        let home = TempDir::new().expect("temp dir");
+        let day_dir = home.path().join(SESSIONS_SUBDIR).join("2025/01/03");
+        std::fs::create_dir_all(&day_dir).expect("create sessions dir");
+        let original = day_dir.join(FILE_NAME);
+        std::fs::write(&original, "{}\n").expect("write rollout");
+
+        let archived = archive_rollout(home.path(), &original)
+            .await
+            .expect("archive");
+        assert_eq!(
+            archived,
+            home.path().join(ARCHIVED_SESSIONS_SUBDIR).join(FILE_NAME)
+        );
+        assert!(!original.exists());
+
+        let restored = unarchive_rollout(home.path(), &archived)
+            .await
+            .expect("unarchive");
+        assert_eq!(restored, original);
+        assert_eq!(std::fs::read_to_string(&restored).expect("read"), "{}\n");
+    }
+
+    #[tokio::test]
+    async fn archive_rejects_files_outside_the_sessions_tree() {
+        let home = TempDir::new().expect("temp dir");
+        std::fs::create_dir_all(home.path().join(SESSIONS_SUBDIR)).expect("create sessions dir");
+        let stray = home.path().join(FILE_NAME);
+        std::fs::write(&stray, "{}\n").expect("write rollout");
+
+        let err = archive_rollout(home.path(), &stray)
+            .await
+            .expect_err("outside rollouts are refused");
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        assert!(stray.exists());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn archive_refuses_rollouts_open_in_another_process() {
+        use std::io::Write;
+
+        let home = TempDir::new().expect("temp dir");
+        let day_dir = home.path().join(SESSIONS_SUBDIR).join("2025/01/03");
+        std::fs::create_dir_all(&day_dir).expect("create sessions dir");
+        let original = day_dir.join(FILE_NAME);
+        std::fs::write(&original, "{}\n").expect("write rollout");
+        // A separate open file description behaves like another process's lock.
+        let mut other =
+            std::fs::File::create(day_dir.join(format!("{FILE_NAME}.lock"))).expect("lock file");
+        write!(other, "4242").expect("write pid");
+        other.try_lock().expect("lock from other handle");
+
+        let err = archive_rollout(home.path(), &original)
+            .await
+            .expect_err("locked rollouts are refused");
+        assert_eq!(err.kind(), io::ErrorKind::WouldBlock);
+        assert!(original.exists());
+
+        drop(other);
+        archive_rollout(home.path(), &original)
+            .await
+            .expect("archive after release");
+    }
+}
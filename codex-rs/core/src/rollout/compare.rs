@@ -0,0 +1,200 @@
+//! Conversation-level view of rollout files used to compare two sessions.
+//!
+//! A rollout is split into turns at each persisted user message. For every turn
+//! we keep the user prompt, the assistant messages, and the `apply_patch`
+//! payloads the model emitted, which is enough to put two sessions that were
+//! driven by the same prompts (but different models or settings) next to each
+//! other.
+
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+
+use codex_protocol::models::ResponseItem;
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::RolloutItem;
+use serde::Deserialize;
+
+use super::RolloutRecorder;
+
+const APPLY_PATCH_TOOL_NAME: &str = "apply_patch";
+
+/// One user prompt and everything the assistant produced in response to it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TranscriptTurn {
+    pub prompt: String,
+    pub responses: Vec<String>,
+    pub patches: Vec<String>,
+}
+
+/// Turn-by-turn summary of a single rollout file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionTranscript {
+    pub path: PathBuf,
+    /// Model recorded by the first turn context, if any.
+    pub model: Option<String>,
+    pub turns: Vec<TranscriptTurn>,
+}
+
+/// Two transcripts whose turns are paired by position.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionComparison {
+    pub left: SessionTranscript,
+    pub right: SessionTranscript,
+}
+
+impl SessionComparison {
+    /// Number of paired rows; the shorter session is padded with `None`.
+    pub fn turn_count(&self) -> usize {
+        self.left.turns.len().max(self.right.turns.len())
+    }
+
+    pub fn turn(&self, idx: usize) -> (Option<&TranscriptTurn>, Option<&TranscriptTurn>) {
+        (self.left.turns.get(idx), self.right.turns.get(idx))
+    }
+}
+
+#[derive(Deserialize)]
+struct ApplyPatchArgs {
+    input: String,
+}
+
+/// Load both rollout files and pair their turns.
+pub async fn compare_rollouts(left: &Path, right: &Path) -> io::Result<SessionComparison> {
+    let (left, right) = tokio::try_join!(load_transcript(left), load_transcript(right))?;
+    Ok(SessionComparison { left, right })
+}
+
+/// Build the turn-by-turn transcript of a rollout file.
+pub async fn load_transcript(path: &Path) -> io::Result<SessionTranscript> {
+    let history = RolloutRecorder::get_rollout_history(path).await?;
+    Ok(transcript_from_items(
+        path.to_path_buf(),
+        &history.get_rollout_items(),
+    ))
+}
+
+pub(crate) fn transcript_from_items(path: PathBuf, items: &[RolloutItem]) -> SessionTranscript {
+    let mut model = None;
+    let mut turns: Vec<TranscriptTurn> = Vec::new();
+    for item in items {
+        match item {
+            RolloutItem::TurnContext(ctx) => {
+                if model.is_none() {
+                    model = Some(ctx.model.clone());
+                }
+            }
+            RolloutItem::EventMsg(EventMsg::UserMessage(event)) => {
+                turns.push(TranscriptTurn {
+                    prompt: event.message.clone(),
+                    ..Default::default()
+                });
+            }
+            RolloutItem::EventMsg(EventMsg::AgentMessage(event)) => {
+                if let Some(turn) = turns.last_mut() {
+                    turn.responses.push(event.message.clone());
+                }
+            }
+            RolloutItem::ResponseItem(ResponseItem::CustomToolCall { name, input, .. })
+                if name == APPLY_PATCH_TOOL_NAME =>
+            {
+                if let Some(turn) = turns.last_mut() {
+                    turn.patches.push(input.clone());
+                }
+            }
+            RolloutItem::ResponseItem(ResponseItem::FunctionCall {
+                name, arguments, ..
+            }) if name == APPLY_PATCH_TOOL_NAME => {
+                if let Some(turn) = turns.last_mut()
+                    && let Ok(args) = serde_json::from_str::<ApplyPatchArgs>(arguments)
+                {
+                    turn.patches.push(args.input);
+                }
+            }
+            RolloutItem::SessionMeta(_)
+            | RolloutItem::ResponseItem(_)
+            | RolloutItem::Compacted(_)
+            | RolloutItem::EventMsg(_) => {}
+        }
+    }
+    SessionTranscript { path, model, turns }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::protocol::AgentMessageEvent;
+    use codex_protocol::protocol::UserMessageEvent;
+    use pretty_assertions::assert_eq;
+
+    fn user(message: &str) -> RolloutItem {
+        RolloutItem::EventMsg(EventMsg::UserMessage(UserMessageEvent {
+            message: message.to_string(),
+            images: None,
+            local_images: Vec::new(),
+            text_elements: Vec::new(),
+        }))
+    }
+
+    fn agent(message: &str) -> RolloutItem {
+        RolloutItem::EventMsg(EventMsg::AgentMessage(AgentMessageEvent {
+            message: message.to_string(),
+            phase: None,
+        }))
+    }
+
+    #[test]
+    fn transcript_groups_responses_and_patches_by_prompt() {
+        let items = vec![
+            agent("ignored before the first prompt"),
+            user("fix the bug"),
+            agent("Looking."),
+            RolloutItem::ResponseItem(ResponseItem::CustomToolCall {
+                id: None,
+                status: None,
+                call_id: "call-1".to_string(),
+                name: "apply_patch".to_string(),
+                input: "*** Begin Patch\n*** End Patch".to_string(),
+            }),
+            agent("Fixed."),
+            user("add a test"),
+            RolloutItem::ResponseItem(ResponseItem::FunctionCall {
+                id: None,
+                name: "apply_patch".to_string(),
+                arguments: r#"{"input":"*** Begin Patch\n*** Add File: t.rs\n*** End Patch"}"#
+                    .to_string(),
+                call_id: "call-2".to_string(),
+            }),
+            RolloutItem::ResponseItem(ResponseItem::FunctionCall {
+                id: None,
+                name: "shell".to_string(),
+                arguments: "{}".to_string(),
+                call_id: "call-3".to_string(),
+            }),
+        ];
+
+        let transcript = transcript_from_items(PathBuf::from("a.jsonl"), &items);
+
+        assert_eq!(
+            transcript,
+            SessionTranscript {
+                path: PathBuf::from("a.jsonl"),
+                model: None,
+                turns: vec![
+                    TranscriptTurn {
+                        prompt: "fix the bug".to_string(),
+                        responses: vec!["Looking.".to_string(), "Fixed.".to_string()],
+                        patches: vec!["*** Begin Patch\n*** End Patch".to_string()],
+                    },
+                    TranscriptTurn {
+                        prompt: "add a test".to_string(),
+                        responses: Vec::new(),
+                        patches: vec![
+                            "*** Begin Patch\n*** Add File: t.rs\n*** End Patch".to_string()
+                        ],
+                    },
+                ],
+            }
+        );
+    }
+}
@@ -0,0 +1,398 @@
+//! Evaluation and fine-tuning examples built from rollout files.
+//!
+//! Every user turn (a "task") becomes one example in the chat-completions `messages` shape: the
+//! user prompt, then the assistant's messages and tool calls in the order they were produced,
+//! with a `tool` message carrying each call's output. Injected context (environment details,
+//! AGENTS.md, developer instructions) and reasoning are not included. Each example records how
+//! its task ended, so callers can keep only the tasks that ran to completion. Turns removed by a
+//! rollback are dropped.
+
+use std::io;
+use std::path::Path;
+
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::LocalShellAction;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::RolloutItem;
+use serde::Serialize;
+
+use super::RolloutRecorder;
+
+/// How a task ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskOutcome {
+    Completed,
+    Interrupted,
+    /// The rollout ends before the turn finished, e.g. because Codex exited mid-turn.
+    Unfinished,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "role", rename_all = "snake_case")]
+pub enum DatasetMessage {
+    User {
+        content: String,
+    },
+    Assistant {
+        content: Option<String>,
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        tool_calls: Vec<DatasetToolCall>,
+    },
+    Tool {
+        tool_call_id: String,
+        content: String,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DatasetToolCall {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub function: DatasetFunctionCall,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DatasetFunctionCall {
+    pub name: String,
+    /// JSON-encoded arguments, as the model produced them.
+    pub arguments: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DatasetMetadata {
+    pub session_id: Option<String>,
+    /// Position of the task within its session, starting at 0.
+    pub task_index: usize,
+    pub model: Option<String>,
+    pub outcome: TaskOutcome,
+}
+
+/// One task, ready to be written as a JSONL line.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DatasetExample {
+    pub messages: Vec<DatasetMessage>,
+    pub metadata: DatasetMetadata,
+}
+
+/// A task being assembled; `outcome` stays `None` while the turn is running.
+struct PendingTask {
+    messages: Vec<DatasetMessage>,
+    model: Option<String>,
+    outcome: Option<TaskOutcome>,
+}
+
+impl PendingTask {
+    fn running(&self) -> bool {
+        self.outcome.is_none()
+    }
+}
+
+/// Read a rollout file and build one example per task.
+pub async fn load_dataset_examples(path: &Path) -> io::Result<Vec<DatasetExample>> {
+    let (items, _, _) = RolloutRecorder::load_rollout_items(path).await?;
+    Ok(examples_from_items(&items))
+}
+
+pub(crate) fn examples_from_items(items: &[RolloutItem]) -> Vec<DatasetExample> {
+    let mut session_id = None;
+    let mut model: Option<String> = None;
+    let mut tasks: Vec<PendingTask> = Vec::new();
+    for item in items {
+        match item {
+            RolloutItem::SessionMeta(meta) => {
+                session_id.get_or_insert_with(|| meta.meta.id.to_string());
+            }
+            RolloutItem::TurnContext(ctx) => {
+                model = Some(ctx.model.clone());
+                if let Some(task) = tasks.last_mut().filter(|task| task.running()) {
+                    task.model = model.clone();
+                }
+            }
+            RolloutItem::EventMsg(EventMsg::UserMessage(event)) => {
+                tasks.push(PendingTask {
+                    messages: vec![DatasetMessage::User {
+                        content: event.message.clone(),
+                    }],
+                    model: model.clone(),
+                    outcome: None,
+                });
+            }
+            RolloutItem::EventMsg(EventMsg::TurnComplete(_)) => {
+                if let Some(task) = tasks.last_mut().filter(|task| task.running()) {
+                    task.outcome = Some(TaskOutcome::Completed);
+                }
+            }
+            RolloutItem::EventMsg(EventMsg::TurnAborted(_)) => {
+                if let Some(task) = tasks.last_mut().filter(|task| task.running()) {
+                    task.outcome = Some(TaskOutcome::Interrupted);
+                }
+            }
+            RolloutItem::EventMsg(EventMsg::ThreadRolledBack(event)) => {
+                let kept = tasks.len().saturating_sub(event.num_turns as usize);
+                tasks.truncate(kept);
+            }
+            RolloutItem::ResponseItem(item) => {
+                if let Some(task) = tasks.last_mut().filter(|task| task.running()) {
+                    push_response_item(&mut task.messages, item);
+                }
+            }
+            RolloutItem::Compacted(_) | RolloutItem::EventMsg(_) => {}
+        }
+    }
+    tasks
+        .into_iter()
+        .enumerate()
+        .map(|(task_index, task)| DatasetExample {
+            messages: task.messages,
+            metadata: DatasetMetadata {
+                session_id: session_id.clone(),
+                task_index,
+                model: task.model,
+                outcome: task.outcome.unwrap_or(TaskOutcome::Unfinished),
+            },
+        })
+        .collect()
+}
+
+fn push_response_item(messages: &mut Vec<DatasetMessage>, item: &ResponseItem) {
+    match item {
+        ResponseItem::Message { role, content, .. } if role == "assistant" => {
+            let text: String = content
+                .iter()
+                .filter_map(|item| match item {
+                    ContentItem::OutputText { text } => Some(text.as_str()),
+                    ContentItem::InputText { .. } | ContentItem::InputImage { .. } => None,
+                })
+                .collect();
+            if !text.is_empty() {
+                messages.push(DatasetMessage::Assistant {
+                    content: Some(text),
+                    tool_calls: Vec::new(),
+                });
+            }
+        }
+        ResponseItem::FunctionCall {
+            name,
+            arguments,
+            call_id,
+            ..
+        } => push_tool_call(messages, call_id, name, arguments.clone()),
+        ResponseItem::CustomToolCall {
+            call_id,
+            name,
+            input,
+            ..
+        } => push_tool_call(
+            messages,
+            call_id,
+            name,
+            serde_json::json!({ "input": input }).to_string(),
+        ),
+        ResponseItem::LocalShellCall {
+            call_id: Some(call_id),
+            action: LocalShellAction::Exec(action),
+            ..
+        } => push_tool_call(
+            messages,
+            call_id,
+            "local_shell",
+            serde_json::to_string(action).unwrap_or_default(),
+        ),
+        ResponseItem::FunctionCallOutput { call_id, output }
+        | ResponseItem::CustomToolCallOutput { call_id, output } => {
+            messages.push(DatasetMessage::Tool {
+                tool_call_id: call_id.clone(),
+                content: output.body.to_text().unwrap_or_default(),
+            });
+        }
+        _ => {}
+    }
+}
+
+/// Attach a call to the assistant message it follows, or start a new assistant message when
+/// the previous message is not from the assistant.
+fn push_tool_call(
+    messages: &mut Vec<DatasetMessage>,
+    call_id: &str,
+    name: &str,
+    arguments: String,
+) {
+    let call = DatasetToolCall {
+        id: call_id.to_string(),
+        kind: "function".to_string(),
+        function: DatasetFunctionCall {
+            name: name.to_string(),
+            arguments,
+        },
+    };
+    match messages.last_mut() {
+        Some(DatasetMessage::Assistant { tool_calls, .. }) => tool_calls.push(call),
+        _ => messages.push(DatasetMessage::Assistant {
+            content: None,
+            tool_calls: vec![call],
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::models::FunctionCallOutputPayload;
+    use codex_protocol::protocol::ThreadRolledBackEvent;
+    use codex_protocol::protocol::TurnAbortReason;
+    use codex_protocol::protocol::TurnAbortedEvent;
+    use codex_protocol::protocol::TurnCompleteEvent;
+    use codex_protocol::protocol::UserMessageEvent;
+    use pretty_assertions::assert_eq;
+
+    fn user(message: &str) -> RolloutItem {
+        RolloutItem::EventMsg(EventMsg::UserMessage(UserMessageEvent {
+            message: message.to_string(),
+            images: None,
+            local_images: Vec::new(),
+            text_elements: Vec::new(),
+        }))
+    }
+
+    fn assistant(text: &str) -> RolloutItem {
+        RolloutItem::ResponseItem(ResponseItem::Message {
+            id: None,
+            role: "assistant".to_string(),
+            content: vec![ContentItem::OutputText {
+                text: text.to_string(),
+            }],
+            end_turn: None,
+            phase: None,
+        })
+    }
+
+    fn complete() -> RolloutItem {
+        RolloutItem::EventMsg(EventMsg::TurnComplete(TurnCompleteEvent {
+            turn_id: "turn".to_string(),
+            last_agent_message: None,
+        }))
+    }
+
+    #[test]
+    fn tasks_pair_tool_calls_with_their_outputs() {
+        let items = vec![
+            user("list files"),
+            RolloutItem::ResponseItem(ResponseItem::Message {
+                id: None,
+                role: "user".to_string(),
+                content: vec![ContentItem::InputText {
+                    text: "<environment_context/>".to_string(),
+                }],
+                end_turn: None,
+                phase: None,
+            }),
+            assistant("Checking."),
+            RolloutItem::ResponseItem(ResponseItem::FunctionCall {
+                id: None,
+                name: "shell".to_string(),
+                arguments: r#"{"command":["ls"]}"#.to_string(),
+                call_id: "call-1".to_string(),
+            }),
+            RolloutItem::ResponseItem(ResponseItem::FunctionCallOutput {
+                call_id: "call-1".to_string(),
+                output: FunctionCallOutputPayload::from_text("a.rs".to_string()),
+            }),
+            assistant("One file: a.rs."),
+            complete(),
+        ];
+
+        let examples = examples_from_items(&items);
+
+        assert_eq!(
+            examples,
+            vec![DatasetExample {
+                messages: vec![
+                    DatasetMessage::User {
+                        content: "list files".to_string(),
+                    },
+                    DatasetMessage::Assistant {
+                        content: Some("Checking.".to_string()),
+                        tool_calls: vec![DatasetToolCall {
+                            id: "call-1".to_string(),
+                            kind: "function".to_string(),
+                            function: DatasetFunctionCall {
+                                name: "shell".to_string(),
+                                arguments: r#"{"command":["ls"]}"#.to_string(),
+                            },
+                        }],
+                    },
+                    DatasetMessage::Tool {
+                        tool_call_id: "call-1".to_string(),
+                        content: "a.rs".to_string(),
+                    },
+                    DatasetMessage::Assistant {
+                        content: Some("One file: a.rs.".to_string()),
+                        tool_calls: Vec::new(),
+                    },
+                ],
+                metadata: DatasetMetadata {
+                    session_id: None,
+                    task_index: 0,
+                    model: None,
+                    outcome: TaskOutcome::Completed,
+                },
+            }]
+        );
+        assert_eq!(
+            serde_json::to_value(&examples[0].messages[1]).expect("serialize"),
+            serde_json::json!({
+                "role": "assistant",
+                "content": "Checking.",
+                "tool_calls": [{
+                    "id": "call-1",
+                    "type": "function",
+                    "function": { "name": "shell", "arguments": r#"{"command":["ls"]}"# },
+                }],
+            })
+        );
+    }
+
+    #[test]
+    fn outcomes_follow_how_each_turn_ended() {
+        let items = vec![
+            user("first"),
+            assistant("done"),
+            complete(),
+            user("second"),
+            RolloutItem::EventMsg(EventMsg::TurnAborted(TurnAbortedEvent {
+                turn_id: None,
+                reason: TurnAbortReason::Interrupted,
+            })),
+            user("rolled back"),
+            complete(),
+            RolloutItem::EventMsg(EventMsg::ThreadRolledBack(ThreadRolledBackEvent {
+                num_turns: 1,
+            })),
+            user("third"),
+            assistant("working"),
+        ];
+
+        let outcomes: Vec<(String, TaskOutcome)> = examples_from_items(&items)
+            .into_iter()
+            .map(|example| {
+                let prompt = match &example.messages[0] {
+                    DatasetMessage::User { content } => content.clone(),
+                    other => panic!("unexpected first message {other:?}"),
+                };
+                (prompt, example.metadata.outcome)
+            })
+            .collect();
+
+        assert_eq!(
+            outcomes,
+            vec![
+                ("first".to_string(), TaskOutcome::Completed),
+                ("second".to_string(), TaskOutcome::Interrupted),
+                ("third".to_string(), TaskOutcome::Unfinished),
+            ]
+        );
+    }
+}
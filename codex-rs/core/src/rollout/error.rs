@@ -3,6 +3,7 @@ use std::path::Path;
 
 use crate::error::CodexErr;
 use crate::rollout::SESSIONS_SUBDIR;
+use crate::rollout::lock::session_locked_error;
 
 pub(crate) fn map_session_init_error(err: &anyhow::Error, codex_home: &Path) -> CodexErr {
     if let Some(mapped) = err
@@ -17,6 +18,15 @@ pub(crate) fn map_session_init_error(err: &anyhow::Error, codex_home: &Path) ->
 }
 
 fn map_rollout_io_error(io_err: &std::io::Error, codex_home: &Path) -> Option<CodexErr> {
+    if let Some(locked) = session_locked_error(io_err) {
+        let holder = locked
+            .holder_pid
+            .map(|pid| format!(" (pid {pid})"))
+            .unwrap_or_default();
+        return Some(CodexErr::Fatal(format!(
+            "This session is already open in another Codex process{holder}. Close it there, or fork the session to continue it separately."
+        )));
+    }
     let sessions_dir = codex_home.join(SESSIONS_SUBDIR);
     let hint = match io_err.kind() {
         ErrorKind::PermissionDenied => format!(
@@ -0,0 +1,377 @@
+//! Convert conversations exported from other agent tools into rollout files.
+//!
+//! Two shapes are understood:
+//!
+//! - OpenAI-style message lists: a JSON array of `{ "role", "content" }` objects, or an
+//!   object with such an array under `messages`. `content` may be a string or a list of
+//!   text parts.
+//! - Claude Code session logs: JSONL files whose `user` and `assistant` lines carry a
+//!   `message` in the Anthropic messages format, plus `cwd` and `timestamp`.
+//!
+//! Only the visible conversation is kept: user prompts and assistant text. Tool calls, tool
+//! results, and system prompts are dropped, since they refer to tools Codex does not have.
+//! The result is written as a regular rollout, so it can be listed, browsed, and resumed.
+
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+
+use chrono::DateTime;
+use chrono::Local;
+use chrono::SecondsFormat;
+use chrono::Utc;
+use codex_protocol::ThreadId;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::protocol::AgentMessageEvent;
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::RolloutItem;
+use codex_protocol::protocol::RolloutLine;
+use codex_protocol::protocol::SessionMeta;
+use codex_protocol::protocol::SessionMetaLine;
+use codex_protocol::protocol::SessionSource;
+use codex_protocol::protocol::UserMessageEvent;
+use serde_json::Value;
+
+use super::SESSIONS_SUBDIR;
+
+/// `originator` recorded on imported sessions.
+const IMPORT_ORIGINATOR: &str = "codex_import";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportFormat {
+    /// Detect the format from the file contents.
+    Auto,
+    OpenAiMessages,
+    ClaudeCode,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportedRole {
+    User,
+    Assistant,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportedMessage {
+    pub role: ImportedRole,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ImportedConversation {
+    pub messages: Vec<ImportedMessage>,
+    /// Working directory recorded by the source tool, if any.
+    pub cwd: Option<PathBuf>,
+    /// When the conversation started, if the source recorded it.
+    pub started_at: Option<DateTime<Utc>>,
+}
+
+/// Parse an exported conversation.
+pub fn parse_transcript(contents: &str, format: ImportFormat) -> io::Result<ImportedConversation> {
+    let conversation = match format {
+        ImportFormat::OpenAiMessages => parse_openai_messages(contents)?,
+        ImportFormat::ClaudeCode => parse_claude_code(contents)?,
+        ImportFormat::Auto => match serde_json::from_str::<Value>(contents) {
+            Ok(value) => openai_messages_from_value(&value)?,
+            Err(_) => parse_claude_code(contents)?,
+        },
+    };
+    if !conversation
+        .messages
+        .iter()
+        .any(|message| message.role == ImportedRole::User)
+    {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "the transcript contains no user messages",
+        ));
+    }
+    Ok(conversation)
+}
+
+fn parse_openai_messages(contents: &str) -> io::Result<ImportedConversation> {
+    let value: Value = serde_json::from_str(contents).map_err(invalid_data)?;
+    openai_messages_from_value(&value)
+}
+
+fn openai_messages_from_value(value: &Value) -> io::Result<ImportedConversation> {
+    let messages = value
+        .as_array()
+        .or_else(|| value.get("messages").and_then(Value::as_array))
+        .ok_or_else(|| {
+            invalid_data("expected a JSON array of messages or an object with `messages`")
+        })?;
+    let messages = messages
+        .iter()
+        .filter_map(|message| {
+            let role = role_from_str(message.get("role")?.as_str()?)?;
+            let text = content_text(message.get("content")?)?;
+            Some(ImportedMessage { role, text })
+        })
+        .collect();
+    Ok(ImportedConversation {
+        messages,
+        ..Default::default()
+    })
+}
+
+fn parse_claude_code(contents: &str) -> io::Result<ImportedConversation> {
+    let mut conversation = ImportedConversation::default();
+    let mut parsed_any = false;
+    for line in contents.lines().filter(|line| !line.trim().is_empty()) {
+        let value: Value = serde_json::from_str(line).map_err(invalid_data)?;
+        parsed_any = true;
+        // Sidechains are sub-agent conversations interleaved into the log.
+        if value.get("isSidechain").and_then(Value::as_bool) == Some(true) {
+            continue;
+        }
+        let Some(role) = value
+            .get("type")
+            .and_then(Value::as_str)
+            .and_then(role_from_str)
+        else {
+            continue;
+        };
+        if conversation.cwd.is_none() {
+            conversation.cwd = value.get("cwd").and_then(Value::as_str).map(PathBuf::from);
+        }
+        if conversation.started_at.is_none() {
+            conversation.started_at = value
+                .get("timestamp")
+                .and_then(Value::as_str)
+                .and_then(|timestamp| DateTime::parse_from_rfc3339(timestamp).ok())
+                .map(|timestamp| timestamp.with_timezone(&Utc));
+        }
+        let Some(text) = value
+            .get("message")
+            .and_then(|message| message.get("content"))
+            .and_then(content_text)
+        else {
+            continue;
+        };
+        conversation.messages.push(ImportedMessage { role, text });
+    }
+    if !parsed_any {
+        return Err(invalid_data("the transcript is empty"));
+    }
+    Ok(conversation)
+}
+
+fn role_from_str(role: &str) -> Option<ImportedRole> {
+    match role {
+        "user" => Some(ImportedRole::User),
+        "assistant" => Some(ImportedRole::Assistant),
+        _ => None,
+    }
+}
+
+/// Join the text parts of a message; `None` when it has no text (for example, only tool calls).
+fn content_text(content: &Value) -> Option<String> {
+    let text = match content {
+        Value::String(text) => text.clone(),
+        Value::Array(parts) => parts
+            .iter()
+            .filter(|part| {
+                matches!(
+                    part.get("type").and_then(Value::as_str),
+                    Some("text" | "input_text" | "output_text")
+                )
+            })
+            .filter_map(|part| part.get("text").and_then(Value::as_str))
+            .collect::<Vec<_>>()
+            .join("\n\n"),
+        _ => return None,
+    };
+    (!text.trim().is_empty()).then_some(text)
+}
+
+fn invalid_data(err: impl Into<Box<dyn std::error::Error + Send + Sync>>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, err)
+}
+
+/// Write `conversation` as a new rollout under `codex_home` and return its thread id and path.
+///
+/// `cwd` is used when the transcript does not record a working directory.
+pub async fn write_imported_rollout(
+    codex_home: &Path,
+    conversation: &ImportedConversation,
+    cwd: &Path,
+) -> io::Result<(ThreadId, PathBuf)> {
+    let thread_id = ThreadId::new();
+    let started_at = conversation.started_at.unwrap_or_else(Utc::now);
+    let local = started_at.with_timezone(&Local);
+    let path = codex_home
+        .join(SESSIONS_SUBDIR)
+        .join(local.format("%Y/%m/%d").to_string())
+        .join(format!(
+            "rollout-{}-{thread_id}.jsonl",
+            local.format("%Y-%m-%dT%H-%M-%S")
+        ));
+    let timestamp = started_at.to_rfc3339_opts(SecondsFormat::Millis, true);
+
+    let meta = SessionMetaLine {
+        meta: SessionMeta {
+            id: thread_id,
+            timestamp: timestamp.clone(),
+            cwd: conversation
+                .cwd
+                .clone()
+                .unwrap_or_else(|| cwd.to_path_buf()),
+            originator: IMPORT_ORIGINATOR.to_string(),
+            cli_version: env!("CARGO_PKG_VERSION").to_string(),
+            source: SessionSource::Cli,
+            ..Default::default()
+        },
+        git: None,
+    };
+    let mut items = vec![RolloutItem::SessionMeta(meta)];
+    items.extend(conversation.messages.iter().flat_map(rollout_items_for));
+
+    let mut contents = String::new();
+    for item in items {
+        let line = RolloutLine {
+            timestamp: timestamp.clone(),
+            item,
+        };
+        contents.push_str(&serde_json::to_string(&line)?);
+        contents.push('\n');
+    }
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+    tokio::fs::write(&path, contents).await?;
+    Ok((thread_id, path))
+}
+
+/// The response item the model sees on resume plus the event the UI replays.
+fn rollout_items_for(message: &ImportedMessage) -> [RolloutItem; 2] {
+    match message.role {
+        ImportedRole::User => [
+            RolloutItem::ResponseItem(ResponseItem::Message {
+                id: None,
+                role: "user".to_string(),
+                content: vec![ContentItem::InputText {
+                    text: message.text.clone(),
+                }],
+                end_turn: None,
+                phase: None,
+            }),
+            RolloutItem::EventMsg(EventMsg::UserMessage(UserMessageEvent {
+                message: message.text.clone(),
+                images: None,
+                local_images: Vec::new(),
+                text_elements: Vec::new(),
+            })),
+        ],
+        ImportedRole::Assistant => [
+            RolloutItem::ResponseItem(ResponseItem::Message {
+                id: None,
+                role: "assistant".to_string(),
+                content: vec![ContentItem::OutputText {
+                    text: message.text.clone(),
+                }],
+                end_turn: None,
+                phase: None,
+            }),
+            RolloutItem::EventMsg(EventMsg::AgentMessage(AgentMessageEvent {
+                message: message.text.clone(),
+                phase: None,
+            })),
+        ],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rollout::compare::load_transcript;
+    use pretty_assertions::assert_eq;
+
+    fn user(text: &str) -> ImportedMessage {
+        ImportedMessage {
+            role: ImportedRole::User,
+            text: text.to_string(),
+        }
+    }
+
+    fn assistant(text: &str) -> ImportedMessage {
+        ImportedMessage {
+            role: ImportedRole::Assistant,
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn parses_openai_messages_with_text_parts() {
+        let contents = r#"{"messages": [
+            {"role": "system", "content": "You are helpful."},
+            {"role": "user", "content": "Fix the build"},
+            {"role": "assistant", "content": [{"type": "text", "text": "Done."}]},
+            {"role": "tool", "content": "ignored"}
+        ]}"#;
+
+        let conversation = parse_transcript(contents, ImportFormat::Auto).expect("parse");
+
+        assert_eq!(
+            conversation.messages,
+            vec![user("Fix the build"), assistant("Done.")]
+        );
+    }
+
+    #[test]
+    fn parses_claude_code_logs_without_tool_traffic() {
+        let contents = [
+            r#"{"type":"summary","summary":"Build fix"}"#,
+            r#"{"type":"user","cwd":"/repo","timestamp":"2025-06-01T10:00:00.000Z","message":{"role":"user","content":"Why does cargo fail?"}}"#,
+            r#"{"type":"assistant","message":{"role":"assistant","content":[{"type":"text","text":"Checking."},{"type":"tool_use","id":"t1","name":"Bash","input":{}}]}}"#,
+            r#"{"type":"user","message":{"role":"user","content":[{"type":"tool_result","tool_use_id":"t1","content":"error"}]}}"#,
+            r#"{"type":"assistant","isSidechain":true,"message":{"role":"assistant","content":"sub-agent"}}"#,
+            r#"{"type":"assistant","message":{"role":"assistant","content":[{"type":"text","text":"A missing import."}]}}"#,
+        ]
+        .join("\n");
+
+        let conversation = parse_transcript(&contents, ImportFormat::Auto).expect("parse");
+
+        assert_eq!(
+            conversation.messages,
+            vec![
+                user("Why does cargo fail?"),
+                assistant("Checking."),
+                assistant("A missing import."),
+            ]
+        );
+        assert_eq!(conversation.cwd, Some(PathBuf::from("/repo")));
+        assert_eq!(
+            conversation
+                .started_at
+                .map(|t| t.to_rfc3339_opts(SecondsFormat::Secs, true)),
+            Some("2025-06-01T10:00:00Z".to_string())
+        );
+    }
+
+    #[test]
+    fn rejects_transcripts_without_user_messages() {
+        assert!(parse_transcript("[]", ImportFormat::OpenAiMessages).is_err());
+        assert!(parse_transcript("not json", ImportFormat::Auto).is_err());
+    }
+
+    #[tokio::test]
+    async fn written_rollouts_read_back_as_transcripts() {
+        let home = tempfile::tempdir().expect("tempdir");
+        let conversation = ImportedConversation {
+            messages: vec![user("Fix the build"), assistant("Done.")],
+            ..Default::default()
+        };
+
+        let (_, path) = write_imported_rollout(home.path(), &conversation, Path::new("/repo"))
+            .await
+            .expect("write rollout");
+        let transcript = load_transcript(&path).await.expect("load transcript");
+
+        assert!(path.starts_with(home.path().join(SESSIONS_SUBDIR)));
+        assert_eq!(transcript.turns.len(), 1);
+        assert_eq!(transcript.turns[0].prompt, "Fix the build");
+        assert_eq!(transcript.turns[0].responses, vec!["Done.".to_string()]);
+    }
+}
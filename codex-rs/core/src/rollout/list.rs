@@ -1258,6 +1258,26 @@ pub async fn find_archived_thread_path_by_id_str(
     find_thread_path_by_id_str_in_subdir(codex_home, ARCHIVED_SESSIONS_SUBDIR, id_str).await
 }
 
+/// Resolve a user-supplied session reference to a rollout file. The reference may be a path to a
+/// rollout file (relative to `cwd`), a thread id, or a thread name.
+pub async fn find_thread_path_by_reference(
+    codex_home: &Path,
+    cwd: &Path,
+    reference: &str,
+) -> io::Result<Option<PathBuf>> {
+    let candidate = cwd.join(reference);
+    if tokio::fs::metadata(&candidate)
+        .await
+        .is_ok_and(|metadata| metadata.is_file())
+    {
+        return Ok(Some(candidate));
+    }
+    if let Some(path) = find_thread_path_by_id_str(codex_home, reference).await? {
+        return Ok(Some(path));
+    }
+    super::session_index::find_thread_path_by_name_str(codex_home, reference).await
+}
+
 /// Extract the `YYYY/MM/DD` directory components from a rollout filename.
 pub fn rollout_date_parts(file_name: &OsStr) -> Option<(String, String, String)> {
     let name = file_name.to_string_lossy();
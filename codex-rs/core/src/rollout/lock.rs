@@ -0,0 +1,248 @@
+//! Advisory lock files that stop two Codex processes from appending to the same rollout.
+//!
+//! The lock for `rollout-….jsonl` lives next to it as `rollout-….jsonl.lock` and holds the pid
+//! of the owning process. It is an OS file lock, so a crashed process never leaves a session
+//! locked; the stale file is simply locked again by the next owner. Within one process the lock
+//! is shared, so resuming a thread that is already loaded keeps working.
+
+use std::collections::HashMap;
+use std::ffi::OsString;
+use std::fs::File;
+use std::fs::OpenOptions;
+use std::fs::TryLockError;
+use std::io::ErrorKind;
+use std::io::Read;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::LazyLock;
+use std::sync::Mutex;
+use std::sync::Weak;
+
+const LOCK_SUFFIX: &str = ".lock";
+
+/// Attempts made when the lock file is removed by its previous owner between our open and lock.
+const MAX_ACQUIRE_ATTEMPTS: usize = 3;
+
+/// Locks held by this process, keyed by lock file path.
+static HELD_LOCKS: LazyLock<Mutex<HashMap<PathBuf, Weak<HeldLock>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Returned (inside an [`std::io::Error`] of kind `WouldBlock`) when another process owns the
+/// rollout.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("{} is open in another Codex process{}", rollout_path.display(), holder_suffix(*holder_pid))]
+pub struct SessionLockedError {
+    pub rollout_path: PathBuf,
+    /// Pid recorded by the owner. Not readable on platforms with mandatory locks.
+    pub holder_pid: Option<u32>,
+}
+
+fn holder_suffix(holder_pid: Option<u32>) -> String {
+    holder_pid
+        .map(|pid| format!(" (pid {pid})"))
+        .unwrap_or_default()
+}
+
+/// Exclusive ownership of a rollout for appending. Released when the last clone is dropped.
+#[derive(Debug, Clone)]
+pub struct RolloutLock {
+    _held: Arc<HeldLock>,
+}
+
+#[derive(Debug)]
+struct HeldLock {
+    path: PathBuf,
+    _file: File,
+}
+
+impl Drop for HeldLock {
+    fn drop(&mut self) {
+        // Remove the file while still holding the lock so a waiting process never locks a
+        // file that is about to disappear without noticing (see `RolloutLock::acquire`).
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+impl RolloutLock {
+    /// Take the lock for `rollout_path`. Fails with a `WouldBlock` error wrapping
+    /// [`SessionLockedError`] when another process holds it.
+    pub fn acquire(rollout_path: &Path) -> std::io::Result<Self> {
+        let path = lock_path(rollout_path);
+        let mut held = HELD_LOCKS
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        if let Some(existing) = held.get(&path).and_then(Weak::upgrade) {
+            return Ok(Self { _held: existing });
+        }
+
+        for _ in 0..MAX_ACQUIRE_ATTEMPTS {
+            let mut file = OpenOptions::new()
+                .read(true)
+                .write(true)
+                .create(true)
+                .truncate(false)
+                .open(&path)?;
+            match file.try_lock() {
+                Ok(()) => {}
+                Err(TryLockError::WouldBlock) => {
+                    return Err(locked_error(rollout_path, read_pid(&mut file)));
+                }
+                Err(TryLockError::Error(err)) => return Err(err),
+            }
+            // The previous owner may have removed the file after we opened it, and a third
+            // process may already have created a new one in its place; locking the orphaned
+            // inode would not exclude anyone, so start over.
+            if !is_file_at(&file, &path) {
+                continue;
+            }
+            file.set_len(0)?;
+            write!(file, "{}", std::process::id())?;
+            file.flush()?;
+
+            let lock = Arc::new(HeldLock {
+                path: path.clone(),
+                _file: file,
+            });
+            held.retain(|_, lock| lock.strong_count() > 0);
+            held.insert(path, Arc::downgrade(&lock));
+            return Ok(Self { _held: lock });
+        }
+        Err(std::io::Error::other(format!(
+            "lock file {} kept disappearing while acquiring it",
+            path.display()
+        )))
+    }
+}
+
+/// Report whether another process currently holds the lock for `rollout_path`, without
+/// taking it.
+pub fn check_rollout_lock(rollout_path: &Path) -> Option<SessionLockedError> {
+    let path = lock_path(rollout_path);
+    let held = HELD_LOCKS
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    if held.get(&path).and_then(Weak::upgrade).is_some() {
+        return None;
+    }
+    let mut file = OpenOptions::new().read(true).open(&path).ok()?;
+    match file.try_lock() {
+        Err(TryLockError::WouldBlock) => Some(SessionLockedError {
+            rollout_path: rollout_path.to_path_buf(),
+            holder_pid: read_pid(&mut file),
+        }),
+        Ok(()) | Err(TryLockError::Error(_)) => None,
+    }
+}
+
+/// Recover the [`SessionLockedError`] carried by an error from [`RolloutLock::acquire`].
+pub fn session_locked_error(err: &std::io::Error) -> Option<&SessionLockedError> {
+    err.get_ref()
+        .and_then(|inner| inner.downcast_ref::<SessionLockedError>())
+}
+
+fn lock_path(rollout_path: &Path) -> PathBuf {
+    let mut path = OsString::from(rollout_path.as_os_str());
+    path.push(LOCK_SUFFIX);
+    PathBuf::from(path)
+}
+
+fn locked_error(rollout_path: &Path, holder_pid: Option<u32>) -> std::io::Error {
+    std::io::Error::new(
+        ErrorKind::WouldBlock,
+        SessionLockedError {
+            rollout_path: rollout_path.to_path_buf(),
+            holder_pid,
+        },
+    )
+}
+
+/// Whether `file` is still the file linked at `path`.
+#[cfg(unix)]
+fn is_file_at(file: &File, path: &Path) -> bool {
+    use std::os::unix::fs::MetadataExt;
+
+    match (file.metadata(), std::fs::metadata(path)) {
+        (Ok(held), Ok(linked)) => held.dev() == linked.dev() && held.ino() == linked.ino(),
+        _ => false,
+    }
+}
+
+/// Whether `file` is still the file linked at `path`. File ids are not exposed on stable Rust
+/// here, so this only notices removal, not replacement.
+#[cfg(not(unix))]
+fn is_file_at(_file: &File, path: &Path) -> bool {
+    path.exists()
+}
+
+fn read_pid(file: &mut File) -> Option<u32> {
+    let mut contents = String::new();
+    file.read_to_string(&mut contents).ok()?;
+    contents.trim().parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    #[test]
+    fn lock_is_shared_in_process_and_released_on_drop() {
+        let dir = TempDir::new().expect("temp dir");
+        let rollout = dir.path().join("rollout.jsonl");
+
+        let first = RolloutLock::acquire(&rollout).expect("first lock");
+        let second = RolloutLock::acquire(&rollout).expect("same process shares the lock");
+        assert_eq!(
+            std::fs::read_to_string(lock_path(&rollout)).expect("read lock file"),
+            std::process::id().to_string()
+        );
+        assert_eq!(check_rollout_lock(&rollout), None);
+
+        drop(first);
+        assert!(lock_path(&rollout).exists());
+        drop(second);
+        if cfg!(unix) {
+            assert!(!lock_path(&rollout).exists());
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn replaced_lock_file_is_not_the_held_file() {
+        let dir = TempDir::new().expect("temp dir");
+        let path = lock_path(&dir.path().join("rollout.jsonl"));
+        let orphaned = File::create(&path).expect("create lock file");
+        assert!(is_file_at(&orphaned, &path));
+
+        std::fs::remove_file(&path).expect("remove lock file");
+        assert!(!is_file_at(&orphaned, &path));
+        let _replacement = File::create(&path).expect("recreate lock file");
+        assert!(!is_file_at(&orphaned, &path));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn lock_held_through_another_handle_is_reported() {
+        let dir = TempDir::new().expect("temp dir");
+        let rollout = dir.path().join("rollout.jsonl");
+        // A separate open file description behaves like another process's lock.
+        let mut other = File::create(lock_path(&rollout)).expect("create lock file");
+        write!(other, "4242").expect("write pid");
+        other.try_lock().expect("lock from other handle");
+
+        let expected = SessionLockedError {
+            rollout_path: rollout.clone(),
+            holder_pid: Some(4242),
+        };
+        assert_eq!(check_rollout_lock(&rollout), Some(expected.clone()));
+        let err = RolloutLock::acquire(&rollout).expect_err("lock is held");
+        assert_eq!(err.kind(), ErrorKind::WouldBlock);
+        assert_eq!(session_locked_error(&err), Some(&expected));
+
+        drop(other);
+        RolloutLock::acquire(&rollout).expect("lock after release");
+    }
+}
@@ -7,20 +7,31 @@ pub const ARCHIVED_SESSIONS_SUBDIR: &str = "archived_sessions";
 pub const INTERACTIVE_SESSION_SOURCES: &[SessionSource] =
     &[SessionSource::Cli, SessionSource::VSCode];
 
+pub mod archive;
+pub mod compare;
+pub mod dataset;
 pub(crate) mod error;
+pub mod import;
 pub mod list;
+pub mod lock;
 pub(crate) mod metadata;
 pub(crate) mod policy;
 pub mod recorder;
 pub(crate) mod session_index;
+pub mod stats;
 pub(crate) mod truncation;
 
+pub use archive::archive_rollout;
+pub use archive::archive_rollout_with_state_db;
+pub use archive::unarchive_rollout;
+pub use archive::unarchive_rollout_with_state_db;
 pub use codex_protocol::protocol::SessionMeta;
 pub(crate) use error::map_session_init_error;
 pub use list::find_archived_thread_path_by_id_str;
 pub use list::find_thread_path_by_id_str;
 #[deprecated(note = "use find_thread_path_by_id_str")]
 pub use list::find_thread_path_by_id_str as find_conversation_path_by_id_str;
+pub use list::find_thread_path_by_reference;
 pub use list::rollout_date_parts;
 pub use recorder::RolloutRecorder;
 pub use recorder::RolloutRecorderParams;
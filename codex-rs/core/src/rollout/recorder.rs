@@ -3,6 +3,7 @@
 use std::fs::File;
 use std::fs::{self};
 use std::io::Error as IoError;
+use std::io::SeekFrom;
 use std::path::Path;
 use std::path::PathBuf;
 
@@ -15,6 +16,8 @@ use time::OffsetDateTime;
 use time::format_description::FormatItem;
 use time::format_description::well_known::Rfc3339;
 use time::macros::format_description;
+use tokio::io::AsyncReadExt;
+use tokio::io::AsyncSeekExt;
 use tokio::io::AsyncWriteExt;
 use tokio::sync::mpsc::Sender;
 use tokio::sync::mpsc::{self};
@@ -35,10 +38,12 @@ use super::list::get_threads;
 use super::list::get_threads_in_root;
 use super::list::parse_cursor;
 use super::list::parse_timestamp_uuid_from_filename;
+use super::lock::RolloutLock;
 use super::metadata;
 use super::policy::EventPersistenceMode;
 use super::policy::is_persisted_response_item;
 use crate::config::Config;
+use crate::config::types::RolloutFsyncPolicy;
 use crate::default_client::originator;
 use crate::git_info::collect_git_info;
 use crate::path_utils;
@@ -60,6 +65,10 @@ use codex_state::ThreadMetadataBuilder;
 /// Records all [`ResponseItem`]s for a session and flushes them to disk after
 /// every update.
 ///
+/// Each batch of items is appended with a single write that either lands whole or is rolled
+/// back, and the file is fsynced according to [`RolloutFsyncPolicy`]. A final line torn by a
+/// crash is repaired when the session is resumed.
+///
 /// Rollouts are recorded as JSONL and can be inspected with tools such as:
 ///
 /// ```ignore
@@ -365,14 +374,15 @@ impl RolloutRecorder {
     /// For newly created sessions, this precomputes path/metadata and defers
     /// file creation/open until an explicit `persist()` call.
     ///
-    /// For resumed sessions, this immediately opens the existing rollout file.
+    /// For resumed sessions, this immediately locks and opens the existing rollout file, failing
+    /// if another Codex process already has it open (see [`super::lock`]).
     pub async fn new(
         config: &Config,
         params: RolloutRecorderParams,
         state_db_ctx: Option<StateDbHandle>,
         state_builder: Option<ThreadMetadataBuilder>,
     ) -> std::io::Result<Self> {
-        let (file, deferred_log_file_info, rollout_path, meta, event_persistence_mode) =
+        let (writer, deferred_log_file_info, rollout_path, meta, event_persistence_mode) =
             match params {
                 RolloutRecorderParams::Create {
                     conversation_id,
@@ -427,18 +437,26 @@ impl RolloutRecorder {
                 RolloutRecorderParams::Resume {
                     path,
                     event_persistence_mode,
-                } => (
-                    Some(
-                        tokio::fs::OpenOptions::new()
-                            .append(true)
-                            .open(&path)
-                            .await?,
-                    ),
-                    None,
-                    path,
-                    None,
-                    event_persistence_mode,
-                ),
+                } => {
+                    // Lock before repairing so we never truncate a line another process is
+                    // still writing.
+                    let lock = RolloutLock::acquire(&path)?;
+                    repair_torn_tail(&path).await?;
+                    (
+                        Some(JsonlWriter {
+                            file: tokio::fs::OpenOptions::new()
+                                .append(true)
+                                .open(&path)
+                                .await?,
+                            fsync: config.rollout_fsync,
+                            _lock: lock,
+                        }),
+                        None,
+                        path,
+                        None,
+                        event_persistence_mode,
+                    )
+                }
             };
 
         // Clone the cwd for the spawned task to collect git info asynchronously
@@ -453,7 +471,7 @@ impl RolloutRecorder {
         // writes. Using `tokio::fs::File` keeps everything on the async I/O
         // driver instead of blocking the runtime.
         tokio::task::spawn(rollout_writer(
-            file,
+            writer,
             deferred_log_file_info,
             rx,
             meta,
@@ -463,6 +481,7 @@ impl RolloutRecorder {
             state_builder,
             config.model_provider_id.clone(),
             config.memories.generate_memories,
+            config.rollout_fsync,
         ));
 
         Ok(Self {
@@ -689,6 +708,56 @@ fn precompute_log_file_info(
     })
 }
 
+/// Bytes read per step while scanning backwards for the start of a rollout's final line.
+const TAIL_SCAN_CHUNK_BYTES: u64 = 8 * 1024;
+
+/// Make sure a resumed rollout ends on a line boundary before anything is appended to it.
+///
+/// A crash mid-write can leave a partial final line; appending after it would glue the next
+/// record onto the fragment and lose both. A tail that is still valid JSON only lost its newline
+/// and is kept; anything else is cut off. Only the final line is read, scanning back from the
+/// end, so long rollouts are not loaded just to check their last byte.
+async fn repair_torn_tail(path: &Path) -> std::io::Result<()> {
+    let mut file = tokio::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(path)
+        .await?;
+    let mut line_start = file.metadata().await?.len();
+    let mut tail = Vec::new();
+    while line_start > 0 {
+        let chunk_start = line_start.saturating_sub(TAIL_SCAN_CHUNK_BYTES);
+        let mut chunk = vec![0; (line_start - chunk_start) as usize];
+        file.seek(SeekFrom::Start(chunk_start)).await?;
+        file.read_exact(&mut chunk).await?;
+        let newline = chunk.iter().rposition(|byte| *byte == b'\n');
+        let keep_from = newline.map_or(0, |newline| newline + 1);
+        chunk.drain(..keep_from);
+        chunk.append(&mut tail);
+        tail = chunk;
+        line_start = chunk_start + keep_from as u64;
+        if newline.is_some() {
+            break;
+        }
+    }
+    if tail.is_empty() {
+        return Ok(());
+    }
+    if serde_json::from_slice::<Value>(&tail).is_ok() {
+        file.seek(SeekFrom::End(0)).await?;
+        file.write_all(b"\n").await?;
+        file.flush().await?;
+    } else {
+        warn!(
+            "dropping {} bytes of a torn final line from {}",
+            tail.len(),
+            path.display()
+        );
+        file.set_len(line_start).await?;
+    }
+    file.sync_all().await
+}
+
 fn open_log_file(path: &Path) -> std::io::Result<File> {
     let Some(parent) = path.parent() else {
         return Err(IoError::other(format!(
@@ -705,7 +774,7 @@ fn open_log_file(path: &Path) -> std::io::Result<File> {
 
 #[allow(clippy::too_many_arguments)]
 async fn rollout_writer(
-    file: Option<tokio::fs::File>,
+    mut writer: Option<JsonlWriter>,
     mut deferred_log_file_info: Option<LogFileInfo>,
     mut rx: mpsc::Receiver<RolloutCmd>,
     mut meta: Option<SessionMeta>,
@@ -715,8 +784,8 @@ async fn rollout_writer(
     mut state_builder: Option<ThreadMetadataBuilder>,
     default_provider: String,
     generate_memories: bool,
+    fsync: RolloutFsyncPolicy,
 ) -> std::io::Result<()> {
-    let mut writer = file.map(|file| JsonlWriter { file });
     let mut buffered_items = Vec::<RolloutItem>::new();
     if let Some(builder) = state_builder.as_mut() {
         builder.rollout_path = rollout_path.clone();
@@ -776,8 +845,11 @@ async fn rollout_writer(
                             ));
                         };
                         let file = open_log_file(log_file_info.path.as_path())?;
+                        let lock = RolloutLock::acquire(log_file_info.path.as_path())?;
                         writer = Some(JsonlWriter {
                             file: tokio::fs::File::from_std(file),
+                            fsync,
+                            _lock: lock,
                         });
 
                         if let Some(session_meta) = meta.take() {
@@ -818,19 +890,16 @@ async fn rollout_writer(
                 }
                 let _ = ack.send(());
             }
-            RolloutCmd::Flush { ack } => {
+            RolloutCmd::Flush { ack } | RolloutCmd::Shutdown { ack } => {
                 // Deferred fresh threads may not have an initialized file yet.
                 if let Some(writer) = writer.as_mut()
-                    && let Err(e) = writer.file.flush().await
+                    && let Err(e) = writer.sync().await
                 {
                     let _ = ack.send(());
                     return Err(e);
                 }
                 let _ = ack.send(());
             }
-            RolloutCmd::Shutdown { ack } => {
-                let _ = ack.send(());
-            }
         }
     }
 
@@ -859,7 +928,9 @@ async fn write_session_meta(
 
     let rollout_item = RolloutItem::SessionMeta(session_meta_line);
     if let Some(writer) = writer.as_mut() {
-        writer.write_rollout_item(&rollout_item).await?;
+        writer
+            .write_rollout_items(std::slice::from_ref(&rollout_item))
+            .await?;
     }
     state_db::reconcile_rollout(
         state_db_ctx,
@@ -883,9 +954,7 @@ async fn write_and_reconcile_items(
     default_provider: &str,
 ) -> std::io::Result<()> {
     if let Some(writer) = writer.as_mut() {
-        for item in items {
-            writer.write_rollout_item(item).await?;
-        }
+        writer.write_rollout_items(items).await?;
     }
     if let Some(builder) = state_builder.as_mut() {
         builder.rollout_path = rollout_path.to_path_buf();
@@ -905,6 +974,9 @@ async fn write_and_reconcile_items(
 
 struct JsonlWriter {
     file: tokio::fs::File,
+    fsync: RolloutFsyncPolicy,
+    /// Held for as long as this process may append to the rollout.
+    _lock: RolloutLock,
 }
 
 #[derive(serde::Serialize)]
@@ -915,7 +987,9 @@ struct RolloutLineRef<'a> {
 }
 
 impl JsonlWriter {
-    async fn write_rollout_item(&mut self, rollout_item: &RolloutItem) -> std::io::Result<()> {
+    /// Append one line per item in a single write. If the write fails partway, the file is cut
+    /// back to where it was so it never ends in a partial line.
+    async fn write_rollout_items(&mut self, items: &[RolloutItem]) -> std::io::Result<()> {
         let timestamp_format: &[FormatItem] = format_description!(
             "[year]-[month]-[day]T[hour]:[minute]:[second].[subsecond digits:3]Z"
         );
@@ -923,17 +997,40 @@ impl JsonlWriter {
             .format(timestamp_format)
             .map_err(|e| IoError::other(format!("failed to format timestamp: {e}")))?;
 
-        let line = RolloutLineRef {
-            timestamp,
-            item: rollout_item,
-        };
-        self.write_line(&line).await
+        let mut buf = Vec::new();
+        for item in items {
+            let line = RolloutLineRef {
+                timestamp: timestamp.clone(),
+                item,
+            };
+            serde_json::to_writer(&mut buf, &line)?;
+            buf.push(b'\n');
+        }
+
+        let len_before = self.file.metadata().await?.len();
+        let written = async {
+            self.file.write_all(&buf).await?;
+            self.file.flush().await
+        }
+        .await;
+        if let Err(err) = written {
+            if let Err(truncate_err) = self.file.set_len(len_before).await {
+                warn!("failed to roll back a partial rollout write: {truncate_err}");
+            }
+            return Err(err);
+        }
+        if self.fsync == RolloutFsyncPolicy::Always {
+            self.file.sync_data().await?;
+        }
+        Ok(())
     }
-    async fn write_line(&mut self, item: &impl serde::Serialize) -> std::io::Result<()> {
-        let mut json = serde_json::to_string(item)?;
-        json.push('\n');
-        self.file.write_all(json.as_bytes()).await?;
+
+    /// Push buffered writes to the OS and, unless the policy is `never`, to disk.
+    async fn sync(&mut self) -> std::io::Result<()> {
         self.file.flush().await?;
+        if self.fsync != RolloutFsyncPolicy::Never {
+            self.file.sync_data().await?;
+        }
         Ok(())
     }
 }
@@ -1114,6 +1211,89 @@ mod tests {
         Ok(path)
     }
 
+    #[tokio::test]
+    async fn resume_drops_torn_final_line_before_appending() -> std::io::Result<()> {
+        let home = TempDir::new().expect("temp dir");
+        let config = ConfigBuilder::default()
+            .codex_home(home.path().to_path_buf())
+            .build()
+            .await?;
+        let path = write_session_file(home.path(), "2025-01-03T12-00-00", Uuid::new_v4())?;
+        let mut file = fs::OpenOptions::new().append(true).open(&path)?;
+        file.write_all(
+            br#"{"timestamp":"2025-01-03T12:00:01Z","type":"event_msg","payload":{"ty"#,
+        )?;
+        drop(file);
+
+        let recorder = RolloutRecorder::new(
+            &config,
+            RolloutRecorderParams::resume(path.clone(), EventPersistenceMode::Limited),
+            None,
+            None,
+        )
+        .await?;
+        recorder
+            .record_items(&[RolloutItem::EventMsg(EventMsg::AgentMessage(
+                AgentMessageEvent {
+                    message: "after-resume".to_string(),
+                    phase: None,
+                },
+            ))])
+            .await?;
+        recorder.flush().await?;
+
+        let (items, _, parse_errors) = RolloutRecorder::load_rollout_items(&path).await?;
+        assert_eq!(parse_errors, 0);
+        assert_eq!(items.len(), 3);
+        assert!(matches!(
+            items.last(),
+            Some(RolloutItem::EventMsg(EventMsg::AgentMessage(event)))
+                if event.message == "after-resume"
+        ));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn repair_keeps_a_complete_final_line_missing_its_newline() -> std::io::Result<()> {
+        let dir = TempDir::new().expect("temp dir");
+        let path = dir.path().join("rollout.jsonl");
+        fs::write(&path, "{\"a\":1}\n{\"b\":2}")?;
+
+        repair_torn_tail(&path).await?;
+
+        assert_eq!(fs::read_to_string(&path)?, "{\"a\":1}\n{\"b\":2}\n");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn repair_cuts_a_torn_final_line_longer_than_a_scan_chunk() -> std::io::Result<()> {
+        let dir = TempDir::new().expect("temp dir");
+        let path = dir.path().join("rollout.jsonl");
+        let head = "{\"a\":1}\n";
+        let torn = format!(
+            "{{\"b\":\"{}",
+            "x".repeat(TAIL_SCAN_CHUNK_BYTES as usize * 2)
+        );
+        fs::write(&path, format!("{head}{torn}"))?;
+
+        repair_torn_tail(&path).await?;
+
+        assert_eq!(fs::read_to_string(&path)?, head);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn repair_leaves_a_rollout_ending_in_a_newline_alone() -> std::io::Result<()> {
+        let dir = TempDir::new().expect("temp dir");
+        let path = dir.path().join("rollout.jsonl");
+        fs::write(&path, "{\"a\":1}\n")?;
+
+        repair_torn_tail(&path).await?;
+
+        assert_eq!(fs::read_to_string(&path)?, "{\"a\":1}\n");
+        Ok(())
+    }
+
     #[tokio::test]
     async fn recorder_materializes_only_after_explicit_persist() -> std::io::Result<()> {
         let home = TempDir::new().expect("temp dir");
@@ -1420,6 +1600,7 @@ mod tests {
                 developer_instructions: None,
                 final_output_json_schema: None,
                 truncation_policy: None,
+                sampling: None,
             }),
         };
         writeln!(file, "{}", serde_json::to_string(&turn_context)?)?;
@@ -0,0 +1,327 @@
+//! Aggregate statistics for a single rollout file.
+//!
+//! Everything is derived from what the rollout already records: user turns from
+//! `UserMessage` events, token usage from `TokenCount` events, tool calls from
+//! persisted response items, and exec outcomes from `ExecCommandEnd` events or,
+//! when those were not persisted, from the exit code reported in the tool output.
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use chrono::DateTime;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::RolloutItem;
+use codex_protocol::protocol::RolloutLine;
+use codex_protocol::protocol::TokenUsage;
+use serde::Serialize;
+
+const EXEC_TOOL_NAMES: &[&str] = &[
+    "shell",
+    "shell_command",
+    "exec_command",
+    "container.exec",
+    "local_shell",
+];
+
+/// USD list prices per million tokens: (input, cached input, output), as published on
+/// [`PRICES_AS_OF`]. Nothing updates these, so costs computed from them are only estimates.
+const MODEL_PRICES: &[(&str, (f64, f64, f64))] = &[
+    ("gpt-5.2", (1.75, 0.175, 14.0)),
+    ("gpt-5.1-codex-mini", (0.25, 0.025, 2.0)),
+    ("gpt-5.1", (1.25, 0.125, 10.0)),
+    ("gpt-5-codex-mini", (0.25, 0.025, 2.0)),
+    ("gpt-5-mini", (0.25, 0.025, 2.0)),
+    ("gpt-5-nano", (0.05, 0.005, 0.4)),
+    ("gpt-5", (1.25, 0.125, 10.0)),
+    ("gpt-4.1-mini", (0.4, 0.1, 1.6)),
+    ("gpt-4.1", (2.0, 0.5, 8.0)),
+    ("o4-mini", (1.1, 0.275, 4.4)),
+    ("o3", (2.0, 0.5, 8.0)),
+];
+
+/// When `MODEL_PRICES` was last checked against the published API prices.
+pub const PRICES_AS_OF: &str = "2025-12";
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SessionStats {
+    pub path: PathBuf,
+    /// Model recorded by the most recent turn context.
+    pub model: Option<String>,
+    pub turns: usize,
+    pub token_usage: TokenUsage,
+    pub tool_calls: BTreeMap<String, usize>,
+    pub exec_succeeded: usize,
+    pub exec_failed: usize,
+    /// Time between the first and last rollout lines.
+    pub wall_time: Option<Duration>,
+    /// `None` when tokens were spent on a model without a known price.
+    pub estimated_cost_usd: Option<f64>,
+}
+
+/// `estimated_cost_usd` for display, marked as an estimate from dated list prices.
+pub fn describe_estimated_cost(estimated_cost_usd: Option<f64>) -> String {
+    match estimated_cost_usd {
+        Some(cost) => format!("~${cost:.2} (list prices as of {PRICES_AS_OF})"),
+        None => "unknown (model price not known)".to_string(),
+    }
+}
+
+/// Read a rollout file and compute its statistics.
+pub async fn load_session_stats(path: &Path) -> io::Result<SessionStats> {
+    let text = tokio::fs::read_to_string(path).await?;
+    let lines: Vec<RolloutLine> = text
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect();
+    Ok(stats_from_lines(path.to_path_buf(), &lines))
+}
+
+pub(crate) fn stats_from_lines(path: PathBuf, lines: &[RolloutLine]) -> SessionStats {
+    let mut model: Option<String> = None;
+    let mut turns = 0;
+    let mut token_usage = TokenUsage::default();
+    let mut usage_by_model: BTreeMap<String, TokenUsage> = BTreeMap::new();
+    let mut tool_calls: BTreeMap<String, usize> = BTreeMap::new();
+    let mut exec_call_ids: Vec<String> = Vec::new();
+    let mut exit_codes: HashMap<String, i32> = HashMap::new();
+
+    for line in lines {
+        match &line.item {
+            RolloutItem::TurnContext(ctx) => model = Some(ctx.model.clone()),
+            RolloutItem::EventMsg(EventMsg::UserMessage(_)) => turns += 1,
+            RolloutItem::EventMsg(EventMsg::TokenCount(event)) => {
+                let Some(info) = &event.info else {
+                    continue;
+                };
+                let total = &info.total_token_usage;
+                let delta = TokenUsage {
+                    input_tokens: (total.input_tokens - token_usage.input_tokens).max(0),
+                    cached_input_tokens: (total.cached_input_tokens
+                        - token_usage.cached_input_tokens)
+                        .max(0),
+                    output_tokens: (total.output_tokens - token_usage.output_tokens).max(0),
+                    reasoning_output_tokens: (total.reasoning_output_tokens
+                        - token_usage.reasoning_output_tokens)
+                        .max(0),
+                    total_tokens: (total.total_tokens - token_usage.total_tokens).max(0),
+                };
+                usage_by_model
+                    .entry(model.clone().unwrap_or_default())
+                    .or_default()
+                    .add_assign(&delta);
+                token_usage = total.clone();
+            }
+            RolloutItem::EventMsg(EventMsg::ExecCommandEnd(event)) => {
+                exit_codes.insert(event.call_id.clone(), event.exit_code);
+            }
+            RolloutItem::ResponseItem(item) => {
+                let (name, call_id) = match item {
+                    ResponseItem::FunctionCall { name, call_id, .. }
+                    | ResponseItem::CustomToolCall { name, call_id, .. } => {
+                        (name.as_str(), Some(call_id))
+                    }
+                    ResponseItem::LocalShellCall { call_id, .. } => {
+                        ("local_shell", call_id.as_ref())
+                    }
+                    ResponseItem::WebSearchCall { .. } => ("web_search", None),
+                    ResponseItem::ImageGenerationCall { .. } => ("image_generation", None),
+                    ResponseItem::FunctionCallOutput { call_id, output } => {
+                        if exec_call_ids.contains(call_id)
+                            && let Some(text) = output.text_content()
+                            && let Some(exit_code) = parse_exit_code(text)
+                        {
+                            exit_codes.entry(call_id.clone()).or_insert(exit_code);
+                        }
+                        continue;
+                    }
+                    ResponseItem::Message { .. }
+                    | ResponseItem::Reasoning { .. }
+                    | ResponseItem::CustomToolCallOutput { .. }
+                    | ResponseItem::GhostSnapshot { .. }
+                    | ResponseItem::Compaction { .. }
+                    | ResponseItem::Other => continue,
+                };
+                *tool_calls.entry(name.to_string()).or_default() += 1;
+                if let Some(call_id) = call_id
+                    && EXEC_TOOL_NAMES.contains(&name)
+                {
+                    exec_call_ids.push(call_id.clone());
+                }
+            }
+            RolloutItem::SessionMeta(_) | RolloutItem::Compacted(_) | RolloutItem::EventMsg(_) => {}
+        }
+    }
+
+    let exec_succeeded = exit_codes.values().filter(|code| **code == 0).count();
+    let exec_failed = exit_codes.len() - exec_succeeded;
+
+    let timestamps: Vec<_> = lines
+        .iter()
+        .filter_map(|line| DateTime::parse_from_rfc3339(&line.timestamp).ok())
+        .collect();
+    let wall_time = match (timestamps.first(), timestamps.last()) {
+        (Some(first), Some(last)) => (*last - *first).to_std().ok(),
+        _ => None,
+    };
+
+    let estimated_cost_usd = usage_by_model
+        .iter()
+        .filter(|(_, usage)| !usage.is_zero())
+        .map(|(model, usage)| estimate_cost_usd(model, usage))
+        .sum();
+
+    SessionStats {
+        path,
+        model,
+        turns,
+        token_usage,
+        tool_calls,
+        exec_succeeded,
+        exec_failed,
+        wall_time,
+        estimated_cost_usd,
+    }
+}
+
+/// Estimated USD cost of `usage` on `model`, or `None` when the model's price is not known.
+pub fn estimate_cost_usd(model: &str, usage: &TokenUsage) -> Option<f64> {
+    let (_, (input, cached, output)) = MODEL_PRICES
+        .iter()
+        .find(|(prefix, _)| model.starts_with(prefix))?;
+    Some(
+        (usage.non_cached_input() as f64 * input
+            + usage.cached_input() as f64 * cached
+            + usage.output_tokens as f64 * output)
+            / 1_000_000.0,
+    )
+}
+
+fn parse_exit_code(output: &str) -> Option<i32> {
+    if let Ok(value) = serde_json::from_str::<serde_json::Value>(output) {
+        return value
+            .pointer("/metadata/exit_code")
+            .and_then(serde_json::Value::as_i64)
+            .and_then(|code| i32::try_from(code).ok());
+    }
+    output.lines().find_map(|line| {
+        line.strip_prefix("Exit code: ")
+            .or_else(|| line.strip_prefix("Process exited with code "))
+            .and_then(|code| code.trim().parse().ok())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::models::FunctionCallOutputPayload;
+    use codex_protocol::protocol::TokenCountEvent;
+    use codex_protocol::protocol::TokenUsageInfo;
+    use codex_protocol::protocol::UserMessageEvent;
+    use pretty_assertions::assert_eq;
+
+    fn line(timestamp: &str, item: RolloutItem) -> RolloutLine {
+        RolloutLine {
+            timestamp: timestamp.to_string(),
+            item,
+        }
+    }
+
+    fn user(timestamp: &str) -> RolloutLine {
+        line(
+            timestamp,
+            RolloutItem::EventMsg(EventMsg::UserMessage(UserMessageEvent {
+                message: "hi".to_string(),
+                images: None,
+                local_images: Vec::new(),
+                text_elements: Vec::new(),
+            })),
+        )
+    }
+
+    fn shell_call(call_id: &str) -> RolloutLine {
+        line(
+            "2025-01-01T00:00:01Z",
+            RolloutItem::ResponseItem(ResponseItem::FunctionCall {
+                id: None,
+                name: "shell_command".to_string(),
+                arguments: "{}".to_string(),
+                call_id: call_id.to_string(),
+            }),
+        )
+    }
+
+    fn shell_output(call_id: &str, text: &str) -> RolloutLine {
+        line(
+            "2025-01-01T00:00:02Z",
+            RolloutItem::ResponseItem(ResponseItem::FunctionCallOutput {
+                call_id: call_id.to_string(),
+                output: FunctionCallOutputPayload::from_text(text.to_string()),
+            }),
+        )
+    }
+
+    #[test]
+    fn stats_count_turns_tools_exec_outcomes_and_tokens() {
+        let usage = TokenUsage {
+            input_tokens: 1_000_000,
+            cached_input_tokens: 0,
+            output_tokens: 100_000,
+            reasoning_output_tokens: 0,
+            total_tokens: 1_100_000,
+        };
+        let lines = vec![
+            user("2025-01-01T00:00:00Z"),
+            shell_call("call-1"),
+            shell_output(
+                "call-1",
+                "Exit code: 0\nWall time: 0.1 seconds\nOutput:\nok",
+            ),
+            shell_call("call-2"),
+            shell_output("call-2", "Process exited with code 2\nOutput:\nboom"),
+            user("2025-01-01T00:00:03Z"),
+            line(
+                "2025-01-01T00:01:30Z",
+                RolloutItem::EventMsg(EventMsg::TokenCount(TokenCountEvent {
+                    info: Some(TokenUsageInfo {
+                        total_token_usage: usage.clone(),
+                        last_token_usage: usage.clone(),
+                        model_context_window: None,
+                    }),
+                    rate_limits: None,
+                })),
+            ),
+        ];
+
+        let stats = stats_from_lines(PathBuf::from("rollout.jsonl"), &lines);
+
+        assert_eq!(
+            stats,
+            SessionStats {
+                path: PathBuf::from("rollout.jsonl"),
+                model: None,
+                turns: 2,
+                token_usage: usage,
+                tool_calls: BTreeMap::from([("shell_command".to_string(), 2)]),
+                exec_succeeded: 1,
+                exec_failed: 1,
+                wall_time: Some(Duration::from_secs(90)),
+                estimated_cost_usd: None,
+            }
+        );
+    }
+
+    #[test]
+    fn parse_exit_code_reads_json_and_text_formats() {
+        assert_eq!(
+            parse_exit_code(r#"{"output":"","metadata":{"exit_code":3,"duration_seconds":0.1}}"#),
+            Some(3)
+        );
+        assert_eq!(parse_exit_code("Exit code: 0\nOutput:\n"), Some(0));
+        assert_eq!(parse_exit_code("no exit code here"), None);
+    }
+}
@@ -0,0 +1,664 @@
+//! Opt-in embedding index for semantic code search.
+//!
+//! When `features.semantic_search` is enabled, a background task walks the
+//! session's workspace, splits text files into line-based chunks, embeds each
+//! chunk, and persists the vectors under `$CODEX_HOME/semantic_index/`. The
+//! `semantic_search` tool and the TUI `@?` picker mode both query that store.
+//!
+//! Files are re-embedded only when their size or modification time changes, so
+//! refreshing an existing index on startup is cheap.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::UNIX_EPOCH;
+
+use anyhow::Context;
+use anyhow::Result;
+use codex_protocol::protocol::SessionSource;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::json;
+use sha1::Digest;
+use sha1::Sha1;
+use tracing::info;
+use tracing::warn;
+
+use crate::config::Config;
+use crate::config::types::SemanticSearchConfig;
+use crate::config::types::SemanticSearchEmbedder;
+use crate::features::Feature;
+
+pub const SEMANTIC_INDEX_SUBDIR: &str = "semantic_index";
+pub const DEFAULT_RESULT_LIMIT: usize = 8;
+
+const INDEX_VERSION: u32 = 1;
+const CHUNK_LINES: usize = 40;
+const SNIPPET_LINES: usize = 12;
+const LOCAL_DIMENSIONS: usize = 256;
+const API_BATCH_SIZE: usize = 64;
+const BINARY_SNIFF_BYTES: usize = 8 * 1024;
+
+/// A single chunk returned from [`search`].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SemanticMatch {
+    /// Path relative to the indexed root.
+    pub path: PathBuf,
+    pub start_line: usize,
+    pub end_line: usize,
+    pub score: f32,
+    pub snippet: String,
+}
+
+/// Counts reported after [`refresh_index`] completes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct IndexStats {
+    pub files: usize,
+    pub reused: usize,
+    pub embedded: usize,
+    pub chunks: usize,
+    /// Files left out because embedding them failed; they are retried on the next refresh.
+    pub failed: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct SemanticIndex {
+    version: u32,
+    embedder: String,
+    files: BTreeMap<PathBuf, IndexedFile>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct IndexedFile {
+    modified_secs: u64,
+    len: u64,
+    chunks: Vec<IndexedChunk>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct IndexedChunk {
+    start_line: usize,
+    end_line: usize,
+    embedding: Vec<f32>,
+}
+
+enum Embedder {
+    Local,
+    Api {
+        client: reqwest::Client,
+        url: String,
+        model: String,
+        api_key: String,
+    },
+}
+
+#[derive(Deserialize)]
+struct EmbeddingsResponse {
+    data: Vec<EmbeddingDatum>,
+}
+
+#[derive(Deserialize)]
+struct EmbeddingDatum {
+    index: usize,
+    embedding: Vec<f32>,
+}
+
+impl Embedder {
+    fn from_config(config: &SemanticSearchConfig) -> Result<Self> {
+        match config.embedder {
+            SemanticSearchEmbedder::Local => Ok(Self::Local),
+            SemanticSearchEmbedder::Api => {
+                let api_key = std::env::var(&config.api_key_env).with_context(|| {
+                    format!(
+                        "semantic_search.embedder = \"api\" requires `{}` to be set",
+                        config.api_key_env
+                    )
+                })?;
+                Ok(Self::Api {
+                    client: crate::default_client::build_reqwest_client(),
+                    url: format!("{}/embeddings", config.base_url.trim_end_matches('/')),
+                    model: config.model.clone(),
+                    api_key,
+                })
+            }
+        }
+    }
+
+    /// Identifier persisted alongside the vectors; an index built by a
+    /// different embedder is discarded rather than mixed.
+    fn id(&self) -> String {
+        match self {
+            Self::Local => format!("local-{LOCAL_DIMENSIONS}"),
+            Self::Api { model, .. } => format!("api-{model}"),
+        }
+    }
+
+    async fn embed(&self, inputs: &[String]) -> Result<Vec<Vec<f32>>> {
+        match self {
+            Self::Local => Ok(inputs
+                .iter()
+                .map(String::as_str)
+                .map(local_embedding)
+                .collect()),
+            Self::Api {
+                client,
+                url,
+                model,
+                api_key,
+            } => {
+                let mut embeddings = Vec::with_capacity(inputs.len());
+                for batch in inputs.chunks(API_BATCH_SIZE) {
+                    let response = client
+                        .post(url.as_str())
+                        .bearer_auth(api_key)
+                        .json(&json!({ "model": model, "input": batch }))
+                        .send()
+                        .await
+                        .context("embeddings request failed")?
+                        .error_for_status()
+                        .context("embeddings request returned an error status")?
+                        .json::<EmbeddingsResponse>()
+                        .await
+                        .context("failed to parse embeddings response")?;
+                    let mut data = response.data;
+                    data.sort_by_key(|datum| datum.index);
+                    if data.len() != batch.len() {
+                        anyhow::bail!(
+                            "embeddings response returned {} vectors for {} inputs",
+                            data.len(),
+                            batch.len()
+                        );
+                    }
+                    embeddings.extend(data.into_iter().map(|datum| normalize(datum.embedding)));
+                }
+                Ok(embeddings)
+            }
+        }
+    }
+}
+
+/// Starts the background indexer for the session's workspace when the
+/// `semantic_search` feature is enabled.
+pub(crate) fn start_semantic_index_task(config: Arc<Config>, source: &SessionSource) {
+    if !config.features.enabled(Feature::SemanticSearch)
+        || matches!(source, SessionSource::SubAgent(_))
+    {
+        return;
+    }
+
+    tokio::spawn(async move {
+        let root = index_root(&config);
+        match refresh_index(root, &config.codex_home, &config.semantic_search).await {
+            Ok(stats) => info!(
+                "semantic index refreshed for {}: {} files ({} reused, {} embedded, {} failed), {} chunks",
+                root.display(),
+                stats.files,
+                stats.reused,
+                stats.embedded,
+                stats.failed,
+                stats.chunks
+            ),
+            Err(err) => warn!(
+                "failed to refresh semantic index for {}: {err:#}",
+                root.display()
+            ),
+        }
+    });
+}
+
+/// The workspace a session indexes and searches: the directory it started in, even after a
+/// turn moves to another cwd, so the `semantic_search` tool always finds the index built at
+/// startup.
+pub(crate) fn index_root(config: &Config) -> &Path {
+    &config.cwd
+}
+
+/// Location of the persisted index for `root`.
+pub fn index_path(codex_home: &Path, root: &Path) -> PathBuf {
+    let mut hasher = Sha1::new();
+    hasher.update(root.to_string_lossy().as_bytes());
+    codex_home
+        .join(SEMANTIC_INDEX_SUBDIR)
+        .join(format!("{:x}.json", hasher.finalize()))
+}
+
+/// Walks `root`, embeds new or changed files, and persists the index. A file whose embedding
+/// fails is left out of the index and counted in [`IndexStats::failed`]; the rest is still saved.
+pub async fn refresh_index(
+    root: &Path,
+    codex_home: &Path,
+    config: &SemanticSearchConfig,
+) -> Result<IndexStats> {
+    let embedder = Embedder::from_config(config)?;
+    let path = index_path(codex_home, root);
+    let previous = load_index(&path)
+        .await
+        .filter(|index| index.version == INDEX_VERSION && index.embedder == embedder.id());
+    let mut previous_files = previous.map(|index| index.files).unwrap_or_default();
+
+    let walk_root = root.to_path_buf();
+    let max_files = config.max_files;
+    let max_file_bytes = config.max_file_bytes as u64;
+    let candidates = tokio::task::spawn_blocking(move || {
+        collect_candidates(&walk_root, max_files, max_file_bytes)
+    })
+    .await
+    .context("semantic index walker panicked")?;
+
+    let mut stats = IndexStats::default();
+    let mut files = BTreeMap::new();
+    for candidate in candidates {
+        if let Some(existing) = previous_files.remove(&candidate.relative_path)
+            && existing.modified_secs == candidate.modified_secs
+            && existing.len == candidate.len
+        {
+            stats.reused += 1;
+            stats.chunks += existing.chunks.len();
+            files.insert(candidate.relative_path, existing);
+            continue;
+        }
+
+        let Ok(contents) = tokio::fs::read(root.join(&candidate.relative_path)).await else {
+            continue;
+        };
+        if contents
+            .iter()
+            .take(BINARY_SNIFF_BYTES)
+            .any(|byte| *byte == 0)
+        {
+            continue;
+        }
+        let Ok(text) = String::from_utf8(contents) else {
+            continue;
+        };
+        let chunks = chunk_lines(&text);
+        if chunks.is_empty() {
+            continue;
+        }
+        let inputs = chunks
+            .iter()
+            .map(|(_, _, body)| format!("{}\n{body}", candidate.relative_path.display()))
+            .collect::<Vec<_>>();
+        let embeddings = match embedder.embed(&inputs).await {
+            Ok(embeddings) => embeddings,
+            Err(err) => {
+                warn!(
+                    "failed to embed {} for the semantic index: {err:#}",
+                    candidate.relative_path.display()
+                );
+                stats.failed += 1;
+                continue;
+            }
+        };
+        stats.embedded += 1;
+        stats.chunks += chunks.len();
+        files.insert(
+            candidate.relative_path,
+            IndexedFile {
+                modified_secs: candidate.modified_secs,
+                len: candidate.len,
+                chunks: chunks
+                    .into_iter()
+                    .zip(embeddings)
+                    .map(|((start_line, end_line, _), embedding)| IndexedChunk {
+                        start_line,
+                        end_line,
+                        embedding,
+                    })
+                    .collect(),
+            },
+        );
+    }
+    stats.files = files.len();
+
+    let index = SemanticIndex {
+        version: INDEX_VERSION,
+        embedder: embedder.id(),
+        files,
+    };
+    write_index(&path, &index).await?;
+    Ok(stats)
+}
+
+/// Returns the chunks most similar to `query`, best first, at most one per file.
+pub async fn search(
+    root: &Path,
+    codex_home: &Path,
+    config: &SemanticSearchConfig,
+    query: &str,
+    limit: usize,
+) -> Result<Vec<SemanticMatch>> {
+    let embedder = Embedder::from_config(config)?;
+    let path = index_path(codex_home, root);
+    let Some(index) = load_index(&path).await else {
+        anyhow::bail!(
+            "semantic index for {} has not been built yet",
+            root.display()
+        );
+    };
+    if index.embedder != embedder.id() {
+        anyhow::bail!(
+            "semantic index was built with `{}`; it will be rebuilt on the next session start",
+            index.embedder
+        );
+    }
+
+    let Some(query_embedding) = embedder.embed(&[query.to_string()]).await?.pop() else {
+        return Ok(Vec::new());
+    };
+
+    let mut best_per_file = index
+        .files
+        .iter()
+        .filter_map(|(path, file)| {
+            file.chunks
+                .iter()
+                .map(|chunk| (chunk, dot(&query_embedding, &chunk.embedding)))
+                .max_by(|a, b| a.1.total_cmp(&b.1))
+                .map(|(chunk, score)| (path, chunk, score))
+        })
+        .collect::<Vec<_>>();
+    best_per_file.sort_by(|a, b| b.2.total_cmp(&a.2).then_with(|| a.0.cmp(b.0)));
+    best_per_file.truncate(limit);
+
+    let mut matches = Vec::with_capacity(best_per_file.len());
+    for (path, chunk, score) in best_per_file {
+        let snippet = tokio::fs::read_to_string(root.join(path))
+            .await
+            .map(|text| {
+                text.lines()
+                    .skip(chunk.start_line.saturating_sub(1))
+                    .take(SNIPPET_LINES.min(chunk.end_line + 1 - chunk.start_line))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            })
+            .unwrap_or_default();
+        matches.push(SemanticMatch {
+            path: path.clone(),
+            start_line: chunk.start_line,
+            end_line: chunk.end_line,
+            score,
+            snippet,
+        });
+    }
+    Ok(matches)
+}
+
+struct Candidate {
+    relative_path: PathBuf,
+    modified_secs: u64,
+    len: u64,
+}
+
+fn collect_candidates(root: &Path, max_files: usize, max_file_bytes: u64) -> Vec<Candidate> {
+    let mut candidates = Vec::new();
+    for entry in ignore::WalkBuilder::new(root).build().flatten() {
+        if candidates.len() >= max_files {
+            break;
+        }
+        if !entry
+            .file_type()
+            .is_some_and(|file_type| file_type.is_file())
+        {
+            continue;
+        }
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+        if metadata.len() == 0 || metadata.len() > max_file_bytes {
+            continue;
+        }
+        let Ok(relative_path) = entry.path().strip_prefix(root) else {
+            continue;
+        };
+        let modified_secs = metadata
+            .modified()
+            .ok()
+            .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+            .map(|duration| duration.as_secs())
+            .unwrap_or_default();
+        candidates.push(Candidate {
+            relative_path: relative_path.to_path_buf(),
+            modified_secs,
+            len: metadata.len(),
+        });
+    }
+    candidates
+}
+
+/// Splits text into `(start_line, end_line, body)` chunks with 1-indexed,
+/// inclusive line numbers. Whitespace-only chunks are dropped.
+fn chunk_lines(text: &str) -> Vec<(usize, usize, String)> {
+    let lines = text.lines().collect::<Vec<_>>();
+    lines
+        .chunks(CHUNK_LINES)
+        .enumerate()
+        .filter(|(_, chunk)| chunk.iter().any(|line| !line.trim().is_empty()))
+        .map(|(idx, chunk)| {
+            let start_line = idx * CHUNK_LINES + 1;
+            (start_line, start_line + chunk.len() - 1, chunk.join("\n"))
+        })
+        .collect()
+}
+
+/// Hashed bag-of-tokens embedding. Identifiers are split on case and
+/// underscore boundaries so `refreshToken` and `refresh_token` land on the
+/// same buckets.
+fn local_embedding(text: &str) -> Vec<f32> {
+    let mut counts: BTreeMap<String, f32> = BTreeMap::new();
+    for word in text.split(|c: char| !c.is_alphanumeric()) {
+        for token in split_identifier(word) {
+            if token.len() > 1 {
+                *counts.entry(token).or_default() += 1.0;
+            }
+        }
+    }
+
+    let mut vector = vec![0.0; LOCAL_DIMENSIONS];
+    for (token, count) in counts {
+        let hash = fnv1a(token.as_bytes());
+        let bucket = (hash % LOCAL_DIMENSIONS as u64) as usize;
+        let sign = if hash & (1 << 63) == 0 { 1.0 } else { -1.0 };
+        vector[bucket] += sign * (1.0 + count.ln());
+    }
+    normalize(vector)
+}
+
+fn split_identifier(word: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut previous_lower = false;
+    for c in word.chars() {
+        if c.is_uppercase() && previous_lower && !current.is_empty() {
+            tokens.push(std::mem::take(&mut current));
+        }
+        previous_lower = c.is_lowercase() || c.is_ascii_digit();
+        current.extend(c.to_lowercase());
+    }
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+    tokens
+}
+
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+fn normalize(mut vector: Vec<f32>) -> Vec<f32> {
+    let norm = vector.iter().map(|value| value * value).sum::<f32>().sqrt();
+    if norm > 0.0 {
+        for value in &mut vector {
+            *value /= norm;
+        }
+    }
+    vector
+}
+
+fn dot(a: &[f32], b: &[f32]) -> f32 {
+    a.iter().zip(b).map(|(a, b)| a * b).sum()
+}
+
+async fn load_index(path: &Path) -> Option<SemanticIndex> {
+    let contents = tokio::fs::read(path).await.ok()?;
+    serde_json::from_slice(&contents)
+        .inspect_err(|err| {
+            warn!(
+                "ignoring unreadable semantic index {}: {err}",
+                path.display()
+            )
+        })
+        .ok()
+}
+
+async fn write_index(path: &Path, index: &SemanticIndex) -> Result<()> {
+    let Some(parent) = path.parent() else {
+        anyhow::bail!("invalid semantic index path {}", path.display());
+    };
+    tokio::fs::create_dir_all(parent).await?;
+    let contents = serde_json::to_vec(index)?;
+    let tmp_path = path.with_extension("json.tmp");
+    tokio::fs::write(&tmp_path, contents).await?;
+    tokio::fs::rename(&tmp_path, path).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core_test_support::skip_if_no_network;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    #[test]
+    fn split_identifier_handles_camel_and_snake_case() {
+        assert_eq!(
+            split_identifier("refreshTokenIfNeeded"),
+            vec!["refresh", "token", "if", "needed"]
+        );
+        assert_eq!(split_identifier("HTTP2Client"), vec!["http2", "client"]);
+    }
+
+    #[test]
+    fn chunk_lines_uses_one_indexed_inclusive_ranges() {
+        let text = (1..=45)
+            .map(|line| format!("line {line}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let ranges = chunk_lines(&text)
+            .into_iter()
+            .map(|(start, end, _)| (start, end))
+            .collect::<Vec<_>>();
+        assert_eq!(ranges, vec![(1, 40), (41, 45)]);
+    }
+
+    #[tokio::test]
+    async fn refresh_then_search_ranks_relevant_file_first() -> Result<()> {
+        let workspace = TempDir::new()?;
+        let codex_home = TempDir::new()?;
+        std::fs::write(
+            workspace.path().join("auth.rs"),
+            "fn refresh_access_token(token: &RefreshToken) {\n    // refresh the token\n}\n",
+        )?;
+        std::fs::write(
+            workspace.path().join("render.rs"),
+            "fn draw_frame(buffer: &mut Buffer) {\n    paint_widgets(buffer);\n}\n",
+        )?;
+        let config = SemanticSearchConfig::default();
+
+        let stats = refresh_index(workspace.path(), codex_home.path(), &config).await?;
+        assert_eq!(
+            stats,
+            IndexStats {
+                files: 2,
+                reused: 0,
+                embedded: 2,
+                chunks: 2,
+                failed: 0,
+            }
+        );
+
+        let matches = search(
+            workspace.path(),
+            codex_home.path(),
+            &config,
+            "refresh token",
+            1,
+        )
+        .await?;
+        assert_eq!(
+            matches
+                .iter()
+                .map(|m| (m.path.clone(), m.start_line, m.end_line))
+                .collect::<Vec<_>>(),
+            vec![(PathBuf::from("auth.rs"), 1, 3)]
+        );
+
+        let stats = refresh_index(workspace.path(), codex_home.path(), &config).await?;
+        assert_eq!(stats.reused, 2);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn refresh_skips_files_the_embedder_rejects() -> Result<()> {
+        skip_if_no_network!(Ok(()));
+
+        use wiremock::Mock;
+        use wiremock::MockServer;
+        use wiremock::ResponseTemplate;
+        use wiremock::matchers::body_string_contains;
+        use wiremock::matchers::method;
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(body_string_contains("broken.rs"))
+            .respond_with(ResponseTemplate::new(500))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "data": [{ "index": 0, "embedding": [1.0, 0.0] }]
+            })))
+            .mount(&server)
+            .await;
+
+        let workspace = TempDir::new()?;
+        let codex_home = TempDir::new()?;
+        std::fs::write(workspace.path().join("broken.rs"), "fn broken() {}\n")?;
+        std::fs::write(workspace.path().join("fine.rs"), "fn fine() {}\n")?;
+        let api_key_env = "CODEX_SEMANTIC_SEARCH_TEST_API_KEY";
+        // Only this test reads the variable.
+        unsafe {
+            std::env::set_var(api_key_env, "sk-test");
+        }
+        let config = SemanticSearchConfig {
+            embedder: SemanticSearchEmbedder::Api,
+            base_url: server.uri(),
+            api_key_env: api_key_env.to_string(),
+            ..Default::default()
+        };
+
+        let stats = refresh_index(workspace.path(), codex_home.path(), &config).await?;
+        assert_eq!(
+            stats,
+            IndexStats {
+                files: 1,
+                reused: 0,
+                embedded: 1,
+                chunks: 1,
+                failed: 1,
+            }
+        );
+        let index = load_index(&index_path(codex_home.path(), workspace.path()))
+            .await
+            .expect("partial index is saved");
+        assert_eq!(
+            index.files.keys().cloned().collect::<Vec<_>>(),
+            vec![PathBuf::from("fine.rs")]
+        );
+        Ok(())
+    }
+}
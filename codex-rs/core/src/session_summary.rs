@@ -0,0 +1,328 @@
+//! Short recaps of what a session did (`/summarize`, and on exit with `tui.summarize_on_exit`).
+//!
+//! The recap is written by the model in a read-only fork of the conversation and stored next to
+//! the rollout as `rollout-….summary.json`, so resuming the session can open with it. Writing a
+//! new summary replaces the previous one. A fresh session in the same project can start from the
+//! recent summaries instead ([`carry_over_briefing`]).
+
+use std::path::Path;
+use std::path::PathBuf;
+
+use chrono::DateTime;
+use chrono::Duration;
+use chrono::SecondsFormat;
+use chrono::Utc;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::json;
+
+use crate::INTERACTIVE_SESSION_SOURCES;
+use crate::RolloutRecorder;
+use crate::ThreadManager;
+use crate::ThreadSortKey;
+use crate::config::Config;
+use crate::forked_prompt::ask_forked_conversation;
+use crate::git_info::get_git_repo_root;
+
+const SUMMARY_EXTENSION: &str = "summary.json";
+
+const SUMMARY_PROMPT: &str = "Summarize this session so far for someone picking it up later. \
+List what was accomplished, the decisions that were made and why, and the TODOs that are still \
+outstanding. Keep each entry to one short sentence, name files and commands where it helps, and \
+leave a list empty rather than padding it. Do not run commands or edit files. Reply with JSON.";
+
+/// Most sessions whose summaries go into a carry-over briefing.
+const MAX_BRIEFING_SESSIONS: usize = 3;
+
+/// How many recently updated sessions are checked for summaries from the same project.
+const BRIEFING_SCAN_LIMIT: usize = 25;
+
+/// Summaries older than this are left out of a carry-over briefing.
+const BRIEFING_MAX_AGE_DAYS: i64 = 14;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionSummary {
+    pub accomplished: Vec<String>,
+    pub decisions: Vec<String>,
+    pub todos: Vec<String>,
+    /// When the summary was written (RFC 3339, UTC).
+    pub generated_at: String,
+}
+
+/// Summaries of recent sessions in a project, offered when a fresh session starts there.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CarryOverBriefing {
+    /// Most recent first.
+    pub summaries: Vec<SessionSummary>,
+}
+
+impl CarryOverBriefing {
+    /// The briefing as given to the model.
+    pub fn to_context(&self) -> String {
+        let mut text = String::from(
+            "Summaries of recent earlier Codex sessions in this project, newest first. Use them \
+for continuity only: the code may have changed since, so check before relying on them.\n",
+        );
+        for summary in &self.summaries {
+            text.push_str(&format!(
+                "\nSession summarized at {}:\n",
+                summary.generated_at
+            ));
+            for (label, entries) in [
+                ("Accomplished", &summary.accomplished),
+                ("Decisions", &summary.decisions),
+                ("Outstanding TODOs", &summary.todos),
+            ] {
+                if entries.is_empty() {
+                    continue;
+                }
+                text.push_str(&format!("{label}:\n"));
+                for entry in entries {
+                    text.push_str(&format!("- {entry}\n"));
+                }
+            }
+        }
+        text
+    }
+}
+
+#[derive(Deserialize)]
+struct SummaryDraft {
+    accomplished: Vec<String>,
+    decisions: Vec<String>,
+    todos: Vec<String>,
+}
+
+/// The sidecar file holding the summary of the session recorded in `rollout_path`.
+pub fn session_summary_path(rollout_path: &Path) -> PathBuf {
+    rollout_path.with_extension(SUMMARY_EXTENSION)
+}
+
+/// Read the stored summary for `rollout_path`, if one was written.
+pub fn read_session_summary(rollout_path: &Path) -> Option<SessionSummary> {
+    let contents = std::fs::read_to_string(session_summary_path(rollout_path)).ok()?;
+    match serde_json::from_str(&contents) {
+        Ok(summary) => Some(summary),
+        Err(err) => {
+            tracing::warn!(
+                "ignoring unreadable session summary for {}: {err}",
+                rollout_path.display()
+            );
+            None
+        }
+    }
+}
+
+/// Ask the model for a summary of the conversation in `rollout_path` and store it next to the
+/// rollout.
+pub async fn summarize_session(
+    thread_manager: &ThreadManager,
+    config: &Config,
+    rollout_path: &Path,
+) -> Result<SessionSummary, String> {
+    let list = json!({ "type": "array", "items": { "type": "string" } });
+    let draft: SummaryDraft = ask_forked_conversation(
+        thread_manager,
+        config,
+        Some(rollout_path),
+        SUMMARY_PROMPT,
+        json!({
+            "type": "object",
+            "properties": {
+                "accomplished": list,
+                "decisions": list,
+                "todos": list,
+            },
+            "required": ["accomplished", "decisions", "todos"],
+            "additionalProperties": false,
+        }),
+        "the session summary",
+    )
+    .await?;
+    let summary = SessionSummary {
+        accomplished: clean_entries(draft.accomplished),
+        decisions: clean_entries(draft.decisions),
+        todos: clean_entries(draft.todos),
+        generated_at: Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true),
+    };
+    write_session_summary(rollout_path, &summary)
+        .await
+        .map_err(|err| format!("failed to save the session summary: {err}"))?;
+    Ok(summary)
+}
+
+/// Collect the summaries of the latest sessions in the project `config.cwd` belongs to (its git
+/// repository, or the directory itself). `None` when no recent session there was summarized.
+pub async fn carry_over_briefing(config: &Config) -> Option<CarryOverBriefing> {
+    let page = match RolloutRecorder::list_threads(
+        config,
+        BRIEFING_SCAN_LIMIT,
+        None,
+        ThreadSortKey::UpdatedAt,
+        INTERACTIVE_SESSION_SOURCES,
+        None,
+        &config.model_provider_id,
+        None,
+    )
+    .await
+    {
+        Ok(page) => page,
+        Err(err) => {
+            tracing::warn!("failed to list sessions for a carry-over briefing: {err}");
+            return None;
+        }
+    };
+    let project_root = get_git_repo_root(&config.cwd).unwrap_or_else(|| config.cwd.clone());
+    let sessions = page
+        .items
+        .into_iter()
+        .filter_map(|item| Some((item.cwd?, item.path)))
+        .collect::<Vec<_>>();
+    briefing_from_sessions(&project_root, &sessions, Utc::now())
+}
+
+/// Build a briefing from `(cwd, rollout_path)` pairs ordered most recently updated first.
+fn briefing_from_sessions(
+    project_root: &Path,
+    sessions: &[(PathBuf, PathBuf)],
+    now: DateTime<Utc>,
+) -> Option<CarryOverBriefing> {
+    let cutoff = now - Duration::days(BRIEFING_MAX_AGE_DAYS);
+    let summaries = sessions
+        .iter()
+        .filter(|(cwd, _)| cwd.starts_with(project_root))
+        .filter_map(|(_, rollout_path)| read_session_summary(rollout_path))
+        .filter(|summary| {
+            DateTime::parse_from_rfc3339(&summary.generated_at)
+                .is_ok_and(|generated_at| generated_at >= cutoff)
+        })
+        .take(MAX_BRIEFING_SESSIONS)
+        .collect::<Vec<_>>();
+    (!summaries.is_empty()).then_some(CarryOverBriefing { summaries })
+}
+
+async fn write_session_summary(
+    rollout_path: &Path,
+    summary: &SessionSummary,
+) -> std::io::Result<()> {
+    let contents = serde_json::to_string_pretty(summary).map_err(std::io::Error::other)?;
+    tokio::fs::write(session_summary_path(rollout_path), contents).await
+}
+
+/// Move the summary along with a rollout that moved from `from` to `to`. Missing summaries are
+/// fine; other failures only leave the recap behind.
+pub(crate) async fn move_session_summary(from: &Path, to: &Path) {
+    let source = session_summary_path(from);
+    if let Err(err) = tokio::fs::rename(&source, session_summary_path(to)).await
+        && err.kind() != std::io::ErrorKind::NotFound
+    {
+        tracing::warn!("failed to move session summary {}: {err}", source.display());
+    }
+}
+
+fn clean_entries(entries: Vec<String>) -> Vec<String> {
+    entries
+        .into_iter()
+        .map(|entry| entry.trim().to_string())
+        .filter(|entry| !entry.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn summaries_are_stored_next_to_the_rollout() {
+        let dir = TempDir::new().expect("tempdir");
+        let rollout = dir.path().join("rollout-2025-01-01T00-00-00-abc.jsonl");
+        let summary = SessionSummary {
+            accomplished: vec!["Added `codex view`".to_string()],
+            decisions: Vec::new(),
+            todos: vec!["Document the new flag".to_string()],
+            generated_at: "2025-01-01T00:10:00Z".to_string(),
+        };
+
+        write_session_summary(&rollout, &summary)
+            .await
+            .expect("write summary");
+
+        assert_eq!(
+            session_summary_path(&rollout),
+            dir.path()
+                .join("rollout-2025-01-01T00-00-00-abc.summary.json")
+        );
+        assert_eq!(read_session_summary(&rollout), Some(summary.clone()));
+
+        let archived = dir.path().join("archived.jsonl");
+        move_session_summary(&rollout, &archived).await;
+        assert_eq!(read_session_summary(&rollout), None);
+        assert_eq!(read_session_summary(&archived), Some(summary));
+    }
+
+    #[tokio::test]
+    async fn briefing_uses_recent_summaries_from_the_same_project() {
+        let dir = TempDir::new().expect("tempdir");
+        let now = DateTime::parse_from_rfc3339("2025-01-20T00:00:00Z")
+            .expect("timestamp")
+            .with_timezone(&Utc);
+        let summarized = |entry: &str, generated_at: &str| SessionSummary {
+            accomplished: vec![entry.to_string()],
+            decisions: Vec::new(),
+            todos: vec!["Fix the flaky lock test".to_string()],
+            generated_at: generated_at.to_string(),
+        };
+        let repo = dir.path().join("repo");
+        let mut sessions = Vec::new();
+        for (name, cwd, summary) in [
+            (
+                "in-subdir",
+                repo.join("tui"),
+                Some(summarized("Refactored the pager", "2025-01-19T10:00:00Z")),
+            ),
+            ("unsummarized", repo.clone(), None),
+            (
+                "elsewhere",
+                dir.path().join("other"),
+                Some(summarized("Elsewhere", "2025-01-19T11:00:00Z")),
+            ),
+            (
+                "in-repo",
+                repo.clone(),
+                Some(summarized("Added the lock", "2025-01-18T10:00:00Z")),
+            ),
+            (
+                "stale",
+                repo.clone(),
+                Some(summarized("Long ago", "2024-12-01T10:00:00Z")),
+            ),
+        ] {
+            let rollout = dir.path().join(format!("{name}.jsonl"));
+            if let Some(summary) = summary {
+                write_session_summary(&rollout, &summary)
+                    .await
+                    .expect("write summary");
+            }
+            sessions.push((cwd, rollout));
+        }
+
+        let briefing = briefing_from_sessions(&repo, &sessions, now).expect("briefing");
+
+        assert_eq!(
+            briefing
+                .summaries
+                .iter()
+                .map(|summary| summary.accomplished[0].as_str())
+                .collect::<Vec<_>>(),
+            vec!["Refactored the pager", "Added the lock"]
+        );
+        assert!(
+            briefing
+                .to_context()
+                .contains("Outstanding TODOs:\n- Fix the flaky lock test\n")
+        );
+        assert_eq!(briefing_from_sessions(&repo, &sessions[2..3], now), None);
+    }
+}
@@ -895,6 +895,7 @@ mod tests {
                     trust_root.to_string_lossy().to_string(),
                     ProjectConfig {
                         trust_level: Some(TrustLevel::Trusted),
+                        shell_environment_set: None,
                     },
                 )])),
                 ..Default::default()
@@ -3,6 +3,7 @@ mod session;
 mod turn;
 
 pub(crate) use service::SessionServices;
+pub(crate) use session::PlanApproval;
 pub(crate) use session::SessionState;
 pub(crate) use turn::ActiveTurn;
 pub(crate) use turn::RunningTask;
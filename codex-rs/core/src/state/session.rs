@@ -3,6 +3,8 @@
 use codex_protocol::models::ResponseItem;
 use std::collections::HashMap;
 use std::collections::HashSet;
+use std::path::Path;
+use std::path::PathBuf;
 use tokio::task::JoinHandle;
 
 use crate::codex::PreviousTurnSettings;
@@ -13,6 +15,7 @@ use crate::protocol::RateLimitSnapshot;
 use crate::protocol::TokenUsage;
 use crate::protocol::TokenUsageInfo;
 use crate::tasks::RegularTask;
+use crate::todo_list::TodoList;
 use crate::truncate::TruncationPolicy;
 use codex_protocol::protocol::TurnContextItem;
 
@@ -32,6 +35,27 @@ pub(crate) struct SessionState {
     pub(crate) startup_regular_task: Option<JoinHandle<CodexResult<RegularTask>>>,
     pub(crate) active_mcp_tool_selection: Option<Vec<String>>,
     pub(crate) active_connector_selection: HashSet<String>,
+    /// Where the agent's last successful shell call left off (`persistent_cwd`), keyed by the
+    /// turn cwd it was tracked from so a changed session cwd discards it.
+    agent_cwd: Option<(PathBuf, PathBuf)>,
+    /// Files set with `Op::SetPinnedFiles`, re-read for every sampling request.
+    pub(crate) pinned_files: Vec<PathBuf>,
+    /// Briefing from `Op::SetCarryOverBriefing`, recorded with the next user turn.
+    pub(crate) pending_briefing: Option<String>,
+    /// The list kept by the `update_todos` tool.
+    pub(crate) todo_list: TodoList,
+    /// How long the plan the user approved under `require_plan_approval` lifts the gate, if
+    /// they approved one.
+    pub(crate) plan_approval: Option<PlanApproval>,
+}
+
+/// How long an approved plan lets mutating tools run under `require_plan_approval`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum PlanApproval {
+    /// Until the next user turn.
+    Turn,
+    /// For the rest of the session.
+    Session,
 }
 
 impl SessionState {
@@ -49,6 +73,11 @@ impl SessionState {
             startup_regular_task: None,
             active_mcp_tool_selection: None,
             active_connector_selection: HashSet::new(),
+            agent_cwd: None,
+            pinned_files: Vec::new(),
+            pending_briefing: None,
+            todo_list: TodoList::default(),
+            plan_approval: None,
         }
     }
 
@@ -161,6 +190,17 @@ impl SessionState {
         self.dependency_env.clone()
     }
 
+    pub(crate) fn agent_cwd(&self, turn_cwd: &Path) -> Option<PathBuf> {
+        self.agent_cwd
+            .as_ref()
+            .filter(|(base, _)| base == turn_cwd)
+            .map(|(_, cwd)| cwd.clone())
+    }
+
+    pub(crate) fn set_agent_cwd(&mut self, turn_cwd: PathBuf, cwd: PathBuf) {
+        self.agent_cwd = Some((turn_cwd, cwd));
+    }
+
     pub(crate) fn set_startup_regular_task(&mut self, task: JoinHandle<CodexResult<RegularTask>>) {
         self.startup_regular_task = Some(task);
     }
@@ -8,6 +8,7 @@ use tokio::sync::Notify;
 use tokio_util::sync::CancellationToken;
 use tokio_util::task::AbortOnDropHandle;
 
+use codex_protocol::approvals::CompactionReviewDecision;
 use codex_protocol::dynamic_tools::DynamicToolResponse;
 use codex_protocol::models::ResponseInputItem;
 use codex_protocol::request_user_input::RequestUserInputResponse;
@@ -73,6 +74,7 @@ pub(crate) struct TurnState {
     pending_approvals: HashMap<String, oneshot::Sender<ReviewDecision>>,
     pending_user_input: HashMap<String, oneshot::Sender<RequestUserInputResponse>>,
     pending_dynamic_tools: HashMap<String, oneshot::Sender<DynamicToolResponse>>,
+    pending_compaction_reviews: HashMap<String, oneshot::Sender<CompactionReviewDecision>>,
     pending_input: Vec<ResponseInputItem>,
     pub(crate) tool_calls: u64,
     pub(crate) token_usage_at_turn_start: TokenUsage,
@@ -98,6 +100,7 @@ impl TurnState {
         self.pending_approvals.clear();
         self.pending_user_input.clear();
         self.pending_dynamic_tools.clear();
+        self.pending_compaction_reviews.clear();
         self.pending_input.clear();
     }
 
@@ -131,6 +134,21 @@ impl TurnState {
         self.pending_dynamic_tools.remove(key)
     }
 
+    pub(crate) fn insert_pending_compaction_review(
+        &mut self,
+        key: String,
+        tx: oneshot::Sender<CompactionReviewDecision>,
+    ) -> Option<oneshot::Sender<CompactionReviewDecision>> {
+        self.pending_compaction_reviews.insert(key, tx)
+    }
+
+    pub(crate) fn remove_pending_compaction_review(
+        &mut self,
+        key: &str,
+    ) -> Option<oneshot::Sender<CompactionReviewDecision>> {
+        self.pending_compaction_reviews.remove(key)
+    }
+
     pub(crate) fn push_pending_input(&mut self, input: ResponseInputItem) {
         self.pending_input.push(input);
     }
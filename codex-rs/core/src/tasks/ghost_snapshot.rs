@@ -13,6 +13,7 @@ use codex_protocol::models::ResponseItem;
 use codex_protocol::user_input::UserInput;
 use codex_utils_readiness::Readiness;
 use codex_utils_readiness::Token;
+use codex_utils_string::format_bytes;
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::oneshot;
@@ -221,7 +222,7 @@ fn format_ignored_untracked_files_warning(
         parts.push(format!(
             "{} ({})",
             file.path.display(),
-            format_bytes(file.byte_size)
+            format_bytes(u64::try_from(file.byte_size).unwrap_or_default())
         ));
     }
     if report.ignored_untracked_files.len() > MAX_FILES {
@@ -231,24 +232,11 @@ fn format_ignored_untracked_files_warning(
 
     Some(format!(
         "Repository snapshot ignored untracked files larger than {}: {}. These files are preserved during undo cleanup, but their contents are not captured in the snapshot. Adjust `ghost_snapshot.ignore_large_untracked_files` to change this behavior. To avoid this message in the future, update your `.gitignore`.",
-        format_bytes(threshold),
+        format_bytes(u64::try_from(threshold).unwrap_or_default()),
         parts.join(", ")
     ))
 }
 
-fn format_bytes(bytes: i64) -> String {
-    const KIB: i64 = 1024;
-    const MIB: i64 = 1024 * 1024;
-
-    if bytes >= MIB {
-        return format!("{} MiB", bytes / MIB);
-    }
-    if bytes >= KIB {
-        return format!("{} KiB", bytes / KIB);
-    }
-    format!("{bytes} B")
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
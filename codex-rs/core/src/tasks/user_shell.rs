@@ -52,11 +52,15 @@ pub(crate) enum UserShellCommandMode {
 #[derive(Clone)]
 pub(crate) struct UserShellCommandTask {
     command: String,
+    exclude_from_context: bool,
 }
 
 impl UserShellCommandTask {
-    pub(crate) fn new(command: String) -> Self {
-        Self { command }
+    pub(crate) fn new(command: String, exclude_from_context: bool) -> Self {
+        Self {
+            command,
+            exclude_from_context,
+        }
     }
 }
 
@@ -81,6 +85,7 @@ impl SessionTask for UserShellCommandTask {
             session.clone_session(),
             turn_context,
             self.command.clone(),
+            self.exclude_from_context,
             cancellation_token,
             UserShellCommandMode::StandaloneTurn,
         )
@@ -93,6 +98,7 @@ pub(crate) async fn execute_user_shell_command(
     session: Arc<Session>,
     turn_context: Arc<TurnContext>,
     command: String,
+    exclude_from_context: bool,
     cancellation_token: CancellationToken,
     mode: UserShellCommandMode,
 ) {
@@ -198,6 +204,7 @@ pub(crate) async fn execute_user_shell_command(
                 &raw_command,
                 &exec_output,
                 mode,
+                exclude_from_context,
             )
             .await;
             session
@@ -254,8 +261,15 @@ pub(crate) async fn execute_user_shell_command(
                 )
                 .await;
 
-            persist_user_shell_output(&session, turn_context.as_ref(), &raw_command, &output, mode)
-                .await;
+            persist_user_shell_output(
+                &session,
+                turn_context.as_ref(),
+                &raw_command,
+                &output,
+                mode,
+                exclude_from_context,
+            )
+            .await;
         }
         Ok(Err(err)) => {
             error!("user shell command failed: {err:?}");
@@ -299,6 +313,7 @@ pub(crate) async fn execute_user_shell_command(
                 &raw_command,
                 &exec_output,
                 mode,
+                exclude_from_context,
             )
             .await;
         }
@@ -311,7 +326,11 @@ async fn persist_user_shell_output(
     raw_command: &str,
     exec_output: &ExecToolCallOutput,
     mode: UserShellCommandMode,
+    exclude_from_context: bool,
 ) {
+    if exclude_from_context {
+        return;
+    }
     let output_item = user_shell_command_record_item(raw_command, exec_output, turn_context);
 
     if mode == UserShellCommandMode::StandaloneTurn {
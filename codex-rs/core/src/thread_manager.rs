@@ -303,6 +303,21 @@ impl ThreadManager {
         }
     }
 
+    /// Flush the rollouts of all live threads, e.g. before the process goes down.
+    pub async fn flush_rollouts(&self) {
+        let threads = self
+            .state
+            .threads
+            .read()
+            .await
+            .values()
+            .cloned()
+            .collect::<Vec<_>>();
+        for thread in threads {
+            thread.flush_rollout().await;
+        }
+    }
+
     pub fn subscribe_thread_created(&self) -> broadcast::Receiver<ThreadId> {
         self.state.thread_created_tx.subscribe()
     }
@@ -388,6 +403,11 @@ impl ThreadManager {
         self.state.threads.write().await.remove(thread_id)
     }
 
+    /// Shut down a sub-agent thread and release its slot in the `agents.max_threads` budget.
+    pub async fn close_agent(&self, thread_id: ThreadId) -> CodexResult<String> {
+        self.agent_control().shutdown_agent(thread_id).await
+    }
+
     /// Closes all threads open in this ThreadManager
     pub async fn remove_and_close_all_threads(&self) -> CodexResult<()> {
         for thread in self.state.threads.read().await.values() {
@@ -0,0 +1,188 @@
+//! The session todo list behind the `update_todos` tool.
+//!
+//! The list lives in `SessionState`. Every change is sent as `EventMsg::TodoListUpdate`, which the
+//! rollout always keeps, so resuming or forking a session picks up the last list and the model can
+//! read it back with an empty `update_todos` call after its history was compacted.
+
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::RolloutItem;
+use codex_protocol::todo_tool::TodoItem;
+use codex_protocol::todo_tool::TodoStatus;
+use serde::Deserialize;
+
+/// Arguments of `update_todos`. Changes apply in order: status updates, removals, then additions.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct TodoListUpdate {
+    #[serde(default)]
+    pub(crate) add: Vec<String>,
+    #[serde(default)]
+    pub(crate) update: Vec<TodoStatusChange>,
+    #[serde(default)]
+    pub(crate) remove: Vec<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct TodoStatusChange {
+    pub(crate) id: u32,
+    pub(crate) status: TodoStatus,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct TodoList {
+    items: Vec<TodoItem>,
+}
+
+impl TodoList {
+    /// The list as of the last `TodoListUpdate` event in a resumed or forked rollout.
+    pub(crate) fn from_rollout(rollout_items: &[RolloutItem]) -> Self {
+        let items = rollout_items
+            .iter()
+            .rev()
+            .find_map(|item| match item {
+                RolloutItem::EventMsg(EventMsg::TodoListUpdate(event)) => Some(event.items.clone()),
+                _ => None,
+            })
+            .unwrap_or_default();
+        Self { items }
+    }
+
+    pub(crate) fn items(&self) -> &[TodoItem] {
+        &self.items
+    }
+
+    /// Apply `update`, or leave the list untouched and explain the problem when it refers to an
+    /// unknown item or adds an empty one. Returns whether anything changed.
+    pub(crate) fn apply(&mut self, update: TodoListUpdate) -> Result<bool, String> {
+        let known = |id: u32| self.items.iter().any(|item| item.id == id);
+        if let Some(id) = update
+            .update
+            .iter()
+            .map(|change| change.id)
+            .chain(update.remove.iter().copied())
+            .find(|id| !known(*id))
+        {
+            return Err(format!("there is no todo item #{id}"));
+        }
+        if update.add.iter().any(|text| text.trim().is_empty()) {
+            return Err("todo items need a description".to_string());
+        }
+
+        let before = self.items.clone();
+        for change in update.update {
+            if let Some(item) = self.items.iter_mut().find(|item| item.id == change.id) {
+                item.status = change.status;
+            }
+        }
+        self.items.retain(|item| !update.remove.contains(&item.id));
+        let mut next_id = before.iter().map(|item| item.id).max().unwrap_or(0) + 1;
+        for text in update.add {
+            self.items.push(TodoItem {
+                id: next_id,
+                text: text.trim().to_string(),
+                status: TodoStatus::Pending,
+            });
+            next_id += 1;
+        }
+        Ok(self.items != before)
+    }
+
+    /// The list as returned to the model.
+    pub(crate) fn describe(&self) -> String {
+        if self.items.is_empty() {
+            return "The todo list is empty.".to_string();
+        }
+        let open = self
+            .items
+            .iter()
+            .filter(|item| item.status.is_open())
+            .count();
+        let mut text = format!("Todo list ({open} of {} open):", self.items.len());
+        for item in &self.items {
+            let status = match item.status {
+                TodoStatus::Pending => "pending",
+                TodoStatus::InProgress => "in_progress",
+                TodoStatus::Done => "done",
+                TodoStatus::Cancelled => "cancelled",
+            };
+            text.push_str(&format!("\n#{} [{status}] {}", item.id, item.text));
+        }
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::todo_tool::TodoListEvent;
+    use pretty_assertions::assert_eq;
+
+    fn update(json: &str) -> TodoListUpdate {
+        serde_json::from_str(json).expect("valid update")
+    }
+
+    #[test]
+    fn updates_apply_in_order_and_keep_ids_stable() {
+        let mut list = TodoList::default();
+        assert_eq!(
+            list.apply(update(r#"{"add":["Write the parser","Add tests"]}"#)),
+            Ok(true)
+        );
+        assert_eq!(
+            list.apply(update(
+                r#"{"update":[{"id":1,"status":"done"},{"id":2,"status":"in_progress"}],"remove":[1],"add":["Update docs"]}"#
+            )),
+            Ok(true)
+        );
+        assert_eq!(
+            list.describe(),
+            "Todo list (2 of 2 open):\n#2 [in_progress] Add tests\n#3 [pending] Update docs"
+        );
+        assert_eq!(list.apply(TodoListUpdate::default()), Ok(false));
+    }
+
+    #[test]
+    fn invalid_updates_leave_the_list_alone() {
+        let mut list = TodoList::default();
+        list.apply(update(r#"{"add":["Write the parser"]}"#))
+            .expect("add item");
+        let before = list.clone();
+
+        assert_eq!(
+            list.apply(update(
+                r#"{"update":[{"id":7,"status":"done"}],"add":["More"]}"#
+            )),
+            Err("there is no todo item #7".to_string())
+        );
+        assert_eq!(
+            list.apply(update(r#"{"add":["  "]}"#)),
+            Err("todo items need a description".to_string())
+        );
+        assert_eq!(list, before);
+    }
+
+    #[test]
+    fn resumed_sessions_restore_the_last_list() {
+        let item = |id, status| TodoItem {
+            id,
+            text: format!("item {id}"),
+            status,
+        };
+        let rollout = vec![
+            RolloutItem::EventMsg(EventMsg::TodoListUpdate(TodoListEvent {
+                items: vec![item(1, TodoStatus::Pending)],
+            })),
+            RolloutItem::EventMsg(EventMsg::TodoListUpdate(TodoListEvent {
+                items: vec![item(1, TodoStatus::Done), item(2, TodoStatus::Pending)],
+            })),
+        ];
+
+        let list = TodoList::from_rollout(&rollout);
+
+        assert_eq!(
+            list.items(),
+            &[item(1, TodoStatus::Done), item(2, TodoStatus::Pending)]
+        );
+    }
+}
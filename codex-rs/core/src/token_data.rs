@@ -1,4 +1,6 @@
 use base64::Engine;
+use chrono::DateTime;
+use chrono::Utc;
 use serde::Deserialize;
 use serde::Serialize;
 use thiserror::Error;
@@ -127,7 +129,13 @@ pub enum IdTokenInfoError {
     Json(#[from] serde_json::Error),
 }
 
-pub fn parse_chatgpt_jwt_claims(jwt: &str) -> Result<IdTokenInfo, IdTokenInfoError> {
+#[derive(Deserialize)]
+struct ExpirationClaims {
+    #[serde(default)]
+    exp: Option<i64>,
+}
+
+fn decode_jwt_payload(jwt: &str) -> Result<Vec<u8>, IdTokenInfoError> {
     // JWT format: header.payload.signature
     let mut parts = jwt.split('.');
     let (_header_b64, payload_b64, _sig_b64) = match (parts.next(), parts.next(), parts.next()) {
@@ -135,8 +143,20 @@ pub fn parse_chatgpt_jwt_claims(jwt: &str) -> Result<IdTokenInfo, IdTokenInfoErr
         _ => return Err(IdTokenInfoError::InvalidFormat),
     };
 
-    let payload_bytes = base64::engine::general_purpose::URL_SAFE_NO_PAD.decode(payload_b64)?;
-    let claims: IdClaims = serde_json::from_slice(&payload_bytes)?;
+    Ok(base64::engine::general_purpose::URL_SAFE_NO_PAD.decode(payload_b64)?)
+}
+
+/// Read the `exp` claim of a JWT such as the ChatGPT access token. Returns
+/// `Ok(None)` when the token carries no expiry.
+pub fn parse_jwt_expiration(jwt: &str) -> Result<Option<DateTime<Utc>>, IdTokenInfoError> {
+    let claims: ExpirationClaims = serde_json::from_slice(&decode_jwt_payload(jwt)?)?;
+    Ok(claims
+        .exp
+        .and_then(|exp| DateTime::<Utc>::from_timestamp(exp, 0)))
+}
+
+pub fn parse_chatgpt_jwt_claims(jwt: &str) -> Result<IdTokenInfo, IdTokenInfoError> {
+    let claims: IdClaims = serde_json::from_slice(&decode_jwt_payload(jwt)?)?;
     let email = claims
         .email
         .or_else(|| claims.profile.and_then(|profile| profile.email));
@@ -271,6 +291,29 @@ mod tests {
         assert!(info.get_chatgpt_plan_type().is_none());
     }
 
+    #[test]
+    fn jwt_expiration_reads_exp_claim() {
+        fn b64url_no_pad(bytes: &[u8]) -> String {
+            base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(bytes)
+        }
+        let fake_jwt = |payload: serde_json::Value| {
+            let header_b64 = b64url_no_pad(br#"{"alg":"none","typ":"JWT"}"#);
+            let payload_b64 = b64url_no_pad(&serde_json::to_vec(&payload).unwrap());
+            format!("{header_b64}.{payload_b64}.{}", b64url_no_pad(b"sig"))
+        };
+
+        let expires_at =
+            parse_jwt_expiration(&fake_jwt(serde_json::json!({ "exp": 1_700_000_000 })))
+                .expect("should parse");
+        assert_eq!(
+            expires_at,
+            DateTime::<Utc>::from_timestamp(1_700_000_000, 0)
+        );
+        let no_expiry = parse_jwt_expiration(&fake_jwt(serde_json::json!({ "sub": "123" })))
+            .expect("should parse");
+        assert_eq!(no_expiry, None);
+    }
+
     #[test]
     fn workspace_account_detection_matches_workspace_plans() {
         let workspace = IdTokenInfo {
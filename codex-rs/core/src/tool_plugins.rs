@@ -0,0 +1,478 @@
+//! Custom tools backed by sandboxed WASI modules (`[tool_plugins.<name>]`).
+//!
+//! Each plugin's `.wasm` file is compiled once when the session starts. A call instantiates it
+//! in a fresh wasmtime store whose WASI context exposes only what the plugin declared: the
+//! session's working directory (read-only or writable), extra host directories, and optionally
+//! the network. The call's JSON arguments arrive on stdin, stdout becomes the tool output, and
+//! the exit status decides whether the call succeeded. Both WASI preview 1 command modules and
+//! preview 2 command components are accepted.
+//!
+//! Declared write and network access is checked against the turn's sandbox policy before each
+//! call; see [`ToolPluginGrants::sandbox_escalations`]. Plugins need the `tool-plugins` cargo
+//! feature, which pulls in wasmtime and is enabled by the `codex` binaries rather than by
+//! default; without it every configured plugin fails to load.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Context;
+use codex_protocol::dynamic_tools::DynamicToolSpec;
+use codex_protocol::protocol::SandboxPolicy;
+use codex_utils_absolute_path::AbsolutePathBuf;
+
+use crate::config::types::ToolPluginAccess;
+use crate::config::types::ToolPluginConfig;
+use crate::tools::spec::BUILT_IN_TOOL_NAMES;
+
+#[cfg(feature = "tool-plugins")]
+mod wasm;
+#[cfg(not(feature = "tool-plugins"))]
+mod wasm {
+    //! Stand-in for builds without the `tool-plugins` feature: no plugin can be loaded.
+
+    use std::path::Path;
+
+    use super::ToolPluginGrants;
+
+    #[derive(Clone)]
+    pub(super) enum Runtime {}
+
+    pub(super) enum Program {}
+
+    pub(super) struct RunOutput {
+        pub exit_code: i32,
+        pub stdout: Vec<u8>,
+        pub stderr: Vec<u8>,
+    }
+
+    impl Runtime {
+        pub(super) fn new() -> anyhow::Result<Self> {
+            anyhow::bail!("this build of Codex was compiled without the `tool-plugins` feature")
+        }
+    }
+
+    impl Program {
+        pub(super) async fn compile(_runtime: &Runtime, _bytes: Vec<u8>) -> anyhow::Result<Self> {
+            anyhow::bail!("this build of Codex was compiled without the `tool-plugins` feature")
+        }
+
+        pub(super) async fn run(
+            &self,
+            _runtime: &Runtime,
+            _name: &str,
+            _stdin: &str,
+            _cwd: &Path,
+            _grants: &ToolPluginGrants,
+        ) -> anyhow::Result<RunOutput> {
+            match *self {}
+        }
+    }
+}
+
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+/// Every qualified MCP tool name starts with this.
+const MCP_TOOL_NAME_PREFIX: &str = "mcp__";
+
+/// The plugins loaded for a session.
+#[derive(Default)]
+pub(crate) struct ToolPlugins {
+    plugins: BTreeMap<String, Arc<ToolPlugin>>,
+}
+
+pub(crate) struct ToolPlugin {
+    name: String,
+    config: ToolPluginConfig,
+    runtime: wasm::Runtime,
+    program: wasm::Program,
+}
+
+/// What one call of a plugin may reach on the host.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ToolPluginGrants {
+    pub workspace: ToolPluginAccess,
+    pub read_paths: Vec<AbsolutePathBuf>,
+    pub write_paths: Vec<AbsolutePathBuf>,
+    pub network: bool,
+}
+
+/// What a plugin produced for one call.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct ToolPluginOutput {
+    pub output: String,
+    pub success: bool,
+}
+
+impl ToolPlugins {
+    /// Compile every configured plugin. Plugins that fail to load, including those whose name
+    /// is taken by a built-in, MCP, or `dynamic_tools` tool, are left out, with one message per
+    /// failure for the caller to surface.
+    pub(crate) async fn load(
+        configs: &BTreeMap<String, ToolPluginConfig>,
+        dynamic_tools: &[DynamicToolSpec],
+    ) -> (Self, Vec<String>) {
+        let mut plugins = BTreeMap::new();
+        let mut errors = Vec::new();
+        if configs.is_empty() {
+            return (Self { plugins }, errors);
+        }
+        let runtime = match wasm::Runtime::new() {
+            Ok(runtime) => runtime,
+            Err(err) => {
+                errors.push(format!("Tool plugins are unavailable: {err:#}"));
+                return (Self { plugins }, errors);
+            }
+        };
+        for (name, config) in configs {
+            match ToolPlugin::load(name, config, dynamic_tools, &runtime).await {
+                Ok(plugin) => {
+                    plugins.insert(name.clone(), Arc::new(plugin));
+                }
+                Err(err) => errors.push(format!("Failed to load tool plugin `{name}`: {err:#}")),
+            }
+        }
+        (Self { plugins }, errors)
+    }
+
+    pub(crate) fn get(&self, name: &str) -> Option<Arc<ToolPlugin>> {
+        self.plugins.get(name).cloned()
+    }
+
+    /// Tool definitions to advertise to the model.
+    pub(crate) fn specs(&self) -> Vec<DynamicToolSpec> {
+        self.plugins
+            .values()
+            .map(|plugin| DynamicToolSpec {
+                name: plugin.name.clone(),
+                description: plugin.config.description.clone(),
+                input_schema: plugin
+                    .config
+                    .parameters
+                    .clone()
+                    .unwrap_or_else(|| serde_json::json!({ "type": "object", "properties": {} })),
+            })
+            .collect()
+    }
+}
+
+impl ToolPlugin {
+    async fn load(
+        name: &str,
+        config: &ToolPluginConfig,
+        dynamic_tools: &[DynamicToolSpec],
+        runtime: &wasm::Runtime,
+    ) -> anyhow::Result<Self> {
+        if !is_valid_tool_name(name) {
+            anyhow::bail!("tool names may only contain letters, digits, `_`, and `-`");
+        }
+        if let Some(owner) = reserved_by(name, dynamic_tools) {
+            anyhow::bail!("the name is already used by {owner}");
+        }
+        let path = config.module.to_path_buf();
+        let bytes = tokio::fs::read(&path)
+            .await
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        let program = wasm::Program::compile(runtime, bytes).await?;
+        Ok(Self {
+            name: name.to_string(),
+            config: config.clone(),
+            runtime: runtime.clone(),
+            program,
+        })
+    }
+
+    /// Everything the plugin's config asks for.
+    pub(crate) fn grants(&self) -> ToolPluginGrants {
+        ToolPluginGrants {
+            workspace: self.config.workspace,
+            read_paths: self.config.read_paths.clone(),
+            write_paths: self.config.write_paths.clone(),
+            network: self.config.network,
+        }
+    }
+
+    /// Run the plugin once with `arguments` on stdin and only the access in `grants`.
+    pub(crate) async fn call(
+        &self,
+        arguments: &str,
+        cwd: &Path,
+        grants: &ToolPluginGrants,
+    ) -> anyhow::Result<ToolPluginOutput> {
+        let timeout = self
+            .config
+            .timeout_sec
+            .map_or(DEFAULT_TIMEOUT, Duration::from_secs);
+        let run = self
+            .program
+            .run(&self.runtime, &self.name, arguments, cwd, grants);
+        let result = tokio::time::timeout(timeout, run)
+            .await
+            .map_err(|_| anyhow::anyhow!("timed out after {}s", timeout.as_secs()))??;
+
+        let mut output = String::from_utf8_lossy(&result.stdout).into_owned();
+        let success = result.exit_code == 0;
+        if !success {
+            let stderr = String::from_utf8_lossy(&result.stderr).into_owned();
+            if !stderr.trim().is_empty() {
+                if !output.is_empty() && !output.ends_with('\n') {
+                    output.push('\n');
+                }
+                output.push_str(&stderr);
+            }
+            if output.trim().is_empty() {
+                output = format!("exited with status {}", result.exit_code);
+            }
+        }
+        Ok(ToolPluginOutput { output, success })
+    }
+}
+
+impl ToolPluginGrants {
+    /// Whether a call can change anything outside the sandbox.
+    pub(crate) fn is_mutating(&self) -> bool {
+        self.workspace == ToolPluginAccess::Write || !self.write_paths.is_empty() || self.network
+    }
+
+    /// Describe each grant that `sandbox_policy` would not allow for a call in `cwd`, such as
+    /// a writable directory outside the writable roots or network access.
+    pub(crate) fn sandbox_escalations(
+        &self,
+        sandbox_policy: &SandboxPolicy,
+        cwd: &Path,
+    ) -> Vec<String> {
+        let writable_roots = sandbox_policy.get_writable_roots_with_cwd(cwd);
+        let is_writable = |path: &Path| {
+            sandbox_policy.has_full_disk_write_access()
+                || writable_roots
+                    .iter()
+                    .any(|root| root.is_path_writable(path))
+        };
+
+        let mut escalations = Vec::new();
+        if self.workspace == ToolPluginAccess::Write && !is_writable(cwd) {
+            escalations.push(format!("write to {}", cwd.display()));
+        }
+        for path in &self.write_paths {
+            if !is_writable(path.as_path()) {
+                escalations.push(format!("write to {}", path.display()));
+            }
+        }
+        if self.network && !sandbox_policy.has_full_network_access() {
+            escalations.push("use the network".to_string());
+        }
+        escalations
+    }
+}
+
+/// What already claims `name`, since two tools with one name would be rejected by the API.
+fn reserved_by(name: &str, dynamic_tools: &[DynamicToolSpec]) -> Option<&'static str> {
+    if BUILT_IN_TOOL_NAMES.contains(&name) {
+        Some("a built-in tool")
+    } else if name.starts_with(MCP_TOOL_NAME_PREFIX) {
+        Some("MCP tools")
+    } else if dynamic_tools.iter().any(|tool| tool.name == name) {
+        Some("a dynamic tool")
+    } else {
+        None
+    }
+}
+
+fn is_valid_tool_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::protocol::ReadOnlyAccess;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    /// A WASI preview 1 command that echoes stdin to stdout and exits with status 0.
+    const ECHO_WAT: &str = r#"
+        (module
+          (import "wasi_snapshot_preview1" "fd_read"
+            (func $fd_read (param i32 i32 i32 i32) (result i32)))
+          (import "wasi_snapshot_preview1" "fd_write"
+            (func $fd_write (param i32 i32 i32 i32) (result i32)))
+          (memory (export "memory") 1)
+          (func (export "_start")
+            (i32.store (i32.const 0) (i32.const 64))
+            (i32.store (i32.const 4) (i32.const 1024))
+            (drop (call $fd_read (i32.const 0) (i32.const 0) (i32.const 1) (i32.const 8)))
+            (i32.store (i32.const 4) (i32.load (i32.const 8)))
+            (drop (call $fd_write (i32.const 1) (i32.const 0) (i32.const 1) (i32.const 12)))))
+    "#;
+
+    /// Loops forever.
+    #[cfg(feature = "tool-plugins")]
+    const SPIN_WAT: &str = r#"
+        (module
+          (memory (export "memory") 1)
+          (func (export "_start") (loop (br 0))))
+    "#;
+
+    /// Grows its memory past the per-instance cap.
+    #[cfg(feature = "tool-plugins")]
+    const GREEDY_WAT: &str = r#"
+        (module
+          (memory (export "memory") 1)
+          (func (export "_start") (drop (memory.grow (i32.const 8192)))))
+    "#;
+
+    fn plugin_config(dir: &TempDir, wat: &str, timeout_sec: Option<u64>) -> ToolPluginConfig {
+        let module = dir.path().join("plugin.wat");
+        std::fs::write(&module, wat).expect("write module");
+        ToolPluginConfig {
+            module: AbsolutePathBuf::try_from(module).expect("absolute path"),
+            description: "test plugin".to_string(),
+            parameters: None,
+            workspace: ToolPluginAccess::None,
+            read_paths: Vec::new(),
+            write_paths: Vec::new(),
+            network: false,
+            timeout_sec,
+        }
+    }
+
+    #[cfg(feature = "tool-plugins")]
+    #[tokio::test]
+    async fn plugin_receives_arguments_on_stdin() {
+        let dir = TempDir::new().expect("temp dir");
+        let configs = BTreeMap::from([("echo".to_string(), plugin_config(&dir, ECHO_WAT, None))]);
+        let (plugins, errors) = ToolPlugins::load(&configs, &[]).await;
+        assert_eq!(errors, Vec::<String>::new());
+
+        let plugin = plugins.get("echo").expect("echo plugin");
+        let output = plugin
+            .call(r#"{"path":"a.rs"}"#, dir.path(), &plugin.grants())
+            .await
+            .expect("call");
+        assert_eq!(
+            output,
+            ToolPluginOutput {
+                output: r#"{"path":"a.rs"}"#.to_string(),
+                success: true,
+            }
+        );
+        assert_eq!(plugins.specs()[0].name, "echo");
+    }
+
+    #[cfg(feature = "tool-plugins")]
+    #[tokio::test]
+    async fn runaway_plugin_hits_the_timeout() {
+        let dir = TempDir::new().expect("temp dir");
+        let configs =
+            BTreeMap::from([("spin".to_string(), plugin_config(&dir, SPIN_WAT, Some(1)))]);
+        let (plugins, _) = ToolPlugins::load(&configs, &[]).await;
+
+        let plugin = plugins.get("spin").expect("spin plugin");
+        let err = plugin
+            .call("{}", dir.path(), &plugin.grants())
+            .await
+            .expect_err("spinning plugin times out");
+        assert_eq!(err.to_string(), "timed out after 1s");
+    }
+
+    #[cfg(feature = "tool-plugins")]
+    #[tokio::test]
+    async fn memory_growth_is_capped() {
+        let dir = TempDir::new().expect("temp dir");
+        let configs =
+            BTreeMap::from([("greedy".to_string(), plugin_config(&dir, GREEDY_WAT, None))]);
+        let (plugins, _) = ToolPlugins::load(&configs, &[]).await;
+
+        let plugin = plugins.get("greedy").expect("greedy plugin");
+        assert!(
+            plugin
+                .call("{}", dir.path(), &plugin.grants())
+                .await
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn grants_outside_the_sandbox_are_escalations() {
+        let dir = TempDir::new().expect("temp dir");
+        let outside = TempDir::new().expect("temp dir");
+        let grants = ToolPluginGrants {
+            workspace: ToolPluginAccess::Write,
+            read_paths: Vec::new(),
+            write_paths: vec![AbsolutePathBuf::try_from(outside.path()).expect("absolute path")],
+            network: true,
+        };
+
+        let workspace_write = SandboxPolicy::WorkspaceWrite {
+            writable_roots: Vec::new(),
+            read_only_access: ReadOnlyAccess::FullAccess,
+            network_access: false,
+            exclude_tmpdir_env_var: true,
+            exclude_slash_tmp: true,
+        };
+        assert_eq!(
+            grants.sandbox_escalations(&workspace_write, dir.path()),
+            vec![
+                format!("write to {}", outside.path().display()),
+                "use the network".to_string(),
+            ]
+        );
+        assert_eq!(
+            grants
+                .sandbox_escalations(&SandboxPolicy::new_read_only_policy(), dir.path())
+                .len(),
+            3
+        );
+        assert_eq!(
+            grants.sandbox_escalations(&SandboxPolicy::DangerFullAccess, dir.path()),
+            Vec::<String>::new()
+        );
+    }
+
+    #[tokio::test]
+    async fn invalid_plugins_are_reported_and_skipped() {
+        let dir = TempDir::new().expect("temp dir");
+        let configs =
+            BTreeMap::from([("bad name".to_string(), plugin_config(&dir, ECHO_WAT, None))]);
+        let (plugins, errors) = ToolPlugins::load(&configs, &[]).await;
+
+        assert!(plugins.specs().is_empty());
+        assert_eq!(errors.len(), 1);
+    }
+
+    #[cfg(feature = "tool-plugins")]
+    #[tokio::test]
+    async fn plugins_may_not_reuse_tool_names() {
+        let dir = TempDir::new().expect("temp dir");
+        let configs = BTreeMap::from([
+            ("shell".to_string(), plugin_config(&dir, ECHO_WAT, None)),
+            (
+                "mcp__docs__search".to_string(),
+                plugin_config(&dir, ECHO_WAT, None),
+            ),
+            ("lookup".to_string(), plugin_config(&dir, ECHO_WAT, None)),
+        ]);
+        let dynamic_tools = vec![DynamicToolSpec {
+            name: "lookup".to_string(),
+            description: "Look something up.".to_string(),
+            input_schema: serde_json::json!({ "type": "object", "properties": {} }),
+        }];
+        let (plugins, errors) = ToolPlugins::load(&configs, &dynamic_tools).await;
+
+        assert!(plugins.specs().is_empty());
+        assert_eq!(
+            errors,
+            vec![
+                "Failed to load tool plugin `lookup`: the name is already used by a dynamic tool"
+                    .to_string(),
+                "Failed to load tool plugin `mcp__docs__search`: the name is already used by MCP tools"
+                    .to_string(),
+                "Failed to load tool plugin `shell`: the name is already used by a built-in tool"
+                    .to_string(),
+            ]
+        );
+    }
+}
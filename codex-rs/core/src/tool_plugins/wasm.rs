@@ -0,0 +1,232 @@
+//! The wasmtime side of tool plugins: compiling a `.wasm` file and running it in a WASI sandbox.
+
+use std::path::Path;
+
+use wasmtime::Engine;
+use wasmtime::Linker;
+use wasmtime::Module;
+use wasmtime::Store;
+use wasmtime::StoreLimits;
+use wasmtime::StoreLimitsBuilder;
+use wasmtime::component::Component;
+use wasmtime::component::ResourceTable;
+use wasmtime_wasi::DirPerms;
+use wasmtime_wasi::FilePerms;
+use wasmtime_wasi::I32Exit;
+use wasmtime_wasi::WasiCtx;
+use wasmtime_wasi::WasiCtxBuilder;
+use wasmtime_wasi::WasiCtxView;
+use wasmtime_wasi::WasiView;
+use wasmtime_wasi::p1::WasiP1Ctx;
+use wasmtime_wasi::p2::bindings::Command;
+use wasmtime_wasi::p2::pipe::MemoryInputPipe;
+use wasmtime_wasi::p2::pipe::MemoryOutputPipe;
+
+use super::ToolPluginGrants;
+use crate::config::types::ToolPluginAccess;
+
+/// Cap on what a plugin may write to stdout or stderr.
+const MAX_OUTPUT_BYTES: usize = 1024 * 1024;
+/// Cap on each linear memory and table a plugin instance may grow.
+const MAX_MEMORY_BYTES: usize = 256 * 1024 * 1024;
+const MAX_TABLE_ELEMENTS: usize = 100_000;
+/// How much fuel a plugin burns between yields to the executor, so a runaway module can still
+/// be stopped by the call timeout.
+const FUEL_YIELD_INTERVAL: u64 = 100_000;
+
+/// The engine shared by every plugin in a session.
+#[derive(Clone)]
+pub(super) struct Runtime {
+    engine: Engine,
+}
+
+pub(super) enum Program {
+    Module(Module),
+    Component(Component),
+}
+
+/// What one run of a program produced.
+pub(super) struct RunOutput {
+    pub exit_code: i32,
+    pub stdout: Vec<u8>,
+    pub stderr: Vec<u8>,
+}
+
+impl Runtime {
+    pub(super) fn new() -> anyhow::Result<Self> {
+        let mut config = wasmtime::Config::new();
+        config.async_support(true).consume_fuel(true);
+        Ok(Self {
+            engine: Engine::new(&config)?,
+        })
+    }
+}
+
+impl Program {
+    pub(super) async fn compile(runtime: &Runtime, bytes: Vec<u8>) -> anyhow::Result<Self> {
+        let engine = runtime.engine.clone();
+        tokio::task::spawn_blocking(move || {
+            if is_component(&bytes) {
+                Component::new(&engine, &bytes).map(Program::Component)
+            } else {
+                Module::new(&engine, &bytes).map(Program::Module)
+            }
+        })
+        .await?
+    }
+
+    /// Instantiate the program with `stdin` and the directories and network in `grants`, and
+    /// run it to completion.
+    pub(super) async fn run(
+        &self,
+        runtime: &Runtime,
+        name: &str,
+        stdin: &str,
+        cwd: &Path,
+        grants: &ToolPluginGrants,
+    ) -> anyhow::Result<RunOutput> {
+        let stdout = MemoryOutputPipe::new(MAX_OUTPUT_BYTES);
+        let stderr = MemoryOutputPipe::new(MAX_OUTPUT_BYTES);
+        let mut wasi = wasi_builder(name, stdin, cwd, grants, &stdout, &stderr)?;
+        let engine = &runtime.engine;
+        let exit_code = match self {
+            Program::Module(module) => {
+                let mut linker: Linker<ModuleState> = Linker::new(engine);
+                wasmtime_wasi::p1::add_to_linker_async(&mut linker, |state| &mut state.wasi)?;
+                let mut store = Store::new(
+                    engine,
+                    ModuleState {
+                        wasi: wasi.build_p1(),
+                        limits: store_limits(),
+                    },
+                );
+                store.limiter(|state| &mut state.limits);
+                limit_fuel(&mut store)?;
+                let instance = linker.instantiate_async(&mut store, module).await?;
+                let start = instance.get_typed_func::<(), ()>(&mut store, "_start")?;
+                exit_code(start.call_async(&mut store, ()).await)?
+            }
+            Program::Component(component) => {
+                let mut linker = wasmtime::component::Linker::new(engine);
+                wasmtime_wasi::p2::add_to_linker_async(&mut linker)?;
+                let mut store = Store::new(
+                    engine,
+                    ComponentState {
+                        ctx: wasi.build(),
+                        table: ResourceTable::new(),
+                        limits: store_limits(),
+                    },
+                );
+                store.limiter(|state| &mut state.limits);
+                limit_fuel(&mut store)?;
+                let command = Command::instantiate_async(&mut store, component, &linker).await?;
+                match command.wasi_cli_run().call_run(&mut store).await {
+                    Ok(Ok(())) => 0,
+                    Ok(Err(())) => 1,
+                    Err(err) => exit_code(Err(err))?,
+                }
+            }
+        };
+        Ok(RunOutput {
+            exit_code,
+            stdout: stdout.contents().to_vec(),
+            stderr: stderr.contents().to_vec(),
+        })
+    }
+}
+
+fn wasi_builder(
+    name: &str,
+    stdin: &str,
+    cwd: &Path,
+    grants: &ToolPluginGrants,
+    stdout: &MemoryOutputPipe,
+    stderr: &MemoryOutputPipe,
+) -> anyhow::Result<WasiCtxBuilder> {
+    let mut builder = WasiCtxBuilder::new();
+    builder
+        .stdin(MemoryInputPipe::new(stdin.to_string()))
+        .stdout(stdout.clone())
+        .stderr(stderr.clone())
+        .arg(name);
+    match grants.workspace {
+        ToolPluginAccess::None => {}
+        ToolPluginAccess::Read => {
+            builder.preopened_dir(cwd, ".", DirPerms::READ, FilePerms::READ)?;
+        }
+        ToolPluginAccess::Write => {
+            builder.preopened_dir(cwd, ".", DirPerms::all(), FilePerms::all())?;
+        }
+    }
+    for path in &grants.read_paths {
+        builder.preopened_dir(
+            path.as_path(),
+            path.to_string_lossy(),
+            DirPerms::READ,
+            FilePerms::READ,
+        )?;
+    }
+    for path in &grants.write_paths {
+        builder.preopened_dir(
+            path.as_path(),
+            path.to_string_lossy(),
+            DirPerms::all(),
+            FilePerms::all(),
+        )?;
+    }
+    if grants.network {
+        builder.inherit_network().allow_ip_name_lookup(true);
+    }
+    Ok(builder)
+}
+
+struct ModuleState {
+    wasi: WasiP1Ctx,
+    limits: StoreLimits,
+}
+
+struct ComponentState {
+    ctx: WasiCtx,
+    table: ResourceTable,
+    limits: StoreLimits,
+}
+
+impl WasiView for ComponentState {
+    fn ctx(&mut self) -> WasiCtxView<'_> {
+        WasiCtxView {
+            ctx: &mut self.ctx,
+            table: &mut self.table,
+        }
+    }
+}
+
+fn store_limits() -> StoreLimits {
+    StoreLimitsBuilder::new()
+        .memory_size(MAX_MEMORY_BYTES)
+        .table_elements(MAX_TABLE_ELEMENTS)
+        .trap_on_grow_failure(true)
+        .build()
+}
+
+fn limit_fuel<T>(store: &mut Store<T>) -> anyhow::Result<()> {
+    store.set_fuel(u64::MAX)?;
+    store.fuel_async_yield_interval(Some(FUEL_YIELD_INTERVAL))?;
+    Ok(())
+}
+
+/// A guest calling `exit` surfaces as an [`I32Exit`] error; any other error is a real failure.
+fn exit_code(result: anyhow::Result<()>) -> anyhow::Result<i32> {
+    match result {
+        Ok(()) => Ok(0),
+        Err(err) => match err.downcast_ref::<I32Exit>() {
+            Some(exit) => Ok(exit.0),
+            None => Err(err),
+        },
+    }
+}
+
+/// Components use a different version/layer in the preamble than core modules
+/// (`\0asm` followed by `0d 00 01 00` instead of `01 00 00 00`).
+fn is_component(bytes: &[u8]) -> bool {
+    bytes.starts_with(b"\0asm") && bytes.get(4..8) == Some(&[0x0d, 0x00, 0x01, 0x00])
+}
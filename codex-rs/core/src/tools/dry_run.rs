@@ -0,0 +1,206 @@
+//! `--dry-run` sessions: mutating exec and patch calls are previewed instead of run.
+//!
+//! The call is reported to clients as a `DryRunAction` event and the model receives a simulated
+//! result, so it can keep working through its plan without touching the workspace. Read-only
+//! commands (the known-safe allowlist) still run normally so the model can explore.
+
+use std::path::Path;
+
+use codex_apply_patch::MaybeApplyPatchVerified;
+use codex_protocol::models::FunctionCallOutputBody;
+use codex_protocol::protocol::DryRunAction;
+use codex_protocol::protocol::DryRunActionEvent;
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::FileChange;
+use serde::Deserialize;
+
+use crate::apply_patch::convert_apply_patch_to_protocol;
+use crate::tools::context::ToolInvocation;
+use crate::tools::context::ToolOutput;
+use crate::tools::context::ToolPayload;
+
+/// Tools whose calls are previewed as commands.
+const EXEC_TOOLS: &[&str] = &[
+    "shell",
+    "container.exec",
+    "local_shell",
+    "shell_command",
+    "exec_command",
+];
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum CommandArg {
+    Argv(Vec<String>),
+    Script(String),
+}
+
+/// The argument shapes of the exec and patch tools that matter for a preview.
+#[derive(Deserialize)]
+struct PreviewArgs {
+    command: Option<CommandArg>,
+    cmd: Option<String>,
+    workdir: Option<String>,
+    login: Option<bool>,
+    input: Option<String>,
+}
+
+/// Stand in for a mutating tool call: report what it would have done and build the simulated
+/// result returned to the model.
+pub(crate) async fn simulate(invocation: &ToolInvocation) -> ToolOutput {
+    let message = match preview(invocation) {
+        Some(action) => {
+            let message = simulated_result(&action, &invocation.turn.cwd);
+            invocation
+                .session
+                .send_event(
+                    &invocation.turn,
+                    EventMsg::DryRunAction(DryRunActionEvent {
+                        call_id: invocation.call_id.clone(),
+                        turn_id: invocation.turn.sub_id.clone(),
+                        action,
+                    }),
+                )
+                .await;
+            message
+        }
+        None => format!(
+            "Dry run: `{}` was not called because it can change state. Continue with the rest of your plan.",
+            invocation.tool_name
+        ),
+    };
+    ToolOutput::Function {
+        body: FunctionCallOutputBody::Text(message),
+        success: Some(true),
+    }
+}
+
+fn preview(invocation: &ToolInvocation) -> Option<DryRunAction> {
+    let turn = &invocation.turn;
+    let tool_name = invocation.tool_name.as_str();
+    let (command, workdir) = match &invocation.payload {
+        ToolPayload::LocalShell { params } => (params.command.clone(), params.workdir.clone()),
+        ToolPayload::Custom { input } if tool_name == "apply_patch" => {
+            return patch_preview(input, &turn.cwd);
+        }
+        ToolPayload::Function { arguments } => {
+            let args: PreviewArgs = serde_json::from_str(arguments).ok()?;
+            if tool_name == "apply_patch" {
+                return patch_preview(&args.input?, &turn.cwd);
+            }
+            if !EXEC_TOOLS.contains(&tool_name) {
+                return None;
+            }
+            let command = match (args.command, args.cmd) {
+                (Some(CommandArg::Argv(argv)), _) => argv,
+                (Some(CommandArg::Script(script)), _) | (None, Some(script)) => invocation
+                    .session
+                    .user_shell()
+                    .derive_exec_args(&script, args.login.unwrap_or(true)),
+                (None, None) => return None,
+            };
+            (command, args.workdir)
+        }
+        ToolPayload::Custom { .. } | ToolPayload::Mcp { .. } => return None,
+    };
+
+    let cwd = turn.resolve_path(workdir);
+    // A shell call that runs `apply_patch` is shown as the patch it carries.
+    if let MaybeApplyPatchVerified::Body(action) =
+        codex_apply_patch::maybe_parse_apply_patch_verified(&command, &cwd)
+    {
+        return Some(DryRunAction::Patch {
+            changes: convert_apply_patch_to_protocol(&action),
+        });
+    }
+    Some(DryRunAction::Exec { command, cwd })
+}
+
+fn patch_preview(patch: &str, cwd: &Path) -> Option<DryRunAction> {
+    let command = vec!["apply_patch".to_string(), patch.to_string()];
+    match codex_apply_patch::maybe_parse_apply_patch_verified(&command, cwd) {
+        MaybeApplyPatchVerified::Body(action) => Some(DryRunAction::Patch {
+            changes: convert_apply_patch_to_protocol(&action),
+        }),
+        _ => None,
+    }
+}
+
+fn simulated_result(action: &DryRunAction, cwd: &Path) -> String {
+    match action {
+        DryRunAction::Exec { command, .. } => format!(
+            "Dry run: `{}` was not executed. Assume it succeeded and continue with the rest of your plan; its effects are not visible in the workspace.",
+            shlex::try_join(command.iter().map(String::as_str))
+                .unwrap_or_else(|_| command.join(" "))
+        ),
+        DryRunAction::Patch { changes } => {
+            let mut files: Vec<String> = changes
+                .iter()
+                .map(|(path, change)| {
+                    let verb = match change {
+                        FileChange::Add { .. } => "add",
+                        FileChange::Delete { .. } => "delete",
+                        FileChange::Update { .. } => "update",
+                    };
+                    let path = path.strip_prefix(cwd).unwrap_or(path);
+                    format!("{verb} {}", path.display())
+                })
+                .collect();
+            files.sort();
+            format!(
+                "Dry run: the patch was not applied. It would {}. The files on disk are unchanged; continue with the rest of your plan.",
+                files.join(", ")
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::collections::HashMap;
+    use std::path::PathBuf;
+
+    #[test]
+    fn simulated_results_describe_the_skipped_action() {
+        let cwd = PathBuf::from("/repo");
+        assert_eq!(
+            simulated_result(
+                &DryRunAction::Exec {
+                    command: vec![
+                        "rm".to_string(),
+                        "-rf".to_string(),
+                        "target dir".to_string()
+                    ],
+                    cwd: cwd.clone(),
+                },
+                &cwd,
+            ),
+            "Dry run: `rm -rf 'target dir'` was not executed. Assume it succeeded and continue with the rest of your plan; its effects are not visible in the workspace."
+        );
+        assert_eq!(
+            simulated_result(
+                &DryRunAction::Patch {
+                    changes: HashMap::from([
+                        (
+                            cwd.join("src/lib.rs"),
+                            FileChange::Update {
+                                unified_diff: String::new(),
+                                move_path: None,
+                            },
+                        ),
+                        (
+                            cwd.join("NOTES.md"),
+                            FileChange::Add {
+                                content: "notes\n".to_string(),
+                            },
+                        ),
+                    ]),
+                },
+                &cwd,
+            ),
+            "Dry run: the patch was not applied. It would add NOTES.md, update src/lib.rs. The files on disk are unchanged; continue with the rest of your plan."
+        );
+    }
+}
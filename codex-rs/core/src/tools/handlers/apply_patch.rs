@@ -1,6 +1,7 @@
 use codex_protocol::models::FunctionCallOutputBody;
 use std::collections::BTreeMap;
 use std::path::Path;
+use std::path::PathBuf;
 
 use crate::apply_patch;
 use crate::apply_patch::InternalApplyPatchInvocation;
@@ -20,6 +21,8 @@ use crate::tools::events::ToolEmitter;
 use crate::tools::events::ToolEventCtx;
 use crate::tools::handlers::parse_arguments;
 use crate::tools::orchestrator::ToolOrchestrator;
+use crate::tools::post_patch::append_post_patch_report;
+use crate::tools::post_patch::run_after_patch;
 use crate::tools::registry::ToolHandler;
 use crate::tools::registry::ToolKind;
 use crate::tools::runtimes::apply_patch::ApplyPatchRequest;
@@ -57,6 +60,26 @@ fn file_paths_for_action(action: &ApplyPatchAction) -> Vec<AbsolutePathBuf> {
     keys
 }
 
+/// Files that exist after the patch: added and updated files, at their destination if moved.
+fn changed_files_for_action(action: &ApplyPatchAction) -> Vec<PathBuf> {
+    let cwd = action.cwd.as_path();
+    let mut files: Vec<PathBuf> = action
+        .changes()
+        .iter()
+        .filter_map(|(path, change)| match change {
+            ApplyPatchFileChange::Delete { .. } => None,
+            ApplyPatchFileChange::Update {
+                move_path: Some(dest),
+                ..
+            } => to_abs_path(cwd, dest),
+            _ => to_abs_path(cwd, path),
+        })
+        .map(AbsolutePathBuf::into_path_buf)
+        .collect();
+    files.sort();
+    files
+}
+
 fn to_abs_path(cwd: &Path, path: &Path) -> Option<AbsolutePathBuf> {
     AbsolutePathBuf::resolve_path_against_base(path, cwd).ok()
 }
@@ -119,6 +142,7 @@ impl ToolHandler for ApplyPatchHandler {
                     InternalApplyPatchInvocation::DelegateToExec(apply) => {
                         let changes = convert_apply_patch_to_protocol(&apply.action);
                         let file_paths = file_paths_for_action(&apply.action);
+                        let changed_files = changed_files_for_action(&apply.action);
                         let emitter =
                             ToolEmitter::apply_patch(changes.clone(), apply.auto_approved);
                         let event_ctx = ToolEventCtx::new(
@@ -163,8 +187,17 @@ impl ToolHandler for ApplyPatchHandler {
                             Some(&tracker),
                         );
                         let content = emitter.finish(event_ctx, out).await?;
+                        let report = run_after_patch(
+                            session.as_ref(),
+                            turn.as_ref(),
+                            &call_id,
+                            &changed_files,
+                        )
+                        .await;
                         Ok(ToolOutput::Function {
-                            body: FunctionCallOutputBody::Text(content),
+                            body: FunctionCallOutputBody::Text(append_post_patch_report(
+                                content, report,
+                            )),
                             success: Some(true),
                         })
                     }
@@ -222,6 +255,7 @@ pub(crate) async fn intercept_apply_patch(
                 InternalApplyPatchInvocation::DelegateToExec(apply) => {
                     let changes = convert_apply_patch_to_protocol(&apply.action);
                     let approval_keys = file_paths_for_action(&apply.action);
+                    let changed_files = changed_files_for_action(&apply.action);
                     let emitter = ToolEmitter::apply_patch(changes.clone(), apply.auto_approved);
                     let event_ctx = ToolEventCtx::new(
                         session.as_ref(),
@@ -265,8 +299,13 @@ pub(crate) async fn intercept_apply_patch(
                         tracker.as_ref().copied(),
                     );
                     let content = emitter.finish(event_ctx, out).await?;
+                    let report =
+                        run_after_patch(session.as_ref(), turn.as_ref(), call_id, &changed_files)
+                            .await;
                     Ok(Some(ToolOutput::Function {
-                        body: FunctionCallOutputBody::Text(content),
+                        body: FunctionCallOutputBody::Text(append_post_patch_report(
+                            content, report,
+                        )),
                         success: Some(true),
                     }))
                 }
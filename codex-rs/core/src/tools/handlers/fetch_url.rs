@@ -0,0 +1,96 @@
+use async_trait::async_trait;
+use codex_protocol::models::FunctionCallOutputBody;
+use codex_protocol::protocol::AskForApproval;
+use codex_protocol::protocol::ReviewDecision;
+use serde::Deserialize;
+
+use crate::fetch_url;
+use crate::function_tool::FunctionCallError;
+use crate::tools::context::ToolInvocation;
+use crate::tools::context::ToolOutput;
+use crate::tools::context::ToolPayload;
+use crate::tools::handlers::parse_arguments;
+use crate::tools::registry::ToolHandler;
+use crate::tools::registry::ToolKind;
+
+pub struct FetchUrlHandler;
+
+pub(crate) const FETCH_URL_TOOL_NAME: &str = "fetch_url";
+
+#[derive(Deserialize)]
+struct FetchUrlArgs {
+    url: String,
+}
+
+#[async_trait]
+impl ToolHandler for FetchUrlHandler {
+    fn kind(&self) -> ToolKind {
+        ToolKind::Function
+    }
+
+    async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
+        let ToolInvocation {
+            session,
+            turn,
+            call_id,
+            payload,
+            ..
+        } = invocation;
+
+        let arguments = match payload {
+            ToolPayload::Function { arguments } => arguments,
+            _ => {
+                return Err(FunctionCallError::RespondToModel(format!(
+                    "{FETCH_URL_TOOL_NAME} handler received unsupported payload"
+                )));
+            }
+        };
+
+        let args: FetchUrlArgs = parse_arguments(&arguments)?;
+        let url = fetch_url::parse_allowed_url(&args.url, &turn.config.fetch_url.allowed_domains)
+            .map_err(FunctionCallError::RespondToModel)?;
+
+        // An allowlisted domain is not enough on its own: a sandbox without network access
+        // still needs the user to sign off on each fetch.
+        if !turn.sandbox_policy.get().has_full_network_access() {
+            if matches!(turn.approval_policy.value(), AskForApproval::Never) {
+                return Err(FunctionCallError::RespondToModel(
+                    "fetch_url is unavailable: the sandbox policy blocks network access"
+                        .to_string(),
+                ));
+            }
+            let decision = session
+                .request_command_approval(
+                    turn.as_ref(),
+                    call_id,
+                    None,
+                    vec![FETCH_URL_TOOL_NAME.to_string(), url.to_string()],
+                    turn.cwd.clone(),
+                    Some(format!("Fetch {url} for the model?")),
+                    None,
+                    None,
+                    None,
+                    None,
+                )
+                .await;
+            if matches!(
+                decision,
+                ReviewDecision::Denied
+                    | ReviewDecision::Abort
+                    | ReviewDecision::NetworkPolicyAmendment { .. }
+            ) {
+                return Err(FunctionCallError::RespondToModel(format!(
+                    "the user declined to fetch {url}"
+                )));
+            }
+        }
+
+        let markdown = fetch_url::fetch_markdown(&turn.config.fetch_url, &url)
+            .await
+            .map_err(|err| FunctionCallError::RespondToModel(format!("fetch failed: {err}")))?;
+        Ok(ToolOutput::Function {
+            body: FunctionCallOutputBody::Text(markdown),
+            success: Some(true),
+        })
+    }
+}
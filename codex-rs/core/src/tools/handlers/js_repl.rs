@@ -106,6 +106,11 @@ impl ToolHandler for JsReplHandler {
         )
     }
 
+    /// The kernel can write files and run processes, so it never counts as read-only.
+    async fn is_mutating(&self, _invocation: &ToolInvocation) -> bool {
+        true
+    }
+
     async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
         let ToolInvocation {
             session,
@@ -3,6 +3,7 @@ use std::sync::Arc;
 
 use crate::function_tool::FunctionCallError;
 use crate::mcp_tool_call::handle_mcp_tool_call;
+use crate::mcp_tool_call::mcp_tool_is_read_only;
 use crate::tools::context::ToolInvocation;
 use crate::tools::context::ToolOutput;
 use crate::tools::context::ToolPayload;
@@ -18,6 +19,13 @@ impl ToolHandler for McpHandler {
         ToolKind::Mcp
     }
 
+    async fn is_mutating(&self, invocation: &ToolInvocation) -> bool {
+        let ToolPayload::Mcp { server, tool, .. } = &invocation.payload else {
+            return true;
+        };
+        !mcp_tool_is_read_only(invocation.session.as_ref(), server, tool).await
+    }
+
     async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
         let ToolInvocation {
             session,
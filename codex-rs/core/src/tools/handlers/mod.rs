@@ -2,6 +2,7 @@ pub(crate) mod agent_jobs;
 pub mod apply_patch;
 mod artifacts;
 mod dynamic;
+mod fetch_url;
 mod grep_files;
 mod js_repl;
 mod list_dir;
@@ -12,12 +13,17 @@ mod plan;
 mod read_file;
 mod request_user_input;
 mod search_tool_bm25;
+mod search_web;
+mod semantic_search;
 mod shell;
 mod test_sync;
+mod todos;
+mod tool_plugin;
 pub(crate) mod unified_exec;
 mod view_image;
 
 use codex_utils_absolute_path::AbsolutePathBufGuard;
+pub(crate) use plan::PLAN_APPROVAL_REQUIRED_MESSAGE;
 pub use plan::PLAN_TOOL;
 use serde::Deserialize;
 use serde_json::Value;
@@ -32,6 +38,8 @@ pub use artifacts::ArtifactsHandler;
 use codex_protocol::models::PermissionProfile;
 use codex_protocol::protocol::AskForApproval;
 pub use dynamic::DynamicToolHandler;
+pub(crate) use fetch_url::FETCH_URL_TOOL_NAME;
+pub use fetch_url::FetchUrlHandler;
 pub use grep_files::GrepFilesHandler;
 pub use js_repl::JsReplHandler;
 pub use js_repl::JsReplResetHandler;
@@ -46,9 +54,16 @@ pub(crate) use request_user_input::request_user_input_tool_description;
 pub(crate) use search_tool_bm25::DEFAULT_LIMIT as SEARCH_TOOL_BM25_DEFAULT_LIMIT;
 pub(crate) use search_tool_bm25::SEARCH_TOOL_BM25_TOOL_NAME;
 pub use search_tool_bm25::SearchToolBm25Handler;
+pub(crate) use search_web::SEARCH_WEB_TOOL_NAME;
+pub use search_web::SearchWebHandler;
+pub(crate) use semantic_search::SEMANTIC_SEARCH_TOOL_NAME;
+pub use semantic_search::SemanticSearchHandler;
 pub use shell::ShellCommandHandler;
 pub use shell::ShellHandler;
 pub use test_sync::TestSyncHandler;
+pub use todos::TodoHandler;
+pub(crate) use todos::UPDATE_TODOS_TOOL_NAME;
+pub use tool_plugin::ToolPluginHandler;
 pub use unified_exec::UnifiedExecHandler;
 pub use view_image::ViewImageHandler;
 
@@ -14,11 +14,19 @@ use codex_protocol::config_types::ModeKind;
 use codex_protocol::models::FunctionCallOutputBody;
 use codex_protocol::plan_tool::UpdatePlanArgs;
 use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::ReviewDecision;
 use std::collections::BTreeMap;
+use std::sync::Arc;
 use std::sync::LazyLock;
 
 pub struct PlanHandler;
 
+/// Returned in place of running a mutating tool while `require_plan_approval` is waiting on the
+/// user.
+pub(crate) const PLAN_APPROVAL_REQUIRED_MESSAGE: &str = "The user must approve a plan before \
+commands or edits that change the workspace can run. Present your plan with update_plan and \
+wait for the user's decision.";
+
 pub static PLAN_TOOL: LazyLock<ToolSpec> = LazyLock::new(|| {
     let mut plan_item_props = BTreeMap::new();
     plan_item_props.insert("step".to_string(), JsonSchema::String { description: None });
@@ -85,8 +93,7 @@ impl ToolHandler for PlanHandler {
             }
         };
 
-        let content =
-            handle_update_plan(session.as_ref(), turn.as_ref(), arguments, call_id).await?;
+        let content = handle_update_plan(&session, turn.as_ref(), arguments, call_id).await?;
 
         Ok(ToolOutput::Function {
             body: FunctionCallOutputBody::Text(content),
@@ -99,10 +106,10 @@ impl ToolHandler for PlanHandler {
 /// So it's the _inputs_ to this function that are useful to clients, not the outputs and neither are actually useful for the model other
 /// than forcing it to come up and document a plan (TBD how that affects performance).
 pub(crate) async fn handle_update_plan(
-    session: &Session,
+    session: &Arc<Session>,
     turn_context: &TurnContext,
     arguments: String,
-    _call_id: String,
+    call_id: String,
 ) -> Result<String, FunctionCallError> {
     if turn_context.collaboration_mode.mode == ModeKind::Plan {
         return Err(FunctionCallError::RespondToModel(
@@ -110,10 +117,37 @@ pub(crate) async fn handle_update_plan(
         ));
     }
     let args = parse_update_plan_arguments(&arguments)?;
+    let awaiting_approval = session.awaiting_plan_approval(turn_context).await;
     session
-        .send_event(turn_context, EventMsg::PlanUpdate(args))
+        .send_event(turn_context, EventMsg::PlanUpdate(args.clone()))
         .await;
-    Ok("Plan updated".to_string())
+    if !awaiting_approval {
+        return Ok("Plan updated".to_string());
+    }
+    // The decision is returned as the tool output, which is what records it in the rollout.
+    match session
+        .request_plan_approval(turn_context, call_id, args)
+        .await
+    {
+        ReviewDecision::Approved | ReviewDecision::ApprovedForSession => Ok(
+            "The user approved the plan. You may now run commands and edit files to carry it out."
+                .to_string(),
+        ),
+        ReviewDecision::Abort => {
+            // Stop the turn as an aborted exec approval does. The interrupt cancels this very
+            // task, so it cannot be awaited here.
+            let session = Arc::clone(session);
+            tokio::spawn(async move { session.interrupt_task().await });
+            Err(FunctionCallError::RespondToModel(
+                "The user rejected the plan and stopped the turn.".to_string(),
+            ))
+        }
+        _ => Err(FunctionCallError::RespondToModel(
+            "The user rejected the plan. Ask what to change, then present a revised plan with \
+update_plan."
+                .to_string(),
+        )),
+    }
 }
 
 fn parse_update_plan_arguments(arguments: &str) -> Result<UpdatePlanArgs, FunctionCallError> {
@@ -0,0 +1,98 @@
+use async_trait::async_trait;
+use codex_protocol::items::TurnItem;
+use codex_protocol::items::WebSearchItem;
+use codex_protocol::models::FunctionCallOutputBody;
+use codex_protocol::models::WebSearchAction;
+use serde::Deserialize;
+
+use crate::function_tool::FunctionCallError;
+use crate::tools::context::ToolInvocation;
+use crate::tools::context::ToolOutput;
+use crate::tools::context::ToolPayload;
+use crate::tools::handlers::parse_arguments;
+use crate::tools::registry::ToolHandler;
+use crate::tools::registry::ToolKind;
+use crate::web_search_provider;
+
+pub struct SearchWebHandler;
+
+pub(crate) const SEARCH_WEB_TOOL_NAME: &str = "search_web";
+
+/// Upper bound on `max_results`, whatever the model asks for.
+const MAX_RESULTS_LIMIT: usize = 20;
+
+#[derive(Deserialize)]
+struct SearchWebArgs {
+    query: String,
+    #[serde(default)]
+    max_results: Option<usize>,
+}
+
+#[async_trait]
+impl ToolHandler for SearchWebHandler {
+    fn kind(&self) -> ToolKind {
+        ToolKind::Function
+    }
+
+    async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
+        let ToolInvocation {
+            session,
+            turn,
+            call_id,
+            payload,
+            ..
+        } = invocation;
+
+        let arguments = match payload {
+            ToolPayload::Function { arguments } => arguments,
+            _ => {
+                return Err(FunctionCallError::RespondToModel(format!(
+                    "{SEARCH_WEB_TOOL_NAME} handler received unsupported payload"
+                )));
+            }
+        };
+
+        let args: SearchWebArgs = parse_arguments(&arguments)?;
+        let query = args.query.trim().to_string();
+        if query.is_empty() {
+            return Err(FunctionCallError::RespondToModel(
+                "query must not be empty".to_string(),
+            ));
+        }
+        let config = &turn.config.web_search_provider;
+        let limit = args
+            .max_results
+            .unwrap_or(config.max_results)
+            .clamp(1, MAX_RESULTS_LIMIT);
+
+        let mut item = WebSearchItem {
+            id: call_id,
+            query: query.clone(),
+            action: WebSearchAction::Search {
+                query: Some(query.clone()),
+                queries: None,
+            },
+            sources: Vec::new(),
+        };
+        session
+            .emit_turn_item_started(turn.as_ref(), &TurnItem::WebSearch(item.clone()))
+            .await;
+        let outcome = web_search_provider::search(config, &query, limit).await;
+        if let Ok(results) = &outcome {
+            item.sources = results.iter().map(|result| result.source()).collect();
+        }
+        session
+            .emit_turn_item_completed(turn.as_ref(), TurnItem::WebSearch(item))
+            .await;
+
+        let results = outcome.map_err(|err| {
+            FunctionCallError::RespondToModel(format!("web search unavailable: {err}"))
+        })?;
+        Ok(ToolOutput::Function {
+            body: FunctionCallOutputBody::Text(web_search_provider::format_results(
+                &query, &results,
+            )),
+            success: Some(true),
+        })
+    }
+}
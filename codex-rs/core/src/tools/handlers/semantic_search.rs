@@ -0,0 +1,172 @@
+use async_trait::async_trait;
+use codex_protocol::models::FunctionCallOutputBody;
+use serde::Deserialize;
+
+use crate::function_tool::FunctionCallError;
+use crate::semantic_search;
+use crate::tools::context::ToolInvocation;
+use crate::tools::context::ToolOutput;
+use crate::tools::context::ToolPayload;
+use crate::tools::handlers::parse_arguments;
+use crate::tools::registry::ToolHandler;
+use crate::tools::registry::ToolKind;
+
+pub struct SemanticSearchHandler;
+
+pub(crate) const SEMANTIC_SEARCH_TOOL_NAME: &str = "semantic_search";
+
+fn default_limit() -> usize {
+    semantic_search::DEFAULT_RESULT_LIMIT
+}
+
+#[derive(Deserialize)]
+struct SemanticSearchArgs {
+    query: String,
+    #[serde(default = "default_limit")]
+    limit: usize,
+}
+
+#[async_trait]
+impl ToolHandler for SemanticSearchHandler {
+    fn kind(&self) -> ToolKind {
+        ToolKind::Function
+    }
+
+    async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
+        let ToolInvocation { payload, turn, .. } = invocation;
+
+        let arguments = match payload {
+            ToolPayload::Function { arguments } => arguments,
+            _ => {
+                return Err(FunctionCallError::RespondToModel(format!(
+                    "{SEMANTIC_SEARCH_TOOL_NAME} handler received unsupported payload"
+                )));
+            }
+        };
+
+        let args: SemanticSearchArgs = parse_arguments(&arguments)?;
+        let query = args.query.trim();
+        if query.is_empty() {
+            return Err(FunctionCallError::RespondToModel(
+                "query must not be empty".to_string(),
+            ));
+        }
+        if args.limit == 0 {
+            return Err(FunctionCallError::RespondToModel(
+                "limit must be greater than zero".to_string(),
+            ));
+        }
+
+        let root = semantic_search::index_root(&turn.config);
+        let matches = semantic_search::search(
+            root,
+            &turn.config.codex_home,
+            &turn.config.semantic_search,
+            query,
+            args.limit,
+        )
+        .await
+        .map_err(|err| {
+            FunctionCallError::RespondToModel(format!(
+                "semantic search unavailable: {err:#}. Fall back to grep or file listing."
+            ))
+        })?;
+
+        if matches.is_empty() {
+            return Ok(ToolOutput::Function {
+                body: FunctionCallOutputBody::Text("No matches found.".to_string()),
+                success: Some(true),
+            });
+        }
+
+        let output = matches
+            .iter()
+            .map(|m| {
+                let snippet = m
+                    .snippet
+                    .lines()
+                    .map(|line| format!("    {line}"))
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                format!(
+                    "{}:{}-{} (score {:.2})\n{snippet}",
+                    root.join(&m.path).display(),
+                    m.start_line,
+                    m.end_line,
+                    m.score
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n\n");
+        Ok(ToolOutput::Function {
+            body: FunctionCallOutputBody::Text(output),
+            success: Some(true),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codex::make_session_and_context;
+    use crate::config::types::SemanticSearchConfig;
+    use crate::turn_diff_tracker::TurnDiffTracker;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use std::sync::Arc;
+    use tempfile::TempDir;
+    use tokio::sync::Mutex;
+
+    #[tokio::test]
+    async fn searches_the_startup_index_after_the_turn_cwd_changes() -> anyhow::Result<()> {
+        let workspace = TempDir::new()?;
+        let codex_home = TempDir::new()?;
+        std::fs::write(
+            workspace.path().join("auth.rs"),
+            "fn refresh_access_token(token: &RefreshToken) {\n    // refresh the token\n}\n",
+        )?;
+        let nested = workspace.path().join("nested");
+        std::fs::create_dir(&nested)?;
+        semantic_search::refresh_index(
+            workspace.path(),
+            codex_home.path(),
+            &SemanticSearchConfig::default(),
+        )
+        .await?;
+
+        let (session, mut turn) = make_session_and_context().await;
+        let mut config = (*turn.config).clone();
+        config.cwd = workspace.path().to_path_buf();
+        config.codex_home = codex_home.path().to_path_buf();
+        turn.config = Arc::new(config);
+        turn.cwd = nested;
+        let invocation = ToolInvocation {
+            session: Arc::new(session),
+            turn: Arc::new(turn),
+            tracker: Arc::new(Mutex::new(TurnDiffTracker::default())),
+            call_id: "call-1".to_string(),
+            tool_name: SEMANTIC_SEARCH_TOOL_NAME.to_string(),
+            payload: ToolPayload::Function {
+                arguments: json!({ "query": "refresh token", "limit": 1 }).to_string(),
+            },
+        };
+
+        let ToolOutput::Function {
+            body: FunctionCallOutputBody::Text(output),
+            success,
+        } = SemanticSearchHandler.handle(invocation).await?
+        else {
+            panic!("expected a function output");
+        };
+        assert_eq!(success, Some(true));
+        let header = output.lines().next().unwrap_or_default();
+        assert!(
+            header.starts_with(&format!(
+                "{}:1-3 ",
+                workspace.path().join("auth.rs").display()
+            )),
+            "unexpected output: {output}"
+        );
+        Ok(())
+    }
+}
@@ -3,10 +3,12 @@ use codex_protocol::ThreadId;
 use codex_protocol::models::FunctionCallOutputBody;
 use codex_protocol::models::ShellCommandToolCallParams;
 use codex_protocol::models::ShellToolCallParams;
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use crate::codex::TurnContext;
 use crate::exec::ExecParams;
+use crate::exec_env::apply_force_color_env;
 use crate::exec_env::create_env;
 use crate::exec_policy::ExecApprovalRequest;
 use crate::features::Feature;
@@ -59,6 +61,15 @@ struct RunExecLikeArgs {
     shell_runtime_backend: ShellRuntimeBackend,
 }
 
+/// Environment for a command the model asked to run.
+fn agent_command_env(turn_context: &TurnContext, thread_id: ThreadId) -> HashMap<String, String> {
+    let mut env = create_env(&turn_context.shell_environment_policy, Some(thread_id));
+    if turn_context.features.enabled(Feature::ColoredExecOutput) {
+        apply_force_color_env(&mut env);
+    }
+    env
+}
+
 impl ShellHandler {
     fn to_exec_params(
         params: &ShellToolCallParams,
@@ -69,7 +80,7 @@ impl ShellHandler {
             command: params.command.clone(),
             cwd: turn_context.resolve_path(params.workdir.clone()),
             expiration: params.timeout_ms.into(),
-            env: create_env(&turn_context.shell_environment_policy, Some(thread_id)),
+            env: agent_command_env(turn_context, thread_id),
             network: turn_context.network.clone(),
             sandbox_permissions: params.sandbox_permissions.unwrap_or_default(),
             windows_sandbox_level: turn_context.windows_sandbox_level,
@@ -119,7 +130,7 @@ impl ShellCommandHandler {
             command,
             cwd: turn_context.resolve_path(params.workdir.clone()),
             expiration: params.timeout_ms.into(),
-            env: create_env(&turn_context.shell_environment_policy, Some(thread_id)),
+            env: agent_command_env(turn_context, thread_id),
             network: turn_context.network.clone(),
             sandbox_permissions: params.sandbox_permissions.unwrap_or_default(),
             windows_sandbox_level: turn_context.windows_sandbox_level,
@@ -177,12 +188,14 @@ impl ToolHandler for ShellHandler {
 
         match payload {
             ToolPayload::Function { arguments } => {
-                let cwd = resolve_workdir_base_path(&arguments, turn.cwd.as_path())?;
+                let agent_cwd = session.agent_cwd(turn.as_ref()).await;
+                let cwd = resolve_workdir_base_path(&arguments, agent_cwd.as_path())?;
                 let params: ShellToolCallParams =
                     parse_arguments_with_base_path(&arguments, cwd.as_path())?;
                 let prefix_rule = params.prefix_rule.clone();
-                let exec_params =
+                let mut exec_params =
                     Self::to_exec_params(&params, turn.as_ref(), session.conversation_id);
+                exec_params.cwd = cwd;
                 Self::run_exec_like(RunExecLikeArgs {
                     tool_name: tool_name.clone(),
                     exec_params,
@@ -269,7 +282,8 @@ impl ToolHandler for ShellCommandHandler {
             )));
         };
 
-        let cwd = resolve_workdir_base_path(&arguments, turn.cwd.as_path())?;
+        let agent_cwd = session.agent_cwd(turn.as_ref()).await;
+        let cwd = resolve_workdir_base_path(&arguments, agent_cwd.as_path())?;
         let params: ShellCommandToolCallParams =
             parse_arguments_with_base_path(&arguments, cwd.as_path())?;
         maybe_emit_implicit_skill_invocation(
@@ -280,13 +294,14 @@ impl ToolHandler for ShellCommandHandler {
         )
         .await;
         let prefix_rule = params.prefix_rule.clone();
-        let exec_params = Self::to_exec_params(
+        let mut exec_params = Self::to_exec_params(
             &params,
             session.as_ref(),
             turn.as_ref(),
             session.conversation_id,
             turn.tools_config.allow_login_shell,
         )?;
+        exec_params.cwd = cwd;
         ShellHandler::run_exec_like(RunExecLikeArgs {
             tool_name,
             exec_params,
@@ -432,8 +447,22 @@ impl ShellHandler {
             )
             .await
             .map(|result| result.output);
+        let next_cwd = match &out {
+            Ok(output) if output.exit_code == 0 => {
+                session
+                    .track_agent_cwd(turn.as_ref(), &exec_params.command, &exec_params.cwd)
+                    .await
+            }
+            _ => None,
+        };
         let event_ctx = ToolEventCtx::new(session.as_ref(), turn.as_ref(), &call_id, None);
-        let content = emitter.finish(event_ctx, out).await?;
+        let mut content = emitter.finish(event_ctx, out).await?;
+        if let Some(next_cwd) = next_cwd {
+            content.push_str(&format!(
+                "\nWorking directory for the next command: {}",
+                next_cwd.display()
+            ));
+        }
         Ok(ToolOutput::Function {
             body: FunctionCallOutputBody::Text(content),
             success: Some(true),
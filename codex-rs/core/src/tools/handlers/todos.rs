@@ -0,0 +1,51 @@
+use async_trait::async_trait;
+use codex_protocol::models::FunctionCallOutputBody;
+
+use crate::function_tool::FunctionCallError;
+use crate::todo_list::TodoListUpdate;
+use crate::tools::context::ToolInvocation;
+use crate::tools::context::ToolOutput;
+use crate::tools::context::ToolPayload;
+use crate::tools::handlers::parse_arguments;
+use crate::tools::registry::ToolHandler;
+use crate::tools::registry::ToolKind;
+
+pub struct TodoHandler;
+
+pub(crate) const UPDATE_TODOS_TOOL_NAME: &str = "update_todos";
+
+#[async_trait]
+impl ToolHandler for TodoHandler {
+    fn kind(&self) -> ToolKind {
+        ToolKind::Function
+    }
+
+    async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
+        let ToolInvocation {
+            session,
+            turn,
+            payload,
+            ..
+        } = invocation;
+
+        let arguments = match payload {
+            ToolPayload::Function { arguments } => arguments,
+            _ => {
+                return Err(FunctionCallError::RespondToModel(format!(
+                    "{UPDATE_TODOS_TOOL_NAME} handler received unsupported payload"
+                )));
+            }
+        };
+
+        let update: TodoListUpdate = parse_arguments(&arguments)?;
+        let list = session
+            .update_todo_list(turn.as_ref(), update)
+            .await
+            .map_err(FunctionCallError::RespondToModel)?;
+
+        Ok(ToolOutput::Function {
+            body: FunctionCallOutputBody::Text(list),
+            success: Some(true),
+        })
+    }
+}
@@ -0,0 +1,127 @@
+use async_trait::async_trait;
+use codex_protocol::models::FunctionCallOutputBody;
+use codex_protocol::protocol::AskForApproval;
+use codex_protocol::protocol::ReviewDecision;
+
+use crate::function_tool::FunctionCallError;
+use crate::tools::context::ToolInvocation;
+use crate::tools::context::ToolOutput;
+use crate::tools::context::ToolPayload;
+use crate::tools::registry::ToolHandler;
+use crate::tools::registry::ToolKind;
+use crate::tools::sandboxing::with_cached_approval;
+
+/// Runs `[tool_plugins]` entries in their WASI sandbox. Grants the turn's sandbox policy would
+/// not allow need the user's approval first, as does any write access under `untrusted`.
+pub struct ToolPluginHandler;
+
+#[async_trait]
+impl ToolHandler for ToolPluginHandler {
+    fn kind(&self) -> ToolKind {
+        ToolKind::Function
+    }
+
+    async fn is_mutating(&self, invocation: &ToolInvocation) -> bool {
+        invocation
+            .session
+            .services
+            .tool_plugins
+            .get(&invocation.tool_name)
+            .is_none_or(|plugin| plugin.grants().is_mutating())
+    }
+
+    async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
+        let ToolInvocation {
+            session,
+            turn,
+            call_id,
+            tool_name,
+            payload,
+            ..
+        } = invocation;
+
+        let arguments = match payload {
+            ToolPayload::Function { arguments } => arguments,
+            _ => {
+                return Err(FunctionCallError::RespondToModel(
+                    "tool plugin handler received unsupported payload".to_string(),
+                ));
+            }
+        };
+
+        let plugin = session
+            .services
+            .tool_plugins
+            .get(&tool_name)
+            .ok_or_else(|| {
+                FunctionCallError::RespondToModel(format!("unknown tool plugin `{tool_name}`"))
+            })?;
+        let grants = plugin.grants();
+        let escalations = grants.sandbox_escalations(turn.sandbox_policy.get(), &turn.cwd);
+        let approval_policy = turn.approval_policy.value();
+        let needs_approval = !escalations.is_empty()
+            || (grants.is_mutating() && approval_policy == AskForApproval::UnlessTrusted);
+        if needs_approval {
+            if approval_policy == AskForApproval::Never {
+                return Err(FunctionCallError::RespondToModel(format!(
+                    "tool plugin `{tool_name}` is unavailable: the sandbox policy does not let it {}",
+                    escalations.join(" or ")
+                )));
+            }
+            let reason = if escalations.is_empty() {
+                format!("Run tool plugin `{tool_name}`, which can change files or use the network?")
+            } else {
+                format!(
+                    "Let tool plugin `{tool_name}` {}?",
+                    escalations.join(" and ")
+                )
+            };
+            let command = vec![tool_name.clone(), arguments.clone()];
+            let keys = vec![(tool_name.clone(), escalations)];
+            let approval_session = session.clone();
+            let approval_turn = turn.clone();
+            let decision =
+                with_cached_approval(&session.services, "tool_plugin", keys, || async move {
+                    approval_session
+                        .request_command_approval(
+                            approval_turn.as_ref(),
+                            call_id,
+                            None,
+                            command,
+                            approval_turn.cwd.clone(),
+                            Some(reason),
+                            None,
+                            None,
+                            None,
+                            None,
+                        )
+                        .await
+                })
+                .await;
+            if matches!(
+                decision,
+                ReviewDecision::Denied
+                    | ReviewDecision::Abort
+                    | ReviewDecision::NetworkPolicyAmendment { .. }
+            ) {
+                return Err(FunctionCallError::RespondToModel(format!(
+                    "the user declined to run tool plugin `{tool_name}`"
+                )));
+            }
+        }
+
+        let output = plugin
+            .call(&arguments, &turn.cwd, &grants)
+            .await
+            .map_err(|err| {
+                FunctionCallError::RespondToModel(format!(
+                    "tool plugin `{tool_name}` failed: {err:#}"
+                ))
+            })?;
+
+        Ok(ToolOutput::Function {
+            body: FunctionCallOutputBody::Text(output.output),
+            success: Some(output.success),
+        })
+    }
+}
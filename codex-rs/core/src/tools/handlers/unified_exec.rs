@@ -25,6 +25,7 @@ use crate::unified_exec::WriteStdinRequest;
 use async_trait::async_trait;
 use codex_protocol::models::FunctionCallOutputBody;
 use codex_protocol::models::PermissionProfile;
+use codex_utils_string::strip_ansi_escapes;
 use serde::Deserialize;
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -331,7 +332,7 @@ fn format_response(response: &UnifiedExecResponse) -> String {
     }
 
     sections.push("Output:".to_string());
-    sections.push(response.output.clone());
+    sections.push(strip_ansi_escapes(&response.output));
 
     sections.join("\n")
 }
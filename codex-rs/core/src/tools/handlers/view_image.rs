@@ -89,7 +89,7 @@ impl ToolHandler for ViewImageHandler {
         let image_mode = if use_original_detail {
             PromptImageMode::Original
         } else {
-            PromptImageMode::ResizeToFit
+            PromptImageMode::Downscale(turn.config.image_upload)
         };
         let image_detail = use_original_detail.then_some(ImageDetail::Original);
 
@@ -1,10 +1,13 @@
 pub mod context;
+pub(crate) mod dry_run;
 pub mod events;
 pub(crate) mod handlers;
 pub mod js_repl;
 pub(crate) mod network_approval;
 pub mod orchestrator;
 pub mod parallel;
+pub(crate) mod policy;
+pub(crate) mod post_patch;
 pub mod registry;
 pub mod router;
 pub mod runtimes;
@@ -15,6 +18,7 @@ use crate::exec::ExecToolCallOutput;
 use crate::truncate::TruncationPolicy;
 use crate::truncate::formatted_truncate_text;
 use crate::truncate::truncate_text;
+use codex_utils_string::strip_ansi_escapes;
 pub use router::ToolRouter;
 use serde::Serialize;
 
@@ -103,14 +107,17 @@ pub fn format_exec_output_str(
 }
 
 /// Extracts exec output content and prepends a timeout message if the command timed out.
+///
+/// ANSI escapes only cost tokens and obscure the text for the model, so they are stripped; the
+/// TUI keeps the raw output so it can render colors.
 fn build_content_with_timeout(exec_output: &ExecToolCallOutput) -> String {
+    let output = strip_ansi_escapes(&exec_output.aggregated_output.text);
     if exec_output.timed_out {
         format!(
-            "command timed out after {} milliseconds\n{}",
+            "command timed out after {} milliseconds\n{output}",
             exec_output.duration.as_millis(),
-            exec_output.aggregated_output.text
         )
     } else {
-        exec_output.aggregated_output.text.clone()
+        output
     }
 }
@@ -0,0 +1,439 @@
+//! Enforcement of `[tool_policies]` before a tool call is dispatched.
+
+use std::collections::BTreeMap;
+
+use serde_json::Value;
+use wildmatch::WildMatch;
+
+use crate::config::types::ToolPolicyConfig;
+use crate::parse_command::extract_shell_command;
+use crate::tools::context::ToolPayload;
+
+/// The policy whose `command` patterns apply to every tool that runs a command.
+const SHELL_POLICY: &str = "shell";
+
+/// Tools that run a command, with the argument that holds it.
+const COMMAND_TOOLS: &[(&str, &str)] = &[
+    ("shell", "command"),
+    ("container.exec", "command"),
+    ("shell_command", "command"),
+    ("local_shell", "command"),
+    ("exec_command", "cmd"),
+];
+
+/// Characters that let a script run more than the one command it starts with.
+const SHELL_METACHARACTERS: &[char] = &[';', '&', '|', '$', '`', '<', '>', '(', ')', '\n', '\r'];
+
+/// Names of the tools that `[tool_policies]` turns off.
+pub(crate) fn disabled_tools(policies: &BTreeMap<String, ToolPolicyConfig>) -> Vec<String> {
+    policies
+        .iter()
+        .filter(|(_, policy)| !policy.enabled)
+        .map(|(name, _)| name.clone())
+        .collect()
+}
+
+/// Check a call against the policy for `tool_name`, returning the refusal to send back to the
+/// model when the call is not allowed.
+pub(crate) fn check_tool_call(
+    policies: &BTreeMap<String, ToolPolicyConfig>,
+    tool_name: &str,
+    payload: &ToolPayload,
+) -> Result<(), String> {
+    let policy = policies.get(tool_name);
+    if policy.is_some_and(|policy| !policy.enabled) {
+        return Err(format!(
+            "`{tool_name}` is disabled by tool_policies in the Codex config; do not call it again."
+        ));
+    }
+
+    let arguments = payload_arguments(payload);
+    let command_argument = command_argument(tool_name, payload);
+    if let Some(command_argument) = command_argument {
+        check_command(policies, tool_name, command_argument, &arguments, payload)?;
+    } else if has_command_patterns(policies) && bypasses_command_patterns(tool_name, &arguments) {
+        return Err(format!(
+            "`{tool_name}` was refused by tool_policies: its input cannot be checked against the allowed commands."
+        ));
+    }
+
+    let Some(policy) = policy else {
+        return Ok(());
+    };
+    for (argument, patterns) in &policy.arguments {
+        if Some(argument.as_str()) == command_argument {
+            continue;
+        }
+        let Some(value) = arguments.get(argument).and_then(argument_text) else {
+            continue;
+        };
+        if !patterns
+            .iter()
+            .any(|pattern| WildMatch::new(pattern).matches(&value))
+        {
+            return Err(format!(
+                "`{tool_name}` was refused by tool_policies: `{argument}` = {value:?} is not allowed. Allowed values: {}.",
+                patterns.join(", ")
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// The argument that holds the command for tools that run one.
+fn command_argument(tool_name: &str, payload: &ToolPayload) -> Option<&'static str> {
+    match (tool_name, payload) {
+        (_, ToolPayload::LocalShell { .. }) => Some("command"),
+        ("shell" | "container.exec" | "shell_command", ToolPayload::Function { .. }) => {
+            Some("command")
+        }
+        ("exec_command", ToolPayload::Function { .. }) => Some("cmd"),
+        _ => None,
+    }
+}
+
+/// Whether any command tool has `command` patterns, directly or through `[tool_policies.shell]`.
+fn has_command_patterns(policies: &BTreeMap<String, ToolPolicyConfig>) -> bool {
+    COMMAND_TOOLS.iter().any(|(tool, argument)| {
+        policies
+            .get(*tool)
+            .is_some_and(|policy| policy.arguments.contains_key(*argument))
+    })
+}
+
+/// Calls that run code without a command to match: `js_repl` can start processes from
+/// JavaScript, and `write_stdin` can type commands into a shell started by `exec_command`.
+/// A `write_stdin` call that only polls for output writes nothing and is let through.
+fn bypasses_command_patterns(tool_name: &str, arguments: &serde_json::Map<String, Value>) -> bool {
+    match tool_name {
+        "js_repl" => true,
+        "write_stdin" => arguments
+            .get("chars")
+            .and_then(Value::as_str)
+            .is_some_and(|chars| !chars.is_empty()),
+        _ => false,
+    }
+}
+
+/// Check a command against the `command` patterns of `[tool_policies.shell]` and the tool's own
+/// entry. Each pattern is matched word by word against the command's argv; a trailing `*` on the
+/// last word also admits any further arguments.
+fn check_command(
+    policies: &BTreeMap<String, ToolPolicyConfig>,
+    tool_name: &str,
+    command_argument: &str,
+    arguments: &serde_json::Map<String, Value>,
+    payload: &ToolPayload,
+) -> Result<(), String> {
+    let pattern_sets = [
+        policies
+            .get(SHELL_POLICY)
+            .and_then(|policy| policy.arguments.get("command")),
+        policies
+            .get(tool_name)
+            .filter(|_| tool_name != SHELL_POLICY)
+            .and_then(|policy| policy.arguments.get(command_argument)),
+    ];
+    let pattern_sets: Vec<&Vec<String>> = pattern_sets.into_iter().flatten().collect();
+    if pattern_sets.is_empty() {
+        return Ok(());
+    }
+
+    let argv = command_argv(arguments.get(command_argument), payload)
+        .map_err(|reason| format!("`{tool_name}` was refused by tool_policies: {reason}."))?;
+    for patterns in pattern_sets {
+        if !patterns
+            .iter()
+            .any(|pattern| command_matches(pattern, &argv))
+        {
+            return Err(format!(
+                "`{tool_name}` was refused by tool_policies: `{}` is not an allowed command. Allowed commands: {}.",
+                argv.join(" "),
+                patterns.join(", ")
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// The argv a command tool would run. Scripts are split into words and refused when they could
+/// chain, substitute, or redirect; argv that hands a script to a shell is refused outright.
+fn command_argv(value: Option<&Value>, payload: &ToolPayload) -> Result<Vec<String>, String> {
+    let argv = match (payload, value) {
+        (ToolPayload::LocalShell { params }, _) => params.command.clone(),
+        (_, Some(Value::Array(items))) => items
+            .iter()
+            .map(|item| match item {
+                Value::String(word) => Ok(word.clone()),
+                _ => Err("the command must be a list of strings".to_string()),
+            })
+            .collect::<Result<_, _>>()?,
+        (_, Some(Value::String(script))) => {
+            if script.contains(SHELL_METACHARACTERS) {
+                return Err(
+                    "commands that chain, substitute, or redirect are not allowed".to_string(),
+                );
+            }
+            shlex::split(script).ok_or_else(|| "the command could not be parsed".to_string())?
+        }
+        _ => return Err("the command is missing".to_string()),
+    };
+    if argv.is_empty() {
+        return Err("the command is empty".to_string());
+    }
+    if extract_shell_command(&argv).is_some() {
+        return Err("commands that run a shell script are not allowed".to_string());
+    }
+    Ok(argv)
+}
+
+fn command_matches(pattern: &str, argv: &[String]) -> bool {
+    let words: Vec<&str> = pattern.split_whitespace().collect();
+    let Some(last) = words.last() else {
+        return false;
+    };
+    let prefix_matches = argv.len() >= words.len()
+        && words
+            .iter()
+            .zip(argv)
+            .all(|(word, arg)| WildMatch::new(word).matches(arg));
+    prefix_matches && (argv.len() == words.len() || last.ends_with('*'))
+}
+
+fn payload_arguments(payload: &ToolPayload) -> serde_json::Map<String, Value> {
+    let parsed = match payload {
+        ToolPayload::Function { arguments } => serde_json::from_str(arguments).ok(),
+        ToolPayload::Mcp { raw_arguments, .. } => serde_json::from_str(raw_arguments).ok(),
+        ToolPayload::LocalShell { params } => Some(serde_json::json!({
+            "command": params.command,
+            "workdir": params.workdir,
+        })),
+        ToolPayload::Custom { input } => Some(serde_json::json!({ "input": input })),
+    };
+    match parsed {
+        Some(Value::Object(arguments)) => arguments,
+        _ => serde_json::Map::new(),
+    }
+}
+
+fn argument_text(value: &Value) -> Option<String> {
+    match value {
+        Value::Null => None,
+        Value::String(text) => Some(text.clone()),
+        Value::Array(items) => Some(
+            items
+                .iter()
+                .filter_map(argument_text)
+                .collect::<Vec<_>>()
+                .join(" "),
+        ),
+        other => Some(other.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn policies(name: &str, policy: ToolPolicyConfig) -> BTreeMap<String, ToolPolicyConfig> {
+        BTreeMap::from([(name.to_string(), policy)])
+    }
+
+    fn function(arguments: &str) -> ToolPayload {
+        ToolPayload::Function {
+            arguments: arguments.to_string(),
+        }
+    }
+
+    #[test]
+    fn argument_patterns_limit_values() {
+        let policies = policies(
+            "fetch_url",
+            ToolPolicyConfig {
+                enabled: true,
+                arguments: BTreeMap::from([(
+                    "url".to_string(),
+                    vec!["https://docs.rs/*".to_string()],
+                )]),
+            },
+        );
+
+        assert_eq!(
+            check_tool_call(
+                &policies,
+                "fetch_url",
+                &function(r#"{"url":"https://docs.rs/serde"}"#)
+            ),
+            Ok(())
+        );
+        assert_eq!(
+            check_tool_call(
+                &policies,
+                "fetch_url",
+                &function(r#"{"url":"https://example.com/"}"#)
+            ),
+            Err(
+                "`fetch_url` was refused by tool_policies: `url` = \"https://example.com/\" is not allowed. Allowed values: https://docs.rs/*."
+                    .to_string()
+            )
+        );
+        assert_eq!(check_tool_call(&policies, "shell", &function("{}")), Ok(()));
+    }
+
+    #[test]
+    fn disabled_tools_are_refused_and_commands_match_per_word() {
+        let policies = BTreeMap::from([
+            (
+                "mcp__github__delete_repo".to_string(),
+                ToolPolicyConfig {
+                    enabled: false,
+                    arguments: BTreeMap::new(),
+                },
+            ),
+            (
+                "shell".to_string(),
+                ToolPolicyConfig {
+                    enabled: true,
+                    arguments: BTreeMap::from([(
+                        "command".to_string(),
+                        vec!["git status*".to_string(), "ls*".to_string()],
+                    )]),
+                },
+            ),
+        ]);
+
+        assert_eq!(
+            disabled_tools(&policies),
+            vec!["mcp__github__delete_repo".to_string()]
+        );
+        assert!(
+            check_tool_call(
+                &policies,
+                "mcp__github__delete_repo",
+                &ToolPayload::Mcp {
+                    server: "github".to_string(),
+                    tool: "delete_repo".to_string(),
+                    raw_arguments: "{}".to_string(),
+                },
+            )
+            .is_err()
+        );
+        assert_eq!(
+            check_tool_call(
+                &policies,
+                "shell",
+                &function(r#"{"command":["git","status","--short"]}"#)
+            ),
+            Ok(())
+        );
+        assert!(
+            check_tool_call(
+                &policies,
+                "shell",
+                &function(r#"{"command":["rm","-rf","/"]}"#)
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn command_patterns_cover_every_shell_tool_and_refuse_chaining() {
+        let policies = policies(
+            "shell",
+            ToolPolicyConfig {
+                enabled: true,
+                arguments: BTreeMap::from([(
+                    "command".to_string(),
+                    vec!["git status*".to_string()],
+                )]),
+            },
+        );
+
+        assert_eq!(
+            check_tool_call(
+                &policies,
+                "shell_command",
+                &function(r#"{"command":"git status --short"}"#)
+            ),
+            Ok(())
+        );
+        assert_eq!(
+            check_tool_call(
+                &policies,
+                "exec_command",
+                &function(r#"{"cmd":"git status; rm -rf ~"}"#)
+            ),
+            Err(
+                "`exec_command` was refused by tool_policies: commands that chain, substitute, or redirect are not allowed."
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            check_tool_call(
+                &policies,
+                "shell",
+                &function(r#"{"command":["bash","-lc","git status"]}"#)
+            ),
+            Err(
+                "`shell` was refused by tool_policies: commands that run a shell script are not allowed."
+                    .to_string()
+            )
+        );
+        assert!(
+            check_tool_call(
+                &policies,
+                "shell",
+                &function(r#"{"command":["git","push","status"]}"#)
+            )
+            .is_err()
+        );
+        assert_eq!(
+            check_tool_call(
+                &policies,
+                "write_stdin",
+                &function(r#"{"session_id":1,"chars":"rm -rf ~\n"}"#)
+            ),
+            Err(
+                "`write_stdin` was refused by tool_policies: its input cannot be checked against the allowed commands."
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            check_tool_call(
+                &policies,
+                "write_stdin",
+                &function(r#"{"session_id":1,"chars":""}"#)
+            ),
+            Ok(())
+        );
+        assert!(
+            check_tool_call(
+                &policies,
+                "js_repl",
+                &ToolPayload::Custom {
+                    input: "require('child_process').execSync('rm -rf ~')".to_string(),
+                },
+            )
+            .is_err()
+        );
+        assert!(
+            check_tool_call(
+                &policies,
+                "local_shell",
+                &ToolPayload::LocalShell {
+                    params: codex_protocol::models::ShellToolCallParams {
+                        command: vec!["rm".to_string(), "-rf".to_string(), "/".to_string()],
+                        workdir: None,
+                        timeout_ms: None,
+                        sandbox_permissions: None,
+                        prefix_rule: None,
+                        additional_permissions: None,
+                        justification: None,
+                    },
+                },
+            )
+            .is_err()
+        );
+    }
+}
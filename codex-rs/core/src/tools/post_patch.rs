@@ -0,0 +1,446 @@
+//! Runs the configured `[[patch_checks]]` and `post_patch_command` after `apply_patch`
+//! succeeds.
+//!
+//! Formatters and linters run first, on just the files the patch changed, and are reported in a
+//! `PatchChecks` event. The post-patch command then runs like any other command in the
+//! transcript. Everything runs under the turn's sandbox, and the combined result is appended to
+//! the `apply_patch` output so the model sees reformatted files, lint diagnostics, and failing
+//! tests without being asked to look for them.
+
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use uuid::Uuid;
+
+use crate::codex::Session;
+use crate::codex::TurnContext;
+use crate::config::types::PatchCheckConfig;
+use crate::config::types::PatchCheckKind;
+use crate::error::CodexErr;
+use crate::error::SandboxErr;
+use crate::exec::ExecParams;
+use crate::exec::ExecToolCallOutput;
+use crate::exec::StdoutStream;
+use crate::exec::StreamOutput;
+use crate::exec::process_exec_tool_call;
+use crate::exec_env::create_env;
+use crate::features::Feature;
+use crate::parse_command::parse_command;
+use crate::protocol::EventMsg;
+use crate::protocol::ExecCommandBeginEvent;
+use crate::protocol::ExecCommandEndEvent;
+use crate::protocol::ExecCommandSource;
+use crate::protocol::ExecCommandStatus;
+use crate::protocol::PatchCheckResult;
+use crate::protocol::PatchChecksEvent;
+use crate::sandboxing::SandboxPermissions;
+use crate::tools::format_exec_output_str;
+use crate::truncate::TruncationPolicy;
+
+const POST_PATCH_TIMEOUT_MS: u64 = 10 * 60 * 1000;
+const PATCH_CHECK_TIMEOUT_MS: u64 = 2 * 60 * 1000;
+const FILES_PLACEHOLDER: &str = "{files}";
+
+/// Run the patch checks and `post_patch_command` for a patch that changed `changed_files`, and
+/// return the note to append to the patch output.
+pub(crate) async fn run_after_patch(
+    session: &Session,
+    turn: &TurnContext,
+    call_id: &str,
+    changed_files: &[PathBuf],
+) -> Option<String> {
+    let reports: Vec<String> = [
+        run_patch_checks(session, turn, call_id, changed_files).await,
+        run_post_patch_command(session, turn).await,
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+    (!reports.is_empty()).then(|| reports.join("\n\n"))
+}
+
+async fn run_patch_checks(
+    session: &Session,
+    turn: &TurnContext,
+    call_id: &str,
+    changed_files: &[PathBuf],
+) -> Option<String> {
+    let mut results = Vec::new();
+    for check in &turn.config.patch_checks {
+        let files: Vec<&PathBuf> = changed_files
+            .iter()
+            .filter(|path| check_applies_to(check, path))
+            .collect();
+        if files.is_empty() {
+            continue;
+        }
+        let Some(command) = patch_check_command(check, &files) else {
+            continue;
+        };
+
+        let before = match check.kind {
+            PatchCheckKind::Format => read_files(&files).await,
+            PatchCheckKind::Lint => Vec::new(),
+        };
+        let output = run_sandboxed(session, turn, command, PATCH_CHECK_TIMEOUT_MS, None).await;
+        let formatted_files = if before.is_empty() {
+            Vec::new()
+        } else {
+            let after = read_files(&files).await;
+            files
+                .iter()
+                .zip(before.iter().zip(after.iter()))
+                .filter(|(_, (before, after))| before != after)
+                .map(|(path, _)| display_path(path, &turn.cwd))
+                .collect()
+        };
+        let failed = output.exit_code != 0 || output.timed_out;
+        results.push(PatchCheckResult {
+            name: check.display_name().to_string(),
+            kind: check.kind,
+            exit_code: output.exit_code,
+            formatted_files,
+            output: if failed {
+                format_exec_output_str(&output, turn.truncation_policy)
+            } else {
+                String::new()
+            },
+        });
+    }
+    if results.is_empty() {
+        return None;
+    }
+
+    let report = patch_checks_report(&results);
+    session
+        .send_event(
+            turn,
+            EventMsg::PatchChecks(PatchChecksEvent {
+                call_id: call_id.to_string(),
+                turn_id: turn.sub_id.clone(),
+                results,
+            }),
+        )
+        .await;
+    report
+}
+
+fn check_applies_to(check: &PatchCheckConfig, path: &Path) -> bool {
+    check.extensions.is_empty()
+        || path.extension().is_some_and(|extension| {
+            check
+                .extensions
+                .iter()
+                .any(|wanted| extension.eq_ignore_ascii_case(wanted.trim_start_matches('.')))
+        })
+}
+
+/// The check's argv with `{files}` expanded, or `None` when no program is configured.
+fn patch_check_command(check: &PatchCheckConfig, files: &[&PathBuf]) -> Option<Vec<String>> {
+    if check.command.first().is_none_or(String::is_empty) {
+        return None;
+    }
+    let mut command = Vec::with_capacity(check.command.len() + files.len());
+    for arg in &check.command {
+        if arg == FILES_PLACEHOLDER {
+            command.extend(files.iter().map(|path| path.to_string_lossy().into_owned()));
+        } else {
+            command.push(arg.clone());
+        }
+    }
+    Some(command)
+}
+
+async fn read_files(files: &[&PathBuf]) -> Vec<Option<Vec<u8>>> {
+    let mut contents = Vec::with_capacity(files.len());
+    for path in files {
+        contents.push(tokio::fs::read(path).await.ok());
+    }
+    contents
+}
+
+fn display_path(path: &Path, cwd: &Path) -> PathBuf {
+    path.strip_prefix(cwd).unwrap_or(path).to_path_buf()
+}
+
+/// What the model needs to hear about the checks: files a formatter rewrote and checks that
+/// failed. Clean runs are left out.
+fn patch_checks_report(results: &[PatchCheckResult]) -> Option<String> {
+    let mut lines = Vec::new();
+    for result in results {
+        if !result.formatted_files.is_empty() {
+            let files = result
+                .formatted_files
+                .iter()
+                .map(|path| path.display().to_string())
+                .collect::<Vec<_>>()
+                .join(", ");
+            lines.push(format!(
+                "{} reformatted {files}. Re-read these files before patching them again.",
+                result.name
+            ));
+        }
+        if result.exit_code != 0 || !result.output.is_empty() {
+            lines.push(format!(
+                "{} failed with exit code {}:\n{}",
+                result.name, result.exit_code, result.output
+            ));
+        }
+    }
+    (!lines.is_empty()).then(|| lines.join("\n\n"))
+}
+
+async fn run_post_patch_command(session: &Session, turn: &TurnContext) -> Option<String> {
+    let command = turn.config.post_patch_command.as_deref()?.trim();
+    if command.is_empty() {
+        return None;
+    }
+
+    let exec_command = session.user_shell().derive_exec_args(command, true);
+    let call_id = format!("post-patch-{}", Uuid::new_v4());
+    let parsed_cmd = parse_command(&exec_command);
+    session
+        .send_event(
+            turn,
+            EventMsg::ExecCommandBegin(ExecCommandBeginEvent {
+                call_id: call_id.clone(),
+                process_id: None,
+                turn_id: turn.sub_id.clone(),
+                command: exec_command.clone(),
+                cwd: turn.cwd.clone(),
+                parsed_cmd: parsed_cmd.clone(),
+                source: ExecCommandSource::Agent,
+                interaction_input: None,
+            }),
+        )
+        .await;
+
+    let stdout_stream = Some(StdoutStream {
+        sub_id: turn.sub_id.clone(),
+        call_id: call_id.clone(),
+        tx_event: session.get_tx_event(),
+    });
+    let output = run_sandboxed(
+        session,
+        turn,
+        exec_command.clone(),
+        POST_PATCH_TIMEOUT_MS,
+        stdout_stream,
+    )
+    .await;
+
+    let formatted_output = format_exec_output_str(&output, turn.truncation_policy);
+    session
+        .send_event(
+            turn,
+            EventMsg::ExecCommandEnd(ExecCommandEndEvent {
+                call_id,
+                process_id: None,
+                turn_id: turn.sub_id.clone(),
+                command: exec_command,
+                cwd: turn.cwd.clone(),
+                parsed_cmd,
+                source: ExecCommandSource::Agent,
+                interaction_input: None,
+                stdout: output.stdout.text.clone(),
+                stderr: output.stderr.text.clone(),
+                aggregated_output: output.aggregated_output.text.clone(),
+                exit_code: output.exit_code,
+                duration: output.duration,
+                formatted_output,
+                status: if output.exit_code == 0 {
+                    ExecCommandStatus::Completed
+                } else {
+                    ExecCommandStatus::Failed
+                },
+            }),
+        )
+        .await;
+
+    Some(post_patch_report(command, &output, turn.truncation_policy))
+}
+
+/// Run `command` in the turn's working directory and sandbox. Failures to start are reported
+/// as output with exit code -1.
+async fn run_sandboxed(
+    session: &Session,
+    turn: &TurnContext,
+    command: Vec<String>,
+    timeout_ms: u64,
+    stdout_stream: Option<StdoutStream>,
+) -> ExecToolCallOutput {
+    let params = ExecParams {
+        command,
+        cwd: turn.cwd.clone(),
+        expiration: timeout_ms.into(),
+        env: create_env(
+            &turn.shell_environment_policy,
+            Some(session.conversation_id),
+        ),
+        network: turn.network.clone(),
+        sandbox_permissions: SandboxPermissions::UseDefault,
+        windows_sandbox_level: turn.windows_sandbox_level,
+        justification: None,
+        arg0: None,
+    };
+    match process_exec_tool_call(
+        params,
+        turn.sandbox_policy.get(),
+        turn.cwd.as_path(),
+        &turn.codex_linux_sandbox_exe,
+        turn.features.enabled(Feature::UseLinuxSandboxBwrap),
+        stdout_stream,
+    )
+    .await
+    {
+        Ok(output) => output,
+        Err(CodexErr::Sandbox(
+            SandboxErr::Denied { output, .. } | SandboxErr::Timeout { output },
+        )) => *output,
+        Err(err) => {
+            let message = format!("execution error: {err}");
+            ExecToolCallOutput {
+                exit_code: -1,
+                stdout: StreamOutput::new(String::new()),
+                stderr: StreamOutput::new(message.clone()),
+                aggregated_output: StreamOutput::new(message),
+                duration: Duration::ZERO,
+                timed_out: false,
+            }
+        }
+    }
+}
+
+/// Append the post-patch report, if any, to the `apply_patch` output.
+pub(crate) fn append_post_patch_report(content: String, report: Option<String>) -> String {
+    match report {
+        Some(report) => format!("{content}\n\n{report}"),
+        None => content,
+    }
+}
+
+fn post_patch_report(
+    command: &str,
+    output: &ExecToolCallOutput,
+    truncation_policy: TruncationPolicy,
+) -> String {
+    if output.exit_code == 0 && !output.timed_out {
+        return format!("Post-patch check `{command}` passed.");
+    }
+    format!(
+        "Post-patch check `{command}` failed with exit code {}. Fix the failures below before \
+continuing:\n{}",
+        output.exit_code,
+        format_exec_output_str(output, truncation_policy)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn output(exit_code: i32, text: &str) -> ExecToolCallOutput {
+        ExecToolCallOutput {
+            exit_code,
+            stdout: StreamOutput::new(text.to_string()),
+            stderr: StreamOutput::new(String::new()),
+            aggregated_output: StreamOutput::new(text.to_string()),
+            duration: Duration::from_secs(1),
+            timed_out: false,
+        }
+    }
+
+    #[test]
+    fn passing_check_is_reported_briefly() {
+        let report = post_patch_report(
+            "cargo test",
+            &output(0, "test result: ok"),
+            TruncationPolicy::Bytes(10_000),
+        );
+        assert_eq!(
+            append_post_patch_report("Success.".to_string(), Some(report)),
+            "Success.\n\nPost-patch check `cargo test` passed."
+        );
+    }
+
+    #[test]
+    fn failing_check_includes_its_output() {
+        let report = post_patch_report(
+            "cargo test",
+            &output(101, "test parse_empty ... FAILED"),
+            TruncationPolicy::Bytes(10_000),
+        );
+        assert_eq!(
+            report,
+            "Post-patch check `cargo test` failed with exit code 101. Fix the failures below \
+before continuing:\ntest parse_empty ... FAILED"
+        );
+        assert_eq!(
+            append_post_patch_report("Success.".to_string(), None),
+            "Success."
+        );
+    }
+
+    #[test]
+    fn patch_checks_expand_files_and_match_extensions() {
+        let check = PatchCheckConfig {
+            name: None,
+            command: vec![
+                "rustfmt".to_string(),
+                FILES_PLACEHOLDER.to_string(),
+                "--check".to_string(),
+            ],
+            extensions: vec![".rs".to_string()],
+            kind: PatchCheckKind::Format,
+        };
+        let rust_file = PathBuf::from("/repo/src/lib.rs");
+        assert!(check_applies_to(&check, &rust_file));
+        assert!(!check_applies_to(&check, Path::new("/repo/README.md")));
+        assert_eq!(
+            patch_check_command(&check, &[&rust_file]),
+            Some(vec![
+                "rustfmt".to_string(),
+                "/repo/src/lib.rs".to_string(),
+                "--check".to_string(),
+            ])
+        );
+        assert_eq!(check.display_name(), "rustfmt");
+    }
+
+    #[test]
+    fn patch_checks_report_formatted_files_and_failures_only() {
+        let results = vec![
+            PatchCheckResult {
+                name: "rustfmt".to_string(),
+                kind: PatchCheckKind::Format,
+                exit_code: 0,
+                formatted_files: vec![PathBuf::from("src/lib.rs")],
+                output: String::new(),
+            },
+            PatchCheckResult {
+                name: "eslint".to_string(),
+                kind: PatchCheckKind::Lint,
+                exit_code: 0,
+                formatted_files: Vec::new(),
+                output: String::new(),
+            },
+            PatchCheckResult {
+                name: "clippy".to_string(),
+                kind: PatchCheckKind::Lint,
+                exit_code: 101,
+                formatted_files: Vec::new(),
+                output: "error: unused variable: `x`".to_string(),
+            },
+        ];
+        assert_eq!(
+            patch_checks_report(&results),
+            Some(
+                "rustfmt reformatted src/lib.rs. Re-read these files before patching them again.\n\nclippy failed with exit code 101:\nerror: unused variable: `x`"
+                    .to_string()
+            )
+        );
+        assert_eq!(patch_checks_report(&results[1..2]), None);
+    }
+}
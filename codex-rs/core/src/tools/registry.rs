@@ -12,6 +12,7 @@ use crate::sandbox_tags::sandbox_tag;
 use crate::tools::context::ToolInvocation;
 use crate::tools::context::ToolOutput;
 use crate::tools::context::ToolPayload;
+use crate::tools::handlers::PLAN_APPROVAL_REQUIRED_MESSAGE;
 use async_trait::async_trait;
 use codex_hooks::HookEvent;
 use codex_hooks::HookEventAfterToolUse;
@@ -24,6 +25,9 @@ use codex_protocol::models::ResponseInputItem;
 use codex_utils_readiness::Readiness;
 use tracing::warn;
 
+/// Returned to the model when a mutating tool is called in a `--read-only` session.
+pub(crate) const READ_ONLY_MODE_MESSAGE: &str = "This session is read-only: file writes, patches, and shell commands that are not known to be safe are disabled. Continue exploring with read-only commands, or describe the change for the user instead of making it.";
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum ToolKind {
     Function,
@@ -163,6 +167,56 @@ impl ToolRegistry {
         }
 
         let is_mutating = handler.is_mutating(&invocation).await;
+        if is_mutating
+            && invocation
+                .session
+                .awaiting_plan_approval(&invocation.turn)
+                .await
+        {
+            let message = PLAN_APPROVAL_REQUIRED_MESSAGE.to_string();
+            otel.tool_result_with_tags(
+                tool_name.as_ref(),
+                &call_id_owned,
+                log_payload.as_ref(),
+                Duration::ZERO,
+                false,
+                &message,
+                &metric_tags,
+                mcp_server_ref,
+                mcp_server_origin_ref,
+            );
+            return Err(FunctionCallError::RespondToModel(message));
+        }
+        if is_mutating && invocation.turn.config.read_only {
+            let message = READ_ONLY_MODE_MESSAGE.to_string();
+            otel.tool_result_with_tags(
+                tool_name.as_ref(),
+                &call_id_owned,
+                log_payload.as_ref(),
+                Duration::ZERO,
+                false,
+                &message,
+                &metric_tags,
+                mcp_server_ref,
+                mcp_server_origin_ref,
+            );
+            return Err(FunctionCallError::RespondToModel(message));
+        }
+        if is_mutating && invocation.turn.config.dry_run {
+            let output = crate::tools::dry_run::simulate(&invocation).await;
+            otel.tool_result_with_tags(
+                tool_name.as_ref(),
+                &call_id_owned,
+                log_payload.as_ref(),
+                Duration::ZERO,
+                true,
+                &output.log_preview(),
+                &metric_tags,
+                mcp_server_ref,
+                mcp_server_origin_ref,
+            );
+            return Ok(output.into_response(&call_id_owned, &payload_for_response));
+        }
         let output_cell = tokio::sync::Mutex::new(None);
         let invocation_for_tool = invocation.clone();
 
@@ -443,3 +497,87 @@ async fn dispatch_after_tool_use_hook(
 
     None
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    use pretty_assertions::assert_eq;
+
+    use super::READ_ONLY_MODE_MESSAGE;
+    use super::ToolHandler;
+    use super::ToolRegistry;
+    use crate::codex::Session;
+    use crate::codex::TurnContext;
+    use crate::codex::make_session_and_context;
+    use crate::function_tool::FunctionCallError;
+    use crate::protocol::AskForApproval;
+    use crate::tools::context::ToolInvocation;
+    use crate::tools::context::ToolPayload;
+    use crate::tools::handlers::McpHandler;
+    use crate::tools::handlers::PLAN_APPROVAL_REQUIRED_MESSAGE;
+    use crate::turn_diff_tracker::TurnDiffTracker;
+    use codex_protocol::models::ResponseInputItem;
+
+    /// Dispatch a call to an MCP tool the (empty) connection manager knows nothing about.
+    async fn dispatch_unannotated_mcp_call(
+        session: Session,
+        turn: TurnContext,
+    ) -> Result<ResponseInputItem, FunctionCallError> {
+        let tool_name = "mcp__docs__write_page".to_string();
+        let handler: Arc<dyn ToolHandler> = Arc::new(McpHandler);
+        let registry = ToolRegistry::new(HashMap::from([(tool_name.clone(), handler)]));
+        registry
+            .dispatch(ToolInvocation {
+                session: Arc::new(session),
+                turn: Arc::new(turn),
+                tracker: Arc::new(tokio::sync::Mutex::new(TurnDiffTracker::new())),
+                call_id: "call-1".to_string(),
+                tool_name,
+                payload: ToolPayload::Mcp {
+                    server: "docs".to_string(),
+                    tool: "write_page".to_string(),
+                    raw_arguments: "{}".to_string(),
+                },
+            })
+            .await
+    }
+
+    #[tokio::test]
+    async fn read_only_sessions_refuse_mcp_tools_not_marked_read_only() {
+        let (session, mut turn) = make_session_and_context().await;
+        let mut config = (*turn.config).clone();
+        config.read_only = true;
+        turn.config = Arc::new(config);
+
+        let err = dispatch_unannotated_mcp_call(session, turn)
+            .await
+            .expect_err("the MCP call should be refused");
+
+        assert_eq!(
+            err,
+            FunctionCallError::RespondToModel(READ_ONLY_MODE_MESSAGE.to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn plan_approval_gate_holds_back_mcp_tools_not_marked_read_only() {
+        let (session, mut turn) = make_session_and_context().await;
+        let mut config = (*turn.config).clone();
+        config.require_plan_approval = true;
+        turn.config = Arc::new(config);
+        turn.approval_policy
+            .set(AskForApproval::OnRequest)
+            .expect("test setup should allow updating approval policy");
+
+        let err = dispatch_unannotated_mcp_call(session, turn)
+            .await
+            .expect_err("the MCP call should wait for a plan");
+
+        assert_eq!(
+            err,
+            FunctionCallError::RespondToModel(PLAN_APPROVAL_REQUIRED_MESSAGE.to_string())
+        );
+    }
+}
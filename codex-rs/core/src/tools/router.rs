@@ -7,6 +7,7 @@ use crate::sandboxing::SandboxPermissions;
 use crate::tools::context::SharedTurnDiffTracker;
 use crate::tools::context::ToolInvocation;
 use crate::tools::context::ToolPayload;
+use crate::tools::policy;
 use crate::tools::registry::ConfiguredToolSpec;
 use crate::tools::registry::ToolRegistry;
 use crate::tools::spec::ToolsConfig;
@@ -44,7 +45,13 @@ impl ToolRouter {
         dynamic_tools: &[DynamicToolSpec],
     ) -> Self {
         let builder = build_specs(config, mcp_tools, app_tools, dynamic_tools);
-        let (specs, registry) = builder.build();
+        let (mut specs, registry) = builder.build();
+        specs.retain(|spec| {
+            !config
+                .disabled_tools
+                .iter()
+                .any(|name| name == spec.spec.name())
+        });
 
         Self { registry, specs }
     }
@@ -168,6 +175,16 @@ impl ToolRouter {
             ));
         }
 
+        if let Err(message) =
+            policy::check_tool_call(&turn.config.tool_policies, &tool_name, &payload)
+        {
+            return Ok(Self::failure_response(
+                failure_call_id,
+                payload_outputs_custom,
+                FunctionCallError::RespondToModel(message),
+            ));
+        }
+
         let invocation = ToolInvocation {
             session,
             turn,
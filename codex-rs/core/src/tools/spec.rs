@@ -7,9 +7,13 @@ use crate::features::Feature;
 use crate::features::Features;
 use crate::mcp_connection_manager::ToolInfo;
 use crate::models_manager::collaboration_mode_presets::CollaborationModesConfig;
+use crate::tools::handlers::FETCH_URL_TOOL_NAME;
 use crate::tools::handlers::PLAN_TOOL;
 use crate::tools::handlers::SEARCH_TOOL_BM25_DEFAULT_LIMIT;
 use crate::tools::handlers::SEARCH_TOOL_BM25_TOOL_NAME;
+use crate::tools::handlers::SEARCH_WEB_TOOL_NAME;
+use crate::tools::handlers::SEMANTIC_SEARCH_TOOL_NAME;
+use crate::tools::handlers::UPDATE_TODOS_TOOL_NAME;
 use crate::tools::handlers::agent_jobs::BatchJobHandler;
 use crate::tools::handlers::apply_patch::create_apply_patch_freeform_tool;
 use crate::tools::handlers::apply_patch::create_apply_patch_json_tool;
@@ -62,6 +66,10 @@ pub(crate) struct ToolsConfig {
     pub image_gen_tool: bool,
     pub agent_roles: BTreeMap<String, AgentRoleConfig>,
     pub search_tool: bool,
+    pub semantic_search: bool,
+    pub fetch_url: bool,
+    pub search_web: bool,
+    pub todo_list: bool,
     pub request_permission_enabled: bool,
     pub js_repl_enabled: bool,
     pub js_repl_tools_only: bool,
@@ -72,6 +80,8 @@ pub(crate) struct ToolsConfig {
     pub experimental_supported_tools: Vec<String>,
     pub agent_jobs_tools: bool,
     pub agent_jobs_worker_tools: bool,
+    pub tool_plugins: Vec<DynamicToolSpec>,
+    pub disabled_tools: Vec<String>,
 }
 
 pub(crate) struct ToolsConfigParams<'a> {
@@ -98,6 +108,10 @@ impl ToolsConfig {
         let include_default_mode_request_user_input =
             include_request_user_input && features.enabled(Feature::DefaultModeRequestUserInput);
         let include_search_tool = features.enabled(Feature::Apps);
+        let include_semantic_search = features.enabled(Feature::SemanticSearch);
+        let include_fetch_url = features.enabled(Feature::FetchUrl);
+        let include_search_web = features.enabled(Feature::WebSearchProvider);
+        let include_todo_list = features.enabled(Feature::TodoList);
         let include_artifact_tools =
             features.enabled(Feature::Artifact) && codex_artifacts::can_manage_artifact_runtime();
         let include_image_gen_tool =
@@ -162,6 +176,10 @@ impl ToolsConfig {
             image_gen_tool: include_image_gen_tool,
             agent_roles: BTreeMap::new(),
             search_tool: include_search_tool,
+            semantic_search: include_semantic_search,
+            fetch_url: include_fetch_url,
+            search_web: include_search_web,
+            todo_list: include_todo_list,
             request_permission_enabled,
             js_repl_enabled: include_js_repl,
             js_repl_tools_only: include_js_repl_tools_only,
@@ -172,6 +190,8 @@ impl ToolsConfig {
             experimental_supported_tools: model_info.experimental_supported_tools.clone(),
             agent_jobs_tools: include_agent_jobs,
             agent_jobs_worker_tools,
+            tool_plugins: Vec::new(),
+            disabled_tools: Vec::new(),
         }
     }
 
@@ -184,6 +204,17 @@ impl ToolsConfig {
         self.allow_login_shell = allow_login_shell;
         self
     }
+
+    pub fn with_tool_plugins(mut self, tool_plugins: Vec<DynamicToolSpec>) -> Self {
+        self.tool_plugins = tool_plugins;
+        self
+    }
+
+    /// Tools left out of the model's tool list.
+    pub fn with_disabled_tools(mut self, disabled_tools: Vec<String>) -> Self {
+        self.disabled_tools = disabled_tools;
+        self
+    }
 }
 
 fn supports_image_generation(model_info: &ModelInfo) -> bool {
@@ -1208,6 +1239,163 @@ fn create_search_tool_bm25_tool(app_tools: &HashMap<String, ToolInfo>) -> ToolSp
     })
 }
 
+fn create_fetch_url_tool() -> ToolSpec {
+    let properties = BTreeMap::from([(
+        "url".to_string(),
+        JsonSchema::String {
+            description: Some("Absolute http(s) URL of the page to fetch.".to_string()),
+        },
+    )]);
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: FETCH_URL_TOOL_NAME.to_string(),
+        description: "Downloads a web page and returns its main content as markdown. Only domains \
+                      on the user's allowlist can be fetched; use this for documentation, issues, \
+                      or references the user points you to."
+            .to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["url".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+fn create_update_todos_tool() -> ToolSpec {
+    let status_change = BTreeMap::from([
+        (
+            "id".to_string(),
+            JsonSchema::Number {
+                description: Some("Number of the item to change.".to_string()),
+            },
+        ),
+        (
+            "status".to_string(),
+            JsonSchema::String {
+                description: Some("One of: pending, in_progress, done, cancelled".to_string()),
+            },
+        ),
+    ]);
+    let properties = BTreeMap::from([
+        (
+            "add".to_string(),
+            JsonSchema::Array {
+                description: Some("New items, each a short description.".to_string()),
+                items: Box::new(JsonSchema::String { description: None }),
+            },
+        ),
+        (
+            "update".to_string(),
+            JsonSchema::Array {
+                description: Some("Status changes for existing items.".to_string()),
+                items: Box::new(JsonSchema::Object {
+                    properties: status_change,
+                    required: Some(vec!["id".to_string(), "status".to_string()]),
+                    additional_properties: Some(false.into()),
+                }),
+            },
+        ),
+        (
+            "remove".to_string(),
+            JsonSchema::Array {
+                description: Some(
+                    "Numbers of items that no longer belong on the list.".to_string(),
+                ),
+                items: Box::new(JsonSchema::Number { description: None }),
+            },
+        ),
+    ]);
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: UPDATE_TODOS_TOOL_NAME.to_string(),
+        description: "Maintains a todo list for this session that the user sees as a checklist \
+                      and that survives resume and compaction. Add items when multi-step work \
+                      comes up, mark one in_progress while you work on it, and mark items done or \
+                      cancelled as they finish. Returns the whole list with item numbers; call it \
+                      with no arguments to read the list."
+            .to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: None,
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+fn create_search_web_tool() -> ToolSpec {
+    let properties = BTreeMap::from([
+        (
+            "query".to_string(),
+            JsonSchema::String {
+                description: Some("Search query.".to_string()),
+            },
+        ),
+        (
+            "max_results".to_string(),
+            JsonSchema::Number {
+                description: Some(
+                    "Number of results to return (defaults to the configured count, at most 20)."
+                        .to_string(),
+                ),
+            },
+        ),
+    ]);
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: SEARCH_WEB_TOOL_NAME.to_string(),
+        description: "Searches the web and returns a numbered list of pages with their URLs and \
+                      snippets, best match first. Use it for information that is newer than your \
+                      training data or outside the workspace, and cite the URLs you rely on."
+            .to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["query".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+fn create_semantic_search_tool() -> ToolSpec {
+    let properties = BTreeMap::from([
+        (
+            "query".to_string(),
+            JsonSchema::String {
+                description: Some(
+                    "Natural-language description of the code to find (e.g. \"where auth tokens \
+                     are refreshed\")."
+                        .to_string(),
+                ),
+            },
+        ),
+        (
+            "limit".to_string(),
+            JsonSchema::Number {
+                description: Some(format!(
+                    "Maximum number of results to return (defaults to {}).",
+                    crate::semantic_search::DEFAULT_RESULT_LIMIT
+                )),
+            },
+        ),
+    ]);
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: SEMANTIC_SEARCH_TOOL_NAME.to_string(),
+        description: "Searches an embedding index of the workspace for code related to a \
+                      natural-language query. Returns file ranges with short snippets, best match \
+                      first. Prefer grep for exact identifiers."
+            .to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["query".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
 fn create_read_file_tool() -> ToolSpec {
     let indentation_properties = BTreeMap::from([
         (
@@ -1728,6 +1916,45 @@ fn sanitize_json_schema(value: &mut JsonValue) {
     }
 }
 
+/// Every tool name `build_specs` can register itself, whichever of them the config enables.
+/// Tool plugins may not reuse these.
+pub(crate) const BUILT_IN_TOOL_NAMES: &[&str] = &[
+    "exec_command",
+    "write_stdin",
+    "shell",
+    "container.exec",
+    "local_shell",
+    "shell_command",
+    "list_mcp_resources",
+    "list_mcp_resource_templates",
+    "read_mcp_resource",
+    "update_plan",
+    UPDATE_TODOS_TOOL_NAME,
+    "js_repl",
+    "js_repl_reset",
+    "request_user_input",
+    SEARCH_TOOL_BM25_TOOL_NAME,
+    SEMANTIC_SEARCH_TOOL_NAME,
+    FETCH_URL_TOOL_NAME,
+    SEARCH_WEB_TOOL_NAME,
+    "apply_patch",
+    "grep_files",
+    "read_file",
+    "list_dir",
+    "test_sync_tool",
+    "web_search",
+    "image_generation",
+    "view_image",
+    "artifacts",
+    "spawn_agent",
+    "send_input",
+    "resume_agent",
+    "wait",
+    "close_agent",
+    "spawn_agents_on_csv",
+    "report_agent_job_result",
+];
+
 /// Builds the tool registry builder while collecting tool specs for later serialization.
 pub(crate) fn build_specs(
     config: &ToolsConfig,
@@ -1738,6 +1965,7 @@ pub(crate) fn build_specs(
     use crate::tools::handlers::ApplyPatchHandler;
     use crate::tools::handlers::ArtifactsHandler;
     use crate::tools::handlers::DynamicToolHandler;
+    use crate::tools::handlers::FetchUrlHandler;
     use crate::tools::handlers::GrepFilesHandler;
     use crate::tools::handlers::JsReplHandler;
     use crate::tools::handlers::JsReplResetHandler;
@@ -1749,9 +1977,13 @@ pub(crate) fn build_specs(
     use crate::tools::handlers::ReadFileHandler;
     use crate::tools::handlers::RequestUserInputHandler;
     use crate::tools::handlers::SearchToolBm25Handler;
+    use crate::tools::handlers::SearchWebHandler;
+    use crate::tools::handlers::SemanticSearchHandler;
     use crate::tools::handlers::ShellCommandHandler;
     use crate::tools::handlers::ShellHandler;
     use crate::tools::handlers::TestSyncHandler;
+    use crate::tools::handlers::TodoHandler;
+    use crate::tools::handlers::ToolPluginHandler;
     use crate::tools::handlers::UnifiedExecHandler;
     use crate::tools::handlers::ViewImageHandler;
     use std::sync::Arc;
@@ -1763,6 +1995,7 @@ pub(crate) fn build_specs(
     let plan_handler = Arc::new(PlanHandler);
     let apply_patch_handler = Arc::new(ApplyPatchHandler);
     let dynamic_tool_handler = Arc::new(DynamicToolHandler);
+    let tool_plugin_handler = Arc::new(ToolPluginHandler);
     let view_image_handler = Arc::new(ViewImageHandler);
     let mcp_handler = Arc::new(McpHandler);
     let mcp_resource_handler = Arc::new(McpResourceHandler);
@@ -1826,6 +2059,11 @@ pub(crate) fn build_specs(
     builder.push_spec(PLAN_TOOL.clone());
     builder.register_handler("update_plan", plan_handler);
 
+    if config.todo_list {
+        builder.push_spec(create_update_todos_tool());
+        builder.register_handler(UPDATE_TODOS_TOOL_NAME, Arc::new(TodoHandler));
+    }
+
     if config.js_repl_enabled {
         builder.push_spec(create_js_repl_tool());
         builder.push_spec(create_js_repl_reset_tool());
@@ -1847,6 +2085,21 @@ pub(crate) fn build_specs(
         builder.register_handler(SEARCH_TOOL_BM25_TOOL_NAME, search_tool_handler);
     }
 
+    if config.semantic_search {
+        builder.push_spec_with_parallel_support(create_semantic_search_tool(), true);
+        builder.register_handler(SEMANTIC_SEARCH_TOOL_NAME, Arc::new(SemanticSearchHandler));
+    }
+
+    if config.fetch_url {
+        builder.push_spec_with_parallel_support(create_fetch_url_tool(), true);
+        builder.register_handler(FETCH_URL_TOOL_NAME, Arc::new(FetchUrlHandler));
+    }
+
+    if config.search_web {
+        builder.push_spec_with_parallel_support(create_search_web_tool(), true);
+        builder.register_handler(SEARCH_WEB_TOOL_NAME, Arc::new(SearchWebHandler));
+    }
+
     if let Some(apply_patch_tool_type) = &config.apply_patch_tool_type {
         match apply_patch_tool_type {
             ApplyPatchToolType::Freeform => {
@@ -1989,6 +2242,21 @@ pub(crate) fn build_specs(
         }
     }
 
+    for tool in &config.tool_plugins {
+        match dynamic_tool_to_openai_tool(tool) {
+            Ok(converted_tool) => {
+                builder.push_spec(ToolSpec::Function(converted_tool));
+                builder.register_handler(tool.name.clone(), tool_plugin_handler.clone());
+            }
+            Err(e) => {
+                tracing::error!(
+                    "Failed to convert tool plugin {:?} to OpenAI tool: {e:?}",
+                    tool.name
+                );
+            }
+        }
+    }
+
     builder
 }
 
@@ -0,0 +1,283 @@
+//! Client for the search APIs behind the `search_web` tool.
+//!
+//! Each provider returns a ranked list of pages with a short text snippet. Snippets are
+//! normalized to plain text so the tool output reads the same regardless of the backend.
+
+use std::time::Duration;
+
+use codex_protocol::models::WebSearchSource;
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::config::types::WebSearchProviderConfig;
+use crate::config::types::WebSearchProviderKind;
+use crate::default_client::build_reqwest_client;
+use crate::fetch_url::decode_entities;
+
+const SEARCH_TIMEOUT: Duration = Duration::from_secs(20);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct WebSearchResult {
+    pub(crate) title: String,
+    pub(crate) url: String,
+    pub(crate) snippet: String,
+}
+
+impl WebSearchResult {
+    pub(crate) fn source(&self) -> WebSearchSource {
+        WebSearchSource {
+            title: self.title.clone(),
+            url: self.url.clone(),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct BraveResponse {
+    #[serde(default)]
+    web: Option<BraveWebResults>,
+}
+
+#[derive(Deserialize)]
+struct BraveWebResults {
+    #[serde(default)]
+    results: Vec<BraveResult>,
+}
+
+#[derive(Deserialize)]
+struct BraveResult {
+    title: String,
+    url: String,
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    extra_snippets: Vec<String>,
+}
+
+/// Tavily and SearXNG share the same result shape.
+#[derive(Deserialize)]
+struct ContentResponse {
+    #[serde(default)]
+    results: Vec<ContentResult>,
+}
+
+#[derive(Deserialize)]
+struct ContentResult {
+    #[serde(default)]
+    title: String,
+    url: String,
+    #[serde(default)]
+    content: String,
+}
+
+/// Run `query` against the configured provider and return at most `limit` results.
+pub(crate) async fn search(
+    config: &WebSearchProviderConfig,
+    query: &str,
+    limit: usize,
+) -> Result<Vec<WebSearchResult>, String> {
+    let Some(base_url) = config.base_url.as_deref() else {
+        return Err(format!(
+            "web_search_provider.base_url must be set for provider `{}`",
+            provider_name(config.provider)
+        ));
+    };
+    let base_url = base_url.trim_end_matches('/');
+    let api_key = match config.api_key_env.as_deref() {
+        Some(env) => Some(std::env::var(env).map_err(|_| format!("{env} is not set"))?),
+        None => None,
+    };
+
+    let count = limit.to_string();
+    let client = build_reqwest_client();
+    let request = match config.provider {
+        WebSearchProviderKind::Brave => client
+            .get(format!("{base_url}/res/v1/web/search"))
+            .query(&[("q", query), ("count", count.as_str())])
+            .header("X-Subscription-Token", api_key.unwrap_or_default()),
+        WebSearchProviderKind::Tavily => client
+            .post(format!("{base_url}/search"))
+            .bearer_auth(api_key.unwrap_or_default())
+            .json(&json!({ "query": query, "max_results": limit })),
+        WebSearchProviderKind::Searxng => {
+            let request = client
+                .get(format!("{base_url}/search"))
+                .query(&[("q", query), ("format", "json")]);
+            match api_key {
+                Some(api_key) => request.bearer_auth(api_key),
+                None => request,
+            }
+        }
+    };
+    let response = request
+        .timeout(SEARCH_TIMEOUT)
+        .send()
+        .await
+        .map_err(|err| format!("search request failed: {err}"))?;
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "<failed to read body>".to_string());
+        return Err(format!("search failed: {status} {}", body.trim()));
+    }
+    let body = response
+        .text()
+        .await
+        .map_err(|err| format!("failed to read search response: {err}"))?;
+
+    let mut results = parse_results(config.provider, &body)?;
+    results.truncate(limit);
+    Ok(results)
+}
+
+fn parse_results(
+    provider: WebSearchProviderKind,
+    body: &str,
+) -> Result<Vec<WebSearchResult>, String> {
+    let parse_error = |err: serde_json::Error| format!("unexpected search response: {err}");
+    let results = match provider {
+        WebSearchProviderKind::Brave => {
+            let response: BraveResponse = serde_json::from_str(body).map_err(parse_error)?;
+            response
+                .web
+                .map(|web| web.results)
+                .unwrap_or_default()
+                .into_iter()
+                .map(|result| {
+                    let mut snippet = result.description;
+                    for extra in result.extra_snippets {
+                        snippet.push(' ');
+                        snippet.push_str(&extra);
+                    }
+                    WebSearchResult {
+                        title: plain_text(&result.title),
+                        url: result.url,
+                        snippet: plain_text(&snippet),
+                    }
+                })
+                .collect()
+        }
+        WebSearchProviderKind::Tavily | WebSearchProviderKind::Searxng => {
+            let response: ContentResponse = serde_json::from_str(body).map_err(parse_error)?;
+            response
+                .results
+                .into_iter()
+                .map(|result| WebSearchResult {
+                    title: plain_text(&result.title),
+                    url: result.url,
+                    snippet: plain_text(&result.content),
+                })
+                .collect()
+        }
+    };
+    Ok(results)
+}
+
+/// Strip inline markup (providers highlight matches with `<strong>`) and collapse whitespace.
+fn plain_text(text: &str) -> String {
+    let mut stripped = String::with_capacity(text.len());
+    let mut in_tag = false;
+    for c in text.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => stripped.push(c),
+            _ => {}
+        }
+    }
+    decode_entities(&stripped)
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn provider_name(provider: WebSearchProviderKind) -> &'static str {
+    match provider {
+        WebSearchProviderKind::Brave => "brave",
+        WebSearchProviderKind::Tavily => "tavily",
+        WebSearchProviderKind::Searxng => "searxng",
+    }
+}
+
+/// Render results as the tool output: a numbered list with the full snippet under each link.
+pub(crate) fn format_results(query: &str, results: &[WebSearchResult]) -> String {
+    if results.is_empty() {
+        return format!("No results for \"{query}\".");
+    }
+    results
+        .iter()
+        .enumerate()
+        .map(|(index, result)| {
+            let mut entry = format!("{}. {}\n   {}", index + 1, result.title, result.url);
+            if !result.snippet.is_empty() {
+                entry.push_str(&format!("\n   {}", result.snippet));
+            }
+            entry
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn parses_brave_results_and_strips_highlighting() {
+        let body = r#"{"web":{"results":[{"title":"Tokio &amp; async","url":"https://tokio.rs/","description":"An <strong>asynchronous</strong> runtime","extra_snippets":["for Rust."]}]}}"#;
+
+        assert_eq!(
+            parse_results(WebSearchProviderKind::Brave, body).expect("results"),
+            vec![WebSearchResult {
+                title: "Tokio & async".to_string(),
+                url: "https://tokio.rs/".to_string(),
+                snippet: "An asynchronous runtime for Rust.".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn parses_searxng_results() {
+        let body = r#"{"query":"serde","results":[{"title":"Serde","url":"https://serde.rs/","content":"Serialization framework."},{"url":"https://docs.rs/serde"}]}"#;
+
+        assert_eq!(
+            parse_results(WebSearchProviderKind::Searxng, body).expect("results"),
+            vec![
+                WebSearchResult {
+                    title: "Serde".to_string(),
+                    url: "https://serde.rs/".to_string(),
+                    snippet: "Serialization framework.".to_string(),
+                },
+                WebSearchResult {
+                    title: String::new(),
+                    url: "https://docs.rs/serde".to_string(),
+                    snippet: String::new(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn formats_numbered_results_with_snippets() {
+        let results = vec![
+            WebSearchResult {
+                title: "Serde".to_string(),
+                url: "https://serde.rs/".to_string(),
+                snippet: "Serialization framework.".to_string(),
+            },
+            WebSearchResult {
+                title: "docs.rs".to_string(),
+                url: "https://docs.rs/serde".to_string(),
+                snippet: String::new(),
+            },
+        ];
+
+        assert_eq!(
+            format_results("serde", &results),
+            "1. Serde\n   https://serde.rs/\n   Serialization framework.\n\n2. docs.rs\n   https://docs.rs/serde"
+        );
+    }
+}
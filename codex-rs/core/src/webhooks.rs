@@ -0,0 +1,202 @@
+//! HTTP webhooks for key session events (`[[webhooks]]`).
+//!
+//! Every event the session emits is offered to [`Webhooks::deliver`]. Events that map to a
+//! [`WebhookEvent`] are POSTed as JSON to each endpoint that wants them, in the background so a
+//! slow endpoint never holds up the turn. The body names the event, the session, and the turn,
+//! and carries the protocol event itself under `data`. Deliveries for `session_ended` are
+//! awaited, since the process may exit right after the session shuts down. Failures are only
+//! logged.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::SecondsFormat;
+use chrono::Utc;
+use codex_protocol::ThreadId;
+use futures::future::join_all;
+use hmac::Hmac;
+use hmac::Mac;
+use serde::Serialize;
+use sha2::Sha256;
+
+use crate::config::types::WebhookConfig;
+use crate::config::types::WebhookEvent;
+use crate::default_client::build_reqwest_client;
+use crate::protocol::Event;
+use crate::protocol::EventMsg;
+
+/// Header carrying `sha256=<hex>` when the webhook has a secret.
+const SIGNATURE_HEADER: &str = "X-Codex-Signature";
+const DELIVERY_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Serialize)]
+struct WebhookBody<'a> {
+    event: WebhookEvent,
+    session_id: String,
+    turn_id: &'a str,
+    timestamp: String,
+    data: &'a EventMsg,
+}
+
+#[derive(Clone, Default)]
+pub(crate) struct Webhooks {
+    inner: Option<Arc<WebhooksInner>>,
+}
+
+struct WebhooksInner {
+    endpoints: Vec<WebhookConfig>,
+    session_id: ThreadId,
+    client: reqwest::Client,
+}
+
+impl Webhooks {
+    pub(crate) fn new(endpoints: &[WebhookConfig], session_id: ThreadId) -> Self {
+        if endpoints.is_empty() {
+            return Self::default();
+        }
+        Self {
+            inner: Some(Arc::new(WebhooksInner {
+                endpoints: endpoints.to_vec(),
+                session_id,
+                client: build_reqwest_client(),
+            })),
+        }
+    }
+
+    /// POST `event` to the endpoints subscribed to it.
+    pub(crate) async fn deliver(&self, event: &Event) {
+        let Some(inner) = self.inner.as_ref() else {
+            return;
+        };
+        let Some(kind) = webhook_event(&event.msg) else {
+            return;
+        };
+        let body = WebhookBody {
+            event: kind,
+            session_id: inner.session_id.to_string(),
+            turn_id: &event.id,
+            timestamp: Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true),
+            data: &event.msg,
+        };
+        let body = match serde_json::to_vec(&body) {
+            Ok(body) => body,
+            Err(err) => {
+                tracing::warn!("failed to serialize webhook payload: {err}");
+                return;
+            }
+        };
+        let deliveries: Vec<_> = inner
+            .endpoints
+            .iter()
+            .filter(|endpoint| endpoint.wants(kind))
+            .map(|endpoint| {
+                tokio::spawn(post(inner.client.clone(), endpoint.clone(), body.clone()))
+            })
+            .collect();
+        if kind == WebhookEvent::SessionEnded {
+            join_all(deliveries).await;
+        }
+    }
+}
+
+fn webhook_event(msg: &EventMsg) -> Option<WebhookEvent> {
+    match msg {
+        EventMsg::TurnComplete(_) => Some(WebhookEvent::TurnCompleted),
+        EventMsg::ExecApprovalRequest(_)
+        | EventMsg::ApplyPatchApprovalRequest(_)
+        | EventMsg::PlanApprovalRequest(_) => Some(WebhookEvent::ApprovalRequested),
+        EventMsg::PatchApplyEnd(_) => Some(WebhookEvent::PatchApplied),
+        EventMsg::ShutdownComplete => Some(WebhookEvent::SessionEnded),
+        _ => None,
+    }
+}
+
+async fn post(client: reqwest::Client, endpoint: WebhookConfig, body: Vec<u8>) {
+    let mut request = client
+        .post(&endpoint.url)
+        .timeout(DELIVERY_TIMEOUT)
+        .header(reqwest::header::CONTENT_TYPE, "application/json");
+    if let Some(signature) = endpoint
+        .secret
+        .as_deref()
+        .and_then(|secret| signature(secret, &body))
+    {
+        request = request.header(SIGNATURE_HEADER, signature);
+    }
+    match request.body(body).send().await {
+        Ok(response) if !response.status().is_success() => {
+            tracing::warn!(
+                "webhook {} answered with {}",
+                endpoint.url,
+                response.status()
+            );
+        }
+        Ok(_) => {}
+        Err(err) => tracing::warn!("webhook {} failed: {err}", endpoint.url),
+    }
+}
+
+/// `sha256=<hex>` HMAC of `body` keyed with `secret`.
+fn signature(secret: &str, body: &[u8]) -> Option<String> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).ok()?;
+    mac.update(body);
+    let digest = mac.finalize().into_bytes();
+    let hex: String = digest.iter().map(|byte| format!("{byte:02x}")).collect();
+    Some(format!("sha256={hex}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::protocol::AgentMessageEvent;
+    use codex_protocol::protocol::TurnCompleteEvent;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn signature_matches_the_hmac_reference() {
+        // RFC 4231 test case 2.
+        assert_eq!(
+            signature("Jefe", b"what do ya want for nothing?").as_deref(),
+            Some("sha256=5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843")
+        );
+    }
+
+    #[test]
+    fn only_key_events_are_delivered() {
+        assert_eq!(
+            webhook_event(&EventMsg::TurnComplete(TurnCompleteEvent {
+                turn_id: "turn-1".to_string(),
+                last_agent_message: None,
+            })),
+            Some(WebhookEvent::TurnCompleted)
+        );
+        assert_eq!(
+            webhook_event(&EventMsg::ShutdownComplete),
+            Some(WebhookEvent::SessionEnded)
+        );
+        assert_eq!(
+            webhook_event(&EventMsg::AgentMessage(AgentMessageEvent {
+                message: "done".to_string(),
+                phase: None,
+            })),
+            None
+        );
+    }
+
+    #[test]
+    fn endpoints_without_an_event_list_want_everything() {
+        let endpoint = WebhookConfig {
+            url: "https://example.com/hook".to_string(),
+            secret: None,
+            events: None,
+        };
+        assert!(endpoint.wants(WebhookEvent::PatchApplied));
+
+        let endpoint = WebhookConfig {
+            events: Some(vec![WebhookEvent::SessionEnded]),
+            ..endpoint
+        };
+        assert!(!endpoint.wants(WebhookEvent::PatchApplied));
+        assert!(endpoint.wants(WebhookEvent::SessionEnded));
+    }
+}
@@ -7,15 +7,22 @@ use codex_protocol::protocol::Op;
 use codex_protocol::user_input::UserInput;
 use core_test_support::responses::ev_completed;
 use core_test_support::responses::ev_function_call;
+use core_test_support::responses::ev_message_item_added;
+use core_test_support::responses::ev_output_text_delta;
 use core_test_support::responses::ev_response_created;
 use core_test_support::responses::mount_sse_once;
 use core_test_support::responses::mount_sse_sequence;
 use core_test_support::responses::sse;
 use core_test_support::responses::start_mock_server;
+use core_test_support::streaming_sse::StreamingSseChunk;
+use core_test_support::streaming_sse::start_streaming_sse_server;
 use core_test_support::test_codex::test_codex;
 use core_test_support::wait_for_event;
+use pretty_assertions::assert_eq;
 use regex_lite::Regex;
+use serde_json::Value;
 use serde_json::json;
+use tokio::sync::oneshot;
 
 /// Integration test: spawn a long‑running shell_command tool via a mocked Responses SSE
 /// function call, then interrupt the session and expect TurnAborted.
@@ -239,3 +246,121 @@ async fn interrupt_persists_turn_aborted_marker_in_next_request() {
         "expected <turn_aborted> marker in follow-up request"
     );
 }
+
+/// Interrupting a turn while the assistant reply is still streaming keeps the text already shown
+/// to the user: the next request carries the partial reply, followed by the `<turn_aborted>`
+/// marker telling the model it was cut off.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn interrupt_mid_stream_records_partial_agent_message() {
+    // Held for the whole test so the first response never completes on its own.
+    let (_hold_completion_tx, hold_completion_rx) = oneshot::channel();
+    let first_chunks = vec![
+        StreamingSseChunk {
+            gate: None,
+            body: sse(vec![ev_response_created("resp-partial")]),
+        },
+        StreamingSseChunk {
+            gate: None,
+            body: sse(vec![ev_message_item_added("msg-partial", "")]),
+        },
+        StreamingSseChunk {
+            gate: None,
+            body: sse(vec![ev_output_text_delta("Partial ")]),
+        },
+        StreamingSseChunk {
+            gate: None,
+            body: sse(vec![ev_output_text_delta("answer")]),
+        },
+        StreamingSseChunk {
+            gate: Some(hold_completion_rx),
+            body: sse(vec![ev_completed("resp-partial")]),
+        },
+    ];
+    let follow_up_chunks = vec![
+        StreamingSseChunk {
+            gate: None,
+            body: sse(vec![ev_response_created("resp-followup")]),
+        },
+        StreamingSseChunk {
+            gate: None,
+            body: sse(vec![ev_completed("resp-followup")]),
+        },
+    ];
+
+    let (server, _completions) =
+        start_streaming_sse_server(vec![first_chunks, follow_up_chunks]).await;
+
+    let codex = test_codex()
+        .with_model("gpt-5.1")
+        .build_with_streaming_server(&server)
+        .await
+        .unwrap()
+        .codex;
+
+    codex
+        .submit(Op::UserInput {
+            items: vec![UserInput::Text {
+                text: "start partial reply".into(),
+                text_elements: Vec::new(),
+            }],
+            final_output_json_schema: None,
+        })
+        .await
+        .unwrap();
+
+    wait_for_event(
+        &codex,
+        |ev| matches!(ev, EventMsg::AgentMessageContentDelta(delta) if delta.delta == "answer"),
+    )
+    .await;
+
+    codex.submit(Op::Interrupt).await.unwrap();
+
+    wait_for_event(&codex, |ev| matches!(ev, EventMsg::TurnAborted(_))).await;
+
+    codex
+        .submit(Op::UserInput {
+            items: vec![UserInput::Text {
+                text: "follow up".into(),
+                text_elements: Vec::new(),
+            }],
+            final_output_json_schema: None,
+        })
+        .await
+        .unwrap();
+
+    wait_for_event(&codex, |ev| matches!(ev, EventMsg::TurnComplete(_))).await;
+
+    let requests = server.requests().await;
+    assert_eq!(requests.len(), 2, "expected two calls to the responses API");
+
+    let follow_up_body: Value =
+        serde_json::from_slice(&requests[1]).expect("parse follow-up request");
+    let input = follow_up_body["input"]
+        .as_array()
+        .expect("follow-up request input");
+    let partial_idx = input
+        .iter()
+        .position(|item| item["role"] == "assistant")
+        .expect("partial assistant message in follow-up request");
+    assert_eq!(
+        input[partial_idx..partial_idx + 2],
+        [
+            json!({
+                "type": "message",
+                "role": "assistant",
+                "content": [{"type": "output_text", "text": "Partial answer"}]
+            }),
+            json!({
+                "type": "message",
+                "role": "user",
+                "content": [{
+                    "type": "input_text",
+                    "text": "<turn_aborted>\nThe user interrupted the previous turn on purpose. Any running unified exec processes were terminated. If any tools/commands were aborted, they may have partially executed; verify current state before retrying. If your last message ends abruptly, it was cut off by this interruption.\n</turn_aborted>"
+                }]
+            }),
+        ]
+    );
+
+    server.shutdown().await;
+}
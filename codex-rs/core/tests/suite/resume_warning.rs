@@ -45,6 +45,7 @@ fn resume_history(
         developer_instructions: None,
         final_output_json_schema: None,
         truncation_policy: None,
+        sampling: None,
     };
 
     InitialHistory::Resumed(ResumedHistory {
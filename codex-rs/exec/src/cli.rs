@@ -75,6 +75,30 @@ pub struct Cli {
     #[arg(long = "ephemeral", global = true, default_value_t = false)]
     pub ephemeral: bool,
 
+    /// Disable every mutating tool (file writes, patches, and shell commands outside the
+    /// known-safe allowlist) and force the read-only sandbox.
+    #[arg(
+        long = "read-only",
+        global = true,
+        default_value_t = false,
+        conflicts_with_all = ["full_auto", "dangerously_bypass_approvals_and_sandbox"]
+    )]
+    pub read_only: bool,
+
+    /// Preview commands and patches instead of running them: the model receives simulated
+    /// results, and each skipped action is printed as "would run" or "would apply".
+    #[arg(
+        long = "dry-run",
+        global = true,
+        default_value_t = false,
+        conflicts_with_all = [
+            "read_only",
+            "full_auto",
+            "dangerously_bypass_approvals_and_sandbox"
+        ]
+    )]
+    pub dry_run: bool,
+
     /// Path to a JSON Schema file describing the model's final response shape.
     #[arg(long = "output-schema", value_name = "FILE")]
     pub output_schema: Option<PathBuf>,
@@ -244,6 +268,15 @@ pub struct ReviewArgs {
     #[arg(long = "title", value_name = "TITLE", requires = "commit")]
     pub commit_title: Option<String>,
 
+    /// Review an existing diff: a git ref, a range such as `main..feature`, or a patch file.
+    /// `codex review --diff` opens the review interactively. PROMPT adds instructions.
+    #[arg(
+        long = "diff",
+        value_name = "REF|PATCH",
+        conflicts_with_all = ["uncommitted", "base", "commit"]
+    )]
+    pub diff: Option<String>,
+
     /// Custom review instructions. If `-` is used, read from stdin.
     #[arg(value_name = "PROMPT", value_hint = clap::ValueHint::Other)]
     pub prompt: Option<String>,
@@ -15,6 +15,8 @@ use codex_protocol::protocol::CollabCloseEndEvent;
 use codex_protocol::protocol::CollabWaitingBeginEvent;
 use codex_protocol::protocol::CollabWaitingEndEvent;
 use codex_protocol::protocol::DeprecationNoticeEvent;
+use codex_protocol::protocol::DryRunAction;
+use codex_protocol::protocol::DryRunActionEvent;
 use codex_protocol::protocol::ErrorEvent;
 use codex_protocol::protocol::Event;
 use codex_protocol::protocol::EventMsg;
@@ -27,6 +29,7 @@ use codex_protocol::protocol::McpToolCallBeginEvent;
 use codex_protocol::protocol::McpToolCallEndEvent;
 use codex_protocol::protocol::PatchApplyBeginEvent;
 use codex_protocol::protocol::PatchApplyEndEvent;
+use codex_protocol::protocol::PatchChecksEvent;
 use codex_protocol::protocol::SessionConfiguredEvent;
 use codex_protocol::protocol::StreamErrorEvent;
 use codex_protocol::protocol::TurnAbortReason;
@@ -52,6 +55,8 @@ use crate::event_processor::EventProcessor;
 use crate::event_processor::handle_last_message;
 use codex_protocol::plan_tool::StepStatus;
 use codex_protocol::plan_tool::UpdatePlanArgs;
+use codex_protocol::todo_tool::TodoListEvent;
+use codex_protocol::todo_tool::TodoStatus;
 use codex_utils_sandbox_summary::create_config_summary_entries;
 
 /// This should be configurable. When used in CI, users may not want to impose
@@ -440,6 +445,7 @@ impl EventProcessor for EventProcessorWithHumanOutput {
                 call_id: _,
                 query,
                 action,
+                sources: _,
             }) => {
                 let detail = web_search_detail(Some(&action), &query);
                 if detail.is_empty() {
@@ -581,6 +587,59 @@ impl EventProcessor for EventProcessorWithHumanOutput {
                     eprintln!("{}", line.style(self.dimmed));
                 }
             }
+            EventMsg::PatchChecks(PatchChecksEvent { results, .. }) => {
+                for result in results {
+                    if result.exit_code == 0 {
+                        if !result.formatted_files.is_empty() {
+                            ts_msg!(
+                                self,
+                                "{}",
+                                format!(
+                                    "{} formatted {} file(s)",
+                                    result.name,
+                                    result.formatted_files.len()
+                                )
+                                .style(self.dimmed)
+                            );
+                        }
+                        continue;
+                    }
+                    let title = format!("{} exited {}:", result.name, result.exit_code);
+                    ts_msg!(self, "{}", title.style(self.red));
+                    for line in result.output.lines() {
+                        eprintln!("{}", line.style(self.dimmed));
+                    }
+                }
+            }
+            EventMsg::DryRunAction(DryRunActionEvent { action, .. }) => match action {
+                DryRunAction::Exec { command, cwd } => {
+                    ts_msg!(
+                        self,
+                        "{}\n{} in {}",
+                        "would run".style(self.italic).style(self.magenta),
+                        escape_command(&command).style(self.bold),
+                        cwd.to_string_lossy(),
+                    );
+                }
+                DryRunAction::Patch { changes } => {
+                    ts_msg!(
+                        self,
+                        "{}",
+                        "would apply".style(self.italic).style(self.magenta),
+                    );
+                    let mut paths: Vec<_> = changes.keys().collect();
+                    paths.sort();
+                    for path in paths {
+                        let change = &changes[path];
+                        eprintln!("{} {}", format_file_change(change), path.to_string_lossy());
+                        if let FileChange::Update { unified_diff, .. } = change {
+                            for diff_line in unified_diff.lines() {
+                                eprintln!("{}", diff_line.style(self.dimmed));
+                            }
+                        }
+                    }
+                }
+            },
             EventMsg::TurnDiff(TurnDiffEvent { unified_diff }) => {
                 ts_msg!(
                     self,
@@ -649,6 +708,18 @@ impl EventProcessor for EventProcessorWithHumanOutput {
                     }
                 }
             }
+            EventMsg::TodoListUpdate(TodoListEvent { items }) => {
+                ts_msg!(self, "{}", "Todo list".style(self.magenta));
+                for item in items {
+                    let marker = match item.status {
+                        TodoStatus::Done => "✓".style(self.green),
+                        TodoStatus::InProgress => "→".style(self.cyan),
+                        TodoStatus::Pending => "•".style(self.dimmed),
+                        TodoStatus::Cancelled => "✗".style(self.red),
+                    };
+                    ts_msg!(self, "  {} #{} {}", marker, item.id, item.text);
+                }
+            }
             EventMsg::ViewImageToolCall(view) => {
                 ts_msg!(
                     self,
@@ -668,6 +739,12 @@ impl EventProcessor for EventProcessorWithHumanOutput {
                     TurnAbortReason::ReviewEnded => {
                         ts_msg!(self, "task aborted: review ended");
                     }
+                    TurnAbortReason::Timeout => {
+                        ts_msg!(self, "task aborted: timed out waiting for the model");
+                    }
+                    TurnAbortReason::Error => {
+                        ts_msg!(self, "task aborted: error");
+                    }
                 }
                 return CodexStatus::InitiateShutdown;
             }
@@ -837,8 +914,11 @@ impl EventProcessor for EventProcessorWithHumanOutput {
             }
             EventMsg::ShutdownComplete => return CodexStatus::Shutdown,
             EventMsg::ThreadNameUpdated(_)
+            | EventMsg::TurnPhase(_)
             | EventMsg::ExecApprovalRequest(_)
             | EventMsg::ApplyPatchApprovalRequest(_)
+            | EventMsg::PlanApprovalRequest(_)
+            | EventMsg::CompactionReviewRequest(_)
             | EventMsg::TerminalInteraction(_)
             | EventMsg::ExecCommandOutputDelta(_)
             | EventMsg::GetHistoryEntryResponse(_)
@@ -918,8 +998,11 @@ impl EventProcessorWithHumanOutput {
             EventMsg::ThreadNameUpdated(_)
                 | EventMsg::TokenCount(_)
                 | EventMsg::TurnStarted(_)
+                | EventMsg::TurnPhase(_)
                 | EventMsg::ExecApprovalRequest(_)
                 | EventMsg::ApplyPatchApprovalRequest(_)
+                | EventMsg::PlanApprovalRequest(_)
+                | EventMsg::CompactionReviewRequest(_)
                 | EventMsg::TerminalInteraction(_)
                 | EventMsg::ExecCommandOutputDelta(_)
                 | EventMsg::GetHistoryEntryResponse(_)
@@ -34,6 +34,8 @@ use codex_core::format_exec_policy_error_with_source;
 use codex_core::git_info::get_git_repo_root;
 use codex_core::models_manager::collaboration_mode_presets::CollaborationModesConfig;
 use codex_core::models_manager::manager::RefreshStrategy;
+use codex_core::review_diff::diff_review_request;
+use codex_core::review_diff::load_review_diff;
 use codex_otel::set_parent_from_context;
 use codex_otel::traceparent_context_from_env;
 use codex_protocol::approvals::ElicitationAction;
@@ -74,8 +76,12 @@ use uuid::Uuid;
 use crate::cli::Command as ExecCommand;
 use crate::event_processor::CodexStatus;
 use crate::event_processor::EventProcessor;
+use codex_core::default_client::set_default_client_api_log;
+use codex_core::default_client::set_default_client_proxy;
 use codex_core::default_client::set_default_client_residency_requirement;
+use codex_core::default_client::set_default_client_tls;
 use codex_core::default_client::set_default_originator;
+use codex_core::find_archived_thread_path_by_id_str;
 use codex_core::find_thread_path_by_id_str;
 use codex_core::find_thread_path_by_name_str;
 
@@ -143,6 +149,8 @@ pub async fn run_main(cli: Cli, arg0_paths: Arg0DispatchPaths) -> anyhow::Result
         skip_git_repo_check,
         add_dir,
         ephemeral,
+        read_only,
+        dry_run,
         color,
         last_message_file,
         json: json_mode,
@@ -320,6 +328,8 @@ pub async fn run_main(cli: Cli, arg0_paths: Arg0DispatchPaths) -> anyhow::Result
         show_raw_agent_reasoning: oss.then_some(true),
         tools_web_search_request: None,
         ephemeral: ephemeral.then_some(true),
+        read_only: read_only.then_some(true),
+        dry_run: dry_run.then_some(true),
         additional_writable_roots: add_dir,
     };
 
@@ -343,6 +353,15 @@ pub async fn run_main(cli: Cli, arg0_paths: Arg0DispatchPaths) -> anyhow::Result
     }
 
     set_default_client_residency_requirement(config.enforce_residency.value());
+    if let Err(err) = set_default_client_proxy(config.outbound_proxy.as_ref()) {
+        eprintln!("Error configuring outbound proxy: {err}");
+        std::process::exit(1);
+    }
+    set_default_client_api_log(config.api_log_path.as_ref().map(AbsolutePathBuf::as_path));
+    if let Err(err) = set_default_client_tls(config.tls.as_ref()) {
+        eprintln!("Error loading TLS certificates: {err}");
+        std::process::exit(1);
+    }
 
     if let Err(err) = enforce_login_restrictions(&config) {
         eprintln!("{err}");
@@ -513,7 +532,16 @@ async fn run_exec_session(args: ExecRunArgs) -> anyhow::Result<()> {
 
     let (initial_operation, prompt_summary) = match (command, prompt, images) {
         (Some(ExecCommand::Review(review_cli)), _, _) => {
-            let review_request = build_review_request(review_cli)?;
+            let review_request = match review_cli.diff.clone() {
+                Some(source) => {
+                    let diff = load_review_diff(&source, &config.cwd)
+                        .await
+                        .map_err(anyhow::Error::msg)?;
+                    let instructions = review_cli.prompt.map(|prompt| resolve_prompt(Some(prompt)));
+                    diff_review_request(&diff, instructions.as_deref())
+                }
+                None => build_review_request(review_cli)?,
+            };
             let summary = codex_core::review_prompts::user_facing_hint(&review_request.target);
             (InitialOperation::Review { review_request }, summary)
         }
@@ -785,6 +813,8 @@ fn should_suppress_agent_job_event(msg: &EventMsg) -> bool {
         msg,
         EventMsg::ExecApprovalRequest(_)
             | EventMsg::ApplyPatchApprovalRequest(_)
+            | EventMsg::PlanApprovalRequest(_)
+            | EventMsg::CompactionReviewRequest(_)
             | EventMsg::RequestUserInput(_)
             | EventMsg::DynamicToolCallRequest(_)
             | EventMsg::DynamicToolCallResponse(_)
@@ -828,8 +858,10 @@ async fn resolve_resume_path(
         }
     } else if let Some(id_str) = args.session_id.as_deref() {
         if Uuid::parse_str(id_str).is_ok() {
-            let path = find_thread_path_by_id_str(&config.codex_home, id_str).await?;
-            Ok(path)
+            match find_thread_path_by_id_str(&config.codex_home, id_str).await? {
+                Some(path) => Ok(Some(path)),
+                None => Ok(find_archived_thread_path_by_id_str(&config.codex_home, id_str).await?),
+            }
         } else {
             let path = find_thread_path_by_name_str(&config.codex_home, id_str).await?;
             Ok(path)
@@ -998,7 +1030,7 @@ fn build_review_request(args: ReviewArgs) -> anyhow::Result<ReviewRequest> {
         }
     } else {
         anyhow::bail!(
-            "Specify --uncommitted, --base, --commit, or provide custom review instructions"
+            "Specify --uncommitted, --base, --commit, --diff, or provide custom review instructions"
         );
     };
 
@@ -1056,6 +1088,7 @@ mod tests {
             base: None,
             commit: None,
             commit_title: None,
+            diff: None,
             prompt: None,
         })
         .expect("builds uncommitted review request");
@@ -1075,6 +1108,7 @@ mod tests {
             base: None,
             commit: Some("123456789".to_string()),
             commit_title: Some("Add review command".to_string()),
+            diff: None,
             prompt: None,
         })
         .expect("builds commit review request");
@@ -1097,6 +1131,7 @@ mod tests {
             base: None,
             commit: None,
             commit_title: None,
+            diff: None,
             prompt: Some("  custom review instructions  ".to_string()),
         })
         .expect("builds custom review request");
@@ -143,6 +143,7 @@ fn web_search_end_emits_item_completed() {
             call_id: "call-123".to_string(),
             query: query.clone(),
             action: action.clone(),
+            sources: Vec::new(),
         }),
     ));
 
@@ -208,6 +209,7 @@ fn web_search_begin_then_end_reuses_item_id() {
             call_id: "call-1".to_string(),
             query: "rust async await".to_string(),
             action: action.clone(),
+            sources: Vec::new(),
         }),
     ));
     let ThreadEvent::ItemCompleted(ItemCompletedEvent {
@@ -808,6 +810,7 @@ fn stream_error_event_produces_error() {
             message: "retrying".to_string(),
             codex_error_info: Some(CodexErrorInfo::Other),
             additional_details: None,
+            retry_delay_ms: None,
         }),
     ));
     assert_eq!(
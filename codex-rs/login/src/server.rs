@@ -21,6 +21,8 @@ use codex_app_server_protocol::AuthMode;
 use codex_core::auth::AuthCredentialsStoreMode;
 use codex_core::auth::AuthDotJson;
 use codex_core::auth::save_auth;
+use codex_core::default_client::apply_client_tls;
+use codex_core::default_client::apply_outbound_proxy;
 use codex_core::default_client::originator;
 use codex_core::token_data::TokenData;
 use codex_core::token_data::parse_chatgpt_jwt_claims;
@@ -533,6 +535,14 @@ pub(crate) struct ExchangedTokens {
     pub refresh_token: String,
 }
 
+/// HTTP client for talking to the auth issuer, using the configured outbound proxy and TLS
+/// certificates.
+pub(crate) fn login_http_client() -> io::Result<reqwest::Client> {
+    apply_client_tls(apply_outbound_proxy(reqwest::Client::builder()))
+        .build()
+        .map_err(io::Error::other)
+}
+
 /// Exchanges an authorization code for tokens.
 pub(crate) async fn exchange_code_for_tokens(
     issuer: &str,
@@ -548,7 +558,7 @@ pub(crate) async fn exchange_code_for_tokens(
         refresh_token: String,
     }
 
-    let client = reqwest::Client::new();
+    let client = login_http_client()?;
     let resp = client
         .post(format!("{issuer}/oauth/token"))
         .header("Content-Type", "application/x-www-form-urlencoded")
@@ -853,7 +863,7 @@ pub(crate) async fn obtain_api_key(
     struct ExchangeResp {
         access_token: String,
     }
-    let client = reqwest::Client::new();
+    let client = login_http_client()?;
     let resp = client
         .post(format!("{issuer}/oauth/token"))
         .header("Content-Type", "application/x-www-form-urlencoded")
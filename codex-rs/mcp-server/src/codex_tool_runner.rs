@@ -343,11 +343,17 @@ async fn run_codex_tool_session_inner(
                     | EventMsg::StreamError(_)
                     | EventMsg::PatchApplyBegin(_)
                     | EventMsg::PatchApplyEnd(_)
+                    | EventMsg::PatchChecks(_)
+                    | EventMsg::DryRunAction(_)
+                    | EventMsg::TurnPhase(_)
                     | EventMsg::TurnDiff(_)
                     | EventMsg::WebSearchBegin(_)
                     | EventMsg::WebSearchEnd(_)
                     | EventMsg::GetHistoryEntryResponse(_)
                     | EventMsg::PlanUpdate(_)
+                    | EventMsg::TodoListUpdate(_)
+                    | EventMsg::PlanApprovalRequest(_)
+                    | EventMsg::CompactionReviewRequest(_)
                     | EventMsg::TurnAborted(_)
                     | EventMsg::UserMessage(_)
                     | EventMsg::ShutdownComplete
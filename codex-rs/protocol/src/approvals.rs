@@ -5,6 +5,7 @@ use crate::mcp::RequestId;
 use crate::models::MacOsSeatbeltProfileExtensions;
 use crate::models::PermissionProfile;
 use crate::parse_command::ParsedCommand;
+use crate::plan_tool::PlanItemArg;
 use crate::protocol::FileChange;
 use crate::protocol::ReviewDecision;
 use crate::protocol::SandboxPolicy;
@@ -225,3 +226,43 @@ pub struct ApplyPatchApprovalRequestEvent {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub grant_root: Option<PathBuf>,
 }
+
+/// The model presented a plan and cannot run mutating tools until the user approves it
+/// (`require_plan_approval`). Answered with `Op::PatchApproval` using `call_id`.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct PlanApprovalRequestEvent {
+    /// Responses API call id of the `update_plan` call that presented the plan.
+    pub call_id: String,
+    /// Turn ID that this plan belongs to.
+    pub turn_id: String,
+    /// Optional explanation the model gave alongside the plan.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub explanation: Option<String>,
+    pub plan: Vec<PlanItemArg>,
+}
+
+/// Auto-compaction summarized older turns and waits for the user to accept, edit, or reject
+/// the summary before it replaces them (`confirm_auto_compaction`). Answered with
+/// `Op::CompactionReview` using `id`.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct CompactionReviewRequestEvent {
+    /// Identifier to echo back in `Op::CompactionReview`.
+    pub id: String,
+    /// Turn ID that triggered the compaction.
+    pub turn_id: String,
+    /// The generated summary that would replace the compacted turns.
+    pub summary: String,
+}
+
+/// The user's answer to a `CompactionReviewRequestEvent`.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[ts(tag = "type")]
+pub enum CompactionReviewDecision {
+    /// Replace the older turns with the generated summary.
+    Accept,
+    /// Replace the older turns with this summary instead.
+    Edit { summary: String },
+    /// Keep the history as it is and stop the turn.
+    Reject,
+}
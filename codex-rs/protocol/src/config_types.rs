@@ -121,6 +121,45 @@ pub enum ServiceTier {
     Flex,
 }
 
+/// Sampling overrides sent with each model request. `None` leaves the provider default.
+///
+/// Reasoning models reject these parameters, so they only apply to models without reasoning.
+#[derive(Debug, Serialize, Deserialize, Default, Clone, Copy, PartialEq, JsonSchema, TS)]
+pub struct SamplingParams {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub top_p: Option<f32>,
+}
+
+impl SamplingParams {
+    pub const MAX_TEMPERATURE: f32 = 2.0;
+
+    pub fn is_default(&self) -> bool {
+        self.temperature.is_none() && self.top_p.is_none()
+    }
+
+    /// Check the values against the ranges the Responses API accepts.
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(temperature) = self.temperature
+            && !(0.0..=Self::MAX_TEMPERATURE).contains(&temperature)
+        {
+            return Err(format!(
+                "temperature must be between 0 and {}, got {temperature}",
+                Self::MAX_TEMPERATURE
+            ));
+        }
+        if let Some(top_p) = self.top_p
+            && !(top_p > 0.0 && top_p <= 1.0)
+        {
+            return Err(format!(
+                "top_p must be greater than 0 and at most 1, got {top_p}"
+            ));
+        }
+        Ok(())
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Display, JsonSchema, TS)]
 #[serde(rename_all = "lowercase")]
 #[strum(serialize_all = "lowercase")]
@@ -345,6 +384,27 @@ mod tests {
         assert_eq!(expected, mode.apply_mask(&mask));
     }
 
+    #[test]
+    fn sampling_params_validate_ranges() {
+        let ok = SamplingParams {
+            temperature: Some(0.0),
+            top_p: Some(1.0),
+        };
+        assert_eq!(Ok(()), ok.validate());
+
+        let hot = SamplingParams {
+            temperature: Some(2.5),
+            top_p: None,
+        };
+        assert!(hot.validate().is_err());
+
+        let zero_top_p = SamplingParams {
+            temperature: None,
+            top_p: Some(0.0),
+        };
+        assert!(zero_top_p.validate().is_err());
+    }
+
     #[test]
     fn mode_kind_deserializes_alias_values_to_default() {
         for alias in ["code", "pair_programming", "execute", "custom"] {
@@ -1,5 +1,6 @@
 use crate::models::MessagePhase;
 use crate::models::WebSearchAction;
+use crate::models::WebSearchSource;
 use crate::protocol::AgentMessageEvent;
 use crate::protocol::AgentReasoningEvent;
 use crate::protocol::AgentReasoningRawContentEvent;
@@ -79,6 +80,9 @@ pub struct WebSearchItem {
     pub id: String,
     pub query: String,
     pub action: WebSearchAction,
+    /// Results of a provider-backed search, best match first. Empty for hosted search.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sources: Vec<WebSearchSource>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, TS, JsonSchema, PartialEq)]
@@ -243,6 +247,7 @@ impl WebSearchItem {
             call_id: self.id.clone(),
             query: self.query.clone(),
             action: self.action.clone(),
+            sources: self.sources.clone(),
         })
     }
 }
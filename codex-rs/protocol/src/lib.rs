@@ -15,4 +15,5 @@ pub mod parse_command;
 pub mod plan_tool;
 pub mod protocol;
 pub mod request_user_input;
+pub mod todo_tool;
 pub mod user_input;
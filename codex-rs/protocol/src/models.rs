@@ -878,6 +878,13 @@ pub enum WebSearchAction {
     Other,
 }
 
+/// A page returned by a provider-backed web search.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema, TS)]
+pub struct WebSearchSource {
+    pub title: String,
+    pub url: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema, TS)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum ReasoningItemReasoningSummary {
@@ -893,6 +900,13 @@ pub enum ReasoningItemContent {
 
 impl From<Vec<UserInput>> for ResponseInputItem {
     fn from(items: Vec<UserInput>) -> Self {
+        Self::from_user_input(items, PromptImageMode::ResizeToFit)
+    }
+}
+
+impl ResponseInputItem {
+    /// The user message for `items`, with local images prepared for upload in `image_mode`.
+    pub fn from_user_input(items: Vec<UserInput>, image_mode: PromptImageMode) -> Self {
         let mut image_index = 0;
         Self::Message {
             role: "user".to_string(),
@@ -917,7 +931,7 @@ impl From<Vec<UserInput>> for ResponseInputItem {
                         local_image_content_items_with_label_number(
                             &path,
                             Some(image_index),
-                            PromptImageMode::ResizeToFit,
+                            image_mode,
                         )
                     }
                     UserInput::Skill { .. } | UserInput::Mention { .. } => Vec::new(), // Tool bodies are injected later in core
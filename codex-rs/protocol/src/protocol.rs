@@ -18,6 +18,7 @@ use crate::config_types::CollaborationMode;
 use crate::config_types::ModeKind;
 use crate::config_types::Personality;
 use crate::config_types::ReasoningSummary as ReasoningSummaryConfig;
+use crate::config_types::SamplingParams;
 use crate::config_types::ServiceTier;
 use crate::config_types::WindowsSandboxLevel;
 use crate::custom_prompts::CustomPrompt;
@@ -37,11 +38,13 @@ use crate::models::ContentItem;
 use crate::models::MessagePhase;
 use crate::models::ResponseItem;
 use crate::models::WebSearchAction;
+use crate::models::WebSearchSource;
 use crate::num_format::format_with_separators;
 use crate::openai_models::ReasoningEffort as ReasoningEffortConfig;
 use crate::parse_command::ParsedCommand;
 use crate::plan_tool::UpdatePlanArgs;
 use crate::request_user_input::RequestUserInputResponse;
+use crate::todo_tool::TodoListEvent;
 use crate::user_input::UserInput;
 use codex_utils_absolute_path::AbsolutePathBuf;
 use schemars::JsonSchema;
@@ -54,6 +57,8 @@ use tracing::error;
 use ts_rs::TS;
 
 pub use crate::approvals::ApplyPatchApprovalRequestEvent;
+pub use crate::approvals::CompactionReviewDecision;
+pub use crate::approvals::CompactionReviewRequestEvent;
 pub use crate::approvals::ElicitationAction;
 pub use crate::approvals::ExecApprovalRequestEvent;
 pub use crate::approvals::ExecPolicyAmendment;
@@ -61,6 +66,7 @@ pub use crate::approvals::NetworkApprovalContext;
 pub use crate::approvals::NetworkApprovalProtocol;
 pub use crate::approvals::NetworkPolicyAmendment;
 pub use crate::approvals::NetworkPolicyRuleAction;
+pub use crate::approvals::PlanApprovalRequestEvent;
 pub use crate::request_user_input::RequestUserInputEvent;
 
 /// Open/close tags for special user-input blocks. Used across crates to avoid
@@ -304,6 +310,19 @@ pub enum Op {
         personality: Option<Personality>,
     },
 
+    /// Replace the session's sampling overrides (temperature / top_p) for subsequent turns.
+    ///
+    /// The values are recorded in each turn's `TurnContextItem` so resumed sessions keep them.
+    SetSamplingParams { sampling: SamplingParams },
+
+    /// Replace the files pinned into the model context. Their current contents are sent with
+    /// every sampling request but never recorded in the conversation history.
+    SetPinnedFiles { paths: Vec<PathBuf> },
+
+    /// Add a briefing built from earlier sessions in the same project. It is recorded as context
+    /// with the next user turn, once.
+    SetCarryOverBriefing { briefing: String },
+
     /// Approve a command execution
     ExecApproval {
         /// The id of the submission we are approving
@@ -315,7 +334,7 @@ pub enum Op {
         decision: ReviewDecision,
     },
 
-    /// Approve a code patch
+    /// Approve a code patch, or a plan presented under `require_plan_approval`
     PatchApproval {
         /// The id of the submission we are approving
         id: String,
@@ -323,6 +342,14 @@ pub enum Op {
         decision: ReviewDecision,
     },
 
+    /// Answer a `CompactionReviewRequest`.
+    CompactionReview {
+        /// Id from the request event.
+        id: String,
+        /// Whether to use the generated summary, an edited one, or none.
+        decision: CompactionReviewDecision,
+    },
+
     /// Resolve an MCP elicitation request.
     ResolveElicitation {
         /// Name of the MCP server that issued the request.
@@ -426,6 +453,10 @@ pub enum Op {
     /// responsible for undoing any edits on disk.
     ThreadRollback { num_turns: u32 },
 
+    /// Replace the output of tool call `call_id` in the in-memory context with a short note,
+    /// to free up the context window. Rejected while a turn is in progress.
+    TrimToolOutput { call_id: String },
+
     /// Request a code review from the agent.
     Review { review_request: ReviewRequest },
 
@@ -440,6 +471,10 @@ pub enum Op {
     RunUserShellCommand {
         /// The raw command string after '!'
         command: String,
+        /// Show the output to the user without recording it in the conversation history,
+        /// so the model never sees it (triggered by "!!cmd").
+        #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+        exclude_from_context: bool,
     },
 
     /// Request the list of available models.
@@ -1039,6 +1074,9 @@ pub enum EventMsg {
     #[serde(rename = "task_complete", alias = "turn_complete")]
     TurnComplete(TurnCompleteEvent),
 
+    /// The phase a running turn has moved into, for progress reporting.
+    TurnPhase(TurnPhaseEvent),
+
     /// Usage update for the current session, including totals and last turn.
     /// Optional means unknown — UIs should not display when `None`.
     TokenCount(TokenCountEvent),
@@ -1116,6 +1154,10 @@ pub enum EventMsg {
 
     ApplyPatchApprovalRequest(ApplyPatchApprovalRequestEvent),
 
+    PlanApprovalRequest(PlanApprovalRequestEvent),
+
+    CompactionReviewRequest(CompactionReviewRequestEvent),
+
     /// Notification advising the user that something they are using has been
     /// deprecated and should be phased out.
     DeprecationNotice(DeprecationNoticeEvent),
@@ -1137,6 +1179,12 @@ pub enum EventMsg {
     /// Notification that a patch application has finished.
     PatchApplyEnd(PatchApplyEndEvent),
 
+    /// Formatters and linters from `[[patch_checks]]` that ran on the files a patch changed.
+    PatchChecks(PatchChecksEvent),
+
+    /// A mutating command or patch that a `--dry-run` session previewed instead of running.
+    DryRunAction(DryRunActionEvent),
+
     TurnDiff(TurnDiffEvent),
 
     /// Response to GetHistoryEntryRequest.
@@ -1162,6 +1210,9 @@ pub enum EventMsg {
 
     PlanUpdate(UpdatePlanArgs),
 
+    /// The session's todo list changed through `update_todos`.
+    TodoListUpdate(TodoListEvent),
+
     TurnAborted(TurnAbortedEvent),
 
     /// Notification that the agent is shutting down.
@@ -1541,6 +1592,30 @@ pub struct TurnStartedEvent {
     pub collaboration_mode_kind: ModeKind,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct TurnPhaseEvent {
+    pub turn_id: String,
+    pub phase: TurnPhase,
+}
+
+/// Where a running turn currently is. Phases are reported as they are entered; a turn may go
+/// through several sampling rounds, each repeating `Sending` through `RunningTool`.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[ts(tag = "type")]
+pub enum TurnPhase {
+    /// The turn has been accepted and is waiting to start its first request.
+    Queued,
+    /// A request is being sent to the model.
+    Sending,
+    /// The model accepted the request and has not produced visible output yet.
+    Thinking,
+    /// The model is streaming its reply.
+    Streaming,
+    /// Tool call `index` (1-based) of the `total` requested in this round is being awaited.
+    RunningTool { index: usize, total: usize },
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Eq, JsonSchema, TS)]
 pub struct TokenUsage {
     #[ts(type = "number")]
@@ -1883,6 +1958,9 @@ pub struct WebSearchEndEvent {
     pub call_id: String,
     pub query: String,
     pub action: WebSearchAction,
+    /// Results of a provider-backed search, best match first. Empty for hosted search.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sources: Vec<WebSearchSource>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
@@ -1956,28 +2034,14 @@ impl InitialHistory {
         }
     }
 
+    /// Events to replay when a client attaches to this history. Tool calls and their outputs are
+    /// included as [`EventMsg::RawResponseItem`] so clients can render them even when the
+    /// rollout did not persist the matching tool events.
     pub fn get_event_msgs(&self) -> Option<Vec<EventMsg>> {
         match self {
             InitialHistory::New => None,
-            InitialHistory::Resumed(resumed) => Some(
-                resumed
-                    .history
-                    .iter()
-                    .filter_map(|ri| match ri {
-                        RolloutItem::EventMsg(ev) => Some(ev.clone()),
-                        _ => None,
-                    })
-                    .collect(),
-            ),
-            InitialHistory::Forked(items) => Some(
-                items
-                    .iter()
-                    .filter_map(|ri| match ri {
-                        RolloutItem::EventMsg(ev) => Some(ev.clone()),
-                        _ => None,
-                    })
-                    .collect(),
-            ),
+            InitialHistory::Resumed(resumed) => Some(replay_event_msgs(&resumed.history)),
+            InitialHistory::Forked(items) => Some(replay_event_msgs(items)),
         }
     }
 
@@ -2015,6 +2079,10 @@ impl InitialHistory {
     }
 }
 
+fn replay_event_msgs(items: &[RolloutItem]) -> Vec<EventMsg> {
+    items.iter().filter_map(RolloutItem::replay_event).collect()
+}
+
 fn session_cwd_from_items(items: &[RolloutItem]) -> Option<PathBuf> {
     items.iter().find_map(|item| match item {
         RolloutItem::SessionMeta(meta_line) => Some(meta_line.meta.cwd.clone()),
@@ -2182,6 +2250,39 @@ pub enum RolloutItem {
     EventMsg(EventMsg),
 }
 
+impl RolloutItem {
+    /// Whether this item contributes an event to [`InitialHistory::get_event_msgs`].
+    pub fn is_replayed(&self) -> bool {
+        matches!(
+            self,
+            RolloutItem::EventMsg(_)
+                | RolloutItem::ResponseItem(
+                    ResponseItem::FunctionCall { .. }
+                        | ResponseItem::FunctionCallOutput { .. }
+                        | ResponseItem::CustomToolCall { .. }
+                        | ResponseItem::CustomToolCallOutput { .. }
+                        | ResponseItem::LocalShellCall { .. }
+                )
+        )
+    }
+
+    /// The event this item contributes to [`InitialHistory::get_event_msgs`], if any.
+    pub fn replay_event(&self) -> Option<EventMsg> {
+        if !self.is_replayed() {
+            return None;
+        }
+        match self {
+            RolloutItem::EventMsg(ev) => Some(ev.clone()),
+            RolloutItem::ResponseItem(item) => {
+                Some(EventMsg::RawResponseItem(RawResponseItemEvent {
+                    item: item.clone(),
+                }))
+            }
+            _ => None,
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, JsonSchema, TS)]
 pub struct CompactedItem {
     pub message: String,
@@ -2244,6 +2345,8 @@ pub struct TurnContextItem {
     pub final_output_json_schema: Option<Value>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub truncation_policy: Option<TruncationPolicy>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sampling: Option<SamplingParams>,
 }
 
 #[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
@@ -2528,6 +2631,11 @@ pub struct StreamErrorEvent {
     /// are exhausted).
     #[serde(default)]
     pub additional_details: Option<String>,
+    /// How long the agent waits before retrying, in milliseconds. Set when the provider
+    /// throttled the request and asked for a specific delay.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub retry_delay_ms: Option<u64>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
@@ -2578,6 +2686,58 @@ pub enum PatchApplyStatus {
     Declined,
 }
 
+/// Whether a `[[patch_checks]]` entry rewrites files or only reports on them.
+#[derive(Debug, Clone, Copy, Default, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum PatchCheckKind {
+    /// Rewrites the files in place, e.g. `rustfmt` or `prettier --write`.
+    Format,
+    /// Reports diagnostics, e.g. `clippy` or `eslint`.
+    #[default]
+    Lint,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct PatchChecksEvent {
+    /// Identifier of the patch the checks ran after.
+    pub call_id: String,
+    pub turn_id: String,
+    pub results: Vec<PatchCheckResult>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
+pub struct PatchCheckResult {
+    pub name: String,
+    pub kind: PatchCheckKind,
+    pub exit_code: i32,
+    /// Files a formatter rewrote. Always empty for linters.
+    pub formatted_files: Vec<PathBuf>,
+    /// Combined output, kept only when the check failed.
+    pub output: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct DryRunActionEvent {
+    /// Identifier of the tool call that was simulated.
+    pub call_id: String,
+    /// Turn ID that this call belongs to.
+    pub turn_id: String,
+    pub action: DryRunAction,
+}
+
+/// What a simulated tool call would have done.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, JsonSchema, TS)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[ts(tag = "type")]
+pub enum DryRunAction {
+    /// A command that would have run in `cwd`.
+    Exec { command: Vec<String>, cwd: PathBuf },
+    /// A patch that would have been applied.
+    Patch {
+        changes: HashMap<PathBuf, FileChange>,
+    },
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct TurnDiffEvent {
     pub unified_diff: String,
@@ -2952,6 +3112,10 @@ pub enum TurnAbortReason {
     Interrupted,
     Replaced,
     ReviewEnded,
+    /// The model stopped responding before the turn finished.
+    Timeout,
+    /// The turn stopped on an error; the `Error` event sent before it has the details.
+    Error,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, JsonSchema, TS)]
@@ -3238,6 +3402,7 @@ mod tests {
                     query: Some("find docs".into()),
                     queries: None,
                 },
+                sources: Vec::new(),
             }),
         };
 
@@ -3533,6 +3698,7 @@ mod tests {
             developer_instructions: None,
             final_output_json_schema: None,
             truncation_policy: None,
+            sampling: None,
         };
 
         let value = serde_json::to_value(item)?;
@@ -0,0 +1,36 @@
+//! Types for the session todo list kept by the `update_todos` tool.
+
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde::Serialize;
+use ts_rs::TS;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum TodoStatus {
+    Pending,
+    InProgress,
+    Done,
+    Cancelled,
+}
+
+impl TodoStatus {
+    /// Whether the item still needs work.
+    pub fn is_open(self) -> bool {
+        matches!(self, TodoStatus::Pending | TodoStatus::InProgress)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema, TS)]
+pub struct TodoItem {
+    /// Assigned when the item is added; stays the same while the item is on the list.
+    pub id: u32,
+    pub text: String,
+    pub status: TodoStatus,
+}
+
+/// The whole todo list after a change. Recorded in the rollout so resumed sessions keep it.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema, TS)]
+pub struct TodoListEvent {
+    pub items: Vec<TodoItem>,
+}
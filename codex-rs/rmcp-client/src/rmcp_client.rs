@@ -58,6 +58,7 @@ use crate::oauth::OAuthPersistor;
 use crate::oauth::StoredOAuthTokens;
 use crate::program_resolver;
 use crate::utils::apply_default_headers;
+use crate::utils::apply_outbound_proxy;
 use crate::utils::build_default_headers;
 use crate::utils::create_env_for_mcp_server;
 use crate::utils::run_with_timeout;
@@ -289,9 +290,11 @@ impl RmcpClient {
                     let http_config =
                         StreamableHttpClientTransportConfig::with_uri(url.to_string())
                             .auth_header(access_token);
-                    let http_client =
-                        apply_default_headers(reqwest::Client::builder(), &default_headers)
-                            .build()?;
+                    let http_client = apply_default_headers(
+                        apply_outbound_proxy(reqwest::Client::builder()),
+                        &default_headers,
+                    )
+                    .build()?;
                     let transport =
                         StreamableHttpClientTransport::with_client(http_client, http_config);
                     PendingTransport::StreamableHttp { transport }
@@ -304,8 +307,11 @@ impl RmcpClient {
                 http_config = http_config.auth_header(bearer_token);
             }
 
-            let http_client =
-                apply_default_headers(reqwest::Client::builder(), &default_headers).build()?;
+            let http_client = apply_default_headers(
+                apply_outbound_proxy(reqwest::Client::builder()),
+                &default_headers,
+            )
+            .build()?;
 
             let transport = StreamableHttpClientTransport::with_client(http_client, http_config);
             PendingTransport::StreamableHttp { transport }
@@ -593,8 +599,11 @@ async fn create_oauth_transport_and_runtime(
     StreamableHttpClientTransport<AuthClient<reqwest::Client>>,
     OAuthPersistor,
 )> {
-    let http_client =
-        apply_default_headers(reqwest::Client::builder(), &default_headers).build()?;
+    let http_client = apply_default_headers(
+        apply_outbound_proxy(reqwest::Client::builder()),
+        &default_headers,
+    )
+    .build()?;
     let mut oauth_state = OAuthState::new(url.to_string(), Some(http_client.clone())).await?;
 
     oauth_state
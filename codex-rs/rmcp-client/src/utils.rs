@@ -1,11 +1,14 @@
 use std::collections::HashMap;
 use std::env;
+use std::sync::LazyLock;
+use std::sync::RwLock;
 use std::time::Duration;
 
 use anyhow::Context;
 use anyhow::Result;
 use anyhow::anyhow;
 use reqwest::ClientBuilder;
+use reqwest::Proxy;
 use reqwest::header::HeaderMap;
 use reqwest::header::HeaderName;
 use reqwest::header::HeaderValue;
@@ -101,6 +104,25 @@ pub(crate) fn build_default_headers(
     Ok(headers)
 }
 
+static OUTBOUND_PROXY: LazyLock<RwLock<Option<Proxy>>> = LazyLock::new(|| RwLock::new(None));
+
+/// Route streamable HTTP MCP clients through `proxy`. With `None`, reqwest keeps honoring the
+/// proxy environment variables.
+pub fn set_outbound_proxy(proxy: Option<Proxy>) {
+    let Ok(mut guard) = OUTBOUND_PROXY.write() else {
+        tracing::warn!("Failed to acquire outbound proxy lock");
+        return;
+    };
+    *guard = proxy;
+}
+
+pub(crate) fn apply_outbound_proxy(builder: ClientBuilder) -> ClientBuilder {
+    match OUTBOUND_PROXY.read().ok().and_then(|guard| guard.clone()) {
+        Some(proxy) => builder.proxy(proxy),
+        None => builder,
+    }
+}
+
 pub(crate) fn apply_default_headers(
     builder: ClientBuilder,
     default_headers: &HeaderMap,
@@ -119,6 +119,24 @@ pub fn parse_shell_lc_plain_commands(command: &[String]) -> Option<Vec<Vec<Strin
     try_parse_word_only_commands_sequence(&tree, script)
 }
 
+/// Like [`parse_shell_lc_plain_commands`], but only accepts commands joined by `&&` or `;`,
+/// so every command runs when the script succeeds. Scripts using `||` or pipes return `None`.
+pub fn parse_shell_lc_sequential_commands(command: &[String]) -> Option<Vec<Vec<String>>> {
+    let (_, script) = extract_bash_command(command)?;
+    let tree = try_parse_shell(script)?;
+    let commands = try_parse_word_only_commands_sequence(&tree, script)?;
+
+    let mut cursor = tree.walk();
+    let mut stack = vec![tree.root_node()];
+    while let Some(node) = stack.pop() {
+        if matches!(node.kind(), "||" | "|" | "pipeline") {
+            return None;
+        }
+        stack.extend(node.children(&mut cursor));
+    }
+    Some(commands)
+}
+
 /// Returns the parsed argv for a single shell command in a here-doc style
 /// script (`<<`), as long as the script contains exactly one command node.
 pub fn parse_shell_lc_single_command_prefix(command: &[String]) -> Option<Vec<String>> {
@@ -453,6 +471,28 @@ mod tests {
         assert_eq!(parsed, vec![vec!["ls".to_string()]]);
     }
 
+    #[test]
+    fn sequential_commands_reject_conditional_and_piped_scripts() {
+        let bash = |script: &str| vec!["bash".to_string(), "-lc".to_string(), script.to_string()];
+
+        assert_eq!(
+            parse_shell_lc_sequential_commands(&bash("cd foo && make; ls")),
+            Some(vec![
+                vec!["cd".to_string(), "foo".to_string()],
+                vec!["make".to_string()],
+                vec!["ls".to_string()],
+            ])
+        );
+        assert_eq!(
+            parse_shell_lc_sequential_commands(&bash("cd foo || exit")),
+            None
+        );
+        assert_eq!(
+            parse_shell_lc_sequential_commands(&bash("ls | wc -l")),
+            None
+        );
+    }
+
     #[test]
     fn accepts_concatenated_flag_and_value() {
         // Test case: -g"*.py" (flag directly concatenated with quoted value)
@@ -268,6 +268,7 @@ mod tests {
                 developer_instructions: None,
                 final_output_json_schema: None,
                 truncation_policy: None,
+                sampling: None,
             }),
             "test-provider",
         );
@@ -305,6 +306,7 @@ mod tests {
                 developer_instructions: None,
                 final_output_json_schema: None,
                 truncation_policy: None,
+                sampling: None,
             }),
             "test-provider",
         );
@@ -2,6 +2,7 @@ use crate::app_backtrack::BacktrackState;
 use crate::app_event::AppEvent;
 use crate::app_event::ExitMode;
 use crate::app_event::RealtimeAudioDeviceKind;
+use crate::app_event::RetryOverrides;
 #[cfg(target_os = "windows")]
 use crate::app_event::WindowsSandboxEnableMode;
 use crate::app_event_sender::AppEventSender;
@@ -9,30 +10,52 @@ use crate::bottom_pane::ApprovalRequest;
 use crate::bottom_pane::FeedbackAudience;
 use crate::bottom_pane::SelectionItem;
 use crate::bottom_pane::SelectionViewParams;
+use crate::bottom_pane::plan_step_lines;
 use crate::bottom_pane::popup_consts::standard_popup_hint_line;
 use crate::chatwidget::ChatWidget;
 use crate::chatwidget::ExternalEditorState;
 use crate::chatwidget::ThreadInputState;
+use crate::config_reload;
+use crate::config_reload::ConfigWatcher;
+use crate::config_view;
+use crate::crash_recovery;
+use crate::crash_recovery::RecoverySnapshot;
 use crate::cwd_prompt::CwdPromptAction;
 use crate::diff_render::DiffSummary;
 use crate::exec_command::strip_bash_lc_and_escape;
 use crate::external_editor;
 use crate::file_search::FileSearchManager;
+use crate::file_search::SemanticSearchSettings;
 use crate::history_cell;
 use crate::history_cell::HistoryCell;
 #[cfg(not(debug_assertions))]
 use crate::history_cell::UpdateAvailableHistoryCell;
+use crate::history_reflow::HistoryReflow;
+use crate::history_reflow::ReflowAction;
+use crate::keymap;
+use crate::model_compare::ModelComparisonView;
 use crate::model_migration::ModelMigrationOutcome;
 use crate::model_migration::migration_copy_for_models;
 use crate::model_migration::run_model_migration_prompt;
 use crate::multi_agents::AgentPickerThreadEntry;
+use crate::multi_agents::AgentTask;
 use crate::multi_agents::agent_picker_status_dot_spans;
+use crate::multi_agents::agent_task_params;
 use crate::multi_agents::format_agent_picker_item_name;
 use crate::multi_agents::sort_agent_picker_threads;
+use crate::multi_agents::task_board_params;
 use crate::pager_overlay::Overlay;
+use crate::pager_overlay::RecordedEvent;
 use crate::render::highlight::highlight_bash_to_lines;
 use crate::render::renderable::Renderable;
 use crate::resume_picker::SessionSelection;
+use crate::session_compare::SessionComparisonView;
+use crate::session_stats::SessionStatsView;
+use crate::spellcheck;
+use crate::terminal_title;
+use crate::terminal_title::SessionActivity;
+use crate::terminal_title::TerminalTitle;
+use crate::transcript_spill::TranscriptSpill;
 use crate::tui;
 use crate::tui::TuiEvent;
 use crate::update_action::UpdateAction;
@@ -41,7 +64,9 @@ use codex_ansi_escape::ansi_escape_line;
 use codex_app_server_protocol::ConfigLayerSource;
 use codex_core::AuthManager;
 use codex_core::CodexAuth;
+use codex_core::NextTurnContext;
 use codex_core::ThreadManager;
+use codex_core::compare_models;
 use codex_core::config::Config;
 use codex_core::config::ConfigBuilder;
 use codex_core::config::ConfigOverrides;
@@ -49,11 +74,15 @@ use codex_core::config::edit::ConfigEdit;
 use codex_core::config::edit::ConfigEditsBuilder;
 use codex_core::config::types::ModelAvailabilityNuxConfig;
 use codex_core::config_loader::ConfigLayerStackOrdering;
+use codex_core::create_pull_request;
+use codex_core::draft_commit_message;
 use codex_core::features::Feature;
 use codex_core::models_manager::collaboration_mode_presets::CollaborationModesConfig;
 use codex_core::models_manager::manager::RefreshStrategy;
 use codex_core::models_manager::model_presets::HIDE_GPT_5_1_CODEX_MAX_MIGRATION_PROMPT_CONFIG;
 use codex_core::models_manager::model_presets::HIDE_GPT5_1_MIGRATION_PROMPT_CONFIG;
+use codex_core::propose_conflict_resolution;
+use codex_core::summarize_session;
 #[cfg(target_os = "windows")]
 use codex_core::windows_sandbox::WindowsSandboxLevelExt;
 use codex_otel::OtelManager;
@@ -67,6 +96,7 @@ use codex_protocol::openai_models::ModelAvailabilityNux;
 use codex_protocol::openai_models::ModelPreset;
 use codex_protocol::openai_models::ModelUpgrade;
 use codex_protocol::openai_models::ReasoningEffort as ReasoningEffortConfig;
+use codex_protocol::protocol::AgentStatus;
 use codex_protocol::protocol::AskForApproval;
 use codex_protocol::protocol::Event;
 use codex_protocol::protocol::EventMsg;
@@ -116,6 +146,11 @@ use self::pending_interactive_replay::PendingInteractiveReplayState;
 
 const EXTERNAL_EDITOR_HINT: &str = "Save and close external editor to continue.";
 const THREAD_EVENT_CHANNEL_CAPACITY: usize = 32768;
+/// Number of protocol events kept for the `/debug-events` inspector.
+const RECENT_EVENTS_CAPACITY: usize = 500;
+/// Shown when `/permissions` is used in a session started with `--read-only` or `--dry-run`.
+const READ_ONLY_PERMISSIONS_MESSAGE: &str =
+    "This session was started with --read-only or --dry-run, so its permissions cannot be changed.";
 /// Baseline cadence for periodic stream commit animation ticks.
 ///
 /// Smooth-mode streaming drains one line per tick, so this interval controls
@@ -644,13 +679,22 @@ pub(crate) struct App {
     runtime_sandbox_policy_override: Option<SandboxPolicy>,
 
     pub(crate) file_search: FileSearchManager,
+    terminal_title: TerminalTitle,
+    config_watcher: Option<ConfigWatcher>,
+    /// Config reloaded from disk whose model or provider change waits for the user to start a
+    /// new session.
+    pending_config_reload: Option<Config>,
 
     pub(crate) transcript_cells: Vec<Arc<dyn HistoryCell>>,
+    /// Moves the oldest transcript cells to disk once `tui.transcript_memory_lines` is exceeded.
+    pub(crate) transcript_spill: TranscriptSpill,
 
     // Pager overlay state (Transcript or Static like Diff)
     pub(crate) overlay: Option<Overlay>,
     pub(crate) deferred_history_lines: Vec<Line<'static>>,
     has_emitted_history_lines: bool,
+    /// Re-wraps the scrollback once the terminal width changes.
+    pub(crate) history_reflow: HistoryReflow,
 
     pub(crate) enhanced_keys_supported: bool,
 
@@ -697,6 +741,8 @@ pub(crate) struct App {
     primary_thread_id: Option<ThreadId>,
     primary_session_configured: Option<SessionConfiguredEvent>,
     pending_primary_events: VecDeque<Event>,
+    /// Most recent protocol events handled for the active thread, oldest first.
+    recent_events: VecDeque<RecordedEvent>,
 }
 
 #[derive(Default)]
@@ -849,6 +895,7 @@ impl App {
             tui.terminal.set_viewport_area(area);
         }
         self.has_emitted_history_lines = false;
+        self.history_reflow.reset();
 
         if redraw_header {
             self.queue_clear_ui_header(tui);
@@ -856,11 +903,41 @@ impl App {
         Ok(())
     }
 
+    /// The lines to write to the scrollback for `cell`, preceded by a blank separator line
+    /// unless it is the first cell or continues a stream.
+    fn history_lines_for_cell(&mut self, cell: &dyn HistoryCell, width: u16) -> Vec<Line<'static>> {
+        let mut display = cell.display_lines(width);
+        // Streaming continuations should not accrue extra blank lines between chunks.
+        if !display.is_empty() && !cell.is_stream_continuation() {
+            if self.has_emitted_history_lines {
+                display.insert(0, Line::from(""));
+            } else {
+                self.has_emitted_history_lines = true;
+            }
+        }
+        display
+    }
+
+    /// Clear the terminal and write the transcript again, wrapped at the current width.
+    fn reflow_history(&mut self, tui: &mut tui::Tui) -> Result<()> {
+        let width = tui.terminal.size()?.width;
+        self.clear_terminal_ui(tui, false)?;
+        self.deferred_history_lines.clear();
+        for cell in self.transcript_cells.clone() {
+            let display = self.history_lines_for_cell(cell.as_ref(), width);
+            tui.insert_history_lines(display);
+        }
+        self.history_reflow.reflowed(width);
+        Ok(())
+    }
+
     fn reset_app_ui_state_after_clear(&mut self) {
         self.overlay = None;
         self.transcript_cells.clear();
+        self.transcript_spill.clear();
         self.deferred_history_lines.clear();
         self.has_emitted_history_lines = false;
+        self.history_reflow.reset();
         self.backtrack = BacktrackState::default();
         self.backtrack_render_pending = false;
     }
@@ -1038,6 +1115,19 @@ impl App {
                     .unwrap_or_else(|| self.config.cwd.clone()),
                 changes: ev.changes.clone(),
             }),
+            EventMsg::PlanApprovalRequest(ev) => Some(ApprovalRequest::Plan {
+                thread_id,
+                thread_label,
+                id: ev.call_id.clone(),
+                explanation: ev.explanation.clone(),
+                steps: ev.plan.iter().map(|item| item.step.clone()).collect(),
+            }),
+            EventMsg::CompactionReviewRequest(ev) => Some(ApprovalRequest::Compaction {
+                thread_id,
+                thread_label,
+                id: ev.id.clone(),
+                summary: ev.summary.clone(),
+            }),
             EventMsg::ElicitationRequest(ev) => Some(ApprovalRequest::McpElicitation {
                 thread_id,
                 thread_label,
@@ -1290,6 +1380,26 @@ impl App {
         );
     }
 
+    /// Every non-primary agent thread seen in this session, with its current status.
+    async fn agent_tasks(&self) -> Vec<AgentTask> {
+        let mut tasks = Vec::new();
+        for (thread_id, entry) in &self.agent_picker_threads {
+            if self.primary_thread_id == Some(*thread_id) {
+                continue;
+            }
+            let status = match self.server.get_thread(*thread_id).await {
+                Ok(thread) => thread.agent_status().await,
+                Err(_) => AgentStatus::Shutdown,
+            };
+            tasks.push(AgentTask {
+                thread_id: *thread_id,
+                entry: entry.clone(),
+                status,
+            });
+        }
+        tasks
+    }
+
     fn mark_agent_picker_thread_closed(&mut self, thread_id: ThreadId) {
         if let Some(entry) = self.agent_picker_threads.get_mut(&thread_id) {
             entry.is_closed = true;
@@ -1360,8 +1470,10 @@ impl App {
     fn reset_for_thread_switch(&mut self, tui: &mut tui::Tui) -> Result<()> {
         self.overlay = None;
         self.transcript_cells.clear();
+        self.transcript_spill.clear();
         self.deferred_history_lines.clear();
         self.has_emitted_history_lines = false;
+        self.history_reflow.reset();
         self.backtrack = BacktrackState::default();
         self.backtrack_render_pending = false;
         tui.terminal.clear_scrollback()?;
@@ -1534,6 +1646,8 @@ impl App {
         active_profile: Option<String>,
         initial_prompt: Option<String>,
         initial_images: Vec<PathBuf>,
+        initial_review: Option<(String, Option<String>)>,
+        initial_draft: Option<String>,
         session_selection: SessionSelection,
         feedback: codex_feedback::CodexFeedback,
         is_first_run: bool,
@@ -1612,6 +1726,7 @@ impl App {
             .tui_status_line
             .as_ref()
             .is_some_and(|cmd| !cmd.is_empty())
+            || config.tui_status_line_rows.is_some()
         {
             otel_manager.counter("codex.status_line", 1, &[]);
         }
@@ -1725,8 +1840,26 @@ impl App {
 
         chat_widget
             .maybe_prompt_windows_sandbox_enable(should_prompt_windows_sandbox_nux_at_startup);
+        if let Some((source, instructions)) = initial_review {
+            chat_widget.review_diff(source, instructions);
+        }
 
-        let file_search = FileSearchManager::new(config.cwd.clone(), app_event_tx.clone());
+        let semantic_search =
+            config
+                .features
+                .enabled(Feature::SemanticSearch)
+                .then(|| SemanticSearchSettings {
+                    codex_home: config.codex_home.clone(),
+                    config: config.semantic_search.clone(),
+                });
+        let file_search =
+            FileSearchManager::new(config.cwd.clone(), semantic_search, app_event_tx.clone());
+        let terminal_title = TerminalTitle::new(
+            config.tui_terminal_title,
+            config.tui_tmux,
+            config.tui_terminal_badge,
+        );
+        let transcript_spill = TranscriptSpill::new(config.tui_transcript_memory_lines);
         #[cfg(not(debug_assertions))]
         let upgrade_version = crate::updates::get_upgrade_version(&config);
 
@@ -1743,11 +1876,16 @@ impl App {
             runtime_approval_policy_override: None,
             runtime_sandbox_policy_override: None,
             file_search,
+            terminal_title,
+            config_watcher: None,
+            pending_config_reload: None,
             enhanced_keys_supported,
             transcript_cells: Vec::new(),
+            transcript_spill,
             overlay: None,
             deferred_history_lines: Vec::new(),
             has_emitted_history_lines: false,
+            history_reflow: HistoryReflow::default(),
             commit_anim_running: Arc::new(AtomicBool::new(false)),
             status_line_invalid_items_warned: status_line_invalid_items_warned.clone(),
             backtrack: BacktrackState::default(),
@@ -1766,6 +1904,7 @@ impl App {
             primary_thread_id: None,
             primary_session_configured: None,
             pending_primary_events: VecDeque::new(),
+            recent_events: VecDeque::new(),
         };
 
         // On startup, if Agent mode (workspace-write) or ReadOnly is active, warn about world-writable dirs on Windows.
@@ -1815,9 +1954,17 @@ impl App {
             }
         }
 
+        if let Some(draft) = initial_draft {
+            app.chat_widget
+                .set_composer_text(draft, Vec::new(), Vec::new());
+        }
+        crash_recovery::install_panic_hook(app.config.codex_home.clone(), Arc::clone(&app.server));
+
         let tui_events = tui.event_stream();
         tokio::pin!(tui_events);
 
+        app.refresh_terminal_title_repo();
+        app.watch_config_files();
         tui.frame_requester().schedule_frame();
 
         let mut thread_created_rx = thread_manager.subscribe_thread_created();
@@ -1872,10 +2019,11 @@ impl App {
                 waiting_for_initial_session_configured = false;
             }
             match control {
-                AppRunControl::Continue => {}
+                AppRunControl::Continue => app.update_recovery_snapshot(),
                 AppRunControl::Exit(reason) => break reason,
             }
         };
+        crash_recovery::discard(&app.config.codex_home);
         tui.terminal.clear()?;
         Ok(AppExitInfo {
             token_usage: app.token_usage(),
@@ -1886,6 +2034,16 @@ impl App {
         })
     }
 
+    /// Record what the panic hook should save if the app crashes now.
+    fn update_recovery_snapshot(&self) {
+        crash_recovery::update(RecoverySnapshot {
+            rollout_path: self.chat_widget.rollout_path(),
+            composer_draft: self.chat_widget.composer_text_with_pending(),
+            pending_approvals: self.chat_widget.pending_approval_summaries(),
+            panic: String::new(),
+        });
+    }
+
     pub(crate) async fn handle_tui_event(
         &mut self,
         tui: &mut tui::Tui,
@@ -1918,6 +2076,16 @@ impl App {
                         self.backtrack_render_pending = false;
                         self.render_transcript_once(tui);
                     }
+                    match self
+                        .history_reflow
+                        .poll(tui.terminal.size()?.width, Instant::now())
+                    {
+                        ReflowAction::None => {}
+                        ReflowAction::WaitFor(delay) => {
+                            tui.frame_requester().schedule_frame_in(delay);
+                        }
+                        ReflowAction::Reflow => self.reflow_history(tui)?,
+                    }
                     self.chat_widget.maybe_post_pending_notification(tui);
                     if self
                         .chat_widget
@@ -1927,6 +2095,19 @@ impl App {
                     }
                     // Allow widgets to process any pending timers before rendering.
                     self.chat_widget.pre_draw_tick();
+                    let activity = if self.chat_widget.is_awaiting_approval() {
+                        SessionActivity::NeedsApproval
+                    } else if self.chat_widget.is_task_running() {
+                        SessionActivity::Busy
+                    } else {
+                        SessionActivity::Idle
+                    };
+                    if self
+                        .terminal_title
+                        .update(self.chat_widget.thread_name().as_deref(), activity)
+                    {
+                        self.refresh_terminal_title_repo();
+                    }
                     tui.draw(
                         self.chat_widget.desired_height(tui.terminal.size()?.width),
                         |frame| {
@@ -2125,18 +2306,12 @@ impl App {
                     tui.frame_requester().schedule_frame();
                 }
                 self.transcript_cells.push(cell.clone());
-                let mut display = cell.display_lines(tui.terminal.last_known_screen_size.width);
+                let width = tui.terminal.last_known_screen_size.width;
+                self.transcript_spill
+                    .on_cell_inserted(&mut self.transcript_cells, &cell, width);
+                let display = self.history_lines_for_cell(cell.as_ref(), width);
                 if !display.is_empty() {
-                    // Only insert a separating blank line for new cells that are not
-                    // part of an ongoing stream. Streaming continuations should not
-                    // accrue extra blank lines between chunks.
-                    if !cell.is_stream_continuation() {
-                        if self.has_emitted_history_lines {
-                            display.insert(0, Line::from(""));
-                        } else {
-                            self.has_emitted_history_lines = true;
-                        }
-                    }
+                    self.history_reflow.note_written(width);
                     if self.overlay.is_some() {
                         self.deferred_history_lines.extend(display);
                     } else {
@@ -2178,6 +2353,11 @@ impl App {
                 self.handle_routed_thread_event(thread_id, event).await?;
             }
             AppEvent::Exit(mode) => {
+                if mode == ExitMode::ShutdownFirst && self.chat_widget.take_exit_summary_request() {
+                    self.app_event_tx
+                        .send(AppEvent::SummarizeSession { exit_after: true });
+                    return Ok(AppRunControl::Continue);
+                }
                 return Ok(self.handle_exit_mode(mode));
             }
             AppEvent::FatalExitRequest(message) => {
@@ -2211,6 +2391,230 @@ impl App {
                 ));
                 tui.frame_requester().schedule_frame();
             }
+            AppEvent::SessionComparisonResult(result) => match result {
+                Ok(comparison) => {
+                    let _ = tui.enter_alt_screen();
+                    self.overlay = Some(Overlay::new_static_with_renderables(
+                        vec![Box::new(SessionComparisonView::new(comparison))],
+                        "C O M P A R E".to_string(),
+                    ));
+                    tui.frame_requester().schedule_frame();
+                }
+                Err(err) => self.chat_widget.add_error_message(err),
+            },
+            AppEvent::CompareModels { prompt } => {
+                let server = Arc::clone(&self.server);
+                let config = self.chat_widget.config_ref().clone();
+                let tx = self.app_event_tx.clone();
+                tokio::spawn(async move {
+                    let comparison =
+                        compare_models(&server, &config, &config.compare_models, &prompt).await;
+                    tx.send(AppEvent::ModelComparisonResult(comparison));
+                });
+            }
+            AppEvent::ConflictsFound(result) => {
+                self.chat_widget.on_conflicts_found(result);
+            }
+            AppEvent::ProposeConflictResolution(hunk) => {
+                let server = Arc::clone(&self.server);
+                let config = self.chat_widget.config_ref().clone();
+                let rollout_path = self.chat_widget.rollout_path();
+                let tx = self.app_event_tx.clone();
+                tokio::spawn(async move {
+                    let result = propose_conflict_resolution(
+                        &server,
+                        &config,
+                        rollout_path.as_deref(),
+                        &hunk,
+                    )
+                    .await;
+                    tx.send(AppEvent::ConflictResolutionProposed { hunk, result });
+                });
+            }
+            AppEvent::ConflictResolutionProposed { hunk, result } => {
+                self.chat_widget
+                    .on_conflict_resolution_proposed(hunk, result);
+            }
+            AppEvent::EditConflictResolution { hunk, text } => {
+                self.chat_widget.open_conflict_resolution_editor(hunk, text);
+            }
+            AppEvent::ApplyConflictResolution { hunk, text } => {
+                self.chat_widget.apply_conflict_resolution(hunk, text);
+            }
+            AppEvent::ConflictResolutionApplied { hunk, result } => {
+                self.chat_widget
+                    .on_conflict_resolution_applied(hunk, result);
+            }
+            AppEvent::SkipConflict => {
+                self.chat_widget.skip_conflict();
+            }
+            AppEvent::DraftCommitMessage => {
+                let server = Arc::clone(&self.server);
+                let config = self.chat_widget.config_ref().clone();
+                let rollout_path = self.chat_widget.rollout_path();
+                let tx = self.app_event_tx.clone();
+                tokio::spawn(async move {
+                    let result =
+                        draft_commit_message(&server, &config, rollout_path.as_deref()).await;
+                    tx.send(AppEvent::CommitMessageDrafted(result));
+                });
+            }
+            AppEvent::CommitMessageDrafted(result) => {
+                self.chat_widget.on_commit_message_drafted(result);
+            }
+            AppEvent::SummarizeSession { exit_after } => {
+                let server = Arc::clone(&self.server);
+                let config = self.chat_widget.config_ref().clone();
+                let rollout_path = self.chat_widget.rollout_path();
+                let tx = self.app_event_tx.clone();
+                tokio::spawn(async move {
+                    let result = match rollout_path {
+                        Some(path) => summarize_session(&server, &config, &path).await,
+                        None => Err("this session is not being saved".to_string()),
+                    };
+                    tx.send(AppEvent::SessionSummarized { result, exit_after });
+                });
+            }
+            AppEvent::CarryOverBriefingLoaded(briefing) => {
+                self.chat_widget.offer_carry_over_briefing(briefing);
+            }
+            AppEvent::SessionSummarized { result, exit_after } => {
+                self.chat_widget.on_session_summarized(result);
+                if exit_after {
+                    return Ok(self.handle_exit_mode(ExitMode::ShutdownFirst));
+                }
+            }
+            AppEvent::CommitStaged { message } => {
+                self.chat_widget.commit_staged(message);
+            }
+            AppEvent::CommitCreated(result) => {
+                self.chat_widget.on_commit_created(result);
+            }
+            AppEvent::CreatePullRequest { instructions } => {
+                let server = Arc::clone(&self.server);
+                let config = self.chat_widget.config_ref().clone();
+                let rollout_path = self.chat_widget.rollout_path();
+                let tx = self.app_event_tx.clone();
+                tokio::spawn(async move {
+                    let result = create_pull_request(
+                        &server,
+                        &config,
+                        rollout_path.as_deref(),
+                        &instructions,
+                    )
+                    .await;
+                    tx.send(AppEvent::PullRequestCreated(result));
+                });
+            }
+            AppEvent::PullRequestCreated(result) => {
+                self.chat_widget.on_pull_request_created(result);
+            }
+            AppEvent::ReviewDiffLoaded {
+                result,
+                instructions,
+            } => {
+                self.chat_widget.on_review_diff_loaded(result, instructions);
+            }
+            AppEvent::ReviewCommentsPosted(result) => {
+                self.chat_widget.on_review_comments_posted(result);
+            }
+            AppEvent::ModelComparisonResult(comparison) => {
+                let _ = tui.enter_alt_screen();
+                self.overlay = Some(Overlay::new_static_with_renderables(
+                    vec![Box::new(ModelComparisonView::new(comparison))],
+                    "C O M P A R E   M O D E L S".to_string(),
+                ));
+                tui.frame_requester().schedule_frame();
+            }
+            AppEvent::OpenEventInspector => {
+                let _ = tui.enter_alt_screen();
+                self.overlay = Some(Overlay::new_event_inspector(
+                    self.recent_events.iter().cloned().collect(),
+                ));
+                tui.frame_requester().schedule_frame();
+            }
+            AppEvent::OpenEnvironmentOverlay => {
+                let _ = tui.enter_alt_screen();
+                self.overlay = Some(Overlay::new_static_with_lines(
+                    crate::debug_config::debug_env_lines(&self.config),
+                    "E N V".to_string(),
+                ));
+                tui.frame_requester().schedule_frame();
+            }
+            AppEvent::OpenContextInspector => {
+                self.open_context_inspector(tui).await;
+            }
+            AppEvent::OpenTrimPicker => {
+                if let Some(context) = self.next_turn_context().await {
+                    self.chat_widget.open_trim_picker(&context);
+                }
+            }
+            AppEvent::OpenConfigEditor => {
+                self.chat_widget
+                    .show_selection_view(config_view::config_editor_params(&self.config));
+            }
+            AppEvent::OpenConfigOverlay => {
+                let _ = tui.enter_alt_screen();
+                self.overlay = Some(Overlay::new_static_with_lines(
+                    config_view::effective_config_lines(&self.config.config_layer_stack),
+                    "C O N F I G".to_string(),
+                ));
+                tui.frame_requester().schedule_frame();
+            }
+            AppEvent::OpenConfigSetting { key } => {
+                if let Some(setting) = config_view::editable_setting(key) {
+                    self.chat_widget
+                        .show_selection_view(config_view::config_setting_params(
+                            &self.config,
+                            setting,
+                        ));
+                }
+            }
+            AppEvent::SetConfigValue { key, value } => {
+                self.set_config_value(tui, key, &value).await;
+            }
+            AppEvent::OpenTranscript => {
+                self.open_transcript_overlay(tui);
+            }
+            AppEvent::OpenLogViewer => {
+                self.open_log_overlay(tui);
+            }
+            AppEvent::OpenKeymap => {
+                self.open_keymap_overlay(tui);
+            }
+            AppEvent::DeviceCodeIssued {
+                verification_url,
+                user_code,
+            } => {
+                self.chat_widget
+                    .on_device_code_issued(verification_url, user_code);
+            }
+            AppEvent::DeviceCodeLoginFinished(result) => {
+                self.chat_widget.on_device_code_login_finished(result);
+            }
+            AppEvent::SwitchAuthProfile(name) => {
+                self.chat_widget.switch_auth_profile(&name);
+            }
+            AppEvent::RefreshAuthToken => {
+                self.chat_widget.refresh_auth_token();
+            }
+            AppEvent::AuthTokenRefreshFinished(result) => {
+                self.chat_widget.on_auth_token_refresh_finished(result);
+            }
+            AppEvent::DispatchSlashCommand(cmd) => {
+                self.chat_widget.dispatch_command(cmd);
+            }
+            AppEvent::SessionStatsResult(result) => match result {
+                Ok(stats) => {
+                    let _ = tui.enter_alt_screen();
+                    self.overlay = Some(Overlay::new_static_with_renderables(
+                        vec![Box::new(SessionStatsView::new(stats))],
+                        "S T A T S".to_string(),
+                    ));
+                    tui.frame_requester().schedule_frame();
+                }
+                Err(err) => self.chat_widget.add_error_message(err),
+            },
             AppEvent::OpenAppLink {
                 app_id,
                 title,
@@ -2246,6 +2650,12 @@ impl App {
             AppEvent::RateLimitSnapshotFetched(snapshot) => {
                 self.chat_widget.on_rate_limit_snapshot(Some(snapshot));
             }
+            AppEvent::ConnectivityRestored => {
+                self.chat_widget.on_connectivity_restored();
+            }
+            AppEvent::RetryLastTurn(overrides) => {
+                self.retry_last_turn(overrides);
+            }
             AppEvent::ConnectorsLoaded { result, is_final } => {
                 self.chat_widget.on_connectors_loaded(result, is_final);
             }
@@ -2264,6 +2674,79 @@ impl App {
             AppEvent::UpdatePersonality(personality) => {
                 self.on_update_personality(personality);
             }
+            AppEvent::AudioAttachmentTranscribed { path, result } => {
+                self.chat_widget
+                    .on_audio_attachment_transcribed(path, result);
+            }
+            AppEvent::ResolveOversizedPaste { text, as_file } => {
+                self.chat_widget.resolve_oversized_paste(text, as_file);
+            }
+            AppEvent::OversizedPasteSaved(result) => {
+                self.chat_widget.on_oversized_paste_saved(result);
+            }
+            AppEvent::ReplaceMisspelling {
+                range,
+                word,
+                replacement,
+            } => {
+                self.chat_widget
+                    .replace_misspelling(range, &word, &replacement);
+            }
+            AppEvent::AddToDictionary(word) => {
+                self.chat_widget.add_to_dictionary(word);
+            }
+            AppEvent::ScrollbackCaptured(result) => {
+                self.chat_widget.on_scrollback_captured(result);
+            }
+            AppEvent::CiLogLoaded(result) => {
+                self.chat_widget.on_ci_log_loaded(result);
+            }
+            AppEvent::DiffPaneRefreshed(result) => {
+                self.chat_widget.on_diff_pane_refreshed(result);
+            }
+            AppEvent::ChangeCwd(cwd) => {
+                let sandbox_policy = crate::change_dir::keep_cwd_writable(
+                    self.config.permissions.sandbox_policy.get(),
+                    &self.config.cwd,
+                );
+                if let Some(policy) = &sandbox_policy {
+                    if let Err(err) = self.config.permissions.sandbox_policy.set(policy.clone()) {
+                        self.chat_widget
+                            .add_error_message(format!("Failed to set sandbox policy: {err}"));
+                        return Ok(AppRunControl::Continue);
+                    }
+                    if let Err(err) = self.chat_widget.set_sandbox_policy(policy.clone()) {
+                        self.chat_widget
+                            .add_error_message(format!("Failed to set sandbox policy: {err}"));
+                        return Ok(AppRunControl::Continue);
+                    }
+                    self.runtime_sandbox_policy_override = Some(policy.clone());
+                }
+                self.config.cwd = cwd.clone();
+                self.file_search.update_search_dir(cwd.clone());
+                self.chat_widget.set_cwd(cwd.clone());
+                self.refresh_terminal_title_repo();
+                self.app_event_tx
+                    .send(AppEvent::CodexOp(Op::OverrideTurnContext {
+                        cwd: Some(cwd.clone()),
+                        approval_policy: None,
+                        sandbox_policy,
+                        windows_sandbox_level: None,
+                        model: None,
+                        effort: None,
+                        summary: None,
+                        service_tier: None,
+                        collaboration_mode: None,
+                        personality: None,
+                    }));
+                self.chat_widget.add_info_message(
+                    format!("Working directory changed to {}", cwd.display()),
+                    None,
+                );
+            }
+            AppEvent::UpdateSamplingParams(sampling) => {
+                self.chat_widget.set_sampling_params(sampling);
+            }
             AppEvent::OpenRealtimeAudioDeviceSelection { kind } => {
                 self.chat_widget.open_realtime_audio_device_selection(kind);
             }
@@ -2778,6 +3261,11 @@ impl App {
                 self.chat_widget.restart_realtime_audio_device(kind);
             }
             AppEvent::UpdateAskForApprovalPolicy(policy) => {
+                if self.config.read_only || self.config.dry_run {
+                    self.chat_widget
+                        .add_error_message(READ_ONLY_PERMISSIONS_MESSAGE.to_string());
+                    return Ok(AppRunControl::Continue);
+                }
                 self.runtime_approval_policy_override = Some(policy);
                 if let Err(err) = self.config.permissions.approval_policy.set(policy) {
                     tracing::warn!(%err, "failed to set approval policy on app config");
@@ -2788,6 +3276,11 @@ impl App {
                 self.chat_widget.set_approval_policy(policy);
             }
             AppEvent::UpdateSandboxPolicy(policy) => {
+                if self.config.read_only || self.config.dry_run {
+                    self.chat_widget
+                        .add_error_message(READ_ONLY_PERMISSIONS_MESSAGE.to_string());
+                    return Ok(AppRunControl::Continue);
+                }
                 #[cfg(target_os = "windows")]
                 let policy_is_workspace_write_or_ro = matches!(
                     &policy,
@@ -3034,6 +3527,37 @@ impl App {
             AppEvent::OpenAgentPicker => {
                 self.open_agent_picker().await;
             }
+            AppEvent::OpenTaskBoard => {
+                let tasks = self.agent_tasks().await;
+                if tasks.is_empty() {
+                    self.chat_widget.add_info_message(
+                        "No sub-agents have been spawned in this session.".to_string(),
+                        None,
+                    );
+                } else {
+                    self.chat_widget
+                        .show_selection_view(task_board_params(tasks));
+                }
+            }
+            AppEvent::OpenAgentTask(thread_id) => {
+                let tasks = self.agent_tasks().await;
+                if let Some(task) = tasks.iter().find(|task| task.thread_id == thread_id) {
+                    self.chat_widget
+                        .show_selection_view(agent_task_params(task));
+                }
+            }
+            AppEvent::CancelAgentThread(thread_id) => {
+                match self.server.close_agent(thread_id).await {
+                    Ok(_) => {
+                        self.mark_agent_picker_thread_closed(thread_id);
+                        self.chat_widget
+                            .add_info_message("Sub-agent cancelled.".to_string(), None);
+                    }
+                    Err(err) => self
+                        .chat_widget
+                        .add_error_message(format!("Failed to cancel the sub-agent: {err}")),
+                }
+            }
             AppEvent::SelectAgentThread(thread_id) => {
                 self.select_agent_thread(tui, thread_id).await?;
             }
@@ -3131,6 +3655,12 @@ impl App {
             AppEvent::OpenReviewCustomPrompt => {
                 self.chat_widget.show_review_custom_prompt();
             }
+            AppEvent::EditPlanSteps => {
+                self.chat_widget.open_plan_steps_editor();
+            }
+            AppEvent::PlanStepsEdited(text) => {
+                self.chat_widget.apply_plan_steps_edit(text);
+            }
             AppEvent::SubmitUserMessageWithMode {
                 text,
                 collaboration_mode,
@@ -3176,7 +3706,39 @@ impl App {
                         "E L I C I T A T I O N".to_string(),
                     ));
                 }
+                ApprovalRequest::Plan {
+                    explanation, steps, ..
+                } => {
+                    let _ = tui.enter_alt_screen();
+                    let mut lines = Vec::new();
+                    if let Some(explanation) = explanation {
+                        lines.push(Line::from(explanation.italic()));
+                        lines.push(Line::from(""));
+                    }
+                    lines.extend(plan_step_lines(&steps));
+                    self.overlay = Some(Overlay::new_static_with_renderables(
+                        vec![Box::new(Paragraph::new(lines).wrap(Wrap { trim: false }))],
+                        "P L A N".to_string(),
+                    ));
+                }
+                ApprovalRequest::Compaction { summary, .. } => {
+                    let _ = tui.enter_alt_screen();
+                    let lines = summary
+                        .lines()
+                        .map(|line| Line::from(line.to_string()))
+                        .collect::<Vec<_>>();
+                    self.overlay = Some(Overlay::new_static_with_lines(
+                        lines,
+                        "S U M M A R Y".to_string(),
+                    ));
+                }
             },
+            AppEvent::EditCompactionSummary(request) => {
+                self.chat_widget.open_compaction_summary_editor(request);
+            }
+            AppEvent::ShowApprovalRequest(request) => {
+                self.chat_widget.push_approval_request(request);
+            }
             #[cfg(not(target_os = "linux"))]
             AppEvent::TranscriptionComplete { id, text } => {
                 self.chat_widget.replace_transcription(&id, &text);
@@ -3194,7 +3756,9 @@ impl App {
                 }
             }
             AppEvent::StatusLineSetup { items } => {
-                let ids = items.iter().map(ToString::to_string).collect::<Vec<_>>();
+                let mut ids = items.iter().map(ToString::to_string).collect::<Vec<_>>();
+                // The picker only offers built-in items; keep configured command segments.
+                ids.extend(self.chat_widget.status_line_command_ids());
                 let edit = codex_core::config::edit::status_line_items_edit(&ids);
                 let apply_result = ConfigEditsBuilder::new(&self.config.codex_home)
                     .with_edits([edit])
@@ -3203,7 +3767,7 @@ impl App {
                 match apply_result {
                     Ok(()) => {
                         self.config.tui_status_line = Some(ids.clone());
-                        self.chat_widget.setup_status_line(items);
+                        self.chat_widget.setup_status_line(ids);
                     }
                     Err(err) => {
                         tracing::error!(error = %err, "failed to persist status line items; keeping previous selection");
@@ -3212,10 +3776,29 @@ impl App {
                     }
                 }
             }
+            AppEvent::StatusLineCommandUpdated { name, value } => {
+                self.chat_widget.set_status_line_command_value(name, value);
+            }
             AppEvent::StatusLineBranchUpdated { cwd, branch } => {
                 self.chat_widget.set_status_line_branch(cwd, branch);
                 self.refresh_status_line();
             }
+            AppEvent::TerminalTitleRepoUpdated { cwd, label } => {
+                self.terminal_title.set_repo_label(&cwd, label);
+                tui.frame_requester().schedule_frame();
+            }
+            AppEvent::ConfigFileChanged => {
+                self.reload_config_from_disk(tui).await;
+            }
+            AppEvent::ApplyPendingConfigReload => {
+                if let Some(config) = self.pending_config_reload.take() {
+                    if let Some(model) = config.model.as_deref() {
+                        self.chat_widget.set_model(model);
+                    }
+                    self.config = config;
+                    self.start_fresh_session_with_summary_hint(tui).await;
+                }
+            }
             AppEvent::StatusLineSetupCancelled => {
                 self.chat_widget.cancel_status_line_setup();
             }
@@ -3290,6 +3873,13 @@ impl App {
             emit_skill_load_warnings(&self.app_event_tx, &errors);
         }
         self.handle_backtrack_event(&event.msg);
+        if self.recent_events.len() == RECENT_EVENTS_CAPACITY {
+            self.recent_events.pop_front();
+        }
+        self.recent_events.push_back(RecordedEvent {
+            received_at: chrono::Local::now(),
+            event: event.clone(),
+        });
         self.chat_widget.handle_codex_event(event);
 
         if needs_refresh {
@@ -3434,7 +4024,7 @@ impl App {
         self.chat_widget.token_usage()
     }
 
-    fn on_update_reasoning_effort(&mut self, effort: Option<ReasoningEffortConfig>) {
+    pub(crate) fn on_update_reasoning_effort(&mut self, effort: Option<ReasoningEffortConfig>) {
         // TODO(aibrahim): Remove this and don't use config as a state object.
         // Instead, explicitly pass the stored collaboration mode's effort into new sessions.
         self.config.model_reasoning_effort = effort;
@@ -3540,25 +4130,67 @@ impl App {
         tui.frame_requester().schedule_frame();
     }
 
-    async fn handle_key_event(&mut self, tui: &mut tui::Tui, key_event: KeyEvent) {
-        match key_event {
-            KeyEvent {
-                code: KeyCode::Char('t'),
-                modifiers: crossterm::event::KeyModifiers::CONTROL,
-                kind: KeyEventKind::Press,
-                ..
-            } => {
-                // Enter alternate screen and set viewport to full size.
+    fn open_log_overlay(&mut self, tui: &mut tui::Tui) {
+        match codex_core::config::log_dir(&self.config) {
+            Ok(log_dir) => {
                 let _ = tui.enter_alt_screen();
-                self.overlay = Some(Overlay::new_transcript(self.transcript_cells.clone()));
+                self.overlay = Some(Overlay::new_log(log_dir.join("codex-tui.log")));
                 tui.frame_requester().schedule_frame();
             }
-            KeyEvent {
-                code: KeyCode::Char('l'),
-                modifiers: crossterm::event::KeyModifiers::CONTROL,
-                kind: KeyEventKind::Press,
-                ..
-            } => {
+            Err(err) => {
+                self.chat_widget
+                    .add_error_message(format!("Failed to locate the log file: {err}"));
+            }
+        }
+    }
+
+    fn open_keymap_overlay(&mut self, tui: &mut tui::Tui) {
+        let _ = tui.enter_alt_screen();
+        self.overlay = Some(Overlay::new_static_with_lines(
+            keymap::cheat_sheet_lines(
+                &self.chat_widget.available_slash_commands(),
+                &self.chat_widget.configured_keys(),
+            ),
+            "K E Y S".to_string(),
+        ));
+        tui.frame_requester().schedule_frame();
+    }
+
+    async fn handle_key_event(&mut self, tui: &mut tui::Tui, key_event: KeyEvent) {
+        match key_event {
+            key_event if keymap::is_initial_press(keymap::SHOW_TRANSCRIPT, key_event) => {
+                self.open_transcript_overlay(tui);
+            }
+            key_event if keymap::is_initial_press(keymap::SHOW_LOG, key_event) => {
+                self.open_log_overlay(tui);
+            }
+            key_event
+                if keymap::is_initial_press(keymap::SHOW_KEYMAP, key_event)
+                    || keymap::is_initial_press(keymap::SHOW_KEYMAP_LEGACY, key_event) =>
+            {
+                self.open_keymap_overlay(tui);
+            }
+            // The palette opens from the plain composer only, not over another popup or view.
+            key_event
+                if keymap::is_initial_press(keymap::COMMAND_PALETTE, key_event)
+                    && self.chat_widget.no_modal_or_popup_active() =>
+            {
+                self.chat_widget.open_command_palette();
+            }
+            key_event
+                if keymap::is_initial_press(keymap::RETRY, key_event)
+                    && self.chat_widget.no_modal_or_popup_active() =>
+            {
+                self.retry_last_turn(RetryOverrides::default());
+            }
+            key_event
+                if keymap::is_initial_press(keymap::SPELLING_SUGGESTIONS, key_event)
+                    && spellcheck::get().is_some()
+                    && self.chat_widget.no_modal_or_popup_active() =>
+            {
+                self.chat_widget.open_spelling_suggestions();
+            }
+            key_event if keymap::is_initial_press(keymap::CLEAR_SCREEN, key_event) => {
                 if !self.chat_widget.can_run_ctrl_l_clear_now() {
                     return;
                 }
@@ -3572,12 +4204,7 @@ impl App {
                     tui.frame_requester().schedule_frame();
                 }
             }
-            KeyEvent {
-                code: KeyCode::Char('g'),
-                modifiers: crossterm::event::KeyModifiers::CONTROL,
-                kind: KeyEventKind::Press,
-                ..
-            } => {
+            key_event if keymap::is_initial_press(keymap::EXTERNAL_EDITOR, key_event) => {
                 // Only launch the external editor if there is no overlay and the bottom pane is not in use.
                 // Note that it can be launched while a task is running to enable editing while the previous turn is ongoing.
                 if self.overlay.is_none()
@@ -3639,6 +4266,133 @@ impl App {
         self.chat_widget.refresh_status_line();
     }
 
+    /// Watch the config files the current config was loaded from.
+    fn watch_config_files(&mut self) {
+        let files = codex_core::config::validation::config_files_for_layers(
+            &self.config.config_layer_stack,
+        );
+        self.config_watcher = ConfigWatcher::spawn(files, self.app_event_tx.clone());
+    }
+
+    /// Reload the config after a watched file changed. Display settings are applied right
+    /// away; a changed model or provider is only applied once the user starts a new session.
+    async fn reload_config_from_disk(&mut self, tui: &mut tui::Tui) {
+        let mut reloaded = match self.rebuild_config_for_cwd(self.config.cwd.clone()).await {
+            Ok(config) => config,
+            Err(err) => {
+                self.chat_widget.add_error_message(format!(
+                    "Config changed but could not be loaded; keeping the current settings. {}",
+                    err.root_cause()
+                ));
+                return;
+            }
+        };
+        self.apply_runtime_policy_overrides(&mut reloaded);
+        let changes = config_reload::diff_config(&self.config, &reloaded);
+        for warning in &reloaded.startup_warnings {
+            if !self.config.startup_warnings.contains(warning) {
+                self.chat_widget.add_warning_message(warning.clone());
+            }
+        }
+        if changes.needs_new_session.is_empty() {
+            self.pending_config_reload = None;
+            self.config = reloaded;
+        } else {
+            config_reload::copy_live_settings(&reloaded, &mut self.config);
+            self.pending_config_reload = Some(reloaded);
+        }
+        if !changes.live.is_empty() {
+            self.restore_runtime_theme_from_config();
+            tui.set_notification_method(self.config.tui_notification_method);
+            self.chat_widget.apply_live_config(&self.config);
+            self.chat_widget.add_info_message(
+                format!("Reloaded config: updated {}.", changes.live.join(", ")),
+                None,
+            );
+        }
+        if !changes.needs_new_session.is_empty() {
+            self.chat_widget
+                .open_config_reload_prompt(changes.needs_new_session);
+        }
+        tui.frame_requester().schedule_frame();
+    }
+
+    /// What the next request on the displayed thread would contain, or `None` after reporting
+    /// why it is unavailable.
+    async fn next_turn_context(&mut self) -> Option<NextTurnContext> {
+        let Some(thread_id) = self.chat_widget.thread_id() else {
+            self.chat_widget
+                .add_error_message("The session has not started yet.".to_string());
+            return None;
+        };
+        match self.server.get_thread(thread_id).await {
+            Ok(thread) => Some(thread.next_turn_context().await),
+            Err(err) => {
+                self.chat_widget
+                    .add_error_message(format!("Failed to inspect the context: {err}"));
+                None
+            }
+        }
+    }
+
+    async fn open_context_inspector(&mut self, tui: &mut tui::Tui) {
+        let Some(context) = self.next_turn_context().await else {
+            return;
+        };
+        let _ = tui.enter_alt_screen();
+        self.overlay = Some(Overlay::new_static_with_lines(
+            crate::debug_config::debug_context_lines(&context),
+            "C O N T E X T".to_string(),
+        ));
+        tui.frame_requester().schedule_frame();
+    }
+
+    /// Save a `/config` edit to the file the value comes from, then reload so it applies.
+    async fn set_config_value(&mut self, tui: &mut tui::Tui, key: &str, value: &str) {
+        let Some(setting) = config_view::editable_setting(key) else {
+            return;
+        };
+        let target = config_view::edit_target(
+            &self.config.config_layer_stack,
+            &self.config.codex_home,
+            key,
+        );
+        if let Err(err) = ConfigEditsBuilder::new(&target.dir)
+            .with_edits([setting.edit(value)])
+            .apply()
+            .await
+        {
+            tracing::error!(error = %err, "failed to persist {key}");
+            self.chat_widget
+                .add_error_message(format!("Failed to save `{key}`: {err}"));
+            return;
+        }
+        self.chat_widget.add_info_message(
+            format!("Set {key} = {value} in {}.", target.file().display()),
+            None,
+        );
+        if let Some(source) = target.overridden_by {
+            self.chat_widget.add_warning_message(format!(
+                "{source} also sets {key} and takes precedence over the saved value."
+            ));
+        }
+        self.reload_config_from_disk(tui).await;
+    }
+
+    /// Look up the `<repo>@<branch>` label for the terminal title in the background.
+    fn refresh_terminal_title_repo(&mut self) {
+        if !self.terminal_title.enabled() {
+            return;
+        }
+        let cwd = self.config.cwd.clone();
+        self.terminal_title.begin_repo_lookup(&cwd);
+        let tx = self.app_event_tx.clone();
+        tokio::spawn(async move {
+            let label = terminal_title::repo_label(&cwd).await;
+            tx.send(AppEvent::TerminalTitleRepoUpdated { cwd, label });
+        });
+    }
+
     #[cfg(target_os = "windows")]
     fn spawn_world_writable_scan(
         cwd: PathBuf,
@@ -5246,7 +6000,7 @@ mod tests {
         let auth_manager = codex_core::test_support::auth_manager_from_auth(
             CodexAuth::from_api_key("Test API Key"),
         );
-        let file_search = FileSearchManager::new(config.cwd.clone(), app_event_tx.clone());
+        let file_search = FileSearchManager::new(config.cwd.clone(), None, app_event_tx.clone());
         let model = codex_core::test_support::get_model_offline(config.model.as_deref());
         let otel_manager = test_otel_manager(&config, model.as_str());
 
@@ -5263,10 +6017,15 @@ mod tests {
             runtime_approval_policy_override: None,
             runtime_sandbox_policy_override: None,
             file_search,
+            terminal_title: TerminalTitle::default(),
+            config_watcher: None,
+            pending_config_reload: None,
             transcript_cells: Vec::new(),
+            transcript_spill: TranscriptSpill::new(0),
             overlay: None,
             deferred_history_lines: Vec::new(),
             has_emitted_history_lines: false,
+            history_reflow: HistoryReflow::default(),
             enhanced_keys_supported: false,
             commit_anim_running: Arc::new(AtomicBool::new(false)),
             status_line_invalid_items_warned: Arc::new(AtomicBool::new(false)),
@@ -5286,6 +6045,7 @@ mod tests {
             primary_thread_id: None,
             primary_session_configured: None,
             pending_primary_events: VecDeque::new(),
+            recent_events: VecDeque::new(),
         }
     }
 
@@ -5305,7 +6065,7 @@ mod tests {
         let auth_manager = codex_core::test_support::auth_manager_from_auth(
             CodexAuth::from_api_key("Test API Key"),
         );
-        let file_search = FileSearchManager::new(config.cwd.clone(), app_event_tx.clone());
+        let file_search = FileSearchManager::new(config.cwd.clone(), None, app_event_tx.clone());
         let model = codex_core::test_support::get_model_offline(config.model.as_deref());
         let otel_manager = test_otel_manager(&config, model.as_str());
 
@@ -5323,10 +6083,15 @@ mod tests {
                 runtime_approval_policy_override: None,
                 runtime_sandbox_policy_override: None,
                 file_search,
+                terminal_title: TerminalTitle::default(),
+                config_watcher: None,
+                pending_config_reload: None,
                 transcript_cells: Vec::new(),
+                transcript_spill: TranscriptSpill::new(0),
                 overlay: None,
                 deferred_history_lines: Vec::new(),
                 has_emitted_history_lines: false,
+                history_reflow: HistoryReflow::default(),
                 enhanced_keys_supported: false,
                 commit_anim_running: Arc::new(AtomicBool::new(false)),
                 status_line_invalid_items_warned: Arc::new(AtomicBool::new(false)),
@@ -5346,6 +6111,7 @@ mod tests {
                 primary_thread_id: None,
                 primary_session_configured: None,
                 pending_primary_events: VecDeque::new(),
+                recent_events: VecDeque::new(),
             },
             rx,
             op_rx,
@@ -6015,6 +6781,76 @@ mod tests {
         }));
     }
 
+    #[tokio::test]
+    async fn retry_resubmits_last_user_message_after_rollback_confirms() {
+        let (mut app, _app_event_rx, mut op_rx) = make_test_app_with_channels().await;
+
+        let thread_id = ThreadId::new();
+        app.chat_widget.handle_codex_event(Event {
+            id: String::new(),
+            msg: EventMsg::SessionConfigured(SessionConfiguredEvent {
+                session_id: thread_id,
+                forked_from_id: None,
+                thread_name: None,
+                model: "gpt-test".to_string(),
+                model_provider_id: "test-provider".to_string(),
+                service_tier: None,
+                approval_policy: AskForApproval::Never,
+                sandbox_policy: SandboxPolicy::new_read_only_policy(),
+                cwd: PathBuf::from("/home/user/project"),
+                reasoning_effort: None,
+                history_log_id: 0,
+                history_entry_count: 0,
+                initial_messages: None,
+                network_proxy: None,
+                rollout_path: Some(PathBuf::new()),
+            }),
+        });
+        app.transcript_cells = vec![
+            Arc::new(UserHistoryCell {
+                message: "first".to_string(),
+                text_elements: Vec::new(),
+                local_image_paths: Vec::new(),
+                remote_image_urls: Vec::new(),
+            }) as Arc<dyn HistoryCell>,
+            Arc::new(UserHistoryCell {
+                message: "explain the bug".to_string(),
+                text_elements: Vec::new(),
+                local_image_paths: Vec::new(),
+                remote_image_urls: Vec::new(),
+            }) as Arc<dyn HistoryCell>,
+        ];
+
+        app.retry_last_turn(RetryOverrides::default());
+
+        let mut rollback_turns = None;
+        while let Ok(op) = op_rx.try_recv() {
+            match op {
+                Op::ThreadRollback { num_turns } => rollback_turns = Some(num_turns),
+                Op::UserTurn { .. } => panic!("retry must wait for the rollback to confirm"),
+                _ => {}
+            }
+        }
+        assert_eq!(rollback_turns, Some(1));
+        assert_eq!(app.chat_widget.composer_text_with_pending(), "");
+
+        app.handle_backtrack_event(&EventMsg::ThreadRolledBack(ThreadRolledBackEvent {
+            num_turns: 1,
+        }));
+
+        let mut submitted_items = None;
+        while let Ok(op) = op_rx.try_recv() {
+            if let Op::UserTurn { items, .. } = op {
+                submitted_items = Some(items);
+            }
+        }
+        let items = submitted_items.expect("expected retried user turn");
+        assert!(items.iter().any(|item| matches!(
+            item,
+            UserInput::Text { text, .. } if text == "explain the bug"
+        )));
+    }
+
     #[tokio::test]
     async fn replayed_initial_messages_apply_rollback_in_queue_order() {
         let (mut app, mut app_event_rx, _op_rx) = make_test_app_with_channels().await;
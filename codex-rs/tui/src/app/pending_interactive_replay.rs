@@ -38,6 +38,8 @@ pub(super) struct PendingInteractiveReplayState {
     exec_approval_call_ids_by_turn_id: HashMap<String, Vec<String>>,
     patch_approval_call_ids: HashSet<String>,
     patch_approval_call_ids_by_turn_id: HashMap<String, Vec<String>>,
+    compaction_review_ids: HashSet<String>,
+    compaction_review_ids_by_turn_id: HashMap<String, Vec<String>>,
     elicitation_requests: HashSet<ElicitationRequestKey>,
     request_user_input_call_ids: HashSet<String>,
     request_user_input_call_ids_by_turn_id: HashMap<String, Vec<String>>,
@@ -49,6 +51,8 @@ impl PendingInteractiveReplayState {
             &event.msg,
             EventMsg::ExecApprovalRequest(_)
                 | EventMsg::ApplyPatchApprovalRequest(_)
+                | EventMsg::PlanApprovalRequest(_)
+                | EventMsg::CompactionReviewRequest(_)
                 | EventMsg::ElicitationRequest(_)
                 | EventMsg::ExecCommandBegin(_)
                 | EventMsg::PatchApplyBegin(_)
@@ -63,6 +67,7 @@ impl PendingInteractiveReplayState {
             op,
             Op::ExecApproval { .. }
                 | Op::PatchApproval { .. }
+                | Op::CompactionReview { .. }
                 | Op::ResolveElicitation { .. }
                 | Op::UserInputAnswer { .. }
                 | Op::Shutdown
@@ -88,6 +93,10 @@ impl PendingInteractiveReplayState {
                     id,
                 );
             }
+            Op::CompactionReview { id, .. } => {
+                self.compaction_review_ids.remove(id);
+                Self::remove_call_id_from_turn_map(&mut self.compaction_review_ids_by_turn_id, id);
+            }
             Op::ResolveElicitation {
                 server_name,
                 request_id,
@@ -146,6 +155,21 @@ impl PendingInteractiveReplayState {
                     .or_default()
                     .push(ev.call_id.clone());
             }
+            // Plan approvals are answered with `Op::PatchApproval`, so they share its tracking.
+            EventMsg::PlanApprovalRequest(ev) => {
+                self.patch_approval_call_ids.insert(ev.call_id.clone());
+                self.patch_approval_call_ids_by_turn_id
+                    .entry(ev.turn_id.clone())
+                    .or_default()
+                    .push(ev.call_id.clone());
+            }
+            EventMsg::CompactionReviewRequest(ev) => {
+                self.compaction_review_ids.insert(ev.id.clone());
+                self.compaction_review_ids_by_turn_id
+                    .entry(ev.turn_id.clone())
+                    .or_default()
+                    .push(ev.id.clone());
+            }
             EventMsg::PatchApplyBegin(ev) => {
                 self.patch_approval_call_ids.remove(&ev.call_id);
                 Self::remove_call_id_from_turn_map(
@@ -171,12 +195,14 @@ impl PendingInteractiveReplayState {
             EventMsg::TurnComplete(ev) => {
                 self.clear_exec_approval_turn(&ev.turn_id);
                 self.clear_patch_approval_turn(&ev.turn_id);
+                self.clear_compaction_review_turn(&ev.turn_id);
                 self.clear_request_user_input_turn(&ev.turn_id);
             }
             EventMsg::TurnAborted(ev) => {
                 if let Some(turn_id) = &ev.turn_id {
                     self.clear_exec_approval_turn(turn_id);
                     self.clear_patch_approval_turn(turn_id);
+                    self.clear_compaction_review_turn(turn_id);
                     self.clear_request_user_input_turn(turn_id);
                 }
             }
@@ -204,6 +230,22 @@ impl PendingInteractiveReplayState {
                     &ev.call_id,
                 );
             }
+            EventMsg::PlanApprovalRequest(ev) => {
+                self.patch_approval_call_ids.remove(&ev.call_id);
+                Self::remove_call_id_from_turn_map_entry(
+                    &mut self.patch_approval_call_ids_by_turn_id,
+                    &ev.turn_id,
+                    &ev.call_id,
+                );
+            }
+            EventMsg::CompactionReviewRequest(ev) => {
+                self.compaction_review_ids.remove(&ev.id);
+                Self::remove_call_id_from_turn_map_entry(
+                    &mut self.compaction_review_ids_by_turn_id,
+                    &ev.turn_id,
+                    &ev.id,
+                );
+            }
             EventMsg::ElicitationRequest(ev) => {
                 self.elicitation_requests
                     .remove(&ElicitationRequestKey::new(
@@ -240,6 +282,8 @@ impl PendingInteractiveReplayState {
             EventMsg::ApplyPatchApprovalRequest(ev) => {
                 self.patch_approval_call_ids.contains(&ev.call_id)
             }
+            EventMsg::PlanApprovalRequest(ev) => self.patch_approval_call_ids.contains(&ev.call_id),
+            EventMsg::CompactionReviewRequest(ev) => self.compaction_review_ids.contains(&ev.id),
             EventMsg::ElicitationRequest(ev) => {
                 self.elicitation_requests
                     .contains(&ElicitationRequestKey::new(
@@ -257,6 +301,7 @@ impl PendingInteractiveReplayState {
     pub(super) fn has_pending_thread_approvals(&self) -> bool {
         !self.exec_approval_call_ids.is_empty()
             || !self.patch_approval_call_ids.is_empty()
+            || !self.compaction_review_ids.is_empty()
             || !self.elicitation_requests.is_empty()
     }
 
@@ -284,6 +329,14 @@ impl PendingInteractiveReplayState {
         }
     }
 
+    fn clear_compaction_review_turn(&mut self, turn_id: &str) {
+        if let Some(ids) = self.compaction_review_ids_by_turn_id.remove(turn_id) {
+            for id in ids {
+                self.compaction_review_ids.remove(&id);
+            }
+        }
+    }
+
     fn remove_call_id_from_turn_map(
         call_ids_by_turn_id: &mut HashMap<String, Vec<String>>,
         call_id: &str,
@@ -316,6 +369,8 @@ impl PendingInteractiveReplayState {
         self.exec_approval_call_ids_by_turn_id.clear();
         self.patch_approval_call_ids.clear();
         self.patch_approval_call_ids_by_turn_id.clear();
+        self.compaction_review_ids.clear();
+        self.compaction_review_ids_by_turn_id.clear();
         self.elicitation_requests.clear();
         self.request_user_input_call_ids.clear();
         self.request_user_input_call_ids_by_turn_id.clear();
@@ -528,6 +583,34 @@ mod tests {
         );
     }
 
+    #[test]
+    fn thread_event_snapshot_keeps_compaction_review_until_answered() {
+        let mut store = ThreadEventStore::new(8);
+        store.push_event(Event {
+            id: "ev-1".to_string(),
+            msg: EventMsg::CompactionReviewRequest(
+                codex_protocol::protocol::CompactionReviewRequestEvent {
+                    id: "turn-1".to_string(),
+                    turn_id: "turn-1".to_string(),
+                    summary: "Summary".to_string(),
+                },
+            ),
+        });
+        assert!(store.has_pending_thread_approvals());
+        assert_eq!(store.snapshot().events.len(), 1);
+
+        store.note_outbound_op(&Op::CompactionReview {
+            id: "turn-1".to_string(),
+            decision: codex_protocol::protocol::CompactionReviewDecision::Accept,
+        });
+
+        assert!(!store.has_pending_thread_approvals());
+        assert!(
+            store.snapshot().events.is_empty(),
+            "answered compaction review should not replay on thread switch"
+        );
+    }
+
     #[test]
     fn thread_event_snapshot_drops_pending_approvals_when_turn_aborts() {
         let mut store = ThreadEventStore::new(8);
@@ -29,6 +29,7 @@ use std::sync::Arc;
 
 use crate::app::App;
 use crate::app_event::AppEvent;
+use crate::app_event::RetryOverrides;
 use crate::history_cell::SessionInfoCell;
 use crate::history_cell::UserHistoryCell;
 use crate::pager_overlay::Overlay;
@@ -97,6 +98,9 @@ pub(crate) struct BacktrackSelection {
 pub(crate) struct PendingBacktrackRollback {
     pub(crate) selection: BacktrackSelection,
     pub(crate) thread_id: Option<ThreadId>,
+    /// Set by `/retry`: once core confirms, resubmit the selected message with these overrides
+    /// instead of leaving it in the composer.
+    pub(crate) retry: Option<RetryOverrides>,
 }
 
 impl App {
@@ -189,42 +193,77 @@ impl App {
     /// The composer prefill is applied immediately as a UX convenience; it does not imply that
     /// core has accepted the rollback.
     pub(crate) fn apply_backtrack_rollback(&mut self, selection: BacktrackSelection) {
+        let prefill = selection.prefill.clone();
+        let text_elements = selection.text_elements.clone();
+        let local_image_paths = selection.local_image_paths.clone();
+        let remote_image_urls = selection.remote_image_urls.clone();
+        let has_remote_image_urls = !remote_image_urls.is_empty();
+        if !self.request_rollback(selection, None) {
+            return;
+        }
+        self.chat_widget.set_remote_image_urls(remote_image_urls);
+        if !prefill.is_empty()
+            || !text_elements.is_empty()
+            || !local_image_paths.is_empty()
+            || has_remote_image_urls
+        {
+            self.chat_widget
+                .set_composer_text(prefill, text_elements, local_image_paths);
+        }
+    }
+
+    /// Rewind the most recent user turn and request it again (`/retry` and its key binding).
+    ///
+    /// This reuses the backtrack rollback: the message is resubmitted only after core confirms the
+    /// rollback, so the retried turn never races the trim of the old one.
+    pub(crate) fn retry_last_turn(&mut self, overrides: RetryOverrides) {
+        if self.chat_widget.is_task_running() {
+            self.chat_widget.add_error_message(
+                "Cannot retry while a turn is running; interrupt it first.".to_string(),
+            );
+            return;
+        }
+        let Some(nth_user_message) = user_count(&self.transcript_cells).checked_sub(1) else {
+            self.chat_widget
+                .add_info_message("Nothing to retry yet.".to_string(), None);
+            return;
+        };
+        let selection = self.user_message_selection(nth_user_message);
+        self.request_rollback(selection, Some(overrides));
+    }
+
+    /// Send `Op::ThreadRollback` for everything from `selection` onward and record the guard.
+    ///
+    /// Returns `false` when nothing was requested.
+    fn request_rollback(
+        &mut self,
+        selection: BacktrackSelection,
+        retry: Option<RetryOverrides>,
+    ) -> bool {
         let user_total = user_count(&self.transcript_cells);
         if user_total == 0 {
-            return;
+            return false;
         }
 
         if self.backtrack.pending_rollback.is_some() {
             self.chat_widget
                 .add_error_message("Backtrack rollback already in progress.".to_string());
-            return;
+            return false;
         }
 
         let num_turns = user_total.saturating_sub(selection.nth_user_message);
         let num_turns = u32::try_from(num_turns).unwrap_or(u32::MAX);
         if num_turns == 0 {
-            return;
+            return false;
         }
 
-        let prefill = selection.prefill.clone();
-        let text_elements = selection.text_elements.clone();
-        let local_image_paths = selection.local_image_paths.clone();
-        let remote_image_urls = selection.remote_image_urls.clone();
-        let has_remote_image_urls = !remote_image_urls.is_empty();
         self.backtrack.pending_rollback = Some(PendingBacktrackRollback {
             selection,
             thread_id: self.chat_widget.thread_id(),
+            retry,
         });
         self.chat_widget.submit_op(Op::ThreadRollback { num_turns });
-        self.chat_widget.set_remote_image_urls(remote_image_urls);
-        if !prefill.is_empty()
-            || !text_elements.is_empty()
-            || !local_image_paths.is_empty()
-            || has_remote_image_urls
-        {
-            self.chat_widget
-                .set_composer_text(prefill, text_elements, local_image_paths);
-        }
+        true
     }
 
     /// Open transcript overlay (enters alternate screen and shows full transcript).
@@ -258,6 +297,7 @@ impl App {
             for cell in &self.transcript_cells {
                 tui.insert_history_lines(cell.display_lines(width));
             }
+            self.history_reflow.note_written(width);
         }
     }
 
@@ -524,6 +564,25 @@ impl App {
             self.sync_overlay_after_transcript_trim();
             self.backtrack_render_pending = true;
         }
+        if let Some(retry) = pending.retry {
+            self.resubmit_for_retry(pending.selection, retry);
+        }
+    }
+
+    fn resubmit_for_retry(&mut self, selection: BacktrackSelection, retry: RetryOverrides) {
+        if let Some(model) = retry.model {
+            self.chat_widget.set_model(&model);
+        }
+        if let Some(effort) = retry.effort {
+            self.on_update_reasoning_effort(Some(effort));
+        }
+        self.chat_widget.refresh_status_line();
+        self.chat_widget.resubmit_user_message(
+            selection.prefill,
+            selection.text_elements,
+            selection.local_image_paths,
+            selection.remote_image_urls,
+        );
     }
 
     fn backtrack_selection(&self, nth_user_message: usize) -> Option<BacktrackSelection> {
@@ -531,7 +590,10 @@ impl App {
         if self.chat_widget.thread_id() != Some(base_id) {
             return None;
         }
+        Some(self.user_message_selection(nth_user_message))
+    }
 
+    fn user_message_selection(&self, nth_user_message: usize) -> BacktrackSelection {
         let (prefill, text_elements, local_image_paths, remote_image_urls) =
             nth_user_position(&self.transcript_cells, nth_user_message)
                 .and_then(|idx| self.transcript_cells.get(idx))
@@ -546,23 +608,26 @@ impl App {
                 })
                 .unwrap_or_else(|| (String::new(), Vec::new(), Vec::new(), Vec::new()));
 
-        Some(BacktrackSelection {
+        BacktrackSelection {
             nth_user_message,
             prefill,
             text_elements,
             local_image_paths,
             remote_image_urls,
-        })
+        }
     }
 
     /// Keep transcript-related UI state aligned after `transcript_cells` was trimmed.
     ///
-    /// This does three things:
+    /// This does four things:
     /// 1. If transcript overlay is open, replace its committed cells so removed turns disappear.
     /// 2. If backtrack preview is active, clamp/recompute the highlighted user selection.
     /// 3. Drop deferred transcript lines buffered while overlay was open to avoid flushing lines
     ///    for cells that were just removed by the trim.
+    /// 4. Recount the transcript lines held in memory for spilling.
     fn sync_overlay_after_transcript_trim(&mut self) {
+        self.transcript_spill
+            .on_cells_replaced(&self.transcript_cells);
         if let Some(Overlay::Transcript(t)) = &mut self.overlay {
             t.replace_cells(self.transcript_cells.clone());
         }
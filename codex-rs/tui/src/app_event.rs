@@ -8,9 +8,19 @@
 //! Exit is modelled explicitly via `AppEvent::Exit(ExitMode)` so callers can request shutdown-first
 //! quits without reaching into the app loop or coupling to shutdown/exit sequencing.
 
+use std::ops::Range;
 use std::path::PathBuf;
 
 use codex_chatgpt::connectors::AppInfo;
+use codex_core::CarryOverBriefing;
+use codex_core::ConflictHunk;
+use codex_core::ConflictResolution;
+use codex_core::ModelComparison;
+use codex_core::PullRequest;
+use codex_core::SessionComparison;
+use codex_core::SessionStats;
+use codex_core::SessionSummary;
+use codex_core::review_diff::ReviewDiff;
 use codex_file_search::FileMatch;
 use codex_protocol::ThreadId;
 use codex_protocol::openai_models::ModelPreset;
@@ -20,11 +30,15 @@ use codex_utils_approval_presets::ApprovalPreset;
 
 use crate::bottom_pane::ApprovalRequest;
 use crate::bottom_pane::StatusLineItem;
+use crate::ci_log::CiLogDigest;
 use crate::history_cell::HistoryCell;
+use crate::paste_file::SavedPaste;
+use crate::slash_command::SlashCommand;
 
 use codex_core::features::Feature;
 use codex_protocol::config_types::CollaborationModeMask;
 use codex_protocol::config_types::Personality;
+use codex_protocol::config_types::SamplingParams;
 use codex_protocol::config_types::ServiceTier;
 use codex_protocol::openai_models::ReasoningEffort;
 use codex_protocol::protocol::AskForApproval;
@@ -52,6 +66,15 @@ impl RealtimeAudioDeviceKind {
     }
 }
 
+/// Model settings applied before `/retry` re-requests the last turn.
+///
+/// `None` keeps the current value. Overrides stick for later turns, like picking them in `/model`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct RetryOverrides {
+    pub(crate) model: Option<String>,
+    pub(crate) effort: Option<ReasoningEffort>,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(not(target_os = "windows"), allow(dead_code))]
 pub(crate) enum WindowsSandboxEnableMode {
@@ -74,6 +97,15 @@ pub(crate) enum AppEvent {
     /// Switch the active thread to the selected agent.
     SelectAgentThread(ThreadId),
 
+    /// Open the `/tasks` board listing sub-agents and their status.
+    OpenTaskBoard,
+
+    /// Open the inspect/cancel actions for one sub-agent on the task board.
+    OpenAgentTask(ThreadId),
+
+    /// Shut down a sub-agent picked from the task board.
+    CancelAgentThread(ThreadId),
+
     /// Submit an op to the specified thread, regardless of current focus.
     SubmitThreadOp {
         thread_id: ThreadId,
@@ -130,6 +162,12 @@ pub(crate) enum AppEvent {
     /// Result of refreshing rate limits
     RateLimitSnapshotFetched(RateLimitSnapshot),
 
+    /// The model provider answered a connectivity probe after a turn failed offline.
+    ConnectivityRestored,
+
+    /// Roll back the most recent user turn and submit the same message again.
+    RetryLastTurn(RetryOverrides),
+
     /// Result of prefetching connectors.
     ConnectorsLoaded {
         result: Result<ConnectorsSnapshot, String>,
@@ -139,6 +177,159 @@ pub(crate) enum AppEvent {
     /// Result of computing a `/diff` command.
     DiffResult(String),
 
+    /// Result of loading the two sessions named by `/compare`.
+    SessionComparisonResult(Result<SessionComparison, String>),
+
+    /// Send a prompt to every model in `compare_models` for `/compare-models`.
+    CompareModels {
+        prompt: String,
+    },
+
+    /// Result of running a `/compare-models` prompt.
+    ModelComparisonResult(ModelComparison),
+
+    /// Result of scanning the workspace for merge conflicts (`/conflicts`).
+    ConflictsFound(Result<Vec<ConflictHunk>, String>),
+
+    /// Ask the model how to resolve a conflict hunk.
+    ProposeConflictResolution(ConflictHunk),
+
+    /// Result of asking the model how to resolve `hunk`.
+    ConflictResolutionProposed {
+        hunk: ConflictHunk,
+        result: Result<ConflictResolution, String>,
+    },
+
+    /// Open `text` in an editor before applying it as the resolution of `hunk`.
+    EditConflictResolution {
+        hunk: ConflictHunk,
+        text: String,
+    },
+
+    /// Replace `hunk` in its file with `text`.
+    ApplyConflictResolution {
+        hunk: ConflictHunk,
+        text: String,
+    },
+
+    /// Result of applying a resolution; carries how many conflicts are left in the file.
+    ConflictResolutionApplied {
+        hunk: ConflictHunk,
+        result: Result<usize, String>,
+    },
+
+    /// Leave the current conflict hunk as is and move on to the next one.
+    SkipConflict,
+
+    /// Draft a commit message for the staged changes for `/commit-msg`.
+    DraftCommitMessage,
+
+    /// Result of drafting the `/commit-msg` message.
+    CommitMessageDrafted(Result<String, String>),
+
+    /// Summarize the session into its sidecar for `/summarize`, or before exiting when
+    /// `exit_after` is set.
+    SummarizeSession {
+        exit_after: bool,
+    },
+
+    /// Result of summarizing the session.
+    SessionSummarized {
+        result: Result<SessionSummary, String>,
+        exit_after: bool,
+    },
+
+    /// Summaries of recent sessions in this project were found for a fresh session; offer them
+    /// as a briefing.
+    CarryOverBriefingLoaded(CarryOverBriefing),
+
+    /// The user approved the (possibly edited) commit message; commit the staged changes.
+    CommitStaged {
+        message: String,
+    },
+
+    /// Result of committing; carries the new commit's short hash and subject.
+    CommitCreated(Result<String, String>),
+
+    /// Push the current branch and open a pull request for `/pr`.
+    CreatePullRequest {
+        instructions: String,
+    },
+
+    /// Result of opening the pull request requested by `/pr`.
+    PullRequestCreated(Result<PullRequest, String>),
+
+    /// The diff named by `codex review --diff` finished loading.
+    ReviewDiffLoaded {
+        result: Result<ReviewDiff, String>,
+        instructions: Option<String>,
+    },
+
+    /// Result of posting review comments with `/post-review`; carries the comment's URL.
+    ReviewCommentsPosted(Result<String, String>),
+
+    /// Result of loading the session named by `/stats`.
+    SessionStatsResult(Result<SessionStats, String>),
+
+    /// Open the overlay listing recently received protocol events.
+    OpenEventInspector,
+
+    /// Open the overlay listing the environment agent commands receive.
+    OpenEnvironmentOverlay,
+
+    /// Open the overlay breaking down what the next model request will contain.
+    OpenContextInspector,
+
+    /// Open the `/trim` picker listing tool outputs in the context, largest first.
+    OpenTrimPicker,
+
+    /// Open the `/config` popup.
+    OpenConfigEditor,
+
+    /// Open the overlay showing the effective config and where each value comes from.
+    OpenConfigOverlay,
+
+    /// Open the choices for one of the settings `/config` can edit.
+    OpenConfigSetting {
+        key: &'static str,
+    },
+
+    /// Write a `/config` edit to the config file and reload.
+    SetConfigValue {
+        key: &'static str,
+        value: String,
+    },
+
+    /// Open the transcript overlay (same as Ctrl+T).
+    OpenTranscript,
+
+    /// Open the log viewer overlay (same as Ctrl+O).
+    OpenLogViewer,
+
+    /// Open the key binding cheat sheet (same as Ctrl+/).
+    OpenKeymap,
+
+    /// A `/login device` attempt received its one-time code.
+    DeviceCodeIssued {
+        verification_url: String,
+        user_code: String,
+    },
+
+    /// A `/login device` attempt finished.
+    DeviceCodeLoginFinished(Result<(), String>),
+
+    /// Make the named auth profile the active credentials.
+    SwitchAuthProfile(String),
+
+    /// Refresh the ChatGPT access token now (the `/login status` "Refresh now" action).
+    RefreshAuthToken,
+
+    /// A manual access token refresh finished.
+    AuthTokenRefreshFinished(Result<(), String>),
+
+    /// Run a slash command as if it had been submitted from the composer without arguments.
+    DispatchSlashCommand(SlashCommand),
+
     /// Open the app link view in the bottom pane.
     OpenAppLink {
         app_id: String,
@@ -187,6 +378,9 @@ pub(crate) enum AppEvent {
     /// Update the current personality in the running app and widget.
     UpdatePersonality(Personality),
 
+    /// Apply temperature / top_p overrides chosen in the `/params` popup.
+    UpdateSamplingParams(SamplingParams),
+
     /// Persist the selected model and reasoning effort to the appropriate config.
     PersistModelSelection {
         model: String,
@@ -404,6 +598,43 @@ pub(crate) enum AppEvent {
         error: String,
     },
 
+    /// Transcription of a pasted audio file finished.
+    AudioAttachmentTranscribed {
+        path: PathBuf,
+        result: Result<String, String>,
+    },
+
+    /// The user chose how to insert a paste over the attach-as-file threshold.
+    ResolveOversizedPaste {
+        text: Arc<String>,
+        as_file: bool,
+    },
+
+    /// Writing an oversized paste to a file finished.
+    OversizedPasteSaved(Result<SavedPaste, String>),
+
+    /// The user picked `replacement` for the misspelled `word` at `range` in the composer.
+    ReplaceMisspelling {
+        range: Range<usize>,
+        word: String,
+        replacement: String,
+    },
+
+    /// Accept a word in the composer spellcheck and save it to the personal dictionary.
+    AddToDictionary(String),
+
+    /// `/scrollback` finished reading the terminal output from before launch.
+    ScrollbackCaptured(Result<String, String>),
+
+    /// `/ci` finished reading and condensing a CI log.
+    CiLogLoaded(Result<CiLogDigest, String>),
+
+    /// The `/split` diff pane finished recomputing the workspace diff.
+    DiffPaneRefreshed(Result<(bool, String), String>),
+
+    /// `/cd` resolved a new session working directory.
+    ChangeCwd(PathBuf),
+
     /// Open the branch picker option from the review popup.
     OpenReviewBranchPicker(PathBuf),
 
@@ -413,6 +644,12 @@ pub(crate) enum AppEvent {
     /// Open the custom prompt option from the review popup.
     OpenReviewCustomPrompt,
 
+    /// Open the editor for the steps of the proposed plan.
+    EditPlanSteps,
+
+    /// The user saved edited plan steps, one per line.
+    PlanStepsEdited(String),
+
     /// Submit a user message with an explicit collaboration mask.
     SubmitUserMessageWithMode {
         text: String,
@@ -422,6 +659,12 @@ pub(crate) enum AppEvent {
     /// Open the approval popup.
     FullScreenApprovalRequest(ApprovalRequest),
 
+    /// Edit the summary of a compaction review before submitting it.
+    EditCompactionSummary(ApprovalRequest),
+
+    /// Show an approval request again, e.g. after its editor was dismissed.
+    ShowApprovalRequest(ApprovalRequest),
+
     /// Open the feedback note entry overlay after the user selects a category.
     OpenFeedbackNote {
         category: FeedbackCategory,
@@ -441,6 +684,26 @@ pub(crate) enum AppEvent {
         cwd: PathBuf,
         branch: Option<String>,
     },
+
+    /// Output of one run of a status line command; `None` hides its segment.
+    StatusLineCommandUpdated {
+        name: String,
+        value: Option<String>,
+    },
+
+    /// Async update of the `<repo>@<branch>` label shown in the terminal title.
+    TerminalTitleRepoUpdated {
+        cwd: PathBuf,
+        label: Option<String>,
+    },
+
+    /// A watched `config.toml` changed on disk; reload it.
+    ConfigFileChanged,
+
+    /// Start a new session with the config reloaded from disk, after the user confirmed the
+    /// settings that need one.
+    ApplyPendingConfigReload,
+
     /// Apply a user-confirmed status-line item ordering/selection.
     StatusLineSetup {
         items: Vec<StatusLineItem>,
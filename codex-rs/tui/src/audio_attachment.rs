@@ -0,0 +1,194 @@
+//! Audio file attachments.
+//!
+//! Pasting or dropping an audio file path into the composer transcribes the file with the
+//! backend configured under `[audio_transcription]` and inserts the transcript into the draft.
+//! The model only ever sees text; the original path is kept in the wrapper so the transcript
+//! stays traceable in history and in the rollout.
+
+use std::path::Path;
+use std::path::PathBuf;
+
+use codex_core::config::types::AudioTranscriptionBackend;
+use codex_core::config::types::AudioTranscriptionConfig;
+use codex_core::default_client::get_codex_user_agent;
+use tokio::process::Command;
+
+use crate::clipboard_paste::normalize_pasted_path;
+
+/// File extensions accepted as audio attachments.
+const AUDIO_EXTENSIONS: &[&str] = &[
+    "flac", "m4a", "mp3", "mp4", "mpeg", "mpga", "oga", "ogg", "opus", "wav", "webm",
+];
+
+/// Whether `path` has an extension we treat as audio.
+pub(crate) fn is_audio_path(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| {
+            AUDIO_EXTENSIONS
+                .iter()
+                .any(|candidate| ext.eq_ignore_ascii_case(candidate))
+        })
+}
+
+/// Returns the audio file referenced by pasted text, if the paste is a single path to an
+/// existing audio file.
+pub(crate) fn pasted_audio_path(pasted: &str) -> Option<PathBuf> {
+    let path = normalize_pasted_path(pasted)?;
+    (is_audio_path(&path) && path.is_file()).then_some(path)
+}
+
+/// Wrap a transcript so the model can tell it apart from typed text and knows its source.
+pub(crate) fn format_transcript(path: &Path, transcript: &str) -> String {
+    format!(
+        "<audio_transcript path=\"{}\">\n{}\n</audio_transcript>",
+        path.display(),
+        transcript.trim()
+    )
+}
+
+/// Transcribe `path` with the configured backend.
+pub(crate) async fn transcribe_file(
+    config: &AudioTranscriptionConfig,
+    path: &Path,
+) -> Result<String, String> {
+    let size = tokio::fs::metadata(path)
+        .await
+        .map_err(|err| format!("failed to read {}: {err}", path.display()))?
+        .len();
+    if size > config.max_file_bytes as u64 {
+        return Err(format!(
+            "{} is {size} bytes; audio_transcription.max_file_bytes is {}",
+            path.display(),
+            config.max_file_bytes
+        ));
+    }
+
+    let transcript = match config.backend {
+        AudioTranscriptionBackend::Api => transcribe_with_api(config, path).await?,
+        AudioTranscriptionBackend::Command => transcribe_with_command(config, path).await?,
+    };
+    let transcript = transcript.trim().to_string();
+    if transcript.is_empty() {
+        return Err("empty transcription result".to_string());
+    }
+    Ok(transcript)
+}
+
+async fn transcribe_with_api(
+    config: &AudioTranscriptionConfig,
+    path: &Path,
+) -> Result<String, String> {
+    let api_key = std::env::var(&config.api_key_env)
+        .map_err(|_| format!("{} is not set", config.api_key_env))?;
+    let bytes = tokio::fs::read(path)
+        .await
+        .map_err(|err| format!("failed to read {}: {err}", path.display()))?;
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "audio".to_string());
+    let form = reqwest::multipart::Form::new()
+        .text("model", config.model.clone())
+        .part(
+            "file",
+            reqwest::multipart::Part::bytes(bytes).file_name(file_name),
+        );
+    let endpoint = format!(
+        "{}/audio/transcriptions",
+        config.base_url.trim_end_matches('/')
+    );
+
+    let response = reqwest::Client::new()
+        .post(&endpoint)
+        .bearer_auth(api_key)
+        .header("User-Agent", get_codex_user_agent())
+        .multipart(form)
+        .send()
+        .await
+        .map_err(|err| format!("transcription request failed: {err}"))?;
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "<failed to read body>".to_string());
+        return Err(format!("transcription failed: {status} {body}"));
+    }
+
+    let value: serde_json::Value = response
+        .json()
+        .await
+        .map_err(|err| format!("failed to parse transcription response: {err}"))?;
+    Ok(value
+        .get("text")
+        .and_then(serde_json::Value::as_str)
+        .unwrap_or_default()
+        .to_string())
+}
+
+async fn transcribe_with_command(
+    config: &AudioTranscriptionConfig,
+    path: &Path,
+) -> Result<String, String> {
+    let Some((program, args)) = config.command.split_first() else {
+        return Err(
+            "audio_transcription.command must be set when backend = \"command\"".to_string(),
+        );
+    };
+    let output = Command::new(program)
+        .args(args)
+        .arg(path)
+        .kill_on_drop(true)
+        .output()
+        .await
+        .map_err(|err| format!("failed to run `{program}`: {err}"))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!(
+            "`{program}` exited with {}: {}",
+            output.status,
+            stderr.trim()
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn audio_paths_are_detected_by_extension() {
+        assert!(is_audio_path(Path::new("/tmp/standup.MP3")));
+        assert!(is_audio_path(Path::new("notes.m4a")));
+        assert!(!is_audio_path(Path::new("diagram.png")));
+        assert!(!is_audio_path(Path::new("README")));
+    }
+
+    #[test]
+    fn transcript_is_wrapped_with_source_path() {
+        assert_eq!(
+            format_transcript(Path::new("/tmp/standup.mp3"), "  hello team \n"),
+            "<audio_transcript path=\"/tmp/standup.mp3\">\nhello team\n</audio_transcript>"
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn command_backend_reads_transcript_from_stdout() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let audio = dir.path().join("clip.wav");
+        std::fs::write(&audio, b"RIFF").expect("write audio");
+        let config = AudioTranscriptionConfig {
+            backend: AudioTranscriptionBackend::Command,
+            command: vec!["echo".to_string(), "transcribed".to_string()],
+            ..Default::default()
+        };
+
+        let transcript = transcribe_file(&config, &audio).await.expect("transcript");
+
+        assert_eq!(transcript, format!("transcribed {}", audio.display()));
+    }
+}
@@ -20,6 +20,7 @@ use codex_core::features::Features;
 use codex_protocol::ThreadId;
 use codex_protocol::mcp::RequestId;
 use codex_protocol::models::PermissionProfile;
+use codex_protocol::protocol::CompactionReviewDecision;
 use codex_protocol::protocol::ElicitationAction;
 use codex_protocol::protocol::FileChange;
 use codex_protocol::protocol::NetworkApprovalContext;
@@ -38,6 +39,12 @@ use ratatui::text::Span;
 use ratatui::widgets::Paragraph;
 use ratatui::widgets::Wrap;
 
+/// `view_id` of the approval modal, used to tell when the session is waiting on the user.
+pub(crate) const APPROVAL_OVERLAY_VIEW_ID: &str = "approval-overlay";
+
+/// Summary lines shown in the compaction review before the rest is folded away.
+const COMPACTION_SUMMARY_PREVIEW_LINES: usize = 12;
+
 /// Request coming from the agent that needs user approval.
 #[derive(Clone, Debug)]
 pub(crate) enum ApprovalRequest {
@@ -66,6 +73,23 @@ pub(crate) enum ApprovalRequest {
         request_id: RequestId,
         message: String,
     },
+    /// The model is waiting for its plan to be approved before it may run mutating tools
+    /// (`require_plan_approval`).
+    Plan {
+        thread_id: ThreadId,
+        thread_label: Option<String>,
+        id: String,
+        explanation: Option<String>,
+        steps: Vec<String>,
+    },
+    /// Auto-compaction is waiting for the user to accept, edit, or reject its summary
+    /// (`confirm_auto_compaction`).
+    Compaction {
+        thread_id: ThreadId,
+        thread_label: Option<String>,
+        id: String,
+        summary: String,
+    },
 }
 
 impl ApprovalRequest {
@@ -73,7 +97,35 @@ impl ApprovalRequest {
         match self {
             ApprovalRequest::Exec { thread_id, .. }
             | ApprovalRequest::ApplyPatch { thread_id, .. }
-            | ApprovalRequest::McpElicitation { thread_id, .. } => *thread_id,
+            | ApprovalRequest::McpElicitation { thread_id, .. }
+            | ApprovalRequest::Plan { thread_id, .. }
+            | ApprovalRequest::Compaction { thread_id, .. } => *thread_id,
+        }
+    }
+
+    /// What is being asked, in one line.
+    fn summary(&self) -> String {
+        match self {
+            ApprovalRequest::Exec { command, .. } => {
+                format!("run `{}`", strip_bash_lc_and_escape(command))
+            }
+            ApprovalRequest::ApplyPatch { changes, .. } => {
+                let mut paths = changes
+                    .keys()
+                    .map(|path| path.display().to_string())
+                    .collect::<Vec<_>>();
+                paths.sort();
+                format!("edit {}", paths.join(", "))
+            }
+            ApprovalRequest::McpElicitation {
+                server_name,
+                message,
+                ..
+            } => format!("answer {server_name}: {message}"),
+            ApprovalRequest::Plan { steps, .. } => {
+                format!("approve a plan with {} steps", steps.len())
+            }
+            ApprovalRequest::Compaction { .. } => "review a compaction summary".to_string(),
         }
     }
 
@@ -81,7 +133,9 @@ impl ApprovalRequest {
         match self {
             ApprovalRequest::Exec { thread_label, .. }
             | ApprovalRequest::ApplyPatch { thread_label, .. }
-            | ApprovalRequest::McpElicitation { thread_label, .. } => thread_label.as_deref(),
+            | ApprovalRequest::McpElicitation { thread_label, .. }
+            | ApprovalRequest::Plan { thread_label, .. }
+            | ApprovalRequest::Compaction { thread_label, .. } => thread_label.as_deref(),
         }
     }
 }
@@ -162,6 +216,14 @@ impl ApprovalOverlay {
                 elicitation_options(),
                 format!("{server_name} needs your approval."),
             ),
+            ApprovalRequest::Plan { .. } => (
+                plan_options(),
+                "Would you like Codex to carry out this plan?".to_string(),
+            ),
+            ApprovalRequest::Compaction { .. } => (
+                compaction_options(),
+                "Replace older turns with this summary?".to_string(),
+            ),
         };
 
         let header = Box::new(ColumnRenderable::with([
@@ -207,6 +269,9 @@ impl ApprovalOverlay {
                 (ApprovalRequest::ApplyPatch { id, .. }, ApprovalDecision::Review(decision)) => {
                     self.handle_patch_decision(id, decision.clone());
                 }
+                (ApprovalRequest::Plan { id, .. }, ApprovalDecision::Review(decision)) => {
+                    self.handle_plan_decision(id, decision.clone());
+                }
                 (
                     ApprovalRequest::McpElicitation {
                         server_name,
@@ -217,6 +282,16 @@ impl ApprovalOverlay {
                 ) => {
                     self.handle_elicitation_decision(server_name, request_id, *decision);
                 }
+                (
+                    ApprovalRequest::Compaction { id, .. },
+                    ApprovalDecision::Compaction(decision),
+                ) => {
+                    self.handle_compaction_decision(id, decision.clone());
+                }
+                (ApprovalRequest::Compaction { .. }, ApprovalDecision::EditCompaction) => {
+                    self.app_event_tx
+                        .send(AppEvent::EditCompactionSummary(request.clone()));
+                }
                 _ => {}
             }
         }
@@ -261,6 +336,41 @@ impl ApprovalOverlay {
         });
     }
 
+    fn handle_plan_decision(&self, id: &str, decision: ReviewDecision) {
+        let Some(request) = self.current_request.as_ref() else {
+            return;
+        };
+        if request.thread_label().is_none() {
+            let cell = history_cell::new_plan_approval_decision_cell(decision.clone());
+            self.app_event_tx.send(AppEvent::InsertHistoryCell(cell));
+        }
+        // Plan decisions travel the same way as patch decisions: keyed by the call id.
+        self.app_event_tx.send(AppEvent::SubmitThreadOp {
+            thread_id: request.thread_id(),
+            op: Op::PatchApproval {
+                id: id.to_string(),
+                decision,
+            },
+        });
+    }
+
+    fn handle_compaction_decision(&self, id: &str, decision: CompactionReviewDecision) {
+        let Some(thread_id) = self
+            .current_request
+            .as_ref()
+            .map(ApprovalRequest::thread_id)
+        else {
+            return;
+        };
+        self.app_event_tx.send(AppEvent::SubmitThreadOp {
+            thread_id,
+            op: Op::CompactionReview {
+                id: id.to_string(),
+                decision,
+            },
+        });
+    }
+
     fn handle_elicitation_decision(
         &self,
         server_name: &str,
@@ -342,6 +452,18 @@ impl ApprovalOverlay {
 }
 
 impl BottomPaneView for ApprovalOverlay {
+    fn pending_approval_summaries(&self) -> Vec<String> {
+        if self.done {
+            return Vec::new();
+        }
+        self.current_request
+            .iter()
+            .filter(|_| !self.current_complete)
+            .chain(&self.queue)
+            .map(ApprovalRequest::summary)
+            .collect()
+    }
+
     fn handle_key_event(&mut self, key_event: KeyEvent) {
         if self.try_handle_shortcut(&key_event) {
             return;
@@ -366,6 +488,12 @@ impl BottomPaneView for ApprovalOverlay {
                 ApprovalRequest::ApplyPatch { id, .. } => {
                     self.handle_patch_decision(id, ReviewDecision::Abort);
                 }
+                ApprovalRequest::Plan { id, .. } => {
+                    self.handle_plan_decision(id, ReviewDecision::Abort);
+                }
+                ApprovalRequest::Compaction { id, .. } => {
+                    self.handle_compaction_decision(id, CompactionReviewDecision::Reject);
+                }
                 ApprovalRequest::McpElicitation {
                     server_name,
                     request_id,
@@ -388,6 +516,10 @@ impl BottomPaneView for ApprovalOverlay {
         self.done
     }
 
+    fn view_id(&self) -> Option<&'static str> {
+        Some(APPROVAL_OVERLAY_VIEW_ID)
+    }
+
     fn try_consume_approval_request(
         &mut self,
         request: ApprovalRequest,
@@ -522,13 +654,87 @@ fn build_header(request: &ApprovalRequest) -> Box<dyn Renderable> {
             let header = Paragraph::new(lines).wrap(Wrap { trim: false });
             Box::new(header)
         }
+        ApprovalRequest::Plan {
+            thread_label,
+            explanation,
+            steps,
+            ..
+        } => {
+            let mut lines = Vec::new();
+            if let Some(thread_label) = thread_label {
+                lines.push(Line::from(vec![
+                    "Thread: ".into(),
+                    thread_label.clone().bold(),
+                ]));
+                lines.push(Line::from(""));
+            }
+            if let Some(explanation) = explanation
+                && !explanation.is_empty()
+            {
+                lines.push(Line::from(explanation.clone().italic()));
+                lines.push(Line::from(""));
+            }
+            lines.extend(plan_step_lines(steps));
+            Box::new(Paragraph::new(lines).wrap(Wrap { trim: false }))
+        }
+        ApprovalRequest::Compaction {
+            thread_label,
+            summary,
+            ..
+        } => {
+            let mut lines = Vec::new();
+            if let Some(thread_label) = thread_label {
+                lines.push(Line::from(vec![
+                    "Thread: ".into(),
+                    thread_label.clone().bold(),
+                ]));
+                lines.push(Line::from(""));
+            }
+            lines.extend(compaction_summary_preview_lines(summary));
+            Box::new(Paragraph::new(lines).wrap(Wrap { trim: false }))
+        }
+    }
+}
+
+/// The start of a compaction summary; the full text is one `ctrl+a` away.
+fn compaction_summary_preview_lines(summary: &str) -> Vec<Line<'static>> {
+    let mut lines: Vec<Line<'static>> = summary
+        .lines()
+        .take(COMPACTION_SUMMARY_PREVIEW_LINES)
+        .map(|line| Line::from(line.to_string()))
+        .collect();
+    let hidden = summary
+        .lines()
+        .count()
+        .saturating_sub(COMPACTION_SUMMARY_PREVIEW_LINES);
+    if hidden > 0 {
+        lines.push(Line::from(vec![
+            format!("… {hidden} more lines (").dim(),
+            key_hint::ctrl(KeyCode::Char('a')).into(),
+            " to view all)".dim(),
+        ]));
     }
+    lines
+}
+
+/// Numbered steps of a plan awaiting approval.
+pub(crate) fn plan_step_lines(steps: &[String]) -> Vec<Line<'static>> {
+    steps
+        .iter()
+        .enumerate()
+        .map(|(index, step)| {
+            Line::from(vec![format!("{}. ", index + 1).dim(), step.clone().into()])
+        })
+        .collect()
 }
 
 #[derive(Clone)]
 enum ApprovalDecision {
     Review(ReviewDecision),
     McpElicitation(ElicitationAction),
+    Compaction(CompactionReviewDecision),
+    /// Open an editor on the compaction summary; the edited text is submitted from there.
+    EditCompaction,
 }
 
 #[derive(Clone)]
@@ -698,6 +904,58 @@ fn patch_options() -> Vec<ApprovalOption> {
     ]
 }
 
+fn plan_options() -> Vec<ApprovalOption> {
+    vec![
+        ApprovalOption {
+            label: "Yes, carry out this plan".to_string(),
+            decision: ApprovalDecision::Review(ReviewDecision::Approved),
+            display_shortcut: None,
+            additional_shortcuts: vec![key_hint::plain(KeyCode::Char('y'))],
+        },
+        ApprovalOption {
+            label: "Yes, and don't ask for a plan again this session".to_string(),
+            decision: ApprovalDecision::Review(ReviewDecision::ApprovedForSession),
+            display_shortcut: None,
+            additional_shortcuts: vec![key_hint::plain(KeyCode::Char('a'))],
+        },
+        ApprovalOption {
+            label: "No, ask for a revised plan".to_string(),
+            decision: ApprovalDecision::Review(ReviewDecision::Denied),
+            display_shortcut: None,
+            additional_shortcuts: vec![key_hint::plain(KeyCode::Char('d'))],
+        },
+        ApprovalOption {
+            label: "No, and tell Codex what to do differently".to_string(),
+            decision: ApprovalDecision::Review(ReviewDecision::Abort),
+            display_shortcut: Some(key_hint::plain(KeyCode::Esc)),
+            additional_shortcuts: vec![key_hint::plain(KeyCode::Char('n'))],
+        },
+    ]
+}
+
+fn compaction_options() -> Vec<ApprovalOption> {
+    vec![
+        ApprovalOption {
+            label: "Yes, compact with this summary".to_string(),
+            decision: ApprovalDecision::Compaction(CompactionReviewDecision::Accept),
+            display_shortcut: None,
+            additional_shortcuts: vec![key_hint::plain(KeyCode::Char('y'))],
+        },
+        ApprovalOption {
+            label: "Edit the summary first".to_string(),
+            decision: ApprovalDecision::EditCompaction,
+            display_shortcut: None,
+            additional_shortcuts: vec![key_hint::plain(KeyCode::Char('e'))],
+        },
+        ApprovalOption {
+            label: "No, keep the full history and stop".to_string(),
+            decision: ApprovalDecision::Compaction(CompactionReviewDecision::Reject),
+            display_shortcut: Some(key_hint::plain(KeyCode::Esc)),
+            additional_shortcuts: vec![key_hint::plain(KeyCode::Char('n'))],
+        },
+    ]
+}
+
 fn elicitation_options() -> Vec<ApprovalOption> {
     vec![
         ApprovalOption {
@@ -1258,4 +1516,123 @@ mod tests {
         }
         assert_eq!(decision, Some(ReviewDecision::Approved));
     }
+
+    #[test]
+    fn plan_approval_shows_steps_and_answers_with_patch_approval() {
+        let (tx_raw, mut rx) = unbounded_channel::<AppEvent>();
+        let tx = AppEventSender::new(tx_raw);
+        let request = ApprovalRequest::Plan {
+            thread_id: ThreadId::new(),
+            thread_label: None,
+            id: "plan-call".to_string(),
+            explanation: Some("Retry failed uploads.".to_string()),
+            steps: vec![
+                "Add a retry policy".to_string(),
+                "Wrap the upload call".to_string(),
+            ],
+        };
+        let mut view = ApprovalOverlay::new(request, tx, Features::with_defaults());
+
+        let rendered = render_overlay_lines(&view, 80);
+        assert!(
+            rendered.contains("Would you like Codex to carry out this plan?")
+                && rendered.contains("1. Add a retry policy")
+                && rendered.contains("2. Wrap the upload call"),
+            "expected the plan in the header, got {rendered}"
+        );
+
+        view.handle_key_event(KeyEvent::new(KeyCode::Char('d'), KeyModifiers::NONE));
+
+        let mut decision = None;
+        let mut recorded = false;
+        while let Ok(ev) = rx.try_recv() {
+            match ev {
+                AppEvent::InsertHistoryCell(_) => recorded = true,
+                AppEvent::SubmitThreadOp {
+                    op: Op::PatchApproval { id, decision: d },
+                    ..
+                } => {
+                    assert_eq!(id, "plan-call");
+                    decision = Some(d);
+                }
+                _ => {}
+            }
+        }
+        assert!(recorded, "expected the decision in the transcript");
+        assert_eq!(decision, Some(ReviewDecision::Denied));
+    }
+
+    #[test]
+    fn compaction_review_prompt_snapshot() {
+        let (tx, _rx) = unbounded_channel::<AppEvent>();
+        let tx = AppEventSender::new(tx);
+        let summary = (1..=14)
+            .map(|n| format!("fact {n}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let request = ApprovalRequest::Compaction {
+            thread_id: ThreadId::new(),
+            thread_label: None,
+            id: "turn-1".to_string(),
+            summary,
+        };
+        let view = ApprovalOverlay::new(request, tx, Features::with_defaults());
+
+        assert_snapshot!(
+            "approval_overlay_compaction_review_prompt",
+            render_overlay_lines(&view, 80)
+        );
+    }
+
+    #[test]
+    fn compaction_review_previews_summary_and_hands_edits_to_the_editor() {
+        let (tx_raw, mut rx) = unbounded_channel::<AppEvent>();
+        let tx = AppEventSender::new(tx_raw);
+        let summary = (1..=20)
+            .map(|n| format!("fact {n}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let request = ApprovalRequest::Compaction {
+            thread_id: ThreadId::new(),
+            thread_label: None,
+            id: "turn-1".to_string(),
+            summary,
+        };
+        let mut view = ApprovalOverlay::new(request, tx.clone(), Features::with_defaults());
+
+        let rendered = render_overlay_lines(&view, 80);
+        assert!(
+            rendered.contains("Replace older turns with this summary?")
+                && rendered.contains("fact 12")
+                && !rendered.contains("fact 13")
+                && rendered.contains("8 more lines"),
+            "expected a folded summary preview, got {rendered}"
+        );
+
+        view.handle_key_event(KeyEvent::new(KeyCode::Char('e'), KeyModifiers::NONE));
+        assert!(view.is_complete());
+        assert!(matches!(
+            rx.try_recv(),
+            Ok(AppEvent::EditCompactionSummary(ApprovalRequest::Compaction { id, .. })) if id == "turn-1"
+        ));
+
+        let request = ApprovalRequest::Compaction {
+            thread_id: ThreadId::new(),
+            thread_label: None,
+            id: "turn-2".to_string(),
+            summary: "Short".to_string(),
+        };
+        let mut view = ApprovalOverlay::new(request, tx, Features::with_defaults());
+        view.on_ctrl_c();
+        assert!(matches!(
+            rx.try_recv(),
+            Ok(AppEvent::SubmitThreadOp {
+                op: Op::CompactionReview {
+                    decision: CompactionReviewDecision::Reject,
+                    ..
+                },
+                ..
+            })
+        ));
+    }
 }
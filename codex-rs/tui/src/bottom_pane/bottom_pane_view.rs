@@ -21,6 +21,11 @@ pub(crate) trait BottomPaneView: Renderable {
         None
     }
 
+    /// One line for each approval this view is still waiting on, for the crash-recovery file.
+    fn pending_approval_summaries(&self) -> Vec<String> {
+        Vec::new()
+    }
+
     /// Handle Ctrl-C while this view is active.
     fn on_ctrl_c(&mut self) -> CancellationEvent {
         CancellationEvent::NotHandled
@@ -129,8 +129,6 @@ use crate::bottom_pane::footer::mode_indicator_line;
 use crate::key_hint;
 use crate::key_hint::KeyBinding;
 use crate::key_hint::has_ctrl_or_alt;
-use crate::line_truncation::truncate_line_with_ellipsis_if_overflow;
-use crate::ui_consts::FOOTER_INDENT_COLS;
 use crossterm::event::KeyCode;
 use crossterm::event::KeyEvent;
 use crossterm::event::KeyEventKind;
@@ -155,11 +153,16 @@ use super::command_popup::CommandPopup;
 use super::command_popup::CommandPopupFlags;
 use super::file_search_popup::FileSearchPopup;
 use super::footer::CollaborationModeIndicator;
+use super::footer::ContextWarningLevel;
+use super::footer::FooterHints;
 use super::footer::FooterMode;
 use super::footer::FooterProps;
+use super::footer::FooterTip;
+use super::footer::PinnedFilesSummary;
+use super::footer::PlanStepSummary;
 use super::footer::SummaryLeft;
 use super::footer::can_show_left_with_context;
-use super::footer::context_window_line;
+use super::footer::context_line;
 use super::footer::esc_hint_mode;
 use super::footer::footer_height;
 use super::footer::footer_hint_items_width;
@@ -170,8 +173,10 @@ use super::footer::render_context_right;
 use super::footer::render_footer_from_props;
 use super::footer::render_footer_hint_items;
 use super::footer::render_footer_line;
+use super::footer::render_status_line_rows;
 use super::footer::reset_mode_after_activity;
 use super::footer::single_line_footer_layout;
+use super::footer::status_line_row_width;
 use super::footer::toggle_shortcut_mode;
 use super::paste_burst::CharDecision;
 use super::paste_burst::PasteBurst;
@@ -179,6 +184,7 @@ use super::skill_popup::MentionItem;
 use super::skill_popup::SkillPopup;
 use super::slash_commands;
 use super::slash_commands::BuiltinCommandFlags;
+use super::status_line::StatusLine;
 use crate::bottom_pane::paste_burst::FlushResult;
 use crate::bottom_pane::prompt_args::expand_custom_prompt;
 use crate::bottom_pane::prompt_args::expand_if_numeric_with_positional_args;
@@ -209,6 +215,7 @@ use crate::bottom_pane::textarea::TextAreaState;
 use crate::clipboard_paste::normalize_pasted_path;
 use crate::clipboard_paste::pasted_image_format;
 use crate::history_cell;
+use crate::paste_file::SavedPaste;
 use crate::tui::FrameRequester;
 use crate::ui_consts::LIVE_PREFIX_COLS;
 use codex_chatgpt::connectors;
@@ -390,6 +397,10 @@ pub(crate) struct ChatComposer {
     #[cfg(not(target_os = "linux"))]
     next_element_id: u64,
     context_window_used_tokens: Option<i64>,
+    context_warning: Option<ContextWarningLevel>,
+    pinned_files: Option<PinnedFilesSummary>,
+    read_only: bool,
+    plan_step: Option<PlanStepSummary>,
     skills: Option<Vec<SkillMetadata>>,
     connectors_snapshot: Option<ConnectorsSnapshot>,
     dismissed_mention_popup_token: Option<String>,
@@ -404,8 +415,11 @@ pub(crate) struct ChatComposer {
     realtime_conversation_enabled: bool,
     audio_device_selection_enabled: bool,
     windows_degraded_sandbox_active: bool,
-    status_line_value: Option<Line<'static>>,
+    status_line_value: Option<StatusLine>,
     status_line_enabled: bool,
+    footer_hints: FooterHints,
+    /// Advances each time a task starts so footer tips rotate between turns.
+    footer_tip_turn: usize,
 }
 
 #[derive(Clone, Debug)]
@@ -431,7 +445,7 @@ enum ActivePopup {
 const FOOTER_SPACING_HEIGHT: u16 = 0;
 
 impl ChatComposer {
-    fn builtin_command_flags(&self) -> BuiltinCommandFlags {
+    pub(crate) fn builtin_command_flags(&self) -> BuiltinCommandFlags {
         BuiltinCommandFlags {
             collaboration_modes_enabled: self.collaboration_modes_enabled,
             connectors_enabled: self.connectors_enabled,
@@ -509,6 +523,10 @@ impl ChatComposer {
             #[cfg(not(target_os = "linux"))]
             next_element_id: 0,
             context_window_used_tokens: None,
+            context_warning: None,
+            pinned_files: None,
+            read_only: false,
+            plan_step: None,
             skills: None,
             connectors_snapshot: None,
             dismissed_mention_popup_token: None,
@@ -525,9 +543,12 @@ impl ChatComposer {
             windows_degraded_sandbox_active: false,
             status_line_value: None,
             status_line_enabled: false,
+            footer_hints: FooterHints::default(),
+            footer_tip_turn: 0,
         };
         // Apply configuration via the setter to keep side-effects centralized.
         this.set_disable_paste_burst(disable_paste_burst);
+        this.textarea.set_spellcheck(true);
         this
     }
 
@@ -1153,6 +1174,39 @@ impl ChatComposer {
             .push(AttachedImage { placeholder, path });
     }
 
+    /// Insert the placeholder for a paste that was saved to a file. Like a large paste, it is
+    /// expanded on submit, but only to a note naming the file.
+    pub(crate) fn attach_pasted_file(&mut self, saved: &SavedPaste) {
+        let placeholder = saved.placeholder();
+        self.textarea.insert_element(&placeholder);
+        self.pending_pastes.push((placeholder, saved.reference()));
+        self.sync_popups();
+    }
+
+    /// The misspelled word under or before the cursor, with its byte range in the draft.
+    pub(crate) fn misspelling_near_cursor(&self) -> Option<(Range<usize>, String)> {
+        let range = self.textarea.misspelling_near_cursor()?;
+        let word = self.textarea.text()[range.clone()].to_string();
+        Some((range, word))
+    }
+
+    /// Replace a misspelling picked from the suggestions popup. Returns `false`, leaving the
+    /// draft alone, when the text at `range` is no longer `word`.
+    pub(crate) fn replace_misspelling(
+        &mut self,
+        range: Range<usize>,
+        word: &str,
+        replacement: &str,
+    ) -> bool {
+        if self.textarea.text().get(range.clone()) != Some(word) {
+            return false;
+        }
+        self.textarea.replace_range(range.clone(), replacement);
+        self.textarea.set_cursor(range.start + replacement.len());
+        self.sync_popups();
+        true
+    }
+
     #[cfg(test)]
     pub fn take_recent_submission_images(&mut self) -> Vec<PathBuf> {
         let images = std::mem::take(&mut self.attached_images);
@@ -3161,8 +3215,17 @@ impl ChatComposer {
             is_wsl,
             context_window_percent: self.context_window_percent,
             context_window_used_tokens: self.context_window_used_tokens,
+            context_warning: self.context_warning,
+            pinned_files: self.pinned_files,
+            read_only: self.read_only,
+            plan_step: self.plan_step.clone(),
             status_line_value: self.status_line_value.clone(),
             status_line_enabled: self.status_line_enabled,
+            hints: self.footer_hints,
+            tip: self
+                .footer_hints
+                .tips
+                .then(|| FooterTip::for_state(self.is_task_running, self.footer_tip_turn)),
         }
     }
 
@@ -3180,6 +3243,7 @@ impl ChatComposer {
         };
 
         match self.footer_mode {
+            FooterMode::EscHint if !self.footer_hints.backtrack => base_mode,
             FooterMode::EscHint => FooterMode::EscHint,
             FooterMode::ShortcutOverlay => FooterMode::ShortcutOverlay,
             FooterMode::QuitShortcutReminder if self.quit_shortcut_hint_visible() => {
@@ -3629,6 +3693,9 @@ impl ChatComposer {
     }
 
     pub fn set_task_running(&mut self, running: bool) {
+        if running && !self.is_task_running {
+            self.footer_tip_turn = self.footer_tip_turn.wrapping_add(1);
+        }
         self.is_task_running = running;
     }
 
@@ -3641,6 +3708,22 @@ impl ChatComposer {
         self.context_window_used_tokens = used_tokens;
     }
 
+    pub(crate) fn set_context_warning(&mut self, warning: Option<ContextWarningLevel>) {
+        self.context_warning = warning;
+    }
+
+    pub(crate) fn set_pinned_files(&mut self, pinned_files: Option<PinnedFilesSummary>) {
+        self.pinned_files = pinned_files;
+    }
+
+    pub(crate) fn set_read_only(&mut self, read_only: bool) {
+        self.read_only = read_only;
+    }
+
+    pub(crate) fn set_plan_step(&mut self, plan_step: Option<PlanStepSummary>) {
+        self.plan_step = plan_step;
+    }
+
     pub(crate) fn set_esc_backtrack_hint(&mut self, show: bool) {
         self.esc_backtrack_hint = show;
         if show {
@@ -3676,7 +3759,7 @@ impl ChatComposer {
         }
     }
 
-    pub(crate) fn set_status_line(&mut self, status_line: Option<Line<'static>>) -> bool {
+    pub(crate) fn set_status_line(&mut self, status_line: Option<StatusLine>) -> bool {
         if self.status_line_value == status_line {
             return false;
         }
@@ -3691,6 +3774,10 @@ impl ChatComposer {
         self.status_line_enabled = enabled;
         true
     }
+
+    pub(crate) fn set_footer_hints(&mut self, hints: FooterHints) {
+        self.footer_hints = hints;
+    }
 }
 
 #[cfg(not(target_os = "linux"))]
@@ -4092,17 +4179,22 @@ impl ChatComposer {
             }
             ActivePopup::None => {
                 let footer_props = self.footer_props();
-                let show_cycle_hint =
-                    !footer_props.is_task_running && self.collaboration_mode_indicator.is_some();
+                let show_cycle_hint = !footer_props.is_task_running
+                    && footer_props.hints.mode_cycle
+                    && self.collaboration_mode_indicator.is_some();
                 let show_shortcuts_hint = match footer_props.mode {
-                    FooterMode::ComposerEmpty => !self.is_in_paste_burst(),
+                    FooterMode::ComposerEmpty => {
+                        footer_props.hints.summary() && !self.is_in_paste_burst()
+                    }
                     FooterMode::ComposerHasDraft => false,
                     FooterMode::QuitShortcutReminder
                     | FooterMode::ShortcutOverlay
                     | FooterMode::EscHint => false,
                 };
                 let show_queue_hint = match footer_props.mode {
-                    FooterMode::ComposerHasDraft => footer_props.is_task_running,
+                    FooterMode::ComposerHasDraft => {
+                        footer_props.is_task_running && footer_props.hints.queue
+                    }
                     FooterMode::QuitShortcutReminder
                     | FooterMode::ComposerEmpty
                     | FooterMode::ShortcutOverlay
@@ -4122,12 +4214,7 @@ impl ChatComposer {
                 } else {
                     popup_rect
                 };
-                let available_width =
-                    hint_rect.width.saturating_sub(FOOTER_INDENT_COLS as u16) as usize;
-                let status_line = footer_props
-                    .status_line_value
-                    .as_ref()
-                    .map(|line| line.clone().dim());
+                let status_line = footer_props.status_line_value.as_ref();
                 let status_line_candidate = footer_props.status_line_enabled
                     && match footer_props.mode {
                         FooterMode::ComposerEmpty => true,
@@ -4137,8 +4224,9 @@ impl ChatComposer {
                         | FooterMode::EscHint => false,
                     };
                 let mut truncated_status_line = if status_line_candidate {
-                    status_line.as_ref().map(|line| {
-                        truncate_line_with_ellipsis_if_overflow(line.clone(), available_width)
+                    status_line.map(|status_line| {
+                        let row = status_line.first_row();
+                        row.layout(status_line_row_width(hint_rect, row)).dim()
                     })
                 } else {
                     None
@@ -4181,18 +4269,15 @@ impl ChatComposer {
                         compact
                     }
                 } else {
-                    Some(context_window_line(
-                        footer_props.context_window_percent,
-                        footer_props.context_window_used_tokens,
-                    ))
+                    (footer_props.hints.context || footer_props.read_only)
+                        .then(|| context_line(&footer_props, hint_rect.width))
                 };
                 let right_width = right_line.as_ref().map(|l| l.width() as u16).unwrap_or(0);
                 if status_line_active
                     && let Some(max_left) = max_left_width_for_right(hint_rect, right_width)
                     && left_width > max_left
-                    && let Some(line) = status_line.as_ref().map(|line| {
-                        truncate_line_with_ellipsis_if_overflow(line.clone(), max_left as usize)
-                    })
+                    && let Some(line) = status_line
+                        .map(|status_line| status_line.first_row().layout(max_left as usize).dim())
                 {
                     left_width = line.width() as u16;
                     truncated_status_line = Some(line);
@@ -4217,6 +4302,7 @@ impl ChatComposer {
                                 show_cycle_hint,
                                 show_shortcuts_hint,
                                 show_queue_hint,
+                                footer_props.tip,
                             ))
                         }
                         FooterMode::EscHint
@@ -4282,6 +4368,9 @@ impl ChatComposer {
                     if let Some(line) = truncated_status_line {
                         render_footer_line(hint_rect, buf, line);
                     }
+                    if let Some(status_line) = status_line {
+                        render_status_line_rows(hint_rect, buf, status_line);
+                    }
                 } else {
                     render_footer_from_props(
                         hint_rect,
@@ -4295,7 +4384,16 @@ impl ChatComposer {
                 }
 
                 if show_right && let Some(line) = &right_line {
-                    render_context_right(hint_rect, buf, line);
+                    // With a multi-row status line the indicator belongs on its first row.
+                    let right_rect = if status_line_active {
+                        Rect {
+                            height: 1,
+                            ..hint_rect
+                        }
+                    } else {
+                        hint_rect
+                    };
+                    render_context_right(right_rect, buf, line);
                 }
             }
         }
@@ -4433,6 +4531,9 @@ mod tests {
 
     #[test]
     fn footer_hint_row_is_separated_from_composer() {
+        use ratatui::Terminal;
+        use ratatui::backend::TestBackend;
+
         let (tx, _rx) = unbounded_channel::<AppEvent>();
         let sender = AppEventSender::new(tx);
         let composer = ChatComposer::new(
@@ -4444,9 +4545,16 @@ mod tests {
         );
 
         let area = Rect::new(0, 0, 40, 6);
-        let mut buf = Buffer::empty(area);
-        composer.render(area, &mut buf);
+        let mut terminal = Terminal::new(TestBackend::new(area.width, area.height)).unwrap();
+        terminal
+            .draw(|f| composer.render(f.area(), f.buffer_mut()))
+            .unwrap();
+        insta::assert_snapshot!(
+            "footer_hint_row_is_separated_from_composer",
+            terminal.backend()
+        );
 
+        let buf = terminal.backend().buffer();
         let row_to_string = |y: u16| {
             let mut row = String::new();
             for x in 0..area.width {
@@ -4455,10 +4563,11 @@ mod tests {
             row
         };
 
+        // Below 60 columns the footer keeps only the compact context indicator.
         let mut hint_row: Option<(u16, String)> = None;
         for y in 0..area.height {
             let row = row_to_string(y);
-            if row.contains("? for shortcuts") {
+            if row.contains("◔100%") {
                 hint_row = Some((y, row));
                 break;
             }
@@ -7574,6 +7683,40 @@ mod tests {
         assert_eq!(vec![path], imgs);
     }
 
+    #[test]
+    fn pasted_file_placeholder_expands_to_a_file_reference() {
+        let (tx, _rx) = unbounded_channel::<AppEvent>();
+        let sender = AppEventSender::new(tx);
+        let mut composer = ChatComposer::new(
+            true,
+            sender,
+            false,
+            "Ask Codex to do anything".to_string(),
+            false,
+        );
+        let saved = SavedPaste {
+            path: PathBuf::from("/tmp/codex-pastes/paste-1.txt"),
+            bytes: 2 * 1024 * 1024,
+            lines: 10,
+        };
+
+        composer.insert_str("summarize ");
+        composer.attach_pasted_file(&saved);
+        assert_eq!(
+            composer.textarea.text(),
+            "summarize [Pasted file paste-1.txt · 2 MiB]"
+        );
+
+        let (result, _) =
+            composer.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        match result {
+            InputResult::Submitted { text, .. } => {
+                assert_eq!(text, format!("summarize {}", saved.reference()));
+            }
+            _ => panic!("expected Submitted"),
+        }
+    }
+
     #[test]
     fn large_paste_with_leading_whitespace_trims_and_shifts_elements() {
         let (tx, _rx) = unbounded_channel::<AppEvent>();
@@ -0,0 +1,155 @@
+//! Alt+P command palette.
+//!
+//! The palette lists every discrete action the app offers — the overlays bound to global keys and
+//! every slash command available in this session — in one fuzzy-searchable popup. Selecting a row
+//! only sends an [`AppEvent`]; the app handles it exactly as it would the key press or the typed
+//! command, so the palette never grows its own copy of any action.
+
+use super::SelectionAction;
+use super::SelectionItem;
+use super::SelectionViewParams;
+use super::popup_consts::standard_popup_hint_line;
+use crate::app_event::AppEvent;
+use crate::app_event_sender::AppEventSender;
+use crate::key_hint::KeyBinding;
+use crate::keymap;
+use crate::slash_command::SlashCommand;
+
+pub(crate) const COMMAND_PALETTE_VIEW_ID: &str = "command-palette";
+
+/// Build the palette from the slash commands enabled for the current session.
+pub(crate) fn command_palette_params(
+    commands: Vec<(&'static str, SlashCommand)>,
+) -> SelectionViewParams {
+    let app_action = |name: &str,
+                      description: &str,
+                      shortcut: KeyBinding,
+                      event: fn() -> AppEvent|
+     -> SelectionItem {
+        let action: SelectionAction = Box::new(move |tx: &AppEventSender| tx.send(event()));
+        SelectionItem {
+            name: name.to_string(),
+            display_shortcut: Some(shortcut),
+            description: Some(description.to_string()),
+            actions: vec![action],
+            dismiss_on_select: true,
+            search_value: Some(format!("{name} {description}")),
+            ..Default::default()
+        }
+    };
+    let mut items = vec![
+        app_action(
+            "Open transcript",
+            "view the full conversation transcript",
+            keymap::SHOW_TRANSCRIPT,
+            || AppEvent::OpenTranscript,
+        ),
+        app_action(
+            "Open log",
+            "view the TUI log with level filtering and search",
+            keymap::SHOW_LOG,
+            || AppEvent::OpenLogViewer,
+        ),
+        app_action(
+            "Show key bindings",
+            "list key bindings and slash commands",
+            keymap::SHOW_KEYMAP,
+            || AppEvent::OpenKeymap,
+        ),
+    ];
+
+    items.extend(commands.into_iter().map(|(name, cmd)| {
+        let action: SelectionAction =
+            Box::new(move |tx: &AppEventSender| tx.send(AppEvent::DispatchSlashCommand(cmd)));
+        SelectionItem {
+            name: format!("/{name}"),
+            description: Some(cmd.description().to_string()),
+            actions: vec![action],
+            dismiss_on_select: true,
+            search_value: Some(format!("{name} {}", cmd.description())),
+            ..Default::default()
+        }
+    }));
+
+    SelectionViewParams {
+        view_id: Some(COMMAND_PALETTE_VIEW_ID),
+        title: Some("Command palette".to_string()),
+        footer_hint: Some(standard_popup_hint_line()),
+        items,
+        is_searchable: true,
+        fuzzy_search: true,
+        search_placeholder: Some("Type to search actions".to_string()),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bottom_pane::bottom_pane_view::BottomPaneView;
+    use crate::bottom_pane::list_selection_view::ListSelectionView;
+    use crate::render::renderable::Renderable;
+    use crossterm::event::KeyCode;
+    use crossterm::event::KeyEvent;
+    use crossterm::event::KeyModifiers;
+    use insta::assert_snapshot;
+    use pretty_assertions::assert_eq;
+    use ratatui::buffer::Buffer;
+    use ratatui::layout::Rect;
+    use tokio::sync::mpsc::unbounded_channel;
+
+    fn render(view: &ListSelectionView, width: u16) -> String {
+        let height = view.desired_height(width);
+        let area = Rect::new(0, 0, width, height);
+        let mut buf = Buffer::empty(area);
+        view.render(area, &mut buf);
+        (0..area.height)
+            .map(|y| {
+                (0..area.width)
+                    .map(|x| buf[(x, y)].symbol().to_string())
+                    .collect::<String>()
+                    .trim_end()
+                    .to_string()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn fuzzy_query_ranks_matching_actions() {
+        let (tx_raw, _rx) = unbounded_channel::<AppEvent>();
+        let tx = AppEventSender::new(tx_raw);
+        let mut view = ListSelectionView::new(
+            command_palette_params(vec![
+                ("model", SlashCommand::Model),
+                ("new", SlashCommand::New),
+                ("diff", SlashCommand::Diff),
+            ]),
+            tx,
+        );
+        for ch in "trns".chars() {
+            view.handle_key_event(KeyEvent::new(KeyCode::Char(ch), KeyModifiers::NONE));
+        }
+
+        assert_snapshot!(render(&view, 80));
+    }
+
+    #[test]
+    fn selecting_slash_command_dispatches_it() {
+        let (tx_raw, mut rx) = unbounded_channel::<AppEvent>();
+        let tx = AppEventSender::new(tx_raw);
+        let mut view = ListSelectionView::new(
+            command_palette_params(vec![("model", SlashCommand::Model)]),
+            tx,
+        );
+        for ch in "mdl".chars() {
+            view.handle_key_event(KeyEvent::new(KeyCode::Char(ch), KeyModifiers::NONE));
+        }
+        view.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+
+        let Ok(AppEvent::DispatchSlashCommand(cmd)) = rx.try_recv() else {
+            panic!("expected DispatchSlashCommand");
+        };
+        assert_eq!(cmd, SlashCommand::Model);
+    }
+}
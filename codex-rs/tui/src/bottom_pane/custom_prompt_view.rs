@@ -24,12 +24,18 @@ use super::textarea::TextAreaState;
 /// Callback invoked when the user submits a custom prompt.
 pub(crate) type PromptSubmitted = Box<dyn Fn(String) + Send + Sync>;
 
+/// Callback invoked when the user dismisses the prompt without submitting.
+pub(crate) type PromptCancelled = Box<dyn Fn() + Send + Sync>;
+
 /// Minimal multi-line text input view to collect custom review instructions.
 pub(crate) struct CustomPromptView {
     title: String,
     placeholder: String,
     context_label: Option<String>,
     on_submit: PromptSubmitted,
+    on_cancel: Option<PromptCancelled>,
+    // Submit the text with its indentation instead of trimmed, for code.
+    keep_whitespace: bool,
 
     // UI state
     textarea: TextArea,
@@ -49,11 +55,32 @@ impl CustomPromptView {
             placeholder,
             context_label,
             on_submit,
+            on_cancel: None,
+            keep_whitespace: false,
             textarea: TextArea::new(),
             textarea_state: RefCell::new(TextAreaState::default()),
             complete: false,
         }
     }
+
+    /// Start with `text` in the input and the cursor at its end.
+    pub(crate) fn with_initial_text(mut self, text: &str) -> Self {
+        self.textarea.set_text_clearing_elements(text);
+        self.textarea.set_cursor(text.len());
+        self
+    }
+
+    /// Submit the text as typed, keeping leading indentation and blank lines.
+    pub(crate) fn keep_whitespace(mut self) -> Self {
+        self.keep_whitespace = true;
+        self
+    }
+
+    /// Run `on_cancel` when the prompt is dismissed without submitting.
+    pub(crate) fn on_cancel(mut self, on_cancel: PromptCancelled) -> Self {
+        self.on_cancel = Some(on_cancel);
+        self
+    }
 }
 
 impl BottomPaneView for CustomPromptView {
@@ -69,8 +96,13 @@ impl BottomPaneView for CustomPromptView {
                 modifiers: KeyModifiers::NONE,
                 ..
             } => {
-                let text = self.textarea.text().trim().to_string();
-                if !text.is_empty() {
+                let text = self.textarea.text();
+                if !text.trim().is_empty() {
+                    let text = if self.keep_whitespace {
+                        text.to_string()
+                    } else {
+                        text.trim().to_string()
+                    };
                     (self.on_submit)(text);
                     self.complete = true;
                 }
@@ -88,6 +120,11 @@ impl BottomPaneView for CustomPromptView {
     }
 
     fn on_ctrl_c(&mut self) -> CancellationEvent {
+        if !self.complete
+            && let Some(on_cancel) = &self.on_cancel
+        {
+            on_cancel();
+        }
         self.complete = true;
         CancellationEvent::Handled
     }
@@ -32,11 +32,20 @@
 //! In short: `single_line_footer_layout` chooses *what* best fits, and the two
 //! render helpers choose whether to draw the chosen line or the default
 //! `FooterProps` mapping.
+//!
+//! Below `NARROW_LAYOUT_COLS` the footer switches to its compact form before any of the above:
+//! the shortcuts and cycle hints are dropped, the queue hint is shortened, and the right-side
+//! context collapses to icons (`context_line`).
+use crate::bottom_pane::status_line::StatusLine;
+use crate::bottom_pane::status_line::StatusLineRow;
 use crate::key_hint;
 use crate::key_hint::KeyBinding;
 use crate::render::line_utils::prefix_lines;
 use crate::status::format_tokens_compact;
+use crate::text_formatting::truncate_text;
 use crate::ui_consts::FOOTER_INDENT_COLS;
+use crate::ui_consts::is_narrow_layout;
+use codex_core::config::types::FooterHint;
 use crossterm::event::KeyCode;
 use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
@@ -67,8 +76,146 @@ pub(crate) struct FooterProps {
     pub(crate) quit_shortcut_key: KeyBinding,
     pub(crate) context_window_percent: Option<i64>,
     pub(crate) context_window_used_tokens: Option<i64>,
-    pub(crate) status_line_value: Option<Line<'static>>,
+    pub(crate) context_warning: Option<ContextWarningLevel>,
+    pub(crate) pinned_files: Option<PinnedFilesSummary>,
+    /// The session was started with `--read-only`; shown as a badge ahead of the context line.
+    pub(crate) read_only: bool,
+    pub(crate) plan_step: Option<PlanStepSummary>,
+    pub(crate) status_line_value: Option<StatusLine>,
     pub(crate) status_line_enabled: bool,
+    pub(crate) hints: FooterHints,
+    /// Tip shown in place of the shortcuts hint, when tips are enabled.
+    pub(crate) tip: Option<FooterTip>,
+}
+
+/// The footer hints enabled by `tui.footer_hints`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) struct FooterHints {
+    pub(crate) shortcuts: bool,
+    pub(crate) queue: bool,
+    pub(crate) mode_cycle: bool,
+    pub(crate) backtrack: bool,
+    pub(crate) context: bool,
+    pub(crate) tips: bool,
+}
+
+impl Default for FooterHints {
+    fn default() -> Self {
+        Self {
+            shortcuts: true,
+            queue: true,
+            mode_cycle: true,
+            backtrack: true,
+            context: true,
+            tips: false,
+        }
+    }
+}
+
+impl FooterHints {
+    /// Enable exactly the listed hints; without a list, keep the defaults.
+    pub(crate) fn from_config(hints: Option<&[FooterHint]>) -> Self {
+        let Some(hints) = hints else {
+            return Self::default();
+        };
+        let enabled = |hint| hints.contains(&hint);
+        Self {
+            shortcuts: enabled(FooterHint::Shortcuts),
+            queue: enabled(FooterHint::Queue),
+            mode_cycle: enabled(FooterHint::ModeCycle),
+            backtrack: enabled(FooterHint::Backtrack),
+            context: enabled(FooterHint::Context),
+            tips: enabled(FooterHint::Tips),
+        }
+    }
+
+    /// Whether the idle summary slot (shortcuts hint or tip) is in use.
+    pub(crate) fn summary(self) -> bool {
+        self.shortcuts || self.tips
+    }
+
+    /// Whether every hint is disabled, which hides the hint bar.
+    fn all_hidden(self) -> bool {
+        self == Self {
+            shortcuts: false,
+            queue: false,
+            mode_cycle: false,
+            backtrack: false,
+            context: false,
+            tips: false,
+        }
+    }
+}
+
+/// A tip naming one shortcut, shown in the idle footer when `tips` is enabled.
+///
+/// Tips rotate once per turn and only suggest shortcuts that are useful in the current state:
+/// while a task runs, tips that would start new input are skipped.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) struct FooterTip(ShortcutId);
+
+const IDLE_TIPS: &[ShortcutId] = &[
+    ShortcutId::Commands,
+    ShortcutId::FilePaths,
+    ShortcutId::ShellCommands,
+    ShortcutId::ShowTranscript,
+    ShortcutId::ExternalEditor,
+];
+
+const RUNNING_TIPS: &[ShortcutId] = &[ShortcutId::ShowTranscript, ShortcutId::ExternalEditor];
+
+impl FooterTip {
+    pub(crate) fn for_state(is_task_running: bool, turn: usize) -> Self {
+        let tips = if is_task_running {
+            RUNNING_TIPS
+        } else {
+            IDLE_TIPS
+        };
+        Self(tips[turn % tips.len()])
+    }
+
+    fn spans(self) -> Vec<Span<'static>> {
+        let state = ShortcutsState {
+            use_shift_enter_hint: false,
+            esc_backtrack_hint: false,
+            is_wsl: false,
+            collaboration_modes_enabled: false,
+        };
+        SHORTCUTS
+            .iter()
+            .find(|descriptor| descriptor.id == self.0)
+            .and_then(|descriptor| {
+                let binding = descriptor.binding_for(state)?;
+                Some(vec![binding.key.into(), descriptor.label.dim()])
+            })
+            .unwrap_or_default()
+    }
+}
+
+/// How far context usage is past the configured warning thresholds (`tui.context_warnings`).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum ContextWarningLevel {
+    /// Past a threshold, but not the last one.
+    Elevated,
+    /// Past the last threshold.
+    Critical,
+}
+
+/// Files pinned with `/pin`, shown next to the context indicator.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) struct PinnedFilesSummary {
+    pub(crate) count: usize,
+    /// Estimated tokens the pinned files add to every request.
+    pub(crate) tokens: i64,
+}
+
+/// The plan step being worked on, shown next to the context indicator.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct PlanStepSummary {
+    /// 1-based position of the step in the plan.
+    pub(crate) number: usize,
+    pub(crate) total: usize,
+    pub(crate) text: String,
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -81,6 +228,7 @@ pub(crate) enum CollaborationModeIndicator {
 }
 
 const MODE_CYCLE_HINT: &str = "shift+tab to cycle";
+const PLAN_STEP_MAX_GRAPHEMES: usize = 40;
 const FOOTER_CONTEXT_GAP_COLS: u16 = 1;
 
 impl CollaborationModeIndicator {
@@ -171,14 +319,14 @@ pub(crate) fn reset_mode_after_activity(current: FooterMode) -> FooterMode {
 
 pub(crate) fn footer_height(props: &FooterProps) -> u16 {
     let show_shortcuts_hint = match props.mode {
-        FooterMode::ComposerEmpty => true,
+        FooterMode::ComposerEmpty => props.hints.summary(),
         FooterMode::ComposerHasDraft => false,
         FooterMode::QuitShortcutReminder | FooterMode::ShortcutOverlay | FooterMode::EscHint => {
             false
         }
     };
     let show_queue_hint = match props.mode {
-        FooterMode::ComposerHasDraft => props.is_task_running,
+        FooterMode::ComposerHasDraft => props.is_task_running && props.hints.queue,
         FooterMode::QuitShortcutReminder
         | FooterMode::ComposerEmpty
         | FooterMode::ShortcutOverlay
@@ -197,6 +345,42 @@ pub(crate) fn render_footer_line(area: Rect, buf: &mut Buffer, line: Line<'stati
     .render(area, buf);
 }
 
+/// Columns a status line row may use in the footer `area`.
+///
+/// Left-only rows run to the edge like the single-line status line always has; rows with
+/// aligned segments also keep the right padding so they line up with the right-side indicators.
+pub(crate) fn status_line_row_width(area: Rect, row: &StatusLineRow) -> usize {
+    let padding = if row.has_aligned_segments() {
+        2 * FOOTER_INDENT_COLS
+    } else {
+        FOOTER_INDENT_COLS
+    };
+    area.width.saturating_sub(padding as u16) as usize
+}
+
+/// Render the status line rows below the first one.
+///
+/// The first row shares its line with the right-side indicator, so callers lay it out and render
+/// it themselves.
+pub(crate) fn render_status_line_rows(area: Rect, buf: &mut Buffer, status_line: &StatusLine) {
+    for (offset, row) in status_line.rows().iter().enumerate().skip(1) {
+        let y = area.y.saturating_add(offset as u16);
+        if y >= area.bottom() {
+            break;
+        }
+        let row_area = Rect {
+            y,
+            height: 1,
+            ..area
+        };
+        render_footer_line(
+            row_area,
+            buf,
+            row.layout(status_line_row_width(area, row)).dim(),
+        );
+    }
+}
+
 /// Render footer content directly from `FooterProps`.
 ///
 /// This is intentionally not part of the width-based collapse/fallback logic.
@@ -236,6 +420,7 @@ pub(crate) fn left_fits(area: Rect, left_width: u16) -> bool {
 enum SummaryHintKind {
     None,
     Shortcuts,
+    Tip(FooterTip),
     QueueMessage,
     QueueShort,
 }
@@ -257,6 +442,7 @@ fn left_side_line(
             line.push_span(key_hint::plain(KeyCode::Char('?')));
             line.push_span(" for shortcuts".dim());
         }
+        SummaryHintKind::Tip(tip) => line.extend(tip.spans()),
         SummaryHintKind::QueueMessage => {
             line.push_span(key_hint::plain(KeyCode::Tab));
             line.push_span(" to queue message".dim());
@@ -292,11 +478,21 @@ pub(crate) fn single_line_footer_layout(
     show_cycle_hint: bool,
     show_shortcuts_hint: bool,
     show_queue_hint: bool,
+    tip: Option<FooterTip>,
 ) -> (SummaryLeft, bool) {
+    if is_narrow_layout(area.width) {
+        return narrow_footer_layout(
+            area,
+            context_width,
+            collaboration_mode_indicator,
+            show_queue_hint,
+        );
+    }
+
     let hint_kind = if show_queue_hint {
         SummaryHintKind::QueueMessage
     } else if show_shortcuts_hint {
-        SummaryHintKind::Shortcuts
+        summary_hint_kind(tip)
     } else {
         SummaryHintKind::None
     };
@@ -449,6 +645,45 @@ pub(crate) fn single_line_footer_layout(
     (SummaryLeft::None, true)
 }
 
+/// The compact footer for terminals narrower than `NARROW_LAYOUT_COLS`: no shortcut or cycle
+/// hints, the short queue hint, and the mode indicator, dropping the queue hint and then the
+/// context indicator when they do not fit.
+fn narrow_footer_layout(
+    area: Rect,
+    context_width: u16,
+    collaboration_mode_indicator: Option<CollaborationModeIndicator>,
+    show_queue_hint: bool,
+) -> (SummaryLeft, bool) {
+    let mut candidates = Vec::new();
+    if show_queue_hint {
+        candidates.push(SummaryHintKind::QueueShort);
+    }
+    if collaboration_mode_indicator.is_some() {
+        candidates.push(SummaryHintKind::None);
+    }
+    for hint in candidates {
+        let line = left_side_line(
+            collaboration_mode_indicator,
+            LeftSideState {
+                hint,
+                show_cycle_hint: false,
+            },
+        );
+        let width = line.width() as u16;
+        if can_show_left_with_context(area, width, context_width) {
+            return (SummaryLeft::Custom(line), true);
+        }
+        if left_fits(area, width) {
+            return (SummaryLeft::Custom(line), false);
+        }
+    }
+    (SummaryLeft::None, true)
+}
+
+fn summary_hint_kind(tip: Option<FooterTip>) -> SummaryHintKind {
+    tip.map_or(SummaryHintKind::Shortcuts, SummaryHintKind::Tip)
+}
+
 pub(crate) fn mode_indicator_line(
     indicator: Option<CollaborationModeIndicator>,
     show_cycle_hint: bool,
@@ -575,7 +810,20 @@ fn footer_from_props_lines(
             | FooterMode::EscHint => false,
         }
     {
-        return vec![status_line.clone().dim()];
+        return status_line
+            .rows()
+            .iter()
+            .map(|row| row.unaligned().dim())
+            .collect();
+    }
+    if props.hints.all_hidden()
+        && !props.collaboration_modes_enabled
+        && matches!(
+            props.mode,
+            FooterMode::ComposerEmpty | FooterMode::ComposerHasDraft
+        )
+    {
+        return Vec::new();
     }
     match props.mode {
         FooterMode::QuitShortcutReminder => {
@@ -584,7 +832,7 @@ fn footer_from_props_lines(
         FooterMode::ComposerEmpty => {
             let state = LeftSideState {
                 hint: if show_shortcuts_hint {
-                    SummaryHintKind::Shortcuts
+                    summary_hint_kind(props.tip)
                 } else {
                     SummaryHintKind::None
                 },
@@ -607,7 +855,7 @@ fn footer_from_props_lines(
                 hint: if show_queue_hint {
                     SummaryHintKind::QueueMessage
                 } else if show_shortcuts_hint {
-                    SummaryHintKind::Shortcuts
+                    summary_hint_kind(props.tip)
                 } else {
                     SummaryHintKind::None
                 },
@@ -782,10 +1030,17 @@ fn build_columns(entries: Vec<Line<'static>>) -> Vec<Line<'static>> {
         .collect()
 }
 
-pub(crate) fn context_window_line(percent: Option<i64>, used_tokens: Option<i64>) -> Line<'static> {
+fn context_window_line(
+    percent: Option<i64>,
+    used_tokens: Option<i64>,
+    warning: Option<ContextWarningLevel>,
+) -> Line<'static> {
     if let Some(percent) = percent {
         let percent = percent.clamp(0, 100);
-        return Line::from(vec![Span::from(format!("{percent}% context left")).dim()]);
+        return Line::from(vec![context_meter_span(
+            format!("{percent}% context left"),
+            warning,
+        )]);
     }
 
     if let Some(tokens) = used_tokens {
@@ -796,6 +1051,93 @@ pub(crate) fn context_window_line(percent: Option<i64>, used_tokens: Option<i64>
     Line::from(vec![Span::from("100% context left").dim()])
 }
 
+const READ_ONLY_BADGE: &str = "READ ONLY";
+
+fn context_meter_span(text: String, warning: Option<ContextWarningLevel>) -> Span<'static> {
+    match warning {
+        None => Span::from(text).dim(),
+        Some(ContextWarningLevel::Elevated) => Span::from(text).magenta(),
+        Some(ContextWarningLevel::Critical) => Span::from(text).red(),
+    }
+}
+
+/// The context indicator, preceded by the current plan step, the pinned-files badge, and the
+/// read-only badge.
+///
+/// On narrow terminals (`width` below `NARROW_LAYOUT_COLS`) each part collapses to an icon and
+/// a number, e.g. `RO ☰2/5 ⚲2 ◔72%`.
+pub(crate) fn context_line(props: &FooterProps, width: u16) -> Line<'static> {
+    if is_narrow_layout(width) {
+        return compact_context_line(props);
+    }
+    let mut line = context_window_line(
+        props.context_window_percent,
+        props.context_window_used_tokens,
+        props.context_warning,
+    );
+    if let Some(pinned) = props.pinned_files {
+        let noun = if pinned.count == 1 { "file" } else { "files" };
+        line.spans.insert(
+            0,
+            Span::from(format!(
+                "{} pinned {noun} (~{} tokens) · ",
+                pinned.count,
+                format_tokens_compact(pinned.tokens)
+            ))
+            .dim(),
+        );
+    }
+    if let Some(step) = &props.plan_step {
+        line.spans.insert(
+            0,
+            Span::from(format!(
+                "Step {}/{}: {} · ",
+                step.number,
+                step.total,
+                truncate_text(&step.text, PLAN_STEP_MAX_GRAPHEMES)
+            ))
+            .dim(),
+        );
+    }
+    if props.read_only {
+        line.spans.insert(0, Span::from(" · ").dim());
+        line.spans
+            .insert(0, Span::from(READ_ONLY_BADGE).red().bold());
+    }
+    line
+}
+
+fn compact_context_line(props: &FooterProps) -> Line<'static> {
+    let mut parts = Vec::new();
+    if let Some(step) = &props.plan_step {
+        parts.push(format!("☰{}/{}", step.number, step.total));
+    }
+    if let Some(pinned) = props.pinned_files {
+        parts.push(format!("⚲{}", pinned.count));
+    }
+    let meter = match (
+        props.context_window_percent,
+        props.context_window_used_tokens,
+    ) {
+        (Some(percent), _) => context_meter_span(
+            format!("◔{}%", percent.clamp(0, 100)),
+            props.context_warning,
+        ),
+        (None, Some(tokens)) => format!("◔{}", format_tokens_compact(tokens)).dim(),
+        (None, None) => "◔100%".dim(),
+    };
+    let mut spans = Vec::new();
+    if props.read_only {
+        spans.push("RO".red().bold());
+        spans.push(" ".into());
+    }
+    for part in parts {
+        spans.push(format!("{part} ").dim());
+    }
+    spans.push(meter);
+    Line::from(spans)
+}
+
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 enum ShortcutId {
     Commands,
@@ -996,7 +1338,6 @@ const SHORTCUTS: &[ShortcutDescriptor] = &[
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::line_truncation::truncate_line_with_ellipsis_if_overflow;
     use crate::test_backend::VT100Backend;
     use insta::assert_snapshot;
     use pretty_assertions::assert_eq;
@@ -1017,16 +1358,16 @@ mod tests {
         terminal
             .draw(|f| {
                 let area = Rect::new(0, 0, f.area().width, height);
-                let show_cycle_hint = !props.is_task_running;
+                let show_cycle_hint = !props.is_task_running && props.hints.mode_cycle;
                 let show_shortcuts_hint = match props.mode {
-                    FooterMode::ComposerEmpty => true,
+                    FooterMode::ComposerEmpty => props.hints.summary(),
                     FooterMode::ComposerHasDraft => false,
                     FooterMode::QuitShortcutReminder
                     | FooterMode::ShortcutOverlay
                     | FooterMode::EscHint => false,
                 };
                 let show_queue_hint = match props.mode {
-                    FooterMode::ComposerHasDraft => props.is_task_running,
+                    FooterMode::ComposerHasDraft => props.is_task_running && props.hints.queue,
                     FooterMode::QuitShortcutReminder
                     | FooterMode::ComposerEmpty
                     | FooterMode::ShortcutOverlay
@@ -1045,17 +1386,15 @@ mod tests {
                 } else {
                     collaboration_mode_indicator
                 };
-                let available_width = area.width.saturating_sub(FOOTER_INDENT_COLS as u16) as usize;
                 let mut truncated_status_line = if status_line_active
                     && matches!(
                         props.mode,
                         FooterMode::ComposerEmpty | FooterMode::ComposerHasDraft
                     ) {
-                    props
-                        .status_line_value
-                        .as_ref()
-                        .map(|line| line.clone().dim())
-                        .map(|line| truncate_line_with_ellipsis_if_overflow(line, available_width))
+                    props.status_line_value.as_ref().map(|status_line| {
+                        let row = status_line.first_row();
+                        row.layout(status_line_row_width(area, row)).dim()
+                    })
                 } else {
                     None
                 };
@@ -1083,10 +1422,8 @@ mod tests {
                         compact
                     }
                 } else {
-                    Some(context_window_line(
-                        props.context_window_percent,
-                        props.context_window_used_tokens,
-                    ))
+                    (props.hints.context || props.read_only)
+                        .then(|| context_line(props, area.width))
                 };
                 let right_width = right_line
                     .as_ref()
@@ -1098,10 +1435,7 @@ mod tests {
                     && let Some(line) = props
                         .status_line_value
                         .as_ref()
-                        .map(|line| line.clone().dim())
-                        .map(|line| {
-                            truncate_line_with_ellipsis_if_overflow(line, max_left as usize)
-                        })
+                        .map(|status_line| status_line.first_row().layout(max_left as usize).dim())
                 {
                     left_width = line.width() as u16;
                     truncated_status_line = Some(line);
@@ -1116,8 +1450,12 @@ mod tests {
                         if let Some(line) = truncated_status_line.clone() {
                             render_footer_line(area, f.buffer_mut(), line);
                         }
+                        if let Some(status_line) = &props.status_line_value {
+                            render_status_line_rows(area, f.buffer_mut(), status_line);
+                        }
                         if can_show_left_and_context && let Some(line) = &right_line {
-                            render_context_right(area, f.buffer_mut(), line);
+                            let first_row = Rect { height: 1, ..area };
+                            render_context_right(first_row, f.buffer_mut(), line);
                         }
                     } else {
                         let (summary_left, show_context) = single_line_footer_layout(
@@ -1127,6 +1465,7 @@ mod tests {
                             show_cycle_hint,
                             show_shortcuts_hint,
                             show_queue_hint,
+                            props.tip,
                         );
                         match summary_left {
                             SummaryLeft::Default => {
@@ -1211,8 +1550,14 @@ mod tests {
                 quit_shortcut_key: key_hint::ctrl(KeyCode::Char('c')),
                 context_window_percent: None,
                 context_window_used_tokens: None,
+                context_warning: None,
+                read_only: false,
+                pinned_files: None,
+                plan_step: None,
                 status_line_value: None,
                 status_line_enabled: false,
+                hints: FooterHints::default(),
+                tip: None,
             },
         );
 
@@ -1228,8 +1573,14 @@ mod tests {
                 quit_shortcut_key: key_hint::ctrl(KeyCode::Char('c')),
                 context_window_percent: None,
                 context_window_used_tokens: None,
+                context_warning: None,
+                read_only: false,
+                pinned_files: None,
+                plan_step: None,
                 status_line_value: None,
                 status_line_enabled: false,
+                hints: FooterHints::default(),
+                tip: None,
             },
         );
 
@@ -1245,8 +1596,14 @@ mod tests {
                 quit_shortcut_key: key_hint::ctrl(KeyCode::Char('c')),
                 context_window_percent: None,
                 context_window_used_tokens: None,
+                context_warning: None,
+                read_only: false,
+                pinned_files: None,
+                plan_step: None,
                 status_line_value: None,
                 status_line_enabled: false,
+                hints: FooterHints::default(),
+                tip: None,
             },
         );
 
@@ -1262,8 +1619,14 @@ mod tests {
                 quit_shortcut_key: key_hint::ctrl(KeyCode::Char('c')),
                 context_window_percent: None,
                 context_window_used_tokens: None,
+                context_warning: None,
+                read_only: false,
+                pinned_files: None,
+                plan_step: None,
                 status_line_value: None,
                 status_line_enabled: false,
+                hints: FooterHints::default(),
+                tip: None,
             },
         );
 
@@ -1279,8 +1642,14 @@ mod tests {
                 quit_shortcut_key: key_hint::ctrl(KeyCode::Char('c')),
                 context_window_percent: None,
                 context_window_used_tokens: None,
+                context_warning: None,
+                read_only: false,
+                pinned_files: None,
+                plan_step: None,
                 status_line_value: None,
                 status_line_enabled: false,
+                hints: FooterHints::default(),
+                tip: None,
             },
         );
 
@@ -1296,8 +1665,14 @@ mod tests {
                 quit_shortcut_key: key_hint::ctrl(KeyCode::Char('c')),
                 context_window_percent: None,
                 context_window_used_tokens: None,
+                context_warning: None,
+                read_only: false,
+                pinned_files: None,
+                plan_step: None,
                 status_line_value: None,
                 status_line_enabled: false,
+                hints: FooterHints::default(),
+                tip: None,
             },
         );
 
@@ -1313,8 +1688,14 @@ mod tests {
                 quit_shortcut_key: key_hint::ctrl(KeyCode::Char('c')),
                 context_window_percent: None,
                 context_window_used_tokens: None,
+                context_warning: None,
+                read_only: false,
+                pinned_files: None,
+                plan_step: None,
                 status_line_value: None,
                 status_line_enabled: false,
+                hints: FooterHints::default(),
+                tip: None,
             },
         );
 
@@ -1330,8 +1711,41 @@ mod tests {
                 quit_shortcut_key: key_hint::ctrl(KeyCode::Char('c')),
                 context_window_percent: Some(72),
                 context_window_used_tokens: None,
+                context_warning: None,
+                read_only: false,
+                pinned_files: None,
+                plan_step: None,
+                status_line_value: None,
+                status_line_enabled: false,
+                hints: FooterHints::default(),
+                tip: None,
+            },
+        );
+
+        snapshot_footer(
+            "footer_context_plan_step",
+            FooterProps {
+                mode: FooterMode::ComposerEmpty,
+                esc_backtrack_hint: false,
+                use_shift_enter_hint: false,
+                is_task_running: true,
+                collaboration_modes_enabled: false,
+                is_wsl: false,
+                quit_shortcut_key: key_hint::ctrl(KeyCode::Char('c')),
+                context_window_percent: Some(72),
+                context_window_used_tokens: None,
+                context_warning: None,
+                read_only: false,
+                pinned_files: None,
+                plan_step: Some(PlanStepSummary {
+                    number: 2,
+                    total: 5,
+                    text: "Wrap the upload call".to_string(),
+                }),
                 status_line_value: None,
                 status_line_enabled: false,
+                hints: FooterHints::default(),
+                tip: None,
             },
         );
 
@@ -1347,8 +1761,14 @@ mod tests {
                 quit_shortcut_key: key_hint::ctrl(KeyCode::Char('c')),
                 context_window_percent: None,
                 context_window_used_tokens: Some(123_456),
+                context_warning: None,
+                read_only: false,
+                pinned_files: None,
+                plan_step: None,
                 status_line_value: None,
                 status_line_enabled: false,
+                hints: FooterHints::default(),
+                tip: None,
             },
         );
 
@@ -1364,8 +1784,14 @@ mod tests {
                 quit_shortcut_key: key_hint::ctrl(KeyCode::Char('c')),
                 context_window_percent: None,
                 context_window_used_tokens: None,
+                context_warning: None,
+                read_only: false,
+                pinned_files: None,
+                plan_step: None,
                 status_line_value: None,
                 status_line_enabled: false,
+                hints: FooterHints::default(),
+                tip: None,
             },
         );
 
@@ -1379,8 +1805,14 @@ mod tests {
             quit_shortcut_key: key_hint::ctrl(KeyCode::Char('c')),
             context_window_percent: None,
             context_window_used_tokens: None,
+            context_warning: None,
+            read_only: false,
+            pinned_files: None,
+            plan_step: None,
             status_line_value: None,
             status_line_enabled: false,
+            hints: FooterHints::default(),
+            tip: None,
         };
 
         snapshot_footer_with_mode_indicator(
@@ -1407,8 +1839,14 @@ mod tests {
             quit_shortcut_key: key_hint::ctrl(KeyCode::Char('c')),
             context_window_percent: None,
             context_window_used_tokens: None,
+            context_warning: None,
+            read_only: false,
+            pinned_files: None,
+            plan_step: None,
             status_line_value: None,
             status_line_enabled: false,
+            hints: FooterHints::default(),
+            tip: None,
         };
 
         snapshot_footer_with_mode_indicator(
@@ -1428,8 +1866,14 @@ mod tests {
             quit_shortcut_key: key_hint::ctrl(KeyCode::Char('c')),
             context_window_percent: None,
             context_window_used_tokens: None,
-            status_line_value: Some(Line::from("Status line content".to_string())),
+            context_warning: None,
+            read_only: false,
+            pinned_files: None,
+            plan_step: None,
+            status_line_value: Some(Line::from("Status line content".to_string()).into()),
             status_line_enabled: true,
+            hints: FooterHints::default(),
+            tip: None,
         };
 
         snapshot_footer("footer_status_line_overrides_shortcuts", props);
@@ -1444,8 +1888,14 @@ mod tests {
             quit_shortcut_key: key_hint::ctrl(KeyCode::Char('c')),
             context_window_percent: None,
             context_window_used_tokens: None,
-            status_line_value: Some(Line::from("Status line content".to_string())),
+            context_warning: None,
+            read_only: false,
+            pinned_files: None,
+            plan_step: None,
+            status_line_value: Some(Line::from("Status line content".to_string()).into()),
             status_line_enabled: true,
+            hints: FooterHints::default(),
+            tip: None,
         };
 
         snapshot_footer("footer_status_line_yields_to_queue_hint", props);
@@ -1460,8 +1910,14 @@ mod tests {
             quit_shortcut_key: key_hint::ctrl(KeyCode::Char('c')),
             context_window_percent: None,
             context_window_used_tokens: None,
-            status_line_value: Some(Line::from("Status line content".to_string())),
+            context_warning: None,
+            read_only: false,
+            pinned_files: None,
+            plan_step: None,
+            status_line_value: Some(Line::from("Status line content".to_string()).into()),
             status_line_enabled: true,
+            hints: FooterHints::default(),
+            tip: None,
         };
 
         snapshot_footer("footer_status_line_overrides_draft_idle", props);
@@ -1476,8 +1932,14 @@ mod tests {
             quit_shortcut_key: key_hint::ctrl(KeyCode::Char('c')),
             context_window_percent: Some(50),
             context_window_used_tokens: None,
+            context_warning: None,
+            read_only: false,
+            pinned_files: None,
+            plan_step: None,
             status_line_value: None, // command timed out / empty
             status_line_enabled: true,
+            hints: FooterHints::default(),
+            tip: None,
         };
 
         snapshot_footer_with_mode_indicator(
@@ -1497,8 +1959,14 @@ mod tests {
             quit_shortcut_key: key_hint::ctrl(KeyCode::Char('c')),
             context_window_percent: Some(50),
             context_window_used_tokens: None,
+            context_warning: None,
+            read_only: false,
+            pinned_files: None,
+            plan_step: None,
             status_line_value: None,
             status_line_enabled: false,
+            hints: FooterHints::default(),
+            tip: None,
         };
 
         snapshot_footer_with_mode_indicator(
@@ -1518,8 +1986,14 @@ mod tests {
             quit_shortcut_key: key_hint::ctrl(KeyCode::Char('c')),
             context_window_percent: Some(50),
             context_window_used_tokens: None,
+            context_warning: None,
+            read_only: false,
+            pinned_files: None,
+            plan_step: None,
             status_line_value: None,
             status_line_enabled: true,
+            hints: FooterHints::default(),
+            tip: None,
         };
 
         // has status line and no collaboration mode
@@ -1540,10 +2014,20 @@ mod tests {
             quit_shortcut_key: key_hint::ctrl(KeyCode::Char('c')),
             context_window_percent: Some(50),
             context_window_used_tokens: None,
-            status_line_value: Some(Line::from(
-                "Status line content that should truncate before the mode indicator".to_string(),
-            )),
+            context_warning: None,
+            read_only: false,
+            pinned_files: None,
+            plan_step: None,
+            status_line_value: Some(
+                Line::from(
+                    "Status line content that should truncate before the mode indicator"
+                        .to_string(),
+                )
+                .into(),
+            ),
             status_line_enabled: true,
+            hints: FooterHints::default(),
+            tip: None,
         };
 
         snapshot_footer_with_mode_indicator(
@@ -1566,11 +2050,18 @@ mod tests {
             quit_shortcut_key: key_hint::ctrl(KeyCode::Char('c')),
             context_window_percent: Some(50),
             context_window_used_tokens: None,
+            context_warning: None,
+            read_only: false,
+            pinned_files: None,
+            plan_step: None,
             status_line_value: Some(Line::from(
                 "Status line content that is definitely too long to fit alongside the mode label"
                     .to_string(),
-            )),
+            )
+            .into()),
             status_line_enabled: true,
+            hints: FooterHints::default(),
+            tip: None,
         };
 
         let screen =
@@ -1590,6 +2081,149 @@ mod tests {
         );
     }
 
+    #[test]
+    fn footer_status_line_rows_render_below_first_row() {
+        let status_line = StatusLine::new(vec![
+            StatusLineRow {
+                left: Line::from("gpt-5 high"),
+                right: Line::from("42% left"),
+                ..Default::default()
+            },
+            StatusLineRow {
+                left: Line::from("main"),
+                right: Line::from("~/code/project"),
+                ..Default::default()
+            },
+        ]);
+        let props = FooterProps {
+            mode: FooterMode::ComposerEmpty,
+            esc_backtrack_hint: false,
+            use_shift_enter_hint: false,
+            is_task_running: false,
+            collaboration_modes_enabled: true,
+            is_wsl: false,
+            quit_shortcut_key: key_hint::ctrl(KeyCode::Char('c')),
+            context_window_percent: Some(50),
+            context_window_used_tokens: None,
+            context_warning: None,
+            read_only: false,
+            pinned_files: None,
+            plan_step: None,
+            status_line_value: status_line,
+            status_line_enabled: true,
+            hints: FooterHints::default(),
+            tip: None,
+        };
+
+        assert_eq!(footer_height(&props), 2);
+        let screen =
+            render_footer_with_mode_indicator(60, &props, Some(CollaborationModeIndicator::Plan));
+        let lines = screen.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 2, "{screen}");
+        assert!(lines[0].starts_with("  gpt-5 high"), "{screen}");
+        assert!(lines[0].contains("42% left"), "{screen}");
+        assert!(lines[0].trim_end().ends_with("Plan mode"), "{screen}");
+        assert!(lines[1].starts_with("  main"), "{screen}");
+        assert!(lines[1].ends_with("~/code/project"), "{screen}");
+        assert_eq!(
+            lines[0].trim_end().len(),
+            lines[1].trim_end().len(),
+            "right-aligned segments should line up with the mode indicator: {screen}"
+        );
+        snapshot_footer_with_mode_indicator(
+            "footer_status_line_rows",
+            60,
+            &props,
+            Some(CollaborationModeIndicator::Plan),
+        );
+    }
+
+    fn hint_props(hints: FooterHints, tip: Option<FooterTip>) -> FooterProps {
+        FooterProps {
+            mode: FooterMode::ComposerEmpty,
+            esc_backtrack_hint: false,
+            use_shift_enter_hint: false,
+            is_task_running: false,
+            collaboration_modes_enabled: false,
+            is_wsl: false,
+            quit_shortcut_key: key_hint::ctrl(KeyCode::Char('c')),
+            context_window_percent: Some(50),
+            context_window_used_tokens: None,
+            context_warning: None,
+            read_only: false,
+            pinned_files: None,
+            plan_step: None,
+            status_line_value: None,
+            status_line_enabled: false,
+            hints,
+            tip,
+        }
+    }
+
+    #[test]
+    fn footer_hints_from_config_enables_only_listed_hints() {
+        assert_eq!(FooterHints::from_config(None), FooterHints::default());
+        assert_eq!(
+            FooterHints::from_config(Some(&[FooterHint::Context, FooterHint::Tips])),
+            FooterHints {
+                shortcuts: false,
+                queue: false,
+                mode_cycle: false,
+                backtrack: false,
+                context: true,
+                tips: true,
+            }
+        );
+    }
+
+    #[test]
+    fn footer_tip_replaces_shortcuts_hint_and_rotates() {
+        let hints = FooterHints {
+            tips: true,
+            ..FooterHints::default()
+        };
+        let first = FooterTip::for_state(false, 0);
+        let second = FooterTip::for_state(false, 1);
+        assert_ne!(first, second);
+        assert_eq!(
+            FooterTip::for_state(true, 0),
+            FooterTip(ShortcutId::ShowTranscript)
+        );
+
+        let screen = render_footer_with_mode_indicator(80, &hint_props(hints, Some(first)), None);
+        assert!(screen.starts_with("  / for commands"), "{screen}");
+        assert!(!screen.contains("for shortcuts"), "{screen}");
+        assert!(screen.contains("50% context left"), "{screen}");
+
+        snapshot_footer_with_mode_indicator(
+            "footer_tip_idle",
+            80,
+            &hint_props(hints, Some(first)),
+            None,
+        );
+        let running = FooterProps {
+            is_task_running: true,
+            ..hint_props(hints, Some(FooterTip::for_state(true, 0)))
+        };
+        snapshot_footer_with_mode_indicator("footer_tip_running", 80, &running, None);
+    }
+
+    #[test]
+    fn footer_hides_disabled_hints() {
+        let context_only = FooterHints::from_config(Some(&[FooterHint::Context]));
+        let screen = render_footer_with_mode_indicator(80, &hint_props(context_only, None), None);
+        assert!(!screen.contains("for shortcuts"), "{screen}");
+        assert!(screen.contains("50% context left"), "{screen}");
+
+        let hidden = FooterHints::from_config(Some(&[]));
+        assert_eq!(footer_height(&hint_props(hidden, None)), 0);
+        let quit_reminder = FooterProps {
+            mode: FooterMode::QuitShortcutReminder,
+            ..hint_props(hidden, None)
+        };
+        assert_eq!(footer_height(&quit_reminder), 1);
+    }
+
     #[test]
     fn paste_image_shortcut_prefers_ctrl_alt_v_under_wsl() {
         let descriptor = SHORTCUTS
@@ -1626,4 +2260,106 @@ mod tests {
 
         assert_eq!(actual_key, expected_key);
     }
+
+    #[test]
+    fn context_line_includes_badges_and_plan_step() {
+        let mut props = FooterProps {
+            mode: FooterMode::ComposerEmpty,
+            esc_backtrack_hint: false,
+            use_shift_enter_hint: false,
+            is_task_running: false,
+            collaboration_modes_enabled: false,
+            is_wsl: false,
+            quit_shortcut_key: key_hint::ctrl(KeyCode::Char('c')),
+            context_window_percent: Some(72),
+            context_window_used_tokens: None,
+            context_warning: None,
+            read_only: false,
+            pinned_files: None,
+            plan_step: None,
+            status_line_value: None,
+            status_line_enabled: false,
+            hints: FooterHints::default(),
+            tip: None,
+        };
+        assert_eq!(context_line(&props, 80).to_string(), "72% context left");
+
+        props.pinned_files = Some(PinnedFilesSummary {
+            count: 2,
+            tokens: 1_500,
+        });
+        assert_eq!(
+            context_line(&props, 80).to_string(),
+            "2 pinned files (~1.5K tokens) · 72% context left"
+        );
+
+        props.plan_step = Some(PlanStepSummary {
+            number: 2,
+            total: 5,
+            text: "Wrap the upload call".to_string(),
+        });
+        assert_eq!(
+            context_line(&props, 80).to_string(),
+            "Step 2/5: Wrap the upload call · 2 pinned files (~1.5K tokens) · 72% context left"
+        );
+        assert_eq!(context_line(&props, 59).to_string(), "☰2/5 ⚲2 ◔72%");
+
+        props.read_only = true;
+        assert_eq!(
+            context_line(&props, 80).to_string(),
+            "READ ONLY · Step 2/5: Wrap the upload call · 2 pinned files (~1.5K tokens) · 72% context left"
+        );
+        assert_eq!(context_line(&props, 59).to_string(), "RO ☰2/5 ⚲2 ◔72%");
+    }
+
+    fn narrow_layout_props(mode: FooterMode, is_task_running: bool) -> FooterProps {
+        FooterProps {
+            mode,
+            esc_backtrack_hint: false,
+            use_shift_enter_hint: false,
+            is_task_running,
+            collaboration_modes_enabled: true,
+            is_wsl: false,
+            quit_shortcut_key: key_hint::ctrl(KeyCode::Char('c')),
+            context_window_percent: Some(72),
+            context_window_used_tokens: None,
+            context_warning: None,
+            read_only: false,
+            pinned_files: Some(PinnedFilesSummary {
+                count: 2,
+                tokens: 1_500,
+            }),
+            plan_step: None,
+            status_line_value: None,
+            status_line_enabled: false,
+            hints: FooterHints::default(),
+            tip: None,
+        }
+    }
+
+    #[test]
+    fn footer_collapses_at_narrow_widths() {
+        let props = narrow_layout_props(FooterMode::ComposerEmpty, false);
+        for width in [40, 59, 60, 120] {
+            snapshot_footer_with_mode_indicator(
+                &format!("footer_narrow_layout_plan_mode_{width}_cols"),
+                width,
+                &props,
+                Some(CollaborationModeIndicator::Plan),
+            );
+        }
+    }
+
+    #[test]
+    fn narrow_footer_keeps_a_short_queue_hint() {
+        let props = narrow_layout_props(FooterMode::ComposerHasDraft, true);
+        for width in [40, 60] {
+            snapshot_footer_with_mode_indicator(
+                &format!("footer_narrow_layout_queue_hint_{width}_cols"),
+                width,
+                &props,
+                None,
+            );
+        }
+    }
 }
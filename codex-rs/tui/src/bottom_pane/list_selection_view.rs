@@ -1,3 +1,4 @@
+use codex_utils_fuzzy_match::fuzzy_match;
 use crossterm::event::KeyCode;
 use crossterm::event::KeyEvent;
 use crossterm::event::KeyModifiers;
@@ -143,6 +144,9 @@ pub(crate) struct SelectionViewParams {
     pub footer_hint: Option<Line<'static>>,
     pub items: Vec<SelectionItem>,
     pub is_searchable: bool,
+    /// Match the query as a subsequence of `search_value` and rank rows by match quality,
+    /// instead of keeping rows that contain it verbatim in their original order.
+    pub fuzzy_search: bool,
     pub search_placeholder: Option<String>,
     pub col_width_mode: ColumnWidthMode,
     pub header: Box<dyn Renderable>,
@@ -185,6 +189,7 @@ impl Default for SelectionViewParams {
             footer_hint: None,
             items: Vec::new(),
             is_searchable: false,
+            fuzzy_search: false,
             search_placeholder: None,
             col_width_mode: ColumnWidthMode::AutoVisible,
             header: Box::new(()),
@@ -214,6 +219,7 @@ pub(crate) struct ListSelectionView {
     complete: bool,
     app_event_tx: AppEventSender,
     is_searchable: bool,
+    fuzzy_search: bool,
     search_query: String,
     search_placeholder: Option<String>,
     col_width_mode: ColumnWidthMode,
@@ -262,6 +268,7 @@ impl ListSelectionView {
             complete: false,
             app_event_tx,
             is_searchable: params.is_searchable,
+            fuzzy_search: params.fuzzy_search,
             search_query: String::new(),
             search_placeholder: if params.is_searchable {
                 params.search_placeholder
@@ -309,7 +316,19 @@ impl ListSelectionView {
             })
             .or_else(|| self.initial_selected_idx.take());
 
-        if self.is_searchable && !self.search_query.is_empty() {
+        if self.is_searchable && self.fuzzy_search && !self.search_query.is_empty() {
+            let mut matches: Vec<(usize, i32)> = self
+                .items
+                .iter()
+                .enumerate()
+                .filter_map(|(idx, item)| {
+                    let value = item.search_value.as_deref()?;
+                    fuzzy_match(value, &self.search_query).map(|(_indices, score)| (idx, score))
+                })
+                .collect();
+            matches.sort_by_key(|(idx, score)| (*score, *idx));
+            self.filtered_indices = matches.into_iter().map(|(idx, _score)| idx).collect();
+        } else if self.is_searchable && !self.search_query.is_empty() {
             let query_lower = self.search_query.to_lowercase();
             self.filtered_indices = self
                 .items
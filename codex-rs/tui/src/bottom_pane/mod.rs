@@ -13,6 +13,7 @@
 //!
 //! Some UI is time-based rather than input-based, such as the transient "press again to quit"
 //! hint. The pane schedules redraws so those hints can expire even when the UI is otherwise idle.
+use std::ops::Range;
 use std::path::PathBuf;
 
 use crate::app_event::ConnectorsSnapshot;
@@ -22,9 +23,12 @@ use crate::bottom_pane::pending_thread_approvals::PendingThreadApprovals;
 use crate::bottom_pane::unified_exec_footer::UnifiedExecFooter;
 use crate::key_hint;
 use crate::key_hint::KeyBinding;
+use crate::keymap;
+use crate::paste_file::SavedPaste;
 use crate::render::renderable::FlexRenderable;
 use crate::render::renderable::Renderable;
 use crate::render::renderable::RenderableItem;
+use crate::slash_command::SlashCommand;
 use crate::tui::FrameRequester;
 use bottom_pane_view::BottomPaneView;
 use codex_core::features::Features;
@@ -37,18 +41,22 @@ use crossterm::event::KeyEvent;
 use crossterm::event::KeyEventKind;
 use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
+use ratatui::style::Stylize;
 use ratatui::text::Line;
 use std::time::Duration;
+use std::time::Instant;
 
 mod app_link_view;
 mod approval_overlay;
 mod multi_select_picker;
 mod request_user_input;
+mod status_line;
 mod status_line_setup;
 pub(crate) use app_link_view::AppLinkView;
 pub(crate) use app_link_view::AppLinkViewParams;
 pub(crate) use approval_overlay::ApprovalOverlay;
 pub(crate) use approval_overlay::ApprovalRequest;
+pub(crate) use approval_overlay::plan_step_lines;
 pub(crate) use request_user_input::RequestUserInputOverlay;
 mod bottom_pane_view;
 
@@ -67,6 +75,7 @@ pub(crate) struct MentionBinding {
 }
 mod chat_composer;
 mod chat_composer_history;
+mod command_palette;
 mod command_popup;
 pub mod custom_prompt_view;
 mod experimental_features_view;
@@ -78,6 +87,10 @@ mod skill_popup;
 mod skills_toggle_view;
 mod slash_commands;
 pub(crate) use footer::CollaborationModeIndicator;
+pub(crate) use footer::ContextWarningLevel;
+pub(crate) use footer::FooterHints;
+pub(crate) use footer::PinnedFilesSummary;
+pub(crate) use footer::PlanStepSummary;
 pub(crate) use list_selection_view::ColumnWidthMode;
 pub(crate) use list_selection_view::SelectionViewParams;
 pub(crate) use list_selection_view::SideContentWidth;
@@ -90,6 +103,9 @@ pub(crate) use feedback_view::feedback_selection_params;
 pub(crate) use feedback_view::feedback_upload_consent_params;
 pub(crate) use skills_toggle_view::SkillsToggleItem;
 pub(crate) use skills_toggle_view::SkillsToggleView;
+pub(crate) use status_line::STATUS_LINE_MAX_ROWS;
+pub(crate) use status_line::StatusLine;
+pub(crate) use status_line::StatusLineRow;
 pub(crate) use status_line_setup::StatusLineItem;
 pub(crate) use status_line_setup::StatusLineSetupView;
 mod paste_burst;
@@ -134,6 +150,7 @@ pub(crate) use chat_composer::ChatComposer;
 pub(crate) use chat_composer::ChatComposerConfig;
 pub(crate) use chat_composer::InputResult;
 use codex_protocol::custom_prompts::CustomPrompt;
+pub(crate) use command_palette::command_palette_params;
 
 use crate::status_indicator_widget::StatusDetailsCapitalization;
 use crate::status_indicator_widget::StatusIndicatorWidget;
@@ -164,6 +181,8 @@ pub(crate) struct BottomPane {
     is_task_running: bool,
     esc_backtrack_hint: bool,
     animations_enabled: bool,
+    /// Key that sends `Op::Interrupt` while a task is running (`tui.interrupt_key`).
+    interrupt_key: KeyBinding,
 
     /// Inline status indicator shown above the composer while a task is running.
     status: Option<StatusIndicatorWidget>,
@@ -176,6 +195,8 @@ pub(crate) struct BottomPane {
     pending_input_preview: PendingInputPreview,
     /// Inactive threads with pending approval requests.
     pending_thread_approvals: PendingThreadApprovals,
+    /// Warning shown directly above the composer while the access token is close to expiring.
+    auth_expiry_warning: Option<String>,
     context_window_percent: Option<i64>,
     context_window_used_tokens: Option<i64>,
 }
@@ -225,13 +246,24 @@ impl BottomPane {
             unified_exec_footer: UnifiedExecFooter::new(),
             pending_input_preview: PendingInputPreview::new(),
             pending_thread_approvals: PendingThreadApprovals::new(),
+            auth_expiry_warning: None,
             esc_backtrack_hint: false,
             animations_enabled,
+            interrupt_key: keymap::INTERRUPT,
             context_window_percent: None,
             context_window_used_tokens: None,
         }
     }
 
+    /// Rebind the key that interrupts a running task and update the status row hint.
+    pub(crate) fn set_interrupt_key(&mut self, key: KeyBinding) {
+        self.interrupt_key = key;
+        if let Some(status) = self.status.as_mut() {
+            status.set_interrupt_key(key);
+        }
+        self.request_redraw();
+    }
+
     pub fn set_skills(&mut self, skills: Option<Vec<SkillMetadata>>) {
         self.composer.set_skill_mentions(skills);
         self.request_redraw();
@@ -411,11 +443,10 @@ impl BottomPane {
                 .and_then(parse_slash_name)
                 .is_some_and(|(name, _, _)| name == "agent");
 
-            // If a task is running and a status line is visible, allow Esc to
-            // send an interrupt even while the composer has focus.
+            // If a task is running and a status line is visible, allow the interrupt key
+            // (Esc by default) to send an interrupt even while the composer has focus.
             // When a popup is active, prefer dismissing it over interrupting the task.
-            if key_event.code == KeyCode::Esc
-                && matches!(key_event.kind, KeyEventKind::Press | KeyEventKind::Repeat)
+            if self.interrupt_key.is_press(key_event)
                 && self.is_task_running
                 && !is_agent_command
                 && !self.composer.popup_active()
@@ -630,6 +661,14 @@ impl BottomPane {
         }
     }
 
+    /// Show (or clear) a live countdown to the next retry of a throttled turn.
+    pub(crate) fn set_status_retry_at(&mut self, retry_at: Option<Instant>) {
+        if let Some(status) = self.status.as_mut() {
+            status.set_retry_at(retry_at);
+            self.request_redraw();
+        }
+    }
+
     /// Show the transient "press again to quit" hint for `key`.
     ///
     /// `ChatWidget` owns the quit shortcut state machine (it decides when quit is
@@ -705,6 +744,9 @@ impl BottomPane {
                         self.frame_requester.clone(),
                         self.animations_enabled,
                     ));
+                    if let Some(status) = self.status.as_mut() {
+                        status.set_interrupt_key(self.interrupt_key);
+                    }
                 }
                 if let Some(status) = self.status.as_mut() {
                     status.set_interrupt_hint_visible(true);
@@ -732,6 +774,9 @@ impl BottomPane {
                 self.frame_requester.clone(),
                 self.animations_enabled,
             ));
+            if let Some(status) = self.status.as_mut() {
+                status.set_interrupt_key(self.interrupt_key);
+            }
             self.sync_status_inline_message();
             self.request_redraw();
         }
@@ -757,6 +802,30 @@ impl BottomPane {
         self.request_redraw();
     }
 
+    /// Color the context indicator by how far usage is past the warning thresholds.
+    pub(crate) fn set_context_warning(&mut self, warning: Option<ContextWarningLevel>) {
+        self.composer.set_context_warning(warning);
+        self.request_redraw();
+    }
+
+    /// Update the pinned-files badge next to the context indicator.
+    pub(crate) fn set_pinned_files(&mut self, pinned_files: Option<PinnedFilesSummary>) {
+        self.composer.set_pinned_files(pinned_files);
+        self.request_redraw();
+    }
+
+    /// Show the "READ ONLY" badge for sessions started with `--read-only`.
+    pub(crate) fn set_read_only(&mut self, read_only: bool) {
+        self.composer.set_read_only(read_only);
+        self.request_redraw();
+    }
+
+    /// Update the current plan step shown next to the context indicator.
+    pub(crate) fn set_plan_step(&mut self, plan_step: Option<PlanStepSummary>) {
+        self.composer.set_plan_step(plan_step);
+        self.request_redraw();
+    }
+
     /// Show a generic list selection view with the provided items.
     pub(crate) fn show_selection_view(&mut self, params: list_selection_view::SelectionViewParams) {
         let view = list_selection_view::ListSelectionView::new(params, self.app_event_tx.clone());
@@ -794,6 +863,14 @@ impl BottomPane {
         self.request_redraw();
     }
 
+    /// Show or hide the "offline" banner above queued messages.
+    pub(crate) fn set_offline(&mut self, offline: bool) {
+        if self.pending_input_preview.offline != offline {
+            self.pending_input_preview.offline = offline;
+            self.request_redraw();
+        }
+    }
+
     /// Update the inactive-thread approval list shown above the composer.
     pub(crate) fn set_pending_thread_approvals(&mut self, threads: Vec<String>) {
         if self.pending_thread_approvals.set_threads(threads) {
@@ -801,6 +878,13 @@ impl BottomPane {
         }
     }
 
+    pub(crate) fn set_auth_expiry_warning(&mut self, warning: Option<String>) {
+        if self.auth_expiry_warning != warning {
+            self.auth_expiry_warning = warning;
+            self.request_redraw();
+        }
+    }
+
     #[cfg(test)]
     pub(crate) fn pending_thread_approvals(&self) -> &[String] {
         self.pending_thread_approvals.threads()
@@ -841,6 +925,26 @@ impl BottomPane {
         self.is_task_running
     }
 
+    /// Built-in slash commands enabled by the current feature flags, in popup order.
+    pub(crate) fn available_slash_commands(&self) -> Vec<(&'static str, SlashCommand)> {
+        slash_commands::builtins_for_input(self.composer.builtin_command_flags())
+    }
+
+    /// One line for each approval request still waiting for the user.
+    pub(crate) fn pending_approval_summaries(&self) -> Vec<String> {
+        self.view_stack
+            .iter()
+            .flat_map(|view| view.pending_approval_summaries())
+            .collect()
+    }
+
+    /// Whether an approval request is waiting for the user.
+    pub(crate) fn is_awaiting_approval(&self) -> bool {
+        self.view_stack
+            .iter()
+            .any(|view| view.view_id() == Some(approval_overlay::APPROVAL_OVERLAY_VIEW_ID))
+    }
+
     #[cfg(test)]
     pub(crate) fn has_active_view(&self) -> bool {
         !self.view_stack.is_empty()
@@ -1000,6 +1104,26 @@ impl BottomPane {
         }
     }
 
+    pub(crate) fn attach_pasted_file(&mut self, saved: &SavedPaste) {
+        self.composer.attach_pasted_file(saved);
+        self.request_redraw();
+    }
+
+    pub(crate) fn misspelling_near_cursor(&self) -> Option<(Range<usize>, String)> {
+        self.composer.misspelling_near_cursor()
+    }
+
+    pub(crate) fn replace_misspelling(
+        &mut self,
+        range: Range<usize>,
+        word: &str,
+        replacement: &str,
+    ) {
+        if self.composer.replace_misspelling(range, word, replacement) {
+            self.request_redraw();
+        }
+    }
+
     #[cfg(test)]
     pub(crate) fn take_recent_submission_images(&mut self) -> Vec<PathBuf> {
         self.composer.take_recent_submission_images()
@@ -1051,12 +1175,16 @@ impl BottomPane {
             }
             let mut flex2 = FlexRenderable::new();
             flex2.push(1, RenderableItem::Owned(flex.into()));
+            if let Some(warning) = &self.auth_expiry_warning {
+                let line = Line::from(vec!["  ⚠ ".red().bold(), warning.clone().red()]);
+                flex2.push(0, RenderableItem::Owned(line.into()));
+            }
             flex2.push(0, RenderableItem::Borrowed(&self.composer));
             RenderableItem::Owned(Box::new(flex2))
         }
     }
 
-    pub(crate) fn set_status_line(&mut self, status_line: Option<Line<'static>>) {
+    pub(crate) fn set_status_line(&mut self, status_line: Option<StatusLine>) {
         if self.composer.set_status_line(status_line) {
             self.request_redraw();
         }
@@ -1067,6 +1195,11 @@ impl BottomPane {
             self.request_redraw();
         }
     }
+
+    pub(crate) fn set_footer_hints(&mut self, hints: FooterHints) {
+        self.composer.set_footer_hints(hints);
+        self.request_redraw();
+    }
 }
 
 #[cfg(not(target_os = "linux"))]
@@ -1370,6 +1503,34 @@ mod tests {
         assert_snapshot!("status_only_snapshot", render_snapshot(&pane, area));
     }
 
+    #[test]
+    fn auth_expiry_warning_renders_above_composer() {
+        let (tx_raw, _rx) = unbounded_channel::<AppEvent>();
+        let tx = AppEventSender::new(tx_raw);
+        let mut pane = BottomPane::new(BottomPaneParams {
+            app_event_tx: tx,
+            frame_requester: FrameRequester::test_dummy(),
+            has_input_focus: true,
+            enhanced_keys_supported: false,
+            placeholder_text: "Ask Codex to do anything".to_string(),
+            disable_paste_burst: false,
+            animations_enabled: true,
+            skills: Some(Vec::new()),
+        });
+
+        pane.set_auth_expiry_warning(Some(
+            "ChatGPT sign-in expires in 4m 05s · /login status to refresh".to_string(),
+        ));
+
+        let width = 70;
+        let height = pane.desired_height(width);
+        let area = Rect::new(0, 0, width, height);
+        assert_snapshot!(
+            "auth_expiry_warning_renders_above_composer",
+            render_snapshot(&pane, area)
+        );
+    }
+
     #[test]
     fn unified_exec_summary_does_not_increase_height_when_status_visible() {
         let (tx_raw, _rx) = unbounded_channel::<AppEvent>();
@@ -1587,6 +1748,39 @@ mod tests {
         );
     }
 
+    #[test]
+    fn configured_interrupt_key_replaces_esc() {
+        let (tx_raw, mut rx) = unbounded_channel::<AppEvent>();
+        let tx = AppEventSender::new(tx_raw);
+        let mut pane = BottomPane::new(BottomPaneParams {
+            app_event_tx: tx,
+            frame_requester: FrameRequester::test_dummy(),
+            has_input_focus: true,
+            enhanced_keys_supported: false,
+            placeholder_text: "Ask Codex to do anything".to_string(),
+            disable_paste_burst: false,
+            animations_enabled: true,
+            skills: Some(Vec::new()),
+        });
+        pane.set_interrupt_key(key_hint::ctrl(KeyCode::Char('x')));
+        pane.set_task_running(true);
+
+        pane.handle_key_event(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+        while let Ok(ev) = rx.try_recv() {
+            assert!(
+                !matches!(ev, AppEvent::CodexOp(Op::Interrupt)),
+                "expected Esc to no longer interrupt once rebound"
+            );
+        }
+
+        pane.handle_key_event(KeyEvent::new(KeyCode::Char('x'), KeyModifiers::CONTROL));
+        let mut interrupted = false;
+        while let Ok(ev) = rx.try_recv() {
+            interrupted |= matches!(ev, AppEvent::CodexOp(Op::Interrupt));
+        }
+        assert!(interrupted, "expected ctrl + x to send Op::Interrupt");
+    }
+
     #[test]
     fn esc_with_slash_command_popup_does_not_interrupt_task() {
         let (tx_raw, mut rx) = unbounded_channel::<AppEvent>();
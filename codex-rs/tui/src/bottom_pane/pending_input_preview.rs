@@ -16,10 +16,12 @@ use crate::wrapping::adaptive_wrap_lines;
 /// shows the edit hint at the bottom (e.g. "⌥ + ↑ edit") when there are actual
 /// queued user messages to pop back into the composer. Because some terminals
 /// intercept certain modifier-key combinations, the displayed binding is
-/// configurable via [`set_edit_binding`](Self::set_edit_binding).
+/// configurable via [`set_edit_binding`](Self::set_edit_binding). While `offline` is set, a
+/// banner above the list explains that queued messages are held until the provider is reachable.
 pub(crate) struct PendingInputPreview {
     pub pending_steers: Vec<String>,
     pub queued_messages: Vec<String>,
+    pub offline: bool,
     /// Key combination rendered in the hint line.  Defaults to Alt+Up but may
     /// be overridden for terminals where that chord is unavailable.
     edit_binding: key_hint::KeyBinding,
@@ -30,6 +32,7 @@ impl PendingInputPreview {
         Self {
             pending_steers: Vec::new(),
             queued_messages: Vec::new(),
+            offline: false,
             edit_binding: key_hint::alt(KeyCode::Up),
         }
     }
@@ -42,12 +45,21 @@ impl PendingInputPreview {
     }
 
     fn as_renderable(&self, width: u16) -> Box<dyn Renderable> {
-        if (self.pending_steers.is_empty() && self.queued_messages.is_empty()) || width < 4 {
+        if (self.pending_steers.is_empty() && self.queued_messages.is_empty() && !self.offline)
+            || width < 4
+        {
             return Box::new(());
         }
 
         let mut lines = vec![];
 
+        if self.offline {
+            lines.push(Line::from(vec![
+                "  ⚠ ".magenta(),
+                "offline — will send when reconnected".magenta(),
+            ]));
+        }
+
         for steer in &self.pending_steers {
             let wrapped = adaptive_wrap_lines(
                 steer
@@ -130,6 +142,15 @@ mod tests {
         assert_eq!(queue.desired_height(40), 2);
     }
 
+    #[test]
+    fn desired_height_offline_banner() {
+        let mut queue = PendingInputPreview::new();
+        queue.offline = true;
+        assert_eq!(queue.desired_height(40), 1);
+        queue.queued_messages.push("Hello, world!".to_string());
+        assert_eq!(queue.desired_height(40), 3);
+    }
+
     #[test]
     fn render_one_message() {
         let mut queue = PendingInputPreview::new();
@@ -155,6 +176,18 @@ mod tests {
         assert_snapshot!("render_two_messages", format!("{buf:?}"));
     }
 
+    #[test]
+    fn render_offline_banner() {
+        let mut queue = PendingInputPreview::new();
+        queue.offline = true;
+        queue.queued_messages.push("Hello, world!".to_string());
+        let width = 40;
+        let height = queue.desired_height(width);
+        let mut buf = Buffer::empty(Rect::new(0, 0, width, height));
+        queue.render(Rect::new(0, 0, width, height), &mut buf);
+        assert_snapshot!("render_offline_banner", format!("{buf:?}"));
+    }
+
     #[test]
     fn render_more_than_three_messages() {
         let mut queue = PendingInputPreview::new();
@@ -0,0 +1,202 @@
+//! Status line content laid out in rows of aligned segments.
+//!
+//! `ChatWidget` resolves the configured items into text and groups them into rows with left,
+//! center, and right segments. The footer places each row in whatever width it has at render
+//! time: the first row shares its line with the mode indicator, and further rows sit below it.
+
+use crate::line_truncation::truncate_line_with_ellipsis_if_overflow;
+use ratatui::text::Line;
+use ratatui::text::Span;
+
+/// Rows beyond this are not shown.
+pub(crate) const STATUS_LINE_MAX_ROWS: usize = 3;
+
+/// Minimum columns between neighbouring segments.
+const SEGMENT_GAP_COLS: usize = 2;
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct StatusLineRow {
+    pub(crate) left: Line<'static>,
+    pub(crate) center: Line<'static>,
+    pub(crate) right: Line<'static>,
+}
+
+impl StatusLineRow {
+    fn is_empty(&self) -> bool {
+        self.left.width() == 0 && !self.has_aligned_segments()
+    }
+
+    /// Whether the row has center or right content, which is placed relative to the row width.
+    pub(crate) fn has_aligned_segments(&self) -> bool {
+        self.center.width() > 0 || self.right.width() > 0
+    }
+
+    /// The row with one gap between segments, for callers that do not lay it out.
+    pub(crate) fn unaligned(&self) -> Line<'static> {
+        let mut spans: Vec<Span<'static>> = Vec::new();
+        for segment in [&self.left, &self.center, &self.right] {
+            if segment.width() == 0 {
+                continue;
+            }
+            if !spans.is_empty() {
+                spans.push(" ".repeat(SEGMENT_GAP_COLS).into());
+            }
+            spans.extend(segment.spans.iter().cloned());
+        }
+        Line::from(spans)
+    }
+
+    /// Place the segments within `width` columns.
+    ///
+    /// The right segment ends at the last column and the center segment is centered on the whole
+    /// row when it clears both neighbours. When space runs out the center segment is dropped
+    /// first, then the left segment is truncated, and finally the right segment itself.
+    pub(crate) fn layout(&self, width: usize) -> Line<'static> {
+        if !self.has_aligned_segments() {
+            return truncate_line_with_ellipsis_if_overflow(self.left.clone(), width);
+        }
+
+        let right = truncate_line_with_ellipsis_if_overflow(self.right.clone(), width);
+        let right_width = right.width();
+        let right_gap = if right_width > 0 { SEGMENT_GAP_COLS } else { 0 };
+        let right_start = width.saturating_sub(right_width + right_gap);
+        let left = truncate_line_with_ellipsis_if_overflow(self.left.clone(), right_start);
+        let left_width = left.width();
+        let mut used = left_width;
+        let mut spans = left.spans;
+
+        let center_width = self.center.width();
+        let center_start = width.saturating_sub(center_width) / 2;
+        let left_end = if left_width > 0 {
+            left_width + SEGMENT_GAP_COLS
+        } else {
+            0
+        };
+        if center_width > 0
+            && center_start >= left_end
+            && center_start + center_width <= right_start
+        {
+            spans.push(" ".repeat(center_start - used).into());
+            spans.extend(self.center.spans.iter().cloned());
+            used = center_start + center_width;
+        }
+
+        if right_width > 0 {
+            spans.push(" ".repeat(width.saturating_sub(used + right_width)).into());
+            spans.extend(right.spans);
+        }
+        Line::from(spans)
+    }
+}
+
+/// The rows currently shown in the status line; never empty.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct StatusLine {
+    rows: Vec<StatusLineRow>,
+}
+
+impl StatusLine {
+    /// Keep the non-empty rows, up to [`STATUS_LINE_MAX_ROWS`]; `None` if nothing is left.
+    pub(crate) fn new(rows: Vec<StatusLineRow>) -> Option<Self> {
+        let rows: Vec<StatusLineRow> = rows
+            .into_iter()
+            .filter(|row| !row.is_empty())
+            .take(STATUS_LINE_MAX_ROWS)
+            .collect();
+        if rows.is_empty() {
+            None
+        } else {
+            Some(Self { rows })
+        }
+    }
+
+    pub(crate) fn rows(&self) -> &[StatusLineRow] {
+        &self.rows
+    }
+
+    pub(crate) fn first_row(&self) -> &StatusLineRow {
+        &self.rows[0]
+    }
+}
+
+impl From<Line<'static>> for StatusLine {
+    fn from(left: Line<'static>) -> Self {
+        Self {
+            rows: vec![StatusLineRow {
+                left,
+                ..Default::default()
+            }],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn text(line: &Line<'_>) -> String {
+        line.spans
+            .iter()
+            .map(|span| span.content.as_ref())
+            .collect()
+    }
+
+    fn row(left: &str, center: &str, right: &str) -> StatusLineRow {
+        StatusLineRow {
+            left: Line::from(left.to_string()),
+            center: Line::from(center.to_string()),
+            right: Line::from(right.to_string()),
+        }
+    }
+
+    #[test]
+    fn layout_aligns_segments_across_the_width() {
+        let row = row("gpt-5", "main", "42% left");
+
+        assert_eq!(text(&row.layout(30)), "gpt-5        main     42% left");
+        assert_eq!(text(&row.layout(21)), "gpt-5        42% left");
+    }
+
+    #[test]
+    fn layout_truncates_left_before_right() {
+        let row = row("gpt-5 high · ~/code/project", "", "42% left");
+
+        assert_eq!(text(&row.layout(24)), "gpt-5 high · …  42% left");
+        assert_eq!(text(&row.layout(6)), "42% l…");
+    }
+
+    #[test]
+    fn left_only_rows_keep_single_line_truncation() {
+        let row = row("gpt-5 high · ~/code/project", "", "");
+
+        assert_eq!(text(&row.layout(12)), "gpt-5 high …");
+        assert_eq!(
+            row.layout(12),
+            truncate_line_with_ellipsis_if_overflow(row.left.clone(), 12)
+        );
+    }
+
+    #[test]
+    fn new_drops_empty_rows_and_caps_the_count() {
+        let rows = vec![
+            row("a", "", ""),
+            StatusLineRow::default(),
+            row("", "", "b"),
+            row("c", "", ""),
+            row("d", "", ""),
+        ];
+
+        let status_line = StatusLine::new(rows).expect("status line");
+
+        assert_eq!(
+            status_line
+                .rows()
+                .iter()
+                .map(|row| text(&row.unaligned()))
+                .collect::<Vec<_>>(),
+            vec!["a", "b", "c"]
+        );
+        assert_eq!(StatusLine::new(vec![StatusLineRow::default()]), None);
+    }
+}
@@ -13,7 +13,7 @@
 //! - Directory paths (current dir, project root)
 //! - Git information (branch name)
 //! - Context usage (remaining %, used %, window size)
-//! - Usage limits (5-hour, weekly)
+//! - Usage limits (5-hour, weekly, provider API quota)
 //! - Session info (ID, tokens used)
 //! - Application version
 
@@ -74,6 +74,9 @@ pub(crate) enum StatusLineItem {
     /// Remaining usage on the weekly rate limit.
     WeeklyLimit,
 
+    /// Remaining provider API quota from `x-ratelimit-*` headers, with its reset time.
+    ApiLimit,
+
     /// Codex application version.
     CodexVersion,
 
@@ -114,6 +117,9 @@ impl StatusLineItem {
             StatusLineItem::WeeklyLimit => {
                 "Remaining usage on weekly usage limit (omitted when unavailable)"
             }
+            StatusLineItem::ApiLimit => {
+                "Remaining provider API quota and reset time (omitted when unavailable)"
+            }
             StatusLineItem::CodexVersion => "Codex application version",
             StatusLineItem::ContextWindowSize => {
                 "Total context window size in tokens (omitted when unknown)"
@@ -142,6 +148,7 @@ impl StatusLineItem {
             StatusLineItem::ContextUsed => "82% used",
             StatusLineItem::FiveHourLimit => "5h 100%",
             StatusLineItem::WeeklyLimit => "weekly 98%",
+            StatusLineItem::ApiLimit => "api 75% (resets 14:03)",
             StatusLineItem::CodexVersion => "v0.93.0",
             StatusLineItem::ContextWindowSize => "258K window",
             StatusLineItem::UsedTokens => "27.3K used",
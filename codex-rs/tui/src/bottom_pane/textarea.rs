@@ -20,6 +20,7 @@ use crossterm::event::KeyModifiers;
 use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
 use ratatui::style::Color;
+use ratatui::style::Modifier;
 use ratatui::style::Style;
 use ratatui::widgets::StatefulWidgetRef;
 use ratatui::widgets::WidgetRef;
@@ -67,6 +68,9 @@ pub(crate) struct TextArea {
     elements: Vec<TextElement>,
     next_element_id: u64,
     kill_buffer: String,
+    /// Underline words the spellchecker rejects; see [`Self::set_spellcheck`].
+    spellcheck: bool,
+    spellcheck_cache: RefCell<Option<SpellcheckCache>>,
 }
 
 #[derive(Debug, Clone)]
@@ -75,6 +79,13 @@ struct WrapCache {
     lines: Vec<Range<usize>>,
 }
 
+#[derive(Debug, Clone)]
+struct SpellcheckCache {
+    text: String,
+    generation: u64,
+    ranges: Vec<Range<usize>>,
+}
+
 #[derive(Debug, Default, Clone, Copy)]
 pub(crate) struct TextAreaState {
     /// Index into wrapped lines of the first visible line.
@@ -91,9 +102,63 @@ impl TextArea {
             elements: Vec::new(),
             next_element_id: 1,
             kill_buffer: String::new(),
+            spellcheck: false,
+            spellcheck_cache: RefCell::new(None),
         }
     }
 
+    /// Opt into underlining misspelled words. Nothing is marked unless the spellchecker was
+    /// loaded at startup (`tui.spellcheck.enabled`).
+    pub(crate) fn set_spellcheck(&mut self, enabled: bool) {
+        self.spellcheck = enabled;
+    }
+
+    /// The misspelled word under the cursor, or else the nearest one before it.
+    pub(crate) fn misspelling_near_cursor(&self) -> Option<Range<usize>> {
+        let ranges = self.misspellings();
+        ranges
+            .iter()
+            .find(|range| range.start <= self.cursor_pos && self.cursor_pos <= range.end)
+            .or_else(|| {
+                ranges
+                    .iter()
+                    .rev()
+                    .find(|range| range.end <= self.cursor_pos)
+            })
+            .cloned()
+    }
+
+    /// Misspelled ranges outside placeholder elements, cached until the text or the personal
+    /// dictionary changes.
+    fn misspellings(&self) -> Vec<Range<usize>> {
+        let Some(checker) = self.spellcheck.then(crate::spellcheck::get).flatten() else {
+            return Vec::new();
+        };
+        let generation = checker.generation();
+        if let Some(cache) = self.spellcheck_cache.borrow().as_ref()
+            && cache.generation == generation
+            && cache.text == self.text
+        {
+            return cache.ranges.clone();
+        }
+        let ranges: Vec<Range<usize>> = checker
+            .misspelled_ranges(&self.text)
+            .into_iter()
+            .filter(|range| {
+                !self
+                    .elements
+                    .iter()
+                    .any(|elem| elem.range.start < range.end && range.start < elem.range.end)
+            })
+            .collect();
+        *self.spellcheck_cache.borrow_mut() = Some(SpellcheckCache {
+            text: self.text.clone(),
+            generation,
+            ranges: ranges.clone(),
+        });
+        ranges
+    }
+
     /// Replace the visible textarea text and clear any existing text elements.
     ///
     /// This is the "fresh buffer" path for callers that want plain text with no placeholder
@@ -1366,6 +1431,7 @@ impl TextArea {
         lines: &[Range<usize>],
         range: std::ops::Range<usize>,
     ) {
+        let misspellings = self.misspellings();
         for (row, idx) in range.enumerate() {
             let r = &lines[idx];
             let y = area.y + row as u16;
@@ -1386,6 +1452,26 @@ impl TextArea {
                 let style = Style::default().fg(Color::Cyan);
                 buf.set_string(area.x + x_off, y, styled, style);
             }
+
+            for word in &misspellings {
+                // The word being typed is not marked until the cursor leaves it.
+                if word.start <= self.cursor_pos && self.cursor_pos <= word.end {
+                    continue;
+                }
+                let overlap_start = word.start.max(line_range.start);
+                let overlap_end = word.end.min(line_range.end);
+                if overlap_start >= overlap_end {
+                    continue;
+                }
+                let x_off = self.text[line_range.start..overlap_start].width() as u16;
+                let width = self.text[overlap_start..overlap_end].width() as u16;
+                buf.set_style(
+                    Rect::new(area.x + x_off, y, width, 1),
+                    Style::default()
+                        .fg(Color::Red)
+                        .add_modifier(Modifier::UNDERLINED),
+                );
+            }
         }
     }
 
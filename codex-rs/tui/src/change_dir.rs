@@ -0,0 +1,143 @@
+//! Target resolution for `/cd`.
+//!
+//! Under `workspace-write`, the session cwd is itself a writable root, so moving it
+//! somewhere new would quietly widen what the sandbox lets commands write. `/cd` therefore
+//! only accepts directories that are already writable under the current policy; other
+//! policies do not derive anything from the cwd and accept any directory. The directory being
+//! left stays a writable root, so moving into a subdirectory does not narrow the sandbox and
+//! `/cd ..` can come back.
+
+use std::path::Path;
+use std::path::PathBuf;
+
+use codex_protocol::protocol::SandboxPolicy;
+use codex_utils_absolute_path::AbsolutePathBuf;
+
+/// Resolve the argument to `/cd` against `cwd` and check it against `sandbox_policy`.
+pub(crate) fn resolve_cd_target(
+    arg: &str,
+    cwd: &Path,
+    sandbox_policy: &SandboxPolicy,
+) -> Result<PathBuf, String> {
+    let arg = arg.trim();
+    let target = match arg.strip_prefix('~') {
+        Some(rest) if rest.is_empty() || rest.starts_with('/') => {
+            let home = dirs::home_dir()
+                .ok_or_else(|| "could not determine the home directory".to_string())?;
+            home.join(rest.trim_start_matches('/'))
+        }
+        _ => cwd.join(arg),
+    };
+    let target = std::fs::canonicalize(&target)
+        .map_err(|err| format!("cannot change directory to {}: {err}", target.display()))?;
+    if !target.is_dir() {
+        return Err(format!("{} is not a directory", target.display()));
+    }
+
+    if matches!(sandbox_policy, SandboxPolicy::WorkspaceWrite { .. })
+        && !sandbox_policy
+            .get_writable_roots_with_cwd(cwd)
+            .iter()
+            .any(|root| root.is_path_writable(&target))
+    {
+        return Err(format!(
+            "{} is outside the sandbox's writable roots; restart Codex there or add it with --add-dir",
+            target.display()
+        ));
+    }
+    Ok(target)
+}
+
+/// The workspace-write `sandbox_policy` with `cwd` added to its writable roots, for when `/cd`
+/// moves away from `cwd`. `None` when nothing needs to change: other policies, or `cwd` already
+/// lies under a configured writable root.
+pub(crate) fn keep_cwd_writable(
+    sandbox_policy: &SandboxPolicy,
+    cwd: &Path,
+) -> Option<SandboxPolicy> {
+    let SandboxPolicy::WorkspaceWrite { writable_roots, .. } = sandbox_policy else {
+        return None;
+    };
+    if writable_roots
+        .iter()
+        .any(|root| cwd.starts_with(root.as_path()))
+    {
+        return None;
+    }
+    let cwd = AbsolutePathBuf::from_absolute_path(cwd).ok()?;
+    let mut policy = sandbox_policy.clone();
+    if let SandboxPolicy::WorkspaceWrite { writable_roots, .. } = &mut policy {
+        writable_roots.push(cwd);
+    }
+    Some(policy)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::protocol::ReadOnlyAccess;
+    use pretty_assertions::assert_eq;
+
+    /// Workspace-write without the temp directories, which the test dirs live under.
+    fn workspace_only_policy() -> SandboxPolicy {
+        SandboxPolicy::WorkspaceWrite {
+            writable_roots: Vec::new(),
+            read_only_access: ReadOnlyAccess::FullAccess,
+            network_access: false,
+            exclude_tmpdir_env_var: true,
+            exclude_slash_tmp: true,
+        }
+    }
+
+    #[test]
+    fn resolves_relative_paths_inside_the_workspace() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let root = std::fs::canonicalize(dir.path()).expect("canonicalize");
+        std::fs::create_dir(root.join("crates")).expect("mkdir");
+        let policy = workspace_only_policy();
+
+        assert_eq!(
+            resolve_cd_target("crates", &root, &policy),
+            Ok(root.join("crates"))
+        );
+        assert_eq!(
+            resolve_cd_target("./crates/../crates", &root, &policy),
+            Ok(root.join("crates"))
+        );
+        assert!(resolve_cd_target("missing", &root, &policy).is_err());
+    }
+
+    #[test]
+    fn moving_into_a_subdirectory_keeps_the_workspace_reachable() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let root = std::fs::canonicalize(dir.path()).expect("canonicalize");
+        std::fs::create_dir(root.join("sub")).expect("mkdir");
+        let policy = workspace_only_policy();
+
+        let sub = resolve_cd_target("sub", &root, &policy).expect("cd sub");
+        assert!(resolve_cd_target("..", &sub, &policy).is_err());
+
+        let policy = keep_cwd_writable(&policy, &root).expect("root pinned");
+        assert_eq!(resolve_cd_target("..", &sub, &policy), Ok(root.clone()));
+        assert_eq!(keep_cwd_writable(&policy, &root), None);
+        assert_eq!(keep_cwd_writable(&policy, &sub), None);
+        assert_eq!(
+            keep_cwd_writable(&SandboxPolicy::DangerFullAccess, &root),
+            None
+        );
+    }
+
+    #[test]
+    fn workspace_write_rejects_directories_outside_writable_roots() {
+        let workspace = tempfile::tempdir().expect("tempdir");
+        let outside = tempfile::tempdir().expect("tempdir");
+        let outside_path = std::fs::canonicalize(outside.path()).expect("canonicalize");
+        let arg = outside_path.to_string_lossy().to_string();
+
+        assert!(resolve_cd_target(&arg, workspace.path(), &workspace_only_policy()).is_err());
+        assert_eq!(
+            resolve_cd_target(&arg, workspace.path(), &SandboxPolicy::DangerFullAccess),
+            Ok(outside_path)
+        );
+    }
+}
@@ -29,6 +29,7 @@ use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::collections::VecDeque;
+use std::ops::Range;
 use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -41,21 +42,40 @@ use self::realtime::PendingSteerCompareKey;
 use crate::app_event::RealtimeAudioDeviceKind;
 #[cfg(all(not(target_os = "linux"), feature = "voice-input"))]
 use crate::audio_device::list_realtime_audio_device_names;
+use crate::bottom_pane::STATUS_LINE_MAX_ROWS;
+use crate::bottom_pane::StatusLine;
 use crate::bottom_pane::StatusLineItem;
+use crate::bottom_pane::StatusLineRow;
 use crate::bottom_pane::StatusLineSetupView;
+use crate::status::PROVIDER_RATE_LIMIT_ID;
 use crate::status::RateLimitWindowDisplay;
 use crate::status::format_directory_display;
 use crate::status::format_tokens_compact;
 use crate::status::rate_limit_snapshot_display_for_limit;
+use crate::status_line_command;
+use crate::status_line_command::StatusLineCommands;
+use crate::status_line_command::StatusLineContextWindow;
+use crate::status_line_command::StatusLineInput;
 use crate::text_formatting::proper_join;
 use crate::version::CODEX_CLI_VERSION;
 use codex_app_server_protocol::ConfigLayerSource;
 use codex_backend_client::Client as BackendClient;
 use codex_chatgpt::connectors;
+use codex_core::CarryOverBriefing;
+use codex_core::ConflictHunk;
+use codex_core::ConflictResolution;
+use codex_core::NextTurnContext;
+use codex_core::PullRequest;
+use codex_core::SessionSummary;
+use codex_core::carry_over_briefing;
+use codex_core::compare_rollouts;
 use codex_core::config::Config;
 use codex_core::config::Constrained;
 use codex_core::config::ConstraintResult;
+use codex_core::config::types::HistoryTimestamps;
 use codex_core::config::types::Notifications;
+use codex_core::config::types::StatusLineCommandConfig;
+use codex_core::config::types::StatusLineRowConfig;
 use codex_core::config::types::WindowsSandboxModeToml;
 use codex_core::config_loader::ConfigLayerStackOrdering;
 use codex_core::features::FEATURES;
@@ -64,10 +84,17 @@ use codex_core::find_thread_name_by_id;
 use codex_core::git_info::current_branch_name;
 use codex_core::git_info::get_git_repo_root;
 use codex_core::git_info::local_git_branches;
+use codex_core::load_session_stats;
 use codex_core::mcp::McpManager;
 use codex_core::models_manager::manager::ModelsManager;
 use codex_core::plugins::PluginsManager;
+use codex_core::post_pull_request_comment;
 use codex_core::project_doc::DEFAULT_PROJECT_DOC_FILENAME;
+use codex_core::read_session_summary;
+use codex_core::review_diff::ReviewDiff;
+use codex_core::review_diff::diff_review_request;
+use codex_core::review_diff::load_review_diff;
+use codex_core::review_format::format_review_markdown;
 use codex_core::skills::model::SkillMetadata;
 use codex_core::terminal::TerminalName;
 use codex_core::terminal::terminal_info;
@@ -82,6 +109,7 @@ use codex_protocol::config_types::CollaborationMode;
 use codex_protocol::config_types::CollaborationModeMask;
 use codex_protocol::config_types::ModeKind;
 use codex_protocol::config_types::Personality;
+use codex_protocol::config_types::SamplingParams;
 use codex_protocol::config_types::ServiceTier;
 use codex_protocol::config_types::Settings;
 #[cfg(target_os = "windows")]
@@ -100,6 +128,8 @@ use codex_protocol::protocol::AgentReasoningRawContentEvent;
 use codex_protocol::protocol::ApplyPatchApprovalRequestEvent;
 use codex_protocol::protocol::BackgroundEventEvent;
 use codex_protocol::protocol::CodexErrorInfo;
+use codex_protocol::protocol::CompactionReviewDecision;
+use codex_protocol::protocol::CompactionReviewRequestEvent;
 use codex_protocol::protocol::CreditsSnapshot;
 use codex_protocol::protocol::DeprecationNoticeEvent;
 use codex_protocol::protocol::ErrorEvent;
@@ -111,6 +141,7 @@ use codex_protocol::protocol::ExecCommandEndEvent;
 use codex_protocol::protocol::ExecCommandOutputDeltaEvent;
 use codex_protocol::protocol::ExecCommandSource;
 use codex_protocol::protocol::ExitedReviewModeEvent;
+use codex_protocol::protocol::FileChange;
 use codex_protocol::protocol::ImageGenerationBeginEvent;
 use codex_protocol::protocol::ImageGenerationEndEvent;
 use codex_protocol::protocol::ListCustomPromptsResponseEvent;
@@ -123,9 +154,12 @@ use codex_protocol::protocol::McpToolCallBeginEvent;
 use codex_protocol::protocol::McpToolCallEndEvent;
 use codex_protocol::protocol::Op;
 use codex_protocol::protocol::PatchApplyBeginEvent;
+use codex_protocol::protocol::PlanApprovalRequestEvent;
 use codex_protocol::protocol::RateLimitSnapshot;
+use codex_protocol::protocol::ReviewOutputEvent;
 use codex_protocol::protocol::ReviewRequest;
 use codex_protocol::protocol::ReviewTarget;
+use codex_protocol::protocol::RolloutLine;
 use codex_protocol::protocol::SkillMetadata as ProtocolSkillMetadata;
 use codex_protocol::protocol::StreamErrorEvent;
 use codex_protocol::protocol::TerminalInteractionEvent;
@@ -134,6 +168,7 @@ use codex_protocol::protocol::TokenUsageInfo;
 use codex_protocol::protocol::TurnAbortReason;
 use codex_protocol::protocol::TurnCompleteEvent;
 use codex_protocol::protocol::TurnDiffEvent;
+use codex_protocol::protocol::TurnPhase;
 use codex_protocol::protocol::UndoCompletedEvent;
 use codex_protocol::protocol::UndoStartedEvent;
 use codex_protocol::protocol::UserMessageEvent;
@@ -159,6 +194,7 @@ use ratatui::style::Stylize;
 use ratatui::text::Line;
 use ratatui::widgets::Paragraph;
 use ratatui::widgets::Wrap;
+use tokio::sync::Notify;
 use tokio::sync::mpsc::UnboundedSender;
 use tokio::task::JoinHandle;
 use tracing::debug;
@@ -167,6 +203,7 @@ use tracing::warn;
 const DEFAULT_MODEL_DISPLAY_NAME: &str = "loading";
 const PLAN_IMPLEMENTATION_TITLE: &str = "Implement this plan?";
 const PLAN_IMPLEMENTATION_YES: &str = "Yes, implement this plan";
+const PLAN_IMPLEMENTATION_EDIT_STEPS: &str = "Edit steps first";
 const PLAN_IMPLEMENTATION_NO: &str = "No, stay in Plan mode";
 const PLAN_IMPLEMENTATION_CODING_MESSAGE: &str = "Implement the plan.";
 const MULTI_AGENT_ENABLE_TITLE: &str = "Enable multi-agent?";
@@ -177,6 +214,67 @@ const PLAN_MODE_REASONING_SCOPE_TITLE: &str = "Apply reasoning change";
 const PLAN_MODE_REASONING_SCOPE_PLAN_ONLY: &str = "Apply to Plan mode override";
 const PLAN_MODE_REASONING_SCOPE_ALL_MODES: &str = "Apply to global default and Plan mode override";
 const CONNECTORS_SELECTION_VIEW_ID: &str = "connectors-selection";
+const TRIM_PICKER_MAX_ITEMS: usize = 20;
+
+/// The next reasoning effort above `current` that `preset` supports, if any.
+fn next_higher_reasoning_effort(
+    preset: &ModelPreset,
+    current: Option<ReasoningEffortConfig>,
+) -> Option<ReasoningEffortConfig> {
+    let rank = |effort: ReasoningEffortConfig| {
+        ReasoningEffortConfig::iter().position(|candidate| candidate == effort)
+    };
+    let current_rank = rank(current.unwrap_or(preset.default_reasoning_effort));
+    preset
+        .supported_reasoning_efforts
+        .iter()
+        .map(|supported| supported.effort)
+        .filter(|effort| rank(*effort) > current_rank)
+        .min_by_key(|effort| rank(*effort))
+}
+
+/// Human-readable summary of sampling overrides, e.g. `temperature=0.2, top_p=default`.
+fn sampling_label(sampling: SamplingParams) -> String {
+    if sampling.is_default() {
+        return "model defaults".to_string();
+    }
+    let fmt = |value: Option<f32>| value.map_or_else(|| "default".to_string(), |v| v.to_string());
+    format!(
+        "temperature={}, top_p={}",
+        fmt(sampling.temperature),
+        fmt(sampling.top_p)
+    )
+}
+
+/// Parse `/params` arguments (`temperature=0.2 top_p=0.9`, `top_p=default`, or `reset`)
+/// on top of the current overrides.
+fn parse_sampling_args(current: SamplingParams, args: &str) -> Result<SamplingParams, String> {
+    if args.trim().eq_ignore_ascii_case("reset") {
+        return Ok(SamplingParams::default());
+    }
+    let mut sampling = current;
+    for arg in args.split_whitespace() {
+        let Some((key, value)) = arg.split_once('=') else {
+            return Err(format!("Expected key=value, got `{arg}`"));
+        };
+        let value = if value.eq_ignore_ascii_case("default") {
+            None
+        } else {
+            Some(
+                value
+                    .parse::<f32>()
+                    .map_err(|_| format!("`{value}` is not a number"))?,
+            )
+        };
+        match key.to_ascii_lowercase().as_str() {
+            "temperature" | "temp" => sampling.temperature = value,
+            "top_p" | "top-p" => sampling.top_p = value,
+            _ => return Err(format!("Unknown parameter `{key}`")),
+        }
+    }
+    sampling.validate()?;
+    Ok(sampling)
+}
 
 /// Choose the keybinding used to edit the most-recently queued message.
 ///
@@ -209,19 +307,23 @@ fn queued_message_edit_binding_for_terminal(terminal_name: TerminalName) -> KeyB
 use crate::app_event::AppEvent;
 use crate::app_event::ConnectorsSnapshot;
 use crate::app_event::ExitMode;
+use crate::app_event::RetryOverrides;
 #[cfg(target_os = "windows")]
 use crate::app_event::WindowsSandboxEnableMode;
 use crate::app_event_sender::AppEventSender;
+use crate::audio_attachment;
 use crate::bottom_pane::ApprovalRequest;
 use crate::bottom_pane::BottomPane;
 use crate::bottom_pane::BottomPaneParams;
 use crate::bottom_pane::CancellationEvent;
 use crate::bottom_pane::CollaborationModeIndicator;
 use crate::bottom_pane::ColumnWidthMode;
+use crate::bottom_pane::ContextWarningLevel;
 use crate::bottom_pane::DOUBLE_PRESS_QUIT_SHORTCUT_ENABLED;
 use crate::bottom_pane::ExperimentalFeatureItem;
 use crate::bottom_pane::ExperimentalFeaturesView;
 use crate::bottom_pane::FeedbackAudience;
+use crate::bottom_pane::FooterHints;
 use crate::bottom_pane::InputResult;
 use crate::bottom_pane::LocalImageAttachment;
 use crate::bottom_pane::MentionBinding;
@@ -231,35 +333,53 @@ use crate::bottom_pane::SelectionItem;
 use crate::bottom_pane::SelectionViewParams;
 use crate::bottom_pane::custom_prompt_view::CustomPromptView;
 use crate::bottom_pane::popup_consts::standard_popup_hint_line;
+use crate::change_dir;
+use crate::ci_log;
 use crate::clipboard_paste::paste_image_to_temp_png;
 use crate::clipboard_text;
 use crate::collaboration_modes;
+use crate::diff_pane::DiffPane;
 use crate::diff_render::display_path_for;
 use crate::exec_cell::CommandOutput;
 use crate::exec_cell::ExecCell;
 use crate::exec_cell::new_active_exec_command;
 use crate::exec_command::strip_bash_lc_and_escape;
+use crate::file_tree::FileMark;
+use crate::file_tree::FileTreeAction;
+use crate::file_tree::FileTreeSidebar;
 use crate::get_git_diff::get_git_diff;
+use crate::get_git_diff::get_git_diff_for_path;
 use crate::history_cell;
 use crate::history_cell::AgentMessageCell;
 use crate::history_cell::HistoryCell;
 use crate::history_cell::McpToolCallCell;
 use crate::history_cell::PlainHistoryCell;
 use crate::history_cell::WebSearchCell;
+use crate::history_registry::HistoryCellRegistry;
 use crate::key_hint;
 use crate::key_hint::KeyBinding;
+use crate::keymap;
 use crate::markdown::append_markdown;
 use crate::multi_agents;
+use crate::paste_file;
+use crate::paste_file::SavedPaste;
+use crate::pinned_files::PinnedFiles;
+use crate::plan_steps::PlanSteps;
 use crate::render::Insets;
 use crate::render::renderable::ColumnRenderable;
 use crate::render::renderable::FlexRenderable;
 use crate::render::renderable::Renderable;
 use crate::render::renderable::RenderableExt;
 use crate::render::renderable::RenderableItem;
+use crate::response_diff;
+use crate::scrollback_capture;
+use crate::session_compare::resolve_session_path;
 use crate::slash_command::SlashCommand;
+use crate::spellcheck;
 use crate::status::RateLimitSnapshotDisplay;
 use crate::status_indicator_widget::STATUS_DETAILS_DEFAULT_MAX_LINES;
 use crate::status_indicator_widget::StatusDetailsCapitalization;
+use crate::status_indicator_widget::fmt_elapsed_compact;
 use crate::text_formatting::truncate_text;
 use crate::tui::FrameRequester;
 mod interrupts;
@@ -285,24 +405,36 @@ use crate::streaming::commit_tick::run_commit_tick;
 use crate::streaming::controller::PlanStreamController;
 use crate::streaming::controller::StreamController;
 
+use chrono::DateTime;
 use chrono::Local;
+use chrono::TimeDelta;
+use chrono::Utc;
 use codex_core::AuthManager;
 use codex_core::CodexAuth;
 use codex_core::ThreadManager;
+use codex_core::auth::CLIENT_ID;
 use codex_file_search::FileMatch;
+use codex_login::ServerOptions;
+use codex_login::complete_device_code_login;
+use codex_login::request_device_code;
 use codex_protocol::openai_models::InputModality;
 use codex_protocol::openai_models::ModelPreset;
 use codex_protocol::openai_models::ReasoningEffort as ReasoningEffortConfig;
 use codex_protocol::plan_tool::UpdatePlanArgs;
 use codex_protocol::protocol::AskForApproval;
 use codex_protocol::protocol::SandboxPolicy;
+use codex_protocol::todo_tool::TodoListEvent;
 use codex_utils_approval_presets::ApprovalPreset;
 use codex_utils_approval_presets::builtin_approval_presets;
+use codex_utils_string::format_bytes;
 use strum::IntoEnumIterator;
 
 const USER_SHELL_COMMAND_HELP_TITLE: &str = "Prefix a command with ! to run it locally";
-const USER_SHELL_COMMAND_HELP_HINT: &str = "Example: !ls";
+const USER_SHELL_COMMAND_HELP_HINT: &str =
+    "Example: !ls (use !!ls to keep the output out of the conversation)";
 const DEFAULT_OPENAI_BASE_URL: &str = "https://api.openai.com/v1";
+/// How often to check whether the provider is reachable again while offline.
+const CONNECTIVITY_PROBE_INTERVAL: Duration = Duration::from_secs(5);
 const DEFAULT_STATUS_LINE_ITEMS: [&str; 3] =
     ["model-with-reasoning", "context-remaining", "current-dir"];
 // Track information about an in-flight exec command.
@@ -372,6 +504,36 @@ fn is_standard_tool_call(parsed_cmd: &[ParsedCommand]) -> bool {
 const RATE_LIMIT_WARNING_THRESHOLDS: [f64; 3] = [75.0, 90.0, 95.0];
 const NUDGE_MODEL_SLUG: &str = "gpt-5.1-codex-mini";
 const RATE_LIMIT_SWITCH_PROMPT_THRESHOLD: f64 = 90.0;
+/// How long before the ChatGPT access token expires the composer starts warning about it.
+const AUTH_EXPIRY_WARNING_WINDOW: TimeDelta = TimeDelta::minutes(10);
+
+/// Describe `expires_at` relative to `now`, e.g. "in 4m 05s" or "2m 10s ago".
+fn describe_token_expiry(expires_at: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let remaining = (expires_at - now).num_seconds();
+    if remaining >= 0 {
+        format!("in {}", fmt_elapsed_compact(remaining as u64))
+    } else {
+        format!("{} ago", fmt_elapsed_compact(remaining.unsigned_abs()))
+    }
+}
+
+/// Warning shown above the composer once the access token is within
+/// [`AUTH_EXPIRY_WARNING_WINDOW`] of expiring (or already expired).
+fn auth_expiry_warning(expires_at: Option<DateTime<Utc>>, now: DateTime<Utc>) -> Option<String> {
+    let expires_at = expires_at?;
+    if expires_at - now > AUTH_EXPIRY_WARNING_WINDOW {
+        return None;
+    }
+    let verb = if expires_at > now {
+        "expires"
+    } else {
+        "expired"
+    };
+    Some(format!(
+        "ChatGPT sign-in {verb} {} · /login status to refresh",
+        describe_token_expiry(expires_at, now)
+    ))
+}
 
 #[derive(Default)]
 struct RateLimitWarningState {
@@ -438,6 +600,35 @@ impl RateLimitWarningState {
     }
 }
 
+/// Tracks which of the configured context warning thresholds usage is past.
+#[derive(Default)]
+struct ContextWarningState {
+    crossed: usize,
+}
+
+impl ContextWarningState {
+    /// Record the share of the context window in use and return the highest threshold this
+    /// update crossed. Thresholds re-arm once usage drops back below them, e.g. after compaction.
+    fn update(&mut self, thresholds: &[u8], used_percent: Option<i64>) -> Option<u8> {
+        let crossed = used_percent.map_or(0, |used| {
+            thresholds
+                .iter()
+                .take_while(|threshold| used >= i64::from(**threshold))
+                .count()
+        });
+        let previous = std::mem::replace(&mut self.crossed, crossed);
+        (crossed > previous).then(|| thresholds[crossed - 1])
+    }
+
+    fn level(&self, threshold_count: usize) -> Option<ContextWarningLevel> {
+        match self.crossed {
+            0 => None,
+            crossed if crossed >= threshold_count => Some(ContextWarningLevel::Critical),
+            _ => Some(ContextWarningLevel::Elevated),
+        }
+    }
+}
+
 pub(crate) fn get_limits_duration(windows_minutes: i64) -> String {
     const MINUTES_PER_HOUR: i64 = 60;
     const MINUTES_PER_DAY: i64 = 24 * MINUTES_PER_HOUR;
@@ -503,6 +694,49 @@ enum RateLimitErrorKind {
     Generic,
 }
 
+/// Whether an error means the provider could not be reached at all (no HTTP response), as
+/// opposed to the provider rejecting the request.
+fn is_connectivity_error(info: &CodexErrorInfo) -> bool {
+    matches!(
+        info,
+        CodexErrorInfo::HttpConnectionFailed {
+            http_status_code: None
+        } | CodexErrorInfo::ResponseStreamConnectionFailed {
+            http_status_code: None
+        } | CodexErrorInfo::ResponseStreamDisconnected {
+            http_status_code: None
+        }
+    )
+}
+
+/// Start time and per-event times of a resumed rollout, aligned with the `initial_messages` core
+/// replays from it. `None` when the file cannot be read or does not line up with the events.
+fn rollout_event_times(
+    path: &Path,
+    event_count: usize,
+) -> Option<(DateTime<Local>, Vec<DateTime<Local>>)> {
+    let contents = std::fs::read_to_string(path).ok()?;
+    let mut started_at = None;
+    let mut times = Vec::with_capacity(event_count);
+    for line in contents.lines() {
+        let Ok(line) = serde_json::from_str::<RolloutLine>(line) else {
+            continue;
+        };
+        let at = DateTime::parse_from_rfc3339(&line.timestamp)
+            .ok()?
+            .with_timezone(&Local);
+        started_at.get_or_insert(at);
+        if line.item.is_replayed() {
+            times.push(at);
+        }
+    }
+    if times.len() < event_count {
+        return None;
+    }
+    times.truncate(event_count);
+    Some((started_at?, times))
+}
+
 fn rate_limit_error_kind(info: &CodexErrorInfo) -> Option<RateLimitErrorKind> {
     match info {
         CodexErrorInfo::ServerOverloaded => Some(RateLimitErrorKind::ServerOverloaded),
@@ -565,8 +799,13 @@ pub(crate) struct ChatWidget {
     rate_limit_snapshots_by_limit_id: BTreeMap<String, RateLimitSnapshotDisplay>,
     plan_type: Option<PlanType>,
     rate_limit_warnings: RateLimitWarningState,
+    context_warnings: ContextWarningState,
     rate_limit_switch_prompt: RateLimitSwitchPromptState,
     rate_limit_poller: Option<JoinHandle<()>>,
+    /// Set when a turn failed because the provider was unreachable. While offline, composed
+    /// messages stay queued and `connectivity_probe` polls until the provider answers again.
+    offline: bool,
+    connectivity_probe: Option<JoinHandle<()>>,
     adaptive_chunking: AdaptiveChunkingPolicy,
     // Stream lifecycle controller
     stream_controller: Option<StreamController>,
@@ -574,6 +813,12 @@ pub(crate) struct ChatWidget {
     plan_stream_controller: Option<PlanStreamController>,
     // Latest completed user-visible Codex output that `/copy` should place on the clipboard.
     last_copyable_output: Option<String>,
+    // Final answer of the most recent turn, kept across rollbacks so `/retry` can diff against it.
+    last_turn_answer: Option<String>,
+    // Answer being retried; the next completed turn is rendered as a diff against it.
+    retry_baseline: Option<String>,
+    // Cancels the in-flight `/login device` attempt, if any.
+    device_code_login_cancel: Option<Arc<Notify>>,
     running_commands: HashMap<String, RunningCommand>,
     suppressed_exec_calls: HashSet<String>,
     skills_all: Vec<ProtocolSkillMetadata>,
@@ -658,6 +903,14 @@ pub(crate) struct ChatWidget {
     // This gates rendering of the "Worked for …" separator so purely conversational turns don't
     // show an empty divider. It is reset when the separator is emitted.
     had_work_activity: bool,
+    // Whether a turn finished since the session was last summarized, for
+    // `tui.summarize_on_exit`.
+    unsummarized_turns: bool,
+    // Start of the session, for `tui.timestamps = "relative"`. Resumed sessions use the first
+    // timestamp in their rollout.
+    session_started_at: DateTime<Local>,
+    // Recorded time of the resumed-rollout event being replayed, used instead of now.
+    replay_event_time: Option<DateTime<Local>>,
     // Whether the current turn emitted a plan update.
     saw_plan_update_this_turn: bool,
     // Whether the current turn emitted a proposed plan item that has not been superseded by a
@@ -676,6 +929,22 @@ pub(crate) struct ChatWidget {
     // Runtime metrics accumulated across delta snapshots for the active turn.
     turn_runtime_metrics: RuntimeMetricsSummary,
     last_rendered_width: std::cell::Cell<Option<usize>>,
+    // Optional workspace tree rendered left of the chat (/tree).
+    file_tree: FileTreeSidebar,
+    // Live workspace diff rendered right of the chat (/split).
+    diff_pane: DiffPane,
+    // Result of the most recent code review, for /export-review and /post-review.
+    last_review_output: Option<ReviewOutputEvent>,
+    // Conflict hunks still to walk through in /conflicts; the front one is being resolved.
+    conflict_queue: VecDeque<ConflictHunk>,
+    // How many hunks the current /conflicts walk started with.
+    conflict_total: usize,
+    // Temperature / top_p overrides chosen with /params for this session.
+    sampling: SamplingParams,
+    // Files sent with every request until unpinned (/pin).
+    pinned_files: PinnedFiles,
+    // Plan steps behind the checklist cell and the footer's current-step indicator.
+    plan_steps: PlanSteps,
     // Feedback sink for /feedback
     feedback: codex_feedback::CodexFeedback,
     feedback_audience: FeedbackAudience,
@@ -695,6 +964,8 @@ pub(crate) struct ChatWidget {
     status_line_branch_pending: bool,
     // True once we've attempted a branch lookup for the current CWD.
     status_line_branch_lookup_complete: bool,
+    // Commands providing status-line segments, each refreshed on its own interval.
+    status_line_commands: StatusLineCommands,
     external_editor_state: ExternalEditorState,
     realtime_conversation: RealtimeConversationUiState,
     last_rendered_user_message_event: Option<RenderedUserMessageEvent>,
@@ -1031,7 +1302,7 @@ impl ChatWidget {
     }
 
     /// Sets the currently rendered footer status-line value.
-    pub(crate) fn set_status_line(&mut self, status_line: Option<Line<'static>>) {
+    pub(crate) fn set_status_line(&mut self, status_line: Option<StatusLine>) {
         self.bottom_pane.set_status_line(status_line);
     }
 
@@ -1044,7 +1315,8 @@ impl ChatWidget {
     ///
     /// The omission behavior is intentional. If selected items are unavailable (for example before
     /// a session id exists or before branch lookup completes), those items are skipped without
-    /// placeholders so the line remains compact and stable.
+    /// placeholders so the line remains compact and stable. Rows and segments that end up empty
+    /// are dropped the same way.
     pub(crate) fn refresh_status_line(&mut self) {
         let (items, invalid_items) = self.status_line_items_with_invalids();
         if self.thread_id.is_some()
@@ -1070,7 +1342,11 @@ impl ChatWidget {
             self.status_line_branch_pending = false;
             self.status_line_branch_lookup_complete = false;
         }
-        let enabled = !items.is_empty();
+        let commands = self.configured_status_line_commands();
+        let enabled = !items.is_empty() || !commands.is_empty();
+        self.status_line_commands
+            .set_input(self.status_line_command_input());
+        self.status_line_commands.sync(commands, &self.app_event_tx);
         self.bottom_pane.set_status_line_enabled(enabled);
         if !enabled {
             self.set_status_line(None);
@@ -1084,19 +1360,38 @@ impl ChatWidget {
             self.request_status_line_branch(cwd);
         }
 
-        let mut parts = Vec::new();
-        for item in items {
-            if let Some(value) = self.status_line_value_for_item(&item) {
-                parts.push(value);
-            }
-        }
+        let rows = self
+            .configured_status_line_rows()
+            .iter()
+            .map(|row| StatusLineRow {
+                left: self.status_line_segment(&row.left),
+                center: self.status_line_segment(&row.center),
+                right: self.status_line_segment(&row.right),
+            })
+            .collect();
+        self.set_status_line(StatusLine::new(rows));
+    }
 
-        let line = if parts.is_empty() {
-            None
-        } else {
-            Some(Line::from(parts.join(" · ")))
-        };
-        self.set_status_line(line);
+    /// Renders one status-line segment, skipping invalid and unavailable items.
+    fn status_line_segment(&self, ids: &[String]) -> Line<'static> {
+        let parts = ids
+            .iter()
+            .filter_map(|id| match status_line_command::command_name(id) {
+                Some(name) => self.status_line_commands.value(name).map(str::to_string),
+                None => id
+                    .parse::<StatusLineItem>()
+                    .ok()
+                    .and_then(|item| self.status_line_value_for_item(&item)),
+            })
+            .collect::<Vec<_>>();
+        Line::from(parts.join(" · "))
+    }
+
+    /// Stores the latest output of a status-line command.
+    pub(crate) fn set_status_line_command_value(&mut self, name: String, value: Option<String>) {
+        if self.status_line_commands.set_value(name, value) {
+            self.refresh_status_line();
+        }
     }
 
     /// Records that status-line setup was canceled.
@@ -1110,9 +1405,8 @@ impl ChatWidget {
     /// Applies status-line item selection from the setup view to in-memory config.
     ///
     /// An empty selection persists as an explicit empty list.
-    pub(crate) fn setup_status_line(&mut self, items: Vec<StatusLineItem>) {
-        tracing::info!("status line setup confirmed with items: {items:#?}");
-        let ids = items.iter().map(ToString::to_string).collect::<Vec<_>>();
+    pub(crate) fn setup_status_line(&mut self, ids: Vec<String>) {
+        tracing::info!("status line setup confirmed with items: {ids:#?}");
         self.config.tui_status_line = Some(ids);
         self.refresh_status_line();
     }
@@ -1170,6 +1464,7 @@ impl ChatWidget {
 
     fn restore_retry_status_header_if_present(&mut self) {
         if let Some(header) = self.retry_status_header.take() {
+            self.bottom_pane.set_status_retry_at(None);
             self.set_status_header(header);
         }
     }
@@ -1207,6 +1502,8 @@ impl ChatWidget {
                 Constrained::allow_only(event.sandbox_policy.clone());
         }
         let initial_messages = event.initial_messages.clone();
+        let rollout_path = event.rollout_path.clone();
+        self.session_started_at = Local::now();
         self.last_copyable_output = None;
         let forked_from_id = event.forked_from_id;
         let model_for_header = event.model.clone();
@@ -1238,8 +1535,28 @@ impl ChatWidget {
         );
         self.apply_session_info_cell(session_info_cell);
 
+        let fresh_start = initial_messages.is_none();
+        if initial_messages.is_some()
+            && let Some(summary) = rollout_path.as_deref().and_then(read_session_summary)
+        {
+            self.add_to_history(history_cell::new_session_summary(
+                &summary,
+                "Where you left off",
+            ));
+        }
         if let Some(messages) = initial_messages {
-            self.replay_initial_messages(messages);
+            let event_times = match (self.config.tui_timestamps, rollout_path) {
+                (HistoryTimestamps::Off, _) | (_, None) => None,
+                (_, Some(path)) => rollout_event_times(&path, messages.len()),
+            };
+            let event_times = match event_times {
+                Some((started_at, times)) => {
+                    self.session_started_at = started_at;
+                    times
+                }
+                None => Vec::new(),
+            };
+            self.replay_initial_messages(messages, event_times);
         }
         // Ask codex-core to enumerate custom prompts for this session.
         self.submit_op(Op::ListCustomPrompts);
@@ -1250,6 +1567,9 @@ impl ChatWidget {
         if self.connectors_enabled() {
             self.prefetch_connectors();
         }
+        if fresh_start && self.initial_user_message.is_none() {
+            self.load_carry_over_briefing();
+        }
         if let Some(user_message) = self.initial_user_message.take() {
             self.submit_user_message(user_message);
         }
@@ -1261,6 +1581,65 @@ impl ChatWidget {
         }
     }
 
+    /// Look for summaries of recent sessions in this project to offer as a briefing.
+    fn load_carry_over_briefing(&self) {
+        let config = self.config.clone();
+        let tx = self.app_event_tx.clone();
+        tokio::spawn(async move {
+            if let Some(briefing) = carry_over_briefing(&config).await {
+                tx.send(AppEvent::CarryOverBriefingLoaded(briefing));
+            }
+        });
+    }
+
+    /// Offer to start the session with the summaries of recent sessions in this project. Once the
+    /// first turn has started the offer is dropped.
+    pub(crate) fn offer_carry_over_briefing(&mut self, briefing: CarryOverBriefing) {
+        if self.bottom_pane.is_task_running() || self.unsummarized_turns {
+            return;
+        }
+        let sessions = match briefing.summaries.len() {
+            1 => "the last session".to_string(),
+            count => format!("the last {count} sessions"),
+        };
+        let context = briefing.to_context();
+        let items = vec![
+            SelectionItem {
+                name: "Start with a briefing".to_string(),
+                description: Some(format!(
+                    "Give Codex the summaries of {sessions} here as context."
+                )),
+                actions: vec![Box::new(move |tx| {
+                    for summary in &briefing.summaries {
+                        tx.send(AppEvent::InsertHistoryCell(Box::new(
+                            history_cell::new_session_summary(summary, "Carried over"),
+                        )));
+                    }
+                    tx.send(AppEvent::CodexOp(Op::SetCarryOverBriefing {
+                        briefing: context.clone(),
+                    }));
+                })],
+                dismiss_on_select: true,
+                ..Default::default()
+            },
+            SelectionItem {
+                name: "Start fresh".to_string(),
+                description: Some("Begin without context from earlier sessions.".to_string()),
+                dismiss_on_select: true,
+                ..Default::default()
+            },
+        ];
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            title: Some("Pick up where you left off?".to_string()),
+            subtitle: Some(format!(
+                "Summaries of {sessions} in this project are available."
+            )),
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            ..Default::default()
+        });
+    }
+
     fn emit_forked_thread_event(&self, forked_from_id: ThreadId) {
         let app_event_tx = self.app_event_tx.clone();
         let codex_home = self.config.codex_home.clone();
@@ -1439,6 +1818,7 @@ impl ChatWidget {
             } else {
                 None
             };
+        let has_steps = self.plan_steps.seed_from_proposed_plan(&plan_text);
         if let Some(cell) = finalized_streamed_cell {
             self.add_boxed_history(cell);
             // TODO: Replace streamed output with the final plan item text if plan streaming is
@@ -1446,6 +1826,10 @@ impl ChatWidget {
         } else if !plan_text.is_empty() {
             self.add_to_history(history_cell::new_proposed_plan(plan_text));
         }
+        if has_steps {
+            self.add_to_history(history_cell::new_plan_update(self.plan_steps.as_update()));
+            self.sync_plan_step();
+        }
         if should_restore_after_stream {
             self.pending_status_indicator_restore = true;
             self.maybe_restore_status_indicator_after_stream_idle();
@@ -1486,6 +1870,24 @@ impl ChatWidget {
         self.request_redraw();
     }
 
+    /// Show the phase the running turn is in as the status header; the indicator already shows
+    /// the elapsed time next to it.
+    fn on_turn_phase(&mut self, phase: TurnPhase) {
+        if !self.bottom_pane.is_task_running() || self.unified_exec_wait_streak.is_some() {
+            return;
+        }
+        let header = match phase {
+            TurnPhase::Queued => "Queued".to_string(),
+            TurnPhase::Sending => "Sending request".to_string(),
+            TurnPhase::Thinking => "Thinking".to_string(),
+            TurnPhase::Streaming => "Responding".to_string(),
+            TurnPhase::RunningTool { total: 1, .. } => "Running tool".to_string(),
+            TurnPhase::RunningTool { index, total } => format!("Running tool {index} of {total}"),
+        };
+        self.set_status_header(header);
+        self.request_redraw();
+    }
+
     fn on_reasoning_section_break(&mut self) {
         // Start a new reasoning block for header extraction and accumulate transcript.
         self.full_reasoning_buffer.push_str(&self.reasoning_buffer);
@@ -1516,17 +1918,32 @@ impl ChatWidget {
         self.set_status_header(String::from("Working"));
         self.full_reasoning_buffer.clear();
         self.reasoning_buffer.clear();
+        self.update_auth_expiry_warning();
         self.request_redraw();
     }
 
     fn on_task_complete(&mut self, last_agent_message: Option<String>, from_replay: bool) {
+        self.refresh_diff_pane();
         if let Some(message) = last_agent_message.as_ref()
             && !message.trim().is_empty()
         {
             self.last_copyable_output = Some(message.clone());
         }
+        let answer = last_agent_message
+            .clone()
+            .filter(|message| !message.trim().is_empty());
         // If a stream is currently active, finalize it.
         self.flush_answer_stream_with_separator();
+        if !from_replay {
+            self.unsummarized_turns = true;
+        }
+        if !from_replay
+            && let Some(previous) = self.retry_baseline.take()
+            && let Some(retried) = answer.as_deref()
+        {
+            self.add_plain_history_lines(response_diff::retry_diff_lines(&previous, retried));
+        }
+        self.last_turn_answer = answer;
         if let Some(mut controller) = self.plan_stream_controller.take()
             && let Some(cell) = controller.finalize()
         {
@@ -1552,10 +1969,12 @@ impl ChatWidget {
                     runtime_metrics,
                 ));
             }
+            self.advance_plan_step_after_turn();
             self.turn_runtime_metrics = RuntimeMetricsSummary::default();
             self.needs_final_message_separator = false;
             self.had_work_activity = false;
             self.request_status_line_branch_refresh();
+            self.run_status_line_commands_now();
         }
         // Mark task stopped and request redraw now that all content is in history.
         self.pending_status_indicator_restore = false;
@@ -1566,6 +1985,7 @@ impl ChatWidget {
         self.suppressed_exec_calls.clear();
         self.last_unified_wait = None;
         self.unified_exec_wait_streak = None;
+        self.update_auth_expiry_warning();
         self.request_redraw();
 
         let had_pending_steers = !self.pending_steers.is_empty();
@@ -1620,7 +2040,14 @@ impl ChatWidget {
         let default_mask = collaboration_modes::default_mode_mask(self.models_manager.as_ref());
         let (implement_actions, implement_disabled_reason) = match default_mask {
             Some(mask) => {
-                let user_text = PLAN_IMPLEMENTATION_CODING_MESSAGE.to_string();
+                let user_text = if self.plan_steps.is_empty() {
+                    PLAN_IMPLEMENTATION_CODING_MESSAGE.to_string()
+                } else {
+                    format!(
+                        "{PLAN_IMPLEMENTATION_CODING_MESSAGE}\n\n{}",
+                        self.plan_steps.implementation_instructions()
+                    )
+                };
                 let actions: Vec<SelectionAction> = vec![Box::new(move |tx| {
                     tx.send(AppEvent::SubmitUserMessageWithMode {
                         text: user_text.clone(),
@@ -1631,27 +2058,34 @@ impl ChatWidget {
             }
             None => (Vec::new(), Some("Default mode unavailable".to_string())),
         };
-        let items = vec![
-            SelectionItem {
-                name: PLAN_IMPLEMENTATION_YES.to_string(),
-                description: Some("Switch to Default and start coding.".to_string()),
-                selected_description: None,
-                is_current: false,
-                actions: implement_actions,
-                disabled_reason: implement_disabled_reason,
-                dismiss_on_select: true,
-                ..Default::default()
-            },
-            SelectionItem {
-                name: PLAN_IMPLEMENTATION_NO.to_string(),
-                description: Some("Continue planning with the model.".to_string()),
-                selected_description: None,
-                is_current: false,
-                actions: Vec::new(),
+        let mut items = vec![SelectionItem {
+            name: PLAN_IMPLEMENTATION_YES.to_string(),
+            description: Some("Switch to Default and start coding.".to_string()),
+            selected_description: None,
+            is_current: false,
+            actions: implement_actions,
+            disabled_reason: implement_disabled_reason,
+            dismiss_on_select: true,
+            ..Default::default()
+        }];
+        if !self.plan_steps.is_empty() {
+            items.push(SelectionItem {
+                name: PLAN_IMPLEMENTATION_EDIT_STEPS.to_string(),
+                description: Some("Change or reorder the steps, then decide.".to_string()),
+                actions: vec![Box::new(|tx| tx.send(AppEvent::EditPlanSteps))],
                 dismiss_on_select: true,
                 ..Default::default()
-            },
-        ];
+            });
+        }
+        items.push(SelectionItem {
+            name: PLAN_IMPLEMENTATION_NO.to_string(),
+            description: Some("Continue planning with the model.".to_string()),
+            selected_description: None,
+            is_current: false,
+            actions: Vec::new(),
+            dismiss_on_select: true,
+            ..Default::default()
+        });
 
         self.bottom_pane.show_selection_view(SelectionViewParams {
             title: Some(PLAN_IMPLEMENTATION_TITLE.to_string()),
@@ -1700,21 +2134,58 @@ impl ChatWidget {
         });
     }
 
-    pub(crate) fn set_token_info(&mut self, info: Option<TokenUsageInfo>) {
+    /// Returns the context warning threshold this update crossed, if any.
+    pub(crate) fn set_token_info(&mut self, info: Option<TokenUsageInfo>) -> Option<u8> {
         match info {
             Some(info) => self.apply_token_info(info),
             None => {
                 self.bottom_pane.set_context_window(None, None);
                 self.token_info = None;
+                self.update_context_warning(None)
             }
         }
     }
 
-    fn apply_token_info(&mut self, info: TokenUsageInfo) {
+    fn apply_token_info(&mut self, info: TokenUsageInfo) -> Option<u8> {
         let percent = self.context_remaining_percent(&info);
         let used_tokens = self.context_used_tokens(&info, percent.is_some());
         self.bottom_pane.set_context_window(percent, used_tokens);
         self.token_info = Some(info);
+        self.update_context_warning(percent)
+    }
+
+    fn update_context_warning(&mut self, percent_remaining: Option<i64>) -> Option<u8> {
+        let thresholds = self.context_warning_thresholds();
+        let used_percent = percent_remaining.map(|percent| 100 - percent.clamp(0, 100));
+        let crossed = self.context_warnings.update(&thresholds, used_percent);
+        self.bottom_pane
+            .set_context_warning(self.context_warnings.level(thresholds.len()));
+        crossed
+    }
+
+    /// The configured thresholds in ascending order, ignoring values outside 1-100.
+    fn context_warning_thresholds(&self) -> Vec<u8> {
+        let mut thresholds: Vec<u8> = self
+            .config
+            .tui_context_warnings
+            .thresholds
+            .iter()
+            .copied()
+            .filter(|threshold| (1..=100).contains(threshold))
+            .collect();
+        thresholds.sort_unstable();
+        thresholds.dedup();
+        thresholds
+    }
+
+    fn on_context_threshold_crossed(&mut self, threshold: u8) {
+        self.add_to_history(history_cell::new_warning_event(format!(
+            "Context window is {threshold}% full. Run /compact to free up space before responses degrade."
+        )));
+        if self.config.tui_context_warnings.notify {
+            self.notify(Notification::ContextWarning { threshold });
+        }
+        self.request_redraw();
     }
 
     fn context_remaining_percent(&self, info: &TokenUsageInfo) -> Option<i64> {
@@ -1734,13 +2205,8 @@ impl ChatWidget {
 
     fn restore_pre_review_token_info(&mut self) {
         if let Some(saved) = self.pre_review_token_info.take() {
-            match saved {
-                Some(info) => self.apply_token_info(info),
-                None => {
-                    self.bottom_pane.set_context_window(None, None);
-                    self.token_info = None;
-                }
-            }
+            // Usage from before the review was already announced when it was first reported.
+            self.set_token_info(saved);
         }
     }
 
@@ -1874,6 +2340,136 @@ impl ChatWidget {
         self.maybe_send_next_queued_input();
     }
 
+    /// Add a dim timestamp line ahead of the cell about to be inserted, when `tui.timestamps` is
+    /// on. Replayed events use the time recorded in the rollout.
+    fn add_timestamp(&mut self) {
+        let at = self.replay_event_time.unwrap_or_else(Local::now);
+        if let Some(cell) =
+            history_cell::new_timestamp(self.config.tui_timestamps, at, self.session_started_at)
+        {
+            self.add_to_history(cell);
+        }
+    }
+
+    /// A turn failed: record a structured error cell and, when the session is idle, offer the
+    /// follow-up actions that fit the failure.
+    fn on_turn_error(&mut self, message: String, info: Option<&CodexErrorInfo>, from_replay: bool) {
+        self.finalize_turn();
+        let (category, retryable) = history_cell::ErrorCategory::from_info(info);
+        self.add_to_history(history_cell::new_structured_error(
+            category,
+            message.clone(),
+            retryable,
+        ));
+        self.request_redraw();
+
+        // After an error ends the turn, try sending the next queued input.
+        self.maybe_send_next_queued_input();
+        if !from_replay && !self.bottom_pane.is_task_running() {
+            self.open_error_actions_popup(category, message, retryable);
+        }
+    }
+
+    /// Keyboard-navigable follow-ups for a failed turn: retry, switch model, open logs, or copy
+    /// the error.
+    fn open_error_actions_popup(
+        &mut self,
+        category: history_cell::ErrorCategory,
+        message: String,
+        retryable: bool,
+    ) {
+        use history_cell::ErrorCategory;
+
+        let mut items = Vec::new();
+        if retryable {
+            items.push(SelectionItem {
+                name: "Retry".to_string(),
+                description: Some("Rewind the failed turn and send it again".to_string()),
+                actions: vec![Box::new(|tx| {
+                    tx.send(AppEvent::RetryLastTurn(RetryOverrides::default()));
+                })],
+                dismiss_on_select: true,
+                ..Default::default()
+            });
+        }
+        if category == ErrorCategory::ContextWindow {
+            items.push(SelectionItem {
+                name: "Compact".to_string(),
+                description: Some("Summarize the conversation to free up context".to_string()),
+                actions: vec![Box::new(|tx| {
+                    tx.send(AppEvent::DispatchSlashCommand(SlashCommand::Compact));
+                })],
+                dismiss_on_select: true,
+                ..Default::default()
+            });
+        }
+        if category == ErrorCategory::Authentication {
+            items.push(SelectionItem {
+                name: "Log in".to_string(),
+                description: Some("Sign in again or switch accounts".to_string()),
+                actions: vec![Box::new(|tx| {
+                    tx.send(AppEvent::DispatchSlashCommand(SlashCommand::Login));
+                })],
+                dismiss_on_select: true,
+                ..Default::default()
+            });
+        }
+        if matches!(
+            category,
+            ErrorCategory::General
+                | ErrorCategory::Server
+                | ErrorCategory::ContextWindow
+                | ErrorCategory::UsageLimit
+                | ErrorCategory::BadRequest
+        ) {
+            items.push(SelectionItem {
+                name: "Switch model".to_string(),
+                description: Some(format!("Currently {}", self.current_model())),
+                actions: vec![Box::new(|tx| {
+                    tx.send(AppEvent::DispatchSlashCommand(SlashCommand::Model));
+                })],
+                dismiss_on_select: true,
+                ..Default::default()
+            });
+        }
+        items.push(SelectionItem {
+            name: "Open logs".to_string(),
+            description: Some("Show codex-tui.log".to_string()),
+            actions: vec![Box::new(|tx| {
+                tx.send(AppEvent::OpenLogViewer);
+            })],
+            dismiss_on_select: true,
+            ..Default::default()
+        });
+        items.push(SelectionItem {
+            name: "Copy error".to_string(),
+            description: Some("Put the error text on the clipboard".to_string()),
+            actions: vec![Box::new(move |tx| {
+                let cell = match clipboard_text::copy_text_to_clipboard(&message) {
+                    Ok(()) => history_cell::new_info_event(
+                        "Copied the error to the clipboard.".to_string(),
+                        None,
+                    ),
+                    Err(err) => history_cell::new_info_event(
+                        format!("Failed to copy to clipboard: {err}"),
+                        None,
+                    ),
+                };
+                tx.send(AppEvent::InsertHistoryCell(Box::new(cell)));
+            })],
+            dismiss_on_select: true,
+            ..Default::default()
+        });
+
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            title: Some("Turn failed".to_string()),
+            subtitle: Some("Choose what to do next, or press Esc to dismiss.".to_string()),
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            ..Default::default()
+        });
+    }
+
     fn on_warning(&mut self, message: impl Into<String>) {
         self.add_to_history(history_cell::new_warning_event(message.into()));
         self.request_redraw();
@@ -1951,6 +2547,9 @@ impl ChatWidget {
     /// When there are queued user messages, restore them into the composer
     /// separated by newlines rather than auto‑submitting the next one.
     fn on_interrupted_turn(&mut self, reason: TurnAbortReason) {
+        // Keep whatever the assistant already streamed; core records the same partial text in
+        // the conversation so the next message can steer from it.
+        self.flush_answer_stream_with_separator();
         // Finalize, log a gentle prompt, and clear running state.
         self.finalize_turn();
         if reason == TurnAbortReason::Interrupted {
@@ -1972,6 +2571,12 @@ impl ChatWidget {
         self.request_redraw();
     }
 
+    /// Mark where a resumed turn stopped early and why.
+    fn on_replayed_turn_cancelled(&mut self, reason: TurnAbortReason) {
+        self.flush_answer_stream_with_separator();
+        self.add_to_history(history_cell::new_turn_cancelled(&reason));
+    }
+
     /// Merge pending steers, queued drafts, and the current composer state into a single message.
     ///
     /// Each pending message numbers attachments from `[Image #1]` relative to its own remote
@@ -2146,9 +2751,51 @@ impl ChatWidget {
 
     fn on_plan_update(&mut self, update: UpdatePlanArgs) {
         self.saw_plan_update_this_turn = true;
+        self.plan_steps.replace(update.plan.clone());
+        self.sync_plan_step();
         self.add_to_history(history_cell::new_plan_update(update));
     }
 
+    fn sync_plan_step(&mut self) {
+        self.bottom_pane.set_plan_step(self.plan_steps.current());
+    }
+
+    /// Check off the step in progress when a working turn ends without the model reporting
+    /// progress itself.
+    fn advance_plan_step_after_turn(&mut self) {
+        if self.active_mode_kind() == ModeKind::Plan || self.saw_plan_update_this_turn {
+            return;
+        }
+        if self.plan_steps.advance() {
+            self.add_to_history(history_cell::new_plan_update(self.plan_steps.as_update()));
+            self.sync_plan_step();
+        }
+    }
+
+    /// Edit the plan's steps, one per line, before implementing it.
+    pub(crate) fn open_plan_steps_editor(&mut self) {
+        let tx = self.app_event_tx.clone();
+        let view = CustomPromptView::new(
+            "Edit plan steps".to_string(),
+            "One step per line".to_string(),
+            Some("Reorder lines to reorder steps · shift+enter for a new line".to_string()),
+            Box::new(move |text: String| {
+                tx.send(AppEvent::PlanStepsEdited(text));
+            }),
+        )
+        .with_initial_text(&self.plan_steps.edit_text());
+        self.bottom_pane.show_view(Box::new(view));
+    }
+
+    pub(crate) fn apply_plan_steps_edit(&mut self, text: String) {
+        self.plan_steps.apply_edit(&text);
+        self.add_to_history(history_cell::new_plan_update(self.plan_steps.as_update()));
+        self.sync_plan_step();
+        if self.active_mode_kind() == ModeKind::Plan && !self.agent_turn_running {
+            self.open_plan_implementation_prompt();
+        }
+    }
+
     fn on_exec_approval_request(&mut self, _id: String, ev: ExecApprovalRequestEvent) {
         let ev2 = ev.clone();
         self.defer_or_handle(
@@ -2165,15 +2812,31 @@ impl ChatWidget {
         );
     }
 
-    fn on_elicitation_request(&mut self, ev: ElicitationRequestEvent) {
+    fn on_plan_approval_request(&mut self, ev: PlanApprovalRequestEvent) {
         let ev2 = ev.clone();
         self.defer_or_handle(
-            |q| q.push_elicitation(ev),
-            |s| s.handle_elicitation_request_now(ev2),
+            |q| q.push_plan_approval(ev),
+            |s| s.handle_plan_approval_now(ev2),
         );
     }
 
-    fn on_request_user_input(&mut self, ev: RequestUserInputEvent) {
+    fn on_compaction_review_request(&mut self, ev: CompactionReviewRequestEvent) {
+        let ev2 = ev.clone();
+        self.defer_or_handle(
+            |q| q.push_compaction_review(ev),
+            |s| s.handle_compaction_review_now(ev2),
+        );
+    }
+
+    fn on_elicitation_request(&mut self, ev: ElicitationRequestEvent) {
+        let ev2 = ev.clone();
+        self.defer_or_handle(
+            |q| q.push_elicitation(ev),
+            |s| s.handle_elicitation_request_now(ev2),
+        );
+    }
+
+    fn on_request_user_input(&mut self, ev: RequestUserInputEvent) {
         let ev2 = ev.clone();
         self.defer_or_handle(
             |q| q.push_user_input(ev),
@@ -2271,6 +2934,19 @@ impl ChatWidget {
     }
 
     fn on_patch_apply_begin(&mut self, event: PatchApplyBeginEvent) {
+        for (path, change) in &event.changes {
+            self.file_tree
+                .mark(self.config.cwd.join(path), FileMark::Modified);
+            if let FileChange::Update {
+                move_path: Some(move_path),
+                ..
+            } = change
+            {
+                self.file_tree
+                    .mark(self.config.cwd.join(move_path), FileMark::Modified);
+            }
+        }
+        self.add_timestamp();
         self.add_to_history(history_cell::new_patch_event(
             event.changes,
             &self.config.cwd,
@@ -2308,6 +2984,22 @@ impl ChatWidget {
         );
     }
 
+    fn on_patch_checks(&mut self, event: codex_protocol::protocol::PatchChecksEvent) {
+        let ev2 = event.clone();
+        self.defer_or_handle(
+            |q| q.push_patch_checks(event),
+            |s| s.handle_patch_checks_now(ev2),
+        );
+    }
+
+    fn on_dry_run_action(&mut self, event: codex_protocol::protocol::DryRunActionEvent) {
+        let ev2 = event.clone();
+        self.defer_or_handle(
+            |q| q.push_dry_run_action(event),
+            |s| s.handle_dry_run_action_now(ev2),
+        );
+    }
+
     fn on_exec_command_end(&mut self, ev: ExecCommandEndEvent) {
         if is_unified_exec_source(ev.source) {
             if let Some(process_id) = ev.process_id.as_deref()
@@ -2433,6 +3125,7 @@ impl ChatWidget {
             call_id,
             query,
             action,
+            sources,
         } = ev;
         let mut handled = false;
         if let Some(cell) = self
@@ -2442,6 +3135,7 @@ impl ChatWidget {
             && cell.call_id() == call_id
         {
             cell.update(action.clone(), query.clone());
+            cell.set_sources(sources.clone());
             cell.complete();
             self.bump_active_cell_revision();
             self.flush_active_cell();
@@ -2449,7 +3143,9 @@ impl ChatWidget {
         }
 
         if !handled {
-            self.add_to_history(history_cell::new_web_search_call(call_id, query, action));
+            let mut cell = history_cell::new_web_search_call(call_id, query, action);
+            cell.set_sources(sources);
+            self.add_to_history(cell);
         }
         self.had_work_activity = true;
     }
@@ -2480,6 +3176,7 @@ impl ChatWidget {
     fn on_turn_diff(&mut self, unified_diff: String) {
         debug!("TurnDiffEvent: {unified_diff}");
         self.refresh_status_line();
+        self.refresh_diff_pane();
     }
 
     fn on_deprecation_notice(&mut self, event: DeprecationNoticeEvent) {
@@ -2521,7 +3218,12 @@ impl ChatWidget {
         }
     }
 
-    fn on_stream_error(&mut self, message: String, additional_details: Option<String>) {
+    fn on_stream_error(
+        &mut self,
+        message: String,
+        additional_details: Option<String>,
+        retry_delay_ms: Option<u64>,
+    ) {
         if self.retry_status_header.is_none() {
             self.retry_status_header = Some(self.current_status_header.clone());
         }
@@ -2532,6 +3234,9 @@ impl ChatWidget {
             StatusDetailsCapitalization::CapitalizeFirst,
             STATUS_DETAILS_DEFAULT_MAX_LINES,
         );
+        let retry_at =
+            retry_delay_ms.map(|delay_ms| Instant::now() + Duration::from_millis(delay_ms));
+        self.bottom_pane.set_status_retry_at(retry_at);
     }
 
     pub(crate) fn pre_draw_tick(&mut self) {
@@ -2663,6 +3368,7 @@ impl ChatWidget {
                 // Reset the flag even if we don't show separator (no work was done)
                 self.needs_final_message_separator = false;
             }
+            self.add_timestamp();
             self.stream_controller = Some(StreamController::new(
                 self.last_rendered_width.get().map(|w| w.saturating_sub(2)),
             ));
@@ -2695,7 +3401,20 @@ impl ChatWidget {
     /// standalone history entry instead of replacing or flushing the unrelated active exploring
     /// cell. If this method treated every unknown end as "complete the active cell", the UI could
     /// merge unrelated commands and hide still-running exploring work.
+    /// How to label a command's working directory, or `None` when it is the session cwd.
+    fn exec_cwd_display(&self, cwd: &Path) -> Option<String> {
+        if cwd == self.config.cwd.as_path() {
+            return None;
+        }
+        match cwd.strip_prefix(&self.config.cwd) {
+            Ok(relative) => Some(relative.display().to_string()),
+            Err(_) => Some(format_directory_display(cwd, None)),
+        }
+    }
+
     pub(crate) fn handle_exec_end_now(&mut self, ev: ExecCommandEndEvent) {
+        // Commands can change files too, not just patches.
+        self.refresh_diff_pane();
         enum ExecEndTarget {
             // Normal case: the active exec cell already tracks this call id.
             ActiveTracked,
@@ -2774,7 +3493,8 @@ impl ChatWidget {
                     source,
                     ev.interaction_input.clone(),
                     self.config.animations,
-                );
+                )
+                .with_cwd(self.exec_cwd_display(&ev.cwd));
                 let completed = orphan.complete_call(&ev.call_id, output, ev.duration);
                 debug_assert!(
                     completed,
@@ -2788,6 +3508,7 @@ impl ChatWidget {
             }
             ExecEndTarget::NewCell => {
                 self.flush_active_cell();
+                self.add_timestamp();
                 let mut cell = new_active_exec_command(
                     ev.call_id.clone(),
                     command,
@@ -2795,7 +3516,8 @@ impl ChatWidget {
                     source,
                     ev.interaction_input.clone(),
                     self.config.animations,
-                );
+                )
+                .with_cwd(self.exec_cwd_display(&ev.cwd));
                 let completed = cell.complete_call(&ev.call_id, output, ev.duration);
                 debug_assert!(completed, "new exec cell should contain {}", ev.call_id);
                 if cell.should_flush() {
@@ -2824,6 +3546,24 @@ impl ChatWidget {
         self.had_work_activity = true;
     }
 
+    pub(crate) fn handle_patch_checks_now(
+        &mut self,
+        event: codex_protocol::protocol::PatchChecksEvent,
+    ) {
+        self.add_to_history(history_cell::new_patch_checks(event.results));
+    }
+
+    pub(crate) fn handle_dry_run_action_now(
+        &mut self,
+        event: codex_protocol::protocol::DryRunActionEvent,
+    ) {
+        self.flush_answer_stream_with_separator();
+        self.add_to_history(history_cell::new_dry_run_action(
+            event.action,
+            &self.config.cwd,
+        ));
+    }
+
     pub(crate) fn handle_exec_approval_now(&mut self, ev: ExecApprovalRequestEvent) {
         self.flush_answer_stream_with_separator();
         let command = shlex::try_join(ev.command.iter().map(String::as_str))
@@ -2866,6 +3606,77 @@ impl ChatWidget {
         });
     }
 
+    pub(crate) fn handle_plan_approval_now(&mut self, ev: PlanApprovalRequestEvent) {
+        self.flush_answer_stream_with_separator();
+        self.notify(Notification::PlanApprovalRequested {
+            step_count: ev.plan.len(),
+        });
+
+        let request = ApprovalRequest::Plan {
+            thread_id: self.thread_id.unwrap_or_default(),
+            thread_label: None,
+            id: ev.call_id,
+            explanation: ev.explanation,
+            steps: ev.plan.into_iter().map(|item| item.step).collect(),
+        };
+        self.bottom_pane
+            .push_approval_request(request, &self.config.features);
+        self.request_redraw();
+    }
+
+    pub(crate) fn handle_compaction_review_now(&mut self, ev: CompactionReviewRequestEvent) {
+        self.flush_answer_stream_with_separator();
+        self.notify(Notification::CompactionReviewRequested);
+
+        let request = ApprovalRequest::Compaction {
+            thread_id: self.thread_id.unwrap_or_default(),
+            thread_label: None,
+            id: ev.id,
+            summary: ev.summary,
+        };
+        self.bottom_pane
+            .push_approval_request(request, &self.config.features);
+        self.request_redraw();
+    }
+
+    /// Edit the summary of a compaction review; dismissing the editor returns to the review.
+    pub(crate) fn open_compaction_summary_editor(&mut self, request: ApprovalRequest) {
+        let ApprovalRequest::Compaction {
+            thread_id,
+            id,
+            summary,
+            ..
+        } = &request
+        else {
+            return;
+        };
+        let tx = self.app_event_tx.clone();
+        let thread_id = *thread_id;
+        let id = id.clone();
+        let view = CustomPromptView::new(
+            "Edit compaction summary".to_string(),
+            "What the model should remember from older turns".to_string(),
+            Some("Replaces older turns once saved · shift+enter for a new line".to_string()),
+            Box::new(move |summary: String| {
+                tx.send(AppEvent::SubmitThreadOp {
+                    thread_id,
+                    op: Op::CompactionReview {
+                        id: id.clone(),
+                        decision: CompactionReviewDecision::Edit { summary },
+                    },
+                });
+            }),
+        )
+        .with_initial_text(summary)
+        .keep_whitespace()
+        .on_cancel({
+            let tx = self.app_event_tx.clone();
+            let request = request.clone();
+            Box::new(move || tx.send(AppEvent::ShowApprovalRequest(request.clone())))
+        });
+        self.bottom_pane.show_view(Box::new(view));
+    }
+
     pub(crate) fn handle_elicitation_request_now(&mut self, ev: ElicitationRequestEvent) {
         self.flush_answer_stream_with_separator();
 
@@ -2904,6 +3715,11 @@ impl ChatWidget {
     pub(crate) fn handle_exec_begin_now(&mut self, ev: ExecCommandBeginEvent) {
         // Ensure the status indicator is visible while the command runs.
         self.bottom_pane.ensure_status_indicator();
+        for parsed in &ev.parsed_cmd {
+            if let ParsedCommand::Read { path, .. } = parsed {
+                self.file_tree.mark(ev.cwd.join(path), FileMark::Read);
+            }
+        }
         self.running_commands.insert(
             ev.call_id.clone(),
             RunningCommand {
@@ -2950,15 +3766,20 @@ impl ChatWidget {
             self.bump_active_cell_revision();
         } else {
             self.flush_active_cell();
+            self.add_timestamp();
 
-            self.active_cell = Some(Box::new(new_active_exec_command(
-                ev.call_id.clone(),
-                ev.command.clone(),
-                ev.parsed_cmd,
-                ev.source,
-                interaction_input,
-                self.config.animations,
-            )));
+            let cwd = self.exec_cwd_display(&ev.cwd);
+            self.active_cell = Some(Box::new(
+                new_active_exec_command(
+                    ev.call_id.clone(),
+                    ev.command.clone(),
+                    ev.parsed_cmd,
+                    ev.source,
+                    interaction_input,
+                    self.config.animations,
+                )
+                .with_cwd(cwd),
+            ));
             self.bump_active_cell_revision();
         }
 
@@ -2968,6 +3789,7 @@ impl ChatWidget {
     pub(crate) fn handle_mcp_begin_now(&mut self, ev: McpToolCallBeginEvent) {
         self.flush_answer_stream_with_separator();
         self.flush_active_cell();
+        self.add_timestamp();
         self.active_cell = Some(Box::new(history_cell::new_active_mcp_tool_call(
             ev.call_id,
             ev.invocation,
@@ -3080,6 +3902,11 @@ impl ChatWidget {
             }),
             active_cell,
             active_cell_revision: 0,
+            file_tree: FileTreeSidebar::new(config.cwd.clone()),
+            diff_pane: DiffPane::default(),
+            last_review_output: None,
+            conflict_queue: VecDeque::new(),
+            conflict_total: 0,
             config,
             skills_all: Vec::new(),
             skills_initial_state: None,
@@ -3094,12 +3921,18 @@ impl ChatWidget {
             rate_limit_snapshots_by_limit_id: BTreeMap::new(),
             plan_type: None,
             rate_limit_warnings: RateLimitWarningState::default(),
+            context_warnings: ContextWarningState::default(),
             rate_limit_switch_prompt: RateLimitSwitchPromptState::default(),
             rate_limit_poller: None,
+            offline: false,
+            connectivity_probe: None,
             adaptive_chunking: AdaptiveChunkingPolicy::default(),
             stream_controller: None,
             plan_stream_controller: None,
             last_copyable_output: None,
+            last_turn_answer: None,
+            retry_baseline: None,
+            device_code_login_cancel: None,
             running_commands: HashMap::new(),
             suppressed_exec_calls: HashSet::new(),
             last_unified_wait: None,
@@ -3135,6 +3968,9 @@ impl ChatWidget {
             pre_review_token_info: None,
             needs_final_message_separator: false,
             had_work_activity: false,
+            unsummarized_turns: false,
+            session_started_at: Local::now(),
+            replay_event_time: None,
             saw_plan_update_this_turn: false,
             saw_plan_item_this_turn: false,
             plan_delta_buffer: String::new(),
@@ -3142,6 +3978,9 @@ impl ChatWidget {
             last_separator_elapsed_secs: None,
             turn_runtime_metrics: RuntimeMetricsSummary::default(),
             last_rendered_width: std::cell::Cell::new(None),
+            sampling: SamplingParams::default(),
+            pinned_files: PinnedFiles::default(),
+            plan_steps: PlanSteps::default(),
             feedback,
             feedback_audience,
             current_rollout_path: None,
@@ -3152,6 +3991,7 @@ impl ChatWidget {
             status_line_branch_cwd: None,
             status_line_branch_pending: false,
             status_line_branch_lookup_complete: false,
+            status_line_commands: StatusLineCommands::default(),
             external_editor_state: ExternalEditorState::Closed,
             realtime_conversation: RealtimeConversationUiState::default(),
             last_rendered_user_message_event: None,
@@ -3176,6 +4016,15 @@ impl ChatWidget {
         widget
             .bottom_pane
             .set_queued_message_edit_binding(widget.queued_message_edit_binding);
+        widget
+            .bottom_pane
+            .set_interrupt_key(widget.configured_interrupt_key());
+        widget
+            .bottom_pane
+            .set_footer_hints(FooterHints::from_config(
+                widget.config.tui_footer_hints.as_deref(),
+            ));
+        widget.bottom_pane.set_read_only(widget.config.read_only);
         #[cfg(target_os = "windows")]
         widget.bottom_pane.set_windows_degraded_sandbox_active(
             codex_core::windows_sandbox::ELEVATED_SANDBOX_NUX_ENABLED
@@ -3262,6 +4111,11 @@ impl ChatWidget {
             }),
             active_cell,
             active_cell_revision: 0,
+            file_tree: FileTreeSidebar::new(config.cwd.clone()),
+            diff_pane: DiffPane::default(),
+            last_review_output: None,
+            conflict_queue: VecDeque::new(),
+            conflict_total: 0,
             config,
             skills_all: Vec::new(),
             skills_initial_state: None,
@@ -3276,12 +4130,18 @@ impl ChatWidget {
             rate_limit_snapshots_by_limit_id: BTreeMap::new(),
             plan_type: None,
             rate_limit_warnings: RateLimitWarningState::default(),
+            context_warnings: ContextWarningState::default(),
             rate_limit_switch_prompt: RateLimitSwitchPromptState::default(),
             rate_limit_poller: None,
+            offline: false,
+            connectivity_probe: None,
             adaptive_chunking: AdaptiveChunkingPolicy::default(),
             stream_controller: None,
             plan_stream_controller: None,
             last_copyable_output: None,
+            last_turn_answer: None,
+            retry_baseline: None,
+            device_code_login_cancel: None,
             running_commands: HashMap::new(),
             suppressed_exec_calls: HashSet::new(),
             last_unified_wait: None,
@@ -3321,9 +4181,15 @@ impl ChatWidget {
             pre_review_token_info: None,
             needs_final_message_separator: false,
             had_work_activity: false,
+            unsummarized_turns: false,
+            session_started_at: Local::now(),
+            replay_event_time: None,
             last_separator_elapsed_secs: None,
             turn_runtime_metrics: RuntimeMetricsSummary::default(),
             last_rendered_width: std::cell::Cell::new(None),
+            sampling: SamplingParams::default(),
+            pinned_files: PinnedFiles::default(),
+            plan_steps: PlanSteps::default(),
             feedback,
             feedback_audience,
             current_rollout_path: None,
@@ -3334,6 +4200,7 @@ impl ChatWidget {
             status_line_branch_cwd: None,
             status_line_branch_pending: false,
             status_line_branch_lookup_complete: false,
+            status_line_commands: StatusLineCommands::default(),
             external_editor_state: ExternalEditorState::Closed,
             realtime_conversation: RealtimeConversationUiState::default(),
             last_rendered_user_message_event: None,
@@ -3358,6 +4225,15 @@ impl ChatWidget {
         widget
             .bottom_pane
             .set_queued_message_edit_binding(widget.queued_message_edit_binding);
+        widget
+            .bottom_pane
+            .set_interrupt_key(widget.configured_interrupt_key());
+        widget
+            .bottom_pane
+            .set_footer_hints(FooterHints::from_config(
+                widget.config.tui_footer_hints.as_deref(),
+            ));
+        widget.bottom_pane.set_read_only(widget.config.read_only);
         widget
             .bottom_pane
             .set_connectors_enabled(widget.config.features.enabled(Feature::Apps));
@@ -3436,6 +4312,11 @@ impl ChatWidget {
             }),
             active_cell: None,
             active_cell_revision: 0,
+            file_tree: FileTreeSidebar::new(config.cwd.clone()),
+            diff_pane: DiffPane::default(),
+            last_review_output: None,
+            conflict_queue: VecDeque::new(),
+            conflict_total: 0,
             config,
             skills_all: Vec::new(),
             skills_initial_state: None,
@@ -3450,12 +4331,18 @@ impl ChatWidget {
             rate_limit_snapshots_by_limit_id: BTreeMap::new(),
             plan_type: None,
             rate_limit_warnings: RateLimitWarningState::default(),
+            context_warnings: ContextWarningState::default(),
             rate_limit_switch_prompt: RateLimitSwitchPromptState::default(),
             rate_limit_poller: None,
+            offline: false,
+            connectivity_probe: None,
             adaptive_chunking: AdaptiveChunkingPolicy::default(),
             stream_controller: None,
             plan_stream_controller: None,
             last_copyable_output: None,
+            last_turn_answer: None,
+            retry_baseline: None,
+            device_code_login_cancel: None,
             running_commands: HashMap::new(),
             suppressed_exec_calls: HashSet::new(),
             last_unified_wait: None,
@@ -3491,6 +4378,9 @@ impl ChatWidget {
             pre_review_token_info: None,
             needs_final_message_separator: false,
             had_work_activity: false,
+            unsummarized_turns: false,
+            session_started_at: Local::now(),
+            replay_event_time: None,
             saw_plan_update_this_turn: false,
             saw_plan_item_this_turn: false,
             plan_delta_buffer: String::new(),
@@ -3498,6 +4388,9 @@ impl ChatWidget {
             last_separator_elapsed_secs: None,
             turn_runtime_metrics: RuntimeMetricsSummary::default(),
             last_rendered_width: std::cell::Cell::new(None),
+            sampling: SamplingParams::default(),
+            pinned_files: PinnedFiles::default(),
+            plan_steps: PlanSteps::default(),
             feedback,
             feedback_audience,
             current_rollout_path: None,
@@ -3508,6 +4401,7 @@ impl ChatWidget {
             status_line_branch_cwd: None,
             status_line_branch_pending: false,
             status_line_branch_lookup_complete: false,
+            status_line_commands: StatusLineCommands::default(),
             external_editor_state: ExternalEditorState::Closed,
             realtime_conversation: RealtimeConversationUiState::default(),
             last_rendered_user_message_event: None,
@@ -3532,6 +4426,15 @@ impl ChatWidget {
         widget
             .bottom_pane
             .set_queued_message_edit_binding(widget.queued_message_edit_binding);
+        widget
+            .bottom_pane
+            .set_interrupt_key(widget.configured_interrupt_key());
+        widget
+            .bottom_pane
+            .set_footer_hints(FooterHints::from_config(
+                widget.config.tui_footer_hints.as_deref(),
+            ));
+        widget.bottom_pane.set_read_only(widget.config.read_only);
         #[cfg(target_os = "windows")]
         widget.bottom_pane.set_windows_degraded_sandbox_active(
             codex_core::windows_sandbox::ELEVATED_SANDBOX_NUX_ENABLED
@@ -3607,6 +4510,13 @@ impl ChatWidget {
             _ => {}
         }
 
+        if self.file_tree.is_focused() {
+            if key_event.kind != KeyEventKind::Release {
+                self.handle_file_tree_key(key_event);
+            }
+            return;
+        }
+
         if key_event.kind == KeyEventKind::Press
             && self.queued_message_edit_binding.is_press(key_event)
             && !self.queued_user_messages.is_empty()
@@ -3728,6 +4638,10 @@ impl ChatWidget {
         self.bottom_pane.composer_text_with_pending()
     }
 
+    pub(crate) fn pending_approval_summaries(&self) -> Vec<String> {
+        self.bottom_pane.pending_approval_summaries()
+    }
+
     pub(crate) fn apply_external_edit(&mut self, text: String) {
         self.bottom_pane.apply_external_edit(text);
         self.request_redraw();
@@ -3754,6 +4668,21 @@ impl ChatWidget {
         self.bottom_pane.can_launch_external_editor()
     }
 
+    pub(crate) fn available_slash_commands(&self) -> Vec<(&'static str, SlashCommand)> {
+        self.bottom_pane.available_slash_commands()
+    }
+
+    pub(crate) fn no_modal_or_popup_active(&self) -> bool {
+        self.bottom_pane.no_modal_or_popup_active() && !self.file_tree.is_focused()
+    }
+
+    pub(crate) fn open_command_palette(&mut self) {
+        let params =
+            crate::bottom_pane::command_palette_params(self.bottom_pane.available_slash_commands());
+        self.bottom_pane.show_selection_view(params);
+        self.request_redraw();
+    }
+
     pub(crate) fn can_run_ctrl_l_clear_now(&mut self) -> bool {
         // Ctrl+L is not a slash command, but it follows /clear's current rule:
         // block while a task is running.
@@ -3767,7 +4696,7 @@ impl ChatWidget {
         false
     }
 
-    fn dispatch_command(&mut self, cmd: SlashCommand) {
+    pub(crate) fn dispatch_command(&mut self, cmd: SlashCommand) {
         if !cmd.available_during_task() && self.bottom_pane.is_task_running() {
             let message = format!(
                 "'/{}' is disabled while a task is in progress.",
@@ -3820,6 +4749,15 @@ impl ChatWidget {
                 self.clear_token_usage();
                 self.app_event_tx.send(AppEvent::CodexOp(Op::Compact));
             }
+            SlashCommand::Trim => {
+                self.app_event_tx.send(AppEvent::OpenTrimPicker);
+            }
+            SlashCommand::Retry => {
+                self.open_retry_popup();
+            }
+            SlashCommand::Params => {
+                self.open_params_popup();
+            }
             SlashCommand::Review => {
                 self.open_review_popup();
             }
@@ -3884,6 +4822,9 @@ impl ChatWidget {
             SlashCommand::Agent | SlashCommand::MultiAgents => {
                 self.app_event_tx.send(AppEvent::OpenAgentPicker);
             }
+            SlashCommand::Tasks => {
+                self.app_event_tx.send(AppEvent::OpenTaskBoard);
+            }
             SlashCommand::Approvals => {
                 self.open_permissions_popup();
             }
@@ -3980,6 +4921,71 @@ impl ChatWidget {
                     tx.send(AppEvent::DiffResult(text));
                 });
             }
+            SlashCommand::Conflicts => {
+                self.find_merge_conflicts();
+            }
+            SlashCommand::CommitMsg => {
+                self.add_info_message(
+                    "Drafting a commit message for the staged changes…".to_string(),
+                    None,
+                );
+                self.app_event_tx.send(AppEvent::DraftCommitMessage);
+            }
+            SlashCommand::Pr => {
+                self.create_pull_request(String::new());
+            }
+            SlashCommand::Summarize => {
+                if self.current_rollout_path.is_none() {
+                    self.add_info_message(
+                        "This session is not being saved, so there is nothing to summarize."
+                            .to_string(),
+                        None,
+                    );
+                    return;
+                }
+                self.add_info_message("Summarizing the session…".to_string(), None);
+                self.app_event_tx
+                    .send(AppEvent::SummarizeSession { exit_after: false });
+            }
+            SlashCommand::ExportReview => {
+                self.add_info_message(
+                    "Usage: /export-review <path>".to_string(),
+                    Some(
+                        "A .json path gets the structured findings; any other path gets markdown."
+                            .to_string(),
+                    ),
+                );
+            }
+            SlashCommand::PostReview => {
+                self.add_info_message(
+                    "Usage: /post-review <pull-request-number>".to_string(),
+                    Some("The comment goes to the repository configured for /pr.".to_string()),
+                );
+            }
+            SlashCommand::Compare => {
+                self.add_info_message(
+                    "Usage: /compare <session> [other-session]".to_string(),
+                    Some(
+                        "Sessions can be rollout paths, thread ids, or thread names. With one session, it is compared against the current one."
+                            .to_string(),
+                    ),
+                );
+            }
+            SlashCommand::CompareModels => {
+                self.add_info_message(
+                    "Usage: /compare-models <prompt>".to_string(),
+                    Some(
+                        "The prompt goes to both models in `compare_models` with read-only tools; the answers open side by side."
+                            .to_string(),
+                    ),
+                );
+            }
+            SlashCommand::Stats => {
+                self.show_session_stats(None);
+            }
+            SlashCommand::Login => {
+                self.open_auth_profile_picker();
+            }
             SlashCommand::Copy => {
                 let Some(text) = self.last_copyable_output.as_deref() else {
                     self.add_info_message(
@@ -4011,15 +5017,63 @@ impl ChatWidget {
             SlashCommand::Mention => {
                 self.insert_str("@");
             }
+            SlashCommand::Scrollback => {
+                self.attach_scrollback(scrollback_capture::DEFAULT_SCROLLBACK_LINES);
+            }
+            SlashCommand::Ci => {
+                self.add_error_message("Usage: /ci <file-or-url>".to_string());
+            }
+            SlashCommand::Tree => {
+                self.file_tree.toggle();
+                self.request_redraw();
+            }
+            SlashCommand::Split => {
+                if self.diff_pane.toggle() {
+                    self.refresh_diff_pane();
+                }
+                self.request_redraw();
+            }
+            SlashCommand::Pin => {
+                self.show_pinned_files();
+            }
+            SlashCommand::Unpin => {
+                let count = self.pinned_files.clear();
+                self.sync_pinned_files();
+                self.add_info_message(
+                    format!(
+                        "Unpinned {count} file{}.",
+                        if count == 1 { "" } else { "s" }
+                    ),
+                    None,
+                );
+            }
+            SlashCommand::Cd => {
+                self.add_info_message(
+                    format!("Working directory: {}", self.config.cwd.display()),
+                    Some("Use /cd <path> to change it.".to_string()),
+                );
+            }
             SlashCommand::Skills => {
                 self.open_skills_menu();
             }
             SlashCommand::Status => {
                 self.add_status_output();
             }
+            SlashCommand::Config => {
+                self.app_event_tx.send(AppEvent::OpenConfigEditor);
+            }
             SlashCommand::DebugConfig => {
                 self.add_debug_config_output();
             }
+            SlashCommand::DebugEvents => {
+                self.app_event_tx.send(AppEvent::OpenEventInspector);
+            }
+            SlashCommand::DebugEnv => {
+                self.app_event_tx.send(AppEvent::OpenEnvironmentOverlay);
+            }
+            SlashCommand::DebugContext => {
+                self.app_event_tx.send(AppEvent::OpenContextInspector);
+            }
             SlashCommand::Statusline => {
                 self.open_status_line_setup();
             }
@@ -4139,6 +5193,20 @@ impl ChatWidget {
                     }
                 }
             }
+            SlashCommand::Params if !trimmed.is_empty() => {
+                let Some((prepared_args, _prepared_elements)) =
+                    self.bottom_pane.prepare_inline_args_submission(false)
+                else {
+                    return;
+                };
+                self.bottom_pane.drain_pending_submission_state();
+                match parse_sampling_args(self.sampling, &prepared_args) {
+                    Ok(sampling) => self.set_sampling_params(sampling),
+                    Err(message) => self.add_error_message(format!(
+                        "{message}. Usage: /params [temperature=<0-2>] [top_p=<0-1>] | reset"
+                    )),
+                }
+            }
             SlashCommand::Rename if !trimmed.is_empty() => {
                 self.otel_manager.counter("codex.thread.rename", 1, &[]);
                 let Some((prepared_args, _prepared_elements)) =
@@ -4203,64 +5271,1245 @@ impl ChatWidget {
                 });
                 self.bottom_pane.drain_pending_submission_state();
             }
-            SlashCommand::SandboxReadRoot if !trimmed.is_empty() => {
+            SlashCommand::Compare if !trimmed.is_empty() => {
                 let Some((prepared_args, _prepared_elements)) =
                     self.bottom_pane.prepare_inline_args_submission(false)
                 else {
                     return;
                 };
-                self.app_event_tx
-                    .send(AppEvent::BeginWindowsSandboxGrantReadRoot {
-                        path: prepared_args,
-                    });
                 self.bottom_pane.drain_pending_submission_state();
+                let references: Vec<String> = prepared_args
+                    .split_whitespace()
+                    .map(str::to_string)
+                    .collect();
+                let (left, right) = match references.as_slice() {
+                    [left, right] => (Some(left.clone()), right.clone()),
+                    [other] => (None, other.clone()),
+                    _ => {
+                        self.dispatch_command(cmd);
+                        return;
+                    }
+                };
+                let current = self.rollout_path();
+                let tx = self.app_event_tx.clone();
+                let codex_home = self.config.codex_home.clone();
+                let cwd = self.config.cwd.clone();
+                tokio::spawn(async move {
+                    let result = async {
+                        let left = match (left, current) {
+                            (Some(reference), _) => {
+                                resolve_session_path(&codex_home, &cwd, &reference).await?
+                            }
+                            (None, Some(path)) => path,
+                            (None, None) => {
+                                return Err("The current session has no rollout yet; name two sessions to compare.".to_string());
+                            }
+                        };
+                        let right = resolve_session_path(&codex_home, &cwd, &right).await?;
+                        compare_rollouts(&left, &right)
+                            .await
+                            .map_err(|err| format!("Failed to read sessions: {err}"))
+                    }
+                    .await;
+                    tx.send(AppEvent::SessionComparisonResult(result));
+                });
             }
-            _ => self.dispatch_command(cmd),
-        }
-    }
-
-    fn show_rename_prompt(&mut self) {
-        let tx = self.app_event_tx.clone();
-        let has_name = self
-            .thread_name
-            .as_ref()
-            .is_some_and(|name| !name.is_empty());
-        let title = if has_name {
-            "Rename thread"
-        } else {
-            "Name thread"
-        };
-        let thread_id = self.thread_id;
-        let view = CustomPromptView::new(
-            title.to_string(),
-            "Type a name and press Enter".to_string(),
-            None,
-            Box::new(move |name: String| {
-                let Some(name) = codex_core::util::normalize_thread_name(&name) else {
-                    tx.send(AppEvent::InsertHistoryCell(Box::new(
-                        history_cell::new_error_event("Thread name cannot be empty.".to_string()),
-                    )));
+            SlashCommand::CompareModels if !trimmed.is_empty() => {
+                let Some((prepared_args, _prepared_elements)) =
+                    self.bottom_pane.prepare_inline_args_submission(false)
+                else {
                     return;
                 };
-                let cell = Self::rename_confirmation_cell(&name, thread_id);
-                tx.send(AppEvent::InsertHistoryCell(Box::new(cell)));
-                tx.send(AppEvent::CodexOp(Op::SetThreadName { name }));
-            }),
-        );
-
-        self.bottom_pane.show_view(Box::new(view));
-    }
-
-    pub(crate) fn handle_paste(&mut self, text: String) {
-        self.bottom_pane.handle_paste(text);
-    }
-
-    // Returns true if caller should skip rendering this frame (a future frame is scheduled).
-    pub(crate) fn handle_paste_burst_tick(&mut self, frame_requester: FrameRequester) -> bool {
-        if self.bottom_pane.flush_paste_burst_if_due() {
-            // A paste just flushed; request an immediate redraw and skip this frame.
-            self.request_redraw();
-            true
+                self.bottom_pane.drain_pending_submission_state();
+                let models = &self.config.compare_models;
+                if models.len() != 2 {
+                    self.add_error_message(
+                        "Set `compare_models = [\"model-a\", \"model-b\"]` in config.toml to use /compare-models."
+                            .to_string(),
+                    );
+                    return;
+                }
+                self.add_info_message(
+                    format!("Sending the prompt to {} and {}…", models[0], models[1]),
+                    Some(
+                        "Both runs use read-only tools; results open when both finish.".to_string(),
+                    ),
+                );
+                self.app_event_tx.send(AppEvent::CompareModels {
+                    prompt: prepared_args,
+                });
+            }
+            SlashCommand::Scrollback if !trimmed.is_empty() => {
+                let Some((prepared_args, _prepared_elements)) =
+                    self.bottom_pane.prepare_inline_args_submission(false)
+                else {
+                    return;
+                };
+                self.bottom_pane.drain_pending_submission_state();
+                match scrollback_capture::parse_line_count(&prepared_args) {
+                    Ok(lines) => self.attach_scrollback(lines),
+                    Err(message) => self.add_error_message(message),
+                }
+            }
+            SlashCommand::Ci if !trimmed.is_empty() => {
+                let Some((prepared_args, _prepared_elements)) =
+                    self.bottom_pane.prepare_inline_args_submission(false)
+                else {
+                    return;
+                };
+                self.bottom_pane.drain_pending_submission_state();
+                self.attach_ci_log(prepared_args);
+            }
+            SlashCommand::Pr if !trimmed.is_empty() => {
+                let Some((prepared_args, _prepared_elements)) =
+                    self.bottom_pane.prepare_inline_args_submission(false)
+                else {
+                    return;
+                };
+                self.bottom_pane.drain_pending_submission_state();
+                self.create_pull_request(prepared_args);
+            }
+            SlashCommand::ExportReview if !trimmed.is_empty() => {
+                let Some((prepared_args, _prepared_elements)) =
+                    self.bottom_pane.prepare_inline_args_submission(false)
+                else {
+                    return;
+                };
+                self.bottom_pane.drain_pending_submission_state();
+                self.export_review(&prepared_args);
+            }
+            SlashCommand::PostReview if !trimmed.is_empty() => {
+                let Some((prepared_args, _prepared_elements)) =
+                    self.bottom_pane.prepare_inline_args_submission(false)
+                else {
+                    return;
+                };
+                self.bottom_pane.drain_pending_submission_state();
+                self.post_review(&prepared_args);
+            }
+            SlashCommand::Pin if !trimmed.is_empty() => {
+                let Some((prepared_args, _prepared_elements)) =
+                    self.bottom_pane.prepare_inline_args_submission(false)
+                else {
+                    return;
+                };
+                self.bottom_pane.drain_pending_submission_state();
+                match self.pinned_files.pin(&prepared_args, &self.config.cwd) {
+                    Ok(file) => {
+                        let message = format!(
+                            "Pinned {} (~{} tokens per request).",
+                            display_path_for(&file.path, &self.config.cwd),
+                            format_tokens_compact(file.tokens)
+                        );
+                        self.sync_pinned_files();
+                        self.add_info_message(message, None);
+                    }
+                    Err(message) => self.add_error_message(message),
+                }
+            }
+            SlashCommand::Unpin if !trimmed.is_empty() => {
+                let Some((prepared_args, _prepared_elements)) =
+                    self.bottom_pane.prepare_inline_args_submission(false)
+                else {
+                    return;
+                };
+                self.bottom_pane.drain_pending_submission_state();
+                match self.pinned_files.unpin(&prepared_args, &self.config.cwd) {
+                    Ok(path) => {
+                        self.sync_pinned_files();
+                        self.add_info_message(
+                            format!("Unpinned {}.", display_path_for(&path, &self.config.cwd)),
+                            None,
+                        );
+                    }
+                    Err(message) => self.add_error_message(message),
+                }
+            }
+            SlashCommand::Cd if !trimmed.is_empty() => {
+                let Some((prepared_args, _prepared_elements)) =
+                    self.bottom_pane.prepare_inline_args_submission(false)
+                else {
+                    return;
+                };
+                self.bottom_pane.drain_pending_submission_state();
+                match change_dir::resolve_cd_target(
+                    &prepared_args,
+                    &self.config.cwd,
+                    self.config.permissions.sandbox_policy.get(),
+                ) {
+                    Ok(cwd) => self.app_event_tx.send(AppEvent::ChangeCwd(cwd)),
+                    Err(message) => self.add_error_message(message),
+                }
+            }
+            SlashCommand::Stats if !trimmed.is_empty() => {
+                let Some((prepared_args, _prepared_elements)) =
+                    self.bottom_pane.prepare_inline_args_submission(false)
+                else {
+                    return;
+                };
+                self.bottom_pane.drain_pending_submission_state();
+                self.show_session_stats(Some(prepared_args.trim().to_string()));
+            }
+            SlashCommand::Login if !trimmed.is_empty() => {
+                let Some((prepared_args, _prepared_elements)) =
+                    self.bottom_pane.prepare_inline_args_submission(false)
+                else {
+                    return;
+                };
+                self.bottom_pane.drain_pending_submission_state();
+                let mut parts = prepared_args.split_whitespace();
+                match (parts.next(), parts.next(), parts.next()) {
+                    (Some("switch"), None, None) => self.open_auth_profile_picker(),
+                    (Some("switch"), Some(name), None) => self.switch_auth_profile(name),
+                    (Some("save"), Some(name), None) => self.save_auth_profile(name),
+                    (Some("device"), None, None) => self.start_device_code_login(),
+                    (Some("cancel"), None, None) => self.cancel_device_code_login(),
+                    (Some("status"), None, None) => self.open_auth_status_view(),
+                    _ => self.add_error_message(
+                        "Usage: /login [status | switch [<name>] | save <name> | device | cancel]"
+                            .to_string(),
+                    ),
+                }
+            }
+            SlashCommand::SandboxReadRoot if !trimmed.is_empty() => {
+                let Some((prepared_args, _prepared_elements)) =
+                    self.bottom_pane.prepare_inline_args_submission(false)
+                else {
+                    return;
+                };
+                self.app_event_tx
+                    .send(AppEvent::BeginWindowsSandboxGrantReadRoot {
+                        path: prepared_args,
+                    });
+                self.bottom_pane.drain_pending_submission_state();
+            }
+            _ => self.dispatch_command(cmd),
+        }
+    }
+
+    fn open_auth_profile_picker(&mut self) {
+        let profiles = match self.auth_manager.auth_profiles() {
+            Ok(profiles) => profiles,
+            Err(err) => {
+                self.add_error_message(format!("Failed to list auth profiles: {err}"));
+                return;
+            }
+        };
+        if profiles.is_empty() {
+            self.add_info_message(
+                "No saved auth profiles.".to_string(),
+                Some("Run `/login save <name>` to save the current credentials.".to_string()),
+            );
+            return;
+        }
+
+        let items = profiles
+            .into_iter()
+            .map(|profile| {
+                let description = match profile.auth_mode {
+                    codex_app_server_protocol::AuthMode::ApiKey => "API key".to_string(),
+                    codex_app_server_protocol::AuthMode::Chatgpt
+                    | codex_app_server_protocol::AuthMode::ChatgptAuthTokens => profile
+                        .email
+                        .unwrap_or_else(|| "ChatGPT account".to_string()),
+                };
+                let name = profile.name.clone();
+                let action: SelectionAction = Box::new(move |tx| {
+                    tx.send(AppEvent::SwitchAuthProfile(name.clone()));
+                });
+                SelectionItem {
+                    name: profile.name,
+                    description: Some(description),
+                    is_current: profile.is_active,
+                    actions: vec![action],
+                    dismiss_on_select: true,
+                    ..Default::default()
+                }
+            })
+            .collect();
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            title: Some("Switch account".to_string()),
+            subtitle: Some("Save more accounts with /login save <name>.".to_string()),
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            ..Default::default()
+        });
+        self.request_redraw();
+    }
+
+    pub(crate) fn switch_auth_profile(&mut self, name: &str) {
+        match self.auth_manager.switch_auth_profile(name) {
+            Ok(_) => self.add_info_message(format!("Switched to auth profile `{name}`."), None),
+            Err(err) => {
+                self.add_error_message(format!("Failed to switch to auth profile `{name}`: {err}"));
+            }
+        }
+        self.update_auth_expiry_warning();
+    }
+
+    fn open_auth_status_view(&mut self) {
+        let Some(auth) = self.auth_manager.auth_cached() else {
+            self.add_info_message(
+                "Not logged in.".to_string(),
+                Some("Run /login device to sign in with ChatGPT.".to_string()),
+            );
+            return;
+        };
+
+        let mut rows = Vec::new();
+        if auth.is_chatgpt_auth() {
+            rows.push((
+                "Account",
+                auth.get_account_email()
+                    .unwrap_or_else(|| "ChatGPT account".to_string()),
+            ));
+            if let Some(plan) = auth
+                .account_plan_type()
+                .filter(|plan| *plan != PlanType::Unknown)
+            {
+                rows.push(("Plan", format!("{plan:?}")));
+            }
+            if let Some(account_id) = auth.get_account_id() {
+                rows.push(("Workspace", account_id));
+            }
+            let expiry = match auth.access_token_expires_at() {
+                Some(expires_at) => format!(
+                    "{} ({})",
+                    expires_at.with_timezone(&Local).format("%Y-%m-%d %H:%M"),
+                    describe_token_expiry(expires_at, Utc::now())
+                ),
+                None => "unknown".to_string(),
+            };
+            rows.push(("Token expires", expiry));
+        } else {
+            rows.push(("Account", "API key".to_string()));
+        }
+        if let Some(profile) = self.auth_manager.active_auth_profile() {
+            rows.push(("Profile", profile));
+        }
+
+        let mut header = ColumnRenderable::new();
+        header.push(Line::from("Login status".bold()));
+        for (label, value) in rows {
+            header.push(Line::from(vec![format!("{label:<15}").dim(), value.into()]));
+        }
+
+        let refresh: SelectionAction = Box::new(|tx| tx.send(AppEvent::RefreshAuthToken));
+        let items = vec![SelectionItem {
+            name: "Refresh now".to_string(),
+            description: Some(
+                "Request a new access token instead of waiting for expiry.".to_string(),
+            ),
+            actions: vec![refresh],
+            dismiss_on_select: true,
+            disabled_reason: (!auth.is_chatgpt_auth())
+                .then(|| "API keys do not expire.".to_string()),
+            ..Default::default()
+        }];
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            header: Box::new(header),
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            ..Default::default()
+        });
+        self.request_redraw();
+    }
+
+    pub(crate) fn refresh_auth_token(&mut self) {
+        self.add_info_message("Refreshing ChatGPT access token...".to_string(), None);
+        let auth_manager = Arc::clone(&self.auth_manager);
+        let tx = self.app_event_tx.clone();
+        tokio::spawn(async move {
+            let result = auth_manager
+                .refresh_token()
+                .await
+                .map_err(|err| format!("Failed to refresh access token: {err}"));
+            tx.send(AppEvent::AuthTokenRefreshFinished(result));
+        });
+    }
+
+    pub(crate) fn on_auth_token_refresh_finished(&mut self, result: Result<(), String>) {
+        match result {
+            Ok(()) => {
+                let hint = self
+                    .auth_manager
+                    .auth_cached()
+                    .as_ref()
+                    .and_then(CodexAuth::access_token_expires_at)
+                    .map(|expires_at| {
+                        format!(
+                            "The new token expires {}.",
+                            describe_token_expiry(expires_at, Utc::now())
+                        )
+                    });
+                self.add_info_message("Access token refreshed.".to_string(), hint);
+            }
+            Err(err) => self.add_error_message(err),
+        }
+        self.update_auth_expiry_warning();
+    }
+
+    /// Re-check the access token expiry so a token that is about to lapse mid-session is flagged
+    /// above the composer.
+    fn update_auth_expiry_warning(&mut self) {
+        let expires_at = self
+            .auth_manager
+            .auth_cached()
+            .as_ref()
+            .and_then(CodexAuth::access_token_expires_at);
+        self.bottom_pane
+            .set_auth_expiry_warning(auth_expiry_warning(expires_at, Utc::now()));
+    }
+
+    fn save_auth_profile(&mut self, name: &str) {
+        match self.auth_manager.save_auth_profile(name) {
+            Ok(()) => self.add_info_message(
+                format!("Saved the current credentials as auth profile `{name}`."),
+                Some("Switch back to it later with /login switch.".to_string()),
+            ),
+            Err(err) => {
+                self.add_error_message(format!("Failed to save auth profile `{name}`: {err}"));
+            }
+        }
+    }
+
+    /// Sign in with the OAuth device-code flow, which only needs a browser on some other machine,
+    /// so it works over SSH where the localhost callback used by onboarding cannot be reached.
+    fn start_device_code_login(&mut self) {
+        if self.device_code_login_cancel.is_some() {
+            self.add_info_message(
+                "A device code login is already in progress.".to_string(),
+                Some("Run /login cancel to stop it.".to_string()),
+            );
+            return;
+        }
+        let opts = ServerOptions::new(
+            self.config.codex_home.clone(),
+            CLIENT_ID.to_string(),
+            self.config.forced_chatgpt_workspace_id.clone(),
+            self.config.cli_auth_credentials_store_mode,
+        );
+        let cancel = Arc::new(Notify::new());
+        self.device_code_login_cancel = Some(Arc::clone(&cancel));
+        self.add_info_message("Requesting a one-time sign-in code...".to_string(), None);
+
+        let tx = self.app_event_tx.clone();
+        tokio::spawn(async move {
+            let login = async {
+                let device_code = request_device_code(&opts)
+                    .await
+                    .map_err(|err| format!("Failed to request a device code: {err}"))?;
+                tx.send(AppEvent::DeviceCodeIssued {
+                    verification_url: device_code.verification_url.clone(),
+                    user_code: device_code.user_code.clone(),
+                });
+                complete_device_code_login(opts, device_code)
+                    .await
+                    .map_err(|err| format!("Device code login failed: {err}"))
+            };
+            tokio::select! {
+                _ = cancel.notified() => {}
+                result = login => tx.send(AppEvent::DeviceCodeLoginFinished(result)),
+            }
+        });
+    }
+
+    fn cancel_device_code_login(&mut self) {
+        match self.device_code_login_cancel.take() {
+            Some(cancel) => {
+                cancel.notify_one();
+                self.add_info_message("Device code login cancelled.".to_string(), None);
+            }
+            None => {
+                self.add_info_message("No device code login is in progress.".to_string(), None);
+            }
+        }
+    }
+
+    pub(crate) fn on_device_code_issued(&mut self, verification_url: String, user_code: String) {
+        if self.device_code_login_cancel.is_none() {
+            return;
+        }
+        self.add_to_history(history_cell::new_device_code_login(
+            verification_url,
+            user_code,
+        ));
+        self.request_redraw();
+    }
+
+    pub(crate) fn on_device_code_login_finished(&mut self, result: Result<(), String>) {
+        if self.device_code_login_cancel.take().is_none() {
+            return;
+        }
+        match result {
+            Ok(()) => {
+                self.auth_manager.reload();
+                self.add_info_message(
+                    "Signed in with ChatGPT.".to_string(),
+                    Some("Keep these credentials with /login save <name>.".to_string()),
+                );
+            }
+            Err(err) => self.add_error_message(err),
+        }
+        self.update_auth_expiry_warning();
+    }
+
+    fn show_rename_prompt(&mut self) {
+        let tx = self.app_event_tx.clone();
+        let has_name = self
+            .thread_name
+            .as_ref()
+            .is_some_and(|name| !name.is_empty());
+        let title = if has_name {
+            "Rename thread"
+        } else {
+            "Name thread"
+        };
+        let thread_id = self.thread_id;
+        let view = CustomPromptView::new(
+            title.to_string(),
+            "Type a name and press Enter".to_string(),
+            None,
+            Box::new(move |name: String| {
+                let Some(name) = codex_core::util::normalize_thread_name(&name) else {
+                    tx.send(AppEvent::InsertHistoryCell(Box::new(
+                        history_cell::new_error_event("Thread name cannot be empty.".to_string()),
+                    )));
+                    return;
+                };
+                let cell = Self::rename_confirmation_cell(&name, thread_id);
+                tx.send(AppEvent::InsertHistoryCell(Box::new(cell)));
+                tx.send(AppEvent::CodexOp(Op::SetThreadName { name }));
+            }),
+        );
+
+        self.bottom_pane.show_view(Box::new(view));
+    }
+
+    pub(crate) fn handle_paste(&mut self, text: String) {
+        if let Some(path) = audio_attachment::pasted_audio_path(&text) {
+            self.attach_audio(path);
+            return;
+        }
+        if paste_file::is_oversized(&text) && self.bottom_pane.no_modal_or_popup_active() {
+            self.open_oversized_paste_prompt(text);
+            return;
+        }
+        self.bottom_pane.handle_paste(text);
+    }
+
+    /// Ask whether a very large paste should go into the draft or be saved to a file.
+    fn open_oversized_paste_prompt(&mut self, text: String) {
+        let size = format_bytes(text.len() as u64);
+        let text = Arc::new(text);
+        let resolve = |as_file: bool| -> SelectionAction {
+            let text = Arc::clone(&text);
+            Box::new(move |tx| {
+                tx.send(AppEvent::ResolveOversizedPaste {
+                    text: Arc::clone(&text),
+                    as_file,
+                });
+            })
+        };
+        let items = vec![
+            SelectionItem {
+                name: "Attach as a file".to_string(),
+                description: Some(
+                    "Save it to a temporary file; the model reads it from there.".to_string(),
+                ),
+                actions: vec![resolve(true)],
+                dismiss_on_select: true,
+                ..Default::default()
+            },
+            SelectionItem {
+                name: "Paste as text".to_string(),
+                description: Some("Send the whole paste with your message.".to_string()),
+                actions: vec![resolve(false)],
+                dismiss_on_select: true,
+                ..Default::default()
+            },
+        ];
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            title: Some(format!("Paste {size} as a file?")),
+            subtitle: Some(
+                "Sending this much text with the message uses much of the context window."
+                    .to_string(),
+            ),
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            ..Default::default()
+        });
+    }
+
+    pub(crate) fn resolve_oversized_paste(&mut self, text: Arc<String>, as_file: bool) {
+        if !as_file {
+            self.bottom_pane.handle_paste(Arc::unwrap_or_clone(text));
+            return;
+        }
+        let tx = self.app_event_tx.clone();
+        tokio::spawn(async move {
+            tx.send(AppEvent::OversizedPasteSaved(paste_file::save(text).await));
+        });
+    }
+
+    pub(crate) fn on_oversized_paste_saved(&mut self, result: Result<SavedPaste, String>) {
+        match result {
+            Ok(saved) => self.bottom_pane.attach_pasted_file(&saved),
+            Err(err) => self.add_error_message(format!("Failed to save the paste: {err}")),
+        }
+        self.request_redraw();
+    }
+
+    /// Offer corrections for the misspelling under or before the composer cursor.
+    pub(crate) fn open_spelling_suggestions(&mut self) {
+        let Some(checker) = spellcheck::get() else {
+            return;
+        };
+        let Some((range, word)) = self.bottom_pane.misspelling_near_cursor() else {
+            return;
+        };
+        let suggestions = checker.suggest(&word);
+        let subtitle = suggestions
+            .is_empty()
+            .then(|| "No suggestions.".to_string());
+        let mut items: Vec<SelectionItem> = suggestions
+            .into_iter()
+            .map(|replacement| {
+                let range = range.clone();
+                let word = word.clone();
+                SelectionItem {
+                    name: replacement.clone(),
+                    actions: vec![Box::new(move |tx| {
+                        tx.send(AppEvent::ReplaceMisspelling {
+                            range: range.clone(),
+                            word: word.clone(),
+                            replacement: replacement.clone(),
+                        });
+                    })],
+                    dismiss_on_select: true,
+                    ..Default::default()
+                }
+            })
+            .collect();
+        let added = word.clone();
+        items.push(SelectionItem {
+            name: format!("Add \"{word}\" to the dictionary"),
+            description: Some("Saved in $CODEX_HOME/dictionary.txt.".to_string()),
+            actions: vec![Box::new(move |tx| {
+                tx.send(AppEvent::AddToDictionary(added.clone()));
+            })],
+            dismiss_on_select: true,
+            ..Default::default()
+        });
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            title: Some(format!("Spelling: {word}")),
+            subtitle,
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            ..Default::default()
+        });
+    }
+
+    pub(crate) fn replace_misspelling(
+        &mut self,
+        range: Range<usize>,
+        word: &str,
+        replacement: &str,
+    ) {
+        self.bottom_pane
+            .replace_misspelling(range, word, replacement);
+    }
+
+    pub(crate) fn add_to_dictionary(&mut self, word: String) {
+        if let Some(checker) = spellcheck::get()
+            && let Err(err) = checker.add_word(&word)
+        {
+            self.add_error_message(format!("Failed to add \"{word}\" to the dictionary: {err}"));
+        }
+        self.request_redraw();
+    }
+
+    /// Transcribe a pasted audio file in the background; the transcript lands in the composer
+    /// once [`Self::on_audio_attachment_transcribed`] runs.
+    fn attach_audio(&mut self, path: PathBuf) {
+        self.add_info_message(
+            format!("Transcribing {}…", path.display()),
+            Some("The transcript will be added to your message.".to_string()),
+        );
+        let config = self.config.audio_transcription.clone();
+        let tx = self.app_event_tx.clone();
+        tokio::spawn(async move {
+            let result = audio_attachment::transcribe_file(&config, &path).await;
+            tx.send(AppEvent::AudioAttachmentTranscribed { path, result });
+        });
+    }
+
+    pub(crate) fn set_cwd(&mut self, cwd: PathBuf) {
+        self.file_tree.set_root(cwd.clone());
+        self.config.cwd = cwd;
+    }
+
+    /// Recompute the workspace diff shown by `/split`, if the pane is open.
+    fn refresh_diff_pane(&mut self) {
+        if !self.diff_pane.begin_refresh() {
+            return;
+        }
+        let tx = self.app_event_tx.clone();
+        tokio::spawn(async move {
+            let result = get_git_diff().await.map_err(|err| err.to_string());
+            tx.send(AppEvent::DiffPaneRefreshed(result));
+        });
+    }
+
+    pub(crate) fn on_diff_pane_refreshed(&mut self, result: Result<(bool, String), String>) {
+        if self.diff_pane.finish_refresh(result) {
+            self.refresh_diff_pane();
+        }
+        self.request_redraw();
+    }
+
+    fn handle_file_tree_key(&mut self, key_event: KeyEvent) {
+        match self.file_tree.handle_key_event(key_event) {
+            FileTreeAction::None => {}
+            FileTreeAction::Mention(path) => {
+                self.file_tree.blur();
+                self.insert_str(&format!("@{} ", path.display()));
+            }
+            FileTreeAction::OpenDiff(path) => {
+                let tx = self.app_event_tx.clone();
+                tokio::spawn(async move {
+                    let text = match get_git_diff_for_path(&path).await {
+                        Ok(diff) => diff,
+                        Err(err) => format!("Failed to compute diff: {err}"),
+                    };
+                    tx.send(AppEvent::DiffResult(text));
+                });
+            }
+            FileTreeAction::Blur => self.file_tree.blur(),
+            FileTreeAction::Close => self.file_tree.toggle(),
+        }
+        self.request_redraw();
+    }
+
+    fn attach_scrollback(&self, lines: usize) {
+        let tx = self.app_event_tx.clone();
+        tokio::spawn(async move {
+            let result = scrollback_capture::capture(lines).await;
+            tx.send(AppEvent::ScrollbackCaptured(result));
+        });
+    }
+
+    fn find_merge_conflicts(&mut self) {
+        self.add_info_message("Looking for merge conflicts…".to_string(), None);
+        let tx = self.app_event_tx.clone();
+        let cwd = self.config.cwd.clone();
+        tokio::spawn(async move {
+            let result = codex_core::find_conflicts(&cwd).await;
+            tx.send(AppEvent::ConflictsFound(result));
+        });
+    }
+
+    pub(crate) fn on_conflicts_found(&mut self, result: Result<Vec<ConflictHunk>, String>) {
+        match result {
+            Ok(hunks) if hunks.is_empty() => {
+                self.add_info_message("No merge conflicts found".to_string(), None);
+            }
+            Ok(hunks) => {
+                let files = hunks
+                    .iter()
+                    .map(|hunk| hunk.display_path.as_str())
+                    .collect::<HashSet<_>>()
+                    .len();
+                self.add_info_message(
+                    format!(
+                        "Found {} conflict{} in {files} file{}",
+                        hunks.len(),
+                        if hunks.len() == 1 { "" } else { "s" },
+                        if files == 1 { "" } else { "s" }
+                    ),
+                    Some(
+                        "Esc pauses; run /conflicts again to pick up where you left off."
+                            .to_string(),
+                    ),
+                );
+                self.conflict_total = hunks.len();
+                self.conflict_queue = hunks.into();
+                self.show_next_conflict();
+            }
+            Err(err) => {
+                self.add_error_message(format!("Failed to look for merge conflicts: {err}"));
+            }
+        }
+        self.request_redraw();
+    }
+
+    fn show_next_conflict(&mut self) {
+        let Some(hunk) = self.conflict_queue.front().cloned() else {
+            self.add_info_message(
+                "Done walking through the merge conflicts".to_string(),
+                Some("Stage the resolved files with `git add`.".to_string()),
+            );
+            return;
+        };
+        let number = self.conflict_total - self.conflict_queue.len() + 1;
+        self.add_to_history(history_cell::new_conflict_hunk(
+            &hunk,
+            number,
+            self.conflict_total,
+        ));
+        self.add_info_message("Proposing a resolution…".to_string(), None);
+        self.app_event_tx
+            .send(AppEvent::ProposeConflictResolution(hunk));
+    }
+
+    pub(crate) fn on_conflict_resolution_proposed(
+        &mut self,
+        hunk: ConflictHunk,
+        result: Result<ConflictResolution, String>,
+    ) {
+        // The walk was restarted while the model was thinking.
+        if self.conflict_queue.front() != Some(&hunk) {
+            return;
+        }
+        let proposal = match result {
+            Ok(resolution) => {
+                self.add_to_history(history_cell::new_conflict_resolution(&resolution));
+                Some(resolution.resolution)
+            }
+            Err(err) => {
+                self.add_error_message(format!("Failed to propose a resolution: {err}"));
+                None
+            }
+        };
+        self.open_conflict_choices(hunk, proposal);
+        self.request_redraw();
+    }
+
+    fn open_conflict_choices(&mut self, hunk: ConflictHunk, proposal: Option<String>) {
+        let apply = |hunk: &ConflictHunk, text: &str| -> Vec<SelectionAction> {
+            let hunk = hunk.clone();
+            let text = text.to_string();
+            vec![Box::new(move |tx| {
+                tx.send(AppEvent::ApplyConflictResolution {
+                    hunk: hunk.clone(),
+                    text: text.clone(),
+                });
+            })]
+        };
+        let mut items = Vec::new();
+        if let Some(text) = &proposal {
+            items.push(SelectionItem {
+                name: "Apply the proposed resolution".to_string(),
+                actions: apply(&hunk, text),
+                dismiss_on_select: true,
+                ..Default::default()
+            });
+        }
+        let edit_hunk = hunk.clone();
+        let edit_text = proposal.unwrap_or_else(|| hunk.ours.clone());
+        items.push(SelectionItem {
+            name: "Edit, then apply".to_string(),
+            description: Some("Start from the proposal and change it first.".to_string()),
+            actions: vec![Box::new(move |tx| {
+                tx.send(AppEvent::EditConflictResolution {
+                    hunk: edit_hunk.clone(),
+                    text: edit_text.clone(),
+                });
+            })],
+            dismiss_on_select: true,
+            ..Default::default()
+        });
+        items.push(SelectionItem {
+            name: "Keep ours".to_string(),
+            description: (!hunk.ours_label.is_empty()).then(|| hunk.ours_label.clone()),
+            actions: apply(&hunk, &hunk.ours),
+            dismiss_on_select: true,
+            ..Default::default()
+        });
+        items.push(SelectionItem {
+            name: "Keep theirs".to_string(),
+            description: (!hunk.theirs_label.is_empty()).then(|| hunk.theirs_label.clone()),
+            actions: apply(&hunk, &hunk.theirs),
+            dismiss_on_select: true,
+            ..Default::default()
+        });
+        items.push(SelectionItem {
+            name: "Skip".to_string(),
+            description: Some("Leave the markers in place for now.".to_string()),
+            actions: vec![Box::new(|tx| tx.send(AppEvent::SkipConflict))],
+            dismiss_on_select: true,
+            ..Default::default()
+        });
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            title: Some(format!(
+                "Resolve the conflict at {}:{}?",
+                hunk.display_path, hunk.line
+            )),
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            ..Default::default()
+        });
+    }
+
+    pub(crate) fn open_conflict_resolution_editor(&mut self, hunk: ConflictHunk, text: String) {
+        let title = format!("Resolve {}:{}", hunk.display_path, hunk.line);
+        let tx = self.app_event_tx.clone();
+        let view = CustomPromptView::new(
+            title,
+            "Text that replaces the conflict".to_string(),
+            Some("enter to apply · shift+enter for a new line · esc to pause".to_string()),
+            Box::new(move |text: String| {
+                tx.send(AppEvent::ApplyConflictResolution {
+                    hunk: hunk.clone(),
+                    text,
+                });
+            }),
+        )
+        .with_initial_text(&text)
+        .keep_whitespace();
+        self.bottom_pane.show_view(Box::new(view));
+    }
+
+    pub(crate) fn apply_conflict_resolution(&mut self, hunk: ConflictHunk, text: String) {
+        let tx = self.app_event_tx.clone();
+        tokio::spawn(async move {
+            let result = codex_core::apply_conflict_resolution(&hunk, &text).await;
+            tx.send(AppEvent::ConflictResolutionApplied { hunk, result });
+        });
+    }
+
+    pub(crate) fn on_conflict_resolution_applied(
+        &mut self,
+        hunk: ConflictHunk,
+        result: Result<usize, String>,
+    ) {
+        if self.conflict_queue.front() != Some(&hunk) {
+            return;
+        }
+        self.conflict_queue.pop_front();
+        match result {
+            Ok(remaining) => self.add_info_message(
+                format!(
+                    "Resolved the conflict at {}:{}",
+                    hunk.display_path, hunk.line
+                ),
+                (remaining == 0).then(|| format!("No conflicts left in {}.", hunk.display_path)),
+            ),
+            Err(err) => self.add_error_message(format!("Failed to apply the resolution: {err}")),
+        }
+        self.show_next_conflict();
+        self.request_redraw();
+    }
+
+    pub(crate) fn skip_conflict(&mut self) {
+        self.conflict_queue.pop_front();
+        self.show_next_conflict();
+        self.request_redraw();
+    }
+
+    pub(crate) fn on_session_summarized(&mut self, result: Result<SessionSummary, String>) {
+        match result {
+            Ok(summary) => {
+                self.unsummarized_turns = false;
+                self.add_to_history(history_cell::new_session_summary(
+                    &summary,
+                    "Session summary",
+                ));
+            }
+            Err(err) => {
+                self.add_error_message(format!("Failed to summarize the session: {err}"));
+            }
+        }
+    }
+
+    /// Whether exiting should first summarize the session (`tui.summarize_on_exit`). Only asks
+    /// once per exit attempt, so a second exit request skips the summary.
+    pub(crate) fn take_exit_summary_request(&mut self) -> bool {
+        if !self.config.tui_summarize_on_exit
+            || !self.unsummarized_turns
+            || self.current_rollout_path.is_none()
+        {
+            return false;
+        }
+        self.unsummarized_turns = false;
+        self.add_info_message(
+            "Summarizing the session before exiting…".to_string(),
+            Some("exit again to skip".to_string()),
+        );
+        true
+    }
+
+    pub(crate) fn on_commit_message_drafted(&mut self, result: Result<String, String>) {
+        match result {
+            Ok(message) => {
+                let tx = self.app_event_tx.clone();
+                let view = CustomPromptView::new(
+                    "Commit message".to_string(),
+                    "Commit message for the staged changes".to_string(),
+                    Some(
+                        "enter to commit · shift+enter for a new line · esc to cancel".to_string(),
+                    ),
+                    Box::new(move |message: String| {
+                        tx.send(AppEvent::CommitStaged { message });
+                    }),
+                )
+                .with_initial_text(&message);
+                self.bottom_pane.show_view(Box::new(view));
+            }
+            Err(err) => {
+                self.add_error_message(format!("Failed to draft a commit message: {err}"));
+            }
+        }
+        self.request_redraw();
+    }
+
+    pub(crate) fn commit_staged(&mut self, message: String) {
+        let cwd = self.config.cwd.clone();
+        let tx = self.app_event_tx.clone();
+        tokio::spawn(async move {
+            let result = codex_core::commit_staged(&cwd, &message).await;
+            tx.send(AppEvent::CommitCreated(result));
+        });
+    }
+
+    pub(crate) fn on_commit_created(&mut self, result: Result<String, String>) {
+        match result {
+            Ok(summary) => self.add_info_message(format!("Committed {summary}"), None),
+            Err(err) => self.add_error_message(format!("Failed to commit: {err}")),
+        }
+        self.request_redraw();
+    }
+
+    fn create_pull_request(&mut self, instructions: String) {
+        self.add_info_message(
+            "Writing a pull request and pushing this branch…".to_string(),
+            Some(
+                "The title and description come from this conversation and the branch's diff."
+                    .to_string(),
+            ),
+        );
+        self.app_event_tx
+            .send(AppEvent::CreatePullRequest { instructions });
+    }
+
+    pub(crate) fn on_pull_request_created(&mut self, result: Result<PullRequest, String>) {
+        match result {
+            Ok(pull_request) => {
+                let mut hint = format!(
+                    "{} · {} → {}",
+                    pull_request.title, pull_request.head, pull_request.base
+                );
+                if pull_request.uncommitted_changes {
+                    hint.push_str(" · uncommitted changes were not included");
+                }
+                self.add_info_message(
+                    format!("Opened pull request {}", pull_request.url),
+                    Some(hint),
+                );
+            }
+            Err(err) => {
+                self.add_error_message(format!("Failed to open a pull request: {err}"));
+            }
+        }
+        self.request_redraw();
+    }
+
+    /// Load `source` (a git ref, range, or patch file), show it in the split pane, and start a
+    /// review of it.
+    pub(crate) fn review_diff(&mut self, source: String, instructions: Option<String>) {
+        self.add_info_message(format!("Loading {source} for review…"), None);
+        let cwd = self.config.cwd.clone();
+        let tx = self.app_event_tx.clone();
+        tokio::spawn(async move {
+            let result = load_review_diff(&source, &cwd).await;
+            tx.send(AppEvent::ReviewDiffLoaded {
+                result,
+                instructions,
+            });
+        });
+    }
+
+    pub(crate) fn on_review_diff_loaded(
+        &mut self,
+        result: Result<ReviewDiff, String>,
+        instructions: Option<String>,
+    ) {
+        match result {
+            Ok(diff) => {
+                self.diff_pane
+                    .pin(format!("Reviewing {}", diff.source), &diff.diff);
+                self.submit_op(Op::Review {
+                    review_request: diff_review_request(&diff, instructions.as_deref()),
+                });
+            }
+            Err(err) => {
+                self.add_error_message(format!("Failed to load the diff to review: {err}"));
+            }
+        }
+        self.request_redraw();
+    }
+
+    /// The last review's output, or `None` after telling the user there is none yet.
+    fn last_review_output_or_warn(&mut self) -> Option<ReviewOutputEvent> {
+        if self.last_review_output.is_none() {
+            self.add_error_message(
+                "There is no review yet; run /review or `codex review --diff` first.".to_string(),
+            );
+        }
+        self.last_review_output.clone()
+    }
+
+    fn export_review(&mut self, path: &str) {
+        let Some(output) = self.last_review_output_or_warn() else {
+            return;
+        };
+        let path = self.config.cwd.join(path.trim());
+        let is_json = path
+            .extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("json"));
+        let contents = if is_json {
+            match serde_json::to_string_pretty(&output) {
+                Ok(json) => json,
+                Err(err) => {
+                    self.add_error_message(format!("Failed to serialize the review: {err}"));
+                    return;
+                }
+            }
+        } else {
+            format_review_markdown(&output, &self.config.cwd)
+        };
+        match std::fs::write(&path, contents) {
+            Ok(()) => self.add_info_message(
+                format!(
+                    "Saved {} review comment{} to {}",
+                    output.findings.len(),
+                    if output.findings.len() == 1 { "" } else { "s" },
+                    path.display()
+                ),
+                None,
+            ),
+            Err(err) => {
+                self.add_error_message(format!("Failed to write {}: {err}", path.display()));
+            }
+        }
+    }
+
+    fn post_review(&mut self, number: &str) {
+        let Ok(number) = number.trim().trim_start_matches('#').parse::<u64>() else {
+            self.add_error_message("Usage: /post-review <pull-request-number>".to_string());
+            return;
+        };
+        let Some(output) = self.last_review_output_or_warn() else {
+            return;
+        };
+        let body = format_review_markdown(&output, &self.config.cwd);
+        self.add_info_message(format!("Posting the review on #{number}…"), None);
+        let config = self.config.clone();
+        let tx = self.app_event_tx.clone();
+        tokio::spawn(async move {
+            let result = post_pull_request_comment(&config, number, &body).await;
+            tx.send(AppEvent::ReviewCommentsPosted(result));
+        });
+    }
+
+    pub(crate) fn on_review_comments_posted(&mut self, result: Result<String, String>) {
+        match result {
+            Ok(url) => self.add_info_message(format!("Posted the review: {url}"), None),
+            Err(err) => self.add_error_message(format!("Failed to post the review: {err}")),
+        }
+        self.request_redraw();
+    }
+
+    fn attach_ci_log(&mut self, source: String) {
+        self.add_info_message(format!("Reading CI log {source}…"), None);
+        let tx = self.app_event_tx.clone();
+        let cwd = self.config.cwd.clone();
+        tokio::spawn(async move {
+            let result = ci_log::load(&source, &cwd).await;
+            tx.send(AppEvent::CiLogLoaded(result));
+        });
+    }
+
+    pub(crate) fn on_ci_log_loaded(&mut self, result: Result<ci_log::CiLogDigest, String>) {
+        match result {
+            Ok(digest) => {
+                self.add_info_message(
+                    format!(
+                        "Attached {} failing step{} and {} error excerpt{} from {} ({} lines)",
+                        digest.failing_steps.len(),
+                        if digest.failing_steps.len() == 1 {
+                            ""
+                        } else {
+                            "s"
+                        },
+                        digest.excerpts.len(),
+                        if digest.excerpts.len() == 1 { "" } else { "s" },
+                        digest.source,
+                        digest.total_lines
+                    ),
+                    Some("Review or trim the pasted block before sending.".to_string()),
+                );
+                self.bottom_pane
+                    .handle_paste(ci_log::format_digest(&digest));
+            }
+            Err(err) => {
+                self.add_error_message(format!("Failed to read CI log: {err}"));
+            }
+        }
+        self.request_redraw();
+    }
+
+    pub(crate) fn on_scrollback_captured(&mut self, result: Result<String, String>) {
+        match result {
+            Ok(text) => {
+                self.add_info_message(
+                    format!(
+                        "Attached {} lines of terminal scrollback",
+                        text.lines().count()
+                    ),
+                    Some("Review or trim the pasted block before sending.".to_string()),
+                );
+                self.bottom_pane
+                    .handle_paste(scrollback_capture::format_scrollback(&text));
+            }
+            Err(err) => {
+                self.add_error_message(format!("Failed to capture scrollback: {err}"));
+            }
+        }
+        self.request_redraw();
+    }
+
+    pub(crate) fn on_audio_attachment_transcribed(
+        &mut self,
+        path: PathBuf,
+        result: Result<String, String>,
+    ) {
+        match result {
+            Ok(transcript) => {
+                self.add_info_message(
+                    format!(
+                        "Attached transcript of {} ({} chars)",
+                        path.display(),
+                        transcript.chars().count()
+                    ),
+                    None,
+                );
+                self.bottom_pane
+                    .handle_paste(audio_attachment::format_transcript(&path, &transcript));
+            }
+            Err(err) => {
+                self.add_error_message(format!("Failed to transcribe {}: {err}", path.display()));
+            }
+        }
+        self.request_redraw();
+    }
+
+    // Returns true if caller should skip rendering this frame (a future frame is scheduled).
+    pub(crate) fn handle_paste_burst_tick(&mut self, frame_requester: FrameRequester) -> bool {
+        if self.bottom_pane.flush_paste_burst_if_due() {
+            // A paste just flushed; request an immediate redraw and skip this frame.
+            self.request_redraw();
+            true
         } else if self.bottom_pane.is_in_paste_burst() {
             // While capturing a burst, schedule a follow-up tick and skip this frame
             // to avoid redundant renders between ticks.
@@ -4305,6 +6554,7 @@ impl ChatWidget {
         if !self.is_session_configured()
             || self.bottom_pane.is_task_running()
             || self.is_review_mode
+            || self.offline
         {
             self.queued_user_messages.push_back(user_message);
             self.refresh_pending_input_preview();
@@ -4353,7 +6603,12 @@ impl ChatWidget {
         let mut items: Vec<UserInput> = Vec::new();
 
         // Special-case: "!cmd" executes a local shell command instead of sending to the model.
+        // "!!cmd" does the same but keeps the output out of the model's context.
         if let Some(stripped) = text.strip_prefix('!') {
+            let (exclude_from_context, stripped) = match stripped.strip_prefix('!') {
+                Some(rest) => (true, rest),
+                None => (false, stripped),
+            };
             let cmd = stripped.trim();
             if cmd.is_empty() {
                 self.app_event_tx.send(AppEvent::InsertHistoryCell(Box::new(
@@ -4366,6 +6621,7 @@ impl ChatWidget {
             }
             self.submit_op(Op::RunUserShellCommand {
                 command: cmd.to_string(),
+                exclude_from_context,
             });
             return;
         }
@@ -4548,6 +6804,7 @@ impl ChatWidget {
                     local_image_paths.clone(),
                     remote_image_urls.clone(),
                 ));
+            self.add_timestamp();
             self.add_to_history(history_cell::new_user_prompt(
                 text,
                 text_elements,
@@ -4562,6 +6819,7 @@ impl ChatWidget {
                     Vec::new(),
                     remote_image_urls.clone(),
                 ));
+            self.add_timestamp();
             self.add_to_history(history_cell::new_user_prompt(
                 String::new(),
                 Vec::new(),
@@ -4608,17 +6866,42 @@ impl ChatWidget {
     /// is intentionally conservative: only safe-to-replay items are rendered to
     /// avoid triggering side effects. Event ids are passed as `None` to
     /// distinguish replayed events from live ones.
-    fn replay_initial_messages(&mut self, events: Vec<EventMsg>) {
+    /// Replay a resumed session's history. `event_times` holds the recorded time of each event
+    /// when timestamps are shown, and is empty otherwise.
+    fn replay_initial_messages(
+        &mut self,
+        events: Vec<EventMsg>,
+        event_times: Vec<DateTime<Local>>,
+    ) {
+        let mut registry = HistoryCellRegistry::default();
+        let mut event_times = event_times.into_iter();
         for msg in events {
+            self.replay_event_time = event_times.next();
             if matches!(
                 msg,
                 EventMsg::SessionConfigured(_) | EventMsg::ThreadNameUpdated(_)
             ) {
                 continue;
             }
+            if let EventMsg::RawResponseItem(ev) = msg {
+                let events = registry
+                    .on_response_item(ev.item, &self.config.cwd)
+                    .unwrap_or_default();
+                for msg in events {
+                    self.dispatch_event_msg(None, msg, Some(ReplayKind::ResumeInitialMessages));
+                }
+                continue;
+            }
+            registry.observe_event(&msg);
             // `id: None` indicates a synthetic/fake id coming from replay.
             self.dispatch_event_msg(None, msg, Some(ReplayKind::ResumeInitialMessages));
         }
+        self.replay_event_time = None;
+        // Replayed exec begins raise the status indicator just as live ones do; nothing is
+        // running once replay is over.
+        if !self.bottom_pane.is_task_running() {
+            self.bottom_pane.hide_status_indicator();
+        }
     }
 
     pub(crate) fn handle_codex_event(&mut self, event: Event) {
@@ -4687,7 +6970,14 @@ impl ChatWidget {
             | EventMsg::AgentReasoningRawContentDelta(AgentReasoningRawContentDeltaEvent {
                 delta,
             }) => self.on_agent_reasoning_delta(delta),
-            EventMsg::AgentReasoning(AgentReasoningEvent { .. }) => self.on_agent_reasoning_final(),
+            EventMsg::AgentReasoning(AgentReasoningEvent { text }) => {
+                // Live turns have already streamed the text as deltas; resumed history only has
+                // the final event, so feed it through the same path.
+                if from_replay {
+                    self.on_agent_reasoning_delta(text);
+                }
+                self.on_agent_reasoning_final();
+            }
             EventMsg::AgentReasoningRawContent(AgentReasoningRawContentEvent { text }) => {
                 self.on_agent_reasoning_delta(text);
                 self.on_agent_reasoning_final();
@@ -4698,11 +6988,19 @@ impl ChatWidget {
                     self.on_task_started();
                 }
             }
+            EventMsg::TurnPhase(ev) => {
+                if !from_replay {
+                    self.on_turn_phase(ev.phase);
+                }
+            }
             EventMsg::TurnComplete(TurnCompleteEvent {
                 last_agent_message, ..
             }) => self.on_task_complete(last_agent_message, from_replay),
             EventMsg::TokenCount(ev) => {
-                self.set_token_info(ev.info);
+                let crossed = self.set_token_info(ev.info);
+                if !from_replay && let Some(threshold) = crossed {
+                    self.on_context_threshold_crossed(threshold);
+                }
                 self.on_rate_limit_snapshot(ev.rate_limits);
             }
             EventMsg::Warning(WarningEvent { message }) => self.on_warning(message),
@@ -4711,23 +7009,22 @@ impl ChatWidget {
                 message,
                 codex_error_info,
             }) => {
-                if let Some(info) = codex_error_info
-                    && let Some(kind) = rate_limit_error_kind(&info)
+                if !from_replay && codex_error_info.as_ref().is_some_and(is_connectivity_error) {
+                    self.go_offline();
+                }
+                if let Some(RateLimitErrorKind::ServerOverloaded) =
+                    codex_error_info.as_ref().and_then(rate_limit_error_kind)
                 {
-                    match kind {
-                        RateLimitErrorKind::ServerOverloaded => {
-                            self.on_server_overloaded_error(message)
-                        }
-                        RateLimitErrorKind::UsageLimit | RateLimitErrorKind::Generic => {
-                            self.on_error(message)
-                        }
-                    }
+                    self.on_server_overloaded_error(message)
                 } else {
-                    self.on_error(message);
+                    self.on_turn_error(message, codex_error_info.as_ref(), from_replay);
                 }
             }
             EventMsg::McpStartupUpdate(ev) => self.on_mcp_startup_update(ev),
             EventMsg::McpStartupComplete(ev) => self.on_mcp_startup_complete(ev),
+            EventMsg::TurnAborted(ev) if is_resume_initial_replay => {
+                self.on_replayed_turn_cancelled(ev.reason)
+            }
             EventMsg::TurnAborted(ev) => match ev.reason {
                 TurnAbortReason::Interrupted => {
                     self.on_interrupted_turn(ev.reason);
@@ -4740,8 +7037,15 @@ impl ChatWidget {
                 TurnAbortReason::ReviewEnded => {
                     self.on_interrupted_turn(ev.reason);
                 }
+                // Only recorded in rollouts; live clients get the `Error` event instead.
+                TurnAbortReason::Timeout | TurnAbortReason::Error => {
+                    self.on_replayed_turn_cancelled(ev.reason)
+                }
             },
             EventMsg::PlanUpdate(update) => self.on_plan_update(update),
+            EventMsg::TodoListUpdate(TodoListEvent { items }) => {
+                self.add_to_history(history_cell::new_todo_list(items));
+            }
             EventMsg::ExecApprovalRequest(ev) => {
                 // For replayed events, synthesize an empty id (these should not occur).
                 self.on_exec_approval_request(id.unwrap_or_default(), ev)
@@ -4749,6 +7053,8 @@ impl ChatWidget {
             EventMsg::ApplyPatchApprovalRequest(ev) => {
                 self.on_apply_patch_approval_request(id.unwrap_or_default(), ev)
             }
+            EventMsg::PlanApprovalRequest(ev) => self.on_plan_approval_request(ev),
+            EventMsg::CompactionReviewRequest(ev) => self.on_compaction_review_request(ev),
             EventMsg::ElicitationRequest(ev) => {
                 self.on_elicitation_request(ev);
             }
@@ -4760,6 +7066,8 @@ impl ChatWidget {
             EventMsg::ExecCommandOutputDelta(delta) => self.on_exec_command_output_delta(delta),
             EventMsg::PatchApplyBegin(ev) => self.on_patch_apply_begin(ev),
             EventMsg::PatchApplyEnd(ev) => self.on_patch_apply_end(ev),
+            EventMsg::PatchChecks(ev) => self.on_patch_checks(ev),
+            EventMsg::DryRunAction(ev) => self.on_dry_run_action(ev),
             EventMsg::ExecCommandEnd(ev) => self.on_exec_command_end(ev),
             EventMsg::ViewImageToolCall(ev) => self.on_view_image_tool_call(ev),
             EventMsg::ImageGenerationBegin(ev) => self.on_image_generation_begin(ev),
@@ -4790,10 +7098,11 @@ impl ChatWidget {
             EventMsg::StreamError(StreamErrorEvent {
                 message,
                 additional_details,
+                retry_delay_ms,
                 ..
             }) => {
                 if !is_resume_initial_replay {
-                    self.on_stream_error(message, additional_details);
+                    self.on_stream_error(message, additional_details, retry_delay_ms);
                 }
             }
             EventMsg::UserMessage(ev) => {
@@ -4805,7 +7114,10 @@ impl ChatWidget {
                 self.on_entered_review_mode(review_request, from_replay)
             }
             EventMsg::ExitedReviewMode(review) => self.on_exited_review_mode(review),
-            EventMsg::ContextCompacted(_) => self.on_agent_message("Context compacted".to_owned()),
+            EventMsg::ContextCompacted(_) => {
+                self.on_agent_message("Context compacted".to_owned());
+                self.run_status_line_commands_now();
+            }
             EventMsg::CollabAgentSpawnBegin(_) => {}
             EventMsg::CollabAgentSpawnEnd(ev) => self.on_collab_event(multi_agents::spawn_end(ev)),
             EventMsg::CollabAgentInteractionBegin(_) => {}
@@ -4926,6 +7238,7 @@ impl ChatWidget {
     fn on_exited_review_mode(&mut self, review: ExitedReviewModeEvent) {
         // Leave review mode; if output is present, flush pending stream + show results.
         if let Some(output) = review.review_output {
+            self.last_review_output = Some(output.clone());
             self.flush_answer_stream_with_separator();
             self.flush_interrupt_queue();
             self.flush_active_cell();
@@ -4966,6 +7279,7 @@ impl ChatWidget {
             || !event.text_elements.is_empty()
             || !remote_image_urls.is_empty()
         {
+            self.add_timestamp();
             self.add_to_history(history_cell::new_user_prompt(
                 event.message,
                 event.text_elements,
@@ -5042,7 +7356,7 @@ impl ChatWidget {
         if self.suppress_queue_autosend {
             return;
         }
-        if self.bottom_pane.is_task_running() {
+        if self.bottom_pane.is_task_running() || self.offline {
             return;
         }
         if let Some(user_message) = self.queued_user_messages.pop_front() {
@@ -5118,6 +7432,13 @@ impl ChatWidget {
     }
 
     fn open_status_line_setup(&mut self) {
+        if self.config.tui_status_line_rows.is_some() {
+            self.add_info_message(
+                "The status line is laid out in rows by `tui.status_line_rows`.".to_string(),
+                Some("Edit those rows in config.toml to change it.".to_string()),
+            );
+            return;
+        }
         let configured_status_line_items = self.configured_status_line_items();
         let view = StatusLineSetupView::new(
             Some(configured_status_line_items.as_slice()),
@@ -5147,7 +7468,17 @@ impl ChatWidget {
         let mut invalid = Vec::new();
         let mut invalid_seen = HashSet::new();
         let mut items = Vec::new();
-        for id in self.configured_status_line_items() {
+        let ids = self
+            .configured_status_line_rows()
+            .into_iter()
+            .flat_map(|row| [row.left, row.center, row.right])
+            .flatten();
+        for id in ids {
+            if let Some(name) = status_line_command::command_name(&id)
+                && self.config.tui_status_line_commands.contains_key(name)
+            {
+                continue;
+            }
             match id.parse::<StatusLineItem>() {
                 Ok(item) => items.push(item),
                 Err(_) => {
@@ -5160,6 +7491,68 @@ impl ChatWidget {
         (items, invalid)
     }
 
+    /// The configured rows, or the single-row `tui.status_line` layout when no rows are set.
+    fn configured_status_line_rows(&self) -> Vec<StatusLineRowConfig> {
+        match &self.config.tui_status_line_rows {
+            Some(rows) => rows.iter().take(STATUS_LINE_MAX_ROWS).cloned().collect(),
+            None => vec![StatusLineRowConfig {
+                left: self.configured_status_line_items(),
+                ..Default::default()
+            }],
+        }
+    }
+
+    /// The commands referenced by the configured status line.
+    fn configured_status_line_commands(&self) -> BTreeMap<String, StatusLineCommandConfig> {
+        self.configured_status_line_rows()
+            .into_iter()
+            .flat_map(|row| [row.left, row.center, row.right])
+            .flatten()
+            .filter_map(|id| {
+                let name = status_line_command::command_name(&id)?;
+                let config = self.config.tui_status_line_commands.get(name)?;
+                Some((name.to_string(), config.clone()))
+            })
+            .collect()
+    }
+
+    /// Command segments in `tui.status_line`, which the setup view does not offer.
+    pub(crate) fn status_line_command_ids(&self) -> Vec<String> {
+        self.configured_status_line_items()
+            .into_iter()
+            .filter(|id| status_line_command::command_name(id).is_some())
+            .collect()
+    }
+
+    fn status_line_command_input(&self) -> StatusLineInput {
+        StatusLineInput {
+            session_id: self.thread_id.map(|id| id.to_string()),
+            cwd: self.status_line_cwd().to_path_buf(),
+            model: self.model_display_name().to_string(),
+            version: CODEX_CLI_VERSION.to_string(),
+            context_window: StatusLineContextWindow {
+                size: self.status_line_context_window_size(),
+                current_usage: self
+                    .token_info
+                    .as_ref()
+                    .map(|info| info.last_token_usage.clone()),
+                percent_remaining: self
+                    .token_info
+                    .as_ref()
+                    .and_then(|info| self.context_remaining_percent(info))
+                    .map(|percent| percent.clamp(0, 100)),
+            },
+        }
+    }
+
+    /// Reruns status-line commands with the latest session state, e.g. once a turn's final token
+    /// count is in.
+    fn run_status_line_commands_now(&self) {
+        self.status_line_commands
+            .set_input(self.status_line_command_input());
+        self.status_line_commands.run_now();
+    }
+
     fn configured_status_line_items(&self) -> Vec<String> {
         self.config.tui_status_line.clone().unwrap_or_else(|| {
             DEFAULT_STATUS_LINE_ITEMS
@@ -5288,6 +7681,21 @@ impl ChatWidget {
                     .unwrap_or_else(|| "weekly".to_string());
                 self.status_line_limit_display(window, &label)
             }
+            StatusLineItem::ApiLimit => {
+                // Show whichever provider window (requests or tokens) is closer to exhaustion.
+                let snapshot = self
+                    .rate_limit_snapshots_by_limit_id
+                    .get(PROVIDER_RATE_LIMIT_ID)?;
+                let window = [snapshot.primary.as_ref(), snapshot.secondary.as_ref()]
+                    .into_iter()
+                    .flatten()
+                    .max_by(|a, b| a.used_percent.total_cmp(&b.used_percent));
+                let display = self.status_line_limit_display(window, "api")?;
+                match window.and_then(|window| window.resets_at.as_deref()) {
+                    Some(resets_at) => Some(format!("{display} (resets {resets_at})")),
+                    None => Some(display),
+                }
+            }
             StatusLineItem::CodexVersion => Some(CODEX_CLI_VERSION.to_string()),
             StatusLineItem::ContextWindowSize => self
                 .status_line_context_window_size()
@@ -5378,6 +7786,61 @@ impl ChatWidget {
         self.add_info_message("Stopping all background terminals.".to_string(), None);
     }
 
+    /// Hold new messages in the queue and poll the provider until it is reachable again.
+    fn go_offline(&mut self) {
+        self.offline = true;
+        self.bottom_pane.set_offline(true);
+        if self.connectivity_probe.is_some() {
+            return;
+        }
+        let Ok(handle) = tokio::runtime::Handle::try_current() else {
+            return;
+        };
+        let probe_url = self
+            .config
+            .model_provider
+            .base_url
+            .clone()
+            .unwrap_or_else(|| DEFAULT_OPENAI_BASE_URL.to_string());
+        let app_event_tx = self.app_event_tx.clone();
+        self.connectivity_probe = Some(handle.spawn(async move {
+            let client = codex_core::default_client::build_reqwest_client();
+            loop {
+                tokio::time::sleep(CONNECTIVITY_PROBE_INTERVAL).await;
+                // Any HTTP response, even an error status, proves the provider is reachable.
+                let probe = client
+                    .head(&probe_url)
+                    .timeout(CONNECTIVITY_PROBE_INTERVAL)
+                    .send()
+                    .await;
+                if probe.is_ok() {
+                    app_event_tx.send(AppEvent::ConnectivityRestored);
+                    break;
+                }
+            }
+        }));
+    }
+
+    /// Leave offline mode and send queued messages in the order they were composed.
+    pub(crate) fn on_connectivity_restored(&mut self) {
+        self.stop_connectivity_probe();
+        if !self.offline {
+            return;
+        }
+        self.offline = false;
+        self.bottom_pane.set_offline(false);
+        if !self.queued_user_messages.is_empty() {
+            self.add_info_message("Back online. Sending queued messages.".to_string(), None);
+        }
+        self.maybe_send_next_queued_input();
+    }
+
+    fn stop_connectivity_probe(&mut self) {
+        if let Some(handle) = self.connectivity_probe.take() {
+            handle.abort();
+        }
+    }
+
     fn stop_rate_limit_poller(&mut self) {
         if let Some(handle) = self.rate_limit_poller.take() {
             handle.abort();
@@ -5636,6 +8099,258 @@ impl ChatWidget {
         self.open_model_popup_with_presets(presets);
     }
 
+    /// Offer to re-request the last turn as-is, with more reasoning, or with another model.
+    pub(crate) fn open_retry_popup(&mut self) {
+        if !self.is_session_configured() {
+            self.add_info_message(
+                "Retry is disabled until startup completes.".to_string(),
+                None,
+            );
+            return;
+        }
+
+        let current_model = self.current_model().to_string();
+        let presets = self.models_manager.try_list_models().unwrap_or_default();
+        let retry_action = |overrides: RetryOverrides| -> Vec<SelectionAction> {
+            vec![Box::new(move |tx| {
+                tx.send(AppEvent::RetryLastTurn(overrides.clone()));
+            })]
+        };
+
+        let mut items = vec![SelectionItem {
+            name: "Retry".to_string(),
+            description: Some(format!("Ask {current_model} again with the same settings")),
+            actions: retry_action(RetryOverrides::default()),
+            dismiss_on_select: true,
+            ..Default::default()
+        }];
+        if let Some(effort) = presets
+            .iter()
+            .find(|preset| preset.model == current_model)
+            .and_then(|preset| {
+                next_higher_reasoning_effort(preset, self.effective_reasoning_effort())
+            })
+        {
+            items.push(SelectionItem {
+                name: format!("Retry with {effort} reasoning"),
+                description: Some(format!("Think harder on {current_model}")),
+                actions: retry_action(RetryOverrides {
+                    model: None,
+                    effort: Some(effort),
+                }),
+                dismiss_on_select: true,
+                ..Default::default()
+            });
+        }
+        for preset in presets
+            .iter()
+            .filter(|preset| preset.show_in_picker && preset.model != current_model)
+        {
+            items.push(SelectionItem {
+                name: format!("Retry with {}", preset.model),
+                description: (!preset.description.is_empty()).then(|| preset.description.clone()),
+                actions: retry_action(RetryOverrides {
+                    model: Some(preset.model.clone()),
+                    effort: Some(preset.default_reasoning_effort),
+                }),
+                dismiss_on_select: true,
+                ..Default::default()
+            });
+        }
+
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            title: Some("Retry Last Response".to_string()),
+            subtitle: Some("Rewind the last turn and ask again.".to_string()),
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            ..Default::default()
+        });
+    }
+
+    /// Offer sampling presets; custom values go through `/params temperature=.. top_p=..`.
+    pub(crate) fn open_params_popup(&mut self) {
+        if !self.is_session_configured() {
+            self.add_info_message(
+                "Sampling parameters can be changed once startup completes.".to_string(),
+                None,
+            );
+            return;
+        }
+
+        let presets = [
+            (
+                "Model default",
+                "Let the model use its own temperature and top_p",
+                SamplingParams::default(),
+            ),
+            (
+                "Precise",
+                "temperature=0.2 for focused, repeatable answers",
+                SamplingParams {
+                    temperature: Some(0.2),
+                    top_p: None,
+                },
+            ),
+            (
+                "Creative",
+                "temperature=1.0, top_p=0.95 for more varied answers",
+                SamplingParams {
+                    temperature: Some(1.0),
+                    top_p: Some(0.95),
+                },
+            ),
+        ];
+        let current = self.sampling;
+        let items = presets
+            .into_iter()
+            .map(|(name, description, sampling)| {
+                let actions: Vec<SelectionAction> = vec![Box::new(move |tx| {
+                    tx.send(AppEvent::UpdateSamplingParams(sampling));
+                })];
+                SelectionItem {
+                    name: name.to_string(),
+                    description: Some(description.to_string()),
+                    is_current: current == sampling,
+                    actions,
+                    dismiss_on_select: true,
+                    ..Default::default()
+                }
+            })
+            .collect();
+
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            title: Some("Sampling Parameters".to_string()),
+            subtitle: Some(format!(
+                "Current: {}. Reasoning models ignore these settings.",
+                sampling_label(current)
+            )),
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            ..Default::default()
+        });
+    }
+
+    /// Offer the tool outputs in the context, largest first, for `/trim`.
+    pub(crate) fn open_trim_picker(&mut self, context: &NextTurnContext) {
+        let items = context
+            .trimmable_entries()
+            .into_iter()
+            .take(TRIM_PICKER_MAX_ITEMS)
+            .filter_map(|entry| {
+                let call_id = entry.trimmable_call_id.clone()?;
+                let tokens = format_tokens_compact(entry.tokens);
+                let preview = entry
+                    .text
+                    .lines()
+                    .find(|line| !line.trim().is_empty())
+                    .unwrap_or_default();
+                let message = format!(
+                    "Removed {} (~{tokens} tokens) from the context.",
+                    entry.heading
+                );
+                let action: SelectionAction = Box::new(move |tx| {
+                    tx.send(AppEvent::CodexOp(Op::TrimToolOutput {
+                        call_id: call_id.clone(),
+                    }));
+                    tx.send(AppEvent::InsertHistoryCell(Box::new(
+                        history_cell::new_info_event(message.clone(), None),
+                    )));
+                });
+                Some(SelectionItem {
+                    name: format!("~{tokens} tokens · {}", entry.heading),
+                    description: Some(truncate_text(preview.trim(), 60)),
+                    actions: vec![action],
+                    dismiss_on_select: true,
+                    ..Default::default()
+                })
+            })
+            .collect::<Vec<_>>();
+        if items.is_empty() {
+            self.add_info_message(
+                "There are no tool outputs in the context.".to_string(),
+                None,
+            );
+            return;
+        }
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            title: Some("Trim tool output".to_string()),
+            subtitle: Some(
+                "The output is replaced by a short note in the model context; the transcript is unchanged."
+                    .to_string(),
+            ),
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            ..Default::default()
+        });
+    }
+
+    /// Apply new sampling overrides to the session and remember them for `/params`.
+    pub(crate) fn set_sampling_params(&mut self, sampling: SamplingParams) {
+        if let Err(message) = sampling.validate() {
+            self.add_error_message(message);
+            return;
+        }
+        self.sampling = sampling;
+        self.submit_op(Op::SetSamplingParams { sampling });
+        self.add_info_message(
+            format!("Sampling set to {}.", sampling_label(sampling)),
+            None,
+        );
+    }
+
+    /// Send the pinned paths to core and refresh the footer badge.
+    fn sync_pinned_files(&mut self) {
+        self.submit_op(Op::SetPinnedFiles {
+            paths: self.pinned_files.paths(),
+        });
+        self.bottom_pane
+            .set_pinned_files(self.pinned_files.summary());
+    }
+
+    fn show_pinned_files(&mut self) {
+        if self.pinned_files.files().is_empty() {
+            self.add_info_message(
+                "No files are pinned.".to_string(),
+                Some("Use /pin <path> to send a file with every request.".to_string()),
+            );
+            return;
+        }
+        let mut lines: Vec<Line<'static>> = vec![vec!["• ".dim(), "Pinned files".bold()].into()];
+        for file in self.pinned_files.files() {
+            lines.push(
+                vec![
+                    "  ".into(),
+                    display_path_for(&file.path, &self.config.cwd).into(),
+                    format!(" (~{} tokens)", format_tokens_compact(file.tokens)).dim(),
+                ]
+                .into(),
+            );
+        }
+        self.add_plain_history_lines(lines);
+    }
+
+    /// Submit a message restored from the transcript, e.g. after `/retry` rolled back its turn.
+    pub(crate) fn resubmit_user_message(
+        &mut self,
+        text: String,
+        text_elements: Vec<TextElement>,
+        local_image_paths: Vec<PathBuf>,
+        remote_image_urls: Vec<String>,
+    ) {
+        let mut user_message =
+            create_initial_user_message(Some(text), local_image_paths, text_elements)
+                .unwrap_or_else(|| UserMessage::from(""));
+        user_message.remote_image_urls = remote_image_urls;
+        if user_message.text.is_empty()
+            && user_message.local_images.is_empty()
+            && user_message.remote_image_urls.is_empty()
+        {
+            return;
+        }
+        self.retry_baseline = self.last_turn_answer.take();
+        self.submit_user_message(user_message);
+    }
+
     pub(crate) fn open_personality_popup(&mut self) {
         if !self.is_session_configured() {
             self.add_info_message(
@@ -7247,11 +9962,73 @@ impl ChatWidget {
         }
     }
 
+    /// The remappable key bindings as resolved from config, for the cheat sheet.
+    pub(crate) fn configured_keys(&self) -> keymap::ConfiguredKeys {
+        keymap::ConfiguredKeys {
+            interrupt: self.configured_interrupt_key(),
+        }
+    }
+
+    /// Resolve `tui.interrupt_key`, falling back to Esc when it is unset or unparseable.
+    fn configured_interrupt_key(&self) -> KeyBinding {
+        let Some(spec) = self.config.tui_interrupt_key.as_deref() else {
+            return keymap::INTERRUPT;
+        };
+        keymap::parse_key_binding(spec).unwrap_or_else(|| {
+            warn!("ignoring unrecognized tui.interrupt_key `{spec}`; using esc");
+            keymap::INTERRUPT
+        })
+    }
+
     /// Set the syntax theme override in the widget's config copy.
     pub(crate) fn set_tui_theme(&mut self, theme: Option<String>) {
         self.config.tui_theme = theme;
     }
 
+    /// Take the display settings of a config reloaded from disk into the widget's config copy.
+    pub(crate) fn apply_live_config(&mut self, config: &Config) {
+        crate::config_reload::copy_live_settings(config, &mut self.config);
+        self.refresh_status_line();
+    }
+
+    /// Ask whether to start a new session so reloaded settings that need one take effect.
+    pub(crate) fn open_config_reload_prompt(&mut self, changes: Vec<String>) {
+        let mut header_children: Vec<Box<dyn Renderable>> = vec![
+            Box::new(Line::from("Config changed").bold()),
+            Box::new(Line::from(
+                "These settings only apply to new sessions:".dim(),
+            )),
+        ];
+        for change in changes {
+            header_children.push(Box::new(Line::from(format!("  {change}"))));
+        }
+        let items = vec![
+            SelectionItem {
+                name: "Start a new session".to_string(),
+                description: Some("Apply the new settings now".to_string()),
+                actions: vec![Box::new(|tx| {
+                    tx.send(AppEvent::ApplyPendingConfigReload);
+                })],
+                dismiss_on_select: true,
+                ..Default::default()
+            },
+            SelectionItem {
+                name: "Keep the current session".to_string(),
+                description: Some(
+                    "The new settings apply the next time you start Codex".to_string(),
+                ),
+                dismiss_on_select: true,
+                ..Default::default()
+            },
+        ];
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            header: Box::new(ColumnRenderable::with(header_children)),
+            ..Default::default()
+        });
+    }
+
     /// Set the model in the widget's config copy and stored collaboration mode.
     pub(crate) fn set_model(&mut self, model: &str) {
         self.current_collaboration_mode =
@@ -7615,6 +10392,11 @@ impl ChatWidget {
         self.request_redraw();
     }
 
+    pub(crate) fn add_warning_message(&mut self, message: String) {
+        self.on_warning(message);
+        self.request_redraw();
+    }
+
     fn rename_confirmation_cell(name: &str, thread_id: Option<ThreadId>) -> PlainHistoryCell {
         let resume_cmd = codex_core::util::resume_command(Some(name), thread_id)
             .unwrap_or_else(|| format!("codex resume {name}"));
@@ -7968,8 +10750,16 @@ impl ChatWidget {
     /// True when the UI is in the regular composer state with no running task,
     /// no modal overlay (e.g. approvals or status indicator), and no composer popups.
     /// In this state Esc-Esc backtracking is enabled.
+    pub(crate) fn is_task_running(&self) -> bool {
+        self.bottom_pane.is_task_running()
+    }
+
+    pub(crate) fn is_awaiting_approval(&self) -> bool {
+        self.bottom_pane.is_awaiting_approval()
+    }
+
     pub(crate) fn is_normal_backtrack_mode(&self) -> bool {
-        self.bottom_pane.is_normal_backtrack_mode()
+        self.bottom_pane.is_normal_backtrack_mode() && !self.file_tree.is_focused()
     }
 
     pub(crate) fn insert_str(&mut self, text: &str) {
@@ -8324,6 +11114,33 @@ impl ChatWidget {
         self.current_rollout_path.clone()
     }
 
+    /// Load statistics for `reference`, or for the current session when it is `None`, and report
+    /// them through `AppEvent::SessionStatsResult`.
+    fn show_session_stats(&self, reference: Option<String>) {
+        let current = self.rollout_path();
+        let tx = self.app_event_tx.clone();
+        let codex_home = self.config.codex_home.clone();
+        let cwd = self.config.cwd.clone();
+        tokio::spawn(async move {
+            let result = async {
+                let path = match (reference, current) {
+                    (Some(reference), _) => {
+                        resolve_session_path(&codex_home, &cwd, &reference).await?
+                    }
+                    (None, Some(path)) => path,
+                    (None, None) => {
+                        return Err("The current session has no rollout yet.".to_string());
+                    }
+                };
+                load_session_stats(&path)
+                    .await
+                    .map_err(|err| format!("Failed to read session: {err}"))
+            }
+            .await;
+            tx.send(AppEvent::SessionStatsResult(result));
+        });
+    }
+
     /// Returns a cache key describing the current in-flight active cell for the transcript overlay.
     ///
     /// `Ctrl+T` renders committed transcript cells plus a render-only live tail derived from the
@@ -8365,6 +11182,27 @@ impl ChatWidget {
         self.token_info = None;
     }
 
+    /// Split `area` into the chat, plus the file tree on the left and the diff pane on the
+    /// right when they are shown.
+    fn split_panes(&self, area: Rect) -> (Rect, Option<Rect>, Option<Rect>) {
+        let mut chat = area;
+        let sidebar = self.file_tree.width_for(area.width).map(|width| {
+            chat.x += width;
+            chat.width -= width;
+            Rect { width, ..area }
+        });
+        let diff = self.diff_pane.width_for(area.width).map(|width| {
+            let width = width.min(chat.width);
+            chat.width -= width;
+            Rect {
+                x: chat.x + chat.width,
+                width,
+                ..area
+            }
+        });
+        (chat, sidebar, diff)
+    }
+
     fn as_renderable(&self) -> RenderableItem<'_> {
         let active_cell_renderable = match &self.active_cell {
             Some(cell) => RenderableItem::Borrowed(cell).inset(Insets::tlbr(1, 0, 0, 0)),
@@ -8416,21 +11254,41 @@ impl Drop for ChatWidget {
     fn drop(&mut self) {
         self.reset_realtime_conversation_state();
         self.stop_rate_limit_poller();
+        self.stop_connectivity_probe();
     }
 }
 
 impl Renderable for ChatWidget {
     fn render(&self, area: Rect, buf: &mut Buffer) {
-        self.as_renderable().render(area, buf);
+        let (chat_area, sidebar_area, diff_area) = self.split_panes(area);
+        if let Some(sidebar_area) = sidebar_area {
+            self.file_tree.render(sidebar_area, buf);
+        }
+        if let Some(diff_area) = diff_area {
+            self.diff_pane.render(diff_area, buf);
+        }
+        self.as_renderable().render(chat_area, buf);
         self.last_rendered_width.set(Some(area.width as usize));
     }
 
     fn desired_height(&self, width: u16) -> u16 {
-        self.as_renderable().desired_height(width)
+        let (chat_area, sidebar_area, diff_area) = self.split_panes(Rect::new(0, 0, width, 1));
+        let mut height = self.as_renderable().desired_height(chat_area.width);
+        if let Some(sidebar_area) = sidebar_area {
+            height = height.max(self.file_tree.desired_height(sidebar_area.width));
+        }
+        if let Some(diff_area) = diff_area {
+            height = height.max(self.diff_pane.desired_height(diff_area.width));
+        }
+        height
     }
 
     fn cursor_pos(&self, area: Rect) -> Option<(u16, u16)> {
-        self.as_renderable().cursor_pos(area)
+        if self.file_tree.is_focused() {
+            return None;
+        }
+        let (chat_area, _, _) = self.split_panes(area);
+        self.as_renderable().cursor_pos(chat_area)
     }
 }
 
@@ -8449,6 +11307,10 @@ enum Notification {
     ElicitationRequested {
         server_name: String,
     },
+    PlanApprovalRequested {
+        step_count: usize,
+    },
+    CompactionReviewRequested,
     PlanModePrompt {
         title: String,
     },
@@ -8456,6 +11318,9 @@ enum Notification {
         question_count: usize,
         summary: Option<String>,
     },
+    ContextWarning {
+        threshold: u8,
+    },
 }
 
 impl Notification {
@@ -8482,6 +11347,12 @@ impl Notification {
             Notification::ElicitationRequested { server_name } => {
                 format!("Approval requested by {server_name}")
             }
+            Notification::PlanApprovalRequested { step_count } => {
+                format!("Codex wants approval for a {step_count}-step plan")
+            }
+            Notification::CompactionReviewRequested => {
+                "Codex wants to compact the conversation".to_string()
+            }
             Notification::PlanModePrompt { title } => {
                 format!("Plan mode prompt: {title}")
             }
@@ -8493,6 +11364,9 @@ impl Notification {
                 (1, None) => "Question requested".to_string(),
                 (count, _) => format!("Questions requested: {count}"),
             },
+            Notification::ContextWarning { threshold } => {
+                format!("Context window is {threshold}% full")
+            }
         }
     }
 
@@ -8501,18 +11375,23 @@ impl Notification {
             Notification::AgentTurnComplete { .. } => "agent-turn-complete",
             Notification::ExecApprovalRequested { .. }
             | Notification::EditApprovalRequested { .. }
-            | Notification::ElicitationRequested { .. } => "approval-requested",
+            | Notification::ElicitationRequested { .. }
+            | Notification::PlanApprovalRequested { .. }
+            | Notification::CompactionReviewRequested => "approval-requested",
             Notification::PlanModePrompt { .. } => "plan-mode-prompt",
             Notification::UserInputRequested { .. } => "user-input-requested",
+            Notification::ContextWarning { .. } => "context-warning",
         }
     }
 
     fn priority(&self) -> u8 {
         match self {
-            Notification::AgentTurnComplete { .. } => 0,
+            Notification::AgentTurnComplete { .. } | Notification::ContextWarning { .. } => 0,
             Notification::ExecApprovalRequested { .. }
             | Notification::EditApprovalRequested { .. }
             | Notification::ElicitationRequested { .. }
+            | Notification::PlanApprovalRequested { .. }
+            | Notification::CompactionReviewRequested
             | Notification::PlanModePrompt { .. }
             | Notification::UserInputRequested { .. } => 1,
         }
@@ -2,12 +2,16 @@ use std::collections::VecDeque;
 
 use codex_protocol::approvals::ElicitationRequestEvent;
 use codex_protocol::protocol::ApplyPatchApprovalRequestEvent;
+use codex_protocol::protocol::CompactionReviewRequestEvent;
+use codex_protocol::protocol::DryRunActionEvent;
 use codex_protocol::protocol::ExecApprovalRequestEvent;
 use codex_protocol::protocol::ExecCommandBeginEvent;
 use codex_protocol::protocol::ExecCommandEndEvent;
 use codex_protocol::protocol::McpToolCallBeginEvent;
 use codex_protocol::protocol::McpToolCallEndEvent;
 use codex_protocol::protocol::PatchApplyEndEvent;
+use codex_protocol::protocol::PatchChecksEvent;
+use codex_protocol::protocol::PlanApprovalRequestEvent;
 use codex_protocol::request_user_input::RequestUserInputEvent;
 
 use super::ChatWidget;
@@ -16,6 +20,8 @@ use super::ChatWidget;
 pub(crate) enum QueuedInterrupt {
     ExecApproval(ExecApprovalRequestEvent),
     ApplyPatchApproval(ApplyPatchApprovalRequestEvent),
+    PlanApproval(PlanApprovalRequestEvent),
+    CompactionReview(CompactionReviewRequestEvent),
     Elicitation(ElicitationRequestEvent),
     RequestUserInput(RequestUserInputEvent),
     ExecBegin(ExecCommandBeginEvent),
@@ -23,6 +29,8 @@ pub(crate) enum QueuedInterrupt {
     McpBegin(McpToolCallBeginEvent),
     McpEnd(McpToolCallEndEvent),
     PatchEnd(PatchApplyEndEvent),
+    PatchChecks(PatchChecksEvent),
+    DryRunAction(DryRunActionEvent),
 }
 
 #[derive(Default)]
@@ -51,6 +59,14 @@ impl InterruptManager {
             .push_back(QueuedInterrupt::ApplyPatchApproval(ev));
     }
 
+    pub(crate) fn push_plan_approval(&mut self, ev: PlanApprovalRequestEvent) {
+        self.queue.push_back(QueuedInterrupt::PlanApproval(ev));
+    }
+
+    pub(crate) fn push_compaction_review(&mut self, ev: CompactionReviewRequestEvent) {
+        self.queue.push_back(QueuedInterrupt::CompactionReview(ev));
+    }
+
     pub(crate) fn push_elicitation(&mut self, ev: ElicitationRequestEvent) {
         self.queue.push_back(QueuedInterrupt::Elicitation(ev));
     }
@@ -79,11 +95,21 @@ impl InterruptManager {
         self.queue.push_back(QueuedInterrupt::PatchEnd(ev));
     }
 
+    pub(crate) fn push_patch_checks(&mut self, ev: PatchChecksEvent) {
+        self.queue.push_back(QueuedInterrupt::PatchChecks(ev));
+    }
+
+    pub(crate) fn push_dry_run_action(&mut self, ev: DryRunActionEvent) {
+        self.queue.push_back(QueuedInterrupt::DryRunAction(ev));
+    }
+
     pub(crate) fn flush_all(&mut self, chat: &mut ChatWidget) {
         while let Some(q) = self.queue.pop_front() {
             match q {
                 QueuedInterrupt::ExecApproval(ev) => chat.handle_exec_approval_now(ev),
                 QueuedInterrupt::ApplyPatchApproval(ev) => chat.handle_apply_patch_approval_now(ev),
+                QueuedInterrupt::PlanApproval(ev) => chat.handle_plan_approval_now(ev),
+                QueuedInterrupt::CompactionReview(ev) => chat.handle_compaction_review_now(ev),
                 QueuedInterrupt::Elicitation(ev) => chat.handle_elicitation_request_now(ev),
                 QueuedInterrupt::RequestUserInput(ev) => chat.handle_request_user_input_now(ev),
                 QueuedInterrupt::ExecBegin(ev) => chat.handle_exec_begin_now(ev),
@@ -91,6 +117,8 @@ impl InterruptManager {
                 QueuedInterrupt::McpBegin(ev) => chat.handle_mcp_begin_now(ev),
                 QueuedInterrupt::McpEnd(ev) => chat.handle_mcp_end_now(ev),
                 QueuedInterrupt::PatchEnd(ev) => chat.handle_patch_apply_end_now(ev),
+                QueuedInterrupt::PatchChecks(ev) => chat.handle_patch_checks_now(ev),
+                QueuedInterrupt::DryRunAction(ev) => chat.handle_dry_run_action_now(ev),
             }
         }
     }
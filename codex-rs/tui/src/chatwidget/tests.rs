@@ -13,15 +13,24 @@ use crate::app_event_sender::AppEventSender;
 use crate::bottom_pane::FeedbackAudience;
 use crate::bottom_pane::LocalImageAttachment;
 use crate::bottom_pane::MentionBinding;
+use crate::bottom_pane::PlanStepSummary;
+use crate::config_view::config_editor_params;
 use crate::history_cell::UserHistoryCell;
+use crate::multi_agents::AgentPickerThreadEntry;
+use crate::multi_agents::AgentTask;
+use crate::multi_agents::agent_task_params;
+use crate::multi_agents::task_board_params;
 use crate::test_backend::VT100Backend;
 use crate::tui::FrameRequester;
 use assert_matches::assert_matches;
+use codex_core::CarryOverBriefing;
 use codex_core::CodexAuth;
+use codex_core::SessionSummary;
 use codex_core::config::Config;
 use codex_core::config::ConfigBuilder;
 use codex_core::config::Constrained;
 use codex_core::config::ConstraintError;
+use codex_core::config::types::NotificationMethod;
 use codex_core::config::types::Notifications;
 #[cfg(target_os = "windows")]
 use codex_core::config::types::WindowsSandboxModeToml;
@@ -30,6 +39,7 @@ use codex_core::features::FEATURES;
 use codex_core::features::Feature;
 use codex_core::models_manager::collaboration_mode_presets::CollaborationModesConfig;
 use codex_core::models_manager::manager::ModelsManager;
+use codex_core::session_summary_path;
 use codex_core::skills::model::SkillMetadata;
 use codex_core::terminal::TerminalName;
 use codex_otel::OtelManager;
@@ -46,7 +56,9 @@ use codex_protocol::items::AgentMessageItem;
 use codex_protocol::items::PlanItem;
 use codex_protocol::items::TurnItem;
 use codex_protocol::items::UserMessageItem;
+use codex_protocol::models::FunctionCallOutputPayload;
 use codex_protocol::models::MessagePhase;
+use codex_protocol::models::ResponseItem;
 use codex_protocol::openai_models::ModelPreset;
 use codex_protocol::openai_models::ReasoningEffortPreset;
 use codex_protocol::openai_models::default_input_modalities;
@@ -58,6 +70,7 @@ use codex_protocol::protocol::AgentMessageDeltaEvent;
 use codex_protocol::protocol::AgentMessageEvent;
 use codex_protocol::protocol::AgentReasoningDeltaEvent;
 use codex_protocol::protocol::AgentReasoningEvent;
+use codex_protocol::protocol::AgentStatus;
 use codex_protocol::protocol::ApplyPatchApprovalRequestEvent;
 use codex_protocol::protocol::BackgroundEventEvent;
 use codex_protocol::protocol::CodexErrorInfo;
@@ -82,6 +95,7 @@ use codex_protocol::protocol::PatchApplyBeginEvent;
 use codex_protocol::protocol::PatchApplyEndEvent;
 use codex_protocol::protocol::PatchApplyStatus as CorePatchApplyStatus;
 use codex_protocol::protocol::RateLimitWindow;
+use codex_protocol::protocol::RawResponseItemEvent;
 use codex_protocol::protocol::ReviewRequest;
 use codex_protocol::protocol::ReviewTarget;
 use codex_protocol::protocol::SessionSource;
@@ -93,6 +107,7 @@ use codex_protocol::protocol::TokenCountEvent;
 use codex_protocol::protocol::TokenUsage;
 use codex_protocol::protocol::TokenUsageInfo;
 use codex_protocol::protocol::TurnCompleteEvent;
+use codex_protocol::protocol::TurnPhaseEvent;
 use codex_protocol::protocol::TurnStartedEvent;
 use codex_protocol::protocol::UndoCompletedEvent;
 use codex_protocol::protocol::UndoStartedEvent;
@@ -101,6 +116,9 @@ use codex_protocol::protocol::WarningEvent;
 use codex_protocol::request_user_input::RequestUserInputEvent;
 use codex_protocol::request_user_input::RequestUserInputQuestion;
 use codex_protocol::request_user_input::RequestUserInputQuestionOption;
+use codex_protocol::todo_tool::TodoItem;
+use codex_protocol::todo_tool::TodoListEvent;
+use codex_protocol::todo_tool::TodoStatus;
 use codex_protocol::user_input::TextElement;
 use codex_protocol::user_input::UserInput;
 use codex_utils_absolute_path::AbsolutePathBuf;
@@ -217,6 +235,102 @@ async fn resumed_initial_messages_render_history() {
     );
 }
 
+#[tokio::test]
+async fn resumed_tool_calls_render_like_live_exec_cells() {
+    let (mut chat, mut rx, _ops) = make_chatwidget_manual(None).await;
+
+    let rollout_file = NamedTempFile::new().unwrap();
+    let configured = codex_protocol::protocol::SessionConfiguredEvent {
+        session_id: ThreadId::new(),
+        forked_from_id: None,
+        thread_name: None,
+        model: "test-model".to_string(),
+        model_provider_id: "test-provider".to_string(),
+        service_tier: None,
+        approval_policy: AskForApproval::Never,
+        sandbox_policy: SandboxPolicy::new_read_only_policy(),
+        cwd: PathBuf::from("/home/user/project"),
+        reasoning_effort: Some(ReasoningEffortConfig::default()),
+        history_log_id: 0,
+        history_entry_count: 0,
+        initial_messages: Some(vec![
+            EventMsg::RawResponseItem(RawResponseItemEvent {
+                item: ResponseItem::FunctionCall {
+                    id: None,
+                    name: "shell".to_string(),
+                    arguments: r#"{"command":["echo","resumed"]}"#.to_string(),
+                    call_id: "call-1".to_string(),
+                },
+            }),
+            EventMsg::RawResponseItem(RawResponseItemEvent {
+                item: ResponseItem::FunctionCallOutput {
+                    call_id: "call-1".to_string(),
+                    output: FunctionCallOutputPayload::from_text(
+                        "Exit code: 0\nWall time: 0.1 seconds\nOutput:\nresumed\n".to_string(),
+                    ),
+                },
+            }),
+            EventMsg::AgentMessage(AgentMessageEvent {
+                message: "done".to_string(),
+                phase: None,
+            }),
+        ]),
+        network_proxy: None,
+        rollout_path: Some(rollout_file.path().to_path_buf()),
+    };
+
+    chat.handle_codex_event(Event {
+        id: "initial".into(),
+        msg: EventMsg::SessionConfigured(configured),
+    });
+
+    let text_blob = drain_insert_history(&mut rx)
+        .iter()
+        .map(|lines| lines_to_single_string(lines))
+        .collect::<String>();
+    assert!(
+        text_blob.contains("Ran echo resumed"),
+        "expected replayed exec cell: {text_blob}",
+    );
+    assert!(
+        !chat.bottom_pane.status_indicator_visible(),
+        "replay should not leave the status indicator up"
+    );
+}
+
+#[tokio::test]
+async fn replayed_history_timestamps_snapshot() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+    chat.config.tui_timestamps = HistoryTimestamps::Relative;
+    let start = Local::now();
+    chat.session_started_at = start;
+
+    chat.replay_initial_messages(
+        vec![
+            EventMsg::UserMessage(UserMessageEvent {
+                message: "hello".to_string(),
+                images: None,
+                text_elements: Vec::new(),
+                local_images: Vec::new(),
+            }),
+            EventMsg::AgentMessage(AgentMessageEvent {
+                message: "hi there".to_string(),
+                phase: None,
+            }),
+        ],
+        vec![
+            start + TimeDelta::seconds(5),
+            start + TimeDelta::seconds(3600 + 65),
+        ],
+    );
+
+    let rendered = drain_insert_history(&mut rx)
+        .iter()
+        .map(|lines| lines_to_single_string(lines))
+        .collect::<String>();
+    assert_snapshot!(rendered);
+}
+
 #[tokio::test]
 async fn thread_snapshot_replay_does_not_duplicate_agent_message_history() {
     let (mut chat, mut rx, _ops) = make_chatwidget_manual(None).await;
@@ -1772,6 +1886,11 @@ async fn make_chatwidget_manual(
         bottom_pane: bottom,
         active_cell: None,
         active_cell_revision: 0,
+        file_tree: FileTreeSidebar::new(cfg.cwd.clone()),
+        diff_pane: DiffPane::default(),
+        last_review_output: None,
+        conflict_queue: VecDeque::new(),
+        conflict_total: 0,
         config: cfg,
         current_collaboration_mode,
         active_collaboration_mask,
@@ -1784,12 +1903,18 @@ async fn make_chatwidget_manual(
         rate_limit_snapshots_by_limit_id: BTreeMap::new(),
         plan_type: None,
         rate_limit_warnings: RateLimitWarningState::default(),
+        context_warnings: ContextWarningState::default(),
         rate_limit_switch_prompt: RateLimitSwitchPromptState::default(),
         rate_limit_poller: None,
+        offline: false,
+        connectivity_probe: None,
         adaptive_chunking: crate::streaming::chunking::AdaptiveChunkingPolicy::default(),
         stream_controller: None,
         plan_stream_controller: None,
         last_copyable_output: None,
+        last_turn_answer: None,
+        retry_baseline: None,
+        device_code_login_cancel: None,
         running_commands: HashMap::new(),
         suppressed_exec_calls: HashSet::new(),
         skills_all: Vec::new(),
@@ -1828,6 +1953,9 @@ async fn make_chatwidget_manual(
         pre_review_token_info: None,
         needs_final_message_separator: false,
         had_work_activity: false,
+        unsummarized_turns: false,
+        session_started_at: Local::now(),
+        replay_event_time: None,
         saw_plan_update_this_turn: false,
         saw_plan_item_this_turn: false,
         plan_delta_buffer: String::new(),
@@ -1835,6 +1963,9 @@ async fn make_chatwidget_manual(
         last_separator_elapsed_secs: None,
         turn_runtime_metrics: RuntimeMetricsSummary::default(),
         last_rendered_width: std::cell::Cell::new(None),
+        sampling: SamplingParams::default(),
+        pinned_files: PinnedFiles::default(),
+        plan_steps: PlanSteps::default(),
         feedback: codex_feedback::CodexFeedback::new(),
         feedback_audience: FeedbackAudience::External,
         current_rollout_path: None,
@@ -1845,6 +1976,7 @@ async fn make_chatwidget_manual(
         status_line_branch_cwd: None,
         status_line_branch_pending: false,
         status_line_branch_lookup_complete: false,
+        status_line_commands: StatusLineCommands::default(),
         external_editor_state: ExternalEditorState::Closed,
         realtime_conversation: RealtimeConversationUiState::default(),
         last_rendered_user_message_event: None,
@@ -1998,6 +2130,52 @@ async fn rate_limit_warnings_emit_thresholds() {
     );
 }
 
+#[test]
+fn context_warnings_escalate_once_per_threshold_and_rearm() {
+    let thresholds = [70, 90];
+    let mut state = ContextWarningState::default();
+
+    assert_eq!(state.update(&thresholds, Some(50)), None);
+    assert_eq!(state.level(thresholds.len()), None);
+    assert_eq!(state.update(&thresholds, Some(72)), Some(70));
+    assert_eq!(state.update(&thresholds, Some(75)), None);
+    assert_eq!(
+        state.level(thresholds.len()),
+        Some(ContextWarningLevel::Elevated)
+    );
+    assert_eq!(state.update(&thresholds, Some(95)), Some(90));
+    assert_eq!(
+        state.level(thresholds.len()),
+        Some(ContextWarningLevel::Critical)
+    );
+
+    // Compaction frees space, so the thresholds warn again the next time they are crossed.
+    assert_eq!(state.update(&thresholds, Some(20)), None);
+    assert_eq!(state.level(thresholds.len()), None);
+    assert_eq!(state.update(&thresholds, Some(91)), Some(90));
+}
+
+#[tokio::test]
+async fn context_warning_history_cell_snapshot() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+    chat.config.tui_context_warnings.thresholds = vec![70, 90];
+
+    chat.handle_codex_event(Event {
+        id: "token-usage".into(),
+        msg: EventMsg::TokenCount(TokenCountEvent {
+            info: Some(make_token_info(84_000, 112_000)),
+            rate_limits: None,
+        }),
+    });
+
+    let cells = drain_insert_history(&mut rx);
+    assert_eq!(cells.len(), 1, "expected one context warning");
+    assert_snapshot!(
+        "context_warning_history_cell",
+        lines_to_single_string(&cells[0])
+    );
+}
+
 #[tokio::test]
 async fn test_rate_limit_warnings_monthly() {
     let mut state = RateLimitWarningState::default();
@@ -2303,6 +2481,25 @@ async fn plan_implementation_popup_snapshot() {
     assert_snapshot!("plan_implementation_popup", popup);
 }
 
+#[tokio::test]
+async fn plan_implementation_popup_with_steps_snapshot() {
+    let (mut chat, _rx, _op_rx) = make_chatwidget_manual(Some("gpt-5")).await;
+    chat.plan_steps.replace(vec![
+        PlanItemArg {
+            step: "Add a retry policy".to_string(),
+            status: StepStatus::Pending,
+        },
+        PlanItemArg {
+            step: "Wrap the upload call".to_string(),
+            status: StepStatus::Pending,
+        },
+    ]);
+    chat.open_plan_implementation_prompt();
+
+    let popup = render_bottom_popup(&chat, 80);
+    assert_snapshot!("plan_implementation_popup_with_steps", popup);
+}
+
 #[tokio::test]
 async fn plan_implementation_popup_no_selected_snapshot() {
     let (mut chat, _rx, _op_rx) = make_chatwidget_manual(Some("gpt-5")).await;
@@ -2829,6 +3026,39 @@ async fn submit_user_message_with_mode_submits_when_plan_stream_is_not_active()
     }
 }
 
+#[tokio::test]
+async fn replayed_turn_cancellation_explains_why_the_turn_stopped() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+
+    chat.replay_initial_messages(
+        vec![
+            EventMsg::AgentMessage(AgentMessageEvent {
+                message: "Looking into the failing test".to_string(),
+                phase: None,
+            }),
+            EventMsg::TurnAborted(codex_protocol::protocol::TurnAbortedEvent {
+                turn_id: Some("turn-1".to_string()),
+                reason: TurnAbortReason::Timeout,
+            }),
+        ],
+        Vec::new(),
+    );
+
+    let rendered = drain_insert_history(&mut rx)
+        .iter()
+        .map(|lines| lines_to_single_string(lines))
+        .collect::<String>();
+    assert!(
+        rendered.contains("Turn cancelled: timed out waiting for the model"),
+        "expected a cancellation cell, got {rendered:?}"
+    );
+    assert!(
+        !rendered.contains("Conversation interrupted"),
+        "replayed cancellations should not prompt for new instructions: {rendered:?}"
+    );
+    assert!(chat.bottom_pane.composer_text().is_empty());
+}
+
 #[tokio::test]
 async fn plan_implementation_popup_skips_replayed_turn_complete() {
     let (mut chat, _rx, _op_rx) = make_chatwidget_manual(Some("gpt-5")).await;
@@ -2838,10 +3068,13 @@ async fn plan_implementation_popup_skips_replayed_turn_complete() {
             .expect("expected plan collaboration mask");
     chat.set_collaboration_mask(plan_mask);
 
-    chat.replay_initial_messages(vec![EventMsg::TurnComplete(TurnCompleteEvent {
-        turn_id: "turn-1".to_string(),
-        last_agent_message: Some("Plan details".to_string()),
-    })]);
+    chat.replay_initial_messages(
+        vec![EventMsg::TurnComplete(TurnCompleteEvent {
+            turn_id: "turn-1".to_string(),
+            last_agent_message: Some("Plan details".to_string()),
+        })],
+        Vec::new(),
+    );
 
     let popup = render_bottom_popup(&chat, 80);
     assert!(
@@ -2863,10 +3096,13 @@ async fn plan_implementation_popup_shows_once_when_replay_precedes_live_turn_com
     chat.on_plan_delta("- Step 1\n- Step 2\n".to_string());
     chat.on_plan_item_completed("- Step 1\n- Step 2\n".to_string());
 
-    chat.replay_initial_messages(vec![EventMsg::TurnComplete(TurnCompleteEvent {
-        turn_id: "turn-1".to_string(),
-        last_agent_message: Some("Plan details".to_string()),
-    })]);
+    chat.replay_initial_messages(
+        vec![EventMsg::TurnComplete(TurnCompleteEvent {
+            turn_id: "turn-1".to_string(),
+            last_agent_message: Some("Plan details".to_string()),
+        })],
+        Vec::new(),
+    );
     let replay_popup = render_bottom_popup(&chat, 80);
     assert!(
         !replay_popup.contains(PLAN_IMPLEMENTATION_TITLE),
@@ -2912,9 +3148,12 @@ async fn plan_implementation_popup_shows_once_when_replay_precedes_live_turn_com
 async fn replayed_thread_rollback_emits_ordered_app_event() {
     let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(Some("gpt-5")).await;
 
-    chat.replay_initial_messages(vec![EventMsg::ThreadRolledBack(ThreadRolledBackEvent {
-        num_turns: 2,
-    })]);
+    chat.replay_initial_messages(
+        vec![EventMsg::ThreadRolledBack(ThreadRolledBackEvent {
+            num_turns: 2,
+        })],
+        Vec::new(),
+    );
 
     let mut saw = false;
     while let Ok(event) = rx.try_recv() {
@@ -2995,6 +3234,45 @@ async fn plan_implementation_popup_shows_after_proposed_plan_output() {
     );
 }
 
+#[tokio::test]
+async fn plan_steps_can_be_edited_and_advance_after_implementation_turns() {
+    let (mut chat, _rx, _op_rx) = make_chatwidget_manual(Some("gpt-5")).await;
+    chat.set_feature_enabled(Feature::CollaborationModes, true);
+    let plan_mask =
+        collaboration_modes::mask_for_kind(chat.models_manager.as_ref(), ModeKind::Plan)
+            .expect("expected plan collaboration mask");
+    chat.set_collaboration_mask(plan_mask);
+
+    let plan = "# Retries\n\n## Steps\n1. Add a retry policy\n2. Wrap the upload call\n";
+    chat.on_task_started();
+    chat.on_plan_item_completed(plan.to_string());
+    chat.on_task_complete(None, false);
+
+    let popup = render_bottom_popup(&chat, 80);
+    assert!(
+        popup.contains(PLAN_IMPLEMENTATION_EDIT_STEPS),
+        "expected an edit steps option, got {popup:?}"
+    );
+
+    chat.apply_plan_steps_edit(
+        "Wrap the upload call\nAdd a retry policy\nTest backoff".to_string(),
+    );
+    let default_mask = collaboration_modes::default_mode_mask(chat.models_manager.as_ref())
+        .expect("expected default collaboration mode");
+    chat.set_collaboration_mask(default_mask);
+    chat.on_task_started();
+    chat.on_task_complete(None, false);
+
+    assert_eq!(
+        chat.plan_steps.current(),
+        Some(PlanStepSummary {
+            number: 2,
+            total: 3,
+            text: "Add a retry policy".to_string(),
+        })
+    );
+}
+
 #[tokio::test]
 async fn plan_implementation_popup_skips_when_steer_follows_proposed_plan() {
     let (mut chat, _rx, mut op_rx) = make_chatwidget_manual(Some("gpt-5")).await;
@@ -3676,6 +3954,22 @@ async fn enqueueing_history_prompt_multiple_times_is_stable() {
     }
 }
 
+#[tokio::test]
+async fn ctrl_p_and_ctrl_n_browse_message_history() {
+    let (mut chat, _rx, _op_rx) = make_chatwidget_manual(None).await;
+    chat.thread_id = Some(ThreadId::new());
+    chat.bottom_pane
+        .set_composer_text("earlier prompt".to_string(), Vec::new(), Vec::new());
+    chat.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+    assert!(chat.bottom_pane.composer_text().is_empty());
+
+    chat.handle_key_event(KeyEvent::new(KeyCode::Char('p'), KeyModifiers::CONTROL));
+    assert_eq!(chat.bottom_pane.composer_text(), "earlier prompt");
+
+    chat.handle_key_event(KeyEvent::new(KeyCode::Char('n'), KeyModifiers::CONTROL));
+    assert!(chat.bottom_pane.composer_text().is_empty());
+}
+
 #[tokio::test]
 async fn streaming_final_answer_keeps_task_running_state() {
     let (mut chat, mut rx, mut op_rx) = make_chatwidget_manual(None).await;
@@ -4899,6 +5193,61 @@ async fn unified_exec_wait_status_header_updates_on_late_command_display() {
     assert_eq!(status.details(), Some("sleep 5"));
 }
 
+#[tokio::test]
+async fn turn_phase_events_update_status_header() {
+    let (mut chat, _rx, _op_rx) = make_chatwidget_manual(None).await;
+    chat.on_task_started();
+
+    for (phase, header) in [
+        (TurnPhase::Queued, "Queued"),
+        (TurnPhase::Sending, "Sending request"),
+        (TurnPhase::Thinking, "Thinking"),
+        (TurnPhase::Streaming, "Responding"),
+        (
+            TurnPhase::RunningTool { index: 1, total: 1 },
+            "Running tool",
+        ),
+        (
+            TurnPhase::RunningTool { index: 2, total: 3 },
+            "Running tool 2 of 3",
+        ),
+    ] {
+        chat.handle_codex_event(Event {
+            id: "turn-1".into(),
+            msg: EventMsg::TurnPhase(TurnPhaseEvent {
+                turn_id: "turn-1".to_string(),
+                phase,
+            }),
+        });
+        let status = chat
+            .bottom_pane
+            .status_widget()
+            .expect("status indicator should be visible");
+        assert_eq!(status.header(), header);
+    }
+}
+
+#[tokio::test]
+async fn turn_phase_status_widget_snapshot() {
+    let (mut chat, _rx, _op_rx) = make_chatwidget_manual(None).await;
+    chat.on_task_started();
+    chat.handle_codex_event(Event {
+        id: "turn-1".into(),
+        msg: EventMsg::TurnPhase(TurnPhaseEvent {
+            turn_id: "turn-1".to_string(),
+            phase: TurnPhase::RunningTool { index: 2, total: 3 },
+        }),
+    });
+
+    let height = chat.desired_height(80);
+    let mut terminal = ratatui::Terminal::new(ratatui::backend::TestBackend::new(80, height))
+        .expect("create terminal");
+    terminal
+        .draw(|f| chat.render(f.area(), f.buffer_mut()))
+        .expect("draw status widget");
+    assert_snapshot!("turn_phase_status_widget", terminal.backend());
+}
+
 #[tokio::test]
 async fn unified_exec_waiting_multiple_empty_snapshots() {
     let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
@@ -5607,6 +5956,49 @@ async fn slash_copy_state_clears_on_thread_rollback() {
     assert_eq!(chat.last_copyable_output, None);
 }
 
+#[tokio::test]
+async fn retried_answer_is_followed_by_a_diff_against_the_previous_one() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+
+    chat.handle_codex_event(Event {
+        id: "turn-1".into(),
+        msg: EventMsg::TurnComplete(TurnCompleteEvent {
+            turn_id: "turn-1".to_string(),
+            last_agent_message: Some("Guard the cache\nwith a mutex.\n".to_string()),
+        }),
+    });
+    chat.handle_codex_event(Event {
+        id: "rollback-1".into(),
+        msg: EventMsg::ThreadRolledBack(ThreadRolledBackEvent { num_turns: 1 }),
+    });
+    chat.resubmit_user_message(
+        "how should I share the cache?".to_string(),
+        Vec::new(),
+        Vec::new(),
+        Vec::new(),
+    );
+    let _ = drain_insert_history(&mut rx);
+
+    chat.handle_codex_event(Event {
+        id: "turn-2".into(),
+        msg: EventMsg::TurnComplete(TurnCompleteEvent {
+            turn_id: "turn-2".to_string(),
+            last_agent_message: Some("Guard the cache\nwith a channel.\n".to_string()),
+        }),
+    });
+
+    let rendered = drain_insert_history(&mut rx)
+        .iter()
+        .map(|lines| lines_to_single_string(lines))
+        .collect::<String>();
+    assert!(
+        rendered.contains("Changes from the previous answer +1 -1"),
+        "{rendered}"
+    );
+    assert!(rendered.contains("- with a mutex."), "{rendered}");
+    assert!(rendered.contains("+ with a channel."), "{rendered}");
+}
+
 #[tokio::test]
 async fn slash_copy_is_unavailable_when_legacy_agent_message_is_not_repeated_on_turn_complete() {
     let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
@@ -6126,24 +6518,61 @@ async fn interrupted_turn_error_message_snapshot() {
     assert_snapshot!("interrupted_turn_error_message", last);
 }
 
-/// Opening custom prompt from the review popup, pressing Esc returns to the
-/// parent popup, pressing Esc again dismisses all panels (back to normal mode).
 #[tokio::test]
-async fn review_custom_prompt_escape_navigates_back_then_dismisses() {
-    let (mut chat, _rx, _op_rx) = make_chatwidget_manual(None).await;
-
-    // Open the Review presets parent popup.
-    chat.open_review_popup();
-
-    // Open the custom prompt submenu (child view) directly.
-    chat.show_review_custom_prompt();
-
-    // Verify child view is on top.
-    let header = render_bottom_first_row(&chat, 60);
-    assert!(
-        header.contains("Custom review instructions"),
-        "expected custom prompt view header: {header:?}"
-    );
+async fn interrupted_turn_keeps_partial_agent_message() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+
+    chat.handle_codex_event(Event {
+        id: "task-1".into(),
+        msg: EventMsg::TurnStarted(TurnStartedEvent {
+            turn_id: "turn-1".to_string(),
+            model_context_window: None,
+            collaboration_mode_kind: ModeKind::Default,
+        }),
+    });
+    // No trailing newline, so the line is still buffered in the stream controller.
+    chat.handle_codex_event(Event {
+        id: "task-1".into(),
+        msg: EventMsg::AgentMessageDelta(AgentMessageDeltaEvent {
+            delta: "Half of the answer".into(),
+        }),
+    });
+    chat.handle_codex_event(Event {
+        id: "task-1".into(),
+        msg: EventMsg::TurnAborted(codex_protocol::protocol::TurnAbortedEvent {
+            turn_id: Some("turn-1".to_string()),
+            reason: TurnAbortReason::Interrupted,
+        }),
+    });
+
+    let combined = drain_insert_history(&mut rx)
+        .iter()
+        .map(|lines| lines_to_single_string(lines))
+        .collect::<String>();
+    assert!(
+        combined.contains("Half of the answer"),
+        "expected partial answer to stay in history, got: {combined}"
+    );
+}
+
+/// Opening custom prompt from the review popup, pressing Esc returns to the
+/// parent popup, pressing Esc again dismisses all panels (back to normal mode).
+#[tokio::test]
+async fn review_custom_prompt_escape_navigates_back_then_dismisses() {
+    let (mut chat, _rx, _op_rx) = make_chatwidget_manual(None).await;
+
+    // Open the Review presets parent popup.
+    chat.open_review_popup();
+
+    // Open the custom prompt submenu (child view) directly.
+    chat.show_review_custom_prompt();
+
+    // Verify child view is on top.
+    let header = render_bottom_first_row(&chat, 60);
+    assert!(
+        header.contains("Custom review instructions"),
+        "expected custom prompt view header: {header:?}"
+    );
 
     // Esc once: child view closes, parent (review presets) remains.
     chat.handle_key_event(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
@@ -6903,6 +7332,19 @@ async fn personality_selection_popup_snapshot() {
     assert_snapshot!("personality_selection_popup", popup);
 }
 
+#[tokio::test]
+async fn config_editor_popup_snapshot() {
+    let (mut chat, _rx, _op_rx) = make_chatwidget_manual(None).await;
+    chat.config.animations = true;
+    chat.config.tui_notifications = Notifications::Enabled(true);
+    chat.config.tui_notification_method = NotificationMethod::Auto;
+    chat.config.show_tooltips = true;
+    chat.show_selection_view(config_editor_params(&chat.config));
+
+    let popup = render_bottom_popup(&chat, 120);
+    assert_snapshot!("config_editor_popup", popup);
+}
+
 #[cfg(all(not(target_os = "linux"), feature = "voice-input"))]
 #[tokio::test]
 async fn realtime_audio_selection_popup_snapshot() {
@@ -7032,6 +7474,45 @@ async fn server_overloaded_error_does_not_switch_models() {
     }
 }
 
+#[tokio::test]
+async fn connectivity_error_queues_messages_until_reconnected() {
+    let (mut chat, mut rx, mut op_rx) = make_chatwidget_manual(None).await;
+    chat.thread_id = Some(ThreadId::new());
+
+    chat.handle_codex_event(Event {
+        id: "err-1".to_string(),
+        msg: EventMsg::Error(ErrorEvent {
+            message: "stream disconnected before completion".to_string(),
+            codex_error_info: Some(CodexErrorInfo::ResponseStreamDisconnected {
+                http_status_code: None,
+            }),
+        }),
+    });
+    drain_insert_history(&mut rx);
+    assert!(chat.offline);
+
+    chat.bottom_pane
+        .set_composer_text("still there?".to_string(), Vec::new(), Vec::new());
+    chat.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+    assert_eq!(chat.queued_user_messages.len(), 1);
+    assert_matches!(op_rx.try_recv(), Err(TryRecvError::Empty));
+
+    chat.on_connectivity_restored();
+    assert!(!chat.offline);
+    assert!(chat.queued_user_messages.is_empty());
+    let items = match next_submit_op(&mut op_rx) {
+        Op::UserTurn { items, .. } => items,
+        other => panic!("expected Op::UserTurn, got {other:?}"),
+    };
+    assert_eq!(
+        items,
+        vec![UserInput::Text {
+            text: "still there?".to_string(),
+            text_elements: Vec::new(),
+        }]
+    );
+}
+
 #[tokio::test]
 async fn approvals_selection_popup_snapshot() {
     let (mut chat, _rx, _op_rx) = make_chatwidget_manual(None).await;
@@ -7282,6 +7763,150 @@ async fn feedback_selection_popup_snapshot() {
     assert_snapshot!("feedback_selection_popup", popup);
 }
 
+#[tokio::test]
+async fn login_switch_popup_lists_saved_profiles() {
+    let (mut chat, _rx, _op_rx) = make_chatwidget_manual(None).await;
+    let codex_home = tempdir().expect("tempdir");
+    let mode = codex_core::auth::AuthCredentialsStoreMode::File;
+    chat.auth_manager = AuthManager::shared(codex_home.path().to_path_buf(), false, mode);
+    for (name, key) in [("work", "sk-work"), ("personal", "sk-personal")] {
+        codex_core::auth::login_with_api_key(codex_home.path(), key, mode).expect("login");
+        chat.auth_manager
+            .save_auth_profile(name)
+            .expect("save profile");
+    }
+
+    chat.dispatch_command(SlashCommand::Login);
+
+    let popup = render_bottom_popup(&chat, 80);
+    assert_snapshot!("login_switch_popup", popup);
+}
+
+#[tokio::test]
+async fn login_status_popup_shows_account_and_refresh_action() {
+    let (mut chat, _rx, _op_rx) = make_chatwidget_manual(None).await;
+    chat.auth_manager = codex_core::test_support::auth_manager_from_auth(
+        CodexAuth::create_dummy_chatgpt_auth_for_testing(),
+    );
+
+    chat.bottom_pane
+        .set_composer_text("/login status".to_string(), Vec::new(), Vec::new());
+    chat.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+
+    let popup = render_bottom_popup(&chat, 80);
+    assert_snapshot!("login_status_popup", popup);
+}
+
+#[test]
+fn auth_expiry_warning_flags_tokens_close_to_expiry() {
+    let now = chrono::DateTime::<chrono::Utc>::from_timestamp(1_700_000_000, 0).expect("now");
+
+    assert_eq!(auth_expiry_warning(None, now), None);
+    assert_eq!(
+        auth_expiry_warning(Some(now + chrono::TimeDelta::hours(1)), now),
+        None
+    );
+    assert_eq!(
+        auth_expiry_warning(Some(now + chrono::TimeDelta::seconds(245)), now),
+        Some("ChatGPT sign-in expires in 4m 05s · /login status to refresh".to_string())
+    );
+    assert_eq!(
+        auth_expiry_warning(Some(now - chrono::TimeDelta::seconds(30)), now),
+        Some("ChatGPT sign-in expired 30s ago · /login status to refresh".to_string())
+    );
+}
+
+#[tokio::test]
+async fn device_code_login_shows_code_and_ignores_cancelled_attempts() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+    chat.device_code_login_cancel = Some(Arc::new(Notify::new()));
+
+    chat.on_device_code_issued(
+        "https://auth.openai.com/codex/device".to_string(),
+        "ABCD-1234".to_string(),
+    );
+    let cells = drain_insert_history(&mut rx);
+    assert_eq!(cells.len(), 1, "expected the device code instructions");
+    assert_snapshot!(
+        "device_code_login_instructions",
+        lines_to_single_string(&cells[0])
+    );
+
+    chat.bottom_pane
+        .set_composer_text("/login cancel".to_string(), Vec::new(), Vec::new());
+    chat.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+    chat.on_device_code_login_finished(Err("Device code login failed: expired".to_string()));
+    let rendered = drain_insert_history(&mut rx)
+        .iter()
+        .map(|cell| lines_to_single_string(cell))
+        .collect::<String>();
+    assert_eq!(rendered, "• Device code login cancelled.\n");
+}
+
+#[tokio::test]
+async fn turn_error_actions_popup_snapshot() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+
+    chat.handle_codex_event(Event {
+        id: "turn-1".into(),
+        msg: EventMsg::Error(ErrorEvent {
+            message: "command was denied".to_string(),
+            codex_error_info: Some(CodexErrorInfo::SandboxError),
+        }),
+    });
+    let _ = drain_insert_history(&mut rx);
+
+    let popup = render_bottom_popup(&chat, 80);
+    assert_snapshot!("turn_error_actions_popup", popup);
+}
+
+fn agent_task(id: &str, nickname: &str, status: AgentStatus) -> AgentTask {
+    AgentTask {
+        thread_id: ThreadId::from_string(id).expect("valid thread id"),
+        entry: AgentPickerThreadEntry {
+            agent_nickname: Some(nickname.to_string()),
+            agent_role: Some("worker".to_string()),
+            is_closed: false,
+        },
+        status,
+    }
+}
+
+#[tokio::test]
+async fn task_board_popup_snapshot() {
+    let (mut chat, _rx, _op_rx) = make_chatwidget_manual(None).await;
+
+    chat.show_selection_view(task_board_params(vec![
+        agent_task(
+            "00000000-0000-0000-0000-000000000001",
+            "Robie",
+            AgentStatus::Completed(Some("Migrated the schema.".to_string())),
+        ),
+        agent_task(
+            "00000000-0000-0000-0000-000000000002",
+            "Bob",
+            AgentStatus::Running,
+        ),
+    ]));
+
+    let popup = render_bottom_popup(&chat, 80);
+    assert_snapshot!("task_board_popup", popup);
+}
+
+#[tokio::test]
+async fn agent_task_popup_snapshot() {
+    let (mut chat, _rx, _op_rx) = make_chatwidget_manual(None).await;
+
+    chat.show_selection_view(agent_task_params(&agent_task(
+        "00000000-0000-0000-0000-000000000002",
+        "Bob",
+        AgentStatus::Running,
+    )));
+
+    let popup = render_bottom_popup(&chat, 80);
+    assert_snapshot!("agent_task_popup", popup);
+}
+
 #[tokio::test]
 async fn feedback_upload_consent_popup_snapshot() {
     let (mut chat, _rx, _op_rx) = make_chatwidget_manual(None).await;
@@ -7434,6 +8059,444 @@ async fn fast_slash_command_updates_and_persists_local_service_tier() {
     assert_matches!(op_rx.try_recv(), Err(TryRecvError::Empty));
 }
 
+#[tokio::test]
+async fn audio_transcript_is_added_to_composer_with_source_path() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+
+    chat.on_audio_attachment_transcribed(
+        PathBuf::from("/tmp/standup.mp3"),
+        Ok("ship the release on friday".to_string()),
+    );
+
+    assert_eq!(
+        chat.bottom_pane.composer_text(),
+        "<audio_transcript path=\"/tmp/standup.mp3\">\nship the release on friday\n</audio_transcript>"
+    );
+    let cells = drain_insert_history(&mut rx);
+    let rendered = lines_to_single_string(cells.last().expect("info cell"));
+    assert!(
+        rendered.contains("/tmp/standup.mp3"),
+        "expected source path in history cell: {rendered}"
+    );
+}
+
+#[tokio::test]
+async fn ci_command_attaches_failing_steps_from_a_log_file() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+    let dir = tempfile::tempdir().expect("tempdir");
+    std::fs::write(
+        dir.path().join("ci.log"),
+        "##[group]Run cargo test\ntest parses_empty ... FAILED\n##[error]Process completed with exit code 101.\n",
+    )
+    .expect("write log");
+    chat.config.cwd = dir.path().to_path_buf();
+
+    chat.bottom_pane
+        .set_composer_text("/ci ci.log".to_string(), Vec::new(), Vec::new());
+    chat.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+    let result = loop {
+        match rx.recv().await {
+            Some(AppEvent::CiLogLoaded(result)) => break result,
+            Some(_) => continue,
+            None => panic!("app event channel closed"),
+        }
+    };
+    chat.on_ci_log_loaded(result);
+
+    let composer = chat.bottom_pane.composer_text();
+    assert!(
+        composer.starts_with("<ci_log source=\"ci.log\" lines=\"3\">\nFailing steps:\n- Run cargo test (exit code 101)\n"),
+        "{composer}"
+    );
+    assert!(
+        composer.contains("test parses_empty ... FAILED"),
+        "{composer}"
+    );
+}
+
+#[tokio::test]
+async fn pin_and_unpin_send_pinned_paths_to_core() {
+    let (mut chat, _rx, mut op_rx) = make_chatwidget_manual(None).await;
+    let dir = tempfile::tempdir().expect("tempdir");
+    let root = std::fs::canonicalize(dir.path()).expect("canonicalize");
+    std::fs::write(root.join("schema.sql"), "create table users (id int);").expect("write");
+    chat.config.cwd = root.clone();
+
+    chat.bottom_pane
+        .set_composer_text("/pin schema.sql".to_string(), Vec::new(), Vec::new());
+    chat.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+    match next_submit_op(&mut op_rx) {
+        Op::SetPinnedFiles { paths } => assert_eq!(paths, vec![root.join("schema.sql")]),
+        other => panic!("expected Op::SetPinnedFiles, got {other:?}"),
+    }
+
+    chat.bottom_pane
+        .set_composer_text("/unpin".to_string(), Vec::new(), Vec::new());
+    chat.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+    match next_submit_op(&mut op_rx) {
+        Op::SetPinnedFiles { paths } => assert_eq!(paths, Vec::<PathBuf>::new()),
+        other => panic!("expected Op::SetPinnedFiles, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn compare_models_sends_the_prompt_when_two_models_are_configured() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+
+    chat.bottom_pane.set_composer_text(
+        "/compare-models why is the build slow?".to_string(),
+        Vec::new(),
+        Vec::new(),
+    );
+    chat.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+    let rendered = drain_insert_history(&mut rx)
+        .iter()
+        .map(|lines| lines_to_single_string(lines))
+        .collect::<String>();
+    assert!(rendered.contains("compare_models"), "{rendered}");
+
+    chat.config.compare_models = vec!["gpt-5".to_string(), "gpt-5-mini".to_string()];
+    chat.bottom_pane.set_composer_text(
+        "/compare-models why is the build slow?".to_string(),
+        Vec::new(),
+        Vec::new(),
+    );
+    chat.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+    let mut prompts = Vec::new();
+    while let Ok(event) = rx.try_recv() {
+        if let AppEvent::CompareModels { prompt } = event {
+            prompts.push(prompt);
+        }
+    }
+    assert_eq!(prompts, vec!["why is the build slow?".to_string()]);
+}
+
+#[tokio::test]
+async fn conflicts_walk_proposes_and_applies_each_hunk() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+    let hunks = codex_core::parse_conflict_hunks(
+        Path::new("/repo/src/main.rs"),
+        "src/main.rs",
+        "<<<<<<< HEAD\n    run(1);\n=======\n    run(2);\n>>>>>>> feature\n",
+    );
+    let hunk = hunks[0].clone();
+
+    chat.on_conflicts_found(Ok(hunks));
+    let mut proposals = Vec::new();
+    while let Ok(event) = rx.try_recv() {
+        if let AppEvent::ProposeConflictResolution(hunk) = event {
+            proposals.push(hunk);
+        }
+    }
+    assert_eq!(proposals, vec![hunk.clone()]);
+
+    chat.on_conflict_resolution_proposed(
+        hunk.clone(),
+        Ok(ConflictResolution {
+            resolution: "    run(1);\n    run(2);\n".to_string(),
+            explanation: "Both calls are needed.".to_string(),
+        }),
+    );
+    chat.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+    let mut applied = Vec::new();
+    while let Ok(event) = rx.try_recv() {
+        if let AppEvent::ApplyConflictResolution { text, .. } = event {
+            applied.push(text);
+        }
+    }
+    assert_eq!(applied, vec!["    run(1);\n    run(2);\n".to_string()]);
+
+    chat.on_conflict_resolution_applied(hunk, Ok(0));
+    let rendered = drain_insert_history(&mut rx)
+        .iter()
+        .map(|lines| lines_to_single_string(lines))
+        .collect::<String>();
+    assert!(
+        rendered.contains("Resolved the conflict at src/main.rs:1"),
+        "{rendered}"
+    );
+    assert!(
+        rendered.contains("Done walking through the merge conflicts"),
+        "{rendered}"
+    );
+}
+
+#[tokio::test]
+async fn resumed_session_opens_with_its_stored_summary() {
+    let (mut chat, mut rx, _ops) = make_chatwidget_manual(None).await;
+    let dir = tempdir().unwrap();
+    let rollout_path = dir.path().join("rollout-2025-01-01T00-00-00-abc.jsonl");
+    std::fs::write(&rollout_path, "").unwrap();
+    let summary = SessionSummary {
+        accomplished: vec!["Added the `codex view` subcommand".to_string()],
+        decisions: vec!["Kept the viewer read-only".to_string()],
+        todos: Vec::new(),
+        generated_at: "2025-01-01T00:10:00Z".to_string(),
+    };
+    std::fs::write(
+        session_summary_path(&rollout_path),
+        serde_json::to_string(&summary).unwrap(),
+    )
+    .unwrap();
+
+    chat.handle_codex_event(Event {
+        id: "initial".into(),
+        msg: EventMsg::SessionConfigured(codex_protocol::protocol::SessionConfiguredEvent {
+            session_id: ThreadId::new(),
+            forked_from_id: None,
+            thread_name: None,
+            model: "test-model".to_string(),
+            model_provider_id: "test-provider".to_string(),
+            service_tier: None,
+            approval_policy: AskForApproval::Never,
+            sandbox_policy: SandboxPolicy::new_read_only_policy(),
+            cwd: PathBuf::from("/home/user/project"),
+            reasoning_effort: Some(ReasoningEffortConfig::default()),
+            history_log_id: 0,
+            history_entry_count: 0,
+            initial_messages: Some(vec![EventMsg::UserMessage(UserMessageEvent {
+                message: "add a viewer".to_string(),
+                images: None,
+                text_elements: Vec::new(),
+                local_images: Vec::new(),
+            })]),
+            network_proxy: None,
+            rollout_path: Some(rollout_path),
+        }),
+    });
+
+    let rendered = drain_insert_history(&mut rx)
+        .iter()
+        .map(|lines| lines_to_single_string(lines))
+        .collect::<String>();
+    let recap = rendered.find("Where you left off").expect(&rendered);
+    let prompt = rendered.find("add a viewer").expect(&rendered);
+    assert!(recap < prompt, "{rendered}");
+    assert!(
+        rendered.contains("Added the `codex view` subcommand"),
+        "{rendered}"
+    );
+    assert!(!rendered.contains("To do"), "{rendered}");
+}
+
+#[tokio::test]
+async fn accepting_the_carry_over_briefing_sends_it_to_core() {
+    let (mut chat, mut rx, _ops) = make_chatwidget_manual(None).await;
+    let briefing = CarryOverBriefing {
+        summaries: vec![SessionSummary {
+            accomplished: vec!["Refactored the pager".to_string()],
+            decisions: Vec::new(),
+            todos: vec!["Fix the failing snapshot tests".to_string()],
+            generated_at: "2025-01-01T00:10:00Z".to_string(),
+        }],
+    };
+
+    chat.offer_carry_over_briefing(briefing.clone());
+    chat.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+
+    let mut briefings = Vec::new();
+    let mut rendered = String::new();
+    while let Ok(event) = rx.try_recv() {
+        match event {
+            AppEvent::CodexOp(Op::SetCarryOverBriefing { briefing }) => briefings.push(briefing),
+            AppEvent::InsertHistoryCell(cell) => {
+                rendered.push_str(&lines_to_single_string(&cell.display_lines(80)));
+            }
+            _ => {}
+        }
+    }
+    assert_eq!(briefings, vec![briefing.to_context()]);
+    assert!(rendered.contains("Carried over"), "{rendered}");
+    assert!(
+        rendered.contains("Fix the failing snapshot tests"),
+        "{rendered}"
+    );
+}
+
+#[tokio::test]
+async fn commit_msg_opens_the_draft_for_editing_and_commits_on_enter() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+
+    chat.dispatch_command(SlashCommand::CommitMsg);
+    let mut drafts = 0;
+    while let Ok(event) = rx.try_recv() {
+        if matches!(event, AppEvent::DraftCommitMessage) {
+            drafts += 1;
+        }
+    }
+    assert_eq!(drafts, 1);
+
+    chat.on_commit_message_drafted(Ok("fix(tui): keep the cursor visible".to_string()));
+    chat.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+    let mut messages = Vec::new();
+    while let Ok(event) = rx.try_recv() {
+        if let AppEvent::CommitStaged { message } = event {
+            messages.push(message);
+        }
+    }
+    assert_eq!(
+        messages,
+        vec!["fix(tui): keep the cursor visible".to_string()]
+    );
+
+    chat.on_commit_created(Ok("abc1234 fix(tui): keep the cursor visible".to_string()));
+    let rendered = drain_insert_history(&mut rx)
+        .iter()
+        .map(|lines| lines_to_single_string(lines))
+        .collect::<String>();
+    assert!(
+        rendered.contains("Committed abc1234 fix(tui): keep the cursor visible"),
+        "{rendered}"
+    );
+}
+
+#[tokio::test]
+async fn pr_command_requests_a_pull_request_and_prints_its_url() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+
+    chat.bottom_pane.set_composer_text(
+        "/pr mention the config flag".to_string(),
+        Vec::new(),
+        Vec::new(),
+    );
+    chat.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+    let mut requests = Vec::new();
+    while let Ok(event) = rx.try_recv() {
+        if let AppEvent::CreatePullRequest { instructions } = event {
+            requests.push(instructions);
+        }
+    }
+    assert_eq!(requests, vec!["mention the config flag".to_string()]);
+
+    chat.on_pull_request_created(Ok(PullRequest {
+        url: "https://github.com/openai/codex/pull/42".to_string(),
+        title: "Add a config flag".to_string(),
+        head: "feature".to_string(),
+        base: "main".to_string(),
+        uncommitted_changes: true,
+    }));
+    let rendered = drain_insert_history(&mut rx)
+        .iter()
+        .map(|lines| lines_to_single_string(lines))
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    assert!(
+        rendered.contains("Opened pull request https://github.com/openai/codex/pull/42"),
+        "{rendered}"
+    );
+    assert!(
+        rendered.contains("uncommitted changes were not included"),
+        "{rendered}"
+    );
+}
+
+#[tokio::test]
+async fn diff_review_starts_a_review_and_exports_its_findings() {
+    let (mut chat, _rx, mut op_rx) = make_chatwidget_manual(None).await;
+    let dir = tempfile::tempdir().expect("tempdir");
+    chat.config.cwd = dir.path().to_path_buf();
+
+    chat.on_review_diff_loaded(
+        Ok(ReviewDiff {
+            source: "fix.patch".to_string(),
+            diff: "-old\n+new\n".to_string(),
+        }),
+        None,
+    );
+    let mut hints = Vec::new();
+    while let Ok(op) = op_rx.try_recv() {
+        if let Op::Review { review_request } = op {
+            hints.push(review_request.user_facing_hint);
+        }
+    }
+    assert_eq!(hints, vec![Some("diff from fix.patch".to_string())]);
+
+    chat.last_review_output = Some(ReviewOutputEvent {
+        findings: vec![codex_protocol::protocol::ReviewFinding {
+            title: "[P2] Off by one".to_string(),
+            body: "The loop skips the last item.".to_string(),
+            confidence_score: 0.7,
+            priority: 2,
+            code_location: codex_protocol::protocol::ReviewCodeLocation {
+                absolute_file_path: dir.path().join("src").join("lib.rs"),
+                line_range: codex_protocol::protocol::ReviewLineRange { start: 3, end: 3 },
+            },
+        }],
+        overall_correctness: "patch is incorrect".to_string(),
+        overall_explanation: String::new(),
+        overall_confidence_score: 0.7,
+    });
+    chat.export_review("review.md");
+    let exported = std::fs::read_to_string(dir.path().join("review.md")).expect("read export");
+    assert!(exported.contains("### [P2] Off by one\n`src"), "{exported}");
+    assert!(exported.contains("lib.rs:3-3`"), "{exported}");
+}
+
+#[tokio::test]
+async fn trim_picker_sends_the_selected_tool_output() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+    let entry = |call_id: &str, tokens: i64| codex_core::ContextEntry {
+        heading: "tool output: shell".to_string(),
+        text: format!("output of {call_id}"),
+        tokens,
+        trimmable_call_id: Some(call_id.to_string()),
+    };
+    let context = NextTurnContext {
+        sections: vec![codex_core::ContextSection {
+            kind: codex_core::ContextSectionKind::RecentTurns,
+            entries: vec![entry("small", 10), entry("large", 9_400)],
+        }],
+        context_window: None,
+    };
+
+    chat.open_trim_picker(&context);
+    chat.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+
+    let events = std::iter::from_fn(|| rx.try_recv().ok()).collect::<Vec<_>>();
+    assert!(
+        events.iter().any(|event| matches!(
+            event,
+            AppEvent::CodexOp(Op::TrimToolOutput { call_id }) if call_id == "large"
+        )),
+        "expected the largest output to be trimmed; events: {events:?}"
+    );
+}
+
+#[tokio::test]
+async fn params_args_update_session_sampling() {
+    let (mut chat, _rx, mut op_rx) = make_chatwidget_manual(None).await;
+
+    let sampling = parse_sampling_args(chat.sampling, "temperature=0.3 top_p=0.9")
+        .expect("valid sampling args");
+    chat.set_sampling_params(sampling);
+
+    match next_submit_op(&mut op_rx) {
+        Op::SetSamplingParams { sampling } => assert_eq!(
+            sampling,
+            SamplingParams {
+                temperature: Some(0.3),
+                top_p: Some(0.9),
+            }
+        ),
+        other => panic!("expected Op::SetSamplingParams, got {other:?}"),
+    }
+    assert_eq!(
+        parse_sampling_args(chat.sampling, "top_p=default"),
+        Ok(SamplingParams {
+            temperature: Some(0.3),
+            top_p: None,
+        })
+    );
+    assert_eq!(
+        parse_sampling_args(chat.sampling, "reset"),
+        Ok(SamplingParams::default())
+    );
+    assert!(parse_sampling_args(chat.sampling, "temperature=3").is_err());
+    assert!(parse_sampling_args(chat.sampling, "seed=1").is_err());
+}
+
 #[tokio::test]
 async fn user_turn_carries_service_tier_after_fast_toggle() {
     let (mut chat, mut rx, mut op_rx) = make_chatwidget_manual(Some("gpt-5.3-codex")).await;
@@ -8880,6 +9943,36 @@ async fn plan_update_renders_history_cell() {
     assert!(blob.contains("Write tests"));
 }
 
+#[tokio::test]
+async fn todo_list_update_renders_checklist() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+    chat.handle_codex_event(Event {
+        id: "sub-1".into(),
+        msg: EventMsg::TodoListUpdate(TodoListEvent {
+            items: vec![
+                TodoItem {
+                    id: 1,
+                    text: "Reproduce the crash".into(),
+                    status: TodoStatus::Done,
+                },
+                TodoItem {
+                    id: 2,
+                    text: "Fix the parser".into(),
+                    status: TodoStatus::InProgress,
+                },
+            ],
+        }),
+    });
+    let cells = drain_insert_history(&mut rx);
+    let blob = lines_to_single_string(cells.last().expect("todo list cell"));
+    assert!(
+        blob.contains("Todo List (1 of 2 open)"),
+        "missing todo header: {blob:?}"
+    );
+    assert!(blob.contains("✔ #1 Reproduce the crash"));
+    assert!(blob.contains("□ #2 Fix the parser"));
+}
+
 #[tokio::test]
 async fn stream_error_updates_status_indicator() {
     let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
@@ -8892,6 +9985,7 @@ async fn stream_error_updates_status_indicator() {
             message: msg.to_string(),
             codex_error_info: Some(CodexErrorInfo::Other),
             additional_details: Some(details.to_string()),
+            retry_delay_ms: None,
         }),
     });
 
@@ -8912,11 +10006,14 @@ async fn stream_error_updates_status_indicator() {
 async fn replayed_turn_started_does_not_mark_task_running() {
     let (mut chat, _rx, _op_rx) = make_chatwidget_manual(None).await;
 
-    chat.replay_initial_messages(vec![EventMsg::TurnStarted(TurnStartedEvent {
-        turn_id: "turn-1".to_string(),
-        model_context_window: None,
-        collaboration_mode_kind: ModeKind::Default,
-    })]);
+    chat.replay_initial_messages(
+        vec![EventMsg::TurnStarted(TurnStartedEvent {
+            turn_id: "turn-1".to_string(),
+            model_context_window: None,
+            collaboration_mode_kind: ModeKind::Default,
+        })],
+        Vec::new(),
+    );
 
     assert!(!chat.bottom_pane.is_task_running());
     assert!(chat.bottom_pane.status_widget().is_none());
@@ -8949,11 +10046,15 @@ async fn replayed_stream_error_does_not_set_retry_status_or_status_indicator() {
     let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
     chat.set_status_header("Idle".to_string());
 
-    chat.replay_initial_messages(vec![EventMsg::StreamError(StreamErrorEvent {
-        message: "Reconnecting... 2/5".to_string(),
-        codex_error_info: Some(CodexErrorInfo::Other),
-        additional_details: Some("Idle timeout waiting for SSE".to_string()),
-    })]);
+    chat.replay_initial_messages(
+        vec![EventMsg::StreamError(StreamErrorEvent {
+            message: "Reconnecting... 2/5".to_string(),
+            codex_error_info: Some(CodexErrorInfo::Other),
+            additional_details: Some("Idle timeout waiting for SSE".to_string()),
+            retry_delay_ms: None,
+        })],
+        Vec::new(),
+    );
 
     let cells = drain_insert_history(&mut rx);
     assert!(
@@ -8985,6 +10086,7 @@ async fn thread_snapshot_replayed_stream_recovery_restores_previous_status_heade
             message: "Reconnecting... 1/5".to_string(),
             codex_error_info: Some(CodexErrorInfo::Other),
             additional_details: None,
+            retry_delay_ms: None,
         }),
     });
     drain_insert_history(&mut rx);
@@ -9010,21 +10112,25 @@ async fn resume_replay_interrupted_reconnect_does_not_leave_stale_working_state(
     let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
     chat.set_status_header("Idle".to_string());
 
-    chat.replay_initial_messages(vec![
-        EventMsg::TurnStarted(TurnStartedEvent {
-            turn_id: "turn-1".to_string(),
-            model_context_window: None,
-            collaboration_mode_kind: ModeKind::Default,
-        }),
-        EventMsg::StreamError(StreamErrorEvent {
-            message: "Reconnecting... 1/5".to_string(),
-            codex_error_info: Some(CodexErrorInfo::Other),
-            additional_details: None,
-        }),
-        EventMsg::AgentMessageDelta(AgentMessageDeltaEvent {
-            delta: "hello".to_string(),
-        }),
-    ]);
+    chat.replay_initial_messages(
+        vec![
+            EventMsg::TurnStarted(TurnStartedEvent {
+                turn_id: "turn-1".to_string(),
+                model_context_window: None,
+                collaboration_mode_kind: ModeKind::Default,
+            }),
+            EventMsg::StreamError(StreamErrorEvent {
+                message: "Reconnecting... 1/5".to_string(),
+                codex_error_info: Some(CodexErrorInfo::Other),
+                additional_details: None,
+                retry_delay_ms: None,
+            }),
+            EventMsg::AgentMessageDelta(AgentMessageDeltaEvent {
+                delta: "hello".to_string(),
+            }),
+        ],
+        Vec::new(),
+    );
 
     let cells = drain_insert_history(&mut rx);
     assert!(
@@ -9041,18 +10147,22 @@ async fn resume_replay_interrupted_reconnect_does_not_leave_stale_working_state(
 async fn replayed_interrupted_reconnect_footer_row_snapshot() {
     let (mut chat, _rx, _op_rx) = make_chatwidget_manual(None).await;
 
-    chat.replay_initial_messages(vec![
-        EventMsg::TurnStarted(TurnStartedEvent {
-            turn_id: "turn-1".to_string(),
-            model_context_window: None,
-            collaboration_mode_kind: ModeKind::Default,
-        }),
-        EventMsg::StreamError(StreamErrorEvent {
-            message: "Reconnecting... 2/5".to_string(),
-            codex_error_info: Some(CodexErrorInfo::Other),
-            additional_details: Some("Idle timeout waiting for SSE".to_string()),
-        }),
-    ]);
+    chat.replay_initial_messages(
+        vec![
+            EventMsg::TurnStarted(TurnStartedEvent {
+                turn_id: "turn-1".to_string(),
+                model_context_window: None,
+                collaboration_mode_kind: ModeKind::Default,
+            }),
+            EventMsg::StreamError(StreamErrorEvent {
+                message: "Reconnecting... 2/5".to_string(),
+                codex_error_info: Some(CodexErrorInfo::Other),
+                additional_details: Some("Idle timeout waiting for SSE".to_string()),
+                retry_delay_ms: None,
+            }),
+        ],
+        Vec::new(),
+    );
 
     let header = render_bottom_first_row(&chat, 80);
     assert!(
@@ -9079,6 +10189,7 @@ async fn stream_error_restores_hidden_status_indicator() {
             message: msg.to_string(),
             codex_error_info: Some(CodexErrorInfo::Other),
             additional_details: Some(details.to_string()),
+            retry_delay_ms: None,
         }),
     });
 
@@ -9137,6 +10248,34 @@ async fn status_line_invalid_items_warn_once() {
     );
 }
 
+#[tokio::test]
+async fn status_line_command_input_reports_live_context_window() {
+    let (mut chat, _rx, _op_rx) = make_chatwidget_manual(None).await;
+    assert_eq!(
+        chat.status_line_command_input()
+            .context_window
+            .current_usage,
+        None
+    );
+
+    let info = make_token_info(60_000, 200_000);
+    let expected_remaining = info
+        .last_token_usage
+        .percent_of_context_window_remaining(200_000);
+    chat.handle_codex_event(Event {
+        id: "token-usage".into(),
+        msg: EventMsg::TokenCount(TokenCountEvent {
+            info: Some(info.clone()),
+            rate_limits: None,
+        }),
+    });
+
+    let context_window = chat.status_line_command_input().context_window;
+    assert_eq!(context_window.size, Some(200_000));
+    assert_eq!(context_window.current_usage, Some(info.last_token_usage));
+    assert_eq!(context_window.percent_remaining, Some(expected_remaining));
+}
+
 #[tokio::test]
 async fn status_line_branch_state_resets_when_git_branch_disabled() {
     let (mut chat, _rx, _op_rx) = make_chatwidget_manual(None).await;
@@ -9206,6 +10345,7 @@ async fn stream_recovery_restores_previous_status_header() {
             message: "Reconnecting... 1/5".to_string(),
             codex_error_info: Some(CodexErrorInfo::Other),
             additional_details: None,
+            retry_delay_ms: None,
         }),
     });
     drain_insert_history(&mut rx);
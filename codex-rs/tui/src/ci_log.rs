@@ -0,0 +1,392 @@
+//! Fetch and condense CI logs for `/ci <file-or-url>`.
+//!
+//! A CI log is cut down to the steps that failed and short excerpts around error lines, so the
+//! model gets what it needs to fix the failure without the whole log. Everything is capped: only
+//! the tail of a huge log is read, and the excerpts stay under `MAX_EXCERPT_BYTES`.
+//!
+//! GitHub Actions logs (`##[group]` step markers, `##[error]` lines, timestamp prefixes) get step
+//! names; other CI systems still get the error excerpts.
+
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::LazyLock;
+use std::time::Duration;
+
+use codex_utils_string::strip_ansi_escapes;
+use regex_lite::Regex;
+use tokio::io::AsyncReadExt;
+use tokio::io::AsyncSeekExt;
+
+/// Only this much of the end of a log is read; failures are almost always near the end.
+const MAX_LOG_BYTES: usize = 8 * 1024 * 1024;
+/// Budget for the excerpt text placed in the composer.
+const MAX_EXCERPT_BYTES: usize = 16 * 1024;
+const MAX_EXCERPTS: usize = 20;
+const MAX_LINE_CHARS: usize = 400;
+const CONTEXT_BEFORE: usize = 3;
+const CONTEXT_AFTER: usize = 6;
+/// Lines kept from the end of a log in which no error line was recognized.
+const FALLBACK_TAIL_LINES: usize = 80;
+const FETCH_TIMEOUT: Duration = Duration::from_secs(30);
+
+static TIMESTAMP_PREFIX: LazyLock<Regex> =
+    LazyLock::new(
+        || match Regex::new(r"^\d{4}-\d{2}-\d{2}T\d{2}:\d{2}:\d{2}(\.\d+)?Z ") {
+            Ok(regex) => regex,
+            Err(error) => panic!("invalid timestamp prefix regex: {error}"),
+        },
+    );
+static ERROR_LINE: LazyLock<Regex> = LazyLock::new(|| {
+    match Regex::new(
+        r"(?i)(^##\[error\]|\berror(\[E\d+\])?[:!]|\bfailed\b|\bfailure\b|panicked at|^\s*FAIL\b|Traceback \(most recent call last\)|\bexception\b)",
+    ) {
+        Ok(regex) => regex,
+        Err(error) => panic!("invalid error line regex: {error}"),
+    }
+});
+static ZERO_FAILURES: LazyLock<Regex> =
+    LazyLock::new(
+        || match Regex::new(r"(?i)\b0 (failed|failures|errors?)\b") {
+            Ok(regex) => regex,
+            Err(error) => panic!("invalid zero failures regex: {error}"),
+        },
+    );
+static EXIT_CODE: LazyLock<Regex> = LazyLock::new(|| match Regex::new(r"exit code (\d+)") {
+    Ok(regex) => regex,
+    Err(error) => panic!("invalid exit code regex: {error}"),
+});
+
+/// What `/ci` attaches: the failing steps and error excerpts from one log.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct CiLogDigest {
+    pub(crate) source: String,
+    pub(crate) total_lines: usize,
+    /// Only the tail of the log was read.
+    pub(crate) head_skipped: bool,
+    pub(crate) failing_steps: Vec<String>,
+    pub(crate) excerpts: Vec<CiLogExcerpt>,
+    /// Some excerpts were dropped to stay within the caps.
+    pub(crate) excerpts_dropped: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct CiLogExcerpt {
+    /// 1-based line number of the first line.
+    pub(crate) first_line: usize,
+    pub(crate) lines: Vec<String>,
+}
+
+/// Read the log `arg` names (a path relative to `cwd`, or an http(s) URL) and condense it.
+pub(crate) async fn load(arg: &str, cwd: &Path) -> Result<CiLogDigest, String> {
+    let arg = arg.trim();
+    if arg.is_empty() {
+        return Err("Usage: /ci <file-or-url>".to_string());
+    }
+    let (text, head_skipped) = if arg.starts_with("http://") || arg.starts_with("https://") {
+        fetch(arg).await?
+    } else {
+        read_tail(&resolve(arg, cwd)).await?
+    };
+    let mut digest = digest(arg, &text);
+    digest.head_skipped = head_skipped;
+    Ok(digest)
+}
+
+async fn fetch(url: &str) -> Result<(String, bool), String> {
+    let client = reqwest::Client::builder()
+        .timeout(FETCH_TIMEOUT)
+        .build()
+        .map_err(|err| format!("failed to fetch {url}: {err}"))?;
+    let mut request = client
+        .get(url)
+        .header(reqwest::header::USER_AGENT, "codex-cli");
+    if let Some(token) = github_token(url) {
+        request = request.bearer_auth(token);
+    }
+    let mut response = request
+        .send()
+        .await
+        .map_err(|err| format!("failed to fetch {url}: {err}"))?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(format!("failed to fetch {url}: HTTP {status}"));
+    }
+
+    let mut bytes = Vec::new();
+    let mut head_skipped = false;
+    while let Some(chunk) = response
+        .chunk()
+        .await
+        .map_err(|err| format!("failed to fetch {url}: {err}"))?
+    {
+        bytes.extend_from_slice(&chunk);
+        if bytes.len() > 2 * MAX_LOG_BYTES {
+            bytes.drain(..bytes.len() - MAX_LOG_BYTES);
+            head_skipped = true;
+        }
+    }
+    let (text, trimmed) = tail_text(&bytes);
+    Ok((text, head_skipped || trimmed))
+}
+
+/// GitHub log downloads need a token; use the one the `gh` CLI and Actions already export.
+fn github_token(url: &str) -> Option<String> {
+    let host = url::Url::parse(url).ok()?.host_str()?.to_ascii_lowercase();
+    if host != "github.com" && host != "api.github.com" {
+        return None;
+    }
+    ["GH_TOKEN", "GITHUB_TOKEN"]
+        .into_iter()
+        .find_map(|name| std::env::var(name).ok().filter(|token| !token.is_empty()))
+}
+
+async fn read_tail(path: &Path) -> Result<(String, bool), String> {
+    let read_error = |err: std::io::Error| format!("failed to read {}: {err}", path.display());
+    let mut file = tokio::fs::File::open(path).await.map_err(read_error)?;
+    let len = file.metadata().await.map_err(read_error)?.len();
+    let start = len.saturating_sub(MAX_LOG_BYTES as u64);
+    if start > 0 {
+        file.seek(std::io::SeekFrom::Start(start))
+            .await
+            .map_err(read_error)?;
+    }
+    let mut bytes = Vec::new();
+    file.read_to_end(&mut bytes).await.map_err(read_error)?;
+    let (text, _) = tail_text(&bytes);
+    Ok((text, start > 0))
+}
+
+/// The last `MAX_LOG_BYTES` of `bytes`, starting at a line boundary.
+fn tail_text(bytes: &[u8]) -> (String, bool) {
+    if bytes.len() <= MAX_LOG_BYTES {
+        return (String::from_utf8_lossy(bytes).into_owned(), false);
+    }
+    let tail = &bytes[bytes.len() - MAX_LOG_BYTES..];
+    let tail = match tail.iter().position(|byte| *byte == b'\n') {
+        Some(newline) => &tail[newline + 1..],
+        None => tail,
+    };
+    (String::from_utf8_lossy(tail).into_owned(), true)
+}
+
+fn resolve(arg: &str, cwd: &Path) -> PathBuf {
+    match arg.strip_prefix("~/") {
+        Some(rest) => dirs::home_dir()
+            .map(|home| home.join(rest))
+            .unwrap_or_else(|| cwd.join(arg)),
+        None => cwd.join(arg),
+    }
+}
+
+/// Find the failing steps and the excerpts around error lines in `text`.
+pub(crate) fn digest(source: &str, text: &str) -> CiLogDigest {
+    let lines: Vec<String> = text.lines().map(clean_line).collect();
+
+    let mut failing_steps = Vec::new();
+    let mut current_step: Option<&str> = None;
+    let mut error_lines = Vec::new();
+    for (index, line) in lines.iter().enumerate() {
+        if let Some(step) = line.strip_prefix("##[group]") {
+            current_step = Some(step.trim());
+            continue;
+        }
+        if let Some(reason) = step_failure(line) {
+            let step = format!("{} ({reason})", current_step.unwrap_or("job"));
+            if !failing_steps.contains(&step) {
+                failing_steps.push(step);
+            }
+        }
+        if ERROR_LINE.is_match(line) && !ZERO_FAILURES.is_match(line) {
+            error_lines.push(index);
+        }
+    }
+
+    let ranges = if error_lines.is_empty() {
+        vec![(lines.len().saturating_sub(FALLBACK_TAIL_LINES), lines.len())]
+    } else {
+        merge_ranges(&error_lines, lines.len())
+    };
+
+    let mut excerpts = Vec::new();
+    let mut excerpts_dropped = false;
+    let mut budget = MAX_EXCERPT_BYTES;
+    for (start, end) in ranges {
+        if excerpts.len() == MAX_EXCERPTS {
+            excerpts_dropped = true;
+            break;
+        }
+        let excerpt_lines: Vec<String> = lines[start..end]
+            .iter()
+            .map(|line| truncate_line(line))
+            .collect();
+        let size: usize = excerpt_lines.iter().map(|line| line.len() + 1).sum();
+        if size > budget {
+            excerpts_dropped = true;
+            break;
+        }
+        budget -= size;
+        if !excerpt_lines.is_empty() {
+            excerpts.push(CiLogExcerpt {
+                first_line: start + 1,
+                lines: excerpt_lines,
+            });
+        }
+    }
+
+    CiLogDigest {
+        source: source.to_string(),
+        total_lines: lines.len(),
+        head_skipped: false,
+        failing_steps,
+        excerpts,
+        excerpts_dropped,
+    }
+}
+
+fn clean_line(line: &str) -> String {
+    let line = line.trim_end_matches('\r');
+    let line = strip_ansi_escapes(line);
+    TIMESTAMP_PREFIX.replace(&line, "").into_owned()
+}
+
+/// Why the current step failed, if `line` says it did.
+fn step_failure(line: &str) -> Option<String> {
+    if !line.contains("Process completed with exit code") && !line.starts_with("ERROR: Job failed")
+    {
+        return None;
+    }
+    Some(
+        EXIT_CODE
+            .captures(line)
+            .and_then(|captures| captures.get(1))
+            .map(|code| format!("exit code {}", code.as_str()))
+            .unwrap_or_else(|| "failed".to_string()),
+    )
+}
+
+/// Context windows around each error line, merged where they overlap or touch.
+fn merge_ranges(error_lines: &[usize], total: usize) -> Vec<(usize, usize)> {
+    let mut ranges: Vec<(usize, usize)> = Vec::new();
+    for &index in error_lines {
+        let start = index.saturating_sub(CONTEXT_BEFORE);
+        let end = (index + CONTEXT_AFTER + 1).min(total);
+        match ranges.last_mut() {
+            Some(last) if start <= last.1 => last.1 = last.1.max(end),
+            _ => ranges.push((start, end)),
+        }
+    }
+    ranges
+}
+
+fn truncate_line(line: &str) -> String {
+    match line.char_indices().nth(MAX_LINE_CHARS) {
+        Some((cut, _)) => format!("{}…", &line[..cut]),
+        None => line.to_string(),
+    }
+}
+
+/// Wrap the digest so the model can tell it apart from typed text and knows where it came from.
+pub(crate) fn format_digest(digest: &CiLogDigest) -> String {
+    let mut text = format!(
+        "<ci_log source=\"{}\" lines=\"{}\">\n",
+        digest.source, digest.total_lines
+    );
+    if digest.head_skipped {
+        text.push_str("Only the end of the log was read; line numbers count from there.\n");
+    }
+    if !digest.failing_steps.is_empty() {
+        text.push_str("Failing steps:\n");
+        for step in &digest.failing_steps {
+            text.push_str(&format!("- {step}\n"));
+        }
+        text.push('\n');
+    }
+    text.push_str("Error excerpts:\n");
+    for (index, excerpt) in digest.excerpts.iter().enumerate() {
+        if index > 0 {
+            text.push('\n');
+        }
+        let last_line = excerpt.first_line + excerpt.lines.len() - 1;
+        text.push_str(&format!("[lines {}-{last_line}]\n", excerpt.first_line));
+        for line in &excerpt.lines {
+            text.push_str(line);
+            text.push('\n');
+        }
+    }
+    if digest.excerpts_dropped {
+        text.push_str("\n[More errors were left out to keep this short.]\n");
+    }
+    text.push_str("</ci_log>");
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    const ACTIONS_LOG: &str = "\
+2024-05-01T10:00:00.0000000Z ##[group]Run cargo build
+2024-05-01T10:00:01.0000000Z    Compiling demo v0.1.0
+2024-05-01T10:00:02.0000000Z ##[endgroup]
+2024-05-01T10:00:03.0000000Z ##[group]Run cargo test
+2024-05-01T10:00:04.0000000Z running 2 tests
+2024-05-01T10:00:05.0000000Z test parses_empty ... \x1b[31mFAILED\x1b[0m
+2024-05-01T10:00:06.0000000Z test parses_one ... ok
+2024-05-01T10:00:07.0000000Z test result: FAILED. 1 passed; 1 failed; 0 ignored
+2024-05-01T10:00:08.0000000Z ##[error]Process completed with exit code 101.
+";
+
+    #[test]
+    fn finds_failing_steps_and_error_excerpts_in_actions_logs() {
+        let digest = digest("ci.log", ACTIONS_LOG);
+        assert_eq!(digest.total_lines, 9);
+        assert_eq!(
+            digest.failing_steps,
+            vec!["Run cargo test (exit code 101)".to_string()]
+        );
+        assert_eq!(
+            digest.excerpts,
+            vec![CiLogExcerpt {
+                first_line: 3,
+                lines: vec![
+                    "##[endgroup]".to_string(),
+                    "##[group]Run cargo test".to_string(),
+                    "running 2 tests".to_string(),
+                    "test parses_empty ... FAILED".to_string(),
+                    "test parses_one ... ok".to_string(),
+                    "test result: FAILED. 1 passed; 1 failed; 0 ignored".to_string(),
+                    "##[error]Process completed with exit code 101.".to_string(),
+                ],
+            }]
+        );
+        assert_eq!(
+            format_digest(&digest),
+            "<ci_log source=\"ci.log\" lines=\"9\">\nFailing steps:\n- Run cargo test (exit code 101)\n\nError excerpts:\n[lines 3-9]\n##[endgroup]\n##[group]Run cargo test\nrunning 2 tests\ntest parses_empty ... FAILED\ntest parses_one ... ok\ntest result: FAILED. 1 passed; 1 failed; 0 ignored\n##[error]Process completed with exit code 101.\n</ci_log>"
+        );
+    }
+
+    #[test]
+    fn logs_without_recognized_errors_fall_back_to_the_tail() {
+        let log = (1..=200)
+            .map(|n| format!("step {n}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let digest = digest("https://ci.example.com/job/1/log", &log);
+        assert!(digest.failing_steps.is_empty());
+        assert_eq!(digest.excerpts.len(), 1);
+        assert_eq!(digest.excerpts[0].first_line, 121);
+        assert_eq!(digest.excerpts[0].lines.len(), FALLBACK_TAIL_LINES);
+    }
+
+    #[test]
+    fn excerpts_are_capped() {
+        let log = (0..200)
+            .map(|n| format!("error: problem {n}\n{}", "filler\n".repeat(20)))
+            .collect::<String>();
+        let digest = digest("big.log", &log);
+        assert_eq!(digest.excerpts.len(), MAX_EXCERPTS);
+        assert!(digest.excerpts_dropped);
+        assert!(format_digest(&digest).len() < MAX_EXCERPT_BYTES + 2048);
+    }
+}
@@ -49,6 +49,15 @@ pub struct Cli {
     #[clap(skip)]
     pub fork_show_all: bool,
 
+    /// Internal: review this git ref, range, or patch file on startup. Set by
+    /// `codex review --diff`; not exposed as a public flag.
+    #[clap(skip)]
+    pub review_diff: Option<String>,
+
+    /// Internal: extra instructions for the startup review of `review_diff`.
+    #[clap(skip)]
+    pub review_instructions: Option<String>,
+
     /// Model the agent should use.
     #[arg(long, short = 'm')]
     pub model: Option<String>,
@@ -90,6 +99,28 @@ pub struct Cli {
     )]
     pub dangerously_bypass_approvals_and_sandbox: bool,
 
+    /// Explore without changing anything: mutating tools (file writes, patches, and shell
+    /// commands outside the known-safe allowlist) are disabled and the sandbox is read-only.
+    #[arg(
+        long = "read-only",
+        default_value_t = false,
+        conflicts_with_all = ["full_auto", "dangerously_bypass_approvals_and_sandbox"]
+    )]
+    pub read_only: bool,
+
+    /// Audit the agent's plan: commands and patches that would change anything are shown as
+    /// "would run" / "would apply" cells and the model receives simulated results.
+    #[arg(
+        long = "dry-run",
+        default_value_t = false,
+        conflicts_with_all = [
+            "read_only",
+            "full_auto",
+            "dangerously_bypass_approvals_and_sandbox"
+        ]
+    )]
+    pub dry_run: bool,
+
     /// Tell the agent to use the specified directory as its working root.
     #[clap(long = "cd", short = 'C', value_name = "DIR")]
     pub cwd: Option<PathBuf>,
@@ -110,6 +141,13 @@ pub struct Cli {
     #[arg(long = "no-alt-screen", default_value_t = false)]
     pub no_alt_screen: bool,
 
+    /// Use a line-based interface instead of the full-screen TUI.
+    ///
+    /// Prompts are read from stdin one line at a time, and responses, commands, and approval
+    /// requests are printed as plain text. Useful for dumb terminals, Emacs shells, and logs.
+    #[arg(long = "plain", default_value_t = false)]
+    pub plain: bool,
+
     #[clap(skip)]
     pub config_overrides: CliConfigOverrides,
 }
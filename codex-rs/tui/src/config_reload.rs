@@ -0,0 +1,178 @@
+//! Pick up edits to `config.toml` while the TUI is running.
+//!
+//! [`ConfigWatcher`] watches the config files the session was loaded from and sends
+//! [`AppEvent::ConfigFileChanged`] once an edit settles. The app then reloads the config and
+//! uses [`diff_config`] to decide what to do: display settings (theme, status line, key
+//! bindings, notifications, tooltips) are applied on the spot, while settings the running thread was
+//! created with (model, provider) only take effect in a new session, so the user is asked
+//! first.
+
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use codex_core::config::Config;
+use notify::EventKind;
+use notify::RecommendedWatcher;
+use notify::RecursiveMode;
+use notify::Watcher;
+use tokio::sync::mpsc;
+
+use crate::app_event::AppEvent;
+use crate::app_event_sender::AppEventSender;
+
+/// Editors often save in several steps (truncate, write, rename); wait for the burst to end.
+const DEBOUNCE: Duration = Duration::from_millis(300);
+
+pub(crate) struct ConfigWatcher {
+    _watcher: RecommendedWatcher,
+}
+
+impl ConfigWatcher {
+    /// Watch `files` for changes. Their parent directories are watched rather than the files
+    /// themselves so edits that replace the file are still noticed.
+    pub(crate) fn spawn(files: Vec<PathBuf>, app_event_tx: AppEventSender) -> Option<Self> {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let watched: HashSet<PathBuf> = files.iter().cloned().collect();
+        let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+            let Ok(event) = res else {
+                return;
+            };
+            if matches!(
+                event.kind,
+                EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
+            ) && event.paths.iter().any(|path| watched.contains(path))
+            {
+                let _ = tx.send(());
+            }
+        })
+        .inspect_err(|err| tracing::warn!("failed to start the config file watcher: {err}"))
+        .ok()?;
+
+        let dirs: HashSet<PathBuf> = files
+            .iter()
+            .filter_map(|file| file.parent().map(PathBuf::from))
+            .collect();
+        let mut watching = false;
+        for dir in dirs {
+            match watcher.watch(&dir, RecursiveMode::NonRecursive) {
+                Ok(()) => watching = true,
+                Err(err) => {
+                    tracing::debug!("not watching {} for config changes: {err}", dir.display())
+                }
+            }
+        }
+        if !watching {
+            return None;
+        }
+
+        tokio::spawn(async move {
+            // The sender lives in the watcher callback, so this ends when the watcher is dropped.
+            while rx.recv().await.is_some() {
+                loop {
+                    match tokio::time::timeout(DEBOUNCE, rx.recv()).await {
+                        Ok(Some(())) => continue,
+                        Ok(None) => return,
+                        Err(_) => break,
+                    }
+                }
+                app_event_tx.send(AppEvent::ConfigFileChanged);
+            }
+        });
+        Some(Self { _watcher: watcher })
+    }
+}
+
+/// What changed between the running config and a reloaded one.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(crate) struct ConfigChanges {
+    /// Display settings that can be applied to the running session.
+    pub(crate) live: Vec<&'static str>,
+    /// Settings that need a new session, as `name: old → new`.
+    pub(crate) needs_new_session: Vec<String>,
+}
+
+pub(crate) fn diff_config(current: &Config, reloaded: &Config) -> ConfigChanges {
+    let mut changes = ConfigChanges::default();
+    let live = [
+        ("theme", current.tui_theme != reloaded.tui_theme),
+        (
+            "status line",
+            current.tui_status_line != reloaded.tui_status_line,
+        ),
+        (
+            "key bindings",
+            current.tui_interrupt_key != reloaded.tui_interrupt_key,
+        ),
+        (
+            "notifications",
+            current.tui_notifications != reloaded.tui_notifications
+                || current.tui_notification_method != reloaded.tui_notification_method,
+        ),
+        ("animations", current.animations != reloaded.animations),
+        ("tooltips", current.show_tooltips != reloaded.show_tooltips),
+    ];
+    changes.live = live
+        .into_iter()
+        .filter_map(|(name, changed)| changed.then_some(name))
+        .collect();
+
+    if current.model != reloaded.model {
+        changes.needs_new_session.push(format!(
+            "model: {} → {}",
+            current.model.as_deref().unwrap_or("default"),
+            reloaded.model.as_deref().unwrap_or("default"),
+        ));
+    }
+    if current.model_provider_id != reloaded.model_provider_id {
+        changes.needs_new_session.push(format!(
+            "model provider: {} → {}",
+            current.model_provider_id, reloaded.model_provider_id
+        ));
+    }
+    changes
+}
+
+/// Copy the settings [`diff_config`] reports as live from `from` into `to`.
+pub(crate) fn copy_live_settings(from: &Config, to: &mut Config) {
+    to.tui_theme = from.tui_theme.clone();
+    to.tui_status_line = from.tui_status_line.clone();
+    to.tui_interrupt_key = from.tui_interrupt_key.clone();
+    to.tui_notifications = from.tui_notifications.clone();
+    to.tui_notification_method = from.tui_notification_method;
+    to.animations = from.animations;
+    to.show_tooltips = from.show_tooltips;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_core::config::ConfigBuilder;
+    use pretty_assertions::assert_eq;
+    use tempfile::tempdir;
+
+    #[tokio::test]
+    async fn separates_live_settings_from_ones_needing_a_new_session() {
+        let codex_home = tempdir().expect("temp codex home");
+        let current = ConfigBuilder::default()
+            .codex_home(codex_home.path().to_path_buf())
+            .build()
+            .await
+            .expect("config");
+        let mut reloaded = current.clone();
+        assert_eq!(diff_config(&current, &reloaded), ConfigChanges::default());
+
+        reloaded.tui_theme = Some("dracula".to_string());
+        reloaded.tui_interrupt_key = Some("ctrl-g".to_string());
+        reloaded.model = Some("gpt-5.1-codex-mini".to_string());
+        let changes = diff_config(&current, &reloaded);
+        assert_eq!(changes.live, vec!["theme", "key bindings"]);
+        assert_eq!(changes.needs_new_session.len(), 1);
+        assert!(changes.needs_new_session[0].ends_with("→ gpt-5.1-codex-mini"));
+
+        let mut updated = current.clone();
+        copy_live_settings(&reloaded, &mut updated);
+        assert_eq!(updated.tui_theme.as_deref(), Some("dracula"));
+        assert_eq!(updated.model, current.model);
+    }
+}
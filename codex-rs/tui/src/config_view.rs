@@ -0,0 +1,377 @@
+//! The `/config` view.
+//!
+//! [`effective_config_lines`] renders the merged configuration with the layer each value comes
+//! from. [`config_editor_params`] lists a few display settings that can be changed inline; a
+//! change is written to the file the value currently comes from (see [`edit_target`]) and then
+//! picked up by the regular config reload.
+
+use std::path::Path;
+use std::path::PathBuf;
+
+use codex_app_server_protocol::ConfigLayerSource;
+use codex_core::config::CONFIG_TOML_FILE;
+use codex_core::config::Config;
+use codex_core::config::edit::ConfigEdit;
+use codex_core::config::types::Notifications;
+use codex_core::config_loader::ConfigLayerStack;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+
+use crate::app_event::AppEvent;
+use crate::bottom_pane::SelectionItem;
+use crate::bottom_pane::SelectionViewParams;
+use crate::bottom_pane::popup_consts::standard_popup_hint_line;
+use crate::debug_config::flatten_toml_key_values;
+use crate::debug_config::format_config_layer_source;
+use crate::render::renderable::ColumnRenderable;
+use crate::render::renderable::Renderable;
+
+/// A setting `/config` can change without leaving the TUI. Only settings the running session
+/// applies immediately are listed.
+pub(crate) struct EditableSetting {
+    pub(crate) key: &'static str,
+    description: &'static str,
+    choices: &'static [&'static str],
+    current: fn(&Config) -> String,
+}
+
+const BOOL_CHOICES: &[&str] = &["true", "false"];
+
+pub(crate) const EDITABLE_SETTINGS: &[EditableSetting] = &[
+    EditableSetting {
+        key: "tui.animations",
+        description: "Spinners and shimmer effects",
+        choices: BOOL_CHOICES,
+        current: |config| config.animations.to_string(),
+    },
+    EditableSetting {
+        key: "tui.notifications",
+        description: "Desktop notifications when a turn finishes or needs approval",
+        choices: BOOL_CHOICES,
+        current: |config| match &config.tui_notifications {
+            Notifications::Enabled(enabled) => enabled.to_string(),
+            Notifications::Custom(events) => format!("[{}]", events.join(", ")),
+        },
+    },
+    EditableSetting {
+        key: "tui.notification_method",
+        description: "How notifications are delivered",
+        choices: &["auto", "osc9", "bel", "tmux"],
+        current: |config| config.tui_notification_method.to_string(),
+    },
+    EditableSetting {
+        key: "tui.show_tooltips",
+        description: "Tips in the session header",
+        choices: BOOL_CHOICES,
+        current: |config| config.show_tooltips.to_string(),
+    },
+];
+
+pub(crate) fn editable_setting(key: &str) -> Option<&'static EditableSetting> {
+    EDITABLE_SETTINGS.iter().find(|setting| setting.key == key)
+}
+
+impl EditableSetting {
+    /// The edit that sets this setting to `choice`, one of its listed choices.
+    pub(crate) fn edit(&self, choice: &str) -> ConfigEdit {
+        let segments = self.key.split('.').map(ToString::to_string).collect();
+        let value = match choice.parse::<bool>() {
+            Ok(enabled) => enabled.into(),
+            Err(_) => choice.into(),
+        };
+        ConfigEdit::SetPath { segments, value }
+    }
+}
+
+/// Where `/config` writes an edit.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct EditTarget {
+    /// Directory whose `config.toml` is edited.
+    pub(crate) dir: PathBuf,
+    /// A higher-precedence source that will keep overriding the edited value.
+    pub(crate) overridden_by: Option<String>,
+}
+
+impl EditTarget {
+    pub(crate) fn file(&self) -> PathBuf {
+        self.dir.join(CONFIG_TOML_FILE)
+    }
+}
+
+/// Edit the project config when the value currently comes from one, and the user config
+/// otherwise. Values from session flags or managed config cannot be edited in place; the user
+/// config is still updated so the change sticks once that source no longer sets the key.
+pub(crate) fn edit_target(stack: &ConfigLayerStack, codex_home: &Path, key: &str) -> EditTarget {
+    let user_config = EditTarget {
+        dir: codex_home.to_path_buf(),
+        overridden_by: None,
+    };
+    let origins = stack.origins();
+    let Some(origin) = origins.get(key) else {
+        return user_config;
+    };
+    match &origin.name {
+        ConfigLayerSource::Project { dot_codex_folder } => EditTarget {
+            dir: dot_codex_folder.to_path_buf(),
+            overridden_by: None,
+        },
+        ConfigLayerSource::User { .. } | ConfigLayerSource::System { .. } => user_config,
+        source @ (ConfigLayerSource::SessionFlags
+        | ConfigLayerSource::Mdm { .. }
+        | ConfigLayerSource::LegacyManagedConfigTomlFromFile { .. }
+        | ConfigLayerSource::LegacyManagedConfigTomlFromMdm) => EditTarget {
+            overridden_by: Some(format_config_layer_source(source)),
+            ..user_config
+        },
+    }
+}
+
+/// The merged config, one `key = value` line per setting, each followed by its source.
+pub(crate) fn effective_config_lines(stack: &ConfigLayerStack) -> Vec<Line<'static>> {
+    let origins = stack.origins();
+    let mut values = Vec::new();
+    flatten_toml_key_values(&stack.effective_config(), None, &mut values);
+
+    let mut lines: Vec<Line<'static>> = vec![
+        "Effective configuration".bold().into(),
+        "Values set in no config file use their defaults and are not listed."
+            .dim()
+            .into(),
+        "".into(),
+    ];
+    if values.is_empty() {
+        lines.push("  <no settings>".dim().into());
+        return lines;
+    }
+    for (key, value) in values {
+        // Arrays are shown whole but their origins are recorded per element.
+        let source = origins
+            .get(&key)
+            .or_else(|| origins.get(&format!("{key}.0")))
+            .map(|origin| format_config_layer_source(&origin.name));
+        let value = if is_secret_key(&key) {
+            "<redacted>".to_string()
+        } else {
+            value
+        };
+        let mut line = Line::from(format!("{key} = {value}"));
+        if let Some(source) = source {
+            line.push_span(format!("  # {source}").dim());
+        }
+        lines.push(line);
+    }
+    lines
+}
+
+fn is_secret_key(key: &str) -> bool {
+    let name = key
+        .rsplit('.')
+        .next()
+        .unwrap_or(key)
+        .to_ascii_lowercase()
+        .replace('-', "_");
+    ["api_key", "token", "secret", "password", "authorization"]
+        .iter()
+        .any(|suffix| name.ends_with(suffix))
+}
+
+/// The `/config` popup: open the full view, or pick a setting to change.
+pub(crate) fn config_editor_params(config: &Config) -> SelectionViewParams {
+    let mut items = vec![SelectionItem {
+        name: "View effective config".to_string(),
+        description: Some("Every configured value and the file it comes from".to_string()),
+        actions: vec![Box::new(|tx| tx.send(AppEvent::OpenConfigOverlay))],
+        dismiss_on_select: true,
+        ..Default::default()
+    }];
+    for setting in EDITABLE_SETTINGS {
+        let key = setting.key;
+        items.push(SelectionItem {
+            name: format!("{key} = {}", (setting.current)(config)),
+            description: Some(setting.description.to_string()),
+            actions: vec![Box::new(move |tx| {
+                tx.send(AppEvent::OpenConfigSetting { key });
+            })],
+            dismiss_on_select: true,
+            ..Default::default()
+        });
+    }
+
+    let header: Vec<Box<dyn Renderable>> = vec![
+        Box::new(Line::from("Config".bold())),
+        Box::new(Line::from(
+            "Changes are saved to config.toml and applied right away.".dim(),
+        )),
+    ];
+    SelectionViewParams {
+        header: Box::new(ColumnRenderable::with(header)),
+        footer_hint: Some(standard_popup_hint_line()),
+        items,
+        ..Default::default()
+    }
+}
+
+/// The choices for one setting, noting which file the change will be written to.
+pub(crate) fn config_setting_params(
+    config: &Config,
+    setting: &'static EditableSetting,
+) -> SelectionViewParams {
+    let target = edit_target(&config.config_layer_stack, &config.codex_home, setting.key);
+    let current = (setting.current)(config);
+    let items = setting
+        .choices
+        .iter()
+        .map(|choice| {
+            let key = setting.key;
+            let value = choice.to_string();
+            SelectionItem {
+                name: value.clone(),
+                is_current: *choice == current,
+                actions: vec![Box::new(move |tx| {
+                    tx.send(AppEvent::SetConfigValue {
+                        key,
+                        value: value.clone(),
+                    });
+                })],
+                dismiss_on_select: true,
+                ..Default::default()
+            }
+        })
+        .collect();
+
+    let mut header: Vec<Box<dyn Renderable>> = vec![
+        Box::new(Line::from(setting.key.bold())),
+        Box::new(Line::from(
+            format!("Saved to {}", target.file().display()).dim(),
+        )),
+    ];
+    if let Some(source) = target.overridden_by {
+        header.push(Box::new(Line::from(
+            format!("Currently overridden by {source}.").magenta(),
+        )));
+    }
+    SelectionViewParams {
+        header: Box::new(ColumnRenderable::with(header)),
+        footer_hint: Some(standard_popup_hint_line()),
+        items,
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_core::config_loader::ConfigLayerEntry;
+    use codex_core::config_loader::ConfigRequirements;
+    use codex_core::config_loader::ConfigRequirementsToml;
+    use codex_utils_absolute_path::AbsolutePathBuf;
+    use pretty_assertions::assert_eq;
+    use toml::Value as TomlValue;
+
+    fn absolute_path(path: &str) -> AbsolutePathBuf {
+        AbsolutePathBuf::from_absolute_path(path).expect("absolute path")
+    }
+
+    fn layer(source: ConfigLayerSource, contents: &str) -> ConfigLayerEntry {
+        let config: TomlValue = toml::from_str(contents).expect("valid toml");
+        ConfigLayerEntry::new(source, config)
+    }
+
+    fn stack(layers: Vec<ConfigLayerEntry>) -> ConfigLayerStack {
+        ConfigLayerStack::new(
+            layers,
+            ConfigRequirements::default(),
+            ConfigRequirementsToml::default(),
+        )
+        .expect("config layer stack")
+    }
+
+    fn render(lines: &[Line<'static>]) -> Vec<String> {
+        lines
+            .iter()
+            .map(|line| {
+                line.spans
+                    .iter()
+                    .map(|span| span.content.as_ref())
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn effective_config_lists_each_value_with_its_source() {
+        let user_file = absolute_path("/home/me/.codex/config.toml");
+        let project = absolute_path("/repo/.codex");
+        let stack = stack(vec![
+            layer(
+                ConfigLayerSource::User {
+                    file: user_file.clone(),
+                },
+                "model = \"gpt-5\"\n[tui]\nanimations = false\nnotifications = [\"approval-requested\"]\n[model_providers.corp]\nexperimental_bearer_token = \"sk-123\"\n",
+            ),
+            layer(
+                ConfigLayerSource::Project {
+                    dot_codex_folder: project.clone(),
+                },
+                "model = \"gpt-5.1-codex\"\n",
+            ),
+        ]);
+
+        let user = format!("user ({})", user_file.as_path().display());
+        let project = format!("project ({}/config.toml)", project.as_path().display());
+        assert_eq!(
+            render(&effective_config_lines(&stack))[3..],
+            [
+                format!("model = \"gpt-5.1-codex\"  # {project}"),
+                format!("model_providers.corp.experimental_bearer_token = <redacted>  # {user}"),
+                format!("tui.animations = false  # {user}"),
+                format!("tui.notifications = [\"approval-requested\"]  # {user}"),
+            ]
+        );
+    }
+
+    #[test]
+    fn edits_go_to_the_file_the_value_comes_from() {
+        let codex_home = Path::new("/home/me/.codex");
+        let project = absolute_path("/repo/.codex");
+        let stack = stack(vec![
+            layer(
+                ConfigLayerSource::User {
+                    file: absolute_path("/home/me/.codex/config.toml"),
+                },
+                "[tui]\nanimations = false\n",
+            ),
+            layer(
+                ConfigLayerSource::Project {
+                    dot_codex_folder: project.clone(),
+                },
+                "[tui]\nnotification_method = \"bel\"\n",
+            ),
+            layer(
+                ConfigLayerSource::SessionFlags,
+                "[tui]\nshow_tooltips = false\n",
+            ),
+        ]);
+
+        let user = EditTarget {
+            dir: codex_home.to_path_buf(),
+            overridden_by: None,
+        };
+        assert_eq!(edit_target(&stack, codex_home, "tui.animations"), user);
+        assert_eq!(edit_target(&stack, codex_home, "tui.notifications"), user);
+        assert_eq!(
+            edit_target(&stack, codex_home, "tui.notification_method"),
+            EditTarget {
+                dir: project.to_path_buf(),
+                overridden_by: None,
+            }
+        );
+        assert_eq!(
+            edit_target(&stack, codex_home, "tui.show_tooltips"),
+            EditTarget {
+                dir: codex_home.to_path_buf(),
+                overridden_by: Some("session-flags".to_string()),
+            }
+        );
+    }
+}
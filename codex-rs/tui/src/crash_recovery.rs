@@ -0,0 +1,384 @@
+//! Crash recovery: save what the user would lose when the TUI panics and offer it back on the
+//! next start.
+//!
+//! While the app runs it keeps a [`RecoverySnapshot`] of the session's rollout path, the
+//! composer draft, and the approvals waiting for the user. The panic hook installed by
+//! [`install_panic_hook`] restores the terminal, flushes every thread's rollout, and writes the
+//! snapshot to `$CODEX_HOME/crash-recovery.json`. The next interactive start without a resume
+//! or fork request asks whether to recover the session ([`run_recovery_prompt`]).
+
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::PoisonError;
+use std::time::Duration;
+
+use codex_core::ThreadManager;
+use color_eyre::Result;
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEvent;
+use crossterm::event::KeyEventKind;
+use crossterm::event::KeyModifiers;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::prelude::Widget;
+use ratatui::style::Stylize as _;
+use ratatui::text::Line;
+use ratatui::widgets::Clear;
+use ratatui::widgets::Paragraph;
+use ratatui::widgets::WidgetRef;
+use ratatui::widgets::Wrap;
+use serde::Deserialize;
+use serde::Serialize;
+use tokio_stream::StreamExt;
+
+use crate::key_hint;
+use crate::render::Insets;
+use crate::render::renderable::ColumnRenderable;
+use crate::render::renderable::Renderable;
+use crate::render::renderable::RenderableExt as _;
+use crate::selection_list::selection_option_row;
+use crate::tui::FrameRequester;
+use crate::tui::Tui;
+use crate::tui::TuiEvent;
+
+const RECOVERY_FILE: &str = "crash-recovery.json";
+/// How long the panic hook waits for the rollouts to be flushed.
+const FLUSH_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// The state kept for the panic hook, refreshed after every event the app handles.
+static SNAPSHOT: Mutex<Option<RecoverySnapshot>> = Mutex::new(None);
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct RecoverySnapshot {
+    /// Rollout of the active session, if it is persisted.
+    pub(crate) rollout_path: Option<PathBuf>,
+    /// Text in the composer, including pending pastes.
+    pub(crate) composer_draft: String,
+    /// One line for each approval that was waiting for the user.
+    pub(crate) pending_approvals: Vec<String>,
+    /// The panic message; filled in by the panic hook.
+    #[serde(default)]
+    pub(crate) panic: String,
+}
+
+impl RecoverySnapshot {
+    /// Whether there is anything to offer back.
+    fn is_worth_recovering(&self) -> bool {
+        self.rollout_path.as_deref().is_some_and(Path::exists)
+            || !self.composer_draft.trim().is_empty()
+            || !self.pending_approvals.is_empty()
+    }
+
+    /// A startup warning listing the approvals that were lost with the crash.
+    pub(crate) fn pending_approvals_warning(&self) -> Option<String> {
+        if self.pending_approvals.is_empty() {
+            return None;
+        }
+        let list = self
+            .pending_approvals
+            .iter()
+            .map(|approval| format!("\n  - {approval}"))
+            .collect::<String>();
+        Some(format!(
+            "Codex crashed while these approvals were waiting; ask the agent to retry them:{list}"
+        ))
+    }
+}
+
+/// Replace the snapshot the panic hook will save.
+pub(crate) fn update(snapshot: RecoverySnapshot) {
+    *SNAPSHOT.lock().unwrap_or_else(PoisonError::into_inner) = Some(snapshot);
+}
+
+/// Install a panic hook that restores the terminal, flushes the rollouts of `thread_manager`,
+/// and saves the current snapshot under `codex_home` before the previous hook runs.
+pub(crate) fn install_panic_hook(codex_home: PathBuf, thread_manager: Arc<ThreadManager>) {
+    let runtime = tokio::runtime::Handle::current();
+    let prev_hook = std::panic::take_hook();
+    #[expect(
+        clippy::print_stderr,
+        reason = "The terminal has been restored, so the crash notice can go to stderr."
+    )]
+    let hook = move |info: &std::panic::PanicHookInfo<'_>| {
+        let _ = crate::tui::restore();
+        flush_rollouts(&runtime, &thread_manager);
+        let snapshot = SNAPSHOT
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone();
+        if let Some(mut snapshot) = snapshot {
+            snapshot.panic = info.to_string();
+            match save(&codex_home, &snapshot) {
+                Ok(path) => eprintln!(
+                    "Codex crashed. Your draft and session state were saved to {}; start codex \
+again to recover them.",
+                    path.display()
+                ),
+                Err(err) => eprintln!("Codex crashed and could not save a recovery file: {err}"),
+            }
+        }
+        prev_hook(info);
+    };
+    std::panic::set_hook(Box::new(hook));
+}
+
+/// Flush from a helper thread: the panicking thread may be a runtime worker, which cannot
+/// block on the runtime itself.
+fn flush_rollouts(runtime: &tokio::runtime::Handle, thread_manager: &Arc<ThreadManager>) {
+    let runtime = runtime.clone();
+    let thread_manager = Arc::clone(thread_manager);
+    let (done_tx, done_rx) = std::sync::mpsc::channel();
+    std::thread::spawn(move || {
+        runtime.block_on(thread_manager.flush_rollouts());
+        let _ = done_tx.send(());
+    });
+    let _ = done_rx.recv_timeout(FLUSH_TIMEOUT);
+}
+
+fn recovery_path(codex_home: &Path) -> PathBuf {
+    codex_home.join(RECOVERY_FILE)
+}
+
+fn save(codex_home: &Path, snapshot: &RecoverySnapshot) -> std::io::Result<PathBuf> {
+    let path = recovery_path(codex_home);
+    let json = serde_json::to_vec_pretty(snapshot).map_err(std::io::Error::other)?;
+    std::fs::write(&path, json)?;
+    Ok(path)
+}
+
+/// Remove and return the snapshot a crashed run left behind, if it has anything to recover.
+pub(crate) fn take(codex_home: &Path) -> Option<RecoverySnapshot> {
+    let path = recovery_path(codex_home);
+    let contents = std::fs::read(&path).ok()?;
+    if let Err(err) = std::fs::remove_file(&path) {
+        tracing::warn!("failed to remove {}: {err}", path.display());
+    }
+    match serde_json::from_slice::<RecoverySnapshot>(&contents) {
+        Ok(snapshot) => snapshot.is_worth_recovering().then_some(snapshot),
+        Err(err) => {
+            tracing::warn!("ignoring unreadable {}: {err}", path.display());
+            None
+        }
+    }
+}
+
+/// Remove the recovery file after a clean exit, so a panic in a background task that the app
+/// survived is not offered for recovery.
+pub(crate) fn discard(codex_home: &Path) {
+    let _ = std::fs::remove_file(recovery_path(codex_home));
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum RecoveryChoice {
+    Recover,
+    StartFresh,
+    Exit,
+}
+
+/// Ask whether to recover the crashed session described by `snapshot`.
+pub(crate) async fn run_recovery_prompt(
+    tui: &mut Tui,
+    snapshot: &RecoverySnapshot,
+) -> Result<RecoveryChoice> {
+    let mut screen = RecoveryPromptScreen::new(tui.frame_requester(), snapshot.clone());
+    tui.draw(u16::MAX, |frame| {
+        frame.render_widget_ref(&screen, frame.area());
+    })?;
+
+    let events = tui.event_stream();
+    tokio::pin!(events);
+
+    while screen.choice.is_none() {
+        if let Some(event) = events.next().await {
+            match event {
+                TuiEvent::Key(key_event) => screen.handle_key(key_event),
+                TuiEvent::Paste(_) => {}
+                TuiEvent::Draw => {
+                    tui.draw(u16::MAX, |frame| {
+                        frame.render_widget_ref(&screen, frame.area());
+                    })?;
+                }
+            }
+        } else {
+            break;
+        }
+    }
+
+    Ok(screen.choice.unwrap_or(RecoveryChoice::StartFresh))
+}
+
+struct RecoveryPromptScreen {
+    request_frame: FrameRequester,
+    snapshot: RecoverySnapshot,
+    recover_highlighted: bool,
+    choice: Option<RecoveryChoice>,
+}
+
+impl RecoveryPromptScreen {
+    fn new(request_frame: FrameRequester, snapshot: RecoverySnapshot) -> Self {
+        Self {
+            request_frame,
+            snapshot,
+            recover_highlighted: true,
+            choice: None,
+        }
+    }
+
+    fn handle_key(&mut self, key_event: KeyEvent) {
+        if key_event.kind == KeyEventKind::Release {
+            return;
+        }
+        if key_event.modifiers.contains(KeyModifiers::CONTROL)
+            && matches!(key_event.code, KeyCode::Char('c') | KeyCode::Char('d'))
+        {
+            self.choose(RecoveryChoice::Exit);
+            return;
+        }
+        match key_event.code {
+            KeyCode::Up | KeyCode::Down | KeyCode::Char('k') | KeyCode::Char('j') => {
+                self.recover_highlighted = !self.recover_highlighted;
+                self.request_frame.schedule_frame();
+            }
+            KeyCode::Char('1') | KeyCode::Char('y') => self.choose(RecoveryChoice::Recover),
+            KeyCode::Char('2') | KeyCode::Char('n') | KeyCode::Esc => {
+                self.choose(RecoveryChoice::StartFresh)
+            }
+            KeyCode::Enter => self.choose(if self.recover_highlighted {
+                RecoveryChoice::Recover
+            } else {
+                RecoveryChoice::StartFresh
+            }),
+            _ => {}
+        }
+    }
+
+    fn choose(&mut self, choice: RecoveryChoice) {
+        self.choice = Some(choice);
+        self.request_frame.schedule_frame();
+    }
+}
+
+impl WidgetRef for &RecoveryPromptScreen {
+    fn render_ref(&self, area: Rect, buf: &mut Buffer) {
+        Clear.render(area, buf);
+        let mut column = ColumnRenderable::new();
+        let snapshot = &self.snapshot;
+
+        column.push("");
+        column.push(Line::from(vec![
+            "Codex crashed last time. ".into(),
+            "Recover the previous session?".bold(),
+        ]));
+        column.push("");
+        // The panic message starts with its location; the message itself follows on the next
+        // line.
+        let panic = snapshot.panic.lines().take(2).collect::<Vec<_>>().join(" ");
+        if !panic.is_empty() {
+            column.push(
+                Paragraph::new(Line::from(panic.dim()))
+                    .wrap(Wrap { trim: false })
+                    .inset(Insets::tlbr(0, 2, 0, 0)),
+            );
+        }
+        if snapshot.rollout_path.is_some() {
+            column.push(
+                Line::from("The conversation will be resumed from its saved history.".dim())
+                    .inset(Insets::tlbr(0, 2, 0, 0)),
+            );
+        }
+        let draft = snapshot.composer_draft.trim();
+        if !draft.is_empty() {
+            column.push(
+                Paragraph::new(Line::from(vec!["Draft: ".dim(), draft.to_string().into()]))
+                    .wrap(Wrap { trim: false })
+                    .inset(Insets::tlbr(0, 2, 0, 0)),
+            );
+        }
+        if !snapshot.pending_approvals.is_empty() {
+            column.push(
+                Line::from(
+                    format!(
+                        "{} approval(s) were waiting and will be listed for you to retry.",
+                        snapshot.pending_approvals.len()
+                    )
+                    .dim(),
+                )
+                .inset(Insets::tlbr(0, 2, 0, 0)),
+            );
+        }
+        column.push("");
+        column.push(selection_option_row(
+            0,
+            "Recover the previous session".to_string(),
+            self.recover_highlighted,
+        ));
+        column.push(selection_option_row(
+            1,
+            "Start a new session".to_string(),
+            !self.recover_highlighted,
+        ));
+        column.push("");
+        column.push(
+            Line::from(vec![
+                "Press ".dim(),
+                key_hint::plain(KeyCode::Enter).into(),
+                " to continue".dim(),
+            ])
+            .inset(Insets::tlbr(0, 2, 0, 0)),
+        );
+        column.render(area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    fn snapshot() -> RecoverySnapshot {
+        RecoverySnapshot {
+            rollout_path: None,
+            composer_draft: "half-written prompt".to_string(),
+            pending_approvals: vec!["run `cargo test`".to_string()],
+            panic: "panicked at src/app.rs:1:1".to_string(),
+        }
+    }
+
+    #[test]
+    fn saved_snapshots_are_taken_once() {
+        let codex_home = TempDir::new().expect("tempdir");
+        save(codex_home.path(), &snapshot()).expect("save");
+
+        assert_eq!(take(codex_home.path()), Some(snapshot()));
+        assert_eq!(take(codex_home.path()), None);
+    }
+
+    #[test]
+    fn empty_snapshots_are_not_offered() {
+        let codex_home = TempDir::new().expect("tempdir");
+        save(
+            codex_home.path(),
+            &RecoverySnapshot {
+                rollout_path: Some(codex_home.path().join("missing.jsonl")),
+                ..RecoverySnapshot::default()
+            },
+        )
+        .expect("save");
+
+        assert_eq!(take(codex_home.path()), None);
+    }
+
+    #[test]
+    fn prompt_recovers_by_default_and_starts_fresh_on_esc() {
+        let mut screen = RecoveryPromptScreen::new(FrameRequester::test_dummy(), snapshot());
+        screen.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert_eq!(screen.choice, Some(RecoveryChoice::Recover));
+
+        let mut screen = RecoveryPromptScreen::new(FrameRequester::test_dummy(), snapshot());
+        screen.handle_key(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+        assert_eq!(screen.choice, Some(RecoveryChoice::StartFresh));
+    }
+}
@@ -578,6 +578,7 @@ where
     let mut bg = Color::Reset;
     let mut modifier = Modifier::empty();
     let mut last_pos: Option<Position> = None;
+    let capabilities = crate::terminal_capabilities::get();
     for command in commands {
         let (x, y) = match command {
             DrawCommand::Put { x, y, .. } => (x, y),
@@ -601,18 +602,24 @@ where
                 if cell.fg != fg || cell.bg != bg {
                     queue!(
                         writer,
-                        SetColors(Colors::new(cell.fg.into(), cell.bg.into()))
+                        SetColors(Colors::new(
+                            capabilities.adapt_color(cell.fg).into(),
+                            capabilities.adapt_color(cell.bg).into()
+                        ))
                     )?;
                     fg = cell.fg;
                     bg = cell.bg;
                 }
 
-                queue!(writer, Print(cell.symbol()))?;
+                queue!(writer, Print(capabilities.adapt_text(cell.symbol())))?;
             }
             DrawCommand::ClearToEnd { bg: clear_bg, .. } => {
                 queue!(writer, SetAttribute(crossterm::style::Attribute::Reset))?;
                 modifier = Modifier::empty();
-                queue!(writer, SetBackgroundColor(clear_bg.into()))?;
+                queue!(
+                    writer,
+                    SetBackgroundColor(capabilities.adapt_color(clear_bg).into())
+                )?;
                 bg = clear_bg;
                 queue!(writer, Clear(crossterm::terminal::ClearType::UntilNewLine))?;
             }
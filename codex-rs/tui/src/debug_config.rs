@@ -1,5 +1,7 @@
 use crate::history_cell::PlainHistoryCell;
+use crate::status::format_tokens_compact;
 use codex_app_server_protocol::ConfigLayerSource;
+use codex_core::NextTurnContext;
 use codex_core::config::Config;
 use codex_core::config_loader::ConfigLayerEntry;
 use codex_core::config_loader::ConfigLayerStack;
@@ -46,6 +48,111 @@ pub(crate) fn new_debug_config_output(
     PlainHistoryCell::new(lines)
 }
 
+/// Lines for the `/debug-context` overlay: a per-section token summary, then every item as it
+/// will be sent, each with its own token estimate.
+pub(crate) fn debug_context_lines(context: &NextTurnContext) -> Vec<Line<'static>> {
+    let total = context.total_tokens();
+    let mut lines = vec!["/debug-context".magenta().into(), "".into()];
+    let mut heading = format!("Next request: ~{} tokens", format_tokens_compact(total));
+    if let Some(window) = context.context_window.filter(|window| *window > 0) {
+        heading.push_str(&format!(
+            " of {} context window ({}%)",
+            format_tokens_compact(window),
+            total.saturating_mul(100) / window
+        ));
+    }
+    lines.push(heading.bold().into());
+    for section in &context.sections {
+        let items = section.entries.len();
+        lines.push(Line::from(vec![
+            format!("  {:<22}", section.kind.label()).into(),
+            format!("~{:>6} tokens", format_tokens_compact(section.tokens())).cyan(),
+            format!("  {items} item{}", if items == 1 { "" } else { "s" }).dim(),
+        ]));
+    }
+    let largest = context.trimmable_entries();
+    if let Some(largest) = largest.first() {
+        lines.push(
+            format!(
+                "Largest tool output: [{}] ~{} tokens. Use /trim to remove tool outputs.",
+                largest.heading,
+                format_tokens_compact(largest.tokens)
+            )
+            .dim()
+            .into(),
+        );
+    }
+    lines.push(
+        "Token counts are estimates; tool definitions are not included."
+            .dim()
+            .into(),
+    );
+
+    for section in &context.sections {
+        lines.push("".into());
+        lines.push(
+            format!(
+                "── {} (~{} tokens) ──",
+                section.kind.label(),
+                format_tokens_compact(section.tokens())
+            )
+            .bold()
+            .into(),
+        );
+        for entry in &section.entries {
+            lines.push(Line::from(vec![
+                format!("[{}]", entry.heading).cyan(),
+                format!(" ~{} tokens", format_tokens_compact(entry.tokens)).dim(),
+            ]));
+            lines.extend(entry.text.lines().map(|line| Line::from(line.to_string())));
+        }
+    }
+    lines
+}
+
+/// Lines for the `/debug-env` overlay: the environment agent commands start with.
+pub(crate) fn debug_env_lines(config: &Config) -> Vec<Line<'static>> {
+    let policy = &config.shell_environment_policy;
+    let mut lines = vec!["/debug-env".magenta().into(), "".into()];
+    lines.push(
+        format!("inherit: {:?}", policy.inherit)
+            .to_lowercase()
+            .into(),
+    );
+    if let Some(secrets_file) = &policy.secrets_file {
+        lines.push(format!("secrets file: {}", secrets_file.display()).into());
+    }
+    lines.push("".into());
+
+    let env = codex_core::exec_env::describe_env(policy);
+    lines.push(
+        format!("Environment for agent commands ({} variables):", env.len())
+            .bold()
+            .into(),
+    );
+    if env.is_empty() {
+        lines.push("  <none>".dim().into());
+    }
+    for (name, value) in env {
+        let value = if value == codex_core::exec_env::REDACTED_ENV_VALUE {
+            value.dim()
+        } else {
+            value.into()
+        };
+        lines.push(Line::from(vec!["  ".into(), name.cyan(), "=".dim(), value]));
+    }
+    lines.push("".into());
+    lines.push(
+        format!(
+            "Each command also receives {}.",
+            codex_core::exec_env::CODEX_THREAD_ID_ENV_VAR
+        )
+        .dim()
+        .into(),
+    );
+    lines
+}
+
 fn session_all_proxy_url(http_addr: &str, socks_addr: &str, socks_enabled: bool) -> String {
     if socks_enabled {
         format!("socks5h://{socks_addr}")
@@ -218,7 +325,7 @@ fn render_mdm_layer_details(layer: &ConfigLayerEntry) -> Vec<Line<'static>> {
     }
 }
 
-fn flatten_toml_key_values(
+pub(crate) fn flatten_toml_key_values(
     value: &TomlValue,
     prefix: Option<&str>,
     out: &mut Vec<(String, String)>,
@@ -280,7 +387,7 @@ fn normalize_allowed_web_search_modes(
     normalized
 }
 
-fn format_config_layer_source(source: &ConfigLayerSource) -> String {
+pub(crate) fn format_config_layer_source(source: &ConfigLayerSource) -> String {
     match source {
         ConfigLayerSource::Mdm { domain, key } => {
             format!("MDM ({domain}:{key})")
@@ -387,9 +494,14 @@ fn format_network_constraints(network: &NetworkConstraints) -> String {
 
 #[cfg(test)]
 mod tests {
+    use super::debug_context_lines;
     use super::render_debug_config_lines;
     use super::session_all_proxy_url;
     use codex_app_server_protocol::ConfigLayerSource;
+    use codex_core::ContextEntry;
+    use codex_core::ContextSection;
+    use codex_core::ContextSectionKind;
+    use codex_core::NextTurnContext;
     use codex_core::config::Constrained;
     use codex_core::config_loader::ConfigLayerEntry;
     use codex_core::config_loader::ConfigLayerStack;
@@ -684,4 +796,39 @@ approval_policy = "never"
             "http://127.0.0.1:3128".to_string()
         );
     }
+
+    #[test]
+    fn debug_context_lines_summarize_sections_then_show_each_item() {
+        let entry = |heading: &str, text: &str, tokens: i64, call_id: Option<&str>| ContextEntry {
+            heading: heading.to_string(),
+            text: text.to_string(),
+            tokens,
+            trimmable_call_id: call_id.map(str::to_string),
+        };
+        let context = NextTurnContext {
+            sections: vec![
+                ContextSection {
+                    kind: ContextSectionKind::Instructions,
+                    entries: vec![entry("instructions", "You are Codex.", 1_200, None)],
+                },
+                ContextSection {
+                    kind: ContextSectionKind::RecentTurns,
+                    entries: vec![
+                        entry("user", "run the tests", 10, None),
+                        entry("tool output: shell", "test result: ok", 790, Some("call-1")),
+                    ],
+                },
+            ],
+            context_window: Some(10_000),
+        };
+
+        let rendered = render_to_text(&debug_context_lines(&context));
+        assert!(rendered.contains("Next request: ~2K tokens of 10K context window (20%)"));
+        assert!(rendered.contains("Largest tool output: [tool output: shell] ~790 tokens"));
+        assert!(rendered.contains(
+            "── Instructions (~1.2K tokens) ──\n[instructions] ~1.2K tokens\nYou are Codex."
+        ));
+        assert!(rendered.ends_with("[tool output: shell] ~790 tokens\ntest result: ok"));
+        insta::assert_snapshot!(rendered);
+    }
 }
@@ -0,0 +1,246 @@
+//! Split layout: a live workspace diff to the right of the conversation (`/split`).
+//!
+//! The pane shows `git diff` for the working tree (tracked changes plus untracked files) and
+//! is refreshed whenever the agent finishes a patch or a command, so edits appear while the
+//! conversation continues. Only one refresh runs at a time; requests that arrive meanwhile
+//! are folded into a single follow-up refresh. A review of an existing diff pins that diff
+//! in the pane instead, and workspace refreshes are skipped until the pane is closed.
+
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Style;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::widgets::Widget;
+
+use crate::line_truncation::truncate_line_with_ellipsis_if_overflow;
+use crate::render::renderable::Renderable;
+
+/// The pane is hidden when the terminal is narrower than this.
+const MIN_TERMINAL_WIDTH: u16 = 100;
+/// The viewport grows to at least this many rows while the pane is shown.
+const MIN_PANE_HEIGHT: u16 = 20;
+
+#[derive(Default)]
+pub(crate) struct DiffPane {
+    visible: bool,
+    /// `None` until the first refresh completes.
+    lines: Option<Vec<Line<'static>>>,
+    refreshing: bool,
+    stale: bool,
+    /// Title of a pinned diff; `None` while the pane follows the workspace.
+    pinned: Option<String>,
+}
+
+impl DiffPane {
+    /// Toggle the pane; returns true when it was just shown and needs a refresh.
+    pub(crate) fn toggle(&mut self) -> bool {
+        self.visible = !self.visible;
+        if !self.visible && self.pinned.take().is_some() {
+            // Closing the pane drops the pin, so reopening it follows the workspace again.
+            self.lines = None;
+        }
+        self.visible
+    }
+
+    /// Width of the pane for a chat area `width` columns wide, if it is shown.
+    pub(crate) fn width_for(&self, width: u16) -> Option<u16> {
+        (self.visible && width >= MIN_TERMINAL_WIDTH).then_some(width / 2)
+    }
+
+    /// Whether a refresh should start now. When one is already running, remember to run
+    /// another once it finishes.
+    pub(crate) fn begin_refresh(&mut self) -> bool {
+        if !self.visible || self.pinned.is_some() {
+            return false;
+        }
+        if self.refreshing {
+            self.stale = true;
+            return false;
+        }
+        self.refreshing = true;
+        true
+    }
+
+    /// Show `diff` under `title` in place of the workspace diff, opening the pane.
+    pub(crate) fn pin(&mut self, title: String, diff: &str) {
+        self.visible = true;
+        self.pinned = Some(title);
+        self.lines = Some(
+            diff.lines()
+                .map(codex_ansi_escape::ansi_escape_line)
+                .collect(),
+        );
+    }
+
+    /// Store a finished refresh; returns true when another refresh was requested meanwhile.
+    pub(crate) fn finish_refresh(&mut self, result: Result<(bool, String), String>) -> bool {
+        self.refreshing = false;
+        self.lines = Some(match result {
+            Ok((true, diff)) if diff.trim().is_empty() => {
+                vec!["No changes in the workspace.".italic().into()]
+            }
+            Ok((true, diff)) => diff
+                .lines()
+                .map(codex_ansi_escape::ansi_escape_line)
+                .collect(),
+            Ok((false, _)) => vec!["Not inside a git repository.".italic().into()],
+            Err(err) => vec![format!("Failed to compute diff: {err}").red().into()],
+        });
+        std::mem::take(&mut self.stale) && self.visible
+    }
+}
+
+impl Renderable for DiffPane {
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        if area.width < 3 || area.height == 0 {
+            return;
+        }
+        for y in area.y..area.y + area.height {
+            buf[(area.x, y)]
+                .set_symbol("│")
+                .set_style(Style::default().dim());
+        }
+        let inner_width = area.width - 2;
+        let x = area.x + 2;
+        let title = match &self.pinned {
+            Some(pinned) => Line::from(pinned.clone().bold()),
+            None if self.refreshing => {
+                Line::from(vec!["Workspace diff".bold(), " updating…".dim()])
+            }
+            None => Line::from("Workspace diff".bold()),
+        };
+        title.render(Rect::new(x, area.y, inner_width, 1), buf);
+
+        let list_height = area.height.saturating_sub(1) as usize;
+        let lines = self.lines.as_deref().unwrap_or_default();
+        let overflow = lines.len() > list_height;
+        let shown = if overflow {
+            list_height.saturating_sub(1)
+        } else {
+            lines.len()
+        };
+        for (offset, line) in lines.iter().take(shown).enumerate() {
+            truncate_line_with_ellipsis_if_overflow(line.clone(), inner_width as usize).render(
+                Rect::new(x, area.y + 1 + offset as u16, inner_width, 1),
+                buf,
+            );
+        }
+        if overflow {
+            let hidden = lines.len() - shown;
+            let note = if self.pinned.is_some() {
+                Line::from(format!("… {hidden} more lines").dim())
+            } else {
+                Line::from(format!("… {hidden} more lines (/diff for the full diff)").dim())
+            };
+            truncate_line_with_ellipsis_if_overflow(note, inner_width as usize)
+                .render(Rect::new(x, area.y + 1 + shown as u16, inner_width, 1), buf);
+        }
+    }
+
+    fn desired_height(&self, _width: u16) -> u16 {
+        MIN_PANE_HEIGHT
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use insta::assert_snapshot;
+    use pretty_assertions::assert_eq;
+    use ratatui::Terminal;
+    use ratatui::backend::TestBackend;
+
+    fn render_lines(pane: &DiffPane, width: u16, height: u16) -> Vec<String> {
+        let area = Rect::new(0, 0, width, height);
+        let mut buf = Buffer::empty(area);
+        pane.render(area, &mut buf);
+        (0..height)
+            .map(|y| {
+                (0..width)
+                    .map(|x| buf[(x, y)].symbol())
+                    .collect::<String>()
+                    .trim_end()
+                    .to_string()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn refreshes_are_coalesced_while_one_is_running() {
+        let mut pane = DiffPane::default();
+        assert!(!pane.begin_refresh());
+        assert!(pane.toggle());
+        assert!(pane.begin_refresh());
+        assert!(!pane.begin_refresh());
+        assert!(!pane.begin_refresh());
+
+        assert!(pane.finish_refresh(Ok((true, String::new()))));
+        assert!(pane.begin_refresh());
+        assert!(!pane.finish_refresh(Ok((true, String::new()))));
+    }
+
+    #[test]
+    fn workspace_diff_pane_while_updating_snapshot() {
+        let mut pane = DiffPane::default();
+        pane.toggle();
+        pane.begin_refresh();
+        pane.finish_refresh(Ok((
+            true,
+            "diff --git a/src/lib.rs b/src/lib.rs\n@@ -1 +1 @@\n-fn old() {}\n+fn new() {}"
+                .to_string(),
+        )));
+        pane.begin_refresh();
+
+        let mut terminal = Terminal::new(TestBackend::new(40, 6)).expect("terminal");
+        terminal
+            .draw(|f| pane.render(f.area(), f.buffer_mut()))
+            .expect("draw");
+        assert_snapshot!(terminal.backend());
+    }
+
+    #[test]
+    fn pinned_diffs_replace_the_workspace_diff() {
+        let mut pane = DiffPane::default();
+        pane.pin(
+            "Reviewing fix.patch".to_string(),
+            "diff --git a/a.rs b/a.rs\n-old\n+new",
+        );
+
+        assert!(!pane.begin_refresh());
+        assert_eq!(
+            render_lines(&pane, 50, 4),
+            vec![
+                "│ Reviewing fix.patch".to_string(),
+                "│ diff --git a/a.rs b/a.rs".to_string(),
+                "│ -old".to_string(),
+                "│ +new".to_string(),
+            ]
+        );
+
+        assert!(!pane.toggle());
+        assert!(pane.toggle());
+        assert!(pane.begin_refresh());
+    }
+
+    #[test]
+    fn long_diffs_end_with_an_overflow_note() {
+        let mut pane = DiffPane::default();
+        pane.toggle();
+        pane.begin_refresh();
+        pane.finish_refresh(Ok((
+            true,
+            "diff --git a/a.rs b/a.rs\n-old\n+new\n+more".to_string(),
+        )));
+
+        assert_eq!(
+            render_lines(&pane, 50, 4),
+            vec![
+                "│ Workspace diff".to_string(),
+                "│ diff --git a/a.rs b/a.rs".to_string(),
+                "│ -old".to_string(),
+                "│ … 2 more lines (/diff for the full diff)".to_string(),
+            ]
+        );
+    }
+}
@@ -20,6 +20,16 @@ pub(crate) struct CommandOutput {
     pub(crate) formatted_output: String,
 }
 
+impl CommandOutput {
+    /// Whether the model was shown a truncated copy of the output.
+    ///
+    /// Core prefixes truncated output with a total line count, and that prefix survives in the
+    /// rollout, so this holds for resumed sessions as well as live ones.
+    pub(crate) fn is_truncated_for_model(&self) -> bool {
+        self.formatted_output.starts_with("Total output lines: ")
+    }
+}
+
 #[derive(Debug, Clone)]
 pub(crate) struct ExecCall {
     pub(crate) call_id: String,
@@ -30,6 +40,8 @@ pub(crate) struct ExecCall {
     pub(crate) start_time: Option<Instant>,
     pub(crate) duration: Option<Duration>,
     pub(crate) interaction_input: Option<String>,
+    /// Working directory to show next to the command when it differs from the session cwd.
+    pub(crate) cwd: Option<String>,
 }
 
 #[derive(Debug)]
@@ -63,6 +75,7 @@ impl ExecCell {
             start_time: Some(Instant::now()),
             duration: None,
             interaction_input,
+            cwd: None,
         };
         if self.is_exploring_cell() && Self::is_exploring_call(&call) {
             Some(Self {
@@ -74,6 +87,14 @@ impl ExecCell {
         }
     }
 
+    /// Show `cwd` next to the most recently added call.
+    pub(crate) fn with_cwd(mut self, cwd: Option<String>) -> Self {
+        if let Some(call) = self.calls.last_mut() {
+            call.cwd = cwd;
+        }
+        self
+    }
+
     /// Marks the most recently matching call as finished and returns whether a call was found.
     ///
     /// Callers should treat `false` as a routing mismatch rather than silently ignoring it. The
@@ -1,3 +1,4 @@
+use std::time::Duration;
 use std::time::Instant;
 
 use super::model::CommandOutput;
@@ -9,6 +10,7 @@ use crate::render::highlight::highlight_bash_to_lines;
 use crate::render::line_utils::prefix_lines;
 use crate::render::line_utils::push_owned_lines;
 use crate::shimmer::shimmer_spans;
+use crate::ui_consts::is_narrow_layout;
 use crate::wrapping::RtOptions;
 use crate::wrapping::adaptive_wrap_line;
 use crate::wrapping::adaptive_wrap_lines;
@@ -29,6 +31,9 @@ use unicode_width::UnicodeWidthStr;
 pub(crate) const TOOL_CALL_MAX_LINES: usize = 5;
 const USER_SHELL_TOOL_CALL_MAX_LINES: usize = 50;
 const MAX_INTERACTION_PREVIEW_CHARS: usize = 80;
+/// Commands faster than this do not get a duration badge; they are the common case and the
+/// badge would only add noise.
+const DURATION_BADGE_MIN: Duration = Duration::from_secs(1);
 
 pub(crate) struct OutputLinesParams {
     pub(crate) line_limit: usize,
@@ -55,6 +60,7 @@ pub(crate) fn new_active_exec_command(
             start_time: Some(Instant::now()),
             duration: None,
             interaction_input,
+            cwd: None,
         },
         animations_enabled,
     )
@@ -140,9 +146,7 @@ pub(crate) fn output_lines(
             "    "
         };
         line.spans.insert(0, prefix.into());
-        line.spans.iter_mut().for_each(|span| {
-            span.style = span.style.add_modifier(Modifier::DIM);
-        });
+        dim_uncolored_spans(&mut line);
         out.push(line);
     }
 
@@ -167,9 +171,7 @@ pub(crate) fn output_lines(
         if include_prefix {
             line.spans.insert(0, "    ".into());
         }
-        line.spans.iter_mut().for_each(|span| {
-            span.style = span.style.add_modifier(Modifier::DIM);
-        });
+        dim_uncolored_spans(&mut line);
         out.push(line);
     }
 
@@ -179,6 +181,16 @@ pub(crate) fn output_lines(
     }
 }
 
+/// Dim plain output so it recedes behind the command, but keep colors the command chose
+/// (compiler diagnostics, test results) at full strength.
+fn dim_uncolored_spans(line: &mut Line<'static>) {
+    for span in &mut line.spans {
+        if matches!(span.style.fg, None | Some(Color::Reset)) {
+            span.style = span.style.add_modifier(Modifier::DIM);
+        }
+    }
+}
+
 pub(crate) fn spinner(start_time: Option<Instant>, animations_enabled: bool) -> Span<'static> {
     if !animations_enabled {
         return "•".dim();
@@ -242,6 +254,9 @@ impl HistoryCell for ExecCell {
                     ])
                 };
                 result.push_span(format!(" • {duration}").dim());
+                if output.is_truncated_for_model() {
+                    result.push_span(" • truncated".dim());
+                }
                 lines.push(result);
             }
         }
@@ -349,7 +364,12 @@ impl ExecCell {
             }
         }
 
-        out.extend(prefix_lines(out_indented, "  └ ".dim(), "    ".into()));
+        let block = exec_display_layout(width).output_block;
+        out.extend(prefix_lines(
+            out_indented,
+            block.initial_prefix.dim(),
+            block.subsequent_prefix.into(),
+        ));
         out
     }
 
@@ -357,7 +377,7 @@ impl ExecCell {
         let [call] = &self.calls.as_slice() else {
             panic!("Expected exactly one call in a command display cell");
         };
-        let layout = EXEC_DISPLAY_LAYOUT;
+        let layout = exec_display_layout(width);
         let success = call.output.as_ref().map(|o| o.exit_code == 0);
         let bullet = match success {
             Some(true) => "•".green().bold(),
@@ -416,12 +436,27 @@ impl ExecCell {
             }
         }
 
-        let mut lines: Vec<Line<'static>> = vec![header_line];
-
-        let continuation_lines = Self::limit_lines_from_start(
+        let mut continuation_lines = Self::limit_lines_from_start(
             &continuation_lines,
             layout.command_continuation_max_lines,
         );
+
+        let badges = Self::header_badges(call);
+        if !badges.is_empty() {
+            let badges_width: usize = badges.iter().map(Span::width).sum();
+            if header_line.width() + badges_width <= width as usize {
+                header_line.extend(badges);
+            } else {
+                // Drop the leading separator when the badges start their own line.
+                let badge_line = Line::from(badges.into_iter().skip(1).collect::<Vec<_>>());
+                push_owned_lines(
+                    &adaptive_wrap_line(&badge_line, continuation_opts),
+                    &mut continuation_lines,
+                );
+            }
+        }
+
+        let mut lines: Vec<Line<'static>> = vec![header_line];
         if !continuation_lines.is_empty() {
             lines.extend(prefix_lines(
                 continuation_lines,
@@ -498,6 +533,33 @@ impl ExecCell {
         lines
     }
 
+    /// Badges after the command: the working directory when it is not the session's, then,
+    /// once finished, a non-zero exit code, a duration worth noticing, and whether the model
+    /// saw truncated output. Each badge is preceded by a separator span.
+    fn header_badges(call: &ExecCall) -> Vec<Span<'static>> {
+        let mut badges = Vec::new();
+        if let Some(cwd) = &call.cwd {
+            badges.push(format!("in {cwd}").dim());
+        }
+        if let (Some(output), Some(duration)) = (call.output.as_ref(), call.duration)
+            && !call.is_unified_exec_interaction()
+        {
+            if output.exit_code != 0 {
+                badges.push(format!("exit {}", output.exit_code).red());
+            }
+            if duration >= DURATION_BADGE_MIN {
+                badges.push(format_duration(duration).dim());
+            }
+            if output.is_truncated_for_model() {
+                badges.push("truncated".dim());
+            }
+        }
+        badges
+            .into_iter()
+            .flat_map(|badge| [" · ".dim(), badge])
+            .collect()
+    }
+
     fn limit_lines_from_start(lines: &[Line<'static>], keep: usize) -> Vec<Line<'static>> {
         if lines.len() <= keep {
             return lines.to_vec();
@@ -686,6 +748,22 @@ const EXEC_DISPLAY_LAYOUT: ExecDisplayLayout = ExecDisplayLayout::new(
     5,
 );
 
+/// The same layout with a two-column narrower gutter, for narrow terminals.
+const NARROW_EXEC_DISPLAY_LAYOUT: ExecDisplayLayout = ExecDisplayLayout::new(
+    PrefixedBlock::new("│ ", "│ "),
+    2,
+    PrefixedBlock::new("└ ", "  "),
+    5,
+);
+
+fn exec_display_layout(width: u16) -> ExecDisplayLayout {
+    if is_narrow_layout(width) {
+        NARROW_EXEC_DISPLAY_LAYOUT
+    } else {
+        EXEC_DISPLAY_LAYOUT
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -708,7 +786,7 @@ mod tests {
             formatted_output: String::new(),
         };
         let width = 20;
-        let layout = EXEC_DISPLAY_LAYOUT;
+        let layout = exec_display_layout(width);
         let raw_output = output_lines(
             Some(&output),
             OutputLinesParams {
@@ -756,6 +834,7 @@ mod tests {
             start_time: None,
             duration: None,
             interaction_input: None,
+            cwd: None,
         };
 
         let cell = ExecCell::new(call, false);
@@ -838,6 +917,7 @@ mod tests {
             start_time: None,
             duration: None,
             interaction_input: None,
+            cwd: None,
         };
 
         let cell = ExecCell::new(call, false);
@@ -859,6 +939,105 @@ mod tests {
         );
     }
 
+    #[test]
+    fn finished_command_shows_exit_duration_and_truncation_badges() {
+        let render = |width: u16| -> Vec<String> {
+            let mut cell = ExecCell::new(
+                ExecCall {
+                    call_id: "call-id".to_string(),
+                    command: vec!["bash".into(), "-lc".into(), "cargo test".into()],
+                    parsed: Vec::new(),
+                    output: None,
+                    source: ExecCommandSource::Agent,
+                    start_time: None,
+                    duration: None,
+                    interaction_input: None,
+                    cwd: None,
+                },
+                false,
+            );
+            cell.complete_call(
+                "call-id",
+                CommandOutput {
+                    exit_code: 101,
+                    aggregated_output: "test failed".to_string(),
+                    formatted_output: "Total output lines: 900\n\ntest failed".to_string(),
+                },
+                Duration::from_secs(242),
+            );
+            cell.command_display_lines(width)
+                .iter()
+                .map(|line| {
+                    line.spans
+                        .iter()
+                        .map(|span| span.content.as_ref())
+                        .collect::<String>()
+                })
+                .collect()
+        };
+
+        assert_eq!(
+            render(80),
+            vec![
+                "• Ran cargo test · exit 101 · 4m 02s · truncated".to_string(),
+                "  └ test failed".to_string(),
+            ]
+        );
+        assert_eq!(
+            render(36),
+            vec![
+                "• Ran cargo test".to_string(),
+                "│ exit 101 · 4m 02s · truncated".to_string(),
+                "└ test failed".to_string(),
+            ]
+        );
+        assert_eq!(
+            render(60),
+            vec![
+                "• Ran cargo test · exit 101 · 4m 02s · truncated".to_string(),
+                "  └ test failed".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn output_keeps_command_colors_and_dims_plain_text() {
+        let output = CommandOutput {
+            exit_code: 0,
+            aggregated_output: "\u{1b}[32mok\u{1b}[0m done".to_string(),
+            formatted_output: String::new(),
+        };
+        let rendered = output_lines(
+            Some(&output),
+            OutputLinesParams {
+                line_limit: TOOL_CALL_MAX_LINES,
+                only_err: false,
+                include_angle_pipe: false,
+                include_prefix: false,
+            },
+        );
+
+        let spans: Vec<(String, bool, bool)> = rendered.lines[0]
+            .spans
+            .iter()
+            .filter(|span| !span.content.is_empty())
+            .map(|span| {
+                (
+                    span.content.to_string(),
+                    span.style.fg == Some(Color::Green),
+                    span.style.add_modifier.contains(Modifier::DIM),
+                )
+            })
+            .collect();
+        assert_eq!(
+            spans,
+            vec![
+                ("ok".to_string(), true, false),
+                (" done".to_string(), false, true),
+            ]
+        );
+    }
+
     #[test]
     fn exploring_display_does_not_split_long_url_like_search_query() {
         let url_like = "example.test/api/v1/projects/alpha-team/releases/2026-02-17/builds/1234567890/artifacts/reports/performance/summary/detail/with/a/very/long/path";
@@ -875,6 +1054,7 @@ mod tests {
             start_time: None,
             duration: None,
             interaction_input: None,
+            cwd: None,
         };
 
         let cell = ExecCell::new(call, false);
@@ -916,6 +1096,7 @@ mod tests {
             start_time: None,
             duration: None,
             interaction_input: None,
+            cwd: None,
         };
 
         let cell = ExecCell::new(call, false);
@@ -953,6 +1134,7 @@ mod tests {
             start_time: None,
             duration: None,
             interaction_input: None,
+            cwd: None,
         };
 
         let cell = ExecCell::new(call, false);
@@ -4,8 +4,15 @@
 //! `AppEvent::StartFileSearch(query)`. This manager owns a single
 //! `codex-file-search` session for the current search root, updates the query
 //! on every keystroke, and drops the session when the query becomes empty.
+//!
+//! When the `semantic_search` feature is enabled, a token of the form
+//! `@?query` (use `+` for spaces, e.g. `@?token+refresh`) bypasses the fuzzy
+//! filename matcher and ranks files from the workspace embedding index instead.
 
+use codex_core::config::types::SemanticSearchConfig;
+use codex_core::semantic_search;
 use codex_file_search as file_search;
+use codex_file_search::FileMatch;
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::Mutex;
@@ -13,12 +20,22 @@ use std::sync::Mutex;
 use crate::app_event::AppEvent;
 use crate::app_event_sender::AppEventSender;
 
+const MAX_SEMANTIC_MATCHES: usize = 20;
+
 pub(crate) struct FileSearchManager {
     state: Arc<Mutex<SearchState>>,
     search_dir: PathBuf,
+    semantic_search: Option<SemanticSearchSettings>,
     app_tx: AppEventSender,
 }
 
+/// Settings for the `@?query` picker mode. `None` disables the mode.
+#[derive(Clone)]
+pub(crate) struct SemanticSearchSettings {
+    pub codex_home: PathBuf,
+    pub config: SemanticSearchConfig,
+}
+
 struct SearchState {
     latest_query: String,
     session: Option<file_search::FileSearchSession>,
@@ -26,7 +43,11 @@ struct SearchState {
 }
 
 impl FileSearchManager {
-    pub fn new(search_dir: PathBuf, tx: AppEventSender) -> Self {
+    pub fn new(
+        search_dir: PathBuf,
+        semantic_search: Option<SemanticSearchSettings>,
+        tx: AppEventSender,
+    ) -> Self {
         Self {
             state: Arc::new(Mutex::new(SearchState {
                 latest_query: String::new(),
@@ -34,6 +55,7 @@ impl FileSearchManager {
                 session_token: 0,
             })),
             search_dir,
+            semantic_search,
             app_tx: tx,
         }
     }
@@ -64,6 +86,17 @@ impl FileSearchManager {
             return;
         }
 
+        if let Some(semantic_query) = query.strip_prefix('?')
+            && let Some(settings) = self.semantic_search.clone()
+        {
+            st.session.take();
+            let semantic_query = semantic_query.replace('+', " ");
+            if !semantic_query.trim().is_empty() {
+                self.spawn_semantic_query(query, semantic_query, settings);
+            }
+            return;
+        }
+
         if st.session.is_none() {
             self.start_session_locked(&mut st);
         }
@@ -72,6 +105,49 @@ impl FileSearchManager {
         }
     }
 
+    fn spawn_semantic_query(
+        &self,
+        query: String,
+        semantic_query: String,
+        settings: SemanticSearchSettings,
+    ) {
+        let state = self.state.clone();
+        let app_tx = self.app_tx.clone();
+        let root = self.search_dir.clone();
+        tokio::spawn(async move {
+            let matches = match semantic_search::search(
+                &root,
+                &settings.codex_home,
+                &settings.config,
+                &semantic_query,
+                MAX_SEMANTIC_MATCHES,
+            )
+            .await
+            {
+                Ok(matches) => matches
+                    .into_iter()
+                    .map(|m| FileMatch {
+                        score: (m.score.max(0.0) * 1000.0) as u32,
+                        path: m.path,
+                        root: root.clone(),
+                        indices: None,
+                    })
+                    .collect(),
+                Err(err) => {
+                    tracing::debug!("semantic file search failed: {err:#}");
+                    Vec::new()
+                }
+            };
+            #[expect(clippy::unwrap_used)]
+            let st = state.lock().unwrap();
+            if st.latest_query != query {
+                return;
+            }
+            drop(st);
+            app_tx.send(AppEvent::FileSearchResult { query, matches });
+        });
+    }
+
     fn start_session_locked(&self, st: &mut SearchState) {
         st.session_token = st.session_token.wrapping_add(1);
         let session_token = st.session_token;
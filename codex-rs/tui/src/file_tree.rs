@@ -0,0 +1,414 @@
+//! Optional workspace tree shown to the left of the chat (`/tree`).
+//!
+//! Directories are listed lazily, honoring `.gitignore`, and files the agent has read or
+//! modified this session are marked so it is easy to see where it has been. While the tree
+//! has focus, Enter inserts an `@` mention for the selected file and `d` opens its diff.
+
+use std::cell::Cell;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::path::Path;
+use std::path::PathBuf;
+
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEvent;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Style;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::text::Span;
+use ratatui::widgets::Widget;
+
+use crate::line_truncation::truncate_line_with_ellipsis_if_overflow;
+use crate::render::renderable::Renderable;
+
+/// Columns taken by the sidebar, including the separator.
+const SIDEBAR_WIDTH: u16 = 32;
+/// The sidebar is hidden when the terminal is narrower than this.
+const MIN_TERMINAL_WIDTH: u16 = 80;
+/// The viewport grows to at least this many rows while the sidebar is shown.
+pub(crate) const MIN_SIDEBAR_HEIGHT: u16 = 14;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum FileMark {
+    Read,
+    Modified,
+}
+
+/// What a key press in the focused tree asks the chat widget to do.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum FileTreeAction {
+    None,
+    Mention(PathBuf),
+    OpenDiff(PathBuf),
+    Blur,
+    Close,
+}
+
+struct Row {
+    path: PathBuf,
+    depth: usize,
+    is_dir: bool,
+}
+
+pub(crate) struct FileTreeSidebar {
+    root: PathBuf,
+    visible: bool,
+    focused: bool,
+    expanded: HashSet<PathBuf>,
+    /// Directory listings, cached until the tree is reopened or re-rooted.
+    listings: RefCell<HashMap<PathBuf, Vec<(PathBuf, bool)>>>,
+    marks: HashMap<PathBuf, FileMark>,
+    selected: usize,
+    scroll: Cell<usize>,
+}
+
+impl FileTreeSidebar {
+    pub(crate) fn new(root: PathBuf) -> Self {
+        Self {
+            root,
+            visible: false,
+            focused: false,
+            expanded: HashSet::new(),
+            listings: RefCell::new(HashMap::new()),
+            marks: HashMap::new(),
+            selected: 0,
+            scroll: Cell::new(0),
+        }
+    }
+
+    pub(crate) fn is_focused(&self) -> bool {
+        self.visible && self.focused
+    }
+
+    /// Show the tree with focus, or hide it if it already has focus.
+    pub(crate) fn toggle(&mut self) {
+        if self.is_focused() {
+            self.visible = false;
+            self.focused = false;
+        } else {
+            self.visible = true;
+            self.focused = true;
+            // Pick up files created since the tree was last opened.
+            self.listings.get_mut().clear();
+        }
+    }
+
+    /// Return focus to the composer, leaving the tree on screen.
+    pub(crate) fn blur(&mut self) {
+        self.focused = false;
+    }
+
+    pub(crate) fn set_root(&mut self, root: PathBuf) {
+        if root != self.root {
+            self.root = root;
+            self.expanded.clear();
+            self.listings.get_mut().clear();
+            self.selected = 0;
+            self.scroll.set(0);
+        }
+    }
+
+    /// Record that the agent touched `path`; a modification outranks a read.
+    pub(crate) fn mark(&mut self, path: PathBuf, mark: FileMark) {
+        let entry = self.marks.entry(path).or_insert(mark);
+        *entry = (*entry).max(mark);
+    }
+
+    /// Width of the sidebar for a chat area `width` columns wide, if it is shown.
+    pub(crate) fn width_for(&self, width: u16) -> Option<u16> {
+        (self.visible && width >= MIN_TERMINAL_WIDTH).then_some(SIDEBAR_WIDTH)
+    }
+
+    pub(crate) fn handle_key_event(&mut self, key_event: KeyEvent) -> FileTreeAction {
+        let rows = self.rows();
+        let Some(row) = rows.get(self.selected.min(rows.len().saturating_sub(1))) else {
+            return match key_event.code {
+                KeyCode::Esc => FileTreeAction::Blur,
+                KeyCode::Char('q') => FileTreeAction::Close,
+                _ => FileTreeAction::None,
+            };
+        };
+        match key_event.code {
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.selected = self.selected.saturating_sub(1);
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.selected = (self.selected + 1).min(rows.len() - 1);
+            }
+            KeyCode::Right | KeyCode::Char('l') if row.is_dir => {
+                self.expanded.insert(row.path.clone());
+            }
+            KeyCode::Left | KeyCode::Char('h') => {
+                if row.is_dir && self.expanded.remove(&row.path) {
+                    return FileTreeAction::None;
+                }
+                if let Some(parent) = rows[..self.selected]
+                    .iter()
+                    .rposition(|candidate| candidate.depth + 1 == row.depth)
+                {
+                    self.selected = parent;
+                }
+            }
+            KeyCode::Enter if row.is_dir => {
+                if !self.expanded.remove(&row.path) {
+                    self.expanded.insert(row.path.clone());
+                }
+            }
+            KeyCode::Enter => return FileTreeAction::Mention(self.relative(&row.path)),
+            KeyCode::Char('d') if !row.is_dir => {
+                return FileTreeAction::OpenDiff(row.path.clone());
+            }
+            KeyCode::Esc => return FileTreeAction::Blur,
+            KeyCode::Char('q') => return FileTreeAction::Close,
+            _ => {}
+        }
+        FileTreeAction::None
+    }
+
+    fn relative(&self, path: &Path) -> PathBuf {
+        path.strip_prefix(&self.root)
+            .map(Path::to_path_buf)
+            .unwrap_or_else(|_| path.to_path_buf())
+    }
+
+    fn rows(&self) -> Vec<Row> {
+        let mut rows = Vec::new();
+        self.collect_rows(&self.root, 0, &mut rows);
+        rows
+    }
+
+    fn collect_rows(&self, dir: &Path, depth: usize, rows: &mut Vec<Row>) {
+        let entries = self
+            .listings
+            .borrow_mut()
+            .entry(dir.to_path_buf())
+            .or_insert_with(|| list_dir(dir))
+            .clone();
+        for (path, is_dir) in entries {
+            let expanded = is_dir && self.expanded.contains(&path);
+            rows.push(Row {
+                path: path.clone(),
+                depth,
+                is_dir,
+            });
+            if expanded {
+                self.collect_rows(&path, depth + 1, rows);
+            }
+        }
+    }
+
+    /// The strongest mark of `path` or, for a directory, of anything under it.
+    fn mark_for(&self, path: &Path, is_dir: bool) -> Option<FileMark> {
+        if is_dir {
+            self.marks
+                .iter()
+                .filter(|(marked, _)| marked.starts_with(path))
+                .map(|(_, mark)| *mark)
+                .max()
+        } else {
+            self.marks.get(path).copied()
+        }
+    }
+
+    fn row_line(&self, row: &Row, selected: bool) -> Line<'static> {
+        let name = row
+            .path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let mut spans: Vec<Span<'static>> = vec!["  ".repeat(row.depth).into()];
+        spans.push(match (row.is_dir, self.expanded.contains(&row.path)) {
+            (true, true) => "▾ ".dim(),
+            (true, false) => "▸ ".dim(),
+            (false, _) => "  ".into(),
+        });
+        let name = if row.is_dir { format!("{name}/") } else { name };
+        spans.push(if selected && self.focused {
+            name.reversed()
+        } else if selected {
+            name.bold()
+        } else {
+            name.into()
+        });
+        match self.mark_for(&row.path, row.is_dir) {
+            Some(FileMark::Modified) => spans.push(" M".magenta()),
+            Some(FileMark::Read) => spans.push(" R".dim()),
+            None => {}
+        }
+        Line::from(spans)
+    }
+}
+
+/// Entries of `dir`, directories first, honoring ignore files.
+fn list_dir(dir: &Path) -> Vec<(PathBuf, bool)> {
+    let mut entries: Vec<(PathBuf, bool)> = ignore::WalkBuilder::new(dir)
+        .max_depth(Some(1))
+        .hidden(false)
+        .filter_entry(|entry| entry.file_name() != ".git")
+        .build()
+        .filter_map(Result::ok)
+        .filter(|entry| entry.depth() == 1)
+        .map(|entry| {
+            let is_dir = entry.file_type().is_some_and(|kind| kind.is_dir());
+            (entry.into_path(), is_dir)
+        })
+        .collect();
+    entries.sort_by(|(a, a_dir), (b, b_dir)| b_dir.cmp(a_dir).then_with(|| a.cmp(b)));
+    entries
+}
+
+impl Renderable for FileTreeSidebar {
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        if area.width < 2 || area.height == 0 {
+            return;
+        }
+        let inner_width = area.width - 2;
+        let title = if self.focused {
+            Line::from(vec!["Files".bold(), " ⏎ mention · d diff".dim()])
+        } else {
+            Line::from("Files".bold())
+        };
+        truncate_line_with_ellipsis_if_overflow(title, inner_width as usize)
+            .render(Rect::new(area.x, area.y, inner_width, 1), buf);
+
+        let rows = self.rows();
+        let list_height = area.height.saturating_sub(1) as usize;
+        let selected = self.selected.min(rows.len().saturating_sub(1));
+        let mut scroll = self.scroll.get();
+        if selected < scroll {
+            scroll = selected;
+        } else if list_height > 0 && selected >= scroll + list_height {
+            scroll = selected + 1 - list_height;
+        }
+        self.scroll.set(scroll);
+
+        for (offset, row) in rows.iter().skip(scroll).take(list_height).enumerate() {
+            let line = self.row_line(row, scroll + offset == selected);
+            truncate_line_with_ellipsis_if_overflow(line, inner_width as usize).render(
+                Rect::new(area.x, area.y + 1 + offset as u16, inner_width, 1),
+                buf,
+            );
+        }
+        for y in area.y..area.y + area.height {
+            buf[(area.x + area.width - 1, y)]
+                .set_symbol("│")
+                .set_style(Style::default().dim());
+        }
+    }
+
+    fn desired_height(&self, _width: u16) -> u16 {
+        MIN_SIDEBAR_HEIGHT
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+    use insta::assert_snapshot;
+    use pretty_assertions::assert_eq;
+    use ratatui::Terminal;
+    use ratatui::backend::TestBackend;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn render_lines(sidebar: &FileTreeSidebar, height: u16) -> Vec<String> {
+        let area = Rect::new(0, 0, SIDEBAR_WIDTH, height);
+        let mut buf = Buffer::empty(area);
+        sidebar.render(area, &mut buf);
+        (0..height)
+            .map(|y| {
+                (0..SIDEBAR_WIDTH - 1)
+                    .map(|x| buf[(x, y)].symbol())
+                    .collect::<String>()
+                    .trim_end()
+                    .to_string()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn lists_directories_first_and_marks_touched_files() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let root = dir.path().to_path_buf();
+        std::fs::create_dir(root.join("src")).expect("mkdir");
+        std::fs::write(root.join("src/lib.rs"), "").expect("write");
+        std::fs::write(root.join("README.md"), "").expect("write");
+
+        let mut sidebar = FileTreeSidebar::new(root.clone());
+        sidebar.toggle();
+        sidebar.mark(root.join("src/lib.rs"), FileMark::Read);
+        sidebar.mark(root.join("src/lib.rs"), FileMark::Modified);
+        sidebar.mark(root.join("README.md"), FileMark::Read);
+        assert_eq!(
+            sidebar.handle_key_event(key(KeyCode::Enter)),
+            FileTreeAction::None
+        );
+
+        assert_eq!(
+            render_lines(&sidebar, 4),
+            vec![
+                "Files ⏎ mention · d diff".to_string(),
+                "▾ src/ M".to_string(),
+                "    lib.rs M".to_string(),
+                "  README.md R".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn focused_sidebar_snapshot() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let root = dir.path().to_path_buf();
+        std::fs::create_dir(root.join("docs")).expect("mkdir");
+        std::fs::write(root.join("docs/guide.md"), "").expect("write");
+        std::fs::create_dir(root.join("src")).expect("mkdir");
+        std::fs::write(root.join("src/lib.rs"), "").expect("write");
+        std::fs::write(root.join("README.md"), "").expect("write");
+
+        let mut sidebar = FileTreeSidebar::new(root.clone());
+        sidebar.toggle();
+        sidebar.mark(root.join("docs/guide.md"), FileMark::Read);
+        sidebar.mark(root.join("src/lib.rs"), FileMark::Modified);
+        sidebar.handle_key_event(key(KeyCode::Down));
+        sidebar.handle_key_event(key(KeyCode::Enter));
+
+        let mut terminal = Terminal::new(TestBackend::new(SIDEBAR_WIDTH, 7)).expect("terminal");
+        terminal
+            .draw(|f| sidebar.render(f.area(), f.buffer_mut()))
+            .expect("draw");
+        assert_snapshot!(terminal.backend());
+    }
+
+    #[test]
+    fn enter_on_a_file_mentions_its_workspace_path() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let root = dir.path().to_path_buf();
+        std::fs::create_dir(root.join("src")).expect("mkdir");
+        std::fs::write(root.join("src/main.rs"), "").expect("write");
+
+        let mut sidebar = FileTreeSidebar::new(root.clone());
+        sidebar.toggle();
+        sidebar.handle_key_event(key(KeyCode::Right));
+        sidebar.handle_key_event(key(KeyCode::Down));
+
+        assert_eq!(
+            sidebar.handle_key_event(key(KeyCode::Enter)),
+            FileTreeAction::Mention(PathBuf::from("src/main.rs"))
+        );
+        assert_eq!(
+            sidebar.handle_key_event(key(KeyCode::Char('d'))),
+            FileTreeAction::OpenDiff(root.join("src/main.rs"))
+        );
+        sidebar.handle_key_event(key(KeyCode::Left));
+        assert_eq!(
+            sidebar.handle_key_event(key(KeyCode::Esc)),
+            FileTreeAction::Blur
+        );
+    }
+}
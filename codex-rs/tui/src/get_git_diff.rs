@@ -61,6 +61,27 @@ pub(crate) async fn get_git_diff() -> io::Result<(bool, String)> {
     Ok((true, format!("{tracked_diff}{untracked_diff}")))
 }
 
+/// Diff of a single file against the index, or against an empty file when it is
+/// untracked. Used by the file-tree sidebar.
+pub(crate) async fn get_git_diff_for_path(path: &Path) -> io::Result<String> {
+    if !inside_git_repo().await? {
+        return Err(io::Error::other("not inside a git repository"));
+    }
+    let path = path.to_string_lossy().to_string();
+    let diff = run_git_capture_diff(&["diff", "--color", "--", &path]).await?;
+    if !diff.is_empty() {
+        return Ok(diff);
+    }
+    let untracked =
+        run_git_capture_stdout(&["ls-files", "--others", "--exclude-standard", "--", &path])
+            .await?;
+    if untracked.trim().is_empty() {
+        return Ok(String::new());
+    }
+    let null_path = if cfg!(windows) { "NUL" } else { "/dev/null" };
+    run_git_capture_diff(&["diff", "--color", "--no-index", "--", null_path, &path]).await
+}
+
 /// Helper that executes `git` with the given `args` and returns `stdout` as a
 /// UTF-8 string. Any non-zero exit status is considered an *error*.
 async fn run_git_capture_stdout(args: &[&str]) -> io::Result<String> {
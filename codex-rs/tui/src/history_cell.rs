@@ -37,7 +37,13 @@ use crate::wrapping::RtOptions;
 use crate::wrapping::adaptive_wrap_line;
 use crate::wrapping::adaptive_wrap_lines;
 use base64::Engine;
+use chrono::DateTime;
+use chrono::Local;
+use codex_core::ConflictHunk;
+use codex_core::ConflictResolution;
+use codex_core::SessionSummary;
 use codex_core::config::Config;
+use codex_core::config::types::HistoryTimestamps;
 use codex_core::config::types::McpServerTransportConfig;
 use codex_core::mcp::McpManager;
 use codex_core::plugins::PluginsManager;
@@ -48,17 +54,23 @@ use codex_protocol::config_types::ServiceTier;
 use codex_protocol::mcp::Resource;
 use codex_protocol::mcp::ResourceTemplate;
 use codex_protocol::models::WebSearchAction;
+use codex_protocol::models::WebSearchSource;
 use codex_protocol::models::local_image_label_text;
 use codex_protocol::openai_models::ReasoningEffort as ReasoningEffortConfig;
 use codex_protocol::plan_tool::PlanItemArg;
 use codex_protocol::plan_tool::StepStatus;
 use codex_protocol::plan_tool::UpdatePlanArgs;
+use codex_protocol::protocol::CodexErrorInfo;
+use codex_protocol::protocol::DryRunAction;
 use codex_protocol::protocol::FileChange;
 use codex_protocol::protocol::McpAuthStatus;
 use codex_protocol::protocol::McpInvocation;
 use codex_protocol::protocol::SessionConfiguredEvent;
+use codex_protocol::protocol::TurnAbortReason;
 use codex_protocol::request_user_input::RequestUserInputAnswer;
 use codex_protocol::request_user_input::RequestUserInputQuestion;
+use codex_protocol::todo_tool::TodoItem;
+use codex_protocol::todo_tool::TodoStatus;
 use codex_protocol::user_input::TextElement;
 use codex_utils_cli::format_env_display::format_env_display;
 use image::DynamicImage;
@@ -889,6 +901,46 @@ pub fn new_approval_decision_cell(
     ))
 }
 
+/// Records the user's answer to a plan presented under `require_plan_approval`.
+pub fn new_plan_approval_decision_cell(
+    decision: codex_protocol::protocol::ReviewDecision,
+) -> Box<dyn HistoryCell> {
+    use codex_protocol::protocol::ReviewDecision::*;
+
+    let (symbol, summary): (Span<'static>, Vec<Span<'static>>) = match decision {
+        Approved | ApprovedExecpolicyAmendment { .. } => (
+            "✔ ".green(),
+            vec!["You ".into(), "approved".bold(), " the plan".into()],
+        ),
+        ApprovedForSession => (
+            "✔ ".green(),
+            vec![
+                "You ".into(),
+                "approved".bold(),
+                " the plan and lifted the plan gate for this session".into(),
+            ],
+        ),
+        Denied | NetworkPolicyAmendment { .. } => (
+            "✗ ".red(),
+            vec![
+                "You ".into(),
+                "rejected".bold(),
+                " the plan and asked for a revision".into(),
+            ],
+        ),
+        Abort => (
+            "✗ ".red(),
+            vec!["You ".into(), "rejected".bold(), " the plan".into()],
+        ),
+    };
+
+    Box::new(PrefixedWrappedHistoryCell::new(
+        Line::from(summary),
+        symbol,
+        "  ",
+    ))
+}
+
 /// Cyan history cell line showing the current review status.
 pub(crate) fn new_review_status_line(message: String) -> PlainHistoryCell {
     PlainHistoryCell {
@@ -908,6 +960,43 @@ impl HistoryCell for PatchHistoryCell {
     }
 }
 
+/// A command or patch that a `--dry-run` session previewed instead of running.
+#[derive(Debug)]
+pub(crate) struct DryRunHistoryCell {
+    action: DryRunAction,
+    cwd: PathBuf,
+}
+
+impl HistoryCell for DryRunHistoryCell {
+    fn display_lines(&self, width: u16) -> Vec<Line<'static>> {
+        match &self.action {
+            DryRunAction::Exec { command, cwd } => {
+                let mut note = "dry run, not executed".to_string();
+                if cwd != &self.cwd {
+                    note.push_str(&format!(" (in {})", display_path_for(cwd, &self.cwd)));
+                }
+                vec![
+                    Line::from(vec![
+                        "• ".dim(),
+                        "Would run ".bold(),
+                        strip_bash_lc_and_escape(command).into(),
+                    ]),
+                    Line::from(vec!["  └ ".dim(), note.dim()]),
+                ]
+            }
+            DryRunAction::Patch { changes } => {
+                let mut lines = vec![Line::from(vec![
+                    "• ".dim(),
+                    "Would apply a patch".bold(),
+                    " (dry run, nothing written)".dim(),
+                ])];
+                lines.extend(create_diff_summary(changes, &self.cwd, width as usize));
+                lines
+            }
+        }
+    }
+}
+
 #[derive(Debug)]
 struct CompletedMcpToolCallWithImageOutput {
     _image: DynamicImage,
@@ -1510,6 +1599,9 @@ pub(crate) fn new_active_mcp_tool_call(
     McpToolCallCell::new(call_id, invocation, animations_enabled)
 }
 
+/// Sources listed under a completed search before the rest are summarized.
+const WEB_SEARCH_MAX_SOURCES_SHOWN: usize = 5;
+
 fn web_search_header(completed: bool) -> &'static str {
     if completed {
         "Searched"
@@ -1523,6 +1615,7 @@ pub(crate) struct WebSearchCell {
     call_id: String,
     query: String,
     action: Option<WebSearchAction>,
+    sources: Vec<WebSearchSource>,
     start_time: Instant,
     completed: bool,
     animations_enabled: bool,
@@ -1539,6 +1632,7 @@ impl WebSearchCell {
             call_id,
             query,
             action,
+            sources: Vec::new(),
             start_time: Instant::now(),
             completed: false,
             animations_enabled,
@@ -1554,9 +1648,51 @@ impl WebSearchCell {
         self.query = query;
     }
 
+    pub(crate) fn set_sources(&mut self, sources: Vec<WebSearchSource>) {
+        self.sources = sources;
+    }
+
     pub(crate) fn complete(&mut self) {
         self.completed = true;
     }
+
+    fn source_lines(&self, width: u16) -> Vec<Line<'static>> {
+        let max_graphemes = (width as usize).saturating_sub(4).max(1);
+        let mut lines: Vec<Line<'static>> = self
+            .sources
+            .iter()
+            .take(WEB_SEARCH_MAX_SOURCES_SHOWN)
+            .map(|source| {
+                let host = source
+                    .url
+                    .split_once("://")
+                    .map_or(source.url.as_str(), |(_, rest)| rest)
+                    .split('/')
+                    .next()
+                    .unwrap_or_default()
+                    .trim_start_matches("www.");
+                let title = if source.title.is_empty() {
+                    source.url.as_str()
+                } else {
+                    source.title.as_str()
+                };
+                let host_suffix = format!(" · {host}");
+                let title_budget = max_graphemes.saturating_sub(host_suffix.chars().count());
+                Line::from(vec![
+                    truncate_text(title, title_budget.max(1)).into(),
+                    host_suffix.dim(),
+                ])
+            })
+            .collect();
+        let hidden = self
+            .sources
+            .len()
+            .saturating_sub(WEB_SEARCH_MAX_SOURCES_SHOWN);
+        if hidden > 0 {
+            lines.push(format!("… +{hidden} more").dim().into());
+        }
+        prefix_lines(lines, "  └ ".dim(), "    ".into())
+    }
 }
 
 impl HistoryCell for WebSearchCell {
@@ -1573,7 +1709,10 @@ impl HistoryCell for WebSearchCell {
         } else {
             Line::from(vec![header.bold(), " ".into(), detail.into()]).into()
         };
-        PrefixedWrappedHistoryCell::new(text, vec![bullet, " ".into()], "  ").display_lines(width)
+        let mut lines = PrefixedWrappedHistoryCell::new(text, vec![bullet, " ".into()], "  ")
+            .display_lines(width);
+        lines.extend(self.source_lines(width));
+        lines
     }
 }
 
@@ -1663,6 +1802,19 @@ pub(crate) fn new_warning_event(message: String) -> PrefixedWrappedHistoryCell {
     PrefixedWrappedHistoryCell::new(message.yellow(), "⚠ ".yellow(), "  ")
 }
 
+/// Closes a resumed turn that ended before the model finished, saying why, so the partial
+/// exchange above it does not look like a complete one.
+pub(crate) fn new_turn_cancelled(reason: &TurnAbortReason) -> PrefixedWrappedHistoryCell {
+    let message = match reason {
+        TurnAbortReason::Interrupted => "Turn cancelled: interrupted by the user",
+        TurnAbortReason::Replaced => "Turn cancelled: replaced by a new task",
+        TurnAbortReason::ReviewEnded => "Turn cancelled: the review ended",
+        TurnAbortReason::Timeout => "Turn cancelled: timed out waiting for the model",
+        TurnAbortReason::Error => "Turn cancelled: stopped by an error",
+    };
+    PrefixedWrappedHistoryCell::new(message.dim(), "■ ".magenta(), "  ")
+}
+
 #[derive(Debug)]
 pub(crate) struct DeprecationNoticeCell {
     summary: String,
@@ -1890,14 +2042,245 @@ pub(crate) fn new_info_event(message: String, hint: Option<String>) -> PlainHist
     PlainHistoryCell { lines }
 }
 
-pub(crate) fn new_error_event(message: String) -> PlainHistoryCell {
-    // Use a hair space (U+200A) to create a subtle, near-invisible separation
-    // before the text. VS16 is intentionally omitted to keep spacing tighter
-    // in terminals like Ghostty.
-    let lines: Vec<Line<'static>> = vec![vec![format!("■ {message}").red()].into()];
+/// A dim line placed above a prompt, reply, or tool call saying when it happened. Relative
+/// times count from `session_start` rather than from now, so they stay correct in scrollback.
+pub(crate) fn new_timestamp(
+    mode: HistoryTimestamps,
+    at: DateTime<Local>,
+    session_start: DateTime<Local>,
+) -> Option<PlainHistoryCell> {
+    let label = match mode {
+        HistoryTimestamps::Off => return None,
+        HistoryTimestamps::Absolute if at.date_naive() == Local::now().date_naive() => {
+            at.format("%H:%M:%S").to_string()
+        }
+        HistoryTimestamps::Absolute => at.format("%Y-%m-%d %H:%M:%S").to_string(),
+        HistoryTimestamps::Relative => {
+            let secs = (at - session_start).num_seconds().max(0);
+            format!("+{}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+        }
+    };
+    Some(PlainHistoryCell {
+        lines: vec![Line::from(label.dim())],
+    })
+}
+
+/// A session summary from `/summarize`, or the stored one shown as a recap when the session is
+/// resumed. Empty sections are left out.
+pub(crate) fn new_session_summary(summary: &SessionSummary, title: &str) -> PlainHistoryCell {
+    let mut header = vec!["• ".dim(), title.to_string().bold()];
+    if let Ok(at) = DateTime::parse_from_rfc3339(&summary.generated_at) {
+        let at = at.with_timezone(&Local);
+        header.push(format!(" · written {}", at.format("%Y-%m-%d %H:%M")).dim());
+    }
+    let mut lines: Vec<Line<'static>> = vec![header.into()];
+    for (label, entries) in [
+        ("Done", &summary.accomplished),
+        ("Decided", &summary.decisions),
+        ("To do", &summary.todos),
+    ] {
+        if entries.is_empty() {
+            continue;
+        }
+        lines.push(Line::from(format!("  {label}").cyan()));
+        for entry in entries {
+            lines.push(Line::from(vec!["    - ".dim(), entry.clone().into()]));
+        }
+    }
+    if lines.len() == 1 {
+        lines.push(Line::from("  Nothing to report yet.".dim()));
+    }
+    PlainHistoryCell { lines }
+}
+
+/// Instructions for finishing a `/login device` sign-in from another browser.
+pub(crate) fn new_device_code_login(
+    verification_url: String,
+    user_code: String,
+) -> PlainHistoryCell {
+    let lines: Vec<Line<'static>> = vec![
+        vec!["• ".dim(), "Finish signing in from any browser".bold()].into(),
+        vec![
+            "  1. Open ".into(),
+            verification_url.cyan().underlined(),
+            " and sign in".into(),
+        ]
+        .into(),
+        vec![
+            "  2. Enter the one-time code ".into(),
+            user_code.cyan().bold(),
+            " (expires in 15 minutes)".dim(),
+        ]
+        .into(),
+        "  Device codes are a common phishing target. Never share this code."
+            .dim()
+            .into(),
+        "  Run /login cancel to stop waiting.".dim().into(),
+    ];
+    PlainHistoryCell { lines }
+}
+
+/// One side of a merge conflict: a heading, then the side's lines behind a gutter.
+fn conflict_side_lines(heading: String, text: &str, lines: &mut Vec<Line<'static>>) {
+    lines.push(vec!["  ".into(), heading.bold()].into());
+    if text.is_empty() {
+        lines.push("    (empty)".dim().into());
+    }
+    for line in text.lines() {
+        lines.push(vec!["    │ ".dim(), line.to_string().into()].into());
+    }
+}
+
+fn with_label(side: &str, label: &str) -> String {
+    if label.is_empty() {
+        side.to_string()
+    } else {
+        format!("{side} ({label})")
+    }
+}
+
+/// A conflict hunk during `/conflicts`: both sides and, when known, their common ancestor.
+pub(crate) fn new_conflict_hunk(
+    hunk: &ConflictHunk,
+    number: usize,
+    total: usize,
+) -> PlainHistoryCell {
+    let mut lines: Vec<Line<'static>> = vec![
+        vec![
+            "• ".dim(),
+            format!("Conflict {number} of {total}").bold(),
+            format!(" · {}:{}", hunk.display_path, hunk.line).dim(),
+        ]
+        .into(),
+    ];
+    conflict_side_lines(with_label("Ours", &hunk.ours_label), &hunk.ours, &mut lines);
+    match &hunk.base {
+        Some(base) => conflict_side_lines("Base".to_string(), base, &mut lines),
+        None => lines.push("  Base not available".dim().into()),
+    }
+    conflict_side_lines(
+        with_label("Theirs", &hunk.theirs_label),
+        &hunk.theirs,
+        &mut lines,
+    );
+    PlainHistoryCell { lines }
+}
+
+/// The model's proposed resolution for a conflict hunk.
+pub(crate) fn new_conflict_resolution(resolution: &ConflictResolution) -> PlainHistoryCell {
+    let mut lines: Vec<Line<'static>> = vec![
+        vec![
+            "• ".dim(),
+            "Proposed resolution ".bold(),
+            resolution.explanation.clone().dim(),
+        ]
+        .into(),
+    ];
+    for line in resolution.resolution.lines() {
+        lines.push(vec!["    │ ".dim(), line.to_string().green()].into());
+    }
     PlainHistoryCell { lines }
 }
 
+/// What kind of failure an [`ErrorHistoryCell`] reports. Decides the label in front of the
+/// detail and which follow-up actions the TUI offers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum ErrorCategory {
+    /// Errors raised by the TUI itself or not classified by core.
+    General,
+    Connection,
+    Server,
+    ContextWindow,
+    UsageLimit,
+    Authentication,
+    BadRequest,
+    Sandbox,
+    Rollback,
+}
+
+impl ErrorCategory {
+    /// Classify an error reported by core, along with whether trying again may succeed.
+    pub(crate) fn from_info(info: Option<&CodexErrorInfo>) -> (Self, bool) {
+        match info {
+            Some(
+                CodexErrorInfo::HttpConnectionFailed { .. }
+                | CodexErrorInfo::ResponseStreamConnectionFailed { .. }
+                | CodexErrorInfo::ResponseStreamDisconnected { .. },
+            ) => (Self::Connection, true),
+            Some(
+                CodexErrorInfo::ServerOverloaded
+                | CodexErrorInfo::InternalServerError
+                | CodexErrorInfo::ResponseTooManyFailedAttempts { .. },
+            ) => (Self::Server, true),
+            Some(CodexErrorInfo::ContextWindowExceeded) => (Self::ContextWindow, false),
+            Some(CodexErrorInfo::UsageLimitExceeded) => (Self::UsageLimit, false),
+            Some(CodexErrorInfo::Unauthorized) => (Self::Authentication, false),
+            Some(CodexErrorInfo::BadRequest) => (Self::BadRequest, false),
+            Some(CodexErrorInfo::SandboxError) => (Self::Sandbox, true),
+            Some(CodexErrorInfo::ThreadRollbackFailed) => (Self::Rollback, false),
+            Some(CodexErrorInfo::Other) | None => (Self::General, true),
+        }
+    }
+
+    fn label(self) -> Option<&'static str> {
+        match self {
+            Self::General => None,
+            Self::Connection => Some("Connection error"),
+            Self::Server => Some("Server error"),
+            Self::ContextWindow => Some("Context window exceeded"),
+            Self::UsageLimit => Some("Usage limit reached"),
+            Self::Authentication => Some("Not authorized"),
+            Self::BadRequest => Some("Request rejected"),
+            Self::Sandbox => Some("Sandbox error"),
+            Self::Rollback => Some("Rollback failed"),
+        }
+    }
+}
+
+/// An error in the transcript: a category label, the detail text, and whether a retry may help.
+#[derive(Debug)]
+pub(crate) struct ErrorHistoryCell {
+    category: ErrorCategory,
+    detail: String,
+    retryable: bool,
+}
+
+impl HistoryCell for ErrorHistoryCell {
+    fn display_lines(&self, _width: u16) -> Vec<Line<'static>> {
+        // Use a hair space (U+200A) to create a subtle, near-invisible separation
+        // before the text. VS16 is intentionally omitted to keep spacing tighter
+        // in terminals like Ghostty.
+        let mut spans = match self.category.label() {
+            Some(label) => vec![
+                format!("■ {label}: ").red().bold(),
+                self.detail.clone().red(),
+            ],
+            None => vec![format!("■ {}", self.detail).red()],
+        };
+        if self.retryable {
+            spans.push(" · retryable".dim());
+        }
+        vec![spans.into()]
+    }
+}
+
+/// An error raised by the TUI itself, e.g. a command that is unavailable right now.
+pub(crate) fn new_error_event(message: String) -> ErrorHistoryCell {
+    new_structured_error(ErrorCategory::General, message, false)
+}
+
+pub(crate) fn new_structured_error(
+    category: ErrorCategory,
+    detail: String,
+    retryable: bool,
+) -> ErrorHistoryCell {
+    ErrorHistoryCell {
+        category,
+        detail,
+        retryable,
+    }
+}
+
 /// Renders a completed (or interrupted) request_user_input exchange in history.
 #[derive(Debug)]
 pub(crate) struct RequestUserInputResultCell {
@@ -2163,6 +2546,59 @@ impl HistoryCell for PlanUpdateCell {
     }
 }
 
+pub(crate) fn new_todo_list(items: Vec<TodoItem>) -> TodoListCell {
+    TodoListCell { items }
+}
+
+/// The session todo list after an `update_todos` call, as a checklist.
+#[derive(Debug)]
+pub(crate) struct TodoListCell {
+    items: Vec<TodoItem>,
+}
+
+impl HistoryCell for TodoListCell {
+    fn display_lines(&self, width: u16) -> Vec<Line<'static>> {
+        let open = self
+            .items
+            .iter()
+            .filter(|item| item.status.is_open())
+            .count();
+        let mut lines: Vec<Line<'static>> = vec![
+            vec![
+                "• ".dim(),
+                "Todo List".bold(),
+                format!(" ({open} of {} open)", self.items.len()).dim(),
+            ]
+            .into(),
+        ];
+
+        let mut indented_lines = vec![];
+        if self.items.is_empty() {
+            indented_lines.push(Line::from("(empty)".dim().italic()));
+        }
+        for item in &self.items {
+            let (box_str, style) = match item.status {
+                TodoStatus::Done => ("✔ ", Style::default().crossed_out().dim()),
+                TodoStatus::Cancelled => ("✗ ", Style::default().crossed_out().dim()),
+                TodoStatus::InProgress => ("□ ", Style::default().cyan().bold()),
+                TodoStatus::Pending => ("□ ", Style::default().dim()),
+            };
+            let opts = RtOptions::new(width.saturating_sub(4).max(1) as usize)
+                .initial_indent(box_str.into())
+                .subsequent_indent("  ".into());
+            let text = Line::from(vec![
+                format!("#{} ", item.id).dim(),
+                item.text.clone().set_style(style),
+            ]);
+            let wrapped = adaptive_wrap_line(&text, opts);
+            push_owned_lines(&wrapped, &mut indented_lines);
+        }
+        lines.extend(prefix_lines(indented_lines, "  └ ".dim(), "    ".into()));
+
+        lines
+    }
+}
+
 /// Create a new `PendingPatch` cell that lists the file‑level summary of
 /// a proposed patch. The summary lines should already be formatted (e.g.
 /// "A path/to/file.rs").
@@ -2176,6 +2612,66 @@ pub(crate) fn new_patch_event(
     }
 }
 
+/// One line per `[[patch_checks]]` entry that ran after a patch, with the output of failed
+/// checks underneath.
+pub(crate) fn new_patch_checks(
+    results: Vec<codex_protocol::protocol::PatchCheckResult>,
+) -> PlainHistoryCell {
+    let mut lines: Vec<Line<'static>> = Vec::new();
+    for result in results {
+        let failed = result.exit_code != 0 || !result.output.is_empty();
+        if failed {
+            lines.push(Line::from(vec![
+                "✘ ".red(),
+                result.name.bold(),
+                format!(" failed (exit {})", result.exit_code).red(),
+            ]));
+            let output = output_lines(
+                Some(&CommandOutput {
+                    exit_code: result.exit_code,
+                    formatted_output: String::new(),
+                    aggregated_output: result.output,
+                }),
+                OutputLinesParams {
+                    line_limit: TOOL_CALL_MAX_LINES,
+                    only_err: false,
+                    include_angle_pipe: true,
+                    include_prefix: true,
+                },
+            );
+            lines.extend(output.lines);
+        } else if result.formatted_files.is_empty() {
+            lines.push(Line::from(vec![
+                "✓ ".green(),
+                result.name.bold(),
+                " passed".dim(),
+            ]));
+        } else {
+            let files = result
+                .formatted_files
+                .iter()
+                .map(|path| path.display().to_string())
+                .collect::<Vec<_>>()
+                .join(", ");
+            lines.push(Line::from(vec![
+                "✓ ".green(),
+                result.name.bold(),
+                " reformatted ".dim(),
+                files.into(),
+            ]));
+        }
+    }
+
+    PlainHistoryCell { lines }
+}
+
+pub(crate) fn new_dry_run_action(action: DryRunAction, cwd: &Path) -> DryRunHistoryCell {
+    DryRunHistoryCell {
+        action,
+        cwd: cwd.to_path_buf(),
+    }
+}
+
 pub(crate) fn new_patch_apply_failure(stderr: String) -> PlainHistoryCell {
     let mut lines: Vec<Line<'static>> = Vec::new();
 
@@ -2739,6 +3235,62 @@ mod tests {
         insta::assert_snapshot!(rendered);
     }
 
+    #[test]
+    fn structured_error_cell_shows_category_and_retryable_flag() {
+        let (category, retryable) =
+            ErrorCategory::from_info(Some(&CodexErrorInfo::ResponseStreamDisconnected {
+                http_status_code: None,
+            }));
+        let cell = new_structured_error(category, "stream closed early".to_string(), retryable);
+        assert_eq!(
+            render_lines(&cell.display_lines(80)),
+            vec!["■ Connection error: stream closed early · retryable".to_string()]
+        );
+
+        let (category, retryable) = ErrorCategory::from_info(Some(&CodexErrorInfo::BadRequest));
+        let cell = new_structured_error(category, "unsupported parameter".to_string(), retryable);
+        assert_eq!(
+            render_lines(&cell.display_lines(80)),
+            vec!["■ Request rejected: unsupported parameter".to_string()]
+        );
+    }
+
+    #[test]
+    fn structured_error_cells_snapshot() {
+        let rendered = [
+            (
+                Some(CodexErrorInfo::ContextWindowExceeded),
+                "prompt too long",
+            ),
+            (Some(CodexErrorInfo::SandboxError), "command was denied"),
+            (None, "something went wrong"),
+        ]
+        .into_iter()
+        .flat_map(|(info, detail)| {
+            let (category, retryable) = ErrorCategory::from_info(info.as_ref());
+            let cell = new_structured_error(category, detail.to_string(), retryable);
+            render_lines(&cell.display_lines(80))
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+        insta::assert_snapshot!(rendered);
+    }
+
+    #[test]
+    fn timestamps_follow_the_configured_mode() {
+        let start = Local::now();
+        let at = start + chrono::TimeDelta::seconds(3 * 3600 + 125);
+
+        assert!(new_timestamp(HistoryTimestamps::Off, at, start).is_none());
+        let relative = new_timestamp(HistoryTimestamps::Relative, at, start).expect("cell");
+        assert_eq!(render_lines(&relative.display_lines(80)), vec!["+3:02:05"]);
+        let absolute = new_timestamp(HistoryTimestamps::Absolute, start, start).expect("cell");
+        assert_eq!(
+            render_lines(&absolute.display_lines(80)),
+            vec![start.format("%H:%M:%S").to_string()]
+        );
+    }
+
     #[test]
     fn error_event_oversized_input_snapshot() {
         let cell = new_error_event(
@@ -2997,6 +3549,39 @@ mod tests {
         insta::assert_snapshot!(rendered);
     }
 
+    #[test]
+    fn web_search_history_cell_lists_sources_compactly() {
+        let mut cell = new_web_search_call(
+            "call-1".to_string(),
+            "tokio runtime".to_string(),
+            WebSearchAction::Search {
+                query: Some("tokio runtime".to_string()),
+                queries: None,
+            },
+        );
+        cell.set_sources(
+            (1..=7)
+                .map(|index| WebSearchSource {
+                    title: format!("Result {index}"),
+                    url: format!("https://www.example{index}.com/page"),
+                })
+                .collect(),
+        );
+
+        assert_eq!(
+            render_lines(&cell.display_lines(64)),
+            vec![
+                "• Searched tokio runtime".to_string(),
+                "  └ Result 1 · example1.com".to_string(),
+                "    Result 2 · example2.com".to_string(),
+                "    Result 3 · example3.com".to_string(),
+                "    Result 4 · example4.com".to_string(),
+                "    Result 5 · example5.com".to_string(),
+                "    … +2 more".to_string(),
+            ]
+        );
+    }
+
     #[test]
     fn web_search_history_cell_wraps_with_indented_continuation() {
         let query =
@@ -3399,6 +3984,7 @@ mod tests {
                 start_time: Some(Instant::now()),
                 duration: None,
                 interaction_input: None,
+                cwd: None,
             },
             true,
         );
@@ -3426,6 +4012,7 @@ mod tests {
                 start_time: Some(Instant::now()),
                 duration: None,
                 interaction_input: None,
+                cwd: None,
             },
             true,
         );
@@ -3495,6 +4082,7 @@ mod tests {
                 start_time: Some(Instant::now()),
                 duration: None,
                 interaction_input: None,
+                cwd: None,
             },
             true,
         );
@@ -3519,6 +4107,7 @@ mod tests {
                 start_time: Some(Instant::now()),
                 duration: None,
                 interaction_input: None,
+                cwd: None,
             },
             true,
         );
@@ -3545,6 +4134,7 @@ mod tests {
                 start_time: Some(Instant::now()),
                 duration: None,
                 interaction_input: None,
+                cwd: None,
             },
             true,
         );
@@ -3569,6 +4159,7 @@ mod tests {
                 start_time: Some(Instant::now()),
                 duration: None,
                 interaction_input: None,
+                cwd: None,
             },
             true,
         );
@@ -3592,6 +4183,7 @@ mod tests {
                 start_time: Some(Instant::now()),
                 duration: None,
                 interaction_input: None,
+                cwd: None,
             },
             true,
         );
@@ -3616,6 +4208,7 @@ mod tests {
                 start_time: Some(Instant::now()),
                 duration: None,
                 interaction_input: None,
+                cwd: None,
             },
             true,
         );
@@ -3640,6 +4233,7 @@ mod tests {
                 start_time: Some(Instant::now()),
                 duration: None,
                 interaction_input: None,
+                cwd: None,
             },
             true,
         );
@@ -3690,6 +4284,7 @@ mod tests {
                 start_time: Some(Instant::now()),
                 duration: None,
                 interaction_input: None,
+                cwd: None,
             },
             true,
         );
@@ -3954,6 +4549,41 @@ mod tests {
         insta::assert_snapshot!(rendered);
     }
 
+    #[test]
+    fn todo_list_snapshot() {
+        let cell = new_todo_list(vec![
+            TodoItem {
+                id: 1,
+                text: "Reproduce the crash".into(),
+                status: TodoStatus::Done,
+            },
+            TodoItem {
+                id: 2,
+                text: "Fix the parser".into(),
+                status: TodoStatus::InProgress,
+            },
+            TodoItem {
+                id: 3,
+                text: "Add a regression test".into(),
+                status: TodoStatus::Pending,
+            },
+            TodoItem {
+                id: 4,
+                text: "Rewrite the lexer".into(),
+                status: TodoStatus::Cancelled,
+            },
+        ]);
+        let rendered = render_lines(&cell.display_lines(40)).join("\n");
+        insta::assert_snapshot!(rendered);
+    }
+
+    #[test]
+    fn empty_todo_list_snapshot() {
+        let cell = new_todo_list(Vec::new());
+        let rendered = render_lines(&cell.display_lines(40)).join("\n");
+        insta::assert_snapshot!(rendered);
+    }
+
     #[test]
     fn plan_update_does_not_split_url_like_tokens_in_note_or_step() {
         let note_url =
@@ -4122,4 +4752,54 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn patch_checks_cell_lists_reformatted_files_and_failure_output() {
+        use codex_protocol::protocol::PatchCheckKind;
+        use codex_protocol::protocol::PatchCheckResult;
+
+        let cell = new_patch_checks(vec![
+            PatchCheckResult {
+                name: "rustfmt".to_string(),
+                kind: PatchCheckKind::Format,
+                exit_code: 0,
+                formatted_files: vec![PathBuf::from("src/lib.rs")],
+                output: String::new(),
+            },
+            PatchCheckResult {
+                name: "clippy".to_string(),
+                kind: PatchCheckKind::Lint,
+                exit_code: 101,
+                formatted_files: Vec::new(),
+                output: "error: unused variable: `x`".to_string(),
+            },
+        ]);
+        assert_eq!(
+            render_transcript(&cell),
+            vec![
+                "✓ rustfmt reformatted src/lib.rs".to_string(),
+                "✘ clippy failed (exit 101)".to_string(),
+                "  └ error: unused variable: `x`".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn dry_run_exec_cell_notes_the_command_was_not_run() {
+        let cwd = PathBuf::from("/repo");
+        let cell = new_dry_run_action(
+            DryRunAction::Exec {
+                command: vec!["cargo".to_string(), "publish".to_string()],
+                cwd: cwd.join("crates/core"),
+            },
+            &cwd,
+        );
+        assert_eq!(
+            render_transcript(&cell),
+            vec![
+                "• Would run cargo publish".to_string(),
+                "  └ dry run, not executed (in crates/core)".to_string(),
+            ]
+        );
+    }
 }
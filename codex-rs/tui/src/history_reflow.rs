@@ -0,0 +1,125 @@
+//! Re-wrap the scrollback after the terminal width changes.
+//!
+//! History lines are wrapped when they are written to the terminal, so widening or narrowing the
+//! window leaves them wrapped for the old width. Once a new width has held for
+//! [`REFLOW_DEBOUNCE`], `App` clears the terminal and renders the transcript again from its cells.
+//! Cells that were moved to the spill file (see `transcript_spill`) keep their old wrapping.
+
+use std::time::Duration;
+use std::time::Instant;
+
+/// Resizing produces a burst of events; wait for the width to settle before re-rendering.
+pub(crate) const REFLOW_DEBOUNCE: Duration = Duration::from_millis(150);
+
+#[derive(Debug, Default)]
+pub(crate) struct HistoryReflow {
+    /// Width the history in the terminal is wrapped at; `None` until something is written.
+    width: Option<u16>,
+    /// A width that differs from `width` and when it will have held long enough.
+    pending: Option<(u16, Instant)>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum ReflowAction {
+    None,
+    /// Check again after this long.
+    WaitFor(Duration),
+    Reflow,
+}
+
+impl HistoryReflow {
+    /// Record that history was written at `width`. Lines written during a pending resize do not
+    /// cancel it: the reflow re-renders them too.
+    pub(crate) fn note_written(&mut self, width: u16) {
+        self.width.get_or_insert(width);
+    }
+
+    /// Record that the whole history was just rendered at `width`.
+    pub(crate) fn reflowed(&mut self, width: u16) {
+        self.width = Some(width);
+        self.pending = None;
+    }
+
+    /// Forget the history, e.g. after the terminal was cleared.
+    pub(crate) fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Decide what to do on a frame drawn at `width`.
+    pub(crate) fn poll(&mut self, width: u16, now: Instant) -> ReflowAction {
+        let Some(current) = self.width else {
+            return ReflowAction::None;
+        };
+        if width == current {
+            self.pending = None;
+            return ReflowAction::None;
+        }
+        match self.pending {
+            Some((pending_width, due)) if pending_width == width => {
+                if now >= due {
+                    ReflowAction::Reflow
+                } else {
+                    ReflowAction::WaitFor(due - now)
+                }
+            }
+            _ => {
+                self.pending = Some((width, now + REFLOW_DEBOUNCE));
+                ReflowAction::WaitFor(REFLOW_DEBOUNCE)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn reflows_once_the_new_width_settles() {
+        let start = Instant::now();
+        let mut reflow = HistoryReflow::default();
+        assert_eq!(reflow.poll(100, start), ReflowAction::None);
+
+        reflow.note_written(80);
+        assert_eq!(reflow.poll(80, start), ReflowAction::None);
+        assert_eq!(
+            reflow.poll(100, start),
+            ReflowAction::WaitFor(REFLOW_DEBOUNCE)
+        );
+        // Still resizing: the wait starts over at the newest width.
+        let later = start + Duration::from_millis(100);
+        assert_eq!(
+            reflow.poll(120, later),
+            ReflowAction::WaitFor(REFLOW_DEBOUNCE)
+        );
+        reflow.note_written(120);
+        assert_eq!(
+            reflow.poll(120, later + REFLOW_DEBOUNCE),
+            ReflowAction::Reflow
+        );
+
+        reflow.reflowed(120);
+        assert_eq!(
+            reflow.poll(120, later + REFLOW_DEBOUNCE),
+            ReflowAction::None
+        );
+    }
+
+    #[test]
+    fn returning_to_the_old_width_cancels_the_reflow() {
+        let start = Instant::now();
+        let mut reflow = HistoryReflow::default();
+        reflow.note_written(80);
+
+        assert_eq!(
+            reflow.poll(90, start),
+            ReflowAction::WaitFor(REFLOW_DEBOUNCE)
+        );
+        assert_eq!(reflow.poll(80, start), ReflowAction::None);
+        assert_eq!(
+            reflow.poll(90, start + REFLOW_DEBOUNCE),
+            ReflowAction::WaitFor(REFLOW_DEBOUNCE)
+        );
+    }
+}
@@ -0,0 +1,525 @@
+//! Renderers for tool calls recorded in a resumed session.
+//!
+//! Rollouts always keep the model-facing `ResponseItem`s for tool calls, but the matching tool
+//! events are only persisted in extended mode. On resume, core replays those items as
+//! `RawResponseItem` events; [`HistoryCellRegistry`] pairs each call with its output and hands
+//! the finished call to the first [`ToolCallRenderer`] that claims the tool.
+//!
+//! Renderers translate a call back into the begin/end events the live session emitted, so
+//! resumed history goes through the same `ChatWidget` handlers and cells as a live turn and looks
+//! identical: exploration calls group into one cell, outputs truncate the same way, and MCP
+//! results keep their images. Support for another tool is a renderer added to
+//! [`HistoryCellRegistry::default`], not a new arm in the event dispatch in `chatwidget.rs`.
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::path::Path;
+use std::time::Duration;
+
+use codex_apply_patch::Hunk;
+use codex_protocol::mcp::CallToolResult;
+use codex_protocol::models::LocalShellAction;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::plan_tool::UpdatePlanArgs;
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::ExecCommandBeginEvent;
+use codex_protocol::protocol::ExecCommandEndEvent;
+use codex_protocol::protocol::ExecCommandSource;
+use codex_protocol::protocol::ExecCommandStatus;
+use codex_protocol::protocol::FileChange;
+use codex_protocol::protocol::McpInvocation;
+use codex_protocol::protocol::McpToolCallBeginEvent;
+use codex_protocol::protocol::McpToolCallEndEvent;
+use codex_protocol::protocol::PatchApplyBeginEvent;
+use codex_protocol::protocol::PatchApplyEndEvent;
+use codex_protocol::protocol::PatchApplyStatus;
+use codex_protocol::protocol::ViewImageToolCallEvent;
+use codex_shell_command::parse_command::parse_command;
+use serde::Deserialize;
+use similar::TextDiff;
+
+/// A tool call from a resumed rollout, paired with its output.
+#[derive(Debug)]
+pub(crate) struct RecordedToolCall {
+    pub(crate) call_id: String,
+    pub(crate) tool_name: String,
+    /// JSON arguments for function tools, the raw input for freeform tools.
+    pub(crate) arguments: String,
+    pub(crate) output: String,
+    pub(crate) success: Option<bool>,
+    /// Whether the rollout already replayed a tool event for this call (extended persistence).
+    pub(crate) reported: bool,
+}
+
+pub(crate) trait ToolCallRenderer {
+    fn handles(&self, tool_name: &str) -> bool;
+
+    /// The events a live session emits for this call, in order.
+    fn render(&self, call: &RecordedToolCall, cwd: &Path) -> Option<Vec<EventMsg>>;
+}
+
+pub(crate) struct HistoryCellRegistry {
+    renderers: Vec<Box<dyn ToolCallRenderer>>,
+    /// Calls waiting for their output, keyed by call id: `(tool_name, arguments)`.
+    pending: HashMap<String, (String, String)>,
+    reported: HashSet<String>,
+}
+
+impl Default for HistoryCellRegistry {
+    fn default() -> Self {
+        let mut registry = Self {
+            renderers: Vec::new(),
+            pending: HashMap::new(),
+            reported: HashSet::new(),
+        };
+        registry.register(Box::new(ExecRenderer));
+        registry.register(Box::new(ApplyPatchRenderer));
+        registry.register(Box::new(PlanRenderer));
+        registry.register(Box::new(ViewImageRenderer));
+        registry.register(Box::new(McpRenderer));
+        registry
+    }
+}
+
+impl HistoryCellRegistry {
+    /// Add a renderer. Earlier registrations win when several claim the same tool.
+    pub(crate) fn register(&mut self, renderer: Box<dyn ToolCallRenderer>) {
+        self.renderers.push(renderer);
+    }
+
+    /// Note replayed tool events so calls they already describe are not rendered twice.
+    pub(crate) fn observe_event(&mut self, event: &EventMsg) {
+        let call_id = match event {
+            EventMsg::ExecCommandEnd(ev) => &ev.call_id,
+            EventMsg::PatchApplyEnd(ev) => &ev.call_id,
+            EventMsg::McpToolCallEnd(ev) => &ev.call_id,
+            EventMsg::ViewImageToolCall(ev) => &ev.call_id,
+            _ => return,
+        };
+        self.reported.insert(call_id.clone());
+    }
+
+    /// Feed the next replayed response item. Returns the events to dispatch once a call's output
+    /// arrives.
+    pub(crate) fn on_response_item(
+        &mut self,
+        item: ResponseItem,
+        cwd: &Path,
+    ) -> Option<Vec<EventMsg>> {
+        match item {
+            ResponseItem::FunctionCall {
+                name,
+                arguments,
+                call_id,
+                ..
+            } => {
+                self.pending.insert(call_id, (name, arguments));
+                None
+            }
+            ResponseItem::CustomToolCall {
+                name,
+                input,
+                call_id,
+                ..
+            } => {
+                self.pending.insert(call_id, (name, input));
+                None
+            }
+            ResponseItem::LocalShellCall {
+                call_id: Some(call_id),
+                action: LocalShellAction::Exec(exec),
+                ..
+            } => {
+                let arguments = serde_json::json!({
+                    "command": exec.command,
+                    "workdir": exec.working_directory,
+                });
+                self.pending
+                    .insert(call_id, ("local_shell".to_string(), arguments.to_string()));
+                None
+            }
+            ResponseItem::FunctionCallOutput { call_id, output }
+            | ResponseItem::CustomToolCallOutput { call_id, output } => {
+                let (tool_name, arguments) = self.pending.remove(&call_id)?;
+                let call = RecordedToolCall {
+                    reported: self.reported.remove(&call_id),
+                    call_id,
+                    tool_name,
+                    arguments,
+                    output: output.body.to_text().unwrap_or_default(),
+                    success: output.success,
+                };
+                self.renderers
+                    .iter()
+                    .find(|renderer| renderer.handles(&call.tool_name))?
+                    .render(&call, cwd)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// `shell`, `shell_command`, and `local_shell` calls, shown as exec cells.
+struct ExecRenderer;
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum CommandArg {
+    Argv(Vec<String>),
+    Script(String),
+}
+
+#[derive(Deserialize)]
+struct ExecArgs {
+    command: CommandArg,
+    workdir: Option<String>,
+}
+
+/// Output shape of `format_exec_output_for_model_structured`.
+#[derive(Deserialize)]
+struct StructuredExecOutput {
+    output: String,
+    metadata: ExecMetadata,
+}
+
+#[derive(Deserialize)]
+struct ExecMetadata {
+    exit_code: i32,
+    duration_seconds: f32,
+}
+
+impl ToolCallRenderer for ExecRenderer {
+    fn handles(&self, tool_name: &str) -> bool {
+        matches!(
+            tool_name,
+            "shell" | "container.exec" | "local_shell" | "shell_command"
+        )
+    }
+
+    fn render(&self, call: &RecordedToolCall, cwd: &Path) -> Option<Vec<EventMsg>> {
+        if call.reported {
+            return None;
+        }
+        let args: ExecArgs = serde_json::from_str(&call.arguments).ok()?;
+        let command = match args.command {
+            CommandArg::Argv(argv) => argv,
+            CommandArg::Script(script) => vec!["bash".to_string(), "-lc".to_string(), script],
+        };
+        let cwd = args
+            .workdir
+            .map_or_else(|| cwd.to_path_buf(), |dir| cwd.join(dir));
+        let parsed_cmd = parse_command(&command);
+        let (exit_code, duration, output) = parse_exec_output(call);
+        Some(vec![
+            EventMsg::ExecCommandBegin(ExecCommandBeginEvent {
+                call_id: call.call_id.clone(),
+                process_id: None,
+                turn_id: String::new(),
+                command: command.clone(),
+                cwd: cwd.clone(),
+                parsed_cmd: parsed_cmd.clone(),
+                source: ExecCommandSource::Agent,
+                interaction_input: None,
+            }),
+            EventMsg::ExecCommandEnd(ExecCommandEndEvent {
+                call_id: call.call_id.clone(),
+                process_id: None,
+                turn_id: String::new(),
+                command,
+                cwd,
+                parsed_cmd,
+                source: ExecCommandSource::Agent,
+                interaction_input: None,
+                stdout: output.clone(),
+                stderr: String::new(),
+                aggregated_output: output.clone(),
+                exit_code,
+                duration,
+                formatted_output: output,
+                status: if exit_code == 0 {
+                    ExecCommandStatus::Completed
+                } else {
+                    ExecCommandStatus::Failed
+                },
+            }),
+        ])
+    }
+}
+
+/// Recover the exit code, duration, and output from either exec output format sent to the
+/// model. Anything else is shown as-is, with the exit code taken from the success flag.
+fn parse_exec_output(call: &RecordedToolCall) -> (i32, Duration, String) {
+    if let Ok(structured) = serde_json::from_str::<StructuredExecOutput>(&call.output) {
+        return (
+            structured.metadata.exit_code,
+            Duration::from_secs_f32(structured.metadata.duration_seconds.max(0.0)),
+            structured.output,
+        );
+    }
+    if let Some(rest) = call.output.strip_prefix("Exit code: ")
+        && let Some((code, rest)) = rest.split_once('\n')
+        && let Ok(exit_code) = code.trim().parse()
+        && let Some((_, output)) = rest.split_once("Output:\n")
+    {
+        let duration = rest
+            .strip_prefix("Wall time: ")
+            .and_then(|rest| rest.split_once(" seconds"))
+            .and_then(|(secs, _)| secs.parse::<f32>().ok())
+            .map_or(Duration::ZERO, |secs| {
+                Duration::from_secs_f32(secs.max(0.0))
+            });
+        return (exit_code, duration, output.to_string());
+    }
+    let exit_code = if call.success == Some(false) { 1 } else { 0 };
+    (exit_code, Duration::ZERO, call.output.clone())
+}
+
+/// `apply_patch` calls, shown as the same "Edited" cell a live patch gets.
+struct ApplyPatchRenderer;
+
+#[derive(Deserialize)]
+struct ApplyPatchArgs {
+    input: String,
+}
+
+impl ToolCallRenderer for ApplyPatchRenderer {
+    fn handles(&self, tool_name: &str) -> bool {
+        tool_name == "apply_patch"
+    }
+
+    fn render(&self, call: &RecordedToolCall, cwd: &Path) -> Option<Vec<EventMsg>> {
+        let patch = serde_json::from_str::<ApplyPatchArgs>(&call.arguments)
+            .map_or_else(|_| call.arguments.clone(), |args| args.input);
+        let changes = patch_changes(&patch, cwd)?;
+        let success = call.success != Some(false);
+        let mut events = vec![EventMsg::PatchApplyBegin(PatchApplyBeginEvent {
+            call_id: call.call_id.clone(),
+            turn_id: String::new(),
+            auto_approved: true,
+            changes: changes.clone(),
+        })];
+        // With extended persistence the end event was replayed already.
+        if !call.reported {
+            events.push(EventMsg::PatchApplyEnd(PatchApplyEndEvent {
+                call_id: call.call_id.clone(),
+                turn_id: String::new(),
+                stdout: if success {
+                    call.output.clone()
+                } else {
+                    String::new()
+                },
+                stderr: if success {
+                    String::new()
+                } else {
+                    call.output.clone()
+                },
+                success,
+                changes,
+                status: if success {
+                    PatchApplyStatus::Completed
+                } else {
+                    PatchApplyStatus::Failed
+                },
+            }));
+        }
+        Some(events)
+    }
+}
+
+/// The changes a patch describes. The files have usually moved on since the session ran, so
+/// update diffs are rebuilt from the patch alone and their line numbers are relative.
+fn patch_changes(patch: &str, cwd: &Path) -> Option<HashMap<std::path::PathBuf, FileChange>> {
+    let args = codex_apply_patch::parse_patch(patch).ok()?;
+    let changes = args
+        .hunks
+        .into_iter()
+        .map(|hunk| {
+            let path = hunk.resolve_path(cwd);
+            let change = match hunk {
+                Hunk::AddFile { contents, .. } => FileChange::Add { content: contents },
+                Hunk::DeleteFile { .. } => FileChange::Delete {
+                    content: String::new(),
+                },
+                Hunk::UpdateFile {
+                    move_path, chunks, ..
+                } => {
+                    let (mut old, mut new) = (String::new(), String::new());
+                    for chunk in &chunks {
+                        for line in &chunk.old_lines {
+                            old.push_str(line);
+                            old.push('\n');
+                        }
+                        for line in &chunk.new_lines {
+                            new.push_str(line);
+                            new.push('\n');
+                        }
+                    }
+                    FileChange::Update {
+                        unified_diff: TextDiff::from_lines(&old, &new)
+                            .unified_diff()
+                            .context_radius(1)
+                            .to_string(),
+                        move_path: move_path.map(|dest| cwd.join(dest)),
+                    }
+                }
+            };
+            (path, change)
+        })
+        .collect();
+    Some(changes)
+}
+
+/// `update_plan` calls, shown as the plan checklist.
+struct PlanRenderer;
+
+impl ToolCallRenderer for PlanRenderer {
+    fn handles(&self, tool_name: &str) -> bool {
+        tool_name == "update_plan"
+    }
+
+    fn render(&self, call: &RecordedToolCall, _cwd: &Path) -> Option<Vec<EventMsg>> {
+        let update: UpdatePlanArgs = serde_json::from_str(&call.arguments).ok()?;
+        Some(vec![EventMsg::PlanUpdate(update)])
+    }
+}
+
+/// `view_image` calls, shown as the attached image path.
+struct ViewImageRenderer;
+
+#[derive(Deserialize)]
+struct ViewImageArgs {
+    path: String,
+}
+
+impl ToolCallRenderer for ViewImageRenderer {
+    fn handles(&self, tool_name: &str) -> bool {
+        tool_name == "view_image"
+    }
+
+    fn render(&self, call: &RecordedToolCall, cwd: &Path) -> Option<Vec<EventMsg>> {
+        if call.reported {
+            return None;
+        }
+        let args: ViewImageArgs = serde_json::from_str(&call.arguments).ok()?;
+        Some(vec![EventMsg::ViewImageToolCall(ViewImageToolCallEvent {
+            call_id: call.call_id.clone(),
+            path: cwd.join(args.path),
+        })])
+    }
+}
+
+/// MCP tool calls (`mcp__<server>__<tool>`). The output is what the model saw: the result's
+/// content list serialized as JSON, or plain text when that is all that is left.
+struct McpRenderer;
+
+impl ToolCallRenderer for McpRenderer {
+    fn handles(&self, tool_name: &str) -> bool {
+        tool_name.starts_with("mcp__")
+    }
+
+    fn render(&self, call: &RecordedToolCall, _cwd: &Path) -> Option<Vec<EventMsg>> {
+        if call.reported {
+            return None;
+        }
+        let (server, tool) = call.tool_name.strip_prefix("mcp__")?.split_once("__")?;
+        let invocation = McpInvocation {
+            server: server.to_string(),
+            tool: tool.to_string(),
+            arguments: serde_json::from_str(&call.arguments).ok(),
+        };
+        let content = serde_json::from_str::<Vec<serde_json::Value>>(&call.output)
+            .ok()
+            .filter(|items| items.iter().all(|item| item.get("type").is_some()))
+            .unwrap_or_else(|| vec![serde_json::json!({ "type": "text", "text": call.output })]);
+        Some(vec![
+            EventMsg::McpToolCallBegin(McpToolCallBeginEvent {
+                call_id: call.call_id.clone(),
+                invocation: invocation.clone(),
+            }),
+            EventMsg::McpToolCallEnd(McpToolCallEndEvent {
+                call_id: call.call_id.clone(),
+                invocation,
+                duration: Duration::ZERO,
+                result: Ok(CallToolResult {
+                    content,
+                    structured_content: None,
+                    is_error: Some(call.success == Some(false)),
+                    meta: None,
+                }),
+            }),
+        ])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::models::FunctionCallOutputPayload;
+    use pretty_assertions::assert_eq;
+    use std::path::PathBuf;
+
+    fn function_call(name: &str, arguments: &str) -> ResponseItem {
+        ResponseItem::FunctionCall {
+            id: None,
+            name: name.to_string(),
+            arguments: arguments.to_string(),
+            call_id: "call-1".to_string(),
+        }
+    }
+
+    fn output(text: &str) -> ResponseItem {
+        ResponseItem::FunctionCallOutput {
+            call_id: "call-1".to_string(),
+            output: FunctionCallOutputPayload::from_text(text.to_string()),
+        }
+    }
+
+    #[test]
+    fn shell_calls_replay_as_live_exec_events() {
+        let cwd = PathBuf::from("/repo");
+        let mut registry = HistoryCellRegistry::default();
+        assert!(
+            registry
+                .on_response_item(
+                    function_call("shell", r#"{"command":["ls","-a"],"workdir":"src"}"#),
+                    &cwd,
+                )
+                .is_none()
+        );
+
+        let Some(events) = registry.on_response_item(
+            output("Exit code: 2\nWall time: 0.5 seconds\nOutput:\nno such file\n"),
+            &cwd,
+        ) else {
+            panic!("expected exec events");
+        };
+        let [
+            EventMsg::ExecCommandBegin(begin),
+            EventMsg::ExecCommandEnd(end),
+        ] = events.as_slice()
+        else {
+            panic!("expected exec begin and end events, got {events:?}");
+        };
+        assert_eq!(begin.call_id, end.call_id);
+        assert_eq!(begin.parsed_cmd, end.parsed_cmd);
+        assert_eq!(end.command, vec!["ls".to_string(), "-a".to_string()]);
+        assert_eq!(end.cwd, cwd.join("src"));
+        assert_eq!(end.exit_code, 2);
+        assert_eq!(end.duration, Duration::from_millis(500));
+        assert_eq!(end.aggregated_output, "no such file\n");
+        assert_eq!(end.status, ExecCommandStatus::Failed);
+    }
+
+    #[test]
+    fn calls_already_reported_by_events_are_skipped() {
+        let cwd = PathBuf::from("/repo");
+        let mut registry = HistoryCellRegistry::default();
+        registry.on_response_item(function_call("view_image", r#"{"path":"a.png"}"#), &cwd);
+        registry.observe_event(&EventMsg::ViewImageToolCall(ViewImageToolCallEvent {
+            call_id: "call-1".to_string(),
+            path: cwd.join("a.png"),
+        }));
+
+        assert!(registry.on_response_item(output("ok"), &cwd).is_none());
+    }
+}
@@ -295,6 +295,7 @@ where
     let mut fg = Color::Reset;
     let mut bg = Color::Reset;
     let mut last_modifier = Modifier::empty();
+    let capabilities = crate::terminal_capabilities::get();
     for span in content {
         let mut modifier = Modifier::empty();
         modifier.insert(span.style.add_modifier);
@@ -312,13 +313,16 @@ where
         if next_fg != fg || next_bg != bg {
             queue!(
                 writer,
-                SetColors(Colors::new(next_fg.into(), next_bg.into()))
+                SetColors(Colors::new(
+                    capabilities.adapt_color(next_fg).into(),
+                    capabilities.adapt_color(next_bg).into()
+                ))
             )?;
             fg = next_fg;
             bg = next_bg;
         }
 
-        queue!(writer, Print(span.content.clone()))?;
+        queue!(writer, Print(capabilities.adapt_text(&span.content)))?;
     }
 
     queue!(
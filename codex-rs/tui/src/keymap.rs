@@ -0,0 +1,427 @@
+//! Registry of the TUI's key bindings, grouped by the context in which they apply.
+//!
+//! `App` matches its global shortcuts against the constants defined here, and the `Ctrl+/`
+//! cheat sheet renders [`KEYMAP`] and the [`ConfiguredKeys`] followed by the slash commands that
+//! are currently available, so changing a binding in this file updates both the behavior and its
+//! documentation.
+
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEvent;
+use crossterm::event::KeyEventKind;
+use crossterm::event::KeyModifiers;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::text::Span;
+
+use crate::key_hint;
+use crate::key_hint::KeyBinding;
+use crate::slash_command::SlashCommand;
+
+pub(crate) const SHOW_TRANSCRIPT: KeyBinding = key_hint::ctrl(KeyCode::Char('t'));
+pub(crate) const SHOW_LOG: KeyBinding = key_hint::ctrl(KeyCode::Char('o'));
+pub(crate) const SHOW_KEYMAP: KeyBinding = key_hint::ctrl(KeyCode::Char('/'));
+/// Terminals without keyboard enhancements encode `Ctrl+/` as `0x1f`, which crossterm reports
+/// as `Ctrl+7`.
+pub(crate) const SHOW_KEYMAP_LEGACY: KeyBinding = key_hint::ctrl(KeyCode::Char('7'));
+/// `Ctrl+P` / `Ctrl+N` stay with the composer, which uses them to browse message history.
+pub(crate) const COMMAND_PALETTE: KeyBinding = key_hint::alt(KeyCode::Char('p'));
+pub(crate) const CLEAR_SCREEN: KeyBinding = key_hint::ctrl(KeyCode::Char('l'));
+pub(crate) const EXTERNAL_EDITOR: KeyBinding = key_hint::ctrl(KeyCode::Char('g'));
+pub(crate) const RETRY: KeyBinding = key_hint::ctrl(KeyCode::Char('r'));
+pub(crate) const SPELLING_SUGGESTIONS: KeyBinding = key_hint::alt(KeyCode::Char('s'));
+/// Default for `tui.interrupt_key`.
+pub(crate) const INTERRUPT: KeyBinding = key_hint::plain(KeyCode::Esc);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum KeyContext {
+    Global,
+    Composer,
+    Pager,
+}
+
+impl KeyContext {
+    const ALL: [KeyContext; 3] = [KeyContext::Global, KeyContext::Composer, KeyContext::Pager];
+
+    fn title(self) -> &'static str {
+        match self {
+            KeyContext::Global => "Anywhere",
+            KeyContext::Composer => "Composer",
+            KeyContext::Pager => "Transcript, log, and other overlays",
+        }
+    }
+}
+
+pub(crate) struct KeymapEntry<'a> {
+    pub(crate) context: KeyContext,
+    /// Alternative bindings for the same action.
+    pub(crate) keys: &'a [KeyBinding],
+    pub(crate) description: &'static str,
+}
+
+/// Bindings that users can remap in config, as resolved for the running session.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct ConfiguredKeys {
+    /// `tui.interrupt_key`.
+    pub(crate) interrupt: KeyBinding,
+}
+
+impl Default for ConfiguredKeys {
+    fn default() -> Self {
+        Self {
+            interrupt: INTERRUPT,
+        }
+    }
+}
+
+impl ConfiguredKeys {
+    /// Cheat sheet entries for these bindings, listed after the [`KEYMAP`] entries of their
+    /// context.
+    fn entries(&self) -> [KeymapEntry<'_>; 1] {
+        [KeymapEntry {
+            context: KeyContext::Global,
+            keys: std::slice::from_ref(&self.interrupt),
+            description: "stop the response and keep its partial text (tui.interrupt_key)",
+        }]
+    }
+}
+
+pub(crate) const KEYMAP: &[KeymapEntry<'static>] = &[
+    KeymapEntry {
+        context: KeyContext::Global,
+        keys: &[SHOW_TRANSCRIPT],
+        description: "view transcript",
+    },
+    KeymapEntry {
+        context: KeyContext::Global,
+        keys: &[SHOW_LOG],
+        description: "view log",
+    },
+    KeymapEntry {
+        context: KeyContext::Global,
+        keys: &[SHOW_KEYMAP],
+        description: "show this cheat sheet",
+    },
+    KeymapEntry {
+        context: KeyContext::Global,
+        keys: &[COMMAND_PALETTE],
+        description: "open the command palette from the composer",
+    },
+    KeymapEntry {
+        context: KeyContext::Global,
+        keys: &[CLEAR_SCREEN],
+        description: "clear the screen",
+    },
+    KeymapEntry {
+        context: KeyContext::Global,
+        keys: &[RETRY],
+        description: "retry the last response",
+    },
+    KeymapEntry {
+        context: KeyContext::Global,
+        keys: &[key_hint::ctrl(KeyCode::Char('c'))],
+        description: "interrupt the running turn, or quit when pressed twice",
+    },
+    KeymapEntry {
+        context: KeyContext::Composer,
+        keys: &[key_hint::plain(KeyCode::Enter)],
+        description: "send message",
+    },
+    KeymapEntry {
+        context: KeyContext::Composer,
+        keys: &[key_hint::plain(KeyCode::Tab)],
+        description: "queue message while a turn is running",
+    },
+    KeymapEntry {
+        context: KeyContext::Composer,
+        keys: &[
+            key_hint::shift(KeyCode::Enter),
+            key_hint::ctrl(KeyCode::Char('j')),
+        ],
+        description: "insert newline",
+    },
+    KeymapEntry {
+        context: KeyContext::Composer,
+        keys: &[
+            key_hint::plain(KeyCode::Up),
+            key_hint::plain(KeyCode::Down),
+            key_hint::ctrl(KeyCode::Char('p')),
+            key_hint::ctrl(KeyCode::Char('n')),
+        ],
+        description: "browse message history",
+    },
+    KeymapEntry {
+        context: KeyContext::Composer,
+        keys: &[key_hint::plain(KeyCode::Char('/'))],
+        description: "open commands",
+    },
+    KeymapEntry {
+        context: KeyContext::Composer,
+        keys: &[key_hint::plain(KeyCode::Char('!'))],
+        description: "run a shell command",
+    },
+    KeymapEntry {
+        context: KeyContext::Composer,
+        keys: &[key_hint::plain(KeyCode::Char('@'))],
+        description: "insert a file path",
+    },
+    KeymapEntry {
+        context: KeyContext::Composer,
+        keys: &[key_hint::ctrl(KeyCode::Char('v'))],
+        description: "paste image",
+    },
+    KeymapEntry {
+        context: KeyContext::Composer,
+        keys: &[EXTERNAL_EDITOR],
+        description: "edit message in external editor",
+    },
+    KeymapEntry {
+        context: KeyContext::Composer,
+        keys: &[SPELLING_SUGGESTIONS],
+        description: "fix the misspelling at the cursor (tui.spellcheck)",
+    },
+    KeymapEntry {
+        context: KeyContext::Composer,
+        keys: &[key_hint::plain(KeyCode::Esc)],
+        description: "press twice with an empty composer to edit the previous message",
+    },
+    KeymapEntry {
+        context: KeyContext::Composer,
+        keys: &[key_hint::plain(KeyCode::Char('?'))],
+        description: "show shortcuts with an empty composer",
+    },
+    KeymapEntry {
+        context: KeyContext::Pager,
+        keys: &[key_hint::plain(KeyCode::Up), key_hint::plain(KeyCode::Down)],
+        description: "scroll",
+    },
+    KeymapEntry {
+        context: KeyContext::Pager,
+        keys: &[
+            key_hint::plain(KeyCode::PageUp),
+            key_hint::plain(KeyCode::PageDown),
+        ],
+        description: "page",
+    },
+    KeymapEntry {
+        context: KeyContext::Pager,
+        keys: &[
+            key_hint::plain(KeyCode::Home),
+            key_hint::plain(KeyCode::End),
+        ],
+        description: "jump to start or end",
+    },
+    KeymapEntry {
+        context: KeyContext::Pager,
+        keys: &[key_hint::plain(KeyCode::Char('q'))],
+        description: "close",
+    },
+];
+
+/// Whether `event` is the initial press of `binding`. Auto-repeat is ignored so holding a key
+/// that toggles an overlay does not open and close it repeatedly.
+pub(crate) fn is_initial_press(binding: KeyBinding, event: KeyEvent) -> bool {
+    event.kind == KeyEventKind::Press && binding.is_press(event)
+}
+
+/// Parse a user-configured binding such as `esc`, `ctrl-x`, or `ctrl+alt+s`.
+///
+/// Modifiers and the key are separated by `-` or `+` and matched case-insensitively. Returns
+/// `None` for anything that does not name exactly one key.
+pub(crate) fn parse_key_binding(spec: &str) -> Option<KeyBinding> {
+    let spec = spec.trim().to_ascii_lowercase();
+    let mut parts: Vec<&str> = spec.split(['-', '+']).collect();
+    // `ctrl--` and `ctrl++` name the separator characters themselves.
+    if spec.len() > 1 && (spec.ends_with("--") || spec.ends_with("++")) {
+        parts.truncate(parts.len() - 2);
+        parts.push(&spec[spec.len() - 1..]);
+    }
+    let (key, modifiers) = parts.split_last()?;
+    let mut mods = KeyModifiers::NONE;
+    for modifier in modifiers {
+        mods |= match *modifier {
+            "ctrl" | "control" => KeyModifiers::CONTROL,
+            "alt" | "option" | "meta" => KeyModifiers::ALT,
+            "shift" => KeyModifiers::SHIFT,
+            _ => return None,
+        };
+    }
+    let code = match *key {
+        "esc" | "escape" => KeyCode::Esc,
+        "enter" | "return" => KeyCode::Enter,
+        "tab" => KeyCode::Tab,
+        "backspace" => KeyCode::Backspace,
+        "delete" | "del" => KeyCode::Delete,
+        "space" => KeyCode::Char(' '),
+        "up" => KeyCode::Up,
+        "down" => KeyCode::Down,
+        "left" => KeyCode::Left,
+        "right" => KeyCode::Right,
+        "home" => KeyCode::Home,
+        "end" => KeyCode::End,
+        "pageup" | "pgup" => KeyCode::PageUp,
+        "pagedown" | "pgdn" => KeyCode::PageDown,
+        function if function.len() > 1 && function.starts_with('f') => KeyCode::F(
+            function[1..]
+                .parse()
+                .ok()
+                .filter(|n| (1..=12).contains(n))?,
+        ),
+        other => {
+            let mut chars = other.chars();
+            let ch = chars.next()?;
+            if chars.next().is_some() {
+                return None;
+            }
+            KeyCode::Char(ch)
+        }
+    };
+    Some(KeyBinding::new(code, mods))
+}
+
+/// Lines for the cheat sheet overlay: the key bindings grouped by context, with `configured`
+/// standing in for the remappable ones, then `commands`.
+pub(crate) fn cheat_sheet_lines(
+    commands: &[(&str, SlashCommand)],
+    configured: &ConfiguredKeys,
+) -> Vec<Line<'static>> {
+    let key_spans = |keys: &[KeyBinding]| -> Vec<Span<'static>> {
+        let mut spans = Vec::new();
+        for (idx, key) in keys.iter().enumerate() {
+            if idx > 0 {
+                spans.push(" / ".dim());
+            }
+            spans.push(Span::from(key).cyan());
+        }
+        spans
+    };
+    let configured_entries = configured.entries();
+    let entries = || KEYMAP.iter().chain(configured_entries.iter());
+    let key_width = entries()
+        .map(|entry| Line::from(key_spans(entry.keys)).width())
+        .chain(commands.iter().map(|(name, _)| name.len() + 1))
+        .max()
+        .unwrap_or(0);
+
+    let mut lines: Vec<Line<'static>> = Vec::new();
+    for context in KeyContext::ALL {
+        lines.push(context.title().bold().into());
+        for entry in entries().filter(|entry| entry.context == context) {
+            let mut spans = vec!["  ".into()];
+            let keys = key_spans(entry.keys);
+            let width = Line::from(keys.clone()).width();
+            spans.extend(keys);
+            spans.push(" ".repeat(key_width - width + 2).into());
+            spans.push(entry.description.into());
+            lines.push(spans.into());
+        }
+        lines.push("".into());
+    }
+
+    lines.push("Slash commands".bold().into());
+    for (name, command) in commands {
+        let name = format!("/{name}");
+        let padding = " ".repeat(key_width - name.len() + 2);
+        lines.push(
+            vec![
+                "  ".into(),
+                name.cyan(),
+                padding.into(),
+                command.description().into(),
+            ]
+            .into(),
+        );
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use insta::assert_snapshot;
+
+    fn render(lines: &[Line<'static>]) -> String {
+        lines
+            .iter()
+            .map(|line| {
+                line.spans
+                    .iter()
+                    .map(|span| span.content.as_ref())
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn cheat_sheet_lists_bindings_by_context_and_commands() {
+        let lines = cheat_sheet_lines(
+            &[("model", SlashCommand::Model), ("diff", SlashCommand::Diff)],
+            &ConfiguredKeys::default(),
+        );
+        assert_snapshot!(render(&lines));
+    }
+
+    #[test]
+    fn cheat_sheet_shows_the_configured_interrupt_key() {
+        let configured = ConfiguredKeys {
+            interrupt: key_hint::ctrl(KeyCode::Char('g')),
+        };
+        let rendered = render(&cheat_sheet_lines(&[], &configured));
+        let interrupt_line = rendered
+            .lines()
+            .find(|line| line.ends_with("(tui.interrupt_key)"))
+            .expect("interrupt entry");
+        assert!(
+            interrupt_line.trim_start().starts_with("ctrl + g "),
+            "expected the remapped key, got {interrupt_line:?}"
+        );
+    }
+
+    #[test]
+    fn app_shortcuts_leave_history_keys_to_the_composer() {
+        let app_shortcuts = [
+            SHOW_TRANSCRIPT,
+            SHOW_LOG,
+            SHOW_KEYMAP,
+            SHOW_KEYMAP_LEGACY,
+            COMMAND_PALETTE,
+            CLEAR_SCREEN,
+            EXTERNAL_EDITOR,
+            RETRY,
+            SPELLING_SUGGESTIONS,
+        ];
+        for key in ['p', 'n'] {
+            let event = KeyEvent::new(KeyCode::Char(key), KeyModifiers::CONTROL);
+            assert!(
+                !app_shortcuts
+                    .iter()
+                    .any(|binding| is_initial_press(*binding, event)),
+                "ctrl+{key} is taken by an app shortcut"
+            );
+        }
+    }
+
+    #[test]
+    fn parse_key_binding_accepts_modifiers_and_named_keys() {
+        assert_eq!(parse_key_binding("esc"), Some(INTERRUPT));
+        assert_eq!(
+            parse_key_binding("Ctrl-X"),
+            Some(key_hint::ctrl(KeyCode::Char('x')))
+        );
+        assert_eq!(
+            parse_key_binding("ctrl+alt+s"),
+            Some(key_hint::ctrl_alt(KeyCode::Char('s')))
+        );
+        assert_eq!(
+            parse_key_binding("ctrl--"),
+            Some(key_hint::ctrl(KeyCode::Char('-')))
+        );
+        assert_eq!(
+            parse_key_binding("f5"),
+            Some(key_hint::plain(KeyCode::F(5)))
+        );
+        assert_eq!(parse_key_binding("hyper-x"), None);
+        assert_eq!(parse_key_binding("ctrl-xy"), None);
+        assert_eq!(parse_key_binding(""), None);
+    }
+}
@@ -16,6 +16,7 @@ use codex_core::ThreadSortKey;
 use codex_core::auth::AuthMode;
 use codex_core::auth::enforce_login_restrictions;
 use codex_core::check_execpolicy_for_warnings;
+use codex_core::check_rollout_lock;
 use codex_core::config::Config;
 use codex_core::config::ConfigBuilder;
 use codex_core::config::ConfigOverrides;
@@ -25,7 +26,11 @@ use codex_core::config::resolve_oss_provider;
 use codex_core::config_loader::CloudRequirementsLoader;
 use codex_core::config_loader::ConfigLoadError;
 use codex_core::config_loader::format_config_error_with_source;
+use codex_core::default_client::set_default_client_api_log;
+use codex_core::default_client::set_default_client_proxy;
 use codex_core::default_client::set_default_client_residency_requirement;
+use codex_core::default_client::set_default_client_tls;
+use codex_core::find_archived_thread_path_by_id_str;
 use codex_core::find_thread_path_by_id_str;
 use codex_core::find_thread_path_by_name_str;
 use codex_core::format_exec_policy_error_with_source;
@@ -63,56 +68,85 @@ mod app_backtrack;
 mod app_event;
 mod app_event_sender;
 mod ascii_animation;
+mod audio_attachment;
 #[cfg(all(not(target_os = "linux"), feature = "voice-input"))]
 mod audio_device;
 mod bottom_pane;
+mod change_dir;
 mod chatwidget;
+mod ci_log;
 mod cli;
 mod clipboard_paste;
 mod clipboard_text;
 mod collaboration_modes;
 mod color;
+mod config_reload;
+mod config_view;
+mod crash_recovery;
 pub mod custom_terminal;
 mod cwd_prompt;
 mod debug_config;
+mod diff_pane;
 mod diff_render;
 mod exec_cell;
 mod exec_command;
 mod external_editor;
 mod file_search;
+mod file_tree;
 mod frames;
 mod get_git_diff;
 mod history_cell;
+mod history_reflow;
+mod history_registry;
 pub mod insert_history;
 mod key_hint;
+mod keymap;
 mod line_truncation;
 pub mod live_wrap;
 mod markdown;
 mod markdown_render;
 mod markdown_stream;
 mod mention_codec;
+mod model_compare;
 mod model_migration;
 mod multi_agents;
 mod notifications;
 pub mod onboarding;
 mod oss_selection;
 mod pager_overlay;
+mod paste_file;
+mod pinned_files;
+mod plain_repl;
+mod plan_steps;
 pub mod public_widgets;
 mod render;
+mod response_diff;
 mod resume_picker;
+mod scrollback_capture;
 mod selection_list;
+mod session_compare;
+mod session_lock;
 mod session_log;
+mod session_stats;
 mod shimmer;
 mod skills_helpers;
 mod slash_command;
+mod spellcheck;
 mod status;
 mod status_indicator_widget;
+mod status_line_command;
 mod streaming;
 mod style;
+mod terminal_badge;
+mod terminal_capabilities;
 mod terminal_palette;
+mod terminal_title;
 mod text_formatting;
 mod theme_picker;
+mod tmux;
 mod tooltips;
+mod transcript_spill;
+mod transcript_view;
 mod tui;
 mod ui_consts;
 pub mod update_action;
@@ -225,6 +259,7 @@ use codex_arg0::Arg0DispatchPaths;
 pub use markdown_render::render_markdown_text;
 pub use public_widgets::composer_input::ComposerAction;
 pub use public_widgets::composer_input::ComposerInput;
+pub use transcript_view::run_transcript_view;
 // (tests access modules directly within the crate)
 
 pub async fn run_main(mut cli: Cli, arg0_paths: Arg0DispatchPaths) -> std::io::Result<AppExitInfo> {
@@ -379,6 +414,8 @@ pub async fn run_main(mut cli: Cli, arg0_paths: Arg0DispatchPaths) -> std::io::R
         codex_linux_sandbox_exe: arg0_paths.codex_linux_sandbox_exe.clone(),
         main_execve_wrapper_exe: arg0_paths.main_execve_wrapper_exe.clone(),
         show_raw_agent_reasoning: cli.oss.then_some(true),
+        read_only: cli.read_only.then_some(true),
+        dry_run: cli.dry_run.then_some(true),
         additional_writable_roots: additional_dirs,
         ..Default::default()
     };
@@ -403,6 +440,18 @@ pub async fn run_main(mut cli: Cli, arg0_paths: Arg0DispatchPaths) -> std::io::R
     }
 
     set_default_client_residency_requirement(config.enforce_residency.value());
+    #[allow(clippy::print_stderr)]
+    if let Err(err) = set_default_client_proxy(config.outbound_proxy.as_ref()) {
+        eprintln!("Error configuring outbound proxy: {err}");
+        std::process::exit(1);
+    }
+    set_default_client_api_log(config.api_log_path.as_ref().map(AbsolutePathBuf::as_path));
+
+    #[allow(clippy::print_stderr)]
+    if let Err(err) = set_default_client_tls(config.tls.as_ref()) {
+        eprintln!("Error loading TLS certificates: {err}");
+        std::process::exit(1);
+    }
 
     if let Some(warning) =
         add_dir_warning_message(&cli.add_dir, config.permissions.sandbox_policy.get())
@@ -517,6 +566,17 @@ pub async fn run_main(mut cli: Cli, arg0_paths: Arg0DispatchPaths) -> std::io::R
         .with(otel_tracing_layer)
         .try_init();
 
+    if cli.plain {
+        if config.model_provider.requires_openai_auth
+            && get_login_status(&config) == LoginStatus::NotAuthenticated
+        {
+            return Err(std::io::Error::other(
+                "Not logged in. Run `codex login` first, or start `codex` without --plain.",
+            ));
+        }
+        return plain_repl::run_plain_repl(config, cli.prompt, cli.images).await;
+    }
+
     run_ratatui_app(
         cli,
         config,
@@ -532,7 +592,7 @@ pub async fn run_main(mut cli: Cli, arg0_paths: Arg0DispatchPaths) -> std::io::R
 async fn run_ratatui_app(
     cli: Cli,
     initial_config: Config,
-    overrides: ConfigOverrides,
+    mut overrides: ConfigOverrides,
     cli_kv_overrides: Vec<(String, toml::Value)>,
     mut cloud_requirements: CloudRequirementsLoader,
     feedback: codex_feedback::CodexFeedback,
@@ -550,6 +610,7 @@ async fn run_ratatui_app(
         tracing::error!("panic: {info}");
         prev_hook(info);
     }));
+    scrollback_capture::record_launch_position();
     let mut terminal = tui::init()?;
     terminal.clear()?;
 
@@ -660,12 +721,14 @@ async fn run_ratatui_app(
         })
     };
 
+    // Set when the user chose to recover a session that crashed.
+    let mut recovered: Option<crash_recovery::RecoverySnapshot> = None;
     let use_fork = cli.fork_picker || cli.fork_last || cli.fork_session_id.is_some();
     let session_selection = if use_fork {
         if let Some(id_str) = cli.fork_session_id.as_deref() {
             let is_uuid = Uuid::parse_str(id_str).is_ok();
             let path = if is_uuid {
-                find_thread_path_by_id_str(&config.codex_home, id_str).await?
+                find_thread_or_archived_path(&config.codex_home, id_str).await?
             } else {
                 find_thread_path_by_name_str(&config.codex_home, id_str).await?
             };
@@ -753,7 +816,7 @@ async fn run_ratatui_app(
     } else if let Some(id_str) = cli.resume_session_id.as_deref() {
         let is_uuid = Uuid::parse_str(id_str).is_ok();
         let path = if is_uuid {
-            find_thread_path_by_id_str(&config.codex_home, id_str).await?
+            find_thread_or_archived_path(&config.codex_home, id_str).await?
         } else {
             find_thread_path_by_name_str(&config.codex_home, id_str).await?
         };
@@ -835,10 +898,72 @@ async fn run_ratatui_app(
             }
             other => other,
         }
+    } else if let Some(snapshot) = crash_recovery::take(&config.codex_home) {
+        match crash_recovery::run_recovery_prompt(&mut tui, &snapshot).await? {
+            crash_recovery::RecoveryChoice::Exit => {
+                restore();
+                session_log::log_session_end();
+                return Ok(AppExitInfo {
+                    token_usage: codex_protocol::protocol::TokenUsage::default(),
+                    thread_id: None,
+                    thread_name: None,
+                    update_action: None,
+                    exit_reason: ExitReason::UserRequested,
+                });
+            }
+            crash_recovery::RecoveryChoice::StartFresh => {
+                resume_picker::SessionSelection::StartFresh
+            }
+            crash_recovery::RecoveryChoice::Recover => {
+                let target = match snapshot.rollout_path.clone() {
+                    Some(path) if path.exists() => resolve_session_thread_id(&path, None)
+                        .await
+                        .map(|thread_id| resume_picker::SessionTarget { path, thread_id }),
+                    _ => None,
+                };
+                recovered = Some(snapshot);
+                match target {
+                    Some(target) => resume_picker::SessionSelection::Resume(target),
+                    None => resume_picker::SessionSelection::StartFresh,
+                }
+            }
+        }
     } else {
         resume_picker::SessionSelection::StartFresh
     };
 
+    // Another process appending to the same rollout would interleave the two histories, so
+    // offer to fork or view the session instead.
+    let session_selection = match session_selection {
+        resume_picker::SessionSelection::Resume(target) => match check_rollout_lock(&target.path) {
+            Some(locked) => {
+                match session_lock::run_locked_session_prompt(&mut tui, &locked).await? {
+                    session_lock::LockedSessionChoice::Fork => {
+                        resume_picker::SessionSelection::Fork(target)
+                    }
+                    session_lock::LockedSessionChoice::ReadOnly => {
+                        overrides.ephemeral = Some(true);
+                        overrides.read_only = Some(true);
+                        resume_picker::SessionSelection::Resume(target)
+                    }
+                    session_lock::LockedSessionChoice::Exit => {
+                        restore();
+                        session_log::log_session_end();
+                        return Ok(AppExitInfo {
+                            token_usage: codex_protocol::protocol::TokenUsage::default(),
+                            thread_id: None,
+                            thread_name: None,
+                            update_action: None,
+                            exit_reason: ExitReason::UserRequested,
+                        });
+                    }
+                }
+            }
+            None => resume_picker::SessionSelection::Resume(target),
+        },
+        other => other,
+    };
+
     let current_cwd = config.cwd.clone();
     let allow_prompt = cli.cwd.is_none();
     let action_and_target_session_if_resume_or_fork = match &session_selection {
@@ -892,6 +1017,12 @@ async fn run_ratatui_app(
         }
         _ => config,
     };
+    if let Some(warning) = recovered
+        .as_ref()
+        .and_then(crash_recovery::RecoverySnapshot::pending_approvals_warning)
+    {
+        config.startup_warnings.push(warning);
+    }
 
     // Configure syntax highlighting theme from the final config — onboarding
     // and resume/fork can both reload config with a different tui_theme, so
@@ -902,8 +1033,23 @@ async fn run_ratatui_app(
     ) {
         config.startup_warnings.push(w);
     }
+    let spellcheck_warnings = spellcheck::init(&config.tui_spellcheck, &config.codex_home);
+    config.startup_warnings.extend(spellcheck_warnings);
 
     set_default_client_residency_requirement(config.enforce_residency.value());
+    if let Err(err) = set_default_client_proxy(config.outbound_proxy.as_ref()) {
+        // The reloaded config must not fall back to a direct connection.
+        restore();
+        return Err(color_eyre::eyre::eyre!(
+            "Error configuring outbound proxy: {err}"
+        ));
+    }
+    set_default_client_api_log(config.api_log_path.as_ref().map(AbsolutePathBuf::as_path));
+    if let Err(err) = set_default_client_tls(config.tls.as_ref()) {
+        config
+            .startup_warnings
+            .push(format!("Error loading TLS certificates: {err}"));
+    }
     let active_profile = config.active_profile.clone();
     let should_show_trust_screen = should_show_trust_screen(&config);
     let should_prompt_windows_sandbox_nux_at_startup = cfg!(target_os = "windows")
@@ -914,6 +1060,8 @@ async fn run_ratatui_app(
         prompt,
         images,
         no_alt_screen,
+        review_diff,
+        review_instructions,
         ..
     } = cli;
 
@@ -929,6 +1077,10 @@ async fn run_ratatui_app(
         active_profile,
         prompt,
         images,
+        review_diff.map(|source| (source, review_instructions)),
+        recovered
+            .map(|snapshot| snapshot.composer_draft)
+            .filter(|draft| !draft.trim().is_empty()),
         session_selection,
         feedback,
         should_show_trust_screen, // Proxy to: is it a first run in this directory?
@@ -943,6 +1095,17 @@ async fn run_ratatui_app(
     app_result
 }
 
+/// Locate a session by id, looking in the archive when it is not among the active sessions.
+async fn find_thread_or_archived_path(
+    codex_home: &Path,
+    id_str: &str,
+) -> std::io::Result<Option<PathBuf>> {
+    match find_thread_path_by_id_str(codex_home, id_str).await? {
+        Some(path) => Ok(Some(path)),
+        None => find_archived_thread_path_by_id_str(codex_home, id_str).await,
+    }
+}
+
 pub(crate) async fn resolve_session_thread_id(
     path: &Path,
     id_str_if_uuid: Option<&str>,
@@ -1213,7 +1376,10 @@ mod tests {
         let temp_dir = TempDir::new()?;
         let mut config = build_config(&temp_dir).await?;
         config.did_user_set_custom_approval_policy_or_sandbox_mode = false;
-        config.active_project = ProjectConfig { trust_level: None };
+        config.active_project = ProjectConfig {
+            trust_level: None,
+            shell_environment_set: None,
+        };
         config.set_windows_sandbox_enabled(false);
 
         let should_show = should_show_trust_screen(&config);
@@ -1229,7 +1395,10 @@ mod tests {
         let temp_dir = TempDir::new()?;
         let mut config = build_config(&temp_dir).await?;
         config.did_user_set_custom_approval_policy_or_sandbox_mode = false;
-        config.active_project = ProjectConfig { trust_level: None };
+        config.active_project = ProjectConfig {
+            trust_level: None,
+            shell_environment_set: None,
+        };
         config.set_windows_sandbox_enabled(true);
 
         let should_show = should_show_trust_screen(&config);
@@ -1254,6 +1423,7 @@ mod tests {
         config.did_user_set_custom_approval_policy_or_sandbox_mode = false;
         config.active_project = ProjectConfig {
             trust_level: Some(TrustLevel::Untrusted),
+            shell_environment_set: None,
         };
 
         let should_show = should_show_trust_screen(&config);
@@ -1289,6 +1459,7 @@ mod tests {
             developer_instructions: None,
             final_output_json_schema: None,
             truncation_policy: None,
+            sampling: None,
         }
     }
 
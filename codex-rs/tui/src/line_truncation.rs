@@ -1,6 +1,6 @@
 use ratatui::text::Line;
 use ratatui::text::Span;
-use unicode_width::UnicodeWidthChar;
+use unicode_segmentation::UnicodeSegmentation;
 use unicode_width::UnicodeWidthStr;
 
 pub(crate) fn line_width(line: &Line<'_>) -> usize {
@@ -43,13 +43,13 @@ pub(crate) fn truncate_line_to_width(line: Line<'static>, max_width: usize) -> L
         let style = span.style;
         let text = span.content.as_ref();
         let mut end_idx = 0usize;
-        for (idx, ch) in text.char_indices() {
-            let ch_width = UnicodeWidthChar::width(ch).unwrap_or(0);
-            if used + ch_width > max_width {
+        for (idx, grapheme) in text.grapheme_indices(true) {
+            let grapheme_width = UnicodeWidthStr::width(grapheme);
+            if used + grapheme_width > max_width {
                 break;
             }
-            end_idx = idx + ch.len_utf8();
-            used += ch_width;
+            end_idx = idx + grapheme.len();
+            used += grapheme_width;
         }
 
         if end_idx > 0 {
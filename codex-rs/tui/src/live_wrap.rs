@@ -1,4 +1,4 @@
-use unicode_width::UnicodeWidthChar;
+use unicode_segmentation::UnicodeSegmentation;
 use unicode_width::UnicodeWidthStr;
 
 /// A single visual row produced by RowBuilder.
@@ -154,9 +154,9 @@ impl RowBuilder {
             let (prefix, suffix, taken) =
                 take_prefix_by_width(&self.current_line, self.target_width);
             if taken == 0 {
-                // Avoid infinite loop on pathological inputs; take one scalar and continue.
-                if let Some((i, ch)) = self.current_line.char_indices().next() {
-                    let len = i + ch.len_utf8();
+                // Avoid infinite loop on pathological inputs; take one grapheme and continue.
+                if let Some(grapheme) = self.current_line.graphemes(true).next() {
+                    let len = grapheme.len();
                     let p = self.current_line[..len].to_string();
                     self.rows.push(Row {
                         text: p,
@@ -182,21 +182,21 @@ impl RowBuilder {
     }
 }
 
-/// Take a prefix of `text` whose visible width is at most `max_cols`.
-/// Returns (prefix, suffix, prefix_width).
+/// Take a prefix of `text` whose visible width is at most `max_cols`, cut at a grapheme
+/// boundary. Returns (prefix, suffix, prefix_width).
 pub fn take_prefix_by_width(text: &str, max_cols: usize) -> (String, &str, usize) {
     if max_cols == 0 || text.is_empty() {
         return (String::new(), text, 0);
     }
     let mut cols = 0usize;
     let mut end_idx = 0usize;
-    for (i, ch) in text.char_indices() {
-        let ch_width = UnicodeWidthChar::width(ch).unwrap_or(0);
-        if cols.saturating_add(ch_width) > max_cols {
+    for (i, grapheme) in text.grapheme_indices(true) {
+        let grapheme_width = grapheme.width();
+        if cols.saturating_add(grapheme_width) > max_cols {
             break;
         }
-        cols += ch_width;
-        end_idx = i + ch.len_utf8();
+        cols += grapheme_width;
+        end_idx = i + grapheme.len();
         if cols == max_cols {
             break;
         }
@@ -266,6 +266,15 @@ mod tests {
         assert_eq!(all_rows, chunk_rows);
     }
 
+    #[test]
+    fn prefix_keeps_combining_marks_with_their_letter() {
+        let (prefix, suffix, width) = take_prefix_by_width("e\u{301}e\u{301}", 1);
+
+        assert_eq!(prefix, "e\u{301}");
+        assert_eq!(suffix, "e\u{301}");
+        assert_eq!(width, 1);
+    }
+
     #[test]
     fn newline_splits_rows() {
         let mut rb = RowBuilder::new(10);
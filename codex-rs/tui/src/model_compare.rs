@@ -0,0 +1,175 @@
+//! Side-by-side results for `/compare-models`.
+//!
+//! The runs come from `codex_core::compare_models`; this module lays them out like `/compare`:
+//! the prompt across the full width, then one column per model with its latency, token usage,
+//! and estimated cost above the response.
+
+use codex_core::ModelComparison;
+use codex_core::ModelRun;
+use codex_protocol::num_format::format_with_separators;
+use codex_utils_elapsed::format_duration;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::widgets::Paragraph;
+use ratatui::widgets::Widget;
+use unicode_width::UnicodeWidthStr;
+
+use crate::markdown::append_markdown;
+use crate::render::renderable::Renderable;
+use crate::session_compare::COLUMN_SEPARATOR;
+use crate::session_compare::prompt_lines;
+use crate::session_compare::push_columns;
+
+pub(crate) struct ModelComparisonView {
+    comparison: ModelComparison,
+}
+
+impl ModelComparisonView {
+    pub(crate) fn new(comparison: ModelComparison) -> Self {
+        Self { comparison }
+    }
+
+    fn lines(&self, width: u16) -> Vec<Line<'static>> {
+        let width = usize::from(width.max(1));
+        let column_width = (width.saturating_sub(COLUMN_SEPARATOR.width()) / 2).max(1);
+
+        let mut lines = prompt_lines(&self.comparison.prompt, width);
+        for pair in self.comparison.runs.chunks(2) {
+            let (left, right) = (&pair[0], pair.get(1));
+            lines.push(Line::from("─".repeat(width)).dim());
+            push_columns(
+                &mut lines,
+                summary_lines(left),
+                right.map(summary_lines).unwrap_or_default(),
+                column_width,
+            );
+            lines.push("".into());
+            push_columns(
+                &mut lines,
+                response_lines(left, column_width),
+                right
+                    .map(|run| response_lines(run, column_width))
+                    .unwrap_or_default(),
+                column_width,
+            );
+        }
+        lines
+    }
+}
+
+impl Renderable for ModelComparisonView {
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        Paragraph::new(self.lines(area.width)).render(area, buf);
+    }
+
+    fn desired_height(&self, width: u16) -> u16 {
+        self.lines(width).len().try_into().unwrap_or(u16::MAX)
+    }
+}
+
+fn summary_lines(run: &ModelRun) -> Vec<Line<'static>> {
+    let usage = &run.token_usage;
+    vec![
+        run.model.clone().cyan().bold().into(),
+        vec!["latency ".dim(), format_duration(run.latency).into()].into(),
+        vec![
+            "tokens  ".dim(),
+            format!(
+                "{} in · {} out",
+                format_with_separators(usage.input_tokens),
+                format_with_separators(usage.output_tokens)
+            )
+            .into(),
+        ]
+        .into(),
+        vec![
+            "cost    ".dim(),
+            run.estimated_cost_usd
+                .map(|cost| format!("~${cost:.4} (est.)"))
+                .unwrap_or_else(|| "unknown".to_string())
+                .into(),
+        ]
+        .into(),
+    ]
+}
+
+fn response_lines(run: &ModelRun, width: usize) -> Vec<Line<'static>> {
+    let mut lines = Vec::new();
+    if let Some(response) = &run.response {
+        append_markdown(response, Some(width), &mut lines);
+    }
+    if let Some(error) = &run.error {
+        lines.push(format!("Error: {error}").red().into());
+    }
+    if lines.is_empty() {
+        lines.push("(no response)".dim().into());
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::protocol::TokenUsage;
+    use pretty_assertions::assert_eq;
+    use std::time::Duration;
+
+    fn render(view: &ModelComparisonView, width: u16) -> Vec<String> {
+        view.lines(width)
+            .iter()
+            .map(|line| {
+                line.spans
+                    .iter()
+                    .map(|span| span.content.as_ref())
+                    .collect::<String>()
+                    .trim_end()
+                    .to_string()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn renders_models_side_by_side_with_metrics() {
+        let comparison = ModelComparison {
+            prompt: "why is the build slow?".to_string(),
+            runs: vec![
+                ModelRun {
+                    model: "gpt-5".to_string(),
+                    response: Some("Incremental builds are off.".to_string()),
+                    error: None,
+                    token_usage: TokenUsage {
+                        input_tokens: 120,
+                        output_tokens: 80,
+                        ..Default::default()
+                    },
+                    latency: Duration::from_millis(4250),
+                    estimated_cost_usd: Some(0.0023),
+                },
+                ModelRun {
+                    model: "local-model".to_string(),
+                    response: None,
+                    error: Some("stream disconnected".to_string()),
+                    token_usage: TokenUsage::default(),
+                    latency: Duration::from_millis(300),
+                    estimated_cost_usd: None,
+                },
+            ],
+        };
+
+        assert_eq!(
+            render(&ModelComparisonView::new(comparison), 60),
+            vec![
+                "› why is the build slow?".to_string(),
+                "─".repeat(60),
+                "gpt-5                        │ local-model".to_string(),
+                "latency 4.25s                │ latency 300ms".to_string(),
+                "tokens  120 in · 80 out      │ tokens  0 in · 0 out".to_string(),
+                "cost    ~$0.0023 (est.)      │ cost    unknown".to_string(),
+                String::new(),
+                "Incremental builds are off.  │ Error: stream disconnected".to_string(),
+            ]
+        );
+    }
+}
@@ -1,3 +1,7 @@
+use crate::app_event::AppEvent;
+use crate::bottom_pane::SelectionItem;
+use crate::bottom_pane::SelectionViewParams;
+use crate::bottom_pane::popup_consts::standard_popup_hint_line;
 use crate::history_cell::PlainHistoryCell;
 use crate::render::line_utils::prefix_lines;
 use crate::text_formatting::truncate_text;
@@ -29,6 +33,28 @@ pub(crate) struct AgentPickerThreadEntry {
     pub(crate) is_closed: bool,
 }
 
+/// One sub-agent as listed on the `/tasks` board.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct AgentTask {
+    pub(crate) thread_id: ThreadId,
+    pub(crate) entry: AgentPickerThreadEntry,
+    pub(crate) status: AgentStatus,
+}
+
+impl AgentTask {
+    fn name(&self) -> String {
+        format_agent_picker_item_name(
+            self.entry.agent_nickname.as_deref(),
+            self.entry.agent_role.as_deref(),
+            false,
+        )
+    }
+
+    fn is_active(&self) -> bool {
+        matches!(self.status, AgentStatus::PendingInit | AgentStatus::Running)
+    }
+}
+
 #[derive(Clone, Copy)]
 struct AgentLabel<'a> {
     thread_id: Option<ThreadId>,
@@ -74,6 +100,85 @@ pub(crate) fn sort_agent_picker_threads(agent_threads: &mut [(ThreadId, AgentPic
     });
 }
 
+/// The `/tasks` board: every sub-agent with its status, active ones first.
+pub(crate) fn task_board_params(mut tasks: Vec<AgentTask>) -> SelectionViewParams {
+    tasks.sort_by(|left, right| {
+        right
+            .is_active()
+            .cmp(&left.is_active())
+            .then_with(|| left.thread_id.to_string().cmp(&right.thread_id.to_string()))
+    });
+    let running = tasks.iter().filter(|task| task.is_active()).count();
+    let items = tasks
+        .iter()
+        .map(|task| {
+            let id = task.thread_id;
+            let name = task.name();
+            SelectionItem {
+                name: name.clone(),
+                name_prefix_spans: agent_picker_status_dot_spans(!task.is_active()),
+                description: Some(status_summary_text(&task.status)),
+                actions: vec![Box::new(move |tx| {
+                    tx.send(AppEvent::OpenAgentTask(id));
+                })],
+                dismiss_on_select: true,
+                search_value: Some(format!("{name} {id}")),
+                ..Default::default()
+            }
+        })
+        .collect();
+    SelectionViewParams {
+        title: Some("Sub-agent tasks".to_string()),
+        subtitle: Some(format!("{running} of {} running", tasks.len())),
+        footer_hint: Some(standard_popup_hint_line()),
+        items,
+        ..Default::default()
+    }
+}
+
+/// Actions for one sub-agent picked from the `/tasks` board.
+pub(crate) fn agent_task_params(task: &AgentTask) -> SelectionViewParams {
+    let id = task.thread_id;
+    let items = vec![
+        SelectionItem {
+            name: "Inspect".to_string(),
+            description: Some("Switch to this agent's thread to read its transcript".to_string()),
+            actions: vec![Box::new(move |tx| {
+                tx.send(AppEvent::SelectAgentThread(id));
+            })],
+            dismiss_on_select: true,
+            ..Default::default()
+        },
+        SelectionItem {
+            name: "Cancel".to_string(),
+            description: Some("Shut the agent down and free its slot".to_string()),
+            is_disabled: !task.is_active(),
+            disabled_reason: (!task.is_active()).then(|| "The agent is not running.".to_string()),
+            actions: vec![Box::new(move |tx| {
+                tx.send(AppEvent::CancelAgentThread(id));
+            })],
+            dismiss_on_select: true,
+            ..Default::default()
+        },
+        SelectionItem {
+            name: "Back".to_string(),
+            description: Some("Return to the task board".to_string()),
+            actions: vec![Box::new(|tx| {
+                tx.send(AppEvent::OpenTaskBoard);
+            })],
+            dismiss_on_select: true,
+            ..Default::default()
+        },
+    ];
+    SelectionViewParams {
+        title: Some(task.name()),
+        subtitle: Some(status_summary_text(&task.status)),
+        footer_hint: Some(standard_popup_hint_line()),
+        items,
+        ..Default::default()
+    }
+}
+
 pub(crate) fn spawn_end(ev: CollabAgentSpawnEndEvent) -> PlainHistoryCell {
     let CollabAgentSpawnEndEvent {
         call_id: _,
@@ -403,6 +508,13 @@ fn wait_complete_lines(
         .collect()
 }
 
+fn status_summary_text(status: &AgentStatus) -> String {
+    status_summary_spans(status)
+        .iter()
+        .map(|span| span.content.as_ref())
+        .collect()
+}
+
 fn status_summary_line(status: &AgentStatus) -> Line<'static> {
     status_summary_spans(status).into()
 }
@@ -560,6 +672,54 @@ mod tests {
         assert!(!title.spans[4].style.add_modifier.contains(Modifier::DIM));
     }
 
+    #[test]
+    fn task_board_lists_running_agents_first() {
+        let task = |id: &str, nickname: &str, status: AgentStatus| AgentTask {
+            thread_id: ThreadId::from_string(id).expect("valid thread id"),
+            entry: AgentPickerThreadEntry {
+                agent_nickname: Some(nickname.to_string()),
+                agent_role: Some("worker".to_string()),
+                is_closed: false,
+            },
+            status,
+        };
+        let done = task(
+            "00000000-0000-0000-0000-000000000001",
+            "Robie",
+            AgentStatus::Completed(Some("Migrated the schema.".to_string())),
+        );
+        let running = task(
+            "00000000-0000-0000-0000-000000000002",
+            "Bob",
+            AgentStatus::Running,
+        );
+
+        let board = task_board_params(vec![done.clone(), running.clone()]);
+        assert_eq!(board.subtitle.as_deref(), Some("1 of 2 running"));
+        assert_eq!(
+            board
+                .items
+                .iter()
+                .map(|item| (item.name.as_str(), item.description.as_deref()))
+                .collect::<Vec<_>>(),
+            vec![
+                ("Bob [worker]", Some("Running")),
+                ("Robie [worker]", Some("Completed - Migrated the schema.")),
+            ]
+        );
+
+        let cancel = |task: &AgentTask| {
+            agent_task_params(task)
+                .items
+                .into_iter()
+                .find(|item| item.name == "Cancel")
+                .expect("cancel action")
+                .is_disabled
+        };
+        assert!(!cancel(&running));
+        assert!(cancel(&done));
+    }
+
     fn cell_to_text(cell: &PlainHistoryCell) -> String {
         cell.display_lines(200)
             .iter()
@@ -1,5 +1,6 @@
 mod bel;
 mod osc9;
+mod tmux;
 
 use std::env;
 use std::io;
@@ -7,11 +8,13 @@ use std::io;
 use bel::BelBackend;
 use codex_core::config::types::NotificationMethod;
 use osc9::Osc9Backend;
+use tmux::TmuxBackend;
 
 #[derive(Debug)]
 pub enum DesktopNotificationBackend {
     Osc9(Osc9Backend),
     Bel(BelBackend),
+    Tmux(TmuxBackend),
 }
 
 impl DesktopNotificationBackend {
@@ -26,6 +29,7 @@ impl DesktopNotificationBackend {
             }
             NotificationMethod::Osc9 => Self::Osc9(Osc9Backend),
             NotificationMethod::Bel => Self::Bel(BelBackend),
+            NotificationMethod::Tmux => Self::Tmux(TmuxBackend),
         }
     }
 
@@ -33,6 +37,7 @@ impl DesktopNotificationBackend {
         match self {
             DesktopNotificationBackend::Osc9(_) => NotificationMethod::Osc9,
             DesktopNotificationBackend::Bel(_) => NotificationMethod::Bel,
+            DesktopNotificationBackend::Tmux(_) => NotificationMethod::Tmux,
         }
     }
 
@@ -40,6 +45,7 @@ impl DesktopNotificationBackend {
         match self {
             DesktopNotificationBackend::Osc9(backend) => backend.notify(message),
             DesktopNotificationBackend::Bel(backend) => backend.notify(message),
+            DesktopNotificationBackend::Tmux(backend) => backend.notify(message),
         }
     }
 }
@@ -128,6 +134,14 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn selects_tmux_method() {
+        assert!(matches!(
+            detect_backend(NotificationMethod::Tmux),
+            super::DesktopNotificationBackend::Tmux(_)
+        ));
+    }
+
     #[test]
     #[serial]
     fn auto_prefers_bel_without_hints() {
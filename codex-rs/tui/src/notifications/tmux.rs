@@ -0,0 +1,43 @@
+use std::io;
+use std::process::Command;
+use std::process::Stdio;
+
+/// Shows notifications in the tmux status line with `display-message`.
+#[derive(Debug, Default)]
+pub struct TmuxBackend;
+
+impl TmuxBackend {
+    pub fn notify(&mut self, message: &str) -> io::Result<()> {
+        let mut command = Command::new("tmux");
+        command.arg("display-message");
+        if let Ok(pane) = std::env::var("TMUX_PANE") {
+            command.args(["-t", &pane]);
+        }
+        let status = command
+            .arg(escape_format(message))
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()?;
+        if status.success() {
+            Ok(())
+        } else {
+            Err(io::Error::other(format!("tmux display-message {status}")))
+        }
+    }
+}
+
+/// `display-message` expands `#` formats; double them so the message is shown verbatim.
+fn escape_format(message: &str) -> String {
+    message.replace('#', "##")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::escape_format;
+
+    #[test]
+    fn escapes_tmux_formats() {
+        assert_eq!(escape_format("PR #12 ready"), "PR ##12 ready");
+    }
+}
@@ -14,9 +14,21 @@
 //! `TranscriptOverlay::sync_live_tail` uses the key to decide when the cached tail must be
 //! recomputed. `ChatWidget` is responsible for producing a key that changes when the active cell
 //! mutates in place or when its transcript output is time-dependent.
+//!
+//! The log overlay (`Ctrl+O`) tails the TUI tracing log file. It re-reads the end of the file
+//! whenever the file grows and filters the lines by minimum level and a search query.
+//!
+//! The event inspector (`/debug-events`) lists the protocol events `App` recently received, each
+//! of which can be expanded to its pretty-printed JSON payload.
 
+use std::io::Read;
 use std::io::Result;
+use std::io::Seek;
+use std::io::SeekFrom;
+use std::path::PathBuf;
+use std::str::FromStr;
 use std::sync::Arc;
+use std::time::Duration;
 
 use crate::chatwidget::ActiveCellTranscriptKey;
 use crate::history_cell::HistoryCell;
@@ -29,8 +41,13 @@ use crate::render::renderable::Renderable;
 use crate::style::user_message_style;
 use crate::tui;
 use crate::tui::TuiEvent;
+use chrono::DateTime;
+use chrono::Local;
+use codex_protocol::protocol::Event;
 use crossterm::event::KeyCode;
 use crossterm::event::KeyEvent;
+use crossterm::event::KeyEventKind;
+use crossterm::event::KeyModifiers;
 use ratatui::buffer::Buffer;
 use ratatui::buffer::Cell;
 use ratatui::layout::Rect;
@@ -44,10 +61,13 @@ use ratatui::widgets::Paragraph;
 use ratatui::widgets::Widget;
 use ratatui::widgets::WidgetRef;
 use ratatui::widgets::Wrap;
+use tracing::Level;
 
 pub(crate) enum Overlay {
     Transcript(TranscriptOverlay),
     Static(StaticOverlay),
+    Log(LogOverlay),
+    EventInspector(EventInspectorOverlay),
 }
 
 impl Overlay {
@@ -66,10 +86,20 @@ impl Overlay {
         Self::Static(StaticOverlay::with_renderables(renderables, title))
     }
 
+    pub(crate) fn new_log(path: PathBuf) -> Self {
+        Self::Log(LogOverlay::new(path))
+    }
+
+    pub(crate) fn new_event_inspector(events: Vec<RecordedEvent>) -> Self {
+        Self::EventInspector(EventInspectorOverlay::new(events))
+    }
+
     pub(crate) fn handle_event(&mut self, tui: &mut tui::Tui, event: TuiEvent) -> Result<()> {
         match self {
             Overlay::Transcript(o) => o.handle_event(tui, event),
             Overlay::Static(o) => o.handle_event(tui, event),
+            Overlay::Log(o) => o.handle_event(tui, event),
+            Overlay::EventInspector(o) => o.handle_event(tui, event),
         }
     }
 
@@ -77,6 +107,8 @@ impl Overlay {
         match self {
             Overlay::Transcript(o) => o.is_done(),
             Overlay::Static(o) => o.is_done(),
+            Overlay::Log(o) => o.is_done(),
+            Overlay::EventInspector(o) => o.is_done(),
         }
     }
 }
@@ -102,6 +134,9 @@ const KEY_ESC: KeyBinding = key_hint::plain(KeyCode::Esc);
 const KEY_ENTER: KeyBinding = key_hint::plain(KeyCode::Enter);
 const KEY_CTRL_T: KeyBinding = key_hint::ctrl(KeyCode::Char('t'));
 const KEY_CTRL_C: KeyBinding = key_hint::ctrl(KeyCode::Char('c'));
+const KEY_CTRL_O: KeyBinding = key_hint::ctrl(KeyCode::Char('o'));
+const KEY_L: KeyBinding = key_hint::plain(KeyCode::Char('l'));
+const KEY_SLASH: KeyBinding = key_hint::plain(KeyCode::Char('/'));
 
 // Common pager navigation hints rendered on the first line
 const PAGER_KEY_HINTS: &[(&[KeyBinding], &str)] = &[
@@ -767,6 +802,410 @@ impl StaticOverlay {
     }
 }
 
+/// Only the end of the log file is loaded so very long sessions stay cheap to open.
+const LOG_TAIL_BYTES: u64 = 512 * 1024;
+/// How often the log overlay checks the file for new lines.
+const LOG_REFRESH_INTERVAL: Duration = Duration::from_millis(500);
+
+/// A line of the tracing log. Continuation lines of a multi-line event inherit the level of the
+/// line that started the event.
+struct LogLine {
+    level: Option<Level>,
+    text: String,
+}
+
+pub(crate) struct LogOverlay {
+    view: PagerView,
+    path: PathBuf,
+    /// File length at the last read; the tail is re-read when it changes.
+    loaded_len: Option<u64>,
+    lines: Vec<LogLine>,
+    /// Least severe level that is shown; `TRACE` shows everything.
+    min_level: Level,
+    query: String,
+    /// Query being typed after `/`, applied on Enter.
+    search_input: Option<String>,
+    is_done: bool,
+}
+
+impl LogOverlay {
+    pub(crate) fn new(path: PathBuf) -> Self {
+        let mut overlay = Self {
+            view: PagerView::new(Vec::new(), "L O G".to_string(), usize::MAX),
+            path,
+            loaded_len: None,
+            lines: Vec::new(),
+            min_level: Level::TRACE,
+            query: String::new(),
+            search_input: None,
+            is_done: false,
+        };
+        overlay.reload_if_changed();
+        overlay
+    }
+
+    fn reload_if_changed(&mut self) {
+        let len = std::fs::metadata(&self.path).ok().map(|meta| meta.len());
+        if len == self.loaded_len && self.loaded_len.is_some() {
+            return;
+        }
+        self.loaded_len = len;
+        self.lines = match read_log_tail(&self.path) {
+            Ok(text) => parse_log_lines(&text),
+            Err(err) => vec![LogLine {
+                level: None,
+                text: format!("Failed to read {}: {err}", self.path.display()),
+            }],
+        };
+        self.rebuild_view();
+    }
+
+    fn rebuild_view(&mut self) {
+        let follow = self.view.is_scrolled_to_bottom();
+        let query = self.query.to_lowercase();
+        let lines: Vec<Line<'static>> = self
+            .lines
+            .iter()
+            .filter(|line| line.level.is_none_or(|level| level <= self.min_level))
+            .filter(|line| query.is_empty() || line.text.to_lowercase().contains(&query))
+            .map(|line| {
+                let text = line.text.clone();
+                match line.level {
+                    Some(Level::ERROR) => text.red().into(),
+                    Some(Level::WARN) => text.bold().into(),
+                    Some(Level::DEBUG) | Some(Level::TRACE) => text.dim().into(),
+                    Some(Level::INFO) | None => text.into(),
+                }
+            })
+            .collect();
+        let renderable: Box<dyn Renderable> = if lines.is_empty() {
+            Box::new(Paragraph::new(
+                Line::from("No matching log lines.").italic(),
+            ))
+        } else {
+            Box::new(CachedRenderable::new(
+                Paragraph::new(Text::from(lines)).wrap(Wrap { trim: false }),
+            ))
+        };
+        self.view.renderables = vec![renderable];
+        if follow {
+            self.view.scroll_offset = usize::MAX;
+        }
+    }
+
+    fn render_hints(&self, area: Rect, buf: &mut Buffer) {
+        let line1 = Rect::new(area.x, area.y, area.width, 1);
+        let line2 = Rect::new(area.x, area.y.saturating_add(1), area.width, 1);
+        if let Some(input) = &self.search_input {
+            Paragraph::new(Line::from(vec![" /".cyan(), input.clone().into()]))
+                .render_ref(line1, buf);
+            render_key_hints(
+                line2,
+                buf,
+                &[(&[KEY_ENTER], "to search"), (&[KEY_ESC], "to cancel")],
+            );
+            return;
+        }
+        render_key_hints(line1, buf, PAGER_KEY_HINTS);
+        let level = format!("to filter (level: {})", self.min_level);
+        let search = if self.query.is_empty() {
+            "to search".to_string()
+        } else {
+            format!("to search (\"{}\")", self.query)
+        };
+        render_key_hints(
+            line2,
+            buf,
+            &[
+                (&[KEY_Q], "to quit"),
+                (&[KEY_L], level.as_str()),
+                (&[KEY_SLASH], search.as_str()),
+            ],
+        );
+    }
+
+    pub(crate) fn render(&mut self, area: Rect, buf: &mut Buffer) {
+        let top_h = area.height.saturating_sub(3);
+        let top = Rect::new(area.x, area.y, area.width, top_h);
+        let bottom = Rect::new(area.x, area.y + top_h, area.width, 3);
+        self.view.render(top, buf);
+        self.render_hints(bottom, buf);
+    }
+
+    fn handle_search_key(&mut self, key_event: KeyEvent) {
+        let Some(input) = self.search_input.as_mut() else {
+            return;
+        };
+        match key_event {
+            e if KEY_ENTER.is_press(e) => {
+                self.query = self.search_input.take().unwrap_or_default();
+                self.view.scroll_offset = usize::MAX;
+                self.rebuild_view();
+            }
+            e if KEY_ESC.is_press(e) => {
+                self.search_input = None;
+            }
+            KeyEvent {
+                code: KeyCode::Backspace,
+                kind: KeyEventKind::Press | KeyEventKind::Repeat,
+                ..
+            } => {
+                input.pop();
+            }
+            KeyEvent {
+                code: KeyCode::Char(c),
+                modifiers: KeyModifiers::NONE | KeyModifiers::SHIFT,
+                kind: KeyEventKind::Press | KeyEventKind::Repeat,
+                ..
+            } => input.push(c),
+            _ => {}
+        }
+    }
+
+    fn cycle_min_level(&mut self) {
+        self.min_level = match self.min_level {
+            Level::TRACE => Level::DEBUG,
+            Level::DEBUG => Level::INFO,
+            Level::INFO => Level::WARN,
+            Level::WARN => Level::ERROR,
+            Level::ERROR => Level::TRACE,
+        };
+        self.view.scroll_offset = usize::MAX;
+        self.rebuild_view();
+    }
+
+    pub(crate) fn handle_event(&mut self, tui: &mut tui::Tui, event: TuiEvent) -> Result<()> {
+        match event {
+            TuiEvent::Key(key_event) if self.search_input.is_some() => {
+                self.handle_search_key(key_event);
+                tui.frame_requester().schedule_frame();
+                Ok(())
+            }
+            TuiEvent::Key(key_event) => match key_event {
+                e if KEY_Q.is_press(e) || KEY_CTRL_C.is_press(e) || KEY_CTRL_O.is_press(e) => {
+                    self.is_done = true;
+                    Ok(())
+                }
+                e if KEY_L.is_press(e) => {
+                    self.cycle_min_level();
+                    tui.frame_requester().schedule_frame();
+                    Ok(())
+                }
+                e if KEY_SLASH.is_press(e) => {
+                    self.search_input = Some(self.query.clone());
+                    tui.frame_requester().schedule_frame();
+                    Ok(())
+                }
+                other => self.view.handle_key_event(tui, other),
+            },
+            TuiEvent::Draw => {
+                self.reload_if_changed();
+                tui.draw(u16::MAX, |frame| {
+                    self.render(frame.area(), frame.buffer);
+                })?;
+                tui.frame_requester()
+                    .schedule_frame_in(LOG_REFRESH_INTERVAL);
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    pub(crate) fn is_done(&self) -> bool {
+        self.is_done
+    }
+}
+
+/// A protocol event as received by `App`, kept for the event inspector.
+#[derive(Clone)]
+pub(crate) struct RecordedEvent {
+    pub(crate) received_at: DateTime<Local>,
+    pub(crate) event: Event,
+}
+
+struct InspectedEvent {
+    header: String,
+    payload: String,
+    expanded: bool,
+}
+
+pub(crate) struct EventInspectorOverlay {
+    view: PagerView,
+    entries: Vec<InspectedEvent>,
+    selected: usize,
+    is_done: bool,
+}
+
+impl EventInspectorOverlay {
+    pub(crate) fn new(events: Vec<RecordedEvent>) -> Self {
+        let entries: Vec<InspectedEvent> = events
+            .into_iter()
+            .map(|RecordedEvent { received_at, event }| InspectedEvent {
+                header: format!(
+                    "{} {} (id {})",
+                    received_at.format("%H:%M:%S%.3f"),
+                    event.msg,
+                    event.id
+                ),
+                payload: serde_json::to_string_pretty(&event.msg)
+                    .unwrap_or_else(|err| format!("<failed to serialize event: {err}>")),
+                expanded: false,
+            })
+            .collect();
+        let mut overlay = Self {
+            view: PagerView::new(Vec::new(), "E V E N T S".to_string(), usize::MAX),
+            selected: entries.len().saturating_sub(1),
+            entries,
+            is_done: false,
+        };
+        overlay.rebuild_view();
+        overlay
+    }
+
+    fn rebuild_view(&mut self) {
+        if self.entries.is_empty() {
+            self.view.renderables = vec![Box::new(Paragraph::new(
+                Line::from("No events received yet.").italic(),
+            ))];
+            return;
+        }
+        self.view.renderables = self
+            .entries
+            .iter()
+            .enumerate()
+            .map(|(idx, entry)| {
+                let mut lines: Vec<Line<'static>> = vec![if idx == self.selected {
+                    Line::from(vec!["› ".cyan(), entry.header.clone().cyan().bold()])
+                } else {
+                    Line::from(format!("  {}", entry.header))
+                }];
+                if entry.expanded {
+                    lines.extend(
+                        entry
+                            .payload
+                            .lines()
+                            .map(|line| Line::from(format!("    {line}")).dim()),
+                    );
+                }
+                let paragraph = Paragraph::new(Text::from(lines)).wrap(Wrap { trim: false });
+                Box::new(CachedRenderable::new(paragraph)) as Box<dyn Renderable>
+            })
+            .collect();
+        self.view.scroll_chunk_into_view(self.selected);
+    }
+
+    fn move_selection(&mut self, delta: isize) {
+        let last = self.entries.len().saturating_sub(1);
+        self.selected = self.selected.saturating_add_signed(delta).min(last);
+        self.rebuild_view();
+    }
+
+    fn toggle_selected(&mut self) {
+        if let Some(entry) = self.entries.get_mut(self.selected) {
+            entry.expanded = !entry.expanded;
+            self.rebuild_view();
+        }
+    }
+
+    fn render_hints(&self, area: Rect, buf: &mut Buffer) {
+        let line1 = Rect::new(area.x, area.y, area.width, 1);
+        let line2 = Rect::new(area.x, area.y.saturating_add(1), area.width, 1);
+        render_key_hints(
+            line1,
+            buf,
+            &[
+                (&[KEY_UP, KEY_DOWN], "to select"),
+                (&[KEY_PAGE_UP, KEY_PAGE_DOWN], "to page"),
+                (&[KEY_HOME, KEY_END], "to jump"),
+            ],
+        );
+        render_key_hints(
+            line2,
+            buf,
+            &[(&[KEY_Q], "to quit"), (&[KEY_ENTER], "to expand/collapse")],
+        );
+    }
+
+    pub(crate) fn render(&mut self, area: Rect, buf: &mut Buffer) {
+        let top_h = area.height.saturating_sub(3);
+        let top = Rect::new(area.x, area.y, area.width, top_h);
+        let bottom = Rect::new(area.x, area.y + top_h, area.width, 3);
+        self.view.render(top, buf);
+        self.render_hints(bottom, buf);
+    }
+
+    pub(crate) fn handle_event(&mut self, tui: &mut tui::Tui, event: TuiEvent) -> Result<()> {
+        match event {
+            TuiEvent::Key(key_event) => {
+                match key_event {
+                    e if KEY_Q.is_press(e) || KEY_ESC.is_press(e) || KEY_CTRL_C.is_press(e) => {
+                        self.is_done = true;
+                        return Ok(());
+                    }
+                    e if KEY_UP.is_press(e) || KEY_K.is_press(e) => self.move_selection(-1),
+                    e if KEY_DOWN.is_press(e) || KEY_J.is_press(e) => self.move_selection(1),
+                    e if KEY_HOME.is_press(e) => self.move_selection(isize::MIN),
+                    e if KEY_END.is_press(e) => self.move_selection(isize::MAX),
+                    e if KEY_ENTER.is_press(e) => self.toggle_selected(),
+                    other => return self.view.handle_key_event(tui, other),
+                }
+                tui.frame_requester().schedule_frame();
+                Ok(())
+            }
+            TuiEvent::Draw => {
+                tui.draw(u16::MAX, |frame| {
+                    self.render(frame.area(), frame.buffer);
+                })?;
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    pub(crate) fn is_done(&self) -> bool {
+        self.is_done
+    }
+}
+
+/// Read at most `LOG_TAIL_BYTES` from the end of the log, dropping a leading partial line.
+fn read_log_tail(path: &std::path::Path) -> Result<String> {
+    let mut file = std::fs::File::open(path)?;
+    let len = file.metadata()?.len();
+    let start = len.saturating_sub(LOG_TAIL_BYTES);
+    file.seek(SeekFrom::Start(start))?;
+    let mut bytes = Vec::new();
+    file.read_to_end(&mut bytes)?;
+    let text = String::from_utf8_lossy(&bytes).into_owned();
+    if start == 0 {
+        return Ok(text);
+    }
+    Ok(text
+        .split_once('\n')
+        .map(|(_, rest)| rest.to_string())
+        .unwrap_or_default())
+}
+
+/// Split log text into lines, reading the level from the second whitespace-separated field that
+/// the `tracing_subscriber` fmt layer writes after the timestamp.
+fn parse_log_lines(text: &str) -> Vec<LogLine> {
+    let mut level = None;
+    text.lines()
+        .map(|line| {
+            if let Some(parsed) = line
+                .split_whitespace()
+                .nth(1)
+                .and_then(|field| Level::from_str(field).ok())
+            {
+                level = Some(parsed);
+            }
+            LogLine {
+                level,
+                text: line.to_string(),
+            }
+        })
+        .collect()
+}
+
 fn render_offset_content(
     area: Rect,
     buf: &mut Buffer,
@@ -797,6 +1236,9 @@ fn render_offset_content(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use chrono::TimeZone;
+    use codex_protocol::protocol::AgentMessageEvent;
+    use codex_protocol::protocol::EventMsg;
     use codex_protocol::protocol::ExecCommandSource;
     use codex_protocol::protocol::ReviewDecision;
     use insta::assert_snapshot;
@@ -812,6 +1254,7 @@ mod tests {
     use crate::history_cell::new_patch_event;
     use codex_protocol::parse_command::ParsedCommand;
     use codex_protocol::protocol::FileChange;
+    use codex_protocol::protocol::WarningEvent;
     use ratatui::Terminal;
     use ratatui::backend::TestBackend;
     use ratatui::text::Text;
@@ -1089,6 +1532,83 @@ mod tests {
         assert_snapshot!(term.backend());
     }
 
+    #[test]
+    fn parse_log_lines_carries_level_to_continuation_lines() {
+        let lines = parse_log_lines(
+            "2025-01-01T00:00:00.000000Z  INFO codex_core: started\n\
+             2025-01-01T00:00:01.000000Z ERROR codex_core: tool failed:\n\
+             stderr: permission denied\n",
+        );
+        let levels: Vec<Option<Level>> = lines.iter().map(|line| line.level).collect();
+        assert_eq!(
+            levels,
+            vec![Some(Level::INFO), Some(Level::ERROR), Some(Level::ERROR)]
+        );
+    }
+
+    #[test]
+    fn log_overlay_filters_by_level_and_query() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("codex-tui.log");
+        std::fs::write(
+            &path,
+            "2025-01-01T00:00:00.000000Z DEBUG codex_core: polling\n\
+             2025-01-01T00:00:01.000000Z  INFO codex_core: turn started\n\
+             2025-01-01T00:00:02.000000Z  WARN codex_core: shell retry\n\
+             2025-01-01T00:00:03.000000Z ERROR codex_core: shell failed: exit 1\n\
+             2025-01-01T00:00:04.000000Z ERROR codex_core: mcp timeout\n",
+        )
+        .expect("write log");
+        let mut overlay = LogOverlay::new(path);
+        overlay.cycle_min_level();
+        overlay.cycle_min_level();
+        overlay.cycle_min_level();
+        overlay.query = "shell".to_string();
+        overlay.rebuild_view();
+
+        let mut term = Terminal::new(TestBackend::new(70, 8)).expect("term");
+        term.draw(|f| overlay.render(f.area(), f.buffer_mut()))
+            .expect("draw");
+        assert_snapshot!(term.backend());
+    }
+
+    #[test]
+    fn event_inspector_expands_selected_event() {
+        let received_at = chrono::Local
+            .with_ymd_and_hms(2025, 1, 1, 12, 0, 0)
+            .single()
+            .expect("timestamp");
+        let events = vec![
+            RecordedEvent {
+                received_at,
+                event: Event {
+                    id: "1".to_string(),
+                    msg: EventMsg::AgentMessage(AgentMessageEvent {
+                        message: "hello".to_string(),
+                        phase: None,
+                    }),
+                },
+            },
+            RecordedEvent {
+                received_at,
+                event: Event {
+                    id: "1".to_string(),
+                    msg: EventMsg::Warning(WarningEvent {
+                        message: "careful".to_string(),
+                    }),
+                },
+            },
+        ];
+        let mut overlay = EventInspectorOverlay::new(events);
+        overlay.move_selection(-1);
+        overlay.toggle_selected();
+
+        let mut term = Terminal::new(TestBackend::new(60, 10)).expect("term");
+        term.draw(|f| overlay.render(f.area(), f.buffer_mut()))
+            .expect("draw");
+        assert_snapshot!(term.backend());
+    }
+
     /// Render transcript overlay and return visible line numbers (`line-NN`) in order.
     fn transcript_line_numbers(overlay: &mut TranscriptOverlay, area: Rect) -> Vec<usize> {
         let mut buf = Buffer::empty(area);
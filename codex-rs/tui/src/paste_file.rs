@@ -0,0 +1,111 @@
+//! Pastes too large to send as text.
+//!
+//! A bracketed paste arrives as a single string. Past [`PASTE_AS_FILE_THRESHOLD_BYTES`] the
+//! composer offers to write it to a temporary file instead of keeping it in the draft, where it
+//! would be expanded into the prompt on submit. The draft then holds a placeholder that expands
+//! to a short note naming the file, and the model reads the parts it needs with its tools.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use codex_utils_string::format_bytes;
+
+/// Pastes larger than this are offered as a file.
+pub(crate) const PASTE_AS_FILE_THRESHOLD_BYTES: usize = 512 * 1024;
+
+const PASTE_DIR: &str = "codex-pastes";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct SavedPaste {
+    pub(crate) path: PathBuf,
+    pub(crate) bytes: usize,
+    pub(crate) lines: usize,
+}
+
+impl SavedPaste {
+    /// The atomic element shown in the composer.
+    pub(crate) fn placeholder(&self) -> String {
+        let name = self
+            .path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| self.path.display().to_string());
+        format!("[Pasted file {name} · {}]", format_bytes(self.bytes as u64))
+    }
+
+    /// What the placeholder expands to when the message is submitted.
+    pub(crate) fn reference(&self) -> String {
+        format!(
+            "[Pasted text ({}, {} lines) saved to {}; read the file for its contents.]",
+            format_bytes(self.bytes as u64),
+            self.lines,
+            self.path.display()
+        )
+    }
+}
+
+pub(crate) fn is_oversized(text: &str) -> bool {
+    text.len() > PASTE_AS_FILE_THRESHOLD_BYTES
+}
+
+/// Write `text` to a new file under the system temporary directory.
+pub(crate) async fn save(text: Arc<String>) -> Result<SavedPaste, String> {
+    let dir = std::env::temp_dir().join(PASTE_DIR);
+    tokio::fs::create_dir_all(&dir)
+        .await
+        .map_err(|err| format!("could not create {}: {err}", dir.display()))?;
+    let name = format!(
+        "paste-{}-{}.txt",
+        chrono::Local::now().format("%Y%m%d-%H%M%S"),
+        &uuid::Uuid::new_v4().simple().to_string()[..8]
+    );
+    let path = dir.join(name);
+    tokio::fs::write(&path, text.as_bytes())
+        .await
+        .map_err(|err| format!("could not write {}: {err}", path.display()))?;
+    Ok(SavedPaste {
+        path,
+        bytes: text.len(),
+        lines: text.lines().count(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn placeholder_and_reference_describe_the_file() {
+        let saved = SavedPaste {
+            path: PathBuf::from("/tmp/codex-pastes/paste-20261016-101500-1a2b3c4d.txt"),
+            bytes: 3 * 1024 * 1024 + 200 * 1024,
+            lines: 41_200,
+        };
+
+        assert_eq!(
+            saved.placeholder(),
+            "[Pasted file paste-20261016-101500-1a2b3c4d.txt · 3.2 MiB]"
+        );
+        assert_eq!(
+            saved.reference(),
+            "[Pasted text (3.2 MiB, 41200 lines) saved to \
+/tmp/codex-pastes/paste-20261016-101500-1a2b3c4d.txt; read the file for its contents.]"
+        );
+    }
+
+    #[tokio::test]
+    async fn save_writes_the_whole_paste() {
+        let text = Arc::new("line\n".repeat(3));
+
+        let saved = save(Arc::clone(&text)).await.expect("save paste");
+
+        assert_eq!(saved.bytes, 15);
+        assert_eq!(saved.lines, 3);
+        assert_eq!(
+            std::fs::read_to_string(&saved.path).expect("read paste"),
+            *text
+        );
+        let _ = std::fs::remove_file(&saved.path);
+    }
+}
@@ -0,0 +1,130 @@
+//! State behind `/pin` and `/unpin`.
+//!
+//! The widget keeps the pinned paths and sends the full list to core with
+//! `Op::SetPinnedFiles` after every change; core re-reads the files for each request. Token
+//! costs are estimated from file sizes when a file is pinned, for the footer badge.
+
+use std::path::Path;
+use std::path::PathBuf;
+
+use codex_core::MAX_PINNED_FILE_BYTES;
+
+use crate::bottom_pane::PinnedFilesSummary;
+
+const APPROX_BYTES_PER_TOKEN: u64 = 4;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct PinnedFile {
+    pub(crate) path: PathBuf,
+    pub(crate) tokens: i64,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct PinnedFiles {
+    files: Vec<PinnedFile>,
+}
+
+impl PinnedFiles {
+    pub(crate) fn files(&self) -> &[PinnedFile] {
+        &self.files
+    }
+
+    pub(crate) fn paths(&self) -> Vec<PathBuf> {
+        self.files.iter().map(|file| file.path.clone()).collect()
+    }
+
+    pub(crate) fn summary(&self) -> Option<PinnedFilesSummary> {
+        (!self.files.is_empty()).then(|| PinnedFilesSummary {
+            count: self.files.len(),
+            tokens: self.files.iter().map(|file| file.tokens).sum(),
+        })
+    }
+
+    /// Pin the file `arg` names, relative to `cwd`. Pinning a file twice is an error.
+    pub(crate) fn pin(&mut self, arg: &str, cwd: &Path) -> Result<&PinnedFile, String> {
+        let path = resolve(arg, cwd);
+        let metadata = std::fs::metadata(&path)
+            .map_err(|err| format!("cannot pin {}: {err}", path.display()))?;
+        if !metadata.is_file() {
+            return Err(format!("{} is not a file", path.display()));
+        }
+        let path = std::fs::canonicalize(&path).unwrap_or(path);
+        if self.files.iter().any(|file| file.path == path) {
+            return Err(format!("{} is already pinned", path.display()));
+        }
+        let sent_bytes = metadata.len().min(MAX_PINNED_FILE_BYTES as u64);
+        let tokens = i64::try_from(sent_bytes.div_ceil(APPROX_BYTES_PER_TOKEN)).unwrap_or(i64::MAX);
+        self.files.push(PinnedFile { path, tokens });
+        Ok(&self.files[self.files.len() - 1])
+    }
+
+    /// Unpin the file `arg` names, relative to `cwd`, returning its path.
+    pub(crate) fn unpin(&mut self, arg: &str, cwd: &Path) -> Result<PathBuf, String> {
+        let path = resolve(arg, cwd);
+        let path = std::fs::canonicalize(&path).unwrap_or(path);
+        let Some(index) = self.files.iter().position(|file| file.path == path) else {
+            return Err(format!("{} is not pinned", path.display()));
+        };
+        Ok(self.files.remove(index).path)
+    }
+
+    /// Unpin everything, returning how many files were pinned.
+    pub(crate) fn clear(&mut self) -> usize {
+        std::mem::take(&mut self.files).len()
+    }
+}
+
+fn resolve(arg: &str, cwd: &Path) -> PathBuf {
+    let arg = arg.trim();
+    match arg.strip_prefix("~/") {
+        Some(rest) => dirs::home_dir()
+            .map(|home| home.join(rest))
+            .unwrap_or_else(|| cwd.join(arg)),
+        None => cwd.join(arg),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn pins_and_unpins_files_relative_to_cwd() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let root = std::fs::canonicalize(dir.path()).expect("canonicalize");
+        std::fs::write(root.join("schema.sql"), "x".repeat(400)).expect("write");
+        std::fs::write(root.join("STYLE.md"), "short").expect("write");
+        std::fs::create_dir(root.join("docs")).expect("mkdir");
+
+        let mut pinned = PinnedFiles::default();
+        assert_eq!(pinned.summary(), None);
+        assert_eq!(
+            pinned.pin("schema.sql", &root),
+            Ok(&PinnedFile {
+                path: root.join("schema.sql"),
+                tokens: 100,
+            })
+        );
+        assert!(pinned.pin("./schema.sql", &root).is_err());
+        assert!(pinned.pin("docs", &root).is_err());
+        assert!(pinned.pin("missing.md", &root).is_err());
+        pinned.pin("STYLE.md", &root).expect("pin style guide");
+        assert_eq!(
+            pinned.summary(),
+            Some(PinnedFilesSummary {
+                count: 2,
+                tokens: 102,
+            })
+        );
+
+        assert_eq!(
+            pinned.unpin("schema.sql", &root),
+            Ok(root.join("schema.sql"))
+        );
+        assert!(pinned.unpin("schema.sql", &root).is_err());
+        assert_eq!(pinned.paths(), vec![root.join("STYLE.md")]);
+        assert_eq!(pinned.clear(), 1);
+        assert_eq!(pinned.summary(), None);
+    }
+}
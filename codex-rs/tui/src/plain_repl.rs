@@ -0,0 +1,499 @@
+//! Line-based frontend for `codex --plain`.
+//!
+//! Prompts are read from stdin one line at a time and everything the agent does is printed as
+//! plain text, without cursor movement, colors, or an alternate screen. It drives the same
+//! `ThreadManager` as the full-screen UI, so sessions are recorded to rollouts and can be resumed
+//! there, and approval requests are asked on stdin. This suits dumb terminals, Emacs shells, and
+//! output that is piped to a log.
+
+use std::collections::HashMap;
+use std::io::BufRead;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use codex_core::AuthManager;
+use codex_core::CodexThread;
+use codex_core::NewThread;
+use codex_core::ThreadManager;
+use codex_core::config::Config;
+use codex_core::features::Feature;
+use codex_core::models_manager::collaboration_mode_presets::CollaborationModesConfig;
+use codex_protocol::protocol::ApplyPatchApprovalRequestEvent;
+use codex_protocol::protocol::CompactionReviewDecision;
+use codex_protocol::protocol::CompactionReviewRequestEvent;
+use codex_protocol::protocol::DryRunAction;
+use codex_protocol::protocol::ElicitationAction;
+use codex_protocol::protocol::Event;
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::ExecApprovalRequestEvent;
+use codex_protocol::protocol::FileChange;
+use codex_protocol::protocol::Op;
+use codex_protocol::protocol::PlanApprovalRequestEvent;
+use codex_protocol::protocol::ReviewDecision;
+use codex_protocol::protocol::SessionSource;
+use codex_protocol::protocol::TokenUsage;
+use codex_protocol::request_user_input::RequestUserInputAnswer;
+use codex_protocol::request_user_input::RequestUserInputEvent;
+use codex_protocol::request_user_input::RequestUserInputResponse;
+use codex_protocol::user_input::UserInput;
+use tokio::sync::mpsc::UnboundedReceiver;
+use tokio::sync::mpsc::unbounded_channel;
+
+use crate::AppExitInfo;
+use crate::ExitReason;
+use crate::exec_command::strip_bash_lc_and_escape;
+
+/// Command output is cut to its last lines so a noisy build does not bury the conversation.
+const MAX_OUTPUT_LINES: usize = 20;
+const PROMPT: &str = "› ";
+
+pub(crate) async fn run_plain_repl(
+    config: Config,
+    prompt: Option<String>,
+    images: Vec<PathBuf>,
+) -> std::io::Result<AppExitInfo> {
+    let auth_manager = AuthManager::shared(
+        config.codex_home.clone(),
+        false,
+        config.cli_auth_credentials_store_mode,
+    );
+    let thread_manager = Arc::new(ThreadManager::new(
+        config.codex_home.clone(),
+        auth_manager,
+        SessionSource::Cli,
+        config.model_catalog.clone(),
+        CollaborationModesConfig {
+            default_mode_request_user_input: config
+                .features
+                .enabled(Feature::DefaultModeRequestUserInput),
+        },
+    ));
+    let NewThread {
+        thread_id,
+        thread,
+        session_configured,
+    } = thread_manager
+        .start_thread(config)
+        .await
+        .map_err(|err| std::io::Error::other(format!("Failed to initialize codex: {err}")))?;
+
+    let mut repl = PlainRepl {
+        thread: Arc::clone(&thread),
+        out: std::io::stdout(),
+        lines: spawn_stdin_reader(),
+        events: spawn_event_reader(Arc::clone(&thread)),
+        token_usage: TokenUsage::default(),
+    };
+    repl.print(&format!(
+        "codex {} · model {} · {}",
+        env!("CARGO_PKG_VERSION"),
+        session_configured.model,
+        session_configured.cwd.display()
+    ))?;
+    repl.print("Type a prompt and press Enter. Ctrl+C interrupts a turn; Ctrl+D or /exit quits.")?;
+
+    {
+        let thread = Arc::clone(&thread);
+        tokio::spawn(async move {
+            while tokio::signal::ctrl_c().await.is_ok() {
+                let _ = thread.submit(Op::Interrupt).await;
+            }
+        });
+    }
+
+    let mut pending = prompt
+        .filter(|prompt| !prompt.trim().is_empty())
+        .map(|prompt| (prompt, images));
+    loop {
+        let (text, images) = match pending.take() {
+            Some(initial) => initial,
+            None => {
+                repl.write_prompt(PROMPT)?;
+                let Some(line) = repl.lines.recv().await else {
+                    break;
+                };
+                (line, Vec::new())
+            }
+        };
+        let text = text.trim().to_string();
+        if text.is_empty() {
+            continue;
+        }
+        if text == "/exit" || text == "/quit" {
+            break;
+        }
+        let mut items: Vec<UserInput> = images
+            .into_iter()
+            .map(|path| UserInput::LocalImage { path })
+            .collect();
+        items.push(UserInput::Text {
+            text,
+            text_elements: Vec::new(),
+        });
+        repl.submit(Op::UserInput {
+            items,
+            final_output_json_schema: None,
+        })
+        .await?;
+        repl.run_turn().await?;
+    }
+
+    repl.submit(Op::Shutdown).await?;
+    while let Some(event) = repl.events.recv().await {
+        if matches!(event.msg, EventMsg::ShutdownComplete) {
+            break;
+        }
+    }
+    Ok(AppExitInfo {
+        token_usage: repl.token_usage,
+        thread_id: Some(thread_id),
+        thread_name: session_configured.thread_name,
+        update_action: None,
+        exit_reason: ExitReason::UserRequested,
+    })
+}
+
+struct PlainRepl {
+    thread: Arc<CodexThread>,
+    out: std::io::Stdout,
+    lines: UnboundedReceiver<String>,
+    events: UnboundedReceiver<Event>,
+    token_usage: TokenUsage,
+}
+
+impl PlainRepl {
+    /// Print events until the turn started by the last prompt ends.
+    async fn run_turn(&mut self) -> std::io::Result<()> {
+        while let Some(event) = self.events.recv().await {
+            match event.msg {
+                EventMsg::TurnComplete(_) => return Ok(()),
+                EventMsg::TurnAborted(_) => {
+                    self.print("(interrupted)")?;
+                    return Ok(());
+                }
+                // A failed turn still ends with `TurnComplete`.
+                EventMsg::Error(err) => self.print(&format!("error: {}", err.message))?,
+                EventMsg::TokenCount(ev) => {
+                    if let Some(info) = ev.info {
+                        self.token_usage = info.total_token_usage;
+                    }
+                }
+                EventMsg::ExecApprovalRequest(ev) => self.approve_exec(ev).await?,
+                EventMsg::ApplyPatchApprovalRequest(ev) => self.approve_patch(ev).await?,
+                EventMsg::PlanApprovalRequest(ev) => self.approve_plan(ev).await?,
+                EventMsg::CompactionReviewRequest(ev) => self.review_compaction(ev).await?,
+                EventMsg::RequestUserInput(ev) => self.answer_questions(ev).await?,
+                EventMsg::ElicitationRequest(ev) => {
+                    self.print(&format!(
+                        "{} asked for input, which --plain does not support; declined.",
+                        ev.server_name
+                    ))?;
+                    self.submit(Op::ResolveElicitation {
+                        server_name: ev.server_name,
+                        request_id: ev.id,
+                        decision: ElicitationAction::Cancel,
+                    })
+                    .await?;
+                }
+                msg => {
+                    if let Some(text) = describe_event(&msg) {
+                        self.print(&text)?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    async fn approve_exec(&mut self, ev: ExecApprovalRequestEvent) -> std::io::Result<()> {
+        let mut question = format!("Run `{}`", strip_bash_lc_and_escape(&ev.command));
+        if let Some(reason) = &ev.reason {
+            question.push_str(&format!(" ({reason})"));
+        }
+        question.push_str("? [y]es / [a]lways this session / [N]o: ");
+        let decision = self.ask_decision(&question).await?;
+        self.submit(Op::ExecApproval {
+            id: ev.effective_approval_id(),
+            turn_id: Some(ev.turn_id),
+            decision,
+        })
+        .await
+    }
+
+    async fn approve_patch(&mut self, ev: ApplyPatchApprovalRequestEvent) -> std::io::Result<()> {
+        for line in describe_changes(&ev.changes) {
+            self.print(&line)?;
+        }
+        let mut question = "Apply these changes".to_string();
+        if let Some(reason) = &ev.reason {
+            question.push_str(&format!(" ({reason})"));
+        }
+        question.push_str("? [y]es / [a]lways this session / [N]o: ");
+        let decision = self.ask_decision(&question).await?;
+        self.submit(Op::PatchApproval {
+            id: ev.call_id,
+            decision,
+        })
+        .await
+    }
+
+    async fn approve_plan(&mut self, ev: PlanApprovalRequestEvent) -> std::io::Result<()> {
+        if let Some(explanation) = &ev.explanation {
+            self.print(explanation)?;
+        }
+        for (index, item) in ev.plan.iter().enumerate() {
+            self.print(&format!("  {}. {}", index + 1, item.step))?;
+        }
+        let decision = self
+            .ask_decision("Start on this plan? [y]es / [N]o: ")
+            .await?;
+        self.submit(Op::PatchApproval {
+            id: ev.call_id,
+            decision,
+        })
+        .await
+    }
+
+    async fn review_compaction(&mut self, ev: CompactionReviewRequestEvent) -> std::io::Result<()> {
+        self.print(&format!("Compaction summary:\n{}", ev.summary.trim_end()))?;
+        // Editing needs a multi-line editor, so plain mode only accepts or rejects.
+        let decision = match self
+            .ask_decision("Replace older turns with this summary? [y]es / [N]o: ")
+            .await?
+        {
+            ReviewDecision::Approved | ReviewDecision::ApprovedForSession => {
+                CompactionReviewDecision::Accept
+            }
+            _ => CompactionReviewDecision::Reject,
+        };
+        self.submit(Op::CompactionReview {
+            id: ev.id,
+            decision,
+        })
+        .await
+    }
+
+    async fn answer_questions(&mut self, ev: RequestUserInputEvent) -> std::io::Result<()> {
+        let mut answers = HashMap::new();
+        for question in ev.questions {
+            self.print(&format!("{}: {}", question.header, question.question))?;
+            for (index, option) in question.options.iter().flatten().enumerate() {
+                self.print(&format!("  {}. {}", index + 1, option.label))?;
+            }
+            self.write_prompt(PROMPT)?;
+            let answer = self.lines.recv().await.unwrap_or_default();
+            let answer = answer.trim();
+            // Accept an option's number as shorthand for its label.
+            let answer = answer
+                .parse::<usize>()
+                .ok()
+                .and_then(|number| question.options.as_ref()?.get(number.checked_sub(1)?))
+                .map(|option| option.label.clone())
+                .unwrap_or_else(|| answer.to_string());
+            answers.insert(
+                question.id,
+                RequestUserInputAnswer {
+                    answers: vec![answer],
+                },
+            );
+        }
+        self.submit(Op::UserInputAnswer {
+            id: ev.turn_id,
+            response: RequestUserInputResponse { answers },
+        })
+        .await
+    }
+
+    async fn ask_decision(&mut self, question: &str) -> std::io::Result<ReviewDecision> {
+        self.write_prompt(question)?;
+        let answer = self.lines.recv().await.unwrap_or_default();
+        Ok(parse_decision(&answer))
+    }
+
+    async fn submit(&self, op: Op) -> std::io::Result<()> {
+        self.thread
+            .submit(op)
+            .await
+            .map(|_| ())
+            .map_err(|err| std::io::Error::other(err.to_string()))
+    }
+
+    fn print(&mut self, text: &str) -> std::io::Result<()> {
+        writeln!(self.out, "{text}")?;
+        self.out.flush()
+    }
+
+    fn write_prompt(&mut self, prompt: &str) -> std::io::Result<()> {
+        write!(self.out, "{prompt}")?;
+        self.out.flush()
+    }
+}
+
+/// Read stdin on a blocking thread; the channel closes at end of input.
+fn spawn_stdin_reader() -> UnboundedReceiver<String> {
+    let (tx, rx) = unbounded_channel();
+    std::thread::spawn(move || {
+        for line in std::io::stdin().lock().lines() {
+            let Ok(line) = line else {
+                break;
+            };
+            if tx.send(line).is_err() {
+                break;
+            }
+        }
+    });
+    rx
+}
+
+fn spawn_event_reader(thread: Arc<CodexThread>) -> UnboundedReceiver<Event> {
+    let (tx, rx) = unbounded_channel();
+    tokio::spawn(async move {
+        while let Ok(event) = thread.next_event().await {
+            let is_shutdown = matches!(event.msg, EventMsg::ShutdownComplete);
+            if tx.send(event).is_err() || is_shutdown {
+                break;
+            }
+        }
+    });
+    rx
+}
+
+fn parse_decision(answer: &str) -> ReviewDecision {
+    match answer.trim().to_ascii_lowercase().as_str() {
+        "y" | "yes" => ReviewDecision::Approved,
+        "a" | "always" => ReviewDecision::ApprovedForSession,
+        _ => ReviewDecision::Denied,
+    }
+}
+
+/// The text printed for an event, or `None` for events that are not shown.
+fn describe_event(msg: &EventMsg) -> Option<String> {
+    match msg {
+        EventMsg::AgentMessage(ev) => Some(format!("\n{}\n", ev.message.trim_end())),
+        EventMsg::Warning(ev) => Some(format!("warning: {}", ev.message)),
+        EventMsg::StreamError(ev) => Some(format!("{} (retrying)", ev.message)),
+        EventMsg::ExecCommandBegin(ev) => {
+            Some(format!("$ {}", strip_bash_lc_and_escape(&ev.command)))
+        }
+        EventMsg::ExecCommandEnd(ev) => {
+            let mut text = tail_lines(&ev.aggregated_output, MAX_OUTPUT_LINES);
+            if ev.exit_code != 0 {
+                if !text.is_empty() {
+                    text.push('\n');
+                }
+                text.push_str(&format!("(exit {})", ev.exit_code));
+            }
+            (!text.is_empty()).then_some(text)
+        }
+        EventMsg::PatchApplyEnd(ev) => {
+            let mut lines = describe_changes(&ev.changes);
+            if !ev.success {
+                lines.push(format!("applying the patch failed: {}", ev.stderr.trim()));
+            }
+            Some(lines.join("\n"))
+        }
+        EventMsg::DryRunAction(ev) => Some(match &ev.action {
+            DryRunAction::Exec { command, .. } => {
+                format!("would run: {}", strip_bash_lc_and_escape(command))
+            }
+            DryRunAction::Patch { changes } => describe_changes(changes)
+                .into_iter()
+                .map(|line| format!("would have {line}"))
+                .collect::<Vec<_>>()
+                .join("\n"),
+        }),
+        EventMsg::McpToolCallBegin(ev) => Some(format!(
+            "calling {}.{}",
+            ev.invocation.server, ev.invocation.tool
+        )),
+        EventMsg::McpToolCallEnd(ev) => match &ev.result {
+            Ok(_) => None,
+            Err(err) => Some(format!(
+                "{}.{} failed: {err}",
+                ev.invocation.server, ev.invocation.tool
+            )),
+        },
+        EventMsg::WebSearchEnd(ev) => Some(format!("searched the web for {}", ev.query)),
+        _ => None,
+    }
+}
+
+fn describe_changes(changes: &HashMap<PathBuf, FileChange>) -> Vec<String> {
+    let mut paths: Vec<&Path> = changes.keys().map(PathBuf::as_path).collect();
+    paths.sort();
+    paths
+        .into_iter()
+        .map(|path| match &changes[path] {
+            FileChange::Add { .. } => format!("added {}", path.display()),
+            FileChange::Delete { .. } => format!("deleted {}", path.display()),
+            FileChange::Update {
+                move_path: Some(dest),
+                ..
+            } => format!("moved {} to {}", path.display(), dest.display()),
+            FileChange::Update { .. } => format!("edited {}", path.display()),
+        })
+        .collect()
+}
+
+fn tail_lines(text: &str, max: usize) -> String {
+    let lines: Vec<&str> = text.trim_end().lines().collect();
+    if lines.len() <= max {
+        return lines.join("\n");
+    }
+    let omitted = lines.len() - max;
+    format!("… +{omitted} lines\n{}", lines[omitted..].join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn approvals_default_to_no() {
+        assert_eq!(parse_decision("y"), ReviewDecision::Approved);
+        assert_eq!(
+            parse_decision(" Always\n"),
+            ReviewDecision::ApprovedForSession
+        );
+        assert_eq!(parse_decision(""), ReviewDecision::Denied);
+        assert_eq!(parse_decision("sure"), ReviewDecision::Denied);
+    }
+
+    #[test]
+    fn long_output_keeps_the_last_lines() {
+        let output = (1..=5)
+            .map(|n| n.to_string())
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        assert_eq!(tail_lines(&output, 2), "… +3 lines\n4\n5");
+        assert_eq!(tail_lines("ok\n", 2), "ok");
+    }
+
+    #[test]
+    fn changes_are_listed_in_path_order() {
+        let changes = HashMap::from([
+            (
+                PathBuf::from("src/b.rs"),
+                FileChange::Update {
+                    unified_diff: String::new(),
+                    move_path: None,
+                },
+            ),
+            (
+                PathBuf::from("src/a.rs"),
+                FileChange::Add {
+                    content: String::new(),
+                },
+            ),
+        ]);
+
+        assert_eq!(
+            describe_changes(&changes),
+            vec!["added src/a.rs".to_string(), "edited src/b.rs".to_string()]
+        );
+    }
+}
@@ -0,0 +1,255 @@
+//! Step list tracked alongside a plan.
+//!
+//! Steps are seeded from the `Steps` section of a proposed plan, can be edited by the user
+//! before "Implement this plan?" is accepted, and are then replaced by whatever the model
+//! reports through `update_plan`. When a turn ends without a plan update, the step in progress
+//! is checked off and the next one started, so the checklist and the footer keep moving even if
+//! the model forgets to report progress.
+
+use codex_protocol::plan_tool::PlanItemArg;
+use codex_protocol::plan_tool::StepStatus;
+use codex_protocol::plan_tool::UpdatePlanArgs;
+
+use crate::bottom_pane::PlanStepSummary;
+
+#[derive(Debug, Default)]
+pub(crate) struct PlanSteps {
+    steps: Vec<PlanItemArg>,
+}
+
+impl PlanSteps {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    /// Replace the steps with the list the model sent through `update_plan`.
+    pub(crate) fn replace(&mut self, steps: Vec<PlanItemArg>) {
+        self.steps = steps;
+    }
+
+    /// Start tracking the `Steps` section of a proposed plan, returning whether it had one.
+    pub(crate) fn seed_from_proposed_plan(&mut self, plan_markdown: &str) -> bool {
+        let steps = parse_plan_steps(plan_markdown);
+        if steps.is_empty() {
+            return false;
+        }
+        self.set_texts(steps);
+        true
+    }
+
+    /// The steps as the user edits them: one per line, in order.
+    pub(crate) fn edit_text(&self) -> String {
+        self.steps
+            .iter()
+            .map(|item| item.step.as_str())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Replace the steps with the user's edited list, one step per non-empty line.
+    pub(crate) fn apply_edit(&mut self, text: &str) {
+        self.set_texts(
+            text.lines()
+                .map(strip_list_marker)
+                .filter(|line| !line.is_empty())
+                .map(str::to_string)
+                .collect(),
+        );
+    }
+
+    /// Check off the step in progress and start the next pending one. Returns whether anything
+    /// changed.
+    pub(crate) fn advance(&mut self) -> bool {
+        let Some(current) = self
+            .steps
+            .iter()
+            .position(|item| matches!(item.status, StepStatus::InProgress))
+        else {
+            return false;
+        };
+        self.steps[current].status = StepStatus::Completed;
+        if let Some(next) = self.steps[current + 1..]
+            .iter_mut()
+            .find(|item| matches!(item.status, StepStatus::Pending))
+        {
+            next.status = StepStatus::InProgress;
+        }
+        true
+    }
+
+    /// The step in progress, or the first pending one, for the footer.
+    pub(crate) fn current(&self) -> Option<PlanStepSummary> {
+        let index = self
+            .steps
+            .iter()
+            .position(|item| matches!(item.status, StepStatus::InProgress))
+            .or_else(|| {
+                self.steps
+                    .iter()
+                    .position(|item| matches!(item.status, StepStatus::Pending))
+            })?;
+        Some(PlanStepSummary {
+            number: index + 1,
+            total: self.steps.len(),
+            text: self.steps[index].step.clone(),
+        })
+    }
+
+    /// The steps as a checklist update for the transcript.
+    pub(crate) fn as_update(&self) -> UpdatePlanArgs {
+        UpdatePlanArgs {
+            explanation: None,
+            plan: self.steps.clone(),
+        }
+    }
+
+    /// Instructions appended to "Implement the plan." so the model works through the steps
+    /// the user agreed to.
+    pub(crate) fn implementation_instructions(&self) -> String {
+        let mut text = String::from(
+            "Work through these steps in order and keep them up to date with update_plan:",
+        );
+        for (index, item) in self.steps.iter().enumerate() {
+            text.push_str(&format!("\n{}. {}", index + 1, item.step));
+        }
+        text
+    }
+
+    fn set_texts(&mut self, steps: Vec<String>) {
+        self.steps = steps
+            .into_iter()
+            .enumerate()
+            .map(|(index, step)| PlanItemArg {
+                step,
+                status: if index == 0 {
+                    StepStatus::InProgress
+                } else {
+                    StepStatus::Pending
+                },
+            })
+            .collect();
+    }
+}
+
+/// Top-level list items under the plan's `Steps` heading.
+fn parse_plan_steps(plan_markdown: &str) -> Vec<String> {
+    let mut in_steps = false;
+    let mut steps = Vec::new();
+    for line in plan_markdown.lines() {
+        if let Some(heading) = heading_text(line) {
+            if in_steps {
+                break;
+            }
+            in_steps = heading.to_ascii_lowercase().contains("steps");
+            continue;
+        }
+        if !in_steps || line.starts_with(char::is_whitespace) {
+            continue;
+        }
+        let step = strip_list_marker(line);
+        if step.len() < line.trim().len() && !step.is_empty() {
+            steps.push(step.to_string());
+        }
+    }
+    steps
+}
+
+/// Text of a markdown heading, or of a line that is entirely bold (`**Steps**`).
+fn heading_text(line: &str) -> Option<&str> {
+    let line = line.trim();
+    if line.starts_with('#') {
+        return Some(line.trim_start_matches('#').trim());
+    }
+    line.strip_prefix("**")
+        .and_then(|rest| rest.strip_suffix("**"))
+        .map(|text| text.trim_end_matches(':'))
+}
+
+/// Strip a leading `1.`, `1)`, `-`, `*`, or `- [ ]` list marker.
+fn strip_list_marker(line: &str) -> &str {
+    let line = line.trim();
+    let rest = if let Some(rest) = line.strip_prefix(['-', '*']) {
+        rest
+    } else {
+        let digits = line.len() - line.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+        match line[digits..].strip_prefix(['.', ')']) {
+            Some(rest) if digits > 0 => rest,
+            _ => return line,
+        }
+    };
+    let rest = rest.trim_start();
+    rest.strip_prefix("[ ]")
+        .or_else(|| rest.strip_prefix("[x]"))
+        .unwrap_or(rest)
+        .trim()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn statuses(steps: &PlanSteps) -> Vec<&'static str> {
+        steps
+            .steps
+            .iter()
+            .map(|item| match item.status {
+                StepStatus::Pending => "pending",
+                StepStatus::InProgress => "in_progress",
+                StepStatus::Completed => "completed",
+            })
+            .collect()
+    }
+
+    #[test]
+    fn seeds_steps_from_the_plan_and_advances_through_them() {
+        let plan = "# Add retries\n\n## Summary\n- Retry failed uploads.\n\n## Steps\n1. Add a retry policy type\n2. Wrap the upload call\n   - keep the timeout\n3. Test backoff timing\n\n## Assumptions\n- Three attempts.\n";
+        let mut steps = PlanSteps::default();
+        assert!(steps.seed_from_proposed_plan(plan));
+        assert_eq!(
+            steps.edit_text(),
+            "Add a retry policy type\nWrap the upload call\nTest backoff timing"
+        );
+        assert_eq!(statuses(&steps), vec!["in_progress", "pending", "pending"]);
+
+        assert!(steps.advance());
+        assert_eq!(
+            statuses(&steps),
+            vec!["completed", "in_progress", "pending"]
+        );
+        assert_eq!(
+            steps.current(),
+            Some(PlanStepSummary {
+                number: 2,
+                total: 3,
+                text: "Wrap the upload call".to_string(),
+            })
+        );
+        assert!(steps.advance());
+        assert!(steps.advance());
+        assert!(!steps.advance());
+        assert_eq!(steps.current(), None);
+    }
+
+    #[test]
+    fn plans_without_a_steps_section_are_not_tracked() {
+        let mut steps = PlanSteps::default();
+        assert!(!steps.seed_from_proposed_plan("# Title\n\n## Summary\n1. Do it\n"));
+        assert!(steps.is_empty());
+    }
+
+    #[test]
+    fn edits_replace_and_reorder_steps() {
+        let mut steps = PlanSteps::default();
+        steps.apply_edit("2. Test backoff timing\n\n- Add a retry policy type\n");
+        assert_eq!(
+            steps.edit_text(),
+            "Test backoff timing\nAdd a retry policy type"
+        );
+        assert_eq!(statuses(&steps), vec!["in_progress", "pending"]);
+        assert_eq!(
+            steps.implementation_instructions(),
+            "Work through these steps in order and keep them up to date with update_plan:\n1. Test backoff timing\n2. Add a retry policy type"
+        );
+    }
+}
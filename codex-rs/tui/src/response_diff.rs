@@ -0,0 +1,187 @@
+//! Compact diff between an assistant answer and the one `/retry` produced in its place.
+//!
+//! Lines are compared first; a changed line that lines up with a single replacement line is
+//! compared word by word so a reworded sentence shows only the words that moved. Unchanged
+//! stretches collapse to one line of context on each side.
+
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::text::Span;
+use similar::ChangeTag;
+use similar::DiffOp;
+use similar::TextDiff;
+
+const CONTEXT_LINES: usize = 1;
+const MAX_DIFF_LINES: usize = 40;
+
+pub(crate) fn retry_diff_lines(previous: &str, retried: &str) -> Vec<Line<'static>> {
+    let diff = TextDiff::from_lines(previous, retried);
+    let (mut added, mut removed) = (0, 0);
+    for change in diff.iter_all_changes() {
+        match change.tag() {
+            ChangeTag::Insert => added += 1,
+            ChangeTag::Delete => removed += 1,
+            ChangeTag::Equal => {}
+        }
+    }
+    if added == 0 && removed == 0 {
+        return vec![
+            vec![
+                "• ".dim(),
+                "The retried answer is identical to the previous one.".into(),
+            ]
+            .into(),
+        ];
+    }
+
+    let mut body = Vec::new();
+    for (index, group) in diff.grouped_ops(CONTEXT_LINES).iter().enumerate() {
+        if index > 0 {
+            body.push("  ⋮".dim().into());
+        }
+        for op in group {
+            push_op(diff.old_slices(), diff.new_slices(), op, &mut body);
+        }
+    }
+    let hidden = body.len().saturating_sub(MAX_DIFF_LINES);
+    body.truncate(MAX_DIFF_LINES);
+
+    let mut lines: Vec<Line<'static>> = vec![
+        vec![
+            "• ".dim(),
+            "Changes from the previous answer ".bold(),
+            format!("+{added}").green(),
+            " ".into(),
+            format!("-{removed}").red(),
+        ]
+        .into(),
+    ];
+    lines.extend(body);
+    if hidden > 0 {
+        lines.push(format!("  … {hidden} more lines").dim().into());
+    }
+    lines
+}
+
+fn push_op(old: &[&str], new: &[&str], op: &DiffOp, out: &mut Vec<Line<'static>>) {
+    match *op {
+        DiffOp::Equal { old_index, len, .. } => {
+            for line in &old[old_index..old_index + len] {
+                out.push(format!("  {}", strip_newline(line)).dim().into());
+            }
+        }
+        DiffOp::Delete {
+            old_index, old_len, ..
+        } => push_removed(&old[old_index..old_index + old_len], out),
+        DiffOp::Insert {
+            new_index, new_len, ..
+        } => push_added(&new[new_index..new_index + new_len], out),
+        DiffOp::Replace {
+            old_index,
+            old_len,
+            new_index,
+            new_len,
+        } => {
+            let old = &old[old_index..old_index + old_len];
+            let new = &new[new_index..new_index + new_len];
+            if old_len == new_len {
+                for (old_line, new_line) in old.iter().zip(new) {
+                    out.extend(word_diff(strip_newline(old_line), strip_newline(new_line)));
+                }
+            } else {
+                push_removed(old, out);
+                push_added(new, out);
+            }
+        }
+    }
+}
+
+fn push_removed(lines: &[&str], out: &mut Vec<Line<'static>>) {
+    out.extend(
+        lines
+            .iter()
+            .map(|line| format!("- {}", strip_newline(line)).red().into()),
+    );
+}
+
+fn push_added(lines: &[&str], out: &mut Vec<Line<'static>>) {
+    out.extend(
+        lines
+            .iter()
+            .map(|line| format!("+ {}", strip_newline(line)).green().into()),
+    );
+}
+
+/// A removed and an added line with the words that differ in bold.
+fn word_diff(old: &str, new: &str) -> [Line<'static>; 2] {
+    let mut removed: Vec<Span<'static>> = vec!["- ".red()];
+    let mut added: Vec<Span<'static>> = vec!["+ ".green()];
+    for change in TextDiff::from_words(old, new).iter_all_changes() {
+        let text = change.value().to_string();
+        match change.tag() {
+            ChangeTag::Equal => {
+                removed.push(text.clone().red());
+                added.push(text.green());
+            }
+            ChangeTag::Delete => removed.push(text.red().bold()),
+            ChangeTag::Insert => added.push(text.green().bold()),
+        }
+    }
+    [removed.into(), added.into()]
+}
+
+fn strip_newline(line: &str) -> &str {
+    line.trim_end_matches(['\n', '\r'])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use ratatui::style::Modifier;
+
+    fn plain(lines: &[Line<'static>]) -> Vec<String> {
+        lines
+            .iter()
+            .map(|line| {
+                line.spans
+                    .iter()
+                    .map(|span| span.content.as_ref())
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn shows_changed_lines_with_context() {
+        let previous = "intro\nkeep\nuse a mutex here\nkeep too\nold tail\n";
+        let retried = "intro\nkeep\nuse a channel here\nkeep too\n";
+
+        let lines = retry_diff_lines(previous, retried);
+        assert_eq!(
+            plain(&lines),
+            vec![
+                "• Changes from the previous answer +1 -2",
+                "  keep",
+                "- use a mutex here",
+                "+ use a channel here",
+                "  keep too",
+                "- old tail",
+            ]
+        );
+        let changed_word = lines[3]
+            .spans
+            .iter()
+            .find(|span| span.content == "channel")
+            .expect("changed word span");
+        assert!(changed_word.style.add_modifier.contains(Modifier::BOLD));
+    }
+
+    #[test]
+    fn identical_answers_say_so() {
+        assert_eq!(
+            plain(&retry_diff_lines("same\n", "same\n")),
+            vec!["• The retried answer is identical to the previous one."]
+        );
+    }
+}
@@ -18,9 +18,11 @@ use codex_core::RolloutRecorder;
 use codex_core::ThreadItem;
 use codex_core::ThreadSortKey;
 use codex_core::ThreadsPage;
+use codex_core::archive_rollout;
 use codex_core::config::Config;
 use codex_core::find_thread_names_by_ids;
 use codex_core::path_utils;
+use codex_core::unarchive_rollout;
 use codex_protocol::ThreadId;
 use color_eyre::eyre::Result;
 use crossterm::event::KeyCode;
@@ -91,6 +93,7 @@ struct PageLoadRequest {
     search_token: Option<usize>,
     default_provider: String,
     sort_key: ThreadSortKey,
+    archived: bool,
 }
 
 type PageLoader = Arc<dyn Fn(PageLoadRequest) + Send + Sync>;
@@ -159,17 +162,31 @@ async fn run_session_picker(
         let config = config.clone();
         tokio::spawn(async move {
             let provider_filter = vec![request.default_provider.clone()];
-            let page = RolloutRecorder::list_threads(
-                &config,
-                PAGE_SIZE,
-                request.cursor.as_ref(),
-                request.sort_key,
-                INTERACTIVE_SESSION_SOURCES,
-                Some(provider_filter.as_slice()),
-                request.default_provider.as_str(),
-                None,
-            )
-            .await;
+            let page = if request.archived {
+                RolloutRecorder::list_archived_threads(
+                    &config,
+                    PAGE_SIZE,
+                    request.cursor.as_ref(),
+                    request.sort_key,
+                    INTERACTIVE_SESSION_SOURCES,
+                    Some(provider_filter.as_slice()),
+                    request.default_provider.as_str(),
+                    None,
+                )
+                .await
+            } else {
+                RolloutRecorder::list_threads(
+                    &config,
+                    PAGE_SIZE,
+                    request.cursor.as_ref(),
+                    request.sort_key,
+                    INTERACTIVE_SESSION_SOURCES,
+                    Some(provider_filter.as_slice()),
+                    request.default_provider.as_str(),
+                    None,
+                )
+                .await
+            };
             let _ = tx.send(BackgroundEvent::PageLoaded {
                 request_token: request.request_token,
                 search_token: request.search_token,
@@ -273,6 +290,8 @@ struct PickerState {
     filter_cwd: Option<PathBuf>,
     action: SessionPickerAction,
     sort_key: ThreadSortKey,
+    /// Whether the picker lists archived sessions instead of the active ones.
+    archived: bool,
     thread_name_cache: HashMap<ThreadId, Option<String>>,
     inline_error: Option<String>,
 }
@@ -391,6 +410,7 @@ impl PickerState {
             filter_cwd,
             action,
             sort_key: ThreadSortKey::CreatedAt,
+            archived: false,
             thread_name_cache: HashMap::new(),
             inline_error: None,
         }
@@ -411,6 +431,21 @@ impl PickerState {
             {
                 return Ok(Some(SessionSelection::Exit));
             }
+            KeyCode::Char('a')
+                if key
+                    .modifiers
+                    .contains(crossterm::event::KeyModifiers::CONTROL) =>
+            {
+                self.toggle_selected_archived().await;
+            }
+            KeyCode::Char('o')
+                if key
+                    .modifiers
+                    .contains(crossterm::event::KeyModifiers::CONTROL) =>
+            {
+                self.archived = !self.archived;
+                self.start_initial_load();
+            }
             KeyCode::Enter => {
                 if let Some(row) = self.filtered_rows.get(self.selected) {
                     let path = row.path.clone();
@@ -487,6 +522,30 @@ impl PickerState {
         Ok(None)
     }
 
+    /// Archive the selected session, or restore it when browsing the archive.
+    async fn toggle_selected_archived(&mut self) {
+        let Some(row) = self.filtered_rows.get(self.selected) else {
+            return;
+        };
+        let path = row.path.clone();
+        let (verb, result) = if self.archived {
+            ("restore", unarchive_rollout(&self.codex_home, &path).await)
+        } else {
+            ("archive", archive_rollout(&self.codex_home, &path).await)
+        };
+        match result {
+            Ok(_) => {
+                self.all_rows.retain(|row| row.path != path);
+                self.seen_paths.remove(&path);
+                self.apply_filter();
+            }
+            Err(err) => {
+                self.inline_error = Some(format!("Failed to {verb} {}: {err}", path.display()));
+                self.request_frame();
+            }
+        }
+    }
+
     fn start_initial_load(&mut self) {
         self.reset_pagination();
         self.all_rows.clear();
@@ -516,6 +575,7 @@ impl PickerState {
             search_token,
             default_provider: self.default_provider.clone(),
             sort_key: self.sort_key,
+            archived: self.archived,
         });
     }
 
@@ -786,6 +846,7 @@ impl PickerState {
             search_token,
             default_provider: self.default_provider.clone(),
             sort_key: self.sort_key,
+            archived: self.archived,
         });
     }
 
@@ -878,14 +939,17 @@ fn draw_picker(tui: &mut Tui, state: &PickerState) -> std::io::Result<()> {
         .areas(area);
 
         // Header
-        let header_line: Line = vec![
-            state.action.title().bold().cyan(),
+        let mut header_spans = vec![state.action.title().bold().cyan()];
+        if state.archived {
+            header_spans.extend(["  ".into(), "(archived)".magenta()]);
+        }
+        header_spans.extend([
             "  ".into(),
             "Sort:".dim(),
             " ".into(),
             sort_key_label(state.sort_key).magenta(),
-        ]
-        .into();
+        ]);
+        let header_line: Line = header_spans.into();
         frame.render_widget_ref(header_line, header);
 
         // Search line
@@ -912,6 +976,20 @@ fn draw_picker(tui: &mut Tui, state: &PickerState) -> std::io::Result<()> {
             key_hint::plain(KeyCode::Tab).into(),
             " to toggle sort ".dim(),
             "    ".dim(),
+            key_hint::ctrl(KeyCode::Char('a')).into(),
+            if state.archived {
+                " to restore ".dim()
+            } else {
+                " to archive ".dim()
+            },
+            "    ".dim(),
+            key_hint::ctrl(KeyCode::Char('o')).into(),
+            if state.archived {
+                " for active sessions ".dim()
+            } else {
+                " for archived ".dim()
+            },
+            "    ".dim(),
             key_hint::plain(KeyCode::Up).into(),
             "/".dim(),
             key_hint::plain(KeyCode::Down).into(),
@@ -1082,6 +1160,10 @@ fn render_empty_state_line(state: &PickerState) -> Line<'static> {
         return vec!["No results for your search".italic().dim()].into();
     }
 
+    if state.archived && state.all_rows.is_empty() && !state.pagination.loading.is_pending() {
+        return vec!["No archived sessions".italic().dim()].into();
+    }
+
     if state.all_rows.is_empty() && state.pagination.num_scanned_files == 0 {
         return vec!["No sessions yet".italic().dim()].into();
     }
@@ -2121,6 +2203,39 @@ mod tests {
         assert_eq!(guard[1].sort_key, ThreadSortKey::UpdatedAt);
     }
 
+    #[tokio::test]
+    async fn ctrl_o_reloads_archived_sessions() {
+        let recorded_requests: Arc<Mutex<Vec<PageLoadRequest>>> = Arc::new(Mutex::new(Vec::new()));
+        let request_sink = recorded_requests.clone();
+        let loader: PageLoader = Arc::new(move |req: PageLoadRequest| {
+            request_sink.lock().unwrap().push(req);
+        });
+
+        let mut state = PickerState::new(
+            PathBuf::from("/tmp"),
+            FrameRequester::test_dummy(),
+            loader,
+            String::from("openai"),
+            true,
+            None,
+            SessionPickerAction::Resume,
+        );
+
+        state.start_initial_load();
+        state
+            .handle_key(KeyEvent::new(KeyCode::Char('o'), KeyModifiers::CONTROL))
+            .await
+            .unwrap();
+        state
+            .handle_key(KeyEvent::new(KeyCode::Char('o'), KeyModifiers::CONTROL))
+            .await
+            .unwrap();
+
+        let guard = recorded_requests.lock().unwrap();
+        let archived: Vec<bool> = guard.iter().map(|req| req.archived).collect();
+        assert_eq!(archived, vec![false, true, false]);
+    }
+
     #[tokio::test]
     async fn page_navigation_uses_view_rows() {
         let loader: PageLoader = Arc::new(|_| {});
@@ -0,0 +1,237 @@
+//! Capture terminal output from before Codex started, for `/scrollback`.
+//!
+//! A terminal does not let applications read back its scrollback, so we rely on something
+//! that records it for us:
+//!
+//! - a log written by a wrapper pty named in `CODEX_SCROLLBACK_LOG` (for example
+//!   `script -qf "$CODEX_SCROLLBACK_LOG"`), or
+//! - the tmux pane Codex runs in (`$TMUX_PANE`).
+//!
+//! The position at launch is recorded before the TUI draws anything, so captures only ever
+//! include output that was on screen before Codex started, not Codex's own rendering.
+
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+use codex_utils_string::strip_ansi_escapes;
+use tokio::io::AsyncReadExt;
+use tokio::io::AsyncSeekExt;
+use tokio::process::Command;
+
+/// Lines captured when `/scrollback` is run without a count.
+pub(crate) const DEFAULT_SCROLLBACK_LINES: usize = 50;
+/// Largest count accepted by `/scrollback <n>`.
+pub(crate) const MAX_SCROLLBACK_LINES: usize = 2_000;
+/// Environment variable naming a pty log to read instead of tmux.
+const SCROLLBACK_LOG_ENV_VAR: &str = "CODEX_SCROLLBACK_LOG";
+/// Only the tail of a pty log is read; `MAX_SCROLLBACK_LINES` lines fit comfortably.
+const MAX_LOG_TAIL_BYTES: u64 = 1024 * 1024;
+
+#[derive(Debug, Clone)]
+enum LaunchPosition {
+    /// Byte length of the pty log when Codex started.
+    Log { path: PathBuf, offset: u64 },
+    /// Absolute line (history plus screen) of the cursor in the tmux pane when Codex started.
+    Tmux { pane: String, line: i64 },
+}
+
+static LAUNCH_POSITION: OnceLock<Option<LaunchPosition>> = OnceLock::new();
+
+/// Remember where the scrollback ends before the TUI takes over the terminal.
+pub(crate) fn record_launch_position() {
+    LAUNCH_POSITION.get_or_init(detect_launch_position);
+}
+
+fn detect_launch_position() -> Option<LaunchPosition> {
+    if let Some(path) = std::env::var_os(SCROLLBACK_LOG_ENV_VAR).map(PathBuf::from) {
+        let offset = std::fs::metadata(&path).ok()?.len();
+        return Some(LaunchPosition::Log { path, offset });
+    }
+    let pane = std::env::var("TMUX_PANE").ok()?;
+    let output = std::process::Command::new("tmux")
+        .args(["display-message", "-p", "-t", &pane])
+        .arg("#{history_size} #{cursor_y}")
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut fields = stdout.split_whitespace().map(str::parse::<i64>);
+    let history_size = fields.next()?.ok()?;
+    let cursor_y = fields.next()?.ok()?;
+    Some(LaunchPosition::Tmux {
+        pane,
+        line: history_size + cursor_y,
+    })
+}
+
+/// Parse the optional line count given to `/scrollback`.
+pub(crate) fn parse_line_count(args: &str) -> Result<usize, String> {
+    let args = args.trim();
+    if args.is_empty() {
+        return Ok(DEFAULT_SCROLLBACK_LINES);
+    }
+    match args.parse::<usize>() {
+        Ok(count) if (1..=MAX_SCROLLBACK_LINES).contains(&count) => Ok(count),
+        _ => Err(format!(
+            "Usage: /scrollback [lines], where lines is between 1 and {MAX_SCROLLBACK_LINES}"
+        )),
+    }
+}
+
+/// Capture up to `lines` lines of output from before Codex started.
+pub(crate) async fn capture(lines: usize) -> Result<String, String> {
+    let position = LAUNCH_POSITION.get().cloned().flatten().ok_or_else(|| {
+        format!(
+            "no scrollback source found; run Codex inside tmux, or under a pty logger with \
+             {SCROLLBACK_LOG_ENV_VAR} set to the log path"
+        )
+    })?;
+    let text = match position {
+        LaunchPosition::Log { path, offset } => capture_from_log(&path, offset, lines).await?,
+        LaunchPosition::Tmux { pane, line } => capture_from_tmux(&pane, line, lines).await?,
+    };
+    if text.trim().is_empty() {
+        return Err("the terminal scrollback before launch is empty".to_string());
+    }
+    Ok(text)
+}
+
+async fn capture_from_log(path: &Path, offset: u64, lines: usize) -> Result<String, String> {
+    let read_error = |err: std::io::Error| format!("failed to read {}: {err}", path.display());
+    let mut file = tokio::fs::File::open(path).await.map_err(read_error)?;
+    let start = offset.saturating_sub(MAX_LOG_TAIL_BYTES);
+    file.seek(std::io::SeekFrom::Start(start))
+        .await
+        .map_err(read_error)?;
+    let mut bytes = Vec::new();
+    file.take(offset - start)
+        .read_to_end(&mut bytes)
+        .await
+        .map_err(read_error)?;
+    Ok(last_lines(
+        &strip_ansi(&String::from_utf8_lossy(&bytes)),
+        lines,
+    ))
+}
+
+async fn capture_from_tmux(pane: &str, launch_line: i64, lines: usize) -> Result<String, String> {
+    let output = Command::new("tmux")
+        .args(["display-message", "-p", "-t", pane, "#{history_size}"])
+        .output()
+        .await
+        .map_err(|err| format!("failed to run tmux: {err}"))?;
+    let history_size = String::from_utf8_lossy(&output.stdout)
+        .trim()
+        .parse::<i64>()
+        .map_err(|_| "tmux did not report the pane history size".to_string())?;
+
+    // `capture-pane` numbers the first visible row 0 and history rows negatively.
+    let end = launch_line - history_size - 1;
+    let start = end - lines as i64 + 1;
+    let output = Command::new("tmux")
+        .args(["capture-pane", "-p", "-J", "-t", pane])
+        .args(["-S", &start.to_string(), "-E", &end.to_string()])
+        .output()
+        .await
+        .map_err(|err| format!("failed to run tmux: {err}"))?;
+    if !output.status.success() {
+        return Err(format!(
+            "tmux capture-pane failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(last_lines(&String::from_utf8_lossy(&output.stdout), lines))
+}
+
+/// The last `count` lines of `text`, ignoring trailing blank lines.
+fn last_lines(text: &str, count: usize) -> String {
+    let lines: Vec<&str> = text.lines().map(str::trim_end).collect();
+    let end = lines
+        .iter()
+        .rposition(|line| !line.is_empty())
+        .map_or(0, |index| index + 1);
+    let start = end.saturating_sub(count);
+    lines[start..end].join("\n")
+}
+
+/// Drop escape sequences and apply carriage returns so a raw pty log reads like the screen did.
+fn strip_ansi(text: &str) -> String {
+    let text = strip_ansi_escapes(text);
+    let mut out = String::with_capacity(text.len());
+    let mut line_start = 0;
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\r' => out.truncate(line_start),
+            '\n' => {
+                out.push('\n');
+                line_start = out.len();
+            }
+            '\u{8}' => {
+                if out.len() > line_start {
+                    out.pop();
+                }
+            }
+            c if c.is_control() && c != '\t' => {}
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Wrap captured output so the model can tell it apart from the user's own words.
+pub(crate) fn format_scrollback(text: &str) -> String {
+    format!(
+        "<terminal_scrollback lines=\"{}\">\n{text}\n</terminal_scrollback>",
+        text.lines().count()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn parses_line_counts() {
+        assert_eq!(parse_line_count(""), Ok(DEFAULT_SCROLLBACK_LINES));
+        assert_eq!(parse_line_count(" 120 "), Ok(120));
+        assert!(parse_line_count("0").is_err());
+        assert!(parse_line_count("lots").is_err());
+        assert!(parse_line_count(&(MAX_SCROLLBACK_LINES + 1).to_string()).is_err());
+    }
+
+    #[test]
+    fn strips_escape_sequences_and_carriage_returns() {
+        let raw = "\u{1b}]0;title\u{7}$ cargo test\r\n\u{1b}[32mok\u{1b}[0m\r\nBuilding 10%\rBuilding 100%\n";
+
+        assert_eq!(
+            strip_ansi(raw),
+            "$ cargo test\nok\nBuilding 100%\n".to_string()
+        );
+    }
+
+    #[test]
+    fn keeps_last_lines_without_trailing_blanks() {
+        assert_eq!(last_lines("one\ntwo\nthree\n\n\n", 2), "two\nthree");
+        assert_eq!(last_lines("only\n", 10), "only");
+    }
+
+    #[tokio::test]
+    async fn log_capture_stops_at_launch_offset() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("pty.log");
+        let before = "$ make\nerror: missing target\n";
+        std::fs::write(&path, format!("{before}codex tui output")).expect("write log");
+
+        let text = capture_from_log(&path, before.len() as u64, 5)
+            .await
+            .expect("capture");
+
+        assert_eq!(text, "$ make\nerror: missing target");
+    }
+}
@@ -0,0 +1,257 @@
+//! Side-by-side comparison of two saved sessions for `/compare`.
+//!
+//! Sessions are referenced by rollout path, thread id, or thread name. The two rollouts are
+//! summarized by `codex_core::compare_rollouts`, which pairs turns by position; this module
+//! renders each pair as a full-width prompt header followed by two columns holding the
+//! assistant responses and `apply_patch` payloads of each session.
+
+use std::path::Path;
+use std::path::PathBuf;
+
+use codex_core::SessionComparison;
+use codex_core::SessionTranscript;
+use codex_core::TranscriptTurn;
+use codex_core::find_thread_path_by_reference;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::text::Span;
+use ratatui::widgets::Paragraph;
+use ratatui::widgets::Widget;
+use unicode_width::UnicodeWidthStr;
+
+use crate::markdown::append_markdown;
+use crate::render::renderable::Renderable;
+use crate::wrapping::word_wrap_lines;
+
+pub(crate) const COLUMN_SEPARATOR: &str = " │ ";
+
+/// Resolve a `/compare` argument to a rollout file.
+pub(crate) async fn resolve_session_path(
+    codex_home: &Path,
+    cwd: &Path,
+    reference: &str,
+) -> Result<PathBuf, String> {
+    find_thread_path_by_reference(codex_home, cwd, reference)
+        .await
+        .map_err(|err| format!("Failed to look up session {reference}: {err}"))?
+        .ok_or_else(|| format!("No saved session matches '{reference}'."))
+}
+
+pub(crate) struct SessionComparisonView {
+    comparison: SessionComparison,
+}
+
+impl SessionComparisonView {
+    pub(crate) fn new(comparison: SessionComparison) -> Self {
+        Self { comparison }
+    }
+
+    fn lines(&self, width: u16) -> Vec<Line<'static>> {
+        let width = usize::from(width.max(1));
+        let column_width = (width.saturating_sub(COLUMN_SEPARATOR.width()) / 2).max(1);
+        let SessionComparison { left, right } = &self.comparison;
+
+        let mut lines = Vec::new();
+        push_columns(
+            &mut lines,
+            vec![session_label("A", left)],
+            vec![session_label("B", right)],
+            column_width,
+        );
+        if self.comparison.turn_count() == 0 {
+            lines.push("".into());
+            lines.push("Neither session has any turns.".italic().into());
+            return lines;
+        }
+
+        for idx in 0..self.comparison.turn_count() {
+            let (left_turn, right_turn) = self.comparison.turn(idx);
+            lines.push("".into());
+            let left_prompt = left_turn.map(|turn| turn.prompt.as_str());
+            let right_prompt = right_turn.map(|turn| turn.prompt.as_str());
+            let header = format!("Turn {}", idx + 1);
+            match (left_prompt, right_prompt) {
+                (Some(left_prompt), Some(right_prompt)) if left_prompt != right_prompt => {
+                    lines.push(vec![header.bold(), " (prompts differ)".dim()].into());
+                    push_columns(
+                        &mut lines,
+                        prompt_lines(left_prompt, column_width),
+                        prompt_lines(right_prompt, column_width),
+                        column_width,
+                    );
+                }
+                (Some(prompt), _) | (None, Some(prompt)) => {
+                    lines.push(header.bold().into());
+                    lines.extend(prompt_lines(prompt, width));
+                }
+                (None, None) => lines.push(header.bold().into()),
+            }
+            lines.push(Line::from("─".repeat(width)).dim());
+            push_columns(
+                &mut lines,
+                column_lines(left_turn, column_width),
+                column_lines(right_turn, column_width),
+                column_width,
+            );
+        }
+        lines
+    }
+}
+
+impl Renderable for SessionComparisonView {
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        Paragraph::new(self.lines(area.width)).render(area, buf);
+    }
+
+    fn desired_height(&self, width: u16) -> u16 {
+        self.lines(width).len().try_into().unwrap_or(u16::MAX)
+    }
+}
+
+fn session_label(tag: &str, transcript: &SessionTranscript) -> Line<'static> {
+    let name = transcript
+        .path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| transcript.path.display().to_string());
+    let mut spans = vec![format!("{tag}: ").bold(), name.cyan()];
+    if let Some(model) = &transcript.model {
+        spans.push(format!(" ({model})").dim());
+    }
+    spans.into()
+}
+
+pub(crate) fn prompt_lines(prompt: &str, width: usize) -> Vec<Line<'static>> {
+    let prompt_lines: Vec<Line<'static>> = prompt
+        .lines()
+        .map(|line| Line::from(format!("› {line}")).italic())
+        .collect();
+    word_wrap_lines(prompt_lines, width)
+}
+
+fn column_lines(turn: Option<&TranscriptTurn>, width: usize) -> Vec<Line<'static>> {
+    let Some(turn) = turn else {
+        return vec!["(no matching turn)".dim().into()];
+    };
+    let mut lines = Vec::new();
+    for response in &turn.responses {
+        if !lines.is_empty() {
+            lines.push("".into());
+        }
+        append_markdown(response, Some(width), &mut lines);
+    }
+    for patch in &turn.patches {
+        if !lines.is_empty() {
+            lines.push("".into());
+        }
+        let patch_lines: Vec<Line<'static>> = patch.lines().map(patch_line).collect();
+        lines.extend(word_wrap_lines(patch_lines, width));
+    }
+    if lines.is_empty() {
+        lines.push("(no response)".dim().into());
+    }
+    lines
+}
+
+fn patch_line(line: &str) -> Line<'static> {
+    let line = line.to_string();
+    if line.starts_with("***") {
+        line.magenta().into()
+    } else if line.starts_with('+') {
+        line.green().into()
+    } else if line.starts_with('-') {
+        line.red().into()
+    } else {
+        line.dim().into()
+    }
+}
+
+pub(crate) fn push_columns(
+    out: &mut Vec<Line<'static>>,
+    left: Vec<Line<'static>>,
+    right: Vec<Line<'static>>,
+    column_width: usize,
+) {
+    let rows = left.len().max(right.len());
+    let mut left = left.into_iter();
+    let mut right = right.into_iter();
+    for _ in 0..rows {
+        let left_line = left.next().unwrap_or_default();
+        let padding = column_width.saturating_sub(left_line.width());
+        let mut spans: Vec<Span<'static>> = left_line
+            .spans
+            .into_iter()
+            .map(|span| span.patch_style(left_line.style))
+            .collect();
+        spans.push(" ".repeat(padding).into());
+        spans.push(COLUMN_SEPARATOR.dim());
+        if let Some(right_line) = right.next() {
+            spans.extend(
+                right_line
+                    .spans
+                    .into_iter()
+                    .map(|span| span.patch_style(right_line.style)),
+            );
+        }
+        out.push(spans.into());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use insta::assert_snapshot;
+
+    fn render(view: &SessionComparisonView, width: u16) -> String {
+        view.lines(width)
+            .iter()
+            .map(|line| {
+                line.spans
+                    .iter()
+                    .map(|span| span.content.as_ref())
+                    .collect::<String>()
+                    .trim_end()
+                    .to_string()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn renders_turns_side_by_side() {
+        let comparison = SessionComparison {
+            left: SessionTranscript {
+                path: PathBuf::from("/sessions/rollout-a.jsonl"),
+                model: Some("gpt-5".to_string()),
+                turns: vec![
+                    TranscriptTurn {
+                        prompt: "fix the bug".to_string(),
+                        responses: vec!["Fixed the off-by-one.".to_string()],
+                        patches: vec![
+                            "*** Begin Patch\n*** Update File: a.rs\n-let x = 1;\n+let x = 0;\n*** End Patch"
+                                .to_string(),
+                        ],
+                    },
+                    TranscriptTurn {
+                        prompt: "add a test".to_string(),
+                        responses: vec!["Added.".to_string()],
+                        patches: Vec::new(),
+                    },
+                ],
+            },
+            right: SessionTranscript {
+                path: PathBuf::from("/sessions/rollout-b.jsonl"),
+                model: Some("gpt-5-mini".to_string()),
+                turns: vec![TranscriptTurn {
+                    prompt: "fix the bug please".to_string(),
+                    responses: vec!["Done.".to_string()],
+                    patches: Vec::new(),
+                }],
+            },
+        };
+
+        assert_snapshot!(render(&SessionComparisonView::new(comparison), 60));
+    }
+}
@@ -0,0 +1,200 @@
+//! Prompt shown when the session picked for resume is already open in another Codex process.
+//!
+//! Only one process may append to a rollout (see `codex_core::RolloutLock`), so instead of
+//! failing at startup the user can fork the session or look at it read-only.
+
+use codex_core::SessionLockedError;
+use color_eyre::Result;
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEvent;
+use crossterm::event::KeyEventKind;
+use crossterm::event::KeyModifiers;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::prelude::Widget;
+use ratatui::style::Stylize as _;
+use ratatui::text::Line;
+use ratatui::widgets::Clear;
+use ratatui::widgets::WidgetRef;
+use tokio_stream::StreamExt;
+
+use crate::key_hint;
+use crate::render::Insets;
+use crate::render::renderable::ColumnRenderable;
+use crate::render::renderable::Renderable;
+use crate::render::renderable::RenderableExt as _;
+use crate::selection_list::selection_option_row;
+use crate::tui::FrameRequester;
+use crate::tui::Tui;
+use crate::tui::TuiEvent;
+
+const CHOICES: [LockedSessionChoice; 3] = [
+    LockedSessionChoice::Fork,
+    LockedSessionChoice::ReadOnly,
+    LockedSessionChoice::Exit,
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum LockedSessionChoice {
+    /// Continue in a new session that starts from a copy of the locked one.
+    Fork,
+    /// Resume without recording anything and with a read-only sandbox.
+    ReadOnly,
+    Exit,
+}
+
+impl LockedSessionChoice {
+    fn label(self) -> &'static str {
+        match self {
+            LockedSessionChoice::Fork => "Fork it into a new session",
+            LockedSessionChoice::ReadOnly => "Open it read-only (nothing is saved)",
+            LockedSessionChoice::Exit => "Exit",
+        }
+    }
+}
+
+/// Ask what to do with a session that another process has open.
+pub(crate) async fn run_locked_session_prompt(
+    tui: &mut Tui,
+    locked: &SessionLockedError,
+) -> Result<LockedSessionChoice> {
+    let mut screen = LockedSessionScreen::new(tui.frame_requester(), locked.holder_pid);
+    tui.draw(u16::MAX, |frame| {
+        frame.render_widget_ref(&screen, frame.area());
+    })?;
+
+    let events = tui.event_stream();
+    tokio::pin!(events);
+
+    while screen.choice.is_none() {
+        if let Some(event) = events.next().await {
+            match event {
+                TuiEvent::Key(key_event) => screen.handle_key(key_event),
+                TuiEvent::Paste(_) => {}
+                TuiEvent::Draw => {
+                    tui.draw(u16::MAX, |frame| {
+                        frame.render_widget_ref(&screen, frame.area());
+                    })?;
+                }
+            }
+        } else {
+            break;
+        }
+    }
+
+    Ok(screen.choice.unwrap_or(LockedSessionChoice::Exit))
+}
+
+struct LockedSessionScreen {
+    request_frame: FrameRequester,
+    holder_pid: Option<u32>,
+    highlighted: usize,
+    choice: Option<LockedSessionChoice>,
+}
+
+impl LockedSessionScreen {
+    fn new(request_frame: FrameRequester, holder_pid: Option<u32>) -> Self {
+        Self {
+            request_frame,
+            holder_pid,
+            highlighted: 0,
+            choice: None,
+        }
+    }
+
+    fn handle_key(&mut self, key_event: KeyEvent) {
+        if key_event.kind == KeyEventKind::Release {
+            return;
+        }
+        if key_event.modifiers.contains(KeyModifiers::CONTROL)
+            && matches!(key_event.code, KeyCode::Char('c') | KeyCode::Char('d'))
+        {
+            self.choose(LockedSessionChoice::Exit);
+            return;
+        }
+        match key_event.code {
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.highlighted = (self.highlighted + CHOICES.len() - 1) % CHOICES.len();
+                self.request_frame.schedule_frame();
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.highlighted = (self.highlighted + 1) % CHOICES.len();
+                self.request_frame.schedule_frame();
+            }
+            KeyCode::Char('1') => self.choose(LockedSessionChoice::Fork),
+            KeyCode::Char('2') => self.choose(LockedSessionChoice::ReadOnly),
+            KeyCode::Char('3') | KeyCode::Esc => self.choose(LockedSessionChoice::Exit),
+            KeyCode::Enter => self.choose(CHOICES[self.highlighted]),
+            _ => {}
+        }
+    }
+
+    fn choose(&mut self, choice: LockedSessionChoice) {
+        self.choice = Some(choice);
+        self.request_frame.schedule_frame();
+    }
+}
+
+impl WidgetRef for &LockedSessionScreen {
+    fn render_ref(&self, area: Rect, buf: &mut Buffer) {
+        Clear.render(area, buf);
+        let mut column = ColumnRenderable::new();
+
+        column.push("");
+        let holder = match self.holder_pid {
+            Some(pid) => format!("another Codex process (pid {pid})"),
+            None => "another Codex process".to_string(),
+        };
+        column.push(Line::from(vec![
+            "This session is already open in ".into(),
+            holder.bold(),
+            ".".into(),
+        ]));
+        column.push("");
+        column.push(
+            Line::from("Only one process can add to a session at a time.".dim())
+                .inset(Insets::tlbr(0, 2, 0, 0)),
+        );
+        column.push("");
+        for (index, choice) in CHOICES.into_iter().enumerate() {
+            column.push(selection_option_row(
+                index,
+                choice.label().to_string(),
+                index == self.highlighted,
+            ));
+        }
+        column.push("");
+        column.push(
+            Line::from(vec![
+                "Press ".dim(),
+                key_hint::plain(KeyCode::Enter).into(),
+                " to continue".dim(),
+            ])
+            .inset(Insets::tlbr(0, 2, 0, 0)),
+        );
+        column.render(area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn prompt_forks_by_default_and_moves_between_choices() {
+        let mut screen = LockedSessionScreen::new(FrameRequester::test_dummy(), Some(42));
+        screen.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert_eq!(screen.choice, Some(LockedSessionChoice::Fork));
+
+        let mut screen = LockedSessionScreen::new(FrameRequester::test_dummy(), None);
+        screen.handle_key(KeyEvent::new(KeyCode::Down, KeyModifiers::NONE));
+        screen.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert_eq!(screen.choice, Some(LockedSessionChoice::ReadOnly));
+
+        let mut screen = LockedSessionScreen::new(FrameRequester::test_dummy(), None);
+        screen.handle_key(KeyEvent::new(KeyCode::Up, KeyModifiers::NONE));
+        screen.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert_eq!(screen.choice, Some(LockedSessionChoice::Exit));
+    }
+}
@@ -0,0 +1,151 @@
+//! Statistics overlay for `/stats`.
+//!
+//! The numbers come from `codex_core::load_session_stats`, which replays the rollout file; this
+//! module only lays them out as a label/value table followed by per-tool call counts.
+
+use codex_core::SessionStats;
+use codex_core::describe_estimated_cost;
+use codex_protocol::num_format::format_with_separators;
+use codex_utils_elapsed::format_duration;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::widgets::Paragraph;
+use ratatui::widgets::Widget;
+use ratatui::widgets::Wrap;
+
+use crate::render::renderable::Renderable;
+
+const MIN_LABEL_WIDTH: usize = 13;
+
+pub(crate) struct SessionStatsView {
+    stats: SessionStats,
+}
+
+impl SessionStatsView {
+    pub(crate) fn new(stats: SessionStats) -> Self {
+        Self { stats }
+    }
+
+    fn lines(&self) -> Vec<Line<'static>> {
+        let stats = &self.stats;
+        let usage = &stats.token_usage;
+        let label_width = stats
+            .tool_calls
+            .keys()
+            .map(|name| name.len() + 2)
+            .fold(MIN_LABEL_WIDTH, usize::max);
+        let row = |label: &str, value: String| -> Line<'static> {
+            vec![format!("{label:<label_width$} ").dim(), value.into()].into()
+        };
+        let mut lines = vec![
+            row("Session", stats.path.display().to_string()),
+            row(
+                "Model",
+                stats.model.as_deref().unwrap_or("unknown").to_string(),
+            ),
+            row("Turns", stats.turns.to_string()),
+            row(
+                "Wall time",
+                stats
+                    .wall_time
+                    .map(format_duration)
+                    .unwrap_or_else(|| "unknown".to_string()),
+            ),
+            "".into(),
+            row("Tokens", format_with_separators(usage.total_tokens)),
+            row("  input", format_with_separators(usage.non_cached_input())),
+            row("  cached", format_with_separators(usage.cached_input())),
+            row("  output", format_with_separators(usage.output_tokens)),
+            row(
+                "  reasoning",
+                format_with_separators(usage.reasoning_output_tokens),
+            ),
+            "".into(),
+            row(
+                "Exec",
+                format!(
+                    "{} succeeded, {} failed",
+                    stats.exec_succeeded, stats.exec_failed
+                ),
+            ),
+            row(
+                "Est. cost",
+                describe_estimated_cost(stats.estimated_cost_usd),
+            ),
+            "".into(),
+        ];
+        let total_calls: usize = stats.tool_calls.values().sum();
+        lines.push(row("Tool calls", total_calls.to_string()));
+        for (name, count) in &stats.tool_calls {
+            lines.push(row(&format!("  {name}"), count.to_string()));
+        }
+        lines
+    }
+}
+
+impl Renderable for SessionStatsView {
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        Paragraph::new(self.lines())
+            .wrap(Wrap { trim: false })
+            .render(area, buf);
+    }
+
+    fn desired_height(&self, width: u16) -> u16 {
+        Paragraph::new(self.lines())
+            .wrap(Wrap { trim: false })
+            .line_count(width)
+            .try_into()
+            .unwrap_or(u16::MAX)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::protocol::TokenUsage;
+    use insta::assert_snapshot;
+    use std::collections::BTreeMap;
+    use std::path::PathBuf;
+    use std::time::Duration;
+
+    #[test]
+    fn renders_session_stats() {
+        let view = SessionStatsView::new(SessionStats {
+            path: PathBuf::from("/sessions/rollout-a.jsonl"),
+            model: Some("gpt-5".to_string()),
+            turns: 3,
+            token_usage: TokenUsage {
+                input_tokens: 12_000,
+                cached_input_tokens: 2_000,
+                output_tokens: 1_500,
+                reasoning_output_tokens: 500,
+                total_tokens: 13_500,
+            },
+            tool_calls: BTreeMap::from([
+                ("apply_patch".to_string(), 1),
+                ("shell_command".to_string(), 4),
+            ]),
+            exec_succeeded: 3,
+            exec_failed: 1,
+            wall_time: Some(Duration::from_secs(95)),
+            estimated_cost_usd: Some(0.0278),
+        });
+
+        let rendered = view
+            .lines()
+            .iter()
+            .map(|line| {
+                line.spans
+                    .iter()
+                    .map(|span| span.content.as_ref())
+                    .collect::<String>()
+                    .trim_end()
+                    .to_string()
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        assert_snapshot!(rendered);
+    }
+}
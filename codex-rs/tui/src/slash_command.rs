@@ -14,6 +14,7 @@ pub enum SlashCommand {
     // more frequently used commands should be listed first.
     Model,
     Fast,
+    Params,
     Approvals,
     Permissions,
     #[strum(serialize = "setup-default-sandbox")]
@@ -23,25 +24,49 @@ pub enum SlashCommand {
     Experimental,
     Skills,
     Review,
+    ExportReview,
+    PostReview,
     Rename,
     New,
     Resume,
     Fork,
     Init,
     Compact,
+    Trim,
+    Retry,
     Plan,
     Collab,
     Agent,
+    Tasks,
     // Undo,
     Diff,
+    Conflicts,
+    CommitMsg,
+    Pr,
+    Summarize,
+    Compare,
+    CompareModels,
+    Stats,
     Copy,
     Mention,
+    Pin,
+    Unpin,
+    Scrollback,
+    Ci,
+    Cd,
+    Tree,
+    Split,
     Status,
+    Config,
     DebugConfig,
+    DebugEvents,
+    DebugEnv,
+    DebugContext,
     Statusline,
     Theme,
     Mcp,
     Apps,
+    Login,
     Logout,
     Quit,
     Exit,
@@ -70,7 +95,15 @@ impl SlashCommand {
             SlashCommand::New => "start a new chat during a conversation",
             SlashCommand::Init => "create an AGENTS.md file with instructions for Codex",
             SlashCommand::Compact => "summarize conversation to prevent hitting the context limit",
+            SlashCommand::Trim => "remove a large tool output from the context",
             SlashCommand::Review => "review my current changes and find issues",
+            SlashCommand::ExportReview => {
+                "save the last review's comments as JSON or markdown: /export-review <path>"
+            }
+            SlashCommand::PostReview => {
+                "post the last review's comments on a pull request: /post-review <number>"
+            }
+            SlashCommand::Retry => "ask for the last response again, optionally with another model",
             SlashCommand::Rename => "rename the current thread",
             SlashCommand::Resume => "resume a saved chat",
             SlashCommand::Clear => "clear the terminal and start a new chat",
@@ -78,11 +111,43 @@ impl SlashCommand {
             // SlashCommand::Undo => "ask Codex to undo a turn",
             SlashCommand::Quit | SlashCommand::Exit => "exit Codex",
             SlashCommand::Diff => "show git diff (including untracked files)",
+            SlashCommand::Conflicts => {
+                "walk through merge conflicts hunk by hunk and resolve each one"
+            }
+            SlashCommand::CommitMsg => {
+                "draft a commit message for the staged changes, then edit it and commit"
+            }
+            SlashCommand::Pr => "push this branch and open a pull request for it: /pr [guidance]",
+            SlashCommand::Summarize => "summarize what this session did, decided, and left to do",
+            SlashCommand::Compare => "compare two saved sessions side by side",
+            SlashCommand::CompareModels => {
+                "send a prompt to the models in `compare_models` and compare the answers"
+            }
+            SlashCommand::Stats => {
+                "show turns, tokens, tool calls, and estimated cost for a session"
+            }
             SlashCommand::Copy => "copy the latest Codex output to your clipboard",
             SlashCommand::Mention => "mention a file",
+            SlashCommand::Pin => "keep a file in the model context every turn: /pin <path>",
+            SlashCommand::Unpin => "stop sending a pinned file, or all of them: /unpin [path]",
+            SlashCommand::Scrollback => {
+                "attach the terminal output from before Codex started: /scrollback [lines]"
+            }
+            SlashCommand::Ci => {
+                "attach the failing steps and errors from a CI log: /ci <file-or-url>"
+            }
+            SlashCommand::Cd => "change the working directory for this session: /cd <path>",
+            SlashCommand::Tree => "show or hide the workspace file tree",
+            SlashCommand::Split => "show or hide a live workspace diff beside the conversation",
             SlashCommand::Skills => "use skills to improve how Codex performs specific tasks",
             SlashCommand::Status => "show current session configuration and token usage",
+            SlashCommand::Config => "view the effective config and change common settings",
             SlashCommand::DebugConfig => "show config layers and requirement sources for debugging",
+            SlashCommand::DebugEvents => "inspect recently received protocol events",
+            SlashCommand::DebugEnv => "show the environment passed to agent commands",
+            SlashCommand::DebugContext => {
+                "show what the next request will send to the model, with token counts"
+            }
             SlashCommand::Statusline => "configure which items appear in the status line",
             SlashCommand::Theme => "choose a syntax highlighting theme",
             SlashCommand::Ps => "list background terminals",
@@ -91,12 +156,14 @@ impl SlashCommand {
             SlashCommand::MemoryUpdate => "DO NOT USE",
             SlashCommand::Model => "choose what model and reasoning effort to use",
             SlashCommand::Fast => "toggle Fast mode to enable fastest inference at 2X plan usage",
+            SlashCommand::Params => "set temperature and top_p for this session",
             SlashCommand::Personality => "choose a communication style for Codex",
             SlashCommand::Realtime => "toggle realtime voice mode (experimental)",
             SlashCommand::Settings => "configure realtime microphone/speaker",
             SlashCommand::Plan => "switch to Plan mode",
             SlashCommand::Collab => "change collaboration mode (experimental)",
             SlashCommand::Agent | SlashCommand::MultiAgents => "switch the active agent thread",
+            SlashCommand::Tasks => "show sub-agent tasks and inspect or cancel them",
             SlashCommand::Approvals => "choose what Codex is allowed to do",
             SlashCommand::Permissions => "choose what Codex is allowed to do",
             SlashCommand::ElevateSandbox => "set up elevated agent sandbox",
@@ -106,6 +173,7 @@ impl SlashCommand {
             SlashCommand::Experimental => "toggle experimental features",
             SlashCommand::Mcp => "list configured MCP tools",
             SlashCommand::Apps => "manage apps",
+            SlashCommand::Login => "sign in, check token expiry, or switch between saved accounts",
             SlashCommand::Logout => "log out of Codex",
             SlashCommand::Rollout => "print the rollout file path",
             SlashCommand::TestApproval => "test approval request",
@@ -126,7 +194,20 @@ impl SlashCommand {
                 | SlashCommand::Rename
                 | SlashCommand::Plan
                 | SlashCommand::Fast
+                | SlashCommand::Params
                 | SlashCommand::SandboxReadRoot
+                | SlashCommand::Pr
+                | SlashCommand::ExportReview
+                | SlashCommand::PostReview
+                | SlashCommand::Compare
+                | SlashCommand::CompareModels
+                | SlashCommand::Stats
+                | SlashCommand::Pin
+                | SlashCommand::Unpin
+                | SlashCommand::Scrollback
+                | SlashCommand::Ci
+                | SlashCommand::Cd
+                | SlashCommand::Login
         )
     }
 
@@ -138,9 +219,13 @@ impl SlashCommand {
             | SlashCommand::Fork
             | SlashCommand::Init
             | SlashCommand::Compact
+            | SlashCommand::Trim
+            | SlashCommand::Retry
+            | SlashCommand::Cd
             // | SlashCommand::Undo
             | SlashCommand::Model
             | SlashCommand::Fast
+            | SlashCommand::Params
             | SlashCommand::Personality
             | SlashCommand::Approvals
             | SlashCommand::Permissions
@@ -148,18 +233,38 @@ impl SlashCommand {
             | SlashCommand::SandboxReadRoot
             | SlashCommand::Experimental
             | SlashCommand::Review
+            | SlashCommand::Conflicts
+            | SlashCommand::CommitMsg
+            | SlashCommand::Pr
+            | SlashCommand::Summarize
             | SlashCommand::Plan
             | SlashCommand::Clear
+            | SlashCommand::Login
             | SlashCommand::Logout
             | SlashCommand::MemoryDrop
             | SlashCommand::MemoryUpdate => false,
             SlashCommand::Diff
+            | SlashCommand::ExportReview
+            | SlashCommand::PostReview
+            | SlashCommand::Compare
+            | SlashCommand::CompareModels
+            | SlashCommand::Stats
             | SlashCommand::Copy
             | SlashCommand::Rename
             | SlashCommand::Mention
+            | SlashCommand::Pin
+            | SlashCommand::Unpin
+            | SlashCommand::Scrollback
+            | SlashCommand::Ci
+            | SlashCommand::Tree
+            | SlashCommand::Split
             | SlashCommand::Skills
             | SlashCommand::Status
+            | SlashCommand::Config
             | SlashCommand::DebugConfig
+            | SlashCommand::DebugEvents
+            | SlashCommand::DebugEnv
+            | SlashCommand::DebugContext
             | SlashCommand::Ps
             | SlashCommand::Clean
             | SlashCommand::Mcp
@@ -172,7 +277,7 @@ impl SlashCommand {
             SlashCommand::Realtime => true,
             SlashCommand::Settings => true,
             SlashCommand::Collab => true,
-            SlashCommand::Agent | SlashCommand::MultiAgents => true,
+            SlashCommand::Agent | SlashCommand::MultiAgents | SlashCommand::Tasks => true,
             SlashCommand::Statusline => false,
             SlashCommand::Theme => false,
         }
@@ -0,0 +1,880 @@
+//! Optional spellcheck for the composer draft (`[tui.spellcheck]`).
+//!
+//! Dictionaries are Hunspell `.dic`/`.aff` pairs, found by language under
+//! `$CODEX_HOME/dictionaries` or the system Hunspell directories, or plain word lists with one
+//! word per line. Only the simple part of Hunspell's affix format is understood: single prefix
+//! and suffix rules with their conditions, combined at most once each. That covers the regular
+//! inflections in the common dictionaries; forms that need compounding or chained affixes are
+//! reported as misspelled.
+//!
+//! The checker is loaded once at startup and kept in a process-wide cell. The composer's
+//! textarea asks it for misspelled ranges when it renders, and `Alt+S` turns the misspelling at
+//! the cursor into a list of suggestions. Words added from that list go to
+//! `$CODEX_HOME/dictionary.txt`.
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::ops::Range;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use std::sync::RwLock;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+
+use codex_core::config::types::SpellcheckConfig;
+use unicode_segmentation::UnicodeSegmentation;
+
+static SPELLCHECKER: OnceLock<Spellchecker> = OnceLock::new();
+
+const DICTIONARY_DIR: &str = "dictionaries";
+const PERSONAL_DICTIONARY_FILE: &str = "dictionary.txt";
+const SYSTEM_DICTIONARY_DIRS: &[&str] = &[
+    "/usr/share/hunspell",
+    "/usr/share/myspell",
+    "/usr/share/myspell/dicts",
+    "/Library/Spelling",
+];
+/// Fallback for English when no Hunspell dictionary is installed.
+const SYSTEM_WORD_LIST: &str = "/usr/share/dict/words";
+
+/// Words shorter than this many characters are not checked.
+const MIN_WORD_CHARS: usize = 3;
+pub(crate) const MAX_SUGGESTIONS: usize = 8;
+/// Two-edit suggestions are only searched for words up to this length.
+const MAX_DOUBLE_EDIT_CHARS: usize = 12;
+
+/// Load the dictionaries named by `config` and make the checker available to the composer.
+///
+/// Does nothing when spellcheck is disabled. Returns warnings for languages without a
+/// dictionary and for files that could not be read; if no dictionary loads at all, spellcheck
+/// stays off rather than underlining every word.
+pub(crate) fn init(config: &SpellcheckConfig, codex_home: &Path) -> Vec<String> {
+    if !config.enabled {
+        return Vec::new();
+    }
+    let mut warnings = Vec::new();
+    let mut dictionaries = Vec::new();
+    let mut load = |path: &Path, warnings: &mut Vec<String>| match Dictionary::load(path) {
+        Ok(dictionary) => dictionaries.push(dictionary),
+        Err(err) => warnings.push(format!(
+            "Spellcheck: could not read {}: {err}",
+            path.display()
+        )),
+    };
+    for language in &config.languages {
+        match find_dictionary(language, codex_home) {
+            Some(path) => load(&path, &mut warnings),
+            None => warnings.push(format!(
+                "Spellcheck: no dictionary found for `{language}`. Add {language}.dic and \
+                 {language}.aff to {}.",
+                codex_home.join(DICTIONARY_DIR).display()
+            )),
+        }
+    }
+    for path in &config.dictionaries {
+        load(path.as_path(), &mut warnings);
+    }
+    if dictionaries.is_empty() {
+        return warnings;
+    }
+
+    let personal_path = codex_home.join(PERSONAL_DICTIONARY_FILE);
+    let personal = std::fs::read_to_string(&personal_path)
+        .map(|contents| word_list(&contents).collect())
+        .unwrap_or_default();
+    let _ = SPELLCHECKER.set(Spellchecker::new(dictionaries, personal, personal_path));
+    warnings
+}
+
+/// The loaded checker, or `None` when spellcheck is off.
+pub(crate) fn get() -> Option<&'static Spellchecker> {
+    SPELLCHECKER.get()
+}
+
+fn find_dictionary(language: &str, codex_home: &Path) -> Option<PathBuf> {
+    let language = language.replace('-', "_");
+    let user_dir = codex_home.join(DICTIONARY_DIR);
+    let mut candidates = vec![
+        user_dir.join(format!("{language}.dic")),
+        user_dir.join(format!("{language}.txt")),
+    ];
+    candidates.extend(
+        SYSTEM_DICTIONARY_DIRS
+            .iter()
+            .map(|dir| Path::new(dir).join(format!("{language}.dic"))),
+    );
+    if let Some(home) = dirs::home_dir() {
+        candidates.push(
+            home.join("Library/Spelling")
+                .join(format!("{language}.dic")),
+        );
+    }
+    if language.starts_with("en") {
+        candidates.push(PathBuf::from(SYSTEM_WORD_LIST));
+    }
+    candidates.into_iter().find(|path| path.is_file())
+}
+
+pub(crate) struct Spellchecker {
+    dictionaries: Vec<Dictionary>,
+    personal: RwLock<HashSet<String>>,
+    personal_path: PathBuf,
+    /// Letters tried when generating suggestions.
+    alphabet: Vec<char>,
+    /// Bumped whenever a word is added, so cached results can be recomputed.
+    generation: AtomicU64,
+}
+
+impl Spellchecker {
+    fn new(
+        dictionaries: Vec<Dictionary>,
+        personal: HashSet<String>,
+        personal_path: PathBuf,
+    ) -> Self {
+        let mut alphabet: Vec<char> = dictionaries
+            .iter()
+            .flat_map(|dictionary| dictionary.affixes.try_chars.iter().copied())
+            .filter(|c| !c.is_uppercase())
+            .collect();
+        if alphabet.is_empty() {
+            alphabet = ('a'..='z').collect();
+        }
+        let mut seen = HashSet::new();
+        alphabet.retain(|c| seen.insert(*c));
+        Self {
+            dictionaries,
+            personal: RwLock::new(personal),
+            personal_path,
+            alphabet,
+            generation: AtomicU64::new(0),
+        }
+    }
+
+    pub(crate) fn generation(&self) -> u64 {
+        self.generation.load(Ordering::Relaxed)
+    }
+
+    /// Byte ranges of the misspelled words in `text`.
+    pub(crate) fn misspelled_ranges(&self, text: &str) -> Vec<Range<usize>> {
+        checkable_words(text)
+            .into_iter()
+            .filter(|range| !self.is_correct(&text[range.clone()]))
+            .collect()
+    }
+
+    pub(crate) fn is_correct(&self, word: &str) -> bool {
+        let word = word.replace('’', "'");
+        if self.knows(&word) {
+            return true;
+        }
+        // A capital at the start of a sentence does not change the spelling.
+        let lowered = lowercase_first(&word);
+        if lowered != word && self.knows(&lowered) {
+            return true;
+        }
+        // Possessives of names the dictionaries do not inflect, e.g. words added by the user.
+        word.strip_suffix("'s")
+            .is_some_and(|base| self.knows(base) || self.knows(&lowercase_first(base)))
+    }
+
+    fn knows(&self, word: &str) -> bool {
+        self.dictionaries
+            .iter()
+            .any(|dictionary| dictionary.contains(word))
+            || self.knows_personal(word)
+    }
+
+    fn knows_personal(&self, word: &str) -> bool {
+        self.personal
+            .read()
+            .is_ok_and(|personal| personal.contains(word))
+    }
+
+    /// Words within one edit of `word`, followed by dictionary stems within two edits, best
+    /// first. The first letter's case follows `word`.
+    pub(crate) fn suggest(&self, word: &str) -> Vec<String> {
+        let word = word.replace('’', "'");
+        let capitalized = word.chars().next().is_some_and(char::is_uppercase);
+        let lowered = lowercase_first(&word);
+        let mut suggestions: Vec<String> = Vec::new();
+        let push = |candidate: &str, suggestions: &mut Vec<String>| {
+            let candidate = if capitalized {
+                uppercase_first(candidate)
+            } else {
+                candidate.to_string()
+            };
+            if candidate != word && !suggestions.contains(&candidate) {
+                suggestions.push(candidate);
+            }
+        };
+
+        let first = edits(&lowered, &self.alphabet);
+        for candidate in &first {
+            if self.knows(candidate) {
+                push(candidate, &mut suggestions);
+            }
+        }
+        if suggestions.len() < MAX_SUGGESTIONS && lowered.chars().count() <= MAX_DOUBLE_EDIT_CHARS {
+            // Affix expansion is too slow for the second round; look up stems directly.
+            'outer: for candidate in &first {
+                for second in edits(candidate, &self.alphabet) {
+                    if suggestions.len() >= MAX_SUGGESTIONS {
+                        break 'outer;
+                    }
+                    if self
+                        .dictionaries
+                        .iter()
+                        .any(|dictionary| dictionary.words.contains_key(&second))
+                        || self.knows_personal(&second)
+                    {
+                        push(&second, &mut suggestions);
+                    }
+                }
+            }
+        }
+        suggestions.truncate(MAX_SUGGESTIONS);
+        suggestions
+    }
+
+    /// Accept `word` from now on and append it to the personal dictionary.
+    pub(crate) fn add_word(&self, word: &str) -> std::io::Result<()> {
+        if let Some(parent) = self.personal_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.personal_path)?;
+        writeln!(file, "{word}")?;
+        if let Ok(mut personal) = self.personal.write() {
+            personal.insert(word.to_string());
+        }
+        self.generation.fetch_add(1, Ordering::Relaxed);
+        Ok(())
+    }
+}
+
+/// Byte ranges of the words in `text` worth checking.
+///
+/// Words are runs of letters, with apostrophes allowed between letters. Backtick code spans,
+/// tokens that look like paths, URLs, or identifiers, words next to digits, acronyms, and
+/// mixed-case names are skipped.
+pub(crate) fn checkable_words(text: &str) -> Vec<Range<usize>> {
+    let code = code_spans(text);
+    let mut words = Vec::new();
+    let mut token_start = None;
+    for (i, c) in text
+        .char_indices()
+        .chain(std::iter::once((text.len(), ' ')))
+    {
+        if c.is_whitespace() {
+            if let Some(start) = token_start.take() {
+                push_token_words(text, start..i, &mut words);
+            }
+        } else if token_start.is_none() {
+            token_start = Some(i);
+        }
+    }
+    words.retain(|word: &Range<usize>| {
+        !code
+            .iter()
+            .any(|span| span.start < word.end && word.start < span.end)
+    });
+    words
+}
+
+fn push_token_words(text: &str, token: Range<usize>, words: &mut Vec<Range<usize>>) {
+    let token_text = &text[token.clone()];
+    if looks_like_code(token_text) {
+        return;
+    }
+    let graphemes: Vec<(usize, &str)> = token_text.grapheme_indices(true).collect();
+    let is_letter = |g: &str| g.chars().next().is_some_and(char::is_alphabetic);
+    let is_digit = |g: &str| g.chars().next().is_some_and(char::is_numeric);
+    let mut i = 0;
+    while i < graphemes.len() {
+        if !is_letter(graphemes[i].1) {
+            i += 1;
+            continue;
+        }
+        let start = i;
+        while i < graphemes.len() {
+            let g = graphemes[i].1;
+            let inner_apostrophe = is_apostrophe(g)
+                && i > start
+                && graphemes
+                    .get(i + 1)
+                    .is_some_and(|(_, next)| is_letter(next));
+            if is_letter(g) || inner_apostrophe {
+                i += 1;
+            } else {
+                break;
+            }
+        }
+        let touches_digit = (start > 0 && is_digit(graphemes[start - 1].1))
+            || graphemes.get(i).is_some_and(|(_, next)| is_digit(next));
+        let word_start = token.start + graphemes[start].0;
+        let word_end = token.start + graphemes.get(i).map_or(token_text.len(), |(at, _)| *at);
+        let word = &text[word_start..word_end];
+        if !touches_digit && i - start >= MIN_WORD_CHARS && !is_name_or_acronym(word) {
+            words.push(word_start..word_end);
+        }
+    }
+}
+
+fn is_apostrophe(grapheme: &str) -> bool {
+    matches!(grapheme, "'" | "’")
+}
+
+/// Acronyms (`HTTP`) and mixed-case names (`iPhone`, `GitHub`) are usually not dictionary
+/// words. A single leading capital is fine.
+fn is_name_or_acronym(word: &str) -> bool {
+    word.chars().skip(1).any(char::is_uppercase)
+}
+
+fn looks_like_code(token: &str) -> bool {
+    let core = token.trim_matches(|c: char| {
+        matches!(
+            c,
+            '(' | ')'
+                | '['
+                | ']'
+                | '"'
+                | '\''
+                | '“'
+                | '”'
+                | '‘'
+                | '’'
+                | '*'
+                | '_'
+                | ','
+                | '.'
+                | ';'
+                | ':'
+                | '!'
+                | '?'
+        )
+    });
+    core.contains(|c: char| {
+        matches!(
+            c,
+            '/' | '\\'
+                | '_'
+                | '.'
+                | ':'
+                | '='
+                | '<'
+                | '>'
+                | '{'
+                | '}'
+                | '['
+                | ']'
+                | '('
+                | ')'
+                | '|'
+                | '~'
+                | '`'
+                | '@'
+                | '#'
+                | '$'
+                | '%'
+                | '^'
+                | '*'
+                | '+'
+                | '&'
+        )
+    })
+}
+
+/// Byte ranges of backtick code spans and fenced blocks. A run of backticks without a matching
+/// closing run opens nothing, so a half-typed span does not hide the rest of the draft.
+fn code_spans(text: &str) -> Vec<Range<usize>> {
+    let bytes = text.as_bytes();
+    let run_end = |mut at: usize| {
+        while bytes.get(at) == Some(&b'`') {
+            at += 1;
+        }
+        at
+    };
+    let mut spans = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] != b'`' {
+            i += 1;
+            continue;
+        }
+        let open = i;
+        i = run_end(i);
+        let fence = &text[open..i];
+        let mut search = i;
+        while let Some(found) = text[search..].find(fence) {
+            let close_start = search + found;
+            let close_end = run_end(close_start);
+            if close_end - close_start == fence.len() {
+                spans.push(open..close_end);
+                i = close_end;
+                break;
+            }
+            search = close_end;
+        }
+    }
+    spans
+}
+
+/// Strings one deletion, transposition, substitution, or insertion away from `word`.
+fn edits(word: &str, alphabet: &[char]) -> Vec<String> {
+    let chars: Vec<char> = word.chars().collect();
+    let join = |parts: &[&[char]]| {
+        parts
+            .iter()
+            .flat_map(|part| part.iter())
+            .collect::<String>()
+    };
+    let mut out = Vec::new();
+    for i in 0..chars.len().saturating_sub(1) {
+        let mut swapped = chars.clone();
+        swapped.swap(i, i + 1);
+        out.push(swapped.into_iter().collect());
+    }
+    for i in 0..chars.len() {
+        for &c in alphabet {
+            if c != chars[i] {
+                out.push(join(&[&chars[..i], &[c], &chars[i + 1..]]));
+            }
+        }
+    }
+    for i in 0..chars.len() {
+        out.push(join(&[&chars[..i], &chars[i + 1..]]));
+    }
+    for i in 0..=chars.len() {
+        for &c in alphabet {
+            out.push(join(&[&chars[..i], &[c], &chars[i..]]));
+        }
+    }
+    out
+}
+
+fn lowercase_first(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_lowercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+fn uppercase_first(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+fn word_list(contents: &str) -> impl Iterator<Item = String> + '_ {
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(str::to_string)
+}
+
+/// Affix flags are single characters, character pairs (`FLAG long`), or numbers (`FLAG num`);
+/// all are folded into a `u32`.
+type Flag = u32;
+
+#[derive(Debug, Default, Clone, Copy)]
+enum FlagFormat {
+    #[default]
+    Char,
+    Long,
+    Num,
+}
+
+impl FlagFormat {
+    fn parse(self, flags: &str) -> Vec<Flag> {
+        match self {
+            FlagFormat::Char => flags.chars().map(u32::from).collect(),
+            FlagFormat::Long => {
+                let chars: Vec<char> = flags.chars().collect();
+                chars
+                    .chunks(2)
+                    .map(|pair| {
+                        pair.iter()
+                            .fold(0u32, |flag, c| (flag << 16) | (u32::from(*c) & 0xffff))
+                    })
+                    .collect()
+            }
+            FlagFormat::Num => flags
+                .split(',')
+                .filter_map(|flag| flag.trim().parse().ok())
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug)]
+enum CharClass {
+    Any,
+    Set { chars: Vec<char>, negated: bool },
+}
+
+impl CharClass {
+    fn matches(&self, c: char) -> bool {
+        match self {
+            CharClass::Any => true,
+            CharClass::Set { chars, negated } => chars.contains(&c) != *negated,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct AffixRule {
+    flag: Flag,
+    strip: String,
+    add: String,
+    /// Characters the stem must start (prefix) or end (suffix) with.
+    condition: Vec<CharClass>,
+    cross_product: bool,
+}
+
+impl AffixRule {
+    fn condition_holds(&self, stem: &str, suffix: bool) -> bool {
+        if suffix {
+            let mut chars = stem.chars().rev();
+            self.condition
+                .iter()
+                .rev()
+                .all(|class| chars.next().is_some_and(|c| class.matches(c)))
+        } else {
+            let mut chars = stem.chars();
+            self.condition
+                .iter()
+                .all(|class| chars.next().is_some_and(|c| class.matches(c)))
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct Affixes {
+    flag_format: FlagFormat,
+    prefixes: Vec<AffixRule>,
+    suffixes: Vec<AffixRule>,
+    try_chars: Vec<char>,
+}
+
+impl Affixes {
+    fn parse(aff: &str) -> Self {
+        let mut affixes = Self::default();
+        let mut cross_products: HashMap<(bool, Flag), bool> = HashMap::new();
+        for line in aff.lines() {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            match fields.as_slice() {
+                ["FLAG", format, ..] => {
+                    affixes.flag_format = match *format {
+                        "long" => FlagFormat::Long,
+                        "num" => FlagFormat::Num,
+                        _ => FlagFormat::Char,
+                    };
+                }
+                ["TRY", chars, ..] => affixes.try_chars = chars.chars().collect(),
+                [kind @ ("PFX" | "SFX"), flag, cross @ ("Y" | "N"), count]
+                    if count.parse::<usize>().is_ok() =>
+                {
+                    let Some(flag) = affixes.flag_format.parse(flag).first().copied() else {
+                        continue;
+                    };
+                    cross_products.insert((*kind == "SFX", flag), *cross == "Y");
+                }
+                [kind @ ("PFX" | "SFX"), flag, strip, add, rest @ ..] => {
+                    let Some(flag) = affixes.flag_format.parse(flag).first().copied() else {
+                        continue;
+                    };
+                    let suffix = *kind == "SFX";
+                    let empty_if_zero = |field: &str| {
+                        if field == "0" {
+                            String::new()
+                        } else {
+                            field.to_string()
+                        }
+                    };
+                    // Continuation classes after `/` would chain further affixes; they are not
+                    // supported.
+                    let add = add.split('/').next().unwrap_or_default();
+                    let rule = AffixRule {
+                        flag,
+                        strip: empty_if_zero(strip),
+                        add: empty_if_zero(add),
+                        condition: parse_condition(rest.first().copied().unwrap_or(".")),
+                        cross_product: cross_products
+                            .get(&(suffix, flag))
+                            .copied()
+                            .unwrap_or(false),
+                    };
+                    if suffix {
+                        affixes.suffixes.push(rule);
+                    } else {
+                        affixes.prefixes.push(rule);
+                    }
+                }
+                _ => {}
+            }
+        }
+        affixes
+    }
+}
+
+fn parse_condition(condition: &str) -> Vec<CharClass> {
+    if condition == "." {
+        return Vec::new();
+    }
+    let mut classes = Vec::new();
+    let mut chars = condition.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '.' => classes.push(CharClass::Any),
+            '[' => {
+                let mut set = Vec::new();
+                let mut negated = false;
+                for (i, c) in chars.by_ref().enumerate() {
+                    match c {
+                        ']' => break,
+                        '^' if i == 0 => negated = true,
+                        c => set.push(c),
+                    }
+                }
+                classes.push(CharClass::Set {
+                    chars: set,
+                    negated,
+                });
+            }
+            c => classes.push(CharClass::Set {
+                chars: vec![c],
+                negated: false,
+            }),
+        }
+    }
+    classes
+}
+
+#[derive(Debug, Default)]
+struct Dictionary {
+    /// Stems and the affix flags that apply to them.
+    words: HashMap<String, Vec<Flag>>,
+    affixes: Affixes,
+}
+
+impl Dictionary {
+    /// Load a Hunspell `.dic` (with the `.aff` next to it, when present) or a plain word list.
+    fn load(path: &Path) -> std::io::Result<Self> {
+        if path.extension().is_none_or(|ext| ext != "dic") {
+            let contents = std::fs::read(path)?;
+            return Ok(Self::from_word_list(&String::from_utf8_lossy(&contents)));
+        }
+        let (affixes, latin1) = match std::fs::read(path.with_extension("aff")) {
+            Ok(bytes) => {
+                let latin1 = declares_latin1(&String::from_utf8_lossy(&bytes));
+                (Affixes::parse(&decode(&bytes, latin1)), latin1)
+            }
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => (Affixes::default(), false),
+            Err(err) => return Err(err),
+        };
+        let dic = std::fs::read(path)?;
+        Ok(Self::from_hunspell(&decode(&dic, latin1), affixes))
+    }
+
+    fn from_word_list(contents: &str) -> Self {
+        Self {
+            words: word_list(contents).map(|word| (word, Vec::new())).collect(),
+            affixes: Affixes::default(),
+        }
+    }
+
+    fn from_hunspell(dic: &str, affixes: Affixes) -> Self {
+        let mut words: HashMap<String, Vec<Flag>> = HashMap::new();
+        let mut lines = dic.lines().peekable();
+        // The first line is the approximate entry count.
+        if lines
+            .peek()
+            .is_some_and(|line| line.trim().parse::<usize>().is_ok())
+        {
+            lines.next();
+        }
+        for line in lines {
+            let Some(entry) = line.split_whitespace().next() else {
+                continue;
+            };
+            let (word, flags) = split_entry(entry);
+            if word.is_empty() {
+                continue;
+            }
+            words
+                .entry(word)
+                .or_default()
+                .extend(affixes.flag_format.parse(flags));
+        }
+        Self { words, affixes }
+    }
+
+    fn has_flags(&self, stem: &str, flags: &[Flag]) -> bool {
+        self.words
+            .get(stem)
+            .is_some_and(|stem_flags| flags.iter().all(|flag| stem_flags.contains(flag)))
+    }
+
+    fn contains(&self, word: &str) -> bool {
+        if self.words.contains_key(word) {
+            return true;
+        }
+        let with_suffix = self.affixes.suffixes.iter().any(|suffix| {
+            let Some(base) = word.strip_suffix(suffix.add.as_str()) else {
+                return false;
+            };
+            let stem = format!("{base}{}", suffix.strip);
+            if !suffix.condition_holds(&stem, true) {
+                return false;
+            }
+            if self.has_flags(&stem, &[suffix.flag]) {
+                return true;
+            }
+            suffix.cross_product
+                && self.affixes.prefixes.iter().any(|prefix| {
+                    prefix.cross_product
+                        && stem.strip_prefix(prefix.add.as_str()).is_some_and(|rest| {
+                            let root = format!("{}{rest}", prefix.strip);
+                            prefix.condition_holds(&root, false)
+                                && self.has_flags(&root, &[suffix.flag, prefix.flag])
+                        })
+                })
+        });
+        with_suffix
+            || self.affixes.prefixes.iter().any(|prefix| {
+                word.strip_prefix(prefix.add.as_str()).is_some_and(|rest| {
+                    let stem = format!("{}{rest}", prefix.strip);
+                    prefix.condition_holds(&stem, false) && self.has_flags(&stem, &[prefix.flag])
+                })
+            })
+    }
+}
+
+/// Split a `.dic` entry into its word and flags. A `/` escaped as `\/` belongs to the word.
+fn split_entry(entry: &str) -> (String, &str) {
+    let bytes = entry.as_bytes();
+    let slash = (1..bytes.len()).find(|&i| bytes[i] == b'/' && bytes[i - 1] != b'\\');
+    match slash {
+        Some(i) => (entry[..i].replace("\\/", "/"), &entry[i + 1..]),
+        None => (entry.replace("\\/", "/"), ""),
+    }
+}
+
+fn declares_latin1(aff: &str) -> bool {
+    aff.lines().any(|line| {
+        let mut fields = line.split_whitespace();
+        fields.next() == Some("SET")
+            && fields.next().is_some_and(|encoding| {
+                matches!(
+                    encoding.to_ascii_uppercase().as_str(),
+                    "ISO8859-1" | "ISO-8859-1" | "ISO8859-15" | "ISO-8859-15"
+                )
+            })
+    })
+}
+
+fn decode(bytes: &[u8], latin1: bool) -> String {
+    if latin1 {
+        bytes.iter().copied().map(char::from).collect()
+    } else {
+        String::from_utf8_lossy(bytes).into_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    const AFF: &str = "\
+SET UTF-8
+TRY esianrtolcdugmphbyfvkwz'
+PFX U Y 1
+PFX U   0     un         .
+SFX D Y 3
+SFX D   0     d          e
+SFX D   y     ied        [^aeiou]y
+SFX D   0     ed         [^ey]
+SFX S Y 1
+SFX S   0     s          .
+";
+
+    const DIC: &str = "\
+6
+carry/DS
+check/DSU
+hello
+happy/U
+the
+word/S
+";
+
+    fn checker() -> Spellchecker {
+        let dictionary = Dictionary::from_hunspell(DIC, Affixes::parse(AFF));
+        Spellchecker::new(
+            vec![dictionary],
+            HashSet::from(["Codex".to_string()]),
+            PathBuf::from("/nonexistent/dictionary.txt"),
+        )
+    }
+
+    #[test]
+    fn affix_rules_derive_inflected_forms() {
+        let checker = checker();
+
+        for word in [
+            "carried",
+            "checks",
+            "checked",
+            "unchecked",
+            "unhappy",
+            "words",
+        ] {
+            assert!(checker.is_correct(word), "{word} should be accepted");
+        }
+        for word in ["carryed", "happyed", "unword", "chekced"] {
+            assert!(!checker.is_correct(word), "{word} should be rejected");
+        }
+    }
+
+    #[test]
+    fn case_and_possessives_follow_the_dictionary() {
+        let checker = checker();
+
+        assert!(checker.is_correct("Hello"));
+        assert!(checker.is_correct("Codex's"));
+        assert!(!checker.is_correct("codex"));
+    }
+
+    #[test]
+    fn code_paths_and_names_are_not_checked() {
+        let text = "teh `fooo barr` src/maen.rs HTTPZ iPhonez v2ab don't wrld, ok";
+        let words: Vec<&str> = checkable_words(text)
+            .into_iter()
+            .map(|range| &text[range])
+            .collect();
+
+        assert_eq!(words, vec!["teh", "don't", "wrld"]);
+    }
+
+    #[test]
+    fn misspelled_ranges_and_suggestions() {
+        let checker = checker();
+        let text = "Teh wrod checked";
+
+        let ranges = checker.misspelled_ranges(text);
+
+        assert_eq!(ranges, vec![0..3, 4..8]);
+        assert_eq!(checker.suggest("Teh"), vec!["The".to_string()]);
+        assert_eq!(checker.suggest("wrod")[0], "word");
+    }
+}
@@ -17,6 +17,7 @@ pub(crate) use card::new_status_output;
 pub(crate) use card::new_status_output_with_rate_limits;
 pub(crate) use helpers::format_directory_display;
 pub(crate) use helpers::format_tokens_compact;
+pub(crate) use rate_limits::PROVIDER_RATE_LIMIT_ID;
 pub(crate) use rate_limits::RateLimitSnapshotDisplay;
 pub(crate) use rate_limits::RateLimitWindowDisplay;
 #[cfg(test)]
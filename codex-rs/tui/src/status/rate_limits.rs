@@ -54,6 +54,10 @@ pub(crate) enum StatusRateLimitData {
     Missing,
 }
 
+/// Limit id of snapshots parsed from generic `x-ratelimit-*` provider headers. Their primary
+/// window tracks requests and the secondary window tracks tokens.
+pub(crate) const PROVIDER_RATE_LIMIT_ID: &str = "provider";
+
 /// Maximum age before a snapshot is considered stale in status output.
 pub(crate) const RATE_LIMIT_STALE_THRESHOLD_MINUTES: i64 = 15;
 
@@ -88,6 +92,8 @@ impl RateLimitWindowDisplay {
 pub(crate) struct RateLimitSnapshotDisplay {
     /// Canonical limit identifier (for example: `codex` or `codex_other`).
     pub limit_name: String,
+    /// Whether the windows are provider request/token quotas rather than time windows.
+    pub is_provider_quota: bool,
     /// Local timestamp representing when this display snapshot was captured.
     pub captured_at: DateTime<Local>,
     /// Primary usage window (typically short duration).
@@ -128,6 +134,7 @@ pub(crate) fn rate_limit_snapshot_display_for_limit(
 ) -> RateLimitSnapshotDisplay {
     RateLimitSnapshotDisplay {
         limit_name,
+        is_provider_quota: snapshot.limit_id.as_deref() == Some(PROVIDER_RATE_LIMIT_ID),
         captured_at,
         primary: snapshot
             .primary
@@ -182,6 +189,11 @@ pub(crate) fn compose_rate_limit_data_many(
 
         let limit_bucket_label = snapshot.limit_name.clone();
         let show_limit_prefix = !limit_bucket_label.eq_ignore_ascii_case("codex");
+        let (primary_default, secondary_default) = if snapshot.is_provider_quota {
+            ("requests", "tokens")
+        } else {
+            ("5h", "weekly")
+        };
         let primary_label = snapshot
             .primary
             .as_ref()
@@ -189,7 +201,7 @@ pub(crate) fn compose_rate_limit_data_many(
                 window
                     .window_minutes
                     .map(get_limits_duration)
-                    .unwrap_or_else(|| "5h".to_string())
+                    .unwrap_or_else(|| primary_default.to_string())
             })
             .map(|label| capitalize_first(&label));
         let secondary_label = snapshot
@@ -199,7 +211,7 @@ pub(crate) fn compose_rate_limit_data_many(
                 window
                     .window_minutes
                     .map(get_limits_duration)
-                    .unwrap_or_else(|| "weekly".to_string())
+                    .unwrap_or_else(|| secondary_default.to_string())
             })
             .map(|label| capitalize_first(&label));
         let window_count =
@@ -365,6 +377,7 @@ mod tests {
         let now = Local::now();
         let codex = RateLimitSnapshotDisplay {
             limit_name: "codex".to_string(),
+            is_provider_quota: false,
             captured_at: now,
             primary: Some(window(10.0)),
             secondary: None,
@@ -376,6 +389,7 @@ mod tests {
         };
         let other = RateLimitSnapshotDisplay {
             limit_name: "codex-other".to_string(),
+            is_provider_quota: false,
             captured_at: now,
             primary: Some(window(20.0)),
             secondary: None,
@@ -409,6 +423,7 @@ mod tests {
         let now = Local::now();
         let other = RateLimitSnapshotDisplay {
             limit_name: "codex-other".to_string(),
+            is_provider_quota: false,
             captured_at: now,
             primary: Some(RateLimitWindowDisplay {
                 used_percent: 20.0,
@@ -437,4 +452,39 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn provider_quota_labels_requests_and_tokens() {
+        let now = Local::now();
+        let provider = RateLimitSnapshotDisplay {
+            limit_name: "API".to_string(),
+            is_provider_quota: true,
+            captured_at: now,
+            primary: Some(RateLimitWindowDisplay {
+                used_percent: 75.0,
+                resets_at: Some("soon".to_string()),
+                window_minutes: None,
+            }),
+            secondary: Some(RateLimitWindowDisplay {
+                used_percent: 10.0,
+                resets_at: None,
+                window_minutes: None,
+            }),
+            credits: None,
+        };
+
+        let rows = match compose_rate_limit_data_many(&[provider], now) {
+            StatusRateLimitData::Available(rows) => rows,
+            other => panic!("unexpected status: {other:?}"),
+        };
+        let labels: Vec<String> = rows.iter().map(|row| row.label.clone()).collect();
+        assert_eq!(
+            labels,
+            vec![
+                "API limit".to_string(),
+                "Requests limit".to_string(),
+                "Tokens limit".to_string(),
+            ]
+        );
+    }
 }
@@ -8,7 +8,6 @@ use std::time::Duration;
 use std::time::Instant;
 
 use codex_protocol::protocol::Op;
-use crossterm::event::KeyCode;
 use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
 use ratatui::style::Stylize;
@@ -22,7 +21,8 @@ use unicode_width::UnicodeWidthStr;
 use crate::app_event::AppEvent;
 use crate::app_event_sender::AppEventSender;
 use crate::exec_cell::spinner;
-use crate::key_hint;
+use crate::key_hint::KeyBinding;
+use crate::keymap;
 use crate::line_truncation::truncate_line_with_ellipsis_if_overflow;
 use crate::render::renderable::Renderable;
 use crate::shimmer::shimmer_spans;
@@ -48,7 +48,10 @@ pub(crate) struct StatusIndicatorWidget {
     details_max_lines: usize,
     /// Optional suffix rendered after the elapsed/interrupt segment.
     inline_message: Option<String>,
+    /// When the provider throttled the turn, the moment the agent retries.
+    retry_at: Option<Instant>,
     show_interrupt_hint: bool,
+    interrupt_key: KeyBinding,
 
     elapsed_running: Duration,
     last_resume_at: Instant,
@@ -86,7 +89,9 @@ impl StatusIndicatorWidget {
             details: None,
             details_max_lines: STATUS_DETAILS_DEFAULT_MAX_LINES,
             inline_message: None,
+            retry_at: None,
             show_interrupt_hint: true,
+            interrupt_key: keymap::INTERRUPT,
             elapsed_running: Duration::ZERO,
             last_resume_at: Instant::now(),
             is_paused: false,
@@ -136,6 +141,14 @@ impl StatusIndicatorWidget {
             .filter(|message| !message.is_empty());
     }
 
+    /// Show a live countdown to `retry_at`, or clear it with `None`.
+    pub(crate) fn set_retry_at(&mut self, retry_at: Option<Instant>) {
+        self.retry_at = retry_at;
+        if retry_at.is_some() {
+            self.frame_requester.schedule_frame();
+        }
+    }
+
     #[cfg(test)]
     pub(crate) fn header(&self) -> &str {
         &self.header
@@ -150,6 +163,11 @@ impl StatusIndicatorWidget {
         self.show_interrupt_hint = visible;
     }
 
+    /// Set the key advertised in the `to interrupt` hint.
+    pub(crate) fn set_interrupt_key(&mut self, key: KeyBinding) {
+        self.interrupt_key = key;
+    }
+
     #[cfg(test)]
     pub(crate) fn interrupt_hint_visible(&self) -> bool {
         self.show_interrupt_hint
@@ -260,12 +278,27 @@ impl Renderable for StatusIndicatorWidget {
         if self.show_interrupt_hint {
             spans.extend(vec![
                 format!("({pretty_elapsed} • ").dim(),
-                key_hint::plain(KeyCode::Esc).into(),
+                self.interrupt_key.into(),
                 " to interrupt)".dim(),
             ]);
         } else {
             spans.push(format!("({pretty_elapsed})").dim());
         }
+        if let Some(retry_at) = self.retry_at {
+            let remaining = retry_at.saturating_duration_since(now);
+            spans.push(" · ".dim());
+            if remaining.is_zero() {
+                spans.push("retrying now".dim());
+            } else {
+                // Round up so the countdown reaches 1s rather than lingering on 0s.
+                let secs = remaining.as_secs() + u64::from(remaining.subsec_nanos() > 0);
+                spans.push(format!("retrying in {}", fmt_elapsed_compact(secs)).dim());
+                if !self.animations_enabled {
+                    self.frame_requester
+                        .schedule_frame_in(remaining.min(Duration::from_secs(1)));
+                }
+            }
+        }
         if let Some(message) = &self.inline_message {
             // Keep optional context after elapsed/interrupt text so that core
             // interrupt affordances stay in a fixed visual location.
@@ -391,6 +424,25 @@ mod tests {
         assert_eq!(after_resume, before_pause + 3);
     }
 
+    #[test]
+    fn renders_retry_countdown() {
+        let (tx_raw, _rx) = unbounded_channel::<AppEvent>();
+        let tx = AppEventSender::new(tx_raw);
+        let mut w = StatusIndicatorWidget::new(tx, crate::tui::FrameRequester::test_dummy(), false);
+        w.set_interrupt_hint_visible(false);
+        w.set_retry_at(Some(Instant::now() + Duration::from_secs(90)));
+
+        // Freeze the elapsed timer; the countdown rounds up, so it still reads 1m 30s.
+        w.is_paused = true;
+        w.elapsed_running = Duration::ZERO;
+
+        let mut terminal = Terminal::new(TestBackend::new(60, 1)).expect("terminal");
+        terminal
+            .draw(|f| w.render(f.area(), f.buffer_mut()))
+            .expect("draw");
+        insta::assert_snapshot!(terminal.backend());
+    }
+
     #[test]
     fn details_overflow_adds_ellipsis() {
         let (tx_raw, _rx) = unbounded_channel::<AppEvent>();
@@ -0,0 +1,280 @@
+//! Status line segments produced by external commands (`tui.status_line_commands`).
+//!
+//! Each configured command runs in its own task on its own interval, so a slow command (a `git`
+//! query in a large repository, say) never delays the built-in items. The chat widget publishes a
+//! [`StatusLineInput`] describing the session, which every run receives as JSON on stdin; the
+//! first line a run prints comes back as [`AppEvent::StatusLineCommandUpdated`]. Commands also
+//! run right away when a turn completes or the context is compacted, so meters built from the
+//! context window catch up without waiting for the next interval.
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::sync::Arc;
+use std::time::Duration;
+
+use codex_core::config::types::StatusLineCommandConfig;
+use codex_protocol::protocol::TokenUsage;
+use serde::Serialize;
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+use tokio::sync::Notify;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
+
+use crate::app_event::AppEvent;
+use crate::app_event_sender::AppEventSender;
+
+/// Prefix marking a status line item as a command segment, e.g. `command:kube-context`.
+const COMMAND_ITEM_PREFIX: &str = "command:";
+
+/// The command a status line item id refers to, if it is a command segment.
+pub(crate) fn command_name(id: &str) -> Option<&str> {
+    id.strip_prefix(COMMAND_ITEM_PREFIX)
+}
+
+/// The session description written to each command's stdin.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub(crate) struct StatusLineInput {
+    pub(crate) session_id: Option<String>,
+    pub(crate) cwd: PathBuf,
+    pub(crate) model: String,
+    pub(crate) version: String,
+    pub(crate) context_window: StatusLineContextWindow,
+}
+
+/// The model's context window as of the latest token count.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub(crate) struct StatusLineContextWindow {
+    /// Tokens the window holds, when known.
+    pub(crate) size: Option<i64>,
+    /// Usage of the latest request, which is what currently occupies the window.
+    pub(crate) current_usage: Option<TokenUsage>,
+    /// Share of the window still free for the conversation, once the reported window size is
+    /// known.
+    pub(crate) percent_remaining: Option<i64>,
+}
+
+struct RunningCommand {
+    config: StatusLineCommandConfig,
+    task: JoinHandle<()>,
+}
+
+/// The command providers referenced by the status line and their latest output.
+pub(crate) struct StatusLineCommands {
+    running: HashMap<String, RunningCommand>,
+    values: HashMap<String, String>,
+    input: watch::Sender<StatusLineInput>,
+    run_now: Arc<Notify>,
+}
+
+impl Default for StatusLineCommands {
+    fn default() -> Self {
+        let (input, _) = watch::channel(StatusLineInput::default());
+        Self {
+            running: HashMap::new(),
+            values: HashMap::new(),
+            input,
+            run_now: Arc::new(Notify::new()),
+        }
+    }
+}
+
+impl StatusLineCommands {
+    /// Run exactly the `wanted` commands: start new ones, restart those whose configuration
+    /// changed, and stop the rest.
+    pub(crate) fn sync(
+        &mut self,
+        wanted: BTreeMap<String, StatusLineCommandConfig>,
+        app_event_tx: &AppEventSender,
+    ) {
+        self.running.retain(|name, running| {
+            let keep = wanted.get(name) == Some(&running.config);
+            if !keep {
+                running.task.abort();
+                self.values.remove(name);
+            }
+            keep
+        });
+        for (name, config) in wanted {
+            if self.running.contains_key(&name) {
+                continue;
+            }
+            let task = tokio::spawn(run_provider(
+                name.clone(),
+                config.clone(),
+                self.input.subscribe(),
+                Arc::clone(&self.run_now),
+                app_event_tx.clone(),
+            ));
+            self.running.insert(name, RunningCommand { config, task });
+        }
+    }
+
+    /// Update the input later runs receive.
+    pub(crate) fn set_input(&self, input: StatusLineInput) {
+        self.input.send_if_modified(|current| {
+            if *current == input {
+                return false;
+            }
+            *current = input;
+            true
+        });
+    }
+
+    /// Run every command that is waiting for its next interval now.
+    pub(crate) fn run_now(&self) {
+        self.run_now.notify_waiters();
+    }
+
+    pub(crate) fn value(&self, name: &str) -> Option<&str> {
+        self.values.get(name).map(String::as_str)
+    }
+
+    /// Record the output of a run; returns whether the segment changed.
+    pub(crate) fn set_value(&mut self, name: String, value: Option<String>) -> bool {
+        if !self.running.contains_key(&name) {
+            // A run that finished after its command was stopped.
+            return false;
+        }
+        match value {
+            Some(value) => self.values.insert(name, value.clone()) != Some(value),
+            None => self.values.remove(&name).is_some(),
+        }
+    }
+}
+
+impl Drop for StatusLineCommands {
+    fn drop(&mut self) {
+        for running in self.running.values() {
+            running.task.abort();
+        }
+    }
+}
+
+async fn run_provider(
+    name: String,
+    config: StatusLineCommandConfig,
+    mut input: watch::Receiver<StatusLineInput>,
+    run_now: Arc<Notify>,
+    app_event_tx: AppEventSender,
+) {
+    let mut ticker = tokio::time::interval(Duration::from_secs(config.interval_secs.max(1)));
+    ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    loop {
+        tokio::select! {
+            _ = ticker.tick() => {}
+            _ = run_now.notified() => ticker.reset(),
+        }
+        let current = input.borrow_and_update().clone();
+        let value = run_once(&name, &config, &current).await;
+        app_event_tx.send(AppEvent::StatusLineCommandUpdated {
+            name: name.clone(),
+            value,
+        });
+    }
+}
+
+/// Run the command once and return the first non-empty line it printed.
+async fn run_once(
+    name: &str,
+    config: &StatusLineCommandConfig,
+    input: &StatusLineInput,
+) -> Option<String> {
+    let (program, args) = config.command.split_first()?;
+    let payload = serde_json::to_vec(input).ok()?;
+    let mut command = Command::new(program);
+    command
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .kill_on_drop(true);
+    if input.cwd.is_dir() {
+        command.current_dir(&input.cwd);
+    }
+    let mut child = match command.spawn() {
+        Ok(child) => child,
+        Err(err) => {
+            tracing::debug!("status line command {name} failed to start: {err}");
+            return None;
+        }
+    };
+    let run = async move {
+        if let Some(mut stdin) = child.stdin.take() {
+            // Commands that ignore their input may exit before reading it.
+            let _ = stdin.write_all(&payload).await;
+        }
+        child.wait_with_output().await
+    };
+    let output = match tokio::time::timeout(Duration::from_millis(config.timeout_ms), run).await {
+        Ok(Ok(output)) => output,
+        Ok(Err(err)) => {
+            tracing::debug!("status line command {name} failed: {err}");
+            return None;
+        }
+        Err(_) => {
+            tracing::debug!(
+                "status line command {name} timed out after {}ms",
+                config.timeout_ms
+            );
+            return None;
+        }
+    };
+    if !output.status.success() {
+        tracing::debug!("status line command {name} exited with {}", output.status);
+        return None;
+    }
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(|line| {
+            line.chars()
+                .filter(|ch| !ch.is_control())
+                .collect::<String>()
+        })
+        .map(|line| line.trim().to_string())
+        .find(|line| !line.is_empty())
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn sh(script: &str, timeout_ms: u64) -> StatusLineCommandConfig {
+        StatusLineCommandConfig {
+            command: vec!["sh".to_string(), "-c".to_string(), script.to_string()],
+            interval_secs: 10,
+            timeout_ms,
+        }
+    }
+
+    #[tokio::test]
+    async fn run_once_passes_session_json_and_keeps_first_line() {
+        let input = StatusLineInput {
+            session_id: None,
+            cwd: std::env::temp_dir(),
+            model: "gpt-5".to_string(),
+            version: "0.0.0".to_string(),
+            context_window: StatusLineContextWindow::default(),
+        };
+        let config = sh(
+            r#"printf '\n'; grep -o '"model":"[^"]*"'; echo second"#,
+            5_000,
+        );
+
+        let value = run_once("test", &config, &input).await;
+
+        assert_eq!(value.as_deref(), Some(r#""model":"gpt-5""#));
+    }
+
+    #[tokio::test]
+    async fn run_once_hides_failed_and_slow_commands() {
+        let input = StatusLineInput::default();
+
+        assert_eq!(run_once("test", &sh("exit 1", 5_000), &input).await, None);
+        assert_eq!(run_once("test", &sh("sleep 5", 50), &input).await, None);
+    }
+}
@@ -0,0 +1,121 @@
+//! iTerm2 badge showing the session and whether it needs attention (`tui.terminal_badge`).
+//!
+//! The badge is the large translucent label iTerm2 draws over a session. Codex sets it to the
+//! session title with the same busy/approval marker the window title uses, so a split or tab
+//! that is waiting for an approval stands out among several Codex sessions. Inside tmux the
+//! escape sequence is wrapped for passthrough, which needs `set -g allow-passthrough on`. The
+//! badge is cleared again when Codex exits.
+
+use std::io::Write;
+use std::io::stdout;
+
+use base64::Engine;
+use codex_core::terminal::Multiplexer;
+use codex_core::terminal::TerminalName;
+use codex_core::terminal::terminal_info;
+
+use crate::terminal_title::SessionActivity;
+use crate::terminal_title::activity_marker;
+
+pub(crate) struct TerminalBadge {
+    passthrough: bool,
+    last_badge: Option<String>,
+}
+
+impl TerminalBadge {
+    /// The badge writer, when `enabled` and running in iTerm2.
+    pub(crate) fn detect(enabled: bool) -> Option<Self> {
+        if !enabled {
+            return None;
+        }
+        let info = terminal_info();
+        if info.name != TerminalName::Iterm2 {
+            return None;
+        }
+        Some(Self {
+            passthrough: matches!(info.multiplexer, Some(Multiplexer::Tmux { .. })),
+            last_badge: None,
+        })
+    }
+
+    pub(crate) fn set(&mut self, session_title: Option<&str>, activity: SessionActivity) {
+        let badge = format_badge(session_title, activity);
+        if self.last_badge.as_ref() == Some(&badge) {
+            return;
+        }
+        if let Err(err) = self.write(&badge) {
+            tracing::debug!("failed to set the iTerm2 badge: {err}");
+            return;
+        }
+        self.last_badge = Some(badge);
+    }
+
+    fn write(&self, badge: &str) -> std::io::Result<()> {
+        let mut out = stdout();
+        out.write_all(set_badge_sequence(badge, self.passthrough).as_bytes())?;
+        out.flush()
+    }
+}
+
+impl Drop for TerminalBadge {
+    fn drop(&mut self) {
+        if self.last_badge.is_some() {
+            let _ = self.write("");
+        }
+    }
+}
+
+fn format_badge(session_title: Option<&str>, activity: SessionActivity) -> String {
+    let title = session_title
+        .map(str::trim)
+        .filter(|title| !title.is_empty())
+        .unwrap_or("codex");
+    let badge: String = format!("{}{title}", activity_marker(activity))
+        .chars()
+        .filter(|c| !c.is_control())
+        .collect();
+    // iTerm2 treats `\(...)` in a badge as an interpolated expression.
+    badge.replace('\\', "\\\\")
+}
+
+/// `OSC 1337 ; SetBadgeFormat=<base64>`, wrapped for tmux passthrough when needed.
+fn set_badge_sequence(badge: &str, passthrough: bool) -> String {
+    let encoded = base64::engine::general_purpose::STANDARD.encode(badge);
+    let sequence = format!("\x1b]1337;SetBadgeFormat={encoded}\x07");
+    if passthrough {
+        format!("\x1bPtmux;{}\x1b\\", sequence.replace('\x1b', "\x1b\x1b"))
+    } else {
+        sequence
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn badge_marks_activity_and_escapes_interpolation() {
+        assert_eq!(
+            format_badge(Some("Fix flaky test"), SessionActivity::NeedsApproval),
+            "! Fix flaky test"
+        );
+        assert_eq!(format_badge(None, SessionActivity::Busy), "● codex");
+        assert_eq!(
+            format_badge(Some(r"a \(user.name)"), SessionActivity::Idle),
+            r"a \\(user.name)"
+        );
+    }
+
+    #[test]
+    fn badge_sequence_wraps_for_tmux() {
+        assert_eq!(
+            set_badge_sequence("hi", false),
+            "\x1b]1337;SetBadgeFormat=aGk=\x07"
+        );
+        assert_eq!(
+            set_badge_sequence("hi", true),
+            "\x1bPtmux;\x1b\x1b]1337;SetBadgeFormat=aGk=\x07\x1b\\"
+        );
+    }
+}
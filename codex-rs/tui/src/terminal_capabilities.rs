@@ -0,0 +1,377 @@
+//! What the terminal can render, probed once at startup, and the fallbacks used when it can't.
+//!
+//! [`probe`] runs from `Tui::new`, after raw mode is enabled and before anything else reads
+//! stdin. It asks the terminal directly where it can: DECRQM reports whether synchronized output
+//! (mode 2026) is supported, and two cursor-position reports around a `•` measure whether
+//! East Asian "ambiguous" characters are drawn one or two cells wide. A trailing primary device
+//! attributes query bounds the wait, since every terminal answers it. Keyboard enhancement comes
+//! from crossterm's own probe and the color level from the environment.
+//!
+//! Rendering does not branch on these flags widget by widget. The two places that write cells to
+//! the terminal, `custom_terminal::draw` and `insert_history::write_spans`, pass colors through
+//! [`TerminalCapabilities::adapt_color`] and text through [`TerminalCapabilities::adapt_text`],
+//! so RGB colors fall back to the nearest palette entry and Unicode markers to ASCII when the
+//! terminal would not show them as intended. Before [`probe`] runs (tests, plain mode) nothing is
+//! degraded.
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::sync::OnceLock;
+
+use ratatui::style::Color;
+
+use crate::color::perceptual_distance;
+use crate::terminal_palette::StdoutColorLevel;
+use crate::terminal_palette::XTERM_COLORS;
+use crate::terminal_palette::indexed_color;
+use crate::terminal_palette::stdout_color_level;
+
+static CAPABILITIES: OnceLock<TerminalCapabilities> = OnceLock::new();
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct TerminalCapabilities {
+    pub(crate) color_level: StdoutColorLevel,
+    /// The kitty keyboard protocol, which disambiguates modified keys such as `Shift+Enter`.
+    pub(crate) keyboard_enhancement: bool,
+    /// DEC mode 2026, which lets a frame be drawn atomically.
+    pub(crate) synchronized_output: bool,
+    /// Whether bullets, box drawing, and other markers draw as a single cell.
+    pub(crate) unicode_markers: bool,
+}
+
+impl TerminalCapabilities {
+    /// What rendering assumes until a terminal has been probed.
+    pub(crate) const ASSUMED: Self = Self {
+        color_level: StdoutColorLevel::TrueColor,
+        keyboard_enhancement: false,
+        synchronized_output: true,
+        unicode_markers: true,
+    };
+
+    /// Map `color` to one the terminal can show: RGB becomes the nearest xterm 256-color entry
+    /// on 256-color terminals, and anything beyond the 16 ANSI colors becomes the nearest ANSI
+    /// color on terminals with fewer colors.
+    pub(crate) fn adapt_color(&self, color: Color) -> Color {
+        match (self.color_level, color) {
+            (StdoutColorLevel::TrueColor, color) => color,
+            (StdoutColorLevel::Ansi256, Color::Rgb(r, g, b)) => nearest_color((r, g, b), false),
+            (StdoutColorLevel::Ansi256, color) => color,
+            (StdoutColorLevel::Ansi16 | StdoutColorLevel::Unknown, Color::Rgb(r, g, b)) => {
+                nearest_color((r, g, b), true)
+            }
+            (StdoutColorLevel::Ansi16 | StdoutColorLevel::Unknown, Color::Indexed(index))
+                if index >= 16 =>
+            {
+                nearest_color(XTERM_COLORS[index as usize], true)
+            }
+            (_, color) => color,
+        }
+    }
+
+    /// Replace markers with ASCII when the terminal would not draw them as one cell.
+    pub(crate) fn adapt_text<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        if self.unicode_markers || text.is_ascii() {
+            return Cow::Borrowed(text);
+        }
+        if !text.chars().any(|c| ascii_marker(c).is_some()) {
+            return Cow::Borrowed(text);
+        }
+        Cow::Owned(text.chars().map(|c| ascii_marker(c).unwrap_or(c)).collect())
+    }
+}
+
+/// The capabilities found by [`probe`], or [`TerminalCapabilities::ASSUMED`] before it runs.
+pub(crate) fn get() -> TerminalCapabilities {
+    CAPABILITIES
+        .get()
+        .copied()
+        .unwrap_or(TerminalCapabilities::ASSUMED)
+}
+
+/// Query the terminal and record what it supports. Must run in raw mode, before the event
+/// stream starts reading stdin; later calls return the first result.
+pub(crate) fn probe() -> TerminalCapabilities {
+    if let Some(capabilities) = CAPABILITIES.get() {
+        return *capabilities;
+    }
+    let reply = imp::query(PROBE_QUERY)
+        .map(|bytes| parse_reply(&bytes))
+        .unwrap_or_default();
+    let capabilities = TerminalCapabilities {
+        color_level: stdout_color_level(),
+        keyboard_enhancement: crossterm::terminal::supports_keyboard_enhancement().unwrap_or(false),
+        // Terminals that ignore DECRQM also ignore the mode itself, so emitting it is harmless
+        // when nobody answered at all.
+        synchronized_output: reply.synchronized_output.unwrap_or(!reply.answered),
+        unicode_markers: reply.marker_width.is_none_or(|width| width == 1) && !locale_is_legacy(),
+    };
+    tracing::info!(?capabilities, "terminal capabilities");
+    *CAPABILITIES.get_or_init(|| capabilities)
+}
+
+/// DECRQM for mode 2026; save the cursor, report its position, print an ambiguous-width marker,
+/// report again, restore and erase; then ask for primary device attributes.
+const PROBE_QUERY: &str = "\x1b[?2026$p\x1b7\x1b[6n•\x1b[6n\x1b8\x1b[K\x1b[c";
+
+#[derive(Debug, Default, PartialEq, Eq)]
+struct ProbeReply {
+    /// The terminal answered the device attributes query.
+    answered: bool,
+    synchronized_output: Option<bool>,
+    /// Columns the probe marker advanced the cursor.
+    marker_width: Option<u16>,
+}
+
+fn parse_reply(bytes: &[u8]) -> ProbeReply {
+    let text = String::from_utf8_lossy(bytes);
+    let mut reply = ProbeReply::default();
+    let mut cursor_columns = Vec::new();
+    for sequence in text.split("\x1b[").skip(1) {
+        let Some(end) = sequence.find(|c: char| ('\x40'..='\x7e').contains(&c)) else {
+            continue;
+        };
+        let params = &sequence[..end];
+        match &sequence[end..=end] {
+            "y" => {
+                if let Some(status) = params
+                    .strip_prefix("?2026;")
+                    .and_then(|rest| rest.strip_suffix('$'))
+                {
+                    // 1 = set, 2 = reset: the mode exists. 0 = unknown, 3/4 = permanently fixed.
+                    reply.synchronized_output = Some(matches!(status, "1" | "2"));
+                }
+            }
+            "R" => {
+                if let Some(column) = params
+                    .split(';')
+                    .nth(1)
+                    .and_then(|column| column.parse::<u16>().ok())
+                {
+                    cursor_columns.push(column);
+                }
+            }
+            "c" if params.starts_with('?') => reply.answered = true,
+            _ => {}
+        }
+    }
+    if let [before, after] = cursor_columns[..] {
+        reply.marker_width = after.checked_sub(before);
+    }
+    reply
+}
+
+/// A locale that names a character set other than UTF-8, e.g. `en_US.ISO-8859-1`. An unset or
+/// `C` locale says nothing about the terminal and is not treated as legacy.
+fn locale_is_legacy() -> bool {
+    let locale = ["LC_ALL", "LC_CTYPE", "LANG"]
+        .iter()
+        .filter_map(|name| std::env::var(name).ok())
+        .find(|value| !value.is_empty());
+    let Some(locale) = locale else {
+        return false;
+    };
+    let Some((_, charset)) = locale.split_once('.') else {
+        return false;
+    };
+    let charset = charset.split('@').next().unwrap_or_default();
+    !charset.eq_ignore_ascii_case("utf-8") && !charset.eq_ignore_ascii_case("utf8")
+}
+
+fn ascii_marker(c: char) -> Option<char> {
+    let ascii = match c {
+        '•' | '●' | '◦' | '○' | '◔' => '*',
+        '·' => '.',
+        '…' => '.',
+        '│' | '┃' | '▌' | '▐' => '|',
+        '└' | '╰' => '`',
+        '├' | '┌' | '┐' | '┘' | '┬' | '┴' | '┼' | '╭' | '╮' | '╯' => '+',
+        '─' | '━' | '—' | '–' | '‑' => '-',
+        '›' | '→' | '↳' => '>',
+        '‹' | '←' => '<',
+        '↑' | '▴' | '▲' => '^',
+        '↓' | '▾' | '▼' => 'v',
+        '✔' | '✓' => 'v',
+        '✗' | '✘' => 'x',
+        '⚠' => '!',
+        '⋮' => ':',
+        '■' | '█' | '☰' => '#',
+        '□' => 'o',
+        '░' | '▒' => '.',
+        _ => return None,
+    };
+    Some(ascii)
+}
+
+const ANSI_COLORS: [Color; 16] = [
+    Color::Black,
+    Color::Red,
+    Color::Green,
+    Color::Yellow,
+    Color::Blue,
+    Color::Magenta,
+    Color::Cyan,
+    Color::Gray,
+    Color::DarkGray,
+    Color::LightRed,
+    Color::LightGreen,
+    Color::LightYellow,
+    Color::LightBlue,
+    Color::LightMagenta,
+    Color::LightCyan,
+    Color::White,
+];
+
+/// The closest ANSI color (`ansi16`) or stable xterm 256-color entry to `target`. Results are
+/// cached because the draw loop asks for the same few colors on every frame.
+fn nearest_color(target: (u8, u8, u8), ansi16: bool) -> Color {
+    static CACHE: OnceLock<Mutex<HashMap<((u8, u8, u8), bool), Color>>> = OnceLock::new();
+    let cache = CACHE.get_or_init(|| Mutex::new(HashMap::new()));
+    if let Ok(cache) = cache.lock()
+        && let Some(color) = cache.get(&(target, ansi16))
+    {
+        return *color;
+    }
+    // The first 16 entries depend on the terminal theme, so 256-color output avoids them.
+    let candidates = if ansi16 { 0..16 } else { 16..256 };
+    let index = candidates
+        .min_by(|a, b| {
+            perceptual_distance(XTERM_COLORS[*a], target)
+                .total_cmp(&perceptual_distance(XTERM_COLORS[*b], target))
+        })
+        .unwrap_or(0);
+    let color = if ansi16 {
+        ANSI_COLORS[index]
+    } else {
+        indexed_color(index as u8)
+    };
+    if let Ok(mut cache) = cache.lock() {
+        cache.insert((target, ansi16), color);
+    }
+    color
+}
+
+#[cfg(all(unix, not(test)))]
+mod imp {
+    use std::io::Write;
+    use std::time::Duration;
+    use std::time::Instant;
+
+    /// How long to wait for the device attributes reply before assuming the terminal does not
+    /// answer queries at all.
+    const TIMEOUT: Duration = Duration::from_millis(500);
+
+    /// Write `query` and read stdin until the primary device attributes reply arrives.
+    pub(super) fn query(query: &str) -> Option<Vec<u8>> {
+        let mut stdout = std::io::stdout();
+        stdout.write_all(query.as_bytes()).ok()?;
+        stdout.flush().ok()?;
+
+        let deadline = Instant::now() + TIMEOUT;
+        let mut reply = Vec::new();
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return None;
+            }
+            let mut fds = libc::pollfd {
+                fd: libc::STDIN_FILENO,
+                events: libc::POLLIN,
+                revents: 0,
+            };
+            // Safety: `fds` is a valid pollfd for the duration of the call.
+            let ready = unsafe { libc::poll(&mut fds, 1, remaining.as_millis() as libc::c_int) };
+            if ready < 0
+                && std::io::Error::last_os_error().kind() == std::io::ErrorKind::Interrupted
+            {
+                continue;
+            }
+            if ready <= 0 {
+                return None;
+            }
+            let mut buf = [0u8; 256];
+            // Safety: `buf` is valid for writes of `buf.len()` bytes.
+            let read =
+                unsafe { libc::read(libc::STDIN_FILENO, buf.as_mut_ptr().cast(), buf.len()) };
+            if read <= 0 {
+                return None;
+            }
+            reply.extend_from_slice(&buf[..read as usize]);
+            if has_device_attributes(&reply) {
+                return Some(reply);
+            }
+        }
+    }
+
+    fn has_device_attributes(reply: &[u8]) -> bool {
+        let text = String::from_utf8_lossy(reply);
+        text.split("\x1b[?").skip(1).any(|sequence| {
+            sequence
+                .trim_start_matches(|c: char| c.is_ascii_digit() || c == ';')
+                .starts_with('c')
+        })
+    }
+}
+
+#[cfg(not(all(unix, not(test))))]
+mod imp {
+    pub(super) fn query(_query: &str) -> Option<Vec<u8>> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::terminal_palette::rgb_color;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn parses_mode_report_cursor_positions_and_device_attributes() {
+        let reply = parse_reply(b"\x1b[?2026;2$y\x1b[12;1R\x1b[12;3R\x1b[?62;22c");
+
+        assert_eq!(
+            reply,
+            ProbeReply {
+                answered: true,
+                synchronized_output: Some(true),
+                marker_width: Some(2),
+            }
+        );
+    }
+
+    #[test]
+    fn unknown_mode_and_missing_reports_leave_defaults() {
+        let reply = parse_reply(b"\x1b[?2026;0$y\x1b[?1;2c");
+
+        assert_eq!(
+            reply,
+            ProbeReply {
+                answered: true,
+                synchronized_output: Some(false),
+                marker_width: None,
+            }
+        );
+    }
+
+    #[test]
+    fn degrades_colors_and_markers() {
+        let limited = TerminalCapabilities {
+            color_level: StdoutColorLevel::Ansi16,
+            unicode_markers: false,
+            ..TerminalCapabilities::ASSUMED
+        };
+
+        assert_eq!(
+            limited.adapt_color(rgb_color((250, 10, 10))),
+            Color::LightRed
+        );
+        assert_eq!(limited.adapt_color(Color::Cyan), Color::Cyan);
+        assert_eq!(limited.adapt_text("• done │ ✔ ok…"), "* done | v ok.");
+        assert_eq!(
+            TerminalCapabilities::ASSUMED.adapt_color(rgb_color((1, 2, 3))),
+            rgb_color((1, 2, 3))
+        );
+        assert_eq!(TerminalCapabilities::ASSUMED.adapt_text("• ok"), "• ok");
+    }
+}
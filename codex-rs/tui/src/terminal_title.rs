@@ -0,0 +1,229 @@
+//! Keep the terminal window title in sync with the session (`tui.terminal_title`).
+//!
+//! The title reads `codex: <session title> — <repo>@<branch>` and gains a leading marker while
+//! a turn is running, so a tab left in the background shows at a glance whether Codex is
+//! still working. The title the terminal had before Codex started is saved on the terminal's
+//! title stack (XTWINOPS 22/23) and restored on exit; terminals that do not keep a stack
+//! ignore those sequences. Inside tmux, the same title and state can also be published to the
+//! pane (see [`crate::tmux`]), and in iTerm2 shown as a badge (see [`crate::terminal_badge`]).
+
+use std::fmt;
+use std::io::stdout;
+use std::path::Path;
+use std::path::PathBuf;
+
+use codex_core::git_info::current_branch_name;
+use codex_core::git_info::get_git_repo_root;
+use crossterm::Command;
+use crossterm::terminal::SetTitle;
+use ratatui::crossterm::execute;
+
+use crate::terminal_badge::TerminalBadge;
+use crate::tmux::TmuxPane;
+
+/// Prefix shown while a turn is running.
+const BUSY_MARKER: &str = "● ";
+/// Prefix shown while an approval request waits for the user.
+const APPROVAL_MARKER: &str = "! ";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SessionActivity {
+    Idle,
+    Busy,
+    NeedsApproval,
+}
+
+#[derive(Default)]
+pub(crate) struct TerminalTitle {
+    enabled: bool,
+    tmux: Option<TmuxPane>,
+    badge: Option<TerminalBadge>,
+    /// `<repo>@<branch>` for the session cwd, once looked up.
+    repo_label: Option<String>,
+    repo_label_cwd: Option<PathBuf>,
+    last_title: Option<String>,
+    was_busy: bool,
+    saved_previous_title: bool,
+}
+
+impl TerminalTitle {
+    pub(crate) fn new(enabled: bool, tmux: bool, badge: bool) -> Self {
+        Self {
+            enabled,
+            tmux: TmuxPane::detect(tmux),
+            badge: TerminalBadge::detect(badge),
+            ..Self::default()
+        }
+    }
+
+    pub(crate) fn enabled(&self) -> bool {
+        self.enabled || self.tmux.is_some() || self.badge.is_some()
+    }
+
+    /// Write the title for the current session state if it changed.
+    ///
+    /// Returns true when a turn just finished, since the agent may have switched branches and
+    /// the repo label should be looked up again.
+    pub(crate) fn update(
+        &mut self,
+        session_title: Option<&str>,
+        activity: SessionActivity,
+    ) -> bool {
+        if !self.enabled() {
+            return false;
+        }
+        let busy = activity != SessionActivity::Idle;
+        let turn_finished = self.was_busy && !busy;
+        self.was_busy = busy;
+
+        let title = format_title(session_title, self.repo_label.as_deref(), activity);
+        if let Some(tmux) = self.tmux.as_mut() {
+            tmux.set_title(&title);
+            tmux.set_session_title(session_title.unwrap_or_default());
+            tmux.set_state(activity);
+        }
+        if let Some(badge) = self.badge.as_mut() {
+            badge.set(session_title, activity);
+        }
+        if !self.enabled || self.last_title.as_ref() == Some(&title) {
+            return turn_finished;
+        }
+        if !self.saved_previous_title {
+            self.saved_previous_title = execute!(stdout(), TitleStack::Push).is_ok();
+        }
+        if let Err(err) = execute!(stdout(), SetTitle(&title)) {
+            tracing::warn!("failed to set the terminal title; disabling updates: {err}");
+            self.enabled = false;
+            return false;
+        }
+        self.last_title = Some(title);
+        turn_finished
+    }
+
+    /// Remember which cwd a repo label lookup is for.
+    pub(crate) fn begin_repo_lookup(&mut self, cwd: &Path) {
+        self.repo_label_cwd = Some(cwd.to_path_buf());
+    }
+
+    /// Store a finished repo label lookup, dropping results for a cwd that is no longer current.
+    pub(crate) fn set_repo_label(&mut self, cwd: &Path, label: Option<String>) {
+        if self.repo_label_cwd.as_deref() == Some(cwd) {
+            self.repo_label = label;
+        }
+    }
+}
+
+impl Drop for TerminalTitle {
+    fn drop(&mut self) {
+        if self.saved_previous_title {
+            let _ = execute!(stdout(), TitleStack::Pop);
+        }
+    }
+}
+
+/// Look up `<repo>@<branch>` for `cwd`, or just the repo name on a detached HEAD.
+pub(crate) async fn repo_label(cwd: &Path) -> Option<String> {
+    let root = get_git_repo_root(cwd)?;
+    let name = root.file_name()?.to_string_lossy().into_owned();
+    Some(match current_branch_name(cwd).await {
+        Some(branch) => format!("{name}@{branch}"),
+        None => name,
+    })
+}
+
+/// The prefix marking a busy session or one waiting for an approval.
+pub(crate) fn activity_marker(activity: SessionActivity) -> &'static str {
+    match activity {
+        SessionActivity::Idle => "",
+        SessionActivity::Busy => BUSY_MARKER,
+        SessionActivity::NeedsApproval => APPROVAL_MARKER,
+    }
+}
+
+fn format_title(
+    session_title: Option<&str>,
+    repo_label: Option<&str>,
+    activity: SessionActivity,
+) -> String {
+    let mut title = activity_marker(activity).to_string();
+    title.push_str("codex");
+    if let Some(session_title) = session_title.map(str::trim).filter(|s| !s.is_empty()) {
+        title.push_str(": ");
+        title.push_str(session_title);
+    }
+    if let Some(repo_label) = repo_label {
+        title.push_str(" — ");
+        title.push_str(repo_label);
+    }
+    // Control characters would end the OSC sequence early.
+    title.chars().filter(|c| !c.is_control()).collect()
+}
+
+/// Save or restore the terminal's title (XTWINOPS 22 and 23).
+#[derive(Debug, Clone, Copy)]
+enum TitleStack {
+    Push,
+    Pop,
+}
+
+impl Command for TitleStack {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        match self {
+            TitleStack::Push => write!(f, "\x1b[22;0t"),
+            TitleStack::Pop => write!(f, "\x1b[23;0t"),
+        }
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> std::io::Result<()> {
+        Err(std::io::Error::other(
+            "tried to execute TitleStack using WinAPI; use ANSI instead",
+        ))
+    }
+
+    #[cfg(windows)]
+    fn is_ansi_code_supported(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn formats_session_repo_and_busy_state() {
+        assert_eq!(
+            format_title(
+                Some("Fix flaky test"),
+                Some("codex@main"),
+                SessionActivity::Idle
+            ),
+            "codex: Fix flaky test — codex@main"
+        );
+        assert_eq!(
+            format_title(None, Some("codex@main"), SessionActivity::Busy),
+            "● codex — codex@main"
+        );
+        assert_eq!(
+            format_title(Some("  "), None, SessionActivity::NeedsApproval),
+            "! codex"
+        );
+        assert_eq!(
+            format_title(Some("evil\u{7}\u{1b}]0;title"), None, SessionActivity::Idle),
+            "codex: evil]0;title"
+        );
+    }
+
+    #[test]
+    fn drops_repo_labels_for_stale_cwds() {
+        let mut title = TerminalTitle::new(false, false, false);
+        title.begin_repo_lookup(Path::new("/a"));
+        title.begin_repo_lookup(Path::new("/b"));
+        title.set_repo_label(Path::new("/a"), Some("a@main".to_string()));
+        assert_eq!(title.repo_label, None);
+        title.set_repo_label(Path::new("/b"), Some("b@main".to_string()));
+        assert_eq!(title.repo_label.as_deref(), Some("b@main"));
+    }
+}
@@ -1,5 +1,4 @@
 use unicode_segmentation::UnicodeSegmentation;
-use unicode_width::UnicodeWidthChar;
 use unicode_width::UnicodeWidthStr;
 
 pub(crate) fn capitalize_first(input: &str) -> String {
@@ -181,21 +180,19 @@ pub(crate) fn center_truncate_path(path: &str, max_width: usize) -> String {
             return "…".to_string();
         }
 
-        let mut kept: Vec<char> = Vec::new();
+        let mut kept: Vec<&str> = Vec::new();
         let mut used_width = 1; // reserve space for leading ellipsis
-        for ch in original.chars().rev() {
-            let ch_width = UnicodeWidthChar::width(ch).unwrap_or(0);
-            if used_width + ch_width > allowed_width {
+        for grapheme in original.graphemes(true).rev() {
+            let grapheme_width = UnicodeWidthStr::width(grapheme);
+            if used_width + grapheme_width > allowed_width {
                 break;
             }
-            used_width += ch_width;
-            kept.push(ch);
+            used_width += grapheme_width;
+            kept.push(grapheme);
         }
         kept.reverse();
         let mut truncated = String::from("…");
-        for ch in kept {
-            truncated.push(ch);
-        }
+        truncated.push_str(&kept.concat());
         truncated
     };
 
@@ -0,0 +1,122 @@
+//! tmux awareness for the pane Codex runs in (`tui.tmux`).
+//!
+//! When enabled inside tmux, Codex names its pane after the session title and publishes
+//! whether it is idle, busy, or waiting for an approval in the `@codex_state` pane option, and
+//! the bare session title in `@codex_title`, so the tmux status bar can surface them, for
+//! example:
+//!
+//! ```text
+//! set -g window-status-format '#I:#W#{?#{==:#{@codex_state},approval}, [approve],}'
+//! ```
+//!
+//! The options are unset again when Codex exits.
+
+use std::process::Stdio;
+
+use tokio::process::Command;
+
+use crate::terminal_title::SessionActivity;
+
+/// Pane option holding the session state.
+const STATE_OPTION: &str = "@codex_state";
+/// Pane option holding the session title.
+const TITLE_OPTION: &str = "@codex_title";
+
+pub(crate) struct TmuxPane {
+    pane: String,
+    last_title: Option<String>,
+    last_session_title: Option<String>,
+    last_state: Option<SessionActivity>,
+}
+
+impl TmuxPane {
+    /// The pane Codex runs in, when `enabled` and running inside tmux.
+    pub(crate) fn detect(enabled: bool) -> Option<Self> {
+        if !enabled {
+            return None;
+        }
+        let pane = std::env::var("TMUX_PANE").ok()?;
+        Some(Self {
+            pane,
+            last_title: None,
+            last_session_title: None,
+            last_state: None,
+        })
+    }
+
+    pub(crate) fn set_title(&mut self, title: &str) {
+        if self.last_title.as_deref() == Some(title) {
+            return;
+        }
+        self.last_title = Some(title.to_string());
+        self.run(&["select-pane", "-t", &self.pane, "-T", title]);
+    }
+
+    pub(crate) fn set_session_title(&mut self, session_title: &str) {
+        if self.last_session_title.as_deref() == Some(session_title) {
+            return;
+        }
+        self.last_session_title = Some(session_title.to_string());
+        self.run(&[
+            "set-option",
+            "-p",
+            "-t",
+            &self.pane,
+            TITLE_OPTION,
+            session_title,
+        ]);
+    }
+
+    pub(crate) fn set_state(&mut self, state: SessionActivity) {
+        if self.last_state == Some(state) {
+            return;
+        }
+        self.last_state = Some(state);
+        self.run(&[
+            "set-option",
+            "-p",
+            "-t",
+            &self.pane,
+            STATE_OPTION,
+            match state {
+                SessionActivity::Idle => "idle",
+                SessionActivity::Busy => "busy",
+                SessionActivity::NeedsApproval => "approval",
+            },
+        ]);
+    }
+
+    /// Run a tmux command in the background; failures only matter for logging.
+    fn run(&self, args: &[&str]) {
+        let mut command = Command::new("tmux");
+        command
+            .args(args)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null());
+        tokio::spawn(async move {
+            if let Err(err) = command.status().await {
+                tracing::debug!("failed to run tmux: {err}");
+            }
+        });
+    }
+}
+
+impl Drop for TmuxPane {
+    fn drop(&mut self) {
+        for (option, was_set) in [
+            (STATE_OPTION, self.last_state.is_some()),
+            (TITLE_OPTION, self.last_session_title.is_some()),
+        ] {
+            if !was_set {
+                continue;
+            }
+            let _ = std::process::Command::new("tmux")
+                .args(["set-option", "-p", "-u", "-t", &self.pane, option])
+                .stdin(Stdio::null())
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .status();
+        }
+    }
+}
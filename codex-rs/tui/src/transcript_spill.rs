@@ -0,0 +1,466 @@
+//! Bounds the memory held by the transcript in long sessions.
+//!
+//! `App::transcript_cells` keeps every committed history cell so the transcript overlay
+//! (`Ctrl+T`) and backtracking can show them again. Once the cells' rendered lines exceed
+//! `tui.transcript_memory_lines`, the oldest cells are rendered one last time, written to an
+//! anonymous temporary file, and replaced by [`SpilledCell`]s that read their lines back only
+//! when the overlay draws or measures them.
+//!
+//! Cells are spilled unwrapped and re-wrapped to the width they are drawn at, so a resize or the
+//! overlay still lays them out correctly. Only cells whose layout is plain wrapping can be
+//! treated this way; cells that fill or truncate to the width, such as separators and cards,
+//! stay in memory. User messages and session headers are never spilled either: backtracking
+//! finds them by type, and they are small.
+
+use std::fs::File;
+use std::io;
+use std::io::Read;
+use std::io::Seek;
+use std::io::SeekFrom;
+use std::io::Write;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use ratatui::layout::Alignment;
+use ratatui::style::Color;
+use ratatui::style::Modifier;
+use ratatui::style::Style;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::text::Span;
+use serde::Deserialize;
+use serde::Serialize;
+
+use unicode_width::UnicodeWidthChar;
+use unicode_width::UnicodeWidthStr;
+
+use crate::history_cell::HistoryCell;
+use crate::history_cell::SessionInfoCell;
+use crate::history_cell::UserHistoryCell;
+use crate::wrapping::RtOptions;
+use crate::wrapping::adaptive_wrap_lines;
+
+/// Width spilled cells are rendered at, wide enough that nothing wraps.
+const UNWRAPPED_WIDTH: u16 = u16::MAX;
+
+/// Tracks which committed cells are still resident and moves the oldest ones to disk.
+#[derive(Debug)]
+pub(crate) struct TranscriptSpill {
+    /// Resident lines allowed before spilling; `0` disables spilling.
+    max_lines: usize,
+    /// Width the most recent cell was measured at, for counting resident lines.
+    width: u16,
+    /// Created on the first spill.
+    file: Option<Arc<SpillFile>>,
+    /// Index of the oldest cell that is still resident and could be spilled.
+    next: usize,
+    /// Rendered lines of the cells from `next` on.
+    resident_lines: usize,
+}
+
+impl TranscriptSpill {
+    pub(crate) fn new(max_lines: usize) -> Self {
+        Self {
+            max_lines,
+            width: u16::MAX,
+            file: None,
+            next: 0,
+            resident_lines: 0,
+        }
+    }
+
+    /// Account for `cell`, just appended to `cells`, and spill the oldest cells while the
+    /// resident lines are over the limit. The newest cell always stays in memory.
+    pub(crate) fn on_cell_inserted(
+        &mut self,
+        cells: &mut [Arc<dyn HistoryCell>],
+        cell: &Arc<dyn HistoryCell>,
+        width: u16,
+    ) {
+        if self.max_lines == 0 {
+            return;
+        }
+        self.width = width;
+        self.resident_lines += cell.transcript_lines(width).len();
+        while self.resident_lines > self.max_lines && self.next + 1 < cells.len() {
+            let index = self.next;
+            self.next += 1;
+            let resident = cells[index].transcript_lines(self.width).len();
+            self.resident_lines = self.resident_lines.saturating_sub(resident);
+            if !is_spillable(cells[index].as_ref()) {
+                continue;
+            }
+            // A cell that renders differently at two widths where nothing wraps lays itself out
+            // to the width, so its lines cannot be re-wrapped later.
+            let lines = cells[index].transcript_lines(UNWRAPPED_WIDTH);
+            if lines != cells[index].transcript_lines(UNWRAPPED_WIDTH - 1) {
+                continue;
+            }
+            match self.spill(lines, cells[index].is_stream_continuation()) {
+                Ok(spilled) => cells[index] = Arc::new(spilled),
+                Err(err) => {
+                    tracing::warn!("keeping the transcript in memory: spilling failed: {err}");
+                    self.max_lines = 0;
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Recount the resident lines after `cells` was truncated or replaced.
+    pub(crate) fn on_cells_replaced(&mut self, cells: &[Arc<dyn HistoryCell>]) {
+        self.next = self.next.min(cells.len());
+        self.resident_lines = cells[self.next..]
+            .iter()
+            .map(|cell| cell.transcript_lines(self.width).len())
+            .sum();
+    }
+
+    /// Forget all cells; the spill file is dropped once no spilled cell refers to it.
+    pub(crate) fn clear(&mut self) {
+        self.file = None;
+        self.next = 0;
+        self.resident_lines = 0;
+    }
+
+    fn spill(
+        &mut self,
+        lines: Vec<Line<'static>>,
+        is_stream_continuation: bool,
+    ) -> io::Result<SpilledCell> {
+        let file = match &self.file {
+            Some(file) => Arc::clone(file),
+            None => {
+                let file = Arc::new(SpillFile::new()?);
+                self.file = Some(Arc::clone(&file));
+                file
+            }
+        };
+        let stored = lines.iter().map(StoredLine::from).collect::<Vec<_>>();
+        let bytes = serde_json::to_vec(&stored).map_err(io::Error::other)?;
+        let offset = file.append(&bytes)?;
+        Ok(SpilledCell {
+            file,
+            offset,
+            len: bytes.len(),
+            is_stream_continuation,
+        })
+    }
+}
+
+fn is_spillable(cell: &dyn HistoryCell) -> bool {
+    let any = cell.as_any();
+    !(any.is::<UserHistoryCell>() || any.is::<SessionInfoCell>() || any.is::<SpilledCell>())
+}
+
+#[derive(Debug)]
+struct SpillFile {
+    /// The file and its length.
+    inner: Mutex<(File, u64)>,
+}
+
+impl SpillFile {
+    fn new() -> io::Result<Self> {
+        Ok(Self {
+            inner: Mutex::new((tempfile::tempfile()?, 0)),
+        })
+    }
+
+    /// Append `bytes`, returning the offset they were written at.
+    fn append(&self, bytes: &[u8]) -> io::Result<u64> {
+        let mut guard = self
+            .inner
+            .lock()
+            .map_err(|_| io::Error::other("spill file lock poisoned"))?;
+        let (file, len) = &mut *guard;
+        let offset = *len;
+        file.seek(SeekFrom::Start(offset))?;
+        file.write_all(bytes)?;
+        *len += bytes.len() as u64;
+        Ok(offset)
+    }
+
+    fn read(&self, offset: u64, len: usize) -> io::Result<Vec<u8>> {
+        let mut guard = self
+            .inner
+            .lock()
+            .map_err(|_| io::Error::other("spill file lock poisoned"))?;
+        let (file, _) = &mut *guard;
+        file.seek(SeekFrom::Start(offset))?;
+        let mut bytes = vec![0; len];
+        file.read_exact(&mut bytes)?;
+        Ok(bytes)
+    }
+}
+
+/// A history cell whose rendered lines live in the spill file.
+#[derive(Debug)]
+pub(crate) struct SpilledCell {
+    file: Arc<SpillFile>,
+    offset: u64,
+    len: usize,
+    is_stream_continuation: bool,
+}
+
+impl SpilledCell {
+    fn read_lines(&self) -> io::Result<Vec<Line<'static>>> {
+        let bytes = self.file.read(self.offset, self.len)?;
+        let stored: Vec<StoredLine> = serde_json::from_slice(&bytes).map_err(io::Error::other)?;
+        Ok(stored.into_iter().map(Line::from).collect())
+    }
+}
+
+impl HistoryCell for SpilledCell {
+    fn display_lines(&self, width: u16) -> Vec<Line<'static>> {
+        match self.read_lines() {
+            Ok(lines) => lines
+                .into_iter()
+                .flat_map(|line| rewrap_line(line, usize::from(width.max(1))))
+                .collect(),
+            Err(err) => vec![
+                format!("(this part of the transcript could not be read back: {err})")
+                    .dim()
+                    .into(),
+            ],
+        }
+    }
+
+    fn is_stream_continuation(&self) -> bool {
+        self.is_stream_continuation
+    }
+}
+
+/// Wrap an unwrapped spilled line to `width`. Continuation rows line up after the line's
+/// leading whitespace and a leading marker such as `• ` or `└ `.
+fn rewrap_line(line: Line<'static>, width: usize) -> Vec<Line<'static>> {
+    if line.width() <= width {
+        return vec![line];
+    }
+    let text = line
+        .spans
+        .iter()
+        .map(|span| span.content.as_ref())
+        .collect::<String>();
+    let indent = hanging_indent(&text).min(width / 2);
+    let alignment = line.alignment;
+    adaptive_wrap_lines(
+        [line],
+        RtOptions::new(width).subsequent_indent(" ".repeat(indent).into()),
+    )
+    .into_iter()
+    .map(|mut wrapped| {
+        wrapped.alignment = alignment;
+        wrapped
+    })
+    .collect()
+}
+
+fn hanging_indent(text: &str) -> usize {
+    let rest = text.trim_start();
+    let mut indent = UnicodeWidthStr::width(&text[..text.len() - rest.len()]);
+    let mut chars = rest.chars();
+    if let (Some(marker), Some(' ')) = (chars.next(), chars.next())
+        && !marker.is_alphanumeric()
+    {
+        indent += marker.width().unwrap_or(0) + 1;
+    }
+    indent
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct StoredLine {
+    style: StoredStyle,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    alignment: Option<StoredAlignment>,
+    spans: Vec<(String, StoredStyle)>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct StoredStyle {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    fg: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    bg: Option<String>,
+    #[serde(default)]
+    add: u16,
+    #[serde(default)]
+    sub: u16,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+enum StoredAlignment {
+    Left,
+    Center,
+    Right,
+}
+
+impl From<&Line<'static>> for StoredLine {
+    fn from(line: &Line<'static>) -> Self {
+        Self {
+            style: line.style.into(),
+            alignment: line.alignment.map(|alignment| match alignment {
+                Alignment::Left => StoredAlignment::Left,
+                Alignment::Center => StoredAlignment::Center,
+                Alignment::Right => StoredAlignment::Right,
+            }),
+            spans: line
+                .spans
+                .iter()
+                .map(|span| (span.content.to_string(), span.style.into()))
+                .collect(),
+        }
+    }
+}
+
+impl From<StoredLine> for Line<'static> {
+    fn from(stored: StoredLine) -> Self {
+        let mut line = Line::from(
+            stored
+                .spans
+                .into_iter()
+                .map(|(content, style)| Span::styled(content, Style::from(style)))
+                .collect::<Vec<_>>(),
+        )
+        .style(Style::from(stored.style));
+        line.alignment = stored.alignment.map(|alignment| match alignment {
+            StoredAlignment::Left => Alignment::Left,
+            StoredAlignment::Center => Alignment::Center,
+            StoredAlignment::Right => Alignment::Right,
+        });
+        line
+    }
+}
+
+impl From<Style> for StoredStyle {
+    fn from(style: Style) -> Self {
+        Self {
+            fg: style.fg.map(|color| color.to_string()),
+            bg: style.bg.map(|color| color.to_string()),
+            add: style.add_modifier.bits(),
+            sub: style.sub_modifier.bits(),
+        }
+    }
+}
+
+impl From<StoredStyle> for Style {
+    fn from(stored: StoredStyle) -> Self {
+        Style {
+            fg: stored.fg.and_then(|color| Color::from_str(&color).ok()),
+            bg: stored.bg.and_then(|color| Color::from_str(&color).ok()),
+            add_modifier: Modifier::from_bits_truncate(stored.add),
+            sub_modifier: Modifier::from_bits_truncate(stored.sub),
+            ..Style::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::history_cell::PlainHistoryCell;
+    use pretty_assertions::assert_eq;
+
+    fn plain(lines: Vec<Line<'static>>) -> Arc<dyn HistoryCell> {
+        Arc::new(PlainHistoryCell::new(lines))
+    }
+
+    #[test]
+    fn spills_the_oldest_cells_and_reads_them_back() {
+        let first = vec![
+            Line::from(vec!["• ".dim(), "Ran ".bold(), "cargo test".cyan()]),
+            Line::from("  └ ok".green()).centered(),
+        ];
+        let mut spill = TranscriptSpill::new(3);
+        let mut cells: Vec<Arc<dyn HistoryCell>> = Vec::new();
+        for cell in [
+            plain(first.clone()),
+            plain(vec!["second".into()]),
+            plain(vec!["third".into(), "".into()]),
+        ] {
+            cells.push(Arc::clone(&cell));
+            spill.on_cell_inserted(&mut cells, &cell, 80);
+        }
+
+        assert!(cells[0].as_any().is::<SpilledCell>());
+        assert!(!cells[1].as_any().is::<SpilledCell>());
+        assert_eq!(cells[0].transcript_lines(80), first);
+        assert_eq!(spill.resident_lines, 3);
+    }
+
+    #[test]
+    fn spilled_cells_rewrap_to_the_width_they_are_drawn_at() {
+        let mut spill = TranscriptSpill::new(1);
+        let mut cells: Vec<Arc<dyn HistoryCell>> = Vec::new();
+        for cell in [
+            plain(vec![
+                "• one two three four five six".into(),
+                "  └ short".into(),
+            ]),
+            plain(vec!["newest".into()]),
+        ] {
+            cells.push(Arc::clone(&cell));
+            spill.on_cell_inserted(&mut cells, &cell, 80);
+        }
+        assert!(cells[0].as_any().is::<SpilledCell>());
+
+        let rendered = |width| {
+            cells[0]
+                .display_lines(width)
+                .iter()
+                .map(|line| {
+                    line.spans
+                        .iter()
+                        .map(|span| span.content.as_ref())
+                        .collect::<String>()
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            rendered(80),
+            vec!["• one two three four five six", "  └ short"]
+        );
+        assert_eq!(
+            rendered(16),
+            vec!["• one two three", "  four five six", "  └ short"]
+        );
+    }
+
+    #[test]
+    fn cells_laid_out_to_the_width_stay_in_memory() {
+        let mut spill = TranscriptSpill::new(1);
+        let mut cells: Vec<Arc<dyn HistoryCell>> = Vec::new();
+        for cell in [
+            Arc::new(crate::history_cell::FinalMessageSeparator::new(None, None))
+                as Arc<dyn HistoryCell>,
+            plain(vec!["newest".into()]),
+        ] {
+            cells.push(Arc::clone(&cell));
+            spill.on_cell_inserted(&mut cells, &cell, 80);
+        }
+
+        assert!(!cells[0].as_any().is::<SpilledCell>());
+        assert!(spill.file.is_none());
+    }
+
+    #[test]
+    fn user_messages_stay_in_memory() {
+        let mut spill = TranscriptSpill::new(1);
+        let mut cells: Vec<Arc<dyn HistoryCell>> = Vec::new();
+        for cell in [
+            Arc::new(UserHistoryCell {
+                message: "hello".to_string(),
+                text_elements: Vec::new(),
+                local_image_paths: Vec::new(),
+                remote_image_urls: Vec::new(),
+            }) as Arc<dyn HistoryCell>,
+            plain(vec!["answer".into()]),
+        ] {
+            cells.push(Arc::clone(&cell));
+            spill.on_cell_inserted(&mut cells, &cell, 80);
+        }
+
+        assert!(cells[0].as_any().is::<UserHistoryCell>());
+        assert!(spill.file.is_none());
+    }
+}
@@ -0,0 +1,273 @@
+//! `codex view`: browse a saved session in the transcript pager without starting a thread.
+//!
+//! The rollout is read once and its persisted events are turned into the history cells the chat
+//! widget shows on resume, with tool calls recovered through the same [`HistoryCellRegistry`]
+//! renderers, then displayed in the same [`TranscriptOverlay`] as `Ctrl+T`. No model
+//! client, tools, or MCP servers are started and the session file is never written to, so this
+//! is safe to use on a session another Codex process has open.
+//!
+//! [`HistoryCellRegistry`]: crate::history_registry::HistoryCellRegistry
+//! [`TranscriptOverlay`]: crate::pager_overlay::TranscriptOverlay
+
+use std::path::Path;
+use std::sync::Arc;
+
+use codex_core::RolloutRecorder;
+use codex_core::config::find_codex_home;
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::RolloutItem;
+use tokio_stream::StreamExt;
+
+use crate::app_backtrack::trim_transcript_cells_drop_last_n_user_turns;
+use crate::exec_cell::CommandOutput;
+use crate::exec_cell::new_active_exec_command;
+use crate::history_cell;
+use crate::history_cell::AgentMessageCell;
+use crate::history_cell::HistoryCell;
+use crate::history_registry::HistoryCellRegistry;
+use crate::markdown::append_markdown;
+use crate::pager_overlay::Overlay;
+use crate::session_compare::resolve_session_path;
+use crate::tui;
+use crate::tui::Tui;
+
+/// Open the session named by `reference` (a rollout path, thread id, or thread name) in the
+/// transcript pager and return once the user closes it.
+pub async fn run_transcript_view(reference: String) -> std::io::Result<()> {
+    let codex_home = find_codex_home()?;
+    let cwd = std::env::current_dir()?;
+    let path = resolve_session_path(&codex_home, &cwd, &reference)
+        .await
+        .map_err(std::io::Error::other)?;
+    let history = RolloutRecorder::get_rollout_history(&path).await?;
+    let session_cwd = history.session_cwd().unwrap_or(cwd);
+
+    let mut cells: Vec<Arc<dyn HistoryCell>> = vec![Arc::new(history_cell::new_info_event(
+        format!("Viewing {} (read-only)", path.display()),
+        Some("press q to exit".to_string()),
+    ))];
+    cells.extend(transcript_cells(&history.get_rollout_items(), &session_cwd));
+
+    show_transcript(cells)
+        .await
+        .map_err(|err| std::io::Error::other(err.to_string()))
+}
+
+async fn show_transcript(cells: Vec<Arc<dyn HistoryCell>>) -> color_eyre::Result<()> {
+    let terminal = tui::init()?;
+    let mut tui = Tui::new(terminal);
+    let _ = tui.enter_alt_screen();
+    let mut overlay = Overlay::new_transcript(cells);
+    tui.frame_requester().schedule_frame();
+
+    let events = tui.event_stream();
+    tokio::pin!(events);
+    let result = async {
+        while let Some(event) = events.next().await {
+            overlay.handle_event(&mut tui, event)?;
+            if overlay.is_done() {
+                break;
+            }
+        }
+        Ok(())
+    }
+    .await;
+
+    let _ = tui.leave_alt_screen();
+    tui::restore()?;
+    result
+}
+
+/// Build the history cells for the events and tool calls persisted in a rollout.
+fn transcript_cells(items: &[RolloutItem], cwd: &Path) -> Vec<Arc<dyn HistoryCell>> {
+    let mut registry = HistoryCellRegistry::default();
+    let mut cells: Vec<Arc<dyn HistoryCell>> = Vec::new();
+    for item in items {
+        match item {
+            RolloutItem::EventMsg(event) => {
+                registry.observe_event(event);
+                push_event_cells(&mut cells, event.clone(), cwd);
+            }
+            RolloutItem::ResponseItem(item) => {
+                for event in registry
+                    .on_response_item(item.clone(), cwd)
+                    .unwrap_or_default()
+                {
+                    push_event_cells(&mut cells, event, cwd);
+                }
+            }
+            _ => {}
+        }
+    }
+    cells
+}
+
+fn push_event_cells(cells: &mut Vec<Arc<dyn HistoryCell>>, event: EventMsg, cwd: &Path) {
+    match event {
+        EventMsg::UserMessage(ev) => {
+            let remote_image_urls = ev.images.unwrap_or_default();
+            if ev.message.trim().is_empty()
+                && ev.text_elements.is_empty()
+                && remote_image_urls.is_empty()
+            {
+                return;
+            }
+            cells.push(Arc::new(history_cell::new_user_prompt(
+                ev.message,
+                ev.text_elements,
+                ev.local_images,
+                remote_image_urls,
+            )));
+        }
+        EventMsg::AgentMessage(ev) => {
+            let mut lines = Vec::new();
+            append_markdown(&ev.message, None, &mut lines);
+            cells.push(Arc::new(AgentMessageCell::new(lines, true)));
+        }
+        EventMsg::AgentReasoning(ev) => {
+            cells.push(Arc::from(history_cell::new_reasoning_summary_block(
+                ev.text,
+            )));
+        }
+        EventMsg::ItemCompleted(ev) => {
+            if let codex_protocol::items::TurnItem::Plan(plan) = ev.item {
+                cells.push(Arc::new(history_cell::new_proposed_plan(plan.text)));
+            }
+        }
+        EventMsg::ExecCommandEnd(ev) => {
+            let mut cell = new_active_exec_command(
+                ev.call_id.clone(),
+                ev.command,
+                ev.parsed_cmd,
+                ev.source,
+                ev.interaction_input,
+                false,
+            );
+            cell.complete_call(
+                &ev.call_id,
+                CommandOutput {
+                    exit_code: ev.exit_code,
+                    aggregated_output: ev.aggregated_output,
+                    formatted_output: ev.formatted_output,
+                },
+                ev.duration,
+            );
+            cells.push(Arc::new(cell));
+        }
+        EventMsg::PatchApplyBegin(ev) => {
+            cells.push(Arc::new(history_cell::new_patch_event(ev.changes, cwd)));
+        }
+        // As in a live session, the diff shown at begin stands unless the patch failed.
+        EventMsg::PatchApplyEnd(ev) => {
+            if !ev.success {
+                cells.push(Arc::new(history_cell::new_patch_apply_failure(ev.stderr)));
+            }
+        }
+        EventMsg::PatchChecks(ev) => {
+            cells.push(Arc::new(history_cell::new_patch_checks(ev.results)));
+        }
+        EventMsg::DryRunAction(ev) => {
+            cells.push(Arc::new(history_cell::new_dry_run_action(ev.action, cwd)));
+        }
+        EventMsg::WebSearchEnd(ev) => {
+            cells.push(Arc::new(history_cell::new_web_search_call(
+                ev.call_id, ev.query, ev.action,
+            )));
+        }
+        EventMsg::McpToolCallEnd(ev) => {
+            let mut cell = history_cell::new_active_mcp_tool_call(ev.call_id, ev.invocation, false);
+            let image = cell.complete(ev.duration, ev.result);
+            cells.push(Arc::new(cell));
+            if let Some(image) = image {
+                cells.push(Arc::from(image));
+            }
+        }
+        EventMsg::ViewImageToolCall(ev) => {
+            cells.push(Arc::new(history_cell::new_view_image_tool_call(
+                ev.path, cwd,
+            )));
+        }
+        EventMsg::ContextCompacted(_) => {
+            cells.push(Arc::new(history_cell::new_info_event(
+                "Context compacted".to_string(),
+                None,
+            )));
+        }
+        EventMsg::TurnAborted(ev) => {
+            cells.push(Arc::new(history_cell::new_turn_cancelled(&ev.reason)));
+        }
+        EventMsg::Error(ev) => {
+            cells.push(Arc::new(history_cell::new_error_event(ev.message)));
+        }
+        EventMsg::PlanUpdate(update) => {
+            cells.push(Arc::new(history_cell::new_plan_update(update)));
+        }
+        EventMsg::TodoListUpdate(ev) => {
+            cells.push(Arc::new(history_cell::new_todo_list(ev.items)));
+        }
+        EventMsg::ThreadRolledBack(ev) => {
+            trim_transcript_cells_drop_last_n_user_turns(cells, ev.num_turns);
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::protocol::AgentMessageEvent;
+    use codex_protocol::protocol::ThreadRolledBackEvent;
+    use codex_protocol::protocol::UserMessageEvent;
+    use pretty_assertions::assert_eq;
+
+    fn user(message: &str) -> RolloutItem {
+        RolloutItem::EventMsg(EventMsg::UserMessage(UserMessageEvent {
+            message: message.to_string(),
+            images: None,
+            local_images: Vec::new(),
+            text_elements: Vec::new(),
+        }))
+    }
+
+    fn agent(message: &str) -> RolloutItem {
+        RolloutItem::EventMsg(EventMsg::AgentMessage(AgentMessageEvent {
+            message: message.to_string(),
+            phase: None,
+        }))
+    }
+
+    fn text(cells: &[Arc<dyn HistoryCell>]) -> Vec<String> {
+        cells
+            .iter()
+            .map(|cell| {
+                cell.display_lines(80)
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            })
+            .collect()
+    }
+
+    #[test]
+    fn rolled_back_turns_are_left_out() {
+        let items = vec![
+            user("first question"),
+            agent("first answer"),
+            user("abandoned question"),
+            agent("abandoned answer"),
+            RolloutItem::EventMsg(EventMsg::ThreadRolledBack(ThreadRolledBackEvent {
+                num_turns: 1,
+            })),
+            user("second question"),
+        ];
+
+        let cells = transcript_cells(&items, Path::new("/repo"));
+
+        let rendered = text(&cells);
+        assert_eq!(rendered.len(), 3);
+        assert!(rendered[0].contains("first question"));
+        assert!(rendered[1].contains("first answer"));
+        assert!(rendered[2].contains("second question"));
+    }
+}
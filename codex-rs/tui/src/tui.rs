@@ -24,7 +24,6 @@ use crossterm::event::PopKeyboardEnhancementFlags;
 use crossterm::event::PushKeyboardEnhancementFlags;
 use crossterm::terminal::EnterAlternateScreen;
 use crossterm::terminal::LeaveAlternateScreen;
-use crossterm::terminal::supports_keyboard_enhancement;
 use ratatui::backend::Backend;
 use ratatui::backend::CrosstermBackend;
 use ratatui::crossterm::execute;
@@ -223,6 +222,16 @@ pub fn init() -> Result<Terminal> {
     Ok(tui)
 }
 
+/// Run `operations` inside a DEC synchronized update (mode 2026) when the terminal supports it,
+/// so the terminal presents everything they write at once rather than as it arrives.
+fn synchronized<T>(operations: impl FnOnce() -> T) -> Result<T> {
+    if crate::terminal_capabilities::get().synchronized_output {
+        stdout().sync_update(|_| operations())
+    } else {
+        Ok(operations())
+    }
+}
+
 fn set_panic_hook() {
     let hook = panic::take_hook();
     panic::set_hook(Box::new(move |panic_info| {
@@ -262,9 +271,9 @@ impl Tui {
         let (draw_tx, _) = broadcast::channel(1);
         let frame_requester = FrameRequester::new(draw_tx.clone());
 
-        // Detect keyboard enhancement support before any EventStream is created so the
-        // crossterm poller can acquire its lock without contention.
-        let enhanced_keys_supported = supports_keyboard_enhancement().unwrap_or(false);
+        // Probe the terminal before any EventStream is created: the probe reads the replies
+        // from stdin itself, and crossterm's poller can then acquire its lock without contention.
+        let enhanced_keys_supported = crate::terminal_capabilities::probe().keyboard_enhancement;
         // Cache this to avoid contention with the event reader.
         supports_color::on_cached(supports_color::Stream::Stdout);
         let _ = crate::terminal_palette::default_colors();
@@ -465,7 +474,9 @@ impl Tui {
         // the synchronized update, to avoid racing with the event reader.
         let mut pending_viewport_area = self.pending_viewport_area()?;
 
-        stdout().sync_update(|_| {
+        // History insertion scrolls the screen and rewrites the viewport; keeping it in the same
+        // update as the frame means the terminal never shows the intermediate state.
+        synchronized(|| {
             #[cfg(unix)]
             if let Some(prepared) = prepared_resume.take() {
                 prepared.apply(&mut self.terminal)?;
@@ -9,3 +9,12 @@
 /// - User history lines account for this many columns (e.g., "▌ ") when wrapping.
 pub(crate) const LIVE_PREFIX_COLS: u16 = 2;
 pub(crate) const FOOTER_INDENT_COLS: usize = LIVE_PREFIX_COLS as usize;
+
+/// Terminals narrower than this many columns get the compact layout: the footer shows icons
+/// instead of labels, the composer's shortcut hints are hidden, and command output uses a
+/// narrower gutter.
+pub(crate) const NARROW_LAYOUT_COLS: u16 = 60;
+
+pub(crate) fn is_narrow_layout(width: u16) -> bool {
+    width < NARROW_LAYOUT_COLS
+}
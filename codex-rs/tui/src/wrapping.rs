@@ -14,8 +14,13 @@
 //!   inspects the line for URL-like tokens; if any are found, the
 //!   wrapping switches to `AsciiSpace` word separation and a custom
 //!   `WordSplitter` that refuses to split URL tokens. Non-URL tokens
-//!   on the same line still break at every character boundary (the
-//!   custom splitter returns all char indices for non-URL words).
+//!   on the same line still break at every grapheme boundary (the
+//!   custom splitter returns all grapheme indices for non-URL words).
+//!
+//! `textwrap` breaks over-long words between `char`s, which can cut an
+//! emoji ZWJ sequence, a flag, or a letter and its combining mark in two.
+//! The range helpers move such breaks to a grapheme boundary before
+//! returning.
 //!
 //! Callers that *might* encounter URLs should use the `adaptive_*`
 //! functions. Callers that definitely will not (code blocks, pure
@@ -31,6 +36,8 @@ use ratatui::text::Span;
 use std::borrow::Cow;
 use std::ops::Range;
 use textwrap::Options;
+use unicode_segmentation::GraphemeCursor;
+use unicode_segmentation::UnicodeSegmentation;
 
 use crate::render::line_utils::push_owned_lines;
 
@@ -66,6 +73,7 @@ where
             }
         }
     }
+    snap_line_starts_to_graphemes(text, &mut lines);
     lines
 }
 
@@ -99,9 +107,53 @@ where
             }
         }
     }
+    snap_line_starts_to_graphemes(text, &mut lines);
     lines
 }
 
+/// Move line breaks that fall inside a grapheme cluster so the whole cluster
+/// starts the next line, or stays on the previous one when it is the only
+/// thing there. The previous line's end moves with the break; `wrap_ranges`
+/// lines end one sentinel byte past the next line's start, `wrap_ranges_trim`
+/// lines end at it.
+fn snap_line_starts_to_graphemes(text: &str, lines: &mut Vec<Range<usize>>) {
+    let mut i = 1;
+    while i < lines.len() {
+        let start = lines[i].start;
+        let mut cursor = GraphemeCursor::new(start, text.len(), true);
+        if cursor.is_boundary(text, 0).unwrap_or(true) {
+            i += 1;
+            continue;
+        }
+        let before = cursor.prev_boundary(text, 0).ok().flatten().unwrap_or(0);
+        let new_start = if before > lines[i - 1].start {
+            before
+        } else {
+            let mut cursor = GraphemeCursor::new(start, text.len(), true);
+            cursor
+                .next_boundary(text, 0)
+                .ok()
+                .flatten()
+                .unwrap_or(text.len())
+        };
+        // `None` when whitespace separates the lines.
+        let sentinel = lines[i - 1].end.checked_sub(start);
+        if let Some(sentinel) = sentinel
+            && lines[i].end - sentinel <= new_start
+        {
+            // The line held nothing but the rest of the cluster.
+            lines[i - 1].end = lines[i].end;
+            lines.remove(i);
+            continue;
+        }
+        if let Some(sentinel) = sentinel {
+            lines[i - 1].end = new_start + sentinel;
+        }
+        lines[i].start = new_start;
+        i += 1;
+    }
+}
+
 /// Maps an owned (materialized) wrapped line back to a byte range in `text`.
 ///
 /// `textwrap` returns `Cow::Owned` when it inserts a hyphenation penalty
@@ -469,14 +521,17 @@ pub(crate) fn url_preserving_wrap_options<'a>(opts: RtOptions<'a>) -> RtOptions<
 
 /// Custom `textwrap::WordSplitter` callback. Returns empty (no split
 /// points) for URL-like tokens so they are kept intact; returns every
-/// char-boundary index for everything else so non-URL words can still
-/// break at any position.
+/// grapheme-boundary index for everything else so non-URL words can still
+/// break at any position without splitting a cluster.
 fn split_non_url_word(word: &str) -> Vec<usize> {
     if is_url_like_token(word) {
         return Vec::new();
     }
 
-    word.char_indices().skip(1).map(|(idx, _)| idx).collect()
+    word.grapheme_indices(true)
+        .skip(1)
+        .map(|(idx, _)| idx)
+        .collect()
 }
 
 /// Wraps a single ratatui `Line`, automatically switching to
@@ -1404,4 +1459,27 @@ them."#
         assert_eq!(rebuilt, text);
         assert!(ranges.len() > 1, "expected wrapped ranges, got: {ranges:?}");
     }
+
+    #[test]
+    fn wrap_ranges_do_not_split_grapheme_clusters() {
+        // `textwrap` breaks the family after its first ZWJ; the whole cluster stays on one line.
+        let family = "👨\u{200D}👩\u{200D}👧";
+        let text = format!("ab{family}cd");
+
+        let trimmed = wrap_ranges_trim(&text, Options::new(3));
+        let lines: Vec<&str> = trimmed.iter().map(|range| &text[range.clone()]).collect();
+        assert_eq!(lines, vec!["ab", family, "c", "d"]);
+
+        let with_sentinel = wrap_ranges(&text, Options::new(3));
+        let lines: Vec<&str> = with_sentinel
+            .iter()
+            .map(|range| &text[range.start..range.end - 1])
+            .collect();
+        assert_eq!(lines, vec!["ab", family, "c", "d"]);
+    }
+
+    #[test]
+    fn url_preserving_split_keeps_combining_marks() {
+        assert_eq!(split_non_url_word("e\u{301}e\u{301}"), vec![3]);
+    }
 }
@@ -20,6 +20,12 @@ use image::imageops::FilterType;
 pub const MAX_WIDTH: u32 = 2048;
 /// Maximum height used when resizing images before uploading.
 pub const MAX_HEIGHT: u32 = 768;
+/// JPEG quality used when re-encoding.
+const JPEG_QUALITY: u8 = 85;
+/// How many times an image is shrunk by a quarter while its encoding is over `max_bytes`.
+const MAX_SHRINK_STEPS: usize = 6;
+/// Images are not shrunk below this width or height to meet `max_bytes`.
+const MIN_SHRINK_SIDE: u32 = 256;
 
 pub mod error;
 
@@ -29,9 +35,19 @@ pub struct EncodedImage {
     pub mime: String,
     pub width: u32,
     pub height: u32,
+    pub original_width: u32,
+    pub original_height: u32,
+    pub original_bytes: usize,
 }
 
 impl EncodedImage {
+    /// Whether the image is sent resized or re-encoded rather than as the source file.
+    pub fn was_downscaled(&self) -> bool {
+        self.width != self.original_width
+            || self.height != self.original_height
+            || self.bytes.len() != self.original_bytes
+    }
+
     pub fn into_data_url(self) -> String {
         let encoded = BASE64_STANDARD.encode(&self.bytes);
         format!("data:{};base64,{encoded}", self.mime)
@@ -40,10 +56,51 @@ impl EncodedImage {
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum PromptImageMode {
+    /// Downscale with the default [`ImageLimits`].
     ResizeToFit,
+    /// Downscale with the given limits.
+    Downscale(ImageLimits),
     Original,
 }
 
+/// Thresholds above which an image is resized or re-encoded before upload. The aspect ratio is
+/// always preserved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ImageLimits {
+    pub max_width: u32,
+    pub max_height: u32,
+    /// Images whose encoding is larger than this are re-encoded and, if still too large, shrunk.
+    pub max_bytes: Option<usize>,
+    /// Format for re-encoded images; `None` keeps the source format where possible.
+    pub format: Option<DownscaleFormat>,
+}
+
+impl Default for ImageLimits {
+    fn default() -> Self {
+        Self {
+            max_width: MAX_WIDTH,
+            max_height: MAX_HEIGHT,
+            max_bytes: None,
+            format: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DownscaleFormat {
+    Jpeg,
+    WebP,
+}
+
+impl DownscaleFormat {
+    fn image_format(self) -> ImageFormat {
+        match self {
+            DownscaleFormat::Jpeg => ImageFormat::Jpeg,
+            DownscaleFormat::WebP => ImageFormat::WebP,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 struct ImageCacheKey {
     digest: [u8; 20],
@@ -87,9 +144,57 @@ pub fn load_for_prompt(
         })?;
 
         let (width, height) = dynamic.dimensions();
-
-        let encoded =
-            if mode == PromptImageMode::Original || (width <= MAX_WIDTH && height <= MAX_HEIGHT) {
+        let original_bytes = file_bytes.len();
+        let limits = match mode {
+            PromptImageMode::Original => None,
+            PromptImageMode::ResizeToFit => Some(ImageLimits::default()),
+            PromptImageMode::Downscale(limits) => Some(limits),
+        };
+        let over_pixels =
+            limits.is_some_and(|limits| width > limits.max_width || height > limits.max_height);
+        let over_bytes = limits
+            .and_then(|limits| limits.max_bytes)
+            .is_some_and(|max_bytes| original_bytes > max_bytes);
+
+        let encoded = match limits {
+            Some(limits) if over_pixels || over_bytes => {
+                let mut image = if over_pixels {
+                    dynamic.resize(limits.max_width, limits.max_height, FilterType::Triangle)
+                } else {
+                    dynamic
+                };
+                let target_format = limits
+                    .format
+                    .map(DownscaleFormat::image_format)
+                    .or(format.filter(|format| can_preserve_source_bytes(*format)))
+                    .unwrap_or(ImageFormat::Png);
+                let (mut bytes, mut output_format) = encode_image(&image, target_format)?;
+                if let Some(max_bytes) = limits.max_bytes {
+                    for _ in 0..MAX_SHRINK_STEPS {
+                        if bytes.len() <= max_bytes
+                            || image.width().min(image.height()) <= MIN_SHRINK_SIDE
+                        {
+                            break;
+                        }
+                        image = image.resize(
+                            image.width() * 3 / 4,
+                            image.height() * 3 / 4,
+                            FilterType::Triangle,
+                        );
+                        (bytes, output_format) = encode_image(&image, target_format)?;
+                    }
+                }
+                EncodedImage {
+                    bytes,
+                    mime: format_to_mime(output_format),
+                    width: image.width(),
+                    height: image.height(),
+                    original_width: width,
+                    original_height: height,
+                    original_bytes,
+                }
+            }
+            _ => {
                 if let Some(format) = format.filter(|format| can_preserve_source_bytes(*format)) {
                     let mime = format_to_mime(format);
                     EncodedImage {
@@ -97,6 +202,9 @@ pub fn load_for_prompt(
                         mime,
                         width,
                         height,
+                        original_width: width,
+                        original_height: height,
+                        original_bytes,
                     }
                 } else {
                     let (bytes, output_format) = encode_image(&dynamic, ImageFormat::Png)?;
@@ -106,27 +214,28 @@ pub fn load_for_prompt(
                         mime,
                         width,
                         height,
+                        original_width: width,
+                        original_height: height,
+                        original_bytes,
                     }
                 }
-            } else {
-                let resized = dynamic.resize(MAX_WIDTH, MAX_HEIGHT, FilterType::Triangle);
-                let target_format = format
-                    .filter(|format| can_preserve_source_bytes(*format))
-                    .unwrap_or(ImageFormat::Png);
-                let (bytes, output_format) = encode_image(&resized, target_format)?;
-                let mime = format_to_mime(output_format);
-                EncodedImage {
-                    bytes,
-                    mime,
-                    width: resized.width(),
-                    height: resized.height(),
-                }
-            };
+            }
+        };
 
         Ok(encoded)
     })
 }
 
+/// The result of an earlier [`load_for_prompt`] of `path` in `mode`, if it is still cached and
+/// the file has not changed since. Nothing is decoded or encoded.
+pub fn cached_for_prompt(path: &Path, mode: PromptImageMode) -> Option<EncodedImage> {
+    let file_bytes = read_file_bytes(path, path).ok()?;
+    IMAGE_CACHE.get(&ImageCacheKey {
+        digest: sha1_digest(&file_bytes),
+        mode,
+    })
+}
+
 fn can_preserve_source_bytes(format: ImageFormat) -> bool {
     // Public API docs explicitly call out non-animated GIF support only.
     // Preserve byte-for-byte only for formats we can safely pass through.
@@ -183,9 +292,11 @@ fn encode_image(
                 })?;
         }
         ImageFormat::Jpeg => {
-            let mut encoder = JpegEncoder::new_with_quality(&mut buffer, 85);
+            // JPEG has no alpha channel.
+            let rgb = DynamicImage::ImageRgb8(image.to_rgb8());
+            let mut encoder = JpegEncoder::new_with_quality(&mut buffer, JPEG_QUALITY);
             encoder
-                .encode_image(image)
+                .encode_image(&rgb)
                 .map_err(|source| ImageProcessingError::Encode {
                     format: target_format,
                     source,
@@ -279,6 +390,40 @@ mod tests {
         }
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn converts_and_shrinks_images_over_the_configured_limits() {
+        let temp_file = NamedTempFile::new().expect("temp file");
+        let image = ImageBuffer::from_fn(1600, 1200, |x, y| {
+            Rgba([(x % 251) as u8, (y % 241) as u8, ((x * y) % 239) as u8, 255])
+        });
+        image
+            .save_with_format(temp_file.path(), ImageFormat::Png)
+            .expect("write png to temp file");
+        let mode = PromptImageMode::Downscale(ImageLimits {
+            max_width: 1024,
+            max_height: 1024,
+            max_bytes: Some(64 * 1024),
+            format: Some(DownscaleFormat::Jpeg),
+        });
+
+        let processed = load_for_prompt(temp_file.path(), mode).expect("process image");
+
+        assert_eq!(processed.mime, "image/jpeg");
+        assert_eq!(
+            (processed.original_width, processed.original_height),
+            (1600, 1200)
+        );
+        assert!(processed.width <= 1024 && processed.height <= 1024);
+        // The aspect ratio is kept while shrinking.
+        assert_eq!(processed.width * 3, processed.height * 4);
+        assert!(processed.bytes.len() <= 64 * 1024 || processed.height <= MIN_SHRINK_SIDE);
+        assert!(processed.was_downscaled());
+
+        let cached = cached_for_prompt(temp_file.path(), mode).expect("cached result");
+        assert_eq!(cached.bytes, processed.bytes);
+        assert!(cached_for_prompt(temp_file.path(), PromptImageMode::Original).is_none());
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn preserves_large_image_in_original_mode() {
         let temp_file = NamedTempFile::new().expect("temp file");
@@ -111,11 +111,70 @@ fn parse_markdown_hash_location_point(point: &str) -> Option<(&str, Option<&str>
     }
 }
 
+/// Format a byte count with binary units, e.g. `512 B`, `300 KiB`, `4 MiB`, or `3.2 MiB`.
+pub fn format_bytes(bytes: u64) -> String {
+    const KIB: u64 = 1024;
+    const MIB: u64 = 1024 * KIB;
+
+    if bytes >= MIB {
+        let mib = format!("{:.1}", bytes as f64 / MIB as f64);
+        return format!("{} MiB", mib.strip_suffix(".0").unwrap_or(&mib));
+    }
+    if bytes >= KIB {
+        return format!("{} KiB", bytes / KIB);
+    }
+    format!("{bytes} B")
+}
+
+/// Remove ANSI escape sequences (colors, cursor movement, terminal titles) from `text`.
+///
+/// Handles CSI sequences, OSC sequences terminated by BEL or `ESC \`, and two-byte escapes
+/// such as `ESC (B`. Other control characters, including `\r` and tabs, are kept.
+pub fn strip_ansi_escapes(text: &str) -> String {
+    if !text.contains('\u{1b}') {
+        return text.to_string();
+    }
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\u{1b}' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            // CSI: parameters end with a byte in 0x40..=0x7e.
+            Some('[') => {
+                for c in chars.by_ref() {
+                    if ('\u{40}'..='\u{7e}').contains(&c) {
+                        break;
+                    }
+                }
+            }
+            // OSC: terminated by BEL or ESC \.
+            Some(']') => {
+                while let Some(c) = chars.next() {
+                    if c == '\u{7}' || (c == '\u{1b}' && chars.next_if_eq(&'\\').is_some()) {
+                        break;
+                    }
+                }
+            }
+            // Character set selection such as `ESC (B` keeps its final byte out of the text too.
+            Some('(' | ')') => {
+                chars.next();
+            }
+            _ => {}
+        }
+    }
+    out
+}
+
 #[cfg(test)]
 mod tests {
     use super::find_uuids;
+    use super::format_bytes;
     use super::normalize_markdown_hash_location_suffix;
     use super::sanitize_metric_tag_value;
+    use super::strip_ansi_escapes;
     use pretty_assertions::assert_eq;
 
     #[test]
@@ -173,4 +232,36 @@ mod tests {
             Some(":74:3-76:9".to_string())
         );
     }
+
+    #[test]
+    fn format_bytes_uses_binary_units() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(300 * 1024 + 100), "300 KiB");
+        assert_eq!(format_bytes(4 * 1024 * 1024), "4 MiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024 + 200 * 1024), "3.2 MiB");
+    }
+
+    #[test]
+    fn strip_ansi_escapes_removes_color_and_title_escapes() {
+        let raw =
+            "\u{1b}]0;cargo\u{7}\u{1b}[1m\u{1b}[32m   Compiling\u{1b}[0m codex v0.1.0\n\u{1b}(Bok";
+
+        assert_eq!(strip_ansi_escapes(raw), "   Compiling codex v0.1.0\nok");
+    }
+
+    #[test]
+    fn strip_ansi_escapes_handles_st_terminated_osc_and_cursor_movement() {
+        let raw =
+            "\u{1b}]8;;https://example.com\u{1b}\\link\u{1b}]8;;\u{1b}\\ \u{1b}[2K\u{1b}[1Gdone";
+
+        assert_eq!(strip_ansi_escapes(raw), "link done");
+    }
+
+    #[test]
+    fn strip_ansi_escapes_keeps_tabs_and_carriage_returns() {
+        assert_eq!(
+            strip_ansi_escapes("a\tb\r\u{1b}[31mc\u{1b}[0m\r\n"),
+            "a\tb\rc\r\n"
+        );
+    }
 }